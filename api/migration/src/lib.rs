@@ -7,6 +7,53 @@ mod m20260220_000001_create_evm_tokens;
 mod m20260220_000002_create_evm_chains;
 mod m20260221_000001_create_solana_tokens;
 mod m20260222_000001_add_native_symbol_to_evm_chains;
+mod m20260223_000001_create_holding_anomalies;
+mod m20260224_000001_create_holding_transactions;
+mod m20260225_000001_add_settings_to_portfolios;
+mod m20260226_000001_create_job_runs;
+mod m20260227_000001_create_positions;
+mod m20260228_000001_create_audit_log;
+mod m20260301_000001_add_settings_to_accounts;
+mod m20260302_000001_create_trades;
+mod m20260303_000001_add_first_activity_at_to_accounts;
+mod m20260304_000001_create_transfers;
+mod m20260305_000001_create_construction_runs;
+mod m20260306_000001_create_cosmos_chains;
+mod m20260307_000001_create_staking_rewards;
+mod m20260308_000001_create_spam_tokens;
+mod m20260309_000001_add_nft_api_to_evm_chains;
+mod m20260309_000002_create_nft_holdings;
+mod m20260310_000001_add_wallet_names_to_accounts;
+mod m20260310_000002_create_account_addresses;
+mod m20260311_000001_create_discovered_tokens;
+mod m20260312_000001_add_explorer_to_evm_chains;
+mod m20260313_000001_add_multisig_to_accounts;
+mod m20260313_000002_create_curve_pools;
+mod m20260313_000003_create_derivative_assets;
+mod m20260313_000004_create_pendle_assets;
+mod m20260313_000005_create_yield_vaults;
+mod m20260313_000006_add_debt_values_to_snapshots;
+mod m20260313_000007_create_income_events;
+mod m20260313_000008_add_coingecko_id_to_assets;
+mod m20260313_000009_add_price_resolution_to_portfolio_allocations;
+mod m20260313_000010_create_fx_rates;
+mod m20260313_000011_add_base_currency_to_users;
+mod m20260313_000012_create_asset_price_overrides;
+mod m20260313_000013_create_rejected_prices;
+mod m20260313_000014_create_asset_price_rollups;
+mod m20260313_000015_add_delisted_at_to_assets;
+mod m20260314_000001_create_portfolio_pnl;
+mod m20260314_000002_create_tax_lots;
+mod m20260314_000003_add_category_to_assets;
+mod m20260314_000004_add_breakdowns_to_portfolio_allocations;
+mod m20260314_000005_create_rebalance_plans;
+mod m20260314_000006_create_fees;
+mod m20260314_000007_create_portfolio_performance;
+mod m20260314_000008_create_account_snapshots;
+mod m20260314_000009_allow_intraday_snapshots;
+mod m20260314_000010_create_notification_deliveries;
+mod m20260314_000011_create_sync_jobs;
+mod m20260314_000012_create_sync_runs;
 
 pub struct Migrator;
 
@@ -21,6 +68,53 @@ impl MigratorTrait for Migrator {
             Box::new(m20260220_000002_create_evm_chains::Migration),
             Box::new(m20260221_000001_create_solana_tokens::Migration),
             Box::new(m20260222_000001_add_native_symbol_to_evm_chains::Migration),
+            Box::new(m20260223_000001_create_holding_anomalies::Migration),
+            Box::new(m20260224_000001_create_holding_transactions::Migration),
+            Box::new(m20260225_000001_add_settings_to_portfolios::Migration),
+            Box::new(m20260226_000001_create_job_runs::Migration),
+            Box::new(m20260227_000001_create_positions::Migration),
+            Box::new(m20260228_000001_create_audit_log::Migration),
+            Box::new(m20260301_000001_add_settings_to_accounts::Migration),
+            Box::new(m20260302_000001_create_trades::Migration),
+            Box::new(m20260303_000001_add_first_activity_at_to_accounts::Migration),
+            Box::new(m20260304_000001_create_transfers::Migration),
+            Box::new(m20260305_000001_create_construction_runs::Migration),
+            Box::new(m20260306_000001_create_cosmos_chains::Migration),
+            Box::new(m20260307_000001_create_staking_rewards::Migration),
+            Box::new(m20260308_000001_create_spam_tokens::Migration),
+            Box::new(m20260309_000001_add_nft_api_to_evm_chains::Migration),
+            Box::new(m20260309_000002_create_nft_holdings::Migration),
+            Box::new(m20260310_000001_add_wallet_names_to_accounts::Migration),
+            Box::new(m20260310_000002_create_account_addresses::Migration),
+            Box::new(m20260311_000001_create_discovered_tokens::Migration),
+            Box::new(m20260312_000001_add_explorer_to_evm_chains::Migration),
+            Box::new(m20260313_000001_add_multisig_to_accounts::Migration),
+            Box::new(m20260313_000002_create_curve_pools::Migration),
+            Box::new(m20260313_000003_create_derivative_assets::Migration),
+            Box::new(m20260313_000004_create_pendle_assets::Migration),
+            Box::new(m20260313_000005_create_yield_vaults::Migration),
+            Box::new(m20260313_000006_add_debt_values_to_snapshots::Migration),
+            Box::new(m20260313_000007_create_income_events::Migration),
+            Box::new(m20260313_000008_add_coingecko_id_to_assets::Migration),
+            Box::new(m20260313_000009_add_price_resolution_to_portfolio_allocations::Migration),
+            Box::new(m20260313_000010_create_fx_rates::Migration),
+            Box::new(m20260313_000011_add_base_currency_to_users::Migration),
+            Box::new(m20260313_000012_create_asset_price_overrides::Migration),
+            Box::new(m20260313_000013_create_rejected_prices::Migration),
+            Box::new(m20260313_000014_create_asset_price_rollups::Migration),
+            Box::new(m20260313_000015_add_delisted_at_to_assets::Migration),
+            Box::new(m20260314_000001_create_portfolio_pnl::Migration),
+            Box::new(m20260314_000002_create_tax_lots::Migration),
+            Box::new(m20260314_000003_add_category_to_assets::Migration),
+            Box::new(m20260314_000004_add_breakdowns_to_portfolio_allocations::Migration),
+            Box::new(m20260314_000005_create_rebalance_plans::Migration),
+            Box::new(m20260314_000006_create_fees::Migration),
+            Box::new(m20260314_000007_create_portfolio_performance::Migration),
+            Box::new(m20260314_000008_create_account_snapshots::Migration),
+            Box::new(m20260314_000009_allow_intraday_snapshots::Migration),
+            Box::new(m20260314_000010_create_notification_deliveries::Migration),
+            Box::new(m20260314_000011_create_sync_jobs::Migration),
+            Box::new(m20260314_000012_create_sync_runs::Migration),
         ]
     }
 }
@@ -0,0 +1,165 @@
+use sea_orm_migration::{prelude::*, schema::*};
+
+/// Creates the `holding_anomalies` and `notifications` tables.
+///
+/// `holding_anomalies` records suspicious balance changes detected during account
+/// sync (large unexplained drops, emptied wallets). An unacknowledged anomaly on any
+/// account in a portfolio holds automatic (EOD) snapshot creation for that portfolio.
+///
+/// `notifications` stores user-facing messages; anomalies raise a `high` priority
+/// notification alongside the anomaly row.
+#[derive(DeriveMigrationName)]
+pub struct Migration;
+
+#[async_trait::async_trait]
+impl MigrationTrait for Migration {
+    async fn up(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        // ── 1. holding_anomalies ──────────────────────────────────────────────
+        manager
+            .create_table(
+                Table::create()
+                    .table(HoldingAnomalies::Table)
+                    .if_not_exists()
+                    .col(uuid(HoldingAnomalies::Id).primary_key().extra("DEFAULT gen_random_uuid()"))
+                    .col(uuid(HoldingAnomalies::UserId).not_null())
+                    .col(uuid(HoldingAnomalies::AccountId).not_null())
+                    .col(string(HoldingAnomalies::AnomalyType).not_null())
+                    .col(string_null(HoldingAnomalies::Asset))
+                    .col(decimal_null(HoldingAnomalies::PreviousQuantity))
+                    .col(decimal_null(HoldingAnomalies::CurrentQuantity))
+                    .col(json_null(HoldingAnomalies::Details))
+                    .col(timestamp_with_time_zone(HoldingAnomalies::DetectedAt).default(Expr::current_timestamp()).not_null())
+                    .col(timestamp_with_time_zone_null(HoldingAnomalies::AcknowledgedAt))
+                    .foreign_key(
+                        ForeignKey::create()
+                            .name("fk_holding_anomalies_user_id")
+                            .from(HoldingAnomalies::Table, HoldingAnomalies::UserId)
+                            .to(Users::Table, Users::Id)
+                            .on_delete(ForeignKeyAction::Cascade)
+                            .on_update(ForeignKeyAction::Cascade),
+                    )
+                    .foreign_key(
+                        ForeignKey::create()
+                            .name("fk_holding_anomalies_account_id")
+                            .from(HoldingAnomalies::Table, HoldingAnomalies::AccountId)
+                            .to(Accounts::Table, Accounts::Id)
+                            .on_delete(ForeignKeyAction::Cascade)
+                            .on_update(ForeignKeyAction::Cascade),
+                    )
+                    .to_owned(),
+            )
+            .await?;
+
+        manager
+            .create_index(
+                Index::create()
+                    .name("idx_holding_anomalies_user_id")
+                    .table(HoldingAnomalies::Table)
+                    .col(HoldingAnomalies::UserId)
+                    .to_owned(),
+            )
+            .await?;
+
+        manager
+            .create_index(
+                Index::create()
+                    .name("idx_holding_anomalies_account_acknowledged")
+                    .table(HoldingAnomalies::Table)
+                    .col(HoldingAnomalies::AccountId)
+                    .col(HoldingAnomalies::AcknowledgedAt)
+                    .to_owned(),
+            )
+            .await?;
+
+        // ── 2. notifications ──────────────────────────────────────────────────
+        manager
+            .create_table(
+                Table::create()
+                    .table(Notifications::Table)
+                    .if_not_exists()
+                    .col(uuid(Notifications::Id).primary_key().extra("DEFAULT gen_random_uuid()"))
+                    .col(uuid(Notifications::UserId).not_null())
+                    .col(string(Notifications::NotificationType).not_null())
+                    .col(string(Notifications::Priority).default("normal").not_null())
+                    .col(string(Notifications::Title).not_null())
+                    .col(text(Notifications::Message).not_null())
+                    .col(json_null(Notifications::Metadata))
+                    .col(timestamp_with_time_zone_null(Notifications::ReadAt))
+                    .col(timestamp_with_time_zone(Notifications::CreatedAt).default(Expr::current_timestamp()).not_null())
+                    .foreign_key(
+                        ForeignKey::create()
+                            .name("fk_notifications_user_id")
+                            .from(Notifications::Table, Notifications::UserId)
+                            .to(Users::Table, Users::Id)
+                            .on_delete(ForeignKeyAction::Cascade)
+                            .on_update(ForeignKeyAction::Cascade),
+                    )
+                    .to_owned(),
+            )
+            .await?;
+
+        manager
+            .create_index(
+                Index::create()
+                    .name("idx_notifications_user_id_created_at")
+                    .table(Notifications::Table)
+                    .col(Notifications::UserId)
+                    .col(Notifications::CreatedAt)
+                    .to_owned(),
+            )
+            .await?;
+
+        Ok(())
+    }
+
+    async fn down(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        manager
+            .drop_table(Table::drop().table(Notifications::Table).to_owned())
+            .await?;
+
+        manager
+            .drop_table(Table::drop().table(HoldingAnomalies::Table).to_owned())
+            .await
+    }
+}
+
+#[derive(DeriveIden)]
+enum HoldingAnomalies {
+    Table,
+    Id,
+    UserId,
+    AccountId,
+    AnomalyType,
+    Asset,
+    PreviousQuantity,
+    CurrentQuantity,
+    Details,
+    DetectedAt,
+    AcknowledgedAt,
+}
+
+#[derive(DeriveIden)]
+enum Notifications {
+    Table,
+    Id,
+    UserId,
+    NotificationType,
+    Priority,
+    Title,
+    Message,
+    Metadata,
+    ReadAt,
+    CreatedAt,
+}
+
+#[derive(DeriveIden)]
+enum Users {
+    Table,
+    Id,
+}
+
+#[derive(DeriveIden)]
+enum Accounts {
+    Table,
+    Id,
+}
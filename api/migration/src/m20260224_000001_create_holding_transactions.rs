@@ -0,0 +1,99 @@
+use sea_orm_migration::{prelude::*, schema::*};
+
+/// Creates the `holding_transactions` ledger.
+///
+/// Every sync appends one row per asset whose quantity changed. When a later sync
+/// reverts a recent change (e.g. an RPC briefly returned a wrong balance), the
+/// original row is superseded by pointing its `corrected_by` at a `correction` row
+/// instead of treating the reversal as a genuine movement.
+#[derive(DeriveMigrationName)]
+pub struct Migration;
+
+#[async_trait::async_trait]
+impl MigrationTrait for Migration {
+    async fn up(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        manager
+            .create_table(
+                Table::create()
+                    .table(HoldingTransactions::Table)
+                    .if_not_exists()
+                    .col(uuid(HoldingTransactions::Id).primary_key().extra("DEFAULT gen_random_uuid()"))
+                    .col(uuid(HoldingTransactions::AccountId).not_null())
+                    .col(string(HoldingTransactions::Asset).not_null())
+                    .col(string(HoldingTransactions::TransactionType).not_null())
+                    .col(decimal(HoldingTransactions::QuantityBefore).not_null())
+                    .col(decimal(HoldingTransactions::QuantityAfter).not_null())
+                    .col(decimal(HoldingTransactions::Delta).not_null())
+                    .col(uuid_null(HoldingTransactions::CorrectedBy))
+                    .col(timestamp_with_time_zone(HoldingTransactions::RecordedAt).default(Expr::current_timestamp()).not_null())
+                    .foreign_key(
+                        ForeignKey::create()
+                            .name("fk_holding_transactions_account_id")
+                            .from(HoldingTransactions::Table, HoldingTransactions::AccountId)
+                            .to(Accounts::Table, Accounts::Id)
+                            .on_delete(ForeignKeyAction::Cascade)
+                            .on_update(ForeignKeyAction::Cascade),
+                    )
+                    .foreign_key(
+                        ForeignKey::create()
+                            .name("fk_holding_transactions_corrected_by")
+                            .from(HoldingTransactions::Table, HoldingTransactions::CorrectedBy)
+                            .to(HoldingTransactions::Table, HoldingTransactions::Id)
+                            .on_delete(ForeignKeyAction::SetNull)
+                            .on_update(ForeignKeyAction::Cascade),
+                    )
+                    .to_owned(),
+            )
+            .await?;
+
+        manager
+            .create_index(
+                Index::create()
+                    .name("idx_holding_transactions_account_asset_recorded_at")
+                    .table(HoldingTransactions::Table)
+                    .col(HoldingTransactions::AccountId)
+                    .col(HoldingTransactions::Asset)
+                    .col(HoldingTransactions::RecordedAt)
+                    .to_owned(),
+            )
+            .await?;
+
+        manager
+            .create_index(
+                Index::create()
+                    .name("idx_holding_transactions_corrected_by")
+                    .table(HoldingTransactions::Table)
+                    .col(HoldingTransactions::CorrectedBy)
+                    .to_owned(),
+            )
+            .await?;
+
+        Ok(())
+    }
+
+    async fn down(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        manager
+            .drop_table(Table::drop().table(HoldingTransactions::Table).to_owned())
+            .await
+    }
+}
+
+#[derive(DeriveIden)]
+enum HoldingTransactions {
+    Table,
+    Id,
+    AccountId,
+    Asset,
+    TransactionType,
+    QuantityBefore,
+    QuantityAfter,
+    Delta,
+    CorrectedBy,
+    RecordedAt,
+}
+
+#[derive(DeriveIden)]
+enum Accounts {
+    Table,
+    Id,
+}
@@ -0,0 +1,39 @@
+use sea_orm_migration::{prelude::*, schema::*};
+
+/// Adds a nullable `settings` JSON column to `portfolios`.
+///
+/// Holds per-portfolio preferences parsed into `domain::PortfolioSettings`, starting
+/// with the EOD snapshot valuation method. NULL means "all defaults".
+#[derive(DeriveMigrationName)]
+pub struct Migration;
+
+#[async_trait::async_trait]
+impl MigrationTrait for Migration {
+    async fn up(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        manager
+            .alter_table(
+                Table::alter()
+                    .table(Portfolios::Table)
+                    .add_column(json_null(Portfolios::Settings))
+                    .to_owned(),
+            )
+            .await
+    }
+
+    async fn down(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        manager
+            .alter_table(
+                Table::alter()
+                    .table(Portfolios::Table)
+                    .drop_column(Portfolios::Settings)
+                    .to_owned(),
+            )
+            .await
+    }
+}
+
+#[derive(DeriveIden)]
+enum Portfolios {
+    Table,
+    Settings,
+}
@@ -0,0 +1,47 @@
+use sea_orm_migration::{prelude::*, schema::*};
+
+/// Creates the `job_runs` table.
+///
+/// Keeps one row per background job with its latest run outcome, so that the
+/// public status endpoint can report when each job last succeeded.
+#[derive(DeriveMigrationName)]
+pub struct Migration;
+
+#[async_trait::async_trait]
+impl MigrationTrait for Migration {
+    async fn up(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        manager
+            .create_table(
+                Table::create()
+                    .table(JobRuns::Table)
+                    .if_not_exists()
+                    .col(string(JobRuns::JobName).primary_key())
+                    .col(string(JobRuns::LastStatus).not_null())
+                    .col(timestamp_with_time_zone(JobRuns::LastStartedAt).not_null())
+                    .col(timestamp_with_time_zone_null(JobRuns::LastFinishedAt))
+                    .col(timestamp_with_time_zone_null(JobRuns::LastSucceededAt))
+                    .col(text_null(JobRuns::LastError))
+                    .col(timestamp_with_time_zone(JobRuns::UpdatedAt).default(Expr::current_timestamp()).not_null())
+                    .to_owned(),
+            )
+            .await
+    }
+
+    async fn down(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        manager
+            .drop_table(Table::drop().table(JobRuns::Table).to_owned())
+            .await
+    }
+}
+
+#[derive(DeriveIden)]
+enum JobRuns {
+    Table,
+    JobName,
+    LastStatus,
+    LastStartedAt,
+    LastFinishedAt,
+    LastSucceededAt,
+    LastError,
+    UpdatedAt,
+}
@@ -0,0 +1,89 @@
+use sea_orm_migration::{prelude::*, schema::*};
+
+/// Creates the `positions` table.
+///
+/// Holds the open derivatives (futures/perpetual) positions of exchange accounts as of
+/// their last sync. Each sync replaces the account's rows, so closed positions disappear.
+#[derive(DeriveMigrationName)]
+pub struct Migration;
+
+#[async_trait::async_trait]
+impl MigrationTrait for Migration {
+    async fn up(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        manager
+            .create_table(
+                Table::create()
+                    .table(Positions::Table)
+                    .if_not_exists()
+                    .col(uuid(Positions::Id).primary_key().extra("DEFAULT gen_random_uuid()"))
+                    .col(uuid(Positions::AccountId).not_null())
+                    .col(string(Positions::Instrument).not_null())
+                    .col(string(Positions::InstrumentType).not_null())
+                    .col(string(Positions::Side).not_null())
+                    .col(decimal(Positions::Size).not_null())
+                    .col(decimal_null(Positions::EntryPrice))
+                    .col(decimal_null(Positions::MarkPrice))
+                    .col(decimal_null(Positions::UnrealizedPnl))
+                    .col(decimal_null(Positions::Margin))
+                    .col(string_null(Positions::MarginCurrency))
+                    .col(string_null(Positions::MarginMode))
+                    .col(decimal_null(Positions::Leverage))
+                    .col(decimal_null(Positions::LiquidationPrice))
+                    .col(timestamp_with_time_zone(Positions::SyncedAt).default(Expr::current_timestamp()).not_null())
+                    .foreign_key(
+                        ForeignKey::create()
+                            .name("fk_positions_account_id")
+                            .from(Positions::Table, Positions::AccountId)
+                            .to(Accounts::Table, Accounts::Id)
+                            .on_delete(ForeignKeyAction::Cascade)
+                            .on_update(ForeignKeyAction::Cascade),
+                    )
+                    .to_owned(),
+            )
+            .await?;
+
+        manager
+            .create_index(
+                Index::create()
+                    .name("idx_positions_account_id")
+                    .table(Positions::Table)
+                    .col(Positions::AccountId)
+                    .to_owned(),
+            )
+            .await?;
+
+        Ok(())
+    }
+
+    async fn down(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        manager
+            .drop_table(Table::drop().table(Positions::Table).to_owned())
+            .await
+    }
+}
+
+#[derive(DeriveIden)]
+enum Positions {
+    Table,
+    Id,
+    AccountId,
+    Instrument,
+    InstrumentType,
+    Side,
+    Size,
+    EntryPrice,
+    MarkPrice,
+    UnrealizedPnl,
+    Margin,
+    MarginCurrency,
+    MarginMode,
+    Leverage,
+    LiquidationPrice,
+    SyncedAt,
+}
+
+#[derive(DeriveIden)]
+enum Accounts {
+    Table,
+    Id,
+}
@@ -0,0 +1,70 @@
+use sea_orm_migration::{prelude::*, schema::*};
+
+/// Creates the `audit_log` table.
+///
+/// Records administrative operations (e.g. reassigning an account to another user)
+/// with the acting Keycloak subject, so support actions can be traced afterwards.
+#[derive(DeriveMigrationName)]
+pub struct Migration;
+
+#[async_trait::async_trait]
+impl MigrationTrait for Migration {
+    async fn up(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        manager
+            .create_table(
+                Table::create()
+                    .table(AuditLog::Table)
+                    .if_not_exists()
+                    .col(uuid(AuditLog::Id).primary_key().extra("DEFAULT gen_random_uuid()"))
+                    .col(string(AuditLog::ActorKeycloakUserId).not_null())
+                    .col(string(AuditLog::Action).not_null())
+                    .col(string(AuditLog::EntityType).not_null())
+                    .col(uuid_null(AuditLog::EntityId))
+                    .col(json_null(AuditLog::Details))
+                    .col(timestamp_with_time_zone(AuditLog::CreatedAt).default(Expr::current_timestamp()).not_null())
+                    .to_owned(),
+            )
+            .await?;
+
+        manager
+            .create_index(
+                Index::create()
+                    .name("idx_audit_log_entity")
+                    .table(AuditLog::Table)
+                    .col(AuditLog::EntityType)
+                    .col(AuditLog::EntityId)
+                    .to_owned(),
+            )
+            .await?;
+
+        manager
+            .create_index(
+                Index::create()
+                    .name("idx_audit_log_created_at")
+                    .table(AuditLog::Table)
+                    .col(AuditLog::CreatedAt)
+                    .to_owned(),
+            )
+            .await?;
+
+        Ok(())
+    }
+
+    async fn down(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        manager
+            .drop_table(Table::drop().table(AuditLog::Table).to_owned())
+            .await
+    }
+}
+
+#[derive(DeriveIden)]
+enum AuditLog {
+    Table,
+    Id,
+    ActorKeycloakUserId,
+    Action,
+    EntityType,
+    EntityId,
+    Details,
+    CreatedAt,
+}
@@ -0,0 +1,39 @@
+use sea_orm_migration::{prelude::*, schema::*};
+
+/// Adds a nullable `settings` JSON column to `accounts`.
+///
+/// Holds per-account sync preferences parsed into `domain::AccountSettings`, starting
+/// with whether exchange sub-accounts are included. NULL means "all defaults".
+#[derive(DeriveMigrationName)]
+pub struct Migration;
+
+#[async_trait::async_trait]
+impl MigrationTrait for Migration {
+    async fn up(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        manager
+            .alter_table(
+                Table::alter()
+                    .table(Accounts::Table)
+                    .add_column(json_null(Accounts::Settings))
+                    .to_owned(),
+            )
+            .await
+    }
+
+    async fn down(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        manager
+            .alter_table(
+                Table::alter()
+                    .table(Accounts::Table)
+                    .drop_column(Accounts::Settings)
+                    .to_owned(),
+            )
+            .await
+    }
+}
+
+#[derive(DeriveIden)]
+enum Accounts {
+    Table,
+    Settings,
+}
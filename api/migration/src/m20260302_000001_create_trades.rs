@@ -0,0 +1,103 @@
+use sea_orm_migration::{prelude::*, schema::*};
+
+/// Creates the `trades` table.
+///
+/// Holds the trade fills (executions) of exchange accounts, appended by account sync.
+/// Fills are unique per account and exchange trade ID, so re-fetching an overlapping
+/// window is idempotent. This is the input for cost-basis and P&L calculations.
+#[derive(DeriveMigrationName)]
+pub struct Migration;
+
+#[async_trait::async_trait]
+impl MigrationTrait for Migration {
+    async fn up(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        manager
+            .create_table(
+                Table::create()
+                    .table(Trades::Table)
+                    .if_not_exists()
+                    .col(uuid(Trades::Id).primary_key().extra("DEFAULT gen_random_uuid()"))
+                    .col(uuid(Trades::AccountId).not_null())
+                    .col(string(Trades::TradeId).not_null())
+                    .col(string_null(Trades::OrderId))
+                    .col(string(Trades::Instrument).not_null())
+                    .col(string(Trades::InstrumentType).not_null())
+                    .col(string_null(Trades::BaseAsset))
+                    .col(string_null(Trades::QuoteAsset))
+                    .col(string(Trades::Side).not_null())
+                    .col(decimal(Trades::Quantity).not_null())
+                    .col(decimal(Trades::Price).not_null())
+                    .col(decimal_null(Trades::Fee))
+                    .col(string_null(Trades::FeeCurrency))
+                    .col(timestamp_with_time_zone(Trades::ExecutedAt).not_null())
+                    .col(timestamp_with_time_zone(Trades::CreatedAt).default(Expr::current_timestamp()).not_null())
+                    .foreign_key(
+                        ForeignKey::create()
+                            .name("fk_trades_account_id")
+                            .from(Trades::Table, Trades::AccountId)
+                            .to(Accounts::Table, Accounts::Id)
+                            .on_delete(ForeignKeyAction::Cascade)
+                            .on_update(ForeignKeyAction::Cascade),
+                    )
+                    .to_owned(),
+            )
+            .await?;
+
+        manager
+            .create_index(
+                Index::create()
+                    .name("idx_trades_account_trade_id")
+                    .table(Trades::Table)
+                    .col(Trades::AccountId)
+                    .col(Trades::TradeId)
+                    .unique()
+                    .to_owned(),
+            )
+            .await?;
+
+        manager
+            .create_index(
+                Index::create()
+                    .name("idx_trades_account_executed_at")
+                    .table(Trades::Table)
+                    .col(Trades::AccountId)
+                    .col(Trades::ExecutedAt)
+                    .to_owned(),
+            )
+            .await?;
+
+        Ok(())
+    }
+
+    async fn down(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        manager
+            .drop_table(Table::drop().table(Trades::Table).to_owned())
+            .await
+    }
+}
+
+#[derive(DeriveIden)]
+enum Trades {
+    Table,
+    Id,
+    AccountId,
+    TradeId,
+    OrderId,
+    Instrument,
+    InstrumentType,
+    BaseAsset,
+    QuoteAsset,
+    Side,
+    Quantity,
+    Price,
+    Fee,
+    FeeCurrency,
+    ExecutedAt,
+    CreatedAt,
+}
+
+#[derive(DeriveIden)]
+enum Accounts {
+    Table,
+    Id,
+}
@@ -0,0 +1,39 @@
+use sea_orm_migration::{prelude::*, schema::*};
+
+/// Adds a nullable `first_activity_at` timestamp column to `accounts`.
+///
+/// Holds the date of a wallet's first on-chain transaction, looked up from a block explorer
+/// during sync. NULL means it has not been determined (yet), or the account is not a wallet.
+#[derive(DeriveMigrationName)]
+pub struct Migration;
+
+#[async_trait::async_trait]
+impl MigrationTrait for Migration {
+    async fn up(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        manager
+            .alter_table(
+                Table::alter()
+                    .table(Accounts::Table)
+                    .add_column(timestamp_with_time_zone_null(Accounts::FirstActivityAt))
+                    .to_owned(),
+            )
+            .await
+    }
+
+    async fn down(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        manager
+            .alter_table(
+                Table::alter()
+                    .table(Accounts::Table)
+                    .drop_column(Accounts::FirstActivityAt)
+                    .to_owned(),
+            )
+            .await
+    }
+}
+
+#[derive(DeriveIden)]
+enum Accounts {
+    Table,
+    FirstActivityAt,
+}
@@ -0,0 +1,96 @@
+use sea_orm_migration::{prelude::*, schema::*};
+
+/// Creates the `transfers` table.
+///
+/// Holds completed deposits and withdrawals of exchange accounts, appended by account sync.
+/// Transfers are unique per account and exchange transfer ID, so re-fetching an overlapping
+/// window is idempotent. Net contributions (deposits minus withdrawals) separate cash flows
+/// from investment performance.
+#[derive(DeriveMigrationName)]
+pub struct Migration;
+
+#[async_trait::async_trait]
+impl MigrationTrait for Migration {
+    async fn up(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        manager
+            .create_table(
+                Table::create()
+                    .table(Transfers::Table)
+                    .if_not_exists()
+                    .col(uuid(Transfers::Id).primary_key().extra("DEFAULT gen_random_uuid()"))
+                    .col(uuid(Transfers::AccountId).not_null())
+                    .col(string(Transfers::TransferId).not_null())
+                    .col(string(Transfers::Direction).not_null())
+                    .col(string(Transfers::Asset).not_null())
+                    .col(decimal(Transfers::Amount).not_null())
+                    .col(decimal_null(Transfers::Fee))
+                    .col(string_null(Transfers::Network))
+                    .col(string_null(Transfers::TxHash))
+                    .col(timestamp_with_time_zone(Transfers::OccurredAt).not_null())
+                    .col(timestamp_with_time_zone(Transfers::CreatedAt).default(Expr::current_timestamp()).not_null())
+                    .foreign_key(
+                        ForeignKey::create()
+                            .name("fk_transfers_account_id")
+                            .from(Transfers::Table, Transfers::AccountId)
+                            .to(Accounts::Table, Accounts::Id)
+                            .on_delete(ForeignKeyAction::Cascade)
+                            .on_update(ForeignKeyAction::Cascade),
+                    )
+                    .to_owned(),
+            )
+            .await?;
+
+        manager
+            .create_index(
+                Index::create()
+                    .name("idx_transfers_account_transfer_id")
+                    .table(Transfers::Table)
+                    .col(Transfers::AccountId)
+                    .col(Transfers::TransferId)
+                    .unique()
+                    .to_owned(),
+            )
+            .await?;
+
+        manager
+            .create_index(
+                Index::create()
+                    .name("idx_transfers_account_occurred_at")
+                    .table(Transfers::Table)
+                    .col(Transfers::AccountId)
+                    .col(Transfers::OccurredAt)
+                    .to_owned(),
+            )
+            .await?;
+
+        Ok(())
+    }
+
+    async fn down(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        manager
+            .drop_table(Table::drop().table(Transfers::Table).to_owned())
+            .await
+    }
+}
+
+#[derive(DeriveIden)]
+enum Transfers {
+    Table,
+    Id,
+    AccountId,
+    TransferId,
+    Direction,
+    Asset,
+    Amount,
+    Fee,
+    Network,
+    TxHash,
+    OccurredAt,
+    CreatedAt,
+}
+
+#[derive(DeriveIden)]
+enum Accounts {
+    Table,
+    Id,
+}
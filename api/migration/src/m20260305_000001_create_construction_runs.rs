@@ -0,0 +1,136 @@
+use sea_orm_migration::{prelude::*, schema::*};
+
+/// Creates the `construction_runs` table and links allocations, snapshots and recommendations to it.
+///
+/// Every allocation construction appends a run recording what triggered it, a digest of its inputs
+/// (account quantities and portfolio settings), the price rows it valued holdings with, and how long
+/// it took. `portfolio_allocations`, `snapshots` and `recommendations` get a nullable
+/// `construction_run_id` (NULL for rows created before runs were recorded); deleting a run keeps
+/// the rows that reference it.
+#[derive(DeriveMigrationName)]
+pub struct Migration;
+
+#[async_trait::async_trait]
+impl MigrationTrait for Migration {
+    async fn up(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        manager
+            .create_table(
+                Table::create()
+                    .table(ConstructionRuns::Table)
+                    .if_not_exists()
+                    .col(uuid(ConstructionRuns::Id).primary_key().extra("DEFAULT gen_random_uuid()"))
+                    .col(uuid(ConstructionRuns::PortfolioId).not_null())
+                    .col(string(ConstructionRuns::Trigger).not_null())
+                    .col(uuid_null(ConstructionRuns::TriggeredBy))
+                    .col(string(ConstructionRuns::InputsDigest).not_null())
+                    .col(json(ConstructionRuns::Inputs).not_null())
+                    .col(json(ConstructionRuns::PriceSources).not_null())
+                    .col(decimal(ConstructionRuns::TotalValueUsd).not_null())
+                    .col(integer(ConstructionRuns::HoldingsCount).not_null())
+                    .col(big_integer(ConstructionRuns::DurationMs).not_null())
+                    .col(timestamp_with_time_zone(ConstructionRuns::StartedAt).not_null())
+                    .col(timestamp_with_time_zone(ConstructionRuns::CreatedAt).default(Expr::current_timestamp()).not_null())
+                    .foreign_key(
+                        ForeignKey::create()
+                            .name("fk_construction_runs_portfolio_id")
+                            .from(ConstructionRuns::Table, ConstructionRuns::PortfolioId)
+                            .to(Portfolios::Table, Portfolios::Id)
+                            .on_delete(ForeignKeyAction::Cascade)
+                            .on_update(ForeignKeyAction::Cascade),
+                    )
+                    .to_owned(),
+            )
+            .await?;
+
+        manager
+            .create_index(
+                Index::create()
+                    .name("idx_construction_runs_portfolio_started_at")
+                    .table(ConstructionRuns::Table)
+                    .col(ConstructionRuns::PortfolioId)
+                    .col(ConstructionRuns::StartedAt)
+                    .to_owned(),
+            )
+            .await?;
+
+        for (table, fk_name) in [
+            (LinkedTable::PortfolioAllocations, "fk_portfolio_allocations_construction_run_id"),
+            (LinkedTable::Snapshots, "fk_snapshots_construction_run_id"),
+            (LinkedTable::Recommendations, "fk_recommendations_construction_run_id"),
+        ] {
+            manager
+                .alter_table(
+                    Table::alter()
+                        .table(table)
+                        .add_column(uuid_null(LinkedTable::ConstructionRunId))
+                        .add_foreign_key(
+                            TableForeignKey::new()
+                                .name(fk_name)
+                                .from_tbl(table)
+                                .from_col(LinkedTable::ConstructionRunId)
+                                .to_tbl(ConstructionRuns::Table)
+                                .to_col(ConstructionRuns::Id)
+                                .on_delete(ForeignKeyAction::SetNull)
+                                .on_update(ForeignKeyAction::Cascade),
+                        )
+                        .to_owned(),
+                )
+                .await?;
+        }
+
+        Ok(())
+    }
+
+    async fn down(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        for table in [
+            LinkedTable::PortfolioAllocations,
+            LinkedTable::Snapshots,
+            LinkedTable::Recommendations,
+        ] {
+            manager
+                .alter_table(
+                    Table::alter()
+                        .table(table)
+                        .drop_column(LinkedTable::ConstructionRunId)
+                        .to_owned(),
+                )
+                .await?;
+        }
+
+        manager
+            .drop_table(Table::drop().table(ConstructionRuns::Table).to_owned())
+            .await
+    }
+}
+
+#[derive(DeriveIden)]
+enum ConstructionRuns {
+    Table,
+    Id,
+    PortfolioId,
+    Trigger,
+    TriggeredBy,
+    InputsDigest,
+    Inputs,
+    PriceSources,
+    TotalValueUsd,
+    HoldingsCount,
+    DurationMs,
+    StartedAt,
+    CreatedAt,
+}
+
+/// Tables linked to the run that produced them, and their new column
+#[derive(DeriveIden, Clone, Copy)]
+enum LinkedTable {
+    PortfolioAllocations,
+    Snapshots,
+    Recommendations,
+    ConstructionRunId,
+}
+
+#[derive(DeriveIden)]
+enum Portfolios {
+    Table,
+    Id,
+}
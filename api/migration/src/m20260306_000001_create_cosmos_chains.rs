@@ -0,0 +1,111 @@
+use sea_orm::{sea_query::Values, DbBackend, Statement};
+use sea_orm_migration::{prelude::*, schema::*};
+
+/// Creates the `cosmos_chains` table and seeds it with default Cosmos-SDK chains.
+///
+/// Each row describes a chain read by the Cosmos wallet connector: its LCD (REST) endpoint,
+/// bech32 address prefix and native staking denom. Chains can be managed at runtime via
+/// `/api/v1/cosmos-chains`.
+#[derive(DeriveMigrationName)]
+pub struct Migration;
+
+/// Default chain seeds: (chain_id, name, lcd_url, bech32_prefix, native_denom, native_symbol, native_decimals)
+const SEED_CHAINS: &[(&str, &str, &str, &str, &str, &str, i32)] = &[
+    ("cosmoshub", "Cosmos Hub", "https://cosmos-rest.publicnode.com", "cosmos", "uatom", "ATOM", 6),
+    ("osmosis", "Osmosis", "https://osmosis-rest.publicnode.com", "osmo", "uosmo", "OSMO", 6),
+    ("celestia", "Celestia", "https://celestia-rest.publicnode.com", "celestia", "utia", "TIA", 6),
+];
+
+#[async_trait::async_trait]
+impl MigrationTrait for Migration {
+    async fn up(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        manager
+            .create_table(
+                Table::create()
+                    .table(CosmosChains::Table)
+                    .if_not_exists()
+                    .col(
+                        uuid(CosmosChains::Id)
+                            .primary_key()
+                            .extra("DEFAULT gen_random_uuid()"),
+                    )
+                    .col(string(CosmosChains::ChainId).not_null())
+                    .col(string(CosmosChains::Name).not_null())
+                    .col(string(CosmosChains::LcdUrl).not_null())
+                    .col(string(CosmosChains::Bech32Prefix).not_null())
+                    .col(string(CosmosChains::NativeDenom).not_null())
+                    .col(string(CosmosChains::NativeSymbol).not_null())
+                    .col(integer(CosmosChains::NativeDecimals).default(6).not_null())
+                    .col(boolean(CosmosChains::IsActive).default(true).not_null())
+                    .col(
+                        timestamp_with_time_zone(CosmosChains::CreatedAt)
+                            .default(Expr::current_timestamp())
+                            .not_null(),
+                    )
+                    .col(
+                        timestamp_with_time_zone(CosmosChains::UpdatedAt)
+                            .default(Expr::current_timestamp())
+                            .not_null(),
+                    )
+                    .to_owned(),
+            )
+            .await?;
+
+        manager
+            .create_index(
+                Index::create()
+                    .name("idx_cosmos_chains_chain_id_unique")
+                    .table(CosmosChains::Table)
+                    .col(CosmosChains::ChainId)
+                    .unique()
+                    .to_owned(),
+            )
+            .await?;
+
+        // Seed default chains
+        let db = manager.get_connection();
+        for (chain_id, name, lcd_url, prefix, denom, symbol, decimals) in SEED_CHAINS {
+            db.execute(Statement::from_sql_and_values(
+                DbBackend::Postgres,
+                "INSERT INTO cosmos_chains \
+                 (chain_id, name, lcd_url, bech32_prefix, native_denom, native_symbol, native_decimals, is_active) \
+                 VALUES ($1, $2, $3, $4, $5, $6, $7, true) \
+                 ON CONFLICT (chain_id) DO NOTHING",
+                Values(vec![
+                    (*chain_id).into(),
+                    (*name).into(),
+                    (*lcd_url).into(),
+                    (*prefix).into(),
+                    (*denom).into(),
+                    (*symbol).into(),
+                    (*decimals).into(),
+                ]),
+            ))
+            .await?;
+        }
+
+        Ok(())
+    }
+
+    async fn down(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        manager
+            .drop_table(Table::drop().table(CosmosChains::Table).to_owned())
+            .await
+    }
+}
+
+#[derive(DeriveIden)]
+enum CosmosChains {
+    Table,
+    Id,
+    ChainId,
+    Name,
+    LcdUrl,
+    Bech32Prefix,
+    NativeDenom,
+    NativeSymbol,
+    NativeDecimals,
+    IsActive,
+    CreatedAt,
+    UpdatedAt,
+}
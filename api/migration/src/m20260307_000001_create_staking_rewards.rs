@@ -0,0 +1,94 @@
+use sea_orm_migration::{prelude::*, schema::*};
+
+/// Creates the `staking_rewards` table.
+///
+/// Holds per-epoch staking rewards of wallet stake accounts (Solana inflation rewards),
+/// appended by account sync for income reporting. Rewards are unique per account, stake
+/// account and epoch, so re-fetching an epoch is idempotent.
+#[derive(DeriveMigrationName)]
+pub struct Migration;
+
+#[async_trait::async_trait]
+impl MigrationTrait for Migration {
+    async fn up(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        manager
+            .create_table(
+                Table::create()
+                    .table(StakingRewards::Table)
+                    .if_not_exists()
+                    .col(uuid(StakingRewards::Id).primary_key().extra("DEFAULT gen_random_uuid()"))
+                    .col(uuid(StakingRewards::AccountId).not_null())
+                    .col(string(StakingRewards::Asset).not_null())
+                    .col(string(StakingRewards::SourceAccount).not_null())
+                    .col(big_integer(StakingRewards::Epoch).not_null())
+                    .col(decimal(StakingRewards::Amount).not_null())
+                    .col(decimal_null(StakingRewards::PostBalance))
+                    .col(small_integer_null(StakingRewards::Commission))
+                    .col(timestamp_with_time_zone(StakingRewards::EarnedAt).not_null())
+                    .col(timestamp_with_time_zone(StakingRewards::CreatedAt).default(Expr::current_timestamp()).not_null())
+                    .foreign_key(
+                        ForeignKey::create()
+                            .name("fk_staking_rewards_account_id")
+                            .from(StakingRewards::Table, StakingRewards::AccountId)
+                            .to(Accounts::Table, Accounts::Id)
+                            .on_delete(ForeignKeyAction::Cascade)
+                            .on_update(ForeignKeyAction::Cascade),
+                    )
+                    .to_owned(),
+            )
+            .await?;
+
+        manager
+            .create_index(
+                Index::create()
+                    .name("idx_staking_rewards_account_source_epoch")
+                    .table(StakingRewards::Table)
+                    .col(StakingRewards::AccountId)
+                    .col(StakingRewards::SourceAccount)
+                    .col(StakingRewards::Epoch)
+                    .unique()
+                    .to_owned(),
+            )
+            .await?;
+
+        manager
+            .create_index(
+                Index::create()
+                    .name("idx_staking_rewards_account_earned_at")
+                    .table(StakingRewards::Table)
+                    .col(StakingRewards::AccountId)
+                    .col(StakingRewards::EarnedAt)
+                    .to_owned(),
+            )
+            .await?;
+
+        Ok(())
+    }
+
+    async fn down(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        manager
+            .drop_table(Table::drop().table(StakingRewards::Table).to_owned())
+            .await
+    }
+}
+
+#[derive(DeriveIden)]
+enum StakingRewards {
+    Table,
+    Id,
+    AccountId,
+    Asset,
+    SourceAccount,
+    Epoch,
+    Amount,
+    PostBalance,
+    Commission,
+    EarnedAt,
+    CreatedAt,
+}
+
+#[derive(DeriveIden)]
+enum Accounts {
+    Table,
+    Id,
+}
@@ -0,0 +1,60 @@
+use sea_orm_migration::{prelude::*, schema::*};
+
+/// Creates the `spam_tokens` table.
+///
+/// Blocklist of token contracts / mints that wallet sync drops before they reach holdings
+/// (currently consulted by the Solana connector for junk SPL airdrops). Manageable at
+/// runtime via `/api/v1/spam-tokens`.
+#[derive(DeriveMigrationName)]
+pub struct Migration;
+
+#[async_trait::async_trait]
+impl MigrationTrait for Migration {
+    async fn up(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        manager
+            .create_table(
+                Table::create()
+                    .table(SpamTokens::Table)
+                    .if_not_exists()
+                    .col(uuid(SpamTokens::Id).primary_key().extra("DEFAULT gen_random_uuid()"))
+                    .col(string(SpamTokens::Chain).not_null())
+                    .col(string(SpamTokens::TokenAddress).not_null())
+                    .col(string_null(SpamTokens::Reason))
+                    .col(timestamp_with_time_zone(SpamTokens::CreatedAt).default(Expr::current_timestamp()).not_null())
+                    .col(timestamp_with_time_zone(SpamTokens::UpdatedAt).default(Expr::current_timestamp()).not_null())
+                    .to_owned(),
+            )
+            .await?;
+
+        manager
+            .create_index(
+                Index::create()
+                    .name("idx_spam_tokens_chain_token_address")
+                    .table(SpamTokens::Table)
+                    .col(SpamTokens::Chain)
+                    .col(SpamTokens::TokenAddress)
+                    .unique()
+                    .to_owned(),
+            )
+            .await?;
+
+        Ok(())
+    }
+
+    async fn down(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        manager
+            .drop_table(Table::drop().table(SpamTokens::Table).to_owned())
+            .await
+    }
+}
+
+#[derive(DeriveIden)]
+enum SpamTokens {
+    Table,
+    Id,
+    Chain,
+    TokenAddress,
+    Reason,
+    CreatedAt,
+    UpdatedAt,
+}
@@ -0,0 +1,42 @@
+use sea_orm_migration::{prelude::*, schema::*};
+
+/// Adds nullable `nft_provider` and `nft_api_url` columns to `evm_chains`.
+///
+/// A chain with both set has its wallets' NFTs discovered during account sync through
+/// that provider ("alchemy" or "reservoir"). NULL leaves NFT discovery off for the chain.
+#[derive(DeriveMigrationName)]
+pub struct Migration;
+
+#[async_trait::async_trait]
+impl MigrationTrait for Migration {
+    async fn up(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        manager
+            .alter_table(
+                Table::alter()
+                    .table(EvmChains::Table)
+                    .add_column(string_null(EvmChains::NftProvider))
+                    .add_column(string_null(EvmChains::NftApiUrl))
+                    .to_owned(),
+            )
+            .await
+    }
+
+    async fn down(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        manager
+            .alter_table(
+                Table::alter()
+                    .table(EvmChains::Table)
+                    .drop_column(EvmChains::NftProvider)
+                    .drop_column(EvmChains::NftApiUrl)
+                    .to_owned(),
+            )
+            .await
+    }
+}
+
+#[derive(DeriveIden)]
+enum EvmChains {
+    Table,
+    NftProvider,
+    NftApiUrl,
+}
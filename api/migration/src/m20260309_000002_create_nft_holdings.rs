@@ -0,0 +1,90 @@
+use sea_orm_migration::{prelude::*, schema::*};
+
+/// Creates the `nft_holdings` table.
+///
+/// Holds the ERC-721/ERC-1155 tokens each wallet account owned at its last sync, with the
+/// collection floor price reported by the NFT provider. Account sync replaces an account's
+/// rows on every successful NFT fetch.
+#[derive(DeriveMigrationName)]
+pub struct Migration;
+
+#[async_trait::async_trait]
+impl MigrationTrait for Migration {
+    async fn up(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        manager
+            .create_table(
+                Table::create()
+                    .table(NftHoldings::Table)
+                    .if_not_exists()
+                    .col(uuid(NftHoldings::Id).primary_key().extra("DEFAULT gen_random_uuid()"))
+                    .col(uuid(NftHoldings::AccountId).not_null())
+                    .col(string(NftHoldings::Chain).not_null())
+                    .col(string(NftHoldings::ContractAddress).not_null())
+                    .col(string(NftHoldings::TokenId).not_null())
+                    .col(string_null(NftHoldings::TokenStandard))
+                    .col(decimal(NftHoldings::Quantity).not_null())
+                    .col(string_null(NftHoldings::CollectionName))
+                    .col(string_null(NftHoldings::Name))
+                    .col(string_null(NftHoldings::ImageUrl))
+                    .col(decimal_null(NftHoldings::FloorPrice))
+                    .col(string_null(NftHoldings::FloorCurrency))
+                    .col(timestamp_with_time_zone(NftHoldings::SyncedAt).not_null())
+                    .foreign_key(
+                        ForeignKey::create()
+                            .name("fk_nft_holdings_account_id")
+                            .from(NftHoldings::Table, NftHoldings::AccountId)
+                            .to(Accounts::Table, Accounts::Id)
+                            .on_delete(ForeignKeyAction::Cascade)
+                            .on_update(ForeignKeyAction::Cascade),
+                    )
+                    .to_owned(),
+            )
+            .await?;
+
+        manager
+            .create_index(
+                Index::create()
+                    .name("idx_nft_holdings_account_token")
+                    .table(NftHoldings::Table)
+                    .col(NftHoldings::AccountId)
+                    .col(NftHoldings::Chain)
+                    .col(NftHoldings::ContractAddress)
+                    .col(NftHoldings::TokenId)
+                    .unique()
+                    .to_owned(),
+            )
+            .await?;
+
+        Ok(())
+    }
+
+    async fn down(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        manager
+            .drop_table(Table::drop().table(NftHoldings::Table).to_owned())
+            .await
+    }
+}
+
+#[derive(DeriveIden)]
+enum NftHoldings {
+    Table,
+    Id,
+    AccountId,
+    Chain,
+    ContractAddress,
+    TokenId,
+    TokenStandard,
+    Quantity,
+    CollectionName,
+    Name,
+    ImageUrl,
+    FloorPrice,
+    FloorCurrency,
+    SyncedAt,
+}
+
+#[derive(DeriveIden)]
+enum Accounts {
+    Table,
+    Id,
+}
@@ -0,0 +1,46 @@
+use sea_orm_migration::{prelude::*, schema::*};
+
+/// Adds name resolution columns to `accounts`:
+/// - `wallet_name`: the ENS / Unstoppable Domains name a wallet was created with; the
+///   account's `wallet_address` is re-resolved from it periodically
+/// - `display_name`: the address's reverse-resolved (primary) name
+/// - `names_resolved_at`: when the names were last resolved
+#[derive(DeriveMigrationName)]
+pub struct Migration;
+
+#[async_trait::async_trait]
+impl MigrationTrait for Migration {
+    async fn up(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        manager
+            .alter_table(
+                Table::alter()
+                    .table(Accounts::Table)
+                    .add_column(string_null(Accounts::WalletName))
+                    .add_column(string_null(Accounts::DisplayName))
+                    .add_column(timestamp_with_time_zone_null(Accounts::NamesResolvedAt))
+                    .to_owned(),
+            )
+            .await
+    }
+
+    async fn down(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        manager
+            .alter_table(
+                Table::alter()
+                    .table(Accounts::Table)
+                    .drop_column(Accounts::WalletName)
+                    .drop_column(Accounts::DisplayName)
+                    .drop_column(Accounts::NamesResolvedAt)
+                    .to_owned(),
+            )
+            .await
+    }
+}
+
+#[derive(DeriveIden)]
+enum Accounts {
+    Table,
+    WalletName,
+    DisplayName,
+    NamesResolvedAt,
+}
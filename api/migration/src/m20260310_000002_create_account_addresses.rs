@@ -0,0 +1,82 @@
+use sea_orm_migration::{prelude::*, schema::*};
+
+/// Creates the `account_addresses` table.
+///
+/// Holds the additional addresses of a wallet account (e.g. cold storage or a hardware
+/// wallet next to the hot wallet in `accounts.wallet_address`). Account sync reads every
+/// address and stores their summed balances as the account's holdings.
+#[derive(DeriveMigrationName)]
+pub struct Migration;
+
+#[async_trait::async_trait]
+impl MigrationTrait for Migration {
+    async fn up(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        manager
+            .create_table(
+                Table::create()
+                    .table(AccountAddresses::Table)
+                    .if_not_exists()
+                    .col(uuid(AccountAddresses::Id).primary_key().extra("DEFAULT gen_random_uuid()"))
+                    .col(uuid(AccountAddresses::AccountId).not_null())
+                    .col(string(AccountAddresses::Address).not_null())
+                    .col(string_null(AccountAddresses::Label))
+                    .col(
+                        timestamp_with_time_zone(AccountAddresses::CreatedAt)
+                            .not_null()
+                            .default(Expr::current_timestamp()),
+                    )
+                    .col(
+                        timestamp_with_time_zone(AccountAddresses::UpdatedAt)
+                            .not_null()
+                            .default(Expr::current_timestamp()),
+                    )
+                    .foreign_key(
+                        ForeignKey::create()
+                            .name("fk_account_addresses_account_id")
+                            .from(AccountAddresses::Table, AccountAddresses::AccountId)
+                            .to(Accounts::Table, Accounts::Id)
+                            .on_delete(ForeignKeyAction::Cascade)
+                            .on_update(ForeignKeyAction::Cascade),
+                    )
+                    .to_owned(),
+            )
+            .await?;
+
+        manager
+            .create_index(
+                Index::create()
+                    .name("idx_account_addresses_account_address")
+                    .table(AccountAddresses::Table)
+                    .col(AccountAddresses::AccountId)
+                    .col(AccountAddresses::Address)
+                    .unique()
+                    .to_owned(),
+            )
+            .await?;
+
+        Ok(())
+    }
+
+    async fn down(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        manager
+            .drop_table(Table::drop().table(AccountAddresses::Table).to_owned())
+            .await
+    }
+}
+
+#[derive(DeriveIden)]
+enum AccountAddresses {
+    Table,
+    Id,
+    AccountId,
+    Address,
+    Label,
+    CreatedAt,
+    UpdatedAt,
+}
+
+#[derive(DeriveIden)]
+enum Accounts {
+    Table,
+    Id,
+}
@@ -0,0 +1,104 @@
+use sea_orm_migration::{prelude::*, schema::*};
+
+/// Creates the token discovery cache of EVM wallets:
+/// - `discovered_tokens`: ERC-20 contracts a wallet has received, per chain
+/// - `token_discovery_scans`: the last block scanned for each wallet and chain, so each
+///   sync only scans the blocks added since
+///
+/// Rows are keyed by wallet address rather than account, so accounts sharing an address
+/// share one cache.
+#[derive(DeriveMigrationName)]
+pub struct Migration;
+
+#[async_trait::async_trait]
+impl MigrationTrait for Migration {
+    async fn up(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        manager
+            .create_table(
+                Table::create()
+                    .table(DiscoveredTokens::Table)
+                    .if_not_exists()
+                    .col(uuid(DiscoveredTokens::Id).primary_key().extra("DEFAULT gen_random_uuid()"))
+                    .col(string(DiscoveredTokens::Chain).not_null())
+                    .col(string(DiscoveredTokens::WalletAddress).not_null())
+                    .col(string(DiscoveredTokens::ContractAddress).not_null())
+                    .col(string_null(DiscoveredTokens::Symbol))
+                    .col(small_integer_null(DiscoveredTokens::Decimals))
+                    .col(timestamp_with_time_zone(DiscoveredTokens::DiscoveredAt).default(Expr::current_timestamp()).not_null())
+                    .to_owned(),
+            )
+            .await?;
+
+        manager
+            .create_index(
+                Index::create()
+                    .name("idx_discovered_tokens_chain_wallet_contract")
+                    .table(DiscoveredTokens::Table)
+                    .col(DiscoveredTokens::Chain)
+                    .col(DiscoveredTokens::WalletAddress)
+                    .col(DiscoveredTokens::ContractAddress)
+                    .unique()
+                    .to_owned(),
+            )
+            .await?;
+
+        manager
+            .create_table(
+                Table::create()
+                    .table(TokenDiscoveryScans::Table)
+                    .if_not_exists()
+                    .col(uuid(TokenDiscoveryScans::Id).primary_key().extra("DEFAULT gen_random_uuid()"))
+                    .col(string(TokenDiscoveryScans::Chain).not_null())
+                    .col(string(TokenDiscoveryScans::WalletAddress).not_null())
+                    .col(big_integer(TokenDiscoveryScans::LastScannedBlock).not_null())
+                    .col(timestamp_with_time_zone(TokenDiscoveryScans::ScannedAt).default(Expr::current_timestamp()).not_null())
+                    .to_owned(),
+            )
+            .await?;
+
+        manager
+            .create_index(
+                Index::create()
+                    .name("idx_token_discovery_scans_chain_wallet")
+                    .table(TokenDiscoveryScans::Table)
+                    .col(TokenDiscoveryScans::Chain)
+                    .col(TokenDiscoveryScans::WalletAddress)
+                    .unique()
+                    .to_owned(),
+            )
+            .await?;
+
+        Ok(())
+    }
+
+    async fn down(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        manager
+            .drop_table(Table::drop().table(TokenDiscoveryScans::Table).to_owned())
+            .await?;
+        manager
+            .drop_table(Table::drop().table(DiscoveredTokens::Table).to_owned())
+            .await
+    }
+}
+
+#[derive(DeriveIden)]
+enum DiscoveredTokens {
+    Table,
+    Id,
+    Chain,
+    WalletAddress,
+    ContractAddress,
+    Symbol,
+    Decimals,
+    DiscoveredAt,
+}
+
+#[derive(DeriveIden)]
+enum TokenDiscoveryScans {
+    Table,
+    Id,
+    Chain,
+    WalletAddress,
+    LastScannedBlock,
+    ScannedAt,
+}
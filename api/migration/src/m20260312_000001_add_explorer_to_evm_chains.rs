@@ -0,0 +1,46 @@
+use sea_orm_migration::{prelude::*, schema::*};
+
+/// Adds nullable explorer columns to `evm_chains`:
+/// - `balance_provider`: "etherscan" or "blockscout" reads a wallet's native and token
+///   balances from the chain's explorer API in two calls; NULL keeps the RPC `balanceOf` loop
+/// - `explorer_api_url`: explorer API base URL (Blockscout; Etherscan defaults to its v2 API)
+/// - `explorer_api_key`: explorer API key for the chain
+#[derive(DeriveMigrationName)]
+pub struct Migration;
+
+#[async_trait::async_trait]
+impl MigrationTrait for Migration {
+    async fn up(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        manager
+            .alter_table(
+                Table::alter()
+                    .table(EvmChains::Table)
+                    .add_column(string_null(EvmChains::BalanceProvider))
+                    .add_column(string_null(EvmChains::ExplorerApiUrl))
+                    .add_column(string_null(EvmChains::ExplorerApiKey))
+                    .to_owned(),
+            )
+            .await
+    }
+
+    async fn down(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        manager
+            .alter_table(
+                Table::alter()
+                    .table(EvmChains::Table)
+                    .drop_column(EvmChains::BalanceProvider)
+                    .drop_column(EvmChains::ExplorerApiUrl)
+                    .drop_column(EvmChains::ExplorerApiKey)
+                    .to_owned(),
+            )
+            .await
+    }
+}
+
+#[derive(DeriveIden)]
+enum EvmChains {
+    Table,
+    BalanceProvider,
+    ExplorerApiUrl,
+    ExplorerApiKey,
+}
@@ -0,0 +1,96 @@
+use sea_orm::{sea_query::Values, DbBackend, Statement};
+use sea_orm_migration::{prelude::*, schema::*};
+
+/// Creates the `curve_pools` table and seeds it with the Ethereum 3pool.
+///
+/// Each row maps a Curve LP token to its pool, so wallet sync can report LP positions as the
+/// pool's underlying coins. `convex_reward_pool` is the Convex reward contract the LP token is
+/// staked in through Convex, if any. Pools can be managed at runtime via `/api/v1/curve-pools`.
+#[derive(DeriveMigrationName)]
+pub struct Migration;
+
+/// Default pool seeds: (chain, name, pool_address, lp_token_address, convex_reward_pool)
+const SEED_POOLS: &[(&str, &str, &str, &str, Option<&str>)] = &[(
+    "ethereum",
+    "3pool",
+    "0xbebc44782c7db0a1a60cb6fe97d0b483032ff1c7",
+    "0x6c3f90f043a72fa612cbac8115ee7e52bde6e490",
+    Some("0x689440f2ff927e1f24c72f1087e1faf471ece1c8"),
+)];
+
+#[async_trait::async_trait]
+impl MigrationTrait for Migration {
+    async fn up(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        manager
+            .create_table(
+                Table::create()
+                    .table(CurvePools::Table)
+                    .if_not_exists()
+                    .col(uuid(CurvePools::Id).primary_key().extra("DEFAULT gen_random_uuid()"))
+                    .col(string(CurvePools::Chain).not_null())
+                    .col(string(CurvePools::Name).not_null())
+                    .col(string(CurvePools::PoolAddress).not_null())
+                    .col(string(CurvePools::LpTokenAddress).not_null())
+                    .col(string_null(CurvePools::ConvexRewardPool))
+                    .col(boolean(CurvePools::IsActive).default(true).not_null())
+                    .col(timestamp_with_time_zone(CurvePools::CreatedAt).default(Expr::current_timestamp()).not_null())
+                    .col(timestamp_with_time_zone(CurvePools::UpdatedAt).default(Expr::current_timestamp()).not_null())
+                    .to_owned(),
+            )
+            .await?;
+
+        manager
+            .create_index(
+                Index::create()
+                    .name("idx_curve_pools_chain_lp_token")
+                    .table(CurvePools::Table)
+                    .col(CurvePools::Chain)
+                    .col(CurvePools::LpTokenAddress)
+                    .unique()
+                    .to_owned(),
+            )
+            .await?;
+
+        // Seed default pools
+        let db = manager.get_connection();
+        for (chain, name, pool, lp_token, convex) in SEED_POOLS {
+            db.execute(Statement::from_sql_and_values(
+                DbBackend::Postgres,
+                "INSERT INTO curve_pools \
+                 (chain, name, pool_address, lp_token_address, convex_reward_pool, is_active) \
+                 VALUES ($1, $2, $3, $4, $5, true) \
+                 ON CONFLICT (chain, lp_token_address) DO NOTHING",
+                Values(vec![
+                    (*chain).into(),
+                    (*name).into(),
+                    (*pool).into(),
+                    (*lp_token).into(),
+                    convex.map(str::to_string).into(),
+                ]),
+            ))
+            .await?;
+        }
+
+        Ok(())
+    }
+
+    async fn down(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        manager
+            .drop_table(Table::drop().table(CurvePools::Table).to_owned())
+            .await
+    }
+}
+
+#[derive(DeriveIden)]
+enum CurvePools {
+    Table,
+    Id,
+    Chain,
+    Name,
+    PoolAddress,
+    LpTokenAddress,
+    ConvexRewardPool,
+    IsActive,
+    CreatedAt,
+    UpdatedAt,
+}
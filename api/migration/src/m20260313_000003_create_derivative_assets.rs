@@ -0,0 +1,117 @@
+use sea_orm::{sea_query::Values, DbBackend, Statement};
+use sea_orm_migration::{prelude::*, schema::*};
+
+/// Creates the `derivative_assets` table and seeds common wrapped and liquid staking tokens.
+///
+/// Each row maps a derivative symbol (e.g. WSTETH) to its underlying asset (ETH) with an
+/// exchange rate, so allocation construction can report exposure in the underlying asset.
+/// Rows with `rate_contract` read the rate from that Ethereum contract using `rate_method` and
+/// store the last value read in `exchange_rate`. Rows can be managed at `/api/v1/derivative-assets`.
+#[derive(DeriveMigrationName)]
+pub struct Migration;
+
+/// Default seeds: (symbol, underlying_symbol, exchange_rate, rate_contract, rate_method)
+const SEED_DERIVATIVES: &[(&str, &str, &str, Option<&str>, Option<&str>)] = &[
+    ("WETH", "ETH", "1", None, None),
+    ("STETH", "ETH", "1", None, None),
+    (
+        "WSTETH",
+        "ETH",
+        "1",
+        Some("0x7f39c581f595b53c5cb19bd0b3f8da6c935e2ca0"),
+        Some("steth_per_token"),
+    ),
+    (
+        "RETH",
+        "ETH",
+        "1",
+        Some("0xae78736cd615f374d3085123a210448e74fc6393"),
+        Some("get_exchange_rate"),
+    ),
+    (
+        "CBETH",
+        "ETH",
+        "1",
+        Some("0xbe9895146f7af43049ca1c1ae358b0541ea49704"),
+        Some("exchange_rate"),
+    ),
+    ("BTCB", "BTC", "1", None, None),
+];
+
+#[async_trait::async_trait]
+impl MigrationTrait for Migration {
+    async fn up(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        manager
+            .create_table(
+                Table::create()
+                    .table(DerivativeAssets::Table)
+                    .if_not_exists()
+                    .col(uuid(DerivativeAssets::Id).primary_key().extra("DEFAULT gen_random_uuid()"))
+                    .col(string(DerivativeAssets::Symbol).not_null())
+                    .col(string(DerivativeAssets::UnderlyingSymbol).not_null())
+                    .col(decimal(DerivativeAssets::ExchangeRate).default(1).not_null())
+                    .col(string_null(DerivativeAssets::RateContract))
+                    .col(string_null(DerivativeAssets::RateMethod))
+                    .col(timestamp_with_time_zone_null(DerivativeAssets::RateUpdatedAt))
+                    .col(boolean(DerivativeAssets::IsActive).default(true).not_null())
+                    .col(timestamp_with_time_zone(DerivativeAssets::CreatedAt).default(Expr::current_timestamp()).not_null())
+                    .col(timestamp_with_time_zone(DerivativeAssets::UpdatedAt).default(Expr::current_timestamp()).not_null())
+                    .to_owned(),
+            )
+            .await?;
+
+        manager
+            .create_index(
+                Index::create()
+                    .name("idx_derivative_assets_symbol")
+                    .table(DerivativeAssets::Table)
+                    .col(DerivativeAssets::Symbol)
+                    .unique()
+                    .to_owned(),
+            )
+            .await?;
+
+        // Seed default mappings; contract-rated rows start at 1 until the first rate read
+        let db = manager.get_connection();
+        for (symbol, underlying, rate, contract, method) in SEED_DERIVATIVES {
+            db.execute(Statement::from_sql_and_values(
+                DbBackend::Postgres,
+                "INSERT INTO derivative_assets \
+                 (symbol, underlying_symbol, exchange_rate, rate_contract, rate_method, is_active) \
+                 VALUES ($1, $2, $3::numeric, $4, $5, true) \
+                 ON CONFLICT (symbol) DO NOTHING",
+                Values(vec![
+                    (*symbol).into(),
+                    (*underlying).into(),
+                    (*rate).into(),
+                    contract.map(str::to_string).into(),
+                    method.map(str::to_string).into(),
+                ]),
+            ))
+            .await?;
+        }
+
+        Ok(())
+    }
+
+    async fn down(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        manager
+            .drop_table(Table::drop().table(DerivativeAssets::Table).to_owned())
+            .await
+    }
+}
+
+#[derive(DeriveIden)]
+enum DerivativeAssets {
+    Table,
+    Id,
+    Symbol,
+    UnderlyingSymbol,
+    ExchangeRate,
+    RateContract,
+    RateMethod,
+    RateUpdatedAt,
+    IsActive,
+    CreatedAt,
+    UpdatedAt,
+}
@@ -0,0 +1,69 @@
+use sea_orm_migration::{prelude::*, schema::*};
+
+/// Creates the `pendle_assets` table: Pendle principal (PT) and yield (YT) tokens per chain
+/// with their maturity and latest USD price.
+///
+/// Rows are written from the Pendle API when a wallet with the `pendle` DeFi protocol syncs
+/// and the chain's list is older than an hour; sync reads wallet balances of the listed
+/// tokens and allocation construction values them at `price_usd`.
+#[derive(DeriveMigrationName)]
+pub struct Migration;
+
+#[async_trait::async_trait]
+impl MigrationTrait for Migration {
+    async fn up(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        manager
+            .create_table(
+                Table::create()
+                    .table(PendleAssets::Table)
+                    .if_not_exists()
+                    .col(uuid(PendleAssets::Id).primary_key().extra("DEFAULT gen_random_uuid()"))
+                    .col(string(PendleAssets::Chain).not_null())
+                    .col(string(PendleAssets::Address).not_null())
+                    .col(string(PendleAssets::Symbol).not_null())
+                    .col(string(PendleAssets::TokenType).not_null())
+                    .col(integer(PendleAssets::Decimals).not_null())
+                    .col(timestamp_with_time_zone(PendleAssets::Expiry).not_null())
+                    .col(decimal_null(PendleAssets::PriceUsd))
+                    .col(timestamp_with_time_zone(PendleAssets::PriceUpdatedAt).default(Expr::current_timestamp()).not_null())
+                    .col(timestamp_with_time_zone(PendleAssets::CreatedAt).default(Expr::current_timestamp()).not_null())
+                    .col(timestamp_with_time_zone(PendleAssets::UpdatedAt).default(Expr::current_timestamp()).not_null())
+                    .to_owned(),
+            )
+            .await?;
+
+        manager
+            .create_index(
+                Index::create()
+                    .name("idx_pendle_assets_chain_address")
+                    .table(PendleAssets::Table)
+                    .col(PendleAssets::Chain)
+                    .col(PendleAssets::Address)
+                    .unique()
+                    .to_owned(),
+            )
+            .await
+    }
+
+    async fn down(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        manager
+            .drop_table(Table::drop().table(PendleAssets::Table).to_owned())
+            .await
+    }
+}
+
+#[derive(DeriveIden)]
+enum PendleAssets {
+    Table,
+    Id,
+    Chain,
+    Address,
+    Symbol,
+    TokenType,
+    Decimals,
+    Expiry,
+    PriceUsd,
+    PriceUpdatedAt,
+    CreatedAt,
+    UpdatedAt,
+}
@@ -0,0 +1,79 @@
+use sea_orm::{sea_query::Values, DbBackend, Statement};
+use sea_orm_migration::{prelude::*, schema::*};
+
+/// Creates the `yield_vaults` table and seeds it with Spark's sDAI vault.
+///
+/// Each row is a vault whose share token wallet sync resolves into the vault's underlying
+/// token: through ERC-4626 `convertToAssets`, or Yearn v2 / Beefy price-per-share as a
+/// fallback. Vaults can be managed at runtime via `/api/v1/yield-vaults`.
+#[derive(DeriveMigrationName)]
+pub struct Migration;
+
+/// Default vault seeds: (chain, name, vault_address)
+const SEED_VAULTS: &[(&str, &str, &str)] = &[("ethereum", "Spark sDAI", "0x83f20f44975d03b1b09e64809b757c47f942beea")];
+
+#[async_trait::async_trait]
+impl MigrationTrait for Migration {
+    async fn up(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        manager
+            .create_table(
+                Table::create()
+                    .table(YieldVaults::Table)
+                    .if_not_exists()
+                    .col(uuid(YieldVaults::Id).primary_key().extra("DEFAULT gen_random_uuid()"))
+                    .col(string(YieldVaults::Chain).not_null())
+                    .col(string(YieldVaults::Name).not_null())
+                    .col(string(YieldVaults::VaultAddress).not_null())
+                    .col(boolean(YieldVaults::IsActive).default(true).not_null())
+                    .col(timestamp_with_time_zone(YieldVaults::CreatedAt).default(Expr::current_timestamp()).not_null())
+                    .col(timestamp_with_time_zone(YieldVaults::UpdatedAt).default(Expr::current_timestamp()).not_null())
+                    .to_owned(),
+            )
+            .await?;
+
+        manager
+            .create_index(
+                Index::create()
+                    .name("idx_yield_vaults_chain_vault")
+                    .table(YieldVaults::Table)
+                    .col(YieldVaults::Chain)
+                    .col(YieldVaults::VaultAddress)
+                    .unique()
+                    .to_owned(),
+            )
+            .await?;
+
+        // Seed default vaults
+        let db = manager.get_connection();
+        for (chain, name, vault) in SEED_VAULTS {
+            db.execute(Statement::from_sql_and_values(
+                DbBackend::Postgres,
+                "INSERT INTO yield_vaults (chain, name, vault_address, is_active) \
+                 VALUES ($1, $2, $3, true) \
+                 ON CONFLICT (chain, vault_address) DO NOTHING",
+                Values(vec![(*chain).into(), (*name).into(), (*vault).into()]),
+            ))
+            .await?;
+        }
+
+        Ok(())
+    }
+
+    async fn down(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        manager
+            .drop_table(Table::drop().table(YieldVaults::Table).to_owned())
+            .await
+    }
+}
+
+#[derive(DeriveIden)]
+enum YieldVaults {
+    Table,
+    Id,
+    Chain,
+    Name,
+    VaultAddress,
+    IsActive,
+    CreatedAt,
+    UpdatedAt,
+}
@@ -0,0 +1,46 @@
+use sea_orm_migration::{prelude::*, schema::*};
+
+/// Adds nullable `gross_value_usd`, `debt_usd` and `net_value_usd` columns to `snapshots`.
+///
+/// Borrowed holdings (e.g. Aave debt) are stored as negative quantities, so a snapshot's value
+/// nets them out. These columns keep the split: the value of the assets alone, the value owed,
+/// and their difference. NULL on snapshots taken before the split was recorded.
+#[derive(DeriveMigrationName)]
+pub struct Migration;
+
+#[async_trait::async_trait]
+impl MigrationTrait for Migration {
+    async fn up(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        manager
+            .alter_table(
+                Table::alter()
+                    .table(Snapshots::Table)
+                    .add_column(decimal_null(Snapshots::GrossValueUsd))
+                    .add_column(decimal_null(Snapshots::DebtUsd))
+                    .add_column(decimal_null(Snapshots::NetValueUsd))
+                    .to_owned(),
+            )
+            .await
+    }
+
+    async fn down(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        manager
+            .alter_table(
+                Table::alter()
+                    .table(Snapshots::Table)
+                    .drop_column(Snapshots::GrossValueUsd)
+                    .drop_column(Snapshots::DebtUsd)
+                    .drop_column(Snapshots::NetValueUsd)
+                    .to_owned(),
+            )
+            .await
+    }
+}
+
+#[derive(DeriveIden)]
+enum Snapshots {
+    Table,
+    GrossValueUsd,
+    DebtUsd,
+    NetValueUsd,
+}
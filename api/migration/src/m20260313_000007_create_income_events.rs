@@ -0,0 +1,94 @@
+use sea_orm_migration::{prelude::*, schema::*};
+
+/// Creates the `income_events` table.
+///
+/// One row per income receipt detected by account sync: staking rewards, interest and
+/// airdrops, with the quantity and its USD value at receipt. `reference` identifies events
+/// read from an explicit source (e.g. a Solana stake account's epoch reward) so recording
+/// them again is a no-op; events detected from balance deltas have none.
+#[derive(DeriveMigrationName)]
+pub struct Migration;
+
+#[async_trait::async_trait]
+impl MigrationTrait for Migration {
+    async fn up(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        manager
+            .create_table(
+                Table::create()
+                    .table(IncomeEvents::Table)
+                    .if_not_exists()
+                    .col(uuid(IncomeEvents::Id).primary_key().extra("DEFAULT gen_random_uuid()"))
+                    .col(uuid(IncomeEvents::AccountId).not_null())
+                    .col(string(IncomeEvents::Asset).not_null())
+                    .col(decimal(IncomeEvents::Quantity).not_null())
+                    .col(decimal_null(IncomeEvents::ValueUsd))
+                    .col(string(IncomeEvents::Source).not_null())
+                    .col(string_null(IncomeEvents::HoldingSource))
+                    .col(string_null(IncomeEvents::Reference))
+                    .col(timestamp_with_time_zone(IncomeEvents::ReceivedAt).not_null())
+                    .col(timestamp_with_time_zone(IncomeEvents::CreatedAt).default(Expr::current_timestamp()).not_null())
+                    .foreign_key(
+                        ForeignKey::create()
+                            .name("fk_income_events_account_id")
+                            .from(IncomeEvents::Table, IncomeEvents::AccountId)
+                            .to(Accounts::Table, Accounts::Id)
+                            .on_delete(ForeignKeyAction::Cascade)
+                            .on_update(ForeignKeyAction::Cascade),
+                    )
+                    .to_owned(),
+            )
+            .await?;
+
+        manager
+            .create_index(
+                Index::create()
+                    .name("idx_income_events_account_reference")
+                    .table(IncomeEvents::Table)
+                    .col(IncomeEvents::AccountId)
+                    .col(IncomeEvents::Reference)
+                    .unique()
+                    .to_owned(),
+            )
+            .await?;
+
+        manager
+            .create_index(
+                Index::create()
+                    .name("idx_income_events_account_received_at")
+                    .table(IncomeEvents::Table)
+                    .col(IncomeEvents::AccountId)
+                    .col(IncomeEvents::ReceivedAt)
+                    .to_owned(),
+            )
+            .await?;
+
+        Ok(())
+    }
+
+    async fn down(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        manager
+            .drop_table(Table::drop().table(IncomeEvents::Table).to_owned())
+            .await
+    }
+}
+
+#[derive(DeriveIden)]
+enum IncomeEvents {
+    Table,
+    Id,
+    AccountId,
+    Asset,
+    Quantity,
+    ValueUsd,
+    Source,
+    HoldingSource,
+    Reference,
+    ReceivedAt,
+    CreatedAt,
+}
+
+#[derive(DeriveIden)]
+enum Accounts {
+    Table,
+    Id,
+}
@@ -0,0 +1,50 @@
+use sea_orm_migration::{prelude::*, schema::*};
+
+/// Adds a nullable, indexed `coingecko_id` column to `assets`.
+///
+/// Price collection can run against CoinGecko instead of CoinPaprika (`PRICE_PROVIDER`);
+/// assets are then matched to CoinGecko coins by this ID, the way `coinpaprika_id` is used
+/// for CoinPaprika.
+#[derive(DeriveMigrationName)]
+pub struct Migration;
+
+#[async_trait::async_trait]
+impl MigrationTrait for Migration {
+    async fn up(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        manager
+            .alter_table(
+                Table::alter()
+                    .table(Assets::Table)
+                    .add_column(string_null(Assets::CoingeckoId))
+                    .to_owned(),
+            )
+            .await?;
+
+        manager
+            .create_index(
+                Index::create()
+                    .name("idx_assets_coingecko_id")
+                    .table(Assets::Table)
+                    .col(Assets::CoingeckoId)
+                    .to_owned(),
+            )
+            .await
+    }
+
+    async fn down(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        manager
+            .alter_table(
+                Table::alter()
+                    .table(Assets::Table)
+                    .drop_column(Assets::CoingeckoId)
+                    .to_owned(),
+            )
+            .await
+    }
+}
+
+#[derive(DeriveIden)]
+enum Assets {
+    Table,
+    CoingeckoId,
+}
@@ -0,0 +1,40 @@
+use sea_orm_migration::{prelude::*, schema::*};
+
+/// Adds a nullable `price_resolution` JSON column to `portfolio_allocations`.
+///
+/// Holds, per holding symbol, the price row construction picked after reconciling the price
+/// sources: its source, time, and whether it came from the primary source, a fallback, or was
+/// the newest of only stale prices. NULL on allocations constructed before reconciliation.
+#[derive(DeriveMigrationName)]
+pub struct Migration;
+
+#[async_trait::async_trait]
+impl MigrationTrait for Migration {
+    async fn up(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        manager
+            .alter_table(
+                Table::alter()
+                    .table(PortfolioAllocations::Table)
+                    .add_column(json_null(PortfolioAllocations::PriceResolution))
+                    .to_owned(),
+            )
+            .await
+    }
+
+    async fn down(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        manager
+            .alter_table(
+                Table::alter()
+                    .table(PortfolioAllocations::Table)
+                    .drop_column(PortfolioAllocations::PriceResolution)
+                    .to_owned(),
+            )
+            .await
+    }
+}
+
+#[derive(DeriveIden)]
+enum PortfolioAllocations {
+    Table,
+    PriceResolution,
+}
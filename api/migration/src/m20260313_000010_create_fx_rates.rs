@@ -0,0 +1,60 @@
+use sea_orm_migration::{prelude::*, schema::*};
+
+/// Creates the `fx_rates` table.
+///
+/// Daily fiat exchange rates against USD, collected by the scheduled FX rates job from the ECB
+/// reference rates or exchangerate.host. `rate` is the number of units of `currency` per USD,
+/// so a USD value converts by multiplying. One row per currency and day.
+#[derive(DeriveMigrationName)]
+pub struct Migration;
+
+#[async_trait::async_trait]
+impl MigrationTrait for Migration {
+    async fn up(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        manager
+            .create_table(
+                Table::create()
+                    .table(FxRates::Table)
+                    .if_not_exists()
+                    .col(uuid(FxRates::Id).primary_key().extra("DEFAULT gen_random_uuid()"))
+                    .col(string_len(FxRates::Currency, 3).not_null())
+                    .col(decimal_len(FxRates::Rate, 30, 12).not_null())
+                    .col(date(FxRates::RateDate).not_null())
+                    .col(string(FxRates::Source).not_null())
+                    .col(timestamp_with_time_zone(FxRates::CreatedAt).default(Expr::current_timestamp()).not_null())
+                    .to_owned(),
+            )
+            .await?;
+
+        manager
+            .create_index(
+                Index::create()
+                    .name("idx_fx_rates_currency_rate_date")
+                    .table(FxRates::Table)
+                    .col(FxRates::Currency)
+                    .col(FxRates::RateDate)
+                    .unique()
+                    .to_owned(),
+            )
+            .await?;
+
+        Ok(())
+    }
+
+    async fn down(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        manager
+            .drop_table(Table::drop().table(FxRates::Table).to_owned())
+            .await
+    }
+}
+
+#[derive(DeriveIden)]
+enum FxRates {
+    Table,
+    Id,
+    Currency,
+    Rate,
+    RateDate,
+    Source,
+    CreatedAt,
+}
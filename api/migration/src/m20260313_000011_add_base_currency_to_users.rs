@@ -0,0 +1,39 @@
+use sea_orm_migration::{prelude::*, schema::*};
+
+/// Adds a `base_currency` column to `users` (ISO 4217 code, default "USD").
+///
+/// Holdings, allocation and snapshot responses convert their values to this currency with
+/// the rates in `fx_rates`.
+#[derive(DeriveMigrationName)]
+pub struct Migration;
+
+#[async_trait::async_trait]
+impl MigrationTrait for Migration {
+    async fn up(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        manager
+            .alter_table(
+                Table::alter()
+                    .table(Users::Table)
+                    .add_column(string_len(Users::BaseCurrency, 3).default("USD").not_null())
+                    .to_owned(),
+            )
+            .await
+    }
+
+    async fn down(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        manager
+            .alter_table(
+                Table::alter()
+                    .table(Users::Table)
+                    .drop_column(Users::BaseCurrency)
+                    .to_owned(),
+            )
+            .await
+    }
+}
+
+#[derive(DeriveIden)]
+enum Users {
+    Table,
+    BaseCurrency,
+}
@@ -0,0 +1,80 @@
+use sea_orm_migration::{prelude::*, schema::*};
+
+/// Creates the `asset_price_overrides` table.
+///
+/// Pins the price source of an asset for allocation construction, for tokens whose default
+/// source has bad data. `source` is either a source of `asset_prices` rows (e.g. "coingecko")
+/// or "dex", which prices the token on DEX pairs by `chain` and `contract_address`. At most one
+/// override per asset.
+#[derive(DeriveMigrationName)]
+pub struct Migration;
+
+#[async_trait::async_trait]
+impl MigrationTrait for Migration {
+    async fn up(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        manager
+            .create_table(
+                Table::create()
+                    .table(AssetPriceOverrides::Table)
+                    .if_not_exists()
+                    .col(uuid(AssetPriceOverrides::Id).primary_key().extra("DEFAULT gen_random_uuid()"))
+                    .col(uuid(AssetPriceOverrides::AssetId).not_null())
+                    .col(string(AssetPriceOverrides::Source).not_null())
+                    .col(string_null(AssetPriceOverrides::Chain))
+                    .col(string_null(AssetPriceOverrides::ContractAddress))
+                    .col(string_null(AssetPriceOverrides::Note))
+                    .col(boolean(AssetPriceOverrides::IsActive).default(true).not_null())
+                    .col(timestamp_with_time_zone(AssetPriceOverrides::CreatedAt).default(Expr::current_timestamp()).not_null())
+                    .col(timestamp_with_time_zone(AssetPriceOverrides::UpdatedAt).default(Expr::current_timestamp()).not_null())
+                    .foreign_key(
+                        ForeignKey::create()
+                            .name("fk_asset_price_overrides_asset_id")
+                            .from(AssetPriceOverrides::Table, AssetPriceOverrides::AssetId)
+                            .to(Assets::Table, Assets::Id)
+                            .on_delete(ForeignKeyAction::Cascade)
+                            .on_update(ForeignKeyAction::Cascade),
+                    )
+                    .to_owned(),
+            )
+            .await?;
+
+        manager
+            .create_index(
+                Index::create()
+                    .name("idx_asset_price_overrides_asset_id")
+                    .table(AssetPriceOverrides::Table)
+                    .col(AssetPriceOverrides::AssetId)
+                    .unique()
+                    .to_owned(),
+            )
+            .await?;
+
+        Ok(())
+    }
+
+    async fn down(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        manager
+            .drop_table(Table::drop().table(AssetPriceOverrides::Table).to_owned())
+            .await
+    }
+}
+
+#[derive(DeriveIden)]
+enum AssetPriceOverrides {
+    Table,
+    Id,
+    AssetId,
+    Source,
+    Chain,
+    ContractAddress,
+    Note,
+    IsActive,
+    CreatedAt,
+    UpdatedAt,
+}
+
+#[derive(DeriveIden)]
+enum Assets {
+    Table,
+    Id,
+}
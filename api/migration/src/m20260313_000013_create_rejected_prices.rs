@@ -0,0 +1,96 @@
+use sea_orm_migration::{prelude::*, schema::*};
+
+/// Creates the `rejected_prices` table.
+///
+/// Prices quarantined by the ingestion sanity check because they deviate from the previous
+/// observation of the asset by an implausible factor (e.g. a 1000x spike from a source glitch).
+/// They are kept out of `asset_prices` until an admin reviews them: `status` is "pending",
+/// "accepted" (copied into `asset_prices`) or "dismissed".
+#[derive(DeriveMigrationName)]
+pub struct Migration;
+
+#[async_trait::async_trait]
+impl MigrationTrait for Migration {
+    async fn up(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        manager
+            .create_table(
+                Table::create()
+                    .table(RejectedPrices::Table)
+                    .if_not_exists()
+                    .col(uuid(RejectedPrices::Id).primary_key().extra("DEFAULT gen_random_uuid()"))
+                    .col(uuid(RejectedPrices::AssetId).not_null())
+                    .col(string(RejectedPrices::Source).not_null())
+                    .col(timestamp_with_time_zone(RejectedPrices::Timestamp).not_null())
+                    .col(decimal_len(RejectedPrices::PriceUsd, 30, 12).not_null())
+                    .col(decimal_len(RejectedPrices::ReferencePriceUsd, 30, 12).not_null())
+                    .col(timestamp_with_time_zone(RejectedPrices::ReferenceTimestamp).not_null())
+                    .col(decimal_len(RejectedPrices::DeviationFactor, 30, 4).not_null())
+                    .col(string(RejectedPrices::Status).default("pending").not_null())
+                    .col(timestamp_with_time_zone_null(RejectedPrices::ReviewedAt))
+                    .col(timestamp_with_time_zone(RejectedPrices::CreatedAt).default(Expr::current_timestamp()).not_null())
+                    .foreign_key(
+                        ForeignKey::create()
+                            .name("fk_rejected_prices_asset_id")
+                            .from(RejectedPrices::Table, RejectedPrices::AssetId)
+                            .to(Assets::Table, Assets::Id)
+                            .on_delete(ForeignKeyAction::Cascade)
+                            .on_update(ForeignKeyAction::Cascade),
+                    )
+                    .to_owned(),
+            )
+            .await?;
+
+        manager
+            .create_index(
+                Index::create()
+                    .name("idx_rejected_prices_asset_source_timestamp")
+                    .table(RejectedPrices::Table)
+                    .col(RejectedPrices::AssetId)
+                    .col(RejectedPrices::Source)
+                    .col(RejectedPrices::Timestamp)
+                    .to_owned(),
+            )
+            .await?;
+
+        manager
+            .create_index(
+                Index::create()
+                    .name("idx_rejected_prices_status_created_at")
+                    .table(RejectedPrices::Table)
+                    .col(RejectedPrices::Status)
+                    .col(RejectedPrices::CreatedAt)
+                    .to_owned(),
+            )
+            .await?;
+
+        Ok(())
+    }
+
+    async fn down(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        manager
+            .drop_table(Table::drop().table(RejectedPrices::Table).to_owned())
+            .await
+    }
+}
+
+#[derive(DeriveIden)]
+enum RejectedPrices {
+    Table,
+    Id,
+    AssetId,
+    Source,
+    Timestamp,
+    PriceUsd,
+    ReferencePriceUsd,
+    ReferenceTimestamp,
+    DeviationFactor,
+    Status,
+    ReviewedAt,
+    CreatedAt,
+}
+
+#[derive(DeriveIden)]
+enum Assets {
+    Table,
+    Id,
+}
@@ -0,0 +1,107 @@
+use sea_orm_migration::{prelude::*, schema::*};
+
+/// Creates the `asset_prices_hourly` and `asset_prices_daily` tables.
+///
+/// OHLC aggregates of `asset_prices` per asset, source and UTC hour or day, maintained by the
+/// price rollup job. Long history ranges are served from these instead of the intraday rows.
+/// Both tables share one layout; `bucket_start` is the start of the hour or day.
+#[derive(DeriveMigrationName)]
+pub struct Migration;
+
+fn rollup_table<T: IntoIden + Copy + 'static>(table: T, prefix: &str) -> TableCreateStatement {
+    Table::create()
+        .table(table)
+        .if_not_exists()
+        .col(uuid(PriceRollup::Id).primary_key().extra("DEFAULT gen_random_uuid()"))
+        .col(uuid(PriceRollup::AssetId).not_null())
+        .col(string(PriceRollup::Source).not_null())
+        .col(timestamp_with_time_zone(PriceRollup::BucketStart).not_null())
+        .col(decimal_len(PriceRollup::OpenUsd, 30, 12).not_null())
+        .col(decimal_len(PriceRollup::HighUsd, 30, 12).not_null())
+        .col(decimal_len(PriceRollup::LowUsd, 30, 12).not_null())
+        .col(decimal_len(PriceRollup::CloseUsd, 30, 12).not_null())
+        .col(integer(PriceRollup::Ticks).not_null())
+        .col(timestamp_with_time_zone(PriceRollup::CreatedAt).default(Expr::current_timestamp()).not_null())
+        .col(timestamp_with_time_zone(PriceRollup::UpdatedAt).default(Expr::current_timestamp()).not_null())
+        .foreign_key(
+            ForeignKey::create()
+                .name(format!("fk_{}_asset_id", prefix))
+                .from(table, PriceRollup::AssetId)
+                .to(Assets::Table, Assets::Id)
+                .on_delete(ForeignKeyAction::Cascade)
+                .on_update(ForeignKeyAction::Cascade),
+        )
+        .to_owned()
+}
+
+fn rollup_index<T: IntoIden + Copy + 'static>(table: T, prefix: &str) -> IndexCreateStatement {
+    Index::create()
+        .name(format!("idx_{}_asset_source_bucket", prefix))
+        .table(table)
+        .col(PriceRollup::AssetId)
+        .col(PriceRollup::Source)
+        .col(PriceRollup::BucketStart)
+        .unique()
+        .to_owned()
+}
+
+#[async_trait::async_trait]
+impl MigrationTrait for Migration {
+    async fn up(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        manager
+            .create_table(rollup_table(AssetPricesHourly::Table, "asset_prices_hourly"))
+            .await?;
+        manager
+            .create_index(rollup_index(AssetPricesHourly::Table, "asset_prices_hourly"))
+            .await?;
+
+        manager
+            .create_table(rollup_table(AssetPricesDaily::Table, "asset_prices_daily"))
+            .await?;
+        manager
+            .create_index(rollup_index(AssetPricesDaily::Table, "asset_prices_daily"))
+            .await?;
+
+        Ok(())
+    }
+
+    async fn down(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        manager
+            .drop_table(Table::drop().table(AssetPricesDaily::Table).to_owned())
+            .await?;
+        manager
+            .drop_table(Table::drop().table(AssetPricesHourly::Table).to_owned())
+            .await
+    }
+}
+
+#[derive(DeriveIden, Clone, Copy)]
+enum AssetPricesHourly {
+    Table,
+}
+
+#[derive(DeriveIden, Clone, Copy)]
+enum AssetPricesDaily {
+    Table,
+}
+
+#[derive(DeriveIden)]
+enum PriceRollup {
+    Id,
+    AssetId,
+    Source,
+    BucketStart,
+    OpenUsd,
+    HighUsd,
+    LowUsd,
+    CloseUsd,
+    Ticks,
+    CreatedAt,
+    UpdatedAt,
+}
+
+#[derive(DeriveIden)]
+enum Assets {
+    Table,
+    Id,
+}
@@ -0,0 +1,40 @@
+use sea_orm_migration::{prelude::*, schema::*};
+
+/// Adds a nullable `delisted_at` column to `assets`.
+///
+/// Set when the price provider stops listing a coin; the asset is deactivated at the same
+/// time, so prices are no longer collected for it, while holdings keep being valued at its
+/// last known price.
+#[derive(DeriveMigrationName)]
+pub struct Migration;
+
+#[async_trait::async_trait]
+impl MigrationTrait for Migration {
+    async fn up(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        manager
+            .alter_table(
+                Table::alter()
+                    .table(Assets::Table)
+                    .add_column(timestamp_with_time_zone_null(Assets::DelistedAt))
+                    .to_owned(),
+            )
+            .await
+    }
+
+    async fn down(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        manager
+            .alter_table(
+                Table::alter()
+                    .table(Assets::Table)
+                    .drop_column(Assets::DelistedAt)
+                    .to_owned(),
+            )
+            .await
+    }
+}
+
+#[derive(DeriveIden)]
+enum Assets {
+    Table,
+    DelistedAt,
+}
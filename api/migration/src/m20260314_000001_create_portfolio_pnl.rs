@@ -0,0 +1,83 @@
+use sea_orm_migration::{prelude::*, schema::*};
+
+/// Creates the `portfolio_pnl` table.
+///
+/// Cost basis and profit/loss per asset of a portfolio, computed from the trade, transfer and
+/// income ledgers of its accounts by the portfolio P&L job. Each computation replaces the
+/// portfolio's rows; `computed_at` is when they were computed and `price_usd` the price the
+/// unrealized P&L was valued at (absent when the asset had none).
+#[derive(DeriveMigrationName)]
+pub struct Migration;
+
+#[async_trait::async_trait]
+impl MigrationTrait for Migration {
+    async fn up(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        manager
+            .create_table(
+                Table::create()
+                    .table(PortfolioPnl::Table)
+                    .if_not_exists()
+                    .col(uuid(PortfolioPnl::Id).primary_key().extra("DEFAULT gen_random_uuid()"))
+                    .col(uuid(PortfolioPnl::PortfolioId).not_null())
+                    .col(string(PortfolioPnl::Asset).not_null())
+                    .col(decimal(PortfolioPnl::Quantity).not_null())
+                    .col(decimal(PortfolioPnl::CostBasisUsd).not_null())
+                    .col(decimal(PortfolioPnl::RealizedPnlUsd).not_null())
+                    .col(decimal_null(PortfolioPnl::PriceUsd))
+                    .col(decimal_null(PortfolioPnl::UnrealizedPnlUsd))
+                    .col(decimal(PortfolioPnl::UnmatchedQuantity).not_null())
+                    .col(timestamp_with_time_zone(PortfolioPnl::ComputedAt).not_null())
+                    .foreign_key(
+                        ForeignKey::create()
+                            .name("fk_portfolio_pnl_portfolio_id")
+                            .from(PortfolioPnl::Table, PortfolioPnl::PortfolioId)
+                            .to(Portfolios::Table, Portfolios::Id)
+                            .on_delete(ForeignKeyAction::Cascade)
+                            .on_update(ForeignKeyAction::Cascade),
+                    )
+                    .to_owned(),
+            )
+            .await?;
+
+        manager
+            .create_index(
+                Index::create()
+                    .name("idx_portfolio_pnl_portfolio_asset")
+                    .table(PortfolioPnl::Table)
+                    .col(PortfolioPnl::PortfolioId)
+                    .col(PortfolioPnl::Asset)
+                    .unique()
+                    .to_owned(),
+            )
+            .await?;
+
+        Ok(())
+    }
+
+    async fn down(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        manager
+            .drop_table(Table::drop().table(PortfolioPnl::Table).to_owned())
+            .await
+    }
+}
+
+#[derive(DeriveIden)]
+enum PortfolioPnl {
+    Table,
+    Id,
+    PortfolioId,
+    Asset,
+    Quantity,
+    CostBasisUsd,
+    RealizedPnlUsd,
+    PriceUsd,
+    UnrealizedPnlUsd,
+    UnmatchedQuantity,
+    ComputedAt,
+}
+
+#[derive(DeriveIden)]
+enum Portfolios {
+    Table,
+    Id,
+}
@@ -0,0 +1,169 @@
+use sea_orm_migration::{prelude::*, schema::*};
+
+/// Creates the `tax_lots` and `tax_disposals` tables and adds `tax_lot_method` to `users`.
+///
+/// Lots are the acquisitions of an asset per account (buys, deposits, income) with their USD
+/// cost and the quantity still held; disposals record which lot each sold, spent or withdrawn
+/// quantity came from, chosen by the owner's method ("fifo", "lifo" or "hifo"). Both are
+/// rebuilt from the account's ledger by the tax lot job, so a changed method applies to the
+/// whole history.
+#[derive(DeriveMigrationName)]
+pub struct Migration;
+
+#[async_trait::async_trait]
+impl MigrationTrait for Migration {
+    async fn up(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        manager
+            .alter_table(
+                Table::alter()
+                    .table(Users::Table)
+                    .add_column(string_len(Users::TaxLotMethod, 8).default("fifo").not_null())
+                    .to_owned(),
+            )
+            .await?;
+
+        manager
+            .create_table(
+                Table::create()
+                    .table(TaxLots::Table)
+                    .if_not_exists()
+                    .col(uuid(TaxLots::Id).primary_key().extra("DEFAULT gen_random_uuid()"))
+                    .col(uuid(TaxLots::AccountId).not_null())
+                    .col(string(TaxLots::Asset).not_null())
+                    .col(timestamp_with_time_zone(TaxLots::AcquiredAt).not_null())
+                    .col(decimal(TaxLots::Quantity).not_null())
+                    .col(decimal(TaxLots::RemainingQuantity).not_null())
+                    .col(decimal(TaxLots::CostBasisUsd).not_null())
+                    .col(timestamp_with_time_zone(TaxLots::CreatedAt).default(Expr::current_timestamp()).not_null())
+                    .foreign_key(
+                        ForeignKey::create()
+                            .name("fk_tax_lots_account_id")
+                            .from(TaxLots::Table, TaxLots::AccountId)
+                            .to(Accounts::Table, Accounts::Id)
+                            .on_delete(ForeignKeyAction::Cascade)
+                            .on_update(ForeignKeyAction::Cascade),
+                    )
+                    .to_owned(),
+            )
+            .await?;
+
+        manager
+            .create_index(
+                Index::create()
+                    .name("idx_tax_lots_account_asset_acquired_at")
+                    .table(TaxLots::Table)
+                    .col(TaxLots::AccountId)
+                    .col(TaxLots::Asset)
+                    .col(TaxLots::AcquiredAt)
+                    .to_owned(),
+            )
+            .await?;
+
+        manager
+            .create_table(
+                Table::create()
+                    .table(TaxDisposals::Table)
+                    .if_not_exists()
+                    .col(uuid(TaxDisposals::Id).primary_key().extra("DEFAULT gen_random_uuid()"))
+                    .col(uuid(TaxDisposals::AccountId).not_null())
+                    .col(uuid_null(TaxDisposals::LotId))
+                    .col(string(TaxDisposals::Asset).not_null())
+                    .col(string(TaxDisposals::Kind).not_null())
+                    .col(decimal(TaxDisposals::Quantity).not_null())
+                    .col(decimal(TaxDisposals::ProceedsUsd).not_null())
+                    .col(decimal(TaxDisposals::CostBasisUsd).not_null())
+                    .col(timestamp_with_time_zone_null(TaxDisposals::AcquiredAt))
+                    .col(timestamp_with_time_zone(TaxDisposals::DisposedAt).not_null())
+                    .col(timestamp_with_time_zone(TaxDisposals::CreatedAt).default(Expr::current_timestamp()).not_null())
+                    .foreign_key(
+                        ForeignKey::create()
+                            .name("fk_tax_disposals_account_id")
+                            .from(TaxDisposals::Table, TaxDisposals::AccountId)
+                            .to(Accounts::Table, Accounts::Id)
+                            .on_delete(ForeignKeyAction::Cascade)
+                            .on_update(ForeignKeyAction::Cascade),
+                    )
+                    .foreign_key(
+                        ForeignKey::create()
+                            .name("fk_tax_disposals_lot_id")
+                            .from(TaxDisposals::Table, TaxDisposals::LotId)
+                            .to(TaxLots::Table, TaxLots::Id)
+                            .on_delete(ForeignKeyAction::Cascade)
+                            .on_update(ForeignKeyAction::Cascade),
+                    )
+                    .to_owned(),
+            )
+            .await?;
+
+        manager
+            .create_index(
+                Index::create()
+                    .name("idx_tax_disposals_account_disposed_at")
+                    .table(TaxDisposals::Table)
+                    .col(TaxDisposals::AccountId)
+                    .col(TaxDisposals::DisposedAt)
+                    .to_owned(),
+            )
+            .await?;
+
+        Ok(())
+    }
+
+    async fn down(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        manager
+            .drop_table(Table::drop().table(TaxDisposals::Table).to_owned())
+            .await?;
+        manager
+            .drop_table(Table::drop().table(TaxLots::Table).to_owned())
+            .await?;
+        manager
+            .alter_table(
+                Table::alter()
+                    .table(Users::Table)
+                    .drop_column(Users::TaxLotMethod)
+                    .to_owned(),
+            )
+            .await
+    }
+}
+
+#[derive(DeriveIden)]
+enum Users {
+    Table,
+    TaxLotMethod,
+}
+
+#[derive(DeriveIden)]
+enum TaxLots {
+    Table,
+    Id,
+    AccountId,
+    Asset,
+    AcquiredAt,
+    Quantity,
+    RemainingQuantity,
+    CostBasisUsd,
+    CreatedAt,
+}
+
+#[derive(DeriveIden)]
+enum TaxDisposals {
+    Table,
+    Id,
+    AccountId,
+    LotId,
+    Asset,
+    Kind,
+    Quantity,
+    ProceedsUsd,
+    CostBasisUsd,
+    AcquiredAt,
+    DisposedAt,
+    CreatedAt,
+}
+
+#[derive(DeriveIden)]
+enum Accounts {
+    Table,
+    Id,
+}
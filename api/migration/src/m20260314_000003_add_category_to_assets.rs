@@ -0,0 +1,66 @@
+use sea_orm_migration::{prelude::*, schema::*};
+
+/// Adds a nullable `category` column to `assets` (e.g. "l1", "defi", "stablecoin", "meme",
+/// "rwa").
+///
+/// Categories are set by administrators; allocation construction copies them onto the
+/// holdings so allocations can be broken down by category. Assets already typed as stablecoins
+/// start in the "stablecoin" category.
+#[derive(DeriveMigrationName)]
+pub struct Migration;
+
+#[async_trait::async_trait]
+impl MigrationTrait for Migration {
+    async fn up(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        manager
+            .alter_table(
+                Table::alter()
+                    .table(Assets::Table)
+                    .add_column(string_len_null(Assets::Category, 50))
+                    .to_owned(),
+            )
+            .await?;
+
+        manager
+            .create_index(
+                Index::create()
+                    .name("idx_assets_category")
+                    .table(Assets::Table)
+                    .col(Assets::Category)
+                    .to_owned(),
+            )
+            .await?;
+
+        manager
+            .exec_stmt(
+                Query::update()
+                    .table(Assets::Table)
+                    .value(Assets::Category, "stablecoin")
+                    .and_where(Expr::col(Assets::AssetType).eq("stablecoin"))
+                    .to_owned(),
+            )
+            .await
+    }
+
+    async fn down(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        manager
+            .drop_index(Index::drop().name("idx_assets_category").table(Assets::Table).to_owned())
+            .await?;
+
+        manager
+            .alter_table(
+                Table::alter()
+                    .table(Assets::Table)
+                    .drop_column(Assets::Category)
+                    .to_owned(),
+            )
+            .await
+    }
+}
+
+#[derive(DeriveIden)]
+enum Assets {
+    Table,
+    AssetType,
+    Category,
+}
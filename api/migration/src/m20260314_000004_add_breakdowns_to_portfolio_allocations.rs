@@ -0,0 +1,44 @@
+use sea_orm_migration::{prelude::*, schema::*};
+
+/// Adds nullable `chain_breakdown` and `venue_breakdown` JSON columns to
+/// `portfolio_allocations`.
+///
+/// Construction stores the allocation grouped by chain (e.g. "ethereum", "solana", or
+/// "off_chain" for exchange holdings) and by venue (each exchange, or "self_custody" for
+/// wallets) next to the per-asset holdings. NULL on allocations constructed before.
+#[derive(DeriveMigrationName)]
+pub struct Migration;
+
+#[async_trait::async_trait]
+impl MigrationTrait for Migration {
+    async fn up(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        manager
+            .alter_table(
+                Table::alter()
+                    .table(PortfolioAllocations::Table)
+                    .add_column(json_null(PortfolioAllocations::ChainBreakdown))
+                    .add_column(json_null(PortfolioAllocations::VenueBreakdown))
+                    .to_owned(),
+            )
+            .await
+    }
+
+    async fn down(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        manager
+            .alter_table(
+                Table::alter()
+                    .table(PortfolioAllocations::Table)
+                    .drop_column(PortfolioAllocations::ChainBreakdown)
+                    .drop_column(PortfolioAllocations::VenueBreakdown)
+                    .to_owned(),
+            )
+            .await
+    }
+}
+
+#[derive(DeriveIden)]
+enum PortfolioAllocations {
+    Table,
+    ChainBreakdown,
+    VenueBreakdown,
+}
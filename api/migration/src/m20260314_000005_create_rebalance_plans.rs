@@ -0,0 +1,81 @@
+use sea_orm_migration::{prelude::*, schema::*};
+
+/// Creates the `rebalance_plans` table.
+///
+/// Rebalance plans generated for a portfolio: the guardrail-adjusted target weights, the
+/// planned buys and sells (asset, side, quantity, USD amount, account) and notes on what the
+/// plan could not place. Plans are kept so they can be reviewed and exported later.
+#[derive(DeriveMigrationName)]
+pub struct Migration;
+
+#[async_trait::async_trait]
+impl MigrationTrait for Migration {
+    async fn up(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        manager
+            .create_table(
+                Table::create()
+                    .table(RebalancePlans::Table)
+                    .if_not_exists()
+                    .col(uuid(RebalancePlans::Id).primary_key().extra("DEFAULT gen_random_uuid()"))
+                    .col(uuid(RebalancePlans::PortfolioId).not_null())
+                    .col(decimal(RebalancePlans::TotalValueUsd).not_null())
+                    .col(decimal(RebalancePlans::MinTradeUsd).not_null())
+                    .col(json(RebalancePlans::Targets).not_null())
+                    .col(json(RebalancePlans::Trades).not_null())
+                    .col(integer(RebalancePlans::SkippedTrades).not_null())
+                    .col(json(RebalancePlans::Notes).not_null())
+                    .col(timestamp_with_time_zone(RebalancePlans::AllocationAsOf).not_null())
+                    .col(timestamp_with_time_zone(RebalancePlans::CreatedAt).not_null())
+                    .foreign_key(
+                        ForeignKey::create()
+                            .name("fk_rebalance_plans_portfolio_id")
+                            .from(RebalancePlans::Table, RebalancePlans::PortfolioId)
+                            .to(Portfolios::Table, Portfolios::Id)
+                            .on_delete(ForeignKeyAction::Cascade)
+                            .on_update(ForeignKeyAction::Cascade),
+                    )
+                    .to_owned(),
+            )
+            .await?;
+
+        manager
+            .create_index(
+                Index::create()
+                    .name("idx_rebalance_plans_portfolio_created")
+                    .table(RebalancePlans::Table)
+                    .col(RebalancePlans::PortfolioId)
+                    .col(RebalancePlans::CreatedAt)
+                    .to_owned(),
+            )
+            .await?;
+
+        Ok(())
+    }
+
+    async fn down(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        manager
+            .drop_table(Table::drop().table(RebalancePlans::Table).to_owned())
+            .await
+    }
+}
+
+#[derive(DeriveIden)]
+enum RebalancePlans {
+    Table,
+    Id,
+    PortfolioId,
+    TotalValueUsd,
+    MinTradeUsd,
+    Targets,
+    Trades,
+    SkippedTrades,
+    Notes,
+    AllocationAsOf,
+    CreatedAt,
+}
+
+#[derive(DeriveIden)]
+enum Portfolios {
+    Table,
+    Id,
+}
@@ -0,0 +1,92 @@
+use sea_orm_migration::{prelude::*, schema::*};
+
+/// Creates the `fees` table.
+///
+/// Fees paid by an account, captured during sync: trading fees from exchange trade fills,
+/// network fees charged on exchange withdrawals and gas paid by wallet transactions. Each
+/// fee is in the asset it was paid in, with its USD value at the time. `reference` names the
+/// trade, withdrawal or transaction it came from so recording it again is a no-op.
+#[derive(DeriveMigrationName)]
+pub struct Migration;
+
+#[async_trait::async_trait]
+impl MigrationTrait for Migration {
+    async fn up(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        manager
+            .create_table(
+                Table::create()
+                    .table(Fees::Table)
+                    .if_not_exists()
+                    .col(uuid(Fees::Id).primary_key().extra("DEFAULT gen_random_uuid()"))
+                    .col(uuid(Fees::AccountId).not_null())
+                    .col(string(Fees::FeeType).not_null())
+                    .col(string(Fees::Asset).not_null())
+                    .col(decimal(Fees::Amount).not_null())
+                    .col(decimal_null(Fees::ValueUsd))
+                    .col(string(Fees::Reference).not_null())
+                    .col(timestamp_with_time_zone(Fees::OccurredAt).not_null())
+                    .col(timestamp_with_time_zone(Fees::CreatedAt).default(Expr::current_timestamp()).not_null())
+                    .foreign_key(
+                        ForeignKey::create()
+                            .name("fk_fees_account_id")
+                            .from(Fees::Table, Fees::AccountId)
+                            .to(Accounts::Table, Accounts::Id)
+                            .on_delete(ForeignKeyAction::Cascade)
+                            .on_update(ForeignKeyAction::Cascade),
+                    )
+                    .to_owned(),
+            )
+            .await?;
+
+        manager
+            .create_index(
+                Index::create()
+                    .name("idx_fees_account_reference")
+                    .table(Fees::Table)
+                    .col(Fees::AccountId)
+                    .col(Fees::Reference)
+                    .unique()
+                    .to_owned(),
+            )
+            .await?;
+
+        manager
+            .create_index(
+                Index::create()
+                    .name("idx_fees_account_occurred_at")
+                    .table(Fees::Table)
+                    .col(Fees::AccountId)
+                    .col(Fees::OccurredAt)
+                    .to_owned(),
+            )
+            .await?;
+
+        Ok(())
+    }
+
+    async fn down(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        manager
+            .drop_table(Table::drop().table(Fees::Table).to_owned())
+            .await
+    }
+}
+
+#[derive(DeriveIden)]
+enum Fees {
+    Table,
+    Id,
+    AccountId,
+    FeeType,
+    Asset,
+    Amount,
+    ValueUsd,
+    Reference,
+    OccurredAt,
+    CreatedAt,
+}
+
+#[derive(DeriveIden)]
+enum Accounts {
+    Table,
+    Id,
+}
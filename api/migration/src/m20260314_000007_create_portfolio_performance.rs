@@ -0,0 +1,84 @@
+use sea_orm_migration::{prelude::*, schema::*};
+
+/// Creates the `portfolio_performance` table.
+///
+/// Daily performance of a portfolio per snapshot date, computed from its snapshots and the
+/// deposits and withdrawals of its accounts by the daily performance job: total value, net
+/// flow since the previous date, the time-weighted return of the day, the cumulative return
+/// since the first snapshot and the drawdown from the highest cumulative growth so far. Each
+/// computation replaces the portfolio's rows.
+#[derive(DeriveMigrationName)]
+pub struct Migration;
+
+#[async_trait::async_trait]
+impl MigrationTrait for Migration {
+    async fn up(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        manager
+            .create_table(
+                Table::create()
+                    .table(PortfolioPerformance::Table)
+                    .if_not_exists()
+                    .col(uuid(PortfolioPerformance::Id).primary_key().extra("DEFAULT gen_random_uuid()"))
+                    .col(uuid(PortfolioPerformance::PortfolioId).not_null())
+                    .col(date(PortfolioPerformance::Date).not_null())
+                    .col(decimal(PortfolioPerformance::TotalValueUsd).not_null())
+                    .col(decimal(PortfolioPerformance::NetFlowUsd).not_null())
+                    .col(decimal_null(PortfolioPerformance::DailyReturn))
+                    .col(decimal(PortfolioPerformance::CumulativeReturn).not_null())
+                    .col(decimal(PortfolioPerformance::Drawdown).not_null())
+                    .col(integer(PortfolioPerformance::UnpricedFlows).not_null().default(0))
+                    .col(timestamp_with_time_zone(PortfolioPerformance::ComputedAt).not_null())
+                    .foreign_key(
+                        ForeignKey::create()
+                            .name("fk_portfolio_performance_portfolio_id")
+                            .from(PortfolioPerformance::Table, PortfolioPerformance::PortfolioId)
+                            .to(Portfolios::Table, Portfolios::Id)
+                            .on_delete(ForeignKeyAction::Cascade)
+                            .on_update(ForeignKeyAction::Cascade),
+                    )
+                    .to_owned(),
+            )
+            .await?;
+
+        manager
+            .create_index(
+                Index::create()
+                    .name("idx_portfolio_performance_portfolio_date")
+                    .table(PortfolioPerformance::Table)
+                    .col(PortfolioPerformance::PortfolioId)
+                    .col(PortfolioPerformance::Date)
+                    .unique()
+                    .to_owned(),
+            )
+            .await?;
+
+        Ok(())
+    }
+
+    async fn down(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        manager
+            .drop_table(Table::drop().table(PortfolioPerformance::Table).to_owned())
+            .await
+    }
+}
+
+#[derive(DeriveIden)]
+enum PortfolioPerformance {
+    Table,
+    Id,
+    PortfolioId,
+    Date,
+    TotalValueUsd,
+    NetFlowUsd,
+    DailyReturn,
+    CumulativeReturn,
+    Drawdown,
+    UnpricedFlows,
+    ComputedAt,
+}
+
+#[derive(DeriveIden)]
+enum Portfolios {
+    Table,
+    Id,
+}
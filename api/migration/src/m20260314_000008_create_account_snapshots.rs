@@ -0,0 +1,75 @@
+use sea_orm_migration::{prelude::*, schema::*};
+
+/// Creates the `account_snapshots` table.
+///
+/// Daily snapshot of one account's holdings and value, taken alongside the EOD portfolio
+/// snapshots so performance can be followed per wallet or exchange. Holdings are the
+/// account's synced quantities per asset, valued at the latest stored price; a second
+/// snapshot of the same account and date replaces the first.
+#[derive(DeriveMigrationName)]
+pub struct Migration;
+
+#[async_trait::async_trait]
+impl MigrationTrait for Migration {
+    async fn up(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        manager
+            .create_table(
+                Table::create()
+                    .table(AccountSnapshots::Table)
+                    .if_not_exists()
+                    .col(uuid(AccountSnapshots::Id).primary_key().extra("DEFAULT gen_random_uuid()"))
+                    .col(uuid(AccountSnapshots::AccountId).not_null())
+                    .col(date(AccountSnapshots::SnapshotDate).not_null())
+                    .col(decimal(AccountSnapshots::TotalValueUsd).not_null())
+                    .col(json(AccountSnapshots::Holdings).not_null())
+                    .col(timestamp_with_time_zone(AccountSnapshots::CreatedAt).default(Expr::current_timestamp()).not_null())
+                    .foreign_key(
+                        ForeignKey::create()
+                            .name("fk_account_snapshots_account_id")
+                            .from(AccountSnapshots::Table, AccountSnapshots::AccountId)
+                            .to(Accounts::Table, Accounts::Id)
+                            .on_delete(ForeignKeyAction::Cascade)
+                            .on_update(ForeignKeyAction::Cascade),
+                    )
+                    .to_owned(),
+            )
+            .await?;
+
+        manager
+            .create_index(
+                Index::create()
+                    .name("idx_account_snapshots_account_date")
+                    .table(AccountSnapshots::Table)
+                    .col(AccountSnapshots::AccountId)
+                    .col(AccountSnapshots::SnapshotDate)
+                    .unique()
+                    .to_owned(),
+            )
+            .await?;
+
+        Ok(())
+    }
+
+    async fn down(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        manager
+            .drop_table(Table::drop().table(AccountSnapshots::Table).to_owned())
+            .await
+    }
+}
+
+#[derive(DeriveIden)]
+enum AccountSnapshots {
+    Table,
+    Id,
+    AccountId,
+    SnapshotDate,
+    TotalValueUsd,
+    Holdings,
+    CreatedAt,
+}
+
+#[derive(DeriveIden)]
+enum Accounts {
+    Table,
+    Id,
+}
@@ -0,0 +1,66 @@
+use sea_orm_migration::prelude::*;
+
+/// Allows several "hourly" snapshots of a portfolio per day.
+///
+/// Portfolios can be snapshotted every 6 hours or every hour (`snapshot_cadence` in their
+/// settings); those intraday snapshots share the snapshot date and type, so the unique index
+/// on `(portfolio_id, snapshot_date, snapshot_type)` now leaves "hourly" rows out. The other
+/// types stay one per portfolio and date.
+#[derive(DeriveMigrationName)]
+pub struct Migration;
+
+#[async_trait::async_trait]
+impl MigrationTrait for Migration {
+    async fn up(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        manager
+            .drop_index(Index::drop().name("idx_snapshots_unique").table(Snapshots::Table).to_owned())
+            .await?;
+
+        // Partial index: not expressible with the schema builder
+        manager
+            .get_connection()
+            .execute_unprepared(
+                "CREATE UNIQUE INDEX idx_snapshots_unique ON snapshots (portfolio_id, snapshot_date, snapshot_type) \
+                 WHERE snapshot_type <> 'hourly'",
+            )
+            .await?;
+        Ok(())
+    }
+
+    async fn down(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        // Keep only the latest "hourly" snapshot of each portfolio and date
+        manager
+            .get_connection()
+            .execute_unprepared(
+                "DELETE FROM snapshots s USING snapshots t \
+                 WHERE s.snapshot_type = 'hourly' AND t.snapshot_type = 'hourly' \
+                 AND s.portfolio_id = t.portfolio_id AND s.snapshot_date = t.snapshot_date \
+                 AND (s.created_at, s.id) < (t.created_at, t.id)",
+            )
+            .await?;
+
+        manager
+            .drop_index(Index::drop().name("idx_snapshots_unique").table(Snapshots::Table).to_owned())
+            .await?;
+        manager
+            .create_index(
+                Index::create()
+                    .name("idx_snapshots_unique")
+                    .table(Snapshots::Table)
+                    .col(Snapshots::PortfolioId)
+                    .col(Snapshots::SnapshotDate)
+                    .col(Snapshots::SnapshotType)
+                    .unique()
+                    .to_owned(),
+            )
+            .await
+    }
+}
+
+#[derive(DeriveIden)]
+enum Snapshots {
+    Table,
+    PortfolioId,
+    SnapshotDate,
+    SnapshotType,
+}
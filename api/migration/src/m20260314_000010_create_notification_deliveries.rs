@@ -0,0 +1,112 @@
+use sea_orm_migration::{prelude::*, schema::*};
+
+/// Adds `notification_channels` to `users` and creates the `notification_deliveries` table.
+///
+/// `notification_channels` holds the channels a user's recommendations are delivered to (in-app
+/// notifications, a webhook). Every delivery attempt is logged in `notification_deliveries`
+/// with a `dedup_key` identifying the advice, so advice already sent on a channel is not sent
+/// again while the dedup window lasts.
+#[derive(DeriveMigrationName)]
+pub struct Migration;
+
+#[async_trait::async_trait]
+impl MigrationTrait for Migration {
+    async fn up(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        manager
+            .alter_table(
+                Table::alter()
+                    .table(Users::Table)
+                    .add_column(json_null(Users::NotificationChannels))
+                    .to_owned(),
+            )
+            .await?;
+
+        manager
+            .create_table(
+                Table::create()
+                    .table(NotificationDeliveries::Table)
+                    .if_not_exists()
+                    .col(uuid(NotificationDeliveries::Id).primary_key().extra("DEFAULT gen_random_uuid()"))
+                    .col(uuid(NotificationDeliveries::UserId).not_null())
+                    .col(uuid_null(NotificationDeliveries::RecommendationId))
+                    .col(string(NotificationDeliveries::Channel).not_null())
+                    .col(string(NotificationDeliveries::DedupKey).not_null())
+                    .col(string(NotificationDeliveries::Status).not_null())
+                    .col(text_null(NotificationDeliveries::Error))
+                    .col(timestamp_with_time_zone(NotificationDeliveries::CreatedAt).default(Expr::current_timestamp()).not_null())
+                    .foreign_key(
+                        ForeignKey::create()
+                            .name("fk_notification_deliveries_user_id")
+                            .from(NotificationDeliveries::Table, NotificationDeliveries::UserId)
+                            .to(Users::Table, Users::Id)
+                            .on_delete(ForeignKeyAction::Cascade)
+                            .on_update(ForeignKeyAction::Cascade),
+                    )
+                    .foreign_key(
+                        ForeignKey::create()
+                            .name("fk_notification_deliveries_recommendation_id")
+                            .from(NotificationDeliveries::Table, NotificationDeliveries::RecommendationId)
+                            .to(Recommendations::Table, Recommendations::Id)
+                            .on_delete(ForeignKeyAction::SetNull)
+                            .on_update(ForeignKeyAction::Cascade),
+                    )
+                    .to_owned(),
+            )
+            .await?;
+
+        manager
+            .create_index(
+                Index::create()
+                    .name("idx_notification_deliveries_user_dedup")
+                    .table(NotificationDeliveries::Table)
+                    .col(NotificationDeliveries::UserId)
+                    .col(NotificationDeliveries::DedupKey)
+                    .col(NotificationDeliveries::CreatedAt)
+                    .to_owned(),
+            )
+            .await?;
+
+        Ok(())
+    }
+
+    async fn down(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        manager
+            .drop_table(Table::drop().table(NotificationDeliveries::Table).to_owned())
+            .await?;
+
+        manager
+            .alter_table(
+                Table::alter()
+                    .table(Users::Table)
+                    .drop_column(Users::NotificationChannels)
+                    .to_owned(),
+            )
+            .await
+    }
+}
+
+#[derive(DeriveIden)]
+enum NotificationDeliveries {
+    Table,
+    Id,
+    UserId,
+    RecommendationId,
+    Channel,
+    DedupKey,
+    Status,
+    Error,
+    CreatedAt,
+}
+
+#[derive(DeriveIden)]
+enum Users {
+    Table,
+    Id,
+    NotificationChannels,
+}
+
+#[derive(DeriveIden)]
+enum Recommendations {
+    Table,
+    Id,
+}
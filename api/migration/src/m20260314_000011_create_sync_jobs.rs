@@ -0,0 +1,116 @@
+use sea_orm_migration::{prelude::*, schema::*};
+
+/// Creates the `sync_jobs` table.
+///
+/// An account sync requested through the API is queued as a sync job and run in the
+/// background; the job row records its status and the stage the sync has reached, so clients
+/// poll it instead of holding the request open while wallets are scanned. A partial unique
+/// index allows one queued or running job per account.
+#[derive(DeriveMigrationName)]
+pub struct Migration;
+
+#[async_trait::async_trait]
+impl MigrationTrait for Migration {
+    async fn up(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        manager
+            .create_table(
+                Table::create()
+                    .table(SyncJobs::Table)
+                    .if_not_exists()
+                    .col(uuid(SyncJobs::Id).primary_key().extra("DEFAULT gen_random_uuid()"))
+                    .col(uuid(SyncJobs::AccountId).not_null())
+                    .col(uuid(SyncJobs::UserId).not_null())
+                    .col(string(SyncJobs::Status).not_null())
+                    .col(string_null(SyncJobs::Stage))
+                    .col(integer(SyncJobs::StepsCompleted).default(0).not_null())
+                    .col(integer(SyncJobs::StepsTotal).not_null())
+                    .col(integer_null(SyncJobs::HoldingsCount))
+                    .col(text_null(SyncJobs::Error))
+                    .col(string_null(SyncJobs::ErrorKind))
+                    .col(timestamp_with_time_zone(SyncJobs::CreatedAt).default(Expr::current_timestamp()).not_null())
+                    .col(timestamp_with_time_zone_null(SyncJobs::StartedAt))
+                    .col(timestamp_with_time_zone_null(SyncJobs::FinishedAt))
+                    .foreign_key(
+                        ForeignKey::create()
+                            .name("fk_sync_jobs_account_id")
+                            .from(SyncJobs::Table, SyncJobs::AccountId)
+                            .to(Accounts::Table, Accounts::Id)
+                            .on_delete(ForeignKeyAction::Cascade)
+                            .on_update(ForeignKeyAction::Cascade),
+                    )
+                    .foreign_key(
+                        ForeignKey::create()
+                            .name("fk_sync_jobs_user_id")
+                            .from(SyncJobs::Table, SyncJobs::UserId)
+                            .to(Users::Table, Users::Id)
+                            .on_delete(ForeignKeyAction::Cascade)
+                            .on_update(ForeignKeyAction::Cascade),
+                    )
+                    .to_owned(),
+            )
+            .await?;
+
+        manager
+            .create_index(
+                Index::create()
+                    .name("idx_sync_jobs_account_created_at")
+                    .table(SyncJobs::Table)
+                    .col(SyncJobs::AccountId)
+                    .col(SyncJobs::CreatedAt)
+                    .to_owned(),
+            )
+            .await?;
+
+        // At most one queued or running job per account, so concurrent requests cannot start
+        // two syncs of it
+        manager
+            .create_index(
+                Index::create()
+                    .name("idx_sync_jobs_active_account")
+                    .table(SyncJobs::Table)
+                    .col(SyncJobs::AccountId)
+                    .unique()
+                    .and_where(Expr::col(SyncJobs::Status).is_in(["queued", "running"]))
+                    .to_owned(),
+            )
+            .await?;
+
+        Ok(())
+    }
+
+    async fn down(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        manager
+            .drop_table(Table::drop().table(SyncJobs::Table).to_owned())
+            .await
+    }
+}
+
+#[derive(DeriveIden)]
+enum SyncJobs {
+    Table,
+    Id,
+    AccountId,
+    UserId,
+    Status,
+    Stage,
+    StepsCompleted,
+    StepsTotal,
+    HoldingsCount,
+    Error,
+    ErrorKind,
+    CreatedAt,
+    StartedAt,
+    FinishedAt,
+}
+
+#[derive(DeriveIden)]
+enum Accounts {
+    Table,
+    Id,
+}
+
+#[derive(DeriveIden)]
+enum Users {
+    Table,
+    Id,
+}
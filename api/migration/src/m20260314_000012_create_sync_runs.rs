@@ -0,0 +1,81 @@
+use sea_orm_migration::{prelude::*, schema::*};
+
+/// Creates the `sync_runs` table.
+///
+/// Every account sync, whether queued for one account or by sync-all, records a sync run
+/// with its timing, outcome and the balances read per chain, so a bad set of holdings can be
+/// traced back to the sync that stored it.
+#[derive(DeriveMigrationName)]
+pub struct Migration;
+
+#[async_trait::async_trait]
+impl MigrationTrait for Migration {
+    async fn up(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        manager
+            .create_table(
+                Table::create()
+                    .table(SyncRuns::Table)
+                    .if_not_exists()
+                    .col(uuid(SyncRuns::Id).primary_key().extra("DEFAULT gen_random_uuid()"))
+                    .col(uuid(SyncRuns::AccountId).not_null())
+                    .col(timestamp_with_time_zone(SyncRuns::StartedAt).not_null())
+                    .col(timestamp_with_time_zone(SyncRuns::FinishedAt).not_null())
+                    .col(boolean(SyncRuns::Success).not_null())
+                    .col(integer(SyncRuns::HoldingsCount).default(0).not_null())
+                    .col(string_null(SyncRuns::Stage))
+                    .col(text_null(SyncRuns::Error))
+                    .col(string_null(SyncRuns::ErrorKind))
+                    .col(json_null(SyncRuns::Chains))
+                    .foreign_key(
+                        ForeignKey::create()
+                            .name("fk_sync_runs_account_id")
+                            .from(SyncRuns::Table, SyncRuns::AccountId)
+                            .to(Accounts::Table, Accounts::Id)
+                            .on_delete(ForeignKeyAction::Cascade)
+                            .on_update(ForeignKeyAction::Cascade),
+                    )
+                    .to_owned(),
+            )
+            .await?;
+
+        manager
+            .create_index(
+                Index::create()
+                    .name("idx_sync_runs_account_started_at")
+                    .table(SyncRuns::Table)
+                    .col(SyncRuns::AccountId)
+                    .col(SyncRuns::StartedAt)
+                    .to_owned(),
+            )
+            .await?;
+
+        Ok(())
+    }
+
+    async fn down(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        manager
+            .drop_table(Table::drop().table(SyncRuns::Table).to_owned())
+            .await
+    }
+}
+
+#[derive(DeriveIden)]
+enum SyncRuns {
+    Table,
+    Id,
+    AccountId,
+    StartedAt,
+    FinishedAt,
+    Success,
+    HoldingsCount,
+    Stage,
+    Error,
+    ErrorKind,
+    Chains,
+}
+
+#[derive(DeriveIden)]
+enum Accounts {
+    Table,
+    Id,
+}
@@ -84,6 +84,22 @@ impl RateLimiter {
         )
     }
 
+    /// Create a rate limiter for Esplora API calls (Bitcoin address and UTXO lookups)
+    pub fn esplora() -> Self {
+        Self::new(
+            2,                           // Max 2 concurrent requests
+            Duration::from_millis(200),  // 200ms delay; xpub scans make many small requests
+        )
+    }
+
+    /// Create a rate limiter for Cosmos LCD (REST) calls
+    pub fn cosmos_lcd() -> Self {
+        Self::new(
+            3,                           // Max 3 concurrent requests
+            Duration::from_millis(100),  // 100ms delay for public endpoint protection
+        )
+    }
+
     /// Acquire permission to make a request
     ///
     /// This will wait until a permit is available and then impose the minimum delay
@@ -0,0 +1,193 @@
+//! Aave v3 supply and borrow positions of EVM wallets.
+//!
+//! The market's `AaveProtocolDataProvider` is looked up through its `PoolAddressesProvider`
+//! (the one address that never changes across upgrades), then every reserve is read with
+//! `getUserReserveData`. Supplied amounts (the aToken balance, interest included) are reported
+//! as [`HOLDING_SOURCE_SUPPLIED`] holdings of the underlying asset, and debt (stable + variable)
+//! as negative [`HOLDING_SOURCE_BORROWED`] holdings, so a portfolio's value is its net exposure.
+
+use super::defi::{DefiAdapter, DefiContext, DefiPositions};
+use super::evm::EvmChain;
+use super::Balance;
+use crate::domain::{HOLDING_SOURCE_BORROWED, HOLDING_SOURCE_SUPPLIED};
+use crate::helpers::balance_normalization::normalize_token_balance;
+use alloy::{
+    primitives::{Address, U256},
+    providers::ProviderBuilder,
+    sol,
+};
+use async_trait::async_trait;
+use rust_decimal::Decimal;
+use std::error::Error;
+use std::str::FromStr;
+
+/// Protocol name used in the `defi_protocols` account setting
+pub const DEFI_PROTOCOL_AAVE_V3: &str = "aave_v3";
+
+sol! {
+    #[sol(rpc)]
+    contract PoolAddressesProvider {
+        function getPoolDataProvider() external view returns (address);
+    }
+
+    #[sol(rpc)]
+    contract AaveProtocolDataProvider {
+        struct TokenData {
+            string symbol;
+            address tokenAddress;
+        }
+
+        function getAllReservesTokens() external view returns (TokenData[] memory);
+
+        function getUserReserveData(address asset, address user)
+            external
+            view
+            returns (
+                uint256 currentATokenBalance,
+                uint256 currentStableDebt,
+                uint256 currentVariableDebt,
+                uint256 principalStableDebt,
+                uint256 scaledVariableDebt,
+                uint256 stableBorrowRate,
+                uint256 liquidityRate,
+                uint40 stableRateLastUpdated,
+                bool usageAsCollateralEnabled
+            );
+
+        function getReserveConfigurationData(address asset)
+            external
+            view
+            returns (
+                uint256 decimals,
+                uint256 ltv,
+                uint256 liquidationThreshold,
+                uint256 liquidationBonus,
+                uint256 reserveFactor,
+                bool usageAsCollateralEnabled,
+                bool borrowingEnabled,
+                bool stableBorrowRateEnabled,
+                bool isActive,
+                bool isFrozen
+            );
+    }
+}
+
+/// `PoolAddressesProvider` of the Aave v3 core market on a chain from the `evm_chains` table
+pub fn pool_addresses_provider(chain_name: &str) -> Option<&'static str> {
+    match chain_name {
+        "ethereum" => Some("0x2f39d218133AFaB8F2B819B1066c7E434Ad94E9e"),
+        "arbitrum" | "optimism" | "polygon" | "avalanche" => Some("0xa97684ead0e402dC232d5A977953DF7ECBaB3CDb"),
+        "base" => Some("0xe20fCBdBfFC4Dd138cE8b2E6FBb6CB49777ad64D"),
+        "bsc" => Some("0xff75B6da14FfbbfD355Daf7a2731456b3562Ba6D"),
+        _ => None,
+    }
+}
+
+/// [`DefiAdapter`] for the Aave v3 core markets
+pub struct AaveAdapter;
+
+#[async_trait]
+impl DefiAdapter for AaveAdapter {
+    fn protocol(&self) -> &'static str {
+        DEFI_PROTOCOL_AAVE_V3
+    }
+
+    fn supports_chain(&self, chain: &str) -> bool {
+        pool_addresses_provider(chain).is_some()
+    }
+
+    fn aggregator_ids(&self) -> &'static [&'static str] {
+        &["aave3"]
+    }
+
+    async fn fetch_positions(
+        &self,
+        ctx: &DefiContext<'_>,
+        wallet: Address,
+    ) -> Result<DefiPositions, Box<dyn Error + Send + Sync>> {
+        let balances = fetch_aave_positions(ctx.chain, ctx.chain.rpc_url(), wallet).await?;
+        Ok(DefiPositions { balances, positions: Vec::new() })
+    }
+}
+
+/// Holding for an amount in the reserve's smallest unit; zero amounts are `None`, debt is negative
+pub(crate) fn position_balance(chain: &EvmChain, symbol: &str, raw: U256, decimals: u8, source: &str) -> Option<Balance> {
+    if raw.is_zero() {
+        return None;
+    }
+    let mut quantity = Decimal::from_str(&normalize_token_balance(&raw.to_string(), decimals).ok()?).ok()?;
+    if source == HOLDING_SOURCE_BORROWED {
+        quantity = -quantity;
+    }
+    Some(Balance {
+        asset: format!("{}-{}", symbol, chain.name()),
+        quantity: quantity.to_string(),
+        available: quantity.to_string(),
+        frozen: "0".to_string(),
+        decimals: Some(decimals),
+        holding_source: Some(source.to_string()),
+    })
+}
+
+/// Supplied and borrowed amounts of `wallet` in the Aave v3 market on `chain`.
+///
+/// Chains without a known market return no positions. Any failed call fails the chain, so a
+/// partial list never drops a debt while keeping its collateral.
+pub async fn fetch_aave_positions(
+    chain: &EvmChain,
+    rpc_url: &str,
+    wallet: Address,
+) -> Result<Vec<Balance>, Box<dyn Error + Send + Sync>> {
+    let Some(addresses_provider) = pool_addresses_provider(chain.name()) else {
+        return Ok(Vec::new());
+    };
+    let provider = ProviderBuilder::new().connect_http(rpc_url.parse()?);
+    let data_provider_address = PoolAddressesProvider::new(addresses_provider.parse()?, provider.clone())
+        .getPoolDataProvider()
+        .call()
+        .await?;
+    let data_provider = AaveProtocolDataProvider::new(data_provider_address, provider);
+
+    let mut balances = Vec::new();
+    for reserve in data_provider.getAllReservesTokens().call().await? {
+        let user = data_provider.getUserReserveData(reserve.tokenAddress, wallet).call().await?;
+        let debt = user.currentStableDebt + user.currentVariableDebt;
+        if user.currentATokenBalance.is_zero() && debt.is_zero() {
+            continue;
+        }
+
+        let config = data_provider.getReserveConfigurationData(reserve.tokenAddress).call().await?;
+        let decimals = u8::try_from(config.decimals).map_err(|_| "Aave reserve decimals out of range")?;
+        balances.extend(position_balance(
+            chain,
+            &reserve.symbol,
+            user.currentATokenBalance,
+            decimals,
+            HOLDING_SOURCE_SUPPLIED,
+        ));
+        balances.extend(position_balance(chain, &reserve.symbol, debt, decimals, HOLDING_SOURCE_BORROWED));
+    }
+    Ok(balances)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_position_balances() {
+        let chain = EvmChain::new("arbitrum", "https://arbitrum.llamarpc.com", "ETH");
+
+        let supplied = position_balance(&chain, "USDC", U256::from(2_500_000u64), 6, HOLDING_SOURCE_SUPPLIED).unwrap();
+        assert_eq!(supplied.asset, "USDC-arbitrum");
+        assert_eq!(Decimal::from_str(&supplied.quantity).unwrap(), Decimal::new(25, 1));
+        assert_eq!(supplied.holding_source.as_deref(), Some(HOLDING_SOURCE_SUPPLIED));
+
+        let borrowed = position_balance(&chain, "WETH", U256::from(10u64).pow(U256::from(18u64)), 18, HOLDING_SOURCE_BORROWED).unwrap();
+        assert_eq!(Decimal::from_str(&borrowed.quantity).unwrap(), Decimal::NEGATIVE_ONE);
+
+        assert!(position_balance(&chain, "DAI", U256::ZERO, 18, HOLDING_SOURCE_BORROWED).is_none());
+        assert!(pool_addresses_provider("base").is_some());
+        assert!(pool_addresses_provider("hyper_liquid").is_none());
+    }
+}
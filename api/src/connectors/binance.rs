@@ -0,0 +1,481 @@
+use super::registry::{ConnectorContext, ConnectorFactory, ACCOUNT_TYPE_EXCHANGE};
+use super::{Balance, ExchangeConnector, SubAccount};
+use crate::domain::HOLDING_SOURCE_EARN;
+use async_trait::async_trait;
+use chrono::Utc;
+use hmac::{Hmac, Mac};
+use reqwest;
+use rust_decimal::Decimal;
+use serde::Deserialize;
+use sha2::Sha256;
+use std::collections::{BTreeMap, HashSet};
+use std::error::Error;
+use std::str::FromStr;
+use tracing;
+
+type HmacSha256 = Hmac<Sha256>;
+
+const BINANCE_API_BASE_URL: &str = "https://api.binance.com";
+
+/// Request validity window (ms) sent as `recvWindow` on signed requests
+const BINANCE_RECV_WINDOW_MS: u32 = 5000;
+
+/// Page size for Simple Earn and staking position lists (maximum allowed)
+const BINANCE_EARN_PAGE_SIZE: u32 = 100;
+
+/// Page size for the sub-account list (maximum allowed)
+const BINANCE_SUB_ACCOUNT_PAGE_SIZE: usize = 200;
+
+/// Binance error body returned with non-2xx responses
+#[derive(Debug, Deserialize)]
+struct BinanceErrorResponse {
+    code: i64,
+    msg: String,
+}
+
+/// Binance spot account information (`GET /api/v3/account`)
+#[derive(Debug, Deserialize)]
+struct BinanceAccountInfo {
+    #[serde(default)]
+    balances: Vec<BinanceBalanceData>,
+}
+
+/// Binance spot balance
+///
+/// NOTE: Only quantities are read; holdings must remain quantity-only.
+#[derive(Debug, Deserialize)]
+struct BinanceBalanceData {
+    asset: String,
+    free: String,
+    locked: String,
+}
+
+/// Sub-account list (`GET /sapi/v1/sub-account/list`)
+#[derive(Debug, Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct BinanceSubAccountList {
+    #[serde(default)]
+    sub_accounts: Vec<BinanceSubAccountEntry>,
+}
+
+#[derive(Debug, Deserialize)]
+struct BinanceSubAccountEntry {
+    email: String,
+}
+
+/// Sub-account spot assets (`GET /sapi/v3/sub-account/assets`)
+#[derive(Debug, Deserialize)]
+struct BinanceSubAccountAssets {
+    #[serde(default)]
+    balances: Vec<BinanceSubAccountBalance>,
+}
+
+/// Sub-account balance; unlike the spot account, amounts are JSON numbers
+#[derive(Debug, Deserialize)]
+struct BinanceSubAccountBalance {
+    asset: String,
+    free: Decimal,
+    locked: Decimal,
+}
+
+/// Paged list returned by the Simple Earn position endpoints
+#[derive(Debug, Deserialize)]
+struct BinanceEarnPage<T> {
+    #[serde(default = "Vec::new")]
+    rows: Vec<T>,
+}
+
+/// Simple Earn Flexible position (`GET /sapi/v1/simple-earn/flexible/position`)
+#[derive(Debug, Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct BinanceFlexiblePosition {
+    asset: String,
+    total_amount: String,
+}
+
+/// Simple Earn Locked position (`GET /sapi/v1/simple-earn/locked/position`)
+#[derive(Debug, Deserialize)]
+struct BinanceLockedPosition {
+    asset: String,
+    amount: String,
+}
+
+/// Staking position (`GET /sapi/v1/staking/position`)
+#[derive(Debug, Deserialize)]
+struct BinanceStakingPosition {
+    asset: String,
+    amount: String,
+}
+
+/// Binance connector for read-only access
+///
+/// Binance API keys have no passphrase; only the key and secret are required.
+/// Besides the spot wallet, Simple Earn (Flexible and Locked) and staking positions
+/// are fetched and reported as separate holdings tagged `earn`.
+pub struct BinanceConnector {
+    api_key: String,
+    api_secret: String,
+    client: reqwest::Client,
+}
+
+impl BinanceConnector {
+    /// Create a new Binance connector with API credentials
+    pub fn new(api_key: String, api_secret: String) -> Self {
+        Self {
+            api_key,
+            api_secret,
+            client: reqwest::Client::new(),
+        }
+    }
+
+    /// Generate the request signature
+    ///
+    /// `signature = hex(HMAC-SHA256(query_string, secret))`
+    fn generate_signature(&self, query_string: &str) -> String {
+        let mut mac = HmacSha256::new_from_slice(self.api_secret.as_bytes())
+            .expect("HMAC can take key of any size");
+        mac.update(query_string.as_bytes());
+
+        mac.finalize()
+            .into_bytes()
+            .iter()
+            .map(|b| format!("{:02x}", b))
+            .collect()
+    }
+
+    /// Make a signed GET request to Binance API; `params` is a query string without
+    /// `timestamp`/`recvWindow` (may be empty)
+    async fn get_signed<T: for<'de> Deserialize<'de>>(
+        &self,
+        endpoint: &str,
+        params: &str,
+    ) -> Result<T, Box<dyn Error + Send + Sync>> {
+        let mut query = format!(
+            "recvWindow={}&timestamp={}",
+            BINANCE_RECV_WINDOW_MS,
+            Utc::now().timestamp_millis()
+        );
+        if !params.is_empty() {
+            query = format!("{}&{}", params, query);
+        }
+        let signature = self.generate_signature(&query);
+
+        let url = format!("{}{}?{}&signature={}", BINANCE_API_BASE_URL, endpoint, query, signature);
+
+        tracing::debug!("Binance API Request: GET {}{}", BINANCE_API_BASE_URL, endpoint);
+
+        let response = self
+            .client
+            .get(&url)
+            .header("X-MBX-APIKEY", &self.api_key)
+            .send()
+            .await?;
+
+        let status = response.status();
+        let body = response.text().await?;
+
+        tracing::debug!("Binance API Response Status: {}", status);
+        tracing::debug!("Binance API Response Body: {}", body);
+
+        if !status.is_success() {
+            return Err(match serde_json::from_str::<BinanceErrorResponse>(&body) {
+                Ok(err) => format!("Binance API error: {} - {}", err.code, err.msg),
+                Err(_) => format!("Binance API error: {} - {}", status, body),
+            }
+            .into());
+        }
+
+        serde_json::from_str(&body).map_err(|e| {
+            tracing::error!("Failed to parse Binance response: {}", e);
+            format!("Failed to parse Binance response: {}", e).into()
+        })
+    }
+
+    /// Fetch an optional earn endpoint, returning no rows (with a warning) on failure.
+    ///
+    /// Earn endpoints need the key's Simple Earn/staking read permission; a missing
+    /// permission must not fail the whole balance sync.
+    async fn get_optional<T: for<'de> Deserialize<'de>>(&self, endpoint: &str, params: &str) -> Option<T> {
+        match self.get_signed(endpoint, params).await {
+            Ok(data) => Some(data),
+            Err(e) => {
+                tracing::warn!("Failed to fetch Binance {}: {}", endpoint, e);
+                None
+            }
+        }
+    }
+
+    /// Fetch Flexible, Locked and staking positions as `(asset, amount)` pairs
+    async fn fetch_earn_amounts(&self) -> Vec<(String, String)> {
+        let page = format!("current=1&size={}", BINANCE_EARN_PAGE_SIZE);
+        let mut amounts = Vec::new();
+
+        if let Some(flexible) = self
+            .get_optional::<BinanceEarnPage<BinanceFlexiblePosition>>("/sapi/v1/simple-earn/flexible/position", &page)
+            .await
+        {
+            amounts.extend(flexible.rows.into_iter().map(|p| (p.asset, p.total_amount)));
+        }
+        if let Some(locked) = self
+            .get_optional::<BinanceEarnPage<BinanceLockedPosition>>("/sapi/v1/simple-earn/locked/position", &page)
+            .await
+        {
+            amounts.extend(locked.rows.into_iter().map(|p| (p.asset, p.amount)));
+        }
+        if let Some(staking) = self
+            .get_optional::<Vec<BinanceStakingPosition>>(
+                "/sapi/v1/staking/position",
+                &format!("product=STAKING&{}", page),
+            )
+            .await
+        {
+            amounts.extend(staking.into_iter().map(|p| (p.asset, p.amount)));
+        }
+
+        amounts
+    }
+}
+
+/// Percent-encode a query parameter value (e.g. a sub-account email)
+fn encode_query_value(value: &str) -> String {
+    value
+        .bytes()
+        .map(|b| match b {
+            b'A'..=b'Z' | b'a'..=b'z' | b'0'..=b'9' | b'-' | b'_' | b'.' | b'~' => (b as char).to_string(),
+            _ => format!("%{:02X}", b),
+        })
+        .collect()
+}
+
+/// Convert a sub-account's spot balances, dropping zero balances
+fn sub_account_balances(assets: BinanceSubAccountAssets) -> Vec<Balance> {
+    assets
+        .balances
+        .into_iter()
+        .filter(|b| b.free + b.locked > Decimal::ZERO)
+        .map(|b| Balance {
+            asset: b.asset.to_uppercase(),
+            quantity: (b.free + b.locked).normalize().to_string(),
+            available: b.free.normalize().to_string(),
+            frozen: b.locked.normalize().to_string(),
+            decimals: None,
+            holding_source: None,
+        })
+        .collect()
+}
+
+/// Convert spot balances, dropping zero balances.
+///
+/// Binance mirrors Flexible Earn positions in the spot account as `LD`-prefixed assets
+/// (e.g. `LDBTC`); those are skipped when the underlying asset is in `earn_assets`, so
+/// they are not counted twice. Real assets starting with "LD" (e.g. `LDO`) are kept.
+fn spot_balances(account: BinanceAccountInfo, earn_assets: &HashSet<String>) -> Vec<Balance> {
+    account
+        .balances
+        .into_iter()
+        .filter(|b| {
+            b.asset
+                .strip_prefix("LD")
+                .is_none_or(|underlying| !earn_assets.contains(underlying))
+        })
+        .filter_map(|b| {
+            let free = Decimal::from_str(&b.free).unwrap_or(Decimal::ZERO);
+            let locked = Decimal::from_str(&b.locked).unwrap_or(Decimal::ZERO);
+            let total = free + locked;
+            (total > Decimal::ZERO).then(|| Balance {
+                asset: b.asset.to_uppercase(),
+                quantity: total.normalize().to_string(),
+                available: free.normalize().to_string(),
+                frozen: locked.normalize().to_string(),
+                decimals: None, // Binance doesn't provide decimal information
+                holding_source: None,
+            })
+        })
+        .collect()
+}
+
+/// Merge Flexible, Locked and staking amounts into one `earn` balance per asset.
+///
+/// Earn funds cannot be traded until redeemed, so they are reported as frozen.
+/// Zero amounts are dropped; results are sorted by asset.
+fn earn_balances(amounts: Vec<(String, String)>) -> Vec<Balance> {
+    let mut totals: BTreeMap<String, Decimal> = BTreeMap::new();
+    for (asset, amount) in amounts {
+        let amount = Decimal::from_str(&amount).unwrap_or(Decimal::ZERO);
+        *totals.entry(asset.to_uppercase()).or_insert(Decimal::ZERO) += amount;
+    }
+
+    totals
+        .into_iter()
+        .filter(|(_, amount)| *amount > Decimal::ZERO)
+        .map(|(asset, amount)| {
+            let amount = amount.normalize().to_string();
+            Balance {
+                asset,
+                quantity: amount.clone(),
+                available: "0".to_string(),
+                frozen: amount,
+                decimals: None,
+                holding_source: Some(HOLDING_SOURCE_EARN.to_string()),
+            }
+        })
+        .collect()
+}
+
+#[async_trait]
+impl ExchangeConnector for BinanceConnector {
+    async fn fetch_spot_balances(&self) -> Result<Vec<Balance>, Box<dyn Error + Send + Sync>> {
+        let account: BinanceAccountInfo = self.get_signed("/api/v3/account", "omitZeroBalances=true").await?;
+
+        // Flexible/Locked Earn and staking, reported as separate "earn" holdings
+        let earn = earn_balances(self.fetch_earn_amounts().await);
+        let earn_assets: HashSet<String> = earn.iter().map(|b| b.asset.clone()).collect();
+
+        let mut balances = spot_balances(account, &earn_assets);
+        balances.extend(earn);
+
+        tracing::info!("Fetched {} balances from Binance", balances.len());
+        Ok(balances)
+    }
+
+    fn supports_sub_accounts(&self) -> bool {
+        true
+    }
+
+    async fn discover_sub_accounts(&self) -> Result<Vec<SubAccount>, Box<dyn Error + Send + Sync>> {
+        let mut sub_accounts = Vec::new();
+        for page in 1.. {
+            let list: BinanceSubAccountList = self
+                .get_signed(
+                    "/sapi/v1/sub-account/list",
+                    &format!("page={}&limit={}", page, BINANCE_SUB_ACCOUNT_PAGE_SIZE),
+                )
+                .await?;
+            let count = list.sub_accounts.len();
+            sub_accounts.extend(list.sub_accounts.into_iter().map(|s| SubAccount {
+                id: s.email,
+                label: None,
+            }));
+            if count < BINANCE_SUB_ACCOUNT_PAGE_SIZE {
+                break;
+            }
+        }
+        Ok(sub_accounts)
+    }
+
+    async fn fetch_sub_account_balances(
+        &self,
+        sub_account: &SubAccount,
+    ) -> Result<Vec<Balance>, Box<dyn Error + Send + Sync>> {
+        let assets: BinanceSubAccountAssets = self
+            .get_signed(
+                "/sapi/v3/sub-account/assets",
+                &format!("email={}", encode_query_value(&sub_account.id)),
+            )
+            .await?;
+        Ok(sub_account_balances(assets))
+    }
+}
+
+/// Builds [`BinanceConnector`]s for Binance accounts
+pub struct BinanceFactory;
+
+#[async_trait]
+impl ConnectorFactory for BinanceFactory {
+    fn account_type(&self) -> &'static str {
+        ACCOUNT_TYPE_EXCHANGE
+    }
+
+    fn name(&self) -> &'static str {
+        "binance"
+    }
+
+    async fn create(
+        &self,
+        ctx: &ConnectorContext<'_>,
+    ) -> Result<Box<dyn ExchangeConnector>, Box<dyn Error + Send + Sync>> {
+        let (api_key, api_secret) = ctx.api_credentials()?;
+        Ok(Box::new(BinanceConnector::new(api_key, api_secret)))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    #[test]
+    fn test_signature_generation() {
+        let connector = BinanceConnector::new("test-api-key".to_string(), "test-secret".to_string());
+        let signature = connector.generate_signature("recvWindow=5000&timestamp=1704067200000");
+
+        // Hex-encoded SHA-256 HMAC is 64 characters
+        assert_eq!(signature.len(), 64);
+        assert!(signature.chars().all(|c| c.is_ascii_hexdigit()));
+    }
+
+    #[test]
+    fn test_sub_account_balances_accept_numeric_amounts() {
+        let assets: BinanceSubAccountAssets = serde_json::from_value(json!({
+            "balances": [
+                { "asset": "BTC", "free": 0.5, "locked": 0.25 },
+                { "asset": "ETH", "free": 0, "locked": 0 }
+            ]
+        }))
+        .unwrap();
+
+        let balances = sub_account_balances(assets);
+        assert_eq!(balances.len(), 1);
+        assert_eq!(balances[0].quantity, "0.75");
+        assert_eq!(balances[0].frozen, "0.25");
+
+        assert_eq!(encode_query_value("desk+1@example.com"), "desk%2B1%40example.com");
+    }
+
+    #[test]
+    fn test_earn_positions_are_tagged_and_not_double_counted() {
+        let flexible: BinanceEarnPage<BinanceFlexiblePosition> = serde_json::from_value(json!({
+            "rows": [{ "asset": "USDT", "totalAmount": "250.5", "canRedeem": true }],
+            "total": 1
+        }))
+        .unwrap();
+        let locked: BinanceEarnPage<BinanceLockedPosition> = serde_json::from_value(json!({
+            "rows": [{ "asset": "USDT", "amount": "100", "duration": "30" }, { "asset": "DOT", "amount": "0" }],
+            "total": 2
+        }))
+        .unwrap();
+        let staking: Vec<BinanceStakingPosition> =
+            serde_json::from_value(json!([{ "asset": "ATOM", "amount": "12.5" }])).unwrap();
+
+        let amounts = flexible
+            .rows
+            .into_iter()
+            .map(|p| (p.asset, p.total_amount))
+            .chain(locked.rows.into_iter().map(|p| (p.asset, p.amount)))
+            .chain(staking.into_iter().map(|p| (p.asset, p.amount)))
+            .collect();
+        let earn = earn_balances(amounts);
+
+        assert_eq!(earn.len(), 2);
+        assert_eq!(earn[0].asset, "ATOM");
+        assert_eq!(earn[1].quantity, "350.5");
+        assert_eq!(earn[1].holding_source.as_deref(), Some(HOLDING_SOURCE_EARN));
+
+        let account: BinanceAccountInfo = serde_json::from_value(json!({
+            "balances": [
+                { "asset": "BTC", "free": "0.5", "locked": "0.1" },
+                { "asset": "LDUSDT", "free": "250.5", "locked": "0" },
+                { "asset": "LDO", "free": "40", "locked": "0" },
+                { "asset": "ETH", "free": "0", "locked": "0" }
+            ]
+        }))
+        .unwrap();
+        let earn_assets: HashSet<String> = earn.iter().map(|b| b.asset.clone()).collect();
+        let spot = spot_balances(account, &earn_assets);
+
+        let assets: Vec<&str> = spot.iter().map(|b| b.asset.as_str()).collect();
+        assert_eq!(assets, vec!["BTC", "LDO"]);
+        assert_eq!(spot[0].quantity, "0.6");
+        assert_eq!(spot[0].frozen, "0.1");
+    }
+}
@@ -0,0 +1,144 @@
+//! Binance public mini-ticker WebSocket stream.
+//!
+//! Subscribes to the `<symbol>usdt@miniTicker` streams of a set of assets over one combined
+//! stream connection and reports each update's last price. USDT pairs are read as USD prices.
+//! No API key is needed.
+
+use chrono::{DateTime, TimeZone, Utc};
+use futures::{SinkExt, StreamExt};
+use rust_decimal::Decimal;
+use serde::Deserialize;
+use std::error::Error;
+use std::str::FromStr;
+use std::time::Duration;
+use tokio_tungstenite::{connect_async, tungstenite::Message};
+
+const BINANCE_STREAM_BASE_URL: &str = "wss://stream.binance.com:9443/stream";
+
+/// Quote asset of the subscribed pairs
+const QUOTE_ASSET: &str = "USDT";
+
+/// Most streams Binance accepts on one connection
+pub const MAX_STREAMS_PER_CONNECTION: usize = 1024;
+
+/// Latest trade price of an asset from the stream
+#[derive(Debug, Clone, PartialEq)]
+pub struct StreamTick {
+    /// Base asset symbol (e.g. "BTC")
+    pub asset: String,
+    pub price_usd: Decimal,
+    pub event_time: DateTime<Utc>,
+}
+
+/// Combined stream envelope: `{"stream": "btcusdt@miniTicker", "data": {...}}`
+#[derive(Debug, Deserialize)]
+struct CombinedMessage {
+    data: MiniTicker,
+}
+
+#[derive(Debug, Deserialize)]
+struct MiniTicker {
+    /// Event time (ms)
+    #[serde(rename = "E")]
+    event_time: i64,
+    /// Pair symbol (e.g. "BTCUSDT")
+    #[serde(rename = "s")]
+    symbol: String,
+    /// Close (last) price
+    #[serde(rename = "c")]
+    close: String,
+}
+
+/// Combined stream URL for the USDT pairs of `assets`.
+///
+/// The quote asset itself has no pair and is left out; at most
+/// [`MAX_STREAMS_PER_CONNECTION`] assets are subscribed.
+pub fn stream_url(assets: &[String]) -> Option<String> {
+    let streams: Vec<String> = assets
+        .iter()
+        .filter(|asset| !asset.eq_ignore_ascii_case(QUOTE_ASSET))
+        .take(MAX_STREAMS_PER_CONNECTION)
+        .map(|asset| format!("{}{}@miniTicker", asset.to_lowercase(), QUOTE_ASSET.to_lowercase()))
+        .collect();
+    if streams.is_empty() {
+        return None;
+    }
+    Some(format!("{}?streams={}", BINANCE_STREAM_BASE_URL, streams.join("/")))
+}
+
+/// Parse a combined mini-ticker message; `None` for other messages or non-USDT pairs
+pub fn parse_mini_ticker(text: &str) -> Option<StreamTick> {
+    let message: CombinedMessage = serde_json::from_str(text).ok()?;
+    let asset = message.data.symbol.strip_suffix(QUOTE_ASSET)?;
+    Some(StreamTick {
+        asset: asset.to_string(),
+        price_usd: Decimal::from_str(&message.data.close).ok()?,
+        event_time: Utc.timestamp_millis_opt(message.data.event_time).single()?,
+    })
+}
+
+/// Stream the prices of `assets` for up to `run_for`, passing each update to `on_tick`.
+///
+/// Returns `Ok` when `run_for` elapsed, so the caller can reconnect with a fresh asset list,
+/// and an error when the connection failed or was closed by Binance.
+pub async fn stream_prices(
+    assets: &[String],
+    run_for: Duration,
+    mut on_tick: impl FnMut(StreamTick),
+) -> Result<(), Box<dyn Error + Send + Sync>> {
+    let Some(url) = stream_url(assets) else {
+        tokio::time::sleep(run_for).await;
+        return Ok(());
+    };
+
+    let (mut socket, _) = connect_async(url.as_str()).await?;
+    tracing::info!("Connected to Binance price stream for {} assets", assets.len());
+
+    let deadline = tokio::time::sleep(run_for);
+    tokio::pin!(deadline);
+    loop {
+        tokio::select! {
+            _ = &mut deadline => {
+                let _ = socket.close(None).await;
+                return Ok(());
+            }
+            message = socket.next() => match message {
+                Some(Ok(Message::Text(text))) => {
+                    if let Some(tick) = parse_mini_ticker(text.as_str()) {
+                        on_tick(tick);
+                    }
+                }
+                Some(Ok(Message::Ping(payload))) => socket.send(Message::Pong(payload)).await?,
+                Some(Ok(Message::Close(frame))) => {
+                    return Err(format!("Binance price stream closed: {:?}", frame).into());
+                }
+                Some(Ok(_)) => {}
+                Some(Err(e)) => return Err(e.into()),
+                None => return Err("Binance price stream ended".into()),
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_stream_url_and_parse() {
+        let assets = vec!["BTC".to_string(), "USDT".to_string(), "eth".to_string()];
+        assert_eq!(
+            stream_url(&assets).unwrap(),
+            "wss://stream.binance.com:9443/stream?streams=btcusdt@miniTicker/ethusdt@miniTicker"
+        );
+        assert!(stream_url(&["USDT".to_string()]).is_none());
+
+        let text = r#"{"stream":"btcusdt@miniTicker","data":{"e":"24hrMiniTicker","E":1700000000000,"s":"BTCUSDT","c":"37123.45","o":"36000","h":"37500","l":"35900","v":"1000","q":"37000000"}}"#;
+        let tick = parse_mini_ticker(text).unwrap();
+        assert_eq!(tick.asset, "BTC");
+        assert_eq!(tick.price_usd, Decimal::from_str("37123.45").unwrap());
+        assert_eq!(tick.event_time.timestamp_millis(), 1_700_000_000_000);
+
+        assert!(parse_mini_ticker(r#"{"result":null,"id":1}"#).is_none());
+    }
+}
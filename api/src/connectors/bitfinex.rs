@@ -0,0 +1,281 @@
+use super::registry::{ConnectorContext, ConnectorFactory, ACCOUNT_TYPE_EXCHANGE};
+use super::{Balance, ExchangeConnector};
+use async_trait::async_trait;
+use chrono::Utc;
+use hmac::{Hmac, Mac};
+use reqwest;
+use rust_decimal::Decimal;
+use serde_json::Value;
+use sha2::Sha384;
+use std::collections::BTreeMap;
+use std::error::Error;
+use std::str::FromStr;
+use tracing;
+
+type HmacSha384 = Hmac<Sha384>;
+
+const BITFINEX_API_BASE_URL: &str = "https://api.bitfinex.com";
+
+/// Wallet types that are aggregated into holdings
+const BITFINEX_WALLET_TYPES: [&str; 3] = ["exchange", "margin", "funding"];
+
+/// Bitfinex-specific currency codes and their common symbols
+const BITFINEX_CURRENCY_ALIASES: [(&str, &str); 6] = [
+    ("UST", "USDT"),
+    ("UDC", "USDC"),
+    ("DSH", "DASH"),
+    ("IOT", "IOTA"),
+    ("QTM", "QTUM"),
+    ("ALG", "ALGO"),
+];
+
+/// A single wallet row from `POST /v2/auth/r/wallets`.
+///
+/// Bitfinex returns each row as an array:
+/// `[WALLET_TYPE, CURRENCY, BALANCE, UNSETTLED_INTEREST, AVAILABLE_BALANCE, ...]`.
+/// Only quantities are read; holdings must remain quantity-only.
+#[derive(Debug, Clone, PartialEq)]
+struct BitfinexWallet {
+    wallet_type: String,
+    currency: String,
+    balance: Decimal,
+    /// `None` when Bitfinex has not computed it yet (returned as null)
+    available: Option<Decimal>,
+}
+
+impl BitfinexWallet {
+    /// Parse a wallet row, returning `None` for malformed rows
+    fn from_row(row: &Value) -> Option<Self> {
+        let row = row.as_array()?;
+        // Parse the JSON number's text to avoid float rounding (small values may use exponents)
+        let number = |v: Option<&Value>| {
+            let text = v?.as_number()?.to_string();
+            Decimal::from_str(&text).or_else(|_| Decimal::from_scientific(&text)).ok()
+        };
+
+        Some(Self {
+            wallet_type: row.first()?.as_str()?.to_string(),
+            currency: row.get(1)?.as_str()?.to_string(),
+            balance: number(row.get(2))?,
+            available: number(row.get(4)),
+        })
+    }
+}
+
+/// Bitfinex connector for read-only access
+///
+/// Bitfinex API keys have no passphrase; only the key and secret are required.
+pub struct BitfinexConnector {
+    api_key: String,
+    api_secret: String,
+    client: reqwest::Client,
+}
+
+impl BitfinexConnector {
+    /// Create a new Bitfinex connector with API credentials
+    pub fn new(api_key: String, api_secret: String) -> Self {
+        Self {
+            api_key,
+            api_secret,
+            client: reqwest::Client::new(),
+        }
+    }
+
+    /// Generate the bfx-signature header value
+    ///
+    /// `signature = hex(HMAC-SHA384("/api" + path + nonce + body, secret))`
+    fn generate_signature(&self, path: &str, nonce: &str, body: &str) -> String {
+        let payload = format!("/api{}{}{}", path, nonce, body);
+
+        let mut mac = HmacSha384::new_from_slice(self.api_secret.as_bytes())
+            .expect("HMAC can take key of any size");
+        mac.update(payload.as_bytes());
+
+        mac.finalize()
+            .into_bytes()
+            .iter()
+            .map(|b| format!("{:02x}", b))
+            .collect()
+    }
+
+    /// Make an authenticated POST request to Bitfinex API
+    async fn post_authenticated(&self, path: &str) -> Result<Value, Box<dyn Error + Send + Sync>> {
+        // Microsecond nonce so that it keeps increasing across quick successive calls
+        let nonce = Utc::now().timestamp_micros().to_string();
+        let body = "{}";
+        let signature = self.generate_signature(path, &nonce, body);
+
+        let url = format!("{}{}", BITFINEX_API_BASE_URL, path);
+
+        tracing::debug!("Bitfinex API Request: POST {}", url);
+
+        let response = self
+            .client
+            .post(&url)
+            .header("bfx-nonce", nonce)
+            .header("bfx-apikey", &self.api_key)
+            .header("bfx-signature", signature)
+            .header("Content-Type", "application/json")
+            .body(body)
+            .send()
+            .await?;
+
+        let status = response.status();
+        let body = response.text().await?;
+
+        tracing::debug!("Bitfinex API Response Status: {}", status);
+        tracing::debug!("Bitfinex API Response Body: {}", body);
+
+        // Errors are returned as ["error", CODE, "message"]
+        let value: Value = serde_json::from_str(&body).map_err(|e| {
+            tracing::error!("Failed to parse Bitfinex response: {}", e);
+            format!("Failed to parse Bitfinex response: {}", e)
+        })?;
+
+        if !status.is_success() || value.get(0).and_then(Value::as_str) == Some("error") {
+            return Err(format!("Bitfinex API error: {} - {}", status, body).into());
+        }
+
+        Ok(value)
+    }
+}
+
+/// Map a Bitfinex currency code to its common symbol
+fn normalize_currency(currency: &str) -> String {
+    let upper = currency.to_uppercase();
+    BITFINEX_CURRENCY_ALIASES
+        .iter()
+        .find(|(code, _)| *code == upper)
+        .map(|(_, symbol)| symbol.to_string())
+        .unwrap_or(upper)
+}
+
+/// Aggregate exchange, margin and funding wallets into one balance per asset.
+///
+/// When Bitfinex has not computed `AVAILABLE_BALANCE`, the full balance is
+/// treated as available. Zero totals are dropped; results are sorted by asset.
+fn aggregate_wallets(wallets: Vec<BitfinexWallet>) -> Vec<Balance> {
+    let mut totals: BTreeMap<String, (Decimal, Decimal)> = BTreeMap::new();
+
+    for wallet in wallets {
+        if !BITFINEX_WALLET_TYPES.contains(&wallet.wallet_type.as_str()) {
+            continue;
+        }
+
+        let entry = totals
+            .entry(normalize_currency(&wallet.currency))
+            .or_insert((Decimal::ZERO, Decimal::ZERO));
+        entry.0 += wallet.balance;
+        entry.1 += wallet.available.unwrap_or(wallet.balance);
+    }
+
+    totals
+        .into_iter()
+        .filter(|(_, (balance, _))| *balance > Decimal::ZERO)
+        .map(|(asset, (balance, available))| Balance {
+            asset,
+            quantity: balance.normalize().to_string(),
+            available: available.normalize().to_string(),
+            frozen: (balance - available).max(Decimal::ZERO).normalize().to_string(),
+            decimals: None, // Bitfinex doesn't provide decimal information
+            holding_source: None,
+        })
+        .collect()
+}
+
+#[async_trait]
+impl ExchangeConnector for BitfinexConnector {
+    async fn fetch_spot_balances(&self) -> Result<Vec<Balance>, Box<dyn Error + Send + Sync>> {
+        let response = self.post_authenticated("/v2/auth/r/wallets").await?;
+
+        let wallets: Vec<BitfinexWallet> = response
+            .as_array()
+            .ok_or("Bitfinex API returned an unexpected wallets payload")?
+            .iter()
+            .filter_map(|row| {
+                let wallet = BitfinexWallet::from_row(row);
+                if wallet.is_none() {
+                    tracing::warn!("Skipping malformed Bitfinex wallet row: {}", row);
+                }
+                wallet
+            })
+            .collect();
+
+        let balances = aggregate_wallets(wallets);
+
+        tracing::info!("Fetched {} balances from Bitfinex", balances.len());
+        Ok(balances)
+    }
+}
+
+/// Builds [`BitfinexConnector`]s for Bitfinex accounts
+pub struct BitfinexFactory;
+
+#[async_trait]
+impl ConnectorFactory for BitfinexFactory {
+    fn account_type(&self) -> &'static str {
+        ACCOUNT_TYPE_EXCHANGE
+    }
+
+    fn name(&self) -> &'static str {
+        "bitfinex"
+    }
+
+    async fn create(
+        &self,
+        ctx: &ConnectorContext<'_>,
+    ) -> Result<Box<dyn ExchangeConnector>, Box<dyn Error + Send + Sync>> {
+        let (api_key, api_secret) = ctx.api_credentials()?;
+        Ok(Box::new(BitfinexConnector::new(api_key, api_secret)))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    #[test]
+    fn test_signature_generation() {
+        let connector = BitfinexConnector::new("test-api-key".to_string(), "test-secret".to_string());
+        let signature = connector.generate_signature("/v2/auth/r/wallets", "1704067200000000", "{}");
+
+        // Hex-encoded SHA-384 HMAC is 96 characters
+        assert_eq!(signature.len(), 96);
+    }
+
+    #[test]
+    fn test_parse_wallet_rows() {
+        let wallet = BitfinexWallet::from_row(&json!(["margin", "UST", 150.5, 0, null, null, null])).unwrap();
+        assert_eq!(wallet.wallet_type, "margin");
+        assert_eq!(wallet.balance, Decimal::from_str("150.5").unwrap());
+        assert!(wallet.available.is_none());
+
+        let dust = BitfinexWallet::from_row(&json!(["exchange", "BTC", 1e-8, 0, 1e-8])).unwrap();
+        assert_eq!(dust.balance, Decimal::from_str("0.00000001").unwrap());
+
+        assert!(BitfinexWallet::from_row(&json!(["exchange"])).is_none());
+    }
+
+    #[test]
+    fn test_aggregate_wallets_across_types() {
+        let rows = json!([
+            ["exchange", "BTC", 0.5, 0, 0.5, null, null],
+            ["margin", "BTC", 0.25, 0, 0.05, null, null],
+            ["funding", "BTC", 0.25, 0, 0.25, null, null],
+            ["exchange", "UST", 100, 0, null, null, null],
+            ["exchange", "ETH", 0, 0, 0, null, null],
+            ["unknown", "XRP", 10, 0, 10, null, null]
+        ]);
+        let wallets = rows.as_array().unwrap().iter().filter_map(BitfinexWallet::from_row).collect();
+        let balances = aggregate_wallets(wallets);
+
+        assert_eq!(balances.len(), 2);
+        assert_eq!(balances[0].asset, "BTC");
+        assert_eq!(balances[0].quantity, "1");
+        assert_eq!(balances[0].available, "0.8");
+        assert_eq!(balances[0].frozen, "0.2");
+        assert_eq!(balances[1].asset, "USDT");
+        assert_eq!(balances[1].available, "100");
+    }
+}
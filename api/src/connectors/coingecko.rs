@@ -1,4 +1,8 @@
+use super::price_provider::{CoinQuote, PricePoint, PriceProvider, PRICE_PROVIDER_COINGECKO};
 use crate::concurrency::RateLimiter;
+use crate::entities::assets;
+use async_trait::async_trait;
+use chrono::{DateTime, Utc};
 use reqwest::Client;
 use serde::{Deserialize, Serialize};
 use std::collections::HashMap;
@@ -18,6 +22,15 @@ fn log_coingecko_error(status: reqwest::StatusCode, error_text: &str) {
 }
 
 
+/// Largest page (and ID batch) of the /coins/markets endpoint
+const MARKETS_PAGE_SIZE: usize = 250;
+
+/// Pages read when listing every coin (by market cap, so the long tail is cut off)
+const DEFAULT_MAX_PAGES: usize = 20;
+
+/// Percent change windows requested from /coins/markets
+const PRICE_CHANGE_WINDOWS: &str = "1h,24h,7d,30d";
+
 /// CoinGecko API client for fetching market data
 pub struct CoinGeckoConnector {
     client: Client,
@@ -26,22 +39,103 @@ pub struct CoinGeckoConnector {
     api_key: Option<String>,
 }
 
-/// Coin data from CoinGecko API
+/// Coin data from CoinGecko API /coins/markets endpoint
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct CoinMarketData {
     pub id: String,
     pub symbol: String,
     pub name: String,
-    pub image: String,
-    pub current_price: f64,
-    pub market_cap: f64,
+    #[serde(default)]
+    pub image: Option<String>,
+    /// `None` for coins CoinGecko no longer has a price for
+    #[serde(default)]
+    pub current_price: Option<f64>,
+    #[serde(default)]
+    pub market_cap: Option<f64>,
+    #[serde(default)]
     pub market_cap_rank: Option<u32>,
+    #[serde(default)]
     pub total_volume: Option<f64>,
+    #[serde(default)]
     pub price_change_percentage_24h: Option<f64>,
+    #[serde(default)]
+    pub price_change_percentage_1h_in_currency: Option<f64>,
+    #[serde(default)]
+    pub price_change_percentage_7d_in_currency: Option<f64>,
+    #[serde(default)]
+    pub price_change_percentage_30d_in_currency: Option<f64>,
+    #[serde(default)]
+    pub circulating_supply: Option<f64>,
+    #[serde(default)]
+    pub total_supply: Option<f64>,
+    #[serde(default)]
+    pub max_supply: Option<f64>,
+    #[serde(default)]
+    pub ath: Option<f64>,
+    #[serde(default)]
+    pub ath_change_percentage: Option<f64>,
+    #[serde(default)]
+    pub ath_date: Option<String>,
     // Note: CoinGecko doesn't provide market dominance in the markets endpoint
     // Market dominance would need to be calculated separately using total market cap
 }
 
+impl CoinMarketData {
+    /// Provider-neutral quote; `None` without a current price
+    pub fn into_quote(self) -> Option<CoinQuote> {
+        let price_usd = self.current_price?;
+        Some(CoinQuote {
+            provider_id: self.id,
+            symbol: self.symbol.to_uppercase(),
+            name: self.name,
+            rank: self.market_cap_rank,
+            price_usd,
+            volume_24h_usd: self.total_volume,
+            market_cap_usd: self.market_cap,
+            percent_change_1h: self.price_change_percentage_1h_in_currency,
+            percent_change_24h: self.price_change_percentage_24h,
+            percent_change_7d: self.price_change_percentage_7d_in_currency,
+            percent_change_30d: self.price_change_percentage_30d_in_currency,
+            circulating_supply: self.circulating_supply,
+            total_supply: self.total_supply,
+            max_supply: self.max_supply,
+            beta_value: None,
+            ath_price: self.ath,
+            ath_date: self.ath_date,
+            percent_from_price_ath: self.ath_change_percentage,
+        })
+    }
+}
+
+/// Response of the CoinGecko /coins/{id}/market_chart/range endpoint: `[unix_ms, value]` pairs
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct MarketChart {
+    #[serde(default)]
+    pub prices: Vec<(f64, f64)>,
+    #[serde(default)]
+    pub market_caps: Vec<(f64, f64)>,
+    #[serde(default)]
+    pub total_volumes: Vec<(f64, f64)>,
+}
+
+impl MarketChart {
+    /// Price points with the market cap and volume of the same timestamp
+    pub fn into_points(self) -> Vec<PricePoint> {
+        let value_at = |series: &[(f64, f64)], ms: f64| series.iter().find(|(t, _)| *t == ms).map(|(_, v)| *v);
+        self.prices
+            .iter()
+            .filter_map(|&(ms, price)| {
+                Some(PricePoint {
+                    timestamp: DateTime::from_timestamp_millis(ms as i64)?,
+                    price_usd: price,
+                    volume_24h_usd: value_at(&self.total_volumes, ms),
+                    market_cap_usd: value_at(&self.market_caps, ms),
+                })
+            })
+            .collect()
+    }
+}
+
 /// Detailed coin data from CoinGecko /coins/{id} API
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct CoinDetailData {
@@ -88,20 +182,68 @@ impl CoinGeckoConnector {
     /// # Returns
     /// Vector of coin market data sorted by market cap rank
     pub async fn fetch_top_coins(&self, limit: usize) -> Result<Vec<CoinMarketData>, Box<dyn Error + Send + Sync>> {
-        if limit == 0 || limit > 250 {
-            return Err(format!("Invalid limit: {}. Limit must be between 1 and 250", limit).into());
+        if limit == 0 || limit > MARKETS_PAGE_SIZE {
+            return Err(format!("Invalid limit: {}. Limit must be between 1 and {}", limit, MARKETS_PAGE_SIZE).into());
         }
 
+        tracing::info!("Fetching top {} coins from CoinGecko", limit);
+
+        let coins = self.fetch_markets_page(limit, 1, None).await?;
+
+        tracing::info!("Successfully fetched {} coins from CoinGecko", coins.len());
+
+        Ok(coins)
+    }
+
+    /// Fetch coins by market cap, page by page, up to `max_coins` or `max_pages` pages
+    ///
+    /// # Returns
+    /// Vector of coin market data sorted by market cap rank
+    pub async fn fetch_coins_paged(
+        &self,
+        max_coins: usize,
+        max_pages: usize,
+    ) -> Result<Vec<CoinMarketData>, Box<dyn Error + Send + Sync>> {
+        let mut coins = Vec::new();
+        for page in 1..=max_pages {
+            let per_page = (max_coins - coins.len()).min(MARKETS_PAGE_SIZE);
+            if per_page == 0 {
+                break;
+            }
+            let batch = self.fetch_markets_page(per_page, page, None).await?;
+            let last_page = batch.len() < per_page;
+            coins.extend(batch);
+            if last_page {
+                break;
+            }
+        }
+
+        tracing::info!("Successfully fetched {} coins from CoinGecko", coins.len());
+
+        Ok(coins)
+    }
+
+    /// Fetch one page of the /coins/markets endpoint, optionally restricted to `ids`
+    async fn fetch_markets_page(
+        &self,
+        per_page: usize,
+        page: usize,
+        ids: Option<&[String]>,
+    ) -> Result<Vec<CoinMarketData>, Box<dyn Error + Send + Sync>> {
         // Acquire rate limit permit
         let _permit = self.rate_limiter.acquire().await?;
-        
-        tracing::info!("Fetching top {} coins from CoinGecko", limit);
 
-        let url = format!(
-            "{}/coins/markets?vs_currency=usd&order=market_cap_desc&per_page={}&page=1&sparkline=false&price_change_percentage=24h",
+        let mut url = format!(
+            "{}/coins/markets?vs_currency=usd&order=market_cap_desc&per_page={}&page={}&sparkline=false&price_change_percentage={}",
             self.base_url,
-            limit
+            per_page,
+            page,
+            PRICE_CHANGE_WINDOWS
         );
+        if let Some(ids) = ids {
+            // CoinGecko API accepts comma-separated list of IDs
+            url.push_str(&format!("&ids={}", ids.join(",")));
+        }
 
         let mut request = self.client
             .get(&url)
@@ -121,11 +263,7 @@ impl CoinGeckoConnector {
             return Err(format!("CoinGecko API error: {} - {}", status, error_text).into());
         }
 
-        let coins: Vec<CoinMarketData> = response.json().await?;
-        
-        tracing::info!("Successfully fetched {} coins from CoinGecko", coins.len());
-        
-        Ok(coins)
+        Ok(response.json().await?)
     }
 
     /// Ping CoinGecko API to check connectivity
@@ -152,29 +290,49 @@ impl CoinGeckoConnector {
             return Ok(Vec::new());
         }
 
-        // Acquire rate limit permit
-        let _permit = self.rate_limiter.acquire().await?;
+        tracing::info!("Fetching {} coins by ID from CoinGecko", coin_ids.len());
+
+        let mut coins = Vec::new();
+        for chunk in coin_ids.chunks(MARKETS_PAGE_SIZE) {
+            coins.extend(self.fetch_markets_page(MARKETS_PAGE_SIZE, 1, Some(chunk)).await?);
+        }
         
-        // CoinGecko API accepts comma-separated list of IDs
-        let ids_param = coin_ids.join(",");
+        tracing::info!("Successfully fetched {} coins by ID from CoinGecko", coins.len());
         
-        tracing::info!("Fetching {} coins by ID from CoinGecko", coin_ids.len());
+        Ok(coins)
+    }
+
+    /// Fetch a coin's price, market cap and volume history between `from` and `to`
+    ///
+    /// CoinGecko returns daily points for ranges over 90 days and finer points for shorter ones.
+    pub async fn fetch_market_chart_range(
+        &self,
+        coin_id: &str,
+        from: DateTime<Utc>,
+        to: DateTime<Utc>,
+    ) -> Result<MarketChart, Box<dyn Error + Send + Sync>> {
+        // Acquire rate limit permit
+        let _permit = self.rate_limiter.acquire().await?;
+
+        tracing::debug!("Fetching market chart for {} from CoinGecko", coin_id);
 
         let url = format!(
-            "{}/coins/markets?vs_currency=usd&ids={}&sparkline=false&price_change_percentage=24h",
+            "{}/coins/{}/market_chart/range?vs_currency=usd&from={}&to={}",
             self.base_url,
-            ids_param
+            coin_id,
+            from.timestamp(),
+            to.timestamp()
         );
 
         let mut request = self.client
             .get(&url)
             .header("accept", "application/json");
-        
+
         // Add API key header if available (for Pro API)
         if let Some(key) = &self.api_key {
             request = request.header("x-cg-pro-api-key", key);
         }
-        
+
         let response = request.send().await?;
 
         if !response.status().is_success() {
@@ -184,11 +342,7 @@ impl CoinGeckoConnector {
             return Err(format!("CoinGecko API error: {} - {}", status, error_text).into());
         }
 
-        let coins: Vec<CoinMarketData> = response.json().await?;
-        
-        tracing::info!("Successfully fetched {} coins by ID from CoinGecko", coins.len());
-        
-        Ok(coins)
+        Ok(response.json().await?)
     }
 
     /// Fetch detailed coin information including contract addresses
@@ -248,6 +402,51 @@ impl Default for CoinGeckoConnector {
     }
 }
 
+#[async_trait]
+impl PriceProvider for CoinGeckoConnector {
+    fn name(&self) -> &'static str {
+        PRICE_PROVIDER_COINGECKO
+    }
+
+    fn id_column(&self) -> assets::Column {
+        assets::Column::CoingeckoId
+    }
+
+    fn coin_id<'a>(&self, asset: &'a assets::Model) -> Option<&'a str> {
+        asset.coingecko_id.as_deref()
+    }
+
+    /// Without a limit, lists the top `COINGECKO_MAX_PAGES` pages of 250 coins (default: 20)
+    async fn fetch_top(&self, limit: Option<usize>) -> Result<Vec<CoinQuote>, Box<dyn Error + Send + Sync>> {
+        let coins = match limit {
+            Some(limit) => self.fetch_top_coins(limit).await?,
+            None => {
+                let max_pages = std::env::var("COINGECKO_MAX_PAGES")
+                    .ok()
+                    .and_then(|v| v.parse().ok())
+                    .filter(|pages| *pages > 0)
+                    .unwrap_or(DEFAULT_MAX_PAGES);
+                self.fetch_coins_paged(usize::MAX, max_pages).await?
+            }
+        };
+        Ok(coins.into_iter().filter_map(CoinMarketData::into_quote).collect())
+    }
+
+    async fn fetch_by_ids(&self, ids: &[String]) -> Result<Vec<CoinQuote>, Box<dyn Error + Send + Sync>> {
+        let coins = self.fetch_coins_by_ids(ids).await?;
+        Ok(coins.into_iter().filter_map(CoinMarketData::into_quote).collect())
+    }
+
+    async fn fetch_history(
+        &self,
+        id: &str,
+        from: DateTime<Utc>,
+        to: DateTime<Utc>,
+    ) -> Result<Vec<PricePoint>, Box<dyn Error + Send + Sync>> {
+        Ok(self.fetch_market_chart_range(id, from, to).await?.into_points())
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -257,4 +456,46 @@ mod tests {
         let connector = CoinGeckoConnector::new();
         assert_eq!(connector.base_url, "https://api.coingecko.com/api/v3");
     }
+
+    #[test]
+    fn test_market_data_into_quote() {
+        let coins: Vec<CoinMarketData> = serde_json::from_value(serde_json::json!([
+            {
+                "id": "ethereum",
+                "symbol": "eth",
+                "name": "Ethereum",
+                "current_price": 3200.25,
+                "market_cap": 3.85e11,
+                "market_cap_rank": 2,
+                "total_volume": 1.2e10,
+                "price_change_percentage_24h": 1.5,
+                "price_change_percentage_7d_in_currency": -3.25,
+                "ath": 4878.26,
+                "ath_change_percentage": -34.4,
+                "ath_date": "2021-11-10T14:24:19.604Z"
+            },
+            { "id": "dead-coin", "symbol": "dead", "name": "Dead", "current_price": null, "market_cap": null }
+        ]))
+        .unwrap();
+
+        let quotes: Vec<CoinQuote> = coins.into_iter().filter_map(CoinMarketData::into_quote).collect();
+        assert_eq!(quotes.len(), 1);
+        assert_eq!(quotes[0].provider_id, "ethereum");
+        assert_eq!(quotes[0].symbol, "ETH");
+        assert_eq!(quotes[0].rank, Some(2));
+        assert_eq!(quotes[0].percent_change_7d, Some(-3.25));
+        assert_eq!(quotes[0].percent_from_price_ath, Some(-34.4));
+
+        let chart: MarketChart = serde_json::from_value(serde_json::json!({
+            "prices": [[1704067200000.0, 42280.5], [1704153600000.0, 44187.1]],
+            "market_caps": [[1704067200000.0, 8.28e11]],
+            "total_volumes": [[1704153600000.0, 2.1e10]]
+        }))
+        .unwrap();
+        let points = chart.into_points();
+        assert_eq!(points.len(), 2);
+        assert_eq!(points[0].timestamp.to_rfc3339(), "2024-01-01T00:00:00+00:00");
+        assert_eq!(points[0].market_cap_usd, Some(8.28e11));
+        assert_eq!(points[1].volume_24h_usd, Some(2.1e10));
+    }
 }
@@ -1,10 +1,17 @@
+use super::price_provider::{CoinQuote, PricePoint, PriceProvider, PRICE_PROVIDER_COINPAPRIKA};
 use crate::concurrency::RateLimiter;
+use crate::entities::assets;
+use async_trait::async_trait;
+use chrono::{DateTime, NaiveDate, Utc};
 use reqwest::Client;
 use serde::{Deserialize, Serialize};
 use std::collections::HashMap;
 use std::error::Error;
 use tracing;
 
+/// Most daily candles returned by one OHLCV request
+pub const OHLCV_MAX_DAYS: i64 = 366;
+
 /// Helper function to log detailed error messages for CoinPaprika API failures
 fn log_coinpaprika_error(status: reqwest::StatusCode, error_text: &str) {
     if status.as_u16() == 429 {
@@ -76,6 +83,58 @@ pub struct UsdQuote {
     pub percent_from_price_ath: Option<f64>,
 }
 
+impl From<CoinMarketData> for CoinQuote {
+    fn from(coin: CoinMarketData) -> Self {
+        let usd = coin.quotes.usd;
+        Self {
+            provider_id: coin.id,
+            symbol: coin.symbol,
+            name: coin.name,
+            rank: Some(coin.rank).filter(|rank| *rank > 0),
+            price_usd: usd.price,
+            volume_24h_usd: usd.volume_24h,
+            market_cap_usd: Some(usd.market_cap),
+            percent_change_1h: usd.percent_change_1h,
+            percent_change_24h: usd.percent_change_24h,
+            percent_change_7d: usd.percent_change_7d,
+            percent_change_30d: usd.percent_change_30d,
+            circulating_supply: coin.circulating_supply,
+            total_supply: coin.total_supply,
+            max_supply: coin.max_supply,
+            beta_value: coin.beta_value,
+            ath_price: usd.ath_price,
+            ath_date: usd.ath_date,
+            percent_from_price_ath: usd.percent_from_price_ath,
+        }
+    }
+}
+
+/// One entry of the CoinPaprika /tickers/{id}/historical endpoint
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct HistoricalTick {
+    pub timestamp: DateTime<Utc>,
+    pub price: f64,
+    #[serde(default)]
+    pub volume_24h: Option<f64>,
+    #[serde(default)]
+    pub market_cap: Option<f64>,
+}
+
+/// One daily candle of the CoinPaprika /coins/{id}/ohlcv/historical endpoint
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct OhlcvCandle {
+    pub time_open: DateTime<Utc>,
+    pub time_close: DateTime<Utc>,
+    pub open: f64,
+    pub high: f64,
+    pub low: f64,
+    pub close: f64,
+    #[serde(default)]
+    pub volume: Option<f64>,
+    #[serde(default)]
+    pub market_cap: Option<f64>,
+}
+
 /// Detailed coin data from CoinPaprika /coins/{id} API
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct CoinDetailData {
@@ -285,6 +344,112 @@ impl CoinPaprikaConnector {
         Ok(results)
     }
 
+    /// Fetch daily historical ticks of a coin between `start` and `end`
+    ///
+    /// # Arguments
+    /// * `coin_id` - CoinPaprika coin ID (e.g., "btc-bitcoin")
+    ///
+    /// # Returns
+    /// Daily ticks, oldest first
+    pub async fn fetch_historical_ticks(
+        &self,
+        coin_id: &str,
+        start: DateTime<Utc>,
+        end: DateTime<Utc>,
+    ) -> Result<Vec<HistoricalTick>, Box<dyn Error + Send + Sync>> {
+        // Acquire rate limit permit
+        let _permit = self.rate_limiter.acquire().await?;
+
+        tracing::debug!("Fetching historical ticks for {} from CoinPaprika", coin_id);
+
+        let url = format!(
+            "{}/tickers/{}/historical?start={}&end={}&interval=1d",
+            self.base_url,
+            coin_id,
+            start.format("%Y-%m-%d"),
+            end.format("%Y-%m-%d")
+        );
+
+        let mut request = self.client
+            .get(&url)
+            .header("accept", "application/json");
+
+        // Add API key header if available (for Pro API)
+        if let Some(key) = &self.api_key {
+            request = request.header("Authorization", format!("Bearer {}", key));
+        }
+
+        let response = request.send().await?;
+
+        if !response.status().is_success() {
+            let status = response.status();
+            let error_text = response.text().await.unwrap_or_else(|_| "Unknown error".to_string());
+            log_coinpaprika_error(status, &error_text);
+            return Err(format!("CoinPaprika API error: {} - {}", status, error_text).into());
+        }
+
+        let ticks: Vec<HistoricalTick> = response.json().await?;
+
+        tracing::debug!("Fetched {} historical ticks for {} from CoinPaprika", ticks.len(), coin_id);
+
+        Ok(ticks)
+    }
+
+    /// Fetch daily OHLCV candles of a coin between `start` and `end`
+    ///
+    /// CoinPaprika returns at most [`OHLCV_MAX_DAYS`] candles per request, so callers split
+    /// longer ranges.
+    ///
+    /// # Arguments
+    /// * `coin_id` - CoinPaprika coin ID (e.g., "btc-bitcoin")
+    ///
+    /// # Returns
+    /// Daily candles, oldest first
+    pub async fn fetch_ohlcv_historical(
+        &self,
+        coin_id: &str,
+        start: NaiveDate,
+        end: NaiveDate,
+    ) -> Result<Vec<OhlcvCandle>, Box<dyn Error + Send + Sync>> {
+        // Acquire rate limit permit
+        let _permit = self.rate_limiter.acquire().await?;
+
+        tracing::debug!("Fetching OHLCV candles for {} from {} to {} from CoinPaprika", coin_id, start, end);
+
+        let url = format!(
+            "{}/coins/{}/ohlcv/historical?start={}&end={}&limit={}",
+            self.base_url,
+            coin_id,
+            start.format("%Y-%m-%d"),
+            end.format("%Y-%m-%d"),
+            OHLCV_MAX_DAYS
+        );
+
+        let mut request = self.client
+            .get(&url)
+            .header("accept", "application/json");
+
+        // Add API key header if available (for Pro API)
+        if let Some(key) = &self.api_key {
+            request = request.header("Authorization", format!("Bearer {}", key));
+        }
+
+        let response = request.send().await?;
+
+        if !response.status().is_success() {
+            let status = response.status();
+            let error_text = response.text().await.unwrap_or_else(|_| "Unknown error".to_string());
+            log_coinpaprika_error(status, &error_text);
+            return Err(format!("CoinPaprika API error: {} - {}", status, error_text).into());
+        }
+
+        let candles: Vec<OhlcvCandle> = response.json().await?;
+
+        tracing::debug!("Fetched {} OHLCV candles for {} from CoinPaprika", candles.len(), coin_id);
+
+        Ok(candles)
+    }
+
     /// Fetch detailed coin information including contract addresses
     /// 
     /// # Arguments
@@ -427,6 +592,52 @@ impl Default for CoinPaprikaConnector {
     }
 }
 
+#[async_trait]
+impl PriceProvider for CoinPaprikaConnector {
+    fn name(&self) -> &'static str {
+        PRICE_PROVIDER_COINPAPRIKA
+    }
+
+    fn id_column(&self) -> assets::Column {
+        assets::Column::CoinpaprikaId
+    }
+
+    fn coin_id<'a>(&self, asset: &'a assets::Model) -> Option<&'a str> {
+        asset.coinpaprika_id.as_deref()
+    }
+
+    /// Without a limit, `/tickers` returns every active coin in one request
+    async fn fetch_top(&self, limit: Option<usize>) -> Result<Vec<CoinQuote>, Box<dyn Error + Send + Sync>> {
+        let coins = match limit {
+            Some(limit) => self.fetch_top_coins(limit).await?,
+            None => self.fetch_all_coins().await?,
+        };
+        Ok(coins.into_iter().map(CoinQuote::from).collect())
+    }
+
+    async fn fetch_by_ids(&self, ids: &[String]) -> Result<Vec<CoinQuote>, Box<dyn Error + Send + Sync>> {
+        Ok(self.fetch_coins_by_ids(ids).await?.into_iter().map(CoinQuote::from).collect())
+    }
+
+    async fn fetch_history(
+        &self,
+        id: &str,
+        from: DateTime<Utc>,
+        to: DateTime<Utc>,
+    ) -> Result<Vec<PricePoint>, Box<dyn Error + Send + Sync>> {
+        let ticks = self.fetch_historical_ticks(id, from, to).await?;
+        Ok(ticks
+            .into_iter()
+            .map(|tick| PricePoint {
+                timestamp: tick.timestamp,
+                price_usd: tick.price,
+                volume_24h_usd: tick.volume_24h,
+                market_cap_usd: tick.market_cap,
+            })
+            .collect())
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -455,4 +666,34 @@ mod tests {
         assert_eq!(platforms.get("ethereum"), Some(&"0x1234".to_string()));
         assert_eq!(platforms.get("binance-smart-chain"), Some(&"0x5678".to_string()));
     }
+
+    #[test]
+    fn test_coin_quote_from_ticker() {
+        let coin: CoinMarketData = serde_json::from_value(serde_json::json!({
+            "id": "btc-bitcoin",
+            "name": "Bitcoin",
+            "symbol": "BTC",
+            "rank": 1,
+            "max_supply": 21000000.0,
+            "quotes": {
+                "USD": {
+                    "price": 65000.5,
+                    "volume_24h": 1.5e10,
+                    "market_cap": 1.28e12,
+                    "percent_change_24h": -1.2,
+                    "ath_date": "2024-03-14T07:10:00Z"
+                }
+            }
+        }))
+        .unwrap();
+
+        let quote = CoinQuote::from(coin);
+        assert_eq!(quote.provider_id, "btc-bitcoin");
+        assert_eq!(quote.rank, Some(1));
+        assert_eq!(quote.price_usd, 65000.5);
+        assert_eq!(quote.market_cap_usd, Some(1.28e12));
+        assert_eq!(quote.max_supply, Some(21000000.0));
+        assert_eq!(quote.ath_date.as_deref(), Some("2024-03-14T07:10:00Z"));
+        assert!(quote.percent_change_1h.is_none());
+    }
 }
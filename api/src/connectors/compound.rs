@@ -0,0 +1,187 @@
+//! Compound v3 (Comet) supply, collateral and borrow positions of EVM wallets.
+//!
+//! Each Comet market lends one base asset against a set of collateral assets. For every known
+//! market on a chain the wallet's base supply (`balanceOf`, interest included) and posted
+//! collateral (`collateralBalanceOf` per asset from `getAssetInfo`) are reported as
+//! [`HOLDING_SOURCE_SUPPLIED`] holdings, and its base debt (`borrowBalanceOf`) as a negative
+//! [`HOLDING_SOURCE_BORROWED`] holding, the same way as Aave v3 positions.
+
+use super::aave::position_balance;
+use super::defi::{DefiAdapter, DefiContext, DefiPositions};
+use super::evm::EvmChain;
+use super::Balance;
+use crate::domain::{HOLDING_SOURCE_BORROWED, HOLDING_SOURCE_SUPPLIED};
+use alloy::{
+    primitives::{Address, U256},
+    providers::{Provider, ProviderBuilder},
+    sol,
+};
+use async_trait::async_trait;
+use std::error::Error;
+
+/// Protocol name used in the `defi_protocols` account setting
+pub const DEFI_PROTOCOL_COMPOUND_V3: &str = "compound_v3";
+
+sol! {
+    #[sol(rpc)]
+    contract Comet {
+        struct AssetInfo {
+            uint8 offset;
+            address asset;
+            address priceFeed;
+            uint64 scale;
+            uint64 borrowCollateralFactor;
+            uint64 liquidateCollateralFactor;
+            uint64 liquidationFactor;
+            uint128 supplyCap;
+        }
+
+        function baseToken() external view returns (address);
+        function balanceOf(address account) external view returns (uint256);
+        function borrowBalanceOf(address account) external view returns (uint256);
+        function numAssets() external view returns (uint8);
+        function getAssetInfo(uint8 i) external view returns (AssetInfo memory);
+        function collateralBalanceOf(address account, address asset) external view returns (uint128);
+    }
+
+    #[sol(rpc)]
+    contract CometToken {
+        function decimals() external view returns (uint8);
+        function symbol() external view returns (string);
+    }
+}
+
+/// Comet market proxies on a chain from the `evm_chains` table
+pub fn comet_markets(chain_name: &str) -> &'static [&'static str] {
+    match chain_name {
+        "ethereum" => &[
+            "0xc3d688B66703497DAA19211EEdff47f25384cdc3", // cUSDCv3
+            "0xA17581A9E3356d9A858b789D68B4d866e593aE94", // cWETHv3
+            "0x3Afdc9BCA9213A35503b077a6072F3D0d5AB0840", // cUSDTv3
+        ],
+        "arbitrum" => &[
+            "0x9c4ec768c28520B50860ea7a15bd7213a9fF58bf", // cUSDCv3
+            "0xA5EDBDD9646f8dFF606d7448e414884C7d905dCA", // cUSDC.ev3
+            "0x6f7D514bbD4aFf3BcD1140B7344b32f063dEe486", // cWETHv3
+            "0xd98Be00b5D27fc98112BdE293e487f8D4cA57d07", // cUSDTv3
+        ],
+        "base" => &[
+            "0xb125E6687d4313864e53df431d5425969c15Eb2F", // cUSDCv3
+            "0x46e6b214b524310239732D51387075E0e70970bf", // cWETHv3
+            "0x9c4ec768c28520B50860ea7a15bd7213a9fF58bf", // cUSDbCv3
+        ],
+        "optimism" => &[
+            "0x2e44e174f7D53F0212823acC11C01A11d58c5bCB", // cUSDCv3
+            "0xE36A30D249f7761327fd973001A32010b521b6Fd", // cWETHv3
+        ],
+        "polygon" => &["0xF25212E676D1F7F89Cd72fFEe66158f541246445"], // cUSDCv3
+        _ => &[],
+    }
+}
+
+/// [`DefiAdapter`] for the Compound v3 markets
+pub struct CompoundAdapter;
+
+#[async_trait]
+impl DefiAdapter for CompoundAdapter {
+    fn protocol(&self) -> &'static str {
+        DEFI_PROTOCOL_COMPOUND_V3
+    }
+
+    fn supports_chain(&self, chain: &str) -> bool {
+        !comet_markets(chain).is_empty()
+    }
+
+    fn aggregator_ids(&self) -> &'static [&'static str] {
+        &["compound3"]
+    }
+
+    async fn fetch_positions(
+        &self,
+        ctx: &DefiContext<'_>,
+        wallet: Address,
+    ) -> Result<DefiPositions, Box<dyn Error + Send + Sync>> {
+        let balances = fetch_compound_positions(ctx.chain, ctx.chain.rpc_url(), wallet).await?;
+        Ok(DefiPositions { balances, positions: Vec::new() })
+    }
+}
+
+/// Symbol and decimals of a market's base or collateral token
+async fn token_info<P: Provider + Clone>(provider: &P, token: Address) -> Result<(String, u8), Box<dyn Error + Send + Sync>> {
+    let token = CometToken::new(token, provider.clone());
+    Ok((token.symbol().call().await?, token.decimals().call().await?))
+}
+
+/// `wallet`'s base supply, collateral and debt in one Comet market
+async fn market_positions<P: Provider + Clone>(
+    provider: &P,
+    chain: &EvmChain,
+    market: Address,
+    wallet: Address,
+) -> Result<Vec<Balance>, Box<dyn Error + Send + Sync>> {
+    let comet = Comet::new(market, provider.clone());
+    let mut balances = Vec::new();
+
+    let supplied = comet.balanceOf(wallet).call().await?;
+    let borrowed = comet.borrowBalanceOf(wallet).call().await?;
+    if !supplied.is_zero() || !borrowed.is_zero() {
+        let (symbol, decimals) = token_info(provider, comet.baseToken().call().await?).await?;
+        balances.extend(position_balance(chain, &symbol, supplied, decimals, HOLDING_SOURCE_SUPPLIED));
+        balances.extend(position_balance(chain, &symbol, borrowed, decimals, HOLDING_SOURCE_BORROWED));
+    }
+
+    for i in 0..comet.numAssets().call().await? {
+        let info = comet.getAssetInfo(i).call().await?;
+        let collateral = comet.collateralBalanceOf(wallet, info.asset).call().await?;
+        if collateral == 0 {
+            continue;
+        }
+        let (symbol, decimals) = token_info(provider, info.asset).await?;
+        balances.extend(position_balance(
+            chain,
+            &symbol,
+            U256::from(collateral),
+            decimals,
+            HOLDING_SOURCE_SUPPLIED,
+        ));
+    }
+    Ok(balances)
+}
+
+/// Supplied, collateral and borrowed amounts of `wallet` in the Compound v3 markets on `chain`.
+///
+/// Chains without a known market return no positions. Any failed call fails the chain, so a
+/// partial list never drops a debt while keeping its collateral.
+pub async fn fetch_compound_positions(
+    chain: &EvmChain,
+    rpc_url: &str,
+    wallet: Address,
+) -> Result<Vec<Balance>, Box<dyn Error + Send + Sync>> {
+    let markets = comet_markets(chain.name());
+    if markets.is_empty() {
+        return Ok(Vec::new());
+    }
+    let provider = ProviderBuilder::new().connect_http(rpc_url.parse()?);
+
+    let mut balances = Vec::new();
+    for market in markets {
+        balances.extend(market_positions(&provider, chain, market.parse()?, wallet).await?);
+    }
+    Ok(balances)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_comet_markets() {
+        for chain in ["ethereum", "arbitrum", "base", "optimism", "polygon"] {
+            assert!(CompoundAdapter.supports_chain(chain));
+            for market in comet_markets(chain) {
+                assert!(market.parse::<Address>().is_ok(), "{} market {} is not an address", chain, market);
+            }
+        }
+        assert!(!CompoundAdapter.supports_chain("bsc"));
+    }
+}
@@ -0,0 +1,386 @@
+use super::registry::{ConnectorContext, ConnectorFactory, ACCOUNT_TYPE_WALLET};
+use super::{Balance, ExchangeConnector};
+use crate::concurrency::RateLimiter;
+use crate::domain::HOLDING_SOURCE_STAKED;
+use crate::entities::cosmos_chains;
+use crate::helpers::balance_normalization::normalize_token_balance;
+use async_trait::async_trait;
+use bitcoin::bech32::{self, Bech32, Hrp};
+use reqwest::Client;
+use sea_orm::{ColumnTrait, DatabaseConnection, EntityTrait, QueryFilter};
+use serde::Deserialize;
+use std::error::Error;
+
+/// Page size for delegation and unbonding queries (LCD default is 100)
+const LCD_PAGE_LIMIT: u32 = 1000;
+
+/// A Cosmos-SDK chain read through its LCD (REST) endpoint
+#[derive(Debug, Clone, PartialEq)]
+pub struct CosmosChain {
+    pub chain_id: String,
+    pub lcd_url: String,
+    pub bech32_prefix: String,
+    pub native_denom: String,
+    pub native_symbol: String,
+    pub native_decimals: u8,
+}
+
+impl From<cosmos_chains::Model> for CosmosChain {
+    fn from(row: cosmos_chains::Model) -> Self {
+        Self {
+            chain_id: row.chain_id,
+            lcd_url: row.lcd_url.trim_end_matches('/').to_string(),
+            bech32_prefix: row.bech32_prefix,
+            native_denom: row.native_denom,
+            native_symbol: row.native_symbol,
+            native_decimals: row.native_decimals.clamp(0, u8::MAX as i32) as u8,
+        }
+    }
+}
+
+impl CosmosChain {
+    /// Holding symbol of the native token (e.g. "ATOM-cosmoshub")
+    pub fn asset(&self) -> String {
+        format!("{}-{}", self.native_symbol, self.chain_id)
+    }
+}
+
+/// Decode a bech32 account address into its payload (20-byte key hash, or 32 bytes for
+/// module and interchain accounts)
+pub fn decode_address(address: &str) -> Result<Vec<u8>, String> {
+    let (_, data) = bech32::decode(address.trim()).map_err(|e| e.to_string())?;
+    if data.len() != 20 && data.len() != 32 {
+        return Err(format!("unexpected address length {}", data.len()));
+    }
+    Ok(data)
+}
+
+/// Encode an address payload under a chain's bech32 prefix.
+///
+/// Chains sharing coin type 118 (Cosmos Hub, Osmosis, Celestia) derive the same key from a
+/// mnemonic, so one address maps to the holder's account on each of them.
+pub fn address_on_chain(payload: &[u8], prefix: &str) -> Result<String, Box<dyn Error + Send + Sync>> {
+    Ok(bech32::encode::<Bech32>(Hrp::parse(prefix)?, payload)?)
+}
+
+// ── LCD response types ─────────────────────────────────────────────────────
+
+#[derive(Debug, Deserialize)]
+struct Coin {
+    denom: String,
+    /// Integer amount in base units; reward amounts are decimals (e.g. "1234.567000000000000000")
+    amount: String,
+}
+
+#[derive(Debug, Deserialize)]
+struct BalanceByDenomResponse {
+    balance: Option<Coin>,
+}
+
+#[derive(Debug, Deserialize)]
+struct DelegationsResponse {
+    delegation_responses: Vec<DelegationResponse>,
+}
+
+#[derive(Debug, Deserialize)]
+struct DelegationResponse {
+    balance: Coin,
+}
+
+#[derive(Debug, Deserialize)]
+struct UnbondingResponse {
+    unbonding_responses: Vec<UnbondingDelegation>,
+}
+
+#[derive(Debug, Deserialize)]
+struct UnbondingDelegation {
+    entries: Vec<UnbondingEntry>,
+}
+
+#[derive(Debug, Deserialize)]
+struct UnbondingEntry {
+    /// Amount still unbonding, in the staking denom
+    balance: String,
+}
+
+#[derive(Debug, Deserialize)]
+struct RewardsResponse {
+    #[serde(default)]
+    total: Vec<Coin>,
+}
+
+/// Whole base units of an LCD amount; fractional reward dust is dropped since only whole
+/// units can be withdrawn
+fn base_units(amount: &str) -> u128 {
+    amount.split('.').next().and_then(|whole| whole.parse().ok()).unwrap_or(0)
+}
+
+/// Native-denom base units staked: delegated, unbonding, and pending rewards
+fn staked_base_units(
+    delegations: &DelegationsResponse,
+    unbonding: &UnbondingResponse,
+    rewards: &RewardsResponse,
+    denom: &str,
+) -> u128 {
+    let delegated: u128 = delegations
+        .delegation_responses
+        .iter()
+        .filter(|d| d.balance.denom == denom)
+        .map(|d| base_units(&d.balance.amount))
+        .sum();
+    let unbonding: u128 = unbonding
+        .unbonding_responses
+        .iter()
+        .flat_map(|u| &u.entries)
+        .map(|e| base_units(&e.balance))
+        .sum();
+    let rewards: u128 = rewards
+        .total
+        .iter()
+        .filter(|c| c.denom == denom)
+        .map(|c| base_units(&c.amount))
+        .sum();
+    delegated + unbonding + rewards
+}
+
+// ── CosmosConnector ────────────────────────────────────────────────────────
+
+/// Cosmos-SDK wallet connector reading bank balances and staking positions over LCD.
+///
+/// For each chain the address is re-encoded under the chain's bech32 prefix, then:
+/// 1. `bank/v1beta1/balances/{address}/by_denom` — liquid native balance (spot)
+/// 2. `staking/v1beta1/delegations/{address}` — delegated stake
+/// 3. `staking/v1beta1/delegators/{address}/unbonding_delegations` — stake still unbonding
+/// 4. `distribution/v1beta1/delegators/{address}/rewards` — pending rewards
+///
+/// Stake, unbonding amounts and rewards are reported together as one [`HOLDING_SOURCE_STAKED`]
+/// holding per chain. Only the native staking denom is read; IBC and factory denoms are not.
+pub struct CosmosConnector {
+    payload: Vec<u8>,
+    chains: Vec<CosmosChain>,
+    http_client: Client,
+}
+
+impl CosmosConnector {
+    /// Create a new Cosmos connector.
+    ///
+    /// # Arguments
+    /// * `wallet_address` — Bech32 account address on any of the chains (e.g. `cosmos1...`)
+    /// * `chains` — Chains to read (from the `cosmos_chains` table)
+    pub fn new(wallet_address: &str, chains: Vec<CosmosChain>) -> Result<Self, Box<dyn Error + Send + Sync>> {
+        Ok(Self {
+            payload: decode_address(wallet_address)?,
+            chains,
+            http_client: Client::new(),
+        })
+    }
+
+    async fn get<T: for<'de> Deserialize<'de>>(
+        &self,
+        chain: &CosmosChain,
+        path: &str,
+        rate_limiter: &RateLimiter,
+    ) -> Result<T, Box<dyn Error + Send + Sync>> {
+        let _permit = rate_limiter.acquire().await?;
+        let url = format!("{}{}", chain.lcd_url, path);
+        Ok(self.http_client.get(&url).send().await?.error_for_status()?.json().await?)
+    }
+
+    /// Native balance and staking position on one chain
+    async fn fetch_chain_balances(
+        &self,
+        chain: &CosmosChain,
+        rate_limiter: &RateLimiter,
+    ) -> Result<Vec<Balance>, Box<dyn Error + Send + Sync>> {
+        let address = address_on_chain(&self.payload, &chain.bech32_prefix)?;
+
+        let bank: BalanceByDenomResponse = self
+            .get(
+                chain,
+                &format!("/cosmos/bank/v1beta1/balances/{}/by_denom?denom={}", address, chain.native_denom),
+                rate_limiter,
+            )
+            .await?;
+        let delegations: DelegationsResponse = self
+            .get(
+                chain,
+                &format!("/cosmos/staking/v1beta1/delegations/{}?pagination.limit={}", address, LCD_PAGE_LIMIT),
+                rate_limiter,
+            )
+            .await?;
+        let unbonding: UnbondingResponse = self
+            .get(
+                chain,
+                &format!(
+                    "/cosmos/staking/v1beta1/delegators/{}/unbonding_delegations?pagination.limit={}",
+                    address, LCD_PAGE_LIMIT
+                ),
+                rate_limiter,
+            )
+            .await?;
+        let rewards: RewardsResponse = self
+            .get(chain, &format!("/cosmos/distribution/v1beta1/delegators/{}/rewards", address), rate_limiter)
+            .await?;
+
+        let liquid = bank.balance.map(|c| base_units(&c.amount)).unwrap_or(0);
+        let staked = staked_base_units(&delegations, &unbonding, &rewards, &chain.native_denom);
+
+        let mut balances = Vec::new();
+        for (amount, source) in [(liquid, None), (staked, Some(HOLDING_SOURCE_STAKED))] {
+            if amount == 0 {
+                continue;
+            }
+            let raw = amount.to_string();
+            let normalized = normalize_token_balance(&raw, chain.native_decimals).unwrap_or(raw);
+            balances.push(Balance {
+                asset: chain.asset(),
+                quantity: normalized.clone(),
+                available: if source.is_some() { "0".to_string() } else { normalized.clone() },
+                frozen: if source.is_some() { normalized } else { "0".to_string() },
+                decimals: Some(chain.native_decimals),
+                holding_source: source.map(str::to_string),
+            });
+        }
+
+        tracing::debug!(
+            "Fetched {} on {} for {}: {} liquid, {} staked (base units)",
+            chain.native_symbol,
+            chain.chain_id,
+            address,
+            liquid,
+            staked
+        );
+
+        Ok(balances)
+    }
+}
+
+#[async_trait]
+impl ExchangeConnector for CosmosConnector {
+    async fn fetch_spot_balances(&self) -> Result<Vec<Balance>, Box<dyn Error + Send + Sync>> {
+        let rate_limiter = RateLimiter::cosmos_lcd();
+        let mut all_balances = Vec::new();
+
+        for chain in &self.chains {
+            match self.fetch_chain_balances(chain, &rate_limiter).await {
+                Ok(balances) => all_balances.extend(balances),
+                Err(e) => tracing::error!("Failed to fetch Cosmos balances on {}: {}", chain.chain_id, e),
+            }
+        }
+
+        tracing::info!(
+            "Fetched {} total balances across {} Cosmos chains",
+            all_balances.len(),
+            self.chains.len()
+        );
+
+        Ok(all_balances)
+    }
+}
+
+/// Builds [`CosmosConnector`]s for wallet accounts with `chain_family` "cosmos"
+pub struct CosmosFactory;
+
+#[async_trait]
+impl ConnectorFactory for CosmosFactory {
+    fn account_type(&self) -> &'static str {
+        ACCOUNT_TYPE_WALLET
+    }
+
+    fn name(&self) -> &'static str {
+        "cosmos"
+    }
+
+    fn requires_wallet_address(&self) -> bool {
+        true
+    }
+
+    fn validate_wallet_address(&self, address: &str) -> Result<(), String> {
+        decode_address(address).map(|_| ())
+    }
+
+    async fn create(
+        &self,
+        ctx: &ConnectorContext<'_>,
+    ) -> Result<Box<dyn ExchangeConnector>, Box<dyn Error + Send + Sync>> {
+        let wallet_address = ctx.wallet_address()?;
+        let all_chains = load_cosmos_chains_from_db(ctx.db).await?;
+
+        // Filter to the account's enabled_chains subset, or use all if unset
+        let enabled: Option<Vec<String>> = ctx
+            .account
+            .enabled_chains
+            .clone()
+            .and_then(|json| serde_json::from_value(json).ok());
+        let chains = match enabled {
+            Some(names) => all_chains.into_iter().filter(|c| names.contains(&c.chain_id)).collect(),
+            None => all_chains,
+        };
+        if chains.is_empty() {
+            return Err("No active Cosmos chains match the account's enabled_chains".into());
+        }
+
+        Ok(Box::new(CosmosConnector::new(&wallet_address, chains)?))
+    }
+}
+
+/// Load all active Cosmos chains from the database
+async fn load_cosmos_chains_from_db(
+    db: &DatabaseConnection,
+) -> Result<Vec<CosmosChain>, Box<dyn Error + Send + Sync>> {
+    let rows = cosmos_chains::Entity::find()
+        .filter(cosmos_chains::Column::IsActive.eq(true))
+        .all(db)
+        .await?;
+    tracing::info!("Loaded {} active Cosmos chains from DB", rows.len());
+    Ok(rows.into_iter().map(CosmosChain::from).collect())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_address_maps_across_chains() {
+        let payload = [7u8; 20];
+        let cosmos = address_on_chain(&payload, "cosmos").unwrap();
+        assert!(cosmos.starts_with("cosmos1"));
+
+        let osmo = address_on_chain(&decode_address(&cosmos).unwrap(), "osmo").unwrap();
+        assert!(osmo.starts_with("osmo1"));
+        assert_eq!(decode_address(&osmo).unwrap(), payload);
+
+        assert!(decode_address("0x742d35Cc6634C0532925a3b844Bc454e4438f44e").is_err());
+        assert!(decode_address(&format!("{}q", &cosmos[..cosmos.len() - 1])).is_err());
+        // Right checksum, wrong payload length
+        assert!(decode_address(&address_on_chain(&[7u8; 10], "cosmos").unwrap()).is_err());
+    }
+
+    #[test]
+    fn test_staked_base_units() {
+        let delegations: DelegationsResponse = serde_json::from_str(
+            r#"{"delegation_responses":[
+                {"delegation":{"validator_address":"cosmosvaloper1a","shares":"1000000.0"},"balance":{"denom":"uatom","amount":"1000000"}},
+                {"delegation":{"validator_address":"cosmosvaloper1b","shares":"250000.0"},"balance":{"denom":"uatom","amount":"250000"}}
+            ],"pagination":{"next_key":null,"total":"2"}}"#,
+        )
+        .unwrap();
+        let unbonding: UnbondingResponse = serde_json::from_str(
+            r#"{"unbonding_responses":[{"validator_address":"cosmosvaloper1a","entries":[
+                {"creation_height":"1","completion_time":"2024-01-22T00:00:00Z","initial_balance":"600000","balance":"500000"}
+            ]}]}"#,
+        )
+        .unwrap();
+        let rewards: RewardsResponse = serde_json::from_str(
+            r#"{"rewards":[],"total":[
+                {"denom":"uatom","amount":"1234.987000000000000000"},
+                {"denom":"ibc/27394FB092D2ECCD56123C74F36E4C1F926001CEADA9CA97EA622B25F41E5EB2","amount":"99.0"}
+            ]}"#,
+        )
+        .unwrap();
+
+        // 1.25 ATOM delegated + 0.5 unbonding + 1234 uatom rewards (dust dropped, IBC ignored)
+        assert_eq!(staked_base_units(&delegations, &unbonding, &rewards, "uatom"), 1_751_234);
+        assert_eq!(base_units("garbage"), 0);
+    }
+}
@@ -0,0 +1,345 @@
+use super::registry::{ConnectorContext, ConnectorFactory, ACCOUNT_TYPE_EXCHANGE};
+use super::{Balance, ExchangeConnector};
+use async_trait::async_trait;
+use chrono::Utc;
+use hmac::{Hmac, Mac};
+use reqwest;
+use rust_decimal::Decimal;
+use serde::Deserialize;
+use serde_json::{json, Value};
+use sha2::Sha256;
+use std::collections::BTreeMap;
+use std::error::Error;
+use std::str::FromStr;
+use tracing;
+
+type HmacSha256 = Hmac<Sha256>;
+
+const CRYPTOCOM_API_BASE_URL: &str = "https://api.crypto.com/exchange/v1/";
+
+/// Suffix appended to the underlying symbol for staked positions (e.g. "CRO.staked").
+///
+/// Staked CRO is locked and earns rewards, so it is reported as its own holding
+/// instead of being merged into liquid CRO.
+pub const STAKED_SUFFIX: &str = ".staked";
+
+/// Crypto.com Exchange API response wrapper
+#[derive(Debug, Deserialize)]
+struct CryptocomResponse<T> {
+    code: i64,
+    #[serde(default)]
+    message: Option<String>,
+    result: Option<T>,
+}
+
+/// `result` of list-style endpoints
+#[derive(Debug, Deserialize)]
+struct CryptocomData<T> {
+    #[serde(default = "Vec::new")]
+    data: Vec<T>,
+}
+
+/// One wallet entry from `private/user-balance`
+#[derive(Debug, Deserialize)]
+struct CryptocomUserBalance {
+    #[serde(default)]
+    position_balances: Vec<CryptocomPositionBalance>,
+}
+
+/// Per-instrument balance inside a wallet
+///
+/// NOTE: Only quantities are read; `market_value` is ignored so holdings stay quantity-only.
+#[derive(Debug, Deserialize)]
+struct CryptocomPositionBalance {
+    instrument_name: String,
+    quantity: String,
+    #[serde(default)]
+    reserved_qty: Option<String>,
+}
+
+/// One entry from `private/staking/get-staking-position`
+#[derive(Debug, Deserialize)]
+struct CryptocomStakingPosition {
+    underlying_inst_name: String,
+    staked_quantity: String,
+    #[serde(default)]
+    pending_unstaked_quantity: Option<String>,
+}
+
+/// Crypto.com Exchange connector for read-only access
+///
+/// Crypto.com API keys have no passphrase; only the key and secret are required.
+pub struct CryptocomConnector {
+    api_key: String,
+    api_secret: String,
+    client: reqwest::Client,
+}
+
+impl CryptocomConnector {
+    /// Create a new Crypto.com connector with API credentials
+    pub fn new(api_key: String, api_secret: String) -> Self {
+        Self {
+            api_key,
+            api_secret,
+            client: reqwest::Client::new(),
+        }
+    }
+
+    /// Generate the request digital signature
+    ///
+    /// `sig = hex(HMAC-SHA256(method + id + api_key + param_string + nonce, secret))`
+    fn generate_signature(&self, method: &str, id: i64, params: &Value, nonce: i64) -> String {
+        let payload = format!("{}{}{}{}{}", method, id, self.api_key, param_string(params), nonce);
+
+        let mut mac = HmacSha256::new_from_slice(self.api_secret.as_bytes())
+            .expect("HMAC can take key of any size");
+        mac.update(payload.as_bytes());
+
+        mac.finalize()
+            .into_bytes()
+            .iter()
+            .map(|b| format!("{:02x}", b))
+            .collect()
+    }
+
+    /// Make a signed private API call
+    async fn private_request<T: for<'de> Deserialize<'de>>(
+        &self,
+        method: &str,
+        params: Value,
+    ) -> Result<T, Box<dyn Error + Send + Sync>> {
+        let nonce = Utc::now().timestamp_millis();
+        let id = nonce;
+        let sig = self.generate_signature(method, id, &params, nonce);
+
+        let body = json!({
+            "id": id,
+            "method": method,
+            "api_key": self.api_key,
+            "params": params,
+            "nonce": nonce,
+            "sig": sig,
+        });
+
+        let url = format!("{}{}", CRYPTOCOM_API_BASE_URL, method);
+
+        tracing::debug!("Crypto.com API Request: POST {}", url);
+
+        let response = self
+            .client
+            .post(&url)
+            .header("Content-Type", "application/json")
+            .json(&body)
+            .send()
+            .await?;
+
+        let status = response.status();
+        let body = response.text().await?;
+
+        tracing::debug!("Crypto.com API Response Status: {}", status);
+        tracing::debug!("Crypto.com API Response Body: {}", body);
+
+        if !status.is_success() {
+            return Err(format!("Crypto.com API error: {} - {}", status, body).into());
+        }
+
+        let response: CryptocomResponse<T> = serde_json::from_str(&body).map_err(|e| {
+            tracing::error!("Failed to parse Crypto.com response: {}", e);
+            format!("Failed to parse Crypto.com response: {}", e)
+        })?;
+
+        if response.code != 0 {
+            return Err(format!(
+                "Crypto.com API error: {} - {}",
+                response.code,
+                response.message.unwrap_or_default()
+            )
+            .into());
+        }
+
+        response
+            .result
+            .ok_or_else(|| format!("Crypto.com API returned no result for {}", method).into())
+    }
+}
+
+/// Build the parameter string used in the signature payload.
+///
+/// Object keys are sorted and concatenated as `key + value`; arrays are flattened
+/// element by element; `null` renders as the string "null".
+fn param_string(value: &Value) -> String {
+    match value {
+        Value::Object(map) => {
+            let sorted: BTreeMap<&String, &Value> = map.iter().collect();
+            sorted
+                .into_iter()
+                .map(|(k, v)| format!("{}{}", k, param_string(v)))
+                .collect()
+        }
+        Value::Array(items) => items.iter().map(param_string).collect(),
+        Value::String(s) => s.clone(),
+        Value::Null => "null".to_string(),
+        other => other.to_string(),
+    }
+}
+
+/// Convert wallet and staking entries into balances.
+///
+/// Liquid balances keep the instrument symbol; staked positions become a separate
+/// `<SYMBOL>.staked` holding (pending unstakes are still locked, so they are counted
+/// as staked and reported as frozen). Zero balances are dropped; results are sorted.
+fn build_balances(
+    positions: Vec<CryptocomPositionBalance>,
+    staking: Vec<CryptocomStakingPosition>,
+) -> Vec<Balance> {
+    let parse = |v: &str| Decimal::from_str(v).unwrap_or(Decimal::ZERO);
+    let mut totals: BTreeMap<String, (Decimal, Decimal)> = BTreeMap::new();
+
+    for position in positions {
+        let quantity = parse(&position.quantity);
+        let reserved = position.reserved_qty.as_deref().map(parse).unwrap_or(Decimal::ZERO);
+
+        let entry = totals
+            .entry(position.instrument_name.to_uppercase())
+            .or_insert((Decimal::ZERO, Decimal::ZERO));
+        entry.0 += quantity;
+        entry.1 += reserved;
+    }
+
+    for position in staking {
+        let staked = parse(&position.staked_quantity);
+        let unstaking = position.pending_unstaked_quantity.as_deref().map(parse).unwrap_or(Decimal::ZERO);
+
+        let asset = format!("{}{}", position.underlying_inst_name.to_uppercase(), STAKED_SUFFIX);
+        let entry = totals.entry(asset).or_insert((Decimal::ZERO, Decimal::ZERO));
+        entry.0 += staked + unstaking;
+        entry.1 += unstaking;
+    }
+
+    totals
+        .into_iter()
+        .filter(|(_, (quantity, _))| *quantity > Decimal::ZERO)
+        .map(|(asset, (quantity, frozen))| Balance {
+            asset,
+            quantity: quantity.normalize().to_string(),
+            available: (quantity - frozen).normalize().to_string(),
+            frozen: frozen.normalize().to_string(),
+            decimals: None, // Crypto.com doesn't provide decimal information
+            holding_source: None,
+        })
+        .collect()
+}
+
+#[async_trait]
+impl ExchangeConnector for CryptocomConnector {
+    async fn fetch_spot_balances(&self) -> Result<Vec<Balance>, Box<dyn Error + Send + Sync>> {
+        let wallets: CryptocomData<CryptocomUserBalance> =
+            self.private_request("private/user-balance", json!({})).await?;
+
+        let positions: Vec<CryptocomPositionBalance> = wallets
+            .data
+            .into_iter()
+            .flat_map(|w| w.position_balances)
+            .collect();
+
+        // Staking needs its own key permission; fall back to liquid balances without it
+        let staking = match self
+            .private_request::<CryptocomData<CryptocomStakingPosition>>(
+                "private/staking/get-staking-position",
+                json!({}),
+            )
+            .await
+        {
+            Ok(result) => result.data,
+            Err(e) => {
+                tracing::warn!("Failed to fetch Crypto.com staking positions: {}", e);
+                Vec::new()
+            }
+        };
+
+        let balances = build_balances(positions, staking);
+
+        tracing::info!("Fetched {} balances from Crypto.com", balances.len());
+        Ok(balances)
+    }
+}
+
+/// Builds [`CryptocomConnector`]s for Crypto.com accounts
+pub struct CryptocomFactory;
+
+#[async_trait]
+impl ConnectorFactory for CryptocomFactory {
+    fn account_type(&self) -> &'static str {
+        ACCOUNT_TYPE_EXCHANGE
+    }
+
+    fn name(&self) -> &'static str {
+        "cryptocom"
+    }
+
+    async fn create(
+        &self,
+        ctx: &ConnectorContext<'_>,
+    ) -> Result<Box<dyn ExchangeConnector>, Box<dyn Error + Send + Sync>> {
+        let (api_key, api_secret) = ctx.api_credentials()?;
+        Ok(Box::new(CryptocomConnector::new(api_key, api_secret)))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_param_string_sorts_and_flattens() {
+        let params = json!({
+            "instrument_name": "CRO_USD",
+            "count": 10,
+            "filters": [{"b": "2", "a": "1"}, "x"],
+        });
+        assert_eq!(param_string(&params), "count10filtersa1b2xinstrument_nameCRO_USD");
+        assert_eq!(param_string(&json!({})), "");
+    }
+
+    #[test]
+    fn test_signature_generation() {
+        let connector = CryptocomConnector::new("test-api-key".to_string(), "test-secret".to_string());
+
+        let signature = connector.generate_signature("private/user-balance", 1, &json!({}), 1704067200000);
+        let again = connector.generate_signature("private/user-balance", 1, &json!({}), 1704067200000);
+
+        assert_eq!(signature.len(), 64);
+        assert_eq!(signature, again);
+    }
+
+    #[test]
+    fn test_staked_cro_is_a_separate_holding() {
+        let balances = build_balances(
+            vec![
+                CryptocomPositionBalance {
+                    instrument_name: "CRO".to_string(),
+                    quantity: "1000".to_string(),
+                    reserved_qty: Some("100".to_string()),
+                },
+                CryptocomPositionBalance {
+                    instrument_name: "USD".to_string(),
+                    quantity: "0".to_string(),
+                    reserved_qty: None,
+                },
+            ],
+            vec![CryptocomStakingPosition {
+                underlying_inst_name: "CRO".to_string(),
+                staked_quantity: "5000".to_string(),
+                pending_unstaked_quantity: Some("500".to_string()),
+            }],
+        );
+
+        assert_eq!(balances.len(), 2);
+        assert_eq!(balances[0].asset, "CRO");
+        assert_eq!(balances[0].quantity, "1000");
+        assert_eq!(balances[0].available, "900");
+        assert_eq!(balances[1].asset, "CRO.staked");
+        assert_eq!(balances[1].quantity, "5500");
+        assert_eq!(balances[1].frozen, "500");
+    }
+}
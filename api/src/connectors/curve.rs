@@ -0,0 +1,304 @@
+//! Curve LP positions of EVM wallets, including LP tokens staked through Convex.
+//!
+//! Pools are configured in the `curve_pools` table (LP token, pool and optional Convex reward
+//! contract per chain). The wallet's LP amount is its LP token balance plus its balance in the
+//! Convex reward contract. That amount is valued with the pool's `get_virtual_price` and split
+//! across the pool's coins by their share of the pool balances, so a 3CRV position is reported
+//! as DAI/USDC/USDT [`HOLDING_SOURCE_LP`] holdings instead of an unpriced LP symbol.
+
+use super::defi::{DefiAdapter, DefiContext, DefiPositions};
+use super::evm::EvmChain;
+use super::Balance;
+use crate::domain::HOLDING_SOURCE_LP;
+use crate::entities::curve_pools;
+use crate::helpers::balance_normalization::normalize_token_balance;
+use alloy::{
+    primitives::{Address, U256},
+    providers::{Provider, ProviderBuilder},
+    sol,
+};
+use async_trait::async_trait;
+use rust_decimal::Decimal;
+use sea_orm::{ColumnTrait, DatabaseConnection, EntityTrait, QueryFilter};
+use std::collections::{BTreeMap, HashSet};
+use std::error::Error;
+use std::str::FromStr;
+
+/// Protocol name used in the `defi_protocols` account setting
+pub const DEFI_PROTOCOL_CURVE: &str = "curve";
+
+/// Placeholder Curve uses for the chain's native coin in `coins(i)`
+const NATIVE_COIN: &str = "0xEeeeeEeeeEeEeeEeEeEeeEEEeeeeEeeeeeeeEEeE";
+
+/// Upper bound on the number of coins in a pool
+const MAX_POOL_COINS: u64 = 8;
+
+sol! {
+    #[sol(rpc)]
+    contract CurvePoolContract {
+        function coins(uint256 i) external view returns (address);
+        function balances(uint256 i) external view returns (uint256);
+        function get_virtual_price() external view returns (uint256);
+    }
+
+    /// Pools deployed before the `uint256` index signature
+    #[sol(rpc)]
+    contract CurveLegacyPool {
+        function coins(int128 i) external view returns (address);
+        function balances(int128 i) external view returns (uint256);
+    }
+
+    #[sol(rpc)]
+    contract CurveToken {
+        function balanceOf(address owner) external view returns (uint256);
+        function decimals() external view returns (uint8);
+        function symbol() external view returns (string);
+    }
+}
+
+/// A Curve pool from the `curve_pools` table
+#[derive(Debug, Clone, PartialEq)]
+pub struct CurvePool {
+    pub name: String,
+    pub pool_address: String,
+    pub lp_token_address: String,
+    /// Convex reward contract holding staked LP tokens, if any
+    pub convex_reward_pool: Option<String>,
+}
+
+/// Load a chain's active pools from `curve_pools`, with lowercase addresses.
+///
+/// Falls back to no pools on a DB error, so LP tokens are simply not resolved for that sync.
+async fn load_curve_pools(db: &DatabaseConnection, chain: &str) -> Vec<CurvePool> {
+    match curve_pools::Entity::find()
+        .filter(curve_pools::Column::Chain.eq(chain))
+        .filter(curve_pools::Column::IsActive.eq(true))
+        .all(db)
+        .await
+    {
+        Ok(rows) => rows
+            .into_iter()
+            .map(|row| CurvePool {
+                name: row.name,
+                pool_address: row.pool_address.to_lowercase(),
+                lp_token_address: row.lp_token_address.to_lowercase(),
+                convex_reward_pool: row.convex_reward_pool.map(|a| a.to_lowercase()),
+            })
+            .collect(),
+        Err(e) => {
+            tracing::warn!("Failed to load Curve pools on {} from DB: {}", chain, e);
+            Vec::new()
+        }
+    }
+}
+
+/// [`DefiAdapter`] for the pools in the `curve_pools` table
+pub struct CurveAdapter;
+
+#[async_trait]
+impl DefiAdapter for CurveAdapter {
+    fn protocol(&self) -> &'static str {
+        DEFI_PROTOCOL_CURVE
+    }
+
+    /// Pools are configured per chain, so any chain may have some
+    fn supports_chain(&self, _chain: &str) -> bool {
+        true
+    }
+
+    fn aggregator_ids(&self) -> &'static [&'static str] {
+        &["curve", "convex"]
+    }
+
+    /// LP tokens and Convex reward contracts of the chain's pools
+    async fn position_tokens(&self, db: &DatabaseConnection, chain: &EvmChain) -> HashSet<String> {
+        load_curve_pools(db, chain.name())
+            .await
+            .into_iter()
+            .flat_map(|pool| std::iter::once(pool.lp_token_address).chain(pool.convex_reward_pool))
+            .collect()
+    }
+
+    async fn fetch_positions(
+        &self,
+        ctx: &DefiContext<'_>,
+        wallet: Address,
+    ) -> Result<DefiPositions, Box<dyn Error + Send + Sync>> {
+        let pools = load_curve_pools(ctx.db, ctx.chain.name()).await;
+        if pools.is_empty() {
+            return Ok(DefiPositions::default());
+        }
+        let balances = fetch_curve_positions(ctx.chain, ctx.chain.rpc_url(), wallet, &pools).await?;
+        Ok(DefiPositions { balances, positions: Vec::new() })
+    }
+}
+
+/// One coin of a pool with its normalized pool balance
+#[derive(Debug, Clone, PartialEq)]
+struct PoolCoin {
+    symbol: String,
+    decimals: u8,
+    balance: Decimal,
+}
+
+fn to_decimal(raw: U256, decimals: u8) -> Result<Decimal, Box<dyn Error + Send + Sync>> {
+    Ok(Decimal::from_str(&normalize_token_balance(&raw.to_string(), decimals)?)?)
+}
+
+/// Underlying amounts of an LP position worth `lp_value` (LP amount × virtual price), split by
+/// each coin's share of the pool balances
+fn split_lp_value(lp_value: Decimal, coins: &[PoolCoin]) -> Vec<(String, u8, Decimal)> {
+    let total: Decimal = coins.iter().map(|c| c.balance).sum();
+    if total.is_zero() || lp_value.is_zero() {
+        return Vec::new();
+    }
+    coins
+        .iter()
+        .filter(|c| !c.balance.is_zero())
+        .map(|c| {
+            let amount = (lp_value * c.balance / total).round_dp(u32::from(c.decimals)).normalize();
+            (c.symbol.clone(), c.decimals, amount)
+        })
+        .collect()
+}
+
+/// Coins of a pool with their normalized balances, trying the legacy `int128` signature when
+/// the pool does not answer `coins(uint256)`
+async fn pool_coins<P: Provider + Clone>(
+    provider: &P,
+    chain: &EvmChain,
+    pool_address: Address,
+) -> Result<Vec<PoolCoin>, Box<dyn Error + Send + Sync>> {
+    let pool = CurvePoolContract::new(pool_address, provider.clone());
+    let legacy = CurveLegacyPool::new(pool_address, provider.clone());
+    let use_legacy = pool.coins(U256::ZERO).call().await.is_err();
+
+    let mut coins = Vec::new();
+    for i in 0..MAX_POOL_COINS {
+        let coin = if use_legacy {
+            legacy.coins(i as i128).call().await
+        } else {
+            pool.coins(U256::from(i)).call().await
+        };
+        // Reading past the last coin reverts
+        let coin = match coin {
+            Ok(address) => address,
+            Err(e) if i == 0 => return Err(format!("Curve pool has no readable coins: {}", e).into()),
+            Err(_) => break,
+        };
+        let raw_balance = if use_legacy {
+            legacy.balances(i as i128).call().await?
+        } else {
+            pool.balances(U256::from(i)).call().await?
+        };
+
+        let (symbol, decimals) = if coin == NATIVE_COIN.parse::<Address>()? {
+            (chain.native_symbol().to_string(), 18)
+        } else {
+            let token = CurveToken::new(coin, provider.clone());
+            (token.symbol().call().await?, token.decimals().call().await?)
+        };
+        coins.push(PoolCoin {
+            symbol,
+            decimals,
+            balance: to_decimal(raw_balance, decimals)?,
+        });
+    }
+    Ok(coins)
+}
+
+/// Underlying amounts of `wallet`'s position in one pool; empty when the wallet holds no LP
+async fn pool_position<P: Provider + Clone>(
+    provider: &P,
+    chain: &EvmChain,
+    pool: &CurvePool,
+    wallet: Address,
+) -> Result<Vec<(String, u8, Decimal)>, Box<dyn Error + Send + Sync>> {
+    let mut lp_raw = CurveToken::new(pool.lp_token_address.parse()?, provider.clone())
+        .balanceOf(wallet)
+        .call()
+        .await?;
+    if let Some(reward_pool) = &pool.convex_reward_pool {
+        lp_raw += CurveToken::new(reward_pool.parse()?, provider.clone())
+            .balanceOf(wallet)
+            .call()
+            .await?;
+    }
+    if lp_raw.is_zero() {
+        return Ok(Vec::new());
+    }
+
+    let pool_address: Address = pool.pool_address.parse()?;
+    let virtual_price = CurvePoolContract::new(pool_address, provider.clone())
+        .get_virtual_price()
+        .call()
+        .await?;
+    let lp_value = to_decimal(lp_raw, 18)? * to_decimal(virtual_price, 18)?;
+    let coins = pool_coins(provider, chain, pool_address).await?;
+    Ok(split_lp_value(lp_value, &coins))
+}
+
+/// Underlying coins of `wallet`'s LP positions in `pools` on `chain`, summed per coin.
+///
+/// A pool that fails to read is logged and left out; the others are still reported.
+pub async fn fetch_curve_positions(
+    chain: &EvmChain,
+    rpc_url: &str,
+    wallet: Address,
+    pools: &[CurvePool],
+) -> Result<Vec<Balance>, Box<dyn Error + Send + Sync>> {
+    let provider = ProviderBuilder::new().connect_http(rpc_url.parse()?);
+
+    let mut totals: BTreeMap<String, (u8, Decimal)> = BTreeMap::new();
+    for pool in pools {
+        match pool_position(&provider, chain, pool, wallet).await {
+            Ok(amounts) => {
+                for (symbol, decimals, amount) in amounts {
+                    totals.entry(symbol).or_insert((decimals, Decimal::ZERO)).1 += amount;
+                }
+            }
+            Err(e) => tracing::error!("Failed to read Curve pool {} on {}: {}", pool.name, chain.name(), e),
+        }
+    }
+
+    Ok(totals
+        .into_iter()
+        .filter(|(_, (_, amount))| !amount.is_zero())
+        .map(|(symbol, (decimals, amount))| Balance {
+            asset: format!("{}-{}", symbol, chain.name()),
+            quantity: amount.to_string(),
+            available: amount.to_string(),
+            frozen: "0".to_string(),
+            decimals: Some(decimals),
+            holding_source: Some(HOLDING_SOURCE_LP.to_string()),
+        })
+        .collect())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_split_lp_value() {
+        let coin = |symbol: &str, decimals, balance| PoolCoin {
+            symbol: symbol.to_string(),
+            decimals,
+            balance: Decimal::from(balance),
+        };
+        // 3pool-like: 50 DAI / 30 USDC / 20 USDT; coins with an empty balance are dropped
+        let coins = vec![coin("DAI", 18, 50), coin("USDC", 6, 30), coin("USDT", 6, 20), coin("FRAX", 18, 0)];
+        // 10 LP at a virtual price of 1.03
+        let lp_value = Decimal::from(10) * Decimal::new(103, 2);
+        let split = split_lp_value(lp_value, &coins);
+
+        assert_eq!(split.len(), 3);
+        assert_eq!(split[0], ("DAI".to_string(), 18, Decimal::new(515, 2)));
+        assert_eq!(split[1], ("USDC".to_string(), 6, Decimal::new(309, 2)));
+        assert_eq!(split[2], ("USDT".to_string(), 6, Decimal::new(206, 2)));
+        assert_eq!(split.iter().map(|(_, _, a)| *a).sum::<Decimal>(), lp_value);
+
+        assert!(split_lp_value(Decimal::ZERO, &coins).is_empty());
+        assert!(split_lp_value(lp_value, &[]).is_empty());
+    }
+}
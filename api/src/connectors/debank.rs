@@ -0,0 +1,242 @@
+//! DeBank Cloud as an aggregated DeFi position source for EVM wallets.
+//!
+//! One `all_complex_protocol_list` call returns a wallet's positions in every protocol DeBank
+//! indexes, across the account's chains. Supplied, staked and LP tokens become holdings of the
+//! underlying token under the usual "<symbol>-<chain>" symbol, debt becomes negative
+//! [`HOLDING_SOURCE_BORROWED`] holdings and unclaimed rewards [`HOLDING_SOURCE_EARN`] holdings.
+//! Protocols read natively by an enabled [`DefiAdapter`](super::defi::DefiAdapter) are skipped,
+//! so their positions are not counted twice. Plain wallet tokens are not read from DeBank; they
+//! come from the EVM connector.
+//!
+//! Requests are authenticated with `DEBANK_API_KEY` (the DeBank Cloud access key).
+
+use super::evm::EvmChain;
+use super::Balance;
+use crate::domain::{
+    HOLDING_SOURCE_BORROWED, HOLDING_SOURCE_EARN, HOLDING_SOURCE_LP, HOLDING_SOURCE_PERP, HOLDING_SOURCE_STAKED,
+    HOLDING_SOURCE_SUPPLIED,
+};
+use reqwest::Client;
+use rust_decimal::Decimal;
+use serde::Deserialize;
+use std::collections::HashSet;
+use std::error::Error;
+
+/// Aggregator name used in the `position_aggregator` account setting
+pub const POSITION_AGGREGATOR_DEBANK: &str = "debank";
+
+/// DeBank Cloud OpenAPI
+pub const DEBANK_API_URL: &str = "https://pro-openapi.debank.com/v1";
+
+/// DeBank chain ID of a chain from the `evm_chains` table
+pub fn debank_chain_id(chain_name: &str) -> Option<&'static str> {
+    match chain_name {
+        "ethereum" => Some("eth"),
+        "optimism" => Some("op"),
+        "bsc" => Some("bsc"),
+        "polygon" => Some("matic"),
+        "zksync" => Some("era"),
+        "base" => Some("base"),
+        "arbitrum" => Some("arb"),
+        "avalanche" => Some("avax"),
+        "linea" => Some("linea"),
+        "scroll" => Some("scrl"),
+        _ => None,
+    }
+}
+
+#[derive(Debug, Deserialize)]
+struct ApiProtocol {
+    /// Chain-prefixed protocol ID, e.g. "arb_aave3" ("aave3" on Ethereum)
+    id: String,
+    chain: String,
+    #[serde(default)]
+    portfolio_item_list: Vec<ApiPortfolioItem>,
+}
+
+#[derive(Debug, Deserialize)]
+struct ApiPortfolioItem {
+    /// Position kind, e.g. "Lending", "Liquidity Pool", "Staked"
+    name: String,
+    #[serde(default)]
+    detail: ApiDetail,
+}
+
+#[derive(Debug, Default, Deserialize)]
+struct ApiDetail {
+    #[serde(default)]
+    supply_token_list: Vec<ApiToken>,
+    #[serde(default)]
+    borrow_token_list: Vec<ApiToken>,
+    #[serde(default)]
+    reward_token_list: Vec<ApiToken>,
+    /// Tokens of locked, vesting and other single-list positions
+    #[serde(default)]
+    token_list: Vec<ApiToken>,
+}
+
+#[derive(Debug, Deserialize)]
+struct ApiToken {
+    symbol: String,
+    decimals: Option<u8>,
+    amount: f64,
+}
+
+/// Holding source of the tokens a position holds, by DeBank's position kind
+fn position_source(kind: &str) -> &'static str {
+    match kind {
+        "Lending" => HOLDING_SOURCE_SUPPLIED,
+        "Liquidity Pool" | "Farming" | "Leveraged Farming" => HOLDING_SOURCE_LP,
+        "Staked" | "Locked" | "Vesting" | "Governance" => HOLDING_SOURCE_STAKED,
+        "Perpetuals" => HOLDING_SOURCE_PERP,
+        _ => HOLDING_SOURCE_EARN,
+    }
+}
+
+/// Protocol ID without the chain prefix ("arb_aave3" -> "aave3")
+fn protocol_key(protocol: &ApiProtocol) -> &str {
+    protocol
+        .id
+        .strip_prefix(protocol.chain.as_str())
+        .and_then(|id| id.strip_prefix('_'))
+        .unwrap_or(&protocol.id)
+}
+
+fn token_balance(token: &ApiToken, chain: &str, source: &str, negative: bool) -> Option<Balance> {
+    let amount = Decimal::try_from(token.amount).ok()?.normalize();
+    if amount.is_zero() || token.symbol.is_empty() {
+        return None;
+    }
+    let quantity = if negative { -amount } else { amount }.to_string();
+    Some(Balance {
+        asset: format!("{}-{}", token.symbol, chain),
+        quantity: quantity.clone(),
+        available: quantity,
+        frozen: "0".to_string(),
+        decimals: token.decimals,
+        holding_source: Some(source.to_string()),
+    })
+}
+
+/// Holdings of the protocol positions on `chains`, leaving out protocols whose chain-less ID is
+/// in `skip_protocols`
+fn debank_balances(protocols: Vec<ApiProtocol>, chains: &[EvmChain], skip_protocols: &HashSet<&str>) -> Vec<Balance> {
+    let mut balances = Vec::new();
+    for protocol in protocols {
+        if skip_protocols.contains(protocol_key(&protocol)) {
+            continue;
+        }
+        let Some(chain) = chains.iter().find(|c| debank_chain_id(c.name()) == Some(protocol.chain.as_str())) else {
+            continue;
+        };
+        for item in &protocol.portfolio_item_list {
+            let source = position_source(&item.name);
+            let detail = &item.detail;
+            let held = detail.supply_token_list.iter().chain(&detail.token_list);
+            balances.extend(held.filter_map(|t| token_balance(t, chain.name(), source, false)));
+            balances.extend(
+                detail
+                    .borrow_token_list
+                    .iter()
+                    .filter_map(|t| token_balance(t, chain.name(), HOLDING_SOURCE_BORROWED, true)),
+            );
+            balances.extend(
+                detail
+                    .reward_token_list
+                    .iter()
+                    .filter_map(|t| token_balance(t, chain.name(), HOLDING_SOURCE_EARN, false)),
+            );
+        }
+    }
+    balances
+}
+
+/// `wallet`'s DeFi positions on `chains` from DeBank, except in `skip_protocols` (DeBank
+/// protocol IDs without the chain prefix)
+pub async fn fetch_debank_positions(
+    client: &Client,
+    api_key: &str,
+    wallet: &str,
+    chains: &[EvmChain],
+    skip_protocols: &HashSet<&str>,
+) -> Result<Vec<Balance>, Box<dyn Error + Send + Sync>> {
+    let chain_ids: Vec<&str> = chains.iter().filter_map(|c| debank_chain_id(c.name())).collect();
+    if chain_ids.is_empty() {
+        return Ok(Vec::new());
+    }
+
+    let protocols: Vec<ApiProtocol> = client
+        .get(format!("{}/user/all_complex_protocol_list", DEBANK_API_URL))
+        .header("AccessKey", api_key)
+        .query(&[("id", wallet.to_lowercase()), ("chain_ids", chain_ids.join(","))])
+        .send()
+        .await?
+        .error_for_status()?
+        .json()
+        .await?;
+    Ok(debank_balances(protocols, chains, skip_protocols))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_debank_balances() {
+        let protocols: Vec<ApiProtocol> = serde_json::from_value(serde_json::json!([
+            {
+                "id": "arb_radiant2",
+                "chain": "arb",
+                "portfolio_item_list": [{
+                    "name": "Lending",
+                    "detail": {
+                        "supply_token_list": [{ "symbol": "WETH", "decimals": 18, "amount": 1.5 }],
+                        "borrow_token_list": [{ "symbol": "USDC", "decimals": 6, "amount": 1200.25 }],
+                        "reward_token_list": [{ "symbol": "RDNT", "decimals": 18, "amount": 0.0 }]
+                    }
+                }]
+            },
+            {
+                "id": "uniswap3",
+                "chain": "eth",
+                "portfolio_item_list": [{
+                    "name": "Liquidity Pool",
+                    "detail": { "supply_token_list": [{ "symbol": "DAI", "amount": 500 }] }
+                }]
+            },
+            // Read natively by the Aave adapter
+            {
+                "id": "arb_aave3",
+                "chain": "arb",
+                "portfolio_item_list": [{
+                    "name": "Lending",
+                    "detail": { "supply_token_list": [{ "symbol": "USDT", "amount": 10 }] }
+                }]
+            },
+            // Chain not enabled for the account
+            { "id": "matic_quickswap", "chain": "matic", "portfolio_item_list": [] }
+        ]))
+        .unwrap();
+        let chains = vec![
+            EvmChain::new("ethereum", "https://eth.example", "ETH"),
+            EvmChain::new("arbitrum", "https://arb.example", "ETH"),
+        ];
+
+        let balances = debank_balances(protocols, &chains, &HashSet::from(["aave3"]));
+        let summary: Vec<(&str, &str, &str)> = balances
+            .iter()
+            .map(|b| (b.asset.as_str(), b.quantity.as_str(), b.holding_source.as_deref().unwrap()))
+            .collect();
+
+        assert_eq!(
+            summary,
+            vec![
+                ("WETH-arbitrum", "1.5", "supplied"),
+                ("USDC-arbitrum", "-1200.25", "borrowed"),
+                ("DAI-ethereum", "500", "lp"),
+            ]
+        );
+        assert_eq!(balances[1].decimals, Some(6));
+        assert_eq!(debank_chain_id("polygon"), Some("matic"));
+    }
+}
@@ -0,0 +1,255 @@
+//! Registry of DeFi protocol adapters for EVM wallets.
+//!
+//! Account sync reads the wallet's raw token balances through the EVM connector, then runs
+//! the adapter of every protocol in the account's `defi_protocols` setting on each chain the
+//! adapter supports, for every address of the account. Adapter balances (supplied, LP,
+//! fixed-yield, ...) are added to the raw balances and adapter positions are stored next to
+//! the connector's. Adding a protocol means implementing [`DefiAdapter`] next to its reader and
+//! registering it in [`DefiRegistry::builtin`].
+//!
+//! An account may also name a `position_aggregator` (DeBank, see [`super::debank`]) that reports
+//! the positions in every other protocol with one call per address.
+
+use super::debank::{fetch_debank_positions, POSITION_AGGREGATOR_DEBANK};
+use super::evm::{account_chains, EvmChain};
+use super::registry::{ConnectorContext, ACCOUNT_TYPE_WALLET, DEFAULT_WALLET_KIND};
+use super::{Balance, Position};
+use crate::domain::AccountSettings;
+use crate::entities::accounts;
+use alloy::primitives::Address;
+use async_trait::async_trait;
+use futures::future::join_all;
+use sea_orm::DatabaseConnection;
+use std::collections::{HashMap, HashSet};
+use std::error::Error;
+use std::sync::OnceLock;
+
+/// What an adapter gets to read one chain
+pub struct DefiContext<'a> {
+    pub db: &'a DatabaseConnection,
+    pub chain: &'a EvmChain,
+}
+
+/// Balances and open positions a protocol reports for one wallet on one chain
+#[derive(Debug, Clone, Default)]
+pub struct DefiPositions {
+    pub balances: Vec<Balance>,
+    pub positions: Vec<Position>,
+}
+
+/// Reads a wallet's positions in one DeFi protocol
+#[async_trait]
+pub trait DefiAdapter: Send + Sync {
+    /// Protocol name used in the `defi_protocols` account setting (e.g. "aave_v3")
+    fn protocol(&self) -> &'static str;
+
+    /// Whether the protocol can be read on a chain from the `evm_chains` table
+    fn supports_chain(&self, chain: &str) -> bool;
+
+    /// Protocol IDs (without the chain prefix) a position aggregator reports this protocol
+    /// under, so its positions are not counted twice
+    fn aggregator_ids(&self) -> &'static [&'static str] {
+        &[]
+    }
+
+    /// Lowercase token contracts on `chain` whose value the adapter reports itself (LP or
+    /// PT/YT tokens); the EVM connector leaves them out of the raw balances
+    async fn position_tokens(&self, _db: &DatabaseConnection, _chain: &EvmChain) -> HashSet<String> {
+        HashSet::new()
+    }
+
+    /// Balances and positions of `wallet` on the context's chain
+    async fn fetch_positions(
+        &self,
+        ctx: &DefiContext<'_>,
+        wallet: Address,
+    ) -> Result<DefiPositions, Box<dyn Error + Send + Sync>>;
+}
+
+/// DeFi adapters keyed by protocol name
+#[derive(Default)]
+pub struct DefiRegistry {
+    adapters: Vec<Box<dyn DefiAdapter>>,
+}
+
+impl DefiRegistry {
+    /// Create an empty registry
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Register an adapter, replacing any adapter already registered for the same protocol
+    pub fn register(mut self, adapter: impl DefiAdapter + 'static) -> Self {
+        self.adapters.retain(|a| a.protocol() != adapter.protocol());
+        self.adapters.push(Box::new(adapter));
+        self
+    }
+
+    /// Registry of all adapters shipped with the backend
+    pub fn builtin() -> &'static DefiRegistry {
+        static BUILTIN: OnceLock<DefiRegistry> = OnceLock::new();
+        BUILTIN.get_or_init(|| {
+            DefiRegistry::new()
+                .register(super::aave::AaveAdapter)
+                .register(super::compound::CompoundAdapter)
+                .register(super::curve::CurveAdapter)
+                .register(super::gmx::GmxAdapter)
+                .register(super::pendle::PendleAdapter)
+                .register(super::vaults::VaultAdapter)
+        })
+    }
+
+    /// Find the adapter for a protocol name (case-insensitive)
+    pub fn find(&self, protocol: &str) -> Option<&dyn DefiAdapter> {
+        self.adapters
+            .iter()
+            .find(|a| a.protocol().eq_ignore_ascii_case(protocol.trim()))
+            .map(|a| a.as_ref())
+    }
+
+    /// Registered protocol names, in registration order
+    pub fn protocols(&self) -> Vec<&'static str> {
+        self.adapters.iter().map(|a| a.protocol()).collect()
+    }
+
+    /// Adapters of the protocols enabled in an account's settings
+    pub fn enabled(&self, settings: &AccountSettings) -> Vec<&dyn DefiAdapter> {
+        self.adapters
+            .iter()
+            .filter(|a| settings.uses_defi_protocol(a.protocol()))
+            .map(|a| a.as_ref())
+            .collect()
+    }
+
+    /// Token contracts per chain that the enabled adapters report themselves
+    pub async fn position_tokens(
+        &self,
+        db: &DatabaseConnection,
+        settings: &AccountSettings,
+        chains: &[EvmChain],
+    ) -> HashMap<String, HashSet<String>> {
+        let mut tokens: HashMap<String, HashSet<String>> = HashMap::new();
+        for adapter in self.enabled(settings) {
+            for chain in chains.iter().filter(|c| adapter.supports_chain(c.name())) {
+                let chain_tokens = adapter.position_tokens(db, chain).await;
+                if !chain_tokens.is_empty() {
+                    tokens.entry(chain.name().to_string()).or_default().extend(chain_tokens);
+                }
+            }
+        }
+        tokens
+    }
+}
+
+/// DeFi balances and positions of an account across its addresses and chains
+#[derive(Debug, Default)]
+pub struct DefiSync {
+    pub balances: Vec<Balance>,
+    pub positions: Vec<Position>,
+    /// Whether every adapter read succeeded; a partial read must not replace stored positions
+    pub complete: bool,
+}
+
+/// Aggregators accepted in the `position_aggregator` account setting
+pub const POSITION_AGGREGATORS: &[&str] = &[POSITION_AGGREGATOR_DEBANK];
+
+/// Run the account's enabled adapters for each of `addresses` on every supported chain, then
+/// its position aggregator for the protocols no enabled adapter covers.
+///
+/// Only EVM wallet accounts use adapters. A failed read is logged and its balances are left
+/// out of that sync.
+pub async fn sync_defi_positions(
+    db: &DatabaseConnection,
+    account: &accounts::Model,
+    addresses: &[String],
+) -> DefiSync {
+    let mut sync = DefiSync { complete: true, ..Default::default() };
+    let is_evm_wallet = account.account_type == ACCOUNT_TYPE_WALLET
+        && account
+            .exchange_name
+            .as_deref()
+            .unwrap_or(DEFAULT_WALLET_KIND)
+            .eq_ignore_ascii_case(DEFAULT_WALLET_KIND);
+    let settings = AccountSettings::from_json(account.settings.as_ref());
+    let adapters = DefiRegistry::builtin().enabled(&settings);
+    let debank_key = settings
+        .position_aggregator
+        .as_deref()
+        .filter(|name| name.trim().eq_ignore_ascii_case(POSITION_AGGREGATOR_DEBANK))
+        .and_then(|_| match std::env::var("DEBANK_API_KEY") {
+            Ok(key) => Some(key),
+            Err(_) => {
+                tracing::warn!("DEBANK_API_KEY not set; skipping DeBank positions of account {}", account.id);
+                None
+            }
+        });
+    if !is_evm_wallet || (adapters.is_empty() && debank_key.is_none()) {
+        return sync;
+    }
+
+    let chains = account_chains(&ConnectorContext { db, account }).await;
+    let mut reads = Vec::new();
+    for address in addresses {
+        let Ok(wallet) = address.parse::<Address>() else {
+            tracing::warn!("Skipping DeFi positions of invalid address {} on account {}", address, account.id);
+            continue;
+        };
+        for adapter in &adapters {
+            for chain in chains.iter().filter(|c| adapter.supports_chain(c.name())) {
+                reads.push(async move {
+                    let result = adapter.fetch_positions(&DefiContext { db, chain }, wallet).await;
+                    (adapter.protocol(), chain.name(), result)
+                });
+            }
+        }
+    }
+
+    for (protocol, chain, result) in join_all(reads).await {
+        match result {
+            Ok(found) => {
+                sync.balances.extend(found.balances);
+                sync.positions.extend(found.positions);
+            }
+            Err(e) => {
+                tracing::error!("Failed to fetch {} positions on {} for account {}: {}", protocol, chain, account.id, e);
+                sync.complete = false;
+            }
+        }
+    }
+
+    if let Some(api_key) = debank_key {
+        let client = reqwest::Client::new();
+        let skip: HashSet<&str> = adapters.iter().flat_map(|a| a.aggregator_ids().iter().copied()).collect();
+        for address in addresses {
+            match fetch_debank_positions(&client, &api_key, address, &chains, &skip).await {
+                Ok(balances) => sync.balances.extend(balances),
+                Err(e) => tracing::error!("Failed to fetch DeBank positions of {} for account {}: {}", address, account.id, e),
+            }
+        }
+    }
+    sync
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_builtin_defi_registry() {
+        let registry = DefiRegistry::builtin();
+
+        assert_eq!(registry.protocols(), vec!["aave_v3", "compound_v3", "curve", "gmx_v2", "pendle", "vaults"]);
+        assert_eq!(registry.find(" GMX_V2 ").unwrap().protocol(), "gmx_v2");
+        assert!(registry.find("uniswap").is_none());
+        assert!(registry.find("gmx_v2").unwrap().supports_chain("arbitrum"));
+        assert!(!registry.find("gmx_v2").unwrap().supports_chain("ethereum"));
+        assert!(registry.find("aave_v3").unwrap().supports_chain("base"));
+
+        let settings = AccountSettings {
+            defi_protocols: vec!["pendle".to_string(), "aave_v3".to_string()],
+            ..Default::default()
+        };
+        let enabled: Vec<&str> = registry.enabled(&settings).iter().map(|a| a.protocol()).collect();
+        assert_eq!(enabled, vec!["aave_v3", "pendle"]);
+    }
+}
@@ -0,0 +1,313 @@
+use super::registry::{ConnectorContext, ConnectorFactory, ACCOUNT_TYPE_EXCHANGE};
+use super::{Balance, ExchangeConnector, Position};
+use crate::domain::HOLDING_SOURCE_DERIVATIVES;
+use async_trait::async_trait;
+use reqwest;
+use rust_decimal::Decimal;
+use serde::Deserialize;
+use std::error::Error;
+use tracing;
+
+const DERIBIT_API_BASE_URL: &str = "https://www.deribit.com/api/v2";
+
+/// JSON-RPC response wrapper used by every Deribit endpoint
+#[derive(Debug, Deserialize)]
+struct DeribitResponse<T> {
+    result: Option<T>,
+    error: Option<DeribitError>,
+}
+
+#[derive(Debug, Deserialize)]
+struct DeribitError {
+    code: i64,
+    message: String,
+}
+
+/// Result of `public/auth`
+#[derive(Debug, Deserialize)]
+struct DeribitAuth {
+    access_token: String,
+}
+
+/// Result of `private/get_account_summaries`
+#[derive(Debug, Deserialize)]
+struct DeribitAccountSummaries {
+    summaries: Vec<DeribitAccountSummary>,
+}
+
+/// Per-currency account summary.
+///
+/// `equity` is the cash balance plus the mark value of options and the unrealized
+/// PnL of futures, all in units of `currency`.
+#[derive(Debug, Deserialize)]
+struct DeribitAccountSummary {
+    currency: String,
+    equity: Decimal,
+    available_funds: Decimal,
+}
+
+/// Open position from `private/get_positions`
+#[derive(Debug, Deserialize)]
+struct DeribitPosition {
+    instrument_name: String,
+    /// "future", "option", "future_combo" or "option_combo"
+    kind: String,
+    /// "buy", "sell" or "zero"
+    direction: String,
+    /// Signed size (USD for inverse futures, contracts for options)
+    size: Decimal,
+    average_price: Option<Decimal>,
+    mark_price: Option<Decimal>,
+    floating_profit_loss: Option<Decimal>,
+    initial_margin: Option<Decimal>,
+    leverage: Option<Decimal>,
+    estimated_liquidation_price: Option<Decimal>,
+}
+
+impl DeribitPosition {
+    /// Convert to a [`Position`], skipping closed (zero-size) positions
+    fn into_position(self) -> Option<Position> {
+        if self.size.is_zero() || self.direction == "zero" {
+            return None;
+        }
+
+        let instrument_type = match self.kind.as_str() {
+            "option" | "option_combo" => "OPTION",
+            _ if self.instrument_name.ends_with("-PERPETUAL") => "SWAP",
+            _ => "FUTURES",
+        };
+        // Settlement currency is the instrument's prefix (e.g. "BTC" in "BTC-PERPETUAL")
+        let currency = self
+            .instrument_name
+            .split(['-', '_'])
+            .next()
+            .map(str::to_string);
+        let text = |v: Option<Decimal>| v.map(|d| d.normalize().to_string());
+
+        Some(Position {
+            instrument: self.instrument_name,
+            instrument_type: instrument_type.to_string(),
+            side: if self.direction == "sell" { "short" } else { "long" }.to_string(),
+            size: self.size.abs().normalize().to_string(),
+            entry_price: text(self.average_price),
+            mark_price: text(self.mark_price),
+            unrealized_pnl: text(self.floating_profit_loss),
+            margin: text(self.initial_margin),
+            margin_currency: currency,
+            margin_mode: None, // Deribit accounts use one margin model for all positions
+            leverage: text(self.leverage),
+            liquidation_price: text(self.estimated_liquidation_price),
+        })
+    }
+}
+
+/// Deribit connector for read-only access
+///
+/// Uses an API key's client ID and client secret (no passphrase) to obtain an access
+/// token. Account equity per currency is reported as holdings tagged `derivatives`,
+/// so option mark value and futures PnL are included in portfolio valuation.
+pub struct DeribitConnector {
+    client_id: String,
+    client_secret: String,
+    client: reqwest::Client,
+}
+
+impl DeribitConnector {
+    /// Create a new Deribit connector with API credentials
+    pub fn new(client_id: String, client_secret: String) -> Self {
+        Self {
+            client_id,
+            client_secret,
+            client: reqwest::Client::new(),
+        }
+    }
+
+    /// Make a GET request to a Deribit JSON-RPC method
+    async fn get_request<T: for<'de> Deserialize<'de>>(
+        &self,
+        method: &str,
+        params: &[(&str, &str)],
+        access_token: Option<&str>,
+    ) -> Result<T, Box<dyn Error + Send + Sync>> {
+        let url = format!("{}/{}", DERIBIT_API_BASE_URL, method);
+
+        tracing::debug!("Deribit API Request: GET {}", url);
+
+        let mut request = self.client.get(&url).query(params);
+        if let Some(token) = access_token {
+            request = request.bearer_auth(token);
+        }
+        let response = request.send().await?;
+
+        let status = response.status();
+        let body = response.text().await?;
+
+        tracing::debug!("Deribit API Response Status: {}", status);
+
+        let parsed: DeribitResponse<T> = serde_json::from_str(&body).map_err(|e| {
+            tracing::error!("Failed to parse Deribit response: {}", e);
+            format!("Failed to parse Deribit response: {}", e)
+        })?;
+
+        if let Some(error) = parsed.error {
+            return Err(format!("Deribit API error: {} - {}", error.code, error.message).into());
+        }
+        if !status.is_success() {
+            return Err(format!("Deribit API error: {} - {}", status, body).into());
+        }
+
+        parsed
+            .result
+            .ok_or_else(|| "Deribit API returned no result".into())
+    }
+
+    /// Obtain an access token with the client credentials grant
+    async fn authenticate(&self) -> Result<String, Box<dyn Error + Send + Sync>> {
+        let auth: DeribitAuth = self
+            .get_request(
+                "public/auth",
+                &[
+                    ("grant_type", "client_credentials"),
+                    ("client_id", &self.client_id),
+                    ("client_secret", &self.client_secret),
+                ],
+                None,
+            )
+            .await?;
+        Ok(auth.access_token)
+    }
+}
+
+/// Convert account summaries into one `derivatives` balance per currency.
+///
+/// Funds not available for new positions (margin in use) are reported as frozen.
+/// Currencies without positive equity are dropped; results are sorted by currency.
+fn equity_balances(summaries: Vec<DeribitAccountSummary>) -> Vec<Balance> {
+    let mut balances: Vec<Balance> = summaries
+        .into_iter()
+        .filter(|s| s.equity > Decimal::ZERO)
+        .map(|s| {
+            let available = s.available_funds.max(Decimal::ZERO).min(s.equity);
+            Balance {
+                asset: s.currency.to_uppercase(),
+                quantity: s.equity.normalize().to_string(),
+                available: available.normalize().to_string(),
+                frozen: (s.equity - available).normalize().to_string(),
+                decimals: None, // Deribit doesn't provide decimal information
+                holding_source: Some(HOLDING_SOURCE_DERIVATIVES.to_string()),
+            }
+        })
+        .collect();
+    balances.sort_by(|a, b| a.asset.cmp(&b.asset));
+    balances
+}
+
+#[async_trait]
+impl ExchangeConnector for DeribitConnector {
+    async fn fetch_spot_balances(&self) -> Result<Vec<Balance>, Box<dyn Error + Send + Sync>> {
+        let token = self.authenticate().await?;
+        let result: DeribitAccountSummaries = self
+            .get_request("private/get_account_summaries", &[], Some(&token))
+            .await?;
+
+        let balances = equity_balances(result.summaries);
+
+        tracing::info!("Fetched {} balances from Deribit", balances.len());
+        Ok(balances)
+    }
+
+    async fn fetch_positions(&self) -> Result<Vec<Position>, Box<dyn Error + Send + Sync>> {
+        let token = self.authenticate().await?;
+        let rows: Vec<DeribitPosition> = self
+            .get_request("private/get_positions", &[("currency", "any")], Some(&token))
+            .await?;
+
+        let positions: Vec<Position> = rows
+            .into_iter()
+            .filter_map(DeribitPosition::into_position)
+            .collect();
+
+        tracing::info!("Fetched {} open positions from Deribit", positions.len());
+        Ok(positions)
+    }
+}
+
+/// Builds [`DeribitConnector`]s for Deribit accounts
+pub struct DeribitFactory;
+
+#[async_trait]
+impl ConnectorFactory for DeribitFactory {
+    fn account_type(&self) -> &'static str {
+        ACCOUNT_TYPE_EXCHANGE
+    }
+
+    fn name(&self) -> &'static str {
+        "deribit"
+    }
+
+    async fn create(
+        &self,
+        ctx: &ConnectorContext<'_>,
+    ) -> Result<Box<dyn ExchangeConnector>, Box<dyn Error + Send + Sync>> {
+        let (client_id, client_secret) = ctx.api_credentials()?;
+        Ok(Box::new(DeribitConnector::new(client_id, client_secret)))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    #[test]
+    fn test_equity_balances_include_option_value() {
+        let result: DeribitAccountSummaries = serde_json::from_value(json!({
+            "summaries": [
+                { "currency": "ETH", "equity": 10.5, "balance": 10.0, "options_value": 0.5, "available_funds": 8.5 },
+                { "currency": "BTC", "equity": 1.25, "balance": 1.5, "options_value": -0.25, "available_funds": 1.4 },
+                { "currency": "USDC", "equity": 0, "balance": 0, "available_funds": 0 }
+            ]
+        }))
+        .unwrap();
+
+        let balances = equity_balances(result.summaries);
+
+        assert_eq!(balances.len(), 2);
+        assert_eq!(balances[0].asset, "BTC");
+        assert_eq!(balances[0].available, "1.25");
+        assert_eq!(balances[0].frozen, "0");
+        assert_eq!(balances[1].quantity, "10.5");
+        assert_eq!(balances[1].frozen, "2");
+        assert_eq!(balances[1].holding_source.as_deref(), Some(HOLDING_SOURCE_DERIVATIVES));
+    }
+
+    #[test]
+    fn test_position_parsing() {
+        let rows: Vec<DeribitPosition> = serde_json::from_value(json!([
+            {
+                "instrument_name": "BTC-PERPETUAL", "kind": "future", "direction": "sell", "size": -5000,
+                "average_price": 65000, "mark_price": 64000.5, "floating_profit_loss": 0.0012,
+                "initial_margin": 0.002, "leverage": 25, "estimated_liquidation_price": 90000
+            },
+            {
+                "instrument_name": "ETH-27JUN25-4000-C", "kind": "option", "direction": "buy", "size": 2,
+                "average_price": 0.05, "mark_price": 0.04, "floating_profit_loss": -0.02
+            },
+            { "instrument_name": "ETH-27JUN25", "kind": "future", "direction": "zero", "size": 0 }
+        ]))
+        .unwrap();
+
+        let positions: Vec<Position> = rows.into_iter().filter_map(DeribitPosition::into_position).collect();
+        assert_eq!(positions.len(), 2);
+
+        assert_eq!(positions[0].instrument_type, "SWAP");
+        assert_eq!(positions[0].side, "short");
+        assert_eq!(positions[0].size, "5000");
+        assert_eq!(positions[0].margin_currency.as_deref(), Some("BTC"));
+
+        assert_eq!(positions[1].instrument_type, "OPTION");
+        assert_eq!(positions[1].side, "long");
+        assert_eq!(positions[1].mark_price.as_deref(), Some("0.04"));
+        assert!(positions[1].liquidation_price.is_none());
+    }
+}
@@ -0,0 +1,131 @@
+//! Token prices from DEX pairs via the DexScreener public API.
+//!
+//! Used for assets pinned to the "dex" price source (see
+//! [`crate::helpers::price_overrides`]): the token is looked up by chain and contract address
+//! and priced at the USD price of its most liquid pair where it is the base token.
+
+use reqwest::Client;
+use rust_decimal::Decimal;
+use serde::Deserialize;
+use std::error::Error;
+use std::str::FromStr;
+
+const DEXSCREENER_API_URL: &str = "https://api.dexscreener.com";
+
+/// DexScreener chain ID of a chain name used in this codebase
+pub fn dexscreener_chain_id(chain: &str) -> Option<&'static str> {
+    match chain {
+        "ethereum" => Some("ethereum"),
+        "bsc" => Some("bsc"),
+        "polygon" => Some("polygon"),
+        "arbitrum" => Some("arbitrum"),
+        "optimism" => Some("optimism"),
+        "base" => Some("base"),
+        "avalanche" => Some("avalanche"),
+        "zksync" => Some("zksync"),
+        "linea" => Some("linea"),
+        "scroll" => Some("scroll"),
+        "hyper_liquid" => Some("hyperevm"),
+        "solana" => Some("solana"),
+        _ => None,
+    }
+}
+
+/// Price of a token on its most liquid DEX pair
+#[derive(Debug, Clone, PartialEq)]
+pub struct DexTokenPrice {
+    pub price_usd: Decimal,
+    /// Pool liquidity in USD, when reported
+    pub liquidity_usd: Option<f64>,
+    /// Pair (pool) contract the price was read from
+    pub pair_address: String,
+    /// DEX of the pair (e.g. "uniswap")
+    pub dex_id: String,
+}
+
+#[derive(Debug, Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct DexPair {
+    dex_id: String,
+    pair_address: String,
+    base_token: DexToken,
+    #[serde(default)]
+    price_usd: Option<String>,
+    #[serde(default)]
+    liquidity: Option<DexLiquidity>,
+}
+
+#[derive(Debug, Deserialize)]
+struct DexToken {
+    address: String,
+}
+
+#[derive(Debug, Deserialize)]
+struct DexLiquidity {
+    #[serde(default)]
+    usd: Option<f64>,
+}
+
+/// Pick the most liquid pair quoting `token_address` as its base token from a
+/// `/tokens/v1/{chain}/{address}` response
+pub fn parse_token_pairs(body: &str, token_address: &str) -> Result<Option<DexTokenPrice>, Box<dyn Error + Send + Sync>> {
+    let pairs: Vec<DexPair> = serde_json::from_str(body)?;
+    let best = pairs
+        .into_iter()
+        .filter(|pair| pair.base_token.address.eq_ignore_ascii_case(token_address))
+        .filter_map(|pair| {
+            let price_usd = Decimal::from_str(pair.price_usd.as_deref()?).ok()?;
+            let liquidity_usd = pair.liquidity.as_ref().and_then(|l| l.usd);
+            Some(DexTokenPrice { price_usd, liquidity_usd, pair_address: pair.pair_address, dex_id: pair.dex_id })
+        })
+        .filter(|price| price.price_usd > Decimal::ZERO)
+        .max_by(|a, b| {
+            a.liquidity_usd
+                .unwrap_or(0.0)
+                .partial_cmp(&b.liquidity_usd.unwrap_or(0.0))
+                .unwrap_or(std::cmp::Ordering::Equal)
+        });
+    Ok(best)
+}
+
+/// Fetch the DEX price of a token; `None` when DexScreener lists no priced pair for it
+pub async fn fetch_dex_token_price(
+    client: &Client,
+    chain: &str,
+    token_address: &str,
+) -> Result<Option<DexTokenPrice>, Box<dyn Error + Send + Sync>> {
+    let chain_id = dexscreener_chain_id(chain).ok_or_else(|| format!("DexScreener does not support chain {}", chain))?;
+    let response = client
+        .get(format!("{}/tokens/v1/{}/{}", DEXSCREENER_API_URL, chain_id, token_address))
+        .send()
+        .await?;
+    if !response.status().is_success() {
+        return Err(format!("DexScreener request failed: {}", response.status()).into());
+    }
+    parse_token_pairs(&response.text().await?, token_address)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_token_pairs() {
+        let token = "0xabc0000000000000000000000000000000000001";
+        let body = r#"[
+            {"dexId":"uniswap","pairAddress":"0xpool1","baseToken":{"address":"0xABC0000000000000000000000000000000000001"},
+             "priceUsd":"0.012","liquidity":{"usd":15000.5}},
+            {"dexId":"sushiswap","pairAddress":"0xpool2","baseToken":{"address":"0xabc0000000000000000000000000000000000001"},
+             "priceUsd":"0.013","liquidity":{"usd":900}},
+            {"dexId":"uniswap","pairAddress":"0xpool3","baseToken":{"address":"0xdef"},
+             "priceUsd":"2000","liquidity":{"usd":5000000}}
+        ]"#;
+        let price = parse_token_pairs(body, token).unwrap().unwrap();
+        assert_eq!(price.pair_address, "0xpool1");
+        assert_eq!(price.price_usd, Decimal::from_str("0.012").unwrap());
+
+        assert!(parse_token_pairs("[]", token).unwrap().is_none());
+        assert_eq!(dexscreener_chain_id("hyper_liquid"), Some("hyperevm"));
+        assert!(dexscreener_chain_id("bitcoin").is_none());
+    }
+}
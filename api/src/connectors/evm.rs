@@ -1,5 +1,12 @@
-use super::{Balance, ExchangeConnector};
+use super::registry::{ConnectorContext, ConnectorFactory, ACCOUNT_TYPE_WALLET};
+use super::defi::DefiRegistry;
+use super::evm_discovery::TokenDiscovery;
+use super::explorer::{fetch_explorer_balances, ExplorerApi};
+use super::nft::{fetch_wallet_nfts, NftApi};
+use super::{Balance, ChainFetch, ChainProgress, ExchangeConnector, GasFee, NftHolding};
 use crate::concurrency::RateLimiter;
+use crate::domain::AccountSettings;
+use crate::entities::{evm_chains, evm_tokens, spam_tokens};
 use crate::helpers::balance_normalization::normalize_token_balance;
 use alloy::{
     primitives::{Address, U256},
@@ -7,9 +14,12 @@ use alloy::{
     sol,
 };
 use async_trait::async_trait;
+use chrono::{DateTime, Utc};
 use futures::future::join_all;
-use std::collections::HashMap;
+use sea_orm::{ColumnTrait, DatabaseConnection, EntityTrait, QueryFilter};
+use std::collections::{HashMap, HashSet};
 use std::error::Error;
+use std::time::Instant;
 use tracing;
 
 // Generate ERC20 contract bindings
@@ -32,6 +42,10 @@ pub struct EvmChain {
     chain_id: String,
     default_rpc_url: String,
     native_symbol: String,
+    /// NFT API used to discover the wallet's NFTs on this chain; `None` skips NFTs
+    nft_api: Option<NftApi>,
+    /// Explorer API that replaces the per-token RPC calls for balances; `None` uses RPC
+    explorer: Option<ExplorerApi>,
 }
 
 impl EvmChain {
@@ -45,9 +59,23 @@ impl EvmChain {
             chain_id: chain_id.into(),
             default_rpc_url: rpc_url.into(),
             native_symbol: native_symbol.into(),
+            nft_api: None,
+            explorer: None,
         }
     }
 
+    /// Discover NFTs on this chain through `nft_api`
+    pub fn with_nft_api(mut self, nft_api: Option<NftApi>) -> Self {
+        self.nft_api = nft_api;
+        self
+    }
+
+    /// Read balances through `explorer` instead of RPC `balanceOf` calls
+    pub fn with_explorer(mut self, explorer: Option<ExplorerApi>) -> Self {
+        self.explorer = explorer;
+        self
+    }
+
     /// Chain identifier, matching the `chain_id` column in the `evm_chains` table
     /// (e.g. `"ethereum"`, `"hyper_liquid"`).
     pub fn name(&self) -> &str {
@@ -65,6 +93,16 @@ impl EvmChain {
         &self.native_symbol
     }
 
+    /// NFT API configured for this chain, if any
+    pub fn nft_api(&self) -> Option<&NftApi> {
+        self.nft_api.as_ref()
+    }
+
+    /// Explorer balance provider configured for this chain, if any
+    pub fn explorer(&self) -> Option<&ExplorerApi> {
+        self.explorer.as_ref()
+    }
+
     /// Hardcoded fallback chain list used when the database is unreachable.
     ///
     /// Callers should prefer loading chains from the `evm_chains` DB table so that
@@ -235,6 +273,16 @@ pub struct EvmConnector {
     /// Key: chain name (e.g. "ethereum"), Value: RPC URL string.
     /// Falls back to `EvmChain::rpc_url()` for chains not present in this map.
     rpc_urls: HashMap<String, String>,
+    /// Etherscan API key used to look up the wallet's first transaction.
+    /// The lookup is skipped when unset.
+    explorer_api_key: Option<String>,
+    /// Discovers the wallet's tokens from its transfers; their balances are checked in
+    /// addition to the configured token list. `None` checks the configured list only.
+    token_discovery: Option<TokenDiscovery>,
+    /// Blocklisted token contracts per chain (lowercase), left out of explorer balance lists
+    spam_tokens: HashMap<String, HashSet<String>>,
+    /// Token contracts per chain (lowercase) whose value a DeFi adapter reports instead
+    position_tokens: HashMap<String, HashSet<String>>,
 }
 
 impl EvmConnector {
@@ -280,13 +328,178 @@ impl EvmConnector {
             chains,
             custom_tokens,
             rpc_urls: custom_rpc_urls.unwrap_or_default(),
+            explorer_api_key: None,
+            token_discovery: None,
+            spam_tokens: HashMap::new(),
+            position_tokens: HashMap::new(),
         })
     }
+
+    /// Enable token discovery (the account's `discover_tokens` setting)
+    pub fn with_token_discovery(mut self, token_discovery: Option<TokenDiscovery>) -> Self {
+        self.token_discovery = token_discovery;
+        self
+    }
+
+    /// Set the blocklisted token contracts per chain, applied to explorer balance lists
+    pub fn with_spam_tokens(mut self, spam_tokens: HashMap<String, HashSet<String>>) -> Self {
+        self.spam_tokens = spam_tokens;
+        self
+    }
+
+    /// Set the token contracts per chain that DeFi adapters report themselves (lowercase)
+    pub fn with_position_tokens(mut self, position_tokens: HashMap<String, HashSet<String>>) -> Self {
+        self.position_tokens = position_tokens;
+        self
+    }
+
+    /// RPC URL of a chain: DB-sourced value first, then the hardcoded default
+    fn chain_rpc_url(&self, chain: &EvmChain) -> String {
+        self.rpc_urls
+            .get(chain.name())
+            .cloned()
+            .unwrap_or_else(|| chain.rpc_url().to_string())
+    }
+
+    /// Set the Etherscan API key used by `fetch_first_activity` and `fetch_gas_fees`
+    pub fn with_explorer_api_key(mut self, api_key: Option<String>) -> Self {
+        self.explorer_api_key = api_key.filter(|k| !k.is_empty());
+        self
+    }
+
+    /// Timestamp of the wallet's earliest transaction of one kind (`txlist` for normal
+    /// transactions, `tokentx` for ERC-20 transfers) on a chain, via the Etherscan v2 API
+    async fn fetch_first_transaction(
+        &self,
+        client: &reqwest::Client,
+        api_key: &str,
+        chain_id: u64,
+        action: &str,
+    ) -> Result<Option<DateTime<Utc>>, Box<dyn Error + Send + Sync>> {
+        let url = format!(
+            "{}?chainid={}&module=account&action={}&address={:?}&page=1&offset=1&sort=asc&apikey={}",
+            ETHERSCAN_V2_API_URL, chain_id, action, self.wallet_address, api_key
+        );
+        let body: serde_json::Value = client.get(&url).send().await?.error_for_status()?.json().await?;
+        Ok(parse_first_transaction(&body)?)
+    }
+}
+
+/// Normal transactions fetched per chain when looking up gas fees, newest first
+const GAS_FEE_PAGE_SIZE: u32 = 1000;
+
+/// Etherscan v2 endpoint; one API key covers every chain selected by `chainid`
+pub(super) const ETHERSCAN_V2_API_URL: &str = "https://api.etherscan.io/v2/api";
+
+/// Numeric chain ID used by Etherscan v2 for a chain name from the `evm_chains` table
+pub(super) fn etherscan_chain_id(chain_name: &str) -> Option<u64> {
+    match chain_name {
+        "ethereum" => Some(1),
+        "optimism" => Some(10),
+        "bsc" => Some(56),
+        "polygon" => Some(137),
+        "zksync" => Some(324),
+        "hyper_liquid" => Some(999),
+        "base" => Some(8453),
+        "arbitrum" => Some(42161),
+        "avalanche" => Some(43114),
+        "linea" => Some(59144),
+        "scroll" => Some(534352),
+        _ => None,
+    }
+}
+
+/// Parse the timestamp of the first transaction in an Etherscan account response.
+///
+/// Etherscan reports "no transactions" as status `"0"` with an empty result array, and errors
+/// (e.g. an invalid API key) as status `"0"` with a message string in `result`.
+fn parse_first_transaction(body: &serde_json::Value) -> Result<Option<DateTime<Utc>>, String> {
+    let rows = match body.get("result") {
+        Some(serde_json::Value::Array(rows)) => rows,
+        other => {
+            return Err(format!(
+                "Etherscan error: {}",
+                other.and_then(|v| v.as_str()).unwrap_or("unexpected response")
+            ))
+        }
+    };
+
+    let Some(first) = rows.first() else {
+        return Ok(None);
+    };
+    let seconds = first
+        .get("timeStamp")
+        .and_then(|v| v.as_str())
+        .and_then(|v| v.parse::<i64>().ok())
+        .ok_or("Etherscan transaction without a valid timeStamp")?;
+
+    Ok(DateTime::from_timestamp(seconds, 0))
+}
+
+/// Gas fees of the transactions `sender` sent in an Etherscan `txlist` response, at or
+/// after `since`. The fee is `gasUsed × gasPrice` in wei, paid in the chain's native asset;
+/// failed transactions pay gas too.
+fn parse_gas_fees(
+    body: &serde_json::Value,
+    sender: &str,
+    chain: &str,
+    native_symbol: &str,
+    since: Option<DateTime<Utc>>,
+) -> Result<Vec<GasFee>, String> {
+    let rows = match body.get("result") {
+        Some(serde_json::Value::Array(rows)) => rows,
+        other => {
+            return Err(format!(
+                "Etherscan error: {}",
+                other.and_then(|v| v.as_str()).unwrap_or("unexpected response")
+            ))
+        }
+    };
+
+    let field = |row: &serde_json::Value, key: &str| row.get(key).and_then(|v| v.as_str()).map(str::to_string);
+    let mut fees = Vec::new();
+    for row in rows {
+        if !field(row, "from").is_some_and(|from| from.eq_ignore_ascii_case(sender)) {
+            continue;
+        }
+        let (Some(hash), Some(gas_used), Some(gas_price), Some(paid_at)) = (
+            field(row, "hash"),
+            field(row, "gasUsed").and_then(|v| v.parse::<u128>().ok()),
+            field(row, "gasPrice").and_then(|v| v.parse::<u128>().ok()),
+            field(row, "timeStamp").and_then(|v| v.parse::<i64>().ok()).and_then(|s| DateTime::from_timestamp(s, 0)),
+        ) else {
+            continue;
+        };
+        if since.is_some_and(|since| paid_at < since) {
+            continue;
+        }
+        let wei = U256::from(gas_used) * U256::from(gas_price);
+        if wei.is_zero() {
+            continue;
+        }
+        fees.push(GasFee {
+            chain: chain.to_string(),
+            tx_hash: hash,
+            asset: format!("{}-{}", native_symbol, chain),
+            amount: normalize_token_balance(&wei.to_string(), 18).map_err(|e| e.to_string())?,
+            paid_at,
+        });
+    }
+    Ok(fees)
 }
 
 #[async_trait]
 impl ExchangeConnector for EvmConnector {
     async fn fetch_spot_balances(&self) -> Result<Vec<Balance>, Box<dyn Error + Send + Sync>> {
+        self.fetch_spot_balances_by_chain(&|_| {}).await
+    }
+
+    /// Chains are read in parallel; a chain whose native or token balances cannot be read is
+    /// reported with the error and contributes what it could read.
+    async fn fetch_spot_balances_by_chain(
+        &self,
+        on_chain: &(dyn Fn(ChainProgress) + Send + Sync),
+    ) -> Result<Vec<Balance>, Box<dyn Error + Send + Sync>> {
         tracing::info!("Fetching balances for wallet {} across {} chains (parallel)", 
             self.wallet_address, self.chains.len());
         
@@ -315,36 +528,98 @@ impl ExchangeConnector for EvmConnector {
                     .collect()
             };
             // Resolve RPC URL: DB-sourced value takes priority over the hardcoded default
-            let rpc_url = self.rpc_urls
-                .get(chain.name())
-                .cloned()
-                .unwrap_or_else(|| chain.rpc_url().to_string());
+            let rpc_url = self.chain_rpc_url(&chain);
+            let token_discovery = self.token_discovery.clone();
+            let wallet = self.wallet_address;
+            let mut blocked = self.spam_tokens.get(chain.name()).cloned().unwrap_or_default();
+            // LP and PT/YT tokens are reported by their DeFi adapters (see `connectors::defi`),
+            // so they are left out of the plain token balances
+            let protocol_tokens = self.position_tokens.get(chain.name()).cloned().unwrap_or_default();
+            blocked.extend(protocol_tokens.iter().cloned());
+            let chain_tokens: Vec<(String, String)> = chain_tokens
+                .into_iter()
+                .filter(|(_, a)| !protocol_tokens.contains(&a.to_lowercase()))
+                .collect();
 
             async move {
-                // Acquire rate limit permit
-                let _permit = rate_limiter.acquire().await.ok()?;
-
-                tracing::info!("Checking {} chain ({} tokens)", chain.name(), chain_tokens.len());
-                let mut chain_balances = Vec::new();
-
-                // Fetch native balance
-                match fetch_native_balance_for_chain(&wallet_address, &chain, &rpc_url).await {
-                    Ok(Some(balance)) => chain_balances.push(balance),
-                    Ok(None) => {},
-                    Err(e) => {
-                        tracing::error!("Failed to fetch native balance on {}: {}", chain.name(), e);
+                let started = Instant::now();
+                let mut errors: Vec<String> = Vec::new();
+                let balances = async {
+                    // Acquire rate limit permit
+                    let Ok(_permit) = rate_limiter.acquire().await else {
+                        errors.push("RPC rate limiter closed".to_string());
+                        return Vec::new();
+                    };
+                    on_chain(ChainProgress::Started { chain: chain.name().to_string() });
+
+                    // The explorer returns the full balance list in one call; fall back to RPC on error
+                    if let Some(explorer) = chain.explorer() {
+                        let client = reqwest::Client::new();
+                        match fetch_explorer_balances(&client, explorer, &chain, &wallet_address, &blocked).await {
+                            Ok(balances) => {
+                                tracing::info!("Fetched {} balances on {} from {}", balances.len(), chain.name(), explorer.provider);
+                                return balances;
+                            }
+                            Err(e) => tracing::warn!(
+                                "{} balances on {} failed, falling back to RPC: {}",
+                                explorer.provider,
+                                chain.name(),
+                                e
+                            ),
+                        }
                     }
-                }
 
-                // Fetch token balances using the resolved token list
-                match fetch_token_balances_for_chain(&wallet_address, &chain, &chain_tokens, &rpc_url).await {
-                    Ok(balances) => chain_balances.extend(balances),
-                    Err(e) => {
-                        tracing::error!("Failed to fetch token balances on {}: {}", chain.name(), e);
+                    // Add the wallet's discovered tokens; the configured list is still checked so a
+                    // failed or partial scan never hides a known token
+                    let mut chain_tokens = chain_tokens;
+                    if let Some(discovery) = &token_discovery {
+                        match discovery.discover(&chain, &rpc_url, wallet).await {
+                            Ok(discovered) => {
+                                for (symbol, contract) in discovered {
+                                    if !protocol_tokens.contains(&contract.to_lowercase())
+                                        && !chain_tokens.iter().any(|(_, a)| a.eq_ignore_ascii_case(&contract))
+                                    {
+                                        chain_tokens.push((symbol, contract));
+                                    }
+                                }
+                            }
+                            Err(e) => tracing::warn!("Token discovery on {} failed: {}", chain.name(), e),
+                        }
                     }
+
+                    tracing::info!("Checking {} chain ({} tokens)", chain.name(), chain_tokens.len());
+                    let mut chain_balances = Vec::new();
+
+                    // Fetch native balance
+                    match fetch_native_balance_for_chain(&wallet_address, &chain, &rpc_url).await {
+                        Ok(Some(balance)) => chain_balances.push(balance),
+                        Ok(None) => {},
+                        Err(e) => {
+                            tracing::error!("Failed to fetch native balance on {}: {}", chain.name(), e);
+                            errors.push(format!("native balance: {}", e));
+                        }
+                    }
+
+                    // Fetch token balances using the resolved token list
+                    match fetch_token_balances_for_chain(&wallet_address, &chain, &chain_tokens, &rpc_url).await {
+                        Ok(balances) => chain_balances.extend(balances),
+                        Err(e) => {
+                            tracing::error!("Failed to fetch token balances on {}: {}", chain.name(), e);
+                            errors.push(format!("token balances: {}", e));
+                        }
+                    }
+
+                    chain_balances
                 }
+                .await;
 
-                Some(chain_balances)
+                on_chain(ChainProgress::Finished(ChainFetch {
+                    chain: chain.name().to_string(),
+                    balances: balances.len(),
+                    error: (!errors.is_empty()).then(|| errors.join("; ")),
+                    duration_ms: started.elapsed().as_millis() as u64,
+                }));
+                balances
             }
         }).collect();
         
@@ -352,16 +627,87 @@ impl ExchangeConnector for EvmConnector {
         let results = join_all(fetch_tasks).await;
         
         // Flatten results
-        let mut all_balances = Vec::new();
-        for result in results {
-            if let Some(balances) = result {
-                all_balances.extend(balances);
-            }
-        }
+        let all_balances: Vec<Balance> = results.into_iter().flatten().collect();
         
         tracing::info!("Fetched {} total balances for wallet", all_balances.len());
         Ok(all_balances)
     }
+
+    /// Earliest normal transaction or token transfer across the enabled chains.
+    ///
+    /// Any failed lookup fails the whole call, so a partial answer is never mistaken for the
+    /// wallet's first activity. Chains unknown to Etherscan are skipped.
+    async fn fetch_first_activity(&self) -> Result<Option<DateTime<Utc>>, Box<dyn Error + Send + Sync>> {
+        let Some(api_key) = self.explorer_api_key.as_deref() else {
+            tracing::debug!("ETHERSCAN_API_KEY not set; skipping first activity lookup");
+            return Ok(None);
+        };
+
+        let client = reqwest::Client::new();
+        let mut earliest: Option<DateTime<Utc>> = None;
+        for chain in &self.chains {
+            let Some(chain_id) = etherscan_chain_id(chain.name()) else {
+                continue;
+            };
+            for action in ["txlist", "tokentx"] {
+                let first = self.fetch_first_transaction(&client, api_key, chain_id, action).await?;
+                earliest = earliest.into_iter().chain(first).min();
+            }
+        }
+
+        Ok(earliest)
+    }
+
+    /// Gas paid by the wallet's latest transactions on each enabled chain, via the Etherscan
+    /// v2 API. Any failed lookup fails the whole call so the next sync retries from the same
+    /// point. Chains unknown to Etherscan are skipped.
+    async fn fetch_gas_fees(
+        &self,
+        since: Option<DateTime<Utc>>,
+    ) -> Result<Vec<GasFee>, Box<dyn Error + Send + Sync>> {
+        let Some(api_key) = self.explorer_api_key.as_deref() else {
+            tracing::debug!("ETHERSCAN_API_KEY not set; skipping gas fee lookup");
+            return Ok(Vec::new());
+        };
+
+        let client = reqwest::Client::new();
+        let sender = format!("{:?}", self.wallet_address);
+        let mut fees = Vec::new();
+        for chain in &self.chains {
+            let Some(chain_id) = etherscan_chain_id(chain.name()) else {
+                continue;
+            };
+            let url = format!(
+                "{}?chainid={}&module=account&action=txlist&address={}&page=1&offset={}&sort=desc&apikey={}",
+                ETHERSCAN_V2_API_URL, chain_id, sender, GAS_FEE_PAGE_SIZE, api_key
+            );
+            let body: serde_json::Value = client.get(&url).send().await?.error_for_status()?.json().await?;
+            fees.extend(parse_gas_fees(&body, &sender, chain.name(), chain.native_symbol(), since)?);
+        }
+
+        Ok(fees)
+    }
+
+    /// NFTs across the enabled chains that have an NFT API configured.
+    ///
+    /// Any failed chain fails the whole call, so the stored NFTs are kept rather than
+    /// replaced by a partial list.
+    async fn fetch_nfts(&self) -> Result<Vec<NftHolding>, Box<dyn Error + Send + Sync>> {
+        let client = reqwest::Client::new();
+        let owner = format!("{:?}", self.wallet_address);
+        let mut holdings = Vec::new();
+        for chain in &self.chains {
+            let Some(api) = chain.nft_api() else {
+                continue;
+            };
+            let chain_holdings = fetch_wallet_nfts(&client, api, chain.name(), chain.native_symbol(), &owner)
+                .await
+                .map_err(|e| format!("NFT discovery on {} failed: {}", chain.name(), e))?;
+            tracing::info!("Found {} NFTs on {} via {}", chain_holdings.len(), chain.name(), api.provider);
+            holdings.extend(chain_holdings);
+        }
+        Ok(holdings)
+    }
 }
 
 // Helper function to fetch native balance for a chain
@@ -391,6 +737,7 @@ async fn fetch_native_balance_for_chain(
         frozen: "0".to_string(),
         // Native tokens typically have 18 decimals
         decimals: Some(18),
+        holding_source: None,
     }))
 }
 
@@ -448,6 +795,7 @@ async fn fetch_token_balances_for_chain(
                         available: normalized,
                         frozen: "0".to_string(),
                         decimals,
+                        holding_source: None,
                     });
                     
                     tracing::debug!(
@@ -474,6 +822,194 @@ async fn fetch_token_balances_for_chain(
     Ok(balances)
 }
 
+/// Builds [`EvmConnector`]s for EVM wallet accounts (the default wallet kind).
+///
+/// Chains and tokens come from the `evm_chains`/`evm_tokens` tables, narrowed to the
+/// account's `enabled_chains` when set.
+pub struct EvmFactory;
+
+#[async_trait]
+impl ConnectorFactory for EvmFactory {
+    fn account_type(&self) -> &'static str {
+        ACCOUNT_TYPE_WALLET
+    }
+
+    fn name(&self) -> &'static str {
+        "evm"
+    }
+
+    fn requires_wallet_address(&self) -> bool {
+        true
+    }
+
+    async fn create(
+        &self,
+        ctx: &ConnectorContext<'_>,
+    ) -> Result<Box<dyn ExchangeConnector>, Box<dyn Error + Send + Sync>> {
+        let wallet_address = ctx.wallet_address()?;
+        let chains = account_chains(ctx).await;
+
+        // Load token list from DB; fall back to built-in list on error
+        let db_tokens = load_tokens_from_db(ctx.db).await;
+
+        // RPC URLs are already embedded in each EvmChain struct (loaded from DB above).
+        // No separate rpc_url override map is needed.
+        let explorer_api_key = std::env::var("ETHERSCAN_API_KEY").ok();
+        let settings = AccountSettings::from_json(ctx.account.settings.as_ref());
+        let token_discovery = settings
+            .discover_tokens
+            .then(|| TokenDiscovery::new(ctx.db.clone(), explorer_api_key.clone()));
+        let spam_tokens = if chains.iter().any(|c| c.explorer().is_some()) {
+            load_spam_tokens_from_db(ctx.db).await
+        } else {
+            HashMap::new()
+        };
+        let position_tokens = DefiRegistry::builtin().position_tokens(ctx.db, &settings, &chains).await;
+        let connector = EvmConnector::new_with_tokens(wallet_address, chains, db_tokens, None)
+            .map_err(|e| format!("Failed to create EVM connector: {}", e))?
+            .with_explorer_api_key(explorer_api_key)
+            .with_token_discovery(token_discovery)
+            .with_spam_tokens(spam_tokens)
+            .with_position_tokens(position_tokens);
+        Ok(Box::new(connector))
+    }
+}
+
+/// Active EVM chains from the `evm_chains` table, limited to the account's `enabled_chains`
+/// (all chains when unset or when none of them match)
+pub(super) async fn account_chains(ctx: &ConnectorContext<'_>) -> Vec<EvmChain> {
+    // Load all active EVM chains from DB (carries chain_id, rpc_url, native_symbol)
+    let all_chains = load_evm_chains_from_db(ctx.db).await;
+
+    // Filter to the account's enabled_chains subset, or use all if unset
+    if let Some(enabled_chains_json) = &ctx.account.enabled_chains {
+        match serde_json::from_value::<Vec<String>>(enabled_chains_json.clone()) {
+            Ok(chain_names) => {
+                let filtered: Vec<EvmChain> = all_chains
+                    .iter()
+                    .filter(|c| chain_names.contains(&c.name().to_string()))
+                    .cloned()
+                    .collect();
+                if filtered.is_empty() {
+                    tracing::warn!(
+                        "None of enabled_chains {:?} matched active DB chains; using all",
+                        chain_names
+                    );
+                    all_chains
+                } else {
+                    filtered
+                }
+            }
+            Err(e) => {
+                tracing::warn!("Failed to parse enabled_chains: {}, using all chains", e);
+                all_chains
+            }
+        }
+    } else {
+        all_chains
+    }
+}
+
+/// Load active EVM tokens from the database grouped by chain name.
+///
+/// Returns `Some(map)` when the table is reachable and contains rows.
+/// Falls back to `None` on any DB error so the EVM connector uses its built-in token list.
+async fn load_tokens_from_db(
+    db: &DatabaseConnection,
+) -> Option<HashMap<String, Vec<(String, String)>>> {
+    match evm_tokens::Entity::find()
+        .filter(evm_tokens::Column::IsActive.eq(true))
+        .all(db)
+        .await
+    {
+        Ok(rows) if !rows.is_empty() => {
+            let mut map: HashMap<String, Vec<(String, String)>> = HashMap::new();
+            for row in rows {
+                map.entry(row.chain)
+                    .or_default()
+                    .push((row.symbol, row.contract_address));
+            }
+            tracing::info!(
+                "Loaded {} active EVM tokens from DB across {} chains",
+                map.values().map(|v| v.len()).sum::<usize>(),
+                map.len()
+            );
+            Some(map)
+        }
+        Ok(_) => {
+            // Table exists but is empty – fall back to built-in list
+            tracing::warn!("evm_tokens table is empty, falling back to built-in token list");
+            None
+        }
+        Err(e) => {
+            tracing::warn!(
+                "Failed to load EVM tokens from DB: {}, falling back to built-in token list",
+                e
+            );
+            None
+        }
+    }
+}
+
+/// Load blocklisted token contracts from `spam_tokens`, grouped by chain with lowercase addresses.
+///
+/// Falls back to an empty blocklist on a DB error.
+async fn load_spam_tokens_from_db(db: &DatabaseConnection) -> HashMap<String, HashSet<String>> {
+    match spam_tokens::Entity::find().all(db).await {
+        Ok(rows) => {
+            let mut map: HashMap<String, HashSet<String>> = HashMap::new();
+            for row in rows {
+                map.entry(row.chain).or_default().insert(row.token_address.to_lowercase());
+            }
+            map
+        }
+        Err(e) => {
+            tracing::warn!("Failed to load spam tokens from DB: {}", e);
+            HashMap::new()
+        }
+    }
+}
+
+/// Load all active EVM chains from the database as [`EvmChain`] structs.
+///
+/// Each returned struct carries the chain's `chain_id`, `rpc_url`, `native_symbol`, NFT API and
+/// explorer balance provider directly from the `evm_chains` table, so no separate RPC URL map is needed.
+///
+/// Falls back to a small hardcoded set when the database is unreachable, ensuring
+/// the sync job can still operate in degraded-DB conditions.
+async fn load_evm_chains_from_db(db: &DatabaseConnection) -> Vec<EvmChain> {
+    match evm_chains::Entity::find()
+        .filter(evm_chains::Column::IsActive.eq(true))
+        .all(db)
+        .await
+    {
+        Ok(rows) if !rows.is_empty() => {
+            tracing::info!("Loaded {} active EVM chains from DB", rows.len());
+            rows.into_iter()
+                .map(|r| {
+                    let nft_api = NftApi::from_columns(r.nft_provider.as_deref(), r.nft_api_url.as_deref());
+                    let explorer = ExplorerApi::from_columns(
+                        r.balance_provider.as_deref(),
+                        r.explorer_api_url.as_deref(),
+                        r.explorer_api_key.as_deref(),
+                    );
+                    EvmChain::new(r.chain_id, r.rpc_url, r.native_symbol)
+                        .with_nft_api(nft_api)
+                        .with_explorer(explorer)
+                })
+                .collect()
+        }
+        Ok(_) => {
+            tracing::warn!("evm_chains table is empty, using hardcoded defaults");
+            EvmChain::defaults()
+        }
+        Err(e) => {
+            tracing::warn!("Failed to load EVM chains from DB: {}, using hardcoded defaults", e);
+            EvmChain::defaults()
+        }
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -572,4 +1108,48 @@ mod tests {
         let hl_tokens = get_common_tokens(&EvmChain::new("hyper_liquid", "", "HYPE"));
         assert!(hl_tokens.is_empty());
     }
+
+    #[test]
+    fn test_parse_first_transaction() {
+        let found = serde_json::json!({
+            "status": "1",
+            "message": "OK",
+            "result": [{ "timeStamp": "1438918233", "hash": "0xabc" }]
+        });
+        assert_eq!(
+            parse_first_transaction(&found).unwrap(),
+            DateTime::from_timestamp(1438918233, 0)
+        );
+
+        let empty = serde_json::json!({ "status": "0", "message": "No transactions found", "result": [] });
+        assert_eq!(parse_first_transaction(&empty).unwrap(), None);
+
+        let error = serde_json::json!({ "status": "0", "message": "NOTOK", "result": "Invalid API Key" });
+        assert!(parse_first_transaction(&error).unwrap_err().contains("Invalid API Key"));
+    }
+
+    #[test]
+    fn test_parse_gas_fees() {
+        let sender = "0x00000000000000000000000000000000000000aa";
+        let body = serde_json::json!({
+            "status": "1",
+            "message": "OK",
+            "result": [
+                // 21000 gas at 20 gwei
+                { "hash": "0x1", "from": "0x00000000000000000000000000000000000000AA", "gasUsed": "21000", "gasPrice": "20000000000", "timeStamp": "1700000000", "isError": "0" },
+                // Received, paid by someone else
+                { "hash": "0x2", "from": "0x00000000000000000000000000000000000000bb", "gasUsed": "21000", "gasPrice": "20000000000", "timeStamp": "1700000100" },
+                // Before `since`
+                { "hash": "0x3", "from": sender, "gasUsed": "50000", "gasPrice": "1000000000", "timeStamp": "1600000000" }
+            ]
+        });
+        let fees = parse_gas_fees(&body, sender, "ethereum", "ETH", DateTime::from_timestamp(1650000000, 0)).unwrap();
+        assert_eq!(fees.len(), 1);
+        assert_eq!(fees[0].tx_hash, "0x1");
+        assert_eq!(fees[0].asset, "ETH-ethereum");
+        assert_eq!(fees[0].amount, "0.00042");
+
+        let error = serde_json::json!({ "status": "0", "message": "NOTOK", "result": "Invalid API Key" });
+        assert!(parse_gas_fees(&error, sender, "ethereum", "ETH", None).is_err());
+    }
 }
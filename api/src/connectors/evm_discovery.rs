@@ -0,0 +1,338 @@
+//! ERC-20 token discovery for EVM wallets.
+//!
+//! Instead of probing a fixed token list, discovery finds every token contract the wallet has
+//! received from its `Transfer` events and caches the set per wallet and chain in
+//! `discovered_tokens`. Each sync only scans the blocks added since the last scan
+//! (`token_discovery_scans`):
+//! - With `ETHERSCAN_API_KEY` and a chain Etherscan v2 knows, transfers come from the explorer's
+//!   `tokentx` list, which covers the wallet's whole history.
+//! - Otherwise `eth_getLogs` is scanned over the chain's RPC in [`LOG_CHUNK_BLOCKS`] ranges,
+//!   starting [`RPC_LOOKBACK_BLOCKS`] back on the first scan and resuming from there, at most
+//!   [`MAX_LOG_CHUNKS`] ranges per sync.
+//!
+//! Tokens in the `spam_tokens` blocklist for the chain are left out.
+
+use super::evm::{etherscan_chain_id, EvmChain, ERC20, ETHERSCAN_V2_API_URL};
+use crate::entities::{discovered_tokens, spam_tokens, token_discovery_scans};
+use alloy::{
+    primitives::{keccak256, Address, B256},
+    providers::{Provider, ProviderBuilder},
+    rpc::types::Filter,
+};
+use chrono::Utc;
+use sea_orm::{
+    sea_query::OnConflict, ActiveValue, ColumnTrait, DatabaseConnection, EntityTrait, QueryFilter,
+};
+use std::collections::{BTreeMap, HashSet};
+use std::error::Error;
+
+/// Blocks per `eth_getLogs` request; most public RPCs cap the range around 10k blocks
+const LOG_CHUNK_BLOCKS: u64 = 10_000;
+/// `eth_getLogs` requests per wallet, chain and sync
+const MAX_LOG_CHUNKS: usize = 50;
+/// How far back the first RPC scan of a wallet starts
+const RPC_LOOKBACK_BLOCKS: u64 = 1_000_000;
+/// Explorer pages (of [`EXPLORER_PAGE_SIZE`] transfers) read per wallet, chain and sync
+const MAX_EXPLORER_PAGES: u32 = 10;
+const EXPLORER_PAGE_SIZE: usize = 1_000;
+
+/// A token contract found in the wallet's transfers
+#[derive(Debug, Clone, PartialEq)]
+pub struct DiscoveredToken {
+    /// Lowercase contract address
+    pub contract_address: String,
+    pub symbol: Option<String>,
+    pub decimals: Option<u8>,
+}
+
+/// Block ranges (inclusive) the next RPC scan covers: from after `last_scanned` (or
+/// `lookback` blocks before `latest` on the first scan) up to `latest`, at most `max_chunks`
+fn scan_ranges(last_scanned: Option<u64>, latest: u64, lookback: u64, chunk: u64, max_chunks: usize) -> Vec<(u64, u64)> {
+    let mut from = match last_scanned {
+        Some(block) => block + 1,
+        None => latest.saturating_sub(lookback),
+    };
+    let mut ranges = Vec::new();
+    while from <= latest && ranges.len() < max_chunks {
+        let to = (from + chunk - 1).min(latest);
+        ranges.push((from, to));
+        from = to + 1;
+    }
+    ranges
+}
+
+/// Tokens and the highest block in one Etherscan `tokentx` page; a "No transactions found"
+/// response is an empty page
+fn parse_token_transfers(body: &serde_json::Value) -> Result<(Vec<DiscoveredToken>, usize, Option<u64>), String> {
+    let rows = match body.get("result") {
+        Some(serde_json::Value::Array(rows)) => rows,
+        other => {
+            return Err(format!(
+                "Etherscan error: {}",
+                other.and_then(|v| v.as_str()).unwrap_or("unexpected response")
+            ))
+        }
+    };
+
+    let field = |row: &serde_json::Value, key: &str| row.get(key).and_then(|v| v.as_str()).map(str::to_string);
+    let mut tokens: BTreeMap<String, DiscoveredToken> = BTreeMap::new();
+    let mut max_block = None;
+    for row in rows {
+        let Some(contract) = field(row, "contractAddress") else {
+            continue;
+        };
+        let block = field(row, "blockNumber").and_then(|b| b.parse::<u64>().ok());
+        max_block = max_block.max(block);
+        let contract_address = contract.to_lowercase();
+        tokens.entry(contract_address.clone()).or_insert(DiscoveredToken {
+            contract_address,
+            symbol: field(row, "tokenSymbol").filter(|s| !s.is_empty()),
+            decimals: field(row, "tokenDecimal").and_then(|d| d.parse().ok()),
+        });
+    }
+
+    Ok((tokens.into_values().collect(), rows.len(), max_block))
+}
+
+/// Discovers and caches the tokens of EVM wallets
+#[derive(Clone)]
+pub struct TokenDiscovery {
+    db: DatabaseConnection,
+    explorer_api_key: Option<String>,
+}
+
+impl TokenDiscovery {
+    pub fn new(db: DatabaseConnection, explorer_api_key: Option<String>) -> Self {
+        Self {
+            db,
+            explorer_api_key: explorer_api_key.filter(|k| !k.is_empty()),
+        }
+    }
+
+    /// Scan the wallet's new transfers on `chain`, cache the tokens found, and return every
+    /// cached token of the wallet as `(symbol, contract_address)`
+    pub async fn discover(
+        &self,
+        chain: &EvmChain,
+        rpc_url: &str,
+        wallet: Address,
+    ) -> Result<Vec<(String, String)>, Box<dyn Error + Send + Sync>> {
+        let wallet_key = format!("{:?}", wallet).to_lowercase();
+        let last_scanned = token_discovery_scans::Entity::find()
+            .filter(token_discovery_scans::Column::Chain.eq(chain.name()))
+            .filter(token_discovery_scans::Column::WalletAddress.eq(&wallet_key))
+            .one(&self.db)
+            .await?
+            .map(|s| s.last_scanned_block as u64);
+
+        let scan = match (self.explorer_api_key.as_deref(), etherscan_chain_id(chain.name())) {
+            (Some(api_key), Some(chain_id)) => self.scan_explorer(api_key, chain_id, &wallet_key, last_scanned).await?,
+            _ => scan_logs(rpc_url, wallet, last_scanned).await?,
+        };
+        if let Some((found, scanned_to)) = scan {
+            tracing::info!(
+                "Token discovery on {} found {} contracts up to block {}",
+                chain.name(),
+                found.len(),
+                scanned_to
+            );
+            self.save(chain.name(), &wallet_key, &found, scanned_to).await?;
+        }
+
+        self.cached_tokens(chain.name(), &wallet_key).await
+    }
+
+    /// Transfers from the Etherscan v2 `tokentx` list, from the last scanned block on.
+    ///
+    /// The last scanned block is read again since a page may end inside it; the cache's unique
+    /// index makes re-reading harmless.
+    async fn scan_explorer(
+        &self,
+        api_key: &str,
+        chain_id: u64,
+        wallet: &str,
+        last_scanned: Option<u64>,
+    ) -> Result<Option<(Vec<DiscoveredToken>, u64)>, Box<dyn Error + Send + Sync>> {
+        let client = reqwest::Client::new();
+        let start_block = last_scanned.unwrap_or(0);
+        let mut found = Vec::new();
+        let mut scanned_to = last_scanned;
+
+        for page in 1..=MAX_EXPLORER_PAGES {
+            let url = format!(
+                "{}?chainid={}&module=account&action=tokentx&address={}&startblock={}&endblock=99999999&page={}&offset={}&sort=asc&apikey={}",
+                ETHERSCAN_V2_API_URL, chain_id, wallet, start_block, page, EXPLORER_PAGE_SIZE, api_key
+            );
+            let body: serde_json::Value = client.get(&url).send().await?.error_for_status()?.json().await?;
+            let (tokens, rows, max_block) = parse_token_transfers(&body)?;
+            found.extend(tokens);
+            scanned_to = scanned_to.max(max_block);
+            if rows < EXPLORER_PAGE_SIZE {
+                break;
+            }
+        }
+
+        Ok(scanned_to.map(|block| (found, block)))
+    }
+
+    /// Store newly found tokens and the scan cursor
+    async fn save(
+        &self,
+        chain: &str,
+        wallet: &str,
+        found: &[DiscoveredToken],
+        scanned_to: u64,
+    ) -> Result<(), Box<dyn Error + Send + Sync>> {
+        let mut seen = HashSet::new();
+        let rows: Vec<discovered_tokens::ActiveModel> = found
+            .iter()
+            .filter(|t| seen.insert(t.contract_address.clone()))
+            .map(|t| discovered_tokens::ActiveModel {
+                id: ActiveValue::Set(uuid::Uuid::new_v4()),
+                chain: ActiveValue::Set(chain.to_string()),
+                wallet_address: ActiveValue::Set(wallet.to_string()),
+                contract_address: ActiveValue::Set(t.contract_address.clone()),
+                symbol: ActiveValue::Set(t.symbol.clone()),
+                decimals: ActiveValue::Set(t.decimals.map(i16::from)),
+                discovered_at: ActiveValue::Set(Utc::now().into()),
+            })
+            .collect();
+        if !rows.is_empty() {
+            discovered_tokens::Entity::insert_many(rows)
+                .on_conflict(
+                    OnConflict::columns([
+                        discovered_tokens::Column::Chain,
+                        discovered_tokens::Column::WalletAddress,
+                        discovered_tokens::Column::ContractAddress,
+                    ])
+                    .do_nothing()
+                    .to_owned(),
+                )
+                .exec_without_returning(&self.db)
+                .await?;
+        }
+
+        token_discovery_scans::Entity::insert(token_discovery_scans::ActiveModel {
+            id: ActiveValue::Set(uuid::Uuid::new_v4()),
+            chain: ActiveValue::Set(chain.to_string()),
+            wallet_address: ActiveValue::Set(wallet.to_string()),
+            last_scanned_block: ActiveValue::Set(scanned_to as i64),
+            scanned_at: ActiveValue::Set(Utc::now().into()),
+        })
+        .on_conflict(
+            OnConflict::columns([
+                token_discovery_scans::Column::Chain,
+                token_discovery_scans::Column::WalletAddress,
+            ])
+            .update_columns([
+                token_discovery_scans::Column::LastScannedBlock,
+                token_discovery_scans::Column::ScannedAt,
+            ])
+            .to_owned(),
+        )
+        .exec_without_returning(&self.db)
+        .await?;
+
+        Ok(())
+    }
+
+    /// Cached tokens of a wallet that have a symbol and are not blocklisted
+    async fn cached_tokens(&self, chain: &str, wallet: &str) -> Result<Vec<(String, String)>, Box<dyn Error + Send + Sync>> {
+        let blocked: HashSet<String> = spam_tokens::Entity::find()
+            .filter(spam_tokens::Column::Chain.eq(chain))
+            .all(&self.db)
+            .await?
+            .into_iter()
+            .map(|t| t.token_address.to_lowercase())
+            .collect();
+
+        let tokens = discovered_tokens::Entity::find()
+            .filter(discovered_tokens::Column::Chain.eq(chain))
+            .filter(discovered_tokens::Column::WalletAddress.eq(wallet))
+            .all(&self.db)
+            .await?
+            .into_iter()
+            .filter(|t| !blocked.contains(&t.contract_address))
+            .filter_map(|t| Some((t.symbol?, t.contract_address)))
+            .collect();
+        Ok(tokens)
+    }
+}
+
+/// ERC-20 `Transfer` logs to the wallet over the chain's RPC, with each new contract's symbol
+/// and decimals read on-chain
+async fn scan_logs(
+    rpc_url: &str,
+    wallet: Address,
+    last_scanned: Option<u64>,
+) -> Result<Option<(Vec<DiscoveredToken>, u64)>, Box<dyn Error + Send + Sync>> {
+    let provider = ProviderBuilder::new().connect_http(rpc_url.parse()?);
+    let latest = provider.get_block_number().await?;
+    let ranges = scan_ranges(last_scanned, latest, RPC_LOOKBACK_BLOCKS, LOG_CHUNK_BLOCKS, MAX_LOG_CHUNKS);
+    let Some(&(_, scanned_to)) = ranges.last() else {
+        return Ok(None);
+    };
+
+    let transfer_topic = keccak256("Transfer(address,address,uint256)");
+    let mut contracts: Vec<Address> = Vec::new();
+    for (from, to) in ranges {
+        let filter = Filter::new()
+            .from_block(from)
+            .to_block(to)
+            .event_signature(transfer_topic)
+            .topic2(B256::left_padding_from(wallet.as_slice()));
+        for log in provider.get_logs(&filter).await? {
+            // ERC-721 transfers share the signature but also index the token ID
+            if log.topics().len() == 3 && !contracts.contains(&log.address()) {
+                contracts.push(log.address());
+            }
+        }
+    }
+
+    let mut found = Vec::with_capacity(contracts.len());
+    for contract in contracts {
+        let token = ERC20::new(contract, provider.clone());
+        found.push(DiscoveredToken {
+            contract_address: format!("{:?}", contract).to_lowercase(),
+            symbol: token.symbol().call().await.ok().filter(|s| !s.is_empty()),
+            decimals: token.decimals().call().await.ok(),
+        });
+    }
+
+    Ok(Some((found, scanned_to)))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_scan_ranges() {
+        assert_eq!(scan_ranges(None, 25_000, 20_000, 10_000, 50), vec![(5_000, 14_999), (15_000, 24_999), (25_000, 25_000)]);
+        assert_eq!(scan_ranges(Some(100), 30_000, 20_000, 10_000, 2), vec![(101, 10_100), (10_101, 20_100)]);
+        assert!(scan_ranges(Some(500), 500, 20_000, 10_000, 50).is_empty());
+    }
+
+    #[test]
+    fn test_parse_token_transfers() {
+        let body: serde_json::Value = serde_json::from_str(
+            r#"{"status":"1","message":"OK","result":[
+                {"blockNumber":"100","contractAddress":"0xA0b86991c6218b36c1d19D4a2e9Eb0cE3606eB48","tokenSymbol":"USDC","tokenDecimal":"6"},
+                {"blockNumber":"250","contractAddress":"0xa0b86991c6218b36c1d19d4a2e9eb0ce3606eb48","tokenSymbol":"USDC","tokenDecimal":"6"},
+                {"blockNumber":"180","contractAddress":"0x1111111111111111111111111111111111111111","tokenSymbol":"","tokenDecimal":"18"}
+            ]}"#,
+        )
+        .unwrap();
+        let (tokens, rows, max_block) = parse_token_transfers(&body).unwrap();
+        assert_eq!(rows, 3);
+        assert_eq!(max_block, Some(250));
+        assert_eq!(tokens.len(), 2);
+        assert_eq!(tokens[1].contract_address, "0xa0b86991c6218b36c1d19d4a2e9eb0ce3606eb48");
+        assert_eq!(tokens[1].decimals, Some(6));
+        assert!(tokens[0].symbol.is_none());
+
+        let empty = serde_json::json!({"status":"0","message":"No transactions found","result":[]});
+        assert_eq!(parse_token_transfers(&empty).unwrap(), (vec![], 0, None));
+        assert!(parse_token_transfers(&serde_json::json!({"status":"0","result":"Invalid API Key"})).is_err());
+    }
+}
@@ -0,0 +1,272 @@
+//! Wallet balances from an Etherscan-family explorer API.
+//!
+//! A chain whose `evm_chains.balance_provider` is set reads the wallet's native balance and its
+//! full ERC-20 balance list from the explorer in two calls, instead of one `balanceOf` RPC
+//! call per listed token:
+//! - **Etherscan** (`addresstokenbalance`, v2 API selected by `chainid`) — the key comes from
+//!   `explorer_api_key`, falling back to `ETHERSCAN_API_KEY`; the token balance endpoint needs
+//!   an Etherscan plan that includes it.
+//! - **Blockscout** (`tokenlist`) — `explorer_api_url` is the instance's `/api` URL; the key is
+//!   optional.
+//!
+//! Explorer lists include airdropped spam, so tokens without a symbol and tokens blocklisted in
+//! `spam_tokens` for the chain are left out.
+
+use super::evm::{etherscan_chain_id, EvmChain, ETHERSCAN_V2_API_URL};
+use super::Balance;
+use crate::helpers::balance_normalization::normalize_token_balance;
+use reqwest::Client;
+use std::collections::HashSet;
+use std::error::Error;
+
+/// Provider name for Etherscan and its v2 multichain API
+pub const BALANCE_PROVIDER_ETHERSCAN: &str = "etherscan";
+/// Provider name for Blockscout instances
+pub const BALANCE_PROVIDER_BLOCKSCOUT: &str = "blockscout";
+/// Accepted values of `evm_chains.balance_provider`
+pub const BALANCE_PROVIDERS: &[&str] = &[BALANCE_PROVIDER_ETHERSCAN, BALANCE_PROVIDER_BLOCKSCOUT];
+
+/// Token balances per Etherscan page, and pages read per wallet and chain
+const ETHERSCAN_PAGE_SIZE: usize = 100;
+const MAX_ETHERSCAN_PAGES: u32 = 10;
+
+/// Explorer API configured for one chain
+#[derive(Debug, Clone, PartialEq)]
+pub struct ExplorerApi {
+    /// One of [`BALANCE_PROVIDERS`]
+    pub provider: String,
+    /// Base URL without a trailing slash
+    pub api_url: String,
+    pub api_key: Option<String>,
+}
+
+impl ExplorerApi {
+    /// Build the API config from the `evm_chains` columns; `None` unless the provider is supported.
+    /// Etherscan defaults to its v2 URL, Blockscout needs an explicit URL.
+    pub fn from_columns(provider: Option<&str>, api_url: Option<&str>, api_key: Option<&str>) -> Option<Self> {
+        let provider = provider?.trim().to_lowercase();
+        let api_url = api_url.map(|u| u.trim().trim_end_matches('/')).filter(|u| !u.is_empty());
+        let api_url = match provider.as_str() {
+            BALANCE_PROVIDER_ETHERSCAN => api_url.unwrap_or(ETHERSCAN_V2_API_URL),
+            BALANCE_PROVIDER_BLOCKSCOUT => api_url?,
+            _ => return None,
+        };
+        Some(Self {
+            provider,
+            api_url: api_url.to_string(),
+            api_key: api_key.map(str::trim).filter(|k| !k.is_empty()).map(str::to_string),
+        })
+    }
+}
+
+/// A token balance row from an explorer
+#[derive(Debug, Clone, PartialEq)]
+struct ExplorerToken {
+    /// Lowercase contract address
+    contract_address: String,
+    symbol: String,
+    decimals: u8,
+    /// Balance in the token's smallest unit
+    raw_balance: String,
+}
+
+/// The `result` array of an explorer response; "No tokens found" style responses are empty
+fn result_rows(body: &serde_json::Value) -> Result<&[serde_json::Value], String> {
+    match body.get("result") {
+        Some(serde_json::Value::Array(rows)) => Ok(rows),
+        other => Err(format!(
+            "Explorer error: {}",
+            other.and_then(|v| v.as_str()).unwrap_or("unexpected response")
+        )),
+    }
+}
+
+/// Raw native balance from an account `balance` response
+fn parse_native_balance(body: &serde_json::Value) -> Result<String, String> {
+    let result = body.get("result").and_then(|v| v.as_str()).unwrap_or("unexpected response");
+    if body.get("status").and_then(|v| v.as_str()) != Some("1") {
+        return Err(format!("Explorer error: {}", result));
+    }
+    Ok(result.to_string())
+}
+
+fn field<'a>(row: &'a serde_json::Value, key: &str) -> Option<&'a str> {
+    row.get(key).and_then(|v| v.as_str()).map(str::trim)
+}
+
+/// Token rows of an Etherscan `addresstokenbalance` page
+fn parse_etherscan_tokens(body: &serde_json::Value) -> Result<(Vec<ExplorerToken>, usize), String> {
+    let rows = result_rows(body)?;
+    let tokens = rows
+        .iter()
+        .filter_map(|row| {
+            Some(ExplorerToken {
+                contract_address: field(row, "TokenAddress")?.to_lowercase(),
+                symbol: field(row, "TokenSymbol").filter(|s| !s.is_empty())?.to_string(),
+                decimals: field(row, "TokenDivisor")?.parse().ok()?,
+                raw_balance: field(row, "TokenQuantity")?.to_string(),
+            })
+        })
+        .collect();
+    Ok((tokens, rows.len()))
+}
+
+/// ERC-20 rows of a Blockscout `tokenlist` response (NFT rows are skipped)
+fn parse_blockscout_tokens(body: &serde_json::Value) -> Result<Vec<ExplorerToken>, String> {
+    let tokens = result_rows(body)?
+        .iter()
+        .filter(|row| field(row, "type").is_none_or(|t| t == "ERC-20"))
+        .filter_map(|row| {
+            Some(ExplorerToken {
+                contract_address: field(row, "contractAddress")?.to_lowercase(),
+                symbol: field(row, "symbol").filter(|s| !s.is_empty())?.to_string(),
+                decimals: field(row, "decimals")?.parse().ok()?,
+                raw_balance: field(row, "balance")?.to_string(),
+            })
+        })
+        .collect();
+    Ok(tokens)
+}
+
+/// Turn a raw amount into a balance; zero amounts are `None`
+fn balance(asset: String, raw: &str, decimals: u8) -> Option<Balance> {
+    if raw.trim_start_matches('0').is_empty() {
+        return None;
+    }
+    let quantity = normalize_token_balance(raw, decimals).ok()?;
+    Some(Balance {
+        asset,
+        quantity: quantity.clone(),
+        available: quantity,
+        frozen: "0".to_string(),
+        decimals: Some(decimals),
+        holding_source: None,
+    })
+}
+
+/// Native and ERC-20 balances of `wallet` on `chain` from the chain's explorer API, skipping
+/// `blocked` contracts (lowercase)
+pub async fn fetch_explorer_balances(
+    client: &Client,
+    api: &ExplorerApi,
+    chain: &EvmChain,
+    wallet: &str,
+    blocked: &HashSet<String>,
+) -> Result<Vec<Balance>, Box<dyn Error + Send + Sync>> {
+    // Etherscan v2 serves every chain from one URL, selected by chainid
+    let mut base: Vec<(&str, String)> = Vec::new();
+    let api_key = if api.provider == BALANCE_PROVIDER_ETHERSCAN {
+        let chain_id = etherscan_chain_id(chain.name())
+            .ok_or_else(|| format!("Etherscan does not support chain {}", chain.name()))?;
+        base.push(("chainid", chain_id.to_string()));
+        api.api_key.clone().or_else(|| std::env::var("ETHERSCAN_API_KEY").ok())
+    } else {
+        api.api_key.clone()
+    };
+    if let Some(key) = api_key {
+        base.push(("apikey", key));
+    }
+
+    let get = |params: Vec<(&'static str, String)>| {
+        client.get(&api.api_url).query(&base).query(&params).send()
+    };
+
+    let native: serde_json::Value = get(vec![
+        ("module", "account".to_string()),
+        ("action", "balance".to_string()),
+        ("address", wallet.to_string()),
+        ("tag", "latest".to_string()),
+    ])
+    .await?
+    .error_for_status()?
+    .json()
+    .await?;
+    let mut balances: Vec<Balance> =
+        balance(format!("{}-{}", chain.native_symbol(), chain.name()), &parse_native_balance(&native)?, 18)
+            .into_iter()
+            .collect();
+
+    let mut tokens = Vec::new();
+    if api.provider == BALANCE_PROVIDER_ETHERSCAN {
+        for page in 1..=MAX_ETHERSCAN_PAGES {
+            let body: serde_json::Value = get(vec![
+                ("module", "account".to_string()),
+                ("action", "addresstokenbalance".to_string()),
+                ("address", wallet.to_string()),
+                ("page", page.to_string()),
+                ("offset", ETHERSCAN_PAGE_SIZE.to_string()),
+            ])
+            .await?
+            .error_for_status()?
+            .json()
+            .await?;
+            let (page_tokens, rows) = parse_etherscan_tokens(&body)?;
+            tokens.extend(page_tokens);
+            if rows < ETHERSCAN_PAGE_SIZE {
+                break;
+            }
+        }
+    } else {
+        let body: serde_json::Value = get(vec![
+            ("module", "account".to_string()),
+            ("action", "tokenlist".to_string()),
+            ("address", wallet.to_string()),
+        ])
+        .await?
+        .error_for_status()?
+        .json()
+        .await?;
+        tokens = parse_blockscout_tokens(&body)?;
+    }
+
+    balances.extend(
+        tokens
+            .into_iter()
+            .filter(|t| !blocked.contains(&t.contract_address))
+            .filter_map(|t| balance(format!("{}-{}", t.symbol, chain.name()), &t.raw_balance, t.decimals)),
+    );
+    Ok(balances)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_api_from_columns() {
+        let etherscan = ExplorerApi::from_columns(Some("Etherscan"), None, Some(" key ")).unwrap();
+        assert_eq!(etherscan.api_url, ETHERSCAN_V2_API_URL);
+        assert_eq!(etherscan.api_key.as_deref(), Some("key"));
+        assert!(ExplorerApi::from_columns(Some("blockscout"), None, None).is_none());
+        let blockscout = ExplorerApi::from_columns(Some("blockscout"), Some("https://eth.blockscout.com/api/"), None).unwrap();
+        assert_eq!(blockscout.api_url, "https://eth.blockscout.com/api");
+        assert!(ExplorerApi::from_columns(Some("covalent"), Some("https://x"), None).is_none());
+    }
+
+    #[test]
+    fn test_parse_explorer_responses() {
+        let native = serde_json::json!({"status":"1","message":"OK","result":"1500000000000000000"});
+        assert_eq!(parse_native_balance(&native).unwrap(), "1500000000000000000");
+        assert!(parse_native_balance(&serde_json::json!({"status":"0","message":"NOTOK","result":"Invalid API Key"})).is_err());
+
+        let etherscan = serde_json::json!({"status":"1","message":"OK","result":[
+            {"TokenAddress":"0xA0b86991c6218b36c1d19D4a2e9Eb0cE3606eB48","TokenName":"USD Coin","TokenSymbol":"USDC","TokenQuantity":"2500000","TokenDivisor":"6"},
+            {"TokenAddress":"0x1111111111111111111111111111111111111111","TokenName":"","TokenSymbol":"","TokenQuantity":"1","TokenDivisor":"0"}
+        ]});
+        let (tokens, rows) = parse_etherscan_tokens(&etherscan).unwrap();
+        assert_eq!((tokens.len(), rows), (1, 2));
+        assert_eq!(tokens[0].contract_address, "0xa0b86991c6218b36c1d19d4a2e9eb0ce3606eb48");
+        let usdc = balance("USDC-ethereum".to_string(), &tokens[0].raw_balance, tokens[0].decimals).unwrap();
+        assert_eq!(usdc.quantity.parse::<rust_decimal::Decimal>().unwrap(), rust_decimal::Decimal::new(25, 1));
+
+        let blockscout = serde_json::json!({"status":"1","message":"OK","result":[
+            {"balance":"1000000000000000000","contractAddress":"0x6B175474E89094C44Da98b954EedeAC495271d0F","decimals":"18","name":"Dai","symbol":"DAI","type":"ERC-20"},
+            {"balance":"1","contractAddress":"0xBC4CA0EdA7647A8aB7C2061c2E118A18a936f13D","decimals":"","name":"BAYC","symbol":"BAYC","type":"ERC-721"}
+        ]});
+        let tokens = parse_blockscout_tokens(&blockscout).unwrap();
+        assert_eq!(tokens.len(), 1);
+        assert_eq!(tokens[0].symbol, "DAI");
+        assert!(parse_blockscout_tokens(&serde_json::json!({"status":"0","message":"No tokens found","result":[]})).unwrap().is_empty());
+        assert!(balance("DAI-ethereum".to_string(), "000", 18).is_none());
+    }
+}
@@ -0,0 +1,158 @@
+//! Fiat exchange rates against USD.
+//!
+//! Two sources are supported: the ECB euro foreign exchange reference rates (free, ~30
+//! currencies, published on working days around 16:00 CET) and exchangerate.host (API key
+//! required, ~170 currencies including VND). ECB rates are quoted per EUR and are rebased to
+//! USD here, so every source yields units of currency per USD.
+
+use chrono::{DateTime, NaiveDate, Utc};
+use reqwest::Client;
+use rust_decimal::Decimal;
+use serde::Deserialize;
+use std::collections::BTreeMap;
+use std::error::Error;
+use std::str::FromStr;
+
+/// Source name of the ECB reference rates, stored in `fx_rates.source`
+pub const FX_SOURCE_ECB: &str = "ecb";
+
+/// Source name of exchangerate.host, stored in `fx_rates.source`
+pub const FX_SOURCE_EXCHANGERATE_HOST: &str = "exchangerate_host";
+
+const ECB_DAILY_URL: &str = "https://www.ecb.europa.eu/stats/eurofxref/eurofxref-daily.xml";
+
+const EXCHANGERATE_HOST_URL: &str = "https://api.exchangerate.host/live";
+
+/// Exchange rates of one day: units of each currency per USD
+#[derive(Debug, Clone, PartialEq)]
+pub struct FxQuotes {
+    pub date: NaiveDate,
+    pub source: &'static str,
+    pub rates: BTreeMap<String, Decimal>,
+}
+
+/// Value of an XML attribute in a single `<Cube .../>` element
+fn xml_attribute<'a>(element: &'a str, name: &str) -> Option<&'a str> {
+    let start = element.find(&format!("{}='", name))? + name.len() + 2;
+    let len = element[start..].find('\'')?;
+    Some(&element[start..start + len])
+}
+
+/// Parse the ECB daily reference rates and rebase them from EUR to USD
+pub fn parse_ecb_daily(xml: &str) -> Result<FxQuotes, Box<dyn Error + Send + Sync>> {
+    let mut date = None;
+    let mut per_eur: BTreeMap<String, Decimal> = BTreeMap::new();
+    for element in xml.split("<Cube").skip(1) {
+        if let Some(time) = xml_attribute(element, "time") {
+            date = Some(NaiveDate::parse_from_str(time, "%Y-%m-%d")?);
+        }
+        if let (Some(currency), Some(rate)) = (xml_attribute(element, "currency"), xml_attribute(element, "rate")) {
+            per_eur.insert(currency.to_string(), Decimal::from_str(rate)?);
+        }
+    }
+
+    let date = date.ok_or("ECB rates without a date")?;
+    let usd_per_eur = per_eur
+        .get("USD")
+        .copied()
+        .filter(|rate| !rate.is_zero())
+        .ok_or("ECB rates without USD")?;
+
+    let mut rates: BTreeMap<String, Decimal> = per_eur
+        .into_iter()
+        .filter(|(currency, _)| currency != "USD")
+        .map(|(currency, rate)| (currency, (rate / usd_per_eur).round_dp(12)))
+        .collect();
+    rates.insert("EUR".to_string(), (Decimal::ONE / usd_per_eur).round_dp(12));
+    Ok(FxQuotes { date, source: FX_SOURCE_ECB, rates })
+}
+
+#[derive(Debug, Deserialize)]
+struct ExchangerateHostLive {
+    success: bool,
+    #[serde(default)]
+    timestamp: Option<i64>,
+    #[serde(default)]
+    quotes: BTreeMap<String, f64>,
+    #[serde(default)]
+    error: Option<serde_json::Value>,
+}
+
+/// Parse an exchangerate.host `/live?source=USD` response (quotes keyed "USDEUR", ...)
+pub fn parse_exchangerate_host(body: &str) -> Result<FxQuotes, Box<dyn Error + Send + Sync>> {
+    let live: ExchangerateHostLive = serde_json::from_str(body)?;
+    if !live.success {
+        return Err(format!("exchangerate.host error: {}", live.error.unwrap_or_default()).into());
+    }
+    let date = live
+        .timestamp
+        .and_then(|ts| DateTime::<Utc>::from_timestamp(ts, 0))
+        .map(|dt| dt.date_naive())
+        .unwrap_or_else(|| Utc::now().date_naive());
+
+    let rates = live
+        .quotes
+        .into_iter()
+        .filter_map(|(pair, rate)| {
+            let currency = pair.strip_prefix("USD")?;
+            let rate = Decimal::from_str(&rate.to_string()).ok()?;
+            (currency.len() == 3 && currency != "USD").then(|| (currency.to_string(), rate))
+        })
+        .collect();
+    Ok(FxQuotes { date, source: FX_SOURCE_EXCHANGERATE_HOST, rates })
+}
+
+/// Fetch the latest ECB reference rates
+pub async fn fetch_ecb_rates(client: &Client) -> Result<FxQuotes, Box<dyn Error + Send + Sync>> {
+    let response = client.get(ECB_DAILY_URL).send().await?;
+    if !response.status().is_success() {
+        return Err(format!("ECB rates request failed: {}", response.status()).into());
+    }
+    parse_ecb_daily(&response.text().await?)
+}
+
+/// Fetch the latest exchangerate.host rates against USD
+pub async fn fetch_exchangerate_host_rates(
+    client: &Client,
+    api_key: &str,
+) -> Result<FxQuotes, Box<dyn Error + Send + Sync>> {
+    let response = client
+        .get(EXCHANGERATE_HOST_URL)
+        .query(&[("access_key", api_key), ("source", "USD")])
+        .send()
+        .await?;
+    if !response.status().is_success() {
+        return Err(format!("exchangerate.host request failed: {}", response.status()).into());
+    }
+    parse_exchangerate_host(&response.text().await?)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_fx_sources() {
+        let xml = r#"<?xml version="1.0" encoding="UTF-8"?>
+<gesmes:Envelope xmlns:gesmes="http://www.gesmes.org/xml/2002-08-01" xmlns="http://www.ecb.int/vocabulary/2002-08-01/eurofxref">
+	<Cube>
+		<Cube time='2025-01-10'>
+			<Cube currency='USD' rate='1.25'/>
+			<Cube currency='JPY' rate='150.0'/>
+		</Cube>
+	</Cube>
+</gesmes:Envelope>"#;
+        let quotes = parse_ecb_daily(xml).unwrap();
+        assert_eq!(quotes.date, NaiveDate::from_ymd_opt(2025, 1, 10).unwrap());
+        assert_eq!(quotes.rates["EUR"], Decimal::from_str("0.8").unwrap());
+        assert_eq!(quotes.rates["JPY"], Decimal::from(120));
+        assert!(!quotes.rates.contains_key("USD"));
+
+        let body = r#"{"success":true,"timestamp":1736467200,"source":"USD","quotes":{"USDEUR":0.97,"USDVND":25400}}"#;
+        let quotes = parse_exchangerate_host(body).unwrap();
+        assert_eq!(quotes.date, NaiveDate::from_ymd_opt(2025, 1, 10).unwrap());
+        assert_eq!(quotes.rates["VND"], Decimal::from(25400));
+
+        assert!(parse_exchangerate_host(r#"{"success":false,"error":{"code":101}}"#).is_err());
+    }
+}
@@ -0,0 +1,323 @@
+//! GMX v2 perpetual positions of EVM wallets on Arbitrum.
+//!
+//! Positions are read from the GMX `DataStore` contract, whose storage keys are stable across
+//! GMX upgrades (unlike the `Reader` contract): the account's position keys come from its
+//! position list, and each position's market, collateral, size and side from the fields stored
+//! under the position key. Token symbols, decimals and mid prices come from the GMX API.
+//!
+//! Each position is reported as a [`Position`] with size in index tokens, entry and mark price
+//! in USD, and unrealized PnL in the collateral token. The wallet's collateral plus PnL is
+//! reported as a [`HOLDING_SOURCE_PERP`] holding of the collateral token, so portfolio value
+//! includes the margin locked in GMX. Pending borrowing and funding fees are not deducted.
+
+use super::defi::{DefiAdapter, DefiContext, DefiPositions};
+use super::evm::EvmChain;
+use super::{Balance, Position};
+use crate::domain::HOLDING_SOURCE_PERP;
+use alloy::{
+    primitives::{keccak256, Address, B256, U256},
+    providers::ProviderBuilder,
+    sol,
+    sol_types::SolValue,
+};
+use async_trait::async_trait;
+use reqwest::Client;
+use rust_decimal::Decimal;
+use serde::Deserialize;
+use std::collections::{BTreeMap, HashMap};
+use std::error::Error;
+use std::str::FromStr;
+
+/// Protocol name used in the `defi_protocols` account setting
+pub const DEFI_PROTOCOL_GMX_V2: &str = "gmx_v2";
+
+/// GMX v2 prices/decimals precision: USD amounts carry 30 decimals
+const USD_DECIMALS: u8 = 30;
+
+sol! {
+    #[sol(rpc)]
+    contract GmxDataStore {
+        function getBytes32Count(bytes32 setKey) external view returns (uint256);
+        function getBytes32ValuesAt(bytes32 setKey, uint256 start, uint256 end) external view returns (bytes32[] memory);
+        function getUint(bytes32 key) external view returns (uint256);
+        function getAddress(bytes32 key) external view returns (address);
+        function getBool(bytes32 key) external view returns (bool);
+    }
+}
+
+/// GMX v2 deployment on a chain from the `evm_chains` table: (DataStore, API URL)
+pub fn gmx_deployment(chain_name: &str) -> Option<(&'static str, &'static str)> {
+    match chain_name {
+        "arbitrum" => Some(("0xFD70de6b91282D8017aA4E741e9Ae325CAb992d8", "https://arbitrum-api.gmxinfra.io")),
+        _ => None,
+    }
+}
+
+/// [`DefiAdapter`] for GMX v2 perpetuals
+pub struct GmxAdapter;
+
+#[async_trait]
+impl DefiAdapter for GmxAdapter {
+    fn protocol(&self) -> &'static str {
+        DEFI_PROTOCOL_GMX_V2
+    }
+
+    fn supports_chain(&self, chain: &str) -> bool {
+        gmx_deployment(chain).is_some()
+    }
+
+    fn aggregator_ids(&self) -> &'static [&'static str] {
+        &["gmx", "gmx2"]
+    }
+
+    async fn fetch_positions(
+        &self,
+        ctx: &DefiContext<'_>,
+        wallet: Address,
+    ) -> Result<DefiPositions, Box<dyn Error + Send + Sync>> {
+        let (positions, balances) = fetch_gmx_positions(ctx.chain, ctx.chain.rpc_url(), wallet).await?;
+        Ok(DefiPositions { balances, positions })
+    }
+}
+
+/// GMX key constant: `keccak256(abi.encode(name))`
+fn key(name: &str) -> B256 {
+    keccak256(name.to_string().abi_encode())
+}
+
+/// Field of a stored struct: `keccak256(abi.encode(struct_key, keccak256(abi.encode(field))))`
+fn field_key(struct_key: B256, field: &str) -> B256 {
+    keccak256((struct_key, key(field)).abi_encode())
+}
+
+/// Scale a raw amount with `decimals` into a Decimal, dropping digits beyond 18 decimals
+fn scaled(raw: U256, decimals: u8) -> Decimal {
+    let (raw, decimals) = if decimals > 18 {
+        (raw / U256::from(10u64).pow(U256::from(decimals - 18)), 18)
+    } else {
+        (raw, decimals)
+    };
+    let mantissa = i128::try_from(raw).unwrap_or(i128::MAX);
+    Decimal::try_from_i128_with_scale(mantissa, u32::from(decimals)).unwrap_or(Decimal::MAX).normalize()
+}
+
+/// A position as stored in the DataStore
+#[derive(Debug, Clone, PartialEq)]
+struct RawPosition {
+    index_token: Address,
+    collateral_token: Address,
+    size_in_usd: U256,
+    size_in_tokens: U256,
+    collateral_amount: U256,
+    is_long: bool,
+}
+
+/// Token symbol, decimals and USD mid price per whole token from the GMX API
+#[derive(Debug, Clone, PartialEq)]
+struct TokenInfo {
+    symbol: String,
+    decimals: u8,
+    price: Option<Decimal>,
+}
+
+#[derive(Debug, Deserialize)]
+struct TokensResponse {
+    tokens: Vec<ApiToken>,
+}
+
+#[derive(Debug, Deserialize)]
+struct ApiToken {
+    symbol: String,
+    address: String,
+    decimals: u8,
+}
+
+#[derive(Debug, Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct Ticker {
+    token_address: String,
+    min_price: String,
+    max_price: String,
+}
+
+/// Join the API's token list and tickers by lowercase address. Ticker prices are per smallest
+/// token unit with 30 decimals.
+fn token_infos(tokens: Vec<ApiToken>, tickers: Vec<Ticker>) -> HashMap<Address, TokenInfo> {
+    let prices: HashMap<String, (String, String)> = tickers
+        .into_iter()
+        .map(|t| (t.token_address.to_lowercase(), (t.min_price, t.max_price)))
+        .collect();
+    tokens
+        .into_iter()
+        .filter_map(|t| {
+            let address = t.address.parse::<Address>().ok()?;
+            let price = prices.get(&t.address.to_lowercase()).and_then(|(min, max)| {
+                let unit_decimals = USD_DECIMALS.checked_sub(t.decimals)?;
+                let min = scaled(U256::from_str(min).ok()?, unit_decimals);
+                let max = scaled(U256::from_str(max).ok()?, unit_decimals);
+                Some((min + max) / Decimal::TWO)
+            });
+            Some((address, TokenInfo { symbol: t.symbol, decimals: t.decimals, price }))
+        })
+        .collect()
+}
+
+/// Convert a stored position into a [`Position`] and the collateral token's equity
+/// (collateral plus PnL, never below zero). `None` for closed positions or unknown tokens.
+fn to_position(raw: &RawPosition, tokens: &HashMap<Address, TokenInfo>) -> Option<(Position, String, Decimal)> {
+    if raw.size_in_usd.is_zero() || raw.size_in_tokens.is_zero() {
+        return None;
+    }
+    let index = tokens.get(&raw.index_token)?;
+    let collateral = tokens.get(&raw.collateral_token)?;
+
+    let size_tokens = scaled(raw.size_in_tokens, index.decimals);
+    let size_usd = scaled(raw.size_in_usd, USD_DECIMALS);
+    let collateral_amount = scaled(raw.collateral_amount, collateral.decimals);
+    let entry_price = size_usd / size_tokens;
+
+    let pnl_usd = index.price.map(|mark| {
+        let value = size_tokens * mark;
+        if raw.is_long { value - size_usd } else { size_usd - value }
+    });
+    // PnL and leverage need the collateral price to be expressed in collateral terms
+    let pnl = match (pnl_usd, collateral.price) {
+        (Some(pnl), Some(price)) if !price.is_zero() => Some((pnl / price).round_dp(u32::from(collateral.decimals))),
+        _ => None,
+    };
+    let leverage = collateral
+        .price
+        .map(|price| collateral_amount * price)
+        .filter(|margin| !margin.is_zero())
+        .map(|margin| (size_usd / margin).round_dp(2));
+
+    let equity = (collateral_amount + pnl.unwrap_or_default()).max(Decimal::ZERO);
+    let position = Position {
+        instrument: format!("{}-USD", index.symbol),
+        instrument_type: "SWAP".to_string(),
+        side: if raw.is_long { "long" } else { "short" }.to_string(),
+        size: size_tokens.to_string(),
+        entry_price: Some(entry_price.round_dp(8).normalize().to_string()),
+        mark_price: index.price.map(|p| p.round_dp(8).normalize().to_string()),
+        unrealized_pnl: pnl.map(|p| p.normalize().to_string()),
+        margin: Some(collateral_amount.to_string()),
+        margin_currency: Some(collateral.symbol.clone()),
+        margin_mode: Some("isolated".to_string()),
+        leverage: leverage.map(|l| l.normalize().to_string()),
+        liquidation_price: None,
+    };
+    Some((position, collateral.symbol.clone(), equity))
+}
+
+/// Open GMX v2 positions of `wallet` on `chain`, with the collateral equity per collateral token
+/// as perp holdings. Chains without a GMX deployment return nothing.
+pub async fn fetch_gmx_positions(
+    chain: &EvmChain,
+    rpc_url: &str,
+    wallet: Address,
+) -> Result<(Vec<Position>, Vec<Balance>), Box<dyn Error + Send + Sync>> {
+    let Some((data_store, api_url)) = gmx_deployment(chain.name()) else {
+        return Ok((Vec::new(), Vec::new()));
+    };
+    let provider = ProviderBuilder::new().connect_http(rpc_url.parse()?);
+    let data_store = GmxDataStore::new(data_store.parse()?, provider);
+
+    let list_key = keccak256((key("ACCOUNT_POSITION_LIST"), wallet).abi_encode());
+    let count = data_store.getBytes32Count(list_key).call().await?;
+    if count.is_zero() {
+        return Ok((Vec::new(), Vec::new()));
+    }
+    let position_keys = data_store.getBytes32ValuesAt(list_key, U256::ZERO, count).call().await?;
+
+    let mut raw_positions = Vec::with_capacity(position_keys.len());
+    for position_key in position_keys {
+        let market = data_store.getAddress(field_key(position_key, "MARKET")).call().await?;
+        raw_positions.push(RawPosition {
+            index_token: data_store.getAddress(field_key(market.into_word(), "INDEX_TOKEN")).call().await?,
+            collateral_token: data_store.getAddress(field_key(position_key, "COLLATERAL_TOKEN")).call().await?,
+            size_in_usd: data_store.getUint(field_key(position_key, "SIZE_IN_USD")).call().await?,
+            size_in_tokens: data_store.getUint(field_key(position_key, "SIZE_IN_TOKENS")).call().await?,
+            collateral_amount: data_store.getUint(field_key(position_key, "COLLATERAL_AMOUNT")).call().await?,
+            is_long: data_store.getBool(field_key(position_key, "IS_LONG")).call().await?,
+        });
+    }
+
+    let client = Client::new();
+    let tokens: TokensResponse = client.get(format!("{}/tokens", api_url)).send().await?.error_for_status()?.json().await?;
+    let tickers: Vec<Ticker> = client.get(format!("{}/prices/tickers", api_url)).send().await?.error_for_status()?.json().await?;
+    let tokens = token_infos(tokens.tokens, tickers);
+
+    let mut positions = Vec::new();
+    let mut equity: BTreeMap<String, (u8, Decimal)> = BTreeMap::new();
+    for raw in &raw_positions {
+        let Some((position, collateral_symbol, amount)) = to_position(raw, &tokens) else {
+            continue;
+        };
+        let decimals = tokens.get(&raw.collateral_token).map_or(18, |t| t.decimals);
+        equity.entry(collateral_symbol).or_insert((decimals, Decimal::ZERO)).1 += amount;
+        positions.push(position);
+    }
+
+    let balances = equity
+        .into_iter()
+        .filter(|(_, (_, amount))| !amount.is_zero())
+        .map(|(symbol, (decimals, amount))| Balance {
+            asset: format!("{}-{}", symbol, chain.name()),
+            quantity: amount.normalize().to_string(),
+            available: "0".to_string(),
+            frozen: amount.normalize().to_string(),
+            decimals: Some(decimals),
+            holding_source: Some(HOLDING_SOURCE_PERP.to_string()),
+        })
+        .collect();
+    Ok((positions, balances))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_gmx_position_conversion() {
+        // abi.encode(string) carries the offset and length words
+        assert_eq!("MARKET".to_string().abi_encode().len(), 96);
+
+        let weth: Address = "0x82aF49447D8a07e3bd95BD0d56f35241523fBab1".parse().unwrap();
+        let usdc: Address = "0xaf88d065e77c8cC2239327C5EDb3A432268e5831".parse().unwrap();
+        let tokens = token_infos(
+            vec![
+                ApiToken { symbol: "ETH".to_string(), address: format!("{:?}", weth), decimals: 18 },
+                ApiToken { symbol: "USDC".to_string(), address: format!("{:?}", usdc), decimals: 6 },
+            ],
+            vec![
+                // $3,000 and $3,002 per ETH, in 30 decimals per wei
+                Ticker { token_address: format!("{:?}", weth), min_price: "3000000000000000".to_string(), max_price: "3002000000000000".to_string() },
+                Ticker { token_address: format!("{:?}", usdc), min_price: "1000000000000000000000000".to_string(), max_price: "1000000000000000000000000".to_string() },
+            ],
+        );
+        assert_eq!(tokens[&weth].price, Some(Decimal::from(3001)));
+
+        // 2 ETH long opened at $2,500 with 1,000 USDC collateral
+        let raw = RawPosition {
+            index_token: weth,
+            collateral_token: usdc,
+            size_in_usd: U256::from(5000u64) * U256::from(10u64).pow(U256::from(30u64)),
+            size_in_tokens: U256::from(2u64) * U256::from(10u64).pow(U256::from(18u64)),
+            collateral_amount: U256::from(1_000_000_000u64),
+            is_long: true,
+        };
+        let (position, collateral, equity) = to_position(&raw, &tokens).unwrap();
+        assert_eq!(position.instrument, "ETH-USD");
+        assert_eq!(position.side, "long");
+        assert_eq!(position.size, "2");
+        assert_eq!(position.entry_price.as_deref(), Some("2500"));
+        assert_eq!(position.unrealized_pnl.as_deref(), Some("1002"));
+        assert_eq!(position.leverage.as_deref(), Some("5"));
+        assert_eq!((collateral.as_str(), equity), ("USDC", Decimal::from(2002)));
+
+        let short = RawPosition { is_long: false, ..raw.clone() };
+        let (_, _, equity) = to_position(&short, &tokens).unwrap();
+        assert_eq!(equity, Decimal::ZERO);
+        assert!(to_position(&RawPosition { size_in_usd: U256::ZERO, ..raw }, &tokens).is_none());
+    }
+}
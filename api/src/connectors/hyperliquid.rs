@@ -0,0 +1,320 @@
+use super::registry::{ConnectorContext, ConnectorFactory, ACCOUNT_TYPE_EXCHANGE};
+use super::{Balance, ExchangeConnector, Position};
+use crate::domain::HOLDING_SOURCE_PERP;
+use async_trait::async_trait;
+use reqwest;
+use rust_decimal::Decimal;
+use serde::Deserialize;
+use serde_json::json;
+use std::error::Error;
+use std::str::FromStr;
+use tracing;
+
+const HYPERLIQUID_INFO_URL: &str = "https://api.hyperliquid.xyz/info";
+
+/// Collateral currency of Hyperliquid perpetuals
+const HYPERLIQUID_PERP_COLLATERAL: &str = "USDC";
+
+/// Response of the `spotClearinghouseState` info request
+#[derive(Debug, Deserialize)]
+struct SpotClearinghouseState {
+    #[serde(default)]
+    balances: Vec<SpotBalance>,
+}
+
+/// Spot token balance; `hold` is the part locked in open orders
+#[derive(Debug, Deserialize)]
+struct SpotBalance {
+    coin: String,
+    total: String,
+    #[serde(default)]
+    hold: Option<String>,
+}
+
+/// Response of the `clearinghouseState` info request (perp account)
+#[derive(Debug, Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct ClearinghouseState {
+    margin_summary: MarginSummary,
+    /// USDC that can be withdrawn right now
+    #[serde(default)]
+    withdrawable: Option<String>,
+    #[serde(default)]
+    asset_positions: Vec<AssetPosition>,
+}
+
+#[derive(Debug, Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct MarginSummary {
+    /// Account equity in USDC, including unrealized PnL
+    account_value: String,
+}
+
+#[derive(Debug, Deserialize)]
+struct AssetPosition {
+    position: PerpPosition,
+}
+
+/// Perp position; `szi` is signed (negative = short)
+#[derive(Debug, Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct PerpPosition {
+    coin: String,
+    szi: String,
+    entry_px: Option<String>,
+    unrealized_pnl: Option<String>,
+    margin_used: Option<String>,
+    liquidation_px: Option<String>,
+    leverage: Option<PerpLeverage>,
+}
+
+#[derive(Debug, Deserialize)]
+struct PerpLeverage {
+    /// "cross" or "isolated"
+    #[serde(rename = "type")]
+    kind: String,
+    value: u32,
+}
+
+impl PerpPosition {
+    /// Convert to a [`Position`], skipping closed (zero-size) positions
+    fn into_position(self) -> Option<Position> {
+        let size = Decimal::from_str(&self.szi).ok()?;
+        if size.is_zero() {
+            return None;
+        }
+
+        Some(Position {
+            instrument: self.coin,
+            instrument_type: "SWAP".to_string(),
+            side: if size.is_sign_negative() { "short" } else { "long" }.to_string(),
+            size: size.abs().normalize().to_string(),
+            entry_price: self.entry_px,
+            mark_price: None, // Not part of clearinghouseState
+            unrealized_pnl: self.unrealized_pnl,
+            margin: self.margin_used,
+            margin_currency: Some(HYPERLIQUID_PERP_COLLATERAL.to_string()),
+            margin_mode: self.leverage.as_ref().map(|l| l.kind.clone()),
+            leverage: self.leverage.map(|l| l.value.to_string()),
+            liquidation_price: self.liquidation_px,
+        })
+    }
+}
+
+/// Hyperliquid connector reading the public info API by wallet address.
+///
+/// Hyperliquid accounts are identified by their EVM address; no API key is needed.
+/// Spot balances are reported as regular holdings, and the perp account equity
+/// (USDC collateral plus unrealized PnL) as a USDC holding tagged `perp`.
+pub struct HyperliquidConnector {
+    address: String,
+    client: reqwest::Client,
+}
+
+impl HyperliquidConnector {
+    /// Create a new Hyperliquid connector for a wallet address
+    pub fn new(address: String) -> Self {
+        Self {
+            address,
+            client: reqwest::Client::new(),
+        }
+    }
+
+    /// POST an info request for this account
+    async fn info<T: for<'de> Deserialize<'de>>(
+        &self,
+        request_type: &str,
+    ) -> Result<T, Box<dyn Error + Send + Sync>> {
+        tracing::debug!("Hyperliquid info request: {}", request_type);
+
+        let response = self
+            .client
+            .post(HYPERLIQUID_INFO_URL)
+            .json(&json!({ "type": request_type, "user": self.address }))
+            .send()
+            .await?;
+
+        let status = response.status();
+        let body = response.text().await?;
+
+        tracing::debug!("Hyperliquid API Response Status: {}", status);
+        tracing::debug!("Hyperliquid API Response Body: {}", body);
+
+        if !status.is_success() {
+            return Err(format!("Hyperliquid API error: {} - {}", status, body).into());
+        }
+
+        serde_json::from_str(&body).map_err(|e| {
+            tracing::error!("Failed to parse Hyperliquid response: {}", e);
+            format!("Failed to parse Hyperliquid response: {}", e).into()
+        })
+    }
+}
+
+/// Convert spot token balances, dropping zero balances
+fn spot_balances(state: SpotClearinghouseState) -> Vec<Balance> {
+    state
+        .balances
+        .into_iter()
+        .filter_map(|b| {
+            let total = Decimal::from_str(&b.total).ok().filter(|t| *t > Decimal::ZERO)?;
+            let hold = b
+                .hold
+                .and_then(|h| Decimal::from_str(&h).ok())
+                .unwrap_or(Decimal::ZERO)
+                .min(total);
+
+            Some(Balance {
+                asset: b.coin.to_uppercase(),
+                quantity: total.normalize().to_string(),
+                available: (total - hold).normalize().to_string(),
+                frozen: hold.normalize().to_string(),
+                decimals: None, // Quantities are already human-readable
+                holding_source: None,
+            })
+        })
+        .collect()
+}
+
+/// Convert the perp account equity into a USDC balance tagged `perp`.
+///
+/// Margin in use is reported as frozen. Returns `None` for an empty perp account.
+fn perp_equity_balance(state: &ClearinghouseState) -> Option<Balance> {
+    let equity = Decimal::from_str(&state.margin_summary.account_value)
+        .ok()
+        .filter(|v| *v > Decimal::ZERO)?;
+    let withdrawable = state
+        .withdrawable
+        .as_deref()
+        .and_then(|w| Decimal::from_str(w).ok())
+        .unwrap_or(equity)
+        .min(equity);
+
+    Some(Balance {
+        asset: HYPERLIQUID_PERP_COLLATERAL.to_string(),
+        quantity: equity.normalize().to_string(),
+        available: withdrawable.normalize().to_string(),
+        frozen: (equity - withdrawable).normalize().to_string(),
+        decimals: None,
+        holding_source: Some(HOLDING_SOURCE_PERP.to_string()),
+    })
+}
+
+#[async_trait]
+impl ExchangeConnector for HyperliquidConnector {
+    async fn fetch_spot_balances(&self) -> Result<Vec<Balance>, Box<dyn Error + Send + Sync>> {
+        let spot: SpotClearinghouseState = self.info("spotClearinghouseState").await?;
+        let perp: ClearinghouseState = self.info("clearinghouseState").await?;
+
+        let mut balances = spot_balances(spot);
+        balances.extend(perp_equity_balance(&perp));
+
+        tracing::info!("Fetched {} balances from Hyperliquid", balances.len());
+        Ok(balances)
+    }
+
+    async fn fetch_positions(&self) -> Result<Vec<Position>, Box<dyn Error + Send + Sync>> {
+        let perp: ClearinghouseState = self.info("clearinghouseState").await?;
+
+        let positions: Vec<Position> = perp
+            .asset_positions
+            .into_iter()
+            .filter_map(|p| p.position.into_position())
+            .collect();
+
+        tracing::info!("Fetched {} open positions from Hyperliquid", positions.len());
+        Ok(positions)
+    }
+}
+
+/// Builds [`HyperliquidConnector`]s for Hyperliquid accounts, read by wallet address without credentials
+pub struct HyperliquidFactory;
+
+#[async_trait]
+impl ConnectorFactory for HyperliquidFactory {
+    fn account_type(&self) -> &'static str {
+        ACCOUNT_TYPE_EXCHANGE
+    }
+
+    fn name(&self) -> &'static str {
+        "hyperliquid"
+    }
+
+    fn requires_wallet_address(&self) -> bool {
+        true
+    }
+
+    async fn create(
+        &self,
+        ctx: &ConnectorContext<'_>,
+    ) -> Result<Box<dyn ExchangeConnector>, Box<dyn Error + Send + Sync>> {
+        Ok(Box::new(HyperliquidConnector::new(ctx.wallet_address()?)))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn clearinghouse_state() -> ClearinghouseState {
+        serde_json::from_value(json!({
+            "marginSummary": { "accountValue": "1250.5", "totalMarginUsed": "200.5" },
+            "withdrawable": "1050",
+            "assetPositions": [
+                {
+                    "type": "oneWay",
+                    "position": {
+                        "coin": "ETH", "szi": "-0.5", "entryPx": "3000", "unrealizedPnl": "12.5",
+                        "marginUsed": "150", "liquidationPx": "3500",
+                        "leverage": { "type": "cross", "value": 10 }
+                    }
+                },
+                { "type": "oneWay", "position": { "coin": "BTC", "szi": "0.0" } }
+            ]
+        }))
+        .unwrap()
+    }
+
+    #[test]
+    fn test_spot_balances() {
+        let state: SpotClearinghouseState = serde_json::from_value(json!({
+            "balances": [
+                { "coin": "USDC", "token": 0, "hold": "10.0", "total": "110.5", "entryNtl": "0.0" },
+                { "coin": "HYPE", "token": 150, "hold": "0.0", "total": "3.0" },
+                { "coin": "PURR", "token": 1, "hold": "0.0", "total": "0.0" }
+            ]
+        }))
+        .unwrap();
+
+        let balances = spot_balances(state);
+
+        assert_eq!(balances.len(), 2);
+        assert_eq!(balances[0].asset, "USDC");
+        assert_eq!(balances[0].available, "100.5");
+        assert_eq!(balances[0].frozen, "10");
+        assert_eq!(balances[1].quantity, "3");
+        assert!(balances[1].holding_source.is_none());
+    }
+
+    #[test]
+    fn test_perp_equity_and_positions() {
+        let state = clearinghouse_state();
+
+        let equity = perp_equity_balance(&state).unwrap();
+        assert_eq!(equity.asset, "USDC");
+        assert_eq!(equity.quantity, "1250.5");
+        assert_eq!(equity.frozen, "200.5");
+        assert_eq!(equity.holding_source.as_deref(), Some(HOLDING_SOURCE_PERP));
+
+        let positions: Vec<Position> = state
+            .asset_positions
+            .into_iter()
+            .filter_map(|p| p.position.into_position())
+            .collect();
+        assert_eq!(positions.len(), 1);
+        assert_eq!(positions[0].side, "short");
+        assert_eq!(positions[0].size, "0.5");
+        assert_eq!(positions[0].margin_mode.as_deref(), Some("cross"));
+        assert_eq!(positions[0].leverage.as_deref(), Some("10"));
+    }
+}
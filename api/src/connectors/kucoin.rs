@@ -0,0 +1,280 @@
+use super::registry::{ConnectorContext, ConnectorFactory, ACCOUNT_TYPE_EXCHANGE};
+use super::{Balance, ExchangeConnector};
+use async_trait::async_trait;
+use base64::{engine::general_purpose, Engine as _};
+use chrono::Utc;
+use hmac::{Hmac, Mac};
+use reqwest;
+use rust_decimal::Decimal;
+use serde::Deserialize;
+use sha2::Sha256;
+use std::collections::BTreeMap;
+use std::error::Error;
+use std::str::FromStr;
+use tracing;
+
+type HmacSha256 = Hmac<Sha256>;
+
+const KUCOIN_API_BASE_URL: &str = "https://api.kucoin.com";
+
+/// KuCoin success code returned in the response envelope
+const KUCOIN_SUCCESS_CODE: &str = "200000";
+
+/// Internal KuCoin account types that are aggregated into holdings.
+///
+/// KuCoin keeps separate ledgers for the funding ("main"), spot ("trade")
+/// and cross-margin ("margin") accounts; a single asset can have a balance in each.
+const KUCOIN_ACCOUNT_TYPES: [&str; 3] = ["main", "trade", "margin"];
+
+/// KuCoin API response wrapper
+#[derive(Debug, Deserialize)]
+struct KucoinResponse<T> {
+    code: String,
+    #[serde(default)]
+    msg: Option<String>,
+    data: Option<T>,
+}
+
+/// KuCoin account data structure (one row per currency per account type)
+///
+/// NOTE: Only quantities are read. KuCoin does not return valuations on this
+/// endpoint and holdings must remain quantity-only.
+#[derive(Debug, Deserialize)]
+struct KucoinAccountData {
+    currency: String,
+    #[serde(rename = "type")]
+    account_type: String,
+    balance: String,
+    available: String,
+    holds: String,
+}
+
+/// KuCoin connector for read-only access
+pub struct KucoinConnector {
+    api_key: String,
+    api_secret: String,
+    passphrase: String,
+    client: reqwest::Client,
+}
+
+impl KucoinConnector {
+    /// Create a new KuCoin connector with API credentials
+    pub fn new(api_key: String, api_secret: String, passphrase: String) -> Self {
+        Self {
+            api_key,
+            api_secret,
+            passphrase,
+            client: reqwest::Client::new(),
+        }
+    }
+
+    /// Base64-encoded HMAC-SHA256 of `payload` keyed with the API secret
+    fn sign(&self, payload: &str) -> String {
+        let mut mac = HmacSha256::new_from_slice(self.api_secret.as_bytes())
+            .expect("HMAC can take key of any size");
+        mac.update(payload.as_bytes());
+
+        let result = mac.finalize();
+        general_purpose::STANDARD.encode(result.into_bytes())
+    }
+
+    /// Generate the KC-API-SIGN header value
+    ///
+    /// `signature = Base64(HMAC-SHA256(timestamp + method + endpoint + body, secret))`
+    fn generate_signature(&self, timestamp: &str, method: &str, endpoint: &str, body: &str) -> String {
+        let prehash = format!("{}{}{}{}", timestamp, method, endpoint, body);
+        self.sign(&prehash)
+    }
+
+    /// Generate the KC-API-PASSPHRASE header value
+    ///
+    /// With API key version 2 the passphrase is itself signed with the API secret.
+    fn generate_passphrase(&self) -> String {
+        self.sign(&self.passphrase)
+    }
+
+    /// Make an authenticated GET request to KuCoin API
+    async fn get_request<T: for<'de> Deserialize<'de>>(
+        &self,
+        endpoint: &str,
+    ) -> Result<T, Box<dyn Error + Send + Sync>> {
+        let timestamp = Utc::now().timestamp_millis().to_string();
+        let method = "GET";
+
+        let signature = self.generate_signature(&timestamp, method, endpoint, "");
+        let passphrase = self.generate_passphrase();
+
+        let url = format!("{}{}", KUCOIN_API_BASE_URL, endpoint);
+
+        tracing::debug!("KuCoin API Request: {} {}", method, url);
+
+        let response = self
+            .client
+            .get(&url)
+            .header("KC-API-KEY", &self.api_key)
+            .header("KC-API-SIGN", signature)
+            .header("KC-API-TIMESTAMP", timestamp)
+            .header("KC-API-PASSPHRASE", passphrase)
+            .header("KC-API-KEY-VERSION", "2")
+            .header("Content-Type", "application/json")
+            .send()
+            .await?;
+
+        let status = response.status();
+        let body = response.text().await?;
+
+        tracing::debug!("KuCoin API Response Status: {}", status);
+        tracing::debug!("KuCoin API Response Body: {}", body);
+
+        if !status.is_success() {
+            return Err(format!("KuCoin API error: {} - {}", status, body).into());
+        }
+
+        serde_json::from_str(&body).map_err(|e| {
+            tracing::error!("Failed to parse KuCoin response: {}", e);
+            format!("Failed to parse KuCoin response: {}", e).into()
+        })
+    }
+}
+
+/// Aggregate per-account-type rows into one balance per currency.
+///
+/// Rows for account types outside [`KUCOIN_ACCOUNT_TYPES`] and rows with a
+/// zero total are ignored. Results are sorted by currency.
+fn aggregate_balances(accounts: Vec<KucoinAccountData>) -> Vec<Balance> {
+    let mut totals: BTreeMap<String, (Decimal, Decimal, Decimal)> = BTreeMap::new();
+
+    for account in accounts {
+        if !KUCOIN_ACCOUNT_TYPES.contains(&account.account_type.as_str()) {
+            continue;
+        }
+
+        let balance = Decimal::from_str(&account.balance).unwrap_or(Decimal::ZERO);
+        let available = Decimal::from_str(&account.available).unwrap_or(Decimal::ZERO);
+        let holds = Decimal::from_str(&account.holds).unwrap_or(Decimal::ZERO);
+
+        let entry = totals
+            .entry(account.currency)
+            .or_insert((Decimal::ZERO, Decimal::ZERO, Decimal::ZERO));
+        entry.0 += balance;
+        entry.1 += available;
+        entry.2 += holds;
+    }
+
+    totals
+        .into_iter()
+        .filter(|(_, (balance, _, _))| *balance > Decimal::ZERO)
+        .map(|(currency, (balance, available, holds))| Balance {
+            asset: currency,
+            quantity: balance.normalize().to_string(),
+            available: available.normalize().to_string(),
+            frozen: holds.normalize().to_string(),
+            decimals: None, // KuCoin doesn't provide decimal information
+            holding_source: None,
+        })
+        .collect()
+}
+
+#[async_trait]
+impl ExchangeConnector for KucoinConnector {
+    async fn fetch_spot_balances(&self) -> Result<Vec<Balance>, Box<dyn Error + Send + Sync>> {
+        // Returns one row per (currency, account type) across all internal accounts
+        let endpoint = "/api/v1/accounts";
+
+        let response: KucoinResponse<Vec<KucoinAccountData>> = self.get_request(endpoint).await?;
+
+        if response.code != KUCOIN_SUCCESS_CODE {
+            return Err(format!(
+                "KuCoin API error: {} - {}",
+                response.code,
+                response.msg.unwrap_or_default()
+            )
+            .into());
+        }
+
+        let balances = aggregate_balances(response.data.unwrap_or_default());
+
+        tracing::info!("Fetched {} balances from KuCoin", balances.len());
+        Ok(balances)
+    }
+}
+
+/// Builds [`KucoinConnector`]s for KuCoin accounts
+pub struct KucoinFactory;
+
+#[async_trait]
+impl ConnectorFactory for KucoinFactory {
+    fn account_type(&self) -> &'static str {
+        ACCOUNT_TYPE_EXCHANGE
+    }
+
+    fn name(&self) -> &'static str {
+        "kucoin"
+    }
+
+    async fn create(
+        &self,
+        ctx: &ConnectorContext<'_>,
+    ) -> Result<Box<dyn ExchangeConnector>, Box<dyn Error + Send + Sync>> {
+        let (api_key, api_secret) = ctx.api_credentials()?;
+        Ok(Box::new(KucoinConnector::new(api_key, api_secret, ctx.passphrase()?)))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn account(currency: &str, account_type: &str, balance: &str, available: &str, holds: &str) -> KucoinAccountData {
+        KucoinAccountData {
+            currency: currency.to_string(),
+            account_type: account_type.to_string(),
+            balance: balance.to_string(),
+            available: available.to_string(),
+            holds: holds.to_string(),
+        }
+    }
+
+    #[test]
+    fn test_signature_generation() {
+        let connector = KucoinConnector::new(
+            "test-api-key".to_string(),
+            "test-secret".to_string(),
+            "test-passphrase".to_string(),
+        );
+
+        let signature = connector.generate_signature("1704067200000", "GET", "/api/v1/accounts", "");
+
+        // Signature should be a non-empty base64 string
+        assert!(!signature.is_empty());
+        assert!(general_purpose::STANDARD.decode(&signature).is_ok());
+
+        // Passphrase is signed (key version 2), never sent in plain text
+        let passphrase = connector.generate_passphrase();
+        assert_ne!(passphrase, "test-passphrase");
+        assert!(general_purpose::STANDARD.decode(&passphrase).is_ok());
+    }
+
+    #[test]
+    fn test_aggregate_balances_across_account_types() {
+        let balances = aggregate_balances(vec![
+            account("BTC", "main", "0.5", "0.5", "0"),
+            account("BTC", "trade", "0.25", "0.2", "0.05"),
+            account("BTC", "margin", "0.25", "0.25", "0"),
+            account("USDT", "trade", "100", "100", "0"),
+            account("ETH", "trade", "0", "0", "0"),
+            account("KCS", "pool", "10", "10", "0"),
+        ]);
+
+        assert_eq!(balances.len(), 2);
+
+        let btc = &balances[0];
+        assert_eq!(btc.asset, "BTC");
+        assert_eq!(btc.quantity, "1");
+        assert_eq!(btc.available, "0.95");
+        assert_eq!(btc.frozen, "0.05");
+
+        assert_eq!(balances[1].asset, "USDT");
+        assert_eq!(balances[1].quantity, "100");
+    }
+}
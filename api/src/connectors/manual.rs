@@ -0,0 +1,66 @@
+use super::registry::{ConnectorContext, ConnectorFactory, ACCOUNT_TYPE_EXCHANGE};
+use super::{Balance, ExchangeConnector};
+use crate::domain::AccountHolding;
+use async_trait::async_trait;
+use std::error::Error;
+
+/// Connector for exchanges without an API connector, whose holdings come from CSV statement
+/// imports (`POST /api/v1/accounts/{id}/import`).
+///
+/// Syncing reports the stored holdings unchanged, so a sync never wipes imported data.
+pub struct ManualConnector {
+    holdings: Vec<AccountHolding>,
+}
+
+impl ManualConnector {
+    /// Create a connector reporting the given stored holdings
+    pub fn new(holdings: Vec<AccountHolding>) -> Self {
+        Self { holdings }
+    }
+}
+
+#[async_trait]
+impl ExchangeConnector for ManualConnector {
+    async fn fetch_spot_balances(&self) -> Result<Vec<Balance>, Box<dyn Error + Send + Sync>> {
+        Ok(self
+            .holdings
+            .iter()
+            .map(|h| Balance {
+                asset: h.asset.clone(),
+                quantity: h.quantity.clone(),
+                available: h.available_quantity().to_string(),
+                frozen: h.frozen_quantity().to_string(),
+                decimals: h.decimals,
+                holding_source: h.holding_source.clone(),
+            })
+            .collect())
+    }
+}
+
+/// Builds [`ManualConnector`]s for "manual" exchange accounts, which need no credentials
+pub struct ManualFactory;
+
+#[async_trait]
+impl ConnectorFactory for ManualFactory {
+    fn account_type(&self) -> &'static str {
+        ACCOUNT_TYPE_EXCHANGE
+    }
+
+    fn name(&self) -> &'static str {
+        "manual"
+    }
+
+    async fn create(
+        &self,
+        ctx: &ConnectorContext<'_>,
+    ) -> Result<Box<dyn ExchangeConnector>, Box<dyn Error + Send + Sync>> {
+        let holdings: Vec<AccountHolding> = ctx
+            .account
+            .holdings
+            .clone()
+            .map(serde_json::from_value)
+            .transpose()?
+            .unwrap_or_default();
+        Ok(Box::new(ManualConnector::new(holdings)))
+    }
+}
@@ -0,0 +1,263 @@
+use super::registry::{ConnectorContext, ConnectorFactory, ACCOUNT_TYPE_EXCHANGE};
+use super::{Balance, ExchangeConnector};
+use crate::helpers::asset_identity::{AssetIdentityNormalizer, NormalizationResult};
+use async_trait::async_trait;
+use chrono::Utc;
+use hmac::{Hmac, Mac};
+use reqwest;
+use rust_decimal::Decimal;
+use serde::Deserialize;
+use sha2::Sha256;
+use std::collections::BTreeMap;
+use std::error::Error;
+use std::str::FromStr;
+use tracing;
+
+type HmacSha256 = Hmac<Sha256>;
+
+const MEXC_API_BASE_URL: &str = "https://api.mexc.com";
+
+/// Request validity window (ms) sent as `recvWindow` on signed requests
+const MEXC_RECV_WINDOW_MS: u32 = 5000;
+
+/// MEXC error body returned with non-2xx responses
+#[derive(Debug, Deserialize)]
+struct MexcErrorResponse {
+    code: i64,
+    msg: String,
+}
+
+/// MEXC spot account information (`GET /api/v3/account`)
+#[derive(Debug, Deserialize)]
+struct MexcAccountInfo {
+    #[serde(default)]
+    balances: Vec<MexcBalanceData>,
+}
+
+/// MEXC balance data structure
+///
+/// NOTE: Only quantities are read. MEXC does not return valuations on this
+/// endpoint and holdings must remain quantity-only.
+#[derive(Debug, Deserialize)]
+struct MexcBalanceData {
+    asset: String,
+    free: String,
+    locked: String,
+}
+
+/// MEXC connector for read-only access
+///
+/// MEXC API keys have no passphrase; only the key and secret are required.
+pub struct MexcConnector {
+    api_key: String,
+    api_secret: String,
+    normalizer: AssetIdentityNormalizer,
+    client: reqwest::Client,
+}
+
+impl MexcConnector {
+    /// Create a new MEXC connector with API credentials.
+    ///
+    /// The normalizer maps MEXC asset codes to canonical symbols from the assets table.
+    pub fn new(api_key: String, api_secret: String, normalizer: AssetIdentityNormalizer) -> Self {
+        Self {
+            api_key,
+            api_secret,
+            normalizer,
+            client: reqwest::Client::new(),
+        }
+    }
+
+    /// Generate the request signature
+    ///
+    /// `signature = hex(HMAC-SHA256(query_string, secret))`
+    fn generate_signature(&self, query_string: &str) -> String {
+        let mut mac = HmacSha256::new_from_slice(self.api_secret.as_bytes())
+            .expect("HMAC can take key of any size");
+        mac.update(query_string.as_bytes());
+
+        mac.finalize()
+            .into_bytes()
+            .iter()
+            .map(|b| format!("{:02x}", b))
+            .collect()
+    }
+
+    /// Make a signed GET request to MEXC API
+    async fn get_signed<T: for<'de> Deserialize<'de>>(
+        &self,
+        endpoint: &str,
+    ) -> Result<T, Box<dyn Error + Send + Sync>> {
+        let query = format!(
+            "recvWindow={}&timestamp={}",
+            MEXC_RECV_WINDOW_MS,
+            Utc::now().timestamp_millis()
+        );
+        let signature = self.generate_signature(&query);
+
+        let url = format!("{}{}?{}&signature={}", MEXC_API_BASE_URL, endpoint, query, signature);
+
+        tracing::debug!("MEXC API Request: GET {}{}", MEXC_API_BASE_URL, endpoint);
+
+        let response = self
+            .client
+            .get(&url)
+            .header("X-MEXC-APIKEY", &self.api_key)
+            .header("Content-Type", "application/json")
+            .send()
+            .await?;
+
+        let status = response.status();
+        let body = response.text().await?;
+
+        tracing::debug!("MEXC API Response Status: {}", status);
+        tracing::debug!("MEXC API Response Body: {}", body);
+
+        if !status.is_success() {
+            return Err(match serde_json::from_str::<MexcErrorResponse>(&body) {
+                Ok(err) => format!("MEXC API error: {} - {}", err.code, err.msg),
+                Err(_) => format!("MEXC API error: {} - {}", status, body),
+            }
+            .into());
+        }
+
+        serde_json::from_str(&body).map_err(|e| {
+            tracing::error!("Failed to parse MEXC response: {}", e);
+            format!("Failed to parse MEXC response: {}", e).into()
+        })
+    }
+
+    /// Map a MEXC asset code to its canonical symbol, keeping the code if unknown
+    async fn canonical_symbol(&self, asset: &str) -> String {
+        match self.normalizer.normalize_from_symbol(asset).await {
+            NormalizationResult::Mapped(identity) => identity.symbol,
+            NormalizationResult::Unknown { .. } => asset.trim().to_uppercase(),
+        }
+    }
+}
+
+/// Build balances from MEXC rows keyed by their canonical symbol.
+///
+/// Rows that map to the same symbol are summed; zero balances are dropped.
+/// Results are sorted by symbol.
+fn build_balances(rows: Vec<(String, MexcBalanceData)>) -> Vec<Balance> {
+    let mut totals: BTreeMap<String, (Decimal, Decimal)> = BTreeMap::new();
+
+    for (symbol, row) in rows {
+        let free = Decimal::from_str(&row.free).unwrap_or(Decimal::ZERO);
+        let locked = Decimal::from_str(&row.locked).unwrap_or(Decimal::ZERO);
+
+        let entry = totals.entry(symbol).or_insert((Decimal::ZERO, Decimal::ZERO));
+        entry.0 += free;
+        entry.1 += locked;
+    }
+
+    totals
+        .into_iter()
+        .filter(|(_, (free, locked))| *free + *locked > Decimal::ZERO)
+        .map(|(symbol, (free, locked))| Balance {
+            asset: symbol,
+            quantity: (free + locked).normalize().to_string(),
+            available: free.normalize().to_string(),
+            frozen: locked.normalize().to_string(),
+            decimals: None, // MEXC doesn't provide decimal information
+            holding_source: None,
+        })
+        .collect()
+}
+
+#[async_trait]
+impl ExchangeConnector for MexcConnector {
+    async fn fetch_spot_balances(&self) -> Result<Vec<Balance>, Box<dyn Error + Send + Sync>> {
+        let account: MexcAccountInfo = self.get_signed("/api/v3/account").await?;
+
+        let mut rows = Vec::with_capacity(account.balances.len());
+        for row in account.balances {
+            // Skip the asset lookup for empty rows
+            if Decimal::from_str(&row.free).unwrap_or(Decimal::ZERO).is_zero()
+                && Decimal::from_str(&row.locked).unwrap_or(Decimal::ZERO).is_zero()
+            {
+                continue;
+            }
+            let symbol = self.canonical_symbol(&row.asset).await;
+            rows.push((symbol, row));
+        }
+
+        let balances = build_balances(rows);
+
+        tracing::info!("Fetched {} balances from MEXC", balances.len());
+        Ok(balances)
+    }
+}
+
+/// Builds [`MexcConnector`]s for MEXC accounts
+pub struct MexcFactory;
+
+#[async_trait]
+impl ConnectorFactory for MexcFactory {
+    fn account_type(&self) -> &'static str {
+        ACCOUNT_TYPE_EXCHANGE
+    }
+
+    fn name(&self) -> &'static str {
+        "mexc"
+    }
+
+    async fn create(
+        &self,
+        ctx: &ConnectorContext<'_>,
+    ) -> Result<Box<dyn ExchangeConnector>, Box<dyn Error + Send + Sync>> {
+        let (api_key, api_secret) = ctx.api_credentials()?;
+        Ok(Box::new(MexcConnector::new(
+            api_key,
+            api_secret,
+            AssetIdentityNormalizer::new(ctx.db.clone()),
+        )))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use sea_orm::DatabaseConnection;
+
+    fn row(asset: &str, free: &str, locked: &str) -> MexcBalanceData {
+        MexcBalanceData {
+            asset: asset.to_string(),
+            free: free.to_string(),
+            locked: locked.to_string(),
+        }
+    }
+
+    #[test]
+    fn test_signature_generation() {
+        let connector = MexcConnector::new(
+            "test-api-key".to_string(),
+            "test-secret".to_string(),
+            AssetIdentityNormalizer::new(DatabaseConnection::default()),
+        );
+
+        let signature = connector.generate_signature("recvWindow=5000&timestamp=1704067200000");
+
+        // Hex-encoded SHA-256 HMAC is 64 lowercase hex characters
+        assert_eq!(signature.len(), 64);
+        assert!(signature.chars().all(|c| c.is_ascii_hexdigit() && !c.is_ascii_uppercase()));
+    }
+
+    #[test]
+    fn test_build_balances_merges_same_symbol() {
+        let balances = build_balances(vec![
+            ("BTC".to_string(), row("BTC", "0.4", "0.1")),
+            ("USDT".to_string(), row("usdt", "50", "0")),
+            ("USDT".to_string(), row("USDT", "50", "0")),
+            ("ETH".to_string(), row("ETH", "0", "0")),
+        ]);
+
+        assert_eq!(balances.len(), 2);
+        assert_eq!(balances[0].asset, "BTC");
+        assert_eq!(balances[0].quantity, "0.5");
+        assert_eq!(balances[0].frozen, "0.1");
+        assert_eq!(balances[1].asset, "USDT");
+        assert_eq!(balances[1].quantity, "100");
+    }
+}
@@ -1,13 +1,44 @@
 pub mod okx;
+pub mod kucoin;
+pub mod mexc;
+pub mod cryptocom;
+pub mod bitfinex;
+pub mod binance;
+pub mod binance_stream;
+pub mod deribit;
+pub mod hyperliquid;
+pub mod manual;
 pub mod evm;
+pub mod aave;
+pub mod compound;
+pub mod curve;
+pub mod gmx;
+pub mod pendle;
+pub mod vaults;
+pub mod defi;
+pub mod debank;
+pub mod evm_discovery;
+pub mod uniswap_twap;
+pub mod explorer;
+pub mod fx;
+pub mod nft;
+pub mod safe;
 pub mod coinpaprika;
-// Legacy CoinGecko connector (deprecated in favor of CoinPaprika)
-// pub mod coingecko;
+pub mod coingecko;
+pub mod dexscreener;
+pub mod price_provider;
 pub mod solana;
+pub mod utxo;
+pub mod cosmos;
+pub mod registry;
 
 use async_trait::async_trait;
+use chrono::{DateTime, Utc};
+use rust_decimal::Decimal;
 use serde::{Deserialize, Serialize};
 use std::error::Error;
+use std::str::FromStr;
+use thiserror::Error as ThisError;
 
 /// Balance information for a single asset
 /// 
@@ -29,6 +60,229 @@ pub struct Balance {
     /// Kept as metadata; the quantity field is already normalized.
     #[serde(skip_serializing_if = "Option::is_none")]
     pub decimals: Option<u8>,
+    /// Where the balance is held ("earn" for savings/staking products, "staked" for on-chain
+    /// stake, "perp" for perpetuals account equity, "derivatives" for futures/options account
+    /// equity, "supplied"/"borrowed" for lending positions, borrowed being negative, "lp" for
+    /// the underlying coins of liquidity pool positions, "fixed_yield" for Pendle PT/YT
+    /// tokens); `None` means spot.
+    /// The same asset may appear once per source.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub holding_source: Option<String>,
+}
+
+/// Outcome of reading one chain during a multi-chain balance fetch
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+pub struct ChainFetch {
+    /// Chain name, e.g. "ethereum" or "bsc"
+    pub chain: String,
+    /// Number of balances read on the chain
+    pub balances: usize,
+    /// Why some or all of the chain's balances could not be read
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub error: Option<String>,
+    pub duration_ms: u64,
+}
+
+/// Progress of one chain during a multi-chain balance fetch
+#[derive(Debug, Clone, PartialEq)]
+pub enum ChainProgress {
+    /// Reading the chain has begun
+    Started { chain: String },
+    /// The chain has been read
+    Finished(ChainFetch),
+}
+
+/// Open derivatives (futures/perpetual/option) position
+///
+/// Unlike [`Balance`], a position carries exchange-reported PnL and margin figures:
+/// a position's exposure cannot be described by a quantity alone.
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+pub struct Position {
+    /// Exchange instrument identifier (e.g., "BTC-USDT-SWAP")
+    pub instrument: String,
+    /// Instrument type (e.g., "SWAP", "FUTURES", "OPTION")
+    pub instrument_type: String,
+    /// "long" or "short"
+    pub side: String,
+    /// Absolute position size in contracts
+    pub size: String,
+    pub entry_price: Option<String>,
+    pub mark_price: Option<String>,
+    pub unrealized_pnl: Option<String>,
+    /// Margin allocated to the position
+    pub margin: Option<String>,
+    /// Currency of `margin` and `unrealized_pnl`
+    pub margin_currency: Option<String>,
+    /// "cross" or "isolated"
+    pub margin_mode: Option<String>,
+    pub leverage: Option<String>,
+    pub liquidation_price: Option<String>,
+}
+
+/// Executed trade (fill) reported by an exchange
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+pub struct Trade {
+    /// Exchange trade ID, unique per account
+    pub trade_id: String,
+    pub order_id: Option<String>,
+    /// Exchange instrument identifier (e.g., "BTC-USDT")
+    pub instrument: String,
+    /// Instrument type (e.g., "SPOT", "SWAP", "FUTURES")
+    pub instrument_type: String,
+    /// Traded asset, when the instrument names one (e.g., "BTC" in "BTC-USDT")
+    pub base_asset: Option<String>,
+    /// Currency the price is quoted in (e.g., "USDT")
+    pub quote_asset: Option<String>,
+    /// "buy" or "sell"
+    pub side: String,
+    /// Filled quantity (base units for spot, contracts for derivatives)
+    pub quantity: String,
+    pub price: String,
+    /// Fee paid in `fee_currency`; negative for rebates
+    pub fee: Option<String>,
+    pub fee_currency: Option<String>,
+    pub executed_at: DateTime<Utc>,
+}
+
+/// Transfer direction: funds received by the account
+pub const TRANSFER_DEPOSIT: &str = "deposit";
+/// Transfer direction: funds sent out of the account
+pub const TRANSFER_WITHDRAWAL: &str = "withdrawal";
+
+/// Completed deposit or withdrawal reported by an exchange
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+pub struct Transfer {
+    /// Exchange deposit/withdrawal ID, unique per account
+    pub transfer_id: String,
+    /// [`TRANSFER_DEPOSIT`] or [`TRANSFER_WITHDRAWAL`]
+    pub direction: String,
+    pub asset: String,
+    /// Amount moved, excluding `fee`
+    pub amount: String,
+    /// Network fee charged on withdrawals, in `asset`
+    pub fee: Option<String>,
+    /// Chain the transfer used (e.g., "USDT-TRC20")
+    pub network: Option<String>,
+    /// On-chain transaction hash; `None` for internal transfers
+    pub tx_hash: Option<String>,
+    pub occurred_at: DateTime<Utc>,
+}
+
+/// Network fee (gas) the wallet paid for one on-chain transaction it sent
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+pub struct GasFee {
+    /// Chain name (e.g., "ethereum")
+    pub chain: String,
+    pub tx_hash: String,
+    /// Native asset the fee was paid in (e.g., "ETH-ethereum")
+    pub asset: String,
+    /// Fee in native units (decimal string)
+    pub amount: String,
+    pub paid_at: DateTime<Utc>,
+}
+
+/// Staking reward credited to a stake account for one epoch
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+pub struct StakingReward {
+    /// Asset the reward was paid in (e.g., "SOL-solana")
+    pub asset: String,
+    /// Stake account (or validator position) that earned the reward
+    pub source_account: String,
+    /// Epoch the reward was earned in
+    pub epoch: u64,
+    /// Reward amount, normalized
+    pub amount: String,
+    /// Stake account balance after the reward, normalized
+    pub post_balance: Option<String>,
+    /// Validator commission (percent) when the reward was paid
+    pub commission: Option<u8>,
+    /// Time the reward was credited
+    pub earned_at: DateTime<Utc>,
+}
+
+/// ERC-721/ERC-1155 token held by a wallet, as reported by an NFT API
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+pub struct NftHolding {
+    /// EVM chain (e.g., "ethereum")
+    pub chain: String,
+    /// Lowercase contract address
+    pub contract_address: String,
+    pub token_id: String,
+    /// "ERC721" or "ERC1155"
+    pub token_standard: Option<String>,
+    /// Tokens of this ID held (always "1" for ERC-721)
+    pub quantity: String,
+    pub collection_name: Option<String>,
+    pub name: Option<String>,
+    pub image_url: Option<String>,
+    /// Collection floor price per token, in `floor_currency`
+    pub floor_price: Option<String>,
+    /// Currency of `floor_price` (e.g., "ETH")
+    pub floor_currency: Option<String>,
+}
+
+/// Configuration of a multisig wallet (Safe) on one chain
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+pub struct MultisigDeployment {
+    /// EVM chain (e.g., "ethereum")
+    pub chain: String,
+    /// Owner confirmations needed to execute a transaction
+    pub threshold: u32,
+    /// Owner addresses
+    pub owners: Vec<String>,
+    /// Enabled modules, which can execute transactions without owner confirmations
+    pub modules: Vec<String>,
+    /// Transaction guard contract, if set
+    pub guard: Option<String>,
+    /// Safe contract version (e.g., "1.3.0")
+    pub version: Option<String>,
+    /// Nonce of the next transaction
+    pub nonce: u64,
+}
+
+/// Sub-account under an exchange master account
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+pub struct SubAccount {
+    /// Identifier used by the exchange API (OKX sub-account name, Binance sub-account email)
+    pub id: String,
+    /// Optional user-defined label
+    pub label: Option<String>,
+}
+
+/// Typed connector failure, so sync results can tell retryable errors from bad credentials.
+///
+/// Connectors return it boxed as their regular error type; callers recover it with
+/// `error.downcast_ref::<ConnectorError>()`.
+#[derive(Debug, Clone, PartialEq, Eq, ThisError)]
+pub enum ConnectorError {
+    /// The exchange rejected the request because of rate limits; retry later
+    #[error("{exchange} rate limit exceeded: {message}")]
+    RateLimited { exchange: String, message: String },
+    /// API key, secret, passphrase or signature rejected
+    #[error("{exchange} rejected the API credentials: {message}")]
+    InvalidCredentials { exchange: String, message: String },
+    /// The key is valid but lacks a permission (or the caller IP is not whitelisted)
+    #[error("{exchange} API permission denied: {message}")]
+    PermissionDenied { exchange: String, message: String },
+    /// The response did not match the expected schema
+    #[error("Unexpected {exchange} response: {message}")]
+    InvalidResponse { exchange: String, message: String },
+    /// Any other error reported by the exchange
+    #[error("{exchange} API error: {code} - {message}")]
+    Api { exchange: String, code: String, message: String },
+}
+
+impl ConnectorError {
+    /// Stable machine-readable error kind (e.g. "rate_limited")
+    pub fn kind(&self) -> &'static str {
+        match self {
+            ConnectorError::RateLimited { .. } => "rate_limited",
+            ConnectorError::InvalidCredentials { .. } => "invalid_credentials",
+            ConnectorError::PermissionDenied { .. } => "permission_denied",
+            ConnectorError::InvalidResponse { .. } => "invalid_response",
+            ConnectorError::Api { .. } => "api_error",
+        }
+    }
 }
 
 /// Trait for exchange connectors
@@ -36,4 +290,173 @@ pub struct Balance {
 pub trait ExchangeConnector: Send + Sync {
     /// Fetch spot balances from the exchange
     async fn fetch_spot_balances(&self) -> Result<Vec<Balance>, Box<dyn Error + Send + Sync>>;
+
+    /// Fetch spot balances, telling `on_chain` as reading each chain starts and finishes.
+    ///
+    /// Connectors reading a single venue or chain report nothing.
+    async fn fetch_spot_balances_by_chain(
+        &self,
+        _on_chain: &(dyn Fn(ChainProgress) + Send + Sync),
+    ) -> Result<Vec<Balance>, Box<dyn Error + Send + Sync>> {
+        self.fetch_spot_balances().await
+    }
+
+    /// Fetch open derivatives positions.
+    ///
+    /// Connectors without derivatives support return no positions.
+    async fn fetch_positions(&self) -> Result<Vec<Position>, Box<dyn Error + Send + Sync>> {
+        Ok(Vec::new())
+    }
+
+    /// Fetch trade fills executed at or after `since` (all available history when `None`).
+    ///
+    /// Connectors without trade history support return no trades.
+    async fn fetch_trades(
+        &self,
+        _since: Option<DateTime<Utc>>,
+    ) -> Result<Vec<Trade>, Box<dyn Error + Send + Sync>> {
+        Ok(Vec::new())
+    }
+
+    /// Fetch completed deposits and withdrawals, optionally only those at or after `since`.
+    ///
+    /// Connectors without transfer history support return no transfers.
+    async fn fetch_transfers(
+        &self,
+        _since: Option<DateTime<Utc>>,
+    ) -> Result<Vec<Transfer>, Box<dyn Error + Send + Sync>> {
+        Ok(Vec::new())
+    }
+
+    /// Fetch gas fees of transactions the wallet sent at or after `since` (recent
+    /// transactions when `None`).
+    ///
+    /// Exchanges and connectors without transaction history return no fees.
+    async fn fetch_gas_fees(
+        &self,
+        _since: Option<DateTime<Utc>>,
+    ) -> Result<Vec<GasFee>, Box<dyn Error + Send + Sync>> {
+        Ok(Vec::new())
+    }
+
+    /// Fetch staking rewards for epochs after `after_epoch` (recent epochs when `None`).
+    ///
+    /// Connectors without staking support return no rewards.
+    async fn fetch_staking_rewards(
+        &self,
+        _after_epoch: Option<u64>,
+    ) -> Result<Vec<StakingReward>, Box<dyn Error + Send + Sync>> {
+        Ok(Vec::new())
+    }
+
+    /// Fetch the NFTs held by the wallet.
+    ///
+    /// Connectors without NFT support return no NFTs.
+    async fn fetch_nfts(&self) -> Result<Vec<NftHolding>, Box<dyn Error + Send + Sync>> {
+        Ok(Vec::new())
+    }
+
+    /// Fetch the multisig configuration of the wallet on each chain it is deployed on.
+    ///
+    /// Connectors for single-owner wallets and exchanges return no deployments.
+    async fn fetch_multisig(&self) -> Result<Vec<MultisigDeployment>, Box<dyn Error + Send + Sync>> {
+        Ok(Vec::new())
+    }
+
+    /// Whether the exchange has a sub-account hierarchy this connector can read.
+    ///
+    /// Sub-account methods need an API key of the master account.
+    fn supports_sub_accounts(&self) -> bool {
+        false
+    }
+
+    /// List the sub-accounts of the master account
+    async fn discover_sub_accounts(&self) -> Result<Vec<SubAccount>, Box<dyn Error + Send + Sync>> {
+        Ok(Vec::new())
+    }
+
+    /// Fetch the balances held by one sub-account
+    async fn fetch_sub_account_balances(
+        &self,
+        _sub_account: &SubAccount,
+    ) -> Result<Vec<Balance>, Box<dyn Error + Send + Sync>> {
+        Ok(Vec::new())
+    }
+
+    /// Time of the wallet's first on-chain transaction, if the connector can look it up.
+    ///
+    /// Returns `None` for exchanges and for wallets without any transactions.
+    async fn fetch_first_activity(&self) -> Result<Option<DateTime<Utc>>, Box<dyn Error + Send + Sync>> {
+        Ok(None)
+    }
+}
+
+/// Sum balances of the same asset and holding source (e.g. master and sub-account balances).
+///
+/// Keeps the order in which each asset/source first appears.
+pub fn merge_balances(balances: Vec<Balance>) -> Vec<Balance> {
+    let parse = |v: &str| Decimal::from_str(v).unwrap_or(Decimal::ZERO);
+    let mut merged: Vec<(Balance, Decimal, Decimal, Decimal)> = Vec::new();
+
+    for balance in balances {
+        let (quantity, available, frozen) =
+            (parse(&balance.quantity), parse(&balance.available), parse(&balance.frozen));
+        match merged
+            .iter_mut()
+            .find(|(b, ..)| b.asset == balance.asset && b.holding_source == balance.holding_source)
+        {
+            Some((existing, q, a, f)) => {
+                *q += quantity;
+                *a += available;
+                *f += frozen;
+                existing.decimals = existing.decimals.or(balance.decimals);
+            }
+            None => merged.push((balance, quantity, available, frozen)),
+        }
+    }
+
+    merged
+        .into_iter()
+        .map(|(balance, quantity, available, frozen)| Balance {
+            quantity: quantity.normalize().to_string(),
+            available: available.normalize().to_string(),
+            frozen: frozen.normalize().to_string(),
+            ..balance
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn balance(asset: &str, quantity: &str, source: Option<&str>) -> Balance {
+        Balance {
+            asset: asset.to_string(),
+            quantity: quantity.to_string(),
+            available: quantity.to_string(),
+            frozen: "0".to_string(),
+            decimals: None,
+            holding_source: source.map(str::to_string),
+        }
+    }
+
+    #[test]
+    fn test_merge_balances_sums_per_asset_and_source() {
+        let merged = merge_balances(vec![
+            balance("BTC", "0.5", None),
+            balance("USDT", "100", Some("earn")),
+            balance("BTC", "0.25", None),
+            balance("USDT", "50", None),
+            balance("USDT", "10", Some("earn")),
+        ]);
+
+        assert_eq!(merged.len(), 3);
+        assert_eq!(merged[0].asset, "BTC");
+        assert_eq!(merged[0].quantity, "0.75");
+        assert_eq!(merged[1].quantity, "110");
+        assert_eq!(merged[1].holding_source.as_deref(), Some("earn"));
+        assert_eq!(merged[2].quantity, "50");
+        assert!(merged[2].holding_source.is_none());
+    }
 }
@@ -0,0 +1,316 @@
+//! NFT discovery for EVM wallets through an NFT indexer API.
+//!
+//! Each chain in `evm_chains` may name a provider and its base URL. Two providers are
+//! supported:
+//! - **Alchemy** (`getNFTsForOwner`, NFT API v3) — the API key is part of the base URL,
+//!   e.g. `https://eth-mainnet.g.alchemy.com/nft/v3/<key>`; floor prices are OpenSea
+//!   floors in the chain's native token.
+//! - **Reservoir** (`/users/{owner}/tokens/v10`) — an optional `RESERVOIR_API_KEY` is sent as
+//!   the `x-api-key` header; floor prices carry their own currency.
+//!
+//! Tokens flagged as spam by the provider are skipped.
+
+use super::NftHolding;
+use reqwest::Client;
+use serde::Deserialize;
+use std::error::Error;
+
+/// Provider name for the Alchemy NFT API
+pub const NFT_PROVIDER_ALCHEMY: &str = "alchemy";
+/// Provider name for the Reservoir API
+pub const NFT_PROVIDER_RESERVOIR: &str = "reservoir";
+/// Accepted values of `evm_chains.nft_provider`
+pub const NFT_PROVIDERS: &[&str] = &[NFT_PROVIDER_ALCHEMY, NFT_PROVIDER_RESERVOIR];
+
+/// Pages fetched per wallet and chain; wallets holding more NFTs are truncated
+const MAX_NFT_PAGES: usize = 20;
+
+/// NFT API configured for one chain
+#[derive(Debug, Clone, PartialEq)]
+pub struct NftApi {
+    /// One of [`NFT_PROVIDERS`]
+    pub provider: String,
+    /// Base URL without a trailing slash
+    pub api_url: String,
+}
+
+impl NftApi {
+    /// Build the API config from the `evm_chains` columns; `None` unless both are set and the
+    /// provider is supported
+    pub fn from_columns(provider: Option<&str>, api_url: Option<&str>) -> Option<Self> {
+        let provider = provider?.trim().to_lowercase();
+        let api_url = api_url?.trim().trim_end_matches('/');
+        if api_url.is_empty() || !NFT_PROVIDERS.contains(&provider.as_str()) {
+            return None;
+        }
+        Some(Self { provider, api_url: api_url.to_string() })
+    }
+}
+
+// ── Alchemy ────────────────────────────────────────────────────────────────
+
+#[derive(Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct AlchemyPage {
+    owned_nfts: Vec<AlchemyNft>,
+    page_key: Option<String>,
+}
+
+#[derive(Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct AlchemyNft {
+    contract: AlchemyContract,
+    token_id: String,
+    token_type: Option<String>,
+    name: Option<String>,
+    image: Option<AlchemyImage>,
+    balance: Option<String>,
+}
+
+#[derive(Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct AlchemyContract {
+    address: String,
+    name: Option<String>,
+    #[serde(default)]
+    is_spam: bool,
+    open_sea_metadata: Option<AlchemyOpenSeaMetadata>,
+}
+
+#[derive(Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct AlchemyOpenSeaMetadata {
+    floor_price: Option<f64>,
+    collection_name: Option<String>,
+}
+
+#[derive(Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct AlchemyImage {
+    cached_url: Option<String>,
+    original_url: Option<String>,
+}
+
+/// Holdings of one Alchemy page; floor prices are in the chain's native token
+fn alchemy_holdings(page: AlchemyPage, chain: &str, native_symbol: &str) -> Vec<NftHolding> {
+    page.owned_nfts
+        .into_iter()
+        .filter(|nft| !nft.contract.is_spam)
+        .map(|nft| {
+            let metadata = nft.contract.open_sea_metadata;
+            let floor_price = metadata.as_ref().and_then(|m| m.floor_price).filter(|p| *p > 0.0);
+            NftHolding {
+                chain: chain.to_string(),
+                contract_address: nft.contract.address.to_lowercase(),
+                token_id: nft.token_id,
+                token_standard: nft.token_type,
+                quantity: nft.balance.unwrap_or_else(|| "1".to_string()),
+                collection_name: metadata.and_then(|m| m.collection_name).or(nft.contract.name),
+                name: nft.name,
+                image_url: nft.image.and_then(|i| i.cached_url.or(i.original_url)),
+                floor_price: floor_price.map(|p| p.to_string()),
+                floor_currency: floor_price.map(|_| native_symbol.to_string()),
+            }
+        })
+        .collect()
+}
+
+// ── Reservoir ──────────────────────────────────────────────────────────────
+
+#[derive(Deserialize)]
+struct ReservoirPage {
+    tokens: Vec<ReservoirEntry>,
+    continuation: Option<String>,
+}
+
+#[derive(Deserialize)]
+struct ReservoirEntry {
+    token: ReservoirToken,
+    ownership: Option<ReservoirOwnership>,
+}
+
+#[derive(Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct ReservoirToken {
+    contract: String,
+    token_id: String,
+    kind: Option<String>,
+    name: Option<String>,
+    image: Option<String>,
+    #[serde(default)]
+    is_spam: bool,
+    collection: Option<ReservoirCollection>,
+}
+
+#[derive(Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct ReservoirCollection {
+    name: Option<String>,
+    floor_ask_price: Option<ReservoirPrice>,
+}
+
+#[derive(Deserialize)]
+struct ReservoirPrice {
+    currency: Option<ReservoirCurrency>,
+    amount: Option<ReservoirAmount>,
+}
+
+#[derive(Deserialize)]
+struct ReservoirCurrency {
+    symbol: Option<String>,
+}
+
+#[derive(Deserialize)]
+struct ReservoirAmount {
+    decimal: Option<f64>,
+}
+
+#[derive(Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct ReservoirOwnership {
+    token_count: Option<String>,
+}
+
+/// Holdings of one Reservoir page; Reservoir's "erc721"/"erc1155" kinds are upper-cased to
+/// match Alchemy's token types
+fn reservoir_holdings(page: ReservoirPage, chain: &str) -> Vec<NftHolding> {
+    page.tokens
+        .into_iter()
+        .filter(|entry| !entry.token.is_spam)
+        .map(|entry| {
+            let token = entry.token;
+            let (collection_name, floor) = match token.collection {
+                Some(c) => (c.name, c.floor_ask_price),
+                None => (None, None),
+            };
+            let floor_price = floor
+                .as_ref()
+                .and_then(|f| f.amount.as_ref())
+                .and_then(|a| a.decimal)
+                .filter(|p| *p > 0.0);
+            let floor_currency = floor.and_then(|f| f.currency).and_then(|c| c.symbol);
+            NftHolding {
+                chain: chain.to_string(),
+                contract_address: token.contract.to_lowercase(),
+                token_id: token.token_id,
+                token_standard: token.kind.map(|k| k.to_uppercase()),
+                quantity: entry
+                    .ownership
+                    .and_then(|o| o.token_count)
+                    .unwrap_or_else(|| "1".to_string()),
+                collection_name,
+                name: token.name,
+                image_url: token.image,
+                floor_price: floor_price.map(|p| p.to_string()),
+                floor_currency: floor_price.and(floor_currency),
+            }
+        })
+        .collect()
+}
+
+/// Fetch every NFT `owner` holds on `chain` through the chain's NFT API
+pub async fn fetch_wallet_nfts(
+    client: &Client,
+    api: &NftApi,
+    chain: &str,
+    native_symbol: &str,
+    owner: &str,
+) -> Result<Vec<NftHolding>, Box<dyn Error + Send + Sync>> {
+    let mut holdings = Vec::new();
+    let mut cursor: Option<String> = None;
+
+    for _ in 0..MAX_NFT_PAGES {
+        let next = if api.provider == NFT_PROVIDER_ALCHEMY {
+            let mut request = client
+                .get(format!("{}/getNFTsForOwner", api.api_url))
+                .query(&[("owner", owner), ("withMetadata", "true"), ("pageSize", "100")]);
+            if let Some(key) = &cursor {
+                request = request.query(&[("pageKey", key)]);
+            }
+            let page: AlchemyPage = request.send().await?.error_for_status()?.json().await?;
+            let next = page.page_key.clone();
+            holdings.extend(alchemy_holdings(page, chain, native_symbol));
+            next
+        } else {
+            let mut request = client
+                .get(format!("{}/users/{}/tokens/v10", api.api_url, owner))
+                .query(&[("limit", "200")]);
+            if let Some(continuation) = &cursor {
+                request = request.query(&[("continuation", continuation)]);
+            }
+            if let Ok(key) = std::env::var("RESERVOIR_API_KEY") {
+                request = request.header("x-api-key", key);
+            }
+            let page: ReservoirPage = request.send().await?.error_for_status()?.json().await?;
+            let next = page.continuation.clone();
+            holdings.extend(reservoir_holdings(page, chain));
+            next
+        };
+
+        match next.filter(|c| !c.is_empty()) {
+            Some(next) => cursor = Some(next),
+            None => return Ok(holdings),
+        }
+    }
+
+    tracing::warn!(
+        "Stopped NFT discovery for {} on {} after {} pages",
+        owner,
+        chain,
+        MAX_NFT_PAGES
+    );
+    Ok(holdings)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_api_from_columns() {
+        let api = NftApi::from_columns(Some("Alchemy"), Some("https://eth-mainnet.g.alchemy.com/nft/v3/key/")).unwrap();
+        assert_eq!(api.provider, "alchemy");
+        assert_eq!(api.api_url, "https://eth-mainnet.g.alchemy.com/nft/v3/key");
+        assert!(NftApi::from_columns(Some("opensea"), Some("https://example.com")).is_none());
+        assert!(NftApi::from_columns(Some("reservoir"), None).is_none());
+    }
+
+    #[test]
+    fn test_alchemy_page_skips_spam() {
+        let page: AlchemyPage = serde_json::from_str(
+            r##"{"ownedNfts":[
+                {"contract":{"address":"0xBC4CA0EdA7647A8aB7C2061c2E118A18a936f13D","name":"BoredApeYachtClub","tokenType":"ERC721","isSpam":false,
+                    "openSeaMetadata":{"floorPrice":11.5,"collectionName":"Bored Ape Yacht Club"}},
+                 "tokenId":"42","tokenType":"ERC721","name":"#42","image":{"cachedUrl":"https://img/42.png"},"balance":"1"},
+                {"contract":{"address":"0x1111111111111111111111111111111111111111","isSpam":true},"tokenId":"7","tokenType":"ERC1155","balance":"500"}
+            ],"totalCount":2,"pageKey":null}"##,
+        )
+        .unwrap();
+        let holdings = alchemy_holdings(page, "ethereum", "ETH");
+        assert_eq!(holdings.len(), 1);
+        assert_eq!(holdings[0].contract_address, "0xbc4ca0eda7647a8ab7c2061c2e118a18a936f13d");
+        assert_eq!(holdings[0].collection_name.as_deref(), Some("Bored Ape Yacht Club"));
+        assert_eq!(holdings[0].floor_price.as_deref(), Some("11.5"));
+        assert_eq!(holdings[0].floor_currency.as_deref(), Some("ETH"));
+    }
+
+    #[test]
+    fn test_reservoir_page() {
+        let page: ReservoirPage = serde_json::from_str(
+            r#"{"tokens":[
+                {"token":{"chainId":1,"contract":"0xabc","tokenId":"1","kind":"erc1155","name":"Pass","image":null,
+                    "collection":{"id":"0xabc","name":"Passes","floorAskPrice":{"currency":{"symbol":"WETH"},"amount":{"decimal":0.25}}}},
+                 "ownership":{"tokenCount":"3"}},
+                {"token":{"chainId":1,"contract":"0xdef","tokenId":"9","kind":"erc721","collection":{"name":"No Floor"}}}
+            ],"continuation":null}"#,
+        )
+        .unwrap();
+        let holdings = reservoir_holdings(page, "ethereum");
+        assert_eq!(holdings.len(), 2);
+        assert_eq!(holdings[0].token_standard.as_deref(), Some("ERC1155"));
+        assert_eq!(holdings[0].quantity, "3");
+        assert_eq!(holdings[0].floor_currency.as_deref(), Some("WETH"));
+        assert_eq!(holdings[1].quantity, "1");
+        assert!(holdings[1].floor_price.is_none() && holdings[1].floor_currency.is_none());
+    }
+}
@@ -1,27 +1,55 @@
-use super::{Balance, ExchangeConnector};
+use super::registry::{ConnectorContext, ConnectorFactory, ACCOUNT_TYPE_EXCHANGE};
+use super::{
+    Balance, ConnectorError, ExchangeConnector, Position, SubAccount, Trade, Transfer, TRANSFER_DEPOSIT,
+    TRANSFER_WITHDRAWAL,
+};
+use crate::domain::HOLDING_SOURCE_EARN;
 use async_trait::async_trait;
 use base64::{engine::general_purpose, Engine as _};
-use chrono::Utc;
+use chrono::{DateTime, Utc};
 use hmac::{Hmac, Mac};
 use reqwest;
+use rust_decimal::Decimal;
 use serde::Deserialize;
 use sha2::Sha256;
+use std::collections::BTreeMap;
 use std::error::Error;
+use std::str::FromStr;
 use tracing;
 
 type HmacSha256 = Hmac<Sha256>;
 
 const OKX_API_BASE_URL: &str = "https://www.okx.com";
 
+/// Page size for paginated OKX list endpoints (maximum allowed)
+const OKX_PAGE_LIMIT: usize = 100;
+
+/// Safety cap on pages fetched from one list endpoint
+const OKX_MAX_PAGES: usize = 50;
+
+/// OKX error codes for rate-limited requests
+const OKX_RATE_LIMIT_CODES: [&str; 3] = ["50011", "50040", "50061"];
+
+/// OKX error codes for a missing, invalid or mismatched key, passphrase or signature
+const OKX_INVALID_CREDENTIAL_CODES: [&str; 8] =
+    ["50100", "50101", "50103", "50104", "50105", "50111", "50113", "50114"];
+
+/// OKX error codes for a valid key lacking permission (or a non-whitelisted IP)
+const OKX_PERMISSION_CODES: [&str; 3] = ["50030", "50110", "50120"];
+
 /// OKX API response wrapper
 #[derive(Debug, Deserialize)]
 struct OkxResponse<T> {
     code: String,
+    #[serde(default)]
     msg: String,
+    #[serde(default = "Vec::new")]
     data: Vec<T>,
 }
 
 /// OKX balance data structure
+///
+/// Used for trading account `details` rows and funding account rows, which share these fields.
 /// 
 /// NOTE: This struct intentionally omits valuation fields (eq, totalEq, upl) from the OKX API response.
 /// Account holdings store ONLY quantities, not valuations. Price/valuation is calculated separately
@@ -35,6 +63,259 @@ struct OkxBalanceData {
     frozen_bal: String,
 }
 
+/// Sub-account entry from `/api/v5/users/subaccount/list`
+#[derive(Debug, Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct OkxSubAccount {
+    sub_acct: String,
+    #[serde(default)]
+    label: String,
+    /// Creation time (Unix ms), the pagination cursor of the list
+    #[serde(default)]
+    ts: String,
+}
+
+/// Simple Earn (savings) balance from `/api/v5/finance/savings/balance`
+#[derive(Debug, Deserialize)]
+struct OkxSavingsBalance {
+    ccy: String,
+    amt: String,
+}
+
+/// Active on-chain staking / DeFi order from `/api/v5/finance/staking-defi/orders-active`
+#[derive(Debug, Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct OkxStakingOrder {
+    #[serde(default)]
+    invest_data: Vec<OkxInvestData>,
+}
+
+/// Invested amount of a staking order
+#[derive(Debug, Deserialize)]
+struct OkxInvestData {
+    ccy: String,
+    amt: String,
+}
+
+/// OKX position data from `/api/v5/account/positions`
+///
+/// OKX reports empty strings for fields that do not apply (e.g. `liqPx` without leverage).
+#[derive(Debug, Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct OkxPositionData {
+    inst_id: String,
+    inst_type: String,
+    #[serde(default)]
+    mgn_mode: String,
+    /// "long"/"short" in long/short mode, "net" in net mode (side given by the sign of `pos`)
+    #[serde(default)]
+    pos_side: String,
+    pos: String,
+    #[serde(default)]
+    avg_px: String,
+    #[serde(default)]
+    mark_px: String,
+    #[serde(default)]
+    upl: String,
+    /// Margin of isolated positions
+    #[serde(default)]
+    margin: String,
+    /// Initial margin requirement of cross positions
+    #[serde(default)]
+    imr: String,
+    #[serde(default)]
+    ccy: String,
+    #[serde(default)]
+    lever: String,
+    #[serde(default)]
+    liq_px: String,
+}
+
+/// Trade fill from `/api/v5/trade/fills-history`
+#[derive(Debug, Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct OkxFill {
+    inst_type: String,
+    inst_id: String,
+    trade_id: String,
+    #[serde(default)]
+    ord_id: String,
+    /// Bill ID, the pagination cursor of the list
+    bill_id: String,
+    side: String,
+    fill_sz: String,
+    fill_px: String,
+    /// Fee charged (negative) or rebate (positive)
+    #[serde(default)]
+    fee: String,
+    #[serde(default)]
+    fee_ccy: String,
+    /// Execution time (Unix ms)
+    ts: String,
+}
+
+/// Deposit record from `/api/v5/asset/deposit-history`
+#[derive(Debug, Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct OkxDeposit {
+    dep_id: String,
+    ccy: String,
+    amt: String,
+    #[serde(default)]
+    chain: String,
+    #[serde(default)]
+    tx_id: String,
+    /// Deposit state; see [`OKX_DEPOSIT_STATE_SUCCESS`]
+    state: String,
+    /// Time the deposit was credited (Unix ms), also the pagination cursor
+    ts: String,
+}
+
+/// Withdrawal record from `/api/v5/asset/withdrawal-history`
+#[derive(Debug, Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct OkxWithdrawal {
+    wd_id: String,
+    ccy: String,
+    amt: String,
+    #[serde(default)]
+    fee: String,
+    #[serde(default)]
+    chain: String,
+    #[serde(default)]
+    tx_id: String,
+    /// Withdrawal state; see [`OKX_WITHDRAWAL_STATE_SUCCESS`]
+    state: String,
+    /// Time the withdrawal was requested (Unix ms), also the pagination cursor
+    ts: String,
+}
+
+/// `state` of a credited deposit
+const OKX_DEPOSIT_STATE_SUCCESS: &str = "2";
+/// `state` of a completed withdrawal
+const OKX_WITHDRAWAL_STATE_SUCCESS: &str = "2";
+
+/// `Some` for a non-empty string field
+fn non_empty(value: String) -> Option<String> {
+    (!value.is_empty()).then_some(value)
+}
+
+impl OkxDeposit {
+    /// Convert a credited deposit to a [`Transfer`]; pending or failed deposits and rows
+    /// without a valid timestamp are skipped
+    fn into_transfer(self) -> Option<Transfer> {
+        if self.state != OKX_DEPOSIT_STATE_SUCCESS {
+            return None;
+        }
+        Some(Transfer {
+            transfer_id: format!("dep:{}", self.dep_id),
+            direction: TRANSFER_DEPOSIT.to_string(),
+            asset: self.ccy,
+            amount: self.amt,
+            fee: None,
+            network: non_empty(self.chain),
+            tx_hash: non_empty(self.tx_id),
+            occurred_at: self.ts.parse::<i64>().ok().and_then(DateTime::from_timestamp_millis)?,
+        })
+    }
+}
+
+impl OkxWithdrawal {
+    /// Convert a completed withdrawal to a [`Transfer`]; pending or cancelled withdrawals and
+    /// rows without a valid timestamp are skipped
+    fn into_transfer(self) -> Option<Transfer> {
+        if self.state != OKX_WITHDRAWAL_STATE_SUCCESS {
+            return None;
+        }
+        Some(Transfer {
+            transfer_id: format!("wd:{}", self.wd_id),
+            direction: TRANSFER_WITHDRAWAL.to_string(),
+            asset: self.ccy,
+            amount: self.amt,
+            fee: non_empty(self.fee),
+            network: non_empty(self.chain),
+            tx_hash: non_empty(self.tx_id),
+            occurred_at: self.ts.parse::<i64>().ok().and_then(DateTime::from_timestamp_millis)?,
+        })
+    }
+}
+
+/// Instrument types whose fills are synced as trades
+const OKX_TRADE_INST_TYPES: [&str; 4] = ["SPOT", "MARGIN", "SWAP", "FUTURES"];
+
+impl OkxFill {
+    /// Convert to a [`Trade`], returning `None` for rows without a valid timestamp
+    fn into_trade(self) -> Option<Trade> {
+        let executed_at = self.ts.parse::<i64>().ok().and_then(DateTime::from_timestamp_millis)?;
+        let non_empty = |v: String| (!v.is_empty()).then_some(v);
+
+        // Instrument IDs start with "BASE-QUOTE" (e.g. "BTC-USDT", "BTC-USDT-SWAP", "ETH-USD-240628")
+        let mut parts = self.inst_id.split('-');
+        let base_asset = parts.next().filter(|p| !p.is_empty()).map(str::to_string);
+        let quote_asset = parts.next().map(str::to_string);
+
+        // OKX reports charged fees as negative amounts; trades store the fee paid
+        let fee = Decimal::from_str(&self.fee).ok().map(|fee| (-fee).normalize().to_string());
+
+        Some(Trade {
+            // OKX trade IDs are only unique per instrument
+            trade_id: format!("{}:{}", self.inst_id, self.trade_id),
+            order_id: non_empty(self.ord_id),
+            instrument: self.inst_id,
+            instrument_type: self.inst_type,
+            base_asset,
+            quote_asset,
+            side: self.side,
+            quantity: self.fill_sz,
+            price: self.fill_px,
+            fee,
+            fee_currency: non_empty(self.fee_ccy),
+            executed_at,
+        })
+    }
+}
+
+/// Instrument types synced as positions (spot margin is already reflected in balances)
+const OKX_POSITION_INST_TYPES: [&str; 2] = ["SWAP", "FUTURES"];
+
+impl OkxPositionData {
+    /// Convert to a [`Position`], returning `None` for closed (zero-size) or unsupported positions
+    fn into_position(self) -> Option<Position> {
+        if !OKX_POSITION_INST_TYPES.contains(&self.inst_type.as_str()) {
+            return None;
+        }
+
+        let pos = Decimal::from_str(&self.pos).ok()?;
+        if pos.is_zero() {
+            return None;
+        }
+
+        let side = match self.pos_side.as_str() {
+            "long" | "short" => self.pos_side.clone(),
+            _ if pos.is_sign_negative() => "short".to_string(),
+            _ => "long".to_string(),
+        };
+
+        let non_empty = |v: String| (!v.is_empty()).then_some(v);
+        let margin = if self.mgn_mode == "isolated" { self.margin } else { self.imr };
+
+        Some(Position {
+            instrument: self.inst_id,
+            instrument_type: self.inst_type,
+            side,
+            size: pos.abs().normalize().to_string(),
+            entry_price: non_empty(self.avg_px),
+            mark_price: non_empty(self.mark_px),
+            unrealized_pnl: non_empty(self.upl),
+            margin: non_empty(margin),
+            margin_currency: non_empty(self.ccy),
+            margin_mode: non_empty(self.mgn_mode),
+            leverage: non_empty(self.lever),
+            liquidation_price: non_empty(self.liq_px),
+        })
+    }
+}
+
 /// OKX connector for read-only access
 pub struct OkxConnector {
     api_key: String,
@@ -66,11 +347,13 @@ impl OkxConnector {
         general_purpose::STANDARD.encode(result.into_bytes())
     }
 
-    /// Make an authenticated GET request to OKX API
-    async fn get_request<T: for<'de> Deserialize<'de>>(
+    /// Make an authenticated GET request to OKX API and return the validated `data` rows.
+    ///
+    /// OKX error codes (also sent with non-2xx responses) are mapped to [`ConnectorError`].
+    async fn get_data<T: for<'de> Deserialize<'de>>(
         &self,
         endpoint: &str,
-    ) -> Result<T, Box<dyn Error + Send + Sync>> {
+    ) -> Result<Vec<T>, Box<dyn Error + Send + Sync>> {
         let timestamp = Utc::now().format("%Y-%m-%dT%H:%M:%S%.3fZ").to_string();
         let method = "GET";
         let request_path = endpoint;
@@ -98,58 +381,346 @@ impl OkxConnector {
         tracing::debug!("OKX API Response Status: {}", status);
         tracing::debug!("OKX API Response Body: {}", body);
 
-        if !status.is_success() {
-            return Err(format!("OKX API error: {} - {}", status, body).into());
+        if status == reqwest::StatusCode::TOO_MANY_REQUESTS {
+            return Err(okx_error("50011", &body).into());
+        }
+
+        let envelope: OkxResponse<serde_json::Value> = match serde_json::from_str(&body) {
+            Ok(envelope) => envelope,
+            Err(_) if !status.is_success() => {
+                return Err(ConnectorError::Api {
+                    exchange: "OKX".to_string(),
+                    code: status.as_u16().to_string(),
+                    message: body,
+                }
+                .into());
+            }
+            Err(e) => {
+                tracing::error!("Failed to parse OKX response: {}", e);
+                return Err(invalid_response(endpoint, e).into());
+            }
+        };
+
+        if envelope.code != "0" {
+            return Err(okx_error(&envelope.code, &envelope.msg).into());
         }
 
-        serde_json::from_str(&body).map_err(|e| {
-            tracing::error!("Failed to parse OKX response: {}", e);
-            format!("Failed to parse OKX response: {}", e).into()
+        Ok(parse_rows(endpoint, envelope.data)?)
+    }
+
+    /// Fetch every page of a list endpoint that paginates with `after` and `limit`.
+    ///
+    /// `cursor_of` returns the `after` value continuing from a row (e.g. its timestamp).
+    async fn get_all_pages<T, F>(&self, endpoint: &str, cursor_of: F) -> Result<Vec<T>, Box<dyn Error + Send + Sync>>
+    where
+        T: for<'de> Deserialize<'de>,
+        F: Fn(&T) -> String,
+    {
+        let separator = if endpoint.contains('?') { '&' } else { '?' };
+        let mut rows = Vec::new();
+        let mut after: Option<String> = None;
+
+        for _ in 0..OKX_MAX_PAGES {
+            let mut page_endpoint = format!("{}{}limit={}", endpoint, separator, OKX_PAGE_LIMIT);
+            if let Some(cursor) = &after {
+                page_endpoint.push_str(&format!("&after={}", cursor));
+            }
+
+            let page: Vec<T> = self.get_data(&page_endpoint).await?;
+            let next = next_page_cursor(&page, &cursor_of).filter(|next| after.as_ref() != Some(next));
+            rows.extend(page);
+
+            match next {
+                Some(cursor) => after = Some(cursor),
+                None => return Ok(rows),
+            }
+        }
+
+        tracing::warn!("Stopped paging OKX {} after {} pages", endpoint, OKX_MAX_PAGES);
+        Ok(rows)
+    }
+
+    /// Fetch an optional endpoint, returning no rows (with a warning) on failure.
+    ///
+    /// Earn and funding endpoints need extra key permissions; a missing permission must
+    /// not fail the whole balance sync.
+    async fn get_optional_data<T: for<'de> Deserialize<'de>>(&self, endpoint: &str) -> Vec<T> {
+        match self.get_data(endpoint).await {
+            Ok(data) => data,
+            Err(e) => {
+                tracing::warn!("Failed to fetch OKX {}: {}", endpoint, e);
+                Vec::new()
+            }
+        }
+    }
+}
+
+/// Map an OKX error code to a typed connector error
+fn okx_error(code: &str, msg: &str) -> ConnectorError {
+    let exchange = "OKX".to_string();
+    let message = msg.to_string();
+
+    if OKX_RATE_LIMIT_CODES.contains(&code) {
+        ConnectorError::RateLimited { exchange, message }
+    } else if OKX_INVALID_CREDENTIAL_CODES.contains(&code) {
+        ConnectorError::InvalidCredentials { exchange, message }
+    } else if OKX_PERMISSION_CODES.contains(&code) {
+        ConnectorError::PermissionDenied { exchange, message }
+    } else {
+        ConnectorError::Api { exchange, code: code.to_string(), message }
+    }
+}
+
+fn invalid_response(endpoint: &str, error: impl std::fmt::Display) -> ConnectorError {
+    ConnectorError::InvalidResponse {
+        exchange: "OKX".to_string(),
+        message: format!("{}: {}", endpoint, error),
+    }
+}
+
+/// Deserialize `data` rows, failing on the first row that does not match the schema
+fn parse_rows<T: for<'de> Deserialize<'de>>(
+    endpoint: &str,
+    data: Vec<serde_json::Value>,
+) -> Result<Vec<T>, ConnectorError> {
+    data.into_iter()
+        .map(serde_json::from_value)
+        .collect::<Result<Vec<T>, _>>()
+        .map_err(|e| invalid_response(endpoint, e))
+}
+
+/// Cursor for the next page, or `None` when `page` is the last (short) page
+fn next_page_cursor<T>(page: &[T], cursor_of: impl Fn(&T) -> String) -> Option<String> {
+    if page.len() < OKX_PAGE_LIMIT {
+        return None;
+    }
+    page.last().map(cursor_of).filter(|cursor| !cursor.is_empty())
+}
+
+/// Extract the per-currency `details` rows of a trading account balance response.
+///
+/// Fails when an account object has no `details` array or a row lacks a balance field,
+/// rather than silently under-reporting the account.
+fn trading_details(data: Vec<serde_json::Value>) -> Result<Vec<OkxBalanceData>, ConnectorError> {
+    let mut rows = Vec::new();
+    for item in data {
+        let details = item
+            .get("details")
+            .cloned()
+            .ok_or_else(|| invalid_response("/api/v5/account/balance", "missing details"))?;
+        rows.extend(parse_rows::<OkxBalanceData>(
+            "/api/v5/account/balance",
+            serde_json::from_value(details).map_err(|e| invalid_response("/api/v5/account/balance", e))?,
+        )?);
+    }
+    Ok(rows)
+}
+
+/// Sum balance rows from several accounts (trading, funding, sub-accounts) per currency.
+///
+/// Zero balances are dropped; results are sorted by currency.
+fn aggregate_balances(rows: Vec<OkxBalanceData>) -> Vec<Balance> {
+    let parse = |v: &str| Decimal::from_str(v).unwrap_or(Decimal::ZERO);
+    let mut totals: BTreeMap<String, (Decimal, Decimal, Decimal)> = BTreeMap::new();
+
+    for row in rows {
+        let entry = totals
+            .entry(row.ccy.to_uppercase())
+            .or_insert((Decimal::ZERO, Decimal::ZERO, Decimal::ZERO));
+        entry.0 += parse(&row.bal);
+        entry.1 += parse(&row.avail_bal);
+        entry.2 += parse(&row.frozen_bal);
+    }
+
+    totals
+        .into_iter()
+        .filter(|(_, (bal, _, _))| *bal > Decimal::ZERO)
+        .map(|(ccy, (bal, avail, frozen))| Balance {
+            asset: ccy,
+            quantity: bal.normalize().to_string(),
+            available: avail.normalize().to_string(),
+            frozen: frozen.normalize().to_string(),
+            decimals: None, // OKX doesn't provide decimal information
+            holding_source: None,
         })
+        .collect()
+}
+
+/// Merge savings and staking amounts into one `earn` balance per currency.
+///
+/// Earn funds cannot be traded until redeemed, so they are reported as frozen.
+/// Zero amounts are dropped; results are sorted by currency.
+fn build_earn_balances(savings: Vec<OkxSavingsBalance>, staking: Vec<OkxStakingOrder>) -> Vec<Balance> {
+    let mut totals: BTreeMap<String, Decimal> = BTreeMap::new();
+
+    let invested = staking
+        .into_iter()
+        .flat_map(|order| order.invest_data)
+        .map(|d| (d.ccy, d.amt));
+    for (ccy, amt) in savings.into_iter().map(|s| (s.ccy, s.amt)).chain(invested) {
+        let amount = Decimal::from_str(&amt).unwrap_or(Decimal::ZERO);
+        *totals.entry(ccy.to_uppercase()).or_insert(Decimal::ZERO) += amount;
     }
+
+    totals
+        .into_iter()
+        .filter(|(_, amount)| *amount > Decimal::ZERO)
+        .map(|(ccy, amount)| {
+            let amount = amount.normalize().to_string();
+            Balance {
+                asset: ccy,
+                quantity: amount.clone(),
+                available: "0".to_string(),
+                frozen: amount,
+                decimals: None, // OKX doesn't provide decimal information
+                holding_source: Some(HOLDING_SOURCE_EARN.to_string()),
+            }
+        })
+        .collect()
 }
 
 #[async_trait]
 impl ExchangeConnector for OkxConnector {
     async fn fetch_spot_balances(&self) -> Result<Vec<Balance>, Box<dyn Error + Send + Sync>> {
         // OKX API endpoint for account balance
-        // Using trading account (balance details)
+        // Using trading account (balance details); all currencies come in one response
         let endpoint = "/api/v5/account/balance";
         
-        let response: OkxResponse<serde_json::Value> = self.get_request(endpoint).await?;
-        
-        if response.code != "0" {
-            return Err(format!("OKX API error: {} - {}", response.code, response.msg).into());
-        }
+        let data: Vec<serde_json::Value> = self.get_data(endpoint).await?;
 
-        let mut balances = Vec::new();
-
-        // Parse the balance data
-        // OKX returns balance in a nested structure
-        for item in response.data {
-            if let Some(details) = item.get("details").and_then(|d| d.as_array()) {
-                for detail in details {
-                    if let Ok(balance_data) = serde_json::from_value::<OkxBalanceData>(detail.clone()) {
-                        // Only include assets with non-zero balance
-                        if let Ok(bal) = balance_data.bal.parse::<f64>() {
-                            if bal > 0.0 {
-                                balances.push(Balance {
-                                    asset: balance_data.ccy,
-                                    quantity: balance_data.bal,
-                                    available: balance_data.avail_bal,
-                                    frozen: balance_data.frozen_bal,
-                                    decimals: None, // OKX doesn't provide decimal information
-                                });
-                            }
-                        }
-                    }
-                }
-            }
-        }
+        let mut rows = trading_details(data)?;
+
+        // Funding account balances (deposits land here before being moved to trading)
+        rows.extend(self.get_optional_data::<OkxBalanceData>("/api/v5/asset/balances").await);
+
+        // Trading and funding balances are reported as one spot holding per asset
+        let mut balances = aggregate_balances(rows);
+
+        // Simple Earn and on-chain staking, reported as separate "earn" holdings
+        let savings: Vec<OkxSavingsBalance> = self.get_optional_data("/api/v5/finance/savings/balance").await;
+        let staking: Vec<OkxStakingOrder> = self
+            .get_optional_data("/api/v5/finance/staking-defi/orders-active")
+            .await;
+        balances.extend(build_earn_balances(savings, staking));
 
         tracing::info!("Fetched {} balances from OKX", balances.len());
         Ok(balances)
     }
+
+    async fn fetch_positions(&self) -> Result<Vec<Position>, Box<dyn Error + Send + Sync>> {
+        let endpoint = "/api/v5/account/positions";
+
+        let rows: Vec<OkxPositionData> = self.get_data(endpoint).await?;
+
+        let positions: Vec<Position> = rows
+            .into_iter()
+            .filter_map(OkxPositionData::into_position)
+            .collect();
+
+        tracing::info!("Fetched {} open positions from OKX", positions.len());
+        Ok(positions)
+    }
+
+    async fn fetch_trades(&self, since: Option<DateTime<Utc>>) -> Result<Vec<Trade>, Box<dyn Error + Send + Sync>> {
+        // Fills of the last three months, newest first, paginated by bill ID
+        let mut trades = Vec::new();
+        for inst_type in OKX_TRADE_INST_TYPES {
+            let mut endpoint = format!("/api/v5/trade/fills-history?instType={}", inst_type);
+            if let Some(since) = since {
+                endpoint.push_str(&format!("&begin={}", since.timestamp_millis()));
+            }
+
+            let fills: Vec<OkxFill> = self.get_all_pages(&endpoint, |fill: &OkxFill| fill.bill_id.clone()).await?;
+            trades.extend(fills.into_iter().filter_map(OkxFill::into_trade));
+        }
+
+        tracing::info!("Fetched {} trades from OKX", trades.len());
+        Ok(trades)
+    }
+
+    async fn fetch_transfers(&self, since: Option<DateTime<Utc>>) -> Result<Vec<Transfer>, Box<dyn Error + Send + Sync>> {
+        // Both histories are newest first and paginated by timestamp; `before` bounds the window
+        let window = since
+            .map(|since| format!("?before={}", since.timestamp_millis() - 1))
+            .unwrap_or_default();
+
+        let deposits: Vec<OkxDeposit> = self
+            .get_all_pages(&format!("/api/v5/asset/deposit-history{}", window), |d: &OkxDeposit| d.ts.clone())
+            .await?;
+        let withdrawals: Vec<OkxWithdrawal> = self
+            .get_all_pages(&format!("/api/v5/asset/withdrawal-history{}", window), |w: &OkxWithdrawal| {
+                w.ts.clone()
+            })
+            .await?;
+
+        let mut transfers: Vec<Transfer> = deposits.into_iter().filter_map(OkxDeposit::into_transfer).collect();
+        transfers.extend(withdrawals.into_iter().filter_map(OkxWithdrawal::into_transfer));
+
+        tracing::info!("Fetched {} transfers from OKX", transfers.len());
+        Ok(transfers)
+    }
+
+    fn supports_sub_accounts(&self) -> bool {
+        true
+    }
+
+    async fn discover_sub_accounts(&self) -> Result<Vec<SubAccount>, Box<dyn Error + Send + Sync>> {
+        // The sub-account list is paginated (100 per page), newest first
+        let sub_accounts: Vec<OkxSubAccount> = self
+            .get_all_pages("/api/v5/users/subaccount/list", |sub: &OkxSubAccount| sub.ts.clone())
+            .await?;
+
+        Ok(sub_accounts
+            .into_iter()
+            .map(|sub| SubAccount {
+                id: sub.sub_acct,
+                label: (!sub.label.is_empty()).then_some(sub.label),
+            })
+            .collect())
+    }
+
+    async fn fetch_sub_account_balances(
+        &self,
+        sub_account: &SubAccount,
+    ) -> Result<Vec<Balance>, Box<dyn Error + Send + Sync>> {
+        let trading: Vec<serde_json::Value> = self
+            .get_data(&format!("/api/v5/account/subaccount/balances?subAcct={}", sub_account.id))
+            .await?;
+        let mut rows = trading_details(trading)?;
+
+        // Funding balances need an extra permission; skip them rather than fail the sub-account
+        rows.extend(
+            self.get_optional_data::<OkxBalanceData>(&format!(
+                "/api/v5/asset/subaccount/balances?subAcct={}",
+                sub_account.id
+            ))
+            .await,
+        );
+
+        Ok(aggregate_balances(rows))
+    }
+}
+
+/// Builds [`OkxConnector`]s for OKX accounts
+pub struct OkxFactory;
+
+#[async_trait]
+impl ConnectorFactory for OkxFactory {
+    fn account_type(&self) -> &'static str {
+        ACCOUNT_TYPE_EXCHANGE
+    }
+
+    fn name(&self) -> &'static str {
+        "okx"
+    }
+
+    async fn create(
+        &self,
+        ctx: &ConnectorContext<'_>,
+    ) -> Result<Box<dyn ExchangeConnector>, Box<dyn Error + Send + Sync>> {
+        let (api_key, api_secret) = ctx.api_credentials()?;
+        Ok(Box::new(OkxConnector::new(api_key, api_secret, ctx.passphrase()?)))
+    }
 }
 
 #[cfg(test)]
@@ -174,4 +745,185 @@ mod tests {
         assert!(!signature.is_empty());
         assert!(general_purpose::STANDARD.decode(&signature).is_ok());
     }
+
+    #[test]
+    fn test_earn_balances_merge_savings_and_staking() {
+        let savings: Vec<OkxSavingsBalance> = serde_json::from_value(serde_json::json!([
+            { "ccy": "USDT", "amt": "1000.5", "earnings": "2" },
+            { "ccy": "BTC", "amt": "0" }
+        ]))
+        .unwrap();
+        let staking: Vec<OkxStakingOrder> = serde_json::from_value(serde_json::json!([
+            { "ordId": "1", "ccy": "ETH", "investData": [{ "ccy": "ETH", "amt": "2" }] },
+            { "ordId": "2", "ccy": "USDT", "investData": [{ "ccy": "USDT", "amt": "500" }] }
+        ]))
+        .unwrap();
+
+        let balances = build_earn_balances(savings, staking);
+
+        assert_eq!(balances.len(), 2);
+        assert_eq!(balances[0].asset, "ETH");
+        assert_eq!(balances[1].asset, "USDT");
+        assert_eq!(balances[1].quantity, "1500.5");
+        assert_eq!(balances[1].available, "0");
+        assert_eq!(balances[1].holding_source.as_deref(), Some(HOLDING_SOURCE_EARN));
+    }
+
+    #[test]
+    fn test_trading_and_funding_balances_are_aggregated() {
+        let trading = trading_details(vec![serde_json::json!({
+            "totalEq": "123",
+            "details": [
+                { "ccy": "BTC", "bal": "0.5", "availBal": "0.4", "frozenBal": "0.1", "eq": "30000" },
+                { "ccy": "DOGE", "bal": "0", "availBal": "0", "frozenBal": "0" }
+            ]
+        })])
+        .unwrap();
+        let funding: Vec<OkxBalanceData> = serde_json::from_value(serde_json::json!([
+            { "ccy": "BTC", "bal": "0.25", "availBal": "0.25", "frozenBal": "0" },
+            { "ccy": "usdt", "bal": "100", "availBal": "100", "frozenBal": "0" }
+        ]))
+        .unwrap();
+
+        let balances = aggregate_balances(trading.into_iter().chain(funding).collect());
+
+        assert_eq!(balances.len(), 2);
+        assert_eq!(balances[0].asset, "BTC");
+        assert_eq!(balances[0].quantity, "0.75");
+        assert_eq!(balances[0].available, "0.65");
+        assert_eq!(balances[0].frozen, "0.1");
+        assert_eq!(balances[1].asset, "USDT");
+        assert!(balances[1].holding_source.is_none());
+    }
+
+    #[test]
+    fn test_error_codes_map_to_typed_errors() {
+        assert_eq!(okx_error("50011", "Rate limit reached").kind(), "rate_limited");
+        assert_eq!(okx_error("50111", "Invalid OK-ACCESS-KEY").kind(), "invalid_credentials");
+        assert_eq!(okx_error("50113", "Invalid Sign").kind(), "invalid_credentials");
+        assert_eq!(okx_error("50110", "IP not whitelisted").kind(), "permission_denied");
+        assert_eq!(
+            okx_error("51000", "Parameter error"),
+            ConnectorError::Api {
+                exchange: "OKX".to_string(),
+                code: "51000".to_string(),
+                message: "Parameter error".to_string(),
+            }
+        );
+    }
+
+    #[test]
+    fn test_malformed_balance_rows_are_rejected() {
+        assert!(trading_details(vec![serde_json::json!({ "totalEq": "1" })]).is_err());
+        assert!(trading_details(vec![serde_json::json!({
+            "details": [{ "ccy": "BTC", "availBal": "1", "frozenBal": "0" }]
+        })])
+        .is_err());
+    }
+
+    #[test]
+    fn test_next_page_cursor_stops_on_short_page() {
+        let full: Vec<OkxSubAccount> = (0..OKX_PAGE_LIMIT)
+            .map(|i| OkxSubAccount {
+                sub_acct: format!("sub{}", i),
+                label: String::new(),
+                ts: (1_700_000_000_000u64 - i as u64).to_string(),
+            })
+            .collect();
+        assert_eq!(
+            next_page_cursor(&full, |s| s.ts.clone()).as_deref(),
+            Some("1699999999901")
+        );
+        assert_eq!(next_page_cursor(&full[..10], |s| s.ts.clone()), None);
+    }
+
+    #[test]
+    fn test_fill_parsing() {
+        let fills: Vec<OkxFill> = serde_json::from_value(serde_json::json!([
+            {
+                "instType": "SPOT", "instId": "BTC-USDT", "tradeId": "123", "ordId": "987", "billId": "555",
+                "side": "buy", "fillSz": "0.01", "fillPx": "65000", "fee": "-0.00001", "feeCcy": "BTC",
+                "ts": "1704067200000"
+            },
+            {
+                "instType": "SWAP", "instId": "ETH-USDT-SWAP", "tradeId": "7", "ordId": "", "billId": "556",
+                "side": "sell", "fillSz": "3", "fillPx": "3500", "fee": "0.2", "feeCcy": "USDT", "ts": ""
+            }
+        ]))
+        .unwrap();
+
+        let trades: Vec<Trade> = fills.into_iter().filter_map(OkxFill::into_trade).collect();
+        assert_eq!(trades.len(), 1);
+        assert_eq!(trades[0].trade_id, "BTC-USDT:123");
+        assert_eq!(trades[0].order_id.as_deref(), Some("987"));
+        assert_eq!(trades[0].base_asset.as_deref(), Some("BTC"));
+        assert_eq!(trades[0].quote_asset.as_deref(), Some("USDT"));
+        assert_eq!(trades[0].fee.as_deref(), Some("0.00001"));
+        assert_eq!(trades[0].executed_at.to_rfc3339(), "2024-01-01T00:00:00+00:00");
+    }
+
+    #[test]
+    fn test_transfer_parsing() {
+        let deposits: Vec<OkxDeposit> = serde_json::from_value(serde_json::json!([
+            {
+                "depId": "111", "ccy": "USDT", "amt": "500", "chain": "USDT-TRC20", "txId": "0xabc",
+                "state": "2", "ts": "1704067200000"
+            },
+            { "depId": "112", "ccy": "BTC", "amt": "0.1", "state": "0", "ts": "1704067300000" }
+        ]))
+        .unwrap();
+        let withdrawals: Vec<OkxWithdrawal> = serde_json::from_value(serde_json::json!([
+            {
+                "wdId": "222", "ccy": "ETH", "amt": "1.5", "fee": "0.001", "chain": "ETH-ERC20",
+                "txId": "", "state": "2", "ts": "1704067400000"
+            }
+        ]))
+        .unwrap();
+
+        let transfers: Vec<Transfer> = deposits
+            .into_iter()
+            .filter_map(OkxDeposit::into_transfer)
+            .chain(withdrawals.into_iter().filter_map(OkxWithdrawal::into_transfer))
+            .collect();
+        assert_eq!(transfers.len(), 2);
+        assert_eq!(transfers[0].transfer_id, "dep:111");
+        assert_eq!(transfers[0].direction, TRANSFER_DEPOSIT);
+        assert_eq!(transfers[0].network.as_deref(), Some("USDT-TRC20"));
+        assert_eq!(transfers[0].fee, None);
+        assert_eq!(transfers[1].transfer_id, "wd:222");
+        assert_eq!(transfers[1].direction, TRANSFER_WITHDRAWAL);
+        assert_eq!(transfers[1].fee.as_deref(), Some("0.001"));
+        assert_eq!(transfers[1].tx_hash, None);
+    }
+
+    #[test]
+    fn test_position_parsing() {
+        let rows: Vec<OkxPositionData> = serde_json::from_value(serde_json::json!([
+            {
+                "instId": "BTC-USDT-SWAP", "instType": "SWAP", "mgnMode": "cross", "posSide": "net",
+                "pos": "-2.5", "avgPx": "65000", "markPx": "64000", "upl": "25", "margin": "",
+                "imr": "160", "ccy": "USDT", "lever": "10", "liqPx": ""
+            },
+            {
+                "instId": "ETH-USD-240628", "instType": "FUTURES", "mgnMode": "isolated", "posSide": "long",
+                "pos": "3", "avgPx": "3500", "markPx": "3600", "upl": "0.02", "margin": "0.1",
+                "imr": "", "ccy": "ETH", "lever": "5", "liqPx": "2900"
+            },
+            { "instId": "SOL-USDT-SWAP", "instType": "SWAP", "posSide": "net", "pos": "0" },
+            { "instId": "BTC-USDT", "instType": "MARGIN", "posSide": "net", "pos": "1" }
+        ]))
+        .unwrap();
+
+        let positions: Vec<Position> = rows.into_iter().filter_map(OkxPositionData::into_position).collect();
+        assert_eq!(positions.len(), 2);
+
+        assert_eq!(positions[0].side, "short");
+        assert_eq!(positions[0].size, "2.5");
+        assert_eq!(positions[0].margin.as_deref(), Some("160"));
+        assert!(positions[0].liquidation_price.is_none());
+
+        assert_eq!(positions[1].side, "long");
+        assert_eq!(positions[1].margin.as_deref(), Some("0.1"));
+        assert_eq!(positions[1].margin_currency.as_deref(), Some("ETH"));
+    }
 }
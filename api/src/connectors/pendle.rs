@@ -0,0 +1,252 @@
+//! Pendle principal (PT) and yield (YT) token holdings of EVM wallets.
+//!
+//! PT and YT tokens are listed per chain by the Pendle API together with their maturity and a
+//! USD price; the list is cached in the `pendle_assets` table (see
+//! [`crate::helpers::pendle_assets`]). Wallet balances of the listed tokens are read in one
+//! Multicall3 batch per chain and reported as [`HOLDING_SOURCE_FIXED_YIELD`] holdings under the
+//! Pendle symbol (e.g. "PT-sUSDE-27MAR2025-ethereum"). Allocation construction values them at
+//! the cached Pendle price and tags them with their maturity date.
+
+use super::defi::{DefiAdapter, DefiContext, DefiPositions};
+use super::evm::{etherscan_chain_id, EvmChain};
+use super::Balance;
+use crate::domain::HOLDING_SOURCE_FIXED_YIELD;
+use crate::helpers::balance_normalization::normalize_token_balance;
+use crate::helpers::pendle_assets::load_pendle_assets;
+use alloy::{
+    primitives::Address,
+    providers::{Provider, ProviderBuilder},
+    sol,
+};
+use async_trait::async_trait;
+use chrono::{DateTime, Utc};
+use reqwest::Client;
+use rust_decimal::Decimal;
+use sea_orm::DatabaseConnection;
+use serde::Deserialize;
+use std::collections::{HashMap, HashSet};
+use std::error::Error;
+
+/// Protocol name used in the `defi_protocols` account setting
+pub const DEFI_PROTOCOL_PENDLE: &str = "pendle";
+
+/// Pendle backend API
+pub const PENDLE_API_URL: &str = "https://api-v2.pendle.finance/core";
+
+/// Token types read from the Pendle asset list
+pub const PENDLE_TOKEN_TYPES: &[&str] = &["PT", "YT"];
+
+/// Chain IDs with Pendle markets
+const PENDLE_CHAIN_IDS: &[u64] = &[1, 10, 56, 8453, 42161];
+
+/// Balance reads per Multicall3 batch
+const MULTICALL_BATCH_SIZE: usize = 200;
+
+sol! {
+    #[sol(rpc)]
+    contract PendleToken {
+        function balanceOf(address owner) external view returns (uint256);
+    }
+}
+
+/// Numeric chain ID of a chain from the `evm_chains` table when Pendle is deployed on it
+pub fn pendle_chain_id(chain_name: &str) -> Option<u64> {
+    etherscan_chain_id(chain_name).filter(|id| PENDLE_CHAIN_IDS.contains(id))
+}
+
+/// [`DefiAdapter`] for Pendle PT/YT tokens
+pub struct PendleAdapter;
+
+#[async_trait]
+impl DefiAdapter for PendleAdapter {
+    fn protocol(&self) -> &'static str {
+        DEFI_PROTOCOL_PENDLE
+    }
+
+    fn supports_chain(&self, chain: &str) -> bool {
+        pendle_chain_id(chain).is_some()
+    }
+
+    fn aggregator_ids(&self) -> &'static [&'static str] {
+        &["pendle", "pendle2"]
+    }
+
+    async fn position_tokens(&self, db: &DatabaseConnection, chain: &EvmChain) -> HashSet<String> {
+        load_pendle_assets(db, chain).await.into_iter().map(|asset| asset.address).collect()
+    }
+
+    async fn fetch_positions(
+        &self,
+        ctx: &DefiContext<'_>,
+        wallet: Address,
+    ) -> Result<DefiPositions, Box<dyn Error + Send + Sync>> {
+        let assets = load_pendle_assets(ctx.db, ctx.chain).await;
+        if assets.is_empty() {
+            return Ok(DefiPositions::default());
+        }
+        let balances = fetch_pendle_balances(ctx.chain, ctx.chain.rpc_url(), wallet, &assets).await?;
+        Ok(DefiPositions { balances, positions: Vec::new() })
+    }
+}
+
+/// A PT or YT token from the Pendle asset list
+#[derive(Debug, Clone, PartialEq)]
+pub struct PendleAsset {
+    /// Lowercase token contract address
+    pub address: String,
+    pub symbol: String,
+    /// "PT" or "YT"
+    pub token_type: String,
+    pub decimals: u8,
+    pub expiry: DateTime<Utc>,
+    pub price_usd: Option<Decimal>,
+}
+
+#[derive(Debug, Deserialize)]
+struct AssetsResponse {
+    assets: Vec<ApiAsset>,
+}
+
+#[derive(Debug, Deserialize)]
+struct ApiAsset {
+    address: String,
+    symbol: String,
+    decimals: u8,
+    #[serde(default)]
+    tags: Vec<String>,
+    expiry: Option<String>,
+}
+
+#[derive(Debug, Deserialize)]
+struct PricesResponse {
+    prices: HashMap<String, Option<f64>>,
+}
+
+/// PT and YT tokens of the asset list with their prices (keyed by lowercase address).
+/// Tokens without a parseable expiry are skipped.
+fn pendle_assets(assets: Vec<ApiAsset>, prices: &HashMap<String, Option<f64>>) -> Vec<PendleAsset> {
+    assets
+        .into_iter()
+        .filter_map(|asset| {
+            let token_type = asset.tags.iter().find(|tag| PENDLE_TOKEN_TYPES.contains(&tag.as_str()))?.clone();
+            let expiry = DateTime::parse_from_rfc3339(asset.expiry.as_deref()?).ok()?.with_timezone(&Utc);
+            let address = asset.address.to_lowercase();
+            let price_usd = prices
+                .get(&address)
+                .copied()
+                .flatten()
+                .and_then(|price| Decimal::try_from(price).ok());
+            Some(PendleAsset { address, symbol: asset.symbol, token_type, decimals: asset.decimals, expiry, price_usd })
+        })
+        .collect()
+}
+
+/// PT and YT tokens listed by the Pendle API on a chain, with their current USD prices
+pub async fn fetch_pendle_assets(chain_id: u64) -> Result<Vec<PendleAsset>, Box<dyn Error + Send + Sync>> {
+    let client = Client::new();
+    let assets: AssetsResponse = client
+        .get(format!("{}/v3/{}/assets/all", PENDLE_API_URL, chain_id))
+        .send()
+        .await?
+        .error_for_status()?
+        .json()
+        .await?;
+    let prices: PricesResponse = client
+        .get(format!("{}/v1/{}/assets/prices", PENDLE_API_URL, chain_id))
+        .query(&[("type", PENDLE_TOKEN_TYPES.join(","))])
+        .send()
+        .await?
+        .error_for_status()?
+        .json()
+        .await?;
+    let prices = prices.prices.into_iter().map(|(address, price)| (address.to_lowercase(), price)).collect();
+    Ok(pendle_assets(assets.assets, &prices))
+}
+
+/// `wallet`'s non-zero balances of the Pendle `assets` on `chain`, read through Multicall3.
+///
+/// A token whose `balanceOf` reverts is skipped; a failed batch fails the call.
+pub async fn fetch_pendle_balances(
+    chain: &EvmChain,
+    rpc_url: &str,
+    wallet: Address,
+    assets: &[PendleAsset],
+) -> Result<Vec<Balance>, Box<dyn Error + Send + Sync>> {
+    let provider = ProviderBuilder::new().connect_http(rpc_url.parse()?);
+
+    let mut balances = Vec::new();
+    for batch in assets.chunks(MULTICALL_BATCH_SIZE) {
+        let mut tokens = Vec::with_capacity(batch.len());
+        for asset in batch {
+            tokens.push(PendleToken::new(asset.address.parse()?, &provider));
+        }
+        let results = provider
+            .multicall()
+            .dynamic::<PendleToken::balanceOfCall>()
+            .extend(tokens.iter().map(|token| token.balanceOf(wallet)))
+            .try_aggregate(false)
+            .await?;
+
+        for (asset, result) in batch.iter().zip(results) {
+            let Ok(raw) = result else {
+                continue;
+            };
+            if raw.is_zero() {
+                continue;
+            }
+            let quantity = normalize_token_balance(&raw.to_string(), asset.decimals)?;
+            balances.push(Balance {
+                asset: format!("{}-{}", asset.symbol, chain.name()),
+                quantity: quantity.clone(),
+                available: quantity,
+                frozen: "0".to_string(),
+                decimals: Some(asset.decimals),
+                holding_source: Some(HOLDING_SOURCE_FIXED_YIELD.to_string()),
+            });
+        }
+    }
+    Ok(balances)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_pendle_assets_from_api() {
+        let asset = |address: &str, symbol: &str, tags: &[&str], expiry: Option<&str>| ApiAsset {
+            address: address.to_string(),
+            symbol: symbol.to_string(),
+            decimals: 18,
+            tags: tags.iter().map(|t| t.to_string()).collect(),
+            expiry: expiry.map(str::to_string),
+        };
+        let prices = HashMap::from([
+            ("0xaa".to_string(), Some(0.97)),
+            ("0xbb".to_string(), None),
+        ]);
+        let assets = pendle_assets(
+            vec![
+                asset("0xAA", "PT-sUSDE-27MAR2025", &["PT"], Some("2025-03-27T00:00:00.000Z")),
+                asset("0xbb", "YT-sUSDE-27MAR2025", &["YT"], Some("2025-03-27T00:00:00.000Z")),
+                // SY and LP tokens are not PT/YT holdings
+                asset("0xcc", "SY-sUSDe", &["SY"], None),
+                asset("0xdd", "PENDLE-LPT", &["PENDLE_LP"], Some("2025-03-27T00:00:00.000Z")),
+                // Unparseable expiry
+                asset("0xee", "PT-broken", &["PT"], Some("soon")),
+            ],
+            &prices,
+        );
+
+        assert_eq!(assets.len(), 2);
+        assert_eq!(assets[0].address, "0xaa");
+        assert_eq!(assets[0].token_type, "PT");
+        assert_eq!(assets[0].price_usd, Some(Decimal::new(97, 2)));
+        assert_eq!(assets[0].expiry.date_naive().to_string(), "2025-03-27");
+        assert_eq!(assets[1].token_type, "YT");
+        assert_eq!(assets[1].price_usd, None);
+
+        assert_eq!(pendle_chain_id("arbitrum"), Some(42161));
+        assert_eq!(pendle_chain_id("polygon"), None);
+    }
+}
@@ -0,0 +1,114 @@
+//! Market data providers behind one interface, so the price source is chosen per deployment.
+//!
+//! The price jobs only talk to a [`PriceProvider`]. CoinPaprika is the default; setting
+//! `PRICE_PROVIDER=coingecko` switches them to CoinGecko. Each provider has its own coin ID
+//! column in the `assets` table (`coinpaprika_id`, `coingecko_id`), and stored prices carry the
+//! provider's name as their `source`.
+
+use super::coingecko::CoinGeckoConnector;
+use super::coinpaprika::CoinPaprikaConnector;
+use crate::entities::assets;
+use async_trait::async_trait;
+use chrono::{DateTime, Utc};
+use std::error::Error;
+
+/// Provider name of CoinPaprika, also its `asset_prices.source`
+pub const PRICE_PROVIDER_COINPAPRIKA: &str = "coinpaprika";
+
+/// Provider name of CoinGecko, also its `asset_prices.source`
+pub const PRICE_PROVIDER_COINGECKO: &str = "coingecko";
+
+/// Current market data of one coin, in USD
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct CoinQuote {
+    /// Provider coin ID (e.g. "btc-bitcoin" on CoinPaprika, "bitcoin" on CoinGecko)
+    pub provider_id: String,
+    pub symbol: String,
+    pub name: String,
+    /// Market cap rank; `None` when unranked
+    pub rank: Option<u32>,
+    pub price_usd: f64,
+    pub volume_24h_usd: Option<f64>,
+    pub market_cap_usd: Option<f64>,
+    pub percent_change_1h: Option<f64>,
+    pub percent_change_24h: Option<f64>,
+    pub percent_change_7d: Option<f64>,
+    pub percent_change_30d: Option<f64>,
+    pub circulating_supply: Option<f64>,
+    pub total_supply: Option<f64>,
+    pub max_supply: Option<f64>,
+    pub beta_value: Option<f64>,
+    pub ath_price: Option<f64>,
+    /// RFC 3339 time of the all-time high
+    pub ath_date: Option<String>,
+    pub percent_from_price_ath: Option<f64>,
+}
+
+/// One point of a coin's price history, in USD
+#[derive(Debug, Clone, PartialEq)]
+pub struct PricePoint {
+    pub timestamp: DateTime<Utc>,
+    pub price_usd: f64,
+    pub volume_24h_usd: Option<f64>,
+    pub market_cap_usd: Option<f64>,
+}
+
+/// Source of coin listings, current quotes and price history
+#[async_trait]
+pub trait PriceProvider: Send + Sync {
+    /// Provider name, stored as the `source` of its prices (e.g. [`PRICE_PROVIDER_COINPAPRIKA`])
+    fn name(&self) -> &'static str;
+
+    /// `assets` column holding this provider's coin IDs
+    fn id_column(&self) -> assets::Column;
+
+    /// This provider's coin ID of an asset, if it is mapped
+    fn coin_id<'a>(&self, asset: &'a assets::Model) -> Option<&'a str>;
+
+    /// Quotes of the top `limit` coins by market cap, or of every listed coin when `None`
+    async fn fetch_top(&self, limit: Option<usize>) -> Result<Vec<CoinQuote>, Box<dyn Error + Send + Sync>>;
+
+    /// Quotes of specific coins by provider ID; unknown IDs are left out
+    async fn fetch_by_ids(&self, ids: &[String]) -> Result<Vec<CoinQuote>, Box<dyn Error + Send + Sync>>;
+
+    /// Daily price history of a coin between `from` and `to`, oldest first
+    async fn fetch_history(
+        &self,
+        id: &str,
+        from: DateTime<Utc>,
+        to: DateTime<Utc>,
+    ) -> Result<Vec<PricePoint>, Box<dyn Error + Send + Sync>>;
+}
+
+/// Provider named by `name`; `None` for an unknown name
+pub fn price_provider(name: &str) -> Option<Box<dyn PriceProvider>> {
+    match name.trim().to_lowercase().as_str() {
+        PRICE_PROVIDER_COINPAPRIKA => Some(Box::new(CoinPaprikaConnector::new())),
+        PRICE_PROVIDER_COINGECKO => Some(Box::new(CoinGeckoConnector::new())),
+        _ => None,
+    }
+}
+
+/// Read the price provider from `PRICE_PROVIDER` (default: coinpaprika).
+///
+/// An unknown name is logged and falls back to CoinPaprika.
+pub fn price_provider_from_env() -> Box<dyn PriceProvider> {
+    let name = std::env::var("PRICE_PROVIDER").unwrap_or_else(|_| PRICE_PROVIDER_COINPAPRIKA.to_string());
+    price_provider(&name).unwrap_or_else(|| {
+        tracing::warn!("Unknown PRICE_PROVIDER '{}', using {}", name, PRICE_PROVIDER_COINPAPRIKA);
+        Box::new(CoinPaprikaConnector::new())
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_price_provider() {
+        assert_eq!(price_provider("coinpaprika").unwrap().name(), PRICE_PROVIDER_COINPAPRIKA);
+        assert_eq!(price_provider(" CoinGecko ").unwrap().name(), PRICE_PROVIDER_COINGECKO);
+        assert!(matches!(price_provider("coingecko").unwrap().id_column(), assets::Column::CoingeckoId));
+        assert!(price_provider("cmc").is_none());
+    }
+}
@@ -0,0 +1,216 @@
+//! Registry of connector factories keyed by account type and exchange/wallet name.
+//!
+//! Account sync looks up the factory for an account instead of matching on names, and
+//! account creation validates `account_type`/`exchange_name` against the same registry.
+//! Adding a connector means implementing [`ConnectorFactory`] next to it and registering
+//! the factory in [`ConnectorRegistry::builtin`].
+
+use super::ExchangeConnector;
+use crate::entities::accounts;
+use async_trait::async_trait;
+use sea_orm::DatabaseConnection;
+use std::error::Error;
+use std::sync::OnceLock;
+
+/// Account type of exchange accounts (API credentials, or a public address for Hyperliquid)
+pub const ACCOUNT_TYPE_EXCHANGE: &str = "exchange";
+/// Account type of self-custody wallets
+pub const ACCOUNT_TYPE_WALLET: &str = "wallet";
+/// Wallet kind used when a wallet account has no `exchange_name`
+pub const DEFAULT_WALLET_KIND: &str = "evm";
+/// Wallet kind of Safe multisig accounts, which use EVM addresses
+pub const WALLET_KIND_SAFE: &str = "safe";
+
+/// Address format (chain family) of a wallet kind, used for name resolution and address
+/// comparison; Safe multisigs share the EVM format
+pub fn address_family(wallet_kind: &str) -> &str {
+    if wallet_kind.eq_ignore_ascii_case(WALLET_KIND_SAFE) {
+        DEFAULT_WALLET_KIND
+    } else {
+        wallet_kind
+    }
+}
+
+/// Decrypt API credentials (placeholder - implement proper encryption/decryption)
+///
+/// SECURITY WARNING: This is a placeholder implementation that stores credentials in plain text.
+/// Before production deployment, this MUST be replaced with proper encryption using:
+/// - AWS KMS (Key Management Service)
+/// - HashiCorp Vault
+/// - Google Cloud KMS
+/// - Azure Key Vault
+///   or similar key management solution.
+fn decrypt_credential(encrypted: &str) -> Result<String, Box<dyn Error + Send + Sync>> {
+    // TODO: Implement proper decryption using a key management service
+    // For now, assuming credentials are stored as-is (not recommended for production)
+    Ok(encrypted.to_string())
+}
+
+/// What a factory gets to build a connector for one account
+pub struct ConnectorContext<'a> {
+    pub db: &'a DatabaseConnection,
+    pub account: &'a accounts::Model,
+}
+
+impl ConnectorContext<'_> {
+    /// The account's wallet address
+    pub fn wallet_address(&self) -> Result<String, Box<dyn Error + Send + Sync>> {
+        Ok(self.account.wallet_address.clone().ok_or("Wallet address not set")?)
+    }
+
+    /// The account's decrypted API key and secret
+    pub fn api_credentials(&self) -> Result<(String, String), Box<dyn Error + Send + Sync>> {
+        let api_key = self.account.api_key_encrypted.as_deref().ok_or("API key not set")?;
+        let api_secret = self.account.api_secret_encrypted.as_deref().ok_or("API secret not set")?;
+        Ok((decrypt_credential(api_key)?, decrypt_credential(api_secret)?))
+    }
+
+    /// The account's decrypted API passphrase (OKX, KuCoin)
+    pub fn passphrase(&self) -> Result<String, Box<dyn Error + Send + Sync>> {
+        let passphrase = self.account.passphrase_encrypted.as_deref().ok_or("Passphrase not set")?;
+        decrypt_credential(passphrase)
+    }
+}
+
+/// Builds the connector for one kind of account
+#[async_trait]
+pub trait ConnectorFactory: Send + Sync {
+    /// Account type handled, [`ACCOUNT_TYPE_EXCHANGE`] or [`ACCOUNT_TYPE_WALLET`]
+    fn account_type(&self) -> &'static str;
+
+    /// Lowercase exchange name (e.g. "okx") or wallet kind (e.g. "solana") stored in `exchange_name`
+    fn name(&self) -> &'static str;
+
+    /// Whether accounts are read by wallet address, so one must be set on creation
+    fn requires_wallet_address(&self) -> bool {
+        false
+    }
+
+    /// Check a wallet address before an account is created, returning a message for the user
+    fn validate_wallet_address(&self, _address: &str) -> Result<(), String> {
+        Ok(())
+    }
+
+    /// Build the connector for an account
+    async fn create(
+        &self,
+        ctx: &ConnectorContext<'_>,
+    ) -> Result<Box<dyn ExchangeConnector>, Box<dyn Error + Send + Sync>>;
+}
+
+/// Connector factories keyed by account type and name
+#[derive(Default)]
+pub struct ConnectorRegistry {
+    factories: Vec<Box<dyn ConnectorFactory>>,
+}
+
+impl ConnectorRegistry {
+    /// Create an empty registry
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Register a factory, replacing any factory already registered for the same type and name
+    pub fn register(mut self, factory: impl ConnectorFactory + 'static) -> Self {
+        self.factories
+            .retain(|f| f.account_type() != factory.account_type() || f.name() != factory.name());
+        self.factories.push(Box::new(factory));
+        self
+    }
+
+    /// Registry of all connectors shipped with the backend
+    pub fn builtin() -> &'static ConnectorRegistry {
+        static BUILTIN: OnceLock<ConnectorRegistry> = OnceLock::new();
+        BUILTIN.get_or_init(|| {
+            ConnectorRegistry::new()
+                .register(super::okx::OkxFactory)
+                .register(super::kucoin::KucoinFactory)
+                .register(super::mexc::MexcFactory)
+                .register(super::cryptocom::CryptocomFactory)
+                .register(super::bitfinex::BitfinexFactory)
+                .register(super::binance::BinanceFactory)
+                .register(super::deribit::DeribitFactory)
+                .register(super::hyperliquid::HyperliquidFactory)
+                .register(super::manual::ManualFactory)
+                .register(super::evm::EvmFactory)
+                .register(super::safe::SafeFactory)
+                .register(super::solana::SolanaFactory)
+                .register(super::utxo::UtxoFactory(&super::utxo::BITCOIN))
+                .register(super::utxo::UtxoFactory(&super::utxo::LITECOIN))
+                .register(super::utxo::UtxoFactory(&super::utxo::DOGECOIN))
+                .register(super::cosmos::CosmosFactory)
+        })
+    }
+
+    /// Find the factory for an account's type and `exchange_name` (case-insensitive).
+    ///
+    /// Wallets without an `exchange_name` are [`DEFAULT_WALLET_KIND`] wallets.
+    pub fn find(&self, account_type: &str, name: Option<&str>) -> Option<&dyn ConnectorFactory> {
+        let name = match name {
+            Some(name) => name,
+            None if account_type == ACCOUNT_TYPE_WALLET => DEFAULT_WALLET_KIND,
+            None => return None,
+        };
+        self.factories
+            .iter()
+            .find(|f| f.account_type() == account_type && f.name().eq_ignore_ascii_case(name))
+            .map(|f| f.as_ref())
+    }
+
+    /// Registered account types, in registration order
+    pub fn account_types(&self) -> Vec<&'static str> {
+        let mut types: Vec<&'static str> = Vec::new();
+        for factory in &self.factories {
+            if !types.contains(&factory.account_type()) {
+                types.push(factory.account_type());
+            }
+        }
+        types
+    }
+
+    /// Registered names for an account type, in registration order
+    pub fn names(&self, account_type: &str) -> Vec<&'static str> {
+        self.factories
+            .iter()
+            .filter(|f| f.account_type() == account_type)
+            .map(|f| f.name())
+            .collect()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_decrypt_credential() {
+        let encrypted = "test-credential";
+        let decrypted = decrypt_credential(encrypted).unwrap();
+        assert_eq!(decrypted, encrypted);
+    }
+
+    #[test]
+    fn test_builtin_registry_lookup() {
+        let registry = ConnectorRegistry::builtin();
+
+        assert_eq!(registry.account_types(), vec![ACCOUNT_TYPE_EXCHANGE, ACCOUNT_TYPE_WALLET]);
+        assert!(registry.names(ACCOUNT_TYPE_EXCHANGE).contains(&"binance"));
+        assert_eq!(registry.find(ACCOUNT_TYPE_EXCHANGE, Some("OKX")).unwrap().name(), "okx");
+        assert!(registry.find(ACCOUNT_TYPE_EXCHANGE, Some("hyperliquid")).unwrap().requires_wallet_address());
+        assert!(registry.find(ACCOUNT_TYPE_EXCHANGE, None).is_none());
+        assert!(registry.find(ACCOUNT_TYPE_EXCHANGE, Some("solana")).is_none());
+        assert_eq!(registry.find(ACCOUNT_TYPE_WALLET, None).unwrap().name(), DEFAULT_WALLET_KIND);
+        assert_eq!(registry.find(ACCOUNT_TYPE_WALLET, Some("solana")).unwrap().name(), "solana");
+
+        let bitcoin = registry.find(ACCOUNT_TYPE_WALLET, Some("Bitcoin")).unwrap();
+        assert!(bitcoin.validate_wallet_address("bc1qcr8te4kr609gcawutmrza0j4xv80jy8z306fyu").is_ok());
+        assert!(bitcoin.validate_wallet_address("0x742d35Cc6634C0532925a3b844Bc454e4438f44e").is_err());
+        assert!(registry.find(ACCOUNT_TYPE_WALLET, Some("litecoin")).unwrap().requires_wallet_address());
+        assert!(registry.names(ACCOUNT_TYPE_WALLET).ends_with(&["bitcoin", "litecoin", "dogecoin", "cosmos"]));
+
+        let safe = registry.find(ACCOUNT_TYPE_WALLET, Some("Safe")).unwrap();
+        assert!(safe.validate_wallet_address("0x742d35Cc6634C0532925a3b844Bc454e4438f44e").is_ok());
+        assert_eq!(address_family(safe.name()), DEFAULT_WALLET_KIND);
+        assert_eq!(address_family("solana"), "solana");
+    }
+}
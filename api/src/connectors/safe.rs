@@ -0,0 +1,315 @@
+//! Safe (formerly Gnosis Safe) multisig wallets, read through the Safe Transaction Service.
+//!
+//! Safe accounts are wallet accounts of kind `safe` with the Safe's address as
+//! `wallet_address`. The same address may be deployed on several chains; every active EVM chain
+//! in the account's `enabled_chains` that the service indexes is checked, and chains where the
+//! address is not a Safe are skipped. Besides balances, the connector reads each deployment's
+//! owners, threshold, modules and guard, which sync stores in `accounts.multisig` for display.
+//!
+//! The service is `SAFE_TRANSACTION_SERVICE_URL` (default: the public gateway), with
+//! `SAFE_API_KEY` sent as a bearer token when set.
+
+use super::evm::{account_chains, EvmChain};
+use super::registry::{ConnectorContext, ConnectorFactory, ACCOUNT_TYPE_WALLET, WALLET_KIND_SAFE};
+use super::{Balance, ChainFetch, ChainProgress, ExchangeConnector, MultisigDeployment};
+use crate::helpers::balance_normalization::normalize_token_balance;
+use alloy::primitives::Address;
+use async_trait::async_trait;
+use reqwest::{Client, StatusCode};
+use serde::de::DeserializeOwned;
+use serde::Deserialize;
+use std::error::Error;
+use std::time::Instant;
+
+/// Public Safe Transaction Service gateway; each chain is served under its network name
+pub const SAFE_TRANSACTION_SERVICE_URL: &str = "https://api.safe.global/tx-service";
+
+/// Guard address the service reports when no guard is set
+const NO_GUARD: &str = "0x0000000000000000000000000000000000000000";
+
+/// Network name of the Safe Transaction Service for a chain name from the `evm_chains` table
+pub fn safe_network(chain_name: &str) -> Option<&'static str> {
+    match chain_name {
+        "ethereum" => Some("eth"),
+        "optimism" => Some("oeth"),
+        "bsc" => Some("bnb"),
+        "gnosis" => Some("gno"),
+        "polygon" => Some("pol"),
+        "zksync" => Some("zksync"),
+        "base" => Some("base"),
+        "arbitrum" => Some("arb1"),
+        "avalanche" => Some("avax"),
+        "linea" => Some("linea"),
+        "scroll" => Some("scr"),
+        _ => None,
+    }
+}
+
+#[derive(Debug, Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct SafeBalance {
+    /// `None` for the chain's native token
+    token_address: Option<String>,
+    token: Option<SafeToken>,
+    /// Balance in the token's smallest unit
+    balance: String,
+}
+
+#[derive(Debug, Deserialize)]
+struct SafeToken {
+    symbol: String,
+    decimals: Option<u8>,
+}
+
+#[derive(Debug, Deserialize)]
+struct SafeInfo {
+    threshold: u32,
+    owners: Vec<String>,
+    #[serde(default)]
+    modules: Vec<String>,
+    guard: Option<String>,
+    version: Option<String>,
+    /// A number in older service versions, a string in newer ones
+    nonce: serde_json::Value,
+}
+
+/// Convert a Safe's balance list on one chain into holdings, skipping zero balances
+fn to_balances(chain: &EvmChain, rows: Vec<SafeBalance>) -> Vec<Balance> {
+    rows.into_iter()
+        .filter(|row| !row.balance.trim_start_matches('0').is_empty())
+        .filter_map(|row| {
+            let (symbol, decimals) = match (&row.token_address, row.token) {
+                (None, _) => (chain.native_symbol().to_string(), 18),
+                (Some(_), Some(token)) if !token.symbol.trim().is_empty() => {
+                    (token.symbol.trim().to_string(), token.decimals.unwrap_or(18))
+                }
+                _ => return None,
+            };
+            let quantity = normalize_token_balance(&row.balance, decimals).ok()?;
+            Some(Balance {
+                asset: format!("{}-{}", symbol, chain.name()),
+                quantity: quantity.clone(),
+                available: quantity,
+                frozen: "0".to_string(),
+                decimals: Some(decimals),
+                holding_source: None,
+            })
+        })
+        .collect()
+}
+
+fn to_deployment(chain_name: &str, info: SafeInfo) -> MultisigDeployment {
+    let nonce = match &info.nonce {
+        serde_json::Value::Number(n) => n.as_u64().unwrap_or_default(),
+        serde_json::Value::String(s) => s.parse().unwrap_or_default(),
+        _ => 0,
+    };
+    MultisigDeployment {
+        chain: chain_name.to_string(),
+        threshold: info.threshold,
+        owners: info.owners,
+        modules: info.modules,
+        guard: info.guard.filter(|g| !g.eq_ignore_ascii_case(NO_GUARD)),
+        version: info.version,
+        nonce,
+    }
+}
+
+/// Safe multisig connector
+pub struct SafeConnector {
+    /// Checksummed Safe address, as the service expects it
+    safe_address: String,
+    chains: Vec<EvmChain>,
+    client: Client,
+    api_url: String,
+    api_key: Option<String>,
+}
+
+impl SafeConnector {
+    /// Create a connector for a Safe address on the given chains
+    pub fn new(
+        safe_address: &str,
+        chains: Vec<EvmChain>,
+        api_url: String,
+        api_key: Option<String>,
+    ) -> Result<Self, Box<dyn Error + Send + Sync>> {
+        let address = safe_address
+            .trim()
+            .parse::<Address>()
+            .map_err(|e| format!("Invalid Safe address: {}", e))?;
+        Ok(Self {
+            safe_address: address.to_checksum(None),
+            chains: chains.into_iter().filter(|c| safe_network(c.name()).is_some()).collect(),
+            client: Client::new(),
+            api_url: api_url.trim_end_matches('/').to_string(),
+            api_key: api_key.filter(|k| !k.is_empty()),
+        })
+    }
+
+    /// GET a Safe endpoint on one chain; `None` when the address is not a Safe there (404)
+    async fn get<T: DeserializeOwned>(
+        &self,
+        chain: &EvmChain,
+        path: &str,
+    ) -> Result<Option<T>, Box<dyn Error + Send + Sync>> {
+        let network = safe_network(chain.name()).ok_or("Chain not indexed by the Safe Transaction Service")?;
+        let url = format!("{}/{}/api/v1/safes/{}/{}", self.api_url, network, self.safe_address, path);
+        let mut request = self.client.get(&url);
+        if let Some(key) = &self.api_key {
+            request = request.bearer_auth(key);
+        }
+        let response = request.send().await?;
+        if response.status() == StatusCode::NOT_FOUND {
+            return Ok(None);
+        }
+        Ok(Some(response.error_for_status()?.json().await?))
+    }
+}
+
+#[async_trait]
+impl ExchangeConnector for SafeConnector {
+    /// Balances across the chains the Safe is deployed on.
+    ///
+    /// A failing chain is logged and skipped, like RPC failures of plain EVM wallets; an address
+    /// that is a Safe on none of the chains fails the sync.
+    async fn fetch_spot_balances(&self) -> Result<Vec<Balance>, Box<dyn Error + Send + Sync>> {
+        self.fetch_spot_balances_by_chain(&|_| {}).await
+    }
+
+    /// Chains are read one after another; a chain the Safe is not deployed on finishes with no
+    /// balances.
+    async fn fetch_spot_balances_by_chain(
+        &self,
+        on_chain: &(dyn Fn(ChainProgress) + Send + Sync),
+    ) -> Result<Vec<Balance>, Box<dyn Error + Send + Sync>> {
+        let mut balances = Vec::new();
+        let mut deployed = false;
+        let mut failed = false;
+        for chain in &self.chains {
+            let started = Instant::now();
+            on_chain(ChainProgress::Started { chain: chain.name().to_string() });
+            let mut chain_balances = Vec::new();
+            let mut error = None;
+            match self.get::<Vec<SafeBalance>>(chain, "balances/?trusted=false&exclude_spam=true").await {
+                Ok(Some(rows)) => {
+                    deployed = true;
+                    chain_balances = to_balances(chain, rows);
+                }
+                Ok(None) => tracing::debug!("{} is not a Safe on {}", self.safe_address, chain.name()),
+                Err(e) => {
+                    failed = true;
+                    tracing::error!("Failed to fetch Safe balances on {}: {}", chain.name(), e);
+                    error = Some(e.to_string());
+                }
+            }
+            on_chain(ChainProgress::Finished(ChainFetch {
+                chain: chain.name().to_string(),
+                balances: chain_balances.len(),
+                error,
+                duration_ms: started.elapsed().as_millis() as u64,
+            }));
+            balances.extend(chain_balances);
+        }
+
+        if !deployed && !failed {
+            return Err(format!("{} is not a Safe on any enabled chain", self.safe_address).into());
+        }
+        tracing::info!("Fetched {} balances for Safe {}", balances.len(), self.safe_address);
+        Ok(balances)
+    }
+
+    /// Owners, threshold and modules per chain. Any failed chain fails the whole call, so the
+    /// stored configuration is kept rather than replaced by a partial one.
+    async fn fetch_multisig(&self) -> Result<Vec<MultisigDeployment>, Box<dyn Error + Send + Sync>> {
+        let mut deployments = Vec::new();
+        for chain in &self.chains {
+            if let Some(info) = self.get::<SafeInfo>(chain, "").await? {
+                deployments.push(to_deployment(chain.name(), info));
+            }
+        }
+        Ok(deployments)
+    }
+}
+
+/// Builds [`SafeConnector`]s for Safe wallet accounts
+pub struct SafeFactory;
+
+#[async_trait]
+impl ConnectorFactory for SafeFactory {
+    fn account_type(&self) -> &'static str {
+        ACCOUNT_TYPE_WALLET
+    }
+
+    fn name(&self) -> &'static str {
+        WALLET_KIND_SAFE
+    }
+
+    fn requires_wallet_address(&self) -> bool {
+        true
+    }
+
+    fn validate_wallet_address(&self, address: &str) -> Result<(), String> {
+        address
+            .trim()
+            .parse::<Address>()
+            .map(|_| ())
+            .map_err(|_| "expected a 0x-prefixed Safe address".to_string())
+    }
+
+    async fn create(
+        &self,
+        ctx: &ConnectorContext<'_>,
+    ) -> Result<Box<dyn ExchangeConnector>, Box<dyn Error + Send + Sync>> {
+        let wallet_address = ctx.wallet_address()?;
+        let chains = account_chains(ctx).await;
+        let api_url = std::env::var("SAFE_TRANSACTION_SERVICE_URL")
+            .unwrap_or_else(|_| SAFE_TRANSACTION_SERVICE_URL.to_string());
+        let api_key = std::env::var("SAFE_API_KEY").ok();
+
+        let connector = SafeConnector::new(&wallet_address, chains, api_url, api_key)?;
+        if connector.chains.is_empty() {
+            return Err("None of the account's chains is indexed by the Safe Transaction Service".into());
+        }
+        Ok(Box::new(connector))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_safe_balances_and_info() {
+        let chain = EvmChain::new("ethereum", "https://eth.llamarpc.com", "ETH");
+        let rows: Vec<SafeBalance> = serde_json::from_value(serde_json::json!([
+            {"tokenAddress": null, "token": null, "balance": "2000000000000000000"},
+            {"tokenAddress": "0xA0b86991c6218b36c1d19D4a2e9Eb0cE3606eB48",
+             "token": {"name": "USD Coin", "symbol": "USDC", "decimals": 6, "logoUri": ""},
+             "balance": "1500000"},
+            {"tokenAddress": "0x6B175474E89094C44Da98b954EedeAC495271d0F",
+             "token": {"name": "Dai", "symbol": "DAI", "decimals": 18}, "balance": "0"}
+        ]))
+        .unwrap();
+        let balances = to_balances(&chain, rows);
+        let assets: Vec<&str> = balances.iter().map(|b| b.asset.as_str()).collect();
+        assert_eq!(assets, vec!["ETH-ethereum", "USDC-ethereum"]);
+        assert_eq!(balances[1].decimals, Some(6));
+
+        let info: SafeInfo = serde_json::from_value(serde_json::json!({
+            "address": "0x5aFE3855358E112B5647B952709E6165e1c1eEEe",
+            "nonce": "42",
+            "threshold": 2,
+            "owners": ["0x1111111111111111111111111111111111111111", "0x2222222222222222222222222222222222222222"],
+            "modules": ["0x3333333333333333333333333333333333333333"],
+            "guard": NO_GUARD,
+            "version": "1.3.0"
+        }))
+        .unwrap();
+        let deployment = to_deployment("ethereum", info);
+        assert_eq!((deployment.threshold, deployment.owners.len(), deployment.nonce), (2, 2, 42));
+        assert_eq!(deployment.modules.len(), 1);
+        assert_eq!(deployment.guard, None);
+        assert_eq!(safe_network("arbitrum"), Some("arb1"));
+        assert_eq!(safe_network("hyper_liquid"), None);
+    }
+}
@@ -1,8 +1,13 @@
-use super::{Balance, ExchangeConnector};
+use super::registry::{ConnectorContext, ConnectorFactory, ACCOUNT_TYPE_WALLET};
+use super::{Balance, ExchangeConnector, StakingReward};
 use crate::concurrency::RateLimiter;
+use crate::domain::HOLDING_SOURCE_STAKED;
+use crate::entities::{asset_contracts, assets, solana_tokens, spam_tokens};
 use crate::helpers::balance_normalization::normalize_token_balance;
 use async_trait::async_trait;
+use chrono::{DateTime, Utc};
 use reqwest::Client;
+use sea_orm::{ColumnTrait, DatabaseConnection, EntityTrait, QueryFilter};
 use serde::Deserialize;
 use serde_json::json;
 use std::collections::HashMap;
@@ -15,9 +20,20 @@ pub const SOLANA_NATIVE_DECIMALS: u8 = 9;
 /// SPL Token program ID — the standard Solana token program
 pub const TOKEN_PROGRAM_ID: &str = "TokenkegQfeZyiNwAJbNbGKPFXCWuBvf9Ss623VQ5DA";
 
+/// Stake program ID — owner of all native stake accounts
+pub const STAKE_PROGRAM_ID: &str = "Stake11111111111111111111111111111111111111";
+
+/// Byte offset of the withdraw authority in stake account data
+/// (4-byte state tag + 8-byte rent reserve + 32-byte staker)
+const STAKE_WITHDRAWER_OFFSET: usize = 44;
+
+/// Epochs of rewards fetched per sync; a first sync (or a long gap) only backfills this many
+pub const MAX_REWARD_EPOCHS_PER_SYNC: u64 = 10;
+
 /// Well-known SPL token mint addresses (symbol, mint_address)
 ///
-/// Only a curated list is tracked. Unrecognized mints are skipped during sync.
+/// Mints outside this list are reported only when the spam filter finds them listed in
+/// `asset_contracts` (see [`SpamFilter`]).
 /// Extend via the admin UI or by adding entries here.
 pub fn get_common_solana_tokens() -> Vec<(&'static str, &'static str)> {
     vec![
@@ -91,6 +107,180 @@ struct TokenAmount {
     #[serde(rename = "uiAmountString")]
     ui_amount_string: String,
     amount: String,
+    decimals: u8,
+}
+
+#[derive(Deserialize)]
+struct EpochInfo {
+    epoch: u64,
+}
+
+#[derive(Deserialize)]
+struct StakeProgramAccount {
+    pubkey: String,
+    account: StakeAccount,
+}
+
+#[derive(Deserialize)]
+struct StakeAccount {
+    lamports: u64,
+    data: StakeAccountData,
+}
+
+#[derive(Deserialize)]
+#[serde(untagged)]
+enum StakeAccountData {
+    Parsed { parsed: ParsedStakeAccount },
+    #[allow(dead_code)]
+    Other(serde_json::Value),
+}
+
+#[derive(Deserialize)]
+struct ParsedStakeAccount {
+    info: StakeInfo,
+}
+
+#[derive(Deserialize)]
+struct StakeInfo {
+    meta: StakeMeta,
+    /// Absent for initialized but never delegated accounts
+    stake: Option<StakeDetails>,
+}
+
+#[derive(Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct StakeMeta {
+    rent_exempt_reserve: String,
+}
+
+#[derive(Deserialize)]
+struct StakeDetails {
+    delegation: StakeDelegation,
+}
+
+#[derive(Deserialize)]
+struct StakeDelegation {
+    stake: String,
+}
+
+#[derive(Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct InflationReward {
+    epoch: u64,
+    effective_slot: u64,
+    /// Reward in lamports
+    amount: u64,
+    post_balance: u64,
+    commission: Option<u8>,
+}
+
+/// Lamports a stake account contributes to the SOL holding: delegated stake plus the
+/// rent-exempt reserve, or the whole balance when nothing is delegated
+fn stake_account_lamports(account: &StakeAccount) -> u64 {
+    match &account.data {
+        StakeAccountData::Parsed { parsed } => match &parsed.info.stake {
+            Some(details) => {
+                let delegated = details.delegation.stake.parse::<u64>().unwrap_or(0);
+                let reserve = parsed.info.meta.rent_exempt_reserve.parse::<u64>().unwrap_or(0);
+                delegated + reserve
+            }
+            None => account.lamports,
+        },
+        StakeAccountData::Other(_) => account.lamports,
+    }
+}
+
+/// Lamports normalized to SOL
+fn lamports_to_sol(lamports: u64) -> String {
+    let raw = lamports.to_string();
+    normalize_token_balance(&raw, SOLANA_NATIVE_DECIMALS).unwrap_or(raw)
+}
+
+/// Rewards of one epoch, paired with the stake accounts they were requested for
+fn epoch_rewards(
+    stake_accounts: &[String],
+    rewards: Vec<Option<InflationReward>>,
+    earned_at: DateTime<Utc>,
+) -> Vec<StakingReward> {
+    stake_accounts
+        .iter()
+        .zip(rewards)
+        .filter_map(|(stake_account, reward)| {
+            let reward = reward.filter(|r| r.amount > 0)?;
+            Some(StakingReward {
+                asset: "SOL-solana".to_string(),
+                source_account: stake_account.clone(),
+                epoch: reward.epoch,
+                amount: lamports_to_sol(reward.amount),
+                post_balance: Some(lamports_to_sol(reward.post_balance)),
+                commission: reward.commission,
+                earned_at,
+            })
+        })
+        .collect()
+}
+
+// ── Spam filtering ─────────────────────────────────────────────────────────
+
+/// An SPL mint listed in `asset_contracts` (chain "solana")
+#[derive(Debug, Clone)]
+pub struct ListedMint {
+    /// Symbol of the listed asset
+    pub symbol: String,
+    /// Whether the asset is still active; delisted assets have no market to sell into
+    pub is_active: bool,
+}
+
+/// Outcome of the spam pass for one SPL token account
+#[derive(Debug, Clone, PartialEq)]
+pub enum SplTokenVerdict {
+    /// Reported as a holding under this symbol
+    Keep(String),
+    /// Dropped from holdings, with the reason
+    Spam(String),
+}
+
+/// Spam pass run over every SPL token account during sync.
+///
+/// In order: mints on the `spam_tokens` blocklist are dropped; mints in the connector's
+/// token list (`solana_tokens`) are kept; any other mint is kept only if it is listed in
+/// `asset_contracts` for an active asset and is not a zero-decimal (NFT-like) token.
+/// Unknown mints — the bulk of unsolicited airdrops — have no listing and are dropped.
+#[derive(Debug, Clone, Default)]
+pub struct SpamFilter {
+    /// Blocked mint address → reason recorded with the block
+    blocked: HashMap<String, Option<String>>,
+    /// Listed mint address → listed asset
+    listed: HashMap<String, ListedMint>,
+}
+
+impl SpamFilter {
+    pub fn new(blocked: HashMap<String, Option<String>>, listed: HashMap<String, ListedMint>) -> Self {
+        Self { blocked, listed }
+    }
+
+    /// Decide whether a token account of `mint` is reported, given the connector's allowlist
+    /// (mint → symbol)
+    pub fn classify(&self, mint: &str, decimals: u8, allowlist: &HashMap<String, String>) -> SplTokenVerdict {
+        if let Some(reason) = self.blocked.get(mint) {
+            return SplTokenVerdict::Spam(match reason {
+                Some(reason) => format!("blocklisted: {}", reason),
+                None => "blocklisted".to_string(),
+            });
+        }
+        if let Some(symbol) = allowlist.get(mint) {
+            return SplTokenVerdict::Keep(symbol.clone());
+        }
+
+        match self.listed.get(mint) {
+            None => SplTokenVerdict::Spam("unknown mint".to_string()),
+            Some(listed) if !listed.is_active => {
+                SplTokenVerdict::Spam(format!("{} is delisted (no liquidity)", listed.symbol))
+            }
+            Some(_) if decimals == 0 => SplTokenVerdict::Spam("zero-decimal token (NFT or airdrop)".to_string()),
+            Some(listed) => SplTokenVerdict::Keep(listed.symbol.to_uppercase()),
+        }
+    }
 }
 
 // ── SolanaConnector ────────────────────────────────────────────────────────
@@ -98,15 +288,21 @@ struct TokenAmount {
 /// Solana wallet connector that fetches native SOL and SPL token balances
 /// using direct JSON-RPC calls (no Solana SDK dependency).
 ///
-/// Uses two RPC calls:
+/// Uses three RPC calls:
 /// 1. `getBalance` — native SOL balance in lamports
 /// 2. `getTokenAccountsByOwner` — all SPL token accounts in one call
+/// 3. `getProgramAccounts` on the stake program — stake accounts the wallet can withdraw,
+///    reported as a separate "staked" SOL holding
+///
+/// Epoch rewards of those stake accounts come from `getInflationReward`.
 pub struct SolanaConnector {
     wallet_address: String,
     rpc_url: String,
     /// Mapping from mint address → symbol for tokens we recognise.
     /// Built from DB-sourced list if available, otherwise from `get_common_solana_tokens()`.
     token_map: HashMap<String, String>,
+    /// Spam pass applied to SPL token accounts; the default drops every mint not in `token_map`
+    spam_filter: SpamFilter,
     http_client: Client,
 }
 
@@ -135,10 +331,17 @@ impl SolanaConnector {
             wallet_address,
             rpc_url,
             token_map,
+            spam_filter: SpamFilter::default(),
             http_client: Client::new(),
         }
     }
 
+    /// Use a spam filter loaded from `spam_tokens` and `asset_contracts`
+    pub fn with_spam_filter(mut self, spam_filter: SpamFilter) -> Self {
+        self.spam_filter = spam_filter;
+        self
+    }
+
     /// Fetch native SOL balance via `getBalance`.
     async fn fetch_sol_balance(&self) -> Result<Option<Balance>, Box<dyn Error + Send + Sync>> {
         let body = json!({
@@ -172,11 +375,66 @@ impl SolanaConnector {
             available: normalized,
             frozen: "0".to_string(),
             decimals: Some(SOLANA_NATIVE_DECIMALS),
+            holding_source: None,
+        }))
+    }
+
+    /// Send a JSON-RPC request and return its `result`
+    async fn rpc<T: for<'de> Deserialize<'de>>(
+        &self,
+        method: &str,
+        params: serde_json::Value,
+    ) -> Result<T, Box<dyn Error + Send + Sync>> {
+        let body = json!({ "jsonrpc": "2.0", "id": 1, "method": method, "params": params });
+        let response: RpcResponse<T> = self.http_client.post(&self.rpc_url).json(&body).send().await?.json().await?;
+        Ok(response.result)
+    }
+
+    /// Stake accounts whose withdraw authority is the wallet, with the lamports each contributes
+    async fn fetch_stake_accounts(&self) -> Result<Vec<(String, u64)>, Box<dyn Error + Send + Sync>> {
+        let accounts: Vec<StakeProgramAccount> = self
+            .rpc(
+                "getProgramAccounts",
+                json!([
+                    STAKE_PROGRAM_ID,
+                    {
+                        "encoding": "jsonParsed",
+                        "filters": [{ "memcmp": { "offset": STAKE_WITHDRAWER_OFFSET, "bytes": self.wallet_address } }]
+                    }
+                ]),
+            )
+            .await?;
+        Ok(accounts
+            .into_iter()
+            .map(|a| {
+                let lamports = stake_account_lamports(&a.account);
+                (a.pubkey, lamports)
+            })
+            .collect())
+    }
+
+    /// Total of the wallet's stake accounts as a "staked" SOL balance
+    async fn fetch_staked_balance(&self) -> Result<Option<Balance>, Box<dyn Error + Send + Sync>> {
+        let stake_accounts = self.fetch_stake_accounts().await?;
+        let lamports: u64 = stake_accounts.iter().map(|(_, lamports)| lamports).sum();
+        if lamports == 0 {
+            return Ok(None);
+        }
+
+        tracing::debug!("Found {} stake accounts holding {} lamports", stake_accounts.len(), lamports);
+        let normalized = lamports_to_sol(lamports);
+        Ok(Some(Balance {
+            asset: "SOL-solana".to_string(),
+            quantity: normalized.clone(),
+            available: "0".to_string(),
+            frozen: normalized,
+            decimals: Some(SOLANA_NATIVE_DECIMALS),
+            holding_source: Some(HOLDING_SOURCE_STAKED.to_string()),
         }))
     }
 
     /// Fetch all SPL token balances via `getTokenAccountsByOwner`.
-    /// Returns only tokens whose mint address is in `self.token_map`.
+    /// Returns only tokens that pass the spam filter (see [`SpamFilter`]).
     async fn fetch_spl_balances(&self) -> Result<Vec<Balance>, Box<dyn Error + Send + Sync>> {
         let body = json!({
             "jsonrpc": "2.0",
@@ -199,6 +457,7 @@ impl SolanaConnector {
             .await?;
 
         let mut balances = Vec::new();
+        let mut spam_count = 0;
 
         for entry in response.result.value {
             let parsed = match entry.account.data {
@@ -207,13 +466,6 @@ impl SolanaConnector {
             };
 
             let mint = &parsed.info.mint;
-            let symbol = match self.token_map.get(mint) {
-                Some(sym) => sym.clone(),
-                None => {
-                    tracing::debug!("Skipping unrecognised mint: {}", mint);
-                    continue;
-                }
-            };
 
             // uiAmountString is already normalised by the RPC (e.g. "1.5")
             let ui_amount = &parsed.info.token_amount.ui_amount_string;
@@ -224,17 +476,31 @@ impl SolanaConnector {
                 continue;
             }
 
+            let symbol = match self.spam_filter.classify(mint, parsed.info.token_amount.decimals, &self.token_map) {
+                SplTokenVerdict::Keep(symbol) => symbol,
+                SplTokenVerdict::Spam(reason) => {
+                    tracing::debug!("Skipping mint {}: {}", mint, reason);
+                    spam_count += 1;
+                    continue;
+                }
+            };
+
             balances.push(Balance {
                 asset: format!("{}-solana", symbol),
                 quantity: ui_amount.clone(),
                 available: ui_amount.clone(),
                 frozen: "0".to_string(),
                 decimals: None,
+                holding_source: None,
             });
 
             tracing::debug!("Found {} {} on solana (mint: {})", ui_amount, symbol, mint);
         }
 
+        if spam_count > 0 {
+            tracing::info!("Filtered {} spam token accounts of wallet {}", spam_count, self.wallet_address);
+        }
+
         Ok(balances)
     }
 }
@@ -266,6 +532,13 @@ impl ExchangeConnector for SolanaConnector {
             Err(e) => tracing::error!("Failed to fetch SPL token balances: {}", e),
         }
 
+        // Fetch native stake accounts
+        match self.fetch_staked_balance().await {
+            Ok(Some(balance)) => all_balances.push(balance),
+            Ok(None) => {}
+            Err(e) => tracing::error!("Failed to fetch Solana stake accounts: {}", e),
+        }
+
         tracing::info!(
             "Fetched {} total balances for Solana wallet {}",
             all_balances.len(),
@@ -274,6 +547,173 @@ impl ExchangeConnector for SolanaConnector {
 
         Ok(all_balances)
     }
+
+    async fn fetch_staking_rewards(
+        &self,
+        after_epoch: Option<u64>,
+    ) -> Result<Vec<StakingReward>, Box<dyn Error + Send + Sync>> {
+        let rate_limiter = RateLimiter::solana_rpc();
+
+        let stake_accounts: Vec<String> = {
+            let _permit = rate_limiter.acquire().await?;
+            self.fetch_stake_accounts().await?.into_iter().map(|(pubkey, _)| pubkey).collect()
+        };
+        if stake_accounts.is_empty() {
+            return Ok(Vec::new());
+        }
+
+        let current: EpochInfo = {
+            let _permit = rate_limiter.acquire().await?;
+            self.rpc("getEpochInfo", json!([])).await?
+        };
+        // Rewards for an epoch are paid at the start of the next one
+        let Some(last_paid) = current.epoch.checked_sub(1) else {
+            return Ok(Vec::new());
+        };
+        let first = after_epoch
+            .map(|e| e + 1)
+            .unwrap_or(0)
+            .max(last_paid.saturating_sub(MAX_REWARD_EPOCHS_PER_SYNC - 1));
+
+        let mut rewards = Vec::new();
+        for epoch in first..=last_paid {
+            let _permit = rate_limiter.acquire().await?;
+            let results: Vec<Option<InflationReward>> = self
+                .rpc("getInflationReward", json!([stake_accounts, { "epoch": epoch }]))
+                .await?;
+
+            // All rewards of an epoch are credited in the same slot
+            let Some(slot) = results.iter().flatten().map(|r| r.effective_slot).next() else {
+                continue;
+            };
+            let block_time: Option<i64> = self.rpc("getBlockTime", json!([slot])).await?;
+            let earned_at = block_time
+                .and_then(|t| DateTime::from_timestamp(t, 0))
+                .unwrap_or_else(Utc::now);
+
+            rewards.extend(epoch_rewards(&stake_accounts, results, earned_at));
+        }
+
+        tracing::info!(
+            "Fetched {} staking rewards for Solana wallet {} (epochs {}..={})",
+            rewards.len(),
+            self.wallet_address,
+            first,
+            last_paid
+        );
+
+        Ok(rewards)
+    }
+}
+
+/// Builds [`SolanaConnector`]s for wallet accounts with `exchange_name` "solana"
+pub struct SolanaFactory;
+
+#[async_trait]
+impl ConnectorFactory for SolanaFactory {
+    fn account_type(&self) -> &'static str {
+        ACCOUNT_TYPE_WALLET
+    }
+
+    fn name(&self) -> &'static str {
+        "solana"
+    }
+
+    fn requires_wallet_address(&self) -> bool {
+        true
+    }
+
+    async fn create(
+        &self,
+        ctx: &ConnectorContext<'_>,
+    ) -> Result<Box<dyn ExchangeConnector>, Box<dyn Error + Send + Sync>> {
+        // Use SOLANA_RPC_URL env var; fall back to public mainnet endpoint
+        let rpc_url = std::env::var("SOLANA_RPC_URL")
+            .unwrap_or_else(|_| "https://api.mainnet-beta.solana.com".to_string());
+        let db_tokens = load_solana_tokens_from_db(ctx.db).await;
+        let spam_filter = load_spam_filter_from_db(ctx.db).await;
+        Ok(Box::new(
+            SolanaConnector::new(ctx.wallet_address()?, rpc_url, db_tokens).with_spam_filter(spam_filter),
+        ))
+    }
+}
+
+/// Load active Solana SPL tokens from the database.
+///
+/// Returns `Some(vec)` of `(symbol, mint_address)` pairs when the table is reachable
+/// and contains rows. Falls back to `None` on any DB error so the Solana connector
+/// uses its built-in token list.
+async fn load_solana_tokens_from_db(
+    db: &DatabaseConnection,
+) -> Option<Vec<(String, String)>> {
+    match solana_tokens::Entity::find()
+        .filter(solana_tokens::Column::IsActive.eq(true))
+        .all(db)
+        .await
+    {
+        Ok(rows) if !rows.is_empty() => {
+            let pairs: Vec<(String, String)> = rows
+                .into_iter()
+                .map(|row| (row.symbol, row.mint_address))
+                .collect();
+            tracing::info!(
+                "Loaded {} active Solana tokens from DB",
+                pairs.len()
+            );
+            Some(pairs)
+        }
+        Ok(_) => {
+            tracing::warn!("solana_tokens table is empty, falling back to built-in token list");
+            None
+        }
+        Err(e) => {
+            tracing::warn!(
+                "Failed to load Solana tokens from DB: {}, falling back to built-in token list",
+                e
+            );
+            None
+        }
+    }
+}
+
+/// Load the spam filter: blocked Solana mints from `spam_tokens` and listed mints from
+/// `asset_contracts`.
+///
+/// Either part falls back to empty on a DB error; with no listings every mint outside the
+/// token list is treated as spam, which matches syncing the token list only.
+async fn load_spam_filter_from_db(db: &DatabaseConnection) -> SpamFilter {
+    let blocked = match spam_tokens::Entity::find()
+        .filter(spam_tokens::Column::Chain.eq("solana"))
+        .all(db)
+        .await
+    {
+        Ok(rows) => rows.into_iter().map(|row| (row.token_address, row.reason)).collect(),
+        Err(e) => {
+            tracing::warn!("Failed to load Solana spam tokens from DB: {}", e);
+            HashMap::new()
+        }
+    };
+
+    let listed = match asset_contracts::Entity::find()
+        .filter(asset_contracts::Column::Chain.eq("solana"))
+        .find_also_related(assets::Entity)
+        .all(db)
+        .await
+    {
+        Ok(rows) => rows
+            .into_iter()
+            .filter_map(|(contract, asset)| {
+                let asset = asset?;
+                Some((contract.contract_address, ListedMint { symbol: asset.symbol, is_active: asset.is_active }))
+            })
+            .collect(),
+        Err(e) => {
+            tracing::warn!("Failed to load listed Solana mints from DB: {}", e);
+            HashMap::new()
+        }
+    };
+
+    SpamFilter::new(blocked, listed)
 }
 
 #[cfg(test)]
@@ -317,6 +757,71 @@ mod tests {
         assert!(connector.token_map.contains_key("SomeMintAddress11111111111111111111111111111"));
     }
 
+    #[test]
+    fn test_spam_filter_verdicts() {
+        let allowlist: HashMap<String, String> = [("UsdcMint".to_string(), "USDC".to_string())].into();
+        let filter = SpamFilter::new(
+            [("ScamMint".to_string(), Some("phishing airdrop".to_string()))].into(),
+            [
+                ("RayMint".to_string(), ListedMint { symbol: "ray".to_string(), is_active: true }),
+                ("DeadMint".to_string(), ListedMint { symbol: "DEAD".to_string(), is_active: false }),
+                ("NftMint".to_string(), ListedMint { symbol: "NFT".to_string(), is_active: true }),
+            ]
+            .into(),
+        );
+
+        assert_eq!(filter.classify("UsdcMint", 6, &allowlist), SplTokenVerdict::Keep("USDC".to_string()));
+        assert_eq!(filter.classify("RayMint", 6, &allowlist), SplTokenVerdict::Keep("RAY".to_string()));
+        assert_eq!(
+            filter.classify("ScamMint", 6, &allowlist),
+            SplTokenVerdict::Spam("blocklisted: phishing airdrop".to_string())
+        );
+        assert!(matches!(filter.classify("RandomMint", 6, &allowlist), SplTokenVerdict::Spam(_)));
+        assert!(matches!(filter.classify("DeadMint", 6, &allowlist), SplTokenVerdict::Spam(_)));
+        assert!(matches!(filter.classify("NftMint", 0, &allowlist), SplTokenVerdict::Spam(_)));
+
+        // The blocklist wins over the allowlist
+        let blocked_usdc = SpamFilter::new([("UsdcMint".to_string(), None)].into(), HashMap::new());
+        assert_eq!(
+            blocked_usdc.classify("UsdcMint", 6, &allowlist),
+            SplTokenVerdict::Spam("blocklisted".to_string())
+        );
+        // Without listings, only the allowlist is kept
+        assert!(matches!(SpamFilter::default().classify("RayMint", 6, &allowlist), SplTokenVerdict::Spam(_)));
+    }
+
+    #[test]
+    fn test_stake_accounts_and_rewards() {
+        let accounts: Vec<StakeProgramAccount> = serde_json::from_str(
+            r#"[
+                {"pubkey":"StakeA","account":{"lamports":5002282880,"data":{"parsed":{"type":"delegated","info":{
+                    "meta":{"rentExemptReserve":"2282880","authorized":{},"lockup":{}},
+                    "stake":{"delegation":{"voter":"Vote1","stake":"4990000000","activationEpoch":"500","deactivationEpoch":"18446744073709551615"},"creditsObserved":1}
+                }},"program":"stake","space":200},"owner":"Stake11111111111111111111111111111111111111"}},
+                {"pubkey":"StakeB","account":{"lamports":1002282880,"data":{"parsed":{"type":"initialized","info":{
+                    "meta":{"rentExemptReserve":"2282880","authorized":{},"lockup":{}}
+                }},"program":"stake","space":200},"owner":"Stake11111111111111111111111111111111111111"}}
+            ]"#,
+        )
+        .unwrap();
+        // Delegated stake + reserve (lamports not yet delegated are left out), or the whole
+        // balance when undelegated
+        assert_eq!(stake_account_lamports(&accounts[0].account), 4_992_282_880);
+        assert_eq!(stake_account_lamports(&accounts[1].account), 1_002_282_880);
+
+        let results: Vec<Option<InflationReward>> = serde_json::from_str(
+            r#"[{"epoch":600,"effectiveSlot":259200000,"amount":2500000,"postBalance":5004782880,"commission":5},null]"#,
+        )
+        .unwrap();
+        let earned_at = Utc::now();
+        let rewards = epoch_rewards(&["StakeA".to_string(), "StakeB".to_string()], results, earned_at);
+        assert_eq!(rewards.len(), 1);
+        assert_eq!(rewards[0].source_account, "StakeA");
+        assert_eq!(rewards[0].epoch, 600);
+        assert_eq!(rewards[0].amount, "0.0025");
+        assert_eq!(rewards[0].commission, Some(5));
+    }
+
     #[test]
     fn test_solana_native_decimals() {
         // 1 SOL = 1_000_000_000 lamports
@@ -0,0 +1,252 @@
+//! Token prices from Uniswap v3 time-weighted average prices (TWAP).
+//!
+//! Used for long-tail tokens with a contract in `asset_contracts` but no CoinPaprika listing.
+//! The token's pools against the chain's main stablecoin are looked up on the Uniswap v3
+//! factory across all fee tiers, and the pool with the most in-range liquidity is read with
+//! `observe()` over the TWAP window. When the token only trades against the wrapped native
+//! token, that price is converted to USD with the native token's own stablecoin TWAP.
+//!
+//! A TWAP is harder to move than the spot price of a thin pool, but it is still an
+//! approximation: it lags and reflects whatever liquidity the pool has.
+
+use alloy::{
+    primitives::{aliases::U24, Address},
+    providers::ProviderBuilder,
+    sol,
+};
+use rust_decimal::{prelude::FromPrimitive, Decimal};
+use std::error::Error;
+
+/// Fee tiers (hundredths of a bip) searched for pools
+const FEE_TIERS: [u32; 4] = [100, 500, 3000, 10000];
+
+sol! {
+    #[sol(rpc)]
+    contract UniswapV3Factory {
+        function getPool(address tokenA, address tokenB, uint24 fee) external view returns (address pool);
+    }
+
+    #[sol(rpc)]
+    contract UniswapV3Pool {
+        function liquidity() external view returns (uint128);
+        function observe(uint32[] calldata secondsAgos) external view returns (int56[] memory tickCumulatives, uint160[] memory secondsPerLiquidityCumulativeX128s);
+    }
+
+    #[sol(rpc)]
+    contract TwapToken {
+        function decimals() external view returns (uint8);
+    }
+}
+
+/// Uniswap v3 deployment and quote tokens of a chain
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct UniswapV3Deployment {
+    pub factory: &'static str,
+    /// USD stablecoin quote token
+    pub stable: &'static str,
+    pub stable_decimals: u8,
+    /// Wrapped native token, for tokens without a stablecoin pool
+    pub wrapped_native: &'static str,
+}
+
+/// Uniswap v3 deployment on a chain from the `evm_chains` table
+pub fn uniswap_v3_deployment(chain: &str) -> Option<UniswapV3Deployment> {
+    const FACTORY: &str = "0x1F98431c8aD98523631AE4a59f267346ea31F984";
+    let deployment = |factory, stable, stable_decimals, wrapped_native| UniswapV3Deployment {
+        factory,
+        stable,
+        stable_decimals,
+        wrapped_native,
+    };
+    match chain {
+        "ethereum" => Some(deployment(
+            FACTORY,
+            "0xA0b86991c6218b36c1d19D4a2e9Eb0cE3606eB48",
+            6,
+            "0xC02aaA39b223FE8D0A0e5C4F27eAD9083C756Cc2",
+        )),
+        "arbitrum" => Some(deployment(
+            FACTORY,
+            "0xaf88d065e77c8cC2239327C5EDb3A432268e5831",
+            6,
+            "0x82aF49447D8a07e3bd95BD0d56f35241523fBab1",
+        )),
+        "optimism" => Some(deployment(
+            FACTORY,
+            "0x0b2C639c533813f4Aa9D7837CAf62653d097Ff85",
+            6,
+            "0x4200000000000000000000000000000000000006",
+        )),
+        "polygon" => Some(deployment(
+            FACTORY,
+            "0x3c499c542cEF5E3811e1192ce70d8cC03d5c3359",
+            6,
+            "0x0d500B1d8E8eF31E21C99d1Db9A6444d3ADf1270",
+        )),
+        "base" => Some(deployment(
+            "0x33128a8fC17869897dcE68Ed026d694621f6FDfD",
+            "0x833589fCD6eDb6E08f4c7C32D4f71b54bdA02913",
+            6,
+            "0x4200000000000000000000000000000000000006",
+        )),
+        "bsc" => Some(deployment(
+            "0xdB1d10011AD0Ff90774D0C6Bb92e5C5c8b4461F7",
+            "0x55d398326f99059fF775485246999027B3197955",
+            18,
+            "0xbb4CdB9CBd36B01bD1cBaEBF2De08d9173bc095c",
+        )),
+        _ => None,
+    }
+}
+
+/// TWAP price of a token
+#[derive(Debug, Clone, PartialEq)]
+pub struct TwapPrice {
+    pub price_usd: Decimal,
+    /// Pool the token was priced in
+    pub pool: Address,
+    /// Whether the price went through the wrapped native token
+    pub via_native: bool,
+}
+
+/// Arithmetic mean tick between two tick cumulatives `window` seconds apart, rounded towards
+/// negative infinity like Uniswap's `OracleLibrary.consult`
+pub fn mean_tick(cumulative_then: i64, cumulative_now: i64, window: u32) -> Option<i64> {
+    (window > 0).then(|| (cumulative_now - cumulative_then).div_euclid(i64::from(window)))
+}
+
+/// Price of `token` in `quote` units at `tick`; pool prices are token1 per token0 in raw units
+pub fn tick_to_price(tick: i64, token_decimals: u8, quote_decimals: u8, token_is_token0: bool) -> Option<Decimal> {
+    let raw = 1.0001_f64.powf(tick as f64);
+    let price = if token_is_token0 {
+        raw * 10_f64.powi(i32::from(token_decimals) - i32::from(quote_decimals))
+    } else {
+        10_f64.powi(i32::from(token_decimals) - i32::from(quote_decimals)) / raw
+    };
+    if !price.is_finite() || price <= 0.0 {
+        return None;
+    }
+    Decimal::from_f64(price).filter(|p| !p.is_zero())
+}
+
+/// Pool of `token`/`quote` with the most in-range liquidity across fee tiers
+async fn deepest_pool<P: alloy::providers::Provider>(
+    provider: &P,
+    factory: Address,
+    token: Address,
+    quote: Address,
+) -> Result<Option<Address>, Box<dyn Error + Send + Sync>> {
+    let factory = UniswapV3Factory::new(factory, provider);
+    let mut best: Option<(Address, u128)> = None;
+    for fee in FEE_TIERS {
+        let pool = factory.getPool(token, quote, U24::from(fee)).call().await?;
+        if pool == Address::ZERO {
+            continue;
+        }
+        let liquidity = UniswapV3Pool::new(pool, provider).liquidity().call().await?;
+        if liquidity > 0 && best.is_none_or(|(_, deepest)| liquidity > deepest) {
+            best = Some((pool, liquidity));
+        }
+    }
+    Ok(best.map(|(pool, _)| pool))
+}
+
+/// TWAP price of `token` in `quote` units from `pool`; `None` when the pool's oracle does not
+/// reach back `window` seconds
+async fn pool_twap<P: alloy::providers::Provider>(
+    provider: &P,
+    pool: Address,
+    token: Address,
+    token_decimals: u8,
+    quote: Address,
+    quote_decimals: u8,
+    window: u32,
+) -> Result<Option<Decimal>, Box<dyn Error + Send + Sync>> {
+    let observed = match UniswapV3Pool::new(pool, provider).observe(vec![window, 0]).call().await {
+        Ok(observed) => observed,
+        Err(e) => {
+            tracing::debug!("Pool {} has no {}s TWAP: {}", pool, window, e);
+            return Ok(None);
+        }
+    };
+    let [then, now] = observed.tickCumulatives[..] else {
+        return Ok(None);
+    };
+    let Some(tick) = mean_tick(then.as_i64(), now.as_i64(), window) else {
+        return Ok(None);
+    };
+    Ok(tick_to_price(tick, token_decimals, quote_decimals, token < quote))
+}
+
+/// Read a token's decimals from its contract
+pub async fn fetch_token_decimals(rpc_url: &str, token: &str) -> Result<u8, Box<dyn Error + Send + Sync>> {
+    let provider = ProviderBuilder::new().connect_http(rpc_url.parse()?);
+    Ok(TwapToken::new(token.parse()?, provider).decimals().call().await?)
+}
+
+/// Price a token from its Uniswap v3 TWAP over `window` seconds; `None` when no pool with
+/// enough oracle history prices it against the stablecoin or the wrapped native token
+pub async fn fetch_twap_price(
+    rpc_url: &str,
+    deployment: &UniswapV3Deployment,
+    token_address: &str,
+    token_decimals: u8,
+    window: u32,
+) -> Result<Option<TwapPrice>, Box<dyn Error + Send + Sync>> {
+    let provider = ProviderBuilder::new().connect_http(rpc_url.parse()?);
+    let factory: Address = deployment.factory.parse()?;
+    let token: Address = token_address.parse()?;
+    let stable: Address = deployment.stable.parse()?;
+    let native: Address = deployment.wrapped_native.parse()?;
+
+    if token != stable {
+        if let Some(pool) = deepest_pool(&provider, factory, token, stable).await? {
+            let price =
+                pool_twap(&provider, pool, token, token_decimals, stable, deployment.stable_decimals, window).await?;
+            if let Some(price_usd) = price {
+                return Ok(Some(TwapPrice { price_usd, pool, via_native: false }));
+            }
+        }
+    }
+
+    if token == native {
+        return Ok(None);
+    }
+    let Some(pool) = deepest_pool(&provider, factory, token, native).await? else {
+        return Ok(None);
+    };
+    let Some(price_native) = pool_twap(&provider, pool, token, token_decimals, native, 18, window).await? else {
+        return Ok(None);
+    };
+    let Some(native_pool) = deepest_pool(&provider, factory, native, stable).await? else {
+        return Ok(None);
+    };
+    let native_usd =
+        pool_twap(&provider, native_pool, native, 18, stable, deployment.stable_decimals, window).await?;
+    Ok(native_usd.map(|native_usd| TwapPrice { price_usd: price_native * native_usd, pool, via_native: true }))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::str::FromStr;
+
+    #[test]
+    fn test_tick_to_price() {
+        // Rounds towards negative infinity
+        assert_eq!(mean_tick(0, 3600, 1800), Some(2));
+        assert_eq!(mean_tick(0, -3601, 1800), Some(-3));
+        assert_eq!(mean_tick(0, 1, 0), None);
+
+        // WETH (token1) / USDC (token0) pool around tick 200311: ~2000 USDC per WETH
+        let weth = tick_to_price(200311, 18, 6, false).unwrap();
+        assert!((weth - Decimal::from(2000)).abs() < Decimal::from(1), "{}", weth);
+
+        // An 18-decimal token0 at tick 0 against a 6-decimal stable is worth 1e12
+        let price = tick_to_price(0, 18, 6, true).unwrap();
+        assert_eq!(price.round(), Decimal::from_str("1000000000000").unwrap());
+
+        assert!(uniswap_v3_deployment("ethereum").is_some());
+        assert!(uniswap_v3_deployment("solana").is_none());
+    }
+}
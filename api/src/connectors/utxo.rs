@@ -0,0 +1,546 @@
+use super::registry::{ConnectorContext, ConnectorFactory, ACCOUNT_TYPE_WALLET};
+use super::{Balance, ExchangeConnector};
+use crate::concurrency::RateLimiter;
+use crate::helpers::balance_normalization::normalize_token_balance;
+use async_trait::async_trait;
+use bitcoin::base58;
+use bitcoin::bech32::{self, segwit, Hrp};
+use bitcoin::bip32::{ChildNumber, Xpub};
+use bitcoin::hashes::{hash160, Hash};
+use bitcoin::secp256k1::{Secp256k1, VerifyOnly};
+use reqwest::Client;
+use serde::Deserialize;
+use std::error::Error;
+use thiserror::Error as ThisError;
+
+/// BTC, LTC and DOGE all have 8 decimal places (1 BTC = 100_000_000 sats)
+pub const UTXO_DECIMALS: u8 = 8;
+
+/// Consecutive unused addresses after which a derivation chain is considered exhausted (BIP44)
+pub const GAP_LIMIT: u32 = 20;
+
+/// Upper bound on addresses scanned per derivation chain, whatever the gap
+pub const MAX_ADDRESSES_PER_CHAIN: u32 = 1000;
+
+/// Extended public keys are 111 base58 characters; anything this long is not an address
+const MIN_EXTENDED_KEY_LEN: usize = 100;
+
+/// Version bytes the bip32 decoder accepts; other versions are swapped to these before decoding
+const XPUB_VERSION: [u8; 4] = [0x04, 0x88, 0xB2, 0x1E];
+
+/// Address type derived from an extended public key
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ScriptKind {
+    /// Legacy pay-to-pubkey-hash addresses (BIP44)
+    P2pkh,
+    /// Nested SegWit addresses (BIP49)
+    P2shP2wpkh,
+    /// Native SegWit addresses (BIP84)
+    P2wpkh,
+}
+
+/// An extended public key encoding a chain accepts
+#[derive(Debug)]
+pub struct ExtendedKeyVersion {
+    /// Version bytes of the base58 encoding
+    pub version: [u8; 4],
+    /// Prefix the encoding starts with (e.g. "zpub")
+    pub prefix: &'static str,
+    /// Addresses derived below keys of this version
+    pub script: ScriptKind,
+}
+
+/// Network parameters of a Bitcoin-derived UTXO chain
+#[derive(Debug)]
+pub struct UtxoChain {
+    /// Chain ID, used as the wallet kind and the holding's chain suffix (e.g. "litecoin")
+    pub id: &'static str,
+    /// Human-readable chain name
+    pub display_name: &'static str,
+    /// Native coin symbol
+    pub symbol: &'static str,
+    /// Base58 version byte of P2PKH addresses
+    pub p2pkh_version: u8,
+    /// Base58 version bytes of P2SH addresses; the first is used for derived addresses
+    pub p2sh_versions: &'static [u8],
+    /// Bech32 human-readable part of SegWit addresses, for chains with SegWit
+    pub bech32_hrp: Option<&'static str>,
+    /// Extended public key encodings accepted in `wallet_address`
+    pub extended_keys: &'static [ExtendedKeyVersion],
+    /// Environment variable overriding the Esplora API URL
+    pub esplora_env: &'static str,
+    /// Public Esplora API, if there is one
+    pub default_esplora_url: Option<&'static str>,
+}
+
+pub static BITCOIN: UtxoChain = UtxoChain {
+    id: "bitcoin",
+    display_name: "Bitcoin",
+    symbol: "BTC",
+    p2pkh_version: 0x00,
+    p2sh_versions: &[0x05],
+    bech32_hrp: Some("bc"),
+    extended_keys: &[
+        ExtendedKeyVersion { version: XPUB_VERSION, prefix: "xpub", script: ScriptKind::P2pkh },
+        ExtendedKeyVersion { version: [0x04, 0x9D, 0x7C, 0xB2], prefix: "ypub", script: ScriptKind::P2shP2wpkh },
+        ExtendedKeyVersion { version: [0x04, 0xB2, 0x47, 0x46], prefix: "zpub", script: ScriptKind::P2wpkh },
+    ],
+    esplora_env: "BITCOIN_ESPLORA_URL",
+    default_esplora_url: Some("https://blockstream.info/api"),
+};
+
+pub static LITECOIN: UtxoChain = UtxoChain {
+    id: "litecoin",
+    display_name: "Litecoin",
+    symbol: "LTC",
+    p2pkh_version: 0x30,
+    // `M...` addresses, and the legacy `3...` form some wallets still show
+    p2sh_versions: &[0x32, 0x05],
+    bech32_hrp: Some("ltc"),
+    extended_keys: &[
+        ExtendedKeyVersion { version: [0x01, 0x9D, 0xA4, 0x62], prefix: "Ltub", script: ScriptKind::P2pkh },
+        ExtendedKeyVersion { version: [0x01, 0xB2, 0x6E, 0xF6], prefix: "Mtub", script: ScriptKind::P2shP2wpkh },
+        // Hardware wallets export Litecoin keys with Bitcoin version bytes
+        ExtendedKeyVersion { version: XPUB_VERSION, prefix: "xpub", script: ScriptKind::P2pkh },
+        ExtendedKeyVersion { version: [0x04, 0xB2, 0x47, 0x46], prefix: "zpub", script: ScriptKind::P2wpkh },
+    ],
+    esplora_env: "LITECOIN_ESPLORA_URL",
+    default_esplora_url: Some("https://litecoinspace.org/api"),
+};
+
+pub static DOGECOIN: UtxoChain = UtxoChain {
+    id: "dogecoin",
+    display_name: "Dogecoin",
+    symbol: "DOGE",
+    p2pkh_version: 0x1E,
+    p2sh_versions: &[0x16],
+    bech32_hrp: None,
+    extended_keys: &[
+        ExtendedKeyVersion { version: [0x02, 0xFA, 0xCA, 0xFD], prefix: "dgub", script: ScriptKind::P2pkh },
+        ExtendedKeyVersion { version: XPUB_VERSION, prefix: "xpub", script: ScriptKind::P2pkh },
+    ],
+    esplora_env: "DOGECOIN_ESPLORA_URL",
+    default_esplora_url: None,
+};
+
+/// Every supported UTXO chain, in the order they are listed
+pub static UTXO_CHAINS: [&UtxoChain; 3] = [&BITCOIN, &LITECOIN, &DOGECOIN];
+
+impl UtxoChain {
+    /// Holding symbol of the native coin (e.g. "LTC-litecoin")
+    pub fn asset(&self) -> String {
+        format!("{}-{}", self.symbol, self.id)
+    }
+
+    /// Esplora API base URL: the chain's environment variable, else the public default
+    pub fn esplora_url(&self) -> Result<String, Box<dyn Error + Send + Sync>> {
+        match (std::env::var(self.esplora_env), self.default_esplora_url) {
+            (Ok(url), _) => Ok(url),
+            (Err(_), Some(url)) => Ok(url.to_string()),
+            (Err(_), None) => Err(format!("{} is not set; {} wallets need an Esplora API", self.esplora_env, self.display_name).into()),
+        }
+    }
+
+    /// Base58check-encode a hash under a version byte
+    fn base58_address(version: u8, hash: &[u8]) -> String {
+        let mut data = Vec::with_capacity(1 + hash.len());
+        data.push(version);
+        data.extend_from_slice(hash);
+        base58::encode_check(&data)
+    }
+
+    /// Check a single mainnet address: base58 P2PKH/P2SH, or bech32 SegWit where the chain has it
+    fn validate_address(&self, address: &str) -> Result<(), UtxoKeyError> {
+        let invalid = |reason: String| UtxoKeyError::InvalidAddress(self.display_name, reason);
+
+        if let Some(hrp) = self.bech32_hrp {
+            if address.to_lowercase().starts_with(&format!("{}1", hrp)) {
+                let (decoded_hrp, _, _) = segwit::decode(address).map_err(|e| invalid(e.to_string()))?;
+                return if decoded_hrp.as_str() == hrp {
+                    Ok(())
+                } else {
+                    Err(invalid(format!("unexpected prefix {}", decoded_hrp)))
+                };
+            }
+        }
+
+        let data = base58::decode_check(address).map_err(|e| invalid(e.to_string()))?;
+        if data.len() != 21 {
+            return Err(invalid(format!("unexpected length {}", data.len())));
+        }
+        if data[0] != self.p2pkh_version && !self.p2sh_versions.contains(&data[0]) {
+            return Err(invalid(format!("version byte {:#04x} belongs to another network", data[0])));
+        }
+        Ok(())
+    }
+
+    /// Parse a wallet address field: a mainnet address, or one of the chain's extended key encodings
+    pub fn parse_wallet_key(&self, input: &str) -> Result<UtxoWalletKey, UtxoKeyError> {
+        let input = input.trim();
+        if input.len() < MIN_EXTENDED_KEY_LEN {
+            self.validate_address(input)?;
+            return Ok(UtxoWalletKey::Address(input.to_string()));
+        }
+
+        let invalid = |reason: String| UtxoKeyError::InvalidExtendedKey(self.display_name, reason);
+        let mut data = base58::decode_check(input).map_err(|e| invalid(e.to_string()))?;
+        if data.len() != 78 {
+            return Err(invalid(format!("unexpected length {}", data.len())));
+        }
+        let version = [data[0], data[1], data[2], data[3]];
+        let script = self
+            .extended_keys
+            .iter()
+            .find(|k| k.version == version)
+            .map(|k| k.script)
+            .ok_or_else(|| {
+                let prefixes: Vec<&str> = self.extended_keys.iter().map(|k| k.prefix).collect();
+                invalid(format!("expected one of {}", prefixes.join(", ")))
+            })?;
+        // The bip32 decoder only knows xpub version bytes; the script kind is kept separately
+        data[..4].copy_from_slice(&XPUB_VERSION);
+        let xpub = Xpub::decode(&data).map_err(|e| invalid(e.to_string()))?;
+        Ok(UtxoWalletKey::Extended { xpub, script })
+    }
+
+    /// Derive the address at `chain/index` (chain 0 = receive, 1 = change) below an account xpub
+    pub fn derive_address(
+        &self,
+        secp: &Secp256k1<VerifyOnly>,
+        xpub: &Xpub,
+        script: ScriptKind,
+        chain: u32,
+        index: u32,
+    ) -> Result<String, Box<dyn Error + Send + Sync>> {
+        let path = [ChildNumber::from_normal_idx(chain)?, ChildNumber::from_normal_idx(index)?];
+        let public_key = xpub.derive_pub(secp, &path)?.to_pub();
+        let wpkh = public_key.wpubkey_hash().to_byte_array();
+
+        Ok(match script {
+            ScriptKind::P2pkh => Self::base58_address(self.p2pkh_version, &public_key.pubkey_hash().to_byte_array()),
+            ScriptKind::P2shP2wpkh => {
+                // Redeem script: OP_0 <20-byte witness program>
+                let mut redeem_script = vec![0x00, 0x14];
+                redeem_script.extend_from_slice(&wpkh);
+                Self::base58_address(self.p2sh_versions[0], &hash160::Hash::hash(&redeem_script).to_byte_array())
+            }
+            ScriptKind::P2wpkh => {
+                let hrp = self.bech32_hrp.ok_or_else(|| format!("{} has no SegWit addresses", self.display_name))?;
+                segwit::encode(Hrp::parse(hrp)?, bech32::segwit::VERSION_0, &wpkh)?
+            }
+        })
+    }
+}
+
+/// Errors for wallet addresses that are neither an address nor an extended public key of the chain
+#[derive(Debug, ThisError, PartialEq)]
+pub enum UtxoKeyError {
+    #[error("Invalid {0} extended public key: {1}")]
+    InvalidExtendedKey(&'static str, String),
+    #[error("Invalid {0} mainnet address: {1}")]
+    InvalidAddress(&'static str, String),
+}
+
+/// What a UTXO wallet account's `wallet_address` holds
+#[derive(Debug, Clone, PartialEq)]
+pub enum UtxoWalletKey {
+    /// A single address
+    Address(String),
+    /// An account-level extended public key; receive (0/i) and change (1/i) addresses are derived
+    Extended { xpub: Xpub, script: ScriptKind },
+}
+
+// ── Esplora response types ─────────────────────────────────────────────────
+
+#[derive(Debug, Deserialize)]
+struct EsploraAddress {
+    chain_stats: EsploraStats,
+    mempool_stats: EsploraStats,
+}
+
+#[derive(Debug, Deserialize)]
+struct EsploraStats {
+    tx_count: u64,
+}
+
+#[derive(Debug, Deserialize)]
+struct EsploraUtxo {
+    /// Output value in the chain's smallest unit (sats, litoshis, koinus)
+    value: u64,
+}
+
+fn sum_utxos(utxos: &[EsploraUtxo]) -> u64 {
+    utxos.iter().map(|u| u.value).sum()
+}
+
+// ── UtxoConnector ──────────────────────────────────────────────────────────
+
+/// Wallet connector for Bitcoin and its forks, reading UTXOs from an Esplora-compatible API
+/// (Blockstream, mempool.space, litecoinspace.org, or a self-hosted electrs).
+///
+/// A single address is read directly. For an extended public key, receive and change addresses
+/// are derived until [`GAP_LIMIT`] consecutive addresses have no transactions, and the UTXOs of
+/// every used address are summed into one holding of the chain's native coin.
+pub struct UtxoConnector {
+    chain: &'static UtxoChain,
+    key: UtxoWalletKey,
+    esplora_url: String,
+    http_client: Client,
+}
+
+impl UtxoConnector {
+    /// Create a new UTXO connector.
+    ///
+    /// # Arguments
+    /// * `chain` — Network parameters (e.g., [`BITCOIN`])
+    /// * `key` — Parsed wallet address or extended public key (see [`UtxoChain::parse_wallet_key`])
+    /// * `esplora_url` — Esplora API base URL (e.g., `https://blockstream.info/api`)
+    pub fn new(chain: &'static UtxoChain, key: UtxoWalletKey, esplora_url: String) -> Self {
+        Self {
+            chain,
+            key,
+            esplora_url: esplora_url.trim_end_matches('/').to_string(),
+            http_client: Client::new(),
+        }
+    }
+
+    /// Number of confirmed and mempool transactions involving an address
+    async fn fetch_tx_count(&self, address: &str) -> Result<u64, Box<dyn Error + Send + Sync>> {
+        let url = format!("{}/address/{}", self.esplora_url, address);
+        let stats: EsploraAddress = self.http_client.get(&url).send().await?.error_for_status()?.json().await?;
+        Ok(stats.chain_stats.tx_count + stats.mempool_stats.tx_count)
+    }
+
+    /// Sum of an address's unspent outputs, in the chain's smallest unit
+    async fn fetch_utxo_total(&self, address: &str) -> Result<u64, Box<dyn Error + Send + Sync>> {
+        let url = format!("{}/address/{}/utxo", self.esplora_url, address);
+        let utxos: Vec<EsploraUtxo> = self.http_client.get(&url).send().await?.error_for_status()?.json().await?;
+        Ok(sum_utxos(&utxos))
+    }
+
+    /// Scan one derivation chain up to the gap limit, returning its total and used address count
+    async fn scan_chain(
+        &self,
+        secp: &Secp256k1<VerifyOnly>,
+        xpub: &Xpub,
+        script: ScriptKind,
+        chain: u32,
+        rate_limiter: &RateLimiter,
+    ) -> Result<(u64, u32), Box<dyn Error + Send + Sync>> {
+        let mut total = 0;
+        let mut used = 0;
+        let mut unused_run = 0;
+
+        for index in 0..MAX_ADDRESSES_PER_CHAIN {
+            if unused_run >= GAP_LIMIT {
+                break;
+            }
+            let address = self.chain.derive_address(secp, xpub, script, chain, index)?;
+            let _permit = rate_limiter.acquire().await?;
+            if self.fetch_tx_count(&address).await? == 0 {
+                unused_run += 1;
+                continue;
+            }
+            unused_run = 0;
+            used += 1;
+            total += self.fetch_utxo_total(&address).await?;
+        }
+
+        Ok((total, used))
+    }
+}
+
+#[async_trait]
+impl ExchangeConnector for UtxoConnector {
+    async fn fetch_spot_balances(&self) -> Result<Vec<Balance>, Box<dyn Error + Send + Sync>> {
+        let rate_limiter = RateLimiter::esplora();
+
+        let total = match &self.key {
+            UtxoWalletKey::Address(address) => {
+                tracing::info!("Fetching {} balance for address {}", self.chain.display_name, address);
+                let _permit = rate_limiter.acquire().await?;
+                self.fetch_utxo_total(address).await?
+            }
+            UtxoWalletKey::Extended { xpub, script } => {
+                let secp = Secp256k1::verification_only();
+                let (receive_total, receive_used) = self.scan_chain(&secp, xpub, *script, 0, &rate_limiter).await?;
+                let (change_total, change_used) = self.scan_chain(&secp, xpub, *script, 1, &rate_limiter).await?;
+                tracing::info!(
+                    "Scanned {} extended key {}: {} receive and {} change addresses used",
+                    self.chain.display_name,
+                    xpub.fingerprint(),
+                    receive_used,
+                    change_used
+                );
+                receive_total + change_total
+            }
+        };
+
+        if total == 0 {
+            return Ok(Vec::new());
+        }
+
+        let raw = total.to_string();
+        let normalized = normalize_token_balance(&raw, UTXO_DECIMALS).unwrap_or(raw);
+
+        Ok(vec![Balance {
+            asset: self.chain.asset(),
+            quantity: normalized.clone(),
+            available: normalized,
+            frozen: "0".to_string(),
+            decimals: Some(UTXO_DECIMALS),
+            holding_source: None,
+        }])
+    }
+}
+
+/// Builds [`UtxoConnector`]s for wallet accounts whose wallet kind is the chain's ID
+/// (e.g. `exchange_name` "litecoin")
+pub struct UtxoFactory(pub &'static UtxoChain);
+
+#[async_trait]
+impl ConnectorFactory for UtxoFactory {
+    fn account_type(&self) -> &'static str {
+        ACCOUNT_TYPE_WALLET
+    }
+
+    fn name(&self) -> &'static str {
+        self.0.id
+    }
+
+    fn requires_wallet_address(&self) -> bool {
+        true
+    }
+
+    fn validate_wallet_address(&self, address: &str) -> Result<(), String> {
+        self.0.parse_wallet_key(address).map(|_| ()).map_err(|e| e.to_string())
+    }
+
+    async fn create(
+        &self,
+        ctx: &ConnectorContext<'_>,
+    ) -> Result<Box<dyn ExchangeConnector>, Box<dyn Error + Send + Sync>> {
+        let esplora_url = self.0.esplora_url()?;
+        let key = self.0.parse_wallet_key(&ctx.wallet_address()?)?;
+        Ok(Box::new(UtxoConnector::new(self.0, key, esplora_url)))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    // BIP44/49/84 test vectors for the "abandon ... about" mnemonic, account 0
+    const XPUB: &str = "xpub6BosfCnifzxcFwrSzQiqu2DBVTshkCXacvNsWGYJVVhhawA7d4R5WSWGFNbi8Aw6ZRc1brxMyWMzG3DSSSSoekkudhUd9yLb6qx39T9nMdj";
+    const YPUB: &str = "ypub6Ww3ibxVfGzLrAH1PNcjyAWenMTbbAosGNB6VvmSEgytSER9azLDWCxoJwW7Ke7icmizBMXrzBx9979FfaHxHcrArf3zbeJJJUZPf663zsP";
+    const ZPUB: &str = "zpub6rFR7y4Q2AijBEqTUquhVz398htDFrtymD9xYYfG1m4wAcvPhXNfE3EfH1r1ADqtfSdVCToUG868RvUUkgDKf31mGDtKsAYz2oz2AGutZYs";
+
+    fn derive(chain: &UtxoChain, key: &str, branch: u32, index: u32) -> String {
+        let secp = Secp256k1::verification_only();
+        match chain.parse_wallet_key(key).unwrap() {
+            UtxoWalletKey::Extended { xpub, script } => {
+                chain.derive_address(&secp, &xpub, script, branch, index).unwrap()
+            }
+            other => panic!("expected an extended key, got {:?}", other),
+        }
+    }
+
+    /// Re-encode an extended key under another chain's version bytes
+    fn with_version(key: &str, version: [u8; 4]) -> String {
+        let mut data = base58::decode_check(key).unwrap();
+        data[..4].copy_from_slice(&version);
+        base58::encode_check(&data)
+    }
+
+    /// Base58 payload of an address without its version byte
+    fn hash_of(address: &str) -> Vec<u8> {
+        base58::decode_check(address).unwrap()[1..].to_vec()
+    }
+
+    #[test]
+    fn test_derive_addresses_from_extended_keys() {
+        assert_eq!(derive(&BITCOIN, XPUB, 0, 0), "1LqBGSKuX5yYUonjxT5qGfpUsXKYYWeabA");
+        assert_eq!(derive(&BITCOIN, YPUB, 0, 0), "37VucYSaXLCAsxYyAPfbSi9eh4iEcbShgf");
+        assert_eq!(derive(&BITCOIN, ZPUB, 0, 0), "bc1qcr8te4kr609gcawutmrza0j4xv80jy8z306fyu");
+        assert_eq!(derive(&BITCOIN, ZPUB, 0, 1), "bc1qnjg0jd8228aq7egyzacy8cys3knf9xvrerkf9g");
+        assert_eq!(derive(&BITCOIN, ZPUB, 1, 0), "bc1q8c6fshw2dlwun7ekn9qwf37cu2rn755upcp6el");
+    }
+
+    #[test]
+    fn test_derive_litecoin_and_dogecoin_addresses() {
+        let btc = derive(&BITCOIN, XPUB, 0, 0);
+
+        // Same key under Litecoin/Dogecoin versions: same hash, the chain's own address prefix
+        let ltc = derive(&LITECOIN, &with_version(XPUB, LITECOIN.extended_keys[0].version), 0, 0);
+        assert!(ltc.starts_with('L'));
+        assert_eq!(hash_of(&ltc), hash_of(&btc));
+
+        let nested = derive(&LITECOIN, &with_version(YPUB, LITECOIN.extended_keys[1].version), 0, 0);
+        assert!(nested.starts_with('M'));
+        assert_eq!(hash_of(&nested), hash_of(&derive(&BITCOIN, YPUB, 0, 0)));
+
+        let native = derive(&LITECOIN, ZPUB, 0, 0);
+        assert!(native.starts_with("ltc1q"));
+        let (_, _, program) = segwit::decode(&native).unwrap();
+        let (_, _, btc_program) = segwit::decode(&derive(&BITCOIN, ZPUB, 0, 0)).unwrap();
+        assert_eq!(program, btc_program);
+
+        let doge = derive(&DOGECOIN, &with_version(XPUB, DOGECOIN.extended_keys[0].version), 0, 0);
+        assert!(doge.starts_with('D'));
+        assert_eq!(hash_of(&doge), hash_of(&btc));
+        // Plain xpubs are read as BIP44 keys on Dogecoin
+        assert_eq!(derive(&DOGECOIN, XPUB, 0, 0), doge);
+    }
+
+    #[test]
+    fn test_parse_wallet_key() {
+        assert!(matches!(
+            BITCOIN.parse_wallet_key(" bc1qcr8te4kr609gcawutmrza0j4xv80jy8z306fyu "),
+            Ok(UtxoWalletKey::Address(_))
+        ));
+        assert!(matches!(
+            BITCOIN.parse_wallet_key("1LqBGSKuX5yYUonjxT5qGfpUsXKYYWeabA"),
+            Ok(UtxoWalletKey::Address(_))
+        ));
+        assert!(matches!(
+            BITCOIN.parse_wallet_key(ZPUB),
+            Ok(UtxoWalletKey::Extended { script: ScriptKind::P2wpkh, .. })
+        ));
+        // Testnet addresses, EVM addresses and corrupted keys are rejected
+        assert!(matches!(
+            BITCOIN.parse_wallet_key("tb1qw508d6qejxtdg4y5r3zarvary0c5xw7kxpjzsx"),
+            Err(UtxoKeyError::InvalidAddress(..))
+        ));
+        assert!(BITCOIN.parse_wallet_key("0x742d35Cc6634C0532925a3b844Bc454e4438f44e").is_err());
+        assert!(matches!(
+            BITCOIN.parse_wallet_key(&format!("{}x", &ZPUB[..ZPUB.len() - 1])),
+            Err(UtxoKeyError::InvalidExtendedKey(..))
+        ));
+    }
+
+    #[test]
+    fn test_addresses_are_checked_against_the_chain() {
+        let doge = derive(&DOGECOIN, XPUB, 0, 0);
+        let ltc = derive(&LITECOIN, ZPUB, 0, 0);
+
+        assert!(DOGECOIN.parse_wallet_key(&doge).is_ok());
+        assert!(LITECOIN.parse_wallet_key(&ltc).is_ok());
+        // Another chain's addresses and keys are rejected
+        assert!(matches!(LITECOIN.parse_wallet_key(&doge), Err(UtxoKeyError::InvalidAddress("Litecoin", _))));
+        assert!(BITCOIN.parse_wallet_key(&ltc).is_err());
+        assert!(DOGECOIN.parse_wallet_key("bc1qcr8te4kr609gcawutmrza0j4xv80jy8z306fyu").is_err());
+        assert!(matches!(DOGECOIN.parse_wallet_key(ZPUB), Err(UtxoKeyError::InvalidExtendedKey("Dogecoin", _))));
+    }
+
+    #[test]
+    fn test_sum_utxos() {
+        let utxos: Vec<EsploraUtxo> = serde_json::from_str(
+            r#"[{"txid":"ab","vout":0,"value":150000000,"status":{"confirmed":true}},
+                {"txid":"cd","vout":1,"value":2500,"status":{"confirmed":false}}]"#,
+        )
+        .unwrap();
+        assert_eq!(sum_utxos(&utxos), 150_002_500);
+        assert_eq!(normalize_token_balance("150002500", UTXO_DECIMALS).unwrap(), "1.500025");
+        assert_eq!(LITECOIN.asset(), "LTC-litecoin");
+    }
+}
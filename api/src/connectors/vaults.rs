@@ -0,0 +1,222 @@
+//! Yield vault deposits of EVM wallets, valued through the vault's share price.
+//!
+//! Vaults are configured in the `yield_vaults` table. A wallet's share balance is converted into
+//! the vault's underlying token with ERC-4626 `asset`/`convertToAssets`; vaults that predate
+//! ERC-4626 fall back to Yearn v2 (`token`/`pricePerShare`) or Beefy
+//! (`want`/`getPricePerFullShare`). The result is reported as an [`HOLDING_SOURCE_EARN`] holding
+//! of the underlying token, so an sDAI or yvUSDC deposit is valued as DAI or USDC instead of an
+//! unpriced share symbol.
+
+use super::defi::{DefiAdapter, DefiContext, DefiPositions};
+use super::evm::EvmChain;
+use super::Balance;
+use crate::domain::HOLDING_SOURCE_EARN;
+use crate::entities::yield_vaults;
+use crate::helpers::balance_normalization::normalize_token_balance;
+use alloy::{
+    primitives::{Address, U256},
+    providers::{Provider, ProviderBuilder},
+    sol,
+};
+use async_trait::async_trait;
+use sea_orm::{ColumnTrait, DatabaseConnection, EntityTrait, QueryFilter};
+use std::collections::HashSet;
+use std::error::Error;
+
+/// Protocol name used in the `defi_protocols` account setting
+pub const DEFI_PROTOCOL_VAULTS: &str = "vaults";
+
+/// Decimals of Beefy's `getPricePerFullShare`
+const BEEFY_PRICE_DECIMALS: u8 = 18;
+
+sol! {
+    #[sol(rpc)]
+    contract Erc4626Vault {
+        function asset() external view returns (address);
+        function convertToAssets(uint256 shares) external view returns (uint256);
+    }
+
+    /// Yearn v2 vaults; `pricePerShare` is scaled by the vault's decimals
+    #[sol(rpc)]
+    contract YearnV2Vault {
+        function token() external view returns (address);
+        function pricePerShare() external view returns (uint256);
+    }
+
+    /// Beefy vaults; `getPricePerFullShare` is scaled by 1e18
+    #[sol(rpc)]
+    contract BeefyVault {
+        function want() external view returns (address);
+        function getPricePerFullShare() external view returns (uint256);
+    }
+
+    #[sol(rpc)]
+    contract VaultToken {
+        function balanceOf(address owner) external view returns (uint256);
+        function decimals() external view returns (uint8);
+        function symbol() external view returns (string);
+    }
+}
+
+/// A vault from the `yield_vaults` table
+#[derive(Debug, Clone, PartialEq)]
+pub struct YieldVault {
+    pub name: String,
+    /// Lowercase vault (share token) address
+    pub vault_address: String,
+}
+
+/// Load a chain's active vaults from `yield_vaults`, with lowercase addresses.
+///
+/// Falls back to no vaults on a DB error, so shares are simply not resolved for that sync.
+async fn load_yield_vaults(db: &DatabaseConnection, chain: &str) -> Vec<YieldVault> {
+    match yield_vaults::Entity::find()
+        .filter(yield_vaults::Column::Chain.eq(chain))
+        .filter(yield_vaults::Column::IsActive.eq(true))
+        .all(db)
+        .await
+    {
+        Ok(rows) => rows
+            .into_iter()
+            .map(|row| YieldVault { name: row.name, vault_address: row.vault_address.to_lowercase() })
+            .collect(),
+        Err(e) => {
+            tracing::warn!("Failed to load yield vaults on {} from DB: {}", chain, e);
+            Vec::new()
+        }
+    }
+}
+
+/// [`DefiAdapter`] for the vaults in the `yield_vaults` table
+pub struct VaultAdapter;
+
+#[async_trait]
+impl DefiAdapter for VaultAdapter {
+    fn protocol(&self) -> &'static str {
+        DEFI_PROTOCOL_VAULTS
+    }
+
+    /// Vaults are configured per chain, so any chain may have some
+    fn supports_chain(&self, _chain: &str) -> bool {
+        true
+    }
+
+    fn aggregator_ids(&self) -> &'static [&'static str] {
+        &["yearn2", "yearn3", "beefy"]
+    }
+
+    /// Share tokens of the chain's vaults
+    async fn position_tokens(&self, db: &DatabaseConnection, chain: &EvmChain) -> HashSet<String> {
+        load_yield_vaults(db, chain.name()).await.into_iter().map(|vault| vault.vault_address).collect()
+    }
+
+    async fn fetch_positions(
+        &self,
+        ctx: &DefiContext<'_>,
+        wallet: Address,
+    ) -> Result<DefiPositions, Box<dyn Error + Send + Sync>> {
+        let vaults = load_yield_vaults(ctx.db, ctx.chain.name()).await;
+        if vaults.is_empty() {
+            return Ok(DefiPositions::default());
+        }
+        let balances = fetch_vault_positions(ctx.chain, ctx.chain.rpc_url(), wallet, &vaults).await?;
+        Ok(DefiPositions { balances, positions: Vec::new() })
+    }
+}
+
+/// Underlying amount of `shares` at a share price scaled by `10^price_decimals`
+fn shares_to_assets(shares: U256, price_per_share: U256, price_decimals: u8) -> U256 {
+    shares * price_per_share / U256::from(10).pow(U256::from(price_decimals))
+}
+
+/// Underlying token and raw amount of `shares` of a vault, trying ERC-4626 first and then the
+/// Yearn v2 and Beefy share-price functions
+async fn underlying_amount<P: Provider + Clone>(
+    provider: &P,
+    vault_address: Address,
+    shares: U256,
+) -> Result<(Address, U256), Box<dyn Error + Send + Sync>> {
+    let erc4626 = Erc4626Vault::new(vault_address, provider.clone());
+    if let (Ok(asset), Ok(assets)) = (erc4626.asset().call().await, erc4626.convertToAssets(shares).call().await) {
+        return Ok((asset, assets));
+    }
+
+    let yearn = YearnV2Vault::new(vault_address, provider.clone());
+    if let (Ok(token), Ok(price)) = (yearn.token().call().await, yearn.pricePerShare().call().await) {
+        let decimals = VaultToken::new(vault_address, provider.clone()).decimals().call().await?;
+        return Ok((token, shares_to_assets(shares, price, decimals)));
+    }
+
+    let beefy = BeefyVault::new(vault_address, provider.clone());
+    match (beefy.want().call().await, beefy.getPricePerFullShare().call().await) {
+        (Ok(want), Ok(price)) => Ok((want, shares_to_assets(shares, price, BEEFY_PRICE_DECIMALS))),
+        _ => Err("vault answers none of the ERC-4626, Yearn v2 or Beefy share-price functions".into()),
+    }
+}
+
+/// `wallet`'s deposit in one vault as a holding of the underlying token; `None` without shares
+async fn vault_position<P: Provider + Clone>(
+    provider: &P,
+    chain: &EvmChain,
+    vault: &YieldVault,
+    wallet: Address,
+) -> Result<Option<Balance>, Box<dyn Error + Send + Sync>> {
+    let vault_address: Address = vault.vault_address.parse()?;
+    let shares = VaultToken::new(vault_address, provider.clone()).balanceOf(wallet).call().await?;
+    if shares.is_zero() {
+        return Ok(None);
+    }
+
+    let (underlying, raw_amount) = underlying_amount(provider, vault_address, shares).await?;
+    let token = VaultToken::new(underlying, provider.clone());
+    let symbol = token.symbol().call().await?;
+    let decimals = token.decimals().call().await?;
+    let quantity = normalize_token_balance(&raw_amount.to_string(), decimals)?;
+    Ok(Some(Balance {
+        asset: format!("{}-{}", symbol, chain.name()),
+        quantity: quantity.clone(),
+        available: quantity,
+        frozen: "0".to_string(),
+        decimals: Some(decimals),
+        holding_source: Some(HOLDING_SOURCE_EARN.to_string()),
+    }))
+}
+
+/// Underlying tokens of `wallet`'s deposits in `vaults` on `chain`.
+///
+/// A vault that fails to read is logged and left out; the others are still reported.
+pub async fn fetch_vault_positions(
+    chain: &EvmChain,
+    rpc_url: &str,
+    wallet: Address,
+    vaults: &[YieldVault],
+) -> Result<Vec<Balance>, Box<dyn Error + Send + Sync>> {
+    let provider = ProviderBuilder::new().connect_http(rpc_url.parse()?);
+
+    let mut balances = Vec::new();
+    for vault in vaults {
+        match vault_position(&provider, chain, vault, wallet).await {
+            Ok(Some(balance)) => balances.push(balance),
+            Ok(None) => {}
+            Err(e) => tracing::error!("Failed to read vault {} on {}: {}", vault.name, chain.name(), e),
+        }
+    }
+    Ok(balances)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_shares_to_assets() {
+        // 1,000 yvUSDC (6 decimals) at a share price of 1.084512
+        let shares = U256::from(1_000_000_000u64);
+        assert_eq!(shares_to_assets(shares, U256::from(1_084_512u64), 6), U256::from(1_084_512_000u64));
+
+        // 2.5 Beefy shares at 1.2 want per share (1e18-scaled price)
+        let shares = U256::from(2_500_000_000_000_000_000u128);
+        let price = U256::from(1_200_000_000_000_000_000u128);
+        assert_eq!(shares_to_assets(shares, price, BEEFY_PRICE_DECIMALS), U256::from(3_000_000_000_000_000_000u128));
+    }
+}
@@ -2,7 +2,12 @@
 ///
 /// Represents computed allocations after aggregating holdings and enriching with price data.
 
+use super::holdings::HOLDING_SOURCE_BORROWED;
+use super::settings::DebtMode;
+use rust_decimal::Decimal;
 use serde::{Deserialize, Serialize};
+use std::collections::BTreeMap;
+use std::str::FromStr;
 use utoipa::ToSchema;
 
 /// A single asset holding in a portfolio allocation with complete pricing information.
@@ -54,6 +59,649 @@ pub struct AllocationItem {
     /// Flag indicating if this asset has no price data
     #[serde(default)]
     pub unpriced: bool,
+
+    /// Flag indicating the price is older than the staleness threshold (`PRICE_MAX_AGE_HOURS`)
+    #[serde(default)]
+    pub stale_price: bool,
+
+    /// Flag indicating the price provider no longer lists the asset; it is valued at its last
+    /// known price, which is not flagged as stale
+    #[serde(default)]
+    pub delisted: bool,
+
+    /// Time of the price used (RFC 3339); None if unpriced
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub price_as_of: Option<String>,
+
+    /// Quantity per holding source (e.g. {"spot": "1.2", "earn": "0.5"}).
+    /// Only present when part of the quantity is held outside spot.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub quantity_by_source: Option<BTreeMap<String, String>>,
+
+    /// Quantity per venue: the exchange of exchange accounts (e.g. {"okx": "1.2"}), or
+    /// "self_custody" for wallets
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub quantity_by_venue: Option<BTreeMap<String, String>>,
+
+    /// Underlying asset when this is a wrapped or liquid staking token (e.g. "ETH" for wstETH),
+    /// from the `derivative_assets` table
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub underlying_asset: Option<String>,
+
+    /// Quantity in units of `underlying_asset` (quantity × exchange rate)
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub underlying_quantity: Option<String>,
+
+    /// Maturity date (YYYY-MM-DD) of fixed-yield holdings such as Pendle PT/YT tokens
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub maturity: Option<String>,
+
+    /// Category of the asset (e.g. "l1", "defi", "stablecoin"), from the `assets` table
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub category: Option<String>,
+
+    /// `value_usd` in the user's base currency; set on API responses only, not stored
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub value: Option<f64>,
+}
+
+impl AllocationItem {
+    /// Whether the item is a stablecoin (see [`is_stablecoin`])
+    pub fn is_stablecoin(&self) -> bool {
+        is_stablecoin(&self.asset, self.category.as_deref())
+    }
+
+    /// Borrowed part of the quantity (negative), when the holding has debt
+    pub fn borrowed_quantity(&self) -> Option<&str> {
+        self.quantity_by_source.as_ref()?.get(HOLDING_SOURCE_BORROWED).map(String::as_str)
+    }
+
+    /// USD value owed on this holding; zero when unpriced
+    pub fn debt_usd(&self) -> f64 {
+        if self.unpriced {
+            return 0.0;
+        }
+        debt_value_usd(self.borrowed_quantity(), self.price_usd)
+    }
+}
+
+/// USD value owed on a borrowed quantity (stored negative) at `price_usd`, as a positive amount
+pub fn debt_value_usd(borrowed_quantity: Option<&str>, price_usd: Option<f64>) -> f64 {
+    match (borrowed_quantity.and_then(|q| q.parse::<f64>().ok()), price_usd) {
+        (Some(quantity), Some(price)) => (-quantity * price).max(0.0),
+        _ => 0.0,
+    }
+}
+
+/// Value of a portfolio's assets before debt, the value owed, and their difference.
+#[derive(Debug, Clone, Copy, Default, Serialize, Deserialize, PartialEq, ToSchema)]
+pub struct DebtSummary {
+    /// Value of the priced holdings before debt
+    pub gross_value_usd: f64,
+    /// Value owed on borrowed holdings (positive)
+    pub debt_usd: f64,
+    /// Gross value minus debt
+    pub net_value_usd: f64,
+}
+
+impl DebtSummary {
+    /// Totals over `(value_usd, debt_usd)` of priced holdings, whose values are net of their debt
+    pub fn from_values(values: impl IntoIterator<Item = (f64, f64)>) -> Self {
+        let (net, debt) = values.into_iter().fold((0.0, 0.0), |(net, debt), (v, d)| (net + v, debt + d));
+        Self { gross_value_usd: net + debt, debt_usd: debt, net_value_usd: net }
+    }
+
+    /// Split of the priced allocation items
+    pub fn of_allocation(items: &[AllocationItem]) -> Self {
+        Self::from_values(items.iter().filter(|i| !i.unpriced).map(|i| (i.value_usd, i.debt_usd())))
+    }
+
+    /// Total portfolio value under a debt mode
+    pub fn total(&self, mode: DebtMode) -> f64 {
+        match mode {
+            DebtMode::Net => self.net_value_usd,
+            DebtMode::Gross => self.gross_value_usd,
+        }
+    }
+}
+
+/// Set the weights of priced items relative to the total under `mode`; returns the debt split.
+///
+/// In gross mode an item weighs its value before debt, so a fully borrowed asset weighs zero.
+pub fn weigh_allocation(items: &mut [AllocationItem], mode: DebtMode) -> DebtSummary {
+    let summary = DebtSummary::of_allocation(items);
+    let total = summary.total(mode);
+    for item in items.iter_mut().filter(|i| !i.unpriced) {
+        let value = match mode {
+            DebtMode::Net => item.value_usd,
+            DebtMode::Gross => item.value_usd + item.debt_usd(),
+        };
+        item.weight = if total > 0.0 { value / total * 100.0 } else { 0.0 };
+    }
+    summary
+}
+
+/// Portfolio-level warning that part of an allocation is valued with stale prices.
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, ToSchema)]
+pub struct PriceStalenessWarning {
+    /// Assets priced with stale data, largest weight first
+    pub assets: Vec<String>,
+    /// Value of the stale-priced holdings in USD
+    pub stale_value_usd: f64,
+    /// Percentage of total portfolio value (0-100) priced with stale data
+    pub stale_weight: f64,
+    /// Time of the oldest price used (RFC 3339)
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub oldest_price_as_of: Option<String>,
+}
+
+/// Warning for the stale-priced items of a weighed allocation.
+///
+/// `None` when no item is stale or when the stale items weigh less than `min_weight`
+/// percent. Debt counts by its absolute weight.
+pub fn price_staleness_warning(items: &[AllocationItem], min_weight: f64) -> Option<PriceStalenessWarning> {
+    let mut stale: Vec<&AllocationItem> = items.iter().filter(|i| i.stale_price && !i.unpriced).collect();
+    if stale.is_empty() {
+        return None;
+    }
+    let stale_weight: f64 = stale.iter().map(|i| i.weight.abs()).sum();
+    if stale_weight < min_weight {
+        return None;
+    }
+
+    stale.sort_by(|a, b| b.weight.abs().partial_cmp(&a.weight.abs()).unwrap_or(std::cmp::Ordering::Equal));
+    Some(PriceStalenessWarning {
+        assets: stale.iter().map(|i| i.asset.clone()).collect(),
+        stale_value_usd: stale.iter().map(|i| i.value_usd).sum(),
+        stale_weight,
+        oldest_price_as_of: stale.iter().filter_map(|i| i.price_as_of.clone()).min(),
+    })
+}
+
+/// Exposure to one asset across an allocation, counting wrapped and liquid staking tokens
+/// as their underlying asset (e.g. ETH, WETH and wstETH together as ETH).
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, ToSchema)]
+pub struct AssetExposure {
+    /// Underlying asset symbol
+    pub asset: String,
+    /// Total quantity in units of the asset (decimal string)
+    pub quantity: String,
+    /// Total value in USD of the holdings counted towards the asset
+    pub value_usd: f64,
+    /// Percentage of total portfolio value (0-100)
+    pub weight: f64,
+}
+
+/// Group allocation items by underlying asset (the item's own asset when it has none).
+///
+/// Quantities are summed in underlying units; values and weights use the items' own market
+/// values, so unpriced items add quantity but no value. Sorted by value, largest first.
+pub fn exposure_by_underlying(items: &[AllocationItem]) -> Vec<AssetExposure> {
+    let mut groups: BTreeMap<String, (Decimal, f64)> = BTreeMap::new();
+    for item in items {
+        let (asset, quantity) = match (&item.underlying_asset, &item.underlying_quantity) {
+            (Some(asset), Some(quantity)) => (asset, quantity),
+            _ => (&item.asset, &item.quantity),
+        };
+        let group = groups.entry(asset.clone()).or_insert((Decimal::ZERO, 0.0));
+        group.0 += Decimal::from_str(quantity).unwrap_or(Decimal::ZERO);
+        if !item.unpriced {
+            group.1 += item.value_usd;
+        }
+    }
+
+    let total: f64 = groups.values().map(|(_, value)| value).sum();
+    let mut exposures: Vec<AssetExposure> = groups
+        .into_iter()
+        .map(|(asset, (quantity, value_usd))| AssetExposure {
+            asset,
+            quantity: quantity.normalize().to_string(),
+            value_usd,
+            weight: if total > 0.0 { value_usd / total * 100.0 } else { 0.0 },
+        })
+        .collect();
+    exposures.sort_by(|a, b| b.value_usd.partial_cmp(&a.value_usd).unwrap_or(std::cmp::Ordering::Equal));
+    exposures
+}
+
+/// Fixed-yield holdings (e.g. Pendle PT/YT tokens) maturing on one date.
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, ToSchema)]
+pub struct MaturityBucket {
+    /// Maturity date (YYYY-MM-DD)
+    pub maturity: String,
+    /// Assets maturing on this date
+    pub assets: Vec<String>,
+    /// Total value in USD of the priced holdings
+    pub value_usd: f64,
+    /// Percentage of total portfolio value (0-100)
+    pub weight: f64,
+}
+
+/// Group allocation items that carry a maturity by maturity date, earliest first.
+///
+/// Weights are summed from the items, so they stay relative to the whole portfolio.
+pub fn fixed_yield_by_maturity(items: &[AllocationItem]) -> Vec<MaturityBucket> {
+    let mut buckets: BTreeMap<&str, MaturityBucket> = BTreeMap::new();
+    for item in items {
+        let Some(maturity) = item.maturity.as_deref() else {
+            continue;
+        };
+        let bucket = buckets.entry(maturity).or_insert_with(|| MaturityBucket {
+            maturity: maturity.to_string(),
+            assets: Vec::new(),
+            value_usd: 0.0,
+            weight: 0.0,
+        });
+        bucket.assets.push(item.asset.clone());
+        if !item.unpriced {
+            bucket.value_usd += item.value_usd;
+            bucket.weight += item.weight;
+        }
+    }
+    buckets.into_values().collect()
+}
+
+/// Category of allocation items whose asset has none
+pub const UNCATEGORIZED: &str = "uncategorized";
+
+/// Category of USD-pegged stablecoins
+pub const CATEGORY_STABLECOIN: &str = "stablecoin";
+
+/// Stablecoins recognised by symbol when their asset has no category
+const KNOWN_STABLECOINS: &[&str] = &["USDT", "USDC", "DAI", "FDUSD", "TUSD", "USDE", "USDS", "PYUSD", "BUSD", "USDP", "FRAX", "GHO"];
+
+/// Whether an asset is a stablecoin: in the "stablecoin" category, or, when it has no
+/// category, one of the well-known stablecoin symbols
+pub fn is_stablecoin(symbol: &str, category: Option<&str>) -> bool {
+    match category {
+        Some(category) => category == CATEGORY_STABLECOIN,
+        None => KNOWN_STABLECOINS.iter().any(|s| s.eq_ignore_ascii_case(&target_symbol(symbol))),
+    }
+}
+
+/// Outcome of checking an allocation against a guardrail.
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, PartialEq, Eq, ToSchema)]
+#[serde(rename_all = "snake_case")]
+pub enum GuardrailStatus {
+    /// Within the guardrail
+    Pass,
+    /// Outside the guardrail
+    Fail,
+    /// The portfolio has no such guardrail
+    NotSet,
+}
+
+/// Share of an allocation held in stablecoins, checked against the `stablecoin_min` guardrail.
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, ToSchema)]
+pub struct StablecoinExposure {
+    /// Stablecoins held, largest value first
+    pub assets: Vec<String>,
+    /// Total value in USD of the priced stablecoin holdings
+    pub value_usd: f64,
+    /// Percentage of total portfolio value (0-100)
+    pub weight: f64,
+    /// The portfolio's `stablecoin_min` guardrail (0-100)
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub minimum_weight: Option<f64>,
+    /// "pass" when `weight` is at least `minimum_weight`, "fail" below it, "not_set" without
+    /// the guardrail
+    pub status: GuardrailStatus,
+}
+
+/// Stablecoin share of a weighed allocation against the minimum `minimum_weight` percent.
+pub fn stablecoin_exposure(items: &[AllocationItem], minimum_weight: Option<f64>) -> StablecoinExposure {
+    let mut stablecoins: Vec<&AllocationItem> = items.iter().filter(|i| i.is_stablecoin()).collect();
+    stablecoins.sort_by(|a, b| b.value_usd.partial_cmp(&a.value_usd).unwrap_or(std::cmp::Ordering::Equal));
+    let priced = stablecoins.iter().filter(|i| !i.unpriced);
+    let weight: f64 = priced.clone().map(|i| i.weight).sum();
+    StablecoinExposure {
+        assets: stablecoins.iter().map(|i| i.asset.clone()).collect(),
+        value_usd: priced.map(|i| i.value_usd).sum(),
+        weight,
+        minimum_weight,
+        status: match minimum_weight {
+            Some(minimum) if weight >= minimum => GuardrailStatus::Pass,
+            Some(_) => GuardrailStatus::Fail,
+            None => GuardrailStatus::NotSet,
+        },
+    }
+}
+
+/// Assets the `max_alt_cap` guardrail does not apply to, besides stablecoins
+pub const MAJOR_ASSETS: &[&str] = &["BTC", "ETH"];
+
+/// HHI from which a portfolio counts as moderately / highly concentrated, as in the usual
+/// market concentration bands
+const HHI_MODERATE: f64 = 1500.0;
+const HHI_HIGH: f64 = 2500.0;
+
+/// Concentration band of an HHI
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, PartialEq, Eq, ToSchema)]
+#[serde(rename_all = "snake_case")]
+pub enum ConcentrationLevel {
+    /// HHI below 1500
+    Low,
+    /// HHI from 1500 to 2500
+    Moderate,
+    /// HHI above 2500
+    High,
+}
+
+/// Concentration of value in few assets, with the largest alt checked against the
+/// `max_alt_cap` guardrail.
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, ToSchema)]
+pub struct ConcentrationRisk {
+    /// Herfindahl-Hirschman index: sum of squared weights (0-10000; 10000 is a single asset)
+    pub hhi: f64,
+    /// Risk score (0-100): the HHI over 100
+    pub score: f64,
+    pub level: ConcentrationLevel,
+    /// Number of equally weighted assets with the same HHI
+    pub effective_assets: f64,
+    /// Largest asset and its weight (0-100)
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub top_asset: Option<String>,
+    pub top_weight: f64,
+    /// Weight of the three largest assets (0-100)
+    pub top3_weight: f64,
+    /// Largest asset other than BTC, ETH and stablecoins, and its weight (0-100)
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub largest_alt: Option<String>,
+    pub largest_alt_weight: f64,
+    /// The portfolio's `max_alt_cap` guardrail (0-100)
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub max_alt_cap: Option<f64>,
+    /// "pass" when `largest_alt_weight` is at most `max_alt_cap`, "fail" above it, "not_set"
+    /// without the guardrail
+    pub status: GuardrailStatus,
+}
+
+/// Concentration of `weights` (asset, category, weight 0-100). Chain-specific holdings count
+/// as their symbol (USDC-ethereum as USDC); negative weights are left out.
+pub fn concentration_risk<'a>(
+    weights: impl IntoIterator<Item = (&'a str, Option<&'a str>, f64)>,
+    max_alt_cap: Option<f64>,
+) -> ConcentrationRisk {
+    let mut by_symbol: BTreeMap<String, (f64, bool)> = BTreeMap::new();
+    for (asset, category, weight) in weights {
+        if weight > 0.0 {
+            let symbol = target_symbol(asset);
+            let alt = !MAJOR_ASSETS.contains(&symbol.as_str()) && !is_stablecoin(asset, category);
+            let entry = by_symbol.entry(symbol).or_insert((0.0, alt));
+            entry.0 += weight;
+        }
+    }
+    let mut sorted: Vec<(String, f64, bool)> = by_symbol.into_iter().map(|(s, (w, alt))| (s, w, alt)).collect();
+    sorted.sort_by(|a, b| b.1.total_cmp(&a.1));
+
+    let hhi: f64 = sorted.iter().map(|(_, w, _)| w * w).sum();
+    let largest_alt = sorted.iter().find(|(_, _, alt)| *alt);
+    let largest_alt_weight = largest_alt.map_or(0.0, |(_, w, _)| *w);
+    ConcentrationRisk {
+        hhi,
+        score: hhi / 100.0,
+        level: if hhi > HHI_HIGH {
+            ConcentrationLevel::High
+        } else if hhi >= HHI_MODERATE {
+            ConcentrationLevel::Moderate
+        } else {
+            ConcentrationLevel::Low
+        },
+        effective_assets: if hhi > 0.0 { 10000.0 / hhi } else { 0.0 },
+        top_asset: sorted.first().map(|(s, _, _)| s.clone()),
+        top_weight: sorted.first().map_or(0.0, |(_, w, _)| *w),
+        top3_weight: sorted.iter().take(3).map(|(_, w, _)| w).sum(),
+        largest_alt: largest_alt.map(|(s, _, _)| s.clone()),
+        largest_alt_weight,
+        max_alt_cap,
+        status: match max_alt_cap {
+            Some(cap) if largest_alt_weight <= cap => GuardrailStatus::Pass,
+            Some(_) => GuardrailStatus::Fail,
+            None => GuardrailStatus::NotSet,
+        },
+    }
+}
+
+/// Concentration of a weighed allocation's priced holdings
+pub fn allocation_concentration(items: &[AllocationItem], max_alt_cap: Option<f64>) -> ConcentrationRisk {
+    concentration_risk(
+        items.iter().filter(|i| !i.unpriced).map(|i| (i.asset.as_str(), i.category.as_deref(), i.weight)),
+        max_alt_cap,
+    )
+}
+
+/// Holdings of one asset category (e.g. "l1", "defi", "stablecoin").
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, ToSchema)]
+pub struct CategoryExposure {
+    /// Category, or "uncategorized"
+    pub category: String,
+    /// Assets in the category, largest value first
+    pub assets: Vec<String>,
+    /// Total value in USD of the priced holdings
+    pub value_usd: f64,
+    /// Percentage of total portfolio value (0-100)
+    pub weight: f64,
+}
+
+/// Group allocation items by category, largest value first.
+///
+/// Items without a category fall under "uncategorized". Weights are summed from the items, so
+/// they stay relative to the whole portfolio; unpriced items are listed but add no value.
+pub fn exposure_by_category(items: &[AllocationItem]) -> Vec<CategoryExposure> {
+    let mut groups: BTreeMap<&str, Vec<&AllocationItem>> = BTreeMap::new();
+    for item in items {
+        groups.entry(item.category.as_deref().unwrap_or(UNCATEGORIZED)).or_default().push(item);
+    }
+
+    let mut categories: Vec<CategoryExposure> = groups
+        .into_iter()
+        .map(|(category, mut members)| {
+            members.sort_by(|a, b| b.value_usd.partial_cmp(&a.value_usd).unwrap_or(std::cmp::Ordering::Equal));
+            let priced = members.iter().filter(|i| !i.unpriced);
+            CategoryExposure {
+                category: category.to_string(),
+                assets: members.iter().map(|i| i.asset.clone()).collect(),
+                value_usd: priced.clone().map(|i| i.value_usd).sum(),
+                weight: priced.map(|i| i.weight).sum(),
+            }
+        })
+        .collect();
+    categories.sort_by(|a, b| b.value_usd.partial_cmp(&a.value_usd).unwrap_or(std::cmp::Ordering::Equal));
+    categories
+}
+
+/// Chain of allocation items held without chain context (e.g. on an exchange)
+pub const CHAIN_OFF_CHAIN: &str = "off_chain";
+
+/// Venue of holdings in wallets and DeFi accounts
+pub const VENUE_SELF_CUSTODY: &str = "self_custody";
+
+/// Holdings on one chain (e.g. "ethereum", "solana", "bsc").
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, ToSchema)]
+pub struct ChainExposure {
+    /// Chain, or "off_chain"
+    pub chain: String,
+    /// Assets on the chain, largest value first
+    pub assets: Vec<String>,
+    /// Total value in USD of the priced holdings
+    pub value_usd: f64,
+    /// Percentage of total portfolio value (0-100)
+    pub weight: f64,
+}
+
+/// Group allocation items by chain, largest value first.
+///
+/// Items without a chain fall under "off_chain". Weights are summed from the items, so they
+/// stay relative to the whole portfolio; unpriced items are listed but add no value.
+pub fn exposure_by_chain(items: &[AllocationItem]) -> Vec<ChainExposure> {
+    let mut groups: BTreeMap<&str, Vec<&AllocationItem>> = BTreeMap::new();
+    for item in items {
+        groups.entry(item.chain.as_deref().unwrap_or(CHAIN_OFF_CHAIN)).or_default().push(item);
+    }
+
+    let mut chains: Vec<ChainExposure> = groups
+        .into_iter()
+        .map(|(chain, mut members)| {
+            members.sort_by(|a, b| b.value_usd.partial_cmp(&a.value_usd).unwrap_or(std::cmp::Ordering::Equal));
+            let priced = members.iter().filter(|i| !i.unpriced);
+            ChainExposure {
+                chain: chain.to_string(),
+                assets: members.iter().map(|i| i.asset.clone()).collect(),
+                value_usd: priced.clone().map(|i| i.value_usd).sum(),
+                weight: priced.map(|i| i.weight).sum(),
+            }
+        })
+        .collect();
+    chains.sort_by(|a, b| b.value_usd.partial_cmp(&a.value_usd).unwrap_or(std::cmp::Ordering::Equal));
+    chains
+}
+
+/// Holdings at one venue: an exchange, or self-custody.
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, ToSchema)]
+pub struct VenueExposure {
+    /// Exchange name (e.g. "okx"), or "self_custody"
+    pub venue: String,
+    /// Whether a third party holds the assets (every venue but self-custody)
+    pub custodial: bool,
+    /// Assets held at the venue, largest value first
+    pub assets: Vec<String>,
+    /// Total value in USD of the priced holdings
+    pub value_usd: f64,
+    /// Percentage of total portfolio value (0-100)
+    pub weight: f64,
+}
+
+/// Group allocation items by venue, largest value first.
+///
+/// An item held at several venues is split by its `quantity_by_venue`; items without the split
+/// (constructed before it was recorded) are left out. Weights stay relative to the whole
+/// portfolio.
+pub fn exposure_by_venue(items: &[AllocationItem]) -> Vec<VenueExposure> {
+    #[derive(Default)]
+    struct Group<'a> {
+        assets: Vec<(&'a str, f64)>,
+        value_usd: f64,
+        weight: f64,
+    }
+
+    let mut groups: BTreeMap<&str, Group> = BTreeMap::new();
+    for item in items {
+        let Some(venues) = &item.quantity_by_venue else {
+            continue;
+        };
+        let quantities: Vec<(&str, Decimal)> = venues
+            .iter()
+            .map(|(venue, quantity)| (venue.as_str(), Decimal::from_str(quantity).unwrap_or(Decimal::ZERO)))
+            .collect();
+        let total: Decimal = quantities.iter().map(|(_, q)| q).sum();
+        for (venue, quantity) in quantities {
+            let share = if total.is_zero() {
+                0.0
+            } else {
+                (quantity / total).to_string().parse::<f64>().unwrap_or(0.0)
+            };
+            let group = groups.entry(venue).or_default();
+            let value_usd = if item.unpriced { 0.0 } else { item.value_usd * share };
+            group.assets.push((item.asset.as_str(), value_usd));
+            if !item.unpriced {
+                group.value_usd += value_usd;
+                group.weight += item.weight * share;
+            }
+        }
+    }
+
+    let mut venues: Vec<VenueExposure> = groups
+        .into_iter()
+        .map(|(venue, mut group)| {
+            group.assets.sort_by(|a, b| b.1.partial_cmp(&a.1).unwrap_or(std::cmp::Ordering::Equal));
+            VenueExposure {
+                venue: venue.to_string(),
+                custodial: venue != VENUE_SELF_CUSTODY,
+                assets: group.assets.into_iter().map(|(asset, _)| asset.to_string()).collect(),
+                value_usd: group.value_usd,
+                weight: group.weight,
+            }
+        })
+        .collect();
+    venues.sort_by(|a, b| b.value_usd.partial_cmp(&a.value_usd).unwrap_or(std::cmp::Ordering::Equal));
+    venues
+}
+
+/// Target weights (0-100) by asset symbol from a portfolio's `target_allocation` JSON
+/// (e.g. `{"BTC": 40, "ETH": 30, "USDT": 30}`); symbols are uppercased and non-numeric entries
+/// are ignored.
+pub fn target_weights(value: Option<&serde_json::Value>) -> BTreeMap<String, f64> {
+    value
+        .and_then(|v| v.as_object())
+        .map(|targets| {
+            targets
+                .iter()
+                .filter_map(|(asset, weight)| Some((asset.trim().to_uppercase(), weight.as_f64()?)))
+                .collect()
+        })
+        .unwrap_or_default()
+}
+
+/// Symbol a holding counts as against target weights: uppercased, without chain suffix
+/// (e.g. "USDC" for "USDC-ethereum")
+pub fn target_symbol(asset: &str) -> String {
+    asset.split_once('-').map_or(asset, |(base, _)| base).to_uppercase()
+}
+
+/// Current against target weight of one asset.
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, ToSchema)]
+pub struct AssetDrift {
+    /// Asset symbol
+    pub asset: String,
+    /// Percentage of total portfolio value held (0-100)
+    pub current_weight: f64,
+    /// Target percentage; 0 for assets held without a target
+    pub target_weight: f64,
+    /// Current minus target weight, in percentage points
+    pub drift: f64,
+    /// Value in USD of the priced holdings of the asset
+    pub current_value_usd: f64,
+    /// Whether `drift` exceeds the portfolio's `drift_band` guardrail either way
+    pub outside_band: bool,
+}
+
+/// Drift of a weighed allocation from target weights, largest absolute drift first.
+///
+/// Covers every target asset and every priced asset held; chain-specific holdings count
+/// towards their symbol (e.g. USDC-ethereum as USDC). Without a `drift_band` nothing is
+/// flagged.
+pub fn drift_from_targets(
+    items: &[AllocationItem],
+    targets: &BTreeMap<String, f64>,
+    drift_band: Option<f64>,
+) -> Vec<AssetDrift> {
+    let mut current: BTreeMap<String, (f64, f64)> = BTreeMap::new();
+    for item in items.iter().filter(|i| !i.unpriced) {
+        let entry = current.entry(target_symbol(&item.asset)).or_insert((0.0, 0.0));
+        entry.0 += item.weight;
+        entry.1 += item.value_usd;
+    }
+    for asset in targets.keys() {
+        current.entry(asset.clone()).or_insert((0.0, 0.0));
+    }
+
+    let mut drifts: Vec<AssetDrift> = current
+        .into_iter()
+        .map(|(asset, (current_weight, current_value_usd))| {
+            let target_weight = targets.get(&asset).copied().unwrap_or(0.0);
+            let drift = current_weight - target_weight;
+            AssetDrift {
+                asset,
+                current_weight,
+                target_weight,
+                drift,
+                current_value_usd,
+                outside_band: drift_band.is_some_and(|band| drift.abs() > band),
+            }
+        })
+        .collect();
+    drifts.sort_by(|a, b| b.drift.abs().partial_cmp(&a.drift.abs()).unwrap_or(std::cmp::Ordering::Equal));
+    drifts
 }
 
 /// Complete allocation data for a portfolio.
@@ -101,3 +749,253 @@ pub struct UnpricedAsset {
     /// Quantity held
     pub quantity: String,
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn item(asset: &str, quantity: &str, value_usd: f64, underlying: Option<(&str, &str)>) -> AllocationItem {
+        AllocationItem {
+            asset: asset.to_string(),
+            chain: None,
+            quantity: quantity.to_string(),
+            price_usd: None,
+            value_usd,
+            weight: 0.0,
+            unpriced: value_usd == 0.0,
+            stale_price: false,
+            delisted: false,
+            price_as_of: None,
+            quantity_by_source: None,
+            quantity_by_venue: None,
+            underlying_asset: underlying.map(|(a, _)| a.to_string()),
+            underlying_quantity: underlying.map(|(_, q)| q.to_string()),
+            maturity: None,
+            category: None,
+            value: None,
+        }
+    }
+
+    #[test]
+    fn test_exposure_by_category() {
+        let tagged = |asset: &str, value_usd: f64, weight: f64, category: Option<&str>| AllocationItem {
+            weight,
+            category: category.map(str::to_string),
+            ..item(asset, "1", value_usd, None)
+        };
+        let items = vec![
+            tagged("ETH", 3000.0, 30.0, Some("l1")),
+            tagged("USDC", 5000.0, 50.0, Some("stablecoin")),
+            tagged("SOL", 1500.0, 15.0, Some("l1")),
+            tagged("PEPE", 500.0, 5.0, None),
+            tagged("XYZ", 0.0, 0.0, Some("l1")),
+        ];
+        let categories = exposure_by_category(&items);
+
+        assert_eq!(categories.len(), 3);
+        assert_eq!(categories[0].category, "stablecoin");
+        assert_eq!(categories[1].category, "l1");
+        assert_eq!(categories[1].assets, vec!["ETH", "SOL", "XYZ"]);
+        assert_eq!(categories[1].value_usd, 4500.0);
+        assert!((categories[1].weight - 45.0).abs() < 1e-9);
+        assert_eq!(categories[2].category, UNCATEGORIZED);
+    }
+
+    #[test]
+    fn test_drift_from_targets() {
+        let weighed = |asset: &str, value_usd: f64, weight: f64| AllocationItem { weight, ..item(asset, "1", value_usd, None) };
+        let items = vec![
+            weighed("BTC", 5000.0, 50.0),
+            weighed("ETH", 2000.0, 20.0),
+            weighed("USDC-ethereum", 2000.0, 20.0),
+            weighed("USDC-solana", 500.0, 5.0),
+            weighed("DOGE", 500.0, 5.0),
+        ];
+        let targets = target_weights(Some(&serde_json::json!({ "btc": 40, "ETH": 30, "USDC": 25, "SOL": 5, "X": "n/a" })));
+        assert_eq!(targets.len(), 4);
+
+        let drift = drift_from_targets(&items, &targets, Some(5.0));
+        assert_eq!(drift.len(), 5);
+        assert_eq!(drift[0].asset, "BTC");
+        assert!((drift[0].drift - 10.0).abs() < 1e-9);
+        assert!(drift[0].outside_band);
+        assert_eq!(drift[1].asset, "ETH");
+        assert!((drift[1].drift + 10.0).abs() < 1e-9);
+        let usdc = drift.iter().find(|d| d.asset == "USDC").unwrap();
+        assert_eq!(usdc.current_value_usd, 2500.0);
+        assert!(!usdc.outside_band);
+        let doge = drift.iter().find(|d| d.asset == "DOGE").unwrap();
+        assert_eq!(doge.target_weight, 0.0);
+        assert!(!doge.outside_band);
+        assert!(drift_from_targets(&items, &targets, None).iter().all(|d| !d.outside_band));
+    }
+
+    #[test]
+    fn test_stablecoin_exposure() {
+        let weighed = |asset: &str, value_usd: f64, weight: f64, category: Option<&str>| AllocationItem {
+            weight,
+            category: category.map(str::to_string),
+            ..item(asset, "1", value_usd, None)
+        };
+        let items = vec![
+            weighed("BTC", 8000.0, 80.0, Some("l1")),
+            weighed("USDC-ethereum", 1500.0, 15.0, None),
+            weighed("MYUSD", 500.0, 5.0, Some(CATEGORY_STABLECOIN)),
+            // A category beats the symbol list
+            weighed("USDT", 0.0, 0.0, Some("wrapped")),
+        ];
+
+        let exposure = stablecoin_exposure(&items, Some(25.0));
+        assert_eq!(exposure.assets, vec!["USDC-ethereum", "MYUSD"]);
+        assert_eq!(exposure.value_usd, 2000.0);
+        assert!((exposure.weight - 20.0).abs() < 1e-9);
+        assert_eq!(exposure.status, GuardrailStatus::Fail);
+        assert_eq!(stablecoin_exposure(&items, Some(20.0)).status, GuardrailStatus::Pass);
+        assert_eq!(stablecoin_exposure(&items, None).status, GuardrailStatus::NotSet);
+    }
+
+    #[test]
+    fn test_concentration_risk() {
+        let weights = [
+            ("BTC", None, 50.0),
+            ("SOL-solana", Some("l1"), 20.0),
+            ("SOL", Some("l1"), 10.0),
+            ("USDC-ethereum", None, 15.0),
+            ("DOGE", Some("meme"), 5.0),
+            ("DEBT", None, -3.0),
+        ];
+
+        let risk = concentration_risk(weights, Some(25.0));
+        // 50² + 30² + 15² + 5²
+        assert_eq!(risk.hhi, 3650.0);
+        assert_eq!(risk.level, ConcentrationLevel::High);
+        assert!((risk.effective_assets - 10000.0 / 3650.0).abs() < 1e-9);
+        assert_eq!((risk.top_asset.as_deref(), risk.top3_weight), (Some("BTC"), 95.0));
+        assert_eq!((risk.largest_alt.as_deref(), risk.largest_alt_weight), (Some("SOL"), 30.0));
+        assert_eq!(risk.status, GuardrailStatus::Fail);
+        assert_eq!(concentration_risk(weights, Some(30.0)).status, GuardrailStatus::Pass);
+
+        let spread = concentration_risk(["A", "B", "C", "D", "E", "F", "G", "H", "I", "J"].map(|a| (a, None, 10.0)), None);
+        assert_eq!((spread.hhi, spread.level, spread.status), (1000.0, ConcentrationLevel::Low, GuardrailStatus::NotSet));
+        assert_eq!(spread.effective_assets, 10.0);
+    }
+
+    #[test]
+    fn test_exposure_by_chain_and_venue() {
+        let held = |asset: &str, chain: Option<&str>, value_usd: f64, weight: f64, venues: &[(&str, &str)]| AllocationItem {
+            chain: chain.map(str::to_string),
+            weight,
+            quantity_by_venue: Some(venues.iter().map(|(v, q)| (v.to_string(), q.to_string())).collect()),
+            ..item(asset, "4", value_usd, None)
+        };
+        let items = vec![
+            held("BTC", None, 6000.0, 60.0, &[("okx", "3"), ("binance", "1")]),
+            held("ETH", Some("ethereum"), 3000.0, 30.0, &[(VENUE_SELF_CUSTODY, "4")]),
+            held("SOL", Some("solana"), 1000.0, 10.0, &[(VENUE_SELF_CUSTODY, "4")]),
+        ];
+
+        let chains = exposure_by_chain(&items);
+        assert_eq!(chains.len(), 3);
+        assert_eq!(chains[0].chain, CHAIN_OFF_CHAIN);
+        assert_eq!(chains[1].chain, "ethereum");
+
+        let venues = exposure_by_venue(&items);
+        assert_eq!(venues.len(), 3);
+        assert_eq!(venues[0].venue, "okx");
+        assert!(venues[0].custodial);
+        assert!((venues[0].value_usd - 4500.0).abs() < 1e-9);
+        assert!((venues[0].weight - 45.0).abs() < 1e-9);
+        assert_eq!(venues[1].venue, VENUE_SELF_CUSTODY);
+        assert!(!venues[1].custodial);
+        assert_eq!(venues[1].assets, vec!["ETH", "SOL"]);
+        assert!((venues[1].weight - 40.0).abs() < 1e-9);
+        assert_eq!(venues[2].venue, "binance");
+    }
+
+    #[test]
+    fn test_exposure_groups_derivatives_under_underlying() {
+        let items = vec![
+            item("ETH", "1", 3000.0, None),
+            item("WSTETH", "2", 7200.0, Some(("ETH", "2.4"))),
+            item("WETH", "0.5", 0.0, Some(("ETH", "0.5"))),
+            item("USDC", "1800", 1800.0, None),
+        ];
+        let exposure = exposure_by_underlying(&items);
+
+        assert_eq!(exposure.len(), 2);
+        assert_eq!(exposure[0].asset, "ETH");
+        assert_eq!(exposure[0].quantity, "3.9");
+        assert_eq!(exposure[0].value_usd, 10200.0);
+        assert!((exposure[0].weight - 85.0).abs() < 1e-9);
+        assert_eq!(exposure[1].asset, "USDC");
+    }
+
+    #[test]
+    fn test_fixed_yield_grouped_by_maturity() {
+        let fixed = |asset: &str, value_usd: f64, weight: f64, maturity: &str| AllocationItem {
+            weight,
+            maturity: Some(maturity.to_string()),
+            ..item(asset, "1", value_usd, None)
+        };
+        let items = vec![
+            item("ETH", "1", 3000.0, None),
+            fixed("PT-weETH-26JUN2025", 1000.0, 10.0, "2025-06-26"),
+            fixed("PT-sUSDE-27MAR2025", 500.0, 5.0, "2025-03-27"),
+            fixed("YT-sUSDE-27MAR2025", 0.0, 0.0, "2025-03-27"),
+        ];
+        let buckets = fixed_yield_by_maturity(&items);
+
+        assert_eq!(buckets.len(), 2);
+        assert_eq!(buckets[0].maturity, "2025-03-27");
+        assert_eq!(buckets[0].assets, vec!["PT-sUSDE-27MAR2025", "YT-sUSDE-27MAR2025"]);
+        assert_eq!((buckets[0].value_usd, buckets[0].weight), (500.0, 5.0));
+        assert_eq!(buckets[1].maturity, "2025-06-26");
+    }
+
+    #[test]
+    fn test_weigh_allocation_debt_modes() {
+        let lent = |asset: &str, quantity: &str, value_usd: f64, sources: &[(&str, &str)]| AllocationItem {
+            price_usd: Some(1.0),
+            unpriced: false,
+            quantity_by_source: Some(sources.iter().map(|(s, q)| (s.to_string(), q.to_string())).collect()),
+            ..item(asset, quantity, value_usd, None)
+        };
+        let mut items = vec![
+            AllocationItem { price_usd: Some(3000.0), ..item("ETH", "2", 6000.0, None) },
+            lent("USDC", "-1000", -1000.0, &[("borrowed", "-1000")]),
+            lent("DAI", "500", 500.0, &[("supplied", "1500"), ("borrowed", "-1000")]),
+        ];
+
+        let summary = weigh_allocation(&mut items, DebtMode::Net);
+        assert_eq!(summary, DebtSummary { gross_value_usd: 7500.0, debt_usd: 2000.0, net_value_usd: 5500.0 });
+        assert!((items[1].weight - (-1000.0 / 5500.0 * 100.0)).abs() < 1e-9);
+
+        weigh_allocation(&mut items, DebtMode::Gross);
+        let weights: Vec<f64> = items.iter().map(|i| i.weight).collect();
+        assert_eq!(weights, vec![80.0, 0.0, 20.0]);
+        assert_eq!(summary.total(DebtMode::Gross), 7500.0);
+    }
+
+    #[test]
+    fn test_price_staleness_warning() {
+        let stale = |asset: &str, weight: f64, as_of: &str| AllocationItem {
+            weight,
+            stale_price: true,
+            price_as_of: Some(as_of.to_string()),
+            ..item(asset, "1", weight * 100.0, None)
+        };
+        let items = vec![
+            AllocationItem { weight: 90.0, ..item("ETH", "1", 9000.0, None) },
+            stale("FOO", 2.0, "2025-01-03T00:00:00+00:00"),
+            stale("BAR", 8.0, "2025-01-01T00:00:00+00:00"),
+        ];
+
+        let warning = price_staleness_warning(&items, 0.0).unwrap();
+        assert_eq!(warning.assets, vec!["BAR", "FOO"]);
+        assert_eq!((warning.stale_value_usd, warning.stale_weight), (1000.0, 10.0));
+        assert_eq!(warning.oldest_price_as_of.as_deref(), Some("2025-01-01T00:00:00+00:00"));
+
+        assert!(price_staleness_warning(&items, 15.0).is_none());
+        assert!(price_staleness_warning(&items[..1], 0.0).is_none());
+    }
+}
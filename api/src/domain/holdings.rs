@@ -5,6 +5,38 @@
 
 use serde::{Deserialize, Serialize};
 
+/// Holding source: regular (trading/spot) balance; assumed when `holding_source` is absent
+pub const HOLDING_SOURCE_SPOT: &str = "spot";
+
+/// Holding source: funds in exchange savings or staking products, or deposits in yield vaults
+/// (e.g. OKX Simple Earn, ERC-4626 vault shares)
+pub const HOLDING_SOURCE_EARN: &str = "earn";
+
+/// Holding source: collateral and unrealized PnL in a perpetuals account (e.g. Hyperliquid)
+pub const HOLDING_SOURCE_PERP: &str = "perp";
+
+/// Holding source: equity of a derivatives (futures/options) account, including option mark value (e.g. Deribit)
+pub const HOLDING_SOURCE_DERIVATIVES: &str = "derivatives";
+
+/// Holding source: native coins delegated to validators, unbonding, or pending staking rewards
+/// (e.g. Cosmos delegations, Solana stake accounts)
+pub const HOLDING_SOURCE_STAKED: &str = "staked";
+
+/// Holding source: assets supplied to a lending protocol, interest included (e.g. Aave aTokens)
+pub const HOLDING_SOURCE_SUPPLIED: &str = "supplied";
+
+/// Holding source: debt owed to a lending protocol, stored as a negative quantity so that the
+/// asset's total is the net exposure (e.g. Aave debt tokens)
+pub const HOLDING_SOURCE_BORROWED: &str = "borrowed";
+
+/// Holding source: underlying coins of a liquidity pool position, valued through the pool
+/// (e.g. Curve LP tokens, staked directly or through Convex)
+pub const HOLDING_SOURCE_LP: &str = "lp";
+
+/// Holding source: fixed-term yield tokens (Pendle principal and yield tokens), valued at the
+/// protocol's price and grouped by maturity in allocations
+pub const HOLDING_SOURCE_FIXED_YIELD: &str = "fixed_yield";
+
 /// A holding in an account with quantity information only.
 ///
 /// This represents the data structure as stored in account holdings JSON.
@@ -22,7 +54,8 @@ use serde::{Deserialize, Serialize};
 ///   "quantity": "1.5",
 ///   "available": "1.5",   // Optional, defaults to quantity if not present
 ///   "frozen": "0",        // Optional, defaults to "0" if not present
-///   "decimals": 8         // Optional, number of decimal places (metadata only)
+///   "decimals": 8,        // Optional, number of decimal places (metadata only)
+///   "holding_source": "earn"  // Optional, defaults to "spot" if not present
 /// }
 /// ```
 #[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
@@ -55,6 +88,11 @@ pub struct AccountHolding {
     /// Optional value from account data (usually not present)
     #[serde(default, skip_serializing_if = "Option::is_none")]
     pub value_usd: Option<f64>,
+
+    /// Where the holding is held ("spot", "earn", "perp" or "derivatives")
+    /// Defaults to "spot" if not specified (for legacy data compatibility)
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub holding_source: Option<String>,
 }
 
 impl AccountHolding {
@@ -75,6 +113,11 @@ impl AccountHolding {
     pub fn frozen_quantity(&self) -> &str {
         self.frozen.as_deref().unwrap_or("0")
     }
+
+    /// Get the holding source, defaulting to "spot" if not specified
+    pub fn source(&self) -> &str {
+        self.holding_source.as_deref().unwrap_or(HOLDING_SOURCE_SPOT)
+    }
 }
 
 #[cfg(test)]
@@ -140,6 +183,7 @@ mod tests {
             decimals: None,
             price_usd: None,
             value_usd: None,
+            holding_source: None,
         };
 
         let decimal = holding.quantity_decimal();
@@ -156,6 +200,7 @@ mod tests {
             decimals: None,
             price_usd: None,
             value_usd: None,
+            holding_source: None,
         };
 
         let json = serde_json::to_string(&holding).unwrap();
@@ -166,5 +211,15 @@ mod tests {
         assert!(!json.contains(r#""available"#));
         assert!(!json.contains(r#""frozen"#));
     }
+
+    #[test]
+    fn test_holding_source_defaults_to_spot() {
+        let legacy: AccountHolding = serde_json::from_str(r#"{"asset": "BTC", "quantity": "1"}"#).unwrap();
+        assert_eq!(legacy.source(), HOLDING_SOURCE_SPOT);
+
+        let earn: AccountHolding =
+            serde_json::from_str(r#"{"asset": "BTC", "quantity": "1", "holding_source": "earn"}"#).unwrap();
+        assert_eq!(earn.source(), HOLDING_SOURCE_EARN);
+    }
 }
 
@@ -9,6 +9,11 @@
 /// - **AccountHolding**: Raw holdings from accounts (quantity-only)
 /// - **AllocationItem**: Enriched holdings with prices, values, and weights
 /// - **SnapshotHolding**: Point-in-time holdings preserved in snapshots
+/// - **PortfolioSettings**: Per-portfolio settings (e.g. EOD valuation method)
+/// - **AccountSettings**: Per-account sync settings (e.g. include sub-accounts)
+/// - **RebalancePlan**: Trades that bring an allocation back to its target weights
+/// - **RebalanceRecommendation**: A rebalance plan as advice with rationale and projected allocation
+/// - **GuardrailViolation**: A guardrail the allocation breaches, with severity and remediation
 ///
 /// # Type Safety Benefits
 ///
@@ -25,7 +30,33 @@
 pub mod holdings;
 pub mod allocation;
 pub mod snapshot;
+pub mod settings;
+pub mod rebalance;
+pub mod recommendation;
 
-pub use holdings::AccountHolding;
-pub use allocation::{AllocationItem, AllocationData, UnpricedAsset};
+pub use holdings::{
+    AccountHolding, HOLDING_SOURCE_BORROWED, HOLDING_SOURCE_DERIVATIVES, HOLDING_SOURCE_EARN, HOLDING_SOURCE_FIXED_YIELD,
+    HOLDING_SOURCE_LP, HOLDING_SOURCE_PERP, HOLDING_SOURCE_SPOT, HOLDING_SOURCE_STAKED, HOLDING_SOURCE_SUPPLIED,
+};
+pub use allocation::{
+    allocation_concentration, concentration_risk, debt_value_usd, drift_from_targets, exposure_by_category, exposure_by_chain, exposure_by_underlying,
+    exposure_by_venue, fixed_yield_by_maturity, is_stablecoin, price_staleness_warning, stablecoin_exposure, target_symbol, target_weights,
+    weigh_allocation, AllocationItem, AllocationData, AssetDrift, AssetExposure, CategoryExposure, ChainExposure,
+    ConcentrationLevel, ConcentrationRisk,
+    DebtSummary, GuardrailStatus, MaturityBucket, PriceStalenessWarning, StablecoinExposure, UnpricedAsset,
+    VenueExposure, CATEGORY_STABLECOIN, CHAIN_OFF_CHAIN, UNCATEGORIZED, VENUE_SELF_CUSTODY,
+};
 pub use snapshot::{SnapshotHolding, SnapshotMetadata, SnapshotData};
+pub use rebalance::{guarded_targets, rebalance_plan, AccountBalance, PlannedTrade, RebalancePlan, TradeSide};
+pub use recommendation::{
+    concentration_alerts, depeg_alerts, drawdown_alert, guardrail_violations, projected_weights,
+    rebalance_recommendation, stale_account_alert, GuardrailViolation, ProposedOrder, RebalanceRecommendation,
+    RiskAlert, RiskAlertThresholds, Severity, RECOMMENDATION_SOURCE_ENGINE, RECOMMENDATION_STATUS_PENDING,
+    RECOMMENDATION_STATUS_RESOLVED, RECOMMENDATION_STATUS_SUPERSEDED, RECOMMENDATION_TYPE_CONCENTRATION,
+    RECOMMENDATION_TYPE_DEPEG, RECOMMENDATION_TYPE_DRAWDOWN, RECOMMENDATION_TYPE_GUARDRAIL,
+    RECOMMENDATION_TYPE_REBALANCE, RECOMMENDATION_TYPE_STALE_ACCOUNT,
+};
+pub use settings::{
+    AccountSettings, DebtMode, EodPriceMethod, EodValuationSettings, NotificationChannels, PortfolioGuardrails,
+    PortfolioSettings, SnapshotCadence,
+};
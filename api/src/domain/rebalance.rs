@@ -0,0 +1,338 @@
+//! Rebalance plans: the buys and sells that bring a weighed allocation back to its target
+//! weights.
+//!
+//! Targets are first adjusted to the portfolio's guardrails (`max_alt_cap`, `stablecoin_min`),
+//! then every asset whose value differs from its target by at least the minimum trade size
+//! gets a trade. Sells are split across the spot balances of the accounts holding the asset;
+//! buys go to the exchange account that already holds the asset, or else the one with the
+//! most stablecoins to pay with.
+
+use std::collections::BTreeMap;
+
+use rust_decimal::prelude::{FromPrimitive, ToPrimitive};
+use rust_decimal::Decimal;
+use serde::{Deserialize, Serialize};
+use utoipa::ToSchema;
+use uuid::Uuid;
+
+use super::allocation::{drift_from_targets, is_stablecoin, target_symbol, AllocationItem, MAJOR_ASSETS};
+use super::settings::PortfolioGuardrails;
+
+/// Decimal places of planned trade quantities
+const QUANTITY_DP: u32 = 8;
+
+/// Stablecoin that receives the `stablecoin_min` share when no stablecoin is targeted or held
+const DEFAULT_STABLECOIN: &str = "USDT";
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize, ToSchema)]
+#[serde(rename_all = "lowercase")]
+pub enum TradeSide {
+    Buy,
+    Sell,
+}
+
+/// Spot balance of one asset on one account
+#[derive(Debug, Clone, PartialEq)]
+pub struct AccountBalance {
+    pub account_id: Uuid,
+    pub account_name: String,
+    /// Whether the account is an exchange account, where orders can be placed
+    pub exchange: bool,
+    pub quantity: Decimal,
+}
+
+/// One order of a rebalance plan.
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, ToSchema)]
+pub struct PlannedTrade {
+    /// Target symbol, e.g. "BTC"
+    pub asset: String,
+    pub side: TradeSide,
+    /// Quantity to trade (decimal string, 8 decimal places)
+    pub quantity: String,
+    /// Quantity × price in USD
+    pub amount_usd: f64,
+    pub price_usd: f64,
+    /// Account to trade on; absent when no account can fill the trade
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub account_id: Option<Uuid>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub account_name: Option<String>,
+}
+
+/// Trades that return an allocation to its targets.
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, ToSchema)]
+pub struct RebalancePlan {
+    /// Target weights (0-100) after guardrail adjustments
+    pub targets: BTreeMap<String, f64>,
+    /// Sells first, then buys, largest first
+    pub trades: Vec<PlannedTrade>,
+    /// Number of trades below the minimum trade size left out
+    pub skipped_trades: usize,
+    /// Target adjustments and trades the plan could not fully place
+    pub notes: Vec<String>,
+}
+
+/// Target weights adjusted to the guardrails: scaled to sum to 100, each alt (neither BTC,
+/// ETH nor a stablecoin) capped at `max_alt_cap` with the excess moved to stablecoins, and
+/// stablecoins raised to `stablecoin_min` at the expense of the other targets.
+///
+/// `categories` maps symbols to their asset category for stablecoin detection. A stablecoin
+/// share without stablecoin targets goes to `preferred_stablecoin`.
+pub fn guarded_targets(
+    targets: &BTreeMap<String, f64>,
+    guardrails: &PortfolioGuardrails,
+    categories: &BTreeMap<String, String>,
+    preferred_stablecoin: &str,
+    notes: &mut Vec<String>,
+) -> BTreeMap<String, f64> {
+    let mut targets: BTreeMap<String, f64> = targets.iter().filter(|(_, w)| **w > 0.0).map(|(a, w)| (a.clone(), *w)).collect();
+    let sum: f64 = targets.values().sum();
+    if sum <= 0.0 {
+        return targets;
+    }
+    if (sum - 100.0).abs() > 0.01 {
+        notes.push(format!("Target weights sum to {:.2}%; scaled to 100%", sum));
+        targets.values_mut().for_each(|w| *w *= 100.0 / sum);
+    }
+
+    let stable = |asset: &str| is_stablecoin(asset, categories.get(asset).map(String::as_str));
+
+    let mut excess = 0.0;
+    if let Some(cap) = guardrails.max_alt_cap {
+        for (asset, weight) in targets.iter_mut() {
+            if !MAJOR_ASSETS.contains(&asset.as_str()) && !stable(asset) && *weight > cap {
+                notes.push(format!("{} target capped from {:.2}% to max_alt_cap {:.2}%", asset, weight, cap));
+                excess += *weight - cap;
+                *weight = cap;
+            }
+        }
+    }
+
+    let stable_sum: f64 = targets.iter().filter(|(a, _)| stable(a)).map(|(_, w)| *w).sum();
+    let required = match guardrails.stablecoin_min {
+        Some(min) if min > stable_sum + excess => {
+            notes.push(format!("Stablecoin target raised from {:.2}% to stablecoin_min {:.2}%", stable_sum + excess, min));
+            min.min(100.0)
+        }
+        _ => stable_sum + excess,
+    };
+    if required <= stable_sum {
+        return targets;
+    }
+
+    // Scale the stablecoin targets up to the required share, or give it to one stablecoin
+    if stable_sum > 0.0 {
+        targets.iter_mut().filter(|(a, _)| stable(a)).for_each(|(_, w)| *w *= required / stable_sum);
+    } else {
+        *targets.entry(preferred_stablecoin.to_string()).or_insert(0.0) += required;
+    }
+    // Scale the others down to the remaining share
+    let others: f64 = targets.iter().filter(|(a, _)| !stable(a)).map(|(_, w)| *w).sum();
+    if others > 0.0 {
+        let factor = (100.0 - required) / others;
+        targets.iter_mut().filter(|(a, _)| !stable(a)).for_each(|(_, w)| *w *= factor);
+    }
+    targets
+}
+
+/// Plan the trades that bring `items` (a weighed allocation) to `targets`.
+///
+/// Current values count every priced holding of a symbol, but only the spot `balances`
+/// (by target symbol) can be sold. `prices` supplies prices of targeted assets that are not
+/// held. With a `drift_band` guardrail nothing is traded until an asset drifts outside it.
+pub fn rebalance_plan(
+    items: &[AllocationItem],
+    targets: &BTreeMap<String, f64>,
+    guardrails: &PortfolioGuardrails,
+    prices: &BTreeMap<String, f64>,
+    balances: &BTreeMap<String, Vec<AccountBalance>>,
+    min_trade_usd: f64,
+) -> RebalancePlan {
+    let mut notes = Vec::new();
+
+    let mut current: BTreeMap<String, f64> = BTreeMap::new();
+    let mut item_prices: BTreeMap<String, f64> = BTreeMap::new();
+    let mut categories: BTreeMap<String, String> = BTreeMap::new();
+    for item in items.iter().filter(|i| !i.unpriced) {
+        let symbol = target_symbol(&item.asset);
+        *current.entry(symbol.clone()).or_insert(0.0) += item.value_usd;
+        if let Some(price) = item.price_usd.filter(|p| *p > 0.0) {
+            item_prices.entry(symbol.clone()).or_insert(price);
+        }
+        if let Some(category) = &item.category {
+            categories.entry(symbol).or_insert_with(|| category.clone());
+        }
+    }
+    let total: f64 = current.values().sum();
+
+    // Largest stablecoin holding receives a stablecoin share nobody targets
+    let preferred_stablecoin = current
+        .iter()
+        .filter(|(a, _)| is_stablecoin(a, categories.get(*a).map(String::as_str)))
+        .max_by(|a, b| a.1.total_cmp(b.1))
+        .map_or(DEFAULT_STABLECOIN.to_string(), |(a, _)| a.clone());
+    let targets = guarded_targets(targets, guardrails, &categories, &preferred_stablecoin, &mut notes);
+
+    let mut plan = RebalancePlan { targets, trades: Vec::new(), skipped_trades: 0, notes: Vec::new() };
+    if total <= 0.0 || plan.targets.is_empty() {
+        notes.push("Nothing to rebalance: the allocation has no priced value".to_string());
+        plan.notes = notes;
+        return plan;
+    }
+    if let Some(band) = guardrails.drift_band {
+        let drifts = drift_from_targets(items, &plan.targets, Some(band));
+        if !drifts.iter().any(|d| d.outside_band) {
+            notes.push(format!("All assets are within the drift band of {:.2} percentage points", band));
+            plan.notes = notes;
+            return plan;
+        }
+    }
+
+    // Exchange accounts by stablecoin quantity, to pay for buys of assets they do not hold
+    let mut stablecoin_holders: BTreeMap<Uuid, (String, Decimal)> = BTreeMap::new();
+    for (symbol, accounts) in balances {
+        if is_stablecoin(symbol, categories.get(symbol).map(String::as_str)) {
+            for balance in accounts.iter().filter(|b| b.exchange) {
+                let entry = stablecoin_holders.entry(balance.account_id).or_insert((balance.account_name.clone(), Decimal::ZERO));
+                entry.1 += balance.quantity;
+            }
+        }
+    }
+    let best_stablecoin_holder = stablecoin_holders.iter().max_by(|a, b| a.1 .1.cmp(&b.1 .1));
+
+    let mut symbols: Vec<&String> = current.keys().chain(plan.targets.keys()).collect();
+    symbols.sort();
+    symbols.dedup();
+
+    let mut sells = Vec::new();
+    let mut buys = Vec::new();
+    for symbol in symbols {
+        let target_value = total * plan.targets.get(symbol).copied().unwrap_or(0.0) / 100.0;
+        let delta = target_value - current.get(symbol).copied().unwrap_or(0.0);
+        if delta.abs() < 0.005 {
+            continue;
+        }
+        if delta.abs() < min_trade_usd {
+            plan.skipped_trades += 1;
+            continue;
+        }
+        let Some(price) = item_prices.get(symbol).or_else(|| prices.get(symbol)).copied().filter(|p| *p > 0.0) else {
+            notes.push(format!("{}: no price, {:.2} USD left untraded", symbol, delta.abs()));
+            continue;
+        };
+        let Some(quantity) = Decimal::from_f64(delta.abs() / price).map(|q| q.round_dp(QUANTITY_DP)) else {
+            continue;
+        };
+        let trade = |quantity: Decimal, account: Option<(Uuid, String)>, side: TradeSide| PlannedTrade {
+            asset: symbol.clone(),
+            side,
+            quantity: quantity.normalize().to_string(),
+            amount_usd: (quantity.to_f64().unwrap_or(0.0) * price * 100.0).round() / 100.0,
+            price_usd: price,
+            account_id: account.as_ref().map(|(id, _)| *id),
+            account_name: account.map(|(_, name)| name),
+        };
+
+        if delta < 0.0 {
+            // Split across spot balances: exchange accounts first, largest balance first
+            let mut holders: Vec<&AccountBalance> =
+                balances.get(symbol).map(|b| b.iter().filter(|b| b.quantity > Decimal::ZERO).collect()).unwrap_or_default();
+            holders.sort_by(|a, b| b.exchange.cmp(&a.exchange).then(b.quantity.cmp(&a.quantity)));
+            let mut remaining = quantity;
+            for holder in holders {
+                if remaining <= Decimal::ZERO {
+                    break;
+                }
+                let leg = remaining.min(holder.quantity);
+                sells.push(trade(leg, Some((holder.account_id, holder.account_name.clone())), TradeSide::Sell));
+                remaining -= leg;
+            }
+            if remaining > Decimal::ZERO {
+                notes.push(format!("{}: {} to sell is not held in spot on any account", symbol, remaining.normalize()));
+                sells.push(trade(remaining, None, TradeSide::Sell));
+            }
+        } else {
+            let holder = balances
+                .get(symbol)
+                .and_then(|b| b.iter().filter(|b| b.exchange).max_by(|a, b| a.quantity.cmp(&b.quantity)))
+                .map(|b| (b.account_id, b.account_name.clone()))
+                .or_else(|| best_stablecoin_holder.map(|(id, (name, _))| (*id, name.clone())));
+            if holder.is_none() {
+                notes.push(format!("{}: no exchange account holds it or stablecoins to buy with", symbol));
+            }
+            buys.push(trade(quantity, holder, TradeSide::Buy));
+        }
+    }
+
+    sells.sort_by(|a, b| b.amount_usd.total_cmp(&a.amount_usd));
+    buys.sort_by(|a, b| b.amount_usd.total_cmp(&a.amount_usd));
+    plan.trades = sells.into_iter().chain(buys).collect();
+    plan.notes = notes;
+    plan
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn item(asset: &str, price: f64, value: f64, weight: f64) -> AllocationItem {
+        serde_json::from_value(serde_json::json!({
+            "asset": asset,
+            "quantity": (value / price).to_string(),
+            "price_usd": price,
+            "value_usd": value,
+            "weight": weight,
+        }))
+        .unwrap()
+    }
+
+    fn balance(account: &str, exchange: bool, quantity: i64) -> AccountBalance {
+        AccountBalance {
+            account_id: Uuid::from_u128(if exchange { 1 } else { 2 }),
+            account_name: account.to_string(),
+            exchange,
+            quantity: Decimal::from(quantity),
+        }
+    }
+
+    #[test]
+    fn test_rebalance_plan() {
+        // 6000 BTC, 3000 SOL, 1000 USDT against 50 / 20 / 30
+        let items = [item("BTC", 60000.0, 6000.0, 60.0), item("SOL", 100.0, 3000.0, 30.0), item("USDT", 1.0, 1000.0, 10.0)];
+        let targets = BTreeMap::from([("BTC".to_string(), 50.0), ("SOL".to_string(), 20.0), ("USDT".to_string(), 30.0)]);
+        let balances = BTreeMap::from([
+            ("SOL".to_string(), vec![balance("wallet", false, 25), balance("okx", true, 5)]),
+            ("USDT".to_string(), vec![balance("okx", true, 1000)]),
+        ]);
+        let guardrails = PortfolioGuardrails::default();
+
+        let plan = rebalance_plan(&items, &targets, &guardrails, &BTreeMap::new(), &balances, 10.0);
+        // Sells first: 1000 USD BTC (no spot balance), 1000 USD SOL split exchange first
+        let summary: Vec<_> = plan.trades.iter().map(|t| (t.asset.as_str(), t.side, t.quantity.as_str(), t.account_name.as_deref())).collect();
+        assert_eq!(
+            summary,
+            vec![
+                ("BTC", TradeSide::Sell, "0.01666667", None),
+                ("SOL", TradeSide::Sell, "5", Some("okx")),
+                ("SOL", TradeSide::Sell, "5", Some("wallet")),
+                ("USDT", TradeSide::Buy, "2000", Some("okx")),
+            ]
+        );
+        assert_eq!(plan.notes.len(), 1);
+
+        // Minimum trade size and drift band
+        let plan = rebalance_plan(&items, &targets, &guardrails, &BTreeMap::new(), &balances, 1500.0);
+        assert!(plan.trades.iter().all(|t| t.asset == "USDT") && plan.skipped_trades == 2);
+        let banded = PortfolioGuardrails { drift_band: Some(25.0), ..Default::default() };
+        assert!(rebalance_plan(&items, &targets, &banded, &BTreeMap::new(), &balances, 10.0).trades.is_empty());
+
+        // Guardrails: SOL capped at 10 with the excess to USDT, then stablecoins raised to 50
+        let guardrails = PortfolioGuardrails { max_alt_cap: Some(10.0), stablecoin_min: Some(50.0), ..Default::default() };
+        let mut notes = Vec::new();
+        let guarded = guarded_targets(&targets, &guardrails, &BTreeMap::new(), "USDT", &mut notes);
+        assert!((guarded["SOL"] - 10.0 * 50.0 / 60.0).abs() < 1e-9);
+        assert!((guarded["USDT"] - 50.0).abs() < 1e-9);
+        assert!((guarded.values().sum::<f64>() - 100.0).abs() < 1e-9);
+        assert_eq!(notes.len(), 2);
+    }
+}
@@ -0,0 +1,729 @@
+//! Recommendations derived from an allocation.
+//!
+//! - Rebalance recommendations: a rebalance plan turned into concrete advice ("Sell 0.2 ETH,
+//!   buy 500 USDC to restore target") with its rationale and the allocation it leads to.
+//!   Trades on assets without a fresh price are left out of the advice, since their quantity
+//!   would be computed from a price that may no longer hold.
+//! - Guardrail violations: each of the portfolio's guardrails (`drift_band`, `stablecoin_min`,
+//!   `futures_cap`, `max_alt_cap`) the allocation breaches, with a severity and a suggested
+//!   remediation.
+//! - Risk alerts: a drawdown beyond a threshold, an asset above a share of the portfolio, a
+//!   stablecoin trading off its peg, and an account not synced for days, each its own
+//!   recommendation type.
+
+use std::collections::{BTreeMap, BTreeSet};
+
+use chrono::{DateTime, Utc};
+
+use rust_decimal::Decimal;
+use serde::{Deserialize, Serialize};
+use utoipa::ToSchema;
+use uuid::Uuid;
+
+use super::allocation::{drift_from_targets, stablecoin_exposure, target_symbol, AllocationItem, MAJOR_ASSETS};
+use super::holdings::{HOLDING_SOURCE_DERIVATIVES, HOLDING_SOURCE_PERP};
+use super::rebalance::{PlannedTrade, RebalancePlan, TradeSide};
+use super::settings::PortfolioGuardrails;
+
+/// `recommendations.recommendation_type` of rebalancing advice
+pub const RECOMMENDATION_TYPE_REBALANCE: &str = "rebalance";
+
+/// `recommendations.recommendation_type` of guardrail violations
+pub const RECOMMENDATION_TYPE_GUARDRAIL: &str = "guardrail_violation";
+
+/// `recommendations.recommendation_type` of a drawdown beyond the alert threshold
+pub const RECOMMENDATION_TYPE_DRAWDOWN: &str = "drawdown_alert";
+
+/// `recommendations.recommendation_type` of a single asset above the concentration threshold
+pub const RECOMMENDATION_TYPE_CONCENTRATION: &str = "concentration_alert";
+
+/// `recommendations.recommendation_type` of a stablecoin held while off its peg
+pub const RECOMMENDATION_TYPE_DEPEG: &str = "depeg_alert";
+
+/// `recommendations.recommendation_type` of an account not synced for too long
+pub const RECOMMENDATION_TYPE_STALE_ACCOUNT: &str = "stale_account";
+
+/// `recommendations.status` of new recommendations
+pub const RECOMMENDATION_STATUS_PENDING: &str = "pending";
+
+/// `recommendations.status` of pending recommendations replaced by newer advice of the same
+/// type
+pub const RECOMMENDATION_STATUS_SUPERSEDED: &str = "superseded";
+
+/// `recommendations.status` of pending recommendations whose condition no longer holds
+pub const RECOMMENDATION_STATUS_RESOLVED: &str = "resolved";
+
+/// `metadata.source` of recommendations written by the recommendation engine
+pub const RECOMMENDATION_SOURCE_ENGINE: &str = "engine";
+
+/// One order of a recommendation, as stored in `recommendations.proposed_orders`
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, ToSchema)]
+pub struct ProposedOrder {
+    pub action: TradeSide,
+    pub asset: String,
+    /// Quantity to trade (decimal string)
+    pub quantity: String,
+    /// Price in USD the quantity was computed at
+    pub estimated_price: String,
+    pub estimated_value_usd: String,
+    /// Account to place the order on; absent when no account can fill it
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub account_id: Option<Uuid>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub account_name: Option<String>,
+}
+
+impl From<&PlannedTrade> for ProposedOrder {
+    fn from(trade: &PlannedTrade) -> Self {
+        Self {
+            action: trade.side,
+            asset: trade.asset.clone(),
+            quantity: trade.quantity.clone(),
+            estimated_price: trade.price_usd.to_string(),
+            estimated_value_usd: format!("{:.2}", trade.amount_usd),
+            account_id: trade.account_id,
+            account_name: trade.account_name.clone(),
+        }
+    }
+}
+
+/// Rebalancing advice for one portfolio.
+#[derive(Debug, Clone, PartialEq)]
+pub struct RebalanceRecommendation {
+    /// Orders to place, sells first
+    pub orders: Vec<ProposedOrder>,
+    /// One-line summary of the orders, e.g. "Sell 0.2 ETH, buy 500 USDC"
+    pub summary: String,
+    pub rationale: String,
+    /// Weights (0-100) by target symbol before the orders
+    pub current_allocation: BTreeMap<String, f64>,
+    /// Weights (0-100) by target symbol once the orders are filled
+    pub projected_allocation: BTreeMap<String, f64>,
+    /// Largest absolute drift from the targets before and after the orders, in percentage points
+    pub max_drift_before: f64,
+    pub max_drift_after: f64,
+    /// Assets left out because their price is stale or missing
+    pub stale_assets: Vec<String>,
+}
+
+/// Weights (0-100) by target symbol of the priced items, after `trades` are filled at their
+/// planned prices
+pub fn projected_weights(items: &[AllocationItem], trades: &[PlannedTrade]) -> BTreeMap<String, f64> {
+    let mut values: BTreeMap<String, f64> = BTreeMap::new();
+    for item in items.iter().filter(|i| !i.unpriced) {
+        *values.entry(target_symbol(&item.asset)).or_insert(0.0) += item.value_usd;
+    }
+    for trade in trades {
+        let value = values.entry(trade.asset.clone()).or_insert(0.0);
+        match trade.side {
+            TradeSide::Buy => *value += trade.amount_usd,
+            TradeSide::Sell => *value -= trade.amount_usd,
+        }
+    }
+    let total: f64 = values.values().sum();
+    if total <= 0.0 {
+        return BTreeMap::new();
+    }
+    values
+        .into_iter()
+        .filter(|(_, value)| value.abs() >= 0.005)
+        .map(|(asset, value)| (asset, (value / total * 10000.0).round() / 100.0))
+        .collect()
+}
+
+/// Largest absolute difference between `weights` and `targets` over every asset of either
+fn max_drift(weights: &BTreeMap<String, f64>, targets: &BTreeMap<String, f64>) -> f64 {
+    weights
+        .keys()
+        .chain(targets.keys())
+        .map(|asset| {
+            (weights.get(asset).copied().unwrap_or(0.0) - targets.get(asset).copied().unwrap_or(0.0)).abs()
+        })
+        .fold(0.0, f64::max)
+}
+
+/// Orders summed per asset and side, e.g. "Sell 0.2 ETH, buy 500 USDC"
+fn summarize(trades: &[PlannedTrade]) -> String {
+    let mut totals: Vec<(TradeSide, &str, Decimal)> = Vec::new();
+    for trade in trades {
+        let quantity: Decimal = trade.quantity.parse().unwrap_or_default();
+        match totals.iter_mut().find(|(side, asset, _)| *side == trade.side && *asset == trade.asset) {
+            Some(total) => total.2 += quantity,
+            None => totals.push((trade.side, &trade.asset, quantity)),
+        }
+    }
+    let parts: Vec<String> = totals
+        .iter()
+        .map(|(side, asset, quantity)| {
+            let action = match side {
+                TradeSide::Buy => "buy",
+                TradeSide::Sell => "sell",
+            };
+            format!("{} {} {}", action, quantity.normalize(), asset)
+        })
+        .collect();
+    let summary = parts.join(", ");
+    let mut chars = summary.chars();
+    chars.next().map_or(summary.clone(), |first| first.to_uppercase().chain(chars).collect())
+}
+
+/// Advice from a rebalance `plan` of the weighed allocation `items`, or `None` when no trade
+/// is left once the trades on stale-priced or unpriced assets are dropped.
+pub fn rebalance_recommendation(items: &[AllocationItem], plan: &RebalancePlan) -> Option<RebalanceRecommendation> {
+    let stale: BTreeSet<String> =
+        items.iter().filter(|i| i.stale_price || i.unpriced).map(|i| target_symbol(&i.asset)).collect();
+    let (trades, dropped): (Vec<PlannedTrade>, Vec<PlannedTrade>) =
+        plan.trades.iter().cloned().partition(|t| !stale.contains(&t.asset));
+    if trades.is_empty() {
+        return None;
+    }
+    let mut stale_assets: Vec<String> = dropped.into_iter().map(|t| t.asset).collect();
+    stale_assets.dedup();
+
+    let current_allocation = projected_weights(items, &[]);
+    let projected_allocation = projected_weights(items, &trades);
+    let max_drift_before = max_drift(&current_allocation, &plan.targets);
+    let max_drift_after = max_drift(&projected_allocation, &plan.targets);
+    let summary = summarize(&trades);
+
+    // Drift of the traded assets, largest first
+    let mut traded: Vec<&String> = trades.iter().map(|t| &t.asset).collect();
+    traded.sort();
+    traded.dedup();
+    let mut drifts: Vec<(&String, f64, f64)> = traded
+        .into_iter()
+        .map(|asset| {
+            (
+                asset,
+                current_allocation.get(asset).copied().unwrap_or(0.0),
+                plan.targets.get(asset).copied().unwrap_or(0.0),
+            )
+        })
+        .collect();
+    drifts.sort_by(|a, b| (b.1 - b.2).abs().total_cmp(&(a.1 - a.2).abs()));
+    let drift_text: Vec<String> = drifts
+        .iter()
+        .map(|(asset, current, target)| format!("{} is {:.2}% (target {:.2}%)", asset, current, target))
+        .collect();
+
+    let mut rationale = format!(
+        "{} to restore the target allocation. {}. Largest drift falls from {:.2} to {:.2} percentage points.",
+        summary,
+        drift_text.join(", "),
+        max_drift_before,
+        max_drift_after
+    );
+    if !stale_assets.is_empty() {
+        rationale.push_str(&format!(
+            " Trades on {} are left out until their price is fresh.",
+            stale_assets.join(", ")
+        ));
+    }
+    for note in &plan.notes {
+        rationale.push_str(&format!(" {}.", note.trim_end_matches('.')));
+    }
+
+    Some(RebalanceRecommendation {
+        orders: trades.iter().map(ProposedOrder::from).collect(),
+        summary,
+        rationale,
+        current_allocation,
+        projected_allocation,
+        max_drift_before,
+        max_drift_after,
+        stale_assets,
+    })
+}
+
+/// Stablecoin bought to meet `stablecoin_min` when the allocation holds none
+const DEFAULT_STABLECOIN: &str = "USDT";
+
+/// How far an allocation is outside a guardrail
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, PartialEq, Eq, PartialOrd, Ord, ToSchema)]
+#[serde(rename_all = "lowercase")]
+pub enum Severity {
+    Low,
+    Medium,
+    High,
+}
+
+impl Severity {
+    /// Severity of a breach by `excess` percentage points of a guardrail of `limit`: low up to a
+    /// quarter of the limit, medium up to the limit itself, high beyond (limits below 1 count
+    /// as 1)
+    pub fn of_breach(excess: f64, limit: f64) -> Self {
+        let ratio = excess / limit.max(1.0);
+        if ratio <= 0.25 {
+            Self::Low
+        } else if ratio <= 1.0 {
+            Self::Medium
+        } else {
+            Self::High
+        }
+    }
+
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            Self::Low => "low",
+            Self::Medium => "medium",
+            Self::High => "high",
+        }
+    }
+}
+
+/// A guardrail the allocation is outside of.
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, ToSchema)]
+pub struct GuardrailViolation {
+    /// "drift_band", "stablecoin_min", "futures_cap" or "max_alt_cap"
+    pub guardrail: String,
+    pub severity: Severity,
+    /// The guardrail's value, in percent (percentage points for `drift_band`)
+    pub limit: f64,
+    /// The allocation's value against it: largest drift, stablecoin share, futures share or
+    /// largest alt weight
+    pub actual: f64,
+    /// Assets involved, largest breach first
+    pub assets: Vec<String>,
+    pub description: String,
+    pub remediation: String,
+    /// Orders that remedy the violation, when it can be put as orders
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub orders: Vec<ProposedOrder>,
+}
+
+/// Order of `value_usd` worth of `asset` at `price`
+fn order(action: TradeSide, asset: &str, value_usd: f64, price: f64) -> ProposedOrder {
+    let quantity = Decimal::from_f64_retain(value_usd / price).unwrap_or_default().round_dp(8);
+    ProposedOrder {
+        action,
+        asset: asset.to_string(),
+        quantity: quantity.normalize().to_string(),
+        estimated_price: price.to_string(),
+        estimated_value_usd: format!("{:.2}", value_usd),
+        account_id: None,
+        account_name: None,
+    }
+}
+
+/// Guardrails of the portfolio the weighed allocation `items` breaches. `targets` (target
+/// weights by symbol) are needed for `drift_band` only; without them drift is not checked.
+pub fn guardrail_violations(
+    items: &[AllocationItem],
+    targets: &BTreeMap<String, f64>,
+    guardrails: &PortfolioGuardrails,
+) -> Vec<GuardrailViolation> {
+    let priced: Vec<&AllocationItem> = items.iter().filter(|i| !i.unpriced).collect();
+    let total: f64 = priced.iter().map(|i| i.value_usd).sum();
+    if total <= 0.0 {
+        return Vec::new();
+    }
+    let price_of = |symbol: &str| {
+        priced.iter().find(|i| target_symbol(&i.asset) == symbol).and_then(|i| i.price_usd).filter(|p| *p > 0.0)
+    };
+    let mut violations = Vec::new();
+
+    if let Some(band) = guardrails.drift_band.filter(|_| !targets.is_empty()) {
+        let outside: Vec<_> = drift_from_targets(items, targets, Some(band)).into_iter().filter(|d| d.outside_band).collect();
+        if let Some(largest) = outside.first() {
+            let actual = largest.drift.abs();
+            let details: Vec<String> = outside
+                .iter()
+                .map(|d| format!("{} {:+.2} pp ({:.2}% vs target {:.2}%)", d.asset, d.drift, d.current_weight, d.target_weight))
+                .collect();
+            let assets: Vec<String> = outside.iter().map(|d| d.asset.clone()).collect();
+            violations.push(GuardrailViolation {
+                guardrail: "drift_band".to_string(),
+                severity: Severity::of_breach(actual - band, band),
+                limit: band,
+                actual,
+                description: format!("Drift outside the {:.2} pp drift band: {}", band, details.join(", ")),
+                remediation: format!(
+                    "Rebalance {} back to target; a rebalance plan or recommendation lists the orders",
+                    assets.join(", ")
+                ),
+                assets,
+                orders: Vec::new(),
+            });
+        }
+    }
+
+    if let Some(minimum) = guardrails.stablecoin_min {
+        let exposure = stablecoin_exposure(items, Some(minimum));
+        if exposure.weight < minimum {
+            let shortfall = minimum - exposure.weight;
+            let shortfall_usd = total * shortfall / 100.0;
+            let stablecoin = exposure.assets.first().map_or(DEFAULT_STABLECOIN.to_string(), |a| target_symbol(a));
+            let price = price_of(&stablecoin).unwrap_or(1.0);
+            violations.push(GuardrailViolation {
+                guardrail: "stablecoin_min".to_string(),
+                severity: Severity::of_breach(shortfall, minimum),
+                limit: minimum,
+                actual: exposure.weight,
+                assets: exposure.assets.iter().map(|a| target_symbol(a)).collect(),
+                description: format!("Stablecoins are {:.2}% of the portfolio, below stablecoin_min {:.2}%", exposure.weight, minimum),
+                remediation: format!("Move about {:.2} USD into stablecoins, e.g. buy {:.2} USD of {}", shortfall_usd, shortfall_usd, stablecoin),
+                orders: vec![order(TradeSide::Buy, &stablecoin, shortfall_usd, price)],
+            });
+        }
+    }
+
+    if let Some(cap) = guardrails.futures_cap {
+        // Futures share of each item: its weight times the share of its quantity in perps and derivatives
+        let mut futures: BTreeMap<String, f64> = BTreeMap::new();
+        for item in &priced {
+            let Some(sources) = &item.quantity_by_source else { continue };
+            let quantity: f64 = item.quantity.parse().unwrap_or(0.0);
+            let in_futures: f64 = [HOLDING_SOURCE_PERP, HOLDING_SOURCE_DERIVATIVES]
+                .iter()
+                .filter_map(|source| sources.get(*source))
+                .filter_map(|q| q.parse::<f64>().ok())
+                .sum();
+            if quantity > 0.0 && in_futures > 0.0 {
+                *futures.entry(target_symbol(&item.asset)).or_insert(0.0) += item.weight * (in_futures / quantity).min(1.0);
+            }
+        }
+        let actual: f64 = futures.values().sum();
+        if actual > cap {
+            let mut assets: Vec<(String, f64)> = futures.into_iter().collect();
+            assets.sort_by(|a, b| b.1.total_cmp(&a.1));
+            let excess_usd = total * (actual - cap) / 100.0;
+            let assets: Vec<String> = assets.into_iter().map(|(a, _)| a).collect();
+            violations.push(GuardrailViolation {
+                guardrail: "futures_cap".to_string(),
+                severity: Severity::of_breach(actual - cap, cap),
+                limit: cap,
+                actual,
+                description: format!("Futures and perpetuals are {:.2}% of the portfolio, above futures_cap {:.2}%", actual, cap),
+                remediation: format!("Reduce futures and perpetual positions ({}) by about {:.2} USD", assets.join(", "), excess_usd),
+                assets,
+                orders: Vec::new(),
+            });
+        }
+    }
+
+    if let Some(cap) = guardrails.max_alt_cap {
+        let mut alts: BTreeMap<String, f64> = BTreeMap::new();
+        for item in priced.iter().filter(|i| i.weight > 0.0 && !i.is_stablecoin()) {
+            let symbol = target_symbol(&item.asset);
+            if !MAJOR_ASSETS.contains(&symbol.as_str()) {
+                *alts.entry(symbol).or_insert(0.0) += item.weight;
+            }
+        }
+        let mut over: Vec<(String, f64)> = alts.into_iter().filter(|(_, weight)| *weight > cap).collect();
+        over.sort_by(|a, b| b.1.total_cmp(&a.1));
+        if let Some((_, largest)) = over.first() {
+            let actual = *largest;
+            let orders: Vec<ProposedOrder> = over
+                .iter()
+                .filter_map(|(asset, weight)| {
+                    price_of(asset).map(|price| order(TradeSide::Sell, asset, total * (weight - cap) / 100.0, price))
+                })
+                .collect();
+            let details: Vec<String> = over
+                .iter()
+                .map(|(asset, weight)| format!("{} {:.2}% (sell about {:.2} USD)", asset, weight, total * (weight - cap) / 100.0))
+                .collect();
+            violations.push(GuardrailViolation {
+                guardrail: "max_alt_cap".to_string(),
+                severity: Severity::of_breach(actual - cap, cap),
+                limit: cap,
+                actual,
+                assets: over.iter().map(|(a, _)| a.clone()).collect(),
+                description: format!("Alts above max_alt_cap {:.2}%: {}", cap, details.join(", ")),
+                remediation: format!("Sell the excess into stablecoins to bring each alt down to {:.2}%", cap),
+                orders,
+            });
+        }
+    }
+
+    violations
+}
+
+/// Thresholds of the risk alerts
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct RiskAlertThresholds {
+    /// Fall from the peak, in percent, at which a drawdown alert is raised
+    pub drawdown_pct: f64,
+    /// Weight, in percent, above which a single asset is too concentrated
+    pub concentration_pct: f64,
+    /// Distance of a stablecoin's price from 1 USD, in percent, beyond which it is off its peg
+    pub depeg_pct: f64,
+    /// Days without a sync after which an account is stale
+    pub stale_account_days: i64,
+}
+
+impl Default for RiskAlertThresholds {
+    fn default() -> Self {
+        Self { drawdown_pct: 20.0, concentration_pct: 50.0, depeg_pct: 2.0, stale_account_days: 3 }
+    }
+}
+
+/// A risk event of a portfolio.
+#[derive(Debug, Clone, PartialEq)]
+pub struct RiskAlert {
+    /// `recommendations.recommendation_type` of the alert
+    pub recommendation_type: &'static str,
+    /// What the alert is about: the asset, the account id, or "portfolio" for a drawdown. One
+    /// pending alert is kept per type and subject.
+    pub subject: String,
+    pub severity: Severity,
+    pub threshold: f64,
+    /// The portfolio's value against the threshold: drawdown or distance from the peg in
+    /// percent, weight, or days since the last sync
+    pub actual: f64,
+    pub assets: Vec<String>,
+    pub account_id: Option<Uuid>,
+    pub description: String,
+    pub remediation: String,
+    pub orders: Vec<ProposedOrder>,
+}
+
+/// Alert when `drawdown` (fall from the highest growth so far, a fraction, 0 or negative) is at
+/// least `threshold_pct` percent
+pub fn drawdown_alert(drawdown: f64, threshold_pct: f64) -> Option<RiskAlert> {
+    let fall = -drawdown * 100.0;
+    if threshold_pct <= 0.0 || fall < threshold_pct {
+        return None;
+    }
+    Some(RiskAlert {
+        recommendation_type: RECOMMENDATION_TYPE_DRAWDOWN,
+        subject: "portfolio".to_string(),
+        severity: Severity::of_breach(fall - threshold_pct, threshold_pct),
+        threshold: threshold_pct,
+        actual: fall,
+        assets: Vec::new(),
+        account_id: None,
+        description: format!("The portfolio is {:.2}% below its peak, beyond the {:.2}% drawdown alert", fall, threshold_pct),
+        remediation: "Review the positions driving the loss and whether the target allocation still fits your risk tolerance"
+            .to_string(),
+        orders: Vec::new(),
+    })
+}
+
+/// Assets other than stablecoins weighing more than `threshold_pct` percent of the weighed
+/// allocation `items`, largest first, each with the sale that brings it back to the threshold
+pub fn concentration_alerts(items: &[AllocationItem], threshold_pct: f64) -> Vec<RiskAlert> {
+    let mut weights: BTreeMap<String, (f64, Option<f64>)> = BTreeMap::new();
+    let mut total = 0.0;
+    for item in items.iter().filter(|i| !i.unpriced) {
+        total += item.value_usd;
+        if !item.is_stablecoin() {
+            let entry = weights.entry(target_symbol(&item.asset)).or_insert((0.0, None));
+            entry.0 += item.weight;
+            entry.1 = entry.1.or(item.price_usd.filter(|p| *p > 0.0));
+        }
+    }
+    let mut over: Vec<(String, f64, Option<f64>)> =
+        weights.into_iter().filter(|(_, (weight, _))| *weight > threshold_pct).map(|(a, (w, p))| (a, w, p)).collect();
+    over.sort_by(|a, b| b.1.total_cmp(&a.1));
+    over.into_iter()
+        .map(|(asset, weight, price)| {
+            let excess_usd = total * (weight - threshold_pct) / 100.0;
+            RiskAlert {
+                recommendation_type: RECOMMENDATION_TYPE_CONCENTRATION,
+                severity: Severity::of_breach(weight - threshold_pct, threshold_pct),
+                threshold: threshold_pct,
+                actual: weight,
+                assets: vec![asset.clone()],
+                account_id: None,
+                description: format!("{} is {:.2}% of the portfolio, above the {:.2}% concentration alert", asset, weight, threshold_pct),
+                remediation: format!("Diversify about {:.2} USD of {} into other assets", excess_usd, asset),
+                orders: price.map(|p| order(TradeSide::Sell, &asset, excess_usd, p)).into_iter().collect(),
+                subject: asset,
+            }
+        })
+        .collect()
+}
+
+/// Stablecoins of `items` priced more than `threshold_pct` percent away from 1 USD, largest
+/// holding first. Stale prices are not trusted to show a de-peg.
+pub fn depeg_alerts(items: &[AllocationItem], threshold_pct: f64) -> Vec<RiskAlert> {
+    let mut stablecoins: BTreeMap<String, (f64, f64, f64)> = BTreeMap::new();
+    for item in items.iter().filter(|i| i.is_stablecoin() && !i.unpriced && !i.stale_price) {
+        let Some(price) = item.price_usd.filter(|p| *p > 0.0) else { continue };
+        let entry = stablecoins.entry(target_symbol(&item.asset)).or_insert((price, 0.0, 0.0));
+        entry.1 += item.value_usd;
+        entry.2 += item.weight;
+    }
+    let mut off_peg: Vec<(String, (f64, f64, f64))> =
+        stablecoins.into_iter().filter(|(_, (price, value, _))| *value > 0.0 && (price - 1.0).abs() * 100.0 > threshold_pct).collect();
+    off_peg.sort_by(|a, b| b.1 .1.total_cmp(&a.1 .1));
+    off_peg
+        .into_iter()
+        .map(|(asset, (price, value_usd, weight))| {
+            let deviation = (price - 1.0).abs() * 100.0;
+            RiskAlert {
+                recommendation_type: RECOMMENDATION_TYPE_DEPEG,
+                severity: Severity::of_breach(deviation - threshold_pct, threshold_pct),
+                threshold: threshold_pct,
+                actual: deviation,
+                assets: vec![asset.clone()],
+                account_id: None,
+                description: format!(
+                    "{} trades at {:.4} USD, {:.2}% off its peg; {:.2} USD ({:.2}% of the portfolio) is exposed",
+                    asset, price, deviation, value_usd, weight
+                ),
+                remediation: format!("Consider moving the {} holdings into a stablecoin that holds its peg", asset),
+                orders: Vec::new(),
+                subject: asset,
+            }
+        })
+        .collect()
+}
+
+/// Alert when an account last synced at `last_synced_at` (or, never synced, created at
+/// `created_at`) is more than `max_days` days old at `now`
+pub fn stale_account_alert(
+    account_id: Uuid,
+    account_name: &str,
+    last_synced_at: Option<DateTime<Utc>>,
+    created_at: DateTime<Utc>,
+    now: DateTime<Utc>,
+    max_days: i64,
+) -> Option<RiskAlert> {
+    let days = (now - last_synced_at.unwrap_or(created_at)).num_days();
+    if max_days <= 0 || days <= max_days {
+        return None;
+    }
+    let description = match last_synced_at {
+        Some(_) => format!("Account {} was last synced {} days ago; its holdings may be out of date", account_name, days),
+        None => format!("Account {} has not synced since it was added {} days ago", account_name, days),
+    };
+    Some(RiskAlert {
+        recommendation_type: RECOMMENDATION_TYPE_STALE_ACCOUNT,
+        subject: account_id.to_string(),
+        severity: Severity::of_breach((days - max_days) as f64, max_days as f64),
+        threshold: max_days as f64,
+        actual: days as f64,
+        assets: Vec::new(),
+        account_id: Some(account_id),
+        description,
+        remediation: format!("Sync {} again and check its credentials or address", account_name),
+        orders: Vec::new(),
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn item(asset: &str, value: f64, weight: f64, stale_price: bool) -> AllocationItem {
+        serde_json::from_value(serde_json::json!({
+            "asset": asset,
+            "quantity": value.to_string(),
+            "price_usd": 1.0,
+            "value_usd": value,
+            "weight": weight,
+            "stale_price": stale_price,
+        }))
+        .unwrap()
+    }
+
+    fn trade(asset: &str, side: TradeSide, quantity: &str, amount_usd: f64) -> PlannedTrade {
+        PlannedTrade {
+            asset: asset.to_string(),
+            side,
+            quantity: quantity.to_string(),
+            amount_usd,
+            price_usd: amount_usd / quantity.parse::<f64>().unwrap(),
+            account_id: None,
+            account_name: None,
+        }
+    }
+
+    #[test]
+    fn test_rebalance_recommendation() {
+        // 7000 ETH, 3000 USDC against 50 / 50; ETH split across two accounts
+        let items = [item("ETH", 7000.0, 70.0, false), item("USDC-ethereum", 3000.0, 30.0, false)];
+        let targets = BTreeMap::from([("ETH".to_string(), 50.0), ("USDC".to_string(), 50.0)]);
+        let plan = RebalancePlan {
+            targets,
+            trades: vec![
+                trade("ETH", TradeSide::Sell, "0.4", 1200.0),
+                trade("ETH", TradeSide::Sell, "0.2", 800.0),
+                trade("USDC", TradeSide::Buy, "2000", 2000.0),
+            ],
+            skipped_trades: 0,
+            notes: Vec::new(),
+        };
+
+        let recommendation = rebalance_recommendation(&items, &plan).unwrap();
+        assert_eq!(recommendation.summary, "Sell 0.6 ETH, buy 2000 USDC");
+        assert_eq!(recommendation.orders.len(), 3);
+        assert_eq!(recommendation.orders[0].estimated_value_usd, "1200.00");
+        assert_eq!(recommendation.projected_allocation, BTreeMap::from([("ETH".to_string(), 50.0), ("USDC".to_string(), 50.0)]));
+        assert_eq!((recommendation.max_drift_before, recommendation.max_drift_after), (20.0, 0.0));
+        assert!(recommendation.rationale.starts_with("Sell 0.6 ETH, buy 2000 USDC to restore the target allocation. ETH is 70.00% (target 50.00%)"));
+
+        // Trades on stale-priced assets are dropped; nothing left means no advice
+        let stale = [item("ETH", 7000.0, 70.0, true), item("USDC", 3000.0, 30.0, false)];
+        let recommendation = rebalance_recommendation(&stale, &plan).unwrap();
+        assert_eq!(recommendation.summary, "Buy 2000 USDC");
+        assert_eq!(recommendation.stale_assets, vec!["ETH".to_string()]);
+        let plan = RebalancePlan { trades: plan.trades[..2].to_vec(), ..plan };
+        assert!(rebalance_recommendation(&stale, &plan).is_none());
+    }
+
+    #[test]
+    fn test_guardrail_violations() {
+        // 5000 BTC, 4000 SOL of which a quarter in perps, 1000 USDT
+        let mut sol = item("SOL", 4000.0, 40.0, false);
+        sol.quantity_by_source = Some(BTreeMap::from([("spot".to_string(), "3000".to_string()), ("perp".to_string(), "1000".to_string())]));
+        let items = [item("BTC", 5000.0, 50.0, false), sol, item("USDT", 1000.0, 10.0, false)];
+        let targets = BTreeMap::from([("BTC".to_string(), 50.0), ("SOL".to_string(), 30.0), ("USDT".to_string(), 20.0)]);
+        let guardrails = PortfolioGuardrails {
+            drift_band: Some(5.0),
+            stablecoin_min: Some(15.0),
+            futures_cap: Some(5.0),
+            max_alt_cap: Some(25.0),
+        };
+
+        let violations = guardrail_violations(&items, &targets, &guardrails);
+        let summary: Vec<_> = violations.iter().map(|v| (v.guardrail.as_str(), v.severity, v.actual)).collect();
+        assert_eq!(
+            summary,
+            vec![
+                ("drift_band", Severity::Medium, 10.0),
+                ("stablecoin_min", Severity::Medium, 10.0),
+                ("futures_cap", Severity::Medium, 10.0),
+                ("max_alt_cap", Severity::Medium, 40.0),
+            ]
+        );
+        assert_eq!(violations[0].assets, vec!["SOL".to_string(), "USDT".to_string()]);
+        assert_eq!(violations[1].orders[0].estimated_value_usd, "500.00");
+        assert_eq!((violations[3].orders[0].action, violations[3].orders[0].quantity.as_str()), (TradeSide::Sell, "1500"));
+
+        // Within every guardrail, or without guardrails, nothing is reported
+        let loose = PortfolioGuardrails { drift_band: Some(20.0), stablecoin_min: Some(5.0), futures_cap: Some(20.0), max_alt_cap: Some(50.0) };
+        assert!(guardrail_violations(&items, &targets, &loose).is_empty());
+        assert!(guardrail_violations(&items, &targets, &PortfolioGuardrails::default()).is_empty());
+    }
+
+    #[test]
+    fn test_risk_alerts() {
+        let thresholds = RiskAlertThresholds::default();
+        assert!(drawdown_alert(-0.1, thresholds.drawdown_pct).is_none());
+        let alert = drawdown_alert(-0.3, thresholds.drawdown_pct).unwrap();
+        assert_eq!((alert.recommendation_type, alert.severity, alert.actual), (RECOMMENDATION_TYPE_DRAWDOWN, Severity::Medium, 30.0));
+
+        // BTC 70%, USDC 10% off its peg, USDT; stablecoins never count as concentrated
+        let mut usdc = item("USDC", 1000.0, 10.0, false);
+        usdc.price_usd = Some(0.9);
+        let items = [item("BTC", 7000.0, 70.0, false), usdc, item("USDT", 2000.0, 20.0, false)];
+        let alerts = concentration_alerts(&items, thresholds.concentration_pct);
+        assert_eq!(alerts.len(), 1);
+        assert_eq!((alerts[0].subject.as_str(), alerts[0].severity), ("BTC", Severity::Medium));
+        assert_eq!(alerts[0].orders[0].estimated_value_usd, "2000.00");
+
+        let alerts = depeg_alerts(&items, thresholds.depeg_pct);
+        assert_eq!(alerts.len(), 1);
+        assert_eq!((alerts[0].subject.as_str(), alerts[0].severity), ("USDC", Severity::High));
+        assert!((alerts[0].actual - 10.0).abs() < 1e-9);
+
+        let now = Utc::now();
+        let account = Uuid::new_v4();
+        assert!(stale_account_alert(account, "OKX", Some(now - chrono::Duration::days(2)), now, now, 3).is_none());
+        let alert = stale_account_alert(account, "OKX", None, now - chrono::Duration::days(10), now, 3).unwrap();
+        assert_eq!((alert.subject, alert.actual, alert.severity), (account.to_string(), 10.0, Severity::High));
+    }
+}
@@ -0,0 +1,456 @@
+//! Domain models for per-portfolio and per-account settings
+//!
+//! Settings are stored as JSON in `portfolios.settings` / `accounts.settings` and parsed into these
+//! typed structs. Every field has a default, so a NULL column or a partial
+//! object behaves exactly like an empty `{}`.
+
+use chrono::{DateTime, Duration, NaiveDate, NaiveTime, Utc};
+use rust_decimal::Decimal;
+use serde::{Deserialize, Serialize};
+use utoipa::ToSchema;
+
+/// Which price an EOD snapshot uses to value each holding.
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, PartialEq, Eq, Default, ToSchema)]
+#[serde(rename_all = "snake_case")]
+pub enum EodPriceMethod {
+    /// Reuse the prices captured by the latest portfolio construction
+    #[default]
+    Allocation,
+    /// Last price recorded before midnight in the portfolio's local time
+    LastBeforeLocalMidnight,
+    /// Last price recorded before 00:00 UTC at the end of the snapshot date
+    UtcMidnight,
+    /// Volume-weighted average of the intraday prices for the snapshot date
+    DailyVwap,
+}
+
+impl EodPriceMethod {
+    /// Stable string form, as stored in JSON
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            EodPriceMethod::Allocation => "allocation",
+            EodPriceMethod::LastBeforeLocalMidnight => "last_before_local_midnight",
+            EodPriceMethod::UtcMidnight => "utc_midnight",
+            EodPriceMethod::DailyVwap => "daily_vwap",
+        }
+    }
+}
+
+/// How EOD snapshots select their close prices.
+///
+/// # JSON Schema
+/// ```json
+/// {
+///   "price_method": "last_before_local_midnight",
+///   "utc_offset_minutes": 420
+/// }
+/// ```
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Default, ToSchema)]
+pub struct EodValuationSettings {
+    /// Price selection method (default: "allocation")
+    #[serde(default)]
+    pub price_method: EodPriceMethod,
+
+    /// Offset of the portfolio's local day from UTC, in minutes (e.g. 420 for UTC+7), within
+    /// ±[`MAX_UTC_OFFSET_MINUTES`]. Used by `last_before_local_midnight` and `daily_vwap`.
+    #[serde(default)]
+    pub utc_offset_minutes: i32,
+}
+
+/// Largest UTC offset of a local day, in minutes (UTC-14:00 to UTC+14:00)
+pub const MAX_UTC_OFFSET_MINUTES: i32 = 14 * 60;
+
+impl EodValuationSettings {
+    /// Reject offsets no time zone has
+    pub fn validate(&self) -> Result<(), String> {
+        if !(-MAX_UTC_OFFSET_MINUTES..=MAX_UTC_OFFSET_MINUTES).contains(&self.utc_offset_minutes) {
+            return Err(format!(
+                "eod_valuation.utc_offset_minutes must be between -{} and {}",
+                MAX_UTC_OFFSET_MINUTES, MAX_UTC_OFFSET_MINUTES
+            ));
+        }
+        Ok(())
+    }
+
+    /// Start and end (exclusive) of the pricing window for a snapshot date.
+    ///
+    /// The window end is the valuation cutoff: prices at or after it are never used.
+    /// `utc_midnight` always uses the UTC day; the other methods use the local day.
+    pub fn price_window(&self, snapshot_date: NaiveDate) -> (DateTime<Utc>, DateTime<Utc>) {
+        let offset = match self.price_method {
+            EodPriceMethod::UtcMidnight => Duration::zero(),
+            _ => Duration::minutes(self.utc_offset_minutes as i64),
+        };
+        let start = snapshot_date.and_time(NaiveTime::MIN).and_utc() - offset;
+        (start, start + Duration::days(1))
+    }
+}
+
+/// How often a portfolio is snapshotted.
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, PartialEq, Eq, Default, ToSchema)]
+#[serde(rename_all = "snake_case")]
+pub enum SnapshotCadence {
+    /// Only the daily EOD snapshot
+    #[default]
+    Eod,
+    /// "hourly" snapshots at 00:00, 06:00, 12:00 and 18:00 UTC, plus the EOD snapshot
+    #[serde(rename = "every_6h")]
+    Every6h,
+    /// "hourly" snapshots every hour, plus the EOD snapshot
+    Hourly,
+}
+
+impl SnapshotCadence {
+    /// Stable string form, as stored in JSON
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            SnapshotCadence::Eod => "eod",
+            SnapshotCadence::Every6h => "every_6h",
+            SnapshotCadence::Hourly => "hourly",
+        }
+    }
+
+    /// Whether an intraday snapshot is due at the given UTC hour (0-23)
+    pub fn is_due(&self, hour: u32) -> bool {
+        match self {
+            SnapshotCadence::Eod => false,
+            SnapshotCadence::Every6h => hour.is_multiple_of(6),
+            SnapshotCadence::Hourly => true,
+        }
+    }
+}
+
+/// How borrowed holdings count towards a portfolio's total value and weights.
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, PartialEq, Eq, Default, ToSchema)]
+#[serde(rename_all = "snake_case")]
+pub enum DebtMode {
+    /// Debt is subtracted: the total is the net value and weights are relative to it
+    #[default]
+    Net,
+    /// Debt is left out: the total is the gross value of the assets and each holding weighs its
+    /// value before debt
+    Gross,
+}
+
+/// Per-portfolio settings stored in `portfolios.settings`.
+///
+/// # JSON Schema
+/// ```json
+/// {
+///   "eod_valuation": { "price_method": "daily_vwap", "utc_offset_minutes": 0 },
+///   "excluded_assets": ["ZKJ", "USDT-tron"],
+///   "include_nfts": true,
+///   "debt_mode": "gross",
+///   "snapshot_cadence": "every_6h"
+/// }
+/// ```
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Default, ToSchema)]
+pub struct PortfolioSettings {
+    /// EOD snapshot valuation settings
+    #[serde(default)]
+    pub eod_valuation: EodValuationSettings,
+
+    /// Asset symbols left out of valuation, allocation and snapshots (case-insensitive).
+    /// A plain symbol such as "LINK" also excludes its chain-specific holdings
+    /// ("LINK-ethereum"); a chain-specific entry only excludes that chain.
+    #[serde(default)]
+    pub excluded_assets: Vec<String>,
+
+    /// Value the accounts' NFTs at collection floor and add them as one "NFT" bucket to
+    /// holdings and allocation (default: false)
+    #[serde(default)]
+    pub include_nfts: bool,
+
+    /// Whether the total value and weights are net of borrowed holdings (default: "net")
+    #[serde(default)]
+    pub debt_mode: DebtMode,
+
+    /// Snapshot cadence: "eod" (default), "every_6h" or "hourly"
+    #[serde(default)]
+    pub snapshot_cadence: SnapshotCadence,
+}
+
+impl PortfolioSettings {
+    /// Parse settings from the stored JSON, falling back to defaults for NULL or invalid values
+    pub fn from_json(value: Option<&serde_json::Value>) -> Self {
+        value
+            .and_then(|v| serde_json::from_value(v.clone()).ok())
+            .unwrap_or_default()
+    }
+
+    /// Check settings sent on portfolio create / update
+    pub fn validate(&self) -> Result<(), String> {
+        self.eod_valuation.validate()
+    }
+
+    /// Whether a holding symbol (raw or canonical) is excluded from this portfolio
+    pub fn excludes_asset(&self, symbol: &str) -> bool {
+        let base = symbol.split_once('-').map_or(symbol, |(base, _)| base);
+        self.excluded_assets.iter().any(|excluded| {
+            let excluded = excluded.trim();
+            excluded.eq_ignore_ascii_case(symbol)
+                || (!excluded.contains('-') && excluded.eq_ignore_ascii_case(base))
+        })
+    }
+}
+
+/// Per-portfolio guardrails stored in `portfolios.guardrails`, as percentages of portfolio
+/// value (0-100). An unset guardrail is not checked.
+///
+/// # JSON Schema
+/// ```json
+/// {
+///   "drift_band": 5,
+///   "stablecoin_min": 10,
+///   "futures_cap": 20,
+///   "max_alt_cap": 50
+/// }
+/// ```
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Default, ToSchema)]
+pub struct PortfolioGuardrails {
+    /// Largest tolerated deviation of an asset's weight from its target, in percentage points
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub drift_band: Option<f64>,
+
+    /// Smallest share of portfolio value to hold in stablecoins
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub stablecoin_min: Option<f64>,
+
+    /// Largest share of portfolio value to hold in futures and perpetuals
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub futures_cap: Option<f64>,
+
+    /// Largest share of portfolio value to hold in any one asset other than BTC, ETH and
+    /// stablecoins
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub max_alt_cap: Option<f64>,
+}
+
+impl PortfolioGuardrails {
+    /// Parse guardrails from the stored JSON, falling back to none for NULL or invalid values
+    pub fn from_json(value: Option<&serde_json::Value>) -> Self {
+        value
+            .and_then(|v| serde_json::from_value(v.clone()).ok())
+            .unwrap_or_default()
+    }
+}
+
+/// Per-account sync settings stored in `accounts.settings`.
+///
+/// # JSON Schema
+/// ```json
+/// {
+///   "include_sub_accounts": true,
+///   "discover_tokens": true,
+///   "defi_protocols": ["aave_v3"],
+///   "position_aggregator": "debank"
+/// }
+/// ```
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Default, ToSchema)]
+pub struct AccountSettings {
+    /// Include balances of the exchange's sub-accounts in sync (default: false).
+    /// Only used by connectors that support sub-accounts (currently OKX and Binance);
+    /// the API key must belong to the master account.
+    #[serde(default)]
+    pub include_sub_accounts: bool,
+
+    /// Discover the tokens of an EVM wallet from its ERC-20 transfers and check their
+    /// balances next to the configured token list (default: false)
+    #[serde(default)]
+    pub discover_tokens: bool,
+
+    /// DeFi protocols whose positions are read for an EVM wallet (e.g. "aave_v3"); supplied
+    /// amounts become holdings and borrows negative holdings (default: none)
+    #[serde(default)]
+    pub defi_protocols: Vec<String>,
+
+    /// Aggregator that reports an EVM wallet's positions in the protocols not listed in
+    /// `defi_protocols` (e.g. "debank"; default: none)
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub position_aggregator: Option<String>,
+}
+
+
+impl AccountSettings {
+    /// Parse settings from the stored JSON, falling back to defaults for NULL or invalid values
+    pub fn from_json(value: Option<&serde_json::Value>) -> Self {
+        value
+            .and_then(|v| serde_json::from_value(v.clone()).ok())
+            .unwrap_or_default()
+    }
+
+    /// Whether positions in `protocol` are read for this account (case-insensitive)
+    pub fn uses_defi_protocol(&self, protocol: &str) -> bool {
+        self.defi_protocols.iter().any(|p| p.trim().eq_ignore_ascii_case(protocol))
+    }
+}
+
+/// Channels a user's recommendations are delivered to, stored in `users.notification_channels`.
+///
+/// # JSON Schema
+/// ```json
+/// {
+///   "in_app": true,
+///   "webhook_url": "https://hooks.example.com/butler"
+/// }
+/// ```
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, ToSchema)]
+pub struct NotificationChannels {
+    /// Raise an in-app notification (default: true)
+    #[serde(default = "default_true")]
+    pub in_app: bool,
+
+    /// URL the recommendation is POSTed to as JSON (default: none)
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub webhook_url: Option<String>,
+}
+
+fn default_true() -> bool {
+    true
+}
+
+impl Default for NotificationChannels {
+    fn default() -> Self {
+        Self { in_app: true, webhook_url: None }
+    }
+}
+
+impl NotificationChannels {
+    /// Parse channels from the stored JSON, falling back to defaults for NULL or invalid values
+    pub fn from_json(value: Option<&serde_json::Value>) -> Self {
+        value
+            .and_then(|v| serde_json::from_value(v.clone()).ok())
+            .unwrap_or_default()
+    }
+}
+
+/// Volume-weighted average price over `(price, volume)` samples.
+///
+/// Samples without a positive volume are ignored; if none of the samples carry
+/// volume, the plain average of the prices is returned instead.
+/// Returns `None` for an empty input.
+pub fn volume_weighted_average(samples: &[(Decimal, Option<Decimal>)]) -> Option<Decimal> {
+    if samples.is_empty() {
+        return None;
+    }
+
+    let (weighted, total_volume) = samples
+        .iter()
+        .filter_map(|(price, volume)| volume.filter(|v| *v > Decimal::ZERO).map(|v| (*price, v)))
+        .fold((Decimal::ZERO, Decimal::ZERO), |(sum, vol), (price, v)| (sum + price * v, vol + v));
+
+    if total_volume > Decimal::ZERO {
+        return Some(weighted / total_volume);
+    }
+
+    let sum: Decimal = samples.iter().map(|(price, _)| *price).sum();
+    Some(sum / Decimal::from(samples.len()))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn date() -> NaiveDate {
+        NaiveDate::from_ymd_opt(2024, 3, 10).unwrap()
+    }
+
+    #[test]
+    fn test_missing_settings_default_to_allocation() {
+        let settings = PortfolioSettings::from_json(None);
+        assert_eq!(settings.eod_valuation.price_method, EodPriceMethod::Allocation);
+
+        let partial = serde_json::json!({ "eod_valuation": { "price_method": "daily_vwap" } });
+        let settings = PortfolioSettings::from_json(Some(&partial));
+        assert_eq!(settings.eod_valuation.price_method, EodPriceMethod::DailyVwap);
+        assert_eq!(settings.eod_valuation.utc_offset_minutes, 0);
+    }
+
+    #[test]
+    fn test_snapshot_cadence() {
+        assert_eq!(PortfolioSettings::from_json(None).snapshot_cadence, SnapshotCadence::Eod);
+        let json = serde_json::json!({ "snapshot_cadence": "every_6h" });
+        let cadence = PortfolioSettings::from_json(Some(&json)).snapshot_cadence;
+        assert_eq!(cadence, SnapshotCadence::Every6h);
+        assert!(cadence.is_due(0) && cadence.is_due(18));
+        assert!(!cadence.is_due(7));
+        assert!(SnapshotCadence::Hourly.is_due(7));
+        assert!(!SnapshotCadence::Eod.is_due(0));
+    }
+
+    #[test]
+    fn test_guardrails_from_json() {
+        let json = serde_json::json!({ "drift_band": 5, "stablecoin_min": 12.5 });
+        let guardrails = PortfolioGuardrails::from_json(Some(&json));
+        assert_eq!(guardrails.drift_band, Some(5.0));
+        assert_eq!(guardrails.stablecoin_min, Some(12.5));
+        assert_eq!(guardrails.futures_cap, None);
+        assert_eq!(PortfolioGuardrails::from_json(None), PortfolioGuardrails::default());
+    }
+
+    #[test]
+    fn test_excluded_assets_match_symbol_and_chain() {
+        let settings = PortfolioSettings {
+            excluded_assets: vec!["link".to_string(), "USDT-tron".to_string()],
+            ..Default::default()
+        };
+        assert!(settings.excludes_asset("LINK"));
+        assert!(settings.excludes_asset("LINK-ethereum"));
+        assert!(settings.excludes_asset("usdt-TRON"));
+        assert!(!settings.excludes_asset("USDT"));
+        assert!(!settings.excludes_asset("USDT-ethereum"));
+        assert!(!settings.excludes_asset("LINKUP"));
+        assert!(!PortfolioSettings::default().excludes_asset("BTC"));
+    }
+
+    #[test]
+    fn test_local_midnight_window_uses_offset() {
+        let settings = EodValuationSettings {
+            price_method: EodPriceMethod::LastBeforeLocalMidnight,
+            utc_offset_minutes: 420,
+        };
+        let (start, end) = settings.price_window(date());
+        assert_eq!(start.to_rfc3339(), "2024-03-09T17:00:00+00:00");
+        assert_eq!(end.to_rfc3339(), "2024-03-10T17:00:00+00:00");
+    }
+
+    #[test]
+    fn test_utc_offset_must_be_a_real_time_zone() {
+        let settings = |utc_offset_minutes| PortfolioSettings {
+            eod_valuation: EodValuationSettings { utc_offset_minutes, ..Default::default() },
+            ..Default::default()
+        };
+        assert!(settings(0).validate().is_ok());
+        assert!(settings(840).validate().is_ok());
+        assert!(settings(-840).validate().is_ok());
+        assert!(settings(841).validate().is_err());
+        assert!(settings(-841).validate().is_err());
+        assert!(settings(i32::MAX).validate().is_err());
+        assert!(settings(i32::MIN).validate().is_err());
+    }
+
+    #[test]
+    fn test_utc_midnight_window_ignores_offset() {
+        let settings = EodValuationSettings {
+            price_method: EodPriceMethod::UtcMidnight,
+            utc_offset_minutes: -300,
+        };
+        let (_, end) = settings.price_window(date());
+        assert_eq!(end.to_rfc3339(), "2024-03-11T00:00:00+00:00");
+    }
+
+    #[test]
+    fn test_volume_weighted_average() {
+        let samples = vec![
+            (Decimal::from(100), Some(Decimal::from(1))),
+            (Decimal::from(200), Some(Decimal::from(3))),
+            (Decimal::from(999), None),
+        ];
+        assert_eq!(volume_weighted_average(&samples), Some(Decimal::from(175)));
+
+        let no_volume = vec![(Decimal::from(10), None), (Decimal::from(20), None)];
+        assert_eq!(volume_weighted_average(&no_volume), Some(Decimal::from(15)));
+
+        assert_eq!(volume_weighted_average(&[]), None);
+    }
+}
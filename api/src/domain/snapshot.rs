@@ -44,11 +44,26 @@ pub struct SnapshotHolding {
     /// Whether asset was unpriced at snapshot time
     #[serde(default)]
     pub unpriced: bool,
+
+    /// Borrowed part of the quantity (negative), when the holding had debt
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub borrowed_quantity: Option<String>,
+}
+
+impl SnapshotHolding {
+    /// USD value owed on this holding at its snapshot price; zero when unpriced
+    pub fn debt_usd(&self) -> f64 {
+        if self.unpriced {
+            return 0.0;
+        }
+        crate::domain::allocation::debt_value_usd(self.borrowed_quantity.as_deref(), self.price_usd)
+    }
 }
 
 impl From<crate::domain::allocation::AllocationItem> for SnapshotHolding {
     fn from(item: crate::domain::allocation::AllocationItem) -> Self {
         Self {
+            borrowed_quantity: item.borrowed_quantity().map(str::to_string),
             asset: item.asset,
             quantity: item.quantity,
             price_usd: item.price_usd,
@@ -67,7 +82,10 @@ impl From<crate::domain::allocation::AllocationItem> for SnapshotHolding {
 ///   "portfolio_name": "My Portfolio",
 ///   "allocation_as_of": "2024-01-01T12:00:00Z",
 ///   "snapshot_time": "2024-01-01T16:00:00Z",
-///   "created_at": "2024-01-01T16:00:00Z"
+///   "created_at": "2024-01-01T16:00:00Z",
+///   "price_method": "utc_midnight",
+///   "utc_offset_minutes": 0,
+///   "valuation_cutoff": "2024-01-02T00:00:00Z"
 /// }
 /// ```
 #[derive(Debug, Clone, Serialize, Deserialize, ToSchema)]
@@ -83,6 +101,22 @@ pub struct SnapshotMetadata {
     
     /// Timestamp when the snapshot record was created
     pub created_at: String,
+
+    /// EOD price selection method used to value the holdings (absent on older snapshots)
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub price_method: Option<String>,
+
+    /// Local-day offset from UTC (minutes) in effect when the snapshot was valued
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub utc_offset_minutes: Option<i32>,
+
+    /// Prices at or after this instant were not considered
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub valuation_cutoff: Option<String>,
+
+    /// Assets with no price in the valuation window that kept their allocation price
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub price_fallback_assets: Vec<String>,
 }
 
 /// Complete snapshot data including holdings, metadata, and totals.
@@ -17,10 +17,16 @@ pub struct Model {
     #[serde(skip_serializing)] // Don't expose in API responses
     pub passphrase_encrypted: Option<String>,
     pub wallet_address: Option<String>,
+    pub wallet_name: Option<String>, // ENS / Unstoppable Domains name the wallet address resolves from
+    pub display_name: Option<String>, // Reverse-resolved name of the wallet address
+    pub names_resolved_at: Option<DateTimeWithTimeZone>,
     pub is_active: bool,
     pub last_synced_at: Option<DateTimeWithTimeZone>,
     pub holdings: Option<Json>, // JSON array of asset holdings
     pub enabled_chains: Option<Json>, // JSON array of enabled chain names for EVM wallets
+    pub settings: Option<Json>, // Per-account sync settings (see domain::AccountSettings)
+    pub first_activity_at: Option<DateTimeWithTimeZone>, // First on-chain transaction of a wallet
+    pub multisig: Option<Json>, // Safe deployments (owners, threshold, modules) of multisig wallets
     pub created_at: DateTimeWithTimeZone,
     pub updated_at: DateTimeWithTimeZone,
 }
@@ -0,0 +1,42 @@
+use sea_orm::entity::prelude::*;
+use serde::{Deserialize, Serialize};
+
+#[derive(Clone, Debug, PartialEq, DeriveEntityModel, Serialize, Deserialize)]
+#[sea_orm(table_name = "asset_price_overrides")]
+pub struct Model {
+    #[sea_orm(primary_key, auto_increment = false)]
+    pub id: Uuid,
+    #[sea_orm(unique)]
+    pub asset_id: Uuid,
+    /// Pinned price source: an `asset_prices.source` (e.g. "coingecko") or "dex"
+    pub source: String,
+    /// Chain of the token contract, for "dex" overrides (e.g. "ethereum", "bsc", "solana")
+    pub chain: Option<String>,
+    /// Token contract priced on DEX pairs, for "dex" overrides (lowercase for EVM chains)
+    pub contract_address: Option<String>,
+    /// Why the override exists
+    pub note: Option<String>,
+    pub is_active: bool,
+    pub created_at: DateTimeWithTimeZone,
+    pub updated_at: DateTimeWithTimeZone,
+}
+
+#[derive(Copy, Clone, Debug, EnumIter, DeriveRelation)]
+pub enum Relation {
+    #[sea_orm(
+        belongs_to = "super::assets::Entity",
+        from = "Column::AssetId",
+        to = "super::assets::Column::Id",
+        on_update = "Cascade",
+        on_delete = "Cascade"
+    )]
+    Assets,
+}
+
+impl Related<super::assets::Entity> for Entity {
+    fn to() -> RelationDef {
+        Relation::Assets.def()
+    }
+}
+
+impl ActiveModelBehavior for ActiveModel {}
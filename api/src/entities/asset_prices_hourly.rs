@@ -0,0 +1,42 @@
+use sea_orm::entity::prelude::*;
+use serde::{Deserialize, Serialize};
+
+#[derive(Clone, Debug, PartialEq, DeriveEntityModel, Serialize, Deserialize)]
+#[sea_orm(table_name = "asset_prices_hourly")]
+pub struct Model {
+    #[sea_orm(primary_key, auto_increment = false)]
+    pub id: Uuid,
+    pub asset_id: Uuid,
+    /// Price source the bucket was aggregated from (e.g. "coinpaprika")
+    pub source: String,
+    /// Start of the UTC hour
+    pub bucket_start: DateTimeWithTimeZone,
+    pub open_usd: Decimal,
+    pub high_usd: Decimal,
+    pub low_usd: Decimal,
+    pub close_usd: Decimal,
+    /// `asset_prices` rows aggregated into the bucket
+    pub ticks: i32,
+    pub created_at: DateTimeWithTimeZone,
+    pub updated_at: DateTimeWithTimeZone,
+}
+
+#[derive(Copy, Clone, Debug, EnumIter, DeriveRelation)]
+pub enum Relation {
+    #[sea_orm(
+        belongs_to = "super::assets::Entity",
+        from = "Column::AssetId",
+        to = "super::assets::Column::Id",
+        on_update = "Cascade",
+        on_delete = "Cascade"
+    )]
+    Assets,
+}
+
+impl Related<super::assets::Entity> for Entity {
+    fn to() -> RelationDef {
+        Relation::Assets.def()
+    }
+}
+
+impl ActiveModelBehavior for ActiveModel {}
@@ -10,11 +10,16 @@ pub struct Model {
     pub name: String,
     pub asset_type: String, // "cryptocurrency", "token", "stablecoin"
     pub coinpaprika_id: Option<String>,
+    pub coingecko_id: Option<String>,
     pub coinmarketcap_id: Option<String>,
     pub logo_url: Option<String>,
     pub description: Option<String>,
     pub decimals: Option<i32>,
     pub is_active: bool,
+    /// When the price provider stopped listing the asset; it is then inactive
+    pub delisted_at: Option<DateTimeWithTimeZone>,
+    /// Category set by administrators, e.g. "l1", "defi", "stablecoin", "meme", "rwa"
+    pub category: Option<String>,
     pub created_at: DateTimeWithTimeZone,
     pub updated_at: DateTimeWithTimeZone,
 }
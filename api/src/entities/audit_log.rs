@@ -0,0 +1,20 @@
+use sea_orm::entity::prelude::*;
+use serde::{Deserialize, Serialize};
+
+#[derive(Clone, Debug, PartialEq, DeriveEntityModel, Serialize, Deserialize)]
+#[sea_orm(table_name = "audit_log")]
+pub struct Model {
+    #[sea_orm(primary_key, auto_increment = false)]
+    pub id: Uuid,
+    pub actor_keycloak_user_id: String, // Admin who performed the action (may have no users row)
+    pub action: String,                 // e.g. "account.transfer"
+    pub entity_type: String,            // e.g. "account"
+    pub entity_id: Option<Uuid>,
+    pub details: Option<Json>,          // Action-specific context (from/to user, memberships, ...)
+    pub created_at: DateTimeWithTimeZone,
+}
+
+#[derive(Copy, Clone, Debug, EnumIter, DeriveRelation)]
+pub enum Relation {}
+
+impl ActiveModelBehavior for ActiveModel {}
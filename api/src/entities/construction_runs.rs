@@ -0,0 +1,40 @@
+use sea_orm::entity::prelude::*;
+use serde::{Deserialize, Serialize};
+
+#[derive(Clone, Debug, PartialEq, DeriveEntityModel, Serialize, Deserialize)]
+#[sea_orm(table_name = "construction_runs")]
+pub struct Model {
+    #[sea_orm(primary_key, auto_increment = false)]
+    pub id: Uuid,
+    pub portfolio_id: Uuid,
+    pub trigger: String,              // "api", ...
+    pub triggered_by: Option<Uuid>,   // User who triggered the run, if any
+    pub inputs_digest: String,        // SHA-256 (hex) of `inputs`
+    pub inputs: Json,                 // Account IDs, quantities per asset and portfolio settings
+    pub price_sources: Json,          // JSON array of the price rows used per asset
+    pub total_value_usd: Decimal,
+    pub holdings_count: i32,
+    pub duration_ms: i64,
+    pub started_at: DateTimeWithTimeZone,
+    pub created_at: DateTimeWithTimeZone,
+}
+
+#[derive(Copy, Clone, Debug, EnumIter, DeriveRelation)]
+pub enum Relation {
+    #[sea_orm(
+        belongs_to = "super::portfolios::Entity",
+        from = "Column::PortfolioId",
+        to = "super::portfolios::Column::Id",
+        on_update = "Cascade",
+        on_delete = "Cascade"
+    )]
+    Portfolios,
+}
+
+impl Related<super::portfolios::Entity> for Entity {
+    fn to() -> RelationDef {
+        Relation::Portfolios.def()
+    }
+}
+
+impl ActiveModelBehavior for ActiveModel {}
@@ -0,0 +1,32 @@
+use sea_orm::entity::prelude::*;
+use serde::{Deserialize, Serialize};
+
+#[derive(Clone, Debug, PartialEq, DeriveEntityModel, Serialize, Deserialize)]
+#[sea_orm(table_name = "cosmos_chains")]
+pub struct Model {
+    #[sea_orm(primary_key, auto_increment = false)]
+    pub id: Uuid,
+    /// Unique chain identifier, e.g. "cosmoshub", "osmosis"
+    pub chain_id: String,
+    /// Human-readable chain name, e.g. "Cosmos Hub"
+    pub name: String,
+    /// LCD (REST) endpoint used to query balances and staking
+    pub lcd_url: String,
+    /// Bech32 prefix of account addresses, e.g. "cosmos", "osmo"
+    pub bech32_prefix: String,
+    /// Base denom of the native staking token, e.g. "uatom"
+    pub native_denom: String,
+    /// Native token symbol, e.g. "ATOM"
+    pub native_symbol: String,
+    /// Decimal places of `native_symbol` over `native_denom` (6 for uatom)
+    pub native_decimals: i32,
+    /// Whether this chain is active for account sync
+    pub is_active: bool,
+    pub created_at: DateTimeWithTimeZone,
+    pub updated_at: DateTimeWithTimeZone,
+}
+
+#[derive(Copy, Clone, Debug, EnumIter, DeriveRelation)]
+pub enum Relation {}
+
+impl ActiveModelBehavior for ActiveModel {}
@@ -0,0 +1,27 @@
+use sea_orm::entity::prelude::*;
+use serde::{Deserialize, Serialize};
+
+#[derive(Clone, Debug, PartialEq, DeriveEntityModel, Serialize, Deserialize)]
+#[sea_orm(table_name = "curve_pools")]
+pub struct Model {
+    #[sea_orm(primary_key, auto_increment = false)]
+    pub id: Uuid,
+    /// EVM chain the pool is deployed on, e.g. "ethereum"
+    pub chain: String,
+    /// Pool name, e.g. "3pool"
+    pub name: String,
+    /// Lowercase pool contract address
+    pub pool_address: String,
+    /// Lowercase LP token contract address
+    pub lp_token_address: String,
+    /// Lowercase Convex reward contract holding staked LP tokens, if the pool is on Convex
+    pub convex_reward_pool: Option<String>,
+    pub is_active: bool,
+    pub created_at: DateTimeWithTimeZone,
+    pub updated_at: DateTimeWithTimeZone,
+}
+
+#[derive(Copy, Clone, Debug, EnumIter, DeriveRelation)]
+pub enum Relation {}
+
+impl ActiveModelBehavior for ActiveModel {}
@@ -0,0 +1,28 @@
+use sea_orm::entity::prelude::*;
+use serde::{Deserialize, Serialize};
+
+#[derive(Clone, Debug, PartialEq, DeriveEntityModel, Serialize, Deserialize)]
+#[sea_orm(table_name = "derivative_assets")]
+pub struct Model {
+    #[sea_orm(primary_key, auto_increment = false)]
+    pub id: Uuid,
+    /// Uppercase symbol of the wrapped or liquid staking token, e.g. "WSTETH"
+    pub symbol: String,
+    /// Uppercase symbol of the underlying asset, e.g. "ETH"
+    pub underlying_symbol: String,
+    /// Underlying units per derivative unit; the last value read for contract-rated rows
+    pub exchange_rate: Decimal,
+    /// Lowercase Ethereum contract the exchange rate is read from, if any
+    pub rate_contract: Option<String>,
+    /// How the rate is read from `rate_contract`, e.g. "steth_per_token"
+    pub rate_method: Option<String>,
+    pub rate_updated_at: Option<DateTimeWithTimeZone>,
+    pub is_active: bool,
+    pub created_at: DateTimeWithTimeZone,
+    pub updated_at: DateTimeWithTimeZone,
+}
+
+#[derive(Copy, Clone, Debug, EnumIter, DeriveRelation)]
+pub enum Relation {}
+
+impl ActiveModelBehavior for ActiveModel {}
@@ -0,0 +1,24 @@
+use sea_orm::entity::prelude::*;
+use serde::{Deserialize, Serialize};
+
+#[derive(Clone, Debug, PartialEq, DeriveEntityModel, Serialize, Deserialize)]
+#[sea_orm(table_name = "discovered_tokens")]
+pub struct Model {
+    #[sea_orm(primary_key, auto_increment = false)]
+    pub id: Uuid,
+    /// EVM chain, e.g. "ethereum"
+    pub chain: String,
+    /// Lowercase wallet address that received the token
+    pub wallet_address: String,
+    /// Lowercase ERC-20 contract address
+    pub contract_address: String,
+    /// Token symbol; `None` when the contract did not report one
+    pub symbol: Option<String>,
+    pub decimals: Option<i16>,
+    pub discovered_at: DateTimeWithTimeZone,
+}
+
+#[derive(Copy, Clone, Debug, EnumIter, DeriveRelation)]
+pub enum Relation {}
+
+impl ActiveModelBehavior for ActiveModel {}
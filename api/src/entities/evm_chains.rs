@@ -16,6 +16,16 @@ pub struct Model {
     pub native_symbol: String,
     /// Whether this chain is active for account sync
     pub is_active: bool,
+    /// NFT API used to discover wallet NFTs: "alchemy" or "reservoir" (NULL: no NFT discovery)
+    pub nft_provider: Option<String>,
+    /// Base URL of the NFT API, e.g. "https://eth-mainnet.g.alchemy.com/nft/v3/<key>"
+    pub nft_api_url: Option<String>,
+    /// Explorer API reading wallet balances: "etherscan" or "blockscout" (NULL: RPC `balanceOf`)
+    pub balance_provider: Option<String>,
+    /// Explorer API base URL, e.g. "https://eth.blockscout.com/api"
+    pub explorer_api_url: Option<String>,
+    #[serde(skip_serializing)] // Don't expose in API responses
+    pub explorer_api_key: Option<String>,
     pub created_at: DateTimeWithTimeZone,
     pub updated_at: DateTimeWithTimeZone,
 }
@@ -0,0 +1,20 @@
+use sea_orm::entity::prelude::*;
+use serde::{Deserialize, Serialize};
+
+#[derive(Clone, Debug, PartialEq, DeriveEntityModel, Serialize, Deserialize)]
+#[sea_orm(table_name = "job_runs")]
+pub struct Model {
+    #[sea_orm(primary_key, auto_increment = false)]
+    pub job_name: String,
+    pub last_status: String, // "running", "succeeded", "failed"
+    pub last_started_at: DateTimeWithTimeZone,
+    pub last_finished_at: Option<DateTimeWithTimeZone>,
+    pub last_succeeded_at: Option<DateTimeWithTimeZone>,
+    pub last_error: Option<String>, // Internal only; never exposed on the public status endpoint
+    pub updated_at: DateTimeWithTimeZone,
+}
+
+#[derive(Copy, Clone, Debug, EnumIter, DeriveRelation)]
+pub enum Relation {}
+
+impl ActiveModelBehavior for ActiveModel {}
@@ -1,27 +1,99 @@
+pub mod account_addresses;
+pub mod account_snapshots;
 pub mod accounts;
 pub mod asset_contracts;
+pub mod asset_price_overrides;
 pub mod asset_prices;
+pub mod asset_prices_daily;
+pub mod asset_prices_hourly;
 pub mod assets;
+pub mod audit_log;
+pub mod construction_runs;
+pub mod cosmos_chains;
+pub mod curve_pools;
+pub mod derivative_assets;
+pub mod discovered_tokens;
 pub mod evm_chains;
 pub mod evm_tokens;
+pub mod fees;
+pub mod fx_rates;
+pub mod holding_anomalies;
+pub mod holding_transactions;
+pub mod income_events;
+pub mod job_runs;
+pub mod nft_holdings;
+pub mod notification_deliveries;
+pub mod notifications;
+pub mod pendle_assets;
 pub mod portfolio_accounts;
 pub mod portfolio_allocations;
+pub mod portfolio_performance;
+pub mod portfolio_pnl;
 pub mod portfolios;
+pub mod positions;
+pub mod rebalance_plans;
 pub mod recommendations;
+pub mod rejected_prices;
 pub mod snapshots;
 pub mod solana_tokens;
+pub mod spam_tokens;
+pub mod staking_rewards;
+pub mod sync_jobs;
+pub mod sync_runs;
+pub mod tax_disposals;
+pub mod tax_lots;
+pub mod token_discovery_scans;
+pub mod trades;
+pub mod transfers;
 pub mod users;
+pub mod yield_vaults;
 
+pub use account_addresses::Entity as AccountAddresses;
+pub use account_snapshots::Entity as AccountSnapshots;
 pub use accounts::Entity as Accounts;
 pub use asset_contracts::Entity as AssetContracts;
+pub use asset_price_overrides::Entity as AssetPriceOverrides;
 pub use asset_prices::Entity as AssetPrices;
+pub use asset_prices_daily::Entity as AssetPricesDaily;
+pub use asset_prices_hourly::Entity as AssetPricesHourly;
 pub use assets::Entity as Assets;
+pub use audit_log::Entity as AuditLog;
+pub use construction_runs::Entity as ConstructionRuns;
+pub use cosmos_chains::Entity as CosmosChains;
+pub use curve_pools::Entity as CurvePools;
+pub use derivative_assets::Entity as DerivativeAssets;
+pub use discovered_tokens::Entity as DiscoveredTokens;
 pub use evm_chains::Entity as EvmChains;
 pub use evm_tokens::Entity as EvmTokens;
+pub use fees::Entity as Fees;
+pub use fx_rates::Entity as FxRates;
+pub use holding_anomalies::Entity as HoldingAnomalies;
+pub use holding_transactions::Entity as HoldingTransactions;
+pub use income_events::Entity as IncomeEvents;
+pub use job_runs::Entity as JobRuns;
+pub use nft_holdings::Entity as NftHoldings;
+pub use notification_deliveries::Entity as NotificationDeliveries;
+pub use notifications::Entity as Notifications;
+pub use pendle_assets::Entity as PendleAssets;
 pub use portfolio_accounts::Entity as PortfolioAccounts;
 pub use portfolio_allocations::Entity as PortfolioAllocations;
+pub use portfolio_performance::Entity as PortfolioPerformance;
+pub use portfolio_pnl::Entity as PortfolioPnl;
 pub use portfolios::Entity as Portfolios;
+pub use positions::Entity as Positions;
+pub use rebalance_plans::Entity as RebalancePlans;
 pub use recommendations::Entity as Recommendations;
+pub use rejected_prices::Entity as RejectedPrices;
 pub use snapshots::Entity as Snapshots;
 pub use solana_tokens::Entity as SolanaTokens;
+pub use spam_tokens::Entity as SpamTokens;
+pub use staking_rewards::Entity as StakingRewards;
+pub use sync_jobs::Entity as SyncJobs;
+pub use sync_runs::Entity as SyncRuns;
+pub use tax_disposals::Entity as TaxDisposals;
+pub use tax_lots::Entity as TaxLots;
+pub use token_discovery_scans::Entity as TokenDiscoveryScans;
+pub use trades::Entity as Trades;
+pub use transfers::Entity as Transfers;
 pub use users::Entity as Users;
+pub use yield_vaults::Entity as YieldVaults;
@@ -0,0 +1,46 @@
+use sea_orm::entity::prelude::*;
+use serde::{Deserialize, Serialize};
+
+#[derive(Clone, Debug, PartialEq, DeriveEntityModel, Serialize, Deserialize)]
+#[sea_orm(table_name = "nft_holdings")]
+pub struct Model {
+    #[sea_orm(primary_key, auto_increment = false)]
+    pub id: Uuid,
+    pub account_id: Uuid,
+    /// EVM chain, e.g. "ethereum"
+    pub chain: String,
+    pub contract_address: String,
+    pub token_id: String,
+    /// "ERC721" or "ERC1155"
+    pub token_standard: Option<String>,
+    /// Tokens of this ID held (always 1 for ERC-721)
+    pub quantity: Decimal,
+    pub collection_name: Option<String>,
+    pub name: Option<String>,
+    pub image_url: Option<String>,
+    /// Collection floor price per token, in `floor_currency`
+    pub floor_price: Option<Decimal>,
+    /// Currency of `floor_price`, e.g. "ETH"
+    pub floor_currency: Option<String>,
+    pub synced_at: DateTimeWithTimeZone,
+}
+
+#[derive(Copy, Clone, Debug, EnumIter, DeriveRelation)]
+pub enum Relation {
+    #[sea_orm(
+        belongs_to = "super::accounts::Entity",
+        from = "Column::AccountId",
+        to = "super::accounts::Column::Id",
+        on_update = "Cascade",
+        on_delete = "Cascade"
+    )]
+    Accounts,
+}
+
+impl Related<super::accounts::Entity> for Entity {
+    fn to() -> RelationDef {
+        Relation::Accounts.def()
+    }
+}
+
+impl ActiveModelBehavior for ActiveModel {}
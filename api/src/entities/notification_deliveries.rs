@@ -0,0 +1,50 @@
+use sea_orm::entity::prelude::*;
+use serde::{Deserialize, Serialize};
+
+#[derive(Clone, Debug, PartialEq, DeriveEntityModel, Serialize, Deserialize)]
+#[sea_orm(table_name = "notification_deliveries")]
+pub struct Model {
+    #[sea_orm(primary_key, auto_increment = false)]
+    pub id: Uuid,
+    pub user_id: Uuid,
+    pub recommendation_id: Option<Uuid>, // Recommendation delivered; None once it is deleted
+    pub channel: String, // "in_app", "webhook"
+    pub dedup_key: String, // Identifies the advice; the same key is not sent twice on a channel within the dedup window
+    pub status: String, // "sent", "failed"
+    pub error: Option<String>, // Why a failed delivery failed
+    pub created_at: DateTimeWithTimeZone,
+}
+
+#[derive(Copy, Clone, Debug, EnumIter, DeriveRelation)]
+pub enum Relation {
+    #[sea_orm(
+        belongs_to = "super::users::Entity",
+        from = "Column::UserId",
+        to = "super::users::Column::Id",
+        on_update = "Cascade",
+        on_delete = "Cascade"
+    )]
+    Users,
+    #[sea_orm(
+        belongs_to = "super::recommendations::Entity",
+        from = "Column::RecommendationId",
+        to = "super::recommendations::Column::Id",
+        on_update = "Cascade",
+        on_delete = "SetNull"
+    )]
+    Recommendations,
+}
+
+impl Related<super::users::Entity> for Entity {
+    fn to() -> RelationDef {
+        Relation::Users.def()
+    }
+}
+
+impl Related<super::recommendations::Entity> for Entity {
+    fn to() -> RelationDef {
+        Relation::Recommendations.def()
+    }
+}
+
+impl ActiveModelBehavior for ActiveModel {}
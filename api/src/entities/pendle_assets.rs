@@ -0,0 +1,31 @@
+use sea_orm::entity::prelude::*;
+use serde::{Deserialize, Serialize};
+
+#[derive(Clone, Debug, PartialEq, DeriveEntityModel, Serialize, Deserialize)]
+#[sea_orm(table_name = "pendle_assets")]
+pub struct Model {
+    #[sea_orm(primary_key, auto_increment = false)]
+    pub id: Uuid,
+    /// EVM chain the token is deployed on, e.g. "ethereum"
+    pub chain: String,
+    /// Lowercase token contract address
+    pub address: String,
+    /// Token symbol as listed by Pendle, e.g. "PT-sUSDE-27MAR2025"
+    pub symbol: String,
+    /// "PT" (principal token) or "YT" (yield token)
+    pub token_type: String,
+    pub decimals: i32,
+    /// Maturity of the token's market
+    pub expiry: DateTimeWithTimeZone,
+    /// Latest USD price per token from the Pendle API; None when Pendle reports no price
+    pub price_usd: Option<Decimal>,
+    /// When the row was last refreshed from the Pendle API
+    pub price_updated_at: DateTimeWithTimeZone,
+    pub created_at: DateTimeWithTimeZone,
+    pub updated_at: DateTimeWithTimeZone,
+}
+
+#[derive(Copy, Clone, Debug, EnumIter, DeriveRelation)]
+pub enum Relation {}
+
+impl ActiveModelBehavior for ActiveModel {}
@@ -11,6 +11,10 @@ pub struct Model {
     pub total_value_usd: Decimal,
     pub holdings: Json, // JSON array of asset holdings with values and weights
     pub created_at: DateTimeWithTimeZone,
+    pub construction_run_id: Option<Uuid>, // Run that produced the current allocation
+    pub price_resolution: Option<Json>, // Per-symbol reconciled price source
+    pub chain_breakdown: Option<Json>, // Value per chain (see domain::ChainExposure)
+    pub venue_breakdown: Option<Json>, // Value per exchange / self-custody (see domain::VenueExposure)
 }
 
 #[derive(Copy, Clone, Debug, EnumIter, DeriveRelation)]
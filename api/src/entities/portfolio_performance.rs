@@ -0,0 +1,38 @@
+use sea_orm::entity::prelude::*;
+use serde::{Deserialize, Serialize};
+
+#[derive(Clone, Debug, PartialEq, DeriveEntityModel, Serialize, Deserialize)]
+#[sea_orm(table_name = "portfolio_performance")]
+pub struct Model {
+    #[sea_orm(primary_key, auto_increment = false)]
+    pub id: Uuid,
+    pub portfolio_id: Uuid,
+    pub date: Date,                     // Snapshot date
+    pub total_value_usd: Decimal,       // Value of the date's latest snapshot
+    pub net_flow_usd: Decimal,          // Deposits minus withdrawals since the previous date
+    pub daily_return: Option<Decimal>,  // Time-weighted return since the previous date; None on the first date
+    pub cumulative_return: Decimal,     // Growth since the first date minus one
+    pub drawdown: Decimal,              // Fall from the highest growth so far (0 or negative)
+    pub unpriced_flows: i32,            // Transfers since the previous date left out for lack of a price
+    pub computed_at: DateTimeWithTimeZone,
+}
+
+#[derive(Copy, Clone, Debug, EnumIter, DeriveRelation)]
+pub enum Relation {
+    #[sea_orm(
+        belongs_to = "super::portfolios::Entity",
+        from = "Column::PortfolioId",
+        to = "super::portfolios::Column::Id",
+        on_update = "Cascade",
+        on_delete = "Cascade"
+    )]
+    Portfolios,
+}
+
+impl Related<super::portfolios::Entity> for Entity {
+    fn to() -> RelationDef {
+        Relation::Portfolios.def()
+    }
+}
+
+impl ActiveModelBehavior for ActiveModel {}
@@ -0,0 +1,38 @@
+use sea_orm::entity::prelude::*;
+use serde::{Deserialize, Serialize};
+
+#[derive(Clone, Debug, PartialEq, DeriveEntityModel, Serialize, Deserialize)]
+#[sea_orm(table_name = "portfolio_pnl")]
+pub struct Model {
+    #[sea_orm(primary_key, auto_increment = false)]
+    pub id: Uuid,
+    pub portfolio_id: Uuid,
+    pub asset: String,                       // Ledger symbol, e.g. "BTC"
+    pub quantity: Decimal,                   // Quantity held according to the ledger
+    pub cost_basis_usd: Decimal,             // Average cost of the held quantity
+    pub realized_pnl_usd: Decimal,           // Proceeds minus cost of disposals
+    pub price_usd: Option<Decimal>,          // Price the held quantity was valued at
+    pub unrealized_pnl_usd: Option<Decimal>, // None when the asset had no price
+    pub unmatched_quantity: Decimal,         // Disposed quantity the ledger has no acquisition for
+    pub computed_at: DateTimeWithTimeZone,
+}
+
+#[derive(Copy, Clone, Debug, EnumIter, DeriveRelation)]
+pub enum Relation {
+    #[sea_orm(
+        belongs_to = "super::portfolios::Entity",
+        from = "Column::PortfolioId",
+        to = "super::portfolios::Column::Id",
+        on_update = "Cascade",
+        on_delete = "Cascade"
+    )]
+    Portfolios,
+}
+
+impl Related<super::portfolios::Entity> for Entity {
+    fn to() -> RelationDef {
+        Relation::Portfolios.def()
+    }
+}
+
+impl ActiveModelBehavior for ActiveModel {}
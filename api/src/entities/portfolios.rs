@@ -12,6 +12,7 @@ pub struct Model {
     pub is_default: bool,
     pub target_allocation: Option<serde_json::Value>,
     pub guardrails: Option<serde_json::Value>,
+    pub settings: Option<serde_json::Value>,
     pub last_constructed_at: Option<DateTimeWithTimeZone>,
     pub created_at: DateTimeWithTimeZone,
     pub updated_at: DateTimeWithTimeZone,
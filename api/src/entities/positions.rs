@@ -0,0 +1,43 @@
+use sea_orm::entity::prelude::*;
+use serde::{Deserialize, Serialize};
+
+#[derive(Clone, Debug, PartialEq, DeriveEntityModel, Serialize, Deserialize)]
+#[sea_orm(table_name = "positions")]
+pub struct Model {
+    #[sea_orm(primary_key, auto_increment = false)]
+    pub id: Uuid,
+    pub account_id: Uuid,
+    pub instrument: String,      // Exchange instrument ID, e.g. "BTC-USDT-SWAP"
+    pub instrument_type: String, // "SWAP", "FUTURES", "OPTION"
+    pub side: String,            // "long", "short"
+    pub size: Decimal,
+    pub entry_price: Option<Decimal>,
+    pub mark_price: Option<Decimal>,
+    pub unrealized_pnl: Option<Decimal>,
+    pub margin: Option<Decimal>,
+    pub margin_currency: Option<String>,
+    pub margin_mode: Option<String>, // "cross", "isolated"
+    pub leverage: Option<Decimal>,
+    pub liquidation_price: Option<Decimal>,
+    pub synced_at: DateTimeWithTimeZone,
+}
+
+#[derive(Copy, Clone, Debug, EnumIter, DeriveRelation)]
+pub enum Relation {
+    #[sea_orm(
+        belongs_to = "super::accounts::Entity",
+        from = "Column::AccountId",
+        to = "super::accounts::Column::Id",
+        on_update = "Cascade",
+        on_delete = "Cascade"
+    )]
+    Accounts,
+}
+
+impl Related<super::accounts::Entity> for Entity {
+    fn to() -> RelationDef {
+        Relation::Accounts.def()
+    }
+}
+
+impl ActiveModelBehavior for ActiveModel {}
@@ -0,0 +1,38 @@
+use sea_orm::entity::prelude::*;
+use serde::{Deserialize, Serialize};
+
+#[derive(Clone, Debug, PartialEq, DeriveEntityModel, Serialize, Deserialize)]
+#[sea_orm(table_name = "rebalance_plans")]
+pub struct Model {
+    #[sea_orm(primary_key, auto_increment = false)]
+    pub id: Uuid,
+    pub portfolio_id: Uuid,
+    pub total_value_usd: Decimal,          // Allocation value the plan was computed from
+    pub min_trade_usd: Decimal,            // Trades below this size were left out
+    pub targets: Json,                     // Guardrail-adjusted target weights by symbol
+    pub trades: Json,                      // JSON array of domain::PlannedTrade
+    pub skipped_trades: i32,               // Trades left out for being below min_trade_usd
+    pub notes: Json,                       // JSON array of strings
+    pub allocation_as_of: DateTimeWithTimeZone,
+    pub created_at: DateTimeWithTimeZone,
+}
+
+#[derive(Copy, Clone, Debug, EnumIter, DeriveRelation)]
+pub enum Relation {
+    #[sea_orm(
+        belongs_to = "super::portfolios::Entity",
+        from = "Column::PortfolioId",
+        to = "super::portfolios::Column::Id",
+        on_update = "Cascade",
+        on_delete = "Cascade"
+    )]
+    Portfolios,
+}
+
+impl Related<super::portfolios::Entity> for Entity {
+    fn to() -> RelationDef {
+        Relation::Portfolios.def()
+    }
+}
+
+impl ActiveModelBehavior for ActiveModel {}
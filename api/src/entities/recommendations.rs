@@ -7,8 +7,8 @@ pub struct Model {
     #[sea_orm(primary_key, auto_increment = false)]
     pub id: Uuid,
     pub portfolio_id: Uuid,
-    pub status: String, // "pending", "approved", "rejected", "executed"
-    pub recommendation_type: String, // "rebalance", "take_profit", "stop_loss"
+    pub status: String, // "pending", "approved", "rejected", "executed", "superseded", "resolved"
+    pub recommendation_type: String, // "rebalance", "guardrail_violation", "drawdown_alert", "concentration_alert", "depeg_alert", "stale_account", "take_profit", "stop_loss"
     pub rationale: String,
     pub proposed_orders: Json, // Array of order objects: [{action: "buy"|"sell", asset, quantity, estimated_price, estimated_value_usd}]
     pub expected_impact: Option<Decimal>,
@@ -16,6 +16,7 @@ pub struct Model {
     pub created_at: DateTimeWithTimeZone,
     pub updated_at: DateTimeWithTimeZone,
     pub executed_at: Option<DateTimeWithTimeZone>,
+    pub construction_run_id: Option<Uuid>, // Latest construction run of the portfolio when created
 }
 
 #[derive(Copy, Clone, Debug, EnumIter, DeriveRelation)]
@@ -0,0 +1,44 @@
+use sea_orm::entity::prelude::*;
+use serde::{Deserialize, Serialize};
+
+#[derive(Clone, Debug, PartialEq, DeriveEntityModel, Serialize, Deserialize)]
+#[sea_orm(table_name = "rejected_prices")]
+pub struct Model {
+    #[sea_orm(primary_key, auto_increment = false)]
+    pub id: Uuid,
+    pub asset_id: Uuid,
+    /// Price source the observation came from (e.g. "coinpaprika")
+    pub source: String,
+    /// Time of the rejected observation
+    pub timestamp: DateTimeWithTimeZone,
+    pub price_usd: Decimal,
+    /// Previous observation the price was compared with
+    pub reference_price_usd: Decimal,
+    pub reference_timestamp: DateTimeWithTimeZone,
+    /// How many times higher or lower than the reference the price is (>= 1)
+    pub deviation_factor: Decimal,
+    /// "pending", "accepted" or "dismissed"
+    pub status: String,
+    pub reviewed_at: Option<DateTimeWithTimeZone>,
+    pub created_at: DateTimeWithTimeZone,
+}
+
+#[derive(Copy, Clone, Debug, EnumIter, DeriveRelation)]
+pub enum Relation {
+    #[sea_orm(
+        belongs_to = "super::assets::Entity",
+        from = "Column::AssetId",
+        to = "super::assets::Column::Id",
+        on_update = "Cascade",
+        on_delete = "Cascade"
+    )]
+    Assets,
+}
+
+impl Related<super::assets::Entity> for Entity {
+    fn to() -> RelationDef {
+        Relation::Assets.def()
+    }
+}
+
+impl ActiveModelBehavior for ActiveModel {}
@@ -8,12 +8,16 @@ pub struct Model {
     pub id: Uuid,
     pub portfolio_id: Uuid,
     pub snapshot_date: Date,
-    pub snapshot_type: String, // "eod", "manual", "hourly"
+    pub snapshot_type: String, // "eod", "manual", "hourly", "backfill"
     pub total_value_usd: Decimal,
     pub holdings: Json, // JSON array of asset holdings
     pub metadata: Option<Json>, // Optional metadata
     pub allocation_id: Option<Uuid>, // Reference to portfolio_allocations
     pub created_at: DateTimeWithTimeZone,
+    pub construction_run_id: Option<Uuid>, // Run that produced the allocation the snapshot was taken from
+    pub gross_value_usd: Option<Decimal>, // Value of the assets, before debt
+    pub debt_usd: Option<Decimal>, // Value owed on borrowed holdings
+    pub net_value_usd: Option<Decimal>, // Gross value minus debt
 }
 
 #[derive(Copy, Clone, Debug, EnumIter, DeriveRelation)]
@@ -0,0 +1,53 @@
+use sea_orm::entity::prelude::*;
+use serde::{Deserialize, Serialize};
+
+#[derive(Clone, Debug, PartialEq, DeriveEntityModel, Serialize, Deserialize)]
+#[sea_orm(table_name = "tax_disposals")]
+pub struct Model {
+    #[sea_orm(primary_key, auto_increment = false)]
+    pub id: Uuid,
+    pub account_id: Uuid,
+    pub lot_id: Option<Uuid>, // None for quantity the ledger has no acquisition for
+    pub asset: String,
+    pub kind: String, // "disposal" (sale or fee) or "transfer" (withdrawal, not taxable)
+    pub quantity: Decimal,
+    pub proceeds_usd: Decimal,
+    pub cost_basis_usd: Decimal, // Share of the lot's cost; zero without a lot
+    pub acquired_at: Option<DateTimeWithTimeZone>,
+    pub disposed_at: DateTimeWithTimeZone,
+    pub created_at: DateTimeWithTimeZone,
+}
+
+#[derive(Copy, Clone, Debug, EnumIter, DeriveRelation)]
+pub enum Relation {
+    #[sea_orm(
+        belongs_to = "super::accounts::Entity",
+        from = "Column::AccountId",
+        to = "super::accounts::Column::Id",
+        on_update = "Cascade",
+        on_delete = "Cascade"
+    )]
+    Accounts,
+    #[sea_orm(
+        belongs_to = "super::tax_lots::Entity",
+        from = "Column::LotId",
+        to = "super::tax_lots::Column::Id",
+        on_update = "Cascade",
+        on_delete = "Cascade"
+    )]
+    TaxLots,
+}
+
+impl Related<super::accounts::Entity> for Entity {
+    fn to() -> RelationDef {
+        Relation::Accounts.def()
+    }
+}
+
+impl Related<super::tax_lots::Entity> for Entity {
+    fn to() -> RelationDef {
+        Relation::TaxLots.def()
+    }
+}
+
+impl ActiveModelBehavior for ActiveModel {}
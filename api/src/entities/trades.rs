@@ -0,0 +1,43 @@
+use sea_orm::entity::prelude::*;
+use serde::{Deserialize, Serialize};
+
+#[derive(Clone, Debug, PartialEq, DeriveEntityModel, Serialize, Deserialize)]
+#[sea_orm(table_name = "trades")]
+pub struct Model {
+    #[sea_orm(primary_key, auto_increment = false)]
+    pub id: Uuid,
+    pub account_id: Uuid,
+    pub trade_id: String, // Exchange fill ID, unique per account
+    pub order_id: Option<String>,
+    pub instrument: String,      // Exchange instrument ID, e.g. "BTC-USDT"
+    pub instrument_type: String, // "SPOT", "MARGIN", "SWAP", "FUTURES", "OPTION"
+    pub base_asset: Option<String>,
+    pub quote_asset: Option<String>,
+    pub side: String, // "buy", "sell"
+    pub quantity: Decimal,
+    pub price: Decimal,
+    pub fee: Option<Decimal>, // Fee paid (negative for rebates)
+    pub fee_currency: Option<String>,
+    pub executed_at: DateTimeWithTimeZone,
+    pub created_at: DateTimeWithTimeZone,
+}
+
+#[derive(Copy, Clone, Debug, EnumIter, DeriveRelation)]
+pub enum Relation {
+    #[sea_orm(
+        belongs_to = "super::accounts::Entity",
+        from = "Column::AccountId",
+        to = "super::accounts::Column::Id",
+        on_update = "Cascade",
+        on_delete = "Cascade"
+    )]
+    Accounts,
+}
+
+impl Related<super::accounts::Entity> for Entity {
+    fn to() -> RelationDef {
+        Relation::Accounts.def()
+    }
+}
+
+impl ActiveModelBehavior for ActiveModel {}
@@ -10,6 +10,9 @@ pub struct Model {
     pub keycloak_user_id: String,
     pub email: Option<String>,
     pub preferred_username: Option<String>,
+    pub base_currency: String, // ISO 4217 code values are converted to, e.g. "USD"
+    pub tax_lot_method: String, // Lot disposal order: "fifo", "lifo" or "hifo"
+    pub notification_channels: Option<Json>, // Channels recommendations are delivered to (see domain::NotificationChannels)
     pub created_at: DateTimeWithTimeZone,
     pub updated_at: DateTimeWithTimeZone,
 }
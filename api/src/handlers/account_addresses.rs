@@ -0,0 +1,310 @@
+use axum::{
+    extract::{Extension, Path, State},
+    http::StatusCode,
+    response::Json,
+    routing::{get, put},
+    Router,
+};
+use axum_keycloak_auth::decode::KeycloakToken;
+use chrono::Utc;
+use sea_orm::{ActiveModelTrait, ColumnTrait, DatabaseConnection, EntityTrait, QueryFilter, QueryOrder, Set};
+use serde::{Deserialize, Serialize};
+use utoipa::ToSchema;
+use uuid::Uuid;
+
+use crate::connectors::registry::{address_family, ConnectorRegistry, ACCOUNT_TYPE_WALLET, DEFAULT_WALLET_KIND};
+use crate::entities::{account_addresses, accounts};
+use crate::helpers::auth::get_or_create_user;
+use super::error::ApiError;
+
+// === Request/Response DTOs ===
+
+#[derive(Debug, Serialize, Deserialize, ToSchema)]
+pub struct AccountAddressResponse {
+    pub id: Uuid,
+    pub account_id: Uuid,
+    /// Wallet address synced alongside the account's `wallet_address`
+    pub address: String,
+    /// User-defined label, e.g. "Ledger"
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub label: Option<String>,
+    pub created_at: String,
+    pub updated_at: String,
+}
+
+impl From<account_addresses::Model> for AccountAddressResponse {
+    fn from(m: account_addresses::Model) -> Self {
+        Self {
+            id: m.id,
+            account_id: m.account_id,
+            address: m.address,
+            label: m.label,
+            created_at: m.created_at.to_rfc3339(),
+            updated_at: m.updated_at.to_rfc3339(),
+        }
+    }
+}
+
+#[derive(Debug, Serialize, Deserialize, ToSchema)]
+pub struct CreateAccountAddressRequest {
+    /// Wallet address on the account's chain family
+    pub address: String,
+    /// Optional label, e.g. "cold storage"
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub label: Option<String>,
+}
+
+#[derive(Debug, Serialize, Deserialize, ToSchema)]
+pub struct UpdateAccountAddressRequest {
+    /// New label; an empty string clears it
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub label: Option<String>,
+}
+
+// === Helper Functions ===
+
+/// Load an account owned by the current user
+async fn owned_account(
+    db: &DatabaseConnection,
+    token: &KeycloakToken<String>,
+    account_id: Uuid,
+) -> Result<accounts::Model, ApiError> {
+    let user = get_or_create_user(db, token).await?;
+
+    let account = accounts::Entity::find_by_id(account_id)
+        .one(db)
+        .await?
+        .ok_or(ApiError::NotFound)?;
+
+    if account.user_id != user.id {
+        return Err(ApiError::Forbidden);
+    }
+
+    Ok(account)
+}
+
+/// Whether two addresses of a chain family are the same; EVM addresses ignore checksum casing
+fn same_address(chain_family: &str, a: &str, b: &str) -> bool {
+    if chain_family == DEFAULT_WALLET_KIND {
+        a.eq_ignore_ascii_case(b)
+    } else {
+        a == b
+    }
+}
+
+fn normalize_label(label: Option<String>) -> Option<String> {
+    label.map(|l| l.trim().to_string()).filter(|l| !l.is_empty())
+}
+
+// === API Handlers ===
+
+/// List the additional addresses of a wallet account
+#[utoipa::path(
+    get,
+    path = "/api/v1/accounts/{account_id}/addresses",
+    params(
+        ("account_id" = Uuid, Path, description = "Account ID")
+    ),
+    responses(
+        (status = 200, description = "Additional wallet addresses", body = Vec<AccountAddressResponse>),
+        (status = 401, description = "Unauthorized"),
+        (status = 403, description = "Forbidden"),
+        (status = 404, description = "Account not found"),
+        (status = 500, description = "Internal server error")
+    ),
+    tag = "accounts"
+)]
+async fn list_account_addresses_handler(
+    State(db): State<DatabaseConnection>,
+    Extension(token): Extension<KeycloakToken<String>>,
+    Path(account_id): Path<Uuid>,
+) -> Result<Json<Vec<AccountAddressResponse>>, ApiError> {
+    let account = owned_account(&db, &token, account_id).await?;
+
+    let rows = account_addresses::Entity::find()
+        .filter(account_addresses::Column::AccountId.eq(account.id))
+        .order_by_asc(account_addresses::Column::CreatedAt)
+        .all(&db)
+        .await?;
+
+    Ok(Json(rows.into_iter().map(|r| r.into()).collect()))
+}
+
+/// Add an address to a wallet account
+///
+/// The address is validated for the account's chain family. From the next sync on, its
+/// balances are added to the account's holdings.
+#[utoipa::path(
+    post,
+    path = "/api/v1/accounts/{account_id}/addresses",
+    params(
+        ("account_id" = Uuid, Path, description = "Account ID")
+    ),
+    request_body = CreateAccountAddressRequest,
+    responses(
+        (status = 201, description = "Address added", body = AccountAddressResponse),
+        (status = 400, description = "Invalid address or not a wallet account"),
+        (status = 401, description = "Unauthorized"),
+        (status = 403, description = "Forbidden"),
+        (status = 404, description = "Account not found"),
+        (status = 409, description = "Address already belongs to the account"),
+        (status = 500, description = "Internal server error")
+    ),
+    tag = "accounts"
+)]
+async fn create_account_address_handler(
+    State(db): State<DatabaseConnection>,
+    Extension(token): Extension<KeycloakToken<String>>,
+    Path(account_id): Path<Uuid>,
+    Json(req): Json<CreateAccountAddressRequest>,
+) -> Result<(StatusCode, Json<AccountAddressResponse>), ApiError> {
+    let account = owned_account(&db, &token, account_id).await?;
+    if account.account_type != ACCOUNT_TYPE_WALLET {
+        return Err(ApiError::BadRequest("Only wallet accounts can hold multiple addresses".to_string()));
+    }
+
+    let address = req.address.trim().to_string();
+    if address.is_empty() {
+        return Err(ApiError::BadRequest("address is required".to_string()));
+    }
+
+    let chain_family = address_family(account.exchange_name.as_deref().unwrap_or(DEFAULT_WALLET_KIND));
+    let factory = ConnectorRegistry::builtin()
+        .find(&account.account_type, account.exchange_name.as_deref())
+        .ok_or_else(|| ApiError::BadRequest(format!("Unsupported wallet kind '{}'", chain_family)))?;
+    factory
+        .validate_wallet_address(&address)
+        .map_err(|e| ApiError::BadRequest(format!("Invalid address: {}", e)))?;
+
+    let existing = account_addresses::Entity::find()
+        .filter(account_addresses::Column::AccountId.eq(account.id))
+        .all(&db)
+        .await?;
+    let duplicate = account
+        .wallet_address
+        .iter()
+        .chain(existing.iter().map(|a| &a.address))
+        .any(|a| same_address(chain_family, a, &address));
+    if duplicate {
+        return Err(ApiError::Conflict(format!("{} already belongs to this account", address)));
+    }
+
+    let now = Utc::now();
+    let row = account_addresses::ActiveModel {
+        id: Set(Uuid::new_v4()),
+        account_id: Set(account.id),
+        address: Set(address),
+        label: Set(normalize_label(req.label)),
+        created_at: Set(now.into()),
+        updated_at: Set(now.into()),
+    }
+    .insert(&db)
+    .await?;
+
+    Ok((StatusCode::CREATED, Json(row.into())))
+}
+
+/// Update the label of an additional address
+#[utoipa::path(
+    put,
+    path = "/api/v1/accounts/{account_id}/addresses/{address_id}",
+    params(
+        ("account_id" = Uuid, Path, description = "Account ID"),
+        ("address_id" = Uuid, Path, description = "Address ID")
+    ),
+    request_body = UpdateAccountAddressRequest,
+    responses(
+        (status = 200, description = "Address updated", body = AccountAddressResponse),
+        (status = 401, description = "Unauthorized"),
+        (status = 403, description = "Forbidden"),
+        (status = 404, description = "Account or address not found"),
+        (status = 500, description = "Internal server error")
+    ),
+    tag = "accounts"
+)]
+async fn update_account_address_handler(
+    State(db): State<DatabaseConnection>,
+    Extension(token): Extension<KeycloakToken<String>>,
+    Path((account_id, address_id)): Path<(Uuid, Uuid)>,
+    Json(req): Json<UpdateAccountAddressRequest>,
+) -> Result<Json<AccountAddressResponse>, ApiError> {
+    let account = owned_account(&db, &token, account_id).await?;
+
+    let row = account_addresses::Entity::find_by_id(address_id)
+        .filter(account_addresses::Column::AccountId.eq(account.id))
+        .one(&db)
+        .await?
+        .ok_or(ApiError::NotFound)?;
+
+    let mut active: account_addresses::ActiveModel = row.into();
+    if req.label.is_some() {
+        active.label = Set(normalize_label(req.label));
+    }
+    active.updated_at = Set(Utc::now().into());
+
+    Ok(Json(active.update(&db).await?.into()))
+}
+
+/// Remove an additional address from a wallet account
+///
+/// Its balances drop out of the account's holdings at the next sync.
+#[utoipa::path(
+    delete,
+    path = "/api/v1/accounts/{account_id}/addresses/{address_id}",
+    params(
+        ("account_id" = Uuid, Path, description = "Account ID"),
+        ("address_id" = Uuid, Path, description = "Address ID")
+    ),
+    responses(
+        (status = 204, description = "Address removed"),
+        (status = 401, description = "Unauthorized"),
+        (status = 403, description = "Forbidden"),
+        (status = 404, description = "Account or address not found"),
+        (status = 500, description = "Internal server error")
+    ),
+    tag = "accounts"
+)]
+async fn delete_account_address_handler(
+    State(db): State<DatabaseConnection>,
+    Extension(token): Extension<KeycloakToken<String>>,
+    Path((account_id, address_id)): Path<(Uuid, Uuid)>,
+) -> Result<StatusCode, ApiError> {
+    let account = owned_account(&db, &token, account_id).await?;
+
+    let row = account_addresses::Entity::find_by_id(address_id)
+        .filter(account_addresses::Column::AccountId.eq(account.id))
+        .one(&db)
+        .await?
+        .ok_or(ApiError::NotFound)?;
+
+    let active: account_addresses::ActiveModel = row.into();
+    active.delete(&db).await?;
+
+    Ok(StatusCode::NO_CONTENT)
+}
+
+/// Create router for account address endpoints
+pub fn create_router() -> Router<DatabaseConnection> {
+    Router::new()
+        .route(
+            "/api/v1/accounts/{account_id}/addresses",
+            get(list_account_addresses_handler).post(create_account_address_handler),
+        )
+        .route(
+            "/api/v1/accounts/{account_id}/addresses/{address_id}",
+            put(update_account_address_handler).delete(delete_account_address_handler),
+        )
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_same_address() {
+        assert!(same_address("evm", "0xd8dA6BF26964aF9D7eEd9e03E53415D37aA96045", "0xd8da6bf26964af9d7eed9e03e53415d37aa96045"));
+        assert!(!same_address("solana", "So11111111111111111111111111111111111111112", "so11111111111111111111111111111111111111112"));
+        assert_eq!(normalize_label(Some("  ".to_string())), None);
+        assert_eq!(normalize_label(Some(" Ledger ".to_string())).as_deref(), Some("Ledger"));
+    }
+}
@@ -0,0 +1,154 @@
+use axum::{
+    extract::{Extension, Path, Query, State},
+    response::Json,
+    routing::get,
+    Router,
+};
+use axum_keycloak_auth::decode::KeycloakToken;
+use sea_orm::{ColumnTrait, DatabaseConnection, EntityTrait, QueryFilter, QueryOrder, QuerySelect};
+use serde::{Deserialize, Serialize};
+use utoipa::ToSchema;
+use uuid::Uuid;
+
+use crate::domain::SnapshotHolding;
+use crate::entities::{account_snapshots, accounts};
+use crate::helpers::auth::get_or_create_user;
+use crate::helpers::pagination::{finish_page, keyset_before, Cursor, PageRequest};
+use crate::helpers::params::parse_date;
+use super::error::ApiError;
+
+// === Request/Response DTOs ===
+
+#[derive(Debug, Deserialize, ToSchema)]
+pub struct ListAccountSnapshotsQuery {
+    /// Filter by start date (YYYY-MM-DD, inclusive)
+    pub start_date: Option<String>,
+    /// Filter by end date (YYYY-MM-DD, inclusive)
+    pub end_date: Option<String>,
+    /// Page size (enables pagination; default 100 when only `cursor` is given)
+    pub limit: Option<u64>,
+    /// Cursor from a previous page's `next_cursor`
+    pub cursor: Option<String>,
+}
+
+#[derive(Debug, Serialize, Deserialize, ToSchema)]
+pub struct AccountSnapshotResponse {
+    pub id: Uuid,
+    pub account_id: Uuid,
+    pub snapshot_date: String, // YYYY-MM-DD
+    /// Value of the priced holdings, borrowed amounts subtracted
+    pub total_value_usd: String,
+    /// Holdings per asset, largest first
+    pub holdings: Vec<SnapshotHolding>,
+    pub created_at: String, // ISO 8601 datetime
+}
+
+impl From<account_snapshots::Model> for AccountSnapshotResponse {
+    fn from(model: account_snapshots::Model) -> Self {
+        Self {
+            id: model.id,
+            account_id: model.account_id,
+            snapshot_date: model.snapshot_date.to_string(),
+            total_value_usd: model.total_value_usd.normalize().to_string(),
+            holdings: serde_json::from_value(model.holdings).unwrap_or_default(),
+            created_at: model.created_at.to_rfc3339(),
+        }
+    }
+}
+
+#[derive(Debug, Serialize, Deserialize, ToSchema)]
+pub struct ListAccountSnapshotsResponse {
+    pub account_id: Uuid,
+    pub snapshots: Vec<AccountSnapshotResponse>,
+    /// Number of snapshots in this response
+    pub total_count: usize,
+    /// Cursor for the next page; absent on the last page
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub next_cursor: Option<String>,
+}
+
+// === API Handlers ===
+
+/// List an account's daily snapshots
+///
+/// Daily snapshots of the account's holdings and value, taken with the EOD portfolio
+/// snapshots, newest first, with optional date filtering. Pass `limit` to page through the
+/// results and `cursor` (from `next_cursor`) to fetch the following page.
+#[utoipa::path(
+    get,
+    path = "/api/v1/accounts/{account_id}/snapshots",
+    params(
+        ("account_id" = Uuid, Path, description = "Account ID"),
+        ("start_date" = Option<String>, Query, description = "Start date filter (YYYY-MM-DD, inclusive)"),
+        ("end_date" = Option<String>, Query, description = "End date filter (YYYY-MM-DD, inclusive)"),
+        ("limit" = Option<u64>, Query, description = "Page size (1-1000); omit to return all snapshots"),
+        ("cursor" = Option<String>, Query, description = "Opaque cursor from a previous page's next_cursor")
+    ),
+    responses(
+        (status = 200, description = "Account snapshots", body = ListAccountSnapshotsResponse),
+        (status = 400, description = "Invalid date, cursor or limit"),
+        (status = 401, description = "Unauthorized"),
+        (status = 403, description = "Forbidden - account does not belong to user"),
+        (status = 404, description = "Account not found"),
+        (status = 500, description = "Internal server error")
+    ),
+    tag = "accounts"
+)]
+pub async fn list_account_snapshots_handler(
+    State(db): State<DatabaseConnection>,
+    Extension(token): Extension<KeycloakToken<String>>,
+    Path(account_id): Path<Uuid>,
+    Query(query): Query<ListAccountSnapshotsQuery>,
+) -> Result<Json<ListAccountSnapshotsResponse>, ApiError> {
+    let user = get_or_create_user(&db, &token).await?;
+
+    let account = accounts::Entity::find_by_id(account_id)
+        .one(&db)
+        .await?
+        .ok_or(ApiError::NotFound)?;
+
+    if account.user_id != user.id {
+        return Err(ApiError::Forbidden);
+    }
+
+    let page = PageRequest::from_query(query.limit, query.cursor.as_deref())?;
+
+    let mut snapshot_query = account_snapshots::Entity::find()
+        .filter(account_snapshots::Column::AccountId.eq(account_id));
+    if let Some(start_date) = query.start_date.as_deref() {
+        snapshot_query = snapshot_query.filter(account_snapshots::Column::SnapshotDate.gte(parse_date(start_date, "start_date")?));
+    }
+    if let Some(end_date) = query.end_date.as_deref() {
+        snapshot_query = snapshot_query.filter(account_snapshots::Column::SnapshotDate.lte(parse_date(end_date, "end_date")?));
+    }
+    if let Some(after) = &page.after {
+        snapshot_query = snapshot_query.filter(keyset_before(
+            account_snapshots::Column::SnapshotDate,
+            account_snapshots::Column::Id,
+            after.timestamp.date_naive(),
+            after.id,
+        ));
+    }
+    snapshot_query = snapshot_query
+        .order_by_desc(account_snapshots::Column::SnapshotDate)
+        .order_by_desc(account_snapshots::Column::Id)
+        .limit(page.fetch_limit());
+
+    let (rows, next_cursor) = finish_page(snapshot_query.all(&db).await?, &page, |s| {
+        Cursor::new(s.snapshot_date.and_time(chrono::NaiveTime::MIN).and_utc(), s.id)
+    });
+    let snapshots: Vec<AccountSnapshotResponse> = rows.into_iter().map(AccountSnapshotResponse::from).collect();
+
+    Ok(Json(ListAccountSnapshotsResponse {
+        account_id,
+        total_count: snapshots.len(),
+        snapshots,
+        next_cursor,
+    }))
+}
+
+// === Router setup ===
+
+pub fn create_router() -> Router<DatabaseConnection> {
+    Router::new().route("/api/v1/accounts/{account_id}/snapshots", get(list_account_snapshots_handler))
+}
@@ -0,0 +1,267 @@
+use axum::{
+    extract::{Extension, Path, State},
+    response::Json,
+    routing::post,
+    Router,
+};
+use axum_keycloak_auth::decode::KeycloakToken;
+use chrono::Utc;
+use sea_orm::{
+    ActiveModelTrait, ActiveValue, ColumnTrait, DatabaseConnection, EntityTrait, QueryFilter,
+    TransactionTrait,
+};
+use serde::{Deserialize, Serialize};
+use serde_json::json;
+use utoipa::ToSchema;
+use uuid::Uuid;
+
+use crate::entities::{accounts, holding_anomalies, portfolio_accounts, portfolios, users};
+use crate::helpers::audit::{record_audit, AUDIT_ACCOUNT_TRANSFER};
+use super::error::ApiError;
+
+// === Request/Response DTOs ===
+
+/// Request to reassign an account to another user
+///
+/// Exactly one of `to_user_id` or `to_email` must be given.
+#[derive(Debug, Deserialize, ToSchema)]
+pub struct TransferAccountRequest {
+    /// Internal ID of the new owner
+    pub to_user_id: Option<Uuid>,
+    /// Email of the new owner
+    pub to_email: Option<String>,
+    /// Add the account to the new owner's default portfolio (default: false)
+    #[serde(default)]
+    pub move_portfolio_memberships: bool,
+}
+
+/// Result of an account transfer
+#[derive(Debug, Serialize, ToSchema)]
+pub struct TransferAccountResponse {
+    pub account_id: Uuid,
+    pub from_user_id: Uuid,
+    pub to_user_id: Uuid,
+    /// Portfolios of the previous owner the account was removed from
+    pub removed_from_portfolio_ids: Vec<Uuid>,
+    /// Default portfolio of the new owner the account was added to
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub added_to_portfolio_id: Option<Uuid>,
+    /// Number of holding anomalies reassigned with the account
+    pub anomalies_reassigned: u64,
+    /// ID of the audit log entry
+    pub audit_id: Uuid,
+}
+
+/// How the new owner is identified
+#[derive(Debug, PartialEq)]
+enum TransferTarget {
+    UserId(Uuid),
+    Email(String),
+}
+
+// === Helper Functions ===
+
+/// Validate that exactly one target identifier is given
+fn transfer_target(req: &TransferAccountRequest) -> Result<TransferTarget, ApiError> {
+    let email = req
+        .to_email
+        .as_deref()
+        .map(str::trim)
+        .filter(|e| !e.is_empty());
+
+    match (req.to_user_id, email) {
+        (Some(id), None) => Ok(TransferTarget::UserId(id)),
+        (None, Some(email)) => Ok(TransferTarget::Email(email.to_string())),
+        _ => Err(ApiError::BadRequest(
+            "Exactly one of to_user_id or to_email must be provided".to_string(),
+        )),
+    }
+}
+
+// === API Handlers ===
+
+/// Transfer an account to another user
+///
+/// Reassigns the account to a new owner. Holdings, the holding ledger, positions and
+/// anomalies stay attached to the account, so history is preserved. The account is
+/// always removed from the previous owner's portfolios; with `move_portfolio_memberships`
+/// it is added to the new owner's default portfolio. An audit entry is written in the
+/// same transaction.
+#[utoipa::path(
+    post,
+    path = "/api/v1/admin/accounts/{account_id}/transfer",
+    params(
+        ("account_id" = Uuid, Path, description = "Account ID")
+    ),
+    request_body = TransferAccountRequest,
+    responses(
+        (status = 200, description = "Account transferred", body = TransferAccountResponse),
+        (status = 400, description = "Invalid request or target user not found"),
+        (status = 401, description = "Unauthorized"),
+        (status = 403, description = "Forbidden - administrator role required"),
+        (status = 404, description = "Account not found"),
+        (status = 500, description = "Internal server error")
+    ),
+    security(
+        ("bearer_auth" = []),
+        ("oauth2_client_credentials" = []),
+        ("oauth2_authorization_code" = [])
+    ),
+    tag = "admin"
+)]
+pub async fn transfer_account_handler(
+    State(db): State<DatabaseConnection>,
+    Extension(token): Extension<KeycloakToken<String>>,
+    Path(account_id): Path<Uuid>,
+    Json(req): Json<TransferAccountRequest>,
+) -> Result<Json<TransferAccountResponse>, ApiError> {
+    let target = transfer_target(&req)?;
+
+    let account = accounts::Entity::find_by_id(account_id)
+        .one(&db)
+        .await?
+        .ok_or(ApiError::NotFound)?;
+
+    let to_user = match &target {
+        TransferTarget::UserId(id) => users::Entity::find_by_id(*id).one(&db).await?,
+        TransferTarget::Email(email) => {
+            users::Entity::find()
+                .filter(users::Column::Email.eq(email.as_str()))
+                .one(&db)
+                .await?
+        }
+    }
+    .ok_or_else(|| ApiError::BadRequest("Target user not found".to_string()))?;
+
+    let from_user_id = account.user_id;
+    if from_user_id == to_user.id {
+        return Err(ApiError::BadRequest("Account already belongs to the target user".to_string()));
+    }
+
+    let txn = db.begin().await?;
+
+    // Memberships point at the previous owner's portfolios, which the new owner cannot see
+    let removed_from_portfolio_ids: Vec<Uuid> = portfolio_accounts::Entity::find()
+        .filter(portfolio_accounts::Column::AccountId.eq(account_id))
+        .all(&txn)
+        .await?
+        .into_iter()
+        .map(|pa| pa.portfolio_id)
+        .collect();
+    portfolio_accounts::Entity::delete_many()
+        .filter(portfolio_accounts::Column::AccountId.eq(account_id))
+        .exec(&txn)
+        .await?;
+
+    let mut added_to_portfolio_id = None;
+    if req.move_portfolio_memberships {
+        let default_portfolio = portfolios::Entity::find()
+            .filter(portfolios::Column::UserId.eq(to_user.id))
+            .filter(portfolios::Column::IsDefault.eq(true))
+            .one(&txn)
+            .await?;
+
+        match default_portfolio {
+            Some(portfolio) => {
+                portfolio_accounts::ActiveModel {
+                    id: ActiveValue::Set(Uuid::new_v4()),
+                    portfolio_id: ActiveValue::Set(portfolio.id),
+                    account_id: ActiveValue::Set(account_id),
+                    added_at: ActiveValue::NotSet,
+                }
+                .insert(&txn)
+                .await?;
+                added_to_portfolio_id = Some(portfolio.id);
+            }
+            None => tracing::info!(
+                "User {} has no default portfolio; account {} transferred without membership",
+                to_user.id,
+                account_id
+            ),
+        }
+    }
+
+    let mut account_update: accounts::ActiveModel = account.into();
+    account_update.user_id = ActiveValue::Set(to_user.id);
+    account_update.updated_at = ActiveValue::Set(Utc::now().into());
+    account_update.update(&txn).await?;
+
+    // Anomalies are part of the account's history and follow it to the new owner
+    let anomalies_reassigned = holding_anomalies::Entity::update_many()
+        .col_expr(holding_anomalies::Column::UserId, sea_orm::sea_query::Expr::value(to_user.id))
+        .filter(holding_anomalies::Column::AccountId.eq(account_id))
+        .exec(&txn)
+        .await?
+        .rows_affected;
+
+    let audit = record_audit(
+        &txn,
+        &token.subject,
+        AUDIT_ACCOUNT_TRANSFER,
+        "account",
+        Some(account_id),
+        json!({
+            "from_user_id": from_user_id,
+            "to_user_id": to_user.id,
+            "removed_from_portfolio_ids": removed_from_portfolio_ids,
+            "added_to_portfolio_id": added_to_portfolio_id,
+            "anomalies_reassigned": anomalies_reassigned,
+        }),
+    )
+    .await?;
+
+    txn.commit().await?;
+
+    tracing::warn!(
+        "Account {} transferred from user {} to user {} by {}",
+        account_id,
+        from_user_id,
+        to_user.id,
+        token.subject
+    );
+
+    Ok(Json(TransferAccountResponse {
+        account_id,
+        from_user_id,
+        to_user_id: to_user.id,
+        removed_from_portfolio_ids,
+        added_to_portfolio_id,
+        anomalies_reassigned,
+        audit_id: audit.id,
+    }))
+}
+
+/// Create router for admin account transfer routes
+pub fn create_router() -> Router<DatabaseConnection> {
+    Router::new().route(
+        "/api/v1/admin/accounts/{account_id}/transfer",
+        post(transfer_account_handler),
+    )
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn request(to_user_id: Option<Uuid>, to_email: Option<&str>) -> TransferAccountRequest {
+        TransferAccountRequest {
+            to_user_id,
+            to_email: to_email.map(str::to_string),
+            move_portfolio_memberships: false,
+        }
+    }
+
+    #[test]
+    fn test_transfer_target_requires_exactly_one_identifier() {
+        let id = Uuid::new_v4();
+        assert_eq!(transfer_target(&request(Some(id), None)).unwrap(), TransferTarget::UserId(id));
+        assert_eq!(
+            transfer_target(&request(None, Some(" a@b.c "))).unwrap(),
+            TransferTarget::Email("a@b.c".to_string())
+        );
+
+        assert!(transfer_target(&request(None, None)).is_err());
+        assert!(transfer_target(&request(None, Some("  "))).is_err());
+        assert!(transfer_target(&request(Some(id), Some("a@b.c"))).is_err());
+    }
+}
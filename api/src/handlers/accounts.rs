@@ -1,19 +1,37 @@
 use axum::{
-    extract::{Extension, Path, State},
+    extract::{Extension, Path, Query, State},
     http::StatusCode,
+    response::sse::{Event, KeepAlive, Sse},
     response::Json,
     routing::{get, post},
     Router,
 };
 use axum_keycloak_auth::decode::KeycloakToken;
-use sea_orm::{ActiveModelTrait, ColumnTrait, DatabaseConnection, EntityTrait, QueryFilter, Set};
+use futures::stream::{self, Stream, StreamExt};
+use sea_orm::{
+    ActiveModelTrait, ColumnTrait, DatabaseConnection, EntityTrait, QueryFilter, QueryOrder,
+    QuerySelect, Set,
+};
 use serde::{Deserialize, Serialize};
+use tokio::sync::broadcast::error::RecvError;
 use utoipa::ToSchema;
 use uuid::Uuid;
 
-use crate::entities::accounts;
+use crate::connectors::registry::{
+    address_family, ConnectorRegistry, ACCOUNT_TYPE_EXCHANGE, ACCOUNT_TYPE_WALLET, DEFAULT_WALLET_KIND,
+    WALLET_KIND_SAFE,
+};
+use crate::connectors::defi::{DefiRegistry, POSITION_AGGREGATORS};
+use crate::domain::AccountSettings;
+use crate::entities::{accounts, holding_transactions, nft_holdings, positions, sync_jobs, sync_runs, trades, transfers};
 use crate::helpers::auth::get_or_create_user;
-use crate::jobs::account_sync;
+use crate::helpers::csv_import::{parse_balances, parse_transactions, CsvColumnMapping, CsvImportError};
+use crate::helpers::name_resolution::{name_service, NameResolver};
+use crate::helpers::pagination::{finish_page, keyset_before, Cursor, PageRequest};
+use crate::jobs::statement_import::{self, ImportRejected};
+use crate::jobs::transfer_sync::{self, NetContribution};
+use crate::jobs::sync_queue::SyncEvent;
+use crate::jobs::{account_sync, holding_ledger, name_resolution, sync_queue};
 use super::error::ApiError;
 
 // === Request/Response DTOs ===
@@ -24,15 +42,30 @@ pub struct CreateAccountRequest {
     pub name: String,
     /// Account type: "exchange" or "wallet"
     pub account_type: String,
-    /// Exchange name (required if account_type is "exchange")
+    /// Exchange name (required if account_type is "exchange"). Must be registered in the connector registry.
+    /// Older clients also pass the wallet kind here; prefer `chain_family`.
     #[serde(skip_serializing_if = "Option::is_none")]
     pub exchange_name: Option<String>,
-    /// Wallet address (required if account_type is "wallet")
+    /// Chain family of a wallet account: "evm" (default), "solana", "bitcoin", "litecoin", "dogecoin"
+    /// or "cosmos" (see `GET /v1/chains`)
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub chain_family: Option<String>,
+    /// Wallet subtype: "safe" for a Safe multisig (EVM chain family only), read through the Safe
+    /// Transaction Service
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub wallet_subtype: Option<String>,
+    /// Wallet address (required if account_type is "wallet", or for "hyperliquid" exchange accounts).
+    /// Bitcoin, Litecoin and Dogecoin wallets accept an address or an account extended public key.
+    /// An ENS (`vitalik.eth`) or Unstoppable Domains (`brad.crypto`) name is resolved to its address;
+    /// the name is kept as `wallet_name` and re-resolved periodically.
     #[serde(skip_serializing_if = "Option::is_none")]
     pub wallet_address: Option<String>,
-    /// Enabled EVM chains for wallet accounts (e.g., ["ethereum", "arbitrum", "bsc"])
+    /// Enabled chains for EVM or Cosmos wallet accounts (e.g., ["ethereum", "arbitrum"] or ["osmosis"])
     #[serde(skip_serializing_if = "Option::is_none")]
     pub enabled_chains: Option<Vec<String>>,
+    /// Account sync settings (e.g., include exchange sub-accounts)
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub settings: Option<AccountSettings>,
     /// API key (for exchange accounts)
     #[serde(skip_serializing_if = "Option::is_none")]
     pub api_key: Option<String>,
@@ -52,6 +85,9 @@ pub struct UpdateAccountRequest {
     /// Whether account is active
     #[serde(skip_serializing_if = "Option::is_none")]
     pub is_active: Option<bool>,
+    /// Account sync settings (replaces the stored settings)
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub settings: Option<AccountSettings>,
     /// API key (for exchange accounts)
     #[serde(skip_serializing_if = "Option::is_none")]
     pub api_key: Option<String>,
@@ -69,6 +105,27 @@ pub struct AccountHolding {
     pub quantity: String,
 }
 
+/// A Safe's configuration on one chain, as read at the last sync
+#[derive(Debug, Serialize, Deserialize, ToSchema, Clone)]
+pub struct MultisigDeploymentResponse {
+    /// EVM chain (e.g. "ethereum")
+    pub chain: String,
+    /// Owner confirmations needed to execute a transaction
+    pub threshold: u32,
+    /// Owner addresses
+    pub owners: Vec<String>,
+    /// Enabled modules, which can execute transactions without owner confirmations
+    pub modules: Vec<String>,
+    /// Transaction guard contract
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub guard: Option<String>,
+    /// Safe contract version (e.g. "1.3.0")
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub version: Option<String>,
+    /// Nonce of the next transaction
+    pub nonce: u64,
+}
+
 #[derive(Debug, Serialize, Deserialize, ToSchema)]
 pub struct AccountResponse {
     pub id: Uuid,
@@ -77,15 +134,40 @@ pub struct AccountResponse {
     pub account_type: String,
     #[serde(skip_serializing_if = "Option::is_none")]
     pub exchange_name: Option<String>,
+    /// Chain family of wallet accounts (e.g. "evm", "bitcoin")
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub chain_family: Option<String>,
+    /// Wallet subtype (e.g. "safe")
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub wallet_subtype: Option<String>,
+    /// Whether the wallet is a multisig (Safe)
+    pub is_multisig: bool,
+    /// Per-chain owners, threshold and modules of a multisig wallet, once read by a sync
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub multisig: Option<Vec<MultisigDeploymentResponse>>,
     #[serde(skip_serializing_if = "Option::is_none")]
     pub wallet_address: Option<String>,
+    /// ENS / Unstoppable Domains name the wallet address is resolved from
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub wallet_name: Option<String>,
+    /// Reverse-resolved (primary) name of the wallet address, for display
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub display_name: Option<String>,
     #[serde(skip_serializing_if = "Option::is_none")]
     pub enabled_chains: Option<Vec<String>>,
+    /// Account sync settings (defaults applied)
+    pub settings: AccountSettings,
     pub is_active: bool,
     #[serde(skip_serializing_if = "Option::is_none")]
     pub last_synced_at: Option<String>,
     #[serde(skip_serializing_if = "Option::is_none")]
     pub holdings: Option<Vec<AccountHolding>>,
+    /// Date of the wallet's first on-chain transaction, once discovered by a sync
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub first_activity_at: Option<String>,
+    /// Whole days since `first_activity_at`
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub account_age_days: Option<i64>,
     pub created_at: String,
     pub updated_at: String,
 }
@@ -102,17 +184,41 @@ impl From<accounts::Model> for AccountResponse {
             serde_json::from_value::<Vec<AccountHolding>>(json.clone()).ok()
         });
         
+        // Wallets store their wallet kind in exchange_name; a subtype kind (Safe) maps back to
+        // the chain family of its addresses
+        let wallet_kind = (account.account_type == ACCOUNT_TYPE_WALLET)
+            .then(|| account.exchange_name.as_deref().unwrap_or(DEFAULT_WALLET_KIND));
+        let chain_family = wallet_kind.map(|kind| address_family(kind).to_string());
+        let wallet_subtype = wallet_kind
+            .filter(|kind| kind.eq_ignore_ascii_case(WALLET_KIND_SAFE))
+            .map(|kind| kind.to_lowercase());
+
+        let multisig = account.multisig.as_ref().and_then(|json| {
+            serde_json::from_value::<Vec<MultisigDeploymentResponse>>(json.clone()).ok()
+        });
+
         Self {
             id: account.id,
             user_id: account.user_id,
             name: account.name,
             account_type: account.account_type,
             exchange_name: account.exchange_name,
+            chain_family,
+            is_multisig: wallet_subtype.is_some(),
+            wallet_subtype,
+            multisig,
             wallet_address: account.wallet_address,
+            wallet_name: account.wallet_name,
+            display_name: account.display_name,
             enabled_chains,
+            settings: AccountSettings::from_json(account.settings.as_ref()),
             is_active: account.is_active,
             last_synced_at: account.last_synced_at.map(|dt| dt.to_rfc3339()),
             holdings,
+            first_activity_at: account.first_activity_at.map(|dt| dt.to_rfc3339()),
+            account_age_days: account
+                .first_activity_at
+                .map(|dt| (chrono::Utc::now() - dt.with_timezone(&chrono::Utc)).num_days()),
             created_at: account.created_at.to_rfc3339(),
             updated_at: account.updated_at.to_rfc3339(),
         }
@@ -131,6 +237,10 @@ pub struct SyncResultResponse {
     pub success: bool,
     #[serde(skip_serializing_if = "Option::is_none")]
     pub error: Option<String>,
+    /// Machine-readable error kind: "rate_limited", "invalid_credentials",
+    /// "permission_denied", "invalid_response" or "api_error" (connector errors only)
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub error_kind: Option<String>,
     pub holdings_count: usize,
 }
 
@@ -140,6 +250,7 @@ impl From<account_sync::SyncResult> for SyncResultResponse {
             account_id: result.account_id,
             success: result.success,
             error: result.error,
+            error_kind: result.error_kind,
             holdings_count: result.holdings_count,
         }
     }
@@ -151,6 +262,159 @@ pub struct SyncInitiatedResponse {
     pub message: String,
     /// Account ID that sync was initiated for
     pub account_id: Uuid,
+    /// Sync job to poll at `GET /api/v1/accounts/{account_id}/sync-status/{job_id}`
+    pub job_id: Uuid,
+    /// "queued", or "running" when the account already had a sync in progress
+    pub status: String,
+}
+
+impl From<sync_jobs::Model> for SyncInitiatedResponse {
+    fn from(job: sync_jobs::Model) -> Self {
+        Self {
+            message: if job.status == sync_queue::SYNC_JOB_QUEUED {
+                "Sync queued".to_string()
+            } else {
+                "Sync already in progress".to_string()
+            },
+            account_id: job.account_id,
+            job_id: job.id,
+            status: job.status,
+        }
+    }
+}
+
+/// Status of a queued account sync
+#[derive(Debug, Serialize, Deserialize, ToSchema)]
+pub struct SyncJobResponse {
+    pub job_id: Uuid,
+    pub account_id: Uuid,
+    /// "queued", "running", "succeeded" or "failed"
+    pub status: String,
+    /// Stage the sync has reached, e.g. "balances" or "trades"; absent while queued
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub stage: Option<String>,
+    pub steps_completed: i32,
+    pub steps_total: i32,
+    /// Share of the stages done (0-100)
+    pub progress_percent: f64,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub holdings_count: Option<i32>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub error: Option<String>,
+    /// Typed connector error, e.g. "rate_limited" or "invalid_credentials"
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub error_kind: Option<String>,
+    pub created_at: String,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub started_at: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub finished_at: Option<String>,
+}
+
+impl From<sync_jobs::Model> for SyncJobResponse {
+    fn from(job: sync_jobs::Model) -> Self {
+        Self {
+            progress_percent: sync_queue::progress_percent(&job),
+            job_id: job.id,
+            account_id: job.account_id,
+            status: job.status,
+            stage: job.stage,
+            steps_completed: job.steps_completed,
+            steps_total: job.steps_total,
+            holdings_count: job.holdings_count,
+            error: job.error,
+            error_kind: job.error_kind,
+            created_at: job.created_at.to_rfc3339(),
+            started_at: job.started_at.map(|t| t.to_rfc3339()),
+            finished_at: job.finished_at.map(|t| t.to_rfc3339()),
+        }
+    }
+}
+
+#[derive(Debug, Deserialize, ToSchema)]
+pub struct ListSyncRunsQuery {
+    /// Page size (enables pagination; default 100 when only `cursor` is given)
+    pub limit: Option<u64>,
+    /// Cursor from a previous page's `next_cursor`
+    pub cursor: Option<String>,
+}
+
+/// Balances read on one chain during a sync
+#[derive(Debug, Serialize, Deserialize, ToSchema)]
+pub struct SyncRunChainResponse {
+    /// Address read, for wallets listing several addresses
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub address: Option<String>,
+    pub chain: String,
+    /// Number of balances read on the chain
+    pub balances: usize,
+    /// Why some or all of the chain's balances could not be read
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub error: Option<String>,
+    pub duration_ms: u64,
+}
+
+impl From<account_sync::SyncChainResult> for SyncRunChainResponse {
+    fn from(result: account_sync::SyncChainResult) -> Self {
+        Self {
+            address: result.address,
+            chain: result.fetch.chain,
+            balances: result.fetch.balances,
+            error: result.fetch.error,
+            duration_ms: result.fetch.duration_ms,
+        }
+    }
+}
+
+/// A past sync of an account
+#[derive(Debug, Serialize, Deserialize, ToSchema)]
+pub struct SyncRunResponse {
+    pub id: Uuid,
+    pub started_at: String,
+    pub finished_at: String,
+    pub success: bool,
+    pub holdings_count: i32,
+    /// Last stage the sync reached, e.g. "balances" or "saving"
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub stage: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub error: Option<String>,
+    /// Typed connector error, e.g. "rate_limited" or "invalid_credentials"
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub error_kind: Option<String>,
+    /// Balances read per chain; empty for exchanges and single-chain wallets
+    pub chains: Vec<SyncRunChainResponse>,
+}
+
+impl From<sync_runs::Model> for SyncRunResponse {
+    fn from(run: sync_runs::Model) -> Self {
+        let chains: Vec<account_sync::SyncChainResult> = run
+            .chains
+            .and_then(|c| serde_json::from_value(c).ok())
+            .unwrap_or_default();
+        Self {
+            id: run.id,
+            started_at: run.started_at.to_rfc3339(),
+            finished_at: run.finished_at.to_rfc3339(),
+            success: run.success,
+            holdings_count: run.holdings_count,
+            stage: run.stage,
+            error: run.error,
+            error_kind: run.error_kind,
+            chains: chains.into_iter().map(SyncRunChainResponse::from).collect(),
+        }
+    }
+}
+
+#[derive(Debug, Serialize, Deserialize, ToSchema)]
+pub struct ListSyncRunsResponse {
+    pub account_id: Uuid,
+    pub syncs: Vec<SyncRunResponse>,
+    /// Number of syncs in this response
+    pub total_count: usize,
+    /// Cursor for the next page; absent on the last page
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub next_cursor: Option<String>,
 }
 
 #[derive(Debug, Serialize, Deserialize, ToSchema)]
@@ -159,10 +423,429 @@ pub struct SyncAllInitiatedResponse {
     pub message: String,
     /// Number of accounts queued for background sync
     pub account_count: usize,
+    /// Sync job of each account, to poll at `GET /api/v1/accounts/{account_id}/sync-status/{job_id}`
+    pub jobs: Vec<SyncInitiatedResponse>,
+}
+
+#[derive(Debug, Serialize, Deserialize, ToSchema)]
+pub struct HoldingTransactionResponse {
+    pub id: Uuid,
+    pub account_id: Uuid,
+    pub asset: String,
+    /// "sync_delta", "correction" or "backfill"
+    pub transaction_type: String,
+    pub quantity_before: String,
+    pub quantity_after: String,
+    pub delta: String,
+    /// ID of the correction that superseded this transaction, if any
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub corrected_by: Option<Uuid>,
+    pub recorded_at: String,
+}
+
+impl From<holding_transactions::Model> for HoldingTransactionResponse {
+    fn from(model: holding_transactions::Model) -> Self {
+        Self {
+            id: model.id,
+            account_id: model.account_id,
+            asset: model.asset,
+            transaction_type: model.transaction_type,
+            quantity_before: model.quantity_before.to_string(),
+            quantity_after: model.quantity_after.to_string(),
+            delta: model.delta.to_string(),
+            corrected_by: model.corrected_by,
+            recorded_at: model.recorded_at.to_rfc3339(),
+        }
+    }
+}
+
+#[derive(Debug, Deserialize, ToSchema)]
+pub struct ListHoldingTransactionsQuery {
+    /// Filter by asset symbol
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub asset: Option<String>,
+    /// Include superseded transactions and their corrections (default: false)
+    #[serde(default)]
+    pub include_corrected: bool,
+    /// Page size (enables pagination; default 100 when only `cursor` is given)
+    pub limit: Option<u64>,
+    /// Cursor from a previous page's `next_cursor`
+    pub cursor: Option<String>,
+}
+
+#[derive(Debug, Serialize, Deserialize, ToSchema)]
+pub struct ListHoldingTransactionsResponse {
+    pub account_id: Uuid,
+    pub transactions: Vec<HoldingTransactionResponse>,
+    /// Number of transactions in this response
+    pub total_count: usize,
+    /// Cursor for the next page; absent on the last page
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub next_cursor: Option<String>,
+}
+
+#[derive(Debug, Serialize, Deserialize, ToSchema)]
+pub struct PositionResponse {
+    pub id: Uuid,
+    /// Exchange instrument ID (e.g., "BTC-USDT-SWAP")
+    pub instrument: String,
+    /// "SWAP", "FUTURES" or "OPTION"
+    pub instrument_type: String,
+    /// "long" or "short"
+    pub side: String,
+    /// Absolute position size in contracts
+    pub size: String,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub entry_price: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub mark_price: Option<String>,
+    /// Unrealized PnL in `margin_currency`
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub unrealized_pnl: Option<String>,
+    /// Margin allocated to the position in `margin_currency`
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub margin: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub margin_currency: Option<String>,
+    /// "cross" or "isolated"
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub margin_mode: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub leverage: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub liquidation_price: Option<String>,
+    pub synced_at: String,
+}
+
+impl From<positions::Model> for PositionResponse {
+    fn from(model: positions::Model) -> Self {
+        Self {
+            id: model.id,
+            instrument: model.instrument,
+            instrument_type: model.instrument_type,
+            side: model.side,
+            size: model.size.to_string(),
+            entry_price: model.entry_price.map(|v| v.to_string()),
+            mark_price: model.mark_price.map(|v| v.to_string()),
+            unrealized_pnl: model.unrealized_pnl.map(|v| v.to_string()),
+            margin: model.margin.map(|v| v.to_string()),
+            margin_currency: model.margin_currency,
+            margin_mode: model.margin_mode,
+            leverage: model.leverage.map(|v| v.to_string()),
+            liquidation_price: model.liquidation_price.map(|v| v.to_string()),
+            synced_at: model.synced_at.to_rfc3339(),
+        }
+    }
+}
+
+#[derive(Debug, Serialize, Deserialize, ToSchema)]
+pub struct ListPositionsResponse {
+    pub account_id: Uuid,
+    pub positions: Vec<PositionResponse>,
+    pub total_count: usize,
+}
+
+#[derive(Debug, Serialize, Deserialize, ToSchema)]
+pub struct NftHoldingResponse {
+    pub id: Uuid,
+    /// EVM chain, e.g. "ethereum"
+    pub chain: String,
+    pub contract_address: String,
+    pub token_id: String,
+    /// "ERC721" or "ERC1155"
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub token_standard: Option<String>,
+    pub quantity: String,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub collection_name: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub name: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub image_url: Option<String>,
+    /// Collection floor price per token, in `floor_currency`
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub floor_price: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub floor_currency: Option<String>,
+    pub synced_at: String,
+}
+
+impl From<nft_holdings::Model> for NftHoldingResponse {
+    fn from(model: nft_holdings::Model) -> Self {
+        Self {
+            id: model.id,
+            chain: model.chain,
+            contract_address: model.contract_address,
+            token_id: model.token_id,
+            token_standard: model.token_standard,
+            quantity: model.quantity.to_string(),
+            collection_name: model.collection_name,
+            name: model.name,
+            image_url: model.image_url,
+            floor_price: model.floor_price.map(|v| v.to_string()),
+            floor_currency: model.floor_currency,
+            synced_at: model.synced_at.to_rfc3339(),
+        }
+    }
+}
+
+#[derive(Debug, Serialize, Deserialize, ToSchema)]
+pub struct ListNftsResponse {
+    pub account_id: Uuid,
+    pub nfts: Vec<NftHoldingResponse>,
+    pub total_count: usize,
+}
+
+#[derive(Debug, Deserialize, ToSchema)]
+pub struct ListTradesQuery {
+    /// Filter by instrument (e.g., "BTC-USDT")
+    pub instrument: Option<String>,
+    /// Page size (1-1000); omit to return all trades
+    pub limit: Option<u64>,
+    /// Cursor from a previous page's `next_cursor`
+    pub cursor: Option<String>,
+}
+
+#[derive(Debug, Serialize, Deserialize, ToSchema)]
+pub struct TradeResponse {
+    pub id: Uuid,
+    /// Exchange trade ID
+    pub trade_id: String,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub order_id: Option<String>,
+    pub instrument: String,
+    /// "SPOT", "MARGIN", "SWAP" or "FUTURES"
+    pub instrument_type: String,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub base_asset: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub quote_asset: Option<String>,
+    /// "buy" or "sell"
+    pub side: String,
+    pub quantity: String,
+    pub price: String,
+    /// Fee paid in `fee_currency` (negative for rebates)
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub fee: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub fee_currency: Option<String>,
+    pub executed_at: String,
+}
+
+impl From<trades::Model> for TradeResponse {
+    fn from(model: trades::Model) -> Self {
+        Self {
+            id: model.id,
+            trade_id: model.trade_id,
+            order_id: model.order_id,
+            instrument: model.instrument,
+            instrument_type: model.instrument_type,
+            base_asset: model.base_asset,
+            quote_asset: model.quote_asset,
+            side: model.side,
+            quantity: model.quantity.to_string(),
+            price: model.price.to_string(),
+            fee: model.fee.map(|v| v.to_string()),
+            fee_currency: model.fee_currency,
+            executed_at: model.executed_at.to_rfc3339(),
+        }
+    }
+}
+
+#[derive(Debug, Serialize, Deserialize, ToSchema)]
+pub struct ListTradesResponse {
+    pub account_id: Uuid,
+    pub trades: Vec<TradeResponse>,
+    pub total_count: usize,
+    /// Cursor for the next page; absent on the last page or when `limit` is not set
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub next_cursor: Option<String>,
+}
+
+#[derive(Debug, Deserialize, ToSchema)]
+pub struct ListTransfersQuery {
+    /// Filter by asset (e.g., "USDT")
+    pub asset: Option<String>,
+    /// Filter by direction ("deposit" or "withdrawal")
+    pub direction: Option<String>,
+    /// Page size (1-1000); omit to return all transfers
+    pub limit: Option<u64>,
+    /// Cursor from a previous page's `next_cursor`
+    pub cursor: Option<String>,
+}
+
+#[derive(Debug, Serialize, Deserialize, ToSchema)]
+pub struct TransferResponse {
+    pub id: Uuid,
+    /// Exchange deposit/withdrawal ID
+    pub transfer_id: String,
+    /// "deposit" or "withdrawal"
+    pub direction: String,
+    pub asset: String,
+    /// Amount moved, excluding the fee
+    pub amount: String,
+    /// Withdrawal network fee, in `asset`
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub fee: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub network: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub tx_hash: Option<String>,
+    pub occurred_at: String,
+}
+
+impl From<transfers::Model> for TransferResponse {
+    fn from(model: transfers::Model) -> Self {
+        Self {
+            id: model.id,
+            transfer_id: model.transfer_id,
+            direction: model.direction,
+            asset: model.asset,
+            amount: model.amount.to_string(),
+            fee: model.fee.map(|v| v.to_string()),
+            network: model.network,
+            tx_hash: model.tx_hash,
+            occurred_at: model.occurred_at.to_rfc3339(),
+        }
+    }
+}
+
+#[derive(Debug, Serialize, Deserialize, ToSchema)]
+pub struct NetContributionResponse {
+    pub asset: String,
+    pub deposited: String,
+    /// Withdrawn amount including withdrawal fees
+    pub withdrawn: String,
+    /// Deposited minus withdrawn
+    pub net: String,
+}
+
+impl From<NetContribution> for NetContributionResponse {
+    fn from(c: NetContribution) -> Self {
+        Self {
+            asset: c.asset,
+            deposited: c.deposited.to_string(),
+            withdrawn: c.withdrawn.to_string(),
+            net: c.net.to_string(),
+        }
+    }
+}
+
+#[derive(Debug, Serialize, Deserialize, ToSchema)]
+pub struct ListTransfersResponse {
+    pub account_id: Uuid,
+    pub transfers: Vec<TransferResponse>,
+    pub total_count: usize,
+    /// Cursor for the next page; absent on the last page or when `limit` is not set
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub next_cursor: Option<String>,
+    /// Net contributions per asset over all of the account's transfers (filters and paging ignored)
+    pub net_contributions: Vec<NetContributionResponse>,
+}
+
+#[derive(Debug, Deserialize, ToSchema)]
+pub struct ImportStatementRequest {
+    /// "balances" (one row per holding; replaces the holdings) or "transactions"
+    /// (one row per quantity change; applied on top of the holdings)
+    pub kind: String,
+    /// CSV text with a header row
+    pub csv: String,
+    /// Column names to read (defaults: asset, quantity, timestamp)
+    #[serde(default)]
+    pub mapping: CsvColumnMapping,
+    /// Field delimiter (default ",")
+    pub delimiter: Option<char>,
+}
+
+#[derive(Debug, Serialize, Deserialize, ToSchema)]
+pub struct ImportStatementResponse {
+    pub account_id: Uuid,
+    pub kind: String,
+    /// Data rows read from the CSV
+    pub rows: usize,
+    /// Holdings stored on the account after the import
+    pub holdings_count: usize,
+    /// Ledger rows written (transaction statements)
+    pub transactions_imported: usize,
+    /// Rows skipped as already imported (transaction statements)
+    pub transactions_skipped: usize,
 }
 
 // === Helper Functions ===
 
+/// Registry name to look an account up by: the exchange name, or a wallet's chain family.
+///
+/// Wallets may still pass their kind as `exchange_name`; giving both with different values is an error.
+fn connector_name(
+    account_type: &str,
+    exchange_name: Option<String>,
+    chain_family: Option<String>,
+) -> Result<Option<String>, ApiError> {
+    match (exchange_name, chain_family) {
+        (_, Some(_)) if account_type != ACCOUNT_TYPE_WALLET => Err(ApiError::BadRequest(
+            "chain_family only applies to wallet accounts".to_string(),
+        )),
+        (Some(name), Some(family)) if !name.eq_ignore_ascii_case(&family) => Err(ApiError::BadRequest(format!(
+            "exchange_name '{}' conflicts with chain_family '{}'",
+            name, family
+        ))),
+        (name, family) => Ok(family.or(name)),
+    }
+}
+
+/// Reject DeFi protocols in account settings that have no registered adapter, and unknown
+/// position aggregators
+fn validate_settings(settings: &AccountSettings) -> Result<(), ApiError> {
+    if let Some(aggregator) = settings
+        .position_aggregator
+        .as_deref()
+        .filter(|name| !POSITION_AGGREGATORS.iter().any(|known| name.trim().eq_ignore_ascii_case(known)))
+    {
+        return Err(ApiError::BadRequest(format!(
+            "Unsupported position aggregator '{}'; supported: {}",
+            aggregator,
+            POSITION_AGGREGATORS.join(", ")
+        )));
+    }
+    let registry = DefiRegistry::builtin();
+    match settings.defi_protocols.iter().find(|p| registry.find(p).is_none()) {
+        Some(unknown) => Err(ApiError::BadRequest(format!(
+            "Unsupported DeFi protocol '{}'; supported: {}",
+            unknown,
+            registry.protocols().join(", ")
+        ))),
+        None => Ok(()),
+    }
+}
+
+/// Apply a wallet subtype to the wallet kind from [`connector_name`]; Safe wallets are stored as
+/// the `safe` kind and must be on the EVM chain family
+fn apply_wallet_subtype(
+    account_type: &str,
+    wallet_kind: Option<String>,
+    wallet_subtype: Option<&str>,
+) -> Result<Option<String>, ApiError> {
+    let Some(subtype) = wallet_subtype.map(|s| s.trim().to_lowercase()).filter(|s| !s.is_empty()) else {
+        return Ok(wallet_kind);
+    };
+    if account_type != ACCOUNT_TYPE_WALLET {
+        return Err(ApiError::BadRequest("wallet_subtype only applies to wallet accounts".to_string()));
+    }
+    if subtype != WALLET_KIND_SAFE {
+        return Err(ApiError::BadRequest(format!(
+            "Unsupported wallet_subtype '{}'; supported: {}",
+            subtype, WALLET_KIND_SAFE
+        )));
+    }
+    match wallet_kind.as_deref() {
+        None => Ok(Some(subtype)),
+        Some(kind) if address_family(kind).eq_ignore_ascii_case(DEFAULT_WALLET_KIND) => Ok(Some(subtype)),
+        Some(kind) => Err(ApiError::BadRequest(format!(
+            "Safe wallets use the evm chain family, not '{}'",
+            kind
+        ))),
+    }
+}
 
 // === API Handlers ===
 
@@ -244,28 +927,76 @@ async fn get_account_handler(
 async fn create_account_handler(
     State(db): State<DatabaseConnection>,
     Extension(token): Extension<KeycloakToken<String>>,
-    Json(req): Json<CreateAccountRequest>,
+    Json(mut req): Json<CreateAccountRequest>,
 ) -> Result<Json<AccountResponse>, ApiError> {
     let user = get_or_create_user(&db, &token).await?;
 
-    // Validate account type
-    if req.account_type != "exchange" && req.account_type != "wallet" {
-        return Err(ApiError::BadRequest(
-            "account_type must be 'exchange' or 'wallet'".to_string(),
-        ));
+    // Validate account type and exchange/wallet kind against the connector registry
+    let registry = ConnectorRegistry::builtin();
+    let account_types = registry.account_types();
+    if !account_types.contains(&req.account_type.as_str()) {
+        return Err(ApiError::BadRequest(format!(
+            "account_type must be one of: {}",
+            account_types.join(", ")
+        )));
     }
 
-    // Validate required fields based on account type
-    if req.account_type == "exchange" && req.exchange_name.is_none() {
+    if req.account_type == ACCOUNT_TYPE_EXCHANGE && req.exchange_name.is_none() {
         return Err(ApiError::BadRequest(
             "exchange_name is required for exchange accounts".to_string(),
         ));
     }
 
-    if req.account_type == "wallet" && req.wallet_address.is_none() {
-        return Err(ApiError::BadRequest(
-            "wallet_address is required for wallet accounts".to_string(),
-        ));
+    let exchange_name = connector_name(&req.account_type, req.exchange_name, req.chain_family)?;
+    let exchange_name = apply_wallet_subtype(&req.account_type, exchange_name, req.wallet_subtype.as_deref())?;
+    if let Some(settings) = &req.settings {
+        validate_settings(settings)?;
+    }
+    let factory = registry
+        .find(&req.account_type, exchange_name.as_deref())
+        .ok_or_else(|| {
+            ApiError::BadRequest(format!(
+                "Unsupported {} '{}'; supported: {}",
+                req.account_type,
+                exchange_name.as_deref().unwrap_or_default(),
+                registry.names(&req.account_type).join(", ")
+            ))
+        })?;
+
+    // Wallets and Hyperliquid are read by wallet address instead of API credentials
+    if factory.requires_wallet_address() && req.wallet_address.is_none() {
+        return Err(ApiError::BadRequest(format!(
+            "wallet_address is required for {} accounts",
+            if req.account_type == ACCOUNT_TYPE_WALLET { ACCOUNT_TYPE_WALLET } else { factory.name() }
+        )));
+    }
+
+    // Resolve ENS / Unstoppable Domains names to the address the connector syncs
+    let resolver = match req.wallet_address {
+        Some(_) => Some(NameResolver::from_db(&db).await),
+        None => None,
+    };
+    let chain_family = if req.account_type == ACCOUNT_TYPE_WALLET {
+        address_family(exchange_name.as_deref().unwrap_or(DEFAULT_WALLET_KIND))
+    } else {
+        DEFAULT_WALLET_KIND
+    };
+    let mut wallet_name = None;
+    if let (Some(name), Some(resolver)) = (req.wallet_address.as_deref().filter(|a| name_service(a).is_some()), &resolver) {
+        let name = name.trim().to_lowercase();
+        let address = resolver
+            .resolve(&name, chain_family)
+            .await
+            .map_err(|e| ApiError::BadRequest(format!("Could not resolve {}: {}", name, e)))?
+            .ok_or_else(|| ApiError::BadRequest(format!("{} does not resolve to a {} address", name, chain_family)))?;
+        req.wallet_address = Some(address);
+        wallet_name = Some(name);
+    }
+
+    if let Some(address) = req.wallet_address.as_deref() {
+        factory
+            .validate_wallet_address(address)
+            .map_err(|e| ApiError::BadRequest(format!("Invalid wallet_address: {}", e)))?;
     }
 
     // Serialize enabled_chains if provided
@@ -287,9 +1018,11 @@ async fn create_account_handler(
         user_id: Set(user.id),
         name: Set(req.name),
         account_type: Set(req.account_type),
-        exchange_name: Set(req.exchange_name),
+        exchange_name: Set(exchange_name),
         wallet_address: Set(req.wallet_address),
+        wallet_name: Set(wallet_name),
         enabled_chains: Set(enabled_chains_json.map(|v| v.into())),
+        settings: Set(req.settings.map(|s| serde_json::json!(s))),
         api_key_encrypted: Set(req.api_key), // TODO: Encrypt before storing
         api_secret_encrypted: Set(req.api_secret), // TODO: Encrypt before storing
         passphrase_encrypted: Set(req.passphrase), // TODO: Encrypt before storing
@@ -299,6 +1032,12 @@ async fn create_account_handler(
 
     let account = new_account.insert(&db).await?;
 
+    // Look up the address's display name; the scheduled name resolution job retries failures
+    let account = match &resolver {
+        Some(resolver) => name_resolution::resolve_account_names(&db, resolver, account).await?.0,
+        None => account,
+    };
+
     Ok(Json(account.into()))
 }
 
@@ -349,6 +1088,10 @@ async fn update_account_handler(
     if let Some(is_active) = req.is_active {
         active_account.is_active = Set(is_active);
     }
+    if let Some(settings) = req.settings {
+        validate_settings(&settings)?;
+        active_account.settings = Set(Some(serde_json::json!(settings)));
+    }
     if let Some(api_key) = req.api_key {
         active_account.api_key_encrypted = Set(Some(api_key)); // TODO: Encrypt before storing
     }
@@ -407,8 +1150,10 @@ async fn delete_account_handler(
 
 /// Sync a specific account
 ///
-/// Triggers a background sync for a specific account to fetch latest balances.
-/// Returns immediately with 202 Accepted while sync runs in the background.
+/// Queues a background sync for a specific account to fetch latest balances and returns
+/// immediately with 202 Accepted and the sync job to poll at
+/// `GET /api/v1/accounts/{account_id}/sync-status/{job_id}`. An account already queued or
+/// syncing gets its current job back.
 #[utoipa::path(
     post,
     path = "/api/v1/accounts/{account_id}/sync",
@@ -416,7 +1161,7 @@ async fn delete_account_handler(
         ("account_id" = Uuid, Path, description = "Account ID to sync")
     ),
     responses(
-        (status = 202, description = "Sync started in background", body = SyncInitiatedResponse),
+        (status = 202, description = "Sync queued", body = SyncInitiatedResponse),
         (status = 401, description = "Unauthorized"),
         (status = 403, description = "Forbidden"),
         (status = 404, description = "Account not found"),
@@ -443,110 +1188,706 @@ async fn sync_account_handler(
         return Err(ApiError::Forbidden);
     }
 
-    // Spawn background sync task – return 202 immediately so the UI is not blocked.
-    // A watcher task monitors the JoinHandle so panics are observable in logs.
-    let db_bg = db.clone();
-    let handle = tokio::spawn(async move {
-        match account_sync::sync_account(&db_bg, account_id).await {
-            Ok(result) => {
-                if result.success {
-                    tracing::info!(
-                        "Background sync completed for account {}: {} holdings",
-                        account_id, result.holdings_count
-                    );
-                } else {
-                    tracing::warn!(
-                        "Background sync finished with error for account {}: {:?}",
-                        account_id, result.error
-                    );
-                }
-            }
-            Err(e) => {
-                tracing::error!("Background sync failed for account {}: {}", account_id, e);
-            }
-        }
-    });
-    tokio::spawn(async move {
-        if let Err(e) = handle.await {
-            tracing::error!("Background sync task panicked for account {}: {:?}", account_id, e);
-        }
-    });
+    // Queue the sync – return 202 immediately so the UI is not blocked
+    let job = sync_queue::enqueue_account_sync(&db, &account).await?;
 
     Ok((
         StatusCode::ACCEPTED,
-        Json(SyncInitiatedResponse {
-            message: "Sync started in background".to_string(),
-            account_id,
-        }),
+        Json(SyncInitiatedResponse::from(job)),
     ))
 }
 
-/// Sync all accounts for the authenticated user
+/// Sync job of an account currently owned by `user_id`.
+///
+/// Ownership is checked on the account rather than `sync_jobs.user_id`, which keeps the owner
+/// at the time of the sync when the account is transferred.
+async fn find_sync_job(
+    db: &DatabaseConnection,
+    user_id: Uuid,
+    account_id: Uuid,
+    job_id: Uuid,
+) -> Result<Option<sync_jobs::Model>, sea_orm::DbErr> {
+    sync_jobs::Entity::find_by_id(job_id)
+        .filter(sync_jobs::Column::AccountId.eq(account_id))
+        .inner_join(accounts::Entity)
+        .filter(accounts::Column::UserId.eq(user_id))
+        .one(db)
+        .await
+}
+
+/// Get the status of a sync job
 ///
-/// Triggers background syncs for all active accounts belonging to the authenticated user.
-/// Returns immediately with 202 Accepted while syncs run in the background.
+/// Returns the job's status, the stage its sync has reached and, once finished, the number of
+/// holdings or the error.
 #[utoipa::path(
-    post,
-    path = "/api/v1/accounts/sync-all",
+    get,
+    path = "/api/v1/accounts/{account_id}/sync-status/{job_id}",
+    params(
+        ("account_id" = Uuid, Path, description = "Account ID"),
+        ("job_id" = Uuid, Path, description = "Sync job ID returned by the sync endpoint")
+    ),
     responses(
-        (status = 202, description = "Sync started in background", body = SyncAllInitiatedResponse),
+        (status = 200, description = "Sync job status", body = SyncJobResponse),
         (status = 401, description = "Unauthorized"),
+        (status = 404, description = "Account or sync job not found"),
         (status = 500, description = "Internal server error")
     ),
 
     tag = "accounts"
 )]
-async fn sync_all_accounts_handler(
+async fn get_sync_status_handler(
     State(db): State<DatabaseConnection>,
     Extension(token): Extension<KeycloakToken<String>>,
-) -> Result<(StatusCode, Json<SyncAllInitiatedResponse>), ApiError> {
-    // Get or create user
+    Path((account_id, job_id)): Path<(Uuid, Uuid)>,
+) -> Result<Json<SyncJobResponse>, ApiError> {
     let user = get_or_create_user(&db, &token).await?;
 
-    // Fetch accounts now so we can report the count in the response
-    let active_accounts = accounts::Entity::find()
-        .filter(accounts::Column::UserId.eq(user.id))
-        .filter(accounts::Column::IsActive.eq(true))
-        .all(&db)
-        .await?;
+    let job = find_sync_job(&db, user.id, account_id, job_id)
+        .await?
+        .ok_or(ApiError::NotFound)?;
 
-    let account_count = active_accounts.len();
-    let user_id = user.id;
-
-    // Spawn a single background task that syncs all accounts sequentially.
-    // A watcher task monitors the JoinHandle so panics are observable in logs.
-    let db_bg = db.clone();
-    let handle = tokio::spawn(async move {
-        tracing::info!("Background sync started for {} accounts of user {}", account_count, user_id);
-        match account_sync::sync_user_accounts(&db_bg, user_id).await {
-            Ok(results) => {
-                let successful = results.iter().filter(|r| r.success).count();
-                let failed = results.len() - successful;
-                tracing::info!(
-                    "Background sync completed for user {}: {} successful, {} failed",
-                    user_id, successful, failed
-                );
-            }
-            Err(e) => {
-                tracing::error!("Background sync failed for user {}: {}", user_id, e);
-            }
-        }
-    });
-    tokio::spawn(async move {
-        if let Err(e) = handle.await {
-            tracing::error!("Background sync-all task panicked for user {}: {:?}", user_id, e);
+    Ok(Json(SyncJobResponse::from(job)))
+}
+
+/// Stream the progress of a sync job
+///
+/// Server-sent events: a `snapshot` of where the job stands (status, stage and the chains
+/// reached so far), then a `stage` event as the sync enters each stage, a `chain` event as
+/// reading each chain of a multi-chain wallet starts (`in_progress`) and finishes (`done`,
+/// with the balances read or the error), and a `finished` event with the outcome, after
+/// which the stream ends. Every event carries its name in a `type` field. A finished job
+/// streams its snapshot and outcome at once; a job running in another API instance streams
+/// only its snapshot.
+#[utoipa::path(
+    get,
+    path = "/api/v1/accounts/{account_id}/sync-status/{job_id}/events",
+    params(
+        ("account_id" = Uuid, Path, description = "Account ID"),
+        ("job_id" = Uuid, Path, description = "Sync job ID returned by the sync endpoint")
+    ),
+    responses(
+        (status = 200, description = "Stream of sync progress events", content_type = "text/event-stream"),
+        (status = 401, description = "Unauthorized"),
+        (status = 404, description = "Account or sync job not found"),
+        (status = 500, description = "Internal server error")
+    ),
+    tag = "accounts"
+)]
+async fn stream_sync_status_handler(
+    State(db): State<DatabaseConnection>,
+    Extension(token): Extension<KeycloakToken<String>>,
+    Path((account_id, job_id)): Path<(Uuid, Uuid)>,
+) -> Result<Sse<impl Stream<Item = Result<Event, axum::Error>>>, ApiError> {
+    let user = get_or_create_user(&db, &token).await?;
+
+    let find_job = || {
+        find_sync_job(&db, user.id, account_id, job_id)
+    };
+    let job = find_job().await?.ok_or(ApiError::NotFound)?;
+
+    // A job still tracked by this process streams from its current state; events published
+    // between the read and the subscription are reflected in the chains of the snapshot
+    let live = if sync_queue::is_finished(&job.status) { None } else { sync_queue::subscribe(job_id) };
+    let (initial, updates) = match live {
+        Some((chains, updates)) => (vec![SyncEvent::snapshot(&job, chains)], Some(updates)),
+        None => {
+            // The job may have finished since it was read
+            let job = find_job().await?.ok_or(ApiError::NotFound)?;
+            let mut initial = vec![SyncEvent::snapshot(&job, Vec::new())];
+            if sync_queue::is_finished(&job.status) {
+                initial.push(SyncEvent::finished(&job));
+            }
+            (initial, None)
+        }
+    };
+
+    let updates = stream::unfold(updates, |updates| async move {
+        let mut updates = updates?;
+        loop {
+            match updates.recv().await {
+                Ok(event) => {
+                    let done = matches!(event, SyncEvent::Finished { .. });
+                    return Some((event, if done { None } else { Some(updates) }));
+                }
+                // A slow client skips the events it missed; the next ones still make sense
+                Err(RecvError::Lagged(_)) => continue,
+                Err(RecvError::Closed) => return None,
+            }
         }
     });
+    let events = stream::iter(initial)
+        .chain(updates)
+        .map(|event| Event::default().event(event.name()).json_data(&event));
+
+    Ok(Sse::new(events).keep_alive(KeepAlive::default()))
+}
+
+/// List the sync history of an account
+///
+/// Returns every sync of the account, newest first, whether queued for the account alone or
+/// by sync-all: when it ran, whether it succeeded, the number of holdings or the error, and
+/// the balances read per chain. Pass `limit` to page through the results and `cursor` (from
+/// `next_cursor`) to fetch the following page.
+#[utoipa::path(
+    get,
+    path = "/api/v1/accounts/{account_id}/syncs",
+    params(
+        ("account_id" = Uuid, Path, description = "Account ID"),
+        ("limit" = Option<u64>, Query, description = "Page size (1-1000); omit to return all syncs"),
+        ("cursor" = Option<String>, Query, description = "Opaque cursor from a previous page's next_cursor")
+    ),
+    responses(
+        (status = 200, description = "Sync history", body = ListSyncRunsResponse),
+        (status = 401, description = "Unauthorized"),
+        (status = 403, description = "Forbidden"),
+        (status = 404, description = "Account not found"),
+        (status = 500, description = "Internal server error")
+    ),
+    tag = "accounts"
+)]
+async fn list_sync_runs_handler(
+    State(db): State<DatabaseConnection>,
+    Extension(token): Extension<KeycloakToken<String>>,
+    Path(account_id): Path<Uuid>,
+    Query(query): Query<ListSyncRunsQuery>,
+) -> Result<Json<ListSyncRunsResponse>, ApiError> {
+    let user = get_or_create_user(&db, &token).await?;
+
+    let account = accounts::Entity::find_by_id(account_id)
+        .one(&db)
+        .await?
+        .ok_or(ApiError::NotFound)?;
+
+    if account.user_id != user.id {
+        return Err(ApiError::Forbidden);
+    }
+
+    let page = PageRequest::from_query(query.limit, query.cursor.as_deref())?;
+
+    let mut run_query = sync_runs::Entity::find()
+        .filter(sync_runs::Column::AccountId.eq(account_id))
+        .order_by_desc(sync_runs::Column::StartedAt)
+        .order_by_desc(sync_runs::Column::Id)
+        .limit(page.fetch_limit());
+
+    if let Some(after) = &page.after {
+        run_query = run_query.filter(keyset_before(
+            sync_runs::Column::StartedAt,
+            sync_runs::Column::Id,
+            after.timestamp,
+            after.id,
+        ));
+    }
+
+    let (rows, next_cursor) = finish_page(run_query.all(&db).await?, &page, |run| {
+        Cursor::new(run.started_at.with_timezone(&chrono::Utc), run.id)
+    });
+    let syncs: Vec<SyncRunResponse> = rows.into_iter().map(SyncRunResponse::from).collect();
+    let total_count = syncs.len();
+
+    Ok(Json(ListSyncRunsResponse {
+        account_id,
+        syncs,
+        total_count,
+        next_cursor,
+    }))
+}
+
+/// Sync all accounts for the authenticated user
+///
+/// Queues a background sync of every active account belonging to the authenticated user and
+/// returns immediately with 202 Accepted and the job of each account. Accounts already
+/// queued or syncing get their current job back.
+#[utoipa::path(
+    post,
+    path = "/api/v1/accounts/sync-all",
+    responses(
+        (status = 202, description = "Syncs queued", body = SyncAllInitiatedResponse),
+        (status = 401, description = "Unauthorized"),
+        (status = 500, description = "Internal server error")
+    ),
+
+    tag = "accounts"
+)]
+async fn sync_all_accounts_handler(
+    State(db): State<DatabaseConnection>,
+    Extension(token): Extension<KeycloakToken<String>>,
+) -> Result<(StatusCode, Json<SyncAllInitiatedResponse>), ApiError> {
+    // Get or create user
+    let user = get_or_create_user(&db, &token).await?;
+
+    let active_accounts = accounts::Entity::find()
+        .filter(accounts::Column::UserId.eq(user.id))
+        .filter(accounts::Column::IsActive.eq(true))
+        .all(&db)
+        .await?;
+
+    let mut jobs = Vec::with_capacity(active_accounts.len());
+    for account in &active_accounts {
+        let job = sync_queue::enqueue_account_sync(&db, account).await?;
+        jobs.push(SyncInitiatedResponse::from(job));
+    }
+    tracing::info!("Queued syncs of {} accounts for user {}", jobs.len(), user.id);
 
     Ok((
         StatusCode::ACCEPTED,
         Json(SyncAllInitiatedResponse {
-            message: "Sync started in background".to_string(),
-            account_count,
+            message: "Syncs queued".to_string(),
+            account_count: jobs.len(),
+            jobs,
         }),
     ))
 }
 
+/// List holding transactions for an account
+///
+/// Returns the ledger of quantity changes recorded by syncs, newest first.
+/// By default superseded transactions and the corrections that replaced them are
+/// hidden, leaving only the effective ledger. Pass `limit` to page through the results
+/// and `cursor` (from `next_cursor`) to fetch the following page.
+#[utoipa::path(
+    get,
+    path = "/api/v1/accounts/{account_id}/holding-transactions",
+    params(
+        ("account_id" = Uuid, Path, description = "Account ID"),
+        ("asset" = Option<String>, Query, description = "Filter by asset symbol"),
+        ("include_corrected" = Option<bool>, Query, description = "Include superseded transactions and corrections (default: false)"),
+        ("limit" = Option<u64>, Query, description = "Page size (1-1000); omit to return all transactions"),
+        ("cursor" = Option<String>, Query, description = "Opaque cursor from a previous page's next_cursor")
+    ),
+    responses(
+        (status = 200, description = "Holding transactions", body = ListHoldingTransactionsResponse),
+        (status = 401, description = "Unauthorized"),
+        (status = 403, description = "Forbidden"),
+        (status = 404, description = "Account not found"),
+        (status = 500, description = "Internal server error")
+    ),
+    tag = "accounts"
+)]
+async fn list_holding_transactions_handler(
+    State(db): State<DatabaseConnection>,
+    Extension(token): Extension<KeycloakToken<String>>,
+    Path(account_id): Path<Uuid>,
+    Query(query): Query<ListHoldingTransactionsQuery>,
+) -> Result<Json<ListHoldingTransactionsResponse>, ApiError> {
+    let user = get_or_create_user(&db, &token).await?;
+
+    let account = accounts::Entity::find_by_id(account_id)
+        .one(&db)
+        .await?
+        .ok_or(ApiError::NotFound)?;
+
+    if account.user_id != user.id {
+        return Err(ApiError::Forbidden);
+    }
+
+    let page = PageRequest::from_query(query.limit, query.cursor.as_deref())?;
+
+    let mut tx_query = holding_transactions::Entity::find()
+        .filter(holding_transactions::Column::AccountId.eq(account_id))
+        .order_by_desc(holding_transactions::Column::RecordedAt)
+        .order_by_desc(holding_transactions::Column::Id)
+        .limit(page.fetch_limit());
+
+    if let Some(after) = &page.after {
+        tx_query = tx_query.filter(keyset_before(
+            holding_transactions::Column::RecordedAt,
+            holding_transactions::Column::Id,
+            after.timestamp,
+            after.id,
+        ));
+    }
+
+    if let Some(asset) = query.asset {
+        tx_query = tx_query.filter(holding_transactions::Column::Asset.eq(asset));
+    }
+
+    if !query.include_corrected {
+        tx_query = tx_query
+            .filter(holding_transactions::Column::CorrectedBy.is_null())
+            .filter(holding_transactions::Column::TransactionType.ne(holding_ledger::TX_CORRECTION));
+    }
+
+    let (rows, next_cursor) = finish_page(tx_query.all(&db).await?, &page, |tx| {
+        Cursor::new(tx.recorded_at.with_timezone(&chrono::Utc), tx.id)
+    });
+    let transactions: Vec<HoldingTransactionResponse> = rows
+        .into_iter()
+        .map(HoldingTransactionResponse::from)
+        .collect();
+    let total_count = transactions.len();
+
+    Ok(Json(ListHoldingTransactionsResponse {
+        account_id,
+        transactions,
+        total_count,
+        next_cursor,
+    }))
+}
+
+/// List open derivatives positions for an account
+///
+/// Returns the futures/perpetual/option positions observed by the account's last sync.
+/// Currently OKX, Deribit and Hyperliquid accounts report positions.
+#[utoipa::path(
+    get,
+    path = "/api/v1/accounts/{account_id}/positions",
+    params(
+        ("account_id" = Uuid, Path, description = "Account ID")
+    ),
+    responses(
+        (status = 200, description = "Open positions", body = ListPositionsResponse),
+        (status = 401, description = "Unauthorized"),
+        (status = 403, description = "Forbidden"),
+        (status = 404, description = "Account not found"),
+        (status = 500, description = "Internal server error")
+    ),
+    tag = "accounts"
+)]
+async fn list_positions_handler(
+    State(db): State<DatabaseConnection>,
+    Extension(token): Extension<KeycloakToken<String>>,
+    Path(account_id): Path<Uuid>,
+) -> Result<Json<ListPositionsResponse>, ApiError> {
+    let user = get_or_create_user(&db, &token).await?;
+
+    let account = accounts::Entity::find_by_id(account_id)
+        .one(&db)
+        .await?
+        .ok_or(ApiError::NotFound)?;
+
+    if account.user_id != user.id {
+        return Err(ApiError::Forbidden);
+    }
+
+    let positions: Vec<PositionResponse> = positions::Entity::find()
+        .filter(positions::Column::AccountId.eq(account_id))
+        .order_by_asc(positions::Column::Instrument)
+        .order_by_asc(positions::Column::Side)
+        .all(&db)
+        .await?
+        .into_iter()
+        .map(PositionResponse::from)
+        .collect();
+    let total_count = positions.len();
+
+    Ok(Json(ListPositionsResponse {
+        account_id,
+        positions,
+        total_count,
+    }))
+}
+
+/// List NFTs held by a wallet account
+///
+/// Returns the ERC-721/ERC-1155 tokens observed by the account's last sync, with collection
+/// floor prices. NFTs are discovered on EVM chains that have an NFT API configured.
+#[utoipa::path(
+    get,
+    path = "/api/v1/accounts/{account_id}/nfts",
+    params(
+        ("account_id" = Uuid, Path, description = "Account ID")
+    ),
+    responses(
+        (status = 200, description = "NFT holdings", body = ListNftsResponse),
+        (status = 401, description = "Unauthorized"),
+        (status = 403, description = "Forbidden"),
+        (status = 404, description = "Account not found"),
+        (status = 500, description = "Internal server error")
+    ),
+    tag = "accounts"
+)]
+async fn list_nfts_handler(
+    State(db): State<DatabaseConnection>,
+    Extension(token): Extension<KeycloakToken<String>>,
+    Path(account_id): Path<Uuid>,
+) -> Result<Json<ListNftsResponse>, ApiError> {
+    let user = get_or_create_user(&db, &token).await?;
+
+    let account = accounts::Entity::find_by_id(account_id)
+        .one(&db)
+        .await?
+        .ok_or(ApiError::NotFound)?;
+
+    if account.user_id != user.id {
+        return Err(ApiError::Forbidden);
+    }
+
+    let nfts: Vec<NftHoldingResponse> = nft_holdings::Entity::find()
+        .filter(nft_holdings::Column::AccountId.eq(account_id))
+        .order_by_asc(nft_holdings::Column::Chain)
+        .order_by_asc(nft_holdings::Column::ContractAddress)
+        .order_by_asc(nft_holdings::Column::TokenId)
+        .all(&db)
+        .await?
+        .into_iter()
+        .map(NftHoldingResponse::from)
+        .collect();
+    let total_count = nfts.len();
+
+    Ok(Json(ListNftsResponse {
+        account_id,
+        nfts,
+        total_count,
+    }))
+}
+
+/// List trade fills for an account
+///
+/// Returns the trades recorded by syncs, newest first. Currently OKX accounts report
+/// trades (fills of the last three months on the first sync, new fills afterwards).
+/// Pass `limit` to page through the results and `cursor` (from `next_cursor`) to fetch
+/// the following page.
+#[utoipa::path(
+    get,
+    path = "/api/v1/accounts/{account_id}/trades",
+    params(
+        ("account_id" = Uuid, Path, description = "Account ID"),
+        ("instrument" = Option<String>, Query, description = "Filter by instrument (e.g., BTC-USDT)"),
+        ("limit" = Option<u64>, Query, description = "Page size (1-1000); omit to return all trades"),
+        ("cursor" = Option<String>, Query, description = "Opaque cursor from a previous page's next_cursor")
+    ),
+    responses(
+        (status = 200, description = "Trades", body = ListTradesResponse),
+        (status = 400, description = "Invalid cursor or limit"),
+        (status = 401, description = "Unauthorized"),
+        (status = 403, description = "Forbidden"),
+        (status = 404, description = "Account not found"),
+        (status = 500, description = "Internal server error")
+    ),
+    tag = "accounts"
+)]
+async fn list_trades_handler(
+    State(db): State<DatabaseConnection>,
+    Extension(token): Extension<KeycloakToken<String>>,
+    Path(account_id): Path<Uuid>,
+    Query(query): Query<ListTradesQuery>,
+) -> Result<Json<ListTradesResponse>, ApiError> {
+    let user = get_or_create_user(&db, &token).await?;
+
+    let account = accounts::Entity::find_by_id(account_id)
+        .one(&db)
+        .await?
+        .ok_or(ApiError::NotFound)?;
+
+    if account.user_id != user.id {
+        return Err(ApiError::Forbidden);
+    }
+
+    let page = PageRequest::from_query(query.limit, query.cursor.as_deref())?;
+
+    let mut trade_query = trades::Entity::find()
+        .filter(trades::Column::AccountId.eq(account_id))
+        .order_by_desc(trades::Column::ExecutedAt)
+        .order_by_desc(trades::Column::Id)
+        .limit(page.fetch_limit());
+
+    if let Some(after) = &page.after {
+        trade_query = trade_query.filter(keyset_before(
+            trades::Column::ExecutedAt,
+            trades::Column::Id,
+            after.timestamp,
+            after.id,
+        ));
+    }
+
+    if let Some(instrument) = query.instrument {
+        trade_query = trade_query.filter(trades::Column::Instrument.eq(instrument));
+    }
+
+    let (rows, next_cursor) = finish_page(trade_query.all(&db).await?, &page, |t| {
+        Cursor::new(t.executed_at.with_timezone(&chrono::Utc), t.id)
+    });
+    let trades: Vec<TradeResponse> = rows.into_iter().map(TradeResponse::from).collect();
+    let total_count = trades.len();
+
+    Ok(Json(ListTradesResponse {
+        account_id,
+        trades,
+        total_count,
+        next_cursor,
+    }))
+}
+
+/// List deposits and withdrawals synced from the exchange, newest first
+///
+/// Pass `limit` to page through the results and `cursor` (from `next_cursor`) to fetch
+/// the following page. The response also carries the account's net contributions per asset.
+#[utoipa::path(
+    get,
+    path = "/api/v1/accounts/{account_id}/transfers",
+    params(
+        ("account_id" = Uuid, Path, description = "Account ID"),
+        ("asset" = Option<String>, Query, description = "Filter by asset (e.g., USDT)"),
+        ("direction" = Option<String>, Query, description = "Filter by direction (deposit or withdrawal)"),
+        ("limit" = Option<u64>, Query, description = "Page size (1-1000); omit to return all transfers"),
+        ("cursor" = Option<String>, Query, description = "Opaque cursor from a previous page's next_cursor")
+    ),
+    responses(
+        (status = 200, description = "Transfers", body = ListTransfersResponse),
+        (status = 400, description = "Invalid cursor or limit"),
+        (status = 401, description = "Unauthorized"),
+        (status = 403, description = "Forbidden"),
+        (status = 404, description = "Account not found"),
+        (status = 500, description = "Internal server error")
+    ),
+    tag = "accounts"
+)]
+async fn list_transfers_handler(
+    State(db): State<DatabaseConnection>,
+    Extension(token): Extension<KeycloakToken<String>>,
+    Path(account_id): Path<Uuid>,
+    Query(query): Query<ListTransfersQuery>,
+) -> Result<Json<ListTransfersResponse>, ApiError> {
+    let user = get_or_create_user(&db, &token).await?;
+
+    let account = accounts::Entity::find_by_id(account_id)
+        .one(&db)
+        .await?
+        .ok_or(ApiError::NotFound)?;
+
+    if account.user_id != user.id {
+        return Err(ApiError::Forbidden);
+    }
+
+    let page = PageRequest::from_query(query.limit, query.cursor.as_deref())?;
+
+    let mut transfer_query = transfers::Entity::find()
+        .filter(transfers::Column::AccountId.eq(account_id))
+        .order_by_desc(transfers::Column::OccurredAt)
+        .order_by_desc(transfers::Column::Id)
+        .limit(page.fetch_limit());
+
+    if let Some(after) = &page.after {
+        transfer_query = transfer_query.filter(keyset_before(
+            transfers::Column::OccurredAt,
+            transfers::Column::Id,
+            after.timestamp,
+            after.id,
+        ));
+    }
+
+    if let Some(asset) = query.asset {
+        transfer_query = transfer_query.filter(transfers::Column::Asset.eq(asset));
+    }
+
+    if let Some(direction) = query.direction {
+        transfer_query = transfer_query.filter(transfers::Column::Direction.eq(direction));
+    }
+
+    let (rows, next_cursor) = finish_page(transfer_query.all(&db).await?, &page, |t| {
+        Cursor::new(t.occurred_at.with_timezone(&chrono::Utc), t.id)
+    });
+    let transfers: Vec<TransferResponse> = rows.into_iter().map(TransferResponse::from).collect();
+    let total_count = transfers.len();
+
+    let all_transfers = transfers::Entity::find()
+        .filter(transfers::Column::AccountId.eq(account_id))
+        .all(&db)
+        .await?;
+    let net_contributions = transfer_sync::net_contributions(&all_transfers)
+        .into_iter()
+        .map(NetContributionResponse::from)
+        .collect();
+
+    Ok(Json(ListTransfersResponse {
+        account_id,
+        transfers,
+        total_count,
+        next_cursor,
+        net_contributions,
+    }))
+}
+
+/// Import a CSV statement into a manual account
+///
+/// For exchanges without a connector: create an exchange account with `exchange_name`
+/// "manual" and import its balances or transactions. A balance statement replaces the
+/// holdings and records the changes in the holding ledger like a sync; a transaction statement
+/// writes one `import` ledger row per transaction at its own time and updates the holdings.
+/// Re-importing the same transactions is a no-op.
+#[utoipa::path(
+    post,
+    path = "/api/v1/accounts/{account_id}/import",
+    params(
+        ("account_id" = Uuid, Path, description = "Account ID")
+    ),
+    request_body = ImportStatementRequest,
+    responses(
+        (status = 200, description = "Statement imported", body = ImportStatementResponse),
+        (status = 400, description = "Invalid CSV, mapping or statement, or not a manual account"),
+        (status = 401, description = "Unauthorized"),
+        (status = 403, description = "Forbidden"),
+        (status = 404, description = "Account not found"),
+        (status = 500, description = "Internal server error")
+    ),
+    tag = "accounts"
+)]
+async fn import_statement_handler(
+    State(db): State<DatabaseConnection>,
+    Extension(token): Extension<KeycloakToken<String>>,
+    Path(account_id): Path<Uuid>,
+    Json(req): Json<ImportStatementRequest>,
+) -> Result<Json<ImportStatementResponse>, ApiError> {
+    let user = get_or_create_user(&db, &token).await?;
+
+    let account = accounts::Entity::find_by_id(account_id)
+        .one(&db)
+        .await?
+        .ok_or(ApiError::NotFound)?;
+
+    if account.user_id != user.id {
+        return Err(ApiError::Forbidden);
+    }
+
+    // A synced account's next sync would overwrite imported holdings
+    let is_manual = ConnectorRegistry::builtin()
+        .find(&account.account_type, account.exchange_name.as_deref())
+        .is_some_and(|factory| factory.name() == "manual");
+    if !is_manual {
+        return Err(ApiError::BadRequest(
+            "Statements can only be imported into manual exchange accounts".to_string(),
+        ));
+    }
+
+    let delimiter = match req.delimiter.unwrap_or(',') {
+        c if c.is_ascii() => c as u8,
+        _ => return Err(ApiError::BadRequest("delimiter must be an ASCII character".to_string())),
+    };
+    let invalid = |e: CsvImportError| ApiError::BadRequest(e.to_string());
+
+    let (rows, result) = match req.kind.as_str() {
+        "balances" => {
+            let balances = parse_balances(&req.csv, delimiter, &req.mapping).map_err(invalid)?;
+            (balances.len(), statement_import::import_balances(&db, account, balances).await)
+        }
+        "transactions" => {
+            let transactions = parse_transactions(&req.csv, delimiter, &req.mapping).map_err(invalid)?;
+            (transactions.len(), statement_import::import_transactions(&db, account, transactions).await)
+        }
+        other => {
+            return Err(ApiError::BadRequest(format!(
+                "kind must be 'balances' or 'transactions', got '{}'",
+                other
+            )))
+        }
+    };
+
+    let summary = result.map_err(|e| match e.downcast_ref::<ImportRejected>() {
+        Some(rejected) => ApiError::BadRequest(rejected.to_string()),
+        None => ApiError::InternalServerError(format!("Failed to import statement: {}", e)),
+    })?;
+
+    Ok(Json(ImportStatementResponse {
+        account_id,
+        kind: req.kind,
+        rows,
+        holdings_count: summary.holdings_count,
+        transactions_imported: summary.transactions_imported,
+        transactions_skipped: summary.transactions_skipped,
+    }))
+}
+
 /// Create router for account endpoints
 /// 
 /// Note: Axum uses curly braces for path parameters {param}, not colon notation :param
@@ -556,5 +1897,60 @@ pub fn create_router() -> Router<DatabaseConnection> {
         .route("/api/v1/accounts", get(list_accounts_handler).post(create_account_handler))
         .route("/api/v1/accounts/{account_id}", get(get_account_handler).put(update_account_handler).delete(delete_account_handler))
         .route("/api/v1/accounts/{account_id}/sync", post(sync_account_handler))
+        .route("/api/v1/accounts/{account_id}/sync-status/{job_id}", get(get_sync_status_handler))
+        .route("/api/v1/accounts/{account_id}/sync-status/{job_id}/events", get(stream_sync_status_handler))
+        .route("/api/v1/accounts/{account_id}/syncs", get(list_sync_runs_handler))
+        .route("/api/v1/accounts/{account_id}/holding-transactions", get(list_holding_transactions_handler))
+        .route("/api/v1/accounts/{account_id}/positions", get(list_positions_handler))
+        .route("/api/v1/accounts/{account_id}/nfts", get(list_nfts_handler))
+        .route("/api/v1/accounts/{account_id}/trades", get(list_trades_handler))
+        .route("/api/v1/accounts/{account_id}/transfers", get(list_transfers_handler))
+        .route("/api/v1/accounts/{account_id}/import", post(import_statement_handler))
         .route("/api/v1/accounts/sync-all", post(sync_all_accounts_handler))
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_connector_name() {
+        let name = |account_type, exchange: Option<&str>, family: Option<&str>| {
+            connector_name(account_type, exchange.map(String::from), family.map(String::from))
+        };
+
+        assert_eq!(name(ACCOUNT_TYPE_WALLET, None, Some("litecoin")).unwrap().as_deref(), Some("litecoin"));
+        assert_eq!(name(ACCOUNT_TYPE_WALLET, Some("bitcoin"), None).unwrap().as_deref(), Some("bitcoin"));
+        assert_eq!(name(ACCOUNT_TYPE_WALLET, Some("Dogecoin"), Some("dogecoin")).unwrap().as_deref(), Some("dogecoin"));
+        assert_eq!(name(ACCOUNT_TYPE_WALLET, None, None).unwrap(), None);
+        assert_eq!(name(ACCOUNT_TYPE_EXCHANGE, Some("OKX"), None).unwrap().as_deref(), Some("OKX"));
+        assert!(name(ACCOUNT_TYPE_WALLET, Some("bitcoin"), Some("litecoin")).is_err());
+        assert!(name(ACCOUNT_TYPE_EXCHANGE, Some("okx"), Some("evm")).is_err());
+    }
+
+    #[test]
+    fn test_validate_settings() {
+        let settings = |protocols: &[&str]| AccountSettings {
+            defi_protocols: protocols.iter().map(|p| p.to_string()).collect(),
+            ..Default::default()
+        };
+        assert!(validate_settings(&settings(&[])).is_ok());
+        assert!(validate_settings(&settings(&["AAVE_V3"])).is_ok());
+        assert!(validate_settings(&settings(&["aave_v3", "maker"])).is_err());
+
+        let aggregator = |name: &str| AccountSettings { position_aggregator: Some(name.to_string()), ..Default::default() };
+        assert!(validate_settings(&aggregator("DeBank")).is_ok());
+        assert!(validate_settings(&aggregator("zerion")).is_err());
+    }
+
+    #[test]
+    fn test_apply_wallet_subtype() {
+        let safe = Some(WALLET_KIND_SAFE.to_string());
+        assert_eq!(apply_wallet_subtype(ACCOUNT_TYPE_WALLET, None, Some(" Safe ")).unwrap(), safe);
+        assert_eq!(apply_wallet_subtype(ACCOUNT_TYPE_WALLET, Some("evm".to_string()), Some("safe")).unwrap(), safe);
+        assert_eq!(apply_wallet_subtype(ACCOUNT_TYPE_WALLET, Some("solana".to_string()), None).unwrap().as_deref(), Some("solana"));
+        assert!(apply_wallet_subtype(ACCOUNT_TYPE_WALLET, Some("solana".to_string()), Some("safe")).is_err());
+        assert!(apply_wallet_subtype(ACCOUNT_TYPE_WALLET, None, Some("argent")).is_err());
+        assert!(apply_wallet_subtype(ACCOUNT_TYPE_EXCHANGE, Some("okx".to_string()), Some("safe")).is_err());
+    }
+}
@@ -0,0 +1,188 @@
+use axum::{
+    extract::{Extension, Path, Query, State},
+    response::Json,
+    routing::{get, post},
+    Router,
+};
+use axum_keycloak_auth::decode::KeycloakToken;
+use chrono::Utc;
+use sea_orm::{
+    ActiveModelTrait, ActiveValue, ColumnTrait, DatabaseConnection, EntityTrait, QueryFilter,
+    QueryOrder,
+};
+use serde::{Deserialize, Serialize};
+use utoipa::ToSchema;
+use uuid::Uuid;
+
+use super::error::ApiError;
+use crate::entities::holding_anomalies;
+use crate::helpers::auth::get_or_create_user;
+
+// === Request/Response DTOs ===
+
+#[derive(Debug, Serialize, Deserialize, ToSchema)]
+pub struct AnomalyResponse {
+    pub id: Uuid,
+    pub account_id: Uuid,
+    /// "balance_drop" or "wallet_emptied"
+    pub anomaly_type: String,
+    /// Affected asset (absent for account-wide anomalies)
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub asset: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub previous_quantity: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub current_quantity: Option<String>,
+    pub details: Option<serde_json::Value>,
+    pub detected_at: String,
+    /// When the user acknowledged the anomaly; null while it still holds automatic snapshots
+    pub acknowledged_at: Option<String>,
+}
+
+impl From<holding_anomalies::Model> for AnomalyResponse {
+    fn from(model: holding_anomalies::Model) -> Self {
+        Self {
+            id: model.id,
+            account_id: model.account_id,
+            anomaly_type: model.anomaly_type,
+            asset: model.asset,
+            previous_quantity: model.previous_quantity.map(|d| d.to_string()),
+            current_quantity: model.current_quantity.map(|d| d.to_string()),
+            details: model.details,
+            detected_at: model.detected_at.to_rfc3339(),
+            acknowledged_at: model.acknowledged_at.map(|dt| dt.to_rfc3339()),
+        }
+    }
+}
+
+#[derive(Debug, Deserialize, ToSchema)]
+pub struct ListAnomaliesQuery {
+    /// Include anomalies that were already acknowledged (default: false)
+    #[serde(default)]
+    pub include_acknowledged: bool,
+    /// Filter by account
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub account_id: Option<Uuid>,
+}
+
+#[derive(Debug, Serialize, Deserialize, ToSchema)]
+pub struct ListAnomaliesResponse {
+    pub anomalies: Vec<AnomalyResponse>,
+    pub total_count: usize,
+}
+
+// === API Handlers ===
+
+/// List holdings anomalies for the authenticated user
+///
+/// By default only unacknowledged anomalies are returned. Any unacknowledged anomaly
+/// holds automatic snapshot creation for the portfolios containing that account.
+#[utoipa::path(
+    get,
+    path = "/api/v1/anomalies",
+    params(
+        ("include_acknowledged" = Option<bool>, Query, description = "Include acknowledged anomalies (default: false)"),
+        ("account_id" = Option<Uuid>, Query, description = "Filter by account ID")
+    ),
+    responses(
+        (status = 200, description = "Anomalies retrieved successfully", body = ListAnomaliesResponse),
+        (status = 401, description = "Unauthorized"),
+        (status = 500, description = "Internal server error")
+    ),
+    tag = "anomalies"
+)]
+pub async fn list_anomalies_handler(
+    State(db): State<DatabaseConnection>,
+    Extension(token): Extension<KeycloakToken<String>>,
+    Query(query): Query<ListAnomaliesQuery>,
+) -> Result<Json<ListAnomaliesResponse>, ApiError> {
+    let user = get_or_create_user(&db, &token)
+        .await
+        .map_err(|e| ApiError::InternalServerError(format!("Database error: {}", e)))?;
+
+    let mut anomaly_query = holding_anomalies::Entity::find()
+        .filter(holding_anomalies::Column::UserId.eq(user.id))
+        .order_by_desc(holding_anomalies::Column::DetectedAt);
+
+    if !query.include_acknowledged {
+        anomaly_query = anomaly_query.filter(holding_anomalies::Column::AcknowledgedAt.is_null());
+    }
+
+    if let Some(account_id) = query.account_id {
+        anomaly_query = anomaly_query.filter(holding_anomalies::Column::AccountId.eq(account_id));
+    }
+
+    let anomalies: Vec<AnomalyResponse> = anomaly_query
+        .all(&db)
+        .await?
+        .into_iter()
+        .map(AnomalyResponse::from)
+        .collect();
+
+    let total_count = anomalies.len();
+
+    Ok(Json(ListAnomaliesResponse {
+        anomalies,
+        total_count,
+    }))
+}
+
+/// Acknowledge a holdings anomaly
+///
+/// Marks the anomaly as reviewed. Once every anomaly on a portfolio's accounts is
+/// acknowledged, automatic snapshots resume. Acknowledging twice is a no-op.
+#[utoipa::path(
+    post,
+    path = "/api/v1/anomalies/{anomaly_id}/acknowledge",
+    params(
+        ("anomaly_id" = Uuid, Path, description = "Anomaly ID")
+    ),
+    responses(
+        (status = 200, description = "Anomaly acknowledged", body = AnomalyResponse),
+        (status = 401, description = "Unauthorized"),
+        (status = 403, description = "Forbidden - anomaly does not belong to user"),
+        (status = 404, description = "Anomaly not found"),
+        (status = 500, description = "Internal server error")
+    ),
+    tag = "anomalies"
+)]
+pub async fn acknowledge_anomaly_handler(
+    State(db): State<DatabaseConnection>,
+    Extension(token): Extension<KeycloakToken<String>>,
+    Path(anomaly_id): Path<Uuid>,
+) -> Result<Json<AnomalyResponse>, ApiError> {
+    let user = get_or_create_user(&db, &token)
+        .await
+        .map_err(|e| ApiError::InternalServerError(format!("Database error: {}", e)))?;
+
+    let anomaly = holding_anomalies::Entity::find_by_id(anomaly_id)
+        .one(&db)
+        .await?
+        .ok_or(ApiError::NotFound)?;
+
+    if anomaly.user_id != user.id {
+        return Err(ApiError::Forbidden);
+    }
+
+    if anomaly.acknowledged_at.is_some() {
+        return Ok(Json(anomaly.into()));
+    }
+
+    let mut active: holding_anomalies::ActiveModel = anomaly.into();
+    active.acknowledged_at = ActiveValue::Set(Some(Utc::now().into()));
+    let updated = active.update(&db).await?;
+
+    tracing::info!("User {} acknowledged holdings anomaly {}", user.id, anomaly_id);
+
+    Ok(Json(updated.into()))
+}
+
+/// Create router for anomaly endpoints
+pub fn create_router() -> Router<DatabaseConnection> {
+    Router::new()
+        .route("/api/v1/anomalies", get(list_anomalies_handler))
+        .route(
+            "/api/v1/anomalies/{anomaly_id}/acknowledge",
+            post(acknowledge_anomaly_handler),
+        )
+}
@@ -0,0 +1,158 @@
+use axum::{
+    extract::{Path, Query, State},
+    response::Json,
+    routing::{get, put},
+    Extension, Router,
+};
+use chrono::Utc;
+use sea_orm::{ActiveModelTrait, ColumnTrait, DatabaseConnection, EntityTrait, QueryFilter, QueryOrder, Set};
+use serde::{Deserialize, Serialize};
+use utoipa::ToSchema;
+use uuid::Uuid;
+
+use crate::domain::UNCATEGORIZED;
+use crate::entities::assets;
+use super::error::ApiError;
+
+/// Longest accepted category name
+const MAX_CATEGORY_LEN: usize = 50;
+
+// === Request / Response DTOs ===
+
+#[derive(Debug, Deserialize, ToSchema)]
+pub struct ListAssetCategoriesQuery {
+    /// Only assets in this category
+    pub category: Option<String>,
+}
+
+#[derive(Debug, Serialize, Deserialize, ToSchema)]
+pub struct AssetCategoryResponse {
+    pub asset_id: Uuid,
+    pub symbol: String,
+    pub name: String,
+    /// Category, e.g. "l1", "defi", "stablecoin", "meme", "rwa"
+    pub category: Option<String>,
+}
+
+impl From<assets::Model> for AssetCategoryResponse {
+    fn from(m: assets::Model) -> Self {
+        Self {
+            asset_id: m.id,
+            symbol: m.symbol,
+            name: m.name,
+            category: m.category,
+        }
+    }
+}
+
+#[derive(Debug, Serialize, Deserialize, ToSchema)]
+pub struct SetAssetCategoryRequest {
+    /// Category to set (lowercase letters, digits, "-" and "_"); null clears it
+    pub category: Option<String>,
+}
+
+// === Helpers ===
+
+/// Normalize a category name to lowercase and check its characters
+fn parse_category(value: &str) -> Result<String, ApiError> {
+    let category = value.trim().to_lowercase();
+    if category.is_empty() {
+        return Err(ApiError::BadRequest("category must not be empty".to_string()));
+    }
+    if category.len() > MAX_CATEGORY_LEN {
+        return Err(ApiError::BadRequest(format!("category must be at most {} characters", MAX_CATEGORY_LEN)));
+    }
+    if !category.chars().all(|c| c.is_ascii_alphanumeric() || c == '-' || c == '_') {
+        return Err(ApiError::BadRequest(
+            "category may only contain letters, digits, '-' and '_'".to_string(),
+        ));
+    }
+    if category == UNCATEGORIZED {
+        return Err(ApiError::BadRequest(format!("'{}' is reserved; send null to clear", UNCATEGORIZED)));
+    }
+    Ok(category)
+}
+
+// === Handlers ===
+
+/// List categorized assets
+///
+/// Returns the assets that have a category, by category and symbol.
+#[utoipa::path(
+    get,
+    path = "/api/v1/asset-categories",
+    params(
+        ("category" = Option<String>, Query, description = "Only assets in this category")
+    ),
+    responses(
+        (status = 200, description = "Categorized assets", body = Vec<AssetCategoryResponse>),
+        (status = 401, description = "Unauthorized"),
+        (status = 500, description = "Internal server error")
+    ),
+    tag = "asset-categories"
+)]
+pub async fn list_asset_categories_handler(
+    State(db): State<DatabaseConnection>,
+    Extension(_token): Extension<axum_keycloak_auth::decode::KeycloakToken<String>>,
+    Query(query): Query<ListAssetCategoriesQuery>,
+) -> Result<Json<Vec<AssetCategoryResponse>>, ApiError> {
+    let mut select = assets::Entity::find().filter(assets::Column::Category.is_not_null());
+    if let Some(category) = query.category.as_deref() {
+        select = select.filter(assets::Column::Category.eq(category.trim().to_lowercase()));
+    }
+    let rows = select
+        .order_by_asc(assets::Column::Category)
+        .order_by_asc(assets::Column::Symbol)
+        .all(&db)
+        .await?;
+
+    Ok(Json(rows.into_iter().map(|r| r.into()).collect()))
+}
+
+/// Set an asset's category
+///
+/// Tags an asset with a category, or clears it with `null`. Allocations pick up the change
+/// from the next construction on.
+#[utoipa::path(
+    put,
+    path = "/api/v1/asset-categories/{asset_id}",
+    params(
+        ("asset_id" = Uuid, Path, description = "Asset ID")
+    ),
+    request_body = SetAssetCategoryRequest,
+    responses(
+        (status = 200, description = "Category set", body = AssetCategoryResponse),
+        (status = 400, description = "Invalid category"),
+        (status = 401, description = "Unauthorized"),
+        (status = 404, description = "Asset not found"),
+        (status = 500, description = "Internal server error")
+    ),
+    tag = "asset-categories"
+)]
+pub async fn set_asset_category_handler(
+    State(db): State<DatabaseConnection>,
+    Extension(_token): Extension<axum_keycloak_auth::decode::KeycloakToken<String>>,
+    Path(asset_id): Path<Uuid>,
+    Json(req): Json<SetAssetCategoryRequest>,
+) -> Result<Json<AssetCategoryResponse>, ApiError> {
+    let category = req.category.as_deref().map(parse_category).transpose()?;
+
+    let row = assets::Entity::find_by_id(asset_id)
+        .one(&db)
+        .await?
+        .ok_or(ApiError::NotFound)?;
+
+    let mut active: assets::ActiveModel = row.into();
+    active.category = Set(category);
+    active.updated_at = Set(Utc::now().into());
+    let updated = active.update(&db).await?;
+
+    Ok(Json(updated.into()))
+}
+
+/// Create router for asset-categories endpoints
+pub fn create_router() -> Router<DatabaseConnection> {
+    Router::new()
+        .route("/api/v1/asset-categories", get(list_asset_categories_handler))
+        .route("/api/v1/asset-categories/{asset_id}", put(set_asset_category_handler))
+}
@@ -0,0 +1,397 @@
+use alloy::primitives::Address;
+use axum::{
+    extract::{Path, State},
+    http::StatusCode,
+    response::Json,
+    routing::get,
+    Extension, Router,
+};
+use chrono::Utc;
+use reqwest::Client;
+use sea_orm::{ActiveModelTrait, ColumnTrait, DatabaseConnection, EntityTrait, QueryFilter, QueryOrder, Set};
+use serde::{Deserialize, Serialize};
+use utoipa::ToSchema;
+use uuid::Uuid;
+
+use crate::connectors::dexscreener::dexscreener_chain_id;
+use crate::entities::{asset_price_overrides, assets};
+use crate::helpers::price_overrides::{refresh_dex_price, PRICE_SOURCE_DEX};
+use super::error::ApiError;
+
+// === Request / Response DTOs ===
+
+#[derive(Debug, Serialize, Deserialize, ToSchema)]
+pub struct AssetPriceOverrideResponse {
+    pub id: Uuid,
+    pub asset_id: Uuid,
+    /// Pinned price source: an `asset_prices` source (e.g. "coingecko") or "dex"
+    pub source: String,
+    /// Chain of the token contract, for "dex" overrides
+    pub chain: Option<String>,
+    /// Token contract priced on DEX pairs, for "dex" overrides
+    pub contract_address: Option<String>,
+    pub note: Option<String>,
+    pub is_active: bool,
+    pub created_at: String,
+    pub updated_at: String,
+}
+
+impl From<asset_price_overrides::Model> for AssetPriceOverrideResponse {
+    fn from(m: asset_price_overrides::Model) -> Self {
+        Self {
+            id: m.id,
+            asset_id: m.asset_id,
+            source: m.source,
+            chain: m.chain,
+            contract_address: m.contract_address,
+            note: m.note,
+            is_active: m.is_active,
+            created_at: m.created_at.to_rfc3339(),
+            updated_at: m.updated_at.to_rfc3339(),
+        }
+    }
+}
+
+#[derive(Debug, Serialize, Deserialize, ToSchema)]
+pub struct CreateAssetPriceOverrideRequest {
+    pub asset_id: Uuid,
+    /// Price source to pin, e.g. "coingecko", or "dex" to price the token on DEX pairs
+    pub source: String,
+    /// Chain of the token contract; required for "dex" (e.g. "ethereum", "bsc", "solana")
+    pub chain: Option<String>,
+    /// Token contract; required for "dex"
+    pub contract_address: Option<String>,
+    pub note: Option<String>,
+    /// Whether allocation construction applies the override (default: true)
+    #[serde(default = "default_true")]
+    pub is_active: bool,
+}
+
+#[derive(Debug, Serialize, Deserialize, ToSchema)]
+pub struct UpdateAssetPriceOverrideRequest {
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub source: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub chain: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub contract_address: Option<String>,
+    /// Note; an empty string clears it
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub note: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub is_active: Option<bool>,
+}
+
+fn default_true() -> bool {
+    true
+}
+
+// === Helpers ===
+
+/// Validated override target: lowercase source, and chain and contract for "dex"
+type OverrideTarget = (String, Option<String>, Option<String>);
+
+/// Validate the source and its DEX contract. EVM contracts are stored lowercase; other
+/// addresses (Solana mints) are case-sensitive and kept as given.
+fn parse_override_target(
+    source: &str,
+    chain: Option<&str>,
+    contract_address: Option<&str>,
+) -> Result<OverrideTarget, ApiError> {
+    let source = source.trim().to_lowercase();
+    if source.is_empty() {
+        return Err(ApiError::BadRequest("source is required".to_string()));
+    }
+    let chain = chain.map(|c| c.trim().to_lowercase()).filter(|c| !c.is_empty());
+    let contract = contract_address.map(str::trim).filter(|c| !c.is_empty());
+
+    if source != PRICE_SOURCE_DEX {
+        if chain.is_some() || contract.is_some() {
+            return Err(ApiError::BadRequest(
+                "chain and contract_address only apply to the dex source".to_string(),
+            ));
+        }
+        return Ok((source, None, None));
+    }
+
+    let (Some(chain), Some(contract)) = (chain, contract) else {
+        return Err(ApiError::BadRequest("The dex source requires chain and contract_address".to_string()));
+    };
+    if dexscreener_chain_id(&chain).is_none() {
+        return Err(ApiError::BadRequest(format!("DEX prices are not available on chain {}", chain)));
+    }
+    let contract = if chain == "solana" {
+        contract.to_string()
+    } else {
+        let address = contract
+            .parse::<Address>()
+            .map_err(|_| ApiError::BadRequest("contract_address must be a 0x-prefixed contract address".to_string()))?;
+        format!("{:?}", address)
+    };
+    Ok((source, Some(chain), Some(contract)))
+}
+
+/// Store the DEX price of a new or changed "dex" override right away, so the next construction
+/// does not wait for the scheduled refresh
+async fn refresh_if_dex(db: &DatabaseConnection, row: &asset_price_overrides::Model) {
+    if row.source != PRICE_SOURCE_DEX || !row.is_active {
+        return;
+    }
+    match refresh_dex_price(db, &Client::new(), row).await {
+        Ok(Some(price)) => tracing::info!("Stored DEX price {} for asset {}", price, row.asset_id),
+        Ok(None) => tracing::warn!("No DEX pair prices asset {}", row.asset_id),
+        Err(e) => tracing::warn!("Failed to read DEX price of asset {}: {}", row.asset_id, e),
+    }
+}
+
+// === Handlers ===
+
+/// List price source overrides
+#[utoipa::path(
+    get,
+    path = "/api/v1/asset-price-overrides",
+    responses(
+        (status = 200, description = "List of price source overrides", body = Vec<AssetPriceOverrideResponse>),
+        (status = 401, description = "Unauthorized"),
+        (status = 500, description = "Internal server error")
+    ),
+    tag = "asset-price-overrides"
+)]
+pub async fn list_asset_price_overrides_handler(
+    State(db): State<DatabaseConnection>,
+    Extension(_token): Extension<axum_keycloak_auth::decode::KeycloakToken<String>>,
+) -> Result<Json<Vec<AssetPriceOverrideResponse>>, ApiError> {
+    let rows = asset_price_overrides::Entity::find()
+        .order_by_asc(asset_price_overrides::Column::CreatedAt)
+        .all(&db)
+        .await?;
+
+    Ok(Json(rows.into_iter().map(|r| r.into()).collect()))
+}
+
+/// Get a price source override by ID
+#[utoipa::path(
+    get,
+    path = "/api/v1/asset-price-overrides/{override_id}",
+    params(
+        ("override_id" = Uuid, Path, description = "Override ID")
+    ),
+    responses(
+        (status = 200, description = "Price source override", body = AssetPriceOverrideResponse),
+        (status = 401, description = "Unauthorized"),
+        (status = 404, description = "Not found"),
+        (status = 500, description = "Internal server error")
+    ),
+    tag = "asset-price-overrides"
+)]
+pub async fn get_asset_price_override_handler(
+    State(db): State<DatabaseConnection>,
+    Extension(_token): Extension<axum_keycloak_auth::decode::KeycloakToken<String>>,
+    Path(override_id): Path<Uuid>,
+) -> Result<Json<AssetPriceOverrideResponse>, ApiError> {
+    let row = asset_price_overrides::Entity::find_by_id(override_id)
+        .one(&db)
+        .await?
+        .ok_or(ApiError::NotFound)?;
+
+    Ok(Json(row.into()))
+}
+
+/// Pin the price source of an asset
+///
+/// Allocation construction then prices the asset from `source` only. With `source` "dex" the
+/// token is priced on its most liquid DEX pair by `chain` and `contract_address`; the price is
+/// read once on save and then with every scheduled coin fetch.
+#[utoipa::path(
+    post,
+    path = "/api/v1/asset-price-overrides",
+    request_body = CreateAssetPriceOverrideRequest,
+    responses(
+        (status = 201, description = "Price source override added", body = AssetPriceOverrideResponse),
+        (status = 400, description = "Invalid request"),
+        (status = 401, description = "Unauthorized"),
+        (status = 409, description = "Asset already has an override"),
+        (status = 500, description = "Internal server error")
+    ),
+    tag = "asset-price-overrides"
+)]
+pub async fn create_asset_price_override_handler(
+    State(db): State<DatabaseConnection>,
+    Extension(_token): Extension<axum_keycloak_auth::decode::KeycloakToken<String>>,
+    Json(req): Json<CreateAssetPriceOverrideRequest>,
+) -> Result<(StatusCode, Json<AssetPriceOverrideResponse>), ApiError> {
+    let (source, chain, contract_address) =
+        parse_override_target(&req.source, req.chain.as_deref(), req.contract_address.as_deref())?;
+
+    if assets::Entity::find_by_id(req.asset_id).one(&db).await?.is_none() {
+        return Err(ApiError::BadRequest(format!("Unknown asset {}", req.asset_id)));
+    }
+
+    let existing = asset_price_overrides::Entity::find()
+        .filter(asset_price_overrides::Column::AssetId.eq(req.asset_id))
+        .one(&db)
+        .await?;
+
+    if existing.is_some() {
+        return Err(ApiError::Conflict(format!("Asset {} already has a price source override", req.asset_id)));
+    }
+
+    let new_row = asset_price_overrides::ActiveModel {
+        id: Set(Uuid::new_v4()),
+        asset_id: Set(req.asset_id),
+        source: Set(source),
+        chain: Set(chain),
+        contract_address: Set(contract_address),
+        note: Set(req.note.filter(|n| !n.trim().is_empty())),
+        is_active: Set(req.is_active),
+        created_at: Set(Utc::now().into()),
+        updated_at: Set(Utc::now().into()),
+    };
+
+    let row = new_row.insert(&db).await?;
+    refresh_if_dex(&db, &row).await;
+
+    Ok((StatusCode::CREATED, Json(row.into())))
+}
+
+/// Update a price source override
+///
+/// Change the pinned source or DEX contract, the note, or activate/deactivate the override.
+#[utoipa::path(
+    put,
+    path = "/api/v1/asset-price-overrides/{override_id}",
+    params(
+        ("override_id" = Uuid, Path, description = "Override ID")
+    ),
+    request_body = UpdateAssetPriceOverrideRequest,
+    responses(
+        (status = 200, description = "Price source override updated", body = AssetPriceOverrideResponse),
+        (status = 400, description = "Invalid request"),
+        (status = 401, description = "Unauthorized"),
+        (status = 404, description = "Not found"),
+        (status = 500, description = "Internal server error")
+    ),
+    tag = "asset-price-overrides"
+)]
+pub async fn update_asset_price_override_handler(
+    State(db): State<DatabaseConnection>,
+    Extension(_token): Extension<axum_keycloak_auth::decode::KeycloakToken<String>>,
+    Path(override_id): Path<Uuid>,
+    Json(req): Json<UpdateAssetPriceOverrideRequest>,
+) -> Result<Json<AssetPriceOverrideResponse>, ApiError> {
+    let row = asset_price_overrides::Entity::find_by_id(override_id)
+        .one(&db)
+        .await?
+        .ok_or(ApiError::NotFound)?;
+
+    let target = if req.source.is_some() || req.chain.is_some() || req.contract_address.is_some() {
+        let source = req.source.as_deref().unwrap_or(&row.source);
+        // Switching away from "dex" drops the contract unless new values are given
+        let keeps_contract = source.trim().eq_ignore_ascii_case(PRICE_SOURCE_DEX);
+        Some(parse_override_target(
+            source,
+            req.chain.as_deref().or(row.chain.as_deref().filter(|_| keeps_contract)),
+            req.contract_address.as_deref().or(row.contract_address.as_deref().filter(|_| keeps_contract)),
+        )?)
+    } else {
+        None
+    };
+
+    let mut active: asset_price_overrides::ActiveModel = row.into();
+
+    if let Some((source, chain, contract_address)) = target {
+        active.source = Set(source);
+        active.chain = Set(chain);
+        active.contract_address = Set(contract_address);
+    }
+    if let Some(note) = req.note {
+        active.note = Set(Some(note).filter(|n| !n.trim().is_empty()));
+    }
+    if let Some(is_active) = req.is_active {
+        active.is_active = Set(is_active);
+    }
+    active.updated_at = Set(Utc::now().into());
+
+    let updated = active.update(&db).await?;
+    refresh_if_dex(&db, &updated).await;
+
+    Ok(Json(updated.into()))
+}
+
+/// Delete a price source override
+///
+/// The asset is priced by source reconciliation again from the next construction on.
+#[utoipa::path(
+    delete,
+    path = "/api/v1/asset-price-overrides/{override_id}",
+    params(
+        ("override_id" = Uuid, Path, description = "Override ID")
+    ),
+    responses(
+        (status = 204, description = "Price source override deleted"),
+        (status = 401, description = "Unauthorized"),
+        (status = 404, description = "Not found"),
+        (status = 500, description = "Internal server error")
+    ),
+    tag = "asset-price-overrides"
+)]
+pub async fn delete_asset_price_override_handler(
+    State(db): State<DatabaseConnection>,
+    Extension(_token): Extension<axum_keycloak_auth::decode::KeycloakToken<String>>,
+    Path(override_id): Path<Uuid>,
+) -> Result<StatusCode, ApiError> {
+    let row = asset_price_overrides::Entity::find_by_id(override_id)
+        .one(&db)
+        .await?
+        .ok_or(ApiError::NotFound)?;
+
+    let active: asset_price_overrides::ActiveModel = row.into();
+    active.delete(&db).await?;
+
+    Ok(StatusCode::NO_CONTENT)
+}
+
+/// Create router for asset-price-overrides endpoints
+pub fn create_router() -> Router<DatabaseConnection> {
+    Router::new()
+        .route(
+            "/api/v1/asset-price-overrides",
+            get(list_asset_price_overrides_handler).post(create_asset_price_override_handler),
+        )
+        .route(
+            "/api/v1/asset-price-overrides/{override_id}",
+            get(get_asset_price_override_handler)
+                .put(update_asset_price_override_handler)
+                .delete(delete_asset_price_override_handler),
+        )
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_override_target() {
+        assert_eq!(
+            parse_override_target(" CoinGecko ", None, Some("")).unwrap(),
+            ("coingecko".to_string(), None, None)
+        );
+        let (source, chain, contract) = parse_override_target(
+            "dex",
+            Some("Ethereum"),
+            Some("0x7f39C581F595B53c5cb19bD0b3f8dA6c935E2Ca0"),
+        )
+        .unwrap();
+        assert_eq!(source, "dex");
+        assert_eq!(chain.as_deref(), Some("ethereum"));
+        assert_eq!(contract.as_deref(), Some("0x7f39c581f595b53c5cb19bd0b3f8da6c935e2ca0"));
+
+        let mint = "EPjFWdd5AufqSSqeM2qN1xzybapC8G4wEGGkZwyTDt1v";
+        assert_eq!(parse_override_target("dex", Some("solana"), Some(mint)).unwrap().2.as_deref(), Some(mint));
+
+        assert!(parse_override_target("dex", Some("ethereum"), None).is_err());
+        assert!(parse_override_target("dex", Some("bitcoin"), Some("bc1q")).is_err());
+        assert!(parse_override_target("coingecko", Some("ethereum"), None).is_err());
+        assert!(parse_override_target(" ", None, None).is_err());
+    }
+}
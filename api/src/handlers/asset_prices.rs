@@ -0,0 +1,468 @@
+use axum::{
+    extract::{Extension, Path, Query, State},
+    response::Json,
+    routing::get,
+    Router,
+};
+use axum_keycloak_auth::decode::KeycloakToken;
+use chrono::{DateTime, Utc};
+use rust_decimal::Decimal;
+use sea_orm::{ColumnTrait, DatabaseConnection, EntityTrait, QueryFilter, QueryOrder, QuerySelect};
+use serde::{Deserialize, Serialize};
+use utoipa::ToSchema;
+use uuid::Uuid;
+
+use crate::entities::{asset_prices, asset_prices_daily, asset_prices_hourly, assets};
+use crate::helpers::candles::{load_candles, Candle, CandleInterval, MAX_CANDLES};
+use crate::helpers::pagination::{finish_page, keyset_before, Cursor, PageRequest, DEFAULT_PAGE_LIMIT};
+use crate::helpers::price_resolution::PriceResolutionConfig;
+use super::error::ApiError;
+
+// === Request/Response DTOs ===
+
+#[derive(Debug, Serialize, Deserialize, ToSchema)]
+pub struct AssetPriceResponse {
+    pub id: Uuid,
+    pub timestamp: String,
+    pub price_usd: String,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub volume_24h_usd: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub market_cap_usd: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub change_percent_24h: Option<String>,
+    /// Price source (e.g., "coinpaprika")
+    pub source: String,
+}
+
+impl From<asset_prices::Model> for AssetPriceResponse {
+    fn from(model: asset_prices::Model) -> Self {
+        Self {
+            id: model.id,
+            timestamp: model.timestamp.to_rfc3339(),
+            price_usd: model.price_usd.to_string(),
+            volume_24h_usd: model.volume_24h_usd.map(|v| v.to_string()),
+            market_cap_usd: model.market_cap_usd.map(|v| v.to_string()),
+            change_percent_24h: model.change_percent_24h.map(|v| v.to_string()),
+            source: model.source,
+        }
+    }
+}
+
+#[derive(Debug, Deserialize, ToSchema)]
+pub struct ListAssetPricesQuery {
+    /// Only prices at or after this time (RFC 3339)
+    pub from: Option<String>,
+    /// Only prices before this time (RFC 3339)
+    pub to: Option<String>,
+    /// "raw" for every stored price, or "1h" / "1d" for the closing price of each hour or day.
+    /// Default: picked from the `from`..`to` range (raw up to 7 days, 1h up to 90 days, else 1d),
+    /// raw without `from`
+    pub granularity: Option<String>,
+    /// Only prices of this source (default: all sources; the primary source with 1h / 1d)
+    pub source: Option<String>,
+    /// Page size (default: 100)
+    pub limit: Option<u64>,
+    /// Cursor from a previous page's `next_cursor`
+    pub cursor: Option<String>,
+}
+
+#[derive(Debug, Serialize, Deserialize, ToSchema)]
+pub struct ListAssetPricesResponse {
+    pub asset_id: Uuid,
+    pub symbol: String,
+    /// Granularity of the prices: "raw", "1h" or "1d"
+    pub granularity: String,
+    pub prices: Vec<AssetPriceResponse>,
+    /// Cursor for the next page; absent on the last page
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub next_cursor: Option<String>,
+}
+
+#[derive(Debug, Deserialize, ToSchema)]
+pub struct AssetCandlesQuery {
+    /// Candle width: "1h" or "1d"
+    pub interval: String,
+    /// Only prices at or after this time (RFC 3339; default: a week of hourly or a year of daily candles before `to`)
+    pub from: Option<String>,
+    /// Only prices before this time (RFC 3339; default: now)
+    pub to: Option<String>,
+    /// Price source to chart (default: the primary price source)
+    pub source: Option<String>,
+}
+
+#[derive(Debug, Serialize, Deserialize, ToSchema)]
+pub struct CandleResponse {
+    /// Start of the candle (RFC 3339)
+    pub open_time: String,
+    pub open: String,
+    pub high: String,
+    pub low: String,
+    pub close: String,
+    /// Stored prices aggregated into the candle
+    pub ticks: usize,
+}
+
+impl From<Candle> for CandleResponse {
+    fn from(candle: Candle) -> Self {
+        Self {
+            open_time: candle.open_time.to_rfc3339(),
+            open: candle.open.to_string(),
+            high: candle.high.to_string(),
+            low: candle.low.to_string(),
+            close: candle.close.to_string(),
+            ticks: candle.ticks,
+        }
+    }
+}
+
+#[derive(Debug, Serialize, Deserialize, ToSchema)]
+pub struct AssetCandlesResponse {
+    pub asset_id: Uuid,
+    pub symbol: String,
+    pub interval: String,
+    /// Price source the candles were built from
+    pub source: String,
+    /// Candles oldest first; hours or days without prices are omitted
+    pub candles: Vec<CandleResponse>,
+}
+
+// === Helper Functions ===
+
+/// Parse an RFC 3339 query timestamp
+fn parse_timestamp(name: &str, value: &str) -> Result<DateTime<Utc>, ApiError> {
+    DateTime::parse_from_rfc3339(value)
+        .map(|dt| dt.with_timezone(&Utc))
+        .map_err(|e| ApiError::BadRequest(format!("Invalid {}: {}. Expected RFC 3339", name, e)))
+}
+
+/// Interval of a price history query: `granularity` when given ("raw" for none), else picked
+/// by the length of the range from `from` to `to`; raw without `from`
+fn price_granularity(
+    granularity: Option<&str>,
+    from: Option<DateTime<Utc>>,
+    to: DateTime<Utc>,
+) -> Result<Option<CandleInterval>, ApiError> {
+    match granularity.map(str::trim) {
+        Some("raw") => Ok(None),
+        Some(value) => CandleInterval::parse(value).map(Some).ok_or_else(|| {
+            ApiError::BadRequest(format!("Invalid granularity '{}'. Expected raw, 1h or 1d", value))
+        }),
+        None => Ok(from.and_then(|from| CandleInterval::for_range(to - from))),
+    }
+}
+
+/// Source filter of a price history query: `source` when given, else the primary source for
+/// rolled-up prices and every source for raw ones
+fn price_source(
+    source: Option<&str>,
+    granularity: Option<CandleInterval>,
+    primary_source: impl FnOnce() -> String,
+) -> Option<String> {
+    match (source, granularity) {
+        (Some(source), _) => Some(source.trim().to_lowercase()),
+        (None, Some(_)) => Some(primary_source()),
+        (None, None) => None,
+    }
+}
+
+/// Closing price of a rolled-up bucket, timestamped at the bucket start
+fn rollup_price(id: Uuid, bucket_start: DateTime<Utc>, close_usd: Decimal, source: String) -> AssetPriceResponse {
+    AssetPriceResponse {
+        id,
+        timestamp: bucket_start.to_rfc3339(),
+        price_usd: close_usd.to_string(),
+        volume_24h_usd: None,
+        market_cap_usd: None,
+        change_percent_24h: None,
+        source,
+    }
+}
+
+/// One page of rolled-up closing prices from `asset_prices_hourly` or `asset_prices_daily`,
+/// newest first
+async fn list_rollup_prices(
+    db: &DatabaseConnection,
+    asset_id: Uuid,
+    source: &str,
+    from: Option<DateTime<Utc>>,
+    to: Option<DateTime<Utc>>,
+    interval: CandleInterval,
+    page: &PageRequest,
+) -> Result<(Vec<AssetPriceResponse>, Option<String>), ApiError> {
+    let rows: Vec<(Uuid, DateTime<Utc>, Decimal, String)> = match interval {
+        CandleInterval::Hour => {
+            let mut rollup_query = asset_prices_hourly::Entity::find()
+                .filter(asset_prices_hourly::Column::AssetId.eq(asset_id))
+                .filter(asset_prices_hourly::Column::Source.eq(source))
+                .order_by_desc(asset_prices_hourly::Column::BucketStart)
+                .order_by_desc(asset_prices_hourly::Column::Id)
+                .limit(page.fetch_limit());
+            if let Some(from) = from {
+                rollup_query = rollup_query.filter(asset_prices_hourly::Column::BucketStart.gte(from));
+            }
+            if let Some(to) = to {
+                rollup_query = rollup_query.filter(asset_prices_hourly::Column::BucketStart.lt(to));
+            }
+            if let Some(after) = &page.after {
+                rollup_query = rollup_query.filter(keyset_before(
+                    asset_prices_hourly::Column::BucketStart,
+                    asset_prices_hourly::Column::Id,
+                    after.timestamp,
+                    after.id,
+                ));
+            }
+            rollup_query
+                .all(db)
+                .await?
+                .into_iter()
+                .map(|row| (row.id, row.bucket_start.with_timezone(&Utc), row.close_usd, row.source))
+                .collect()
+        }
+        CandleInterval::Day => {
+            let mut rollup_query = asset_prices_daily::Entity::find()
+                .filter(asset_prices_daily::Column::AssetId.eq(asset_id))
+                .filter(asset_prices_daily::Column::Source.eq(source))
+                .order_by_desc(asset_prices_daily::Column::BucketStart)
+                .order_by_desc(asset_prices_daily::Column::Id)
+                .limit(page.fetch_limit());
+            if let Some(from) = from {
+                rollup_query = rollup_query.filter(asset_prices_daily::Column::BucketStart.gte(from));
+            }
+            if let Some(to) = to {
+                rollup_query = rollup_query.filter(asset_prices_daily::Column::BucketStart.lt(to));
+            }
+            if let Some(after) = &page.after {
+                rollup_query = rollup_query.filter(keyset_before(
+                    asset_prices_daily::Column::BucketStart,
+                    asset_prices_daily::Column::Id,
+                    after.timestamp,
+                    after.id,
+                ));
+            }
+            rollup_query
+                .all(db)
+                .await?
+                .into_iter()
+                .map(|row| (row.id, row.bucket_start.with_timezone(&Utc), row.close_usd, row.source))
+                .collect()
+        }
+    };
+
+    let (rows, next_cursor) = finish_page(rows, page, |(id, bucket_start, _, _)| Cursor::new(*bucket_start, *id));
+    let prices = rows
+        .into_iter()
+        .map(|(id, bucket_start, close_usd, source)| rollup_price(id, bucket_start, close_usd, source))
+        .collect();
+    Ok((prices, next_cursor))
+}
+
+// === API Handlers ===
+
+/// List price history for an asset
+///
+/// Returns stored prices newest first, one page at a time. Unlike other list endpoints
+/// this one is always paginated, since an asset can have a very long price history.
+/// With `granularity` 1h or 1d the closing price of each hour or day is read from the price
+/// rollups, timestamped at the start of the hour or day; buckets appear once the rollup job
+/// has run. Without `granularity`, a range given with `from` picks one by its length.
+#[utoipa::path(
+    get,
+    path = "/api/v1/assets/{asset_id}/prices",
+    params(
+        ("asset_id" = Uuid, Path, description = "Asset ID"),
+        ("from" = Option<String>, Query, description = "Only prices at or after this time (RFC 3339)"),
+        ("to" = Option<String>, Query, description = "Only prices before this time (RFC 3339)"),
+        ("granularity" = Option<String>, Query, description = "raw, 1h or 1d (default: by range length; raw without from)"),
+        ("source" = Option<String>, Query, description = "Only prices of this source (default: all; primary source with 1h / 1d)"),
+        ("limit" = Option<u64>, Query, description = "Page size (1-1000, default: 100)"),
+        ("cursor" = Option<String>, Query, description = "Opaque cursor from a previous page's next_cursor")
+    ),
+    responses(
+        (status = 200, description = "Price history page", body = ListAssetPricesResponse),
+        (status = 400, description = "Invalid time range, granularity or cursor"),
+        (status = 401, description = "Unauthorized"),
+        (status = 404, description = "Asset not found"),
+        (status = 500, description = "Internal server error")
+    ),
+    tag = "assets"
+)]
+pub async fn list_asset_prices_handler(
+    State(db): State<DatabaseConnection>,
+    Extension(_token): Extension<KeycloakToken<String>>,
+    Path(asset_id): Path<Uuid>,
+    Query(query): Query<ListAssetPricesQuery>,
+) -> Result<Json<ListAssetPricesResponse>, ApiError> {
+    let page = PageRequest::from_query(
+        Some(query.limit.unwrap_or(DEFAULT_PAGE_LIMIT)),
+        query.cursor.as_deref(),
+    )?;
+
+    let from = query.from.as_deref().map(|from| parse_timestamp("from", from)).transpose()?;
+    let to = query.to.as_deref().map(|to| parse_timestamp("to", to)).transpose()?;
+    let granularity = price_granularity(query.granularity.as_deref(), from, to.unwrap_or_else(Utc::now))?;
+    let source = price_source(query.source.as_deref(), granularity, || PriceResolutionConfig::from_env().primary_source);
+
+    let asset = assets::Entity::find_by_id(asset_id)
+        .one(&db)
+        .await?
+        .ok_or(ApiError::NotFound)?;
+
+    if let (Some(interval), Some(source)) = (granularity, source.as_deref()) {
+        let (prices, next_cursor) = list_rollup_prices(&db, asset_id, source, from, to, interval, &page).await?;
+        return Ok(Json(ListAssetPricesResponse {
+            asset_id,
+            symbol: asset.symbol,
+            granularity: interval.as_str().to_string(),
+            prices,
+            next_cursor,
+        }));
+    }
+
+    let mut price_query = asset_prices::Entity::find()
+        .filter(asset_prices::Column::AssetId.eq(asset_id))
+        .order_by_desc(asset_prices::Column::Timestamp)
+        .order_by_desc(asset_prices::Column::Id)
+        .limit(page.fetch_limit());
+
+    if let Some(from) = from {
+        price_query = price_query.filter(asset_prices::Column::Timestamp.gte(from));
+    }
+    if let Some(to) = to {
+        price_query = price_query.filter(asset_prices::Column::Timestamp.lt(to));
+    }
+    if let Some(source) = source.as_deref() {
+        price_query = price_query.filter(asset_prices::Column::Source.eq(source));
+    }
+    if let Some(after) = &page.after {
+        price_query = price_query.filter(keyset_before(
+            asset_prices::Column::Timestamp,
+            asset_prices::Column::Id,
+            after.timestamp,
+            after.id,
+        ));
+    }
+
+    let (rows, next_cursor) = finish_page(price_query.all(&db).await?, &page, |p| {
+        Cursor::new(p.timestamp.with_timezone(&Utc), p.id)
+    });
+
+    Ok(Json(ListAssetPricesResponse {
+        asset_id,
+        symbol: asset.symbol,
+        granularity: "raw".to_string(),
+        prices: rows.into_iter().map(AssetPriceResponse::from).collect(),
+        next_cursor,
+    }))
+}
+
+/// Get OHLC candles for an asset
+///
+/// Aggregates stored prices of one source into hourly or daily candles server-side, so charts
+/// don't need the raw ticks. Rolled-up buckets come from the hourly and daily price rollups.
+/// A request may span at most 1000 candles.
+#[utoipa::path(
+    get,
+    path = "/api/v1/assets/{asset_id}/candles",
+    params(
+        ("asset_id" = Uuid, Path, description = "Asset ID"),
+        ("interval" = String, Query, description = "Candle width: 1h or 1d"),
+        ("from" = Option<String>, Query, description = "Only prices at or after this time (RFC 3339)"),
+        ("to" = Option<String>, Query, description = "Only prices before this time (RFC 3339, default: now)"),
+        ("source" = Option<String>, Query, description = "Price source (default: primary price source)")
+    ),
+    responses(
+        (status = 200, description = "Candles", body = AssetCandlesResponse),
+        (status = 400, description = "Invalid interval or time range"),
+        (status = 401, description = "Unauthorized"),
+        (status = 404, description = "Asset not found"),
+        (status = 500, description = "Internal server error")
+    ),
+    tag = "assets"
+)]
+pub async fn get_asset_candles_handler(
+    State(db): State<DatabaseConnection>,
+    Extension(_token): Extension<KeycloakToken<String>>,
+    Path(asset_id): Path<Uuid>,
+    Query(query): Query<AssetCandlesQuery>,
+) -> Result<Json<AssetCandlesResponse>, ApiError> {
+    let interval = CandleInterval::parse(&query.interval).ok_or_else(|| {
+        ApiError::BadRequest(format!("Invalid interval '{}'. Expected 1h or 1d", query.interval))
+    })?;
+    let to = match query.to.as_deref() {
+        Some(to) => parse_timestamp("to", to)?,
+        None => Utc::now(),
+    };
+    let from = match query.from.as_deref() {
+        Some(from) => parse_timestamp("from", from)?,
+        None => to - interval.default_range(),
+    };
+    if from >= to {
+        return Err(ApiError::BadRequest("from must be before to".to_string()));
+    }
+    if (to - from).num_seconds() > interval.duration().num_seconds() * MAX_CANDLES {
+        return Err(ApiError::BadRequest(format!(
+            "Range spans more than {} {} candles",
+            MAX_CANDLES,
+            interval.as_str()
+        )));
+    }
+    let source = match query.source {
+        Some(source) => source.trim().to_lowercase(),
+        None => PriceResolutionConfig::from_env().primary_source,
+    };
+
+    let asset = assets::Entity::find_by_id(asset_id)
+        .one(&db)
+        .await?
+        .ok_or(ApiError::NotFound)?;
+
+    let candles = load_candles(&db, asset_id, &source, interval, from, to).await?;
+
+    Ok(Json(AssetCandlesResponse {
+        asset_id,
+        symbol: asset.symbol,
+        interval: interval.as_str().to_string(),
+        source,
+        candles: candles.into_iter().map(CandleResponse::from).collect(),
+    }))
+}
+
+/// Create router for asset price routes
+pub fn create_router() -> Router<DatabaseConnection> {
+    Router::new()
+        .route("/api/v1/assets/{asset_id}/prices", get(list_asset_prices_handler))
+        .route("/api/v1/assets/{asset_id}/candles", get(get_asset_candles_handler))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use chrono::{Duration, TimeZone};
+
+    #[test]
+    fn test_price_granularity() {
+        let to = Utc.with_ymd_and_hms(2026, 3, 1, 0, 0, 0).unwrap();
+        let days_before = |days| Some(to - Duration::days(days));
+
+        assert_eq!(price_granularity(Some("raw"), days_before(365), to).unwrap(), None);
+        assert_eq!(price_granularity(Some(" 1H "), None, to).unwrap(), Some(CandleInterval::Hour));
+        assert_eq!(price_granularity(Some("1d"), days_before(1), to).unwrap(), Some(CandleInterval::Day));
+        assert!(price_granularity(Some("5m"), None, to).is_err());
+
+        // Without granularity the range decides; without from it stays raw
+        assert_eq!(price_granularity(None, None, to).unwrap(), None);
+        assert_eq!(price_granularity(None, days_before(7), to).unwrap(), None);
+        assert_eq!(price_granularity(None, days_before(30), to).unwrap(), Some(CandleInterval::Hour));
+        assert_eq!(price_granularity(None, days_before(91), to).unwrap(), Some(CandleInterval::Day));
+    }
+
+    #[test]
+    fn test_price_source() {
+        let primary = || "coinpaprika".to_string();
+        assert_eq!(price_source(Some(" Binance "), None, primary), Some("binance".to_string()));
+        assert_eq!(price_source(Some("okx"), Some(CandleInterval::Day), primary), Some("okx".to_string()));
+        assert_eq!(price_source(None, Some(CandleInterval::Hour), primary), Some("coinpaprika".to_string()));
+        assert_eq!(price_source(None, None, primary), None);
+    }
+}
@@ -0,0 +1,129 @@
+use axum::{
+    extract::{Extension, Path, Query, State},
+    response::Json,
+    routing::get,
+    Router,
+};
+use axum_keycloak_auth::decode::KeycloakToken;
+use chrono::Utc;
+use sea_orm::DatabaseConnection;
+use serde::{Deserialize, Serialize};
+use utoipa::ToSchema;
+use uuid::Uuid;
+
+use crate::helpers::attribution::{attribute_returns, load_holdings_series};
+use crate::helpers::auth::{check_portfolio_ownership, get_or_create_user};
+use crate::helpers::performance::PerformancePeriod;
+use super::error::ApiError;
+
+// === Request/Response DTOs ===
+
+pub use crate::helpers::attribution::AssetAttribution;
+
+#[derive(Debug, Deserialize, ToSchema)]
+pub struct AttributionQuery {
+    /// "7d", "30d" (default), "ytd" or "all"
+    pub period: Option<String>,
+}
+
+#[derive(Debug, Serialize, Deserialize, ToSchema)]
+pub struct AttributionResponse {
+    pub portfolio_id: Uuid,
+    pub period: String,
+    /// First and last snapshot date of the period; absent with fewer than two snapshots
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub start_date: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub end_date: Option<String>,
+    pub start_value_usd: f64,
+    pub end_value_usd: f64,
+    /// Compounded return from price moves of the held quantities, as a fraction; the sum of
+    /// the assets' contributions
+    pub price_return: f64,
+    /// Value change from price moves
+    pub pnl_usd: f64,
+    /// Value change not explained by price moves: deposits, withdrawals, trades and assets
+    /// bought between snapshots
+    pub other_change_usd: f64,
+    /// Per asset, largest absolute contribution first
+    pub assets: Vec<AssetAttribution>,
+}
+
+// === API Handlers ===
+
+/// Get a portfolio's return attribution
+///
+/// Splits the portfolio's return over `period` into the contribution of each asset, from the
+/// holdings and prices stored in its snapshots: between consecutive snapshots, an asset
+/// contributes the price change of its held quantity over the portfolio value, linked across
+/// the period so the contributions add up to the portfolio's price return. The period starts
+/// at the latest snapshot on or before its first day, as in the performance endpoint.
+#[utoipa::path(
+    get,
+    path = "/api/v1/portfolios/{portfolio_id}/attribution",
+    params(
+        ("portfolio_id" = Uuid, Path, description = "Portfolio ID"),
+        ("period" = Option<String>, Query, description = "7d, 30d (default), ytd or all")
+    ),
+    responses(
+        (status = 200, description = "Return attribution", body = AttributionResponse),
+        (status = 400, description = "Invalid period"),
+        (status = 401, description = "Unauthorized"),
+        (status = 403, description = "Forbidden - portfolio does not belong to user"),
+        (status = 404, description = "Portfolio not found"),
+        (status = 500, description = "Internal server error")
+    ),
+    tag = "portfolios"
+)]
+pub async fn get_portfolio_attribution_handler(
+    State(db): State<DatabaseConnection>,
+    Extension(token): Extension<KeycloakToken<String>>,
+    Path(portfolio_id): Path<Uuid>,
+    Query(query): Query<AttributionQuery>,
+) -> Result<Json<AttributionResponse>, ApiError> {
+    let user = get_or_create_user(&db, &token).await?;
+    check_portfolio_ownership(&db, portfolio_id, user.id).await?;
+
+    let period = match query.period.as_deref() {
+        Some(value) => PerformancePeriod::parse(value).ok_or_else(|| {
+            ApiError::BadRequest(format!("Invalid period '{}'. Expected 7d, 30d, ytd or all", value))
+        })?,
+        None => PerformancePeriod::Month,
+    };
+
+    let snapshots = load_holdings_series(&db, portfolio_id, period.baseline_date(Utc::now().date_naive())).await?;
+    let Some(attribution) = attribute_returns(&snapshots) else {
+        let value = snapshots.first().map_or(0.0, |s| s.total_value_usd);
+        return Ok(Json(AttributionResponse {
+            portfolio_id,
+            period: period.as_str().to_string(),
+            start_date: None,
+            end_date: None,
+            start_value_usd: value,
+            end_value_usd: value,
+            price_return: 0.0,
+            pnl_usd: 0.0,
+            other_change_usd: 0.0,
+            assets: Vec::new(),
+        }));
+    };
+
+    Ok(Json(AttributionResponse {
+        portfolio_id,
+        period: period.as_str().to_string(),
+        start_date: snapshots.first().map(|s| s.date.to_string()),
+        end_date: snapshots.last().map(|s| s.date.to_string()),
+        start_value_usd: attribution.start_value_usd,
+        end_value_usd: attribution.end_value_usd,
+        price_return: attribution.price_return,
+        pnl_usd: attribution.pnl_usd,
+        other_change_usd: attribution.other_change_usd,
+        assets: attribution.assets,
+    }))
+}
+
+// === Router setup ===
+
+pub fn create_router() -> Router<DatabaseConnection> {
+    Router::new().route("/api/v1/portfolios/{portfolio_id}/attribution", get(get_portfolio_attribution_handler))
+}
@@ -0,0 +1,205 @@
+use std::collections::BTreeMap;
+
+use axum::{
+    extract::{Extension, Path, State},
+    response::Json,
+    routing::post,
+    Router,
+};
+use axum_keycloak_auth::decode::KeycloakToken;
+use chrono::{Duration, NaiveDate, NaiveTime, Utc};
+use rust_decimal::prelude::ToPrimitive;
+use sea_orm::DatabaseConnection;
+use serde::{Deserialize, Serialize};
+use utoipa::ToSchema;
+use uuid::Uuid;
+
+use crate::domain::target_weights;
+use crate::helpers::asset_identity::{AssetIdentityNormalizer, NormalizationResult};
+use crate::helpers::auth::{check_portfolio_ownership, get_or_create_user};
+use crate::helpers::backtest::run_backtest;
+use crate::helpers::candles::{load_candles, CandleInterval};
+use crate::helpers::price_resolution::PriceResolutionConfig;
+use crate::helpers::params::parse_date;
+use super::error::ApiError;
+
+/// Default lookback in days when `from` is not given, and the longest window
+const DEFAULT_BACKTEST_DAYS: i64 = 365;
+const MAX_BACKTEST_DAYS: i64 = 3650;
+
+/// Starting value of the simulated portfolio when not given
+const DEFAULT_INITIAL_VALUE_USD: f64 = 10_000.0;
+
+// === Request/Response DTOs ===
+
+pub use crate::helpers::backtest::{BacktestPoint, CurveStats, RebalanceFrequency};
+
+#[derive(Debug, Default, Deserialize, ToSchema)]
+pub struct BacktestRequest {
+    /// First day (YYYY-MM-DD, default 365 days before `to`)
+    pub from: Option<String>,
+    /// Last day (YYYY-MM-DD, default today)
+    pub to: Option<String>,
+    /// "never", "daily", "weekly", "monthly" (default) or "quarterly"
+    pub rebalance: Option<String>,
+    /// Weights by symbol to simulate (e.g. {"BTC": 60, "USDT": 40}); default the portfolio's
+    /// `target_allocation`
+    pub targets: Option<BTreeMap<String, f64>>,
+    /// Starting value in USD (default 10000)
+    pub initial_value_usd: Option<f64>,
+}
+
+#[derive(Debug, Serialize, Deserialize, ToSchema)]
+pub struct BacktestResponse {
+    pub portfolio_id: Uuid,
+    /// First day every asset has a price (may be after the requested `from`)
+    pub from: String,
+    pub to: String,
+    pub rebalance: RebalanceFrequency,
+    /// Simulated weights (0-100)
+    pub targets: BTreeMap<String, f64>,
+    pub initial_value_usd: f64,
+    /// Rebalanced to `targets` on the schedule
+    pub strategy: CurveStats,
+    /// Starting quantities held unchanged
+    pub buy_and_hold: CurveStats,
+    /// Strategy total return minus buy-and-hold total return
+    pub excess_return: f64,
+    pub rebalances: usize,
+    /// USD value bought and sold by rebalancing, counted one way
+    pub traded_usd: f64,
+    /// `traded_usd` over the average portfolio value
+    pub turnover: f64,
+    /// Daily values of both strategies, oldest first
+    pub points: Vec<BacktestPoint>,
+}
+
+// === API Handlers ===
+
+/// Backtest a target allocation
+///
+/// Simulates the portfolio's target allocation (or the given `targets`) over a historical
+/// window of daily closes from `asset_prices`, rebalanced back to the targets on the chosen
+/// schedule, against holding the starting quantities unchanged. Returns the daily value
+/// curves, total and annualized returns, volatility, maximum drawdown and rebalancing
+/// turnover of both. Trades are frictionless.
+#[utoipa::path(
+    post,
+    path = "/api/v1/portfolios/{portfolio_id}/backtest",
+    params(
+        ("portfolio_id" = Uuid, Path, description = "Portfolio ID")
+    ),
+    request_body = BacktestRequest,
+    responses(
+        (status = 200, description = "Backtest result", body = BacktestResponse),
+        (status = 400, description = "Invalid window, schedule or targets, or assets without price history"),
+        (status = 401, description = "Unauthorized"),
+        (status = 403, description = "Forbidden - portfolio does not belong to user"),
+        (status = 404, description = "Portfolio not found"),
+        (status = 500, description = "Internal server error")
+    ),
+    tag = "portfolios"
+)]
+pub async fn backtest_portfolio_handler(
+    State(db): State<DatabaseConnection>,
+    Extension(token): Extension<KeycloakToken<String>>,
+    Path(portfolio_id): Path<Uuid>,
+    Json(req): Json<BacktestRequest>,
+) -> Result<Json<BacktestResponse>, ApiError> {
+    let user = get_or_create_user(&db, &token).await?;
+    let portfolio = check_portfolio_ownership(&db, portfolio_id, user.id).await?;
+
+    let to = match req.to.as_deref() {
+        Some(to) => parse_date(to, "to")?,
+        None => Utc::now().date_naive(),
+    };
+    let from = match req.from.as_deref() {
+        Some(from) => parse_date(from, "from")?,
+        None => to - Duration::days(DEFAULT_BACKTEST_DAYS),
+    };
+    if from >= to {
+        return Err(ApiError::BadRequest("from must be before to".to_string()));
+    }
+    if (to - from).num_days() > MAX_BACKTEST_DAYS {
+        return Err(ApiError::BadRequest(format!("The window must be at most {} days", MAX_BACKTEST_DAYS)));
+    }
+    let rebalance = match req.rebalance.as_deref() {
+        None => RebalanceFrequency::Monthly,
+        Some(value) => RebalanceFrequency::parse(value).ok_or_else(|| {
+            ApiError::BadRequest(format!(
+                "Invalid rebalance '{}'. Expected never, daily, weekly, monthly or quarterly",
+                value
+            ))
+        })?,
+    };
+    let initial_value = req.initial_value_usd.unwrap_or(DEFAULT_INITIAL_VALUE_USD);
+    if !initial_value.is_finite() || initial_value <= 0.0 {
+        return Err(ApiError::BadRequest("initial_value_usd must be positive".to_string()));
+    }
+
+    let mut targets: BTreeMap<String, f64> = match req.targets {
+        Some(targets) => targets.into_iter().map(|(asset, weight)| (asset.trim().to_uppercase(), weight)).collect(),
+        None => target_weights(portfolio.target_allocation.as_ref()),
+    };
+    if targets.values().any(|w| !w.is_finite() || *w < 0.0) {
+        return Err(ApiError::BadRequest("Target weights must be zero or more".to_string()));
+    }
+    targets.retain(|_, w| *w > 0.0);
+    let sum: f64 = targets.values().sum();
+    if targets.is_empty() {
+        return Err(ApiError::BadRequest("No targets: set the portfolio's target_allocation or send targets".to_string()));
+    }
+    targets.values_mut().for_each(|w| *w *= 100.0 / sum);
+
+    // Daily closes of each target asset
+    let source = PriceResolutionConfig::from_env().primary_source;
+    let start = from.and_time(NaiveTime::MIN).and_utc();
+    let end = (to + Duration::days(1)).and_time(NaiveTime::MIN).and_utc();
+    let normalizer = AssetIdentityNormalizer::new(db.clone());
+    let mut closes = BTreeMap::new();
+    let mut missing = Vec::new();
+    for symbol in targets.keys() {
+        let series: BTreeMap<NaiveDate, f64> = match normalizer.normalize_from_symbol(symbol).await {
+            NormalizationResult::Mapped(identity) => load_candles(&db, identity.asset_id, &source, CandleInterval::Day, start, end)
+                .await?
+                .into_iter()
+                .filter_map(|c| Some((c.open_time.date_naive(), c.close.to_f64()?)))
+                .collect(),
+            NormalizationResult::Unknown { .. } => BTreeMap::new(),
+        };
+        if series.is_empty() {
+            missing.push(symbol.clone());
+        } else {
+            closes.insert(symbol.clone(), series);
+        }
+    }
+    if !missing.is_empty() {
+        return Err(ApiError::BadRequest(format!("No price history in the window for: {}", missing.join(", "))));
+    }
+
+    let result = run_backtest(&closes, &targets, rebalance, initial_value).ok_or_else(|| {
+        ApiError::BadRequest("The assets' price histories overlap on fewer than two days".to_string())
+    })?;
+
+    Ok(Json(BacktestResponse {
+        portfolio_id,
+        from: result.start.to_string(),
+        to: result.end.to_string(),
+        rebalance,
+        targets,
+        initial_value_usd: initial_value,
+        excess_return: result.strategy.total_return - result.buy_and_hold.total_return,
+        strategy: result.strategy,
+        buy_and_hold: result.buy_and_hold,
+        rebalances: result.rebalances,
+        traded_usd: result.traded_usd,
+        turnover: result.turnover,
+        points: result.points,
+    }))
+}
+
+// === Router setup ===
+
+pub fn create_router() -> Router<DatabaseConnection> {
+    Router::new().route("/api/v1/portfolios/{portfolio_id}/backtest", post(backtest_portfolio_handler))
+}
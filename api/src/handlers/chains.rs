@@ -3,7 +3,9 @@ use sea_orm::{ColumnTrait, DatabaseConnection, EntityTrait, QueryFilter};
 use serde::{Deserialize, Serialize};
 use utoipa::ToSchema;
 
-use crate::entities::evm_chains;
+use crate::connectors::registry::DEFAULT_WALLET_KIND;
+use crate::connectors::utxo::UTXO_CHAINS;
+use crate::entities::{cosmos_chains, evm_chains};
 
 /// Supported chain information
 #[derive(Debug, Serialize, Deserialize, ToSchema, Clone)]
@@ -14,6 +16,8 @@ pub struct ChainInfo {
     pub name: String,
     /// Native token symbol
     pub native_symbol: String,
+    /// Value of `chain_family` for wallet accounts on this chain ("evm" for all EVM chains)
+    pub chain_family: String,
 }
 
 /// Response containing list of supported chains
@@ -26,8 +30,9 @@ pub struct ListChainsResponse {
 /// List all supported chains
 ///
 /// Returns a list of active chains from the database that can be selected for wallet accounts.
-/// EVM chains are used in the `enabled_chains` field when creating or updating wallet accounts.
-/// Solana wallets use `exchange_name: "solana"` with no `enabled_chains` needed.
+/// EVM and Cosmos chains are used in the `enabled_chains` field when creating or updating wallet
+/// accounts of their `chain_family` ("evm", "cosmos"). Other wallets select their chain with
+/// `chain_family` (e.g. "solana", "bitcoin", "litecoin", "dogecoin") and need no `enabled_chains`.
 #[utoipa::path(
     get,
     path = "/v1/chains",
@@ -51,15 +56,35 @@ pub async fn list_supported_chains(
             id: r.chain_id,
             name: r.name,
             native_symbol: r.native_symbol,
+            chain_family: DEFAULT_WALLET_KIND.to_string(),
         })
         .collect();
 
-    // Solana is not stored in the evm_chains table; append it here
+    let cosmos_rows = cosmos_chains::Entity::find()
+        .filter(cosmos_chains::Column::IsActive.eq(true))
+        .all(&db)
+        .await
+        .unwrap_or_default();
+    chains.extend(cosmos_rows.into_iter().map(|r| ChainInfo {
+        id: r.chain_id,
+        name: r.name,
+        native_symbol: r.native_symbol,
+        chain_family: "cosmos".to_string(),
+    }));
+
+    // Solana and the UTXO chains are not stored in the evm_chains table; append them here
     chains.push(ChainInfo {
         id: "solana".to_string(),
         name: "Solana".to_string(),
         native_symbol: "SOL".to_string(),
+        chain_family: "solana".to_string(),
     });
+    chains.extend(UTXO_CHAINS.iter().map(|chain| ChainInfo {
+        id: chain.id.to_string(),
+        name: chain.display_name.to_string(),
+        native_symbol: chain.symbol.to_string(),
+        chain_family: chain.id.to_string(),
+    }));
 
     Json(ListChainsResponse { chains })
 }
@@ -79,6 +104,7 @@ mod tests {
             id: "ethereum".to_string(),
             name: "Ethereum".to_string(),
             native_symbol: "ETH".to_string(),
+            chain_family: "evm".to_string(),
         };
         let json = serde_json::to_string(&info).unwrap();
         assert!(json.contains("ethereum"));
@@ -0,0 +1,328 @@
+use axum::{
+    extract::{Extension, Path, State},
+    http::StatusCode,
+    response::Json,
+    routing::get,
+    Router,
+};
+use chrono::Utc;
+use sea_orm::{
+    ActiveModelTrait, ColumnTrait, DatabaseConnection, EntityTrait, QueryFilter, Set,
+};
+use serde::{Deserialize, Serialize};
+use utoipa::ToSchema;
+use uuid::Uuid;
+
+use crate::entities::cosmos_chains;
+use super::error::ApiError;
+
+// === Request / Response DTOs ===
+
+#[derive(Debug, Serialize, Deserialize, ToSchema)]
+pub struct CosmosChainResponse {
+    pub id: Uuid,
+    /// Unique chain identifier, e.g. "cosmoshub", "osmosis"
+    pub chain_id: String,
+    /// Human-readable chain name
+    pub name: String,
+    /// LCD (REST) endpoint used to query balances and staking
+    pub lcd_url: String,
+    /// Bech32 prefix of account addresses, e.g. "cosmos", "osmo"
+    pub bech32_prefix: String,
+    /// Base denom of the native staking token, e.g. "uatom"
+    pub native_denom: String,
+    /// Native token symbol, e.g. "ATOM"
+    pub native_symbol: String,
+    /// Decimal places of `native_symbol` over `native_denom`
+    pub native_decimals: i32,
+    /// Whether this chain is active for account sync
+    pub is_active: bool,
+    pub created_at: String,
+    pub updated_at: String,
+}
+
+impl From<cosmos_chains::Model> for CosmosChainResponse {
+    fn from(m: cosmos_chains::Model) -> Self {
+        Self {
+            id: m.id,
+            chain_id: m.chain_id,
+            name: m.name,
+            lcd_url: m.lcd_url,
+            bech32_prefix: m.bech32_prefix,
+            native_denom: m.native_denom,
+            native_symbol: m.native_symbol,
+            native_decimals: m.native_decimals,
+            is_active: m.is_active,
+            created_at: m.created_at.to_rfc3339(),
+            updated_at: m.updated_at.to_rfc3339(),
+        }
+    }
+}
+
+#[derive(Debug, Serialize, Deserialize, ToSchema)]
+pub struct CreateCosmosChainRequest {
+    /// Unique chain identifier (e.g. "cosmoshub"); used as the holdings' chain suffix
+    pub chain_id: String,
+    /// Human-readable chain name (e.g. "Cosmos Hub")
+    pub name: String,
+    /// LCD endpoint URL
+    pub lcd_url: String,
+    /// Bech32 prefix of account addresses (e.g. "cosmos")
+    pub bech32_prefix: String,
+    /// Base denom of the native staking token (e.g. "uatom")
+    pub native_denom: String,
+    /// Native token symbol (e.g. "ATOM")
+    pub native_symbol: String,
+    /// Decimal places of the native token (default: 6)
+    #[serde(default = "default_decimals")]
+    pub native_decimals: i32,
+    /// Whether to include this chain during account sync (default: true)
+    #[serde(default = "default_true")]
+    pub is_active: bool,
+}
+
+#[derive(Debug, Serialize, Deserialize, ToSchema)]
+pub struct UpdateCosmosChainRequest {
+    /// Human-readable chain name
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub name: Option<String>,
+    /// LCD endpoint URL
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub lcd_url: Option<String>,
+    /// Whether this chain is active for account sync
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub is_active: Option<bool>,
+}
+
+fn default_true() -> bool {
+    true
+}
+
+fn default_decimals() -> i32 {
+    6
+}
+
+// === Handlers ===
+
+/// List Cosmos chains
+///
+/// Returns all configured Cosmos-SDK chains including their LCD URLs.
+#[utoipa::path(
+    get,
+    path = "/api/v1/cosmos-chains",
+    responses(
+        (status = 200, description = "List of Cosmos chains", body = Vec<CosmosChainResponse>),
+        (status = 401, description = "Unauthorized"),
+        (status = 500, description = "Internal server error")
+    ),
+    tag = "cosmos-chains"
+)]
+pub async fn list_cosmos_chains_handler(
+    State(db): State<DatabaseConnection>,
+    Extension(_token): Extension<axum_keycloak_auth::decode::KeycloakToken<String>>,
+) -> Result<Json<Vec<CosmosChainResponse>>, ApiError> {
+    let rows = cosmos_chains::Entity::find().all(&db).await?;
+    Ok(Json(rows.into_iter().map(|r| r.into()).collect()))
+}
+
+/// Get a Cosmos chain by ID
+#[utoipa::path(
+    get,
+    path = "/api/v1/cosmos-chains/{chain_id}",
+    params(
+        ("chain_id" = Uuid, Path, description = "Cosmos chain record ID")
+    ),
+    responses(
+        (status = 200, description = "Cosmos chain", body = CosmosChainResponse),
+        (status = 401, description = "Unauthorized"),
+        (status = 404, description = "Not found"),
+        (status = 500, description = "Internal server error")
+    ),
+    tag = "cosmos-chains"
+)]
+pub async fn get_cosmos_chain_handler(
+    State(db): State<DatabaseConnection>,
+    Extension(_token): Extension<axum_keycloak_auth::decode::KeycloakToken<String>>,
+    Path(chain_uuid): Path<Uuid>,
+) -> Result<Json<CosmosChainResponse>, ApiError> {
+    let row = cosmos_chains::Entity::find_by_id(chain_uuid)
+        .one(&db)
+        .await?
+        .ok_or(ApiError::NotFound)?;
+    Ok(Json(row.into()))
+}
+
+/// Create a new Cosmos chain
+///
+/// Adds a Cosmos-SDK chain for wallet accounts with `chain_family` "cosmos" to read.
+#[utoipa::path(
+    post,
+    path = "/api/v1/cosmos-chains",
+    request_body = CreateCosmosChainRequest,
+    responses(
+        (status = 201, description = "Chain created", body = CosmosChainResponse),
+        (status = 400, description = "Invalid request"),
+        (status = 401, description = "Unauthorized"),
+        (status = 409, description = "Chain ID already exists"),
+        (status = 500, description = "Internal server error")
+    ),
+    tag = "cosmos-chains"
+)]
+pub async fn create_cosmos_chain_handler(
+    State(db): State<DatabaseConnection>,
+    Extension(_token): Extension<axum_keycloak_auth::decode::KeycloakToken<String>>,
+    Json(req): Json<CreateCosmosChainRequest>,
+) -> Result<(StatusCode, Json<CosmosChainResponse>), ApiError> {
+    for (field, value) in [
+        ("chain_id", &req.chain_id),
+        ("name", &req.name),
+        ("lcd_url", &req.lcd_url),
+        ("bech32_prefix", &req.bech32_prefix),
+        ("native_denom", &req.native_denom),
+        ("native_symbol", &req.native_symbol),
+    ] {
+        if value.is_empty() {
+            return Err(ApiError::BadRequest(format!("{} is required", field)));
+        }
+    }
+    if !(0..=18).contains(&req.native_decimals) {
+        return Err(ApiError::BadRequest("native_decimals must be between 0 and 18".to_string()));
+    }
+
+    // Check for duplicate chain_id
+    let existing = cosmos_chains::Entity::find()
+        .filter(cosmos_chains::Column::ChainId.eq(&req.chain_id))
+        .one(&db)
+        .await?;
+
+    if existing.is_some() {
+        return Err(ApiError::Conflict(format!(
+            "Chain '{}' already exists",
+            req.chain_id
+        )));
+    }
+
+    let new_chain = cosmos_chains::ActiveModel {
+        id: Set(Uuid::new_v4()),
+        chain_id: Set(req.chain_id),
+        name: Set(req.name),
+        lcd_url: Set(req.lcd_url),
+        bech32_prefix: Set(req.bech32_prefix),
+        native_denom: Set(req.native_denom),
+        native_symbol: Set(req.native_symbol),
+        native_decimals: Set(req.native_decimals),
+        is_active: Set(req.is_active),
+        created_at: Set(Utc::now().into()),
+        updated_at: Set(Utc::now().into()),
+    };
+
+    let row = new_chain.insert(&db).await?;
+    Ok((StatusCode::CREATED, Json(row.into())))
+}
+
+/// Update a Cosmos chain
+///
+/// Update the name, LCD URL, or active status of an existing Cosmos chain.
+#[utoipa::path(
+    put,
+    path = "/api/v1/cosmos-chains/{chain_id}",
+    params(
+        ("chain_id" = Uuid, Path, description = "Cosmos chain record ID")
+    ),
+    request_body = UpdateCosmosChainRequest,
+    responses(
+        (status = 200, description = "Chain updated", body = CosmosChainResponse),
+        (status = 401, description = "Unauthorized"),
+        (status = 404, description = "Not found"),
+        (status = 500, description = "Internal server error")
+    ),
+    tag = "cosmos-chains"
+)]
+pub async fn update_cosmos_chain_handler(
+    State(db): State<DatabaseConnection>,
+    Extension(_token): Extension<axum_keycloak_auth::decode::KeycloakToken<String>>,
+    Path(chain_uuid): Path<Uuid>,
+    Json(req): Json<UpdateCosmosChainRequest>,
+) -> Result<Json<CosmosChainResponse>, ApiError> {
+    let row = cosmos_chains::Entity::find_by_id(chain_uuid)
+        .one(&db)
+        .await?
+        .ok_or(ApiError::NotFound)?;
+
+    let mut active: cosmos_chains::ActiveModel = row.into();
+
+    if let Some(name) = req.name {
+        active.name = Set(name);
+    }
+    if let Some(lcd_url) = req.lcd_url {
+        active.lcd_url = Set(lcd_url);
+    }
+    if let Some(is_active) = req.is_active {
+        active.is_active = Set(is_active);
+    }
+    active.updated_at = Set(Utc::now().into());
+
+    let updated = active.update(&db).await?;
+    Ok(Json(updated.into()))
+}
+
+/// Delete a Cosmos chain
+///
+/// Permanently removes a Cosmos chain configuration.
+#[utoipa::path(
+    delete,
+    path = "/api/v1/cosmos-chains/{chain_id}",
+    params(
+        ("chain_id" = Uuid, Path, description = "Cosmos chain record ID")
+    ),
+    responses(
+        (status = 204, description = "Chain deleted"),
+        (status = 401, description = "Unauthorized"),
+        (status = 404, description = "Not found"),
+        (status = 500, description = "Internal server error")
+    ),
+    tag = "cosmos-chains"
+)]
+pub async fn delete_cosmos_chain_handler(
+    State(db): State<DatabaseConnection>,
+    Extension(_token): Extension<axum_keycloak_auth::decode::KeycloakToken<String>>,
+    Path(chain_uuid): Path<Uuid>,
+) -> Result<StatusCode, ApiError> {
+    let row = cosmos_chains::Entity::find_by_id(chain_uuid)
+        .one(&db)
+        .await?
+        .ok_or(ApiError::NotFound)?;
+
+    let active: cosmos_chains::ActiveModel = row.into();
+    active.delete(&db).await?;
+
+    Ok(StatusCode::NO_CONTENT)
+}
+
+/// Create router for cosmos-chains endpoints
+pub fn create_router() -> Router<DatabaseConnection> {
+    Router::new()
+        .route(
+            "/api/v1/cosmos-chains",
+            get(list_cosmos_chains_handler).post(create_cosmos_chain_handler),
+        )
+        .route(
+            "/api/v1/cosmos-chains/{chain_id}",
+            get(get_cosmos_chain_handler)
+                .put(update_cosmos_chain_handler)
+                .delete(delete_cosmos_chain_handler),
+        )
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_create_request_defaults() {
+        let json = r#"{"chain_id":"juno","name":"Juno","lcd_url":"https://lcd.example.com","bech32_prefix":"juno","native_denom":"ujuno","native_symbol":"JUNO"}"#;
+        let req: CreateCosmosChainRequest = serde_json::from_str(json).unwrap();
+        assert!(req.is_active, "is_active should default to true");
+        assert_eq!(req.native_decimals, 6);
+    }
+}
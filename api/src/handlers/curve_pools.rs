@@ -0,0 +1,359 @@
+use alloy::primitives::Address;
+use axum::{
+    extract::{Path, Query, State},
+    http::StatusCode,
+    response::Json,
+    routing::get,
+    Extension, Router,
+};
+use chrono::Utc;
+use sea_orm::{
+    ActiveModelTrait, ColumnTrait, Condition, DatabaseConnection, EntityTrait, QueryFilter, QueryOrder, Set,
+};
+use serde::{Deserialize, Serialize};
+use utoipa::{IntoParams, ToSchema};
+use uuid::Uuid;
+
+use crate::entities::curve_pools;
+use super::error::ApiError;
+
+// === Request / Response DTOs ===
+
+#[derive(Debug, Serialize, Deserialize, ToSchema)]
+pub struct CurvePoolResponse {
+    pub id: Uuid,
+    /// EVM chain the pool is deployed on, e.g. "ethereum"
+    pub chain: String,
+    /// Pool name, e.g. "3pool"
+    pub name: String,
+    pub pool_address: String,
+    pub lp_token_address: String,
+    /// Convex reward contract holding staked LP tokens, if the pool is on Convex
+    pub convex_reward_pool: Option<String>,
+    pub is_active: bool,
+    pub created_at: String,
+    pub updated_at: String,
+}
+
+impl From<curve_pools::Model> for CurvePoolResponse {
+    fn from(m: curve_pools::Model) -> Self {
+        Self {
+            id: m.id,
+            chain: m.chain,
+            name: m.name,
+            pool_address: m.pool_address,
+            lp_token_address: m.lp_token_address,
+            convex_reward_pool: m.convex_reward_pool,
+            is_active: m.is_active,
+            created_at: m.created_at.to_rfc3339(),
+            updated_at: m.updated_at.to_rfc3339(),
+        }
+    }
+}
+
+#[derive(Debug, Serialize, Deserialize, ToSchema)]
+pub struct CreateCurvePoolRequest {
+    /// EVM chain name from `evm_chains`, e.g. "ethereum"
+    pub chain: String,
+    /// Pool name, e.g. "3pool"
+    pub name: String,
+    /// Pool contract address (the swap contract answering `coins`, `balances`, `get_virtual_price`)
+    pub pool_address: String,
+    /// LP token contract address
+    pub lp_token_address: String,
+    /// Convex reward contract for the pool's LP token
+    pub convex_reward_pool: Option<String>,
+    /// Whether wallet sync reads this pool (default: true)
+    #[serde(default = "default_true")]
+    pub is_active: bool,
+}
+
+#[derive(Debug, Serialize, Deserialize, ToSchema)]
+pub struct UpdateCurvePoolRequest {
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub name: Option<String>,
+    /// Convex reward contract; an empty string clears it
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub convex_reward_pool: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub is_active: Option<bool>,
+}
+
+#[derive(Debug, Deserialize, IntoParams)]
+pub struct ListCurvePoolsQuery {
+    /// Filter by chain, e.g. "ethereum"
+    pub chain: Option<String>,
+}
+
+fn default_true() -> bool {
+    true
+}
+
+// === Helpers ===
+
+/// Validate a contract address and return it lowercase, as stored
+fn parse_contract(field: &str, value: &str) -> Result<String, ApiError> {
+    value
+        .trim()
+        .parse::<Address>()
+        .map(|a| format!("{:?}", a))
+        .map_err(|_| ApiError::BadRequest(format!("{} must be a 0x-prefixed contract address", field)))
+}
+
+// === Handlers ===
+
+/// List Curve pools
+///
+/// Returns the Curve pools whose LP positions wallet sync resolves into the pool's coins.
+#[utoipa::path(
+    get,
+    path = "/api/v1/curve-pools",
+    params(ListCurvePoolsQuery),
+    responses(
+        (status = 200, description = "List of Curve pools", body = Vec<CurvePoolResponse>),
+        (status = 401, description = "Unauthorized"),
+        (status = 500, description = "Internal server error")
+    ),
+    tag = "curve-pools"
+)]
+pub async fn list_curve_pools_handler(
+    State(db): State<DatabaseConnection>,
+    Extension(_token): Extension<axum_keycloak_auth::decode::KeycloakToken<String>>,
+    Query(q): Query<ListCurvePoolsQuery>,
+) -> Result<Json<Vec<CurvePoolResponse>>, ApiError> {
+    let mut condition = Condition::all();
+    if let Some(chain) = q.chain {
+        condition = condition.add(curve_pools::Column::Chain.eq(chain.trim().to_lowercase()));
+    }
+
+    let rows = curve_pools::Entity::find()
+        .filter(condition)
+        .order_by_asc(curve_pools::Column::Chain)
+        .order_by_asc(curve_pools::Column::Name)
+        .all(&db)
+        .await?;
+
+    Ok(Json(rows.into_iter().map(|r| r.into()).collect()))
+}
+
+/// Get a Curve pool by ID
+#[utoipa::path(
+    get,
+    path = "/api/v1/curve-pools/{pool_id}",
+    params(
+        ("pool_id" = Uuid, Path, description = "Curve pool ID")
+    ),
+    responses(
+        (status = 200, description = "Curve pool", body = CurvePoolResponse),
+        (status = 401, description = "Unauthorized"),
+        (status = 404, description = "Not found"),
+        (status = 500, description = "Internal server error")
+    ),
+    tag = "curve-pools"
+)]
+pub async fn get_curve_pool_handler(
+    State(db): State<DatabaseConnection>,
+    Extension(_token): Extension<axum_keycloak_auth::decode::KeycloakToken<String>>,
+    Path(pool_id): Path<Uuid>,
+) -> Result<Json<CurvePoolResponse>, ApiError> {
+    let row = curve_pools::Entity::find_by_id(pool_id)
+        .one(&db)
+        .await?
+        .ok_or(ApiError::NotFound)?;
+
+    Ok(Json(row.into()))
+}
+
+/// Add a Curve pool
+///
+/// Accounts with `curve` in their `defi_protocols` setting report positions in the pool's LP
+/// token, held directly or staked in the Convex reward contract, as the pool's coins.
+#[utoipa::path(
+    post,
+    path = "/api/v1/curve-pools",
+    request_body = CreateCurvePoolRequest,
+    responses(
+        (status = 201, description = "Pool added", body = CurvePoolResponse),
+        (status = 400, description = "Invalid request"),
+        (status = 401, description = "Unauthorized"),
+        (status = 409, description = "LP token already configured on this chain"),
+        (status = 500, description = "Internal server error")
+    ),
+    tag = "curve-pools"
+)]
+pub async fn create_curve_pool_handler(
+    State(db): State<DatabaseConnection>,
+    Extension(_token): Extension<axum_keycloak_auth::decode::KeycloakToken<String>>,
+    Json(req): Json<CreateCurvePoolRequest>,
+) -> Result<(StatusCode, Json<CurvePoolResponse>), ApiError> {
+    let chain = req.chain.trim().to_lowercase();
+    let name = req.name.trim().to_string();
+    if chain.is_empty() {
+        return Err(ApiError::BadRequest("chain is required".to_string()));
+    }
+    if name.is_empty() {
+        return Err(ApiError::BadRequest("name is required".to_string()));
+    }
+    let pool_address = parse_contract("pool_address", &req.pool_address)?;
+    let lp_token_address = parse_contract("lp_token_address", &req.lp_token_address)?;
+    let convex_reward_pool = match req.convex_reward_pool.as_deref().map(str::trim) {
+        Some(address) if !address.is_empty() => Some(parse_contract("convex_reward_pool", address)?),
+        _ => None,
+    };
+
+    // Check for duplicate (chain, lp_token_address)
+    let existing = curve_pools::Entity::find()
+        .filter(curve_pools::Column::Chain.eq(&chain))
+        .filter(curve_pools::Column::LpTokenAddress.eq(&lp_token_address))
+        .one(&db)
+        .await?;
+
+    if existing.is_some() {
+        return Err(ApiError::Conflict(format!(
+            "LP token {} is already configured on {}",
+            lp_token_address, chain
+        )));
+    }
+
+    let new_pool = curve_pools::ActiveModel {
+        id: Set(Uuid::new_v4()),
+        chain: Set(chain),
+        name: Set(name),
+        pool_address: Set(pool_address),
+        lp_token_address: Set(lp_token_address),
+        convex_reward_pool: Set(convex_reward_pool),
+        is_active: Set(req.is_active),
+        created_at: Set(Utc::now().into()),
+        updated_at: Set(Utc::now().into()),
+    };
+
+    let row = new_pool.insert(&db).await?;
+
+    Ok((StatusCode::CREATED, Json(row.into())))
+}
+
+/// Update a Curve pool
+///
+/// Rename a pool, change its Convex reward contract, or activate/deactivate it.
+#[utoipa::path(
+    put,
+    path = "/api/v1/curve-pools/{pool_id}",
+    params(
+        ("pool_id" = Uuid, Path, description = "Curve pool ID")
+    ),
+    request_body = UpdateCurvePoolRequest,
+    responses(
+        (status = 200, description = "Pool updated", body = CurvePoolResponse),
+        (status = 400, description = "Invalid request"),
+        (status = 401, description = "Unauthorized"),
+        (status = 404, description = "Not found"),
+        (status = 500, description = "Internal server error")
+    ),
+    tag = "curve-pools"
+)]
+pub async fn update_curve_pool_handler(
+    State(db): State<DatabaseConnection>,
+    Extension(_token): Extension<axum_keycloak_auth::decode::KeycloakToken<String>>,
+    Path(pool_id): Path<Uuid>,
+    Json(req): Json<UpdateCurvePoolRequest>,
+) -> Result<Json<CurvePoolResponse>, ApiError> {
+    let row = curve_pools::Entity::find_by_id(pool_id)
+        .one(&db)
+        .await?
+        .ok_or(ApiError::NotFound)?;
+
+    let mut active: curve_pools::ActiveModel = row.into();
+
+    if let Some(name) = req.name {
+        let name = name.trim().to_string();
+        if name.is_empty() {
+            return Err(ApiError::BadRequest("name must not be empty".to_string()));
+        }
+        active.name = Set(name);
+    }
+    if let Some(convex_reward_pool) = req.convex_reward_pool {
+        let address = convex_reward_pool.trim();
+        active.convex_reward_pool = Set(if address.is_empty() {
+            None
+        } else {
+            Some(parse_contract("convex_reward_pool", address)?)
+        });
+    }
+    if let Some(is_active) = req.is_active {
+        active.is_active = Set(is_active);
+    }
+    active.updated_at = Set(Utc::now().into());
+
+    let updated = active.update(&db).await?;
+
+    Ok(Json(updated.into()))
+}
+
+/// Delete a Curve pool
+///
+/// Removes a pool; positions in its LP token are no longer resolved from the next sync on.
+#[utoipa::path(
+    delete,
+    path = "/api/v1/curve-pools/{pool_id}",
+    params(
+        ("pool_id" = Uuid, Path, description = "Curve pool ID")
+    ),
+    responses(
+        (status = 204, description = "Pool deleted"),
+        (status = 401, description = "Unauthorized"),
+        (status = 404, description = "Not found"),
+        (status = 500, description = "Internal server error")
+    ),
+    tag = "curve-pools"
+)]
+pub async fn delete_curve_pool_handler(
+    State(db): State<DatabaseConnection>,
+    Extension(_token): Extension<axum_keycloak_auth::decode::KeycloakToken<String>>,
+    Path(pool_id): Path<Uuid>,
+) -> Result<StatusCode, ApiError> {
+    let row = curve_pools::Entity::find_by_id(pool_id)
+        .one(&db)
+        .await?
+        .ok_or(ApiError::NotFound)?;
+
+    let active: curve_pools::ActiveModel = row.into();
+    active.delete(&db).await?;
+
+    Ok(StatusCode::NO_CONTENT)
+}
+
+/// Create router for curve-pools endpoints
+pub fn create_router() -> Router<DatabaseConnection> {
+    Router::new()
+        .route(
+            "/api/v1/curve-pools",
+            get(list_curve_pools_handler).post(create_curve_pool_handler),
+        )
+        .route(
+            "/api/v1/curve-pools/{pool_id}",
+            get(get_curve_pool_handler)
+                .put(update_curve_pool_handler)
+                .delete(delete_curve_pool_handler),
+        )
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_contract_lowercases() {
+        assert_eq!(
+            parse_contract("pool_address", " 0xbEbc44782C7dB0a1A60Cb6fe97d0b483032FF1C7 ").unwrap(),
+            "0xbebc44782c7db0a1a60cb6fe97d0b483032ff1c7"
+        );
+        assert!(parse_contract("pool_address", "3pool").is_err());
+
+        let req: CreateCurvePoolRequest = serde_json::from_str(
+            r#"{"chain":"ethereum","name":"3pool","pool_address":"0x1","lp_token_address":"0x2"}"#,
+        )
+        .unwrap();
+        assert!(req.is_active);
+        assert!(req.convex_reward_pool.is_none());
+    }
+}
@@ -0,0 +1,392 @@
+use alloy::primitives::Address;
+use axum::{
+    extract::{Path, State},
+    http::StatusCode,
+    response::Json,
+    routing::get,
+    Extension, Router,
+};
+use chrono::Utc;
+use rust_decimal::Decimal;
+use sea_orm::{ActiveModelTrait, ColumnTrait, DatabaseConnection, EntityTrait, QueryFilter, QueryOrder, Set};
+use serde::{Deserialize, Serialize};
+use utoipa::ToSchema;
+use uuid::Uuid;
+
+use crate::entities::derivative_assets;
+use crate::helpers::derivative_assets::RATE_METHODS;
+use super::error::ApiError;
+
+// === Request / Response DTOs ===
+
+#[derive(Debug, Serialize, Deserialize, ToSchema)]
+pub struct DerivativeAssetResponse {
+    pub id: Uuid,
+    /// Wrapped or liquid staking token symbol, e.g. "WSTETH"
+    pub symbol: String,
+    /// Underlying asset symbol, e.g. "ETH"
+    pub underlying_symbol: String,
+    /// Underlying units per token (decimal string)
+    pub exchange_rate: String,
+    /// Ethereum contract the rate is read from
+    pub rate_contract: Option<String>,
+    /// How the rate is read, e.g. "steth_per_token"
+    pub rate_method: Option<String>,
+    /// When the rate was last read from the contract
+    pub rate_updated_at: Option<String>,
+    pub is_active: bool,
+    pub created_at: String,
+    pub updated_at: String,
+}
+
+impl From<derivative_assets::Model> for DerivativeAssetResponse {
+    fn from(m: derivative_assets::Model) -> Self {
+        Self {
+            id: m.id,
+            symbol: m.symbol,
+            underlying_symbol: m.underlying_symbol,
+            exchange_rate: m.exchange_rate.normalize().to_string(),
+            rate_contract: m.rate_contract,
+            rate_method: m.rate_method,
+            rate_updated_at: m.rate_updated_at.map(|t| t.to_rfc3339()),
+            is_active: m.is_active,
+            created_at: m.created_at.to_rfc3339(),
+            updated_at: m.updated_at.to_rfc3339(),
+        }
+    }
+}
+
+#[derive(Debug, Serialize, Deserialize, ToSchema)]
+pub struct CreateDerivativeAssetRequest {
+    /// Token symbol, matched case-insensitively against holdings
+    pub symbol: String,
+    /// Underlying asset symbol
+    pub underlying_symbol: String,
+    /// Underlying units per token (default: 1)
+    #[schema(value_type = Option<String>)]
+    pub exchange_rate: Option<Decimal>,
+    /// Ethereum contract to read the rate from; requires `rate_method`
+    pub rate_contract: Option<String>,
+    /// One of "steth_per_token", "get_exchange_rate", "exchange_rate"
+    pub rate_method: Option<String>,
+    /// Whether allocation construction applies the mapping (default: true)
+    #[serde(default = "default_true")]
+    pub is_active: bool,
+}
+
+#[derive(Debug, Serialize, Deserialize, ToSchema)]
+pub struct UpdateDerivativeAssetRequest {
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub underlying_symbol: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    #[schema(value_type = Option<String>)]
+    pub exchange_rate: Option<Decimal>,
+    /// Rate contract; an empty string clears it together with `rate_method`
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub rate_contract: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub rate_method: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub is_active: Option<bool>,
+}
+
+fn default_true() -> bool {
+    true
+}
+
+// === Helpers ===
+
+/// Validate a symbol and return it uppercase, as stored
+fn parse_symbol(field: &str, value: &str) -> Result<String, ApiError> {
+    let symbol = value.trim().to_uppercase();
+    if symbol.is_empty() {
+        return Err(ApiError::BadRequest(format!("{} is required", field)));
+    }
+    Ok(symbol)
+}
+
+fn check_exchange_rate(rate: Decimal) -> Result<Decimal, ApiError> {
+    if rate <= Decimal::ZERO {
+        return Err(ApiError::BadRequest("exchange_rate must be positive".to_string()));
+    }
+    Ok(rate)
+}
+
+/// Validate the rate source; both fields are set together or not at all.
+/// Returns the lowercase contract and the method.
+fn parse_rate_source(
+    contract: Option<&str>,
+    method: Option<&str>,
+) -> Result<(Option<String>, Option<String>), ApiError> {
+    let contract = contract.map(str::trim).filter(|c| !c.is_empty());
+    let method = method.map(|m| m.trim().to_lowercase()).filter(|m| !m.is_empty());
+    match (contract, method) {
+        (None, None) => Ok((None, None)),
+        (Some(contract), Some(method)) => {
+            if !RATE_METHODS.contains(&method.as_str()) {
+                return Err(ApiError::BadRequest(format!(
+                    "Unknown rate_method {}, expected one of: {}",
+                    method,
+                    RATE_METHODS.join(", ")
+                )));
+            }
+            let contract = contract
+                .parse::<Address>()
+                .map_err(|_| ApiError::BadRequest("rate_contract must be a 0x-prefixed contract address".to_string()))?;
+            Ok((Some(format!("{:?}", contract)), Some(method)))
+        }
+        _ => Err(ApiError::BadRequest(
+            "rate_contract and rate_method must be set together".to_string(),
+        )),
+    }
+}
+
+// === Handlers ===
+
+/// List derivative assets
+///
+/// Returns the wrapped and liquid staking tokens that allocation construction counts towards
+/// their underlying asset.
+#[utoipa::path(
+    get,
+    path = "/api/v1/derivative-assets",
+    responses(
+        (status = 200, description = "List of derivative assets", body = Vec<DerivativeAssetResponse>),
+        (status = 401, description = "Unauthorized"),
+        (status = 500, description = "Internal server error")
+    ),
+    tag = "derivative-assets"
+)]
+pub async fn list_derivative_assets_handler(
+    State(db): State<DatabaseConnection>,
+    Extension(_token): Extension<axum_keycloak_auth::decode::KeycloakToken<String>>,
+) -> Result<Json<Vec<DerivativeAssetResponse>>, ApiError> {
+    let rows = derivative_assets::Entity::find()
+        .order_by_asc(derivative_assets::Column::UnderlyingSymbol)
+        .order_by_asc(derivative_assets::Column::Symbol)
+        .all(&db)
+        .await?;
+
+    Ok(Json(rows.into_iter().map(|r| r.into()).collect()))
+}
+
+/// Get a derivative asset by ID
+#[utoipa::path(
+    get,
+    path = "/api/v1/derivative-assets/{derivative_id}",
+    params(
+        ("derivative_id" = Uuid, Path, description = "Derivative asset ID")
+    ),
+    responses(
+        (status = 200, description = "Derivative asset", body = DerivativeAssetResponse),
+        (status = 401, description = "Unauthorized"),
+        (status = 404, description = "Not found"),
+        (status = 500, description = "Internal server error")
+    ),
+    tag = "derivative-assets"
+)]
+pub async fn get_derivative_asset_handler(
+    State(db): State<DatabaseConnection>,
+    Extension(_token): Extension<axum_keycloak_auth::decode::KeycloakToken<String>>,
+    Path(derivative_id): Path<Uuid>,
+) -> Result<Json<DerivativeAssetResponse>, ApiError> {
+    let row = derivative_assets::Entity::find_by_id(derivative_id)
+        .one(&db)
+        .await?
+        .ok_or(ApiError::NotFound)?;
+
+    Ok(Json(row.into()))
+}
+
+/// Add a derivative asset
+///
+/// Maps a wrapped or liquid staking token to its underlying asset. Set `rate_contract` and
+/// `rate_method` to read the exchange rate on Ethereum instead of using a fixed rate.
+#[utoipa::path(
+    post,
+    path = "/api/v1/derivative-assets",
+    request_body = CreateDerivativeAssetRequest,
+    responses(
+        (status = 201, description = "Derivative asset added", body = DerivativeAssetResponse),
+        (status = 400, description = "Invalid request"),
+        (status = 401, description = "Unauthorized"),
+        (status = 409, description = "Symbol already mapped"),
+        (status = 500, description = "Internal server error")
+    ),
+    tag = "derivative-assets"
+)]
+pub async fn create_derivative_asset_handler(
+    State(db): State<DatabaseConnection>,
+    Extension(_token): Extension<axum_keycloak_auth::decode::KeycloakToken<String>>,
+    Json(req): Json<CreateDerivativeAssetRequest>,
+) -> Result<(StatusCode, Json<DerivativeAssetResponse>), ApiError> {
+    let symbol = parse_symbol("symbol", &req.symbol)?;
+    let underlying_symbol = parse_symbol("underlying_symbol", &req.underlying_symbol)?;
+    if symbol == underlying_symbol {
+        return Err(ApiError::BadRequest("symbol and underlying_symbol must differ".to_string()));
+    }
+    let exchange_rate = check_exchange_rate(req.exchange_rate.unwrap_or(Decimal::ONE))?;
+    let (rate_contract, rate_method) = parse_rate_source(req.rate_contract.as_deref(), req.rate_method.as_deref())?;
+
+    let existing = derivative_assets::Entity::find()
+        .filter(derivative_assets::Column::Symbol.eq(&symbol))
+        .one(&db)
+        .await?;
+
+    if existing.is_some() {
+        return Err(ApiError::Conflict(format!("{} is already mapped", symbol)));
+    }
+
+    let new_row = derivative_assets::ActiveModel {
+        id: Set(Uuid::new_v4()),
+        symbol: Set(symbol),
+        underlying_symbol: Set(underlying_symbol),
+        exchange_rate: Set(exchange_rate),
+        rate_contract: Set(rate_contract),
+        rate_method: Set(rate_method),
+        rate_updated_at: Set(None),
+        is_active: Set(req.is_active),
+        created_at: Set(Utc::now().into()),
+        updated_at: Set(Utc::now().into()),
+    };
+
+    let row = new_row.insert(&db).await?;
+
+    Ok((StatusCode::CREATED, Json(row.into())))
+}
+
+/// Update a derivative asset
+///
+/// Change the underlying asset, the fixed rate or the rate source, or activate/deactivate the
+/// mapping. Changing the rate source makes the next construction read the rate again.
+#[utoipa::path(
+    put,
+    path = "/api/v1/derivative-assets/{derivative_id}",
+    params(
+        ("derivative_id" = Uuid, Path, description = "Derivative asset ID")
+    ),
+    request_body = UpdateDerivativeAssetRequest,
+    responses(
+        (status = 200, description = "Derivative asset updated", body = DerivativeAssetResponse),
+        (status = 400, description = "Invalid request"),
+        (status = 401, description = "Unauthorized"),
+        (status = 404, description = "Not found"),
+        (status = 500, description = "Internal server error")
+    ),
+    tag = "derivative-assets"
+)]
+pub async fn update_derivative_asset_handler(
+    State(db): State<DatabaseConnection>,
+    Extension(_token): Extension<axum_keycloak_auth::decode::KeycloakToken<String>>,
+    Path(derivative_id): Path<Uuid>,
+    Json(req): Json<UpdateDerivativeAssetRequest>,
+) -> Result<Json<DerivativeAssetResponse>, ApiError> {
+    let row = derivative_assets::Entity::find_by_id(derivative_id)
+        .one(&db)
+        .await?
+        .ok_or(ApiError::NotFound)?;
+
+    if let Some(underlying) = &req.underlying_symbol {
+        if parse_symbol("underlying_symbol", underlying)? == row.symbol {
+            return Err(ApiError::BadRequest("symbol and underlying_symbol must differ".to_string()));
+        }
+    }
+    let rate_source = if req.rate_contract.is_some() || req.rate_method.is_some() {
+        Some(parse_rate_source(
+            req.rate_contract.as_deref().or(row.rate_contract.as_deref()),
+            req.rate_method.as_deref().or(row.rate_method.as_deref()),
+        )?)
+    } else {
+        None
+    };
+
+    let mut active: derivative_assets::ActiveModel = row.into();
+
+    if let Some(underlying) = req.underlying_symbol {
+        active.underlying_symbol = Set(parse_symbol("underlying_symbol", &underlying)?);
+    }
+    if let Some(rate) = req.exchange_rate {
+        active.exchange_rate = Set(check_exchange_rate(rate)?);
+    }
+    if let Some((contract, method)) = rate_source {
+        active.rate_contract = Set(contract);
+        active.rate_method = Set(method);
+        active.rate_updated_at = Set(None);
+    }
+    if let Some(is_active) = req.is_active {
+        active.is_active = Set(is_active);
+    }
+    active.updated_at = Set(Utc::now().into());
+
+    let updated = active.update(&db).await?;
+
+    Ok(Json(updated.into()))
+}
+
+/// Delete a derivative asset
+///
+/// Removes a mapping; the token is reported as its own asset from the next construction on.
+#[utoipa::path(
+    delete,
+    path = "/api/v1/derivative-assets/{derivative_id}",
+    params(
+        ("derivative_id" = Uuid, Path, description = "Derivative asset ID")
+    ),
+    responses(
+        (status = 204, description = "Derivative asset deleted"),
+        (status = 401, description = "Unauthorized"),
+        (status = 404, description = "Not found"),
+        (status = 500, description = "Internal server error")
+    ),
+    tag = "derivative-assets"
+)]
+pub async fn delete_derivative_asset_handler(
+    State(db): State<DatabaseConnection>,
+    Extension(_token): Extension<axum_keycloak_auth::decode::KeycloakToken<String>>,
+    Path(derivative_id): Path<Uuid>,
+) -> Result<StatusCode, ApiError> {
+    let row = derivative_assets::Entity::find_by_id(derivative_id)
+        .one(&db)
+        .await?
+        .ok_or(ApiError::NotFound)?;
+
+    let active: derivative_assets::ActiveModel = row.into();
+    active.delete(&db).await?;
+
+    Ok(StatusCode::NO_CONTENT)
+}
+
+/// Create router for derivative-assets endpoints
+pub fn create_router() -> Router<DatabaseConnection> {
+    Router::new()
+        .route(
+            "/api/v1/derivative-assets",
+            get(list_derivative_assets_handler).post(create_derivative_asset_handler),
+        )
+        .route(
+            "/api/v1/derivative-assets/{derivative_id}",
+            get(get_derivative_asset_handler)
+                .put(update_derivative_asset_handler)
+                .delete(delete_derivative_asset_handler),
+        )
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_rate_source() {
+        assert_eq!(parse_rate_source(None, Some(" ")).unwrap(), (None, None));
+        let (contract, method) = parse_rate_source(
+            Some("0x7f39C581F595B53c5cb19bD0b3f8dA6c935E2Ca0"),
+            Some("STETH_PER_TOKEN"),
+        )
+        .unwrap();
+        assert_eq!(contract.as_deref(), Some("0x7f39c581f595b53c5cb19bd0b3f8da6c935e2ca0"));
+        assert_eq!(method.as_deref(), Some("steth_per_token"));
+        assert!(parse_rate_source(Some("0x7f39C581F595B53c5cb19bD0b3f8dA6c935E2Ca0"), None).is_err());
+        assert!(parse_rate_source(Some("0x7f39C581F595B53c5cb19bD0b3f8dA6c935E2Ca0"), Some("price")).is_err());
+        assert!(check_exchange_rate(Decimal::ZERO).is_err());
+    }
+}
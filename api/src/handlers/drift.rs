@@ -0,0 +1,103 @@
+use axum::{
+    extract::{Extension, Path, State},
+    response::Json,
+    routing::get,
+    Router,
+};
+use axum_keycloak_auth::decode::KeycloakToken;
+use rust_decimal::prelude::ToPrimitive;
+use sea_orm::{ColumnTrait, DatabaseConnection, EntityTrait, QueryFilter};
+use serde::{Deserialize, Serialize};
+use utoipa::ToSchema;
+use uuid::Uuid;
+
+use crate::domain::{drift_from_targets, target_weights, AllocationItem, PortfolioGuardrails};
+use crate::entities::portfolio_allocations;
+use crate::helpers::auth::{check_portfolio_ownership, get_or_create_user};
+use super::error::ApiError;
+
+// === Request/Response DTOs ===
+
+pub use crate::domain::AssetDrift;
+
+#[derive(Debug, Serialize, Deserialize, ToSchema)]
+pub struct DriftResponse {
+    pub portfolio_id: Uuid,
+    /// Total portfolio value in USD of the allocation
+    pub total_value_usd: f64,
+    /// The portfolio's `drift_band` guardrail in percentage points; absent when not set
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub drift_band: Option<f64>,
+    /// Largest absolute drift, in percentage points
+    pub max_drift: f64,
+    /// Number of assets whose drift exceeds `drift_band`
+    pub assets_outside_band: usize,
+    /// Drift per asset, largest absolute drift first
+    pub assets: Vec<AssetDrift>,
+    /// Timestamp when the allocation was computed
+    pub as_of: String,
+}
+
+// === API Handlers ===
+
+/// Get a portfolio's drift from its target allocation
+///
+/// Compares the weights of the latest allocation with `target_allocation`, in percentage
+/// points per asset (current minus target), and flags the assets outside the portfolio's
+/// `drift_band` guardrail. Held assets without a target count as a target of 0.
+#[utoipa::path(
+    get,
+    path = "/api/v1/portfolios/{portfolio_id}/drift",
+    params(
+        ("portfolio_id" = Uuid, Path, description = "Portfolio ID")
+    ),
+    responses(
+        (status = 200, description = "Drift from the target allocation", body = DriftResponse),
+        (status = 400, description = "Portfolio has no target allocation"),
+        (status = 401, description = "Unauthorized"),
+        (status = 403, description = "Forbidden - portfolio does not belong to user"),
+        (status = 404, description = "Portfolio or allocation not found"),
+        (status = 500, description = "Internal server error")
+    ),
+    tag = "portfolios"
+)]
+pub async fn get_portfolio_drift_handler(
+    State(db): State<DatabaseConnection>,
+    Extension(token): Extension<KeycloakToken<String>>,
+    Path(portfolio_id): Path<Uuid>,
+) -> Result<Json<DriftResponse>, ApiError> {
+    let user = get_or_create_user(&db, &token).await?;
+    let portfolio = check_portfolio_ownership(&db, portfolio_id, user.id).await?;
+
+    let targets = target_weights(portfolio.target_allocation.as_ref());
+    if targets.is_empty() {
+        return Err(ApiError::BadRequest("Portfolio has no target_allocation".to_string()));
+    }
+    let guardrails = PortfolioGuardrails::from_json(portfolio.guardrails.as_ref());
+
+    let allocation = portfolio_allocations::Entity::find()
+        .filter(portfolio_allocations::Column::PortfolioId.eq(portfolio_id))
+        .one(&db)
+        .await?
+        .ok_or(ApiError::NotFound)?;
+    let holdings: Vec<AllocationItem> = serde_json::from_value(allocation.holdings)
+        .map_err(|e| ApiError::InternalServerError(format!("Failed to deserialize allocation: {}", e)))?;
+
+    let assets = drift_from_targets(&holdings, &targets, guardrails.drift_band);
+
+    Ok(Json(DriftResponse {
+        portfolio_id,
+        total_value_usd: allocation.total_value_usd.to_f64().unwrap_or(0.0),
+        drift_band: guardrails.drift_band,
+        max_drift: assets.iter().map(|a| a.drift.abs()).fold(0.0, f64::max),
+        assets_outside_band: assets.iter().filter(|a| a.outside_band).count(),
+        assets,
+        as_of: allocation.as_of.to_rfc3339(),
+    }))
+}
+
+// === Router setup ===
+
+pub fn create_router() -> Router<DatabaseConnection> {
+    Router::new().route("/api/v1/portfolios/{portfolio_id}/drift", get(get_portfolio_drift_handler))
+}
@@ -13,6 +13,8 @@ use serde::{Deserialize, Serialize};
 use utoipa::ToSchema;
 use uuid::Uuid;
 
+use crate::connectors::explorer::{BALANCE_PROVIDERS, BALANCE_PROVIDER_BLOCKSCOUT};
+use crate::connectors::nft::NFT_PROVIDERS;
 use crate::entities::evm_chains;
 use super::error::ApiError;
 
@@ -31,6 +33,20 @@ pub struct EvmChainResponse {
     pub native_symbol: String,
     /// Whether this chain is active for account sync
     pub is_active: bool,
+    /// NFT API used to discover wallet NFTs: "alchemy" or "reservoir"
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub nft_provider: Option<String>,
+    /// Base URL of the NFT API
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub nft_api_url: Option<String>,
+    /// Explorer API used for balances instead of RPC calls: "etherscan" or "blockscout"
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub balance_provider: Option<String>,
+    /// Base URL of the explorer API
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub explorer_api_url: Option<String>,
+    /// Whether an explorer API key is stored; the key itself is never returned
+    pub explorer_api_key_set: bool,
     pub created_at: String,
     pub updated_at: String,
 }
@@ -44,6 +60,11 @@ impl From<evm_chains::Model> for EvmChainResponse {
             rpc_url: m.rpc_url,
             native_symbol: m.native_symbol,
             is_active: m.is_active,
+            nft_provider: m.nft_provider,
+            nft_api_url: m.nft_api_url,
+            balance_provider: m.balance_provider,
+            explorer_api_url: m.explorer_api_url,
+            explorer_api_key_set: m.explorer_api_key.is_some(),
             created_at: m.created_at.to_rfc3339(),
             updated_at: m.updated_at.to_rfc3339(),
         }
@@ -63,6 +84,22 @@ pub struct CreateEvmChainRequest {
     /// Whether to include this chain during account sync (default: true)
     #[serde(default = "default_true")]
     pub is_active: bool,
+    /// NFT API used to discover wallet NFTs: "alchemy" or "reservoir" (default: none)
+    #[serde(default)]
+    pub nft_provider: Option<String>,
+    /// Base URL of the NFT API, e.g. "https://eth-mainnet.g.alchemy.com/nft/v3/<key>"
+    #[serde(default)]
+    pub nft_api_url: Option<String>,
+    /// Explorer API used for balances: "etherscan" or "blockscout" (default: none, RPC only)
+    #[serde(default)]
+    pub balance_provider: Option<String>,
+    /// Explorer API base URL; required for Blockscout, e.g. "https://eth.blockscout.com/api".
+    /// Etherscan defaults to its v2 multichain API.
+    #[serde(default)]
+    pub explorer_api_url: Option<String>,
+    /// Explorer API key; Etherscan falls back to `ETHERSCAN_API_KEY`
+    #[serde(default)]
+    pub explorer_api_key: Option<String>,
 }
 
 #[derive(Debug, Serialize, Deserialize, ToSchema)]
@@ -79,12 +116,69 @@ pub struct UpdateEvmChainRequest {
     /// Whether this chain is active for account sync
     #[serde(skip_serializing_if = "Option::is_none")]
     pub is_active: Option<bool>,
+    /// NFT API provider; an empty string turns NFT discovery off
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub nft_provider: Option<String>,
+    /// NFT API base URL; an empty string clears it
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub nft_api_url: Option<String>,
+    /// Explorer balance provider; an empty string switches back to RPC
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub balance_provider: Option<String>,
+    /// Explorer API base URL; an empty string clears it
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub explorer_api_url: Option<String>,
+    /// Explorer API key; an empty string clears it
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub explorer_api_key: Option<String>,
 }
 
 fn default_true() -> bool {
     true
 }
 
+/// Normalize an NFT provider from a request: blank clears it, unknown providers are rejected
+fn parse_nft_provider(provider: Option<String>) -> Result<Option<String>, ApiError> {
+    let Some(provider) = provider.map(|p| p.trim().to_lowercase()).filter(|p| !p.is_empty()) else {
+        return Ok(None);
+    };
+    if !NFT_PROVIDERS.contains(&provider.as_str()) {
+        return Err(ApiError::BadRequest(format!(
+            "nft_provider must be one of: {}",
+            NFT_PROVIDERS.join(", ")
+        )));
+    }
+    Ok(Some(provider))
+}
+
+/// Normalize a balance provider from a request: blank clears it, unknown providers are rejected
+fn parse_balance_provider(provider: Option<String>) -> Result<Option<String>, ApiError> {
+    let Some(provider) = provider.map(|p| p.trim().to_lowercase()).filter(|p| !p.is_empty()) else {
+        return Ok(None);
+    };
+    if !BALANCE_PROVIDERS.contains(&provider.as_str()) {
+        return Err(ApiError::BadRequest(format!(
+            "balance_provider must be one of: {}",
+            BALANCE_PROVIDERS.join(", ")
+        )));
+    }
+    Ok(Some(provider))
+}
+
+/// Blockscout has no default endpoint, so it needs an explorer URL
+fn check_explorer_config(balance_provider: Option<&str>, explorer_api_url: Option<&str>) -> Result<(), ApiError> {
+    if balance_provider == Some(BALANCE_PROVIDER_BLOCKSCOUT) && explorer_api_url.is_none() {
+        return Err(ApiError::BadRequest(
+            "explorer_api_url is required for the blockscout balance provider".to_string(),
+        ));
+    }
+    Ok(())
+}
+
+fn non_blank(value: Option<String>) -> Option<String> {
+    value.map(|v| v.trim().to_string()).filter(|v| !v.is_empty())
+}
+
 // === Handlers ===
 
 /// List EVM chains
@@ -179,6 +273,11 @@ pub async fn create_evm_chain_handler(
         )));
     }
 
+    let nft_provider = parse_nft_provider(req.nft_provider)?;
+    let balance_provider = parse_balance_provider(req.balance_provider)?;
+    let explorer_api_url = non_blank(req.explorer_api_url);
+    check_explorer_config(balance_provider.as_deref(), explorer_api_url.as_deref())?;
+
     let new_chain = evm_chains::ActiveModel {
         id: Set(Uuid::new_v4()),
         chain_id: Set(req.chain_id),
@@ -186,6 +285,11 @@ pub async fn create_evm_chain_handler(
         rpc_url: Set(req.rpc_url),
         native_symbol: Set(req.native_symbol),
         is_active: Set(req.is_active),
+        nft_provider: Set(nft_provider),
+        nft_api_url: Set(req.nft_api_url.filter(|u| !u.trim().is_empty())),
+        balance_provider: Set(balance_provider),
+        explorer_api_url: Set(explorer_api_url),
+        explorer_api_key: Set(non_blank(req.explorer_api_key)),
         created_at: Set(Utc::now().into()),
         updated_at: Set(Utc::now().into()),
     };
@@ -196,7 +300,8 @@ pub async fn create_evm_chain_handler(
 
 /// Update an EVM chain
 ///
-/// Update the name, RPC URL, or active status of an existing EVM chain.
+/// Update the name, RPC URL, NFT API, explorer balance provider, or active status of an
+/// existing EVM chain.
 #[utoipa::path(
     put,
     path = "/api/v1/evm-chains/{chain_id}",
@@ -206,6 +311,7 @@ pub async fn create_evm_chain_handler(
     request_body = UpdateEvmChainRequest,
     responses(
         (status = 200, description = "Chain updated", body = EvmChainResponse),
+        (status = 400, description = "Invalid provider configuration"),
         (status = 401, description = "Unauthorized"),
         (status = 404, description = "Not found"),
         (status = 500, description = "Internal server error")
@@ -237,6 +343,25 @@ pub async fn update_evm_chain_handler(
     if let Some(is_active) = req.is_active {
         active.is_active = Set(is_active);
     }
+    if req.nft_provider.is_some() {
+        active.nft_provider = Set(parse_nft_provider(req.nft_provider)?);
+    }
+    if let Some(nft_api_url) = req.nft_api_url {
+        active.nft_api_url = Set(Some(nft_api_url).filter(|u| !u.trim().is_empty()));
+    }
+    if req.balance_provider.is_some() {
+        active.balance_provider = Set(parse_balance_provider(req.balance_provider)?);
+    }
+    if req.explorer_api_url.is_some() {
+        active.explorer_api_url = Set(non_blank(req.explorer_api_url));
+    }
+    if req.explorer_api_key.is_some() {
+        active.explorer_api_key = Set(non_blank(req.explorer_api_key));
+    }
+    check_explorer_config(
+        active.balance_provider.as_ref().as_deref(),
+        active.explorer_api_url.as_ref().as_deref(),
+    )?;
     active.updated_at = Set(Utc::now().into());
 
     let updated = active.update(&db).await?;
@@ -319,4 +444,20 @@ mod tests {
         assert!(req.name.is_none());
         assert!(req.is_active.is_none());
     }
+
+    #[test]
+    fn test_parse_nft_provider() {
+        assert_eq!(parse_nft_provider(Some(" Alchemy ".to_string())).unwrap(), Some("alchemy".to_string()));
+        assert_eq!(parse_nft_provider(Some(String::new())).unwrap(), None);
+        assert!(parse_nft_provider(Some("opensea".to_string())).is_err());
+    }
+
+    #[test]
+    fn test_parse_balance_provider() {
+        assert_eq!(parse_balance_provider(Some(" Etherscan ".to_string())).unwrap(), Some("etherscan".to_string()));
+        assert_eq!(parse_balance_provider(Some(" ".to_string())).unwrap(), None);
+        assert!(parse_balance_provider(Some("covalent".to_string())).is_err());
+        assert!(check_explorer_config(Some("blockscout"), None).is_err());
+        assert!(check_explorer_config(Some("etherscan"), None).is_ok());
+    }
 }
@@ -0,0 +1,132 @@
+use axum::{
+    extract::{Extension, Path, Query, State},
+    response::Json,
+    routing::get,
+    Router,
+};
+use axum_keycloak_auth::decode::KeycloakToken;
+use chrono::NaiveTime;
+use sea_orm::{ColumnTrait, DatabaseConnection, EntityTrait, QueryFilter, QueryOrder};
+use serde::{Deserialize, Serialize};
+use std::collections::BTreeMap;
+use utoipa::ToSchema;
+use uuid::Uuid;
+
+use crate::entities::fees;
+use crate::helpers::auth::{check_portfolio_ownership, get_or_create_user};
+use crate::helpers::fees::summarize_fees;
+use crate::helpers::params::parse_date;
+use crate::jobs::portfolio_pnl::portfolio_account_ids;
+use super::error::ApiError;
+
+// === Request/Response DTOs ===
+
+pub use crate::helpers::fees::{AssetFees, FeePeriod, PeriodFees};
+
+#[derive(Debug, Deserialize, ToSchema)]
+pub struct FeeReportQuery {
+    /// Start date (YYYY-MM-DD, inclusive, UTC)
+    pub start_date: Option<String>,
+    /// End date (YYYY-MM-DD, inclusive, UTC)
+    pub end_date: Option<String>,
+    /// "day", "week", "month" (default) or "year"
+    pub period: Option<String>,
+}
+
+#[derive(Debug, Serialize, Deserialize, ToSchema)]
+pub struct FeeReportResponse {
+    pub portfolio_id: Uuid,
+    pub period: FeePeriod,
+    /// Total USD value of the fees when paid
+    pub total_usd: String,
+    /// USD value per fee type ("trading", "withdrawal", "gas")
+    pub by_type: BTreeMap<String, String>,
+    /// Per asset paid in, largest USD value first
+    pub by_asset: Vec<AssetFees>,
+    /// Per period, oldest first; periods without fees are left out
+    pub periods: Vec<PeriodFees>,
+    pub fee_count: usize,
+    /// Fees without a price when paid, left out of the USD totals
+    pub unpriced_fees: usize,
+}
+
+// === API Handlers ===
+
+/// Get a portfolio's fee report
+///
+/// Trading fees, withdrawal network fees and gas paid by the portfolio's accounts, as
+/// captured by account sync, with their USD value when paid. Totals are given per fee type,
+/// per asset and per day, week, month or year.
+#[utoipa::path(
+    get,
+    path = "/api/v1/portfolios/{portfolio_id}/fees",
+    params(
+        ("portfolio_id" = Uuid, Path, description = "Portfolio ID"),
+        ("start_date" = Option<String>, Query, description = "Start date filter (YYYY-MM-DD, inclusive)"),
+        ("end_date" = Option<String>, Query, description = "End date filter (YYYY-MM-DD, inclusive)"),
+        ("period" = Option<String>, Query, description = "Grouping: day, week, month (default) or year")
+    ),
+    responses(
+        (status = 200, description = "Fee report", body = FeeReportResponse),
+        (status = 400, description = "Invalid date or period"),
+        (status = 401, description = "Unauthorized"),
+        (status = 403, description = "Forbidden - portfolio does not belong to user"),
+        (status = 404, description = "Portfolio not found"),
+        (status = 500, description = "Internal server error")
+    ),
+    tag = "portfolios"
+)]
+pub async fn get_portfolio_fees_handler(
+    State(db): State<DatabaseConnection>,
+    Extension(token): Extension<KeycloakToken<String>>,
+    Path(portfolio_id): Path<Uuid>,
+    Query(query): Query<FeeReportQuery>,
+) -> Result<Json<FeeReportResponse>, ApiError> {
+    let user = get_or_create_user(&db, &token).await?;
+    check_portfolio_ownership(&db, portfolio_id, user.id).await?;
+
+    let period = match query.period.as_deref() {
+        None => FeePeriod::Month,
+        Some(value) => FeePeriod::parse(value).ok_or_else(|| {
+            ApiError::BadRequest(format!("Invalid period '{}'. Expected day, week, month or year", value))
+        })?,
+    };
+
+    let account_ids = portfolio_account_ids(&db, portfolio_id).await?;
+    let mut fees_query = fees::Entity::find().filter(fees::Column::AccountId.is_in(account_ids));
+    if let Some(start_date) = query.start_date.as_deref() {
+        let start = parse_date(start_date, "start_date")?.and_time(NaiveTime::MIN).and_utc();
+        fees_query = fees_query.filter(fees::Column::OccurredAt.gte(start));
+    }
+    if let Some(end_date) = query.end_date.as_deref() {
+        let end = parse_date(end_date, "end_date")?
+            .succ_opt()
+            .ok_or_else(|| ApiError::BadRequest("end_date out of range".to_string()))?
+            .and_time(NaiveTime::MIN)
+            .and_utc();
+        fees_query = fees_query.filter(fees::Column::OccurredAt.lt(end));
+    }
+    let fees = fees_query.order_by_asc(fees::Column::OccurredAt).all(&db).await?;
+
+    let summary = summarize_fees(&fees, period);
+    Ok(Json(FeeReportResponse {
+        portfolio_id,
+        period,
+        total_usd: summary.total_usd.round_dp(2).normalize().to_string(),
+        by_type: summary
+            .by_type
+            .into_iter()
+            .map(|(fee_type, value)| (fee_type, value.round_dp(2).normalize().to_string()))
+            .collect(),
+        by_asset: summary.by_asset,
+        periods: summary.periods,
+        fee_count: fees.len(),
+        unpriced_fees: summary.unpriced,
+    }))
+}
+
+// === Router setup ===
+
+pub fn create_router() -> Router<DatabaseConnection> {
+    Router::new().route("/api/v1/portfolios/{portfolio_id}/fees", get(get_portfolio_fees_handler))
+}
@@ -0,0 +1,252 @@
+use axum::{
+    extract::{Extension, Query, State},
+    response::Json,
+    routing::get,
+    Router,
+};
+use axum_keycloak_auth::decode::KeycloakToken;
+use rust_decimal::prelude::ToPrimitive;
+use rust_decimal::Decimal;
+use sea_orm::{ColumnTrait, DatabaseConnection, EntityTrait, QueryFilter, QueryOrder};
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+use utoipa::ToSchema;
+use uuid::Uuid;
+
+use crate::domain::AccountHolding;
+use crate::entities::{accounts, asset_prices, portfolio_accounts, portfolios};
+use crate::helpers::asset_identity::{split_chain_suffix, AssetIdentityNormalizer, NormalizationResult};
+use crate::helpers::auth::get_or_create_user;
+use super::error::ApiError;
+
+// === Request/Response DTOs ===
+
+#[derive(Debug, Deserialize, ToSchema)]
+pub struct SearchHoldingsQuery {
+    /// Asset symbol to search for (e.g., "LINK"); chain-specific holdings such as
+    /// "LINK-ethereum" are included
+    pub asset: String,
+}
+
+/// One account holding of the searched asset
+#[derive(Debug, Serialize, Deserialize, ToSchema)]
+pub struct AssetAccountHolding {
+    pub account_id: Uuid,
+    pub account_name: String,
+    /// "exchange" or "wallet"
+    pub account_type: String,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub exchange_name: Option<String>,
+    /// Symbol as stored on the account (e.g., "LINK-ethereum")
+    pub holding_asset: String,
+    /// Chain label for chain-specific holdings
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub chain: Option<String>,
+    /// "spot", "earn", "perp" or "derivatives"
+    pub holding_source: String,
+    pub quantity: String,
+    /// Value in USD; absent when no price is known
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub value_usd: Option<f64>,
+}
+
+/// Total of the searched asset across one portfolio's accounts
+#[derive(Debug, Serialize, Deserialize, ToSchema)]
+pub struct AssetPortfolioHolding {
+    pub portfolio_id: Uuid,
+    pub portfolio_name: String,
+    pub account_ids: Vec<Uuid>,
+    pub quantity: String,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub value_usd: Option<f64>,
+}
+
+#[derive(Debug, Serialize, Deserialize, ToSchema)]
+pub struct SearchHoldingsResponse {
+    /// Canonical asset symbol (or the searched symbol when the asset is unknown)
+    pub asset: String,
+    /// Latest known USD price
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub price_usd: Option<f64>,
+    /// Total quantity across all of the user's accounts
+    pub total_quantity: String,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub total_value_usd: Option<f64>,
+    /// Account holdings, largest first
+    pub accounts: Vec<AssetAccountHolding>,
+    /// Portfolios containing at least one of those accounts, largest first
+    pub portfolios: Vec<AssetPortfolioHolding>,
+}
+
+// === Helper Functions ===
+
+/// Whether a stored holding symbol refers to `asset`, with or without a chain suffix
+fn matches_asset(holding_asset: &str, asset: &str) -> bool {
+    let (base, _) = split_chain_suffix(holding_asset);
+    base.eq_ignore_ascii_case(asset)
+}
+
+fn value_of(quantity: Decimal, price: Option<Decimal>) -> Option<f64> {
+    price.and_then(|p| (quantity * p).to_f64())
+}
+
+// === API Handlers ===
+
+/// Find where the user holds an asset
+///
+/// Returns every account holding the asset (per chain and holding source) and every
+/// portfolio that includes one of those accounts, with quantities and USD values based
+/// on the latest stored price. Quantities come from each account's last sync.
+#[utoipa::path(
+    get,
+    path = "/api/v1/holdings/search",
+    params(
+        ("asset" = String, Query, description = "Asset symbol (e.g., LINK)")
+    ),
+    responses(
+        (status = 200, description = "Accounts and portfolios holding the asset", body = SearchHoldingsResponse),
+        (status = 400, description = "Missing asset"),
+        (status = 401, description = "Unauthorized"),
+        (status = 500, description = "Internal server error")
+    ),
+    tag = "holdings"
+)]
+pub async fn search_holdings_handler(
+    State(db): State<DatabaseConnection>,
+    Extension(token): Extension<KeycloakToken<String>>,
+    Query(query): Query<SearchHoldingsQuery>,
+) -> Result<Json<SearchHoldingsResponse>, ApiError> {
+    let asset = query.asset.trim().to_uppercase();
+    if asset.is_empty() {
+        return Err(ApiError::BadRequest("asset is required".to_string()));
+    }
+
+    let user = get_or_create_user(&db, &token).await?;
+
+    // Resolve the canonical asset and its latest price
+    let normalizer = AssetIdentityNormalizer::new(db.clone());
+    let (canonical_symbol, price) = match normalizer.normalize_from_symbol(&asset).await {
+        NormalizationResult::Mapped(identity) => {
+            let latest = asset_prices::Entity::find()
+                .filter(asset_prices::Column::AssetId.eq(identity.asset_id))
+                .order_by_desc(asset_prices::Column::Timestamp)
+                .one(&db)
+                .await?;
+            (identity.symbol, latest.map(|p| p.price_usd))
+        }
+        NormalizationResult::Unknown { .. } => (asset.clone(), None),
+    };
+
+    let user_accounts = accounts::Entity::find()
+        .filter(accounts::Column::UserId.eq(user.id))
+        .order_by_asc(accounts::Column::Name)
+        .all(&db)
+        .await?;
+
+    let mut account_holdings: Vec<(AssetAccountHolding, Decimal)> = Vec::new();
+    for account in &user_accounts {
+        let Some(json) = account.holdings.clone() else { continue };
+        let holdings: Vec<AccountHolding> = match serde_json::from_value(json) {
+            Ok(h) => h,
+            Err(e) => {
+                tracing::warn!("Failed to deserialize holdings for account {}: {}", account.id, e);
+                continue;
+            }
+        };
+
+        for holding in holdings.iter().filter(|h| matches_asset(&h.asset, &asset)) {
+            let quantity = holding.quantity_decimal();
+            if quantity.is_zero() {
+                continue;
+            }
+            account_holdings.push((
+                AssetAccountHolding {
+                    account_id: account.id,
+                    account_name: account.name.clone(),
+                    account_type: account.account_type.clone(),
+                    exchange_name: account.exchange_name.clone(),
+                    holding_asset: holding.asset.clone(),
+                    chain: split_chain_suffix(&holding.asset).1.map(str::to_lowercase),
+                    holding_source: holding.source().to_string(),
+                    quantity: quantity.to_string(),
+                    value_usd: value_of(quantity, price),
+                },
+                quantity,
+            ));
+        }
+    }
+    account_holdings.sort_by_key(|(_, quantity)| std::cmp::Reverse(*quantity));
+
+    // Quantity per account, for portfolio totals
+    let mut quantity_by_account: HashMap<Uuid, Decimal> = HashMap::new();
+    for (holding, quantity) in &account_holdings {
+        *quantity_by_account.entry(holding.account_id).or_insert(Decimal::ZERO) += *quantity;
+    }
+
+    let mut portfolio_holdings: Vec<(AssetPortfolioHolding, Decimal)> = Vec::new();
+    if !quantity_by_account.is_empty() {
+        let user_portfolios = portfolios::Entity::find()
+            .filter(portfolios::Column::UserId.eq(user.id))
+            .order_by_asc(portfolios::Column::Name)
+            .all(&db)
+            .await?;
+        let memberships = portfolio_accounts::Entity::find()
+            .filter(portfolio_accounts::Column::AccountId.is_in(quantity_by_account.keys().copied()))
+            .all(&db)
+            .await?;
+
+        for portfolio in user_portfolios {
+            let account_ids: Vec<Uuid> = memberships
+                .iter()
+                .filter(|m| m.portfolio_id == portfolio.id)
+                .map(|m| m.account_id)
+                .collect();
+            if account_ids.is_empty() {
+                continue;
+            }
+
+            let quantity: Decimal = account_ids.iter().map(|id| quantity_by_account[id]).sum();
+            portfolio_holdings.push((
+                AssetPortfolioHolding {
+                    portfolio_id: portfolio.id,
+                    portfolio_name: portfolio.name,
+                    account_ids,
+                    quantity: quantity.to_string(),
+                    value_usd: value_of(quantity, price),
+                },
+                quantity,
+            ));
+        }
+        portfolio_holdings.sort_by_key(|(_, quantity)| std::cmp::Reverse(*quantity));
+    }
+
+    let total_quantity: Decimal = quantity_by_account.values().sum();
+
+    Ok(Json(SearchHoldingsResponse {
+        asset: canonical_symbol,
+        price_usd: price.and_then(|p| p.to_f64()),
+        total_quantity: total_quantity.to_string(),
+        total_value_usd: value_of(total_quantity, price),
+        accounts: account_holdings.into_iter().map(|(h, _)| h).collect(),
+        portfolios: portfolio_holdings.into_iter().map(|(p, _)| p).collect(),
+    }))
+}
+
+/// Create router for holdings routes
+pub fn create_router() -> Router<DatabaseConnection> {
+    Router::new().route("/api/v1/holdings/search", get(search_holdings_handler))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_matches_asset_ignores_chain_suffix_and_case() {
+        assert!(matches_asset("LINK", "LINK"));
+        assert!(matches_asset("link-ethereum", "LINK"));
+        assert!(matches_asset("LINK-arbitrum", "link"));
+        assert!(!matches_asset("LINKUP", "LINK"));
+        assert!(!matches_asset("LINK-PERP", "LINK"));
+    }
+}
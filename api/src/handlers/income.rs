@@ -0,0 +1,201 @@
+use axum::{
+    extract::{Extension, Path, Query, State},
+    response::Json,
+    routing::get,
+    Router,
+};
+use axum_keycloak_auth::decode::KeycloakToken;
+use chrono::NaiveTime;
+use rust_decimal::Decimal;
+use sea_orm::{ColumnTrait, DatabaseConnection, EntityTrait, QueryFilter, QueryOrder};
+use serde::{Deserialize, Serialize};
+use std::collections::BTreeMap;
+use utoipa::ToSchema;
+use uuid::Uuid;
+
+use crate::entities::{income_events, portfolio_accounts};
+use crate::helpers::auth::{check_portfolio_ownership, get_or_create_user};
+use crate::helpers::params::parse_date;
+use super::error::ApiError;
+
+// === Request/Response DTOs ===
+
+#[derive(Debug, Deserialize, ToSchema)]
+pub struct IncomeReportQuery {
+    /// Start date (YYYY-MM-DD, inclusive, UTC)
+    pub start_date: Option<String>,
+    /// End date (YYYY-MM-DD, inclusive, UTC)
+    pub end_date: Option<String>,
+}
+
+#[derive(Debug, Serialize, Deserialize, ToSchema)]
+pub struct IncomeEventResponse {
+    pub id: Uuid,
+    pub account_id: Uuid,
+    pub asset: String,
+    pub quantity: String,
+    /// USD value at receipt; absent when no price was known
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub value_usd: Option<String>,
+    /// "staking", "interest" or "airdrop"
+    pub source: String,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub holding_source: Option<String>,
+    pub received_at: String, // ISO 8601 datetime
+}
+
+impl From<income_events::Model> for IncomeEventResponse {
+    fn from(model: income_events::Model) -> Self {
+        Self {
+            id: model.id,
+            account_id: model.account_id,
+            asset: model.asset,
+            quantity: model.quantity.normalize().to_string(),
+            value_usd: model.value_usd.map(|v| v.normalize().to_string()),
+            source: model.source,
+            holding_source: model.holding_source,
+            received_at: model.received_at.to_rfc3339(),
+        }
+    }
+}
+
+/// Income of one asset from one source over the report period
+#[derive(Debug, Serialize, Deserialize, PartialEq, ToSchema)]
+pub struct IncomeTotal {
+    pub asset: String,
+    pub source: String,
+    pub quantity: String,
+    /// USD value at receipt of the events that had a price
+    pub value_usd: String,
+    pub event_count: usize,
+}
+
+#[derive(Debug, Serialize, Deserialize, ToSchema)]
+pub struct IncomeReportResponse {
+    pub portfolio_id: Uuid,
+    /// Total USD value at receipt
+    pub total_value_usd: String,
+    /// USD value at receipt per source (e.g. {"staking": "12.5"})
+    pub value_by_source: BTreeMap<String, String>,
+    /// Totals per asset and source, largest value first
+    pub totals: Vec<IncomeTotal>,
+    /// Income events, newest first
+    pub events: Vec<IncomeEventResponse>,
+    pub total_count: usize,
+}
+
+// === Helper Functions ===
+
+/// Sum events per asset and source, largest value first
+fn income_totals(events: &[income_events::Model]) -> Vec<IncomeTotal> {
+    let mut sums: BTreeMap<(&str, &str), (Decimal, Decimal, usize)> = BTreeMap::new();
+    for event in events {
+        let sum = sums.entry((&event.asset, &event.source)).or_default();
+        sum.0 += event.quantity;
+        sum.1 += event.value_usd.unwrap_or(Decimal::ZERO);
+        sum.2 += 1;
+    }
+
+    let mut totals: Vec<(Decimal, IncomeTotal)> = sums
+        .into_iter()
+        .map(|((asset, source), (quantity, value, count))| {
+            let total = IncomeTotal {
+                asset: asset.to_string(),
+                source: source.to_string(),
+                quantity: quantity.normalize().to_string(),
+                value_usd: value.normalize().to_string(),
+                event_count: count,
+            };
+            (value, total)
+        })
+        .collect();
+    totals.sort_by_key(|(value, _)| std::cmp::Reverse(*value));
+    totals.into_iter().map(|(_, total)| total).collect()
+}
+
+// === API Handlers ===
+
+/// Get a portfolio's income report
+///
+/// Staking rewards, interest and airdrops received by the portfolio's accounts, as detected
+/// by account sync, with their USD value at receipt. Totals are given per source and per
+/// asset and source.
+#[utoipa::path(
+    get,
+    path = "/api/v1/portfolios/{portfolio_id}/income",
+    params(
+        ("portfolio_id" = Uuid, Path, description = "Portfolio ID"),
+        ("start_date" = Option<String>, Query, description = "Start date filter (YYYY-MM-DD, inclusive)"),
+        ("end_date" = Option<String>, Query, description = "End date filter (YYYY-MM-DD, inclusive)")
+    ),
+    responses(
+        (status = 200, description = "Income report", body = IncomeReportResponse),
+        (status = 400, description = "Invalid date"),
+        (status = 401, description = "Unauthorized"),
+        (status = 403, description = "Forbidden - portfolio does not belong to user"),
+        (status = 404, description = "Portfolio not found"),
+        (status = 500, description = "Internal server error")
+    ),
+    tag = "portfolios"
+)]
+pub async fn get_portfolio_income_handler(
+    State(db): State<DatabaseConnection>,
+    Extension(token): Extension<KeycloakToken<String>>,
+    Path(portfolio_id): Path<Uuid>,
+    Query(query): Query<IncomeReportQuery>,
+) -> Result<Json<IncomeReportResponse>, ApiError> {
+    let user = get_or_create_user(&db, &token).await?;
+    check_portfolio_ownership(&db, portfolio_id, user.id).await?;
+
+    let account_ids: Vec<Uuid> = portfolio_accounts::Entity::find()
+        .filter(portfolio_accounts::Column::PortfolioId.eq(portfolio_id))
+        .all(&db)
+        .await?
+        .into_iter()
+        .map(|pa| pa.account_id)
+        .collect();
+
+    let mut events_query = income_events::Entity::find().filter(income_events::Column::AccountId.is_in(account_ids));
+    if let Some(start_date) = query.start_date.as_deref() {
+        let start = parse_date(start_date, "start_date")?.and_time(NaiveTime::MIN).and_utc();
+        events_query = events_query.filter(income_events::Column::ReceivedAt.gte(start));
+    }
+    if let Some(end_date) = query.end_date.as_deref() {
+        let end = parse_date(end_date, "end_date")?
+            .succ_opt()
+            .ok_or_else(|| ApiError::BadRequest("end_date out of range".to_string()))?
+            .and_time(NaiveTime::MIN)
+            .and_utc();
+        events_query = events_query.filter(income_events::Column::ReceivedAt.lt(end));
+    }
+    let events = events_query
+        .order_by_desc(income_events::Column::ReceivedAt)
+        .all(&db)
+        .await?;
+
+    let mut value_by_source: BTreeMap<String, Decimal> = BTreeMap::new();
+    for event in &events {
+        *value_by_source.entry(event.source.clone()).or_insert(Decimal::ZERO) += event.value_usd.unwrap_or(Decimal::ZERO);
+    }
+    let total_value: Decimal = value_by_source.values().sum();
+    let totals = income_totals(&events);
+    let total_count = events.len();
+
+    Ok(Json(IncomeReportResponse {
+        portfolio_id,
+        total_value_usd: total_value.normalize().to_string(),
+        value_by_source: value_by_source
+            .into_iter()
+            .map(|(source, value)| (source, value.normalize().to_string()))
+            .collect(),
+        totals,
+        events: events.into_iter().map(IncomeEventResponse::from).collect(),
+        total_count,
+    }))
+}
+
+// === Router setup ===
+
+pub fn create_router() -> Router<DatabaseConnection> {
+    Router::new().route("/api/v1/portfolios/{portfolio_id}/income", get(get_portfolio_income_handler))
+}
@@ -1,10 +1,19 @@
-use axum::{extract::State, response::Json, routing::post, Router};
+use axum::{extract::{Query, State}, response::Json, routing::post, Router};
 use axum_keycloak_auth::decode::KeycloakToken;
 use axum::Extension;
-use sea_orm::DatabaseConnection;
-use serde::Serialize;
-use crate::jobs::fetch_all_coins;
+use chrono::{Duration, NaiveDate, Utc};
+use sea_orm::{DatabaseConnection, EntityTrait};
+use serde::{Deserialize, Serialize};
+use crate::connectors::coinpaprika::CoinPaprikaConnector;
+use crate::entities::{assets, portfolios};
+use crate::helpers::params::parse_date;
+use crate::jobs::{
+    fetch_all_coins, holdings_backfill, job_runs, portfolio_performance, price_history_backfill, snapshot_backfill,
+};
 use utoipa::ToSchema;
+use uuid::Uuid;
+
+use super::error::ApiError;
 
 /// Response from fetch all coins job
 #[derive(Debug, Serialize, ToSchema)]
@@ -17,6 +26,8 @@ pub struct FetchAllCoinsResponse {
     pub assets_created: usize,
     /// Number of existing assets updated
     pub assets_updated: usize,
+    /// Number of assets delisted because the provider no longer lists them
+    pub assets_delisted: usize,
     /// Number of price records stored
     pub prices_stored: usize,
     /// Error message if failed
@@ -49,14 +60,19 @@ pub async fn fetch_all_coins_handler(
 ) -> Json<FetchAllCoinsResponse> {
     tracing::info!("Manual fetch all coins triggered");
 
-    match fetch_all_coins::fetch_all_coins(&db).await {
+    if let Err(e) = job_runs::record_job_started(&db, job_runs::JOB_FETCH_ALL_COINS).await {
+        tracing::warn!("Failed to record fetch all coins job start: {}", e);
+    }
+
+    let response = match fetch_all_coins::fetch_all_coins(&db).await {
         Ok(result) => {
             tracing::info!(
-                "Fetch all coins completed: success={}, coins_fetched={}, assets_created={}, assets_updated={}, prices_stored={}",
+                "Fetch all coins completed: success={}, coins_fetched={}, assets_created={}, assets_updated={}, assets_delisted={}, prices_stored={}",
                 result.success,
                 result.coins_fetched,
                 result.assets_created,
                 result.assets_updated,
+                result.assets_delisted,
                 result.prices_stored
             );
 
@@ -65,6 +81,7 @@ pub async fn fetch_all_coins_handler(
                 coins_fetched: result.coins_fetched,
                 assets_created: result.assets_created,
                 assets_updated: result.assets_updated,
+                assets_delisted: result.assets_delisted,
                 prices_stored: result.prices_stored,
                 error: result.error,
             })
@@ -76,15 +93,578 @@ pub async fn fetch_all_coins_handler(
                 coins_fetched: 0,
                 assets_created: 0,
                 assets_updated: 0,
+                assets_delisted: 0,
                 prices_stored: 0,
                 error: Some(format!("Collection failed: {}", e)),
             })
         }
+    };
+
+    let run_error = (!response.success)
+        .then(|| response.error.clone().unwrap_or_else(|| "Unknown error".to_string()));
+    if let Err(e) = job_runs::record_job_finished(&db, job_runs::JOB_FETCH_ALL_COINS, run_error).await {
+        tracing::warn!("Failed to record fetch all coins job result: {}", e);
     }
+
+    response
+}
+
+/// Query parameters for the holdings backfill
+#[derive(Debug, Deserialize, ToSchema)]
+pub struct BackfillHoldingsQuery {
+    /// Report what would be written without writing anything (default: false)
+    #[serde(default)]
+    pub dry_run: bool,
+}
+
+/// Backfill outcome for one account
+#[derive(Debug, Serialize, ToSchema)]
+pub struct BackfillAccountResponse {
+    pub account_id: Uuid,
+    pub account_name: String,
+    /// "backfilled", "skipped" or "failed"
+    pub status: String,
+    /// Number of baseline holding transactions created
+    pub rows_created: usize,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub message: Option<String>,
+}
+
+/// Response from the holdings backfill
+#[derive(Debug, Serialize, ToSchema)]
+pub struct BackfillHoldingsResponse {
+    pub dry_run: bool,
+    pub total: usize,
+    pub backfilled: usize,
+    pub skipped: usize,
+    pub failed: usize,
+    pub results: Vec<BackfillAccountResponse>,
+}
+
+/// Backfill legacy holdings JSON into the holding ledger
+///
+/// Converts every account's `holdings` JSON into baseline `holding_transactions` rows
+/// (transaction_type = "backfill"). Accounts that were already backfilled are skipped,
+/// so the job can be re-run safely.
+#[utoipa::path(
+    post,
+    path = "/api/v1/jobs/backfill-holdings",
+    params(
+        ("dry_run" = Option<bool>, Query, description = "Report without writing (default: false)")
+    ),
+    responses(
+        (status = 200, description = "Backfill completed", body = BackfillHoldingsResponse),
+        (status = 401, description = "Unauthorized"),
+        (status = 500, description = "Internal server error")
+    ),
+    security(
+        ("bearer_auth" = []),
+        ("oauth2_client_credentials" = []),
+        ("oauth2_authorization_code" = [])
+    ),
+    tag = "jobs"
+)]
+pub async fn backfill_holdings_handler(
+    State(db): State<DatabaseConnection>,
+    Extension(_token): Extension<KeycloakToken<String>>,
+    Query(query): Query<BackfillHoldingsQuery>,
+) -> Result<Json<BackfillHoldingsResponse>, ApiError> {
+    tracing::info!("Manual holdings backfill triggered (dry_run={})", query.dry_run);
+
+    let results = holdings_backfill::backfill_holdings(&db, query.dry_run)
+        .await
+        .map_err(|e| ApiError::InternalServerError(format!("Holdings backfill failed: {}", e)))?;
+
+    let count = |status: &str| results.iter().filter(|r| r.status == status).count();
+    let backfilled = count(holdings_backfill::BACKFILL_STATUS_BACKFILLED);
+    let skipped = count(holdings_backfill::BACKFILL_STATUS_SKIPPED);
+    let failed = count(holdings_backfill::BACKFILL_STATUS_FAILED);
+
+    Ok(Json(BackfillHoldingsResponse {
+        dry_run: query.dry_run,
+        total: results.len(),
+        backfilled,
+        skipped,
+        failed,
+        results: results
+            .into_iter()
+            .map(|r| BackfillAccountResponse {
+                account_id: r.account_id,
+                account_name: r.account_name,
+                status: r.status.to_string(),
+                rows_created: r.rows_created,
+                message: r.message,
+            })
+            .collect(),
+    }))
+}
+
+/// Request to backfill an asset's price history
+#[derive(Debug, Deserialize, ToSchema)]
+pub struct BackfillPriceHistoryRequest {
+    pub asset_id: Uuid,
+    /// First day to backfill (YYYY-MM-DD, UTC)
+    pub start_date: String,
+    /// Last day to backfill (YYYY-MM-DD, UTC, inclusive; default: today)
+    pub end_date: Option<String>,
+}
+
+/// Response from the price history backfill
+#[derive(Debug, Serialize, ToSchema)]
+pub struct BackfillPriceHistoryResponse {
+    pub asset_id: Uuid,
+    pub symbol: String,
+    pub start_date: String,
+    pub end_date: String,
+    /// Daily candles returned by CoinPaprika
+    pub candles_fetched: usize,
+    /// Price rows inserted or updated
+    pub prices_stored: usize,
+}
+
+/// Backfill an asset's daily price history
+///
+/// Pulls daily OHLCV candles from CoinPaprika's historical endpoint and stores each day's
+/// close, volume and market cap in `asset_prices` (source "coinpaprika"), so newly added
+/// assets have a price history for performance analytics. Re-running over the same range
+/// updates the stored rows instead of duplicating them.
+#[utoipa::path(
+    post,
+    path = "/api/v1/jobs/backfill-price-history",
+    request_body = BackfillPriceHistoryRequest,
+    responses(
+        (status = 200, description = "Backfill completed", body = BackfillPriceHistoryResponse),
+        (status = 400, description = "Invalid date range or asset without a CoinPaprika ID"),
+        (status = 401, description = "Unauthorized"),
+        (status = 404, description = "Asset not found"),
+        (status = 500, description = "Internal server error")
+    ),
+    security(
+        ("bearer_auth" = []),
+        ("oauth2_client_credentials" = []),
+        ("oauth2_authorization_code" = [])
+    ),
+    tag = "jobs"
+)]
+pub async fn backfill_price_history_handler(
+    State(db): State<DatabaseConnection>,
+    Extension(_token): Extension<KeycloakToken<String>>,
+    Json(req): Json<BackfillPriceHistoryRequest>,
+) -> Result<Json<BackfillPriceHistoryResponse>, ApiError> {
+    let today = Utc::now().date_naive();
+    let start = parse_date(&req.start_date, "start_date")?;
+    let end = match req.end_date.as_deref() {
+        Some(end_date) => parse_date(end_date, "end_date")?,
+        None => today,
+    };
+    if start > end {
+        return Err(ApiError::BadRequest("start_date must not be after end_date".to_string()));
+    }
+    if end > today {
+        return Err(ApiError::BadRequest("end_date must not be in the future".to_string()));
+    }
+
+    let asset = assets::Entity::find_by_id(req.asset_id)
+        .one(&db)
+        .await?
+        .ok_or(ApiError::NotFound)?;
+    if asset.coinpaprika_id.is_none() {
+        return Err(ApiError::BadRequest(format!("Asset {} has no CoinPaprika ID", asset.symbol)));
+    }
+
+    tracing::info!("Manual price history backfill triggered for {} ({} to {})", asset.symbol, start, end);
+
+    if let Err(e) = job_runs::record_job_started(&db, job_runs::JOB_BACKFILL_PRICE_HISTORY).await {
+        tracing::warn!("Failed to record price history backfill job start: {}", e);
+    }
+
+    let outcome =
+        price_history_backfill::backfill_price_history(&db, &CoinPaprikaConnector::new(), &asset, start, end).await;
+
+    let run_error = outcome.as_ref().err().map(|e| e.to_string());
+    if let Err(e) = job_runs::record_job_finished(&db, job_runs::JOB_BACKFILL_PRICE_HISTORY, run_error).await {
+        tracing::warn!("Failed to record price history backfill job result: {}", e);
+    }
+
+    let result = outcome.map_err(|e| ApiError::InternalServerError(format!("Price history backfill failed: {}", e)))?;
+    Ok(Json(BackfillPriceHistoryResponse {
+        asset_id: result.asset_id,
+        symbol: asset.symbol,
+        start_date: start.to_string(),
+        end_date: end.to_string(),
+        candles_fetched: result.candles_fetched,
+        prices_stored: result.prices_stored,
+    }))
+}
+
+/// Request to backfill missing daily snapshots
+#[derive(Debug, Default, Deserialize, ToSchema)]
+pub struct BackfillSnapshotsRequest {
+    /// Portfolio to backfill (default: every portfolio)
+    pub portfolio_id: Option<Uuid>,
+    /// First day to fill (YYYY-MM-DD; default: each portfolio's earliest snapshot)
+    pub start_date: Option<String>,
+    /// Last day to fill (YYYY-MM-DD, inclusive; default: yesterday)
+    pub end_date: Option<String>,
+    /// Report the dates that would be filled without writing anything (default: false)
+    #[serde(default)]
+    pub dry_run: bool,
+}
+
+/// Backfill outcome for one portfolio
+#[derive(Debug, Serialize, ToSchema)]
+pub struct BackfillPortfolioSnapshotsResponse {
+    pub portfolio_id: Uuid,
+    pub portfolio_name: String,
+    /// Dates snapshotted (or that would be in a dry run), oldest first
+    pub created_dates: Vec<String>,
+    /// Missing dates before the holding ledger starts, which cannot be reconstructed
+    pub skipped_dates: Vec<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub error: Option<String>,
+}
+
+/// Response from the snapshot backfill
+#[derive(Debug, Serialize, ToSchema)]
+pub struct BackfillSnapshotsResponse {
+    pub dry_run: bool,
+    /// Snapshots created (or that would be) across all portfolios
+    pub snapshots_created: usize,
+    pub failed: usize,
+    pub results: Vec<BackfillPortfolioSnapshotsResponse>,
+}
+
+/// Backfill missing daily snapshots
+///
+/// Fills the days without any snapshot (e.g. while the EOD job was down) with snapshots of
+/// type "backfill". Each day's quantities are reconstructed from the holding ledger
+/// (`holding_transactions`) of the portfolio's accounts and valued at the latest stored
+/// prices as of midnight UTC at the end of that day. Days before the ledger starts are
+/// reported as skipped. Days that already have a snapshot are left alone, so the job can be
+/// re-run safely.
+#[utoipa::path(
+    post,
+    path = "/api/v1/jobs/backfill-snapshots",
+    request_body = BackfillSnapshotsRequest,
+    responses(
+        (status = 200, description = "Backfill completed", body = BackfillSnapshotsResponse),
+        (status = 400, description = "Invalid date range"),
+        (status = 401, description = "Unauthorized"),
+        (status = 404, description = "Portfolio not found"),
+        (status = 500, description = "Internal server error")
+    ),
+    security(
+        ("bearer_auth" = []),
+        ("oauth2_client_credentials" = []),
+        ("oauth2_authorization_code" = [])
+    ),
+    tag = "jobs"
+)]
+pub async fn backfill_snapshots_handler(
+    State(db): State<DatabaseConnection>,
+    Extension(_token): Extension<KeycloakToken<String>>,
+    Json(req): Json<BackfillSnapshotsRequest>,
+) -> Result<Json<BackfillSnapshotsResponse>, ApiError> {
+    // Today's snapshot is still to come from the EOD job
+    let yesterday = Utc::now().date_naive() - Duration::days(1);
+    let start = req.start_date.as_deref().map(|d| parse_date(d, "start_date")).transpose()?;
+    let end = match req.end_date.as_deref() {
+        Some(end_date) => parse_date(end_date, "end_date")?,
+        None => yesterday,
+    };
+    if end > yesterday {
+        return Err(ApiError::BadRequest("end_date must be before today".to_string()));
+    }
+    if start.is_some_and(|start| start > end) {
+        return Err(ApiError::BadRequest("start_date must not be after end_date".to_string()));
+    }
+    if let Some(portfolio_id) = req.portfolio_id {
+        portfolios::Entity::find_by_id(portfolio_id).one(&db).await?.ok_or(ApiError::NotFound)?;
+    }
+
+    tracing::info!(
+        "Manual snapshot backfill triggered (portfolio={:?}, start={:?}, end={}, dry_run={})",
+        req.portfolio_id,
+        start,
+        end,
+        req.dry_run
+    );
+
+    if !req.dry_run {
+        if let Err(e) = job_runs::record_job_started(&db, job_runs::JOB_BACKFILL_SNAPSHOTS).await {
+            tracing::warn!("Failed to record snapshot backfill job start: {}", e);
+        }
+    }
+
+    let outcome = snapshot_backfill::backfill_snapshots(&db, req.portfolio_id, start, end, req.dry_run).await;
+
+    if !req.dry_run {
+        let run_error = match &outcome {
+            Ok(results) => {
+                let failed = results.iter().filter(|r| r.error.is_some()).count();
+                (failed > 0).then(|| format!("{} portfolios failed", failed))
+            }
+            Err(e) => Some(e.to_string()),
+        };
+        if let Err(e) = job_runs::record_job_finished(&db, job_runs::JOB_BACKFILL_SNAPSHOTS, run_error).await {
+            tracing::warn!("Failed to record snapshot backfill job result: {}", e);
+        }
+    }
+
+    let results = outcome.map_err(|e| ApiError::InternalServerError(format!("Snapshot backfill failed: {}", e)))?;
+    Ok(Json(BackfillSnapshotsResponse {
+        dry_run: req.dry_run,
+        snapshots_created: results.iter().map(|r| r.created_dates.len()).sum(),
+        failed: results.iter().filter(|r| r.error.is_some()).count(),
+        results: results
+            .into_iter()
+            .map(|r| BackfillPortfolioSnapshotsResponse {
+                portfolio_id: r.portfolio_id,
+                portfolio_name: r.portfolio_name,
+                created_dates: r.created_dates.iter().map(NaiveDate::to_string).collect(),
+                skipped_dates: r.skipped_dates.iter().map(NaiveDate::to_string).collect(),
+                error: r.error,
+            })
+            .collect(),
+    }))
+}
+
+/// Parse a required `start_date` / `end_date` range, rejecting reversed ranges and days from
+/// `today` on (today's snapshot is still to come from the EOD job)
+fn parse_past_range(start_date: &str, end_date: &str, today: NaiveDate) -> Result<(NaiveDate, NaiveDate), ApiError> {
+    let start = parse_date(start_date, "start_date")?;
+    let end = parse_date(end_date, "end_date")?;
+    if start > end {
+        return Err(ApiError::BadRequest("start_date must not be after end_date".to_string()));
+    }
+    if end >= today {
+        return Err(ApiError::BadRequest("end_date must be before today".to_string()));
+    }
+    Ok((start, end))
+}
+
+/// Refresh the stored daily performance of a portfolio after its snapshots changed
+async fn refresh_performance(db: &DatabaseConnection, portfolio_id: Uuid) {
+    if let Err(e) = portfolio_performance::compute_portfolio_performance(db, portfolio_id).await {
+        tracing::warn!("Failed to recompute performance of portfolio {}: {}", portfolio_id, e);
+    }
+}
+
+/// Request to delete a range of a portfolio's snapshots
+#[derive(Debug, Deserialize, ToSchema)]
+pub struct DeleteSnapshotsRequest {
+    pub portfolio_id: Uuid,
+    /// First day to delete (YYYY-MM-DD)
+    pub start_date: String,
+    /// Last day to delete (YYYY-MM-DD, inclusive; before today)
+    pub end_date: String,
+    /// Only delete snapshots of this type (default: every type)
+    pub snapshot_type: Option<String>,
+    /// Count the snapshots that would be deleted without deleting them (default: false)
+    #[serde(default)]
+    pub dry_run: bool,
+}
+
+/// Response from deleting a snapshot range
+#[derive(Debug, Serialize, ToSchema)]
+pub struct DeleteSnapshotsResponse {
+    pub dry_run: bool,
+    pub portfolio_id: Uuid,
+    pub start_date: String,
+    pub end_date: String,
+    /// Snapshots deleted (or that would be in a dry run)
+    pub deleted: u64,
+}
+
+/// Delete a range of snapshots
+///
+/// Removes a portfolio's snapshots dated `start_date` to `end_date`, e.g. ones valued by a
+/// pricing bug, so they no longer feed value history, deltas and performance. The portfolio's
+/// stored daily performance is recomputed afterwards. The days can then be refilled with
+/// `/api/v1/jobs/backfill-snapshots`, or use `/api/v1/jobs/rebuild-snapshots` to replace them
+/// in one step.
+#[utoipa::path(
+    post,
+    path = "/api/v1/jobs/delete-snapshots",
+    request_body = DeleteSnapshotsRequest,
+    responses(
+        (status = 200, description = "Snapshots deleted", body = DeleteSnapshotsResponse),
+        (status = 400, description = "Invalid date range"),
+        (status = 401, description = "Unauthorized"),
+        (status = 404, description = "Portfolio not found"),
+        (status = 500, description = "Internal server error")
+    ),
+    security(
+        ("bearer_auth" = []),
+        ("oauth2_client_credentials" = []),
+        ("oauth2_authorization_code" = [])
+    ),
+    tag = "jobs"
+)]
+pub async fn delete_snapshots_handler(
+    State(db): State<DatabaseConnection>,
+    Extension(_token): Extension<KeycloakToken<String>>,
+    Json(req): Json<DeleteSnapshotsRequest>,
+) -> Result<Json<DeleteSnapshotsResponse>, ApiError> {
+    let (start, end) = parse_past_range(&req.start_date, &req.end_date, Utc::now().date_naive())?;
+    portfolios::Entity::find_by_id(req.portfolio_id).one(&db).await?.ok_or(ApiError::NotFound)?;
+
+    tracing::info!(
+        "Snapshot range deletion triggered (portfolio={}, start={}, end={}, type={:?}, dry_run={})",
+        req.portfolio_id,
+        start,
+        end,
+        req.snapshot_type,
+        req.dry_run
+    );
+
+    let deleted = snapshot_backfill::delete_snapshot_range(
+        &db,
+        req.portfolio_id,
+        start,
+        end,
+        req.snapshot_type.as_deref(),
+        req.dry_run,
+    )
+    .await
+    .map_err(|e| ApiError::InternalServerError(format!("Snapshot deletion failed: {}", e)))?;
+    if !req.dry_run && deleted > 0 {
+        refresh_performance(&db, req.portfolio_id).await;
+    }
+
+    Ok(Json(DeleteSnapshotsResponse {
+        dry_run: req.dry_run,
+        portfolio_id: req.portfolio_id,
+        start_date: start.to_string(),
+        end_date: end.to_string(),
+        deleted,
+    }))
+}
+
+/// Request to rebuild a range of a portfolio's snapshots
+#[derive(Debug, Deserialize, ToSchema)]
+pub struct RebuildSnapshotsRequest {
+    pub portfolio_id: Uuid,
+    /// First day to rebuild (YYYY-MM-DD)
+    pub start_date: String,
+    /// Last day to rebuild (YYYY-MM-DD, inclusive; before today)
+    pub end_date: String,
+    /// Report the days that would be rebuilt without writing anything (default: false)
+    #[serde(default)]
+    pub dry_run: bool,
+}
+
+/// Response from rebuilding a snapshot range
+#[derive(Debug, Serialize, ToSchema)]
+pub struct RebuildSnapshotsResponse {
+    pub dry_run: bool,
+    pub portfolio_id: Uuid,
+    pub portfolio_name: String,
+    /// Days rebuilt (or that would be in a dry run), oldest first
+    pub rebuilt_dates: Vec<String>,
+    /// Days before the holding ledger starts, which cannot be reconstructed and keep their
+    /// snapshots
+    pub skipped_dates: Vec<String>,
+    /// Existing snapshots replaced by the rebuilt ones
+    pub replaced: u64,
+}
+
+/// Rebuild a range of snapshots
+///
+/// Replaces every snapshot of a portfolio dated `start_date` to `end_date` with one
+/// reconstructed snapshot per day (type "backfill"), built like the snapshot backfill: the
+/// day's quantities come from the holding ledger and are valued at the latest stored prices as
+/// of midnight UTC at the end of the day. Each day is replaced in its own transaction. Days
+/// before the ledger starts are reported as skipped and left as they are. The portfolio's
+/// stored daily performance is recomputed afterwards.
+#[utoipa::path(
+    post,
+    path = "/api/v1/jobs/rebuild-snapshots",
+    request_body = RebuildSnapshotsRequest,
+    responses(
+        (status = 200, description = "Snapshots rebuilt", body = RebuildSnapshotsResponse),
+        (status = 400, description = "Invalid date range"),
+        (status = 401, description = "Unauthorized"),
+        (status = 404, description = "Portfolio not found"),
+        (status = 500, description = "Internal server error")
+    ),
+    security(
+        ("bearer_auth" = []),
+        ("oauth2_client_credentials" = []),
+        ("oauth2_authorization_code" = [])
+    ),
+    tag = "jobs"
+)]
+pub async fn rebuild_snapshots_handler(
+    State(db): State<DatabaseConnection>,
+    Extension(_token): Extension<KeycloakToken<String>>,
+    Json(req): Json<RebuildSnapshotsRequest>,
+) -> Result<Json<RebuildSnapshotsResponse>, ApiError> {
+    let (start, end) = parse_past_range(&req.start_date, &req.end_date, Utc::now().date_naive())?;
+    let portfolio = portfolios::Entity::find_by_id(req.portfolio_id).one(&db).await?.ok_or(ApiError::NotFound)?;
+
+    tracing::info!(
+        "Snapshot range rebuild triggered (portfolio={}, start={}, end={}, dry_run={})",
+        portfolio.id,
+        start,
+        end,
+        req.dry_run
+    );
+
+    if !req.dry_run {
+        if let Err(e) = job_runs::record_job_started(&db, job_runs::JOB_REBUILD_SNAPSHOTS).await {
+            tracing::warn!("Failed to record snapshot rebuild job start: {}", e);
+        }
+    }
+
+    let outcome = snapshot_backfill::rebuild_snapshot_range(&db, &portfolio, start, end, req.dry_run).await;
+
+    if !req.dry_run {
+        let run_error = outcome.as_ref().err().map(|e| e.to_string());
+        if let Err(e) = job_runs::record_job_finished(&db, job_runs::JOB_REBUILD_SNAPSHOTS, run_error).await {
+            tracing::warn!("Failed to record snapshot rebuild job result: {}", e);
+        }
+        // Days rebuilt before a failure are committed, so refresh either way
+        refresh_performance(&db, portfolio.id).await;
+    }
+
+    let result = outcome.map_err(|e| ApiError::InternalServerError(format!("Snapshot rebuild failed: {}", e)))?;
+    Ok(Json(RebuildSnapshotsResponse {
+        dry_run: req.dry_run,
+        portfolio_id: result.portfolio_id,
+        portfolio_name: result.portfolio_name,
+        rebuilt_dates: result.created_dates.iter().map(NaiveDate::to_string).collect(),
+        skipped_dates: result.skipped_dates.iter().map(NaiveDate::to_string).collect(),
+        replaced: result.replaced,
+    }))
 }
 
 /// Create router for job endpoints
 pub fn create_router() -> Router<DatabaseConnection> {
     Router::new()
         .route("/api/v1/jobs/fetch-all-coins", post(fetch_all_coins_handler))
+        .route("/api/v1/jobs/backfill-holdings", post(backfill_holdings_handler))
+        .route("/api/v1/jobs/backfill-price-history", post(backfill_price_history_handler))
+        .route("/api/v1/jobs/backfill-snapshots", post(backfill_snapshots_handler))
+        .route("/api/v1/jobs/delete-snapshots", post(delete_snapshots_handler))
+        .route("/api/v1/jobs/rebuild-snapshots", post(rebuild_snapshots_handler))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_past_range() {
+        let today = NaiveDate::from_ymd_opt(2025, 3, 10).unwrap();
+        let day = |d| NaiveDate::from_ymd_opt(2025, 3, d).unwrap();
+
+        assert_eq!(parse_past_range("2025-03-01", "2025-03-09", today).unwrap(), (day(1), day(9)));
+        assert_eq!(parse_past_range("2025-03-05", "2025-03-05", today).unwrap(), (day(5), day(5)));
+
+        assert!(parse_past_range("2025-03-06", "2025-03-05", today).is_err());
+        assert!(parse_past_range("2025-03-01", "2025-03-10", today).is_err());
+        assert!(parse_past_range("2025-03-01", "03/09/2025", today).is_err());
+        assert!(parse_past_range("", "2025-03-09", today).is_err());
+    }
 }
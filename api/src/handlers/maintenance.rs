@@ -0,0 +1,108 @@
+use axum::{extract::Extension, response::Json, routing::get, Router};
+use axum_keycloak_auth::decode::KeycloakToken;
+use sea_orm::DatabaseConnection;
+use serde::{Deserialize, Serialize};
+use utoipa::ToSchema;
+
+use crate::maintenance::{self, MaintenanceState};
+
+// === Request/Response DTOs ===
+
+/// Request to turn maintenance mode on or off
+#[derive(Debug, Deserialize, ToSchema)]
+pub struct UpdateMaintenanceRequest {
+    /// Whether maintenance mode should be on
+    pub enabled: bool,
+    /// Message returned with 503 responses (default message when omitted)
+    pub message: Option<String>,
+    /// Value of the Retry-After header in seconds (keeps the current value when omitted)
+    pub retry_after_seconds: Option<u64>,
+}
+
+/// Current maintenance mode state
+#[derive(Debug, Serialize, ToSchema)]
+pub struct MaintenanceResponse {
+    pub enabled: bool,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub message: Option<String>,
+    pub retry_after_seconds: u64,
+    /// When maintenance mode was turned on
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub since: Option<String>,
+}
+
+impl From<MaintenanceState> for MaintenanceResponse {
+    fn from(state: MaintenanceState) -> Self {
+        Self {
+            enabled: state.enabled,
+            message: state.message,
+            retry_after_seconds: state.retry_after_seconds,
+            since: state.since.map(|dt| dt.to_rfc3339()),
+        }
+    }
+}
+
+// === API Handlers ===
+
+/// Get maintenance mode state
+#[utoipa::path(
+    get,
+    path = "/api/v1/maintenance",
+    responses(
+        (status = 200, description = "Current maintenance mode state", body = MaintenanceResponse),
+        (status = 401, description = "Unauthorized"),
+        (status = 403, description = "Forbidden - administrator role required")
+    ),
+    security(
+        ("bearer_auth" = []),
+        ("oauth2_client_credentials" = []),
+        ("oauth2_authorization_code" = [])
+    ),
+    tag = "maintenance"
+)]
+pub async fn get_maintenance_handler(
+    Extension(_token): Extension<KeycloakToken<String>>,
+) -> Json<MaintenanceResponse> {
+    Json(maintenance::current().into())
+}
+
+/// Turn maintenance mode on or off
+///
+/// While on, mutating user endpoints return 503 with a Retry-After header, reads keep
+/// working and scheduled jobs skip their runs. Admin endpoints stay available.
+#[utoipa::path(
+    put,
+    path = "/api/v1/maintenance",
+    request_body = UpdateMaintenanceRequest,
+    responses(
+        (status = 200, description = "Maintenance mode updated", body = MaintenanceResponse),
+        (status = 401, description = "Unauthorized"),
+        (status = 403, description = "Forbidden - administrator role required")
+    ),
+    security(
+        ("bearer_auth" = []),
+        ("oauth2_client_credentials" = []),
+        ("oauth2_authorization_code" = [])
+    ),
+    tag = "maintenance"
+)]
+pub async fn update_maintenance_handler(
+    Extension(token): Extension<KeycloakToken<String>>,
+    Json(req): Json<UpdateMaintenanceRequest>,
+) -> Json<MaintenanceResponse> {
+    tracing::warn!(
+        "Maintenance mode set to {} by {}",
+        req.enabled,
+        token.subject
+    );
+
+    Json(maintenance::set(req.enabled, req.message, req.retry_after_seconds).into())
+}
+
+/// Create router for maintenance mode routes (admin only)
+pub fn create_router() -> Router<DatabaseConnection> {
+    Router::new().route(
+        "/api/v1/maintenance",
+        get(get_maintenance_handler).put(update_maintenance_handler),
+    )
+}
@@ -1,11 +1,44 @@
+pub mod account_addresses;
+pub mod account_snapshots;
+pub mod account_transfers;
 pub mod accounts;
+pub mod anomalies;
+pub mod asset_categories;
+pub mod asset_price_overrides;
+pub mod asset_prices;
+pub mod attribution;
+pub mod backtest;
 pub mod chains;
+pub mod cosmos_chains;
+pub mod curve_pools;
+pub mod derivative_assets;
+pub mod drift;
 pub mod error;
 pub mod evm_chains;
 pub mod evm_tokens;
+pub mod fees;
+pub mod holdings;
+pub mod income;
 pub mod jobs;
+pub mod maintenance;
 pub mod migrations;
+pub mod notifications;
+pub mod performance;
+pub mod pnl;
 pub mod portfolios;
+pub mod preferences;
+pub mod prices;
+pub mod purchases;
+pub mod rebalance;
 pub mod recommendations;
+pub mod rejected_prices;
+pub mod reports;
+pub mod risk;
 pub mod snapshots;
 pub mod solana_tokens;
+pub mod spam_tokens;
+pub mod status;
+pub mod tax_lots;
+pub mod units;
+pub mod value_history;
+pub mod yield_vaults;
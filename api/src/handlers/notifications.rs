@@ -0,0 +1,165 @@
+use axum::{
+    extract::{Extension, Path, Query, State},
+    response::Json,
+    routing::{get, post},
+    Router,
+};
+use axum_keycloak_auth::decode::KeycloakToken;
+use chrono::Utc;
+use sea_orm::{
+    ActiveModelTrait, ActiveValue, ColumnTrait, DatabaseConnection, EntityTrait, QueryFilter,
+    QueryOrder,
+};
+use serde::{Deserialize, Serialize};
+use utoipa::ToSchema;
+use uuid::Uuid;
+
+use super::error::ApiError;
+use crate::entities::notifications;
+use crate::helpers::auth::get_or_create_user;
+
+// === Request/Response DTOs ===
+
+#[derive(Debug, Serialize, Deserialize, ToSchema)]
+pub struct NotificationResponse {
+    pub id: Uuid,
+    pub notification_type: String,
+    /// "low", "normal" or "high"
+    pub priority: String,
+    pub title: String,
+    pub message: String,
+    pub metadata: Option<serde_json::Value>,
+    pub read_at: Option<String>,
+    pub created_at: String,
+}
+
+impl From<notifications::Model> for NotificationResponse {
+    fn from(model: notifications::Model) -> Self {
+        Self {
+            id: model.id,
+            notification_type: model.notification_type,
+            priority: model.priority,
+            title: model.title,
+            message: model.message,
+            metadata: model.metadata,
+            read_at: model.read_at.map(|dt| dt.to_rfc3339()),
+            created_at: model.created_at.to_rfc3339(),
+        }
+    }
+}
+
+#[derive(Debug, Deserialize, ToSchema)]
+pub struct ListNotificationsQuery {
+    /// Only return unread notifications (default: false)
+    #[serde(default)]
+    pub unread_only: bool,
+}
+
+#[derive(Debug, Serialize, Deserialize, ToSchema)]
+pub struct ListNotificationsResponse {
+    pub notifications: Vec<NotificationResponse>,
+    pub total_count: usize,
+}
+
+// === API Handlers ===
+
+/// List notifications for the authenticated user (newest first)
+#[utoipa::path(
+    get,
+    path = "/api/v1/notifications",
+    params(
+        ("unread_only" = Option<bool>, Query, description = "Only return unread notifications")
+    ),
+    responses(
+        (status = 200, description = "Notifications retrieved successfully", body = ListNotificationsResponse),
+        (status = 401, description = "Unauthorized"),
+        (status = 500, description = "Internal server error")
+    ),
+    tag = "notifications"
+)]
+pub async fn list_notifications_handler(
+    State(db): State<DatabaseConnection>,
+    Extension(token): Extension<KeycloakToken<String>>,
+    Query(query): Query<ListNotificationsQuery>,
+) -> Result<Json<ListNotificationsResponse>, ApiError> {
+    let user = get_or_create_user(&db, &token)
+        .await
+        .map_err(|e| ApiError::InternalServerError(format!("Database error: {}", e)))?;
+
+    let mut notification_query = notifications::Entity::find()
+        .filter(notifications::Column::UserId.eq(user.id))
+        .order_by_desc(notifications::Column::CreatedAt);
+
+    if query.unread_only {
+        notification_query = notification_query.filter(notifications::Column::ReadAt.is_null());
+    }
+
+    let notifications: Vec<NotificationResponse> = notification_query
+        .all(&db)
+        .await?
+        .into_iter()
+        .map(NotificationResponse::from)
+        .collect();
+
+    let total_count = notifications.len();
+
+    Ok(Json(ListNotificationsResponse {
+        notifications,
+        total_count,
+    }))
+}
+
+/// Mark a notification as read
+#[utoipa::path(
+    post,
+    path = "/api/v1/notifications/{notification_id}/read",
+    params(
+        ("notification_id" = Uuid, Path, description = "Notification ID")
+    ),
+    responses(
+        (status = 200, description = "Notification marked as read", body = NotificationResponse),
+        (status = 401, description = "Unauthorized"),
+        (status = 403, description = "Forbidden - notification does not belong to user"),
+        (status = 404, description = "Notification not found"),
+        (status = 500, description = "Internal server error")
+    ),
+    tag = "notifications"
+)]
+pub async fn mark_notification_read_handler(
+    State(db): State<DatabaseConnection>,
+    Extension(token): Extension<KeycloakToken<String>>,
+    Path(notification_id): Path<Uuid>,
+) -> Result<Json<NotificationResponse>, ApiError> {
+    let user = get_or_create_user(&db, &token)
+        .await
+        .map_err(|e| ApiError::InternalServerError(format!("Database error: {}", e)))?;
+
+    let notification = notifications::Entity::find_by_id(notification_id)
+        .one(&db)
+        .await?
+        .ok_or(ApiError::NotFound)?;
+
+    if notification.user_id != user.id {
+        return Err(ApiError::Forbidden);
+    }
+
+    if notification.read_at.is_some() {
+        return Ok(Json(notification.into()));
+    }
+
+    let mut active: notifications::ActiveModel = notification.into();
+    active.read_at = ActiveValue::Set(Some(Utc::now().into()));
+    let updated = active.update(&db).await?;
+
+    Ok(Json(updated.into()))
+}
+
+/// Create router for notification endpoints
+pub fn create_router() -> Router<DatabaseConnection> {
+    Router::new()
+        .route("/api/v1/notifications", get(list_notifications_handler))
+        .route(
+            "/api/v1/notifications/{notification_id}/read",
+            post(mark_notification_read_handler),
+        )
+}
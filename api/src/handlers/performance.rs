@@ -0,0 +1,235 @@
+use axum::{
+    extract::{Extension, Path, Query, State},
+    response::Json,
+    routing::get,
+    Router,
+};
+use axum_keycloak_auth::decode::KeycloakToken;
+use chrono::Utc;
+use rust_decimal::Decimal;
+use sea_orm::{ColumnTrait, DatabaseConnection, EntityTrait, QueryFilter};
+use serde::{Deserialize, Serialize};
+use std::collections::BTreeMap;
+use utoipa::ToSchema;
+use uuid::Uuid;
+
+use crate::entities::portfolio_accounts;
+use crate::helpers::auth::{check_portfolio_ownership, get_or_create_user};
+use crate::helpers::performance::{
+    drawdown, growth_index, load_cash_flows, load_value_series, money_weighted_return, period_values,
+    time_weighted_returns, Drawdown, PerformancePeriod,
+};
+use crate::jobs::portfolio_performance::{load_portfolio_performance, stored_cash_flows};
+use super::error::ApiError;
+
+// === Request/Response DTOs ===
+
+#[derive(Debug, Deserialize, ToSchema)]
+pub struct PerformanceQuery {
+    /// "7d", "30d" (default), "ytd" or "all"
+    pub period: Option<String>,
+}
+
+/// Value and return of the sub-period ending on one snapshot date
+#[derive(Debug, Serialize, Deserialize, ToSchema)]
+pub struct PerformancePoint {
+    pub date: String, // YYYY-MM-DD
+    pub value_usd: String,
+    /// Deposits minus withdrawals since the previous point
+    pub net_flow_usd: String,
+    /// Return since the previous point; absent when nothing was invested
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub return_percent: Option<String>,
+    /// Time-weighted return from the start of the period to this point
+    pub cumulative_return_percent: String,
+}
+
+/// Falls of the time-weighted growth index, so withdrawals don't count as drawdowns
+#[derive(Debug, Serialize, Deserialize, ToSchema)]
+pub struct DrawdownResponse {
+    /// Largest fall from a peak (0 or negative)
+    pub max_drawdown_percent: String,
+    /// Peak and trough of the largest fall; absent when the portfolio never fell
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub peak_date: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub trough_date: Option<String>,
+    /// First date back at the peak after the largest fall; absent while not recovered
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub recovered_date: Option<String>,
+    /// Days from the trough to the recovery
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub recovery_days: Option<i64>,
+    /// Fall from the highest peak so far to the latest snapshot (0 or negative)
+    pub current_drawdown_percent: String,
+    pub current_peak_date: String,
+}
+
+impl From<Drawdown> for DrawdownResponse {
+    fn from(dd: Drawdown) -> Self {
+        Self {
+            max_drawdown_percent: percent(dd.max_drawdown),
+            peak_date: dd.peak_date.map(|d| d.to_string()),
+            trough_date: dd.trough_date.map(|d| d.to_string()),
+            recovered_date: dd.recovered_date.map(|d| d.to_string()),
+            recovery_days: dd.trough_date.zip(dd.recovered_date).map(|(trough, recovered)| (recovered - trough).num_days()),
+            current_drawdown_percent: percent(dd.current_drawdown),
+            current_peak_date: dd.current_peak_date.to_string(),
+        }
+    }
+}
+
+#[derive(Debug, Serialize, Deserialize, ToSchema)]
+pub struct PerformanceResponse {
+    pub portfolio_id: Uuid,
+    pub period: String,
+    /// Date of the baseline snapshot; absent when the portfolio has no snapshots
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub start_date: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub end_date: Option<String>,
+    pub start_value_usd: String,
+    pub end_value_usd: String,
+    /// Deposits minus withdrawals over the period
+    pub net_flows_usd: String,
+    /// Time-weighted return over the period; absent with fewer than two snapshots
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub time_weighted_return_percent: Option<String>,
+    /// Annualized money-weighted return (XIRR), which weighs returns by the money invested
+    /// when they happened; absent when it can't be solved (e.g. nothing invested)
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub money_weighted_return_percent: Option<String>,
+    /// Drawdowns over the period; absent when the portfolio has no snapshots
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub drawdown: Option<DrawdownResponse>,
+    /// Drawdowns over each period ("7d", "30d", "ytd", "all")
+    pub drawdown_by_period: BTreeMap<String, DrawdownResponse>,
+    /// Deposits and withdrawals in the portfolio's history left out because their asset had
+    /// no price at the time
+    pub unpriced_flows: usize,
+    /// One point per snapshot date after the baseline, oldest first
+    pub daily: Vec<PerformancePoint>,
+}
+
+// === Helper Functions ===
+
+/// Fraction as a percentage string with 4 decimal places
+fn percent(rate: Decimal) -> String {
+    (rate * Decimal::ONE_HUNDRED).round_dp(4).normalize().to_string()
+}
+
+// === API Handlers ===
+
+/// Get a portfolio's performance
+///
+/// Time-weighted return over the period, computed from the portfolio's snapshots with
+/// deposits and withdrawals of its accounts taken out, so moving money in or out doesn't
+/// count as a gain or loss, and the annualized money-weighted return (XIRR) of the same
+/// values and flows. Drawdowns of the time-weighted growth are given for the period and
+/// for each of 7d, 30d, ytd and all. The period starts at the latest snapshot on or before its first
+/// day (the first snapshot when there is none). Flows are read from the rows of the daily
+/// performance job while they match the snapshots, and priced from the transfers otherwise.
+#[utoipa::path(
+    get,
+    path = "/api/v1/portfolios/{portfolio_id}/performance",
+    params(
+        ("portfolio_id" = Uuid, Path, description = "Portfolio ID"),
+        ("period" = Option<String>, Query, description = "7d, 30d (default), ytd or all")
+    ),
+    responses(
+        (status = 200, description = "Portfolio performance", body = PerformanceResponse),
+        (status = 400, description = "Invalid period"),
+        (status = 401, description = "Unauthorized"),
+        (status = 403, description = "Forbidden - portfolio does not belong to user"),
+        (status = 404, description = "Portfolio not found"),
+        (status = 500, description = "Internal server error")
+    ),
+    tag = "portfolios"
+)]
+pub async fn get_portfolio_performance_handler(
+    State(db): State<DatabaseConnection>,
+    Extension(token): Extension<KeycloakToken<String>>,
+    Path(portfolio_id): Path<Uuid>,
+    Query(query): Query<PerformanceQuery>,
+) -> Result<Json<PerformanceResponse>, ApiError> {
+    let user = get_or_create_user(&db, &token).await?;
+    check_portfolio_ownership(&db, portfolio_id, user.id).await?;
+
+    let period = match query.period.as_deref() {
+        Some(value) => PerformancePeriod::parse(value).ok_or_else(|| {
+            ApiError::BadRequest(format!("Invalid period '{}'. Expected 7d, 30d, ytd or all", value))
+        })?,
+        None => PerformancePeriod::Month,
+    };
+
+    let today = Utc::now().date_naive();
+    let all_values = load_value_series(&db, portfolio_id, None).await?;
+
+    let account_ids: Vec<Uuid> = portfolio_accounts::Entity::find()
+        .filter(portfolio_accounts::Column::PortfolioId.eq(portfolio_id))
+        .all(&db)
+        .await?
+        .into_iter()
+        .map(|pa| pa.account_id)
+        .collect();
+
+    // Flows precomputed by the performance job, unless snapshots changed since
+    let stored = load_portfolio_performance(&db, portfolio_id).await?;
+    let flows = match (stored_cash_flows(&stored, &all_values), all_values.first(), all_values.last()) {
+        (Some(flows), _, _) => flows,
+        (None, Some((start, _)), Some((end, _))) => load_cash_flows(&db, &account_ids, *start, *end).await?,
+        _ => Default::default(),
+    };
+
+    let period_drawdown = |period: PerformancePeriod| {
+        let values = period_values(&all_values, period.baseline_date(today));
+        let returns = time_weighted_returns(values, &flows.by_date);
+        values.first().and_then(|(baseline, _)| drawdown(&growth_index(*baseline, &returns)))
+    };
+    let drawdown_by_period = [
+        PerformancePeriod::Week,
+        PerformancePeriod::Month,
+        PerformancePeriod::YearToDate,
+        PerformancePeriod::All,
+    ]
+    .into_iter()
+    .filter_map(|p| period_drawdown(p).map(|dd| (p.as_str().to_string(), DrawdownResponse::from(dd))))
+    .collect();
+
+    let values = period_values(&all_values, period.baseline_date(today));
+    let (first, last) = (values.first().copied(), values.last().copied());
+    let returns = time_weighted_returns(values, &flows.by_date);
+    let money_weighted = money_weighted_return(values, &flows.by_date).and_then(Decimal::from_f64_retain);
+    let net_flows: Decimal = returns.iter().map(|r| r.net_flow_usd).sum();
+
+    Ok(Json(PerformanceResponse {
+        portfolio_id,
+        period: period.as_str().to_string(),
+        start_date: first.map(|(date, _)| date.to_string()),
+        end_date: last.map(|(date, _)| date.to_string()),
+        start_value_usd: first.map(|(_, v)| v).unwrap_or_default().normalize().to_string(),
+        end_value_usd: last.map(|(_, v)| v).unwrap_or_default().normalize().to_string(),
+        net_flows_usd: net_flows.normalize().to_string(),
+        time_weighted_return_percent: returns.last().map(|r| percent(r.growth - Decimal::ONE)),
+        money_weighted_return_percent: money_weighted.map(percent),
+        drawdown: period_drawdown(period).map(DrawdownResponse::from),
+        drawdown_by_period,
+        unpriced_flows: flows.unpriced,
+        daily: returns
+            .into_iter()
+            .map(|r| PerformancePoint {
+                date: r.date.to_string(),
+                value_usd: r.value_usd.normalize().to_string(),
+                net_flow_usd: r.net_flow_usd.normalize().to_string(),
+                return_percent: r.return_rate.map(percent),
+                cumulative_return_percent: percent(r.growth - Decimal::ONE),
+            })
+            .collect(),
+    }))
+}
+
+// === Router setup ===
+
+pub fn create_router() -> Router<DatabaseConnection> {
+    Router::new().route("/api/v1/portfolios/{portfolio_id}/performance", get(get_portfolio_performance_handler))
+}
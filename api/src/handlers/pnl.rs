@@ -0,0 +1,158 @@
+use axum::{
+    extract::{Extension, Path, Query, State},
+    response::Json,
+    routing::get,
+    Router,
+};
+use axum_keycloak_auth::decode::KeycloakToken;
+use rust_decimal::Decimal;
+use sea_orm::{ColumnTrait, DatabaseConnection, EntityTrait, QueryFilter, QueryOrder};
+use serde::{Deserialize, Serialize};
+use utoipa::ToSchema;
+use uuid::Uuid;
+
+use crate::entities::portfolio_pnl;
+use crate::helpers::auth::{check_portfolio_ownership, get_or_create_user};
+use crate::jobs::portfolio_pnl::compute_portfolio_pnl;
+use super::error::ApiError;
+
+// === Request/Response DTOs ===
+
+#[derive(Debug, Deserialize, ToSchema)]
+pub struct PnlQuery {
+    /// Recompute from the ledger instead of returning the stored figures
+    #[serde(default)]
+    pub refresh: bool,
+}
+
+#[derive(Debug, Serialize, Deserialize, ToSchema)]
+pub struct AssetPnlResponse {
+    pub asset: String,
+    pub quantity: String,
+    pub cost_basis_usd: String,
+    /// Cost basis per unit held
+    pub average_cost_usd: String,
+    pub realized_pnl_usd: String,
+    /// Price the held quantity was valued at; absent when the asset has no price
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub price_usd: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub market_value_usd: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub unrealized_pnl_usd: Option<String>,
+    /// Quantity sold or withdrawn beyond what the ledger acquired, counted at zero cost
+    pub unmatched_quantity: String,
+}
+
+impl From<portfolio_pnl::Model> for AssetPnlResponse {
+    fn from(model: portfolio_pnl::Model) -> Self {
+        let average_cost = if model.quantity > Decimal::ZERO {
+            model.cost_basis_usd / model.quantity
+        } else {
+            Decimal::ZERO
+        };
+        Self {
+            asset: model.asset,
+            quantity: model.quantity.normalize().to_string(),
+            cost_basis_usd: model.cost_basis_usd.round_dp(2).normalize().to_string(),
+            average_cost_usd: average_cost.round_dp(8).normalize().to_string(),
+            realized_pnl_usd: model.realized_pnl_usd.round_dp(2).normalize().to_string(),
+            price_usd: model.price_usd.map(|p| p.normalize().to_string()),
+            market_value_usd: model.price_usd.map(|p| (model.quantity * p).round_dp(2).normalize().to_string()),
+            unrealized_pnl_usd: model.unrealized_pnl_usd.map(|v| v.round_dp(2).normalize().to_string()),
+            unmatched_quantity: model.unmatched_quantity.normalize().to_string(),
+        }
+    }
+}
+
+#[derive(Debug, Serialize, Deserialize, ToSchema)]
+pub struct PnlResponse {
+    pub portfolio_id: Uuid,
+    /// When the figures were computed; absent when the ledger is empty
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub computed_at: Option<String>,
+    pub total_cost_basis_usd: String,
+    pub total_realized_pnl_usd: String,
+    /// Sum over the assets that have a price
+    pub total_unrealized_pnl_usd: String,
+    /// Ledger entries without a price at the time, valued at zero or left out; only
+    /// reported when the figures were computed by this request
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub unpriced_entries: Option<usize>,
+    /// Per asset, sorted by asset
+    pub assets: Vec<AssetPnlResponse>,
+}
+
+// === API Handlers ===
+
+/// Get a portfolio's profit and loss
+///
+/// Average cost basis, realized P&L on disposals and unrealized P&L on held quantities per
+/// asset, from the spot trades, deposits, withdrawals and income of the portfolio's accounts.
+/// Figures are recomputed daily by the portfolio P&L job; `refresh=true` (or a portfolio that
+/// was never computed) recomputes them now.
+#[utoipa::path(
+    get,
+    path = "/api/v1/portfolios/{portfolio_id}/pnl",
+    params(
+        ("portfolio_id" = Uuid, Path, description = "Portfolio ID"),
+        ("refresh" = Option<bool>, Query, description = "Recompute from the ledger (default false)")
+    ),
+    responses(
+        (status = 200, description = "Portfolio P&L", body = PnlResponse),
+        (status = 401, description = "Unauthorized"),
+        (status = 403, description = "Forbidden - portfolio does not belong to user"),
+        (status = 404, description = "Portfolio not found"),
+        (status = 500, description = "Internal server error")
+    ),
+    tag = "portfolios"
+)]
+pub async fn get_portfolio_pnl_handler(
+    State(db): State<DatabaseConnection>,
+    Extension(token): Extension<KeycloakToken<String>>,
+    Path(portfolio_id): Path<Uuid>,
+    Query(query): Query<PnlQuery>,
+) -> Result<Json<PnlResponse>, ApiError> {
+    let user = get_or_create_user(&db, &token).await?;
+    check_portfolio_ownership(&db, portfolio_id, user.id).await?;
+
+    let mut rows = if query.refresh {
+        Vec::new()
+    } else {
+        portfolio_pnl::Entity::find()
+            .filter(portfolio_pnl::Column::PortfolioId.eq(portfolio_id))
+            .order_by_asc(portfolio_pnl::Column::Asset)
+            .all(&db)
+            .await?
+    };
+    let mut unpriced_entries = None;
+    if rows.is_empty() {
+        let (computed, unpriced) = compute_portfolio_pnl(&db, portfolio_id).await.map_err(|e| {
+            tracing::error!("Failed to compute P&L of portfolio {}: {}", portfolio_id, e);
+            ApiError::InternalServerError(e.to_string())
+        })?;
+        rows = computed;
+        unpriced_entries = Some(unpriced);
+    }
+
+    let computed_at = rows.first().map(|row| row.computed_at.to_rfc3339());
+    let total_cost: Decimal = rows.iter().map(|row| row.cost_basis_usd).sum();
+    let total_realized: Decimal = rows.iter().map(|row| row.realized_pnl_usd).sum();
+    let total_unrealized: Decimal = rows.iter().filter_map(|row| row.unrealized_pnl_usd).sum();
+
+    Ok(Json(PnlResponse {
+        portfolio_id,
+        computed_at,
+        total_cost_basis_usd: total_cost.round_dp(2).normalize().to_string(),
+        total_realized_pnl_usd: total_realized.round_dp(2).normalize().to_string(),
+        total_unrealized_pnl_usd: total_unrealized.round_dp(2).normalize().to_string(),
+        unpriced_entries,
+        assets: rows.into_iter().map(AssetPnlResponse::from).collect(),
+    }))
+}
+
+// === Router setup ===
+
+pub fn create_router() -> Router<DatabaseConnection> {
+    Router::new().route("/api/v1/portfolios/{portfolio_id}/pnl", get(get_portfolio_pnl_handler))
+}
@@ -1,5 +1,5 @@
 use axum::{
-    extract::{Extension, Path, State},
+    extract::{Extension, Path, Query, State},
     http::StatusCode,
     response::Json,
     routing::{delete, get},
@@ -7,20 +7,42 @@ use axum::{
 };
 use axum_keycloak_auth::decode::KeycloakToken;
 use rust_decimal::Decimal;
-use rust_decimal::prelude::ToPrimitive;
+use rust_decimal::prelude::{FromPrimitive, ToPrimitive};
 use sea_orm::{
     ActiveModelTrait, ActiveValue, ColumnTrait, DatabaseConnection, EntityTrait, ModelTrait,
-    QueryFilter, QueryOrder, TransactionTrait,
+    QueryFilter, QueryOrder, QuerySelect, TransactionTrait,
 };
 use serde::{Deserialize, Serialize};
-use std::collections::HashMap;
+use std::collections::{BTreeMap, HashMap};
 use std::str::FromStr;
 use utoipa::ToSchema;
 use uuid::Uuid;
 
-use crate::domain::AccountHolding;
-use crate::entities::{accounts, portfolio_accounts, portfolios};
-use crate::helpers::auth::get_or_create_user;
+use crate::domain::{
+    allocation_concentration, exposure_by_category, exposure_by_chain, exposure_by_underlying, exposure_by_venue, fixed_yield_by_maturity,
+    guardrail_violations, price_staleness_warning, stablecoin_exposure, target_weights, weigh_allocation, AccountHolding, DebtMode, DebtSummary,
+    PortfolioGuardrails, PortfolioSettings, HOLDING_SOURCE_SPOT, VENUE_SELF_CUSTODY,
+};
+use crate::entities::{accounts, construction_runs, portfolio_accounts, portfolios, positions};
+use crate::helpers::auth::{check_portfolio_ownership, get_or_create_user};
+use crate::helpers::derivative_assets::load_derivative_assets;
+use crate::helpers::fx::{load_user_fx_rates, CurrencyInfo};
+use crate::helpers::nft_valuation::{value_account_nfts, NFT_BUCKET_ASSET, NFT_HOLDING_SOURCE};
+use crate::helpers::pagination::{finish_page, keyset_before, Cursor, PageRequest};
+use crate::helpers::pendle_assets::{load_pendle_quotes, PENDLE_PRICE_SOURCE};
+use crate::helpers::price_overrides::load_price_overrides;
+use crate::helpers::price_resolution::{
+    resolve_latest_price, resolve_pinned_price, PriceResolution, PriceResolutionConfig, RESOLUTION_OVERRIDE,
+    RESOLUTION_PRIMARY,
+};
+use crate::helpers::value_deltas::{load_value_deltas, ValueDeltas};
+use crate::jobs::construction_runs::{
+    record_construction_run, ConstructionInputs, NewConstructionRun, PriceSource, TRIGGER_API,
+};
+use crate::jobs::recommendation_delivery::deliver_recommendations;
+use crate::jobs::recommendations::sync_guardrail_recommendations;
+use crate::live_prices::{self, LIVE_PRICE_SOURCE};
+use super::accounts::PositionResponse;
 use super::error::ApiError;
 
 // === Request/Response DTOs ===
@@ -41,6 +63,9 @@ pub struct CreatePortfolioRequest {
     /// Guardrails as JSON (e.g., {"drift_band": 5, "stablecoin_min": 10, "futures_cap": 20, "max_alt_cap": 50})
     #[serde(skip_serializing_if = "Option::is_none")]
     pub guardrails: Option<serde_json::Value>,
+    /// Portfolio settings (e.g., EOD snapshot valuation method, excluded assets)
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub settings: Option<PortfolioSettings>,
 }
 
 #[derive(Debug, Serialize, Deserialize, ToSchema)]
@@ -60,6 +85,9 @@ pub struct UpdatePortfolioRequest {
     /// Guardrails as JSON (e.g., {"drift_band": 5, "stablecoin_min": 10, "futures_cap": 20, "max_alt_cap": 50})
     #[serde(skip_serializing_if = "Option::is_none")]
     pub guardrails: Option<serde_json::Value>,
+    /// Portfolio settings (e.g., EOD snapshot valuation method, excluded assets)
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub settings: Option<PortfolioSettings>,
 }
 
 #[derive(Debug, Serialize, Deserialize, ToSchema)]
@@ -74,6 +102,7 @@ pub struct PortfolioResponse {
     pub target_allocation: Option<serde_json::Value>,
     #[serde(skip_serializing_if = "Option::is_none")]
     pub guardrails: Option<serde_json::Value>,
+    pub settings: PortfolioSettings,
     #[serde(skip_serializing_if = "Option::is_none")]
     pub last_constructed_at: Option<String>,
     pub created_at: String,
@@ -90,6 +119,7 @@ impl From<portfolios::Model> for PortfolioResponse {
             is_default: model.is_default,
             target_allocation: model.target_allocation,
             guardrails: model.guardrails,
+            settings: PortfolioSettings::from_json(model.settings.as_ref()),
             last_constructed_at: model.last_constructed_at.map(|dt| dt.to_string()),
             created_at: model.created_at.to_string(),
             updated_at: model.updated_at.to_string(),
@@ -157,6 +187,20 @@ impl From<accounts::Model> for AccountInPortfolioResponse {
     }
 }
 
+/// Venue of an account's holdings: its exchange for exchange accounts, self-custody otherwise
+fn account_venue(account: &accounts::Model) -> String {
+    if account.account_type == "exchange" {
+        account
+            .exchange_name
+            .as_deref()
+            .map(|name| name.trim().to_lowercase())
+            .filter(|name| !name.is_empty())
+            .unwrap_or_else(|| "exchange".to_string())
+    } else {
+        VENUE_SELF_CUSTODY.to_string()
+    }
+}
+
 /// Extract the chain name from a raw asset string that was stored with a chain suffix.
 ///
 /// Examples:
@@ -165,7 +209,7 @@ impl From<accounts::Model> for AccountInPortfolioResponse {
 /// - `"SOL-solana"`   → `Some("solana")`
 /// - `"BTC"`          → `None`  (OKX exchange asset, no chain suffix)
 fn extract_chain_suffix(raw: &str) -> Option<String> {
-    const KNOWN_CHAINS: &[&str] = &["ethereum", "arbitrum", "optimism", "base", "bsc", "solana"];
+    const KNOWN_CHAINS: &[&str] = &["ethereum", "arbitrum", "optimism", "base", "bsc", "solana", "bitcoin", "litecoin", "dogecoin", "cosmoshub", "osmosis", "celestia"];
     if let Some((_, suffix)) = raw.rsplit_once('-') {
         let lower = suffix.to_lowercase();
         if KNOWN_CHAINS.contains(&lower.as_str()) {
@@ -199,6 +243,10 @@ pub struct AssetHolding {
     pub price_usd: f64,
     /// Total value in USD
     pub value_usd: f64,
+    /// Price per unit in the user's base currency
+    pub price: f64,
+    /// Total value in the user's base currency
+    pub value: f64,
     /// List of accounts holding this asset
     pub accounts: Vec<AccountHoldingDetail>,
 }
@@ -219,6 +267,8 @@ pub struct AccountHoldingDetail {
     /// Normalized quantity — same as `quantity` (kept for backwards compatibility)
     #[serde(skip_serializing_if = "Option::is_none")]
     pub normalized_quantity: Option<String>,
+    /// Where the quantity is held: "spot", "earn", "staked", ... or "nft" for the NFT bucket
+    pub holding_source: String,
 }
 
 #[derive(Debug, Serialize, Deserialize, ToSchema)]
@@ -230,6 +280,8 @@ pub struct AllocationItem {
     pub chain: Option<String>,
     /// Value in USD
     pub value_usd: f64,
+    /// Value in the user's base currency
+    pub value: f64,
     /// Percentage of total portfolio
     pub percentage: f64,
 }
@@ -240,6 +292,11 @@ pub struct PortfolioHoldingsResponse {
     pub portfolio_id: Uuid,
     /// Total portfolio value in USD
     pub total_value_usd: f64,
+    /// Total portfolio value in the user's base currency
+    pub total_value: f64,
+    /// Base currency of the converted values and the FX rate used
+    #[serde(flatten)]
+    pub currency: CurrencyInfo,
     /// Holdings grouped by asset
     pub holdings: Vec<AssetHolding>,
     /// Allocation breakdown
@@ -250,26 +307,6 @@ pub struct PortfolioHoldingsResponse {
 
 // === Helper functions ===
 
-/// Get or create user in database based on Keycloak token
-
-/// Check if user owns a portfolio
-async fn check_portfolio_ownership(
-    db: &DatabaseConnection,
-    portfolio_id: Uuid,
-    user_id: Uuid,
-) -> Result<portfolios::Model, ApiError> {
-    let portfolio = portfolios::Entity::find_by_id(portfolio_id)
-        .one(db)
-        .await?
-        .ok_or(ApiError::NotFound)?;
-
-    if portfolio.user_id != user_id {
-        return Err(ApiError::Forbidden);
-    }
-
-    Ok(portfolio)
-}
-
 /// Check if user owns an account
 async fn check_account_ownership(
     db: &DatabaseConnection,
@@ -393,6 +430,9 @@ pub async fn create_portfolio(
     Json(req): Json<CreatePortfolioRequest>,
 ) -> Result<(StatusCode, Json<PortfolioResponse>), ApiError> {
     let user = get_or_create_user(&db, &token).await?;
+    if let Some(settings) = &req.settings {
+        settings.validate().map_err(ApiError::BadRequest)?;
+    }
 
     // If this is set as default, unset any existing default portfolio
     if req.is_default {
@@ -407,6 +447,7 @@ pub async fn create_portfolio(
         is_default: ActiveValue::Set(req.is_default),
         target_allocation: ActiveValue::Set(req.target_allocation),
         guardrails: ActiveValue::Set(req.guardrails),
+        settings: ActiveValue::Set(req.settings.map(|s| serde_json::json!(s))),
         last_constructed_at: ActiveValue::NotSet,
         created_at: ActiveValue::NotSet,
         updated_at: ActiveValue::NotSet,
@@ -442,6 +483,9 @@ pub async fn update_portfolio(
 ) -> Result<Json<PortfolioResponse>, ApiError> {
     let user = get_or_create_user(&db, &token).await?;
     let portfolio = check_portfolio_ownership(&db, id, user.id).await?;
+    if let Some(settings) = &req.settings {
+        settings.validate().map_err(ApiError::BadRequest)?;
+    }
 
     // If this is set as default, unset any existing default portfolio
     if req.is_default == Some(true) {
@@ -465,6 +509,9 @@ pub async fn update_portfolio(
     if req.guardrails.is_some() {
         active_portfolio.guardrails = ActiveValue::Set(req.guardrails);
     }
+    if let Some(settings) = req.settings {
+        active_portfolio.settings = ActiveValue::Set(Some(serde_json::json!(settings)));
+    }
 
     let updated_portfolio = active_portfolio.update(&db).await?;
     Ok(Json(updated_portfolio.into()))
@@ -757,7 +804,9 @@ pub async fn get_portfolio_holdings(
     Path(id): Path<Uuid>,
 ) -> Result<Json<PortfolioHoldingsResponse>, ApiError> {
     let user = get_or_create_user(&db, &token).await?;
-    check_portfolio_ownership(&db, id, user.id).await?;
+    let portfolio = check_portfolio_ownership(&db, id, user.id).await?;
+    let settings = PortfolioSettings::from_json(portfolio.settings.as_ref());
+    let fx = load_user_fx_rates(&db, &user).await?;
 
     // Get all accounts linked to this portfolio
     let portfolio_accounts = portfolio_accounts::Entity::find()
@@ -771,9 +820,10 @@ pub async fn get_portfolio_holdings(
         .collect();
 
     let accounts = accounts::Entity::find()
-        .filter(accounts::Column::Id.is_in(account_ids))
+        .filter(accounts::Column::Id.is_in(account_ids.clone()))
         .all(&db)
         .await?;
+    let account_names: HashMap<Uuid, String> = accounts.iter().map(|a| (a.id, a.name.clone())).collect();
 
     // Step 1: Aggregate holdings by asset symbol, collecting account details
     // Use a temporary structure that stores per-account details
@@ -811,6 +861,9 @@ pub async fn get_portfolio_holdings(
                     );
                     continue;
                 }
+                if settings.excludes_asset(&holding.asset) {
+                    continue;
+                }
 
                 let entry = holdings_by_symbol.entry(holding.asset.clone()).or_insert_with(|| {
                     HoldingAggregate {
@@ -846,6 +899,7 @@ pub async fn get_portfolio_holdings(
                     decimals: holding.decimals,
                     // normalized_quantity mirrors quantity since it is already normalized
                     normalized_quantity: Some(holding.quantity.clone()),
+                    holding_source: holding.source().to_string(),
                 });
             }
         }
@@ -856,45 +910,55 @@ pub async fn get_portfolio_holdings(
     use crate::helpers::asset_identity::{AssetIdentityNormalizer, NormalizationResult};
     
     let normalizer = AssetIdentityNormalizer::new(db.clone());
+    let pendle_quotes = load_pendle_quotes(&db).await?;
     let mut holdings: Vec<AssetHolding> = Vec::new();
 
     for (symbol, aggregate) in holdings_by_symbol.into_iter() {
         // Normalize the asset symbol to get canonical asset identity
-        let normalization_result = normalizer.normalize_from_symbol(&symbol).await;
-        
-        let (canonical_symbol, price_usd) = match normalization_result {
-            NormalizationResult::Mapped(asset_identity) => {
-                // Successfully mapped - now get the latest price
-                let latest_price = asset_prices::Entity::find()
-                    .filter(asset_prices::Column::AssetId.eq(asset_identity.asset_id))
-                    .order_by_desc(asset_prices::Column::Timestamp)
-                    .one(&db)
-                    .await?;
-
-                if let Some(price) = latest_price {
-                    let price_f64 = price.price_usd.to_f64().unwrap_or(0.0);
-                    (asset_identity.symbol, price_f64)
-                } else {
-                    // Asset found but no price available
+        let (canonical_symbol, price_usd) = if let Some(quote) = pendle_quotes.get(&symbol) {
+            // Pendle PT/YT tokens are priced from the Pendle API and keep their Pendle symbol
+            let pendle_symbol = symbol.rsplit_once('-').map_or(symbol.as_str(), |(s, _)| s);
+            (pendle_symbol.to_string(), quote.price_usd.and_then(|p| p.to_f64()).unwrap_or(0.0))
+        } else {
+            match normalizer.normalize_from_symbol(&symbol).await {
+                NormalizationResult::Mapped(asset_identity) => {
+                    // Successfully mapped - now get the latest price
+                    let latest_price = asset_prices::Entity::find()
+                        .filter(asset_prices::Column::AssetId.eq(asset_identity.asset_id))
+                        .order_by_desc(asset_prices::Column::Timestamp)
+                        .one(&db)
+                        .await?;
+
+                    if let Some(price) = latest_price {
+                        let price_f64 = price.price_usd.to_f64().unwrap_or(0.0);
+                        (asset_identity.symbol, price_f64)
+                    } else {
+                        // Asset found but no price available
+                        tracing::warn!(
+                            "No price found for asset '{}' ({})",
+                            asset_identity.symbol,
+                            asset_identity.asset_id
+                        );
+                        (asset_identity.symbol, 0.0)
+                    }
+                }
+                NormalizationResult::Unknown { original_identifier, context, .. } => {
+                    // Could not normalize the asset - use original symbol with 0 price
                     tracing::warn!(
-                        "No price found for asset '{}' ({})",
-                        asset_identity.symbol,
-                        asset_identity.asset_id
+                        "Could not normalize asset '{}': {}",
+                        original_identifier,
+                        context
                     );
-                    (asset_identity.symbol, 0.0)
+                    (symbol.clone(), 0.0)
                 }
             }
-            NormalizationResult::Unknown { original_identifier, context, .. } => {
-                // Could not normalize the asset - use original symbol with 0 price
-                tracing::warn!(
-                    "Could not normalize asset '{}': {}",
-                    original_identifier,
-                    context
-                );
-                (symbol.clone(), 0.0)
-            }
         };
 
+        // Excluded assets may also be listed under their canonical symbol
+        if settings.excludes_asset(&canonical_symbol) {
+            continue;
+        }
+
         // Extract chain label from the raw symbol (e.g., "ETH-ethereum" → Some("ethereum"))
         let chain = extract_chain_suffix(&symbol);
 
@@ -914,10 +978,48 @@ pub async fn get_portfolio_holdings(
             normalized_quantity: Some(total_quantity_str),
             price_usd,
             value_usd,
+            price: fx.convert(price_usd),
+            value: fx.convert(value_usd),
             accounts: aggregate.account_details,
         });
     }
 
+    // Optional NFT bucket, valued at collection floor
+    if settings.include_nfts && !settings.excludes_asset(NFT_BUCKET_ASSET) {
+        if let Some(bucket) = value_account_nfts(&db, &account_ids).await? {
+            let quantity = bucket.quantity.to_string();
+            let value_usd = bucket.value_usd.to_f64().unwrap_or(0.0);
+            holdings.push(AssetHolding {
+                asset: NFT_BUCKET_ASSET.to_string(),
+                chain: None,
+                total_quantity: quantity.clone(),
+                total_available: quantity.clone(),
+                total_frozen: "0".to_string(),
+                decimals: None,
+                normalized_quantity: Some(quantity),
+                // Tokens are valued one by one; there is no single unit price
+                price_usd: 0.0,
+                value_usd,
+                price: 0.0,
+                value: fx.convert(value_usd),
+                accounts: bucket
+                    .by_account
+                    .iter()
+                    .map(|(account_id, (qty, _))| AccountHoldingDetail {
+                        account_id: *account_id,
+                        account_name: account_names.get(account_id).cloned().unwrap_or_default(),
+                        quantity: qty.to_string(),
+                        available: qty.to_string(),
+                        frozen: "0".to_string(),
+                        decimals: None,
+                        normalized_quantity: Some(qty.to_string()),
+                        holding_source: NFT_HOLDING_SOURCE.to_string(),
+                    })
+                    .collect(),
+            });
+        }
+    }
+
     // Sort by value descending (highest value first)
     holdings.sort_by(|a, b| {
         b.value_usd
@@ -934,6 +1036,7 @@ pub async fn get_portfolio_holdings(
             asset: h.asset.clone(),
             chain: h.chain.clone(),
             value_usd: h.value_usd,
+            value: h.value,
             percentage: if total_value_usd > 0.0 {
                 (h.value_usd / total_value_usd) * 100.0
             } else {
@@ -945,6 +1048,8 @@ pub async fn get_portfolio_holdings(
     Ok(Json(PortfolioHoldingsResponse {
         portfolio_id: id,
         total_value_usd,
+        total_value: fx.convert(total_value_usd),
+        currency: fx.info(),
         holdings,
         allocation,
         as_of: chrono::Utc::now().to_rfc3339(),
@@ -955,17 +1060,78 @@ pub async fn get_portfolio_holdings(
 
 // Use domain struct for AllocationHolding to ensure type safety
 pub use crate::domain::AllocationItem as AllocationHolding;
+pub use crate::domain::{
+    AssetExposure, CategoryExposure, ChainExposure, ConcentrationLevel, ConcentrationRisk, GuardrailStatus,
+    GuardrailViolation, MaturityBucket, PriceStalenessWarning, ProposedOrder, Severity, StablecoinExposure,
+    VenueExposure,
+};
 
 #[derive(Debug, Serialize, Deserialize, ToSchema)]
 pub struct ConstructAllocationResponse {
     /// Portfolio ID
     pub portfolio_id: Uuid,
-    /// Total portfolio value in USD (excludes unpriced assets)
+    /// Total portfolio value in USD (excludes unpriced assets); net or gross of debt per the
+    /// portfolio's `debt_mode` setting
     pub total_value_usd: f64,
+    /// Value of the holdings before subtracting borrowed amounts
+    pub gross_value_usd: f64,
+    /// Value of borrowed amounts (positive)
+    pub debt_usd: f64,
+    /// Gross value minus debt
+    pub net_value_usd: f64,
+    /// `total_value_usd` in the user's base currency
+    pub total_value: f64,
+    /// `gross_value_usd` in the user's base currency
+    pub gross_value: f64,
+    /// `debt_usd` in the user's base currency
+    pub debt: f64,
+    /// `net_value_usd` in the user's base currency
+    pub net_value: f64,
+    /// Base currency of the converted values and the FX rate used
+    #[serde(flatten)]
+    pub currency: CurrencyInfo,
     /// Per-asset breakdown with values and weights
     pub holdings: Vec<AllocationHolding>,
+    /// Exposure per underlying asset, counting wrapped and liquid staking tokens as the asset
+    /// they represent (e.g. wstETH as ETH)
+    pub exposure: Vec<AssetExposure>,
+    /// Value per asset category (see `category` on the holdings), largest first
+    pub categories: Vec<CategoryExposure>,
+    /// Value per chain, largest first; exchange holdings are "off_chain". Absent on allocations
+    /// constructed before the breakdown was stored
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub chains: Option<Vec<ChainExposure>>,
+    /// Value per venue (each exchange, or "self_custody"), largest first. Absent on allocations
+    /// constructed before the breakdown was stored
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub venues: Option<Vec<VenueExposure>>,
+    /// Share of value in stablecoins, checked against the portfolio's `stablecoin_min` guardrail
+    pub stablecoin: StablecoinExposure,
+    /// Concentration in few assets (HHI, top-3 weight, risk score), with the largest alt checked
+    /// against the portfolio's `max_alt_cap` guardrail
+    pub concentration: ConcentrationRisk,
+    /// Fixed-yield holdings (Pendle PT/YT tokens) grouped by maturity date, earliest first
+    pub fixed_yield: Vec<MaturityBucket>,
     /// Timestamp when allocation was computed
     pub as_of: String,
+    /// Value change over 24h / 7d / 30d against the snapshot series (only on GET allocation)
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub deltas: Option<ValueDeltas>,
+    /// Construction run that produced the allocation (see `GET /api/v1/portfolios/{id}/construction-runs`)
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub construction_run_id: Option<Uuid>,
+    /// Price row picked for each holding symbol after reconciling the price sources; absent on
+    /// allocations constructed before reconciliation
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub price_resolution: Option<BTreeMap<String, PriceResolution>>,
+    /// Guardrails the allocation breaches, with severity and suggested remediation; each is
+    /// also kept as a pending "guardrail_violation" recommendation
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub guardrail_violations: Vec<GuardrailViolation>,
+    /// Present when holdings priced with stale data (see `stale_price` on the holdings) make up
+    /// at least `PRICE_STALE_WARNING_WEIGHT` percent of the portfolio
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub price_staleness: Option<PriceStalenessWarning>,
 }
 
 /// Construct portfolio allocation
@@ -988,11 +1154,16 @@ pub async fn construct_portfolio_allocation(
     Extension(token): Extension<KeycloakToken<String>>,
     Path(id): Path<Uuid>,
 ) -> Result<Json<ConstructAllocationResponse>, ApiError> {
-    use crate::entities::{asset_prices, portfolio_allocations};
-    use sea_orm::{QueryOrder, Set};
+    use crate::entities::portfolio_allocations;
+    use sea_orm::Set;
+
+    let started_at = chrono::Utc::now();
+    let timer = std::time::Instant::now();
 
     let user = get_or_create_user(&db, &token).await?;
     let portfolio = check_portfolio_ownership(&db, id, user.id).await?;
+    let settings = PortfolioSettings::from_json(portfolio.settings.as_ref());
+    let guardrails = PortfolioGuardrails::from_json(portfolio.guardrails.as_ref());
 
     // Step 1: Get all accounts linked to this portfolio
     let portfolio_accounts = portfolio_accounts::Entity::find()
@@ -1010,10 +1181,13 @@ pub async fn construct_portfolio_allocation(
         .all(&db)
         .await?;
 
-    // Step 2: Aggregate holdings by asset across all accounts
+    // Step 2: Aggregate holdings by asset across all accounts, keeping the split per holding source
     let mut holdings_map: HashMap<String, Decimal> = HashMap::new();
+    let mut sources_map: HashMap<String, BTreeMap<String, Decimal>> = HashMap::new();
+    let mut venues_map: HashMap<String, BTreeMap<String, Decimal>> = HashMap::new();
 
     for account in &accounts_list {
+        let venue = account_venue(account);
         if let Some(holdings_json) = &account.holdings {
             let holdings: Vec<AccountHolding> = match serde_json::from_value(serde_json::Value::from(holdings_json.clone())) {
                 Ok(h) => h,
@@ -1027,61 +1201,160 @@ pub async fn construct_portfolio_allocation(
             };
             
             for holding in holdings {
-                if holding.asset.is_empty() {
+                if holding.asset.is_empty() || settings.excludes_asset(&holding.asset) {
                     continue;
                 }
 
                 let qty = holding.quantity_decimal();
                 let curr_qty = holdings_map.get(&holding.asset).copied().unwrap_or(Decimal::ZERO);
                 holdings_map.insert(holding.asset.clone(), curr_qty + qty);
+                *sources_map
+                    .entry(holding.asset.clone())
+                    .or_default()
+                    .entry(holding.source().to_string())
+                    .or_insert(Decimal::ZERO) += qty;
+                *venues_map
+                    .entry(holding.asset.clone())
+                    .or_default()
+                    .entry(venue.clone())
+                    .or_insert(Decimal::ZERO) += qty;
             }
         }
     }
 
     // Step 3: Normalize assets - get asset IDs from symbols using centralized normalization
     // Step 4: Join latest prices from asset_prices
-    use crate::helpers::asset_identity::{AssetIdentityNormalizer, NormalizationResult};
+    use crate::helpers::asset_identity::{
+        load_asset_categories, load_delisted_assets, AssetIdentityNormalizer, NormalizationResult,
+    };
     
     let normalizer = AssetIdentityNormalizer::new(db.clone());
+    let delisted_assets = load_delisted_assets(&db).await?;
+    let asset_categories = load_asset_categories(&db).await?;
+    let derivatives = load_derivative_assets(&db).await?;
+    let pendle_quotes = load_pendle_quotes(&db).await?;
+    let price_overrides = load_price_overrides(&db).await?;
     let mut allocation_holdings: Vec<AllocationHolding> = Vec::new();
+    let mut price_sources: Vec<PriceSource> = Vec::new();
+    let resolution_config = PriceResolutionConfig::from_env();
+    let mut price_resolution: BTreeMap<String, PriceResolution> = BTreeMap::new();
     let mut total_value = Decimal::ZERO;
 
     for (symbol, quantity) in holdings_map.iter() {
+        let mut category = None;
         // Normalize the asset symbol to get canonical asset identity
-        let normalization_result = normalizer.normalize_from_symbol(symbol).await;
-        
-        let (canonical_symbol, price_opt, unpriced) = match normalization_result {
-            NormalizationResult::Mapped(asset_identity) => {
-                // Successfully mapped - now get the latest price
-                let latest_price = asset_prices::Entity::find()
-                    .filter(asset_prices::Column::AssetId.eq(asset_identity.asset_id))
-                    .order_by_desc(asset_prices::Column::Timestamp)
-                    .one(&db)
-                    .await?;
-
-                if let Some(price) = latest_price {
-                    (asset_identity.symbol, Some(price.price_usd), false)
-                } else {
-                    // Asset found but no price available - mark as unpriced
+        let (canonical_symbol, price_opt, unpriced, price_as_of, delisted) = if let Some(quote) = pendle_quotes.get(symbol) {
+            // Pendle PT/YT tokens are priced from the Pendle API and keep their Pendle symbol
+            price_sources.push(PriceSource {
+                asset: symbol.clone(),
+                asset_id: None,
+                price_id: None,
+                price_usd: quote.price_usd.map(|p| p.to_string()),
+                source: Some(PENDLE_PRICE_SOURCE.to_string()),
+                priced_at: Some(quote.priced_at.clone()),
+                resolution: None,
+            });
+            let pendle_symbol = symbol.rsplit_once('-').map_or(symbol.as_str(), |(s, _)| s);
+            let priced_at = chrono::DateTime::parse_from_rfc3339(&quote.priced_at).ok().map(|t| t.with_timezone(&chrono::Utc));
+            (pendle_symbol.to_string(), quote.price_usd, quote.price_usd.is_none(), priced_at, false)
+        } else {
+            match normalizer.normalize_from_symbol(symbol).await {
+                NormalizationResult::Mapped(asset_identity) => {
+                    category = asset_categories.get(&asset_identity.asset_id).cloned();
+                    // Delisted assets keep their last known price
+                    let delisted = delisted_assets.contains(&asset_identity.asset_id);
+                    // An admin-pinned price source beats both the live stream and reconciliation
+                    let pinned_source = price_overrides.get(&asset_identity.asset_id).map(|o| o.source.as_str());
+                    let live_price = live_prices::fresh_price(&asset_identity.symbol).filter(|_| pinned_source.is_none());
+                    if let Some(live) = live_price {
+                        // A fresh price from the live stream beats the last collected one
+                        price_sources.push(PriceSource {
+                            asset: symbol.clone(),
+                            asset_id: Some(asset_identity.asset_id),
+                            price_id: None,
+                            price_usd: Some(live.price_usd.to_string()),
+                            source: Some(LIVE_PRICE_SOURCE.to_string()),
+                            priced_at: Some(live.updated_at.to_rfc3339()),
+                            resolution: None,
+                        });
+                        (asset_identity.symbol, Some(live.price_usd), false, Some(live.updated_at), delisted)
+                    } else {
+                        // Successfully mapped - now reconcile the latest price of each source
+                        let resolved = match pinned_source {
+                            Some(source) => {
+                                resolve_pinned_price(&db, asset_identity.asset_id, source, &resolution_config).await?
+                            }
+                            None => resolve_latest_price(&db, asset_identity.asset_id, &resolution_config).await?,
+                        };
+                        let latest_price = resolved.map(|(price, resolution)| {
+                            if resolution.resolution != RESOLUTION_PRIMARY && resolution.resolution != RESOLUTION_OVERRIDE {
+                                tracing::warn!(
+                                    "Price of '{}' resolved as {} from {} ({})",
+                                    symbol,
+                                    resolution.resolution,
+                                    resolution.source,
+                                    resolution.reason.as_deref().unwrap_or_default()
+                                );
+                            }
+                            price_resolution.insert(symbol.clone(), resolution);
+                            price
+                        });
+
+                        price_sources.push(PriceSource {
+                            asset: symbol.clone(),
+                            asset_id: Some(asset_identity.asset_id),
+                            price_id: latest_price.as_ref().map(|p| p.id),
+                            price_usd: latest_price.as_ref().map(|p| p.price_usd.to_string()),
+                            source: latest_price.as_ref().map(|p| p.source.clone()),
+                            priced_at: latest_price.as_ref().map(|p| p.timestamp.to_rfc3339()),
+                            resolution: price_resolution.get(symbol).map(|r| r.resolution.clone()),
+                        });
+
+                        if let Some(price) = latest_price {
+                            (
+                                asset_identity.symbol,
+                                Some(price.price_usd),
+                                false,
+                                Some(price.timestamp.with_timezone(&chrono::Utc)),
+                                delisted,
+                            )
+                        } else {
+                            // Asset found but no price available - mark as unpriced
+                            tracing::warn!(
+                                "No price found for asset '{}' ({})",
+                                asset_identity.symbol,
+                                asset_identity.asset_id
+                            );
+                            (asset_identity.symbol, None, true, None, delisted)
+                        }
+                    }
+                }
+                NormalizationResult::Unknown { original_identifier, context, .. } => {
+                    // Could not normalize the asset - mark as unpriced and use original symbol
                     tracing::warn!(
-                        "No price found for asset '{}' ({})",
-                        asset_identity.symbol,
-                        asset_identity.asset_id
+                        "Could not normalize asset '{}': {}",
+                        original_identifier,
+                        context
                     );
-                    (asset_identity.symbol, None, true)
+                    price_sources.push(PriceSource {
+                        asset: symbol.clone(),
+                        asset_id: None,
+                        price_id: None,
+                        price_usd: None,
+                        source: None,
+                        priced_at: None,
+                        resolution: None,
+                    });
+                    (symbol.clone(), None, true, None, false)
                 }
             }
-            NormalizationResult::Unknown { original_identifier, context, .. } => {
-                // Could not normalize the asset - mark as unpriced and use original symbol
-                tracing::warn!(
-                    "Could not normalize asset '{}': {}",
-                    original_identifier,
-                    context
-                );
-                (symbol.clone(), None, true)
-            }
         };
 
+        // Excluded assets may also be listed under their canonical symbol
+        if settings.excludes_asset(&canonical_symbol) {
+            continue;
+        }
+
         // Step 5: Compute value
         let value_usd = if let Some(price) = price_opt {
             let price_f64 = price.to_string().parse::<f64>().unwrap_or(0.0);
@@ -1098,9 +1371,24 @@ pub async fn construct_portfolio_allocation(
             0.0
         };
 
+        // Flag prices older than the staleness threshold; a delisted asset's last price is expected to be old
+        let stale_price =
+            !unpriced && !delisted && price_as_of.is_some_and(|t| resolution_config.is_stale(t, started_at));
+
         // Extract chain label from the raw symbol (e.g., "ETH-ethereum" → Some("ethereum"))
         let chain = extract_chain_suffix(symbol);
 
+        // Only break the quantity down when some of it is held outside spot (e.g. OKX Earn)
+        let quantity_by_source = sources_map
+            .remove(symbol)
+            .filter(|sources| sources.keys().any(|source| source != HOLDING_SOURCE_SPOT))
+            .map(|sources| sources.into_iter().map(|(source, q)| (source, q.to_string())).collect());
+
+        // Wrapped and liquid staking tokens also count towards their underlying asset
+        let underlying = derivatives
+            .resolve(&canonical_symbol, symbol)
+            .map(|(asset, rate)| (asset.to_string(), (*quantity * rate).normalize().to_string()));
+
         allocation_holdings.push(AllocationHolding {
             asset: canonical_symbol,
             chain,
@@ -1109,17 +1397,60 @@ pub async fn construct_portfolio_allocation(
             weight: 0.0, // Will be computed after we know total
             price_usd: price_opt.map(|p| p.to_string().parse::<f64>().unwrap_or(0.0)),
             unpriced,
+            stale_price,
+            delisted,
+            price_as_of: price_as_of.filter(|_| !unpriced).map(|t| t.to_rfc3339()),
+            quantity_by_source,
+            quantity_by_venue: venues_map
+                .remove(symbol)
+                .map(|venues| venues.into_iter().map(|(venue, q)| (venue, q.to_string())).collect()),
+            underlying_quantity: underlying.as_ref().map(|(_, q)| q.clone()),
+            underlying_asset: underlying.map(|(asset, _)| asset),
+            maturity: pendle_quotes.get(symbol).map(|quote| quote.maturity.to_string()),
+            category,
+            value: None,
         });
     }
 
-    // Compute weights for priced assets only
-    let total_value_f64 = total_value.to_string().parse::<f64>().unwrap_or(0.0);
-    for holding in &mut allocation_holdings {
-        if !holding.unpriced && total_value_f64 > 0.0 {
-            holding.weight = (holding.value_usd / total_value_f64) * 100.0;
+    // Optional NFT bucket, valued at collection floor; unpriced when no NFT has a usable floor
+    if settings.include_nfts && !settings.excludes_asset(NFT_BUCKET_ASSET) {
+        let account_ids: Vec<Uuid> = accounts_list.iter().map(|a| a.id).collect();
+        if let Some(bucket) = value_account_nfts(&db, &account_ids).await? {
+            let unpriced = bucket.value_usd.is_zero();
+            total_value += bucket.value_usd;
+            allocation_holdings.push(AllocationHolding {
+                asset: NFT_BUCKET_ASSET.to_string(),
+                chain: None,
+                quantity: bucket.quantity.to_string(),
+                price_usd: None,
+                value_usd: bucket.value_usd.to_f64().unwrap_or(0.0),
+                weight: 0.0,
+                unpriced,
+                stale_price: false,
+                delisted: false,
+                price_as_of: None,
+                quantity_by_source: None,
+                // NFTs are only synced from wallets
+                quantity_by_venue: Some(BTreeMap::from([(
+                    VENUE_SELF_CUSTODY.to_string(),
+                    bucket.quantity.to_string(),
+                )])),
+                underlying_asset: None,
+                underlying_quantity: None,
+                maturity: None,
+                category: None,
+                value: None,
+            });
         }
     }
 
+    // Compute weights for priced assets only; in gross mode the total leaves debt out
+    let debt = weigh_allocation(&mut allocation_holdings, settings.debt_mode);
+    if settings.debt_mode == DebtMode::Gross {
+        total_value += Decimal::from_f64(debt.debt_usd).unwrap_or(Decimal::ZERO);
+    }
+    let total_value_f64 = total_value.to_string().parse::<f64>().unwrap_or(0.0);
+
     // Sort by value descending
     allocation_holdings.sort_by(|a, b| {
         b.value_usd
@@ -1132,9 +1463,38 @@ pub async fn construct_portfolio_allocation(
     // Step 6: Persist the allocation (UPSERT to maintain one row per portfolio)
     let allocation_json = serde_json::to_value(&allocation_holdings)
         .map_err(|e| ApiError::BadRequest(format!("Failed to serialize allocation: {}", e)))?;
+    let price_resolution_json = serde_json::to_value(&price_resolution)
+        .map_err(|e| ApiError::BadRequest(format!("Failed to serialize price resolution: {}", e)))?;
+    let chains = exposure_by_chain(&allocation_holdings);
+    let venues = exposure_by_venue(&allocation_holdings);
+    let chain_breakdown_json = serde_json::to_value(&chains)
+        .map_err(|e| ApiError::BadRequest(format!("Failed to serialize chain breakdown: {}", e)))?;
+    let venue_breakdown_json = serde_json::to_value(&venues)
+        .map_err(|e| ApiError::BadRequest(format!("Failed to serialize venue breakdown: {}", e)))?;
 
     // Use a transaction to ensure atomic UPSERT
     let txn = db.begin().await?;
+
+    // Record the run first so the allocation can point at it
+    let construction_run_id = record_construction_run(
+        &txn,
+        NewConstructionRun {
+            portfolio_id: id,
+            trigger: TRIGGER_API,
+            triggered_by: Some(user.id),
+            inputs: ConstructionInputs::new(
+                accounts_list.iter().map(|a| a.id).collect(),
+                holdings_map,
+                portfolio.settings.clone(),
+            ),
+            price_sources,
+            total_value_usd: total_value,
+            holdings_count: allocation_holdings.len(),
+            started_at,
+            duration_ms: timer.elapsed().as_millis() as i64,
+        },
+    )
+    .await?;
     
     // Try to find existing allocation
     let existing_allocation = portfolio_allocations::Entity::find()
@@ -1148,6 +1508,10 @@ pub async fn construct_portfolio_allocation(
         allocation_active.as_of = Set(as_of);
         allocation_active.total_value_usd = Set(total_value);
         allocation_active.holdings = Set(allocation_json);
+        allocation_active.construction_run_id = Set(Some(construction_run_id));
+        allocation_active.price_resolution = Set(Some(price_resolution_json));
+        allocation_active.chain_breakdown = Set(Some(chain_breakdown_json));
+        allocation_active.venue_breakdown = Set(Some(venue_breakdown_json));
         allocation_active.update(&txn).await?;
     } else {
         // Insert new allocation - if unique constraint violation occurs,
@@ -1162,6 +1526,10 @@ pub async fn construct_portfolio_allocation(
             total_value_usd: Set(total_value),
             holdings: Set(allocation_json),
             created_at: ActiveValue::NotSet,
+            construction_run_id: Set(Some(construction_run_id)),
+            price_resolution: Set(Some(price_resolution_json.clone())),
+            chain_breakdown: Set(Some(chain_breakdown_json.clone())),
+            venue_breakdown: Set(Some(venue_breakdown_json.clone())),
         };
         
         match new_allocation.insert(&txn).await {
@@ -1183,12 +1551,22 @@ pub async fn construct_portfolio_allocation(
                 allocation_active.as_of = Set(as_of);
                 allocation_active.total_value_usd = Set(total_value);
                 allocation_active.holdings = Set(allocation_json_retry);
+                allocation_active.construction_run_id = Set(Some(construction_run_id));
+                allocation_active.price_resolution = Set(Some(price_resolution_json));
+                allocation_active.chain_breakdown = Set(Some(chain_breakdown_json));
+                allocation_active.venue_breakdown = Set(Some(venue_breakdown_json));
                 allocation_active.update(&txn).await?;
             },
             Err(e) => return Err(ApiError::DatabaseError(e)),
         }
     }
 
+    let violations = guardrail_violations(
+        &allocation_holdings,
+        &target_weights(portfolio.target_allocation.as_ref()),
+        &guardrails,
+    );
+
     // Update portfolio's last_constructed_at
     let mut portfolio_active: portfolios::ActiveModel = portfolio.into();
     portfolio_active.last_constructed_at = Set(Some(as_of));
@@ -1197,11 +1575,51 @@ pub async fn construct_portfolio_allocation(
     // Commit transaction
     txn.commit().await?;
 
+    // Guardrail violations become recommendations; the allocation stands even if this fails
+    match sync_guardrail_recommendations(&db, id, &violations, Some(construction_run_id)).await {
+        Ok(changes) if !changes.created.is_empty() => {
+            let db_bg = db.clone();
+            tokio::spawn(async move {
+                if let Err(e) = deliver_recommendations(&db_bg, id, &changes.created).await {
+                    tracing::warn!("Failed to deliver guardrail recommendations of portfolio {}: {}", id, e);
+                }
+            });
+        }
+        Ok(_) => {}
+        Err(e) => tracing::warn!("Failed to record guardrail recommendations of portfolio {}: {}", id, e),
+    }
+
+    // Converted values are only part of the response, never of the stored allocation
+    let fx = load_user_fx_rates(&db, &user).await?;
+    for holding in &mut allocation_holdings {
+        holding.value = Some(fx.convert(holding.value_usd));
+    }
+
     Ok(Json(ConstructAllocationResponse {
         portfolio_id: id,
         total_value_usd: total_value_f64,
+        gross_value_usd: debt.gross_value_usd,
+        debt_usd: debt.debt_usd,
+        net_value_usd: debt.net_value_usd,
+        total_value: fx.convert(total_value_f64),
+        gross_value: fx.convert(debt.gross_value_usd),
+        debt: fx.convert(debt.debt_usd),
+        net_value: fx.convert(debt.net_value_usd),
+        currency: fx.info(),
+        exposure: exposure_by_underlying(&allocation_holdings),
+        categories: exposure_by_category(&allocation_holdings),
+        chains: Some(chains),
+        venues: Some(venues),
+        stablecoin: stablecoin_exposure(&allocation_holdings, guardrails.stablecoin_min),
+        concentration: allocation_concentration(&allocation_holdings, guardrails.max_alt_cap),
+        fixed_yield: fixed_yield_by_maturity(&allocation_holdings),
+        price_staleness: price_staleness_warning(&allocation_holdings, resolution_config.stale_warning_weight),
+        guardrail_violations: violations,
         holdings: allocation_holdings,
         as_of: as_of.to_rfc3339(),
+        deltas: None,
+        construction_run_id: Some(construction_run_id),
+        price_resolution: Some(price_resolution),
     }))
 }
 
@@ -1228,7 +1646,8 @@ pub async fn get_portfolio_allocation(
     use crate::entities::portfolio_allocations;
 
     let user = get_or_create_user(&db, &token).await?;
-    check_portfolio_ownership(&db, id, user.id).await?;
+    let portfolio = check_portfolio_ownership(&db, id, user.id).await?;
+    let guardrails = PortfolioGuardrails::from_json(portfolio.guardrails.as_ref());
 
     // Find the latest allocation for this portfolio
     let allocation = portfolio_allocations::Entity::find()
@@ -1239,18 +1658,377 @@ pub async fn get_portfolio_allocation(
     let allocation = allocation.ok_or(ApiError::NotFound)?;
 
     // Deserialize holdings from JSON
-    let holdings: Vec<AllocationHolding> = serde_json::from_value(allocation.holdings.clone())
+    let mut holdings: Vec<AllocationHolding> = serde_json::from_value(allocation.holdings.clone())
         .map_err(|e| ApiError::BadRequest(format!("Failed to deserialize allocation: {}", e)))?;
 
+    let fx = load_user_fx_rates(&db, &user).await?;
+    for holding in &mut holdings {
+        holding.value = Some(fx.convert(holding.value_usd));
+    }
+
     let total_value_f64 = allocation.total_value_usd
         .to_f64()
         .ok_or_else(|| ApiError::BadRequest("Failed to convert total value to f64".to_string()))?;
 
+    let deltas = load_value_deltas(
+        &db,
+        id,
+        allocation.as_of.date_naive(),
+        allocation.total_value_usd,
+    )
+    .await?;
+
+    let debt = DebtSummary::of_allocation(&holdings);
+    let price_resolution = allocation
+        .price_resolution
+        .and_then(|json| serde_json::from_value(json).ok());
+
     Ok(Json(ConstructAllocationResponse {
         portfolio_id: id,
         total_value_usd: total_value_f64,
+        gross_value_usd: debt.gross_value_usd,
+        debt_usd: debt.debt_usd,
+        net_value_usd: debt.net_value_usd,
+        total_value: fx.convert(total_value_f64),
+        gross_value: fx.convert(debt.gross_value_usd),
+        debt: fx.convert(debt.debt_usd),
+        net_value: fx.convert(debt.net_value_usd),
+        currency: fx.info(),
+        exposure: exposure_by_underlying(&holdings),
+        categories: exposure_by_category(&holdings),
+        chains: allocation.chain_breakdown.and_then(|json| serde_json::from_value(json).ok()),
+        venues: allocation.venue_breakdown.and_then(|json| serde_json::from_value(json).ok()),
+        stablecoin: stablecoin_exposure(&holdings, guardrails.stablecoin_min),
+        concentration: allocation_concentration(&holdings, guardrails.max_alt_cap),
+        fixed_yield: fixed_yield_by_maturity(&holdings),
+        price_staleness: price_staleness_warning(&holdings, PriceResolutionConfig::from_env().stale_warning_weight),
+        guardrail_violations: guardrail_violations(
+            &holdings,
+            &target_weights(portfolio.target_allocation.as_ref()),
+            &guardrails,
+        ),
         holdings,
         as_of: allocation.as_of.to_rfc3339(),
+        deltas: Some(deltas),
+        construction_run_id: allocation.construction_run_id,
+        price_resolution,
+    }))
+}
+
+#[derive(Debug, Serialize, Deserialize, ToSchema)]
+pub struct CategoryAllocationResponse {
+    pub portfolio_id: Uuid,
+    /// Total portfolio value in USD of the allocation
+    pub total_value_usd: f64,
+    /// Value per category, largest first
+    pub categories: Vec<CategoryExposure>,
+    /// Timestamp when the allocation was computed
+    pub as_of: String,
+}
+
+/// Get portfolio allocation by category
+///
+/// Breaks the latest allocation down by asset category (e.g. "l1", "defi", "stablecoin");
+/// assets without a category are grouped as "uncategorized". Categories are taken from the
+/// assets when the allocation is constructed.
+#[utoipa::path(
+    get,
+    path = "/api/v1/portfolios/{id}/allocation/by-category",
+    params(
+        ("id" = Uuid, Path, description = "Portfolio ID")
+    ),
+    responses(
+        (status = 200, description = "Latest portfolio allocation by category", body = CategoryAllocationResponse),
+        (status = 401, description = "Unauthorized"),
+        (status = 403, description = "Forbidden"),
+        (status = 404, description = "Portfolio or allocation not found")
+    ),
+    tag = "portfolios"
+)]
+pub async fn get_portfolio_allocation_by_category(
+    State(db): State<DatabaseConnection>,
+    Extension(token): Extension<KeycloakToken<String>>,
+    Path(id): Path<Uuid>,
+) -> Result<Json<CategoryAllocationResponse>, ApiError> {
+    use crate::entities::portfolio_allocations;
+
+    let user = get_or_create_user(&db, &token).await?;
+    check_portfolio_ownership(&db, id, user.id).await?;
+
+    let allocation = portfolio_allocations::Entity::find()
+        .filter(portfolio_allocations::Column::PortfolioId.eq(id))
+        .one(&db)
+        .await?
+        .ok_or(ApiError::NotFound)?;
+
+    let holdings: Vec<AllocationHolding> = serde_json::from_value(allocation.holdings)
+        .map_err(|e| ApiError::BadRequest(format!("Failed to deserialize allocation: {}", e)))?;
+
+    Ok(Json(CategoryAllocationResponse {
+        portfolio_id: id,
+        total_value_usd: allocation.total_value_usd.to_f64().unwrap_or(0.0),
+        categories: exposure_by_category(&holdings),
+        as_of: allocation.as_of.to_rfc3339(),
+    }))
+}
+
+// === Construction run DTOs ===
+
+#[derive(Debug, Deserialize, ToSchema)]
+pub struct ListConstructionRunsQuery {
+    /// Page size (enables pagination; default 100 when only `cursor` is given)
+    pub limit: Option<u64>,
+    /// Cursor from a previous page's `next_cursor`
+    pub cursor: Option<String>,
+}
+
+#[derive(Debug, Serialize, Deserialize, ToSchema)]
+pub struct ConstructionRunResponse {
+    pub id: Uuid,
+    pub portfolio_id: Uuid,
+    /// What triggered the run (e.g. "api")
+    pub trigger: String,
+    /// User who triggered the run
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub triggered_by: Option<Uuid>,
+    /// SHA-256 of the run's inputs; equal digests mean equal quantities and settings
+    pub inputs_digest: String,
+    pub total_value_usd: String,
+    pub holdings_count: i32,
+    pub duration_ms: i64,
+    pub started_at: String,
+}
+
+impl From<&construction_runs::Model> for ConstructionRunResponse {
+    fn from(model: &construction_runs::Model) -> Self {
+        Self {
+            id: model.id,
+            portfolio_id: model.portfolio_id,
+            trigger: model.trigger.clone(),
+            triggered_by: model.triggered_by,
+            inputs_digest: model.inputs_digest.clone(),
+            total_value_usd: model.total_value_usd.to_string(),
+            holdings_count: model.holdings_count,
+            duration_ms: model.duration_ms,
+            started_at: model.started_at.to_rfc3339(),
+        }
+    }
+}
+
+#[derive(Debug, Serialize, Deserialize, ToSchema)]
+pub struct ListConstructionRunsResponse {
+    pub portfolio_id: Uuid,
+    /// Runs, newest first
+    pub runs: Vec<ConstructionRunResponse>,
+    /// Cursor for the next page; absent on the last page
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub next_cursor: Option<String>,
+}
+
+/// A construction run with everything needed to reproduce its result
+#[derive(Debug, Serialize, Deserialize, ToSchema)]
+pub struct ConstructionRunDetailResponse {
+    #[serde(flatten)]
+    pub run: ConstructionRunResponse,
+    /// Quantities and settings the allocation was computed from
+    pub inputs: ConstructionInputs,
+    /// Price row used for each holding
+    pub price_sources: Vec<PriceSource>,
+}
+
+/// List construction runs of a portfolio
+///
+/// Every `construct` call records a run. Allocations, snapshots and recommendations carry the
+/// `construction_run_id` of the run that produced them.
+#[utoipa::path(
+    get,
+    path = "/api/v1/portfolios/{id}/construction-runs",
+    params(
+        ("id" = Uuid, Path, description = "Portfolio ID"),
+        ("limit" = Option<u64>, Query, description = "Page size (1-1000)"),
+        ("cursor" = Option<String>, Query, description = "Opaque cursor from a previous page's next_cursor")
+    ),
+    responses(
+        (status = 200, description = "Construction runs, newest first", body = ListConstructionRunsResponse),
+        (status = 400, description = "Invalid cursor or limit"),
+        (status = 401, description = "Unauthorized"),
+        (status = 403, description = "Forbidden"),
+        (status = 404, description = "Portfolio not found")
+    ),
+    tag = "portfolios"
+)]
+pub async fn list_construction_runs(
+    State(db): State<DatabaseConnection>,
+    Extension(token): Extension<KeycloakToken<String>>,
+    Path(id): Path<Uuid>,
+    Query(query): Query<ListConstructionRunsQuery>,
+) -> Result<Json<ListConstructionRunsResponse>, ApiError> {
+    let user = get_or_create_user(&db, &token).await?;
+    check_portfolio_ownership(&db, id, user.id).await?;
+
+    let page = PageRequest::from_query(query.limit, query.cursor.as_deref())?;
+
+    let mut run_query = construction_runs::Entity::find()
+        .filter(construction_runs::Column::PortfolioId.eq(id))
+        .order_by_desc(construction_runs::Column::StartedAt)
+        .order_by_desc(construction_runs::Column::Id)
+        .limit(page.fetch_limit());
+
+    if let Some(after) = &page.after {
+        run_query = run_query.filter(keyset_before(
+            construction_runs::Column::StartedAt,
+            construction_runs::Column::Id,
+            after.timestamp,
+            after.id,
+        ));
+    }
+
+    let (rows, next_cursor) = finish_page(run_query.all(&db).await?, &page, |r| {
+        Cursor::new(r.started_at.with_timezone(&chrono::Utc), r.id)
+    });
+
+    Ok(Json(ListConstructionRunsResponse {
+        portfolio_id: id,
+        runs: rows.iter().map(ConstructionRunResponse::from).collect(),
+        next_cursor,
+    }))
+}
+
+/// Get a construction run with its inputs and price sources
+#[utoipa::path(
+    get,
+    path = "/api/v1/portfolios/{id}/construction-runs/{run_id}",
+    params(
+        ("id" = Uuid, Path, description = "Portfolio ID"),
+        ("run_id" = Uuid, Path, description = "Construction run ID")
+    ),
+    responses(
+        (status = 200, description = "Construction run", body = ConstructionRunDetailResponse),
+        (status = 401, description = "Unauthorized"),
+        (status = 403, description = "Forbidden"),
+        (status = 404, description = "Portfolio or run not found")
+    ),
+    tag = "portfolios"
+)]
+pub async fn get_construction_run(
+    State(db): State<DatabaseConnection>,
+    Extension(token): Extension<KeycloakToken<String>>,
+    Path((id, run_id)): Path<(Uuid, Uuid)>,
+) -> Result<Json<ConstructionRunDetailResponse>, ApiError> {
+    let user = get_or_create_user(&db, &token).await?;
+    check_portfolio_ownership(&db, id, user.id).await?;
+
+    let run = construction_runs::Entity::find_by_id(run_id)
+        .filter(construction_runs::Column::PortfolioId.eq(id))
+        .one(&db)
+        .await?
+        .ok_or(ApiError::NotFound)?;
+
+    let inputs = serde_json::from_value(run.inputs.clone())
+        .map_err(|e| ApiError::InternalServerError(format!("Failed to deserialize run inputs: {}", e)))?;
+    let price_sources = serde_json::from_value(run.price_sources.clone())
+        .map_err(|e| ApiError::InternalServerError(format!("Failed to deserialize price sources: {}", e)))?;
+
+    Ok(Json(ConstructionRunDetailResponse {
+        run: ConstructionRunResponse::from(&run),
+        inputs,
+        price_sources,
+    }))
+}
+
+// === Positions ===
+
+#[derive(Debug, Serialize, Deserialize, ToSchema)]
+pub struct PortfolioPositionResponse {
+    pub account_id: Uuid,
+    pub account_name: String,
+    #[serde(flatten)]
+    pub position: PositionResponse,
+}
+
+#[derive(Debug, Serialize, Deserialize, ToSchema)]
+pub struct PortfolioPositionsResponse {
+    pub portfolio_id: Uuid,
+    /// Open positions of the portfolio's accounts
+    pub positions: Vec<PortfolioPositionResponse>,
+    /// Unrealized PnL summed per margin currency (e.g. {"USDC": "-12.5"})
+    pub unrealized_pnl_by_currency: BTreeMap<String, String>,
+    pub total_count: usize,
+}
+
+/// List a portfolio's open positions
+///
+/// Open derivatives and perp DEX positions (e.g. OKX swaps, Hyperliquid, GMX v2) of every
+/// account in the portfolio as of each account's last sync, with leverage and unrealized PnL.
+#[utoipa::path(
+    get,
+    path = "/api/v1/portfolios/{id}/positions",
+    params(
+        ("id" = Uuid, Path, description = "Portfolio ID")
+    ),
+    responses(
+        (status = 200, description = "Open positions", body = PortfolioPositionsResponse),
+        (status = 401, description = "Unauthorized"),
+        (status = 403, description = "Forbidden"),
+        (status = 404, description = "Portfolio not found")
+    ),
+    tag = "portfolios"
+)]
+pub async fn list_portfolio_positions(
+    State(db): State<DatabaseConnection>,
+    Extension(token): Extension<KeycloakToken<String>>,
+    Path(id): Path<Uuid>,
+) -> Result<Json<PortfolioPositionsResponse>, ApiError> {
+    let user = get_or_create_user(&db, &token).await?;
+    check_portfolio_ownership(&db, id, user.id).await?;
+
+    let account_ids: Vec<Uuid> = portfolio_accounts::Entity::find()
+        .filter(portfolio_accounts::Column::PortfolioId.eq(id))
+        .all(&db)
+        .await?
+        .into_iter()
+        .map(|pa| pa.account_id)
+        .collect();
+
+    let account_names: HashMap<Uuid, String> = accounts::Entity::find()
+        .filter(accounts::Column::Id.is_in(account_ids.clone()))
+        .all(&db)
+        .await?
+        .into_iter()
+        .map(|a| (a.id, a.name))
+        .collect();
+
+    let rows = positions::Entity::find()
+        .filter(positions::Column::AccountId.is_in(account_ids))
+        .order_by_asc(positions::Column::Instrument)
+        .order_by_asc(positions::Column::Side)
+        .all(&db)
+        .await?;
+
+    let mut pnl_by_currency: BTreeMap<String, Decimal> = BTreeMap::new();
+    let positions: Vec<PortfolioPositionResponse> = rows
+        .into_iter()
+        .map(|row| {
+            if let (Some(pnl), Some(currency)) = (row.unrealized_pnl, &row.margin_currency) {
+                *pnl_by_currency.entry(currency.clone()).or_insert(Decimal::ZERO) += pnl;
+            }
+            PortfolioPositionResponse {
+                account_id: row.account_id,
+                account_name: account_names.get(&row.account_id).cloned().unwrap_or_default(),
+                position: row.into(),
+            }
+        })
+        .collect();
+    let total_count = positions.len();
+
+    Ok(Json(PortfolioPositionsResponse {
+        portfolio_id: id,
+        positions,
+        unrealized_pnl_by_currency: pnl_by_currency
+            .into_iter()
+            .map(|(currency, pnl)| (currency, pnl.normalize().to_string()))
+            .collect(),
+        total_count,
     }))
 }
 
@@ -1279,6 +2057,10 @@ pub fn create_router() -> Router<DatabaseConnection> {
             "/api/v1/portfolios/{id}/holdings",
             get(get_portfolio_holdings),
         )
+        .route(
+            "/api/v1/portfolios/{id}/positions",
+            get(list_portfolio_positions),
+        )
         .route(
             "/api/v1/portfolios/{id}/construct",
             axum::routing::post(construct_portfolio_allocation),
@@ -1287,4 +2069,16 @@ pub fn create_router() -> Router<DatabaseConnection> {
             "/api/v1/portfolios/{id}/allocation",
             get(get_portfolio_allocation),
         )
+        .route(
+            "/api/v1/portfolios/{id}/allocation/by-category",
+            get(get_portfolio_allocation_by_category),
+        )
+        .route(
+            "/api/v1/portfolios/{id}/construction-runs",
+            get(list_construction_runs),
+        )
+        .route(
+            "/api/v1/portfolios/{id}/construction-runs/{run_id}",
+            get(get_construction_run),
+        )
 }
@@ -0,0 +1,175 @@
+use axum::{
+    extract::{Extension, State},
+    response::Json,
+    routing::get,
+    Router,
+};
+use axum_keycloak_auth::decode::KeycloakToken;
+use chrono::Utc;
+use sea_orm::{
+    ActiveModelTrait, ActiveValue, ColumnTrait, DatabaseConnection, EntityTrait, PaginatorTrait,
+    QueryFilter,
+};
+use serde::{Deserialize, Serialize};
+use utoipa::ToSchema;
+
+use super::error::ApiError;
+pub use crate::domain::NotificationChannels;
+use crate::entities::{fx_rates, users};
+use crate::helpers::auth::get_or_create_user;
+use crate::helpers::fx::{is_currency_code, CURRENCY_USD};
+use crate::helpers::tax_lots::LotMethod;
+use crate::helpers::webhook::resolve_webhook_url;
+use crate::jobs::tax_lots::rebuild_user_lots;
+
+// === Request/Response DTOs ===
+
+#[derive(Debug, Serialize, Deserialize, ToSchema)]
+pub struct PreferencesResponse {
+    /// ISO 4217 code that holdings, allocation and snapshot values are converted to
+    pub base_currency: String,
+    /// Order in which tax lots are disposed: "fifo", "lifo" or "hifo"
+    pub tax_lot_method: String,
+    /// Channels new recommendations are delivered to
+    pub notification_channels: NotificationChannels,
+}
+
+impl From<users::Model> for PreferencesResponse {
+    fn from(user: users::Model) -> Self {
+        Self {
+            notification_channels: NotificationChannels::from_json(user.notification_channels.as_ref()),
+            base_currency: user.base_currency,
+            tax_lot_method: user.tax_lot_method,
+        }
+    }
+}
+
+#[derive(Debug, Deserialize, ToSchema)]
+pub struct UpdatePreferencesRequest {
+    /// ISO 4217 code (e.g. "EUR", "VND"); USD or a currency with collected FX rates
+    pub base_currency: Option<String>,
+    /// "fifo", "lifo" or "hifo"; changing it rebuilds the user's tax lots in the background
+    pub tax_lot_method: Option<String>,
+    /// Replaces the channels new recommendations are delivered to; `webhook_url` must be an
+    /// http(s) URL whose host resolves to public addresses only
+    pub notification_channels: Option<NotificationChannels>,
+}
+
+// === API Handlers ===
+
+/// Get the preferences of the authenticated user
+#[utoipa::path(
+    get,
+    path = "/api/v1/me/preferences",
+    responses(
+        (status = 200, description = "Preferences retrieved successfully", body = PreferencesResponse),
+        (status = 401, description = "Unauthorized"),
+        (status = 500, description = "Internal server error")
+    ),
+    tag = "preferences"
+)]
+pub async fn get_preferences_handler(
+    State(db): State<DatabaseConnection>,
+    Extension(token): Extension<KeycloakToken<String>>,
+) -> Result<Json<PreferencesResponse>, ApiError> {
+    let user = get_or_create_user(&db, &token)
+        .await
+        .map_err(|e| ApiError::InternalServerError(format!("Database error: {}", e)))?;
+
+    Ok(Json(PreferencesResponse::from(user)))
+}
+
+/// Update the preferences of the authenticated user
+///
+/// Fields left out are unchanged. The base currency must be USD or a currency present in
+/// `fx_rates`.
+#[utoipa::path(
+    put,
+    path = "/api/v1/me/preferences",
+    request_body = UpdatePreferencesRequest,
+    responses(
+        (status = 200, description = "Preferences updated successfully", body = PreferencesResponse),
+        (status = 400, description = "Unknown currency or tax lot method, or invalid webhook URL"),
+        (status = 401, description = "Unauthorized"),
+        (status = 500, description = "Internal server error")
+    ),
+    tag = "preferences"
+)]
+pub async fn update_preferences_handler(
+    State(db): State<DatabaseConnection>,
+    Extension(token): Extension<KeycloakToken<String>>,
+    Json(request): Json<UpdatePreferencesRequest>,
+) -> Result<Json<PreferencesResponse>, ApiError> {
+    let user = get_or_create_user(&db, &token)
+        .await
+        .map_err(|e| ApiError::InternalServerError(format!("Database error: {}", e)))?;
+
+    let previous_method = user.tax_lot_method.clone();
+    let mut active: users::ActiveModel = user.into();
+
+    if let Some(base_currency) = request.base_currency.as_deref() {
+        let currency = base_currency.trim().to_uppercase();
+        if !is_currency_code(&currency) {
+            return Err(ApiError::BadRequest(format!(
+                "'{}' is not an ISO 4217 currency code",
+                base_currency
+            )));
+        }
+        if currency != CURRENCY_USD {
+            let known = fx_rates::Entity::find()
+                .filter(fx_rates::Column::Currency.eq(currency.as_str()))
+                .count(&db)
+                .await?;
+            if known == 0 {
+                return Err(ApiError::BadRequest(format!("No FX rates are available for {}", currency)));
+            }
+        }
+        active.base_currency = ActiveValue::Set(currency);
+    }
+
+    if let Some(tax_lot_method) = request.tax_lot_method.as_deref() {
+        let method = LotMethod::parse(tax_lot_method).ok_or_else(|| {
+            ApiError::BadRequest(format!("Invalid tax_lot_method '{}'. Expected fifo, lifo or hifo", tax_lot_method))
+        })?;
+        active.tax_lot_method = ActiveValue::Set(method.as_str().to_string());
+    }
+
+    if let Some(mut channels) = request.notification_channels {
+        channels.webhook_url = channels.webhook_url.map(|url| url.trim().to_string()).filter(|url| !url.is_empty());
+        if let Some(url) = &channels.webhook_url {
+            resolve_webhook_url(url).await.map_err(ApiError::BadRequest)?;
+        }
+        active.notification_channels = ActiveValue::Set(Some(serde_json::json!(channels)));
+    }
+
+    active.updated_at = ActiveValue::Set(Utc::now().into());
+    let user = active.update(&db).await?;
+
+    if user.tax_lot_method != previous_method {
+        let db_bg = db.clone();
+        let user_bg = user.clone();
+        tokio::spawn(async move {
+            match rebuild_user_lots(&db_bg, &user_bg).await {
+                Ok(result) => tracing::info!(
+                    "Rebuilt tax lots of user {} with {}: {} lots over {} accounts",
+                    user_bg.id,
+                    user_bg.tax_lot_method,
+                    result.lots,
+                    result.accounts
+                ),
+                Err(e) => tracing::error!("Failed to rebuild tax lots of user {}: {}", user_bg.id, e),
+            }
+        });
+    }
+
+    Ok(Json(PreferencesResponse::from(user)))
+}
+
+// === Router setup ===
+
+pub fn create_router() -> Router<DatabaseConnection> {
+    Router::new().route(
+        "/api/v1/me/preferences",
+        get(get_preferences_handler).put(update_preferences_handler),
+    )
+}
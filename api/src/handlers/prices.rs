@@ -0,0 +1,244 @@
+use axum::{
+    extract::{Extension, Query, State},
+    response::Json,
+    routing::get,
+    Router,
+};
+use axum_keycloak_auth::decode::KeycloakToken;
+use chrono::Utc;
+use sea_orm::DatabaseConnection;
+use serde::{Deserialize, Serialize};
+use utoipa::ToSchema;
+use uuid::Uuid;
+
+use super::error::ApiError;
+use crate::helpers::asset_identity::{AssetIdentityNormalizer, NormalizationResult};
+use crate::helpers::fx::{is_currency_code, load_fx_rates, CurrencyInfo, CURRENCY_USD};
+use crate::helpers::price_overrides::load_price_overrides;
+use crate::helpers::price_resolution::{resolve_latest_price, resolve_pinned_price, PriceResolutionConfig};
+use crate::live_prices::{self, LivePrice, LIVE_PRICE_SOURCE};
+
+/// Most symbols one stored price query may ask for
+const MAX_PRICE_SYMBOLS: usize = 100;
+
+// === Request/Response DTOs ===
+
+#[derive(Debug, Deserialize, ToSchema)]
+pub struct LivePricesQuery {
+    /// Comma-separated asset symbols (e.g. "BTC,ETH"); all streamed assets when omitted
+    pub symbols: Option<String>,
+}
+
+#[derive(Debug, Serialize, Deserialize, ToSchema)]
+pub struct LivePriceResponse {
+    /// Asset symbol (e.g. "BTC")
+    pub asset: String,
+    pub price_usd: String,
+    /// Price source ("binance_stream")
+    pub source: String,
+    /// Time of the last stream update (RFC 3339)
+    pub updated_at: String,
+}
+
+impl From<LivePrice> for LivePriceResponse {
+    fn from(price: LivePrice) -> Self {
+        Self {
+            asset: price.asset,
+            price_usd: price.price_usd.normalize().to_string(),
+            source: LIVE_PRICE_SOURCE.to_string(),
+            updated_at: price.updated_at.to_rfc3339(),
+        }
+    }
+}
+
+#[derive(Debug, Serialize, Deserialize, ToSchema)]
+pub struct LivePricesResponse {
+    /// Whether the live price stream is running (`LIVE_PRICES_ENABLED`)
+    pub enabled: bool,
+    /// Latest streamed prices, sorted by asset
+    pub prices: Vec<LivePriceResponse>,
+    pub as_of: String,
+}
+
+#[derive(Debug, Deserialize, ToSchema)]
+pub struct StoredPricesQuery {
+    /// Comma-separated asset symbols (e.g. "BTC,ETH"), at most 100
+    pub symbols: String,
+    /// Currency to quote in (e.g. "usd", "eur"; default: usd)
+    pub vs: Option<String>,
+}
+
+#[derive(Debug, Serialize, Deserialize, ToSchema)]
+pub struct StoredPriceResponse {
+    /// Symbol as requested, uppercased
+    pub symbol: String,
+    pub asset_id: Uuid,
+    /// Price in the `vs` currency
+    pub price: String,
+    pub price_usd: String,
+    /// Price source (e.g. "coinpaprika")
+    pub source: String,
+    /// Time of the stored price (RFC 3339)
+    pub priced_at: String,
+    /// "primary", "fallback", "stale" or "override"
+    pub resolution: String,
+}
+
+#[derive(Debug, Serialize, Deserialize, ToSchema)]
+pub struct StoredPricesResponse {
+    /// Prices in request order
+    pub prices: Vec<StoredPriceResponse>,
+    /// Requested symbols without a known asset or stored price
+    pub missing: Vec<String>,
+    /// Currency prices are quoted in and the rate used
+    #[serde(flatten)]
+    pub currency: CurrencyInfo,
+}
+
+// === Helper Functions ===
+
+/// Uppercased symbols of a comma-separated list, without blanks and duplicates, in order
+fn parse_symbols(value: &str) -> Result<Vec<String>, ApiError> {
+    let mut symbols: Vec<String> = Vec::new();
+    for symbol in value.split(',').map(|s| s.trim().to_uppercase()).filter(|s| !s.is_empty()) {
+        if !symbols.contains(&symbol) {
+            symbols.push(symbol);
+        }
+    }
+    if symbols.is_empty() || symbols.len() > MAX_PRICE_SYMBOLS {
+        return Err(ApiError::BadRequest(format!("symbols must list 1 to {} symbols", MAX_PRICE_SYMBOLS)));
+    }
+    Ok(symbols)
+}
+
+// === API Handlers ===
+
+/// Get stored prices
+///
+/// Latest price of each symbol from the price store, resolved like allocation construction
+/// does (pinned source, else primary source with fallback), quoted in `vs` at the latest FX
+/// rate. Lets clients read prices without calling the price provider themselves.
+#[utoipa::path(
+    get,
+    path = "/api/v1/prices",
+    params(
+        ("symbols" = String, Query, description = "Comma-separated asset symbols (e.g. BTC,ETH), at most 100"),
+        ("vs" = Option<String>, Query, description = "Quote currency (default: usd)")
+    ),
+    responses(
+        (status = 200, description = "Stored prices", body = StoredPricesResponse),
+        (status = 400, description = "Invalid symbols or unknown currency"),
+        (status = 401, description = "Unauthorized"),
+        (status = 500, description = "Internal server error")
+    ),
+    tag = "prices"
+)]
+pub async fn get_stored_prices_handler(
+    State(db): State<DatabaseConnection>,
+    Extension(_token): Extension<KeycloakToken<String>>,
+    Query(query): Query<StoredPricesQuery>,
+) -> Result<Json<StoredPricesResponse>, ApiError> {
+    let symbols = parse_symbols(&query.symbols)?;
+
+    let vs = query.vs.as_deref().unwrap_or(CURRENCY_USD).trim().to_uppercase();
+    if !is_currency_code(&vs) {
+        return Err(ApiError::BadRequest(format!("'{}' is not an ISO 4217 currency code", vs)));
+    }
+    let fx = load_fx_rates(&db, &vs).await?;
+    if fx.currency() != vs {
+        return Err(ApiError::BadRequest(format!("No FX rates are available for {}", vs)));
+    }
+    let (rate, _) = fx.latest();
+
+    let config = PriceResolutionConfig::from_env();
+    let overrides = load_price_overrides(&db).await?;
+    let normalizer = AssetIdentityNormalizer::new(db.clone());
+    let mut prices = Vec::with_capacity(symbols.len());
+    let mut missing = Vec::new();
+    for symbol in symbols {
+        let NormalizationResult::Mapped(identity) = normalizer.normalize_from_symbol(&symbol).await else {
+            missing.push(symbol);
+            continue;
+        };
+        let resolved = match overrides.get(&identity.asset_id) {
+            Some(pinned) => resolve_pinned_price(&db, identity.asset_id, &pinned.source, &config).await?,
+            None => resolve_latest_price(&db, identity.asset_id, &config).await?,
+        };
+        let Some((price, resolution)) = resolved else {
+            missing.push(symbol);
+            continue;
+        };
+        prices.push(StoredPriceResponse {
+            symbol,
+            asset_id: identity.asset_id,
+            price: (price.price_usd * rate).normalize().to_string(),
+            price_usd: price.price_usd.normalize().to_string(),
+            source: price.source,
+            priced_at: price.timestamp.to_rfc3339(),
+            resolution: resolution.resolution,
+        });
+    }
+
+    Ok(Json(StoredPricesResponse { prices, missing, currency: fx.info() }))
+}
+
+
+/// Get live prices
+///
+/// Latest prices of the assets held in any portfolio, from the Binance public WebSocket
+/// stream (USDT pairs). Requested symbols without a streamed price are left out.
+#[utoipa::path(
+    get,
+    path = "/api/v1/prices/live",
+    params(
+        ("symbols" = Option<String>, Query, description = "Comma-separated asset symbols (e.g. BTC,ETH)")
+    ),
+    responses(
+        (status = 200, description = "Live prices", body = LivePricesResponse),
+        (status = 401, description = "Unauthorized")
+    ),
+    tag = "prices"
+)]
+pub async fn get_live_prices_handler(
+    Extension(_token): Extension<KeycloakToken<String>>,
+    Query(query): Query<LivePricesQuery>,
+) -> Json<LivePricesResponse> {
+    let mut prices = live_prices::all();
+    if let Some(symbols) = query.symbols.as_deref() {
+        let wanted: Vec<String> = symbols
+            .split(',')
+            .map(|s| s.trim().to_uppercase())
+            .filter(|s| !s.is_empty())
+            .collect();
+        prices.retain(|price| wanted.contains(&price.asset));
+    }
+
+    Json(LivePricesResponse {
+        enabled: live_prices::is_enabled(),
+        prices: prices.into_iter().map(LivePriceResponse::from).collect(),
+        as_of: Utc::now().to_rfc3339(),
+    })
+}
+
+// === Router setup ===
+
+pub fn create_router() -> Router<DatabaseConnection> {
+    Router::new()
+        .route("/api/v1/prices", get(get_stored_prices_handler))
+        .route("/api/v1/prices/live", get(get_live_prices_handler))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_symbols() {
+        assert_eq!(parse_symbols(" btc,ETH,,Btc , sol").unwrap(), vec!["BTC", "ETH", "SOL"]);
+        assert!(parse_symbols(" , ").is_err());
+
+        let many: Vec<String> = (0..=MAX_PRICE_SYMBOLS).map(|i| format!("T{}", i)).collect();
+        assert!(parse_symbols(&many[..MAX_PRICE_SYMBOLS].join(",")).is_ok());
+        assert!(parse_symbols(&many.join(",")).is_err());
+    }
+}
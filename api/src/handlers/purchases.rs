@@ -0,0 +1,206 @@
+use axum::{
+    extract::{Extension, Path, Query, State},
+    response::Json,
+    routing::get,
+    Router,
+};
+use axum_keycloak_auth::decode::KeycloakToken;
+use chrono::Utc;
+use rust_decimal::Decimal;
+use sea_orm::DatabaseConnection;
+use serde::{Deserialize, Serialize};
+use utoipa::ToSchema;
+use uuid::Uuid;
+
+use crate::helpers::auth::{check_portfolio_ownership, get_or_create_user};
+use crate::helpers::cost_basis::{average_cost_basis, purchase_summaries, LedgerAction, LedgerEntry};
+use crate::jobs::portfolio_pnl::{load_ledger, portfolio_account_ids, LedgerPricer};
+use super::error::ApiError;
+
+// === Request/Response DTOs ===
+
+#[derive(Debug, Deserialize, ToSchema)]
+pub struct PurchasesQuery {
+    /// Count deposits as purchases at their value when received (default false)
+    #[serde(default)]
+    pub include_deposits: bool,
+}
+
+#[derive(Debug, Serialize, Deserialize, ToSchema)]
+pub struct AssetPurchasesResponse {
+    pub asset: String,
+    /// Number of purchases
+    pub purchases: usize,
+    pub quantity_purchased: String,
+    /// USD cost of all purchases
+    pub invested_usd: String,
+    /// `invested_usd` per unit purchased
+    pub average_entry_price_usd: String,
+    pub first_purchase_at: String,
+    pub last_purchase_at: String,
+    /// Quantity held according to the ledger, after sales and withdrawals
+    pub quantity_held: String,
+    /// Latest price; absent when the asset has no price
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub current_price_usd: Option<String>,
+    /// `quantity_held` at the current price
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub current_value_usd: Option<String>,
+    /// Current price against the average entry price, in percent
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub price_change_percent: Option<String>,
+    /// E.g. "You've invested $1,200.00 in BTC at an average price of $40,000.00; current price $60,000.00"
+    pub summary: String,
+}
+
+#[derive(Debug, Serialize, Deserialize, ToSchema)]
+pub struct PurchasesResponse {
+    pub portfolio_id: Uuid,
+    pub include_deposits: bool,
+    pub total_invested_usd: String,
+    /// Value of the held quantities of the priced assets
+    pub total_current_value_usd: String,
+    /// Ledger entries without a price at the time, valued at zero or left out
+    pub unpriced_entries: usize,
+    /// Per asset, largest investment first
+    pub assets: Vec<AssetPurchasesResponse>,
+}
+
+// === Helper Functions ===
+
+/// USD amount with thousands separators and two decimals, e.g. "$1,234.50"; amounts below
+/// one dollar keep up to 8 decimals so small prices stay readable
+fn format_usd(value: Decimal) -> String {
+    if !value.is_zero() && value.abs() < Decimal::ONE {
+        let sign = if value < Decimal::ZERO { "-" } else { "" };
+        return format!("{}${}", sign, value.abs().round_dp(8).normalize());
+    }
+    let rounded = value.round_dp(2);
+    let text = format!("{:.2}", rounded.abs());
+    let (whole, fraction) = text.split_once('.').unwrap_or((&text, "00"));
+    let mut grouped = String::new();
+    for (i, digit) in whole.chars().enumerate() {
+        if i > 0 && (whole.len() - i) % 3 == 0 {
+            grouped.push(',');
+        }
+        grouped.push(digit);
+    }
+    let sign = if rounded < Decimal::ZERO { "-" } else { "" };
+    format!("{}${}.{}", sign, grouped, fraction)
+}
+
+// === API Handlers ===
+
+/// Get a portfolio's purchases report
+///
+/// Dollar-cost averaging view of the spot buys (and, with `include_deposits`, deposits at
+/// their value when received) of the portfolio's accounts: per asset, the amount invested,
+/// the average entry price and the current price. Sales do not reduce the amount invested;
+/// `quantity_held` shows what is left of the purchases according to the ledger.
+#[utoipa::path(
+    get,
+    path = "/api/v1/portfolios/{portfolio_id}/purchases",
+    params(
+        ("portfolio_id" = Uuid, Path, description = "Portfolio ID"),
+        ("include_deposits" = Option<bool>, Query, description = "Count deposits as purchases (default false)")
+    ),
+    responses(
+        (status = 200, description = "Purchases report", body = PurchasesResponse),
+        (status = 401, description = "Unauthorized"),
+        (status = 403, description = "Forbidden - portfolio does not belong to user"),
+        (status = 404, description = "Portfolio not found"),
+        (status = 500, description = "Internal server error")
+    ),
+    tag = "portfolios"
+)]
+pub async fn get_portfolio_purchases_handler(
+    State(db): State<DatabaseConnection>,
+    Extension(token): Extension<KeycloakToken<String>>,
+    Path(portfolio_id): Path<Uuid>,
+    Query(query): Query<PurchasesQuery>,
+) -> Result<Json<PurchasesResponse>, ApiError> {
+    let user = get_or_create_user(&db, &token).await?;
+    check_portfolio_ownership(&db, portfolio_id, user.id).await?;
+
+    let account_ids = portfolio_account_ids(&db, portfolio_id).await?;
+    let ledger = load_ledger(&db, &account_ids).await?;
+    let purchases: Vec<LedgerEntry> = ledger
+        .purchases
+        .iter()
+        .filter(|p| query.include_deposits || p.action == LedgerAction::Acquire)
+        .cloned()
+        .collect();
+    let mut summaries: Vec<_> = purchase_summaries(&purchases).into_iter().collect();
+    summaries.sort_by_key(|(_, summary)| std::cmp::Reverse(summary.invested_usd));
+    let positions = average_cost_basis(&ledger.entries);
+
+    let now = Utc::now();
+    let mut pricer = LedgerPricer::new(db.clone());
+    let mut assets = Vec::with_capacity(summaries.len());
+    let mut total_invested = Decimal::ZERO;
+    let mut total_value = Decimal::ZERO;
+    for (asset, summary) in summaries {
+        let held = positions.get(&asset).map(|p| p.quantity).unwrap_or_default();
+        let price = pricer.price(&asset, now).await?;
+        let average = summary.average_price();
+        let value = price.map(|p| held * p);
+        total_invested += summary.invested_usd;
+        total_value += value.unwrap_or_default();
+
+        let current = match price {
+            Some(price) => format!("current price {}", format_usd(price)),
+            None => "no current price".to_string(),
+        };
+        assets.push(AssetPurchasesResponse {
+            summary: format!(
+                "You've invested {} in {} at an average price of {}; {}",
+                format_usd(summary.invested_usd),
+                asset,
+                format_usd(average),
+                current
+            ),
+            purchases: summary.purchases,
+            quantity_purchased: summary.quantity.normalize().to_string(),
+            invested_usd: summary.invested_usd.round_dp(2).normalize().to_string(),
+            average_entry_price_usd: average.round_dp(8).normalize().to_string(),
+            first_purchase_at: summary.first_at.to_rfc3339(),
+            last_purchase_at: summary.last_at.to_rfc3339(),
+            quantity_held: held.normalize().to_string(),
+            current_price_usd: price.map(|p| p.normalize().to_string()),
+            current_value_usd: value.map(|v| v.round_dp(2).normalize().to_string()),
+            price_change_percent: price
+                .filter(|_| average > Decimal::ZERO)
+                .map(|p| ((p / average - Decimal::ONE) * Decimal::ONE_HUNDRED).round_dp(2).normalize().to_string()),
+            asset,
+        });
+    }
+
+    Ok(Json(PurchasesResponse {
+        portfolio_id,
+        include_deposits: query.include_deposits,
+        total_invested_usd: total_invested.round_dp(2).normalize().to_string(),
+        total_current_value_usd: total_value.round_dp(2).normalize().to_string(),
+        unpriced_entries: ledger.unpriced,
+        assets,
+    }))
+}
+
+// === Router setup ===
+
+pub fn create_router() -> Router<DatabaseConnection> {
+    Router::new().route("/api/v1/portfolios/{portfolio_id}/purchases", get(get_portfolio_purchases_handler))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_format_usd() {
+        assert_eq!(format_usd(Decimal::new(123456789, 2)), "$1,234,567.89");
+        assert_eq!(format_usd(Decimal::new(5, 1)), "$0.5");
+        assert_eq!(format_usd(Decimal::new(123, 8)), "$0.00000123");
+        assert_eq!(format_usd(Decimal::ZERO), "$0.00");
+        assert_eq!(format_usd(Decimal::from(-1000)), "-$1,000.00");
+    }
+}
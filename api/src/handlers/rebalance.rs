@@ -0,0 +1,308 @@
+use std::collections::{BTreeMap, HashSet};
+
+use axum::{
+    extract::{Extension, Path, Query, State},
+    http::header,
+    response::{IntoResponse, Json, Response},
+    routing::{get, post},
+    Router,
+};
+use axum_keycloak_auth::decode::KeycloakToken;
+use chrono::Utc;
+use rust_decimal::prelude::{FromPrimitive, ToPrimitive};
+use rust_decimal::Decimal;
+use sea_orm::{ActiveModelTrait, ColumnTrait, DatabaseConnection, EntityTrait, QueryFilter, Set};
+use serde::{Deserialize, Serialize};
+use utoipa::ToSchema;
+use uuid::Uuid;
+
+use crate::connectors::registry::ACCOUNT_TYPE_EXCHANGE;
+use crate::domain::{rebalance_plan, target_weights, AllocationItem, PortfolioGuardrails};
+use crate::entities::{accounts, portfolio_allocations, rebalance_plans};
+use crate::helpers::auth::{check_portfolio_ownership, get_or_create_user};
+use crate::helpers::order_drafts::{okx_order_drafts, to_csv, DEFAULT_QUOTE_CURRENCY};
+use crate::jobs::recommendations::{load_spot_balances, min_trade_usd_from_env, unheld_target_prices};
+use super::error::ApiError;
+
+// === Request/Response DTOs ===
+
+pub use crate::domain::{PlannedTrade, TradeSide};
+pub use crate::helpers::order_drafts::{OkxOrderRequest, OrderDraft, SkippedOrder};
+
+#[derive(Debug, Default, Deserialize, ToSchema)]
+pub struct RebalancePlanRequest {
+    /// Smallest trade in USD to include (default `REBALANCE_MIN_TRADE_USD`, 10)
+    pub min_trade_usd: Option<f64>,
+}
+
+#[derive(Debug, Serialize, Deserialize, ToSchema)]
+pub struct RebalancePlanResponse {
+    pub id: Uuid,
+    pub portfolio_id: Uuid,
+    /// Allocation value the plan was computed from
+    pub total_value_usd: f64,
+    pub min_trade_usd: f64,
+    /// Target weights (0-100) after guardrail adjustments
+    pub targets: BTreeMap<String, f64>,
+    /// Sells first, then buys, largest first
+    pub trades: Vec<PlannedTrade>,
+    pub total_buy_usd: f64,
+    pub total_sell_usd: f64,
+    /// Number of trades below `min_trade_usd` left out
+    pub skipped_trades: i32,
+    /// Target adjustments and trades the plan could not fully place
+    pub notes: Vec<String>,
+    /// Timestamp of the allocation the plan was computed from
+    pub allocation_as_of: String,
+    pub created_at: String,
+}
+
+#[derive(Debug, Deserialize, ToSchema)]
+pub struct OrderDraftsQuery {
+    /// "json" (default) or "csv"
+    pub format: Option<String>,
+    /// Quote currency of the spot instruments (default "USDT")
+    pub quote: Option<String>,
+}
+
+#[derive(Debug, Serialize, Deserialize, ToSchema)]
+pub struct OrderDraftsResponse {
+    pub plan_id: Uuid,
+    pub quote: String,
+    /// OKX order requests, in plan order (sells first)
+    pub drafts: Vec<OrderDraft>,
+    /// Trades of the plan without a draft, with the reason
+    pub skipped: Vec<SkippedOrder>,
+}
+
+impl TryFrom<rebalance_plans::Model> for RebalancePlanResponse {
+    type Error = ApiError;
+
+    fn try_from(m: rebalance_plans::Model) -> Result<Self, Self::Error> {
+        let invalid = |e: serde_json::Error| ApiError::InternalServerError(format!("Failed to deserialize plan: {}", e));
+        let trades: Vec<PlannedTrade> = serde_json::from_value(m.trades).map_err(invalid)?;
+        let total = |side: TradeSide| {
+            let sum: f64 = trades.iter().filter(|t| t.side == side).map(|t| t.amount_usd).sum();
+            (sum * 100.0).round() / 100.0
+        };
+        Ok(Self {
+            id: m.id,
+            portfolio_id: m.portfolio_id,
+            total_value_usd: m.total_value_usd.to_f64().unwrap_or(0.0),
+            min_trade_usd: m.min_trade_usd.to_f64().unwrap_or(0.0),
+            targets: serde_json::from_value(m.targets).map_err(invalid)?,
+            total_buy_usd: total(TradeSide::Buy),
+            total_sell_usd: total(TradeSide::Sell),
+            trades,
+            skipped_trades: m.skipped_trades,
+            notes: serde_json::from_value(m.notes).map_err(invalid)?,
+            allocation_as_of: m.allocation_as_of.to_rfc3339(),
+            created_at: m.created_at.to_rfc3339(),
+        })
+    }
+}
+
+// === API Handlers ===
+
+/// Generate a rebalance plan
+///
+/// Plans the buys and sells that bring the latest allocation back to `target_allocation`,
+/// with targets adjusted to the `max_alt_cap` and `stablecoin_min` guardrails. Nothing is
+/// traded while every asset is within `drift_band`, and trades smaller than `min_trade_usd`
+/// are left out. Each trade names the account to place it on. The plan is stored and can be
+/// fetched again by its id.
+#[utoipa::path(
+    post,
+    path = "/api/v1/portfolios/{portfolio_id}/rebalance/plan",
+    params(
+        ("portfolio_id" = Uuid, Path, description = "Portfolio ID")
+    ),
+    request_body = RebalancePlanRequest,
+    responses(
+        (status = 200, description = "Rebalance plan", body = RebalancePlanResponse),
+        (status = 400, description = "Portfolio has no target allocation, or invalid min_trade_usd"),
+        (status = 401, description = "Unauthorized"),
+        (status = 403, description = "Forbidden - portfolio does not belong to user"),
+        (status = 404, description = "Portfolio or allocation not found"),
+        (status = 500, description = "Internal server error")
+    ),
+    tag = "portfolios"
+)]
+pub async fn create_rebalance_plan_handler(
+    State(db): State<DatabaseConnection>,
+    Extension(token): Extension<KeycloakToken<String>>,
+    Path(portfolio_id): Path<Uuid>,
+    Json(req): Json<RebalancePlanRequest>,
+) -> Result<Json<RebalancePlanResponse>, ApiError> {
+    let user = get_or_create_user(&db, &token).await?;
+    let portfolio = check_portfolio_ownership(&db, portfolio_id, user.id).await?;
+
+    let min_trade_usd = req.min_trade_usd.unwrap_or_else(min_trade_usd_from_env);
+    if !min_trade_usd.is_finite() || min_trade_usd < 0.0 {
+        return Err(ApiError::BadRequest("min_trade_usd must be zero or more".to_string()));
+    }
+    let targets = target_weights(portfolio.target_allocation.as_ref());
+    if targets.is_empty() {
+        return Err(ApiError::BadRequest("Portfolio has no target_allocation".to_string()));
+    }
+    let guardrails = PortfolioGuardrails::from_json(portfolio.guardrails.as_ref());
+
+    let allocation = portfolio_allocations::Entity::find()
+        .filter(portfolio_allocations::Column::PortfolioId.eq(portfolio_id))
+        .one(&db)
+        .await?
+        .ok_or(ApiError::NotFound)?;
+    let holdings: Vec<AllocationItem> = serde_json::from_value(allocation.holdings)
+        .map_err(|e| ApiError::InternalServerError(format!("Failed to deserialize allocation: {}", e)))?;
+
+    // Prices of targeted assets the allocation does not price
+    let prices = unheld_target_prices(&db, &holdings, &targets).await?;
+    let balances = load_spot_balances(&db, portfolio_id).await?;
+    let plan = rebalance_plan(&holdings, &targets, &guardrails, &prices, &balances, min_trade_usd);
+
+    let to_json = |e: serde_json::Error| ApiError::InternalServerError(format!("Failed to serialize plan: {}", e));
+    let stored = rebalance_plans::ActiveModel {
+        id: Set(Uuid::new_v4()),
+        portfolio_id: Set(portfolio_id),
+        total_value_usd: Set(allocation.total_value_usd),
+        min_trade_usd: Set(Decimal::from_f64(min_trade_usd).unwrap_or_default()),
+        targets: Set(serde_json::to_value(&plan.targets).map_err(to_json)?),
+        trades: Set(serde_json::to_value(&plan.trades).map_err(to_json)?),
+        skipped_trades: Set(plan.skipped_trades as i32),
+        notes: Set(serde_json::to_value(&plan.notes).map_err(to_json)?),
+        allocation_as_of: Set(allocation.as_of),
+        created_at: Set(Utc::now().into()),
+    }
+    .insert(&db)
+    .await?;
+
+    Ok(Json(stored.try_into()?))
+}
+
+/// Get a stored rebalance plan
+#[utoipa::path(
+    get,
+    path = "/api/v1/portfolios/{portfolio_id}/rebalance/plans/{plan_id}",
+    params(
+        ("portfolio_id" = Uuid, Path, description = "Portfolio ID"),
+        ("plan_id" = Uuid, Path, description = "Rebalance plan ID")
+    ),
+    responses(
+        (status = 200, description = "Rebalance plan", body = RebalancePlanResponse),
+        (status = 401, description = "Unauthorized"),
+        (status = 403, description = "Forbidden - portfolio does not belong to user"),
+        (status = 404, description = "Portfolio or plan not found"),
+        (status = 500, description = "Internal server error")
+    ),
+    tag = "portfolios"
+)]
+pub async fn get_rebalance_plan_handler(
+    State(db): State<DatabaseConnection>,
+    Extension(token): Extension<KeycloakToken<String>>,
+    Path((portfolio_id, plan_id)): Path<(Uuid, Uuid)>,
+) -> Result<Json<RebalancePlanResponse>, ApiError> {
+    let user = get_or_create_user(&db, &token).await?;
+    check_portfolio_ownership(&db, portfolio_id, user.id).await?;
+
+    let plan = rebalance_plans::Entity::find_by_id(plan_id)
+        .filter(rebalance_plans::Column::PortfolioId.eq(portfolio_id))
+        .one(&db)
+        .await?
+        .ok_or(ApiError::NotFound)?;
+
+    Ok(Json(plan.try_into()?))
+}
+
+/// Export a rebalance plan as OKX order drafts
+///
+/// Converts the plan's trades on OKX accounts into OKX spot order requests (`instId`,
+/// `side`, `sz` in the base currency, market orders) ready to submit; nothing is executed.
+/// Trades of the quote currency, without an account, or on other venues are listed as
+/// skipped. `format=csv` downloads the drafts as CSV.
+#[utoipa::path(
+    get,
+    path = "/api/v1/portfolios/{portfolio_id}/rebalance/plans/{plan_id}/orders",
+    params(
+        ("portfolio_id" = Uuid, Path, description = "Portfolio ID"),
+        ("plan_id" = Uuid, Path, description = "Rebalance plan ID"),
+        ("format" = Option<String>, Query, description = "json (default) or csv"),
+        ("quote" = Option<String>, Query, description = "Quote currency (default USDT)")
+    ),
+    responses(
+        (status = 200, description = "Order drafts (JSON, or text/csv with format=csv)", body = OrderDraftsResponse),
+        (status = 400, description = "Invalid format or quote"),
+        (status = 401, description = "Unauthorized"),
+        (status = 403, description = "Forbidden - portfolio does not belong to user"),
+        (status = 404, description = "Portfolio or plan not found"),
+        (status = 500, description = "Internal server error")
+    ),
+    tag = "portfolios"
+)]
+pub async fn export_rebalance_orders_handler(
+    State(db): State<DatabaseConnection>,
+    Extension(token): Extension<KeycloakToken<String>>,
+    Path((portfolio_id, plan_id)): Path<(Uuid, Uuid)>,
+    Query(query): Query<OrderDraftsQuery>,
+) -> Result<Response, ApiError> {
+    let user = get_or_create_user(&db, &token).await?;
+    check_portfolio_ownership(&db, portfolio_id, user.id).await?;
+
+    let csv = match query.format.as_deref().map(|f| f.trim().to_lowercase()) {
+        None => false,
+        Some(format) if format == "json" => false,
+        Some(format) if format == "csv" => true,
+        Some(format) => return Err(ApiError::BadRequest(format!("Invalid format '{}'. Expected json or csv", format))),
+    };
+    let quote = query.quote.as_deref().map(|q| q.trim().to_uppercase()).unwrap_or_else(|| DEFAULT_QUOTE_CURRENCY.to_string());
+    if quote.is_empty() || !quote.chars().all(|c| c.is_ascii_alphanumeric()) {
+        return Err(ApiError::BadRequest(format!("Invalid quote currency '{}'", quote)));
+    }
+
+    let plan = rebalance_plans::Entity::find_by_id(plan_id)
+        .filter(rebalance_plans::Column::PortfolioId.eq(portfolio_id))
+        .one(&db)
+        .await?
+        .ok_or(ApiError::NotFound)?;
+    let trades: Vec<PlannedTrade> = serde_json::from_value(plan.trades)
+        .map_err(|e| ApiError::InternalServerError(format!("Failed to deserialize plan: {}", e)))?;
+
+    let account_ids: Vec<Uuid> = trades.iter().filter_map(|t| t.account_id).collect();
+    let okx_accounts: HashSet<Uuid> = accounts::Entity::find()
+        .filter(accounts::Column::Id.is_in(account_ids))
+        .filter(accounts::Column::UserId.eq(user.id))
+        .filter(accounts::Column::AccountType.eq(ACCOUNT_TYPE_EXCHANGE))
+        .all(&db)
+        .await?
+        .into_iter()
+        .filter(|a| a.exchange_name.as_deref().is_some_and(|name| name.trim().eq_ignore_ascii_case("okx")))
+        .map(|a| a.id)
+        .collect();
+
+    let (drafts, skipped) = okx_order_drafts(plan_id, &trades, &okx_accounts, &quote);
+
+    if csv {
+        let body = to_csv(&drafts).map_err(|e| ApiError::InternalServerError(format!("Failed to write CSV: {}", e)))?;
+        return Ok((
+            [
+                (header::CONTENT_TYPE, "text/csv; charset=utf-8".to_string()),
+                (header::CONTENT_DISPOSITION, format!("attachment; filename=\"okx-orders-{}.csv\"", plan_id)),
+            ],
+            body,
+        )
+            .into_response());
+    }
+
+    Ok(Json(OrderDraftsResponse { plan_id, quote, drafts, skipped }).into_response())
+}
+
+// === Router setup ===
+
+pub fn create_router() -> Router<DatabaseConnection> {
+    Router::new()
+        .route("/api/v1/portfolios/{portfolio_id}/rebalance/plan", post(create_rebalance_plan_handler))
+        .route("/api/v1/portfolios/{portfolio_id}/rebalance/plans/{plan_id}", get(get_rebalance_plan_handler))
+        .route(
+            "/api/v1/portfolios/{portfolio_id}/rebalance/plans/{plan_id}/orders",
+            get(export_rebalance_orders_handler),
+        )
+}
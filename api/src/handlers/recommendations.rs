@@ -13,13 +13,14 @@ use sea_orm::{
     Set,
 };
 use serde::{Deserialize, Serialize};
-use serde_json::json;
 use utoipa::ToSchema;
 use uuid::Uuid;
 
 use super::error::ApiError;
 use crate::entities::{portfolios, recommendations};
 use crate::helpers::auth::get_or_create_user;
+use crate::jobs::construction_runs::current_construction_run_id;
+use crate::jobs::recommendations::generate_portfolio_recommendations;
 
 // === Request/Response DTOs ===
 
@@ -36,6 +37,9 @@ pub struct RecommendationResponse {
     pub created_at: String,
     pub updated_at: String,
     pub executed_at: Option<String>,
+    /// Latest construction run of the portfolio when the recommendation was created
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub construction_run_id: Option<Uuid>,
 }
 
 impl From<recommendations::Model> for RecommendationResponse {
@@ -52,6 +56,7 @@ impl From<recommendations::Model> for RecommendationResponse {
             created_at: model.created_at.to_rfc3339(),
             updated_at: model.updated_at.to_rfc3339(),
             executed_at: model.executed_at.map(|dt| dt.to_rfc3339()),
+            construction_run_id: model.construction_run_id,
         }
     }
 }
@@ -63,9 +68,26 @@ pub struct ListRecommendationsResponse {
     pub total_count: usize,
 }
 
+#[derive(Debug, Serialize, Deserialize, ToSchema)]
+pub struct GenerateRecommendationsResponse {
+    pub portfolio_id: Uuid,
+    /// Recommendations written by this run: the rebalancing advice and new risk alerts
+    pub recommendations: Vec<RecommendationResponse>,
+    pub total_count: usize,
+    /// Pending recommendations of the engine marked "superseded" by this run
+    pub superseded: u64,
+    /// Pending risk alerts updated because their condition still holds
+    pub alerts_updated: usize,
+    /// Pending risk alerts marked "resolved" because their condition is gone
+    pub alerts_resolved: usize,
+    /// Why no rebalancing advice was written, if none was
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub skipped_reason: Option<String>,
+}
+
 #[derive(Debug, Deserialize, ToSchema)]
 pub struct ListRecommendationsQuery {
-    /// Filter by status (e.g., "pending", "approved", "rejected", "executed")
+    /// Filter by status (e.g., "pending", "approved", "rejected", "executed", "superseded", "resolved")
     #[serde(skip_serializing_if = "Option::is_none")]
     pub status: Option<String>,
 }
@@ -243,6 +265,8 @@ pub async fn create_recommendation(
         None
     };
 
+    let construction_run_id = current_construction_run_id(&db, portfolio_id).await?;
+
     let now = Utc::now();
     let new_recommendation = recommendations::ActiveModel {
         portfolio_id: Set(portfolio_id),
@@ -254,6 +278,7 @@ pub async fn create_recommendation(
         metadata: Set(payload.metadata),
         created_at: Set(now.into()),
         updated_at: Set(now.into()),
+        construction_run_id: Set(construction_run_id),
         ..Default::default()
     };
 
@@ -268,7 +293,17 @@ pub async fn create_recommendation(
     Ok((StatusCode::CREATED, Json(response)))
 }
 
-/// Generate mock recommendations for a portfolio (demo feature)
+/// Run the recommendation engine for a portfolio
+///
+/// Evaluates the portfolio's latest allocation against its target allocation and guardrails
+/// and writes a "rebalance" recommendation with the orders that restore the target (e.g.
+/// "Sell 0.2 ETH, buy 500 USDC"), its rationale and the projected post-trade allocation in
+/// `metadata`. Trades on assets with a stale price are left out. No recommendation is written
+/// when nothing needs trading or the allocation is too old; `skipped_reason` says why. The
+/// engine's previous pending rebalance recommendations are marked "superseded".
+///
+/// Risk alerts are evaluated as well: "drawdown_alert", "concentration_alert", "depeg_alert"
+/// and "stale_account" recommendations are created, updated or resolved.
 #[utoipa::path(
     post,
     path = "/api/v1/portfolios/{portfolio_id}/recommendations/generate",
@@ -276,22 +311,22 @@ pub async fn create_recommendation(
         ("portfolio_id" = Uuid, Path, description = "Portfolio ID")
     ),
     responses(
-        (status = 200, description = "Mock recommendations generated", body = ListRecommendationsResponse),
+        (status = 200, description = "Recommendations generated", body = GenerateRecommendationsResponse),
         (status = 404, description = "Portfolio not found"),
     ),
     tag = "recommendations"
 )]
-pub async fn generate_mock_recommendations(
+pub async fn generate_recommendations_handler(
     State(db): State<DatabaseConnection>,
     Extension(token): Extension<KeycloakToken<String>>,
     Path(portfolio_id): Path<Uuid>,
-) -> Result<Json<ListRecommendationsResponse>, ApiError> {
+) -> Result<Json<GenerateRecommendationsResponse>, ApiError> {
     let user = get_or_create_user(&db, &token).await.map_err(|e| {
         ApiError::InternalServerError(format!("Database error: {}", e))
     })?;
 
     // Verify portfolio ownership
-    let _portfolio = portfolios::Entity::find_by_id(portfolio_id)
+    let portfolio = portfolios::Entity::find_by_id(portfolio_id)
         .filter(portfolios::Column::UserId.eq(user.id))
         .one(&db)
         .await
@@ -300,85 +335,20 @@ pub async fn generate_mock_recommendations(
         })?
         .ok_or(ApiError::NotFound)?;
 
-    // Generate mock recommendations
-    let now = Utc::now();
-    let mock_recommendations = vec![
-        recommendations::ActiveModel {
-            portfolio_id: Set(portfolio_id),
-            status: Set("pending".to_string()),
-            recommendation_type: Set("rebalance".to_string()),
-            rationale: Set(
-                "Portfolio allocation has drifted significantly from target. BTC allocation is 45% (target: 40%), ETH is 25% (target: 30%). Recommend rebalancing to maintain strategic allocation.".to_string(),
-            ),
-            proposed_orders: Set(json!([
-                {
-                    "action": "sell",
-                    "asset": "BTC",
-                    "quantity": "0.05",
-                    "estimated_price": "65000",
-                    "estimated_value_usd": "3250.00"
-                },
-                {
-                    "action": "buy",
-                    "asset": "ETH",
-                    "quantity": "1.2",
-                    "estimated_price": "3200",
-                    "estimated_value_usd": "3840.00"
-                }
-            ])),
-            expected_impact: Set(Some(Decimal::new(590, 2))), // 5.90
-            metadata: Set(Some(json!({
-                "risk_score": 0.3,
-                "confidence": 0.85,
-                "drift_percentage": 5.2
-            }))),
-            created_at: Set(now.into()),
-            updated_at: Set(now.into()),
-            ..Default::default()
-        },
-        recommendations::ActiveModel {
-            portfolio_id: Set(portfolio_id),
-            status: Set("pending".to_string()),
-            recommendation_type: Set("take_profit".to_string()),
-            rationale: Set(
-                "SOL has appreciated 45% in the past 30 days and is approaching resistance at $150. Consider taking partial profits to secure gains while maintaining exposure.".to_string(),
-            ),
-            proposed_orders: Set(json!([
-                {
-                    "action": "sell",
-                    "asset": "SOL",
-                    "quantity": "50",
-                    "estimated_price": "145",
-                    "estimated_value_usd": "7250.00"
-                }
-            ])),
-            expected_impact: Set(Some(Decimal::new(7250, 2))), // 72.50
-            metadata: Set(Some(json!({
-                "risk_score": 0.2,
-                "confidence": 0.75,
-                "price_appreciation_30d": 0.45,
-                "resistance_level": "$150"
-            }))),
-            created_at: Set(now.into()),
-            updated_at: Set(now.into()),
-            ..Default::default()
-        },
-    ];
-
-    let mut created_recommendations = Vec::new();
-    for rec in mock_recommendations {
-        let created = rec.insert(&db).await.map_err(|e| {
-            ApiError::InternalServerError(format!("Failed to create recommendation: {}", e))
-        })?;
-        created_recommendations.push(RecommendationResponse::from(created));
-    }
-
-    let total_count = created_recommendations.len();
+    let outcome = generate_portfolio_recommendations(&db, &portfolio)
+        .await
+        .map_err(|e| ApiError::InternalServerError(format!("Failed to generate recommendations: {}", e)))?;
 
-    Ok(Json(ListRecommendationsResponse {
+    let recommendations: Vec<RecommendationResponse> =
+        outcome.created.into_iter().map(RecommendationResponse::from).collect();
+    Ok(Json(GenerateRecommendationsResponse {
         portfolio_id,
-        recommendations: created_recommendations,
-        total_count,
+        total_count: recommendations.len(),
+        recommendations,
+        superseded: outcome.superseded,
+        alerts_updated: outcome.alerts_updated,
+        alerts_resolved: outcome.alerts_resolved,
+        skipped_reason: outcome.skipped_reason,
     }))
 }
 
@@ -392,7 +362,7 @@ pub fn create_router() -> Router<DatabaseConnection> {
         )
         .route(
             "/api/v1/portfolios/{portfolio_id}/recommendations/generate",
-            post(generate_mock_recommendations),
+            post(generate_recommendations_handler),
         )
         .route(
             "/api/v1/portfolios/{portfolio_id}/recommendations/{recommendation_id}",
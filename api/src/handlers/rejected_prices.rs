@@ -0,0 +1,281 @@
+use axum::{
+    extract::{Path, Query, State},
+    response::Json,
+    routing::{get, put},
+    Extension, Router,
+};
+use chrono::Utc;
+use sea_orm::{
+    sea_query::OnConflict, ActiveModelTrait, ActiveValue, ColumnTrait, DatabaseConnection, EntityTrait, QueryFilter,
+    QueryOrder, QuerySelect, Set, TransactionTrait,
+};
+use serde::{Deserialize, Serialize};
+use utoipa::ToSchema;
+use uuid::Uuid;
+
+use crate::entities::{asset_prices, assets, rejected_prices};
+use crate::helpers::pagination::{finish_page, keyset_before, Cursor, PageRequest, DEFAULT_PAGE_LIMIT};
+use crate::helpers::price_sanity::{REJECTION_ACCEPTED, REJECTION_DISMISSED, REJECTION_PENDING};
+use super::error::ApiError;
+
+// === Request / Response DTOs ===
+
+#[derive(Debug, Serialize, Deserialize, ToSchema)]
+pub struct RejectedPriceResponse {
+    pub id: Uuid,
+    pub asset_id: Uuid,
+    /// Asset symbol, when the asset still exists
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub symbol: Option<String>,
+    /// Price source that reported the price
+    pub source: String,
+    pub timestamp: String,
+    /// Rejected price (decimal string)
+    pub price_usd: String,
+    /// Previous observation the price was compared with (decimal string)
+    pub reference_price_usd: String,
+    pub reference_timestamp: String,
+    /// How many times higher or lower the price was than the reference; 0 for a non-positive price
+    pub deviation_factor: String,
+    /// "pending", "accepted" or "dismissed"
+    pub status: String,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub reviewed_at: Option<String>,
+    pub created_at: String,
+}
+
+impl RejectedPriceResponse {
+    fn new(model: rejected_prices::Model, symbol: Option<String>) -> Self {
+        Self {
+            id: model.id,
+            asset_id: model.asset_id,
+            symbol,
+            source: model.source,
+            timestamp: model.timestamp.to_rfc3339(),
+            price_usd: model.price_usd.to_string(),
+            reference_price_usd: model.reference_price_usd.to_string(),
+            reference_timestamp: model.reference_timestamp.to_rfc3339(),
+            deviation_factor: model.deviation_factor.to_string(),
+            status: model.status,
+            reviewed_at: model.reviewed_at.map(|t| t.to_rfc3339()),
+            created_at: model.created_at.to_rfc3339(),
+        }
+    }
+}
+
+#[derive(Debug, Deserialize, ToSchema)]
+pub struct ListRejectedPricesQuery {
+    /// Only rejections with this status (e.g. "pending")
+    pub status: Option<String>,
+    /// Only rejections of this asset
+    pub asset_id: Option<Uuid>,
+    /// Page size (default: 100)
+    pub limit: Option<u64>,
+    /// Cursor from a previous page's `next_cursor`
+    pub cursor: Option<String>,
+}
+
+#[derive(Debug, Serialize, Deserialize, ToSchema)]
+pub struct ListRejectedPricesResponse {
+    pub rejected_prices: Vec<RejectedPriceResponse>,
+    /// Cursor for the next page; absent on the last page
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub next_cursor: Option<String>,
+}
+
+#[derive(Debug, Deserialize, ToSchema)]
+pub struct ReviewRejectedPriceRequest {
+    /// "accepted" stores the price in `asset_prices`; "dismissed" discards it
+    pub status: String,
+}
+
+// === Helper Functions ===
+
+fn parse_status(value: &str) -> Result<String, ApiError> {
+    let status = value.trim().to_lowercase();
+    match status.as_str() {
+        REJECTION_PENDING | REJECTION_ACCEPTED | REJECTION_DISMISSED => Ok(status),
+        _ => Err(ApiError::BadRequest(format!(
+            "Invalid status '{}'. Expected pending, accepted or dismissed",
+            value
+        ))),
+    }
+}
+
+/// `asset_prices` row of an accepted rejection
+fn accepted_price_row(rejected: &rejected_prices::Model) -> asset_prices::ActiveModel {
+    asset_prices::ActiveModel {
+        id: ActiveValue::Set(Uuid::new_v4()),
+        asset_id: ActiveValue::Set(rejected.asset_id),
+        timestamp: ActiveValue::Set(rejected.timestamp),
+        price_usd: ActiveValue::Set(rejected.price_usd),
+        volume_24h_usd: ActiveValue::Set(None),
+        market_cap_usd: ActiveValue::Set(None),
+        change_percent_24h: ActiveValue::Set(None),
+        source: ActiveValue::Set(rejected.source.clone()),
+        created_at: ActiveValue::Set(Utc::now().into()),
+        rank: ActiveValue::Set(None),
+        circulating_supply: ActiveValue::Set(None),
+        total_supply: ActiveValue::Set(None),
+        max_supply: ActiveValue::Set(None),
+        beta_value: ActiveValue::Set(None),
+        percent_change_1h: ActiveValue::Set(None),
+        percent_change_7d: ActiveValue::Set(None),
+        percent_change_30d: ActiveValue::Set(None),
+        ath_price: ActiveValue::Set(None),
+        ath_date: ActiveValue::Set(None),
+        percent_from_price_ath: ActiveValue::Set(None),
+    }
+}
+
+// === API Handlers ===
+
+/// List quarantined prices
+///
+/// Prices rejected by the ingestion sanity check, newest first, one page at a time.
+#[utoipa::path(
+    get,
+    path = "/api/v1/rejected-prices",
+    params(
+        ("status" = Option<String>, Query, description = "Only rejections with this status (pending, accepted, dismissed)"),
+        ("asset_id" = Option<Uuid>, Query, description = "Only rejections of this asset"),
+        ("limit" = Option<u64>, Query, description = "Page size (1-1000, default: 100)"),
+        ("cursor" = Option<String>, Query, description = "Opaque cursor from a previous page's next_cursor")
+    ),
+    responses(
+        (status = 200, description = "Rejected prices page", body = ListRejectedPricesResponse),
+        (status = 400, description = "Invalid status or cursor"),
+        (status = 401, description = "Unauthorized"),
+        (status = 500, description = "Internal server error")
+    ),
+    tag = "rejected-prices"
+)]
+pub async fn list_rejected_prices_handler(
+    State(db): State<DatabaseConnection>,
+    Extension(_token): Extension<axum_keycloak_auth::decode::KeycloakToken<String>>,
+    Query(query): Query<ListRejectedPricesQuery>,
+) -> Result<Json<ListRejectedPricesResponse>, ApiError> {
+    let page = PageRequest::from_query(
+        Some(query.limit.unwrap_or(DEFAULT_PAGE_LIMIT)),
+        query.cursor.as_deref(),
+    )?;
+
+    let mut rejected_query = rejected_prices::Entity::find()
+        .find_also_related(assets::Entity)
+        .order_by_desc(rejected_prices::Column::CreatedAt)
+        .order_by_desc(rejected_prices::Column::Id)
+        .limit(page.fetch_limit());
+
+    if let Some(status) = query.status.as_deref() {
+        rejected_query = rejected_query.filter(rejected_prices::Column::Status.eq(parse_status(status)?));
+    }
+    if let Some(asset_id) = query.asset_id {
+        rejected_query = rejected_query.filter(rejected_prices::Column::AssetId.eq(asset_id));
+    }
+    if let Some(after) = &page.after {
+        rejected_query = rejected_query.filter(keyset_before(
+            rejected_prices::Column::CreatedAt,
+            rejected_prices::Column::Id,
+            after.timestamp,
+            after.id,
+        ));
+    }
+
+    let (rows, next_cursor) = finish_page(rejected_query.all(&db).await?, &page, |(r, _)| {
+        Cursor::new(r.created_at.with_timezone(&Utc), r.id)
+    });
+
+    Ok(Json(ListRejectedPricesResponse {
+        rejected_prices: rows
+            .into_iter()
+            .map(|(r, asset)| RejectedPriceResponse::new(r, asset.map(|a| a.symbol)))
+            .collect(),
+        next_cursor,
+    }))
+}
+
+/// Review a quarantined price
+///
+/// Accepting stores the price in `asset_prices` at its original time and source, for a
+/// genuine move the sanity check mistook for a glitch; dismissing keeps it out for good.
+/// Only pending rejections can be reviewed.
+#[utoipa::path(
+    put,
+    path = "/api/v1/rejected-prices/{rejected_price_id}",
+    params(
+        ("rejected_price_id" = Uuid, Path, description = "Rejected price ID")
+    ),
+    request_body = ReviewRejectedPriceRequest,
+    responses(
+        (status = 200, description = "Rejected price reviewed", body = RejectedPriceResponse),
+        (status = 400, description = "Invalid status"),
+        (status = 401, description = "Unauthorized"),
+        (status = 404, description = "Not found"),
+        (status = 409, description = "Already reviewed"),
+        (status = 500, description = "Internal server error")
+    ),
+    tag = "rejected-prices"
+)]
+pub async fn review_rejected_price_handler(
+    State(db): State<DatabaseConnection>,
+    Extension(_token): Extension<axum_keycloak_auth::decode::KeycloakToken<String>>,
+    Path(rejected_price_id): Path<Uuid>,
+    Json(req): Json<ReviewRejectedPriceRequest>,
+) -> Result<Json<RejectedPriceResponse>, ApiError> {
+    let status = parse_status(&req.status)?;
+    if status == REJECTION_PENDING {
+        return Err(ApiError::BadRequest("status must be accepted or dismissed".to_string()));
+    }
+
+    let (row, asset) = rejected_prices::Entity::find_by_id(rejected_price_id)
+        .find_also_related(assets::Entity)
+        .one(&db)
+        .await?
+        .ok_or(ApiError::NotFound)?;
+    if row.status != REJECTION_PENDING {
+        return Err(ApiError::Conflict(format!("Rejected price is already {}", row.status)));
+    }
+
+    let txn = db.begin().await?;
+    if status == REJECTION_ACCEPTED {
+        asset_prices::Entity::insert(accepted_price_row(&row))
+            .on_conflict(
+                OnConflict::columns([
+                    asset_prices::Column::AssetId,
+                    asset_prices::Column::Timestamp,
+                    asset_prices::Column::Source,
+                ])
+                .update_column(asset_prices::Column::PriceUsd)
+                .to_owned(),
+            )
+            .exec_without_returning(&txn)
+            .await?;
+    }
+    let mut active: rejected_prices::ActiveModel = row.into();
+    active.status = Set(status);
+    active.reviewed_at = Set(Some(Utc::now().into()));
+    let updated = active.update(&txn).await?;
+    txn.commit().await?;
+
+    Ok(Json(RejectedPriceResponse::new(updated, asset.map(|a| a.symbol))))
+}
+
+// === Router setup ===
+
+pub fn create_router() -> Router<DatabaseConnection> {
+    Router::new()
+        .route("/api/v1/rejected-prices", get(list_rejected_prices_handler))
+        .route("/api/v1/rejected-prices/{rejected_price_id}", put(review_rejected_price_handler))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_status() {
+        assert_eq!(parse_status(" Accepted ").unwrap(), REJECTION_ACCEPTED);
+        assert_eq!(parse_status("dismissed").unwrap(), REJECTION_DISMISSED);
+        assert!(parse_status("ignored").is_err());
+    }
+}
@@ -0,0 +1,184 @@
+use axum::{
+    extract::{Extension, Query, State},
+    http::header,
+    response::{IntoResponse, Json, Response},
+    routing::get,
+    Router,
+};
+use axum_keycloak_auth::decode::KeycloakToken;
+use chrono::{DateTime, NaiveDate, NaiveTime, Utc};
+use rust_decimal::Decimal;
+use sea_orm::{ColumnTrait, DatabaseConnection, EntityTrait, QueryFilter, QueryOrder};
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+use utoipa::ToSchema;
+use uuid::Uuid;
+
+use crate::entities::{accounts, tax_disposals};
+use crate::helpers::auth::get_or_create_user;
+use crate::helpers::tax_lots::DISPOSAL_KIND_DISPOSAL;
+use crate::helpers::tax_report::{holding_period, report_row, to_csv, TaxReportRow, TERM_LONG, TERM_SHORT, TERM_UNKNOWN};
+use crate::jobs::tax_lots::{rebuild_user_lots, user_lot_method};
+use super::error::ApiError;
+
+// === Request/Response DTOs ===
+
+#[derive(Debug, Deserialize, ToSchema)]
+pub struct TaxReportQuery {
+    /// Tax year (calendar year, UTC)
+    pub year: i32,
+    /// "json" (default) or "csv"
+    pub format: Option<String>,
+    /// Rebuild the user's tax lots before reporting (default false)
+    #[serde(default)]
+    pub refresh: bool,
+}
+
+#[derive(Debug, Serialize, Deserialize, ToSchema)]
+pub struct TaxReportResponse {
+    pub year: i32,
+    /// Tax lot method the disposals were matched with
+    pub method: String,
+    pub total_proceeds_usd: String,
+    pub total_cost_basis_usd: String,
+    pub short_term_gain_usd: String,
+    pub long_term_gain_usd: String,
+    /// Gain of disposals whose acquisition is not in the ledger (counted at zero cost)
+    pub unknown_term_gain_usd: String,
+    /// Disposals, oldest first
+    pub disposals: Vec<TaxReportRow>,
+}
+
+// === Helper Functions ===
+
+/// Start and end (exclusive) of a calendar year, UTC
+fn year_window(year: i32) -> Result<(DateTime<Utc>, DateTime<Utc>), ApiError> {
+    let start = NaiveDate::from_ymd_opt(year, 1, 1);
+    let end = year.checked_add(1).and_then(|next| NaiveDate::from_ymd_opt(next, 1, 1));
+    match (start, end) {
+        (Some(start), Some(end)) => Ok((start.and_time(NaiveTime::MIN).and_utc(), end.and_time(NaiveTime::MIN).and_utc())),
+        _ => Err(ApiError::BadRequest(format!("Invalid year {}", year))),
+    }
+}
+
+// === API Handlers ===
+
+/// Export the tax report of a year
+///
+/// Sales and fees paid in crypto across the user's accounts, one row per tax lot they drew
+/// from (see `/api/v1/accounts/{account_id}/tax-lots`), with dates acquired and sold,
+/// proceeds, cost basis, gain or loss and holding period (long term when held more than a
+/// year). Withdrawals are not disposals and are left out. `format=csv` returns a CSV in the
+/// layout of Form 8949.
+#[utoipa::path(
+    get,
+    path = "/api/v1/reports/tax",
+    params(
+        ("year" = i32, Query, description = "Tax year, e.g. 2025"),
+        ("format" = Option<String>, Query, description = "json (default) or csv"),
+        ("refresh" = Option<bool>, Query, description = "Rebuild tax lots before reporting (default false)")
+    ),
+    responses(
+        (status = 200, description = "Tax report (JSON, or text/csv with format=csv)", body = TaxReportResponse),
+        (status = 400, description = "Invalid year or format"),
+        (status = 401, description = "Unauthorized"),
+        (status = 500, description = "Internal server error")
+    ),
+    tag = "reports"
+)]
+pub async fn get_tax_report_handler(
+    State(db): State<DatabaseConnection>,
+    Extension(token): Extension<KeycloakToken<String>>,
+    Query(query): Query<TaxReportQuery>,
+) -> Result<Response, ApiError> {
+    let user = get_or_create_user(&db, &token).await?;
+
+    let csv = match query.format.as_deref().map(|f| f.trim().to_lowercase()) {
+        None => false,
+        Some(format) if format == "json" => false,
+        Some(format) if format == "csv" => true,
+        Some(format) => return Err(ApiError::BadRequest(format!("Invalid format '{}'. Expected json or csv", format))),
+    };
+    let (start, end) = year_window(query.year)?;
+
+    if query.refresh {
+        rebuild_user_lots(&db, &user).await.map_err(|e| {
+            tracing::error!("Failed to rebuild tax lots of user {}: {}", user.id, e);
+            ApiError::InternalServerError(e.to_string())
+        })?;
+    }
+
+    let account_names: HashMap<Uuid, String> = accounts::Entity::find()
+        .filter(accounts::Column::UserId.eq(user.id))
+        .all(&db)
+        .await?
+        .into_iter()
+        .map(|a| (a.id, a.name))
+        .collect();
+    let disposals = tax_disposals::Entity::find()
+        .filter(tax_disposals::Column::AccountId.is_in(account_names.keys().copied().collect::<Vec<_>>()))
+        .filter(tax_disposals::Column::Kind.eq(DISPOSAL_KIND_DISPOSAL))
+        .filter(tax_disposals::Column::DisposedAt.gte(start))
+        .filter(tax_disposals::Column::DisposedAt.lt(end))
+        .order_by_asc(tax_disposals::Column::DisposedAt)
+        .all(&db)
+        .await?;
+    let rows: Vec<TaxReportRow> = disposals
+        .iter()
+        .map(|d| report_row(d, account_names.get(&d.account_id).map(String::as_str).unwrap_or_default()))
+        .collect();
+
+    if csv {
+        let body = to_csv(&rows).map_err(|e| ApiError::InternalServerError(format!("Failed to write CSV: {}", e)))?;
+        return Ok((
+            [
+                (header::CONTENT_TYPE, "text/csv; charset=utf-8".to_string()),
+                (header::CONTENT_DISPOSITION, format!("attachment; filename=\"tax-report-{}.csv\"", query.year)),
+            ],
+            body,
+        )
+            .into_response());
+    }
+
+    let mut gains: HashMap<&str, Decimal> = HashMap::new();
+    for disposal in &disposals {
+        let (term, _) = holding_period(disposal.acquired_at.map(|at| at.to_utc()), disposal.disposed_at.to_utc());
+        *gains.entry(term).or_default() += disposal.proceeds_usd - disposal.cost_basis_usd;
+    }
+    let gain = |term: &str| gains.get(term).copied().unwrap_or_default().round_dp(2).normalize().to_string();
+    let total_proceeds: Decimal = disposals.iter().map(|d| d.proceeds_usd).sum();
+    let total_cost: Decimal = disposals.iter().map(|d| d.cost_basis_usd).sum();
+
+    Ok(Json(TaxReportResponse {
+        year: query.year,
+        method: user_lot_method(&user).as_str().to_string(),
+        total_proceeds_usd: total_proceeds.round_dp(2).normalize().to_string(),
+        total_cost_basis_usd: total_cost.round_dp(2).normalize().to_string(),
+        short_term_gain_usd: gain(TERM_SHORT),
+        long_term_gain_usd: gain(TERM_LONG),
+        unknown_term_gain_usd: gain(TERM_UNKNOWN),
+        disposals: rows,
+    })
+    .into_response())
+}
+
+// === Router setup ===
+
+pub fn create_router() -> Router<DatabaseConnection> {
+    Router::new().route("/api/v1/reports/tax", get(get_tax_report_handler))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_year_window() {
+        let (start, end) = year_window(2025).unwrap();
+        assert_eq!(start.to_rfc3339(), "2025-01-01T00:00:00+00:00");
+        assert_eq!(end.to_rfc3339(), "2026-01-01T00:00:00+00:00");
+
+        assert!(year_window(i32::MAX).is_err());
+        assert!(year_window(i32::MIN).is_err());
+    }
+}
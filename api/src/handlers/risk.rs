@@ -0,0 +1,272 @@
+use axum::{
+    extract::{Extension, Path, Query, State},
+    response::Json,
+    routing::get,
+    Router,
+};
+use axum_keycloak_auth::decode::KeycloakToken;
+use chrono::{Duration, NaiveTime, Utc};
+use rust_decimal::prelude::ToPrimitive;
+use sea_orm::{ColumnTrait, DatabaseConnection, EntityTrait, QueryFilter};
+use serde::{Deserialize, Serialize};
+use utoipa::ToSchema;
+use uuid::Uuid;
+
+use crate::domain::AllocationItem;
+use crate::entities::{portfolio_accounts, portfolio_allocations};
+use crate::helpers::asset_identity::{AssetIdentityNormalizer, NormalizationResult};
+use crate::helpers::auth::{check_portfolio_ownership, get_or_create_user};
+use crate::helpers::candles::{load_candles, CandleInterval};
+use crate::helpers::performance::{load_cash_flows, load_value_series, time_weighted_returns};
+use crate::helpers::price_resolution::PriceResolutionConfig;
+use crate::helpers::risk::{
+    dated_returns, periods_per_year, risk_free_rate_from_env, risk_metrics, simple_returns, value_at_risk,
+    weighted_returns, RiskMetrics, VarEstimate, DAYS_PER_YEAR, VAR_CONFIDENCE_LEVELS, VAR_HORIZONS,
+};
+use super::error::ApiError;
+
+/// Default and maximum lookback in days
+const DEFAULT_RISK_DAYS: i64 = 90;
+const MAX_RISK_DAYS: i64 = 3650;
+
+// === Request/Response DTOs ===
+
+#[derive(Debug, Deserialize, ToSchema)]
+pub struct RiskQuery {
+    /// Lookback in days (default 90, max 3650)
+    pub days: Option<i64>,
+}
+
+/// Annualized risk metrics of a return series; ratios are absent when undefined
+#[derive(Debug, Serialize, Deserialize, ToSchema)]
+pub struct RiskMetricsResponse {
+    /// Returns the metrics were computed from
+    pub observations: usize,
+    pub annualized_return_percent: f64,
+    pub annualized_volatility_percent: f64,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub sharpe_ratio: Option<f64>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub sortino_ratio: Option<f64>,
+}
+
+impl From<RiskMetrics> for RiskMetricsResponse {
+    fn from(metrics: RiskMetrics) -> Self {
+        let round = |value: f64| (value * 10_000.0).round() / 10_000.0;
+        Self {
+            observations: metrics.observations,
+            annualized_return_percent: round(metrics.annualized_return * 100.0),
+            annualized_volatility_percent: round(metrics.annualized_volatility * 100.0),
+            sharpe_ratio: metrics.sharpe_ratio.map(round),
+            sortino_ratio: metrics.sortino_ratio.map(round),
+        }
+    }
+}
+
+#[derive(Debug, Serialize, Deserialize, ToSchema)]
+pub struct AssetRiskResponse {
+    pub asset: String,
+    /// Weight in the current allocation (0-100)
+    pub weight: f64,
+    /// Absent when the asset has fewer than three daily prices in the window
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub metrics: Option<RiskMetricsResponse>,
+}
+
+/// Value at risk at one confidence level and horizon: the loss not exceeded with that
+/// confidence, in percent of the current value and in USD
+#[derive(Debug, Serialize, Deserialize, ToSchema)]
+pub struct VarEstimateResponse {
+    /// 95 or 99
+    pub confidence_percent: f64,
+    /// 1 or 7
+    pub horizon_days: usize,
+    /// From the observed returns over the horizon; absent with fewer than two of them
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub historical_percent: Option<f64>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub historical_usd: Option<f64>,
+    /// Assuming normally distributed daily returns
+    pub parametric_percent: f64,
+    pub parametric_usd: f64,
+}
+
+impl VarEstimateResponse {
+    fn new(estimate: VarEstimate, value_usd: f64) -> Self {
+        let round = |value: f64| (value * 10_000.0).round() / 10_000.0;
+        let usd = |fraction: f64| (fraction * value_usd * 100.0).round() / 100.0;
+        Self {
+            confidence_percent: round(estimate.confidence * 100.0),
+            horizon_days: estimate.horizon_days,
+            historical_percent: estimate.historical.map(|v| round(v * 100.0)),
+            historical_usd: estimate.historical.map(usd),
+            parametric_percent: round(estimate.parametric * 100.0),
+            parametric_usd: usd(estimate.parametric),
+        }
+    }
+}
+
+/// Value at risk of the current allocation, from the daily returns its assets would have
+/// had at their current weights
+#[derive(Debug, Serialize, Deserialize, ToSchema)]
+pub struct ValueAtRiskResponse {
+    /// Daily returns the estimates were computed from: days every covered asset has a return
+    pub observations: usize,
+    /// Weight (0-100) of the assets with price history; the others are left out and the
+    /// covered weights scaled up
+    pub covered_weight: f64,
+    /// Current allocation value the USD amounts are of
+    pub value_usd: f64,
+    /// Per confidence level (95%, 99%) and horizon (1 and 7 days)
+    pub estimates: Vec<VarEstimateResponse>,
+}
+
+#[derive(Debug, Serialize, Deserialize, ToSchema)]
+pub struct RiskResponse {
+    pub portfolio_id: Uuid,
+    pub days: i64,
+    /// Annual risk-free rate used for Sharpe and Sortino (`RISK_FREE_RATE`)
+    pub risk_free_rate_percent: f64,
+    /// From the time-weighted returns between snapshots; absent with fewer than three snapshots
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub portfolio: Option<RiskMetricsResponse>,
+    /// From daily closes of the primary price source, per priced asset of the current
+    /// allocation, largest weight first
+    pub assets: Vec<AssetRiskResponse>,
+    /// Absent when fewer than two days have a return of every covered asset
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub value_at_risk: Option<ValueAtRiskResponse>,
+}
+
+// === API Handlers ===
+
+/// Get a portfolio's risk metrics
+///
+/// Annualized return, volatility, Sharpe and Sortino ratios over the last `days`: for the
+/// portfolio from its snapshots, with deposits and withdrawals taken out as in the
+/// performance endpoint, and for each asset of its current allocation from daily prices.
+/// Value at risk (95% and 99%, 1 and 7 days, historical and parametric) of the current
+/// allocation comes from the daily prices of its assets weighted at their current weights.
+#[utoipa::path(
+    get,
+    path = "/api/v1/portfolios/{portfolio_id}/risk",
+    params(
+        ("portfolio_id" = Uuid, Path, description = "Portfolio ID"),
+        ("days" = Option<i64>, Query, description = "Lookback in days (default 90, max 3650)")
+    ),
+    responses(
+        (status = 200, description = "Risk metrics", body = RiskResponse),
+        (status = 400, description = "Invalid days"),
+        (status = 401, description = "Unauthorized"),
+        (status = 403, description = "Forbidden - portfolio does not belong to user"),
+        (status = 404, description = "Portfolio not found"),
+        (status = 500, description = "Internal server error")
+    ),
+    tag = "portfolios"
+)]
+pub async fn get_portfolio_risk_handler(
+    State(db): State<DatabaseConnection>,
+    Extension(token): Extension<KeycloakToken<String>>,
+    Path(portfolio_id): Path<Uuid>,
+    Query(query): Query<RiskQuery>,
+) -> Result<Json<RiskResponse>, ApiError> {
+    let user = get_or_create_user(&db, &token).await?;
+    check_portfolio_ownership(&db, portfolio_id, user.id).await?;
+
+    let days = query.days.unwrap_or(DEFAULT_RISK_DAYS);
+    if !(2..=MAX_RISK_DAYS).contains(&days) {
+        return Err(ApiError::BadRequest(format!("days must be between 2 and {}", MAX_RISK_DAYS)));
+    }
+    let risk_free_rate = risk_free_rate_from_env();
+    let today = Utc::now().date_naive();
+    let start = today - Duration::days(days);
+
+    // Portfolio: time-weighted returns between snapshots
+    let values = load_value_series(&db, portfolio_id, Some(start)).await?;
+    let account_ids: Vec<Uuid> = portfolio_accounts::Entity::find()
+        .filter(portfolio_accounts::Column::PortfolioId.eq(portfolio_id))
+        .all(&db)
+        .await?
+        .into_iter()
+        .map(|pa| pa.account_id)
+        .collect();
+    let flows = match (values.first(), values.last()) {
+        (Some((first, _)), Some((last, _))) => load_cash_flows(&db, &account_ids, *first, *last).await?,
+        _ => Default::default(),
+    };
+    let returns: Vec<f64> = time_weighted_returns(&values, &flows.by_date)
+        .iter()
+        .filter_map(|r| r.return_rate.and_then(|rate| rate.to_f64()))
+        .collect();
+    let dates: Vec<_> = values.iter().map(|(date, _)| *date).collect();
+    let portfolio = risk_metrics(&returns, periods_per_year(&dates), risk_free_rate).map(RiskMetricsResponse::from);
+
+    // Assets: daily closes of the current allocation's priced holdings
+    let allocation = portfolio_allocations::Entity::find()
+        .filter(portfolio_allocations::Column::PortfolioId.eq(portfolio_id))
+        .one(&db)
+        .await?;
+    let total_value = allocation.as_ref().and_then(|a| a.total_value_usd.to_f64()).unwrap_or(0.0);
+    let mut holdings: Vec<AllocationItem> = allocation
+        .and_then(|a| serde_json::from_value(a.holdings).ok())
+        .unwrap_or_default();
+    holdings.retain(|h| !h.unpriced && h.weight > 0.0);
+    holdings.sort_by(|a, b| b.weight.total_cmp(&a.weight));
+
+    let source = PriceResolutionConfig::from_env().primary_source;
+    let from = start.and_time(NaiveTime::MIN).and_utc();
+    let to = (today + Duration::days(1)).and_time(NaiveTime::MIN).and_utc();
+    let normalizer = AssetIdentityNormalizer::new(db.clone());
+    let mut assets = Vec::with_capacity(holdings.len());
+    let mut weighted = Vec::new();
+    for holding in holdings {
+        let metrics = match normalizer.normalize_from_symbol(&holding.asset).await {
+            NormalizationResult::Mapped(identity) => {
+                let candles = load_candles(&db, identity.asset_id, &source, CandleInterval::Day, from, to).await?;
+                let closes: Vec<_> = candles.iter().map(|c| c.close).collect();
+                let dated: Vec<_> = candles.iter().map(|c| (c.open_time.date_naive(), c.close)).collect();
+                let returns = dated_returns(&dated);
+                if !returns.is_empty() {
+                    weighted.push((holding.weight, returns));
+                }
+                risk_metrics(&simple_returns(&closes), DAYS_PER_YEAR, risk_free_rate)
+            }
+            NormalizationResult::Unknown { .. } => None,
+        };
+        assets.push(AssetRiskResponse {
+            asset: holding.asset,
+            weight: holding.weight,
+            metrics: metrics.map(RiskMetricsResponse::from),
+        });
+    }
+
+    // Value at risk of the current weights
+    let daily = weighted_returns(&weighted);
+    let estimates: Vec<VarEstimateResponse> = VAR_CONFIDENCE_LEVELS
+        .iter()
+        .flat_map(|(confidence, z)| VAR_HORIZONS.iter().map(move |h| (*confidence, *z, *h)))
+        .filter_map(|(confidence, z, horizon)| value_at_risk(&daily, confidence, z, horizon))
+        .map(|estimate| VarEstimateResponse::new(estimate, total_value))
+        .collect();
+    let value_at_risk = (!estimates.is_empty()).then(|| ValueAtRiskResponse {
+        observations: daily.len(),
+        covered_weight: weighted.iter().map(|(w, _)| w).sum(),
+        value_usd: (total_value * 100.0).round() / 100.0,
+        estimates,
+    });
+
+    Ok(Json(RiskResponse {
+        portfolio_id,
+        days,
+        risk_free_rate_percent: risk_free_rate * 100.0,
+        portfolio,
+        assets,
+        value_at_risk,
+    }))
+}
+
+// === Router setup ===
+
+pub fn create_router() -> Router<DatabaseConnection> {
+    Router::new().route("/api/v1/portfolios/{portfolio_id}/risk", get(get_portfolio_risk_handler))
+}
@@ -1,19 +1,32 @@
 use axum::{
+    body::Body,
     extract::{Extension, Path, Query, State},
-    response::Json,
+    http::header,
+    response::{IntoResponse, Json, Response},
     routing::{get, post},
     Router,
 };
 use axum_keycloak_auth::decode::KeycloakToken;
 use chrono::NaiveDate;
-use sea_orm::{ColumnTrait, DatabaseConnection, EntityTrait, QueryFilter, QueryOrder};
+use std::collections::HashMap;
+use rust_decimal::Decimal;
+use sea_orm::prelude::DateTimeWithTimeZone;
+use sea_orm::{ColumnTrait, Condition, DatabaseConnection, EntityTrait, QueryFilter, QueryOrder, QuerySelect};
 use serde::{Deserialize, Serialize};
 use utoipa::ToSchema;
 use uuid::Uuid;
 
+use crate::domain::{concentration_risk, ConcentrationRisk, PortfolioGuardrails, SnapshotHolding};
 use crate::entities::{portfolios, snapshots};
-use crate::helpers::auth::get_or_create_user;
+use crate::helpers::auth::{check_portfolio_ownership, get_or_create_user};
+use crate::helpers::fx::{load_user_fx_rates, FxRates};
+use crate::helpers::pagination::{finish_page, keyset_after, keyset_before, Cursor, PageRequest};
+use crate::helpers::snapshot_export::{export_stream, ExportFormat};
+use crate::helpers::value_history::{latest_per_period, ValueGranularity};
+use crate::helpers::value_deltas::{load_value_deltas, value_delta, ValueDelta, ValueDeltas};
 use crate::jobs::portfolio_snapshot;
+
+pub use crate::helpers::snapshot_export::SnapshotExportRow;
 use super::error::ApiError;
 
 // === Request/Response DTOs ===
@@ -72,6 +85,22 @@ pub struct SnapshotResponse {
     pub snapshot_date: String, // ISO 8601 date
     pub snapshot_type: String,
     pub total_value_usd: String,
+    /// Value of the holdings before subtracting borrowed amounts
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub gross_value_usd: Option<String>,
+    /// Value of borrowed amounts (positive)
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub debt_usd: Option<String>,
+    /// Gross value minus debt
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub net_value_usd: Option<String>,
+    /// User's base currency (ISO 4217) of `total_value` and `net_value`
+    pub currency: String,
+    /// Total value in `currency`, at the FX rate of the snapshot date
+    pub total_value: String,
+    /// Net value in `currency`, at the FX rate of the snapshot date
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub net_value: Option<String>,
     /// Holdings data as JSON array of SnapshotHolding objects
     /// See domain::SnapshotHolding for the structured schema
     pub holdings: serde_json::Value,
@@ -80,83 +109,166 @@ pub struct SnapshotResponse {
     pub metadata: Option<serde_json::Value>,
     #[serde(skip_serializing_if = "Option::is_none")]
     pub allocation_id: Option<Uuid>, // Reference to portfolio_allocations
+    /// Construction run that produced the allocation the snapshot was taken from
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub construction_run_id: Option<Uuid>,
+    /// Concentration of the snapshot's holdings, with the largest alt checked against the
+    /// portfolio's current `max_alt_cap` guardrail; absent when the holdings can't be read
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub concentration: Option<ConcentrationRisk>,
     pub created_at: String, // ISO 8601 datetime
 }
 
 impl From<snapshots::Model> for SnapshotResponse {
     fn from(model: snapshots::Model) -> Self {
+        Self::in_currency(model, &FxRates::usd(), None)
+    }
+}
+
+impl SnapshotResponse {
+    /// Response with values converted to the currency of `fx`, and concentration checked
+    /// against `max_alt_cap`
+    pub fn in_currency(model: snapshots::Model, fx: &FxRates, max_alt_cap: Option<f64>) -> Self {
+        let date = model.snapshot_date;
+        let convert = |usd| fx.convert_on(usd, date).round_dp(2).to_string();
+        let concentration = serde_json::from_value::<Vec<SnapshotHolding>>(model.holdings.clone()).ok().map(|holdings| {
+            concentration_risk(
+                holdings.iter().filter(|h| !h.unpriced).map(|h| (h.asset.as_str(), None, h.weight)),
+                max_alt_cap,
+            )
+        });
         Self {
             id: model.id,
             portfolio_id: model.portfolio_id,
             snapshot_date: model.snapshot_date.to_string(),
             snapshot_type: model.snapshot_type,
             total_value_usd: model.total_value_usd.to_string(),
+            gross_value_usd: model.gross_value_usd.map(|v| v.to_string()),
+            debt_usd: model.debt_usd.map(|v| v.to_string()),
+            net_value_usd: model.net_value_usd.map(|v| v.to_string()),
+            currency: fx.currency().to_string(),
+            total_value: convert(model.total_value_usd),
+            net_value: model.net_value_usd.map(convert),
             holdings: model.holdings,
             metadata: model.metadata,
             allocation_id: model.allocation_id,
+            construction_run_id: model.construction_run_id,
+            concentration,
             created_at: model.created_at.to_rfc3339(),
         }
     }
 }
 
+/// Latest snapshot with value changes against earlier snapshots
+#[derive(Debug, Serialize, Deserialize, ToSchema)]
+pub struct LatestSnapshotResponse {
+    #[serde(flatten)]
+    pub snapshot: SnapshotResponse,
+    /// Value change over 24h / 7d / 30d, relative to the snapshot date
+    pub deltas: ValueDeltas,
+}
+
+/// Summary of a portfolio's latest snapshot
+#[derive(Debug, Serialize, Deserialize, ToSchema)]
+pub struct LatestSnapshotSummary {
+    pub snapshot_id: Uuid,
+    pub snapshot_date: String, // ISO 8601 date
+    pub snapshot_type: String,
+    pub total_value_usd: String,
+    /// Total value in the user's base currency, at the FX rate of the snapshot date
+    pub total_value: String,
+    /// Value change against the latest snapshot dated a day or more earlier; absent without one
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub change_24h: Option<ValueDelta>,
+    pub created_at: String, // ISO 8601 datetime
+}
+
+/// A portfolio with its latest snapshot
+#[derive(Debug, Serialize, Deserialize, ToSchema)]
+pub struct PortfolioLatestSnapshot {
+    pub portfolio_id: Uuid,
+    pub portfolio_name: String,
+    /// Absent when the portfolio has no snapshot yet
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub latest: Option<LatestSnapshotSummary>,
+}
+
+#[derive(Debug, Serialize, Deserialize, ToSchema)]
+pub struct LatestSnapshotsResponse {
+    /// User's base currency (ISO 4217) of `total_value`
+    pub currency: String,
+    /// Every portfolio of the user, by name
+    pub portfolios: Vec<PortfolioLatestSnapshot>,
+}
+
 #[derive(Debug, Deserialize, ToSchema)]
 pub struct ListSnapshotsQuery {
-    /// Filter by start date (ISO 8601 format, inclusive)
-    #[serde(skip_serializing_if = "Option::is_none")]
+    /// Filter by start date (ISO 8601 format, inclusive); also accepted as `from`
+    #[serde(alias = "from", skip_serializing_if = "Option::is_none")]
     pub start_date: Option<String>,
-    /// Filter by end date (ISO 8601 format, inclusive)
-    #[serde(skip_serializing_if = "Option::is_none")]
+    /// Filter by end date (ISO 8601 format, inclusive); also accepted as `to`
+    #[serde(alias = "to", skip_serializing_if = "Option::is_none")]
     pub end_date: Option<String>,
     /// Filter by snapshot type
     #[serde(skip_serializing_if = "Option::is_none")]
     pub snapshot_type: Option<String>,
+    /// "all" (default), or "daily", "weekly" or "monthly" for the latest snapshot of each period
+    pub granularity: Option<String>,
+    /// "desc" (default, newest first) or "asc"
+    pub sort: Option<String>,
+    /// Page size (enables pagination; default 100 when only `cursor` is given)
+    pub limit: Option<u64>,
+    /// Cursor from a previous page's `next_cursor`
+    pub cursor: Option<String>,
+}
+
+#[derive(Debug, Deserialize, ToSchema)]
+pub struct ExportSnapshotsQuery {
+    /// First snapshot date (YYYY-MM-DD, inclusive)
+    pub from: Option<String>,
+    /// Last snapshot date (YYYY-MM-DD, inclusive)
+    pub to: Option<String>,
+    /// "json" (default) or "csv"
+    pub format: Option<String>,
 }
 
 #[derive(Debug, Serialize, Deserialize, ToSchema)]
 pub struct ListSnapshotsResponse {
     pub portfolio_id: Uuid,
     pub snapshots: Vec<SnapshotResponse>,
+    /// Number of snapshots in this response
     pub total_count: usize,
-}
-
-// === Helper Functions ===
-
-/// Get or create user in database from Keycloak token
-
-/// Check if portfolio belongs to user
-async fn check_portfolio_ownership(
-    db: &DatabaseConnection,
-    portfolio_id: Uuid,
-    user_id: Uuid,
-) -> Result<(), ApiError> {
-    let portfolio = portfolios::Entity::find_by_id(portfolio_id)
-        .one(db)
-        .await?
-        .ok_or(ApiError::NotFound)?;
-
-    if portfolio.user_id != user_id {
-        return Err(ApiError::Forbidden);
-    }
-
-    Ok(())
+    /// Cursor for the next page; absent on the last page
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub next_cursor: Option<String>,
 }
 
 // === API Handlers ===
 
 /// Get snapshots for a specific portfolio
 ///
-/// Retrieves all snapshots for the specified portfolio, with optional date and type filtering.
+/// Retrieves snapshots for the specified portfolio, newest first (or oldest first with
+/// `sort=asc`), with optional date (`start_date` / `from`, `end_date` / `to`) and type
+/// filtering. `granularity=daily|weekly|monthly` returns only the latest snapshot (by date,
+/// then creation time) of each day, week (starting Monday) or month, so charts need not fetch
+/// every snapshot. Pass `limit` to page through the results and `cursor` (from `next_cursor`)
+/// to fetch the following page.
 #[utoipa::path(
     get,
     path = "/api/v1/portfolios/{portfolio_id}/snapshots",
     params(
         ("portfolio_id" = Uuid, Path, description = "Portfolio ID"),
-        ("start_date" = Option<String>, Query, description = "Start date filter (YYYY-MM-DD, inclusive)"),
-        ("end_date" = Option<String>, Query, description = "End date filter (YYYY-MM-DD, inclusive)"),
-        ("snapshot_type" = Option<String>, Query, description = "Snapshot type filter (eod, manual, hourly)")
+        ("start_date" = Option<String>, Query, description = "Start date filter (YYYY-MM-DD, inclusive); alias from"),
+        ("end_date" = Option<String>, Query, description = "End date filter (YYYY-MM-DD, inclusive); alias to"),
+        ("snapshot_type" = Option<String>, Query, description = "Snapshot type filter (eod, manual, hourly, backfill)"),
+        ("granularity" = Option<String>, Query, description = "all (default), daily, weekly or monthly: latest snapshot per period"),
+        ("sort" = Option<String>, Query, description = "desc (default) or asc"),
+        ("limit" = Option<u64>, Query, description = "Page size (1-1000); omit to return all snapshots"),
+        ("cursor" = Option<String>, Query, description = "Opaque cursor from a previous page's next_cursor")
     ),
     responses(
         (status = 200, description = "Snapshots retrieved successfully", body = ListSnapshotsResponse),
+        (status = 400, description = "Invalid date, granularity, sort or cursor"),
         (status = 401, description = "Unauthorized"),
         (status = 403, description = "Forbidden - portfolio does not belong to user"),
         (status = 404, description = "Portfolio not found"),
@@ -176,7 +288,23 @@ async fn list_portfolio_snapshots_handler(
         .map_err(|e| ApiError::InternalServerError(format!("Database error: {}", e)))?;
 
     // Verify portfolio belongs to user
-    check_portfolio_ownership(&db, portfolio_id, user.id).await?;
+    let portfolio = check_portfolio_ownership(&db, portfolio_id, user.id).await?;
+    let max_alt_cap = PortfolioGuardrails::from_json(portfolio.guardrails.as_ref()).max_alt_cap;
+
+    let page = PageRequest::from_query(query.limit, query.cursor.as_deref())?;
+    let granularity = match query.granularity.as_deref().map(|g| g.trim().to_lowercase()) {
+        None => None,
+        Some(g) if g == "all" => None,
+        Some(g) => Some(ValueGranularity::parse(&g).ok_or_else(|| {
+            ApiError::BadRequest(format!("Invalid granularity '{}'. Expected all, daily, weekly or monthly", g))
+        })?),
+    };
+    let ascending = match query.sort.as_deref().map(|s| s.trim().to_lowercase()) {
+        None => false,
+        Some(sort) if sort == "desc" => false,
+        Some(sort) if sort == "asc" => true,
+        Some(sort) => return Err(ApiError::BadRequest(format!("Invalid sort '{}'. Expected asc or desc", sort))),
+    };
 
     // Build query
     let mut snapshot_query = snapshots::Entity::find()
@@ -202,22 +330,74 @@ async fn list_portfolio_snapshots_handler(
         snapshot_query = snapshot_query.filter(snapshots::Column::SnapshotType.eq(snapshot_type));
     }
 
-    // Order by date descending (most recent first)
-    snapshot_query = snapshot_query.order_by_desc(snapshots::Column::SnapshotDate);
+    let cursor_of = |date: NaiveDate, id: Uuid| Cursor::new(date.and_time(chrono::NaiveTime::MIN).and_utc(), id);
+    let (snapshot_models, next_cursor) = match granularity {
+        None => {
+            // Keyset position: snapshots are keyed by (snapshot_date, id)
+            if let Some(after) = &page.after {
+                let (date, id) = (after.timestamp.date_naive(), after.id);
+                snapshot_query = snapshot_query.filter(if ascending {
+                    keyset_after(snapshots::Column::SnapshotDate, snapshots::Column::Id, date, id)
+                } else {
+                    keyset_before(snapshots::Column::SnapshotDate, snapshots::Column::Id, date, id)
+                });
+            }
 
-    // Execute query
-    let snapshot_models = snapshot_query.all(&db).await?;
+            // Order by date (most recent first by default), id breaks ties within a day
+            snapshot_query = if ascending {
+                snapshot_query.order_by_asc(snapshots::Column::SnapshotDate).order_by_asc(snapshots::Column::Id)
+            } else {
+                snapshot_query.order_by_desc(snapshots::Column::SnapshotDate).order_by_desc(snapshots::Column::Id)
+            };
 
+            finish_page(snapshot_query.limit(page.fetch_limit()).all(&db).await?, &page, |s| {
+                cursor_of(s.snapshot_date, s.id)
+            })
+        }
+        Some(granularity) => {
+            // Pick the latest snapshot of each period from the keys alone, then load only those
+            let keys: Vec<(Uuid, NaiveDate, chrono::DateTime<chrono::FixedOffset>)> = snapshot_query
+                .select_only()
+                .column(snapshots::Column::Id)
+                .column(snapshots::Column::SnapshotDate)
+                .column(snapshots::Column::CreatedAt)
+                .into_tuple()
+                .all(&db)
+                .await?;
+            let mut picked = latest_per_period(keys, granularity, |(id, date, created_at)| (*date, (*created_at, *id)));
+            if !ascending {
+                picked.reverse();
+            }
+            if let Some(after) = &page.after {
+                let position = (after.timestamp.date_naive(), after.id);
+                picked.retain(|(id, date, _)| if ascending { (*date, *id) > position } else { (*date, *id) < position });
+            }
+            let (picked, next_cursor) = finish_page(picked, &page, |(id, date, _)| cursor_of(*date, *id));
+
+            let ids: Vec<Uuid> = picked.iter().map(|(id, _, _)| *id).collect();
+            let mut models: HashMap<Uuid, snapshots::Model> = snapshots::Entity::find()
+                .filter(snapshots::Column::Id.is_in(ids.clone()))
+                .all(&db)
+                .await?
+                .into_iter()
+                .map(|s| (s.id, s))
+                .collect();
+            (ids.iter().filter_map(|id| models.remove(id)).collect(), next_cursor)
+        }
+    };
+
+    let fx = load_user_fx_rates(&db, &user).await?;
     let total_count = snapshot_models.len();
     let snapshots: Vec<SnapshotResponse> = snapshot_models
         .into_iter()
-        .map(|model| model.into())
+        .map(|model| SnapshotResponse::in_currency(model, &fx, max_alt_cap))
         .collect();
 
     Ok(Json(ListSnapshotsResponse {
         portfolio_id,
         snapshots,
         total_count,
+        next_cursor,
     }))
 }
 
@@ -359,7 +539,8 @@ async fn create_all_user_snapshots_handler(
 
 /// Get the latest snapshot for a specific portfolio
 ///
-/// Retrieves the most recent snapshot for the specified portfolio based on snapshot_date and created_at.
+/// Retrieves the most recent snapshot for the specified portfolio based on snapshot_date and created_at,
+/// annotated with 24h / 7d / 30d value deltas computed from earlier snapshots.
 #[utoipa::path(
     get,
     path = "/api/v1/portfolios/{portfolio_id}/snapshots/latest",
@@ -367,7 +548,7 @@ async fn create_all_user_snapshots_handler(
         ("portfolio_id" = Uuid, Path, description = "Portfolio ID")
     ),
     responses(
-        (status = 200, description = "Latest snapshot retrieved successfully", body = SnapshotResponse),
+        (status = 200, description = "Latest snapshot retrieved successfully", body = LatestSnapshotResponse),
         (status = 401, description = "Unauthorized"),
         (status = 403, description = "Forbidden - portfolio does not belong to user"),
         (status = 404, description = "Portfolio or snapshot not found"),
@@ -379,14 +560,15 @@ async fn get_latest_portfolio_snapshot_handler(
     State(db): State<DatabaseConnection>,
     Extension(token): Extension<KeycloakToken<String>>,
     Path(portfolio_id): Path<Uuid>,
-) -> Result<Json<SnapshotResponse>, ApiError> {
+) -> Result<Json<LatestSnapshotResponse>, ApiError> {
     // Get or create user
     let user = get_or_create_user(&db, &token)
         .await
         .map_err(|e| ApiError::InternalServerError(format!("Database error: {}", e)))?;
 
     // Verify portfolio belongs to user
-    check_portfolio_ownership(&db, portfolio_id, user.id).await?;
+    let portfolio = check_portfolio_ownership(&db, portfolio_id, user.id).await?;
+    let max_alt_cap = PortfolioGuardrails::from_json(portfolio.guardrails.as_ref()).max_alt_cap;
 
     // Get the latest snapshot for this portfolio
     let latest_snapshot = snapshots::Entity::find()
@@ -397,7 +579,201 @@ async fn get_latest_portfolio_snapshot_handler(
         .await?
         .ok_or(ApiError::NotFound)?;
 
-    Ok(Json(latest_snapshot.into()))
+    let deltas = load_value_deltas(
+        &db,
+        portfolio_id,
+        latest_snapshot.snapshot_date,
+        latest_snapshot.total_value_usd,
+    )
+    .await?;
+
+    let fx = load_user_fx_rates(&db, &user).await?;
+
+    Ok(Json(LatestSnapshotResponse {
+        snapshot: SnapshotResponse::in_currency(latest_snapshot, &fx, max_alt_cap),
+        deltas,
+    }))
+}
+
+/// Get the latest snapshot of every portfolio
+///
+/// Summary of the latest snapshot (by snapshot_date, then created_at) of each portfolio of
+/// the authenticated user: total value in USD and in the base currency, and the change over
+/// 24h against the latest snapshot taken (created_at) at least 24h before it. Lets dashboards show every
+/// portfolio in one call; portfolios without snapshots are listed without `latest`.
+#[utoipa::path(
+    get,
+    path = "/api/v1/snapshots/latest",
+    responses(
+        (status = 200, description = "Latest snapshot of each portfolio", body = LatestSnapshotsResponse),
+        (status = 401, description = "Unauthorized"),
+        (status = 500, description = "Internal server error")
+    ),
+    tag = "snapshots"
+)]
+async fn get_latest_snapshots_handler(
+    State(db): State<DatabaseConnection>,
+    Extension(token): Extension<KeycloakToken<String>>,
+) -> Result<Json<LatestSnapshotsResponse>, ApiError> {
+    let user = get_or_create_user(&db, &token).await?;
+    let user_portfolios = portfolios::Entity::find()
+        .filter(portfolios::Column::UserId.eq(user.id))
+        .order_by_asc(portfolios::Column::Name)
+        .all(&db)
+        .await?;
+    let fx = load_user_fx_rates(&db, &user).await?;
+
+    let portfolio_ids: Vec<Uuid> = user_portfolios.iter().map(|p| p.id).collect();
+    let latest_snapshots: HashMap<Uuid, snapshots::Model> = snapshots::Entity::find()
+        .distinct_on([snapshots::Column::PortfolioId])
+        .filter(snapshots::Column::PortfolioId.is_in(portfolio_ids))
+        .order_by_asc(snapshots::Column::PortfolioId)
+        .order_by_desc(snapshots::Column::SnapshotDate)
+        .order_by_desc(snapshots::Column::CreatedAt)
+        .all(&db)
+        .await?
+        .into_iter()
+        .map(|s| (s.portfolio_id, s))
+        .collect();
+
+    // Newest snapshot of each portfolio taken at least 24h before its latest one
+    let baselines: Vec<BaselineRow> = if latest_snapshots.is_empty() {
+        Vec::new()
+    } else {
+        let cutoffs = latest_snapshots.values().fold(Condition::any(), |cond, s| {
+            cond.add(
+                Condition::all()
+                    .add(snapshots::Column::PortfolioId.eq(s.portfolio_id))
+                    .add(snapshots::Column::CreatedAt.lte(baseline_cutoff(s.created_at))),
+            )
+        });
+        snapshots::Entity::find()
+            .select_only()
+            .column(snapshots::Column::PortfolioId)
+            .column(snapshots::Column::CreatedAt)
+            .column(snapshots::Column::SnapshotDate)
+            .column(snapshots::Column::TotalValueUsd)
+            .distinct_on([snapshots::Column::PortfolioId])
+            .filter(cutoffs)
+            .order_by_asc(snapshots::Column::PortfolioId)
+            .order_by_desc(snapshots::Column::CreatedAt)
+            .into_tuple()
+            .all(&db)
+            .await?
+    };
+
+    let mut results = Vec::with_capacity(user_portfolios.len());
+    for portfolio in user_portfolios {
+        let latest = latest_snapshots.get(&portfolio.id).map(|snapshot| {
+            let baseline = pick_baseline(portfolio.id, snapshot.created_at, &baselines);
+            LatestSnapshotSummary {
+                snapshot_id: snapshot.id,
+                snapshot_date: snapshot.snapshot_date.to_string(),
+                snapshot_type: snapshot.snapshot_type.clone(),
+                total_value_usd: snapshot.total_value_usd.to_string(),
+                total_value: fx.convert_on(snapshot.total_value_usd, snapshot.snapshot_date).round_dp(2).to_string(),
+                change_24h: value_delta(snapshot.total_value_usd, baseline),
+                created_at: snapshot.created_at.to_rfc3339(),
+            }
+        });
+        results.push(PortfolioLatestSnapshot { portfolio_id: portfolio.id, portfolio_name: portfolio.name, latest });
+    }
+
+    Ok(Json(LatestSnapshotsResponse { currency: fx.currency().to_string(), portfolios: results }))
+}
+
+/// `(portfolio_id, created_at, snapshot_date, total_value_usd)` of a baseline candidate
+type BaselineRow = (Uuid, DateTimeWithTimeZone, NaiveDate, Decimal);
+
+/// Latest creation time a snapshot may have to serve as the 24h baseline of one taken at `latest`
+fn baseline_cutoff(latest: DateTimeWithTimeZone) -> DateTimeWithTimeZone {
+    latest - chrono::Duration::hours(24)
+}
+
+/// `(date, total value)` of the newest candidate of `portfolio_id` taken at least 24h before
+/// `latest`, if any
+fn pick_baseline(
+    portfolio_id: Uuid,
+    latest: DateTimeWithTimeZone,
+    candidates: &[BaselineRow],
+) -> Option<(NaiveDate, Decimal)> {
+    let cutoff = baseline_cutoff(latest);
+    candidates
+        .iter()
+        .filter(|(id, created_at, _, _)| *id == portfolio_id && *created_at <= cutoff)
+        .max_by_key(|(_, created_at, _, _)| *created_at)
+        .map(|(_, _, date, value)| (*date, *value))
+}
+
+/// Export a portfolio's snapshots
+///
+/// Every snapshot of the portfolio dated `from` to `to` (inclusive, both optional), oldest
+/// first, with its holdings flattened into one row per holding (asset, quantity, price, value,
+/// weight) next to the snapshot's id, date, type, creation time and total value. A snapshot
+/// without holdings has one row with empty holding columns. `format=csv` returns a CSV with a
+/// header line; the default is a JSON array of the same rows. The response is streamed, so
+/// long histories need no paging.
+#[utoipa::path(
+    get,
+    path = "/api/v1/portfolios/{portfolio_id}/snapshots/export",
+    params(
+        ("portfolio_id" = Uuid, Path, description = "Portfolio ID"),
+        ("from" = Option<String>, Query, description = "First snapshot date (YYYY-MM-DD, inclusive)"),
+        ("to" = Option<String>, Query, description = "Last snapshot date (YYYY-MM-DD, inclusive)"),
+        ("format" = Option<String>, Query, description = "json (default) or csv")
+    ),
+    responses(
+        (status = 200, description = "Snapshot rows (JSON array, or text/csv with format=csv)", body = [SnapshotExportRow]),
+        (status = 400, description = "Invalid date or format"),
+        (status = 401, description = "Unauthorized"),
+        (status = 403, description = "Forbidden - portfolio does not belong to user"),
+        (status = 404, description = "Portfolio not found"),
+        (status = 500, description = "Internal server error")
+    ),
+    tag = "snapshots"
+)]
+async fn export_portfolio_snapshots_handler(
+    State(db): State<DatabaseConnection>,
+    Extension(token): Extension<KeycloakToken<String>>,
+    Path(portfolio_id): Path<Uuid>,
+    Query(query): Query<ExportSnapshotsQuery>,
+) -> Result<Response, ApiError> {
+    let user = get_or_create_user(&db, &token).await?;
+    check_portfolio_ownership(&db, portfolio_id, user.id).await?;
+
+    let format = match query.format.as_deref() {
+        None => ExportFormat::Json,
+        Some(value) => ExportFormat::parse(value)
+            .ok_or_else(|| ApiError::BadRequest(format!("Invalid format '{}'. Expected json or csv", value)))?,
+    };
+    let parse = |value: &str, field: &str| {
+        NaiveDate::parse_from_str(value.trim(), "%Y-%m-%d")
+            .map_err(|_| ApiError::BadRequest(format!("Invalid {} '{}'. Expected YYYY-MM-DD", field, value)))
+    };
+    let from = query.from.as_deref().map(|v| parse(v, "from")).transpose()?;
+    let to = query.to.as_deref().map(|v| parse(v, "to")).transpose()?;
+    if let (Some(from), Some(to)) = (from, to) {
+        if from > to {
+            return Err(ApiError::BadRequest("from must not be after to".to_string()));
+        }
+    }
+
+    let (content_type, extension) = match format {
+        ExportFormat::Json => ("application/json", "json"),
+        ExportFormat::Csv => ("text/csv; charset=utf-8", "csv"),
+    };
+    let body = Body::from_stream(export_stream(db, portfolio_id, from, to, format));
+    Ok((
+        [
+            (header::CONTENT_TYPE, content_type.to_string()),
+            (
+                header::CONTENT_DISPOSITION,
+                format!("attachment; filename=\"snapshots-{}.{}\"", portfolio_id, extension),
+            ),
+        ],
+        body,
+    )
+        .into_response())
 }
 
 /// Create router for snapshot endpoints
@@ -411,8 +787,53 @@ pub fn create_router() -> Router<DatabaseConnection> {
             "/api/v1/portfolios/{portfolio_id}/snapshots/latest",
             get(get_latest_portfolio_snapshot_handler),
         )
+        .route(
+            "/api/v1/portfolios/{portfolio_id}/snapshots/export",
+            get(export_portfolio_snapshots_handler),
+        )
+        .route("/api/v1/snapshots/latest", get(get_latest_snapshots_handler))
         .route(
             "/api/v1/snapshots/create-all",
             post(create_all_user_snapshots_handler),
         )
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use chrono::{TimeZone, Utc};
+
+    fn at(day: u32, hour: u32) -> DateTimeWithTimeZone {
+        Utc.with_ymd_and_hms(2026, 3, day, hour, 0, 0).unwrap().fixed_offset()
+    }
+
+    fn date(day: u32) -> NaiveDate {
+        NaiveDate::from_ymd_opt(2026, 3, day).unwrap()
+    }
+
+    #[test]
+    fn test_pick_baseline_takes_newest_snapshot_24h_before_latest() {
+        let portfolio = Uuid::new_v4();
+        let other = Uuid::new_v4();
+        let candidates = vec![
+            (portfolio, at(9, 12), date(9), Decimal::from(900)),
+            (portfolio, at(10, 12), date(10), Decimal::from(1000)),
+            // Same calendar day as the 24h mark but less than 24h old
+            (portfolio, at(10, 18), date(10), Decimal::from(1050)),
+            (other, at(11, 0), date(11), Decimal::from(5)),
+        ];
+
+        assert_eq!(pick_baseline(portfolio, at(11, 12), &candidates), Some((date(10), Decimal::from(1000))));
+        assert_eq!(pick_baseline(portfolio, at(11, 11), &candidates), Some((date(9), Decimal::from(900))));
+    }
+
+    #[test]
+    fn test_pick_baseline_without_snapshot_24h_earlier() {
+        let portfolio = Uuid::new_v4();
+        let candidates = vec![(portfolio, at(10, 18), date(10), Decimal::from(1050))];
+
+        assert_eq!(pick_baseline(portfolio, at(11, 12), &candidates), None);
+        assert_eq!(pick_baseline(Uuid::new_v4(), at(11, 12), &candidates), None);
+        assert!(value_delta(Decimal::from(1100), pick_baseline(portfolio, at(11, 12), &candidates)).is_none());
+    }
+}
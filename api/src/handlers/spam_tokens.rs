@@ -0,0 +1,299 @@
+use axum::{
+    extract::{Path, Query, State},
+    http::StatusCode,
+    response::Json,
+    routing::get,
+    Extension, Router,
+};
+use chrono::Utc;
+use sea_orm::{
+    ActiveModelTrait, ColumnTrait, Condition, DatabaseConnection, EntityTrait, QueryFilter, QueryOrder, Set,
+};
+use serde::{Deserialize, Serialize};
+use utoipa::{IntoParams, ToSchema};
+use uuid::Uuid;
+
+use crate::entities::spam_tokens;
+use super::error::ApiError;
+
+// === Request / Response DTOs ===
+
+#[derive(Debug, Serialize, Deserialize, ToSchema)]
+pub struct SpamTokenResponse {
+    pub id: Uuid,
+    /// Chain the token lives on, e.g. "solana"
+    pub chain: String,
+    /// Token contract address or SPL mint address
+    pub token_address: String,
+    /// Why the token was blocked
+    pub reason: Option<String>,
+    pub created_at: String,
+    pub updated_at: String,
+}
+
+impl From<spam_tokens::Model> for SpamTokenResponse {
+    fn from(m: spam_tokens::Model) -> Self {
+        Self {
+            id: m.id,
+            chain: m.chain,
+            token_address: m.token_address,
+            reason: m.reason,
+            created_at: m.created_at.to_rfc3339(),
+            updated_at: m.updated_at.to_rfc3339(),
+        }
+    }
+}
+
+#[derive(Debug, Serialize, Deserialize, ToSchema)]
+pub struct CreateSpamTokenRequest {
+    /// Chain the token lives on (default: "solana")
+    #[serde(default = "default_chain")]
+    pub chain: String,
+    /// Token contract address or SPL mint address, exactly as reported by the chain
+    pub token_address: String,
+    /// Why the token is blocked (e.g. "phishing airdrop")
+    pub reason: Option<String>,
+}
+
+#[derive(Debug, Serialize, Deserialize, ToSchema)]
+pub struct UpdateSpamTokenRequest {
+    /// Why the token is blocked
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub reason: Option<String>,
+}
+
+#[derive(Debug, Deserialize, IntoParams)]
+pub struct ListSpamTokensQuery {
+    /// Filter by chain, e.g. "solana"
+    pub chain: Option<String>,
+}
+
+fn default_chain() -> String {
+    "solana".to_string()
+}
+
+// === Handlers ===
+
+/// List spam tokens
+///
+/// Returns the blocklist of tokens dropped from wallet holdings during sync.
+#[utoipa::path(
+    get,
+    path = "/api/v1/spam-tokens",
+    params(ListSpamTokensQuery),
+    responses(
+        (status = 200, description = "List of spam tokens", body = Vec<SpamTokenResponse>),
+        (status = 401, description = "Unauthorized"),
+        (status = 500, description = "Internal server error")
+    ),
+    tag = "spam-tokens"
+)]
+pub async fn list_spam_tokens_handler(
+    State(db): State<DatabaseConnection>,
+    Extension(_token): Extension<axum_keycloak_auth::decode::KeycloakToken<String>>,
+    Query(q): Query<ListSpamTokensQuery>,
+) -> Result<Json<Vec<SpamTokenResponse>>, ApiError> {
+    let mut condition = Condition::all();
+    if let Some(chain) = q.chain {
+        condition = condition.add(spam_tokens::Column::Chain.eq(chain.trim().to_lowercase()));
+    }
+
+    let rows = spam_tokens::Entity::find()
+        .filter(condition)
+        .order_by_asc(spam_tokens::Column::Chain)
+        .order_by_asc(spam_tokens::Column::TokenAddress)
+        .all(&db)
+        .await?;
+
+    Ok(Json(rows.into_iter().map(|r| r.into()).collect()))
+}
+
+/// Get a spam token by ID
+#[utoipa::path(
+    get,
+    path = "/api/v1/spam-tokens/{token_id}",
+    params(
+        ("token_id" = Uuid, Path, description = "Spam token ID")
+    ),
+    responses(
+        (status = 200, description = "Spam token", body = SpamTokenResponse),
+        (status = 401, description = "Unauthorized"),
+        (status = 404, description = "Not found"),
+        (status = 500, description = "Internal server error")
+    ),
+    tag = "spam-tokens"
+)]
+pub async fn get_spam_token_handler(
+    State(db): State<DatabaseConnection>,
+    Extension(_token): Extension<axum_keycloak_auth::decode::KeycloakToken<String>>,
+    Path(token_id): Path<Uuid>,
+) -> Result<Json<SpamTokenResponse>, ApiError> {
+    let row = spam_tokens::Entity::find_by_id(token_id)
+        .one(&db)
+        .await?
+        .ok_or(ApiError::NotFound)?;
+
+    Ok(Json(row.into()))
+}
+
+/// Block a token
+///
+/// Adds a token to the blocklist. Wallet sync drops it from holdings from the next sync on,
+/// even if it is also in the chain's token list.
+#[utoipa::path(
+    post,
+    path = "/api/v1/spam-tokens",
+    request_body = CreateSpamTokenRequest,
+    responses(
+        (status = 201, description = "Token blocked", body = SpamTokenResponse),
+        (status = 400, description = "Invalid request"),
+        (status = 401, description = "Unauthorized"),
+        (status = 409, description = "Token already blocked on this chain"),
+        (status = 500, description = "Internal server error")
+    ),
+    tag = "spam-tokens"
+)]
+pub async fn create_spam_token_handler(
+    State(db): State<DatabaseConnection>,
+    Extension(_token): Extension<axum_keycloak_auth::decode::KeycloakToken<String>>,
+    Json(req): Json<CreateSpamTokenRequest>,
+) -> Result<(StatusCode, Json<SpamTokenResponse>), ApiError> {
+    let chain = req.chain.trim().to_lowercase();
+    // Solana mint addresses are case-sensitive, so the address is stored as given
+    let token_address = req.token_address.trim().to_string();
+    if chain.is_empty() {
+        return Err(ApiError::BadRequest("chain is required".to_string()));
+    }
+    if token_address.is_empty() {
+        return Err(ApiError::BadRequest("token_address is required".to_string()));
+    }
+
+    // Check for duplicate (chain, token_address)
+    let existing = spam_tokens::Entity::find()
+        .filter(spam_tokens::Column::Chain.eq(&chain))
+        .filter(spam_tokens::Column::TokenAddress.eq(&token_address))
+        .one(&db)
+        .await?;
+
+    if existing.is_some() {
+        return Err(ApiError::Conflict(format!(
+            "Token {} is already blocked on {}",
+            token_address, chain
+        )));
+    }
+
+    let new_token = spam_tokens::ActiveModel {
+        id: Set(Uuid::new_v4()),
+        chain: Set(chain),
+        token_address: Set(token_address),
+        reason: Set(req.reason.filter(|r| !r.trim().is_empty())),
+        created_at: Set(Utc::now().into()),
+        updated_at: Set(Utc::now().into()),
+    };
+
+    let row = new_token.insert(&db).await?;
+
+    Ok((StatusCode::CREATED, Json(row.into())))
+}
+
+/// Update a spam token
+///
+/// Update the reason recorded for a blocked token.
+#[utoipa::path(
+    put,
+    path = "/api/v1/spam-tokens/{token_id}",
+    params(
+        ("token_id" = Uuid, Path, description = "Spam token ID")
+    ),
+    request_body = UpdateSpamTokenRequest,
+    responses(
+        (status = 200, description = "Token updated", body = SpamTokenResponse),
+        (status = 401, description = "Unauthorized"),
+        (status = 404, description = "Not found"),
+        (status = 500, description = "Internal server error")
+    ),
+    tag = "spam-tokens"
+)]
+pub async fn update_spam_token_handler(
+    State(db): State<DatabaseConnection>,
+    Extension(_token): Extension<axum_keycloak_auth::decode::KeycloakToken<String>>,
+    Path(token_id): Path<Uuid>,
+    Json(req): Json<UpdateSpamTokenRequest>,
+) -> Result<Json<SpamTokenResponse>, ApiError> {
+    let row = spam_tokens::Entity::find_by_id(token_id)
+        .one(&db)
+        .await?
+        .ok_or(ApiError::NotFound)?;
+
+    let mut active: spam_tokens::ActiveModel = row.into();
+
+    if let Some(reason) = req.reason {
+        active.reason = Set(Some(reason).filter(|r| !r.trim().is_empty()));
+    }
+    active.updated_at = Set(Utc::now().into());
+
+    let updated = active.update(&db).await?;
+
+    Ok(Json(updated.into()))
+}
+
+/// Unblock a token
+///
+/// Removes a token from the blocklist; the spam heuristics still apply to it.
+#[utoipa::path(
+    delete,
+    path = "/api/v1/spam-tokens/{token_id}",
+    params(
+        ("token_id" = Uuid, Path, description = "Spam token ID")
+    ),
+    responses(
+        (status = 204, description = "Token unblocked"),
+        (status = 401, description = "Unauthorized"),
+        (status = 404, description = "Not found"),
+        (status = 500, description = "Internal server error")
+    ),
+    tag = "spam-tokens"
+)]
+pub async fn delete_spam_token_handler(
+    State(db): State<DatabaseConnection>,
+    Extension(_token): Extension<axum_keycloak_auth::decode::KeycloakToken<String>>,
+    Path(token_id): Path<Uuid>,
+) -> Result<StatusCode, ApiError> {
+    let row = spam_tokens::Entity::find_by_id(token_id)
+        .one(&db)
+        .await?
+        .ok_or(ApiError::NotFound)?;
+
+    let active: spam_tokens::ActiveModel = row.into();
+    active.delete(&db).await?;
+
+    Ok(StatusCode::NO_CONTENT)
+}
+
+/// Create router for spam-tokens endpoints
+pub fn create_router() -> Router<DatabaseConnection> {
+    Router::new()
+        .route(
+            "/api/v1/spam-tokens",
+            get(list_spam_tokens_handler).post(create_spam_token_handler),
+        )
+        .route(
+            "/api/v1/spam-tokens/{token_id}",
+            get(get_spam_token_handler)
+                .put(update_spam_token_handler)
+                .delete(delete_spam_token_handler),
+        )
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_create_request_defaults_to_solana() {
+        let json = r#"{"token_address":"ScamMint1111111111111111111111111111111111"}"#;
+        let req: CreateSpamTokenRequest = serde_json::from_str(json).unwrap();
+        assert_eq!(req.chain, "solana");
+        assert!(req.reason.is_none());
+    }
+}
@@ -0,0 +1,224 @@
+use axum::{extract::State, routing::get, Json, Router};
+use chrono::{DateTime, Duration, Utc};
+use sea_orm::{DatabaseConnection, EntityTrait, QueryOrder};
+use serde::{Deserialize, Serialize};
+use utoipa::ToSchema;
+
+use crate::entities::{asset_prices, job_runs};
+use crate::jobs::job_runs::{JOB_EOD_SNAPSHOT, JOB_FETCH_ALL_COINS, JOB_NAME_RESOLUTION, RUN_STATUS_FAILED};
+
+/// Default age (minutes) after which the newest price is reported as stale
+const DEFAULT_PRICE_STALE_MINUTES: i64 = 60;
+
+/// Scheduled jobs reported on the status page, with their enable flag and default
+const STATUS_JOBS: [(&str, &str); 3] = [
+    (JOB_FETCH_ALL_COINS, "FETCH_ALL_COINS_ENABLED"),
+    (JOB_EOD_SNAPSHOT, "EOD_SNAPSHOT_ENABLED"),
+    (JOB_NAME_RESOLUTION, "NAME_RESOLUTION_ENABLED"),
+];
+
+// === Response DTOs ===
+
+/// Last known state of a background job
+#[derive(Debug, Serialize, Deserialize, ToSchema)]
+pub struct JobStatus {
+    /// Job name (e.g., "fetch_all_coins", "eod_snapshot")
+    pub name: String,
+    /// Whether the job is scheduled on this deployment
+    pub enabled: bool,
+    /// "running", "succeeded", "failed", or "never_run"
+    pub last_status: String,
+    /// When the job last completed successfully
+    pub last_succeeded_at: Option<String>,
+    /// When the job last finished (successfully or not)
+    pub last_finished_at: Option<String>,
+}
+
+/// Freshness of the stored price data
+#[derive(Debug, Serialize, Deserialize, ToSchema)]
+pub struct PriceDataStatus {
+    /// Timestamp of the newest stored price
+    pub latest_price_at: Option<String>,
+    /// Age of the newest price in seconds
+    pub age_seconds: Option<i64>,
+    /// Whether the newest price is older than the staleness threshold (or missing)
+    pub stale: bool,
+    /// Staleness threshold in minutes
+    pub stale_after_minutes: i64,
+}
+
+/// Health of an external dependency
+#[derive(Debug, Serialize, Deserialize, ToSchema)]
+pub struct DependencyStatus {
+    /// Dependency name (e.g., "database", "price_provider")
+    pub name: String,
+    /// "ok", "failing", or "unknown"
+    pub status: String,
+}
+
+/// Public system status summary
+#[derive(Debug, Serialize, Deserialize, ToSchema)]
+pub struct StatusResponse {
+    /// Overall status: "operational", "degraded", or "down"
+    pub status: String,
+    pub generated_at: String,
+    /// Whether maintenance mode is on (writes rejected, scheduled jobs paused)
+    pub maintenance: bool,
+    pub jobs: Vec<JobStatus>,
+    pub price_data: PriceDataStatus,
+    pub dependencies: Vec<DependencyStatus>,
+}
+
+// === Helper Functions ===
+
+/// Read the price staleness threshold from `STATUS_PRICE_STALE_MINUTES` (default: 60)
+fn price_stale_minutes_from_env() -> i64 {
+    std::env::var("STATUS_PRICE_STALE_MINUTES")
+        .ok()
+        .and_then(|v| v.parse::<i64>().ok())
+        .filter(|v| *v > 0)
+        .unwrap_or(DEFAULT_PRICE_STALE_MINUTES)
+}
+
+/// Whether a job is enabled, using the same env flags (default: true) as the scheduler
+fn job_enabled(env_flag: &str) -> bool {
+    std::env::var(env_flag)
+        .unwrap_or_else(|_| "true".to_string())
+        .parse::<bool>()
+        .unwrap_or(true)
+}
+
+/// Whether the newest price is missing or older than `stale_after`
+fn is_price_stale(latest: Option<DateTime<Utc>>, now: DateTime<Utc>, stale_after: Duration) -> bool {
+    latest.is_none_or(|at| now - at > stale_after)
+}
+
+/// Combine component health into the overall status
+fn overall_status(database_ok: bool, price_stale: bool, any_job_failed: bool) -> &'static str {
+    if !database_ok {
+        "down"
+    } else if price_stale || any_job_failed {
+        "degraded"
+    } else {
+        "operational"
+    }
+}
+
+// === API Handlers ===
+
+/// Public system status
+///
+/// Summarizes the last successful run of each scheduled job, price data freshness,
+/// and dependency health. Requires no authentication and exposes no error details.
+#[utoipa::path(
+    get,
+    path = "/status",
+    responses(
+        (status = 200, description = "System status summary", body = StatusResponse)
+    ),
+    tag = "status"
+)]
+pub async fn get_status_handler(State(db): State<DatabaseConnection>) -> Json<StatusResponse> {
+    let now = Utc::now();
+    let database_ok = db.ping().await.is_ok();
+
+    let runs: Vec<job_runs::Model> = if database_ok {
+        job_runs::Entity::find().all(&db).await.unwrap_or_else(|e| {
+            tracing::warn!("Status: failed to load job runs: {}", e);
+            Vec::new()
+        })
+    } else {
+        Vec::new()
+    };
+
+    let jobs: Vec<JobStatus> = STATUS_JOBS
+        .iter()
+        .map(|(name, env_flag)| {
+            let run = runs.iter().find(|r| r.job_name == *name);
+            JobStatus {
+                name: name.to_string(),
+                enabled: job_enabled(env_flag),
+                last_status: run.map(|r| r.last_status.clone()).unwrap_or_else(|| "never_run".to_string()),
+                last_succeeded_at: run.and_then(|r| r.last_succeeded_at).map(|dt| dt.to_rfc3339()),
+                last_finished_at: run.and_then(|r| r.last_finished_at).map(|dt| dt.to_rfc3339()),
+            }
+        })
+        .collect();
+
+    let latest_price_at: Option<DateTime<Utc>> = if database_ok {
+        asset_prices::Entity::find()
+            .order_by_desc(asset_prices::Column::Timestamp)
+            .one(&db)
+            .await
+            .unwrap_or_else(|e| {
+                tracing::warn!("Status: failed to load latest price: {}", e);
+                None
+            })
+            .map(|p| p.timestamp.with_timezone(&Utc))
+    } else {
+        None
+    };
+
+    let stale_after_minutes = price_stale_minutes_from_env();
+    let stale = is_price_stale(latest_price_at, now, Duration::minutes(stale_after_minutes));
+
+    // The price provider is only contacted by the fetch job; report its last outcome
+    let price_provider = match jobs.iter().find(|j| j.name == JOB_FETCH_ALL_COINS) {
+        Some(job) if job.last_status == RUN_STATUS_FAILED => "failing",
+        Some(job) if job.last_succeeded_at.is_some() => "ok",
+        _ => "unknown",
+    };
+
+    let any_job_failed = jobs.iter().any(|j| j.enabled && j.last_status == RUN_STATUS_FAILED);
+
+    Json(StatusResponse {
+        status: overall_status(database_ok, stale, any_job_failed).to_string(),
+        generated_at: now.to_rfc3339(),
+        maintenance: crate::maintenance::is_enabled(),
+        jobs,
+        price_data: PriceDataStatus {
+            latest_price_at: latest_price_at.map(|dt| dt.to_rfc3339()),
+            age_seconds: latest_price_at.map(|dt| (now - dt).num_seconds()),
+            stale,
+            stale_after_minutes,
+        },
+        dependencies: vec![
+            DependencyStatus {
+                name: "database".to_string(),
+                status: if database_ok { "ok" } else { "failing" }.to_string(),
+            },
+            DependencyStatus {
+                name: "price_provider".to_string(),
+                status: price_provider.to_string(),
+            },
+        ],
+    })
+}
+
+/// Create router for the public status endpoint
+pub fn create_router() -> Router<DatabaseConnection> {
+    Router::new().route("/status", get(get_status_handler))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_price_staleness() {
+        let now = Utc::now();
+        let threshold = Duration::minutes(60);
+
+        assert!(is_price_stale(None, now, threshold));
+        assert!(!is_price_stale(Some(now - Duration::minutes(10)), now, threshold));
+        assert!(is_price_stale(Some(now - Duration::minutes(61)), now, threshold));
+    }
+
+    #[test]
+    fn test_overall_status() {
+        assert_eq!(overall_status(true, false, false), "operational");
+        assert_eq!(overall_status(true, true, false), "degraded");
+        assert_eq!(overall_status(true, false, true), "degraded");
+        assert_eq!(overall_status(false, false, false), "down");
+    }
+}
@@ -0,0 +1,133 @@
+use axum::{
+    extract::{Extension, Path, Query, State},
+    response::Json,
+    routing::get,
+    Router,
+};
+use axum_keycloak_auth::decode::KeycloakToken;
+use rust_decimal::Decimal;
+use sea_orm::{ColumnTrait, DatabaseConnection, EntityTrait, QueryFilter, QueryOrder};
+use serde::{Deserialize, Serialize};
+use utoipa::ToSchema;
+use uuid::Uuid;
+
+use crate::entities::{accounts, tax_lots};
+use crate::helpers::auth::get_or_create_user;
+use crate::jobs::portfolio_pnl::ledger_asset;
+use crate::jobs::tax_lots::user_lot_method;
+use super::error::ApiError;
+
+// === Request/Response DTOs ===
+
+#[derive(Debug, Deserialize, ToSchema)]
+pub struct TaxLotsQuery {
+    /// Only lots of this asset symbol
+    pub asset: Option<String>,
+    /// Only lots with remaining quantity (default false)
+    #[serde(default)]
+    pub open_only: bool,
+}
+
+#[derive(Debug, Serialize, Deserialize, ToSchema)]
+pub struct TaxLotResponse {
+    pub id: Uuid,
+    pub asset: String,
+    pub acquired_at: String, // ISO 8601 datetime
+    pub quantity: String,
+    pub remaining_quantity: String,
+    pub cost_basis_usd: String,
+    pub unit_cost_usd: String,
+}
+
+impl From<tax_lots::Model> for TaxLotResponse {
+    fn from(model: tax_lots::Model) -> Self {
+        let unit_cost = if model.quantity > Decimal::ZERO {
+            model.cost_basis_usd / model.quantity
+        } else {
+            Decimal::ZERO
+        };
+        Self {
+            id: model.id,
+            asset: model.asset,
+            acquired_at: model.acquired_at.to_rfc3339(),
+            quantity: model.quantity.normalize().to_string(),
+            remaining_quantity: model.remaining_quantity.normalize().to_string(),
+            cost_basis_usd: model.cost_basis_usd.round_dp(2).normalize().to_string(),
+            unit_cost_usd: unit_cost.round_dp(8).normalize().to_string(),
+        }
+    }
+}
+
+#[derive(Debug, Serialize, Deserialize, ToSchema)]
+pub struct TaxLotsResponse {
+    pub account_id: Uuid,
+    /// Method the lots were matched with: "fifo", "lifo" or "hifo"
+    pub method: String,
+    /// Lots, oldest first
+    pub lots: Vec<TaxLotResponse>,
+}
+
+// === API Handlers ===
+
+/// List an account's tax lots
+///
+/// Acquisition lots per asset, with the quantity not yet disposed under the user's tax lot
+/// method (see `/api/v1/me/preferences`). Lots are rebuilt daily from the account's trades,
+/// transfers and income by the tax lot job, and when the method changes.
+#[utoipa::path(
+    get,
+    path = "/api/v1/accounts/{account_id}/tax-lots",
+    params(
+        ("account_id" = Uuid, Path, description = "Account ID"),
+        ("asset" = Option<String>, Query, description = "Asset symbol filter"),
+        ("open_only" = Option<bool>, Query, description = "Only lots with remaining quantity (default false)")
+    ),
+    responses(
+        (status = 200, description = "Tax lots", body = TaxLotsResponse),
+        (status = 401, description = "Unauthorized"),
+        (status = 403, description = "Forbidden - account does not belong to user"),
+        (status = 404, description = "Account not found"),
+        (status = 500, description = "Internal server error")
+    ),
+    tag = "accounts"
+)]
+pub async fn list_tax_lots_handler(
+    State(db): State<DatabaseConnection>,
+    Extension(token): Extension<KeycloakToken<String>>,
+    Path(account_id): Path<Uuid>,
+    Query(query): Query<TaxLotsQuery>,
+) -> Result<Json<TaxLotsResponse>, ApiError> {
+    let user = get_or_create_user(&db, &token).await?;
+
+    let account = accounts::Entity::find_by_id(account_id)
+        .one(&db)
+        .await?
+        .ok_or(ApiError::NotFound)?;
+    if account.user_id != user.id {
+        return Err(ApiError::Forbidden);
+    }
+
+    let mut lots_query = tax_lots::Entity::find().filter(tax_lots::Column::AccountId.eq(account_id));
+    if let Some(asset) = query.asset.as_deref() {
+        lots_query = lots_query.filter(tax_lots::Column::Asset.eq(ledger_asset(asset)));
+    }
+    if query.open_only {
+        lots_query = lots_query.filter(tax_lots::Column::RemainingQuantity.gt(Decimal::ZERO));
+    }
+    let lots = lots_query
+        .order_by_asc(tax_lots::Column::AcquiredAt)
+        .all(&db)
+        .await?;
+
+    Ok(Json(TaxLotsResponse {
+        account_id,
+        method: user_lot_method(&user).as_str().to_string(),
+        lots: lots.into_iter().map(TaxLotResponse::from).collect(),
+    }))
+}
+
+// === Router setup ===
+
+pub fn create_router() -> Router<DatabaseConnection> {
+    Router::new().route("/api/v1/accounts/{account_id}/tax-lots", get(list_tax_lots_handler))
+}
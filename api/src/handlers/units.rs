@@ -0,0 +1,175 @@
+use axum::{extract::Query, routing::get, Json, Router};
+use rust_decimal::Decimal;
+use sea_orm::DatabaseConnection;
+use serde::{Deserialize, Serialize};
+use std::str::FromStr;
+use utoipa::{IntoParams, ToSchema};
+
+use super::error::ApiError;
+use crate::helpers::balance_normalization::{convert_amount, find_unit, DenominationUnit, DENOMINATION_UNITS};
+
+// ============================================================================
+// DTOs
+// ============================================================================
+
+/// A supported denomination unit
+#[derive(Debug, Serialize, Deserialize, ToSchema)]
+pub struct UnitResponse {
+    /// Unit symbol (e.g. "sat", "gwei", "BTC")
+    pub symbol: String,
+    /// Other accepted spellings
+    pub aliases: Vec<String>,
+    /// Asset the unit denominates
+    pub asset: String,
+    /// Decimal places of the unit over the asset's base unit (sat = 0, BTC = 8)
+    pub decimals: u8,
+}
+
+/// Response containing the supported units
+#[derive(Debug, Serialize, Deserialize, ToSchema)]
+pub struct ListUnitsResponse {
+    pub units: Vec<UnitResponse>,
+}
+
+/// Query parameters for converting an amount between units
+#[derive(Debug, Deserialize, IntoParams)]
+pub struct ConvertUnitsQuery {
+    /// Amount to convert (decimal string, e.g. "0.00021")
+    pub amount: String,
+    /// Unit of `amount`: a supported unit (e.g. "ETH", "gwei") or, with `decimals`, "base"/"human"
+    pub from: String,
+    /// Unit to convert to, same forms as `from`
+    pub to: String,
+    /// Token decimals for "base"/"human" conversions of tokens without a unit table (e.g. 6 for USDC)
+    pub decimals: Option<u8>,
+}
+
+/// Result of a unit conversion
+#[derive(Debug, Serialize, Deserialize, ToSchema)]
+pub struct ConvertUnitsResponse {
+    /// Amount as given
+    pub amount: String,
+    pub from: String,
+    pub to: String,
+    /// Converted amount, exact and without trailing zeros
+    pub result: String,
+}
+
+// ============================================================================
+// Helper Functions
+// ============================================================================
+
+/// Generic token unit for "base"/"human" conversions with explicit decimals
+fn token_unit(name: &str, decimals: Option<u8>) -> Option<Result<DenominationUnit, ApiError>> {
+    let symbol = match name.trim().to_lowercase().as_str() {
+        "base" => "base",
+        "human" => "human",
+        _ => return None,
+    };
+    Some(
+        decimals
+            .map(|decimals| DenominationUnit {
+                symbol,
+                aliases: &[],
+                asset: "token",
+                decimals: if symbol == "base" { 0 } else { decimals },
+            })
+            .ok_or_else(|| ApiError::BadRequest(format!("decimals is required to convert '{}' amounts", symbol))),
+    )
+}
+
+/// Resolve a unit name from the query
+fn resolve_unit(name: &str, decimals: Option<u8>) -> Result<DenominationUnit, ApiError> {
+    if let Some(unit) = token_unit(name, decimals) {
+        return unit;
+    }
+    find_unit(name)
+        .copied()
+        .ok_or_else(|| ApiError::BadRequest(format!("Unknown unit '{}'", name)))
+}
+
+// ============================================================================
+// API Handlers
+// ============================================================================
+
+/// List the supported denomination units
+#[utoipa::path(
+    get,
+    path = "/v1/units",
+    responses(
+        (status = 200, description = "Supported units", body = ListUnitsResponse),
+    ),
+    tag = "units"
+)]
+pub async fn list_units_handler() -> Json<ListUnitsResponse> {
+    let units = DENOMINATION_UNITS
+        .iter()
+        .map(|u| UnitResponse {
+            symbol: u.symbol.to_string(),
+            aliases: u.aliases.iter().map(|a| a.to_string()).collect(),
+            asset: u.asset.to_string(),
+            decimals: u.decimals,
+        })
+        .collect();
+    Json(ListUnitsResponse { units })
+}
+
+/// Convert an amount between units of the same asset
+///
+/// Conversions are exact: an amount finer than the target allows (e.g. half a satoshi) is
+/// rejected rather than rounded. Tokens without a unit table convert between "base" (raw
+/// on-chain integer) and "human" units given their `decimals`.
+#[utoipa::path(
+    get,
+    path = "/v1/units/convert",
+    params(ConvertUnitsQuery),
+    responses(
+        (status = 200, description = "Converted amount", body = ConvertUnitsResponse),
+        (status = 400, description = "Invalid amount, unknown unit, or units of different assets"),
+    ),
+    tag = "units"
+)]
+pub async fn convert_units_handler(
+    Query(query): Query<ConvertUnitsQuery>,
+) -> Result<Json<ConvertUnitsResponse>, ApiError> {
+    let amount = Decimal::from_str(query.amount.trim())
+        .map_err(|_| ApiError::BadRequest(format!("Invalid amount '{}'", query.amount)))?;
+    let from = resolve_unit(&query.from, query.decimals)?;
+    let to = resolve_unit(&query.to, query.decimals)?;
+
+    let result = convert_amount(amount, &from, &to).map_err(|e| ApiError::BadRequest(e.to_string()))?;
+
+    Ok(Json(ConvertUnitsResponse {
+        amount: query.amount,
+        from: from.symbol.to_string(),
+        to: to.symbol.to_string(),
+        result: result.to_string(),
+    }))
+}
+
+/// Create router for unit conversion endpoints
+pub fn create_router() -> Router<DatabaseConnection> {
+    Router::new()
+        .route("/v1/units", get(list_units_handler))
+        .route("/v1/units/convert", get(convert_units_handler))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_resolve_unit() {
+        assert_eq!(resolve_unit("Sats", None).unwrap().symbol, "sat");
+        assert_eq!(resolve_unit("human", Some(6)).unwrap().decimals, 6);
+        assert_eq!(resolve_unit("base", Some(6)).unwrap().decimals, 0);
+        assert!(matches!(resolve_unit("base", None), Err(ApiError::BadRequest(_))));
+        assert!(matches!(resolve_unit("finney", None), Err(ApiError::BadRequest(_))));
+
+        // Generic token units only convert among themselves
+        let base = resolve_unit("base", Some(6)).unwrap();
+        let human = resolve_unit("human", Some(6)).unwrap();
+        assert_eq!(convert_amount(Decimal::from(706_000), &base, &human).unwrap().to_string(), "0.706");
+        assert!(convert_amount(Decimal::ONE, &base, find_unit("ETH").unwrap()).is_err());
+    }
+}
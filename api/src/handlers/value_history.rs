@@ -0,0 +1,139 @@
+use axum::{
+    extract::{Extension, Path, Query, State},
+    response::Json,
+    routing::get,
+    Router,
+};
+use axum_keycloak_auth::decode::KeycloakToken;
+use rust_decimal::Decimal;
+use sea_orm::DatabaseConnection;
+use serde::{Deserialize, Serialize};
+use std::str::FromStr;
+use utoipa::ToSchema;
+use uuid::Uuid;
+
+use crate::helpers::auth::{check_portfolio_ownership, get_or_create_user};
+use crate::helpers::performance::load_value_series;
+use crate::helpers::value_history::value_history;
+use crate::helpers::params::parse_date;
+use super::error::ApiError;
+
+// === Request/Response DTOs ===
+
+pub use crate::helpers::value_history::{GapFill, ValueGranularity, ValuePoint};
+
+#[derive(Debug, Deserialize, ToSchema)]
+pub struct ValueHistoryQuery {
+    /// First date (YYYY-MM-DD, inclusive); default the first snapshot
+    pub from: Option<String>,
+    /// Last date (YYYY-MM-DD, inclusive); default the latest snapshot
+    pub to: Option<String>,
+    /// "daily" (default), "weekly" or "monthly"
+    pub granularity: Option<String>,
+    /// Dates without a snapshot: "none" (default, left out), "previous" or "linear"
+    pub fill: Option<String>,
+}
+
+#[derive(Debug, Serialize, Deserialize, ToSchema)]
+pub struct ValueHistoryResponse {
+    pub portfolio_id: Uuid,
+    pub granularity: ValueGranularity,
+    pub fill: GapFill,
+    /// Oldest first
+    pub points: Vec<ValuePoint>,
+    /// Last point's value minus the first's; absent without points
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub change_usd: Option<String>,
+    /// `change_usd` relative to the first point's value; absent when it is zero
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub change_percent: Option<String>,
+}
+
+// === API Handlers ===
+
+/// Get a portfolio's value history
+///
+/// Total value per day, week or month from the portfolio's snapshots, oldest first, without
+/// paging through the snapshots themselves. A date's value is its latest snapshot; weekly
+/// and monthly points are the last value of their period. With `fill`, dates between two
+/// snapshots get the previous value or a linear interpolation, marked `interpolated`.
+#[utoipa::path(
+    get,
+    path = "/api/v1/portfolios/{portfolio_id}/value-history",
+    params(
+        ("portfolio_id" = Uuid, Path, description = "Portfolio ID"),
+        ("granularity" = Option<String>, Query, description = "daily (default), weekly or monthly"),
+        ("from" = Option<String>, Query, description = "First date (YYYY-MM-DD, inclusive)"),
+        ("to" = Option<String>, Query, description = "Last date (YYYY-MM-DD, inclusive)"),
+        ("fill" = Option<String>, Query, description = "Gap filling: none (default), previous or linear")
+    ),
+    responses(
+        (status = 200, description = "Value history", body = ValueHistoryResponse),
+        (status = 400, description = "Invalid date, granularity or fill"),
+        (status = 401, description = "Unauthorized"),
+        (status = 403, description = "Forbidden - portfolio does not belong to user"),
+        (status = 404, description = "Portfolio not found"),
+        (status = 500, description = "Internal server error")
+    ),
+    tag = "portfolios"
+)]
+pub async fn get_portfolio_value_history_handler(
+    State(db): State<DatabaseConnection>,
+    Extension(token): Extension<KeycloakToken<String>>,
+    Path(portfolio_id): Path<Uuid>,
+    Query(query): Query<ValueHistoryQuery>,
+) -> Result<Json<ValueHistoryResponse>, ApiError> {
+    let user = get_or_create_user(&db, &token).await?;
+    check_portfolio_ownership(&db, portfolio_id, user.id).await?;
+
+    let granularity = match query.granularity.as_deref() {
+        None => ValueGranularity::Daily,
+        Some(value) => ValueGranularity::parse(value).ok_or_else(|| {
+            ApiError::BadRequest(format!("Invalid granularity '{}'. Expected daily, weekly or monthly", value))
+        })?,
+    };
+    let fill = match query.fill.as_deref() {
+        None => GapFill::None,
+        Some(value) => GapFill::parse(value).ok_or_else(|| {
+            ApiError::BadRequest(format!("Invalid fill '{}'. Expected none, previous or linear", value))
+        })?,
+    };
+    let from = query.from.as_deref().map(|from| parse_date(from, "from")).transpose()?;
+    let to = query.to.as_deref().map(|to| parse_date(to, "to")).transpose()?;
+    if let (Some(from), Some(to)) = (from, to) {
+        if from > to {
+            return Err(ApiError::BadRequest("from must not be after to".to_string()));
+        }
+    }
+
+    // Starting at the latest snapshot on or before `from` lets gaps be filled up to it
+    let series = load_value_series(&db, portfolio_id, from).await?;
+    let points = value_history(&series, from, to, granularity, fill);
+
+    let values = (points.first(), points.last());
+    let (change_usd, change_percent) = match values {
+        (Some(first), Some(last)) => {
+            let first = Decimal::from_str(&first.value_usd).unwrap_or_default();
+            let change = Decimal::from_str(&last.value_usd).unwrap_or_default() - first;
+            let percent = (!first.is_zero())
+                .then(|| (change / first * Decimal::ONE_HUNDRED).round_dp(2).normalize().to_string());
+            (Some(change.normalize().to_string()), percent)
+        }
+        _ => (None, None),
+    };
+
+    Ok(Json(ValueHistoryResponse {
+        portfolio_id,
+        granularity,
+        fill,
+        points,
+        change_usd,
+        change_percent,
+    }))
+}
+
+// === Router setup ===
+
+pub fn create_router() -> Router<DatabaseConnection> {
+    Router::new().route("/api/v1/portfolios/{portfolio_id}/value-history", get(get_portfolio_value_history_handler))
+}
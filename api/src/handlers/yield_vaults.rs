@@ -0,0 +1,312 @@
+use alloy::primitives::Address;
+use axum::{
+    extract::{Path, Query, State},
+    http::StatusCode,
+    response::Json,
+    routing::get,
+    Extension, Router,
+};
+use chrono::Utc;
+use sea_orm::{
+    ActiveModelTrait, ColumnTrait, Condition, DatabaseConnection, EntityTrait, QueryFilter, QueryOrder, Set,
+};
+use serde::{Deserialize, Serialize};
+use utoipa::{IntoParams, ToSchema};
+use uuid::Uuid;
+
+use crate::entities::yield_vaults;
+use super::error::ApiError;
+
+// === Request / Response DTOs ===
+
+#[derive(Debug, Serialize, Deserialize, ToSchema)]
+pub struct YieldVaultResponse {
+    pub id: Uuid,
+    /// EVM chain the vault is deployed on, e.g. "ethereum"
+    pub chain: String,
+    /// Vault name, e.g. "Spark sDAI"
+    pub name: String,
+    /// Vault (share token) contract address
+    pub vault_address: String,
+    pub is_active: bool,
+    pub created_at: String,
+    pub updated_at: String,
+}
+
+impl From<yield_vaults::Model> for YieldVaultResponse {
+    fn from(m: yield_vaults::Model) -> Self {
+        Self {
+            id: m.id,
+            chain: m.chain,
+            name: m.name,
+            vault_address: m.vault_address,
+            is_active: m.is_active,
+            created_at: m.created_at.to_rfc3339(),
+            updated_at: m.updated_at.to_rfc3339(),
+        }
+    }
+}
+
+#[derive(Debug, Serialize, Deserialize, ToSchema)]
+pub struct CreateYieldVaultRequest {
+    /// EVM chain name from `evm_chains`, e.g. "ethereum"
+    pub chain: String,
+    /// Vault name, e.g. "Spark sDAI"
+    pub name: String,
+    /// Vault (share token) contract address: an ERC-4626, Yearn v2 or Beefy vault
+    pub vault_address: String,
+    /// Whether wallet sync reads this vault (default: true)
+    #[serde(default = "default_true")]
+    pub is_active: bool,
+}
+
+#[derive(Debug, Serialize, Deserialize, ToSchema)]
+pub struct UpdateYieldVaultRequest {
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub name: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub is_active: Option<bool>,
+}
+
+#[derive(Debug, Deserialize, IntoParams)]
+pub struct ListYieldVaultsQuery {
+    /// Filter by chain, e.g. "ethereum"
+    pub chain: Option<String>,
+}
+
+fn default_true() -> bool {
+    true
+}
+
+// === Helpers ===
+
+/// Validate a contract address and return it lowercase, as stored
+fn parse_contract(field: &str, value: &str) -> Result<String, ApiError> {
+    value
+        .trim()
+        .parse::<Address>()
+        .map(|a| format!("{:?}", a))
+        .map_err(|_| ApiError::BadRequest(format!("{} must be a 0x-prefixed contract address", field)))
+}
+
+// === Handlers ===
+
+/// List yield vaults
+///
+/// Returns the vaults whose shares wallet sync resolves into the vault's underlying token.
+#[utoipa::path(
+    get,
+    path = "/api/v1/yield-vaults",
+    params(ListYieldVaultsQuery),
+    responses(
+        (status = 200, description = "List of yield vaults", body = Vec<YieldVaultResponse>),
+        (status = 401, description = "Unauthorized"),
+        (status = 500, description = "Internal server error")
+    ),
+    tag = "yield-vaults"
+)]
+pub async fn list_yield_vaults_handler(
+    State(db): State<DatabaseConnection>,
+    Extension(_token): Extension<axum_keycloak_auth::decode::KeycloakToken<String>>,
+    Query(q): Query<ListYieldVaultsQuery>,
+) -> Result<Json<Vec<YieldVaultResponse>>, ApiError> {
+    let mut condition = Condition::all();
+    if let Some(chain) = q.chain {
+        condition = condition.add(yield_vaults::Column::Chain.eq(chain.trim().to_lowercase()));
+    }
+
+    let rows = yield_vaults::Entity::find()
+        .filter(condition)
+        .order_by_asc(yield_vaults::Column::Chain)
+        .order_by_asc(yield_vaults::Column::Name)
+        .all(&db)
+        .await?;
+
+    Ok(Json(rows.into_iter().map(|r| r.into()).collect()))
+}
+
+/// Get a yield vault by ID
+#[utoipa::path(
+    get,
+    path = "/api/v1/yield-vaults/{vault_id}",
+    params(
+        ("vault_id" = Uuid, Path, description = "Yield vault ID")
+    ),
+    responses(
+        (status = 200, description = "Yield vault", body = YieldVaultResponse),
+        (status = 401, description = "Unauthorized"),
+        (status = 404, description = "Not found"),
+        (status = 500, description = "Internal server error")
+    ),
+    tag = "yield-vaults"
+)]
+pub async fn get_yield_vault_handler(
+    State(db): State<DatabaseConnection>,
+    Extension(_token): Extension<axum_keycloak_auth::decode::KeycloakToken<String>>,
+    Path(vault_id): Path<Uuid>,
+) -> Result<Json<YieldVaultResponse>, ApiError> {
+    let row = yield_vaults::Entity::find_by_id(vault_id)
+        .one(&db)
+        .await?
+        .ok_or(ApiError::NotFound)?;
+
+    Ok(Json(row.into()))
+}
+
+/// Add a yield vault
+///
+/// Accounts with `vaults` in their `defi_protocols` setting report their shares in the vault as
+/// the vault's underlying token.
+#[utoipa::path(
+    post,
+    path = "/api/v1/yield-vaults",
+    request_body = CreateYieldVaultRequest,
+    responses(
+        (status = 201, description = "Vault added", body = YieldVaultResponse),
+        (status = 400, description = "Invalid request"),
+        (status = 401, description = "Unauthorized"),
+        (status = 409, description = "Vault already configured on this chain"),
+        (status = 500, description = "Internal server error")
+    ),
+    tag = "yield-vaults"
+)]
+pub async fn create_yield_vault_handler(
+    State(db): State<DatabaseConnection>,
+    Extension(_token): Extension<axum_keycloak_auth::decode::KeycloakToken<String>>,
+    Json(req): Json<CreateYieldVaultRequest>,
+) -> Result<(StatusCode, Json<YieldVaultResponse>), ApiError> {
+    let chain = req.chain.trim().to_lowercase();
+    let name = req.name.trim().to_string();
+    if chain.is_empty() {
+        return Err(ApiError::BadRequest("chain is required".to_string()));
+    }
+    if name.is_empty() {
+        return Err(ApiError::BadRequest("name is required".to_string()));
+    }
+    let vault_address = parse_contract("vault_address", &req.vault_address)?;
+
+    // Check for duplicate (chain, vault_address)
+    let existing = yield_vaults::Entity::find()
+        .filter(yield_vaults::Column::Chain.eq(&chain))
+        .filter(yield_vaults::Column::VaultAddress.eq(&vault_address))
+        .one(&db)
+        .await?;
+
+    if existing.is_some() {
+        return Err(ApiError::Conflict(format!(
+            "Vault {} is already configured on {}",
+            vault_address, chain
+        )));
+    }
+
+    let new_vault = yield_vaults::ActiveModel {
+        id: Set(Uuid::new_v4()),
+        chain: Set(chain),
+        name: Set(name),
+        vault_address: Set(vault_address),
+        is_active: Set(req.is_active),
+        created_at: Set(Utc::now().into()),
+        updated_at: Set(Utc::now().into()),
+    };
+
+    let row = new_vault.insert(&db).await?;
+
+    Ok((StatusCode::CREATED, Json(row.into())))
+}
+
+/// Update a yield vault
+///
+/// Rename a vault or activate/deactivate it.
+#[utoipa::path(
+    put,
+    path = "/api/v1/yield-vaults/{vault_id}",
+    params(
+        ("vault_id" = Uuid, Path, description = "Yield vault ID")
+    ),
+    request_body = UpdateYieldVaultRequest,
+    responses(
+        (status = 200, description = "Vault updated", body = YieldVaultResponse),
+        (status = 400, description = "Invalid request"),
+        (status = 401, description = "Unauthorized"),
+        (status = 404, description = "Not found"),
+        (status = 500, description = "Internal server error")
+    ),
+    tag = "yield-vaults"
+)]
+pub async fn update_yield_vault_handler(
+    State(db): State<DatabaseConnection>,
+    Extension(_token): Extension<axum_keycloak_auth::decode::KeycloakToken<String>>,
+    Path(vault_id): Path<Uuid>,
+    Json(req): Json<UpdateYieldVaultRequest>,
+) -> Result<Json<YieldVaultResponse>, ApiError> {
+    let row = yield_vaults::Entity::find_by_id(vault_id)
+        .one(&db)
+        .await?
+        .ok_or(ApiError::NotFound)?;
+
+    let mut active: yield_vaults::ActiveModel = row.into();
+
+    if let Some(name) = req.name {
+        let name = name.trim().to_string();
+        if name.is_empty() {
+            return Err(ApiError::BadRequest("name must not be empty".to_string()));
+        }
+        active.name = Set(name);
+    }
+    if let Some(is_active) = req.is_active {
+        active.is_active = Set(is_active);
+    }
+    active.updated_at = Set(Utc::now().into());
+
+    let updated = active.update(&db).await?;
+
+    Ok(Json(updated.into()))
+}
+
+/// Delete a yield vault
+///
+/// Removes a vault; its shares are no longer resolved from the next sync on.
+#[utoipa::path(
+    delete,
+    path = "/api/v1/yield-vaults/{vault_id}",
+    params(
+        ("vault_id" = Uuid, Path, description = "Yield vault ID")
+    ),
+    responses(
+        (status = 204, description = "Vault deleted"),
+        (status = 401, description = "Unauthorized"),
+        (status = 404, description = "Not found"),
+        (status = 500, description = "Internal server error")
+    ),
+    tag = "yield-vaults"
+)]
+pub async fn delete_yield_vault_handler(
+    State(db): State<DatabaseConnection>,
+    Extension(_token): Extension<axum_keycloak_auth::decode::KeycloakToken<String>>,
+    Path(vault_id): Path<Uuid>,
+) -> Result<StatusCode, ApiError> {
+    let row = yield_vaults::Entity::find_by_id(vault_id)
+        .one(&db)
+        .await?
+        .ok_or(ApiError::NotFound)?;
+
+    let active: yield_vaults::ActiveModel = row.into();
+    active.delete(&db).await?;
+
+    Ok(StatusCode::NO_CONTENT)
+}
+
+/// Create router for yield-vaults endpoints
+pub fn create_router() -> Router<DatabaseConnection> {
+    Router::new()
+        .route(
+            "/api/v1/yield-vaults",
+            get(list_yield_vaults_handler).post(create_yield_vault_handler),
+        )
+        .route(
+            "/api/v1/yield-vaults/{vault_id}",
+            get(get_yield_vault_handler)
+                .put(update_yield_vault_handler)
+                .delete(delete_yield_vault_handler),
+        )
+}
@@ -26,8 +26,9 @@
 //! ).await?;
 //! ```
 
-use sea_orm::{DatabaseConnection, EntityTrait, QueryFilter, ColumnTrait, QueryOrder};
+use sea_orm::{DatabaseConnection, DbErr, EntityTrait, QueryFilter, ColumnTrait, QueryOrder, QuerySelect};
 use serde::{Deserialize, Serialize};
+use std::collections::{HashMap, HashSet};
 use uuid::Uuid;
 
 /// Known chain identifiers used for chain-specific symbol parsing
@@ -37,9 +38,22 @@ use uuid::Uuid;
 /// Includes both EVM chains and other supported chains (e.g. Solana).
 const KNOWN_CHAIN_SUFFIXES: &[&str] = &[
     "ethereum", "arbitrum", "optimism", "base", "bsc", "solana",
-    "hyper_liquid", "mantle",
+    "hyper_liquid", "mantle", "bitcoin", "litecoin", "dogecoin", "cosmoshub", "osmosis", "celestia",
 ];
 
+/// Split a holding symbol into its base symbol and known chain suffix.
+///
+/// `"USDT-ethereum"` becomes `("USDT", Some("ethereum"))`; symbols without a known
+/// chain suffix (including legitimately hyphenated ones) are returned unchanged.
+pub fn split_chain_suffix(symbol: &str) -> (&str, Option<&str>) {
+    match symbol.rsplit_once('-') {
+        Some((base, chain)) if KNOWN_CHAIN_SUFFIXES.contains(&chain.to_lowercase().as_str()) => {
+            (base, Some(chain))
+        }
+        _ => (symbol, None),
+    }
+}
+
 /// Represents a canonical asset identity
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct AssetIdentity {
@@ -121,6 +135,35 @@ impl NormalizationResult {
     }
 }
 
+/// IDs of assets the price provider no longer lists (`delisted_at` set)
+pub async fn load_delisted_assets(db: &DatabaseConnection) -> Result<HashSet<Uuid>, DbErr> {
+    use crate::entities::assets;
+
+    let ids: Vec<Uuid> = assets::Entity::find()
+        .select_only()
+        .column(assets::Column::Id)
+        .filter(assets::Column::DelistedAt.is_not_null())
+        .into_tuple()
+        .all(db)
+        .await?;
+    Ok(ids.into_iter().collect())
+}
+
+/// Category of each categorized asset, by asset ID
+pub async fn load_asset_categories(db: &DatabaseConnection) -> Result<HashMap<Uuid, String>, DbErr> {
+    use crate::entities::assets;
+
+    let rows: Vec<(Uuid, Option<String>)> = assets::Entity::find()
+        .select_only()
+        .column(assets::Column::Id)
+        .column(assets::Column::Category)
+        .filter(assets::Column::Category.is_not_null())
+        .into_tuple()
+        .all(db)
+        .await?;
+    Ok(rows.into_iter().filter_map(|(id, category)| Some((id, category?))).collect())
+}
+
 /// Asset identity normalizer
 pub struct AssetIdentityNormalizer {
     db: DatabaseConnection,
@@ -431,7 +474,9 @@ impl AssetIdentityNormalizer {
     /// when multiple assets share the same symbol.
     ///
     /// This helper method joins with asset_prices to access rank information and selects
-    /// the asset with the lowest rank value (e.g., rank 2 before rank 900).
+    /// the asset with the lowest rank value (e.g., rank 2 before rank 900). Active assets win
+    /// over delisted ones, whose last rank may be better than a listed asset's current one;
+    /// a delisted asset is still matched when it is the only one with the symbol.
     async fn find_asset_by_symbol_with_rank(&self, normalized_symbol: &str) -> Result<Option<crate::entities::assets::Model>, sea_orm::DbErr> {
         use crate::entities::{assets, asset_prices};
         
@@ -441,6 +486,7 @@ impl AssetIdentityNormalizer {
             .filter(assets::Column::Symbol.eq(normalized_symbol))
             .inner_join(asset_prices::Entity)
             .filter(asset_prices::Column::Rank.is_not_null())
+            .order_by_desc(assets::Column::IsActive)
             .order_by_asc(asset_prices::Column::Rank)
             .one(&self.db)
             .await?;
@@ -452,6 +498,7 @@ impl AssetIdentityNormalizer {
         // Fallback: if no assets have price data with rank, just return any matching asset
         assets::Entity::find()
             .filter(assets::Column::Symbol.eq(normalized_symbol))
+            .order_by_desc(assets::Column::IsActive)
             .one(&self.db)
             .await
     }
@@ -611,6 +658,14 @@ mod tests {
         assert!(unknown.asset_identity().is_none());
     }
     
+    #[test]
+    fn test_split_chain_suffix() {
+        assert_eq!(split_chain_suffix("LINK-ethereum"), ("LINK", Some("ethereum")));
+        assert_eq!(split_chain_suffix("USDC-BSC"), ("USDC", Some("BSC")));
+        assert_eq!(split_chain_suffix("LINK"), ("LINK", None));
+        assert_eq!(split_chain_suffix("BTC-PERP"), ("BTC-PERP", None));
+    }
+
     #[test]
     fn test_normalization_result_display() {
         let unknown = NormalizationResult::Unknown {
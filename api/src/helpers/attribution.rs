@@ -0,0 +1,249 @@
+//! Attribution of a portfolio's return to its assets, from the holdings and prices stored in
+//! consecutive snapshots.
+//!
+//! Between two snapshots, an asset contributes the value change of its starting quantity at
+//! the new price, over the starting portfolio value: `c = q_start × (p_end - p_start) / V_start`.
+//! The contributions of a sub-period sum to its price return; sub-periods are linked by
+//! scaling each contribution by the growth of the price returns before it, so the assets'
+//! contributions add up to the period's compounded price return.
+//!
+//! What the price changes don't explain — deposits, withdrawals, trades, assets bought
+//! between snapshots — is reported apart as `other_change_usd`. Holdings unpriced in either
+//! snapshot contribute nothing.
+
+use std::collections::{BTreeMap, HashMap};
+
+use chrono::NaiveDate;
+use rust_decimal::prelude::ToPrimitive;
+use sea_orm::{ColumnTrait, DatabaseConnection, DbErr, EntityTrait, QueryFilter, QueryOrder};
+use serde::{Deserialize, Serialize};
+use utoipa::ToSchema;
+use uuid::Uuid;
+
+use crate::domain::SnapshotHolding;
+use crate::entities::snapshots;
+
+/// Holdings and total value of the portfolio on one snapshot date
+#[derive(Debug, Clone, PartialEq)]
+pub struct HoldingsSnapshot {
+    pub date: NaiveDate,
+    pub total_value_usd: f64,
+    pub holdings: Vec<SnapshotHolding>,
+}
+
+/// Return attribution of one asset
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, ToSchema)]
+pub struct AssetAttribution {
+    pub asset: String,
+    pub start_value_usd: f64,
+    pub end_value_usd: f64,
+    /// Value change from price moves of the held quantities
+    pub pnl_usd: f64,
+    /// Linked contribution to the portfolio's price return, as a fraction
+    pub contribution: f64,
+    /// Price change from the first to the last snapshot pricing the asset, as a fraction
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub price_change: Option<f64>,
+    /// Average weight at the start of the sub-periods (0-100)
+    pub average_weight: f64,
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct Attribution {
+    pub start_value_usd: f64,
+    pub end_value_usd: f64,
+    /// Compounded price return of the held quantities, as a fraction
+    pub price_return: f64,
+    pub pnl_usd: f64,
+    /// Value change not explained by price moves: flows, trades and assets bought in between
+    pub other_change_usd: f64,
+    /// Largest absolute contribution first
+    pub assets: Vec<AssetAttribution>,
+}
+
+fn quantity(holding: &SnapshotHolding) -> f64 {
+    holding.quantity.parse().unwrap_or(0.0)
+}
+
+/// Attribute the change between the first and last of `snapshots` (oldest first); `None`
+/// with fewer than two snapshots
+pub fn attribute_returns(snapshots: &[HoldingsSnapshot]) -> Option<Attribution> {
+    let (first, last) = (snapshots.first()?, snapshots.last()?);
+    if snapshots.len() < 2 {
+        return None;
+    }
+
+    #[derive(Default)]
+    struct Sums {
+        pnl: f64,
+        contribution: f64,
+        weight: f64,
+        first_price: Option<f64>,
+        last_price: Option<f64>,
+    }
+    let mut sums: BTreeMap<String, Sums> = BTreeMap::new();
+    let mut growth = 1.0;
+    let periods = snapshots.len() - 1;
+    for pair in snapshots.windows(2) {
+        let (start, end) = (&pair[0], &pair[1]);
+        let end_prices: HashMap<&str, f64> = end
+            .holdings
+            .iter()
+            .filter(|h| !h.unpriced)
+            .filter_map(|h| Some((h.asset.as_str(), h.price_usd?)))
+            .collect();
+
+        let mut period_return = 0.0;
+        for holding in &start.holdings {
+            let sums = sums.entry(holding.asset.clone()).or_default();
+            if start.total_value_usd > 0.0 {
+                sums.weight += holding.value_usd / start.total_value_usd * 100.0 / periods as f64;
+            }
+            let (Some(start_price), Some(end_price)) =
+                (holding.price_usd.filter(|_| !holding.unpriced), end_prices.get(holding.asset.as_str()))
+            else {
+                continue;
+            };
+            sums.first_price.get_or_insert(start_price);
+            sums.last_price = Some(*end_price);
+            let pnl = quantity(holding) * (end_price - start_price);
+            sums.pnl += pnl;
+            if start.total_value_usd > 0.0 {
+                let contribution = pnl / start.total_value_usd;
+                sums.contribution += contribution * growth;
+                period_return += contribution;
+            }
+        }
+        growth *= 1.0 + period_return;
+    }
+
+    let value_of = |snapshot: &HoldingsSnapshot, asset: &str| -> f64 {
+        snapshot.holdings.iter().filter(|h| h.asset == asset).map(|h| h.value_usd).sum()
+    };
+    for holding in &last.holdings {
+        sums.entry(holding.asset.clone()).or_default();
+    }
+    let mut assets: Vec<AssetAttribution> = sums
+        .into_iter()
+        .map(|(asset, s)| AssetAttribution {
+            start_value_usd: value_of(first, &asset),
+            end_value_usd: value_of(last, &asset),
+            pnl_usd: s.pnl,
+            contribution: s.contribution,
+            price_change: match (s.first_price, s.last_price) {
+                (Some(first), Some(last)) if first > 0.0 => Some(last / first - 1.0),
+                _ => None,
+            },
+            average_weight: s.weight,
+            asset,
+        })
+        .collect();
+    assets.sort_by(|a, b| b.contribution.abs().total_cmp(&a.contribution.abs()));
+
+    let pnl_usd: f64 = assets.iter().map(|a| a.pnl_usd).sum();
+    Some(Attribution {
+        start_value_usd: first.total_value_usd,
+        end_value_usd: last.total_value_usd,
+        price_return: growth - 1.0,
+        pnl_usd,
+        other_change_usd: last.total_value_usd - first.total_value_usd - pnl_usd,
+        assets,
+    })
+}
+
+/// Holdings per snapshot date from the baseline on, oldest first: the latest snapshot on or
+/// before `baseline_date`, or the first snapshot when there is none (or no baseline date).
+/// The latest snapshot of a date stands for that date; snapshots with unreadable holdings
+/// are skipped.
+pub async fn load_holdings_series(
+    db: &DatabaseConnection,
+    portfolio_id: Uuid,
+    baseline_date: Option<NaiveDate>,
+) -> Result<Vec<HoldingsSnapshot>, DbErr> {
+    let baseline = match baseline_date {
+        Some(date) => snapshots::Entity::find()
+            .filter(snapshots::Column::PortfolioId.eq(portfolio_id))
+            .filter(snapshots::Column::SnapshotDate.lte(date))
+            .order_by_desc(snapshots::Column::SnapshotDate)
+            .one(db)
+            .await?
+            .map(|s| s.snapshot_date)
+            .unwrap_or(date),
+        None => NaiveDate::MIN,
+    };
+
+    let rows = snapshots::Entity::find()
+        .filter(snapshots::Column::PortfolioId.eq(portfolio_id))
+        .filter(snapshots::Column::SnapshotDate.gte(baseline))
+        .order_by_asc(snapshots::Column::SnapshotDate)
+        .order_by_asc(snapshots::Column::CreatedAt)
+        .all(db)
+        .await?;
+
+    let mut series: Vec<HoldingsSnapshot> = Vec::new();
+    for row in rows {
+        let Ok(holdings) = serde_json::from_value::<Vec<SnapshotHolding>>(row.holdings) else {
+            tracing::warn!("Skipping snapshot {} with unreadable holdings", row.id);
+            continue;
+        };
+        let snapshot = HoldingsSnapshot {
+            date: row.snapshot_date,
+            total_value_usd: row.total_value_usd.to_f64().unwrap_or(0.0),
+            holdings,
+        };
+        match series.last_mut() {
+            Some(last) if last.date == snapshot.date => *last = snapshot,
+            _ => series.push(snapshot),
+        }
+    }
+    Ok(series)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn holding(asset: &str, quantity: f64, price: f64) -> SnapshotHolding {
+        SnapshotHolding {
+            asset: asset.to_string(),
+            quantity: quantity.to_string(),
+            price_usd: Some(price),
+            value_usd: quantity * price,
+            weight: 0.0,
+            unpriced: false,
+            borrowed_quantity: None,
+        }
+    }
+
+    fn snapshot(day: u32, holdings: Vec<SnapshotHolding>) -> HoldingsSnapshot {
+        HoldingsSnapshot {
+            date: NaiveDate::from_ymd_opt(2025, 1, day).unwrap(),
+            total_value_usd: holdings.iter().map(|h| h.value_usd).sum(),
+            holdings,
+        }
+    }
+
+    #[test]
+    fn test_attribute_returns() {
+        // 1 BTC at 100 and 100 USDT; BTC rises 10% then 10% again; 50 USDT deposited on day 3
+        let snapshots = [
+            snapshot(1, vec![holding("BTC", 1.0, 100.0), holding("USDT", 100.0, 1.0)]),
+            snapshot(2, vec![holding("BTC", 1.0, 110.0), holding("USDT", 100.0, 1.0)]),
+            snapshot(3, vec![holding("BTC", 1.0, 121.0), holding("USDT", 150.0, 1.0)]),
+        ];
+        let result = attribute_returns(&snapshots).unwrap();
+        assert_eq!(result.assets[0].asset, "BTC");
+        assert!((result.assets[0].pnl_usd - 21.0).abs() < 1e-9);
+        assert!((result.assets[0].price_change.unwrap() - 0.21).abs() < 1e-9);
+        assert_eq!(result.assets[1].contribution, 0.0);
+        // Price returns 5% then 11/210, linked
+        let expected = 1.05 * (1.0 + 11.0 / 210.0) - 1.0;
+        assert!((result.price_return - expected).abs() < 1e-12);
+        let contributions: f64 = result.assets.iter().map(|a| a.contribution).sum();
+        assert!((contributions - result.price_return).abs() < 1e-12);
+        assert!((result.other_change_usd - 50.0).abs() < 1e-9);
+        assert!((result.assets[0].average_weight - (50.0 + 110.0 / 210.0 * 100.0) / 2.0).abs() < 1e-9);
+
+        assert!(attribute_returns(&snapshots[..1]).is_none());
+    }
+}
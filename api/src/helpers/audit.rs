@@ -0,0 +1,35 @@
+//! Audit trail for administrative operations.
+//!
+//! Entries are written through any [`ConnectionTrait`] so they can share the
+//! transaction of the operation they describe and are rolled back with it.
+
+use chrono::Utc;
+use sea_orm::{ActiveModelTrait, ActiveValue, ConnectionTrait, DbErr};
+use uuid::Uuid;
+
+use crate::entities::audit_log;
+
+/// Audit action: an account was reassigned to another user
+pub const AUDIT_ACCOUNT_TRANSFER: &str = "account.transfer";
+
+/// Append an audit entry
+pub async fn record_audit<C: ConnectionTrait>(
+    conn: &C,
+    actor_keycloak_user_id: &str,
+    action: &str,
+    entity_type: &str,
+    entity_id: Option<Uuid>,
+    details: serde_json::Value,
+) -> Result<audit_log::Model, DbErr> {
+    audit_log::ActiveModel {
+        id: ActiveValue::Set(Uuid::new_v4()),
+        actor_keycloak_user_id: ActiveValue::Set(actor_keycloak_user_id.to_string()),
+        action: ActiveValue::Set(action.to_string()),
+        entity_type: ActiveValue::Set(entity_type.to_string()),
+        entity_id: ActiveValue::Set(entity_id),
+        details: ActiveValue::Set(Some(details)),
+        created_at: ActiveValue::Set(Utc::now().into()),
+    }
+    .insert(conn)
+    .await
+}
@@ -2,7 +2,8 @@ use axum_keycloak_auth::decode::KeycloakToken;
 use sea_orm::{ActiveModelTrait, ActiveValue, ColumnTrait, DatabaseConnection, EntityTrait, QueryFilter};
 use uuid::Uuid;
 
-use crate::entities::users;
+use crate::entities::{portfolios, users};
+use crate::handlers::error::ApiError;
 
 /// Get or create user in database from Keycloak token
 /// 
@@ -38,6 +39,9 @@ pub async fn get_or_create_user(
         keycloak_user_id: ActiveValue::Set(keycloak_user_id.clone()),
         email: ActiveValue::Set(Some(token.extra.email.email.clone())),
         preferred_username: ActiveValue::Set(Some(token.extra.profile.preferred_username.clone())),
+        base_currency: ActiveValue::NotSet,
+        tax_lot_method: ActiveValue::NotSet,
+        notification_channels: ActiveValue::NotSet,
         created_at: ActiveValue::NotSet,
         updated_at: ActiveValue::NotSet,
     };
@@ -45,3 +49,26 @@ pub async fn get_or_create_user(
     let user = new_user.insert(db).await?;
     Ok(user)
 }
+
+/// Load a portfolio, checking that it belongs to `user_id`
+///
+/// # Returns
+/// * `Ok(portfolios::Model)` - The portfolio
+/// * `Err(ApiError::NotFound)` - No such portfolio
+/// * `Err(ApiError::Forbidden)` - The portfolio belongs to another user
+pub async fn check_portfolio_ownership(
+    db: &DatabaseConnection,
+    portfolio_id: Uuid,
+    user_id: Uuid,
+) -> Result<portfolios::Model, ApiError> {
+    let portfolio = portfolios::Entity::find_by_id(portfolio_id)
+        .one(db)
+        .await?
+        .ok_or(ApiError::NotFound)?;
+
+    if portfolio.user_id != user_id {
+        return Err(ApiError::Forbidden);
+    }
+
+    Ok(portfolio)
+}
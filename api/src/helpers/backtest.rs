@@ -0,0 +1,238 @@
+//! Backtest of a target allocation: start with the target weights, rebalance back to them on
+//! a schedule, and compare the value curve with holding the starting quantities unchanged.
+//!
+//! Simulation runs on daily closes. Days where an asset has no close reuse its previous one;
+//! the simulation starts on the first day every asset has a close. Trades are frictionless.
+
+use std::collections::BTreeMap;
+
+use chrono::{Duration, Months, NaiveDate};
+use serde::{Deserialize, Serialize};
+use utoipa::ToSchema;
+
+use super::risk::{risk_metrics, DAYS_PER_YEAR};
+
+/// How often the simulated portfolio is rebalanced to the target weights
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize, ToSchema)]
+#[serde(rename_all = "lowercase")]
+pub enum RebalanceFrequency {
+    Never,
+    Daily,
+    Weekly,
+    Monthly,
+    Quarterly,
+}
+
+impl RebalanceFrequency {
+    /// Parse "never", "daily", "weekly", "monthly" or "quarterly"
+    pub fn parse(value: &str) -> Option<Self> {
+        match value.trim().to_lowercase().as_str() {
+            "never" => Some(Self::Never),
+            "daily" => Some(Self::Daily),
+            "weekly" => Some(Self::Weekly),
+            "monthly" => Some(Self::Monthly),
+            "quarterly" => Some(Self::Quarterly),
+            _ => None,
+        }
+    }
+
+    /// First rebalance date after one on `date`; `None` for never
+    fn next(self, date: NaiveDate) -> Option<NaiveDate> {
+        match self {
+            Self::Never => None,
+            Self::Daily => Some(date + Duration::days(1)),
+            Self::Weekly => Some(date + Duration::days(7)),
+            Self::Monthly => date.checked_add_months(Months::new(1)),
+            Self::Quarterly => date.checked_add_months(Months::new(3)),
+        }
+    }
+}
+
+/// Value of both strategies on one day
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, ToSchema)]
+pub struct BacktestPoint {
+    pub date: String, // YYYY-MM-DD
+    /// Value of the rebalanced portfolio
+    pub value_usd: f64,
+    /// Value of the starting quantities held unchanged
+    pub buy_and_hold_value_usd: f64,
+    /// Whether the portfolio was rebalanced at this day's close
+    #[serde(default, skip_serializing_if = "std::ops::Not::not")]
+    pub rebalanced: bool,
+}
+
+/// Summary of one value curve
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, ToSchema)]
+pub struct CurveStats {
+    pub final_value_usd: f64,
+    /// Final over initial value minus one
+    pub total_return: f64,
+    /// Compound annual growth rate
+    pub annualized_return: f64,
+    /// Standard deviation of daily returns, annualized
+    pub annualized_volatility: f64,
+    /// Largest fall from a previous peak, as a fraction of the peak (0-1)
+    pub max_drawdown: f64,
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct BacktestResult {
+    pub start: NaiveDate,
+    pub end: NaiveDate,
+    pub points: Vec<BacktestPoint>,
+    pub strategy: CurveStats,
+    pub buy_and_hold: CurveStats,
+    pub rebalances: usize,
+    /// USD value bought and sold by rebalancing, counted one way
+    pub traded_usd: f64,
+    /// `traded_usd` over the average portfolio value
+    pub turnover: f64,
+}
+
+/// Stats of a value curve spanning `days` days
+pub fn curve_stats(values: &[f64], days: i64) -> CurveStats {
+    let initial = values.first().copied().unwrap_or(0.0);
+    let last = values.last().copied().unwrap_or(0.0);
+    let total_return = if initial > 0.0 { last / initial - 1.0 } else { 0.0 };
+    let annualized_return = if initial > 0.0 && last > 0.0 && days > 0 {
+        (last / initial).powf(DAYS_PER_YEAR / days as f64) - 1.0
+    } else {
+        total_return
+    };
+    let returns: Vec<f64> = values
+        .windows(2)
+        .filter(|pair| pair[0] > 0.0)
+        .map(|pair| pair[1] / pair[0] - 1.0)
+        .collect();
+    let annualized_volatility = risk_metrics(&returns, DAYS_PER_YEAR, 0.0).map_or(0.0, |m| m.annualized_volatility);
+
+    let mut peak = f64::MIN;
+    let mut max_drawdown: f64 = 0.0;
+    for value in values {
+        peak = peak.max(*value);
+        if peak > 0.0 {
+            max_drawdown = max_drawdown.max(1.0 - value / peak);
+        }
+    }
+    CurveStats { final_value_usd: last, total_return, annualized_return, annualized_volatility, max_drawdown }
+}
+
+/// Simulate `weights` (by asset, any positive scale) over the daily `closes` of each asset
+/// from `initial_value`. `None` when some weighted asset has no close, or there are fewer
+/// than two days with a close of every asset.
+pub fn run_backtest(
+    closes: &BTreeMap<String, BTreeMap<NaiveDate, f64>>,
+    weights: &BTreeMap<String, f64>,
+    frequency: RebalanceFrequency,
+    initial_value: f64,
+) -> Option<BacktestResult> {
+    let weight_sum: f64 = weights.values().filter(|w| **w > 0.0).sum();
+    let assets: Vec<(&String, f64)> =
+        weights.iter().filter(|(_, w)| **w > 0.0).map(|(a, w)| (a, *w / weight_sum)).collect();
+    if assets.is_empty() {
+        return None;
+    }
+    let series: Vec<&BTreeMap<NaiveDate, f64>> = assets.iter().map(|(a, _)| closes.get(*a)).collect::<Option<_>>()?;
+    let start = series.iter().map(|s| s.keys().next().copied()).collect::<Option<Vec<_>>>()?.into_iter().max()?;
+    let end = series.iter().filter_map(|s| s.keys().next_back().copied()).min()?;
+    if end <= start {
+        return None;
+    }
+
+    // Price of each asset on each day, carrying the previous close forward
+    let mut last: Vec<f64> = series.iter().map(|s| s.range(..=start).next_back().map_or(0.0, |(_, p)| *p)).collect();
+    if last.iter().any(|p| *p <= 0.0) {
+        return None;
+    }
+    let buy = |value: f64, prices: &[f64]| -> Vec<f64> {
+        assets.iter().zip(prices).map(|((_, w), p)| value * w / p).collect()
+    };
+    let mut quantities = buy(initial_value, &last);
+    let held = quantities.clone();
+    let mut next_rebalance = frequency.next(start);
+
+    let mut points = Vec::new();
+    let mut traded_usd = 0.0;
+    let mut rebalances = 0;
+    let mut date = start;
+    while date <= end {
+        for (price, s) in last.iter_mut().zip(&series) {
+            if let Some(close) = s.get(&date).filter(|p| **p > 0.0) {
+                *price = *close;
+            }
+        }
+        let value: f64 = quantities.iter().zip(&last).map(|(q, p)| q * p).sum();
+        let buy_and_hold: f64 = held.iter().zip(&last).map(|(q, p)| q * p).sum();
+
+        let rebalanced = next_rebalance.is_some_and(|at| date >= at);
+        if rebalanced {
+            let target = buy(value, &last);
+            traded_usd += target.iter().zip(&quantities).zip(&last).map(|((t, q), p)| ((t - q) * p).abs()).sum::<f64>() / 2.0;
+            quantities = target;
+            rebalances += 1;
+            next_rebalance = frequency.next(date);
+        }
+        points.push(BacktestPoint {
+            date: date.to_string(),
+            value_usd: value,
+            buy_and_hold_value_usd: buy_and_hold,
+            rebalanced,
+        });
+        date += Duration::days(1);
+    }
+
+    let days = (end - start).num_days();
+    let values: Vec<f64> = points.iter().map(|p| p.value_usd).collect();
+    let held_values: Vec<f64> = points.iter().map(|p| p.buy_and_hold_value_usd).collect();
+    let average = values.iter().sum::<f64>() / values.len() as f64;
+    Some(BacktestResult {
+        start,
+        end,
+        strategy: curve_stats(&values, days),
+        buy_and_hold: curve_stats(&held_values, days),
+        points,
+        rebalances,
+        traded_usd,
+        turnover: if average > 0.0 { traded_usd / average } else { 0.0 },
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn series(start: NaiveDate, prices: &[f64]) -> BTreeMap<NaiveDate, f64> {
+        prices.iter().enumerate().map(|(i, p)| (start + Duration::days(i as i64), *p)).collect()
+    }
+
+    #[test]
+    fn test_run_backtest() {
+        let start = NaiveDate::from_ymd_opt(2025, 1, 1).unwrap();
+        // BTC doubles on day 1 and falls back; USDT stays at 1
+        let closes = BTreeMap::from([
+            ("BTC".to_string(), series(start, &[100.0, 200.0, 100.0])),
+            ("USDT".to_string(), series(start, &[1.0, 1.0, 1.0])),
+        ]);
+        let weights = BTreeMap::from([("BTC".to_string(), 50.0), ("USDT".to_string(), 50.0)]);
+
+        let held = run_backtest(&closes, &weights, RebalanceFrequency::Never, 1000.0).unwrap();
+        assert_eq!(held.points.len(), 3);
+        assert_eq!(held.points[1].value_usd, 1500.0);
+        assert_eq!(held.strategy, held.buy_and_hold);
+        assert_eq!(held.rebalances, 0);
+        assert!((held.strategy.max_drawdown - 1.0 / 3.0).abs() < 1e-12);
+
+        // Rebalanced on day 1 at 750 / 750: BTC halves to 375, ending at 1125
+        let daily = run_backtest(&closes, &weights, RebalanceFrequency::Daily, 1000.0).unwrap();
+        assert!(daily.points[1].rebalanced);
+        assert!((daily.strategy.final_value_usd - 1125.0).abs() < 1e-9);
+        assert!((daily.strategy.total_return - 0.125).abs() < 1e-12);
+        assert_eq!(daily.buy_and_hold.final_value_usd, 1000.0);
+        // Day 1 sells 250 of BTC, day 2 buys 187.5 back
+        assert!((daily.traded_usd - 437.5).abs() < 1e-9);
+
+        // An asset without prices cannot be simulated
+        let missing = BTreeMap::from([("ETH".to_string(), 100.0)]);
+        assert!(run_backtest(&closes, &missing, RebalanceFrequency::Monthly, 1000.0).is_none());
+    }
+}
@@ -38,6 +38,10 @@ pub enum NormalizationError {
     InvalidBalance(String),
     #[error("Arithmetic overflow during normalization")]
     ArithmeticOverflow,
+    #[error("Amount {amount} has more than {decimals} decimal places")]
+    ExcessPrecision { amount: String, decimals: u8 },
+    #[error("Cannot convert {from} to {to}: units of different assets")]
+    UnitMismatch { from: String, to: String },
 }
 
 /// Normalizes a raw token balance to a human-readable decimal string.
@@ -135,6 +139,124 @@ pub fn normalize_and_format_balance(
     Ok(format!("{:.prec$}", decimal, prec = display_decimals as usize))
 }
 
+/// A denomination of a chain's native asset, e.g. satoshis for BTC or gwei for ETH.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct DenominationUnit {
+    /// Unit symbol (e.g. "sat", "gwei", "BTC")
+    pub symbol: &'static str,
+    /// Other accepted spellings (e.g. "sats", "satoshi")
+    pub aliases: &'static [&'static str],
+    /// Asset the unit denominates
+    pub asset: &'static str,
+    /// Decimal places of the unit over the asset's base unit (sat = 0, BTC = 8)
+    pub decimals: u8,
+}
+
+/// Supported units. The unit whose symbol equals the asset is the human (whole) unit;
+/// the unit with 0 decimals is the on-chain base unit.
+pub const DENOMINATION_UNITS: &[DenominationUnit] = &[
+    DenominationUnit { symbol: "BTC", aliases: &["bitcoin"], asset: "BTC", decimals: 8 },
+    DenominationUnit { symbol: "sat", aliases: &["sats", "satoshi", "satoshis"], asset: "BTC", decimals: 0 },
+    DenominationUnit { symbol: "LTC", aliases: &["litecoin"], asset: "LTC", decimals: 8 },
+    DenominationUnit { symbol: "litoshi", aliases: &["litoshis"], asset: "LTC", decimals: 0 },
+    DenominationUnit { symbol: "DOGE", aliases: &["dogecoin"], asset: "DOGE", decimals: 8 },
+    DenominationUnit { symbol: "koinu", aliases: &["koinus"], asset: "DOGE", decimals: 0 },
+    DenominationUnit { symbol: "ETH", aliases: &["ether"], asset: "ETH", decimals: 18 },
+    DenominationUnit { symbol: "gwei", aliases: &["shannon"], asset: "ETH", decimals: 9 },
+    DenominationUnit { symbol: "wei", aliases: &[], asset: "ETH", decimals: 0 },
+    DenominationUnit { symbol: "SOL", aliases: &["solana"], asset: "SOL", decimals: 9 },
+    DenominationUnit { symbol: "lamport", aliases: &["lamports"], asset: "SOL", decimals: 0 },
+];
+
+/// Finds a unit by symbol or alias (case-insensitive).
+pub fn find_unit(name: &str) -> Option<&'static DenominationUnit> {
+    let name = name.trim();
+    DENOMINATION_UNITS.iter().find(|u| {
+        u.symbol.eq_ignore_ascii_case(name) || u.aliases.iter().any(|a| a.eq_ignore_ascii_case(name))
+    })
+}
+
+/// Finds the human (whole) unit of an asset, e.g. BTC for "sat".
+pub fn whole_unit(asset: &str) -> Option<&'static DenominationUnit> {
+    DENOMINATION_UNITS
+        .iter()
+        .find(|u| u.asset.eq_ignore_ascii_case(asset) && u.symbol.eq_ignore_ascii_case(u.asset))
+}
+
+fn pow10(decimals: u8) -> Result<Decimal, NormalizationError> {
+    Decimal::from(10_u64)
+        .checked_powi(decimals as i64)
+        .ok_or(NormalizationError::ArithmeticOverflow)
+}
+
+/// Scales an amount to base units, exactly.
+///
+/// Fails with `NormalizationError::ExcessPrecision` when the amount has more decimal places
+/// than `decimals` (it would not be a whole number of base units) rather than rounding.
+pub fn scale_to_base_units(amount: Decimal, decimals: u8) -> Result<Decimal, NormalizationError> {
+    let amount = amount.normalize();
+    if amount.scale() > decimals as u32 {
+        return Err(NormalizationError::ExcessPrecision { amount: amount.to_string(), decimals });
+    }
+    amount
+        .checked_mul(pow10(decimals)?)
+        .map(|base| base.normalize())
+        .ok_or(NormalizationError::ArithmeticOverflow)
+}
+
+/// Converts a human-readable amount to a raw integer balance; the inverse of
+/// [`normalize_token_balance`].
+///
+/// # Examples
+///
+/// ```rust
+/// use crypto_pocket_butler_backend::helpers::balance_normalization::to_base_units;
+///
+/// assert_eq!(to_base_units("1.5", 18).unwrap(), "1500000000000000000");
+/// assert_eq!(to_base_units("0.00000001", 8).unwrap(), "1");
+/// assert!(to_base_units("0.000000001", 8).is_err());
+/// ```
+///
+/// # Errors
+///
+/// Returns `NormalizationError::InvalidBalance` if the amount cannot be parsed,
+/// `NormalizationError::ExcessPrecision` if it is finer than one base unit, and
+/// `NormalizationError::ArithmeticOverflow` if the result does not fit.
+pub fn to_base_units(amount: &str, decimals: u8) -> Result<String, NormalizationError> {
+    let amount = Decimal::from_str(amount.trim())
+        .map_err(|e| NormalizationError::InvalidBalance(format!("{}: {}", amount, e)))?;
+    Ok(scale_to_base_units(amount, decimals)?.to_string())
+}
+
+/// Converts an amount between two units of the same asset, exactly (e.g. gwei to ETH).
+///
+/// # Examples
+///
+/// ```rust
+/// use crypto_pocket_butler_backend::helpers::balance_normalization::{convert_amount, find_unit};
+/// use rust_decimal::Decimal;
+///
+/// let gwei = find_unit("gwei").unwrap();
+/// let eth = find_unit("ETH").unwrap();
+/// assert_eq!(convert_amount(Decimal::from(21_000), gwei, eth).unwrap().to_string(), "0.000021");
+/// ```
+pub fn convert_amount(
+    amount: Decimal,
+    from: &DenominationUnit,
+    to: &DenominationUnit,
+) -> Result<Decimal, NormalizationError> {
+    if from.asset != to.asset {
+        return Err(NormalizationError::UnitMismatch {
+            from: from.symbol.to_string(),
+            to: to.symbol.to_string(),
+        });
+    }
+    scale_to_base_units(amount, from.decimals)?
+        .checked_div(pow10(to.decimals)?)
+        .map(|converted| converted.normalize())
+        .ok_or(NormalizationError::ArithmeticOverflow)
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -266,4 +388,98 @@ mod tests {
         // 0.0000000000000001 preserves all necessary precision
         assert_eq!(result, "0.0000000000000001");
     }
+
+    #[test]
+    fn test_find_unit_symbols_and_aliases() {
+        assert_eq!(find_unit("SATS").unwrap().symbol, "sat");
+        assert_eq!(find_unit(" Gwei ").unwrap().decimals, 9);
+        assert_eq!(find_unit("lamports").unwrap().asset, "SOL");
+        assert_eq!(find_unit("eth").unwrap().decimals, 18);
+        assert!(find_unit("finney").is_none());
+
+        assert_eq!(whole_unit("btc").unwrap().symbol, "BTC");
+        assert!(whole_unit("USDC").is_none());
+        // Every asset has exactly one whole unit and one base unit
+        for unit in DENOMINATION_UNITS {
+            assert!(whole_unit(unit.asset).is_some());
+            assert_eq!(DENOMINATION_UNITS.iter().filter(|u| u.asset == unit.asset && u.decimals == 0).count(), 1);
+        }
+    }
+
+    #[test]
+    fn test_to_base_units_precision_boundaries() {
+        // Exactly one base unit
+        assert_eq!(to_base_units("0.00000001", 8).unwrap(), "1");
+        assert_eq!(to_base_units("0.000000000000000001", 18).unwrap(), "1");
+        assert_eq!(to_base_units("0.000000001", 9).unwrap(), "1");
+        // Trailing zeros past the unit's precision are not extra precision
+        assert_eq!(to_base_units("0.000000010000", 8).unwrap(), "1");
+        assert_eq!(to_base_units("1.50", 18).unwrap(), "1500000000000000000");
+        // Half a base unit is rejected rather than rounded
+        assert_eq!(
+            to_base_units("0.000000005", 8).unwrap_err().to_string(),
+            "Amount 0.000000005 has more than 8 decimal places"
+        );
+        assert!(matches!(
+            to_base_units("1.0000000000000000001", 18),
+            Err(NormalizationError::ExcessPrecision { decimals: 18, .. })
+        ));
+        // Zero, whole numbers and 0-decimal tokens
+        assert_eq!(to_base_units("0", 18).unwrap(), "0");
+        assert_eq!(to_base_units("21000000", 8).unwrap(), "2100000000000000");
+        assert_eq!(to_base_units("42", 0).unwrap(), "42");
+        assert!(to_base_units("42.1", 0).is_err());
+        // Signed amounts keep their sign
+        assert_eq!(to_base_units("-0.5", 9).unwrap(), "-500000000");
+        assert!(matches!(to_base_units("abc", 8), Err(NormalizationError::InvalidBalance(_))));
+    }
+
+    #[test]
+    fn test_to_base_units_overflow_boundaries() {
+        // Decimal holds 96-bit integers (up to ~7.9e28)
+        assert_eq!(to_base_units("79228162514", 18).unwrap(), "79228162514000000000000000000");
+        assert!(matches!(to_base_units("79228162515", 18), Err(NormalizationError::ArithmeticOverflow)));
+        // 10^28 is the largest supported scale
+        assert_eq!(to_base_units("1", 28).unwrap(), "10000000000000000000000000000");
+        assert!(matches!(to_base_units("1", 29), Err(NormalizationError::ArithmeticOverflow)));
+    }
+
+    #[test]
+    fn test_base_units_round_trip() {
+        for (raw, decimals) in [
+            ("1", 18),
+            ("291725391649", 18),
+            ("79228162514264337593543950335", 18),
+            ("2100000000000000", 8),
+            ("1", 8),
+            ("999999999", 9),
+            ("706000", 6),
+        ] {
+            let human = normalize_token_balance(raw, decimals).unwrap();
+            assert_eq!(to_base_units(&human, decimals).unwrap(), raw, "{} at {} decimals", raw, decimals);
+        }
+    }
+
+    #[test]
+    fn test_convert_amount_between_units() {
+        let unit = |name| find_unit(name).unwrap();
+        let convert = |amount: &str, from, to| {
+            convert_amount(Decimal::from_str(amount).unwrap(), unit(from), unit(to)).map(|d| d.to_string())
+        };
+
+        assert_eq!(convert("1", "BTC", "sat").unwrap(), "100000000");
+        assert_eq!(convert("1", "sat", "BTC").unwrap(), "0.00000001");
+        assert_eq!(convert("1", "wei", "ETH").unwrap(), "0.000000000000000001");
+        assert_eq!(convert("1", "wei", "gwei").unwrap(), "0.000000001");
+        assert_eq!(convert("2.5", "gwei", "wei").unwrap(), "2500000000");
+        assert_eq!(convert("1.5", "SOL", "lamport").unwrap(), "1500000000");
+        assert_eq!(convert("0.1", "ETH", "ETH").unwrap(), "0.1");
+        // A fraction of a wei does not exist
+        assert!(matches!(convert("0.5", "wei", "ETH"), Err(NormalizationError::ExcessPrecision { .. })));
+        assert!(matches!(convert("0.0000000001", "gwei", "ETH"), Err(NormalizationError::ExcessPrecision { .. })));
+        assert_eq!(
+            convert("1", "sat", "lamport").unwrap_err().to_string(),
+            "Cannot convert sat to lamport: units of different assets"
+        );
+    }
 }
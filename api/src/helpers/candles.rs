@@ -0,0 +1,361 @@
+//! OHLC candles aggregated from `asset_prices` rows.
+//!
+//! Prices are bucketed by the UTC hour or day they were observed in; each bucket with at
+//! least one price becomes a candle. Empty buckets are left out rather than filled.
+//!
+//! The price rollup job keeps these candles in `asset_prices_hourly` and `asset_prices_daily`,
+//! so long ranges are read from there and only buckets not rolled up yet are aggregated from
+//! raw prices.
+
+use crate::entities::{asset_prices, asset_prices_daily, asset_prices_hourly};
+use chrono::{DateTime, Duration, DurationRound, Utc};
+use rust_decimal::Decimal;
+use sea_orm::{
+    prelude::DateTimeWithTimeZone, ColumnTrait, DatabaseConnection, DbErr, EntityTrait, QueryFilter, QueryOrder,
+    QuerySelect,
+};
+use uuid::Uuid;
+
+/// Most candles one request may span
+pub const MAX_CANDLES: i64 = 1000;
+
+/// Candle width
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum CandleInterval {
+    Hour,
+    Day,
+}
+
+impl CandleInterval {
+    /// Parse "1h" or "1d"
+    pub fn parse(value: &str) -> Option<Self> {
+        match value.trim().to_lowercase().as_str() {
+            "1h" => Some(Self::Hour),
+            "1d" => Some(Self::Day),
+            _ => None,
+        }
+    }
+
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            Self::Hour => "1h",
+            Self::Day => "1d",
+        }
+    }
+
+    pub fn duration(&self) -> Duration {
+        match self {
+            Self::Hour => Duration::hours(1),
+            Self::Day => Duration::days(1),
+        }
+    }
+
+    /// Range requested when `from` is omitted: a week of hourly or a year of daily candles
+    pub fn default_range(&self) -> Duration {
+        match self {
+            Self::Hour => Duration::days(7),
+            Self::Day => Duration::days(365),
+        }
+    }
+
+    /// Start of the bucket `timestamp` falls in
+    pub fn bucket_start(&self, timestamp: DateTime<Utc>) -> DateTime<Utc> {
+        timestamp.duration_trunc(self.duration()).unwrap_or(timestamp)
+    }
+
+    /// Granularity for a price history range: raw prices up to a week, hourly up to 90 days,
+    /// daily beyond
+    pub fn for_range(range: Duration) -> Option<Self> {
+        if range <= Duration::days(7) {
+            None
+        } else if range <= Duration::days(90) {
+            Some(Self::Hour)
+        } else {
+            Some(Self::Day)
+        }
+    }
+}
+
+/// One OHLC candle
+#[derive(Debug, Clone, PartialEq)]
+pub struct Candle {
+    /// Start of the bucket
+    pub open_time: DateTime<Utc>,
+    pub open: Decimal,
+    pub high: Decimal,
+    pub low: Decimal,
+    pub close: Decimal,
+    /// Prices aggregated into the candle
+    pub ticks: usize,
+}
+
+/// Aggregate prices sorted by time (oldest first) into candles
+pub fn aggregate_candles(
+    prices: impl IntoIterator<Item = (DateTime<Utc>, Decimal)>,
+    interval: CandleInterval,
+) -> Vec<Candle> {
+    let mut candles: Vec<Candle> = Vec::new();
+    for (timestamp, price) in prices {
+        let open_time = interval.bucket_start(timestamp);
+        match candles.last_mut() {
+            Some(candle) if candle.open_time == open_time => {
+                candle.high = candle.high.max(price);
+                candle.low = candle.low.min(price);
+                candle.close = price;
+                candle.ticks += 1;
+            }
+            _ => candles.push(Candle { open_time, open: price, high: price, low: price, close: price, ticks: 1 }),
+        }
+    }
+    candles
+}
+
+/// Candles of one source from raw `asset_prices` in `[from, to)`
+async fn raw_candles(
+    db: &DatabaseConnection,
+    asset_id: Uuid,
+    source: &str,
+    interval: CandleInterval,
+    from: DateTime<Utc>,
+    to: DateTime<Utc>,
+) -> Result<Vec<Candle>, DbErr> {
+    if from >= to {
+        return Ok(Vec::new());
+    }
+    let prices = asset_prices::Entity::find()
+        .select_only()
+        .column(asset_prices::Column::Timestamp)
+        .column(asset_prices::Column::PriceUsd)
+        .filter(asset_prices::Column::AssetId.eq(asset_id))
+        .filter(asset_prices::Column::Source.eq(source))
+        .filter(asset_prices::Column::Timestamp.gte(from))
+        .filter(asset_prices::Column::Timestamp.lt(to))
+        .order_by_asc(asset_prices::Column::Timestamp)
+        .into_tuple::<(DateTimeWithTimeZone, Decimal)>()
+        .all(db)
+        .await?
+        .into_iter()
+        .map(|(timestamp, price)| (timestamp.with_timezone(&Utc), price));
+    Ok(aggregate_candles(prices, interval))
+}
+
+/// Rolled-up candles of one source with a bucket start in `[from, to)`, oldest first
+async fn rollup_candles(
+    db: &DatabaseConnection,
+    asset_id: Uuid,
+    source: &str,
+    interval: CandleInterval,
+    from: DateTime<Utc>,
+    to: DateTime<Utc>,
+) -> Result<Vec<Candle>, DbErr> {
+    let candle = |open_time: DateTimeWithTimeZone, open, high, low, close, ticks: i32| Candle {
+        open_time: open_time.with_timezone(&Utc),
+        open,
+        high,
+        low,
+        close,
+        ticks: ticks.max(0) as usize,
+    };
+    let candles = match interval {
+        CandleInterval::Hour => asset_prices_hourly::Entity::find()
+            .filter(asset_prices_hourly::Column::AssetId.eq(asset_id))
+            .filter(asset_prices_hourly::Column::Source.eq(source))
+            .filter(asset_prices_hourly::Column::BucketStart.gte(from))
+            .filter(asset_prices_hourly::Column::BucketStart.lt(to))
+            .order_by_asc(asset_prices_hourly::Column::BucketStart)
+            .all(db)
+            .await?
+            .into_iter()
+            .map(|row| candle(row.bucket_start, row.open_usd, row.high_usd, row.low_usd, row.close_usd, row.ticks))
+            .collect(),
+        CandleInterval::Day => asset_prices_daily::Entity::find()
+            .filter(asset_prices_daily::Column::AssetId.eq(asset_id))
+            .filter(asset_prices_daily::Column::Source.eq(source))
+            .filter(asset_prices_daily::Column::BucketStart.gte(from))
+            .filter(asset_prices_daily::Column::BucketStart.lt(to))
+            .order_by_asc(asset_prices_daily::Column::BucketStart)
+            .all(db)
+            .await?
+            .into_iter()
+            .map(|row| candle(row.bucket_start, row.open_usd, row.high_usd, row.low_usd, row.close_usd, row.ticks))
+            .collect(),
+    };
+    Ok(candles)
+}
+
+/// Start of the newest rolled-up bucket of one source before `to`
+async fn latest_rollup_bucket(
+    db: &DatabaseConnection,
+    asset_id: Uuid,
+    source: &str,
+    interval: CandleInterval,
+    to: DateTime<Utc>,
+) -> Result<Option<DateTime<Utc>>, DbErr> {
+    let latest: Option<DateTimeWithTimeZone> = match interval {
+        CandleInterval::Hour => asset_prices_hourly::Entity::find()
+            .select_only()
+            .column(asset_prices_hourly::Column::BucketStart)
+            .filter(asset_prices_hourly::Column::AssetId.eq(asset_id))
+            .filter(asset_prices_hourly::Column::Source.eq(source))
+            .filter(asset_prices_hourly::Column::BucketStart.lt(to))
+            .order_by_desc(asset_prices_hourly::Column::BucketStart)
+            .into_tuple()
+            .one(db)
+            .await?,
+        CandleInterval::Day => asset_prices_daily::Entity::find()
+            .select_only()
+            .column(asset_prices_daily::Column::BucketStart)
+            .filter(asset_prices_daily::Column::AssetId.eq(asset_id))
+            .filter(asset_prices_daily::Column::Source.eq(source))
+            .filter(asset_prices_daily::Column::BucketStart.lt(to))
+            .order_by_desc(asset_prices_daily::Column::BucketStart)
+            .into_tuple()
+            .one(db)
+            .await?,
+    };
+    Ok(latest.map(|bucket| bucket.with_timezone(&Utc)))
+}
+
+/// Where the candles of a part of a range are read from
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum CandleSource {
+    Raw,
+    Rollup,
+}
+
+/// Split `[from, to)` into the parts read from raw prices and from rollups, given the start of
+/// the newest rolled-up bucket before `to`.
+///
+/// Whole buckets up to the newest rolled-up one are read from the rollup tables. That newest
+/// bucket may have been rolled up while still open, so it is re-aggregated from raw prices
+/// along with everything after it, as are partial buckets at either end of the range. Empty
+/// parts are left out.
+fn candle_segments(
+    interval: CandleInterval,
+    from: DateTime<Utc>,
+    to: DateTime<Utc>,
+    latest_rollup: Option<DateTime<Utc>>,
+) -> Vec<(CandleSource, DateTime<Utc>, DateTime<Utc>)> {
+    let first_whole = match interval.bucket_start(from) {
+        start if start == from => from,
+        start => start + interval.duration(),
+    };
+    let rolled_up_until = match latest_rollup {
+        Some(latest) => latest.min(interval.bucket_start(to)),
+        None => first_whole,
+    };
+    let segments = if rolled_up_until <= first_whole {
+        vec![(CandleSource::Raw, from, to)]
+    } else {
+        vec![
+            (CandleSource::Raw, from, first_whole),
+            (CandleSource::Rollup, first_whole, rolled_up_until),
+            (CandleSource::Raw, rolled_up_until, to),
+        ]
+    };
+    segments.into_iter().filter(|(_, start, end)| start < end).collect()
+}
+
+/// Candles of one source for prices in `[from, to)`, oldest first, read from the rollups
+/// where possible (see [`candle_segments`])
+pub async fn load_candles(
+    db: &DatabaseConnection,
+    asset_id: Uuid,
+    source: &str,
+    interval: CandleInterval,
+    from: DateTime<Utc>,
+    to: DateTime<Utc>,
+) -> Result<Vec<Candle>, DbErr> {
+    let latest = latest_rollup_bucket(db, asset_id, source, interval, to).await?;
+    let mut candles = Vec::new();
+    for (candle_source, start, end) in candle_segments(interval, from, to, latest) {
+        candles.extend(match candle_source {
+            CandleSource::Raw => raw_candles(db, asset_id, source, interval, start, end).await?,
+            CandleSource::Rollup => rollup_candles(db, asset_id, source, interval, start, end).await?,
+        });
+    }
+    Ok(candles)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_aggregate_candles() {
+        let at = |s: &str| DateTime::parse_from_rfc3339(s).unwrap().with_timezone(&Utc);
+        let prices = vec![
+            (at("2025-01-10T10:00:00Z"), Decimal::from(100)),
+            (at("2025-01-10T10:15:00Z"), Decimal::from(110)),
+            (at("2025-01-10T10:30:00Z"), Decimal::from(95)),
+            (at("2025-01-10T10:45:00Z"), Decimal::from(105)),
+            (at("2025-01-10T12:00:00Z"), Decimal::from(120)),
+        ];
+
+        let hourly = aggregate_candles(prices.clone(), CandleInterval::Hour);
+        assert_eq!(hourly.len(), 2);
+        assert_eq!(
+            hourly[0],
+            Candle {
+                open_time: at("2025-01-10T10:00:00Z"),
+                open: Decimal::from(100),
+                high: Decimal::from(110),
+                low: Decimal::from(95),
+                close: Decimal::from(105),
+                ticks: 4,
+            }
+        );
+        assert_eq!(hourly[1].open_time, at("2025-01-10T12:00:00Z"));
+
+        let daily = aggregate_candles(prices, CandleInterval::Day);
+        assert_eq!(daily.len(), 1);
+        assert_eq!(daily[0].open_time, at("2025-01-10T00:00:00Z"));
+        assert_eq!(daily[0].close, Decimal::from(120));
+
+        assert_eq!(CandleInterval::parse("1D"), Some(CandleInterval::Day));
+        assert!(CandleInterval::parse("5m").is_none());
+
+        assert_eq!(CandleInterval::for_range(Duration::days(7)), None);
+        assert_eq!(CandleInterval::for_range(Duration::days(30)), Some(CandleInterval::Hour));
+        assert_eq!(CandleInterval::for_range(Duration::days(365)), Some(CandleInterval::Day));
+    }
+
+    #[test]
+    fn test_candle_segments() {
+        use CandleSource::{Raw, Rollup};
+        let at = |s: &str| DateTime::parse_from_rfc3339(s).unwrap().with_timezone(&Utc);
+        let from = at("2025-01-10T10:30:00Z");
+        let to = at("2025-01-10T15:20:00Z");
+
+        // Nothing rolled up: all raw
+        assert_eq!(candle_segments(CandleInterval::Hour, from, to, None), vec![(Raw, from, to)]);
+        // Newest rollup inside the partial first bucket: all raw
+        let early = Some(at("2025-01-10T10:00:00Z"));
+        assert_eq!(candle_segments(CandleInterval::Hour, from, to, early), vec![(Raw, from, to)]);
+
+        // Partial head and the newest rolled-up bucket onwards are raw, whole buckets between
+        // come from the rollups
+        let latest = Some(at("2025-01-10T13:00:00Z"));
+        assert_eq!(
+            candle_segments(CandleInterval::Hour, from, to, latest),
+            vec![
+                (Raw, from, at("2025-01-10T11:00:00Z")),
+                (Rollup, at("2025-01-10T11:00:00Z"), at("2025-01-10T13:00:00Z")),
+                (Raw, at("2025-01-10T13:00:00Z"), to),
+            ]
+        );
+
+        // A bucket-aligned range has no raw head; a rollup past `to` stops at its last bucket
+        let aligned = at("2025-01-10T11:00:00Z");
+        let late = Some(at("2025-01-11T00:00:00Z"));
+        assert_eq!(
+            candle_segments(CandleInterval::Hour, aligned, to, late),
+            vec![(Rollup, aligned, at("2025-01-10T15:00:00Z")), (Raw, at("2025-01-10T15:00:00Z"), to)]
+        );
+        let day_end = at("2025-01-12T00:00:00Z");
+        assert_eq!(
+            candle_segments(CandleInterval::Day, at("2025-01-01T00:00:00Z"), day_end, Some(at("2025-01-20T00:00:00Z"))),
+            vec![(Rollup, at("2025-01-01T00:00:00Z"), day_end)]
+        );
+    }
+}
@@ -0,0 +1,200 @@
+//! Average cost basis and profit/loss of an asset ledger.
+//!
+//! Acquisitions (buys, income, fee rebates) add their quantity at their USD value; disposals
+//! (sells, fees) realize their proceeds minus the average cost of the disposed quantity.
+//! Deposits enter at their market value when received since their original cost is unknown,
+//! and withdrawals leave at average cost without realizing anything.
+
+use chrono::{DateTime, Utc};
+use rust_decimal::Decimal;
+use std::collections::BTreeMap;
+use uuid::Uuid;
+
+/// What a ledger entry does to an asset's position
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum LedgerAction {
+    Acquire,
+    Dispose,
+    TransferIn,
+    TransferOut,
+}
+
+/// One movement of an asset, valued in USD at the time it happened
+#[derive(Debug, Clone, PartialEq)]
+pub struct LedgerEntry {
+    pub at: DateTime<Utc>,
+    pub asset: String,
+    pub action: LedgerAction,
+    pub quantity: Decimal,
+    /// Cost of an acquisition, proceeds of a disposal or market value of a deposit;
+    /// ignored for withdrawals
+    pub value_usd: Decimal,
+    /// `transfers` row of a deposit or withdrawal
+    pub transfer_id: Option<Uuid>,
+}
+
+/// Position and profit/loss of one asset after its ledger
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct AssetCostBasis {
+    pub quantity: Decimal,
+    /// Cost of `quantity`
+    pub cost_basis_usd: Decimal,
+    pub realized_pnl_usd: Decimal,
+    /// Quantity disposed or withdrawn beyond what the ledger acquired; disposed as zero-cost
+    pub unmatched_quantity: Decimal,
+}
+
+impl AssetCostBasis {
+    /// Average cost per unit; zero with nothing held
+    pub fn average_cost(&self) -> Decimal {
+        if self.quantity > Decimal::ZERO {
+            self.cost_basis_usd / self.quantity
+        } else {
+            Decimal::ZERO
+        }
+    }
+
+    /// Remove up to `quantity` at average cost, returning the cost removed
+    fn remove(&mut self, quantity: Decimal) -> Decimal {
+        let matched = quantity.min(self.quantity);
+        self.unmatched_quantity += quantity - matched;
+        let cost = if matched == self.quantity {
+            self.cost_basis_usd
+        } else {
+            self.average_cost() * matched
+        };
+        self.quantity -= matched;
+        self.cost_basis_usd -= cost;
+        cost
+    }
+
+    /// Value of the held quantity at `price` minus its cost
+    pub fn unrealized_pnl(&self, price: Decimal) -> Decimal {
+        self.quantity * price - self.cost_basis_usd
+    }
+}
+
+/// Apply ledger entries, oldest first, into a position per asset
+pub fn average_cost_basis(entries: &[LedgerEntry]) -> BTreeMap<String, AssetCostBasis> {
+    let mut positions: BTreeMap<String, AssetCostBasis> = BTreeMap::new();
+    for entry in entries {
+        if entry.quantity <= Decimal::ZERO {
+            continue;
+        }
+        let position = positions.entry(entry.asset.clone()).or_default();
+        match entry.action {
+            LedgerAction::Acquire | LedgerAction::TransferIn => {
+                position.quantity += entry.quantity;
+                position.cost_basis_usd += entry.value_usd;
+            }
+            LedgerAction::Dispose => {
+                let cost = position.remove(entry.quantity);
+                position.realized_pnl_usd += entry.value_usd - cost;
+            }
+            LedgerAction::TransferOut => {
+                position.remove(entry.quantity);
+            }
+        }
+    }
+    positions
+}
+
+/// Purchases of one asset: how much was bought and what it cost
+#[derive(Debug, Clone, PartialEq)]
+pub struct PurchaseSummary {
+    pub purchases: usize,
+    pub quantity: Decimal,
+    pub invested_usd: Decimal,
+    pub first_at: DateTime<Utc>,
+    pub last_at: DateTime<Utc>,
+}
+
+impl PurchaseSummary {
+    /// Invested amount per unit bought; zero without quantity
+    pub fn average_price(&self) -> Decimal {
+        if self.quantity > Decimal::ZERO {
+            self.invested_usd / self.quantity
+        } else {
+            Decimal::ZERO
+        }
+    }
+}
+
+/// Sum purchases (acquisitions, and deposits at their value when received) per asset.
+/// Unlike the cost basis, nothing is taken off for later disposals.
+pub fn purchase_summaries(purchases: &[LedgerEntry]) -> BTreeMap<String, PurchaseSummary> {
+    let mut summaries: BTreeMap<String, PurchaseSummary> = BTreeMap::new();
+    for entry in purchases.iter().filter(|e| e.quantity > Decimal::ZERO) {
+        let summary = summaries.entry(entry.asset.clone()).or_insert(PurchaseSummary {
+            purchases: 0,
+            quantity: Decimal::ZERO,
+            invested_usd: Decimal::ZERO,
+            first_at: entry.at,
+            last_at: entry.at,
+        });
+        summary.purchases += 1;
+        summary.quantity += entry.quantity;
+        summary.invested_usd += entry.value_usd;
+        summary.first_at = summary.first_at.min(entry.at);
+        summary.last_at = summary.last_at.max(entry.at);
+    }
+    summaries
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use chrono::Duration;
+
+    fn entry(action: LedgerAction, quantity: i64, value_usd: i64, minutes: i64) -> LedgerEntry {
+        LedgerEntry {
+            at: DateTime::<Utc>::UNIX_EPOCH + Duration::minutes(minutes),
+            asset: "BTC".to_string(),
+            action,
+            quantity: Decimal::from(quantity),
+            value_usd: Decimal::from(value_usd),
+            transfer_id: None,
+        }
+    }
+
+    #[test]
+    fn test_average_cost_basis() {
+        let positions = average_cost_basis(&[
+            entry(LedgerAction::Acquire, 1, 100, 0),
+            entry(LedgerAction::Acquire, 1, 300, 1),
+            // Average cost 200: sells 1 for 250
+            entry(LedgerAction::Dispose, 1, 250, 2),
+            // Deposit enters at market value
+            entry(LedgerAction::TransferIn, 2, 600, 3),
+            // Withdrawal leaves at average cost (800 / 3), realizing nothing
+            entry(LedgerAction::TransferOut, 1, 0, 4),
+        ]);
+        let btc = &positions["BTC"];
+        assert_eq!(btc.quantity, Decimal::from(2));
+        assert_eq!(btc.realized_pnl_usd, Decimal::from(50));
+        assert_eq!(btc.cost_basis_usd.round_dp(6), (Decimal::from(1600) / Decimal::from(3)).round_dp(6));
+        assert_eq!(btc.unrealized_pnl(Decimal::from(400)).round_dp(6), (Decimal::from(800) - Decimal::from(1600) / Decimal::from(3)).round_dp(6));
+
+        // Selling more than the ledger acquired: the excess has no cost
+        let positions = average_cost_basis(&[entry(LedgerAction::Acquire, 1, 100, 0), entry(LedgerAction::Dispose, 3, 600, 1)]);
+        let btc = &positions["BTC"];
+        assert_eq!(btc.quantity, Decimal::ZERO);
+        assert_eq!(btc.unmatched_quantity, Decimal::from(2));
+        assert_eq!(btc.realized_pnl_usd, Decimal::from(500));
+    }
+
+    #[test]
+    fn test_purchase_summaries() {
+        let summaries = purchase_summaries(&[
+            entry(LedgerAction::Acquire, 1, 100, 0),
+            entry(LedgerAction::Acquire, 3, 500, 1),
+            entry(LedgerAction::TransferIn, 1, 200, 2),
+        ]);
+        let btc = &summaries["BTC"];
+        assert_eq!(btc.purchases, 3);
+        assert_eq!(btc.quantity, Decimal::from(5));
+        assert_eq!(btc.invested_usd, Decimal::from(800));
+        assert_eq!(btc.average_price(), Decimal::from(160));
+        assert_eq!(btc.last_at - btc.first_at, Duration::minutes(2));
+    }
+}
@@ -0,0 +1,340 @@
+use crate::helpers::balance_normalization::{convert_amount, find_unit, whole_unit, DenominationUnit};
+use chrono::{DateTime, NaiveDate, NaiveDateTime, Utc};
+use rust_decimal::Decimal;
+use serde::{Deserialize, Serialize};
+use std::str::FromStr;
+use thiserror::Error;
+use utoipa::ToSchema;
+
+/// Which CSV columns hold each field; names are matched case-insensitively against the header row
+#[derive(Debug, Clone, Serialize, Deserialize, ToSchema)]
+#[serde(default)]
+pub struct CsvColumnMapping {
+    /// Asset symbol column (default "asset")
+    pub asset: String,
+    /// Quantity column: the balance for balance statements, the amount moved for transactions
+    /// (default "quantity")
+    pub quantity: String,
+    /// Time column for transactions (default "timestamp")
+    pub timestamp: String,
+    /// Optional direction column for transactions (e.g. "deposit"/"withdrawal", "buy"/"sell").
+    /// When unset, the quantity is signed (negative for outflows).
+    pub direction: Option<String>,
+    /// Optional unit of the quantity column (e.g. "sat", "gwei", "lamport"). Quantities are
+    /// converted to whole units of the unit's asset, and every row must be of that asset.
+    pub unit: Option<String>,
+}
+
+impl Default for CsvColumnMapping {
+    fn default() -> Self {
+        Self {
+            asset: "asset".to_string(),
+            quantity: "quantity".to_string(),
+            timestamp: "timestamp".to_string(),
+            direction: None,
+            unit: None,
+        }
+    }
+}
+
+/// Errors found while reading a CSV statement
+#[derive(Debug, Error, PartialEq)]
+pub enum CsvImportError {
+    #[error("Invalid CSV: {0}")]
+    Malformed(String),
+    #[error("Column '{0}' not found in CSV header")]
+    MissingColumn(String),
+    #[error("Unknown unit '{0}'")]
+    UnknownUnit(String),
+    #[error("Row {row}: {message}")]
+    InvalidRow { row: usize, message: String },
+}
+
+/// One balance line of a statement
+#[derive(Debug, Clone, PartialEq)]
+pub struct ImportedBalance {
+    pub asset: String,
+    pub quantity: Decimal,
+}
+
+/// One quantity change of a statement
+#[derive(Debug, Clone, PartialEq)]
+pub struct ImportedTransaction {
+    pub asset: String,
+    /// Signed change: positive for inflows, negative for outflows
+    pub delta: Decimal,
+    pub occurred_at: DateTime<Utc>,
+}
+
+/// Direction values that move funds out of the account
+const OUTFLOW_DIRECTIONS: [&str; 7] = ["withdrawal", "withdraw", "sell", "out", "send", "sent", "fee"];
+/// Direction values that move funds into the account
+const INFLOW_DIRECTIONS: [&str; 8] = ["deposit", "buy", "in", "receive", "received", "reward", "interest", "airdrop"];
+
+/// Parsed CSV: header-resolved column indexes and the data records
+struct CsvTable {
+    headers: Vec<String>,
+    records: Vec<csv::StringRecord>,
+}
+
+impl CsvTable {
+    fn parse(data: &str, delimiter: u8) -> Result<Self, CsvImportError> {
+        let mut reader = csv::ReaderBuilder::new()
+            .delimiter(delimiter)
+            .trim(csv::Trim::All)
+            .flexible(true)
+            .from_reader(data.as_bytes());
+        let headers = reader
+            .headers()
+            .map_err(|e| CsvImportError::Malformed(e.to_string()))?
+            .iter()
+            .map(|h| h.to_lowercase())
+            .collect();
+        let records = reader
+            .records()
+            .collect::<Result<Vec<_>, _>>()
+            .map_err(|e| CsvImportError::Malformed(e.to_string()))?;
+        Ok(Self { headers, records })
+    }
+
+    fn column(&self, name: &str) -> Result<usize, CsvImportError> {
+        self.headers
+            .iter()
+            .position(|h| h.eq_ignore_ascii_case(name.trim()))
+            .ok_or_else(|| CsvImportError::MissingColumn(name.to_string()))
+    }
+
+    /// Records with their 1-based line numbers (the header is line 1), skipping blank lines
+    fn rows(&self) -> impl Iterator<Item = (usize, &csv::StringRecord)> {
+        self.records
+            .iter()
+            .enumerate()
+            .filter(|(_, r)| r.iter().any(|f| !f.is_empty()))
+            .map(|(i, r)| (i + 2, r))
+    }
+}
+
+fn field<'a>(record: &'a csv::StringRecord, index: usize, row: usize, name: &str) -> Result<&'a str, CsvImportError> {
+    record
+        .get(index)
+        .filter(|v| !v.is_empty())
+        .ok_or_else(|| CsvImportError::InvalidRow { row, message: format!("missing {}", name) })
+}
+
+/// Parse a decimal, tolerating thousands separators
+fn parse_quantity(value: &str, row: usize) -> Result<Decimal, CsvImportError> {
+    let cleaned = value.replace(',', "");
+    Decimal::from_str(&cleaned)
+        .or_else(|_| Decimal::from_scientific(&cleaned))
+        .map_err(|_| CsvImportError::InvalidRow { row, message: format!("invalid quantity '{}'", value) })
+}
+
+/// Resolve the mapping's quantity unit
+fn quantity_unit(mapping: &CsvColumnMapping) -> Result<Option<&'static DenominationUnit>, CsvImportError> {
+    mapping
+        .unit
+        .as_deref()
+        .map(|u| find_unit(u).ok_or_else(|| CsvImportError::UnknownUnit(u.to_string())))
+        .transpose()
+}
+
+/// Convert a quantity given in `unit` to whole units of the row's asset
+fn in_whole_units(
+    quantity: Decimal,
+    asset: &str,
+    unit: Option<&DenominationUnit>,
+    row: usize,
+) -> Result<Decimal, CsvImportError> {
+    let Some(unit) = unit else {
+        return Ok(quantity);
+    };
+    if unit.asset != asset {
+        return Err(CsvImportError::InvalidRow {
+            row,
+            message: format!("unit '{}' is for {}, not {}", unit.symbol, unit.asset, asset),
+        });
+    }
+    let whole = whole_unit(unit.asset).unwrap_or(unit);
+    convert_amount(quantity, unit, whole).map_err(|e| CsvImportError::InvalidRow { row, message: e.to_string() })
+}
+
+/// Parse an RFC 3339 time, a UTC "YYYY-MM-DD HH:MM:SS" time, a date, or Unix seconds/milliseconds
+fn parse_timestamp(value: &str, row: usize) -> Result<DateTime<Utc>, CsvImportError> {
+    if let Ok(dt) = DateTime::parse_from_rfc3339(value) {
+        return Ok(dt.with_timezone(&Utc));
+    }
+    for format in ["%Y-%m-%d %H:%M:%S", "%Y-%m-%dT%H:%M:%S", "%Y-%m-%d %H:%M"] {
+        if let Ok(dt) = NaiveDateTime::parse_from_str(value, format) {
+            return Ok(dt.and_utc());
+        }
+    }
+    if let Ok(date) = NaiveDate::parse_from_str(value, "%Y-%m-%d") {
+        return Ok(date.and_hms_opt(0, 0, 0).unwrap_or_default().and_utc());
+    }
+    if let Ok(n) = value.parse::<i64>() {
+        // 13-digit values are milliseconds
+        let parsed = if n.abs() >= 100_000_000_000 {
+            DateTime::from_timestamp_millis(n)
+        } else {
+            DateTime::from_timestamp(n, 0)
+        };
+        if let Some(dt) = parsed {
+            return Ok(dt);
+        }
+    }
+    Err(CsvImportError::InvalidRow { row, message: format!("invalid timestamp '{}'", value) })
+}
+
+/// Parse a balance statement: one row per asset holding.
+///
+/// Rows of the same asset are summed; zero balances are kept so the import can clear an asset.
+pub fn parse_balances(
+    data: &str,
+    delimiter: u8,
+    mapping: &CsvColumnMapping,
+) -> Result<Vec<ImportedBalance>, CsvImportError> {
+    let table = CsvTable::parse(data, delimiter)?;
+    let asset_col = table.column(&mapping.asset)?;
+    let quantity_col = table.column(&mapping.quantity)?;
+    let unit = quantity_unit(mapping)?;
+
+    let mut balances: Vec<ImportedBalance> = Vec::new();
+    for (row, record) in table.rows() {
+        let asset = field(record, asset_col, row, "asset")?.to_uppercase();
+        let quantity = parse_quantity(field(record, quantity_col, row, "quantity")?, row)?;
+        let quantity = in_whole_units(quantity, &asset, unit, row)?;
+        match balances.iter_mut().find(|b| b.asset == asset) {
+            Some(existing) => existing.quantity += quantity,
+            None => balances.push(ImportedBalance { asset, quantity }),
+        }
+    }
+    Ok(balances)
+}
+
+/// Parse a transaction statement: one row per quantity change, returned in time order.
+///
+/// With a direction column, the quantity's sign is taken from the direction (outflows such as
+/// "withdrawal" or "sell" are negative); without one, the quantity must already be signed.
+pub fn parse_transactions(
+    data: &str,
+    delimiter: u8,
+    mapping: &CsvColumnMapping,
+) -> Result<Vec<ImportedTransaction>, CsvImportError> {
+    let table = CsvTable::parse(data, delimiter)?;
+    let asset_col = table.column(&mapping.asset)?;
+    let quantity_col = table.column(&mapping.quantity)?;
+    let timestamp_col = table.column(&mapping.timestamp)?;
+    let direction_col = mapping.direction.as_deref().map(|d| table.column(d)).transpose()?;
+    let unit = quantity_unit(mapping)?;
+
+    let mut transactions = Vec::new();
+    for (row, record) in table.rows() {
+        let asset = field(record, asset_col, row, "asset")?.to_uppercase();
+        let quantity = parse_quantity(field(record, quantity_col, row, "quantity")?, row)?;
+        let quantity = in_whole_units(quantity, &asset, unit, row)?;
+        let occurred_at = parse_timestamp(field(record, timestamp_col, row, "timestamp")?, row)?;
+
+        let delta = match direction_col {
+            None => quantity,
+            Some(col) => {
+                let direction = field(record, col, row, "direction")?.to_lowercase();
+                if OUTFLOW_DIRECTIONS.contains(&direction.as_str()) {
+                    -quantity.abs()
+                } else if INFLOW_DIRECTIONS.contains(&direction.as_str()) {
+                    quantity.abs()
+                } else {
+                    return Err(CsvImportError::InvalidRow {
+                        row,
+                        message: format!("unknown direction '{}'", direction),
+                    });
+                }
+            }
+        };
+
+        transactions.push(ImportedTransaction { asset, delta, occurred_at });
+    }
+
+    // Stable sort keeps the file order of same-time rows
+    transactions.sort_by_key(|t| t.occurred_at);
+    Ok(transactions)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_balances_with_custom_mapping() {
+        let csv = "Coin;Total;Note\nbtc;0.5;cold\nETH;1,200.25;\nbtc;0.25;\n\n";
+        let mapping = CsvColumnMapping {
+            asset: "coin".to_string(),
+            quantity: "Total".to_string(),
+            ..Default::default()
+        };
+
+        let balances = parse_balances(csv, b';', &mapping).unwrap();
+        assert_eq!(balances.len(), 2);
+        assert_eq!(balances[0], ImportedBalance { asset: "BTC".to_string(), quantity: Decimal::new(75, 2) });
+        assert_eq!(balances[1].quantity, Decimal::from_str("1200.25").unwrap());
+
+        assert_eq!(
+            parse_balances("symbol,quantity\nBTC,1\n", b',', &CsvColumnMapping::default()),
+            Err(CsvImportError::MissingColumn("asset".to_string()))
+        );
+    }
+
+    #[test]
+    fn test_parse_transactions_signs_and_orders_rows() {
+        let csv = "time,asset,amount,type\n\
+                   2024-02-01 10:00:00,USDT,100,withdrawal\n\
+                   2024-01-01T00:00:00Z,USDT,500,deposit\n\
+                   1706745600,BTC,0.01,Buy\n";
+        let mapping = CsvColumnMapping {
+            quantity: "amount".to_string(),
+            timestamp: "time".to_string(),
+            direction: Some("type".to_string()),
+            ..Default::default()
+        };
+
+        let txs = parse_transactions(csv, b',', &mapping).unwrap();
+        assert_eq!(txs.len(), 3);
+        assert_eq!(txs[0].delta, Decimal::from(500));
+        assert_eq!(txs[1].asset, "BTC");
+        assert_eq!(txs[1].occurred_at.to_rfc3339(), "2024-02-01T00:00:00+00:00");
+        assert_eq!(txs[2].delta, Decimal::from(-100));
+
+        let bad = "time,asset,amount,type\n2024-01-01,USDT,1,transfer\n";
+        assert_eq!(
+            parse_transactions(bad, b',', &mapping),
+            Err(CsvImportError::InvalidRow { row: 2, message: "unknown direction 'transfer'".to_string() })
+        );
+    }
+
+    #[test]
+    fn test_parse_quantities_in_base_units() {
+        let mapping = CsvColumnMapping {
+            unit: Some("sats".to_string()),
+            ..Default::default()
+        };
+
+        let balances = parse_balances("asset,quantity\nBTC,150000000\nbtc,1\n", b',', &mapping).unwrap();
+        assert_eq!(balances[0].quantity, Decimal::from_str("1.50000001").unwrap());
+
+        let txs = parse_transactions("asset,quantity,timestamp\nBTC,-2500,2024-01-01\n", b',', &mapping).unwrap();
+        assert_eq!(txs[0].delta, Decimal::from_str("-0.000025").unwrap());
+
+        assert_eq!(
+            parse_balances("asset,quantity\nETH,1\n", b',', &mapping),
+            Err(CsvImportError::InvalidRow { row: 2, message: "unit 'sat' is for BTC, not ETH".to_string() })
+        );
+        assert!(matches!(
+            parse_balances("asset,quantity\nBTC,0.5\n", b',', &mapping),
+            Err(CsvImportError::InvalidRow { row: 2, .. })
+        ));
+        let unknown = CsvColumnMapping { unit: Some("finney".to_string()), ..Default::default() };
+        assert_eq!(
+            parse_balances("asset,quantity\nETH,1\n", b',', &unknown),
+            Err(CsvImportError::UnknownUnit("finney".to_string()))
+        );
+    }
+}
@@ -0,0 +1,147 @@
+//! Wrapped and liquid staking token mapping to underlying assets.
+//!
+//! The `derivative_assets` table maps a derivative symbol (WETH, stETH, wstETH, rETH, cbETH,
+//! BTCB, ...) to its underlying asset and an exchange rate in underlying units per token.
+//! Allocation construction uses it to report the underlying quantity next to the raw holding,
+//! so ETH exposure counts wstETH at its staked ETH value.
+//!
+//! Rows with a `rate_contract` read the rate on Ethereum mainnet through the `ethereum` RPC URL
+//! in `evm_chains`, at most once per [`RATE_MAX_AGE_SECS`]; the last value read is stored in
+//! `exchange_rate` and used when a read fails.
+
+use alloy::{primitives::U256, providers::ProviderBuilder, sol};
+use chrono::Utc;
+use rust_decimal::Decimal;
+use sea_orm::{ActiveModelTrait, ColumnTrait, DatabaseConnection, DbErr, EntityTrait, QueryFilter, Set};
+use std::collections::HashMap;
+use std::error::Error;
+use std::str::FromStr;
+
+use crate::entities::derivative_assets;
+use crate::helpers::balance_normalization::normalize_token_balance;
+use crate::helpers::name_resolution::ethereum_rpc_url;
+
+/// wstETH: stETH per wstETH (`stEthPerToken()`)
+pub const RATE_METHOD_STETH_PER_TOKEN: &str = "steth_per_token";
+/// rETH: ETH per rETH (`getExchangeRate()`)
+pub const RATE_METHOD_GET_EXCHANGE_RATE: &str = "get_exchange_rate";
+/// cbETH: ETH per cbETH (`exchangeRate()`)
+pub const RATE_METHOD_EXCHANGE_RATE: &str = "exchange_rate";
+/// Accepted values of `derivative_assets.rate_method`
+pub const RATE_METHODS: &[&str] = &[
+    RATE_METHOD_STETH_PER_TOKEN,
+    RATE_METHOD_GET_EXCHANGE_RATE,
+    RATE_METHOD_EXCHANGE_RATE,
+];
+
+/// Contract rates older than this are read again
+pub const RATE_MAX_AGE_SECS: i64 = 3600;
+
+sol! {
+    #[sol(rpc)]
+    contract RateProvider {
+        function stEthPerToken() external view returns (uint256);
+        function getExchangeRate() external view returns (uint256);
+        function exchangeRate() external view returns (uint256);
+    }
+}
+
+/// Read an 18-decimal exchange rate from a rate contract
+async fn read_rate(rpc_url: &str, contract: &str, method: &str) -> Result<Decimal, Box<dyn Error + Send + Sync>> {
+    let provider = ProviderBuilder::new().connect_http(rpc_url.parse()?);
+    let rate_provider = RateProvider::new(contract.parse()?, provider);
+    let raw: U256 = match method {
+        RATE_METHOD_STETH_PER_TOKEN => rate_provider.stEthPerToken().call().await?,
+        RATE_METHOD_GET_EXCHANGE_RATE => rate_provider.getExchangeRate().call().await?,
+        RATE_METHOD_EXCHANGE_RATE => rate_provider.exchangeRate().call().await?,
+        other => return Err(format!("Unknown rate method {}", other).into()),
+    };
+    Ok(Decimal::from_str(&normalize_token_balance(&raw.to_string(), 18)?)?)
+}
+
+/// Derivative symbols (uppercase) with their underlying asset and exchange rate
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct DerivativeMap {
+    by_symbol: HashMap<String, (String, Decimal)>,
+}
+
+impl DerivativeMap {
+    /// Build a map from (symbol, underlying symbol, exchange rate) rows
+    pub fn new(rows: impl IntoIterator<Item = (String, String, Decimal)>) -> Self {
+        Self {
+            by_symbol: rows
+                .into_iter()
+                .map(|(symbol, underlying, rate)| (symbol.to_uppercase(), (underlying.to_uppercase(), rate)))
+                .collect(),
+        }
+    }
+
+    /// Underlying asset and exchange rate of a holding, matched by its canonical symbol, then by
+    /// the raw holding symbol with and without its chain suffix (e.g. "wstETH-arbitrum")
+    pub fn resolve(&self, canonical: &str, raw: &str) -> Option<(&str, Decimal)> {
+        let bare = raw.rsplit_once('-').map_or(raw, |(symbol, _)| symbol);
+        [canonical, raw, bare]
+            .iter()
+            .find_map(|symbol| self.by_symbol.get(&symbol.to_uppercase()))
+            .filter(|(underlying, _)| !underlying.eq_ignore_ascii_case(canonical))
+            .map(|(underlying, rate)| (underlying.as_str(), *rate))
+    }
+}
+
+/// Load active mappings, refreshing contract rates older than [`RATE_MAX_AGE_SECS`].
+///
+/// A failed rate read is logged and the stored rate is used.
+pub async fn load_derivative_assets(db: &DatabaseConnection) -> Result<DerivativeMap, DbErr> {
+    let rows = derivative_assets::Entity::find()
+        .filter(derivative_assets::Column::IsActive.eq(true))
+        .all(db)
+        .await?;
+
+    let now = Utc::now();
+    let mut rpc_url: Option<String> = None;
+    let mut entries = Vec::with_capacity(rows.len());
+    for row in rows {
+        let mut rate = row.exchange_rate;
+        let stale = row
+            .rate_updated_at
+            .is_none_or(|at| (now - at.with_timezone(&Utc)).num_seconds() >= RATE_MAX_AGE_SECS);
+        if let (Some(contract), Some(method), true) = (&row.rate_contract, &row.rate_method, stale) {
+            if rpc_url.is_none() {
+                rpc_url = Some(ethereum_rpc_url(db).await);
+            }
+            match read_rate(rpc_url.as_deref().unwrap_or_default(), contract, method).await {
+                Ok(read) => {
+                    rate = read;
+                    let mut active: derivative_assets::ActiveModel = row.clone().into();
+                    active.exchange_rate = Set(read);
+                    active.rate_updated_at = Set(Some(now.into()));
+                    active.update(db).await?;
+                }
+                Err(e) => tracing::warn!("Failed to read the {} exchange rate, using {}: {}", row.symbol, rate, e),
+            }
+        }
+        entries.push((row.symbol, row.underlying_symbol, rate));
+    }
+    Ok(DerivativeMap::new(entries))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_resolve_derivatives() {
+        let map = DerivativeMap::new(vec![
+            ("WSTETH".to_string(), "ETH".to_string(), Decimal::new(122, 2)),
+            ("WETH".to_string(), "ETH".to_string(), Decimal::ONE),
+            ("BTCB".to_string(), "btc".to_string(), Decimal::ONE),
+        ]);
+
+        assert_eq!(map.resolve("WSTETH", "wstETH-ethereum"), Some(("ETH", Decimal::new(122, 2))));
+        // Unmapped canonical symbol, matched through the raw holding symbol
+        assert_eq!(map.resolve("wstETH-arbitrum", "wstETH-arbitrum"), Some(("ETH", Decimal::new(122, 2))));
+        assert_eq!(map.resolve("BTCB", "BTCB-bsc"), Some(("BTC", Decimal::ONE)));
+        assert_eq!(map.resolve("ETH", "ETH-ethereum"), None);
+        assert_eq!(map.resolve("USDC", "USDC-base"), None);
+    }
+}
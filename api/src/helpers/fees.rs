@@ -0,0 +1,183 @@
+//! Fee report: fees captured by account sync summed per calendar period, per fee type and
+//! per asset. USD totals count only fees that had a price when paid.
+
+use std::collections::BTreeMap;
+
+use chrono::{Datelike, Duration, NaiveDate, Utc};
+use rust_decimal::Decimal;
+use serde::{Deserialize, Serialize};
+use utoipa::ToSchema;
+
+use crate::entities::fees;
+
+/// Calendar period fees are grouped by
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize, ToSchema)]
+#[serde(rename_all = "lowercase")]
+pub enum FeePeriod {
+    Day,
+    Week,
+    Month,
+    Year,
+}
+
+impl FeePeriod {
+    /// Parse "day", "week", "month" or "year"
+    pub fn parse(value: &str) -> Option<Self> {
+        match value.trim().to_lowercase().as_str() {
+            "day" => Some(Self::Day),
+            "week" => Some(Self::Week),
+            "month" => Some(Self::Month),
+            "year" => Some(Self::Year),
+            _ => None,
+        }
+    }
+
+    /// First day of the period containing `date`; weeks start on Monday
+    pub fn start(self, date: NaiveDate) -> NaiveDate {
+        match self {
+            Self::Day => date,
+            Self::Week => date - Duration::days(date.weekday().num_days_from_monday() as i64),
+            Self::Month => date.with_day(1).unwrap_or(date),
+            Self::Year => NaiveDate::from_ymd_opt(date.year(), 1, 1).unwrap_or(date),
+        }
+    }
+}
+
+/// Fees of one asset over the report
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, ToSchema)]
+pub struct AssetFees {
+    pub asset: String,
+    /// Total paid in the asset
+    pub amount: String,
+    pub value_usd: String,
+    pub fee_count: usize,
+}
+
+/// Fees of one period
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, ToSchema)]
+pub struct PeriodFees {
+    /// First day of the period (YYYY-MM-DD)
+    pub period_start: String,
+    pub total_usd: String,
+    /// USD value per fee type (e.g. {"trading": "12.5", "gas": "3.1"})
+    pub by_type: BTreeMap<String, String>,
+    pub fee_count: usize,
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct FeeSummary {
+    pub total_usd: Decimal,
+    pub by_type: BTreeMap<String, Decimal>,
+    /// Largest USD value first
+    pub by_asset: Vec<AssetFees>,
+    /// Oldest first
+    pub periods: Vec<PeriodFees>,
+    /// Fees without a USD value
+    pub unpriced: usize,
+}
+
+fn usd(value: Decimal) -> String {
+    value.round_dp(2).normalize().to_string()
+}
+
+/// Sum `fees` per `period`, per fee type and per asset
+pub fn summarize_fees(fees: &[fees::Model], period: FeePeriod) -> FeeSummary {
+    let mut by_type: BTreeMap<String, Decimal> = BTreeMap::new();
+    let mut assets: BTreeMap<&str, (Decimal, Decimal, usize)> = BTreeMap::new();
+    let mut periods: BTreeMap<NaiveDate, (BTreeMap<String, Decimal>, usize)> = BTreeMap::new();
+    let mut unpriced = 0;
+    for fee in fees {
+        let value = fee.value_usd.unwrap_or(Decimal::ZERO);
+        if fee.value_usd.is_none() {
+            unpriced += 1;
+        }
+        *by_type.entry(fee.fee_type.clone()).or_default() += value;
+
+        let asset = assets.entry(&fee.asset).or_default();
+        asset.0 += fee.amount;
+        asset.1 += value;
+        asset.2 += 1;
+
+        let start = period.start(fee.occurred_at.with_timezone(&Utc).date_naive());
+        let bucket = periods.entry(start).or_default();
+        *bucket.0.entry(fee.fee_type.clone()).or_default() += value;
+        bucket.1 += 1;
+    }
+
+    let mut by_asset: Vec<(Decimal, AssetFees)> = assets
+        .into_iter()
+        .map(|(asset, (amount, value, count))| {
+            let fees = AssetFees {
+                asset: asset.to_string(),
+                amount: amount.normalize().to_string(),
+                value_usd: usd(value),
+                fee_count: count,
+            };
+            (value, fees)
+        })
+        .collect();
+    by_asset.sort_by_key(|(value, _)| std::cmp::Reverse(*value));
+
+    FeeSummary {
+        total_usd: by_type.values().sum(),
+        by_type,
+        by_asset: by_asset.into_iter().map(|(_, fees)| fees).collect(),
+        periods: periods
+            .into_iter()
+            .map(|(start, (types, count))| PeriodFees {
+                period_start: start.to_string(),
+                total_usd: usd(types.values().sum()),
+                by_type: types.into_iter().map(|(fee_type, value)| (fee_type, usd(value))).collect(),
+                fee_count: count,
+            })
+            .collect(),
+        unpriced,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use chrono::TimeZone;
+    use uuid::Uuid;
+
+    fn fee(fee_type: &str, asset: &str, amount: i64, value_usd: Option<i64>, day: u32) -> fees::Model {
+        let at = Utc.with_ymd_and_hms(2025, 3, day, 12, 0, 0).unwrap();
+        fees::Model {
+            id: Uuid::new_v4(),
+            account_id: Uuid::new_v4(),
+            fee_type: fee_type.to_string(),
+            asset: asset.to_string(),
+            amount: Decimal::from(amount),
+            value_usd: value_usd.map(Decimal::from),
+            reference: format!("{}:{}", fee_type, day),
+            occurred_at: at.into(),
+            created_at: at.into(),
+        }
+    }
+
+    #[test]
+    fn test_summarize_fees() {
+        assert_eq!(FeePeriod::Week.start(NaiveDate::from_ymd_opt(2025, 3, 9).unwrap()).to_string(), "2025-03-03");
+        assert_eq!(FeePeriod::parse("Month"), Some(FeePeriod::Month));
+
+        let fees = [
+            fee("trading", "USDT", 5, Some(5), 3),
+            fee("trading", "USDT", 7, Some(7), 10),
+            fee("gas", "ETH-ethereum", 1, Some(20), 10),
+            fee("withdrawal", "XYZ", 3, None, 11),
+        ];
+        let summary = summarize_fees(&fees, FeePeriod::Week);
+        assert_eq!(summary.total_usd, Decimal::from(32));
+        assert_eq!(summary.by_type["trading"], Decimal::from(12));
+        assert_eq!(summary.unpriced, 1);
+        assert_eq!(summary.by_asset[0].asset, "ETH-ethereum");
+        assert_eq!(summary.by_asset[1].amount, "12");
+
+        assert_eq!(summary.periods.len(), 2);
+        assert_eq!(summary.periods[0].period_start, "2025-03-03");
+        assert_eq!(summary.periods[1].total_usd, "27");
+        assert_eq!(summary.periods[1].fee_count, 3);
+        assert_eq!(summarize_fees(&fees, FeePeriod::Month).periods.len(), 1);
+    }
+}
@@ -0,0 +1,149 @@
+//! Conversion of USD values to a user's base currency.
+//!
+//! Rates come from the `fx_rates` table (units of currency per USD, one row per day). A value
+//! dated on a given day converts at the latest rate on or before that day, or at the earliest
+//! known rate for days before the first one. A currency without any rate falls back to USD,
+//! and responses report the currency they were actually converted to.
+
+use chrono::NaiveDate;
+use rust_decimal::prelude::ToPrimitive;
+use rust_decimal::Decimal;
+use sea_orm::{ColumnTrait, ConnectionTrait, DbErr, EntityTrait, QueryFilter, QueryOrder};
+use serde::{Deserialize, Serialize};
+use std::collections::BTreeMap;
+use utoipa::ToSchema;
+
+use crate::entities::{fx_rates, users};
+
+/// Default base currency; values are stored in USD
+pub const CURRENCY_USD: &str = "USD";
+
+/// Daily rates of one currency against USD
+#[derive(Debug, Clone, PartialEq)]
+pub struct FxRates {
+    currency: String,
+    rates: BTreeMap<NaiveDate, Decimal>,
+}
+
+impl FxRates {
+    /// Identity conversion
+    pub fn usd() -> Self {
+        Self { currency: CURRENCY_USD.to_string(), rates: BTreeMap::new() }
+    }
+
+    pub fn new(currency: &str, rates: BTreeMap<NaiveDate, Decimal>) -> Self {
+        Self { currency: currency.to_string(), rates }
+    }
+
+    /// Currency values are converted to
+    pub fn currency(&self) -> &str {
+        &self.currency
+    }
+
+    /// Units of the currency per USD on `date`, with the date of the rate used
+    pub fn rate_on(&self, date: NaiveDate) -> (Decimal, Option<NaiveDate>) {
+        if self.currency == CURRENCY_USD {
+            return (Decimal::ONE, None);
+        }
+        self.rates
+            .range(..=date)
+            .next_back()
+            .or_else(|| self.rates.iter().next())
+            .map(|(day, rate)| (*rate, Some(*day)))
+            .unwrap_or((Decimal::ONE, None))
+    }
+
+    /// Units of the currency per USD at the latest known rate
+    pub fn latest(&self) -> (Decimal, Option<NaiveDate>) {
+        self.rate_on(NaiveDate::MAX)
+    }
+
+    /// Convert a USD amount at the latest rate
+    pub fn convert(&self, usd: f64) -> f64 {
+        usd * self.latest().0.to_f64().unwrap_or(1.0)
+    }
+
+    /// Convert a USD amount at the rate of `date`
+    pub fn convert_on(&self, usd: Decimal, date: NaiveDate) -> Decimal {
+        usd * self.rate_on(date).0
+    }
+
+    /// Currency block for responses converted at the latest rate
+    pub fn info(&self) -> CurrencyInfo {
+        let (rate, rate_date) = self.latest();
+        CurrencyInfo {
+            currency: self.currency.clone(),
+            fx_rate: rate.normalize().to_string(),
+            fx_rate_date: rate_date.map(|d| d.to_string()),
+        }
+    }
+}
+
+/// Currency of converted values in a response
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize, ToSchema)]
+pub struct CurrencyInfo {
+    /// ISO 4217 code (e.g. "EUR")
+    pub currency: String,
+    /// Units of `currency` per USD used for the conversion
+    pub fx_rate: String,
+    /// Date of the rate; absent for USD
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub fx_rate_date: Option<String>,
+}
+
+/// Whether `code` looks like an ISO 4217 currency code
+pub fn is_currency_code(code: &str) -> bool {
+    code.len() == 3 && code.chars().all(|c| c.is_ascii_uppercase())
+}
+
+/// Rates of `currency`; USD when it has none
+pub async fn load_fx_rates<C: ConnectionTrait>(db: &C, currency: &str) -> Result<FxRates, DbErr> {
+    if currency == CURRENCY_USD {
+        return Ok(FxRates::usd());
+    }
+    let rates: BTreeMap<NaiveDate, Decimal> = fx_rates::Entity::find()
+        .filter(fx_rates::Column::Currency.eq(currency))
+        .order_by_asc(fx_rates::Column::RateDate)
+        .all(db)
+        .await?
+        .into_iter()
+        .map(|row| (row.rate_date, row.rate))
+        .collect();
+
+    if rates.is_empty() {
+        tracing::warn!("No FX rates for {}; values are shown in USD", currency);
+        return Ok(FxRates::usd());
+    }
+    Ok(FxRates::new(currency, rates))
+}
+
+/// Rates of a user's base currency
+pub async fn load_user_fx_rates<C: ConnectionTrait>(db: &C, user: &users::Model) -> Result<FxRates, DbErr> {
+    load_fx_rates(db, &user.base_currency).await
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::str::FromStr;
+
+    #[test]
+    fn test_fx_rates() {
+        let day = |d: u32| NaiveDate::from_ymd_opt(2025, 1, d).unwrap();
+        let eur = FxRates::new(
+            "EUR",
+            BTreeMap::from([(day(10), Decimal::from_str("0.9").unwrap()), (day(13), Decimal::from_str("0.95").unwrap())]),
+        );
+
+        assert_eq!(eur.rate_on(day(12)), (Decimal::from_str("0.9").unwrap(), Some(day(10))));
+        assert_eq!(eur.rate_on(day(1)).1, Some(day(10)));
+        assert_eq!(eur.latest().1, Some(day(13)));
+        assert!((eur.convert(100.0) - 95.0).abs() < 1e-9);
+        assert_eq!(eur.convert_on(Decimal::from(100), day(11)), Decimal::from(90));
+
+        assert_eq!(FxRates::usd().convert(42.0), 42.0);
+        assert_eq!(FxRates::usd().info().fx_rate, "1");
+        assert!(is_currency_code("VND"));
+        assert!(!is_currency_code("usd"));
+    }
+}
@@ -1,3 +1,29 @@
 pub mod asset_identity;
+pub mod attribution;
+pub mod audit;
 pub mod auth;
+pub mod backtest;
 pub mod balance_normalization;
+pub mod candles;
+pub mod cost_basis;
+pub mod csv_import;
+pub mod derivative_assets;
+pub mod fees;
+pub mod fx;
+pub mod name_resolution;
+pub mod nft_valuation;
+pub mod order_drafts;
+pub mod pagination;
+pub mod params;
+pub mod pendle_assets;
+pub mod performance;
+pub mod price_overrides;
+pub mod price_resolution;
+pub mod price_sanity;
+pub mod risk;
+pub mod snapshot_export;
+pub mod tax_lots;
+pub mod tax_report;
+pub mod value_deltas;
+pub mod value_history;
+pub mod webhook;
@@ -0,0 +1,294 @@
+//! Wallet name resolution through ENS and Unstoppable Domains.
+//!
+//! Forward resolution turns a name such as `vitalik.eth` or `brad.crypto` into the address a
+//! wallet account syncs; reverse resolution turns an address into the display name shown with
+//! the account.
+//!
+//! - **ENS** (`.eth`) is read on-chain from the Ethereum mainnet registry through the
+//!   `ethereum` RPC URL in `evm_chains`, and only resolves EVM addresses. A reverse name is only
+//!   returned when it forward-resolves back to the same address.
+//! - **Unstoppable Domains** goes through its Resolution API and needs
+//!   `UNSTOPPABLE_DOMAINS_API_KEY`; the address record used depends on the wallet's chain
+//!   family (e.g. `crypto.BTC.address` for Bitcoin wallets).
+//!
+//! Names are lower-cased before hashing; full ENSIP-15 normalization is not applied.
+
+use alloy::{
+    primitives::{keccak256, Address, B256},
+    providers::ProviderBuilder,
+    sol,
+};
+use reqwest::Client;
+use sea_orm::{ColumnTrait, DatabaseConnection, EntityTrait, QueryFilter};
+use serde::Deserialize;
+use std::error::Error;
+
+use crate::connectors::evm::EvmChain;
+use crate::entities::evm_chains;
+
+sol! {
+    #[sol(rpc)]
+    contract EnsRegistry {
+        function resolver(bytes32 node) public view returns (address);
+    }
+
+    #[sol(rpc)]
+    contract EnsResolver {
+        function addr(bytes32 node) public view returns (address);
+        function name(bytes32 node) public view returns (string);
+    }
+}
+
+/// ENS registry, deployed at the same address on Ethereum mainnet and testnets
+const ENS_REGISTRY: &str = "0x00000000000C2E074eC69A0dFb2997BA6C7d2e1e";
+
+/// Unstoppable Domains Resolution API
+const UD_API_URL: &str = "https://api.unstoppabledomains.com/resolve";
+
+/// Top-level domains served by Unstoppable Domains
+const UD_TLDS: &[&str] = &[
+    "crypto", "nft", "wallet", "x", "bitcoin", "dao", "888", "zil", "blockchain", "polygon",
+    "unstoppable", "klever", "hi", "kresus", "anime", "manga", "binanceus", "go", "pudgy",
+];
+
+/// Naming service a name belongs to
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum NameService {
+    Ens,
+    UnstoppableDomains,
+}
+
+/// Naming service of `input`, or `None` when it is not a resolvable name (e.g. an address)
+pub fn name_service(input: &str) -> Option<NameService> {
+    let name = input.trim().to_lowercase();
+    if name.starts_with("0x") || name.chars().any(char::is_whitespace) {
+        return None;
+    }
+    let (label, tld) = name.rsplit_once('.')?;
+    if label.is_empty() || label.ends_with('.') {
+        return None;
+    }
+    if tld == "eth" {
+        Some(NameService::Ens)
+    } else if UD_TLDS.contains(&tld) {
+        Some(NameService::UnstoppableDomains)
+    } else {
+        None
+    }
+}
+
+/// ENS namehash (EIP-137) of an already normalized name
+pub fn namehash(name: &str) -> B256 {
+    let mut node = B256::ZERO;
+    if name.is_empty() {
+        return node;
+    }
+    for label in name.rsplit('.') {
+        let mut buf = [0u8; 64];
+        buf[..32].copy_from_slice(node.as_slice());
+        buf[32..].copy_from_slice(keccak256(label.as_bytes()).as_slice());
+        node = keccak256(buf);
+    }
+    node
+}
+
+/// Unstoppable Domains record holding the address of a wallet chain family
+fn ud_record_key(chain_family: &str) -> Option<&'static str> {
+    match chain_family {
+        "evm" => Some("crypto.ETH.address"),
+        "solana" => Some("crypto.SOL.address"),
+        "bitcoin" => Some("crypto.BTC.address"),
+        "litecoin" => Some("crypto.LTC.address"),
+        "dogecoin" => Some("crypto.DOGE.address"),
+        "cosmos" => Some("crypto.ATOM.address"),
+        _ => None,
+    }
+}
+
+#[derive(Deserialize)]
+struct UdResponse {
+    meta: Option<UdMeta>,
+    #[serde(default)]
+    records: std::collections::HashMap<String, String>,
+}
+
+#[derive(Deserialize)]
+struct UdMeta {
+    domain: Option<String>,
+}
+
+/// RPC URL of the `ethereum` chain configured in `evm_chains`, falling back to the built-in
+/// default when the chain is missing or the table cannot be read
+pub async fn ethereum_rpc_url(db: &DatabaseConnection) -> String {
+    let configured = evm_chains::Entity::find()
+        .filter(evm_chains::Column::ChainId.eq("ethereum"))
+        .one(db)
+        .await
+        .unwrap_or_else(|e| {
+            tracing::warn!("Failed to load the ethereum chain: {}", e);
+            None
+        })
+        .map(|c| c.rpc_url);
+    configured.unwrap_or_else(|| {
+        EvmChain::defaults()
+            .into_iter()
+            .find(|c| c.name() == "ethereum")
+            .map(|c| c.rpc_url().to_string())
+            .unwrap_or_default()
+    })
+}
+
+/// Resolves wallet names with ENS and Unstoppable Domains
+pub struct NameResolver {
+    client: Client,
+    ens_rpc_url: String,
+    ud_api_key: Option<String>,
+}
+
+impl NameResolver {
+    pub fn new(ens_rpc_url: impl Into<String>, ud_api_key: Option<String>) -> Self {
+        Self {
+            client: Client::new(),
+            ens_rpc_url: ens_rpc_url.into(),
+            ud_api_key,
+        }
+    }
+
+    /// Resolver reading ENS through the `ethereum` chain configured in `evm_chains` (falling back
+    /// to the built-in default RPC) and Unstoppable Domains with `UNSTOPPABLE_DOMAINS_API_KEY`
+    pub async fn from_db(db: &DatabaseConnection) -> Self {
+        let ens_rpc_url = ethereum_rpc_url(db).await;
+        let ud_api_key = std::env::var("UNSTOPPABLE_DOMAINS_API_KEY").ok().filter(|k| !k.is_empty());
+        Self::new(ens_rpc_url, ud_api_key)
+    }
+
+    /// Resolve `name` to the address of a `chain_family` wallet; `Ok(None)` when the name is not
+    /// registered or has no address for that chain family
+    pub async fn resolve(
+        &self,
+        name: &str,
+        chain_family: &str,
+    ) -> Result<Option<String>, Box<dyn Error + Send + Sync>> {
+        let name = name.trim().to_lowercase();
+        match name_service(&name).ok_or_else(|| format!("'{}' is not an ENS or Unstoppable Domains name", name))? {
+            NameService::Ens => {
+                if chain_family != "evm" {
+                    return Err(format!("ENS names can only be used for EVM wallets, not {}", chain_family).into());
+                }
+                Ok(self.ens_address(&name).await?.map(|a| a.to_checksum(None)))
+            }
+            NameService::UnstoppableDomains => {
+                let record = ud_record_key(chain_family)
+                    .ok_or_else(|| format!("Unstoppable Domains names are not supported for {} wallets", chain_family))?;
+                let response = self.ud_get(&format!("domains/{}", name)).await?;
+                Ok(response.and_then(|r| r.records.get(record).cloned()).filter(|a| !a.is_empty()))
+            }
+        }
+    }
+
+    /// Reverse-resolve the primary name of `address` (ENS first, then Unstoppable Domains);
+    /// only EVM addresses have reverse records
+    pub async fn reverse(
+        &self,
+        address: &str,
+        chain_family: &str,
+    ) -> Result<Option<String>, Box<dyn Error + Send + Sync>> {
+        if chain_family != "evm" {
+            return Ok(None);
+        }
+        let address: Address = address.trim().parse()?;
+
+        if let Some(name) = self.ens_name(address).await? {
+            return Ok(Some(name));
+        }
+        if self.ud_api_key.is_none() {
+            return Ok(None);
+        }
+        let response = self.ud_get(&format!("reverse/{}", address.to_string().to_lowercase())).await?;
+        Ok(response.and_then(|r| r.meta).and_then(|m| m.domain).filter(|d| !d.is_empty()))
+    }
+
+    /// Address set for an ENS name, through the name's resolver
+    async fn ens_address(&self, name: &str) -> Result<Option<Address>, Box<dyn Error + Send + Sync>> {
+        let provider = ProviderBuilder::new().connect_http(self.ens_rpc_url.parse()?);
+        let node = namehash(name);
+
+        let resolver = EnsRegistry::new(ENS_REGISTRY.parse()?, provider.clone()).resolver(node).call().await?;
+        if resolver == Address::ZERO {
+            return Ok(None);
+        }
+        let address = EnsResolver::new(resolver, provider).addr(node).call().await?;
+        Ok((address != Address::ZERO).then_some(address))
+    }
+
+    /// Primary ENS name of `address`, verified by resolving it forward again
+    async fn ens_name(&self, address: Address) -> Result<Option<String>, Box<dyn Error + Send + Sync>> {
+        let provider = ProviderBuilder::new().connect_http(self.ens_rpc_url.parse()?);
+        let reverse_name = format!("{}.addr.reverse", hex_lower(address));
+        let node = namehash(&reverse_name);
+
+        let resolver = EnsRegistry::new(ENS_REGISTRY.parse()?, provider.clone()).resolver(node).call().await?;
+        if resolver == Address::ZERO {
+            return Ok(None);
+        }
+        let name = EnsResolver::new(resolver, provider).name(node).call().await?.to_lowercase();
+        if name.is_empty() {
+            return Ok(None);
+        }
+
+        // Anyone can set any reverse name, so it only counts if the name points back here
+        Ok((self.ens_address(&name).await? == Some(address)).then_some(name))
+    }
+
+    /// GET a Resolution API path; `Ok(None)` when the domain or address is unknown
+    async fn ud_get(&self, path: &str) -> Result<Option<UdResponse>, Box<dyn Error + Send + Sync>> {
+        let api_key = self
+            .ud_api_key
+            .as_deref()
+            .ok_or("Unstoppable Domains resolution requires UNSTOPPABLE_DOMAINS_API_KEY")?;
+        let response = self
+            .client
+            .get(format!("{}/{}", UD_API_URL, path))
+            .bearer_auth(api_key)
+            .send()
+            .await?;
+        if response.status() == reqwest::StatusCode::NOT_FOUND {
+            return Ok(None);
+        }
+        Ok(Some(response.error_for_status()?.json().await?))
+    }
+}
+
+/// Lowercase hex of an address without the `0x` prefix, as used in `addr.reverse` names
+fn hex_lower(address: Address) -> String {
+    address.to_string().trim_start_matches("0x").to_lowercase()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_namehash() {
+        assert_eq!(namehash(""), B256::ZERO);
+        assert_eq!(
+            namehash("eth").to_string(),
+            "0x93cdeb708b7545dc668eb9280176169d1c33cfd8ed6f04690a0bcc88a93fc4ae"
+        );
+        assert_eq!(
+            namehash("foo.eth").to_string(),
+            "0xde9b09fd7c5f901e23a3f19fecc54828e9c848539801e86591bd9801b019f84f"
+        );
+    }
+
+    #[test]
+    fn test_name_service() {
+        assert_eq!(name_service("vitalik.eth"), Some(NameService::Ens));
+        assert_eq!(name_service(" Pay.Vitalik.ETH "), Some(NameService::Ens));
+        assert_eq!(name_service("brad.crypto"), Some(NameService::UnstoppableDomains));
+        assert_eq!(name_service("0xd8dA6BF26964aF9D7eEd9e03E53415D37aA96045"), None);
+        assert_eq!(name_service("bc1qar0srrr7xfkvy5l643lydnw9re59gtzzwf5mdq"), None);
+        assert_eq!(name_service("example.com"), None);
+        assert_eq!(name_service(".eth"), None);
+    }
+}
@@ -0,0 +1,139 @@
+//! Floor-price valuation of wallet NFTs, reported as one "NFT" bucket per portfolio.
+//!
+//! Each token is valued at its collection floor times the quantity held, converted to USD
+//! with the latest price of the floor currency. NFTs without a floor (or whose currency has
+//! no price) count towards the bucket's quantity but add no value.
+
+use rust_decimal::Decimal;
+use sea_orm::{ColumnTrait, DatabaseConnection, DbErr, EntityTrait, QueryFilter, QueryOrder};
+use std::collections::{BTreeMap, HashMap};
+use uuid::Uuid;
+
+use crate::entities::{asset_prices, nft_holdings};
+use crate::helpers::asset_identity::{AssetIdentityNormalizer, NormalizationResult};
+
+/// Asset name of the NFT bucket in holdings and allocations
+pub const NFT_BUCKET_ASSET: &str = "NFT";
+/// Holding source of the NFT bucket's per-account details
+pub const NFT_HOLDING_SOURCE: &str = "nft";
+
+/// NFTs of a set of accounts valued at floor
+#[derive(Debug, Clone, PartialEq, Default)]
+pub struct NftBucket {
+    /// Tokens held (ERC-1155 quantities included)
+    pub quantity: Decimal,
+    /// Floor value in USD
+    pub value_usd: Decimal,
+    /// Tokens held that could not be valued
+    pub unvalued_quantity: Decimal,
+    /// Per-account (quantity, value_usd)
+    pub by_account: BTreeMap<Uuid, (Decimal, Decimal)>,
+}
+
+/// Price symbol of a floor currency; wrapped ETH is priced as ETH
+fn price_symbol(currency: &str) -> String {
+    match currency.trim().to_uppercase().as_str() {
+        "WETH" => "ETH".to_string(),
+        other => other.to_string(),
+    }
+}
+
+/// Value NFT rows with USD prices keyed by [`price_symbol`]
+pub fn value_nfts(rows: &[nft_holdings::Model], usd_prices: &HashMap<String, Decimal>) -> NftBucket {
+    let mut bucket = NftBucket::default();
+    for row in rows {
+        let value = match (row.floor_price, row.floor_currency.as_deref()) {
+            (Some(floor), Some(currency)) => usd_prices.get(&price_symbol(currency)).map(|usd| floor * usd * row.quantity),
+            _ => None,
+        };
+
+        bucket.quantity += row.quantity;
+        let account = bucket.by_account.entry(row.account_id).or_default();
+        account.0 += row.quantity;
+        match value {
+            Some(value) => {
+                bucket.value_usd += value;
+                account.1 += value;
+            }
+            None => bucket.unvalued_quantity += row.quantity,
+        }
+    }
+    bucket
+}
+
+/// Floor-value the NFTs held by `account_ids`; `None` when they hold no NFTs
+pub async fn value_account_nfts(db: &DatabaseConnection, account_ids: &[Uuid]) -> Result<Option<NftBucket>, DbErr> {
+    let rows = nft_holdings::Entity::find()
+        .filter(nft_holdings::Column::AccountId.is_in(account_ids.iter().copied()))
+        .all(db)
+        .await?;
+    if rows.is_empty() {
+        return Ok(None);
+    }
+
+    let normalizer = AssetIdentityNormalizer::new(db.clone());
+    let mut usd_prices: HashMap<String, Decimal> = HashMap::new();
+    let currencies: Vec<String> = rows.iter().filter_map(|r| r.floor_currency.as_deref()).map(price_symbol).collect();
+    for symbol in currencies {
+        if usd_prices.contains_key(&symbol) {
+            continue;
+        }
+        let NormalizationResult::Mapped(identity) = normalizer.normalize_from_symbol(&symbol).await else {
+            tracing::warn!("Could not price NFT floor currency '{}'", symbol);
+            continue;
+        };
+        let latest = asset_prices::Entity::find()
+            .filter(asset_prices::Column::AssetId.eq(identity.asset_id))
+            .order_by_desc(asset_prices::Column::Timestamp)
+            .one(db)
+            .await?;
+        if let Some(price) = latest {
+            usd_prices.insert(symbol, price.price_usd);
+        }
+    }
+
+    Ok(Some(value_nfts(&rows, &usd_prices)))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use chrono::Utc;
+
+    fn nft(account_id: Uuid, quantity: i64, floor: Option<Decimal>, currency: Option<&str>) -> nft_holdings::Model {
+        nft_holdings::Model {
+            id: Uuid::new_v4(),
+            account_id,
+            chain: "ethereum".to_string(),
+            contract_address: "0xabc".to_string(),
+            token_id: "1".to_string(),
+            token_standard: None,
+            quantity: Decimal::from(quantity),
+            collection_name: None,
+            name: None,
+            image_url: None,
+            floor_price: floor,
+            floor_currency: currency.map(str::to_string),
+            synced_at: Utc::now().into(),
+        }
+    }
+
+    #[test]
+    fn test_value_nfts_at_floor() {
+        let (a, b) = (Uuid::new_v4(), Uuid::new_v4());
+        let rows = vec![
+            nft(a, 1, Some(Decimal::new(15, 1)), Some("ETH")),
+            nft(a, 2, Some(Decimal::new(1, 1)), Some("WETH")),
+            nft(b, 1, None, None),
+            nft(b, 1, Some(Decimal::from(100)), Some("APE")),
+        ];
+        let prices = HashMap::from([("ETH".to_string(), Decimal::from(2000))]);
+
+        let bucket = value_nfts(&rows, &prices);
+        assert_eq!(bucket.quantity, Decimal::from(5));
+        // 1.5 ETH + 2 × 0.1 WETH at $2000
+        assert_eq!(bucket.value_usd, Decimal::from(3400));
+        assert_eq!(bucket.unvalued_quantity, Decimal::from(2));
+        assert_eq!(bucket.by_account[&b], (Decimal::from(2), Decimal::ZERO));
+    }
+}
@@ -0,0 +1,185 @@
+//! OKX order drafts of a rebalance plan: each trade on an OKX account as the body of an OKX
+//! `POST /api/v5/trade/order` request (spot market order sized in the base currency), and
+//! their CSV export. Drafts are never submitted.
+
+use std::collections::HashSet;
+
+use serde::{Deserialize, Serialize};
+use utoipa::ToSchema;
+use uuid::Uuid;
+
+use crate::domain::{PlannedTrade, TradeSide};
+
+/// Quote currency of the drafted spot instruments when the export does not name one
+pub const DEFAULT_QUOTE_CURRENCY: &str = "USDT";
+
+/// OKX spot order request fields, named as in the OKX API
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, ToSchema)]
+#[serde(rename_all = "camelCase")]
+pub struct OkxOrderRequest {
+    /// Spot instrument, e.g. "BTC-USDT"
+    pub inst_id: String,
+    /// Always "cash" (spot, no margin)
+    pub td_mode: String,
+    /// "buy" or "sell"
+    pub side: String,
+    /// Always "market"
+    pub ord_type: String,
+    /// Size in the base currency
+    pub sz: String,
+    /// Always "base_ccy", so `sz` of market buys is in the base currency too
+    pub tgt_ccy: String,
+    /// Client order id: the plan id and the trade's position in the plan
+    pub cl_ord_id: String,
+}
+
+/// Order draft for one trade of a plan
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, ToSchema)]
+pub struct OrderDraft {
+    pub account_id: Uuid,
+    pub account_name: String,
+    /// Estimated USD amount from the plan
+    pub amount_usd: f64,
+    /// Body of the OKX order request
+    pub order: OkxOrderRequest,
+}
+
+/// Trade of a plan that has no draft, with the reason
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, ToSchema)]
+pub struct SkippedOrder {
+    pub asset: String,
+    pub side: TradeSide,
+    pub quantity: String,
+    pub reason: String,
+}
+
+/// Drafts of the trades on `okx_accounts`, in plan order.
+///
+/// Trades of the quote currency itself are the other side of the drafted orders and get no
+/// draft, nor do trades without an account or on an account that is not on OKX.
+pub fn okx_order_drafts(
+    plan_id: Uuid,
+    trades: &[PlannedTrade],
+    okx_accounts: &HashSet<Uuid>,
+    quote: &str,
+) -> (Vec<OrderDraft>, Vec<SkippedOrder>) {
+    let mut drafts = Vec::new();
+    let mut skipped = Vec::new();
+    for (index, trade) in trades.iter().enumerate() {
+        let skip = |reason: &str| SkippedOrder {
+            asset: trade.asset.clone(),
+            side: trade.side,
+            quantity: trade.quantity.clone(),
+            reason: reason.to_string(),
+        };
+        if trade.asset.eq_ignore_ascii_case(quote) {
+            skipped.push(skip("quote currency; settled by the other orders"));
+            continue;
+        }
+        let (Some(account_id), Some(account_name)) = (trade.account_id, trade.account_name.as_ref()) else {
+            skipped.push(skip("no account to trade on"));
+            continue;
+        };
+        if !okx_accounts.contains(&account_id) {
+            skipped.push(skip("account is not an OKX account"));
+            continue;
+        }
+
+        let side = match trade.side {
+            TradeSide::Buy => "buy",
+            TradeSide::Sell => "sell",
+        };
+        // "rb", 24 hex digits of the plan id and the index stay within the length limit
+        let plan_hex = plan_id.simple().to_string();
+        let cl_ord_id = format!("rb{}{}", &plan_hex[..24], index);
+        drafts.push(OrderDraft {
+            account_id,
+            account_name: account_name.clone(),
+            amount_usd: trade.amount_usd,
+            order: OkxOrderRequest {
+                inst_id: format!("{}-{}", trade.asset.to_uppercase(), quote.to_uppercase()),
+                td_mode: "cash".to_string(),
+                side: side.to_string(),
+                ord_type: "market".to_string(),
+                sz: trade.quantity.clone(),
+                tgt_ccy: "base_ccy".to_string(),
+                cl_ord_id,
+            },
+        });
+    }
+    (drafts, skipped)
+}
+
+/// CSV of order drafts, one row per order with the OKX field names as headers
+pub fn to_csv(drafts: &[OrderDraft]) -> Result<String, csv::Error> {
+    let mut writer = csv::Writer::from_writer(Vec::new());
+    writer.write_record([
+        "account", "instId", "tdMode", "side", "ordType", "sz", "tgtCcy", "clOrdId", "amountUsd",
+    ])?;
+    for draft in drafts {
+        let order = &draft.order;
+        writer.write_record([
+            draft.account_name.as_str(),
+            &order.inst_id,
+            &order.td_mode,
+            &order.side,
+            &order.ord_type,
+            &order.sz,
+            &order.tgt_ccy,
+            &order.cl_ord_id,
+            &draft.amount_usd.to_string(),
+        ])?;
+    }
+    let bytes = writer.into_inner().map_err(|e| e.into_error())?;
+    Ok(String::from_utf8_lossy(&bytes).into_owned())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn trade(asset: &str, side: TradeSide, account_id: Option<Uuid>) -> PlannedTrade {
+        PlannedTrade {
+            asset: asset.to_string(),
+            side,
+            quantity: "0.5".to_string(),
+            amount_usd: 30000.0,
+            price_usd: 60000.0,
+            account_id,
+            account_name: account_id.map(|_| "OKX main".to_string()),
+        }
+    }
+
+    #[test]
+    fn test_okx_order_drafts_and_csv() {
+        let okx = Uuid::new_v4();
+        let wallet = Uuid::new_v4();
+        let trades = [
+            trade("BTC", TradeSide::Sell, Some(okx)),
+            trade("SOL", TradeSide::Sell, Some(wallet)),
+            trade("ETH", TradeSide::Sell, None),
+            trade("USDT", TradeSide::Buy, Some(okx)),
+            trade("USDC", TradeSide::Buy, Some(okx)),
+        ];
+        let plan_id = Uuid::new_v4();
+        let (drafts, skipped) = okx_order_drafts(plan_id, &trades, &HashSet::from([okx]), "USDT");
+
+        assert_eq!(drafts.len(), 2);
+        assert_eq!(drafts[0].order.inst_id, "BTC-USDT");
+        assert_eq!(drafts[0].order.side, "sell");
+        assert_eq!(drafts[1].order.inst_id, "USDC-USDT");
+        assert_ne!(drafts[0].order.cl_ord_id, drafts[1].order.cl_ord_id);
+        assert!(drafts[1].order.cl_ord_id.len() <= 32);
+        let skipped_assets: Vec<_> = skipped.iter().map(|s| s.asset.as_str()).collect();
+        assert_eq!(skipped_assets, vec!["SOL", "ETH", "USDT"]);
+
+        let json = serde_json::to_value(&drafts[0].order).unwrap();
+        assert_eq!(json["instId"], "BTC-USDT");
+        assert_eq!(json["tgtCcy"], "base_ccy");
+
+        let csv = to_csv(&drafts).unwrap();
+        let lines: Vec<&str> = csv.lines().collect();
+        assert_eq!(lines[0], "account,instId,tdMode,side,ordType,sz,tgtCcy,clOrdId,amountUsd");
+        assert!(lines[1].starts_with("OKX main,BTC-USDT,cash,sell,market,0.5,base_ccy,rb"));
+    }
+}
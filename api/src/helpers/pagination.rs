@@ -0,0 +1,188 @@
+//! Keyset (cursor) pagination for time-ordered list endpoints.
+//!
+//! Lists are ordered newest first by `(timestamp, id)` (some can be sorted oldest first), so
+//! the id breaks ties between rows sharing a timestamp and every row has a unique, stable
+//! position. A cursor encodes the last row of a page; the next page starts strictly after
+//! it. Unlike offsets, this stays cheap on large tables and never skips or repeats rows when
+//! new rows are inserted between requests.
+//!
+//! Cursors are opaque to clients: URL-safe base64 of `"<rfc3339 timestamp>|<uuid>"`.
+
+use base64::{engine::general_purpose::URL_SAFE_NO_PAD, Engine as _};
+use chrono::{DateTime, SecondsFormat, Utc};
+use sea_orm::{sea_query::Value, ColumnTrait, Condition};
+use uuid::Uuid;
+
+use crate::handlers::error::ApiError;
+
+/// Page size used when a cursor is given without a limit
+pub const DEFAULT_PAGE_LIMIT: u64 = 100;
+/// Largest accepted page size
+pub const MAX_PAGE_LIMIT: u64 = 1000;
+
+/// Position of the last row of a page
+#[derive(Debug, Clone, PartialEq)]
+pub struct Cursor {
+    pub timestamp: DateTime<Utc>,
+    pub id: Uuid,
+}
+
+impl Cursor {
+    pub fn new(timestamp: DateTime<Utc>, id: Uuid) -> Self {
+        Self { timestamp, id }
+    }
+
+    /// Encode as an opaque string
+    pub fn encode(&self) -> String {
+        let raw = format!(
+            "{}|{}",
+            self.timestamp.to_rfc3339_opts(SecondsFormat::Micros, true),
+            self.id
+        );
+        URL_SAFE_NO_PAD.encode(raw)
+    }
+
+    /// Decode a cursor previously returned by [`Cursor::encode`]
+    pub fn decode(cursor: &str) -> Result<Self, ApiError> {
+        let invalid = || ApiError::BadRequest("Invalid cursor".to_string());
+
+        let bytes = URL_SAFE_NO_PAD.decode(cursor).map_err(|_| invalid())?;
+        let raw = String::from_utf8(bytes).map_err(|_| invalid())?;
+        let (timestamp, id) = raw.split_once('|').ok_or_else(invalid)?;
+
+        Ok(Self {
+            timestamp: DateTime::parse_from_rfc3339(timestamp)
+                .map_err(|_| invalid())?
+                .with_timezone(&Utc),
+            id: Uuid::parse_str(id).map_err(|_| invalid())?,
+        })
+    }
+}
+
+/// Pagination parameters of a list request
+#[derive(Debug, Clone, PartialEq)]
+pub struct PageRequest {
+    /// Rows per page; `None` returns all remaining rows
+    pub limit: Option<u64>,
+    pub after: Option<Cursor>,
+}
+
+impl PageRequest {
+    /// Validate `limit` and `cursor` query parameters.
+    ///
+    /// Pagination is opt-in: without either parameter the whole list is returned.
+    /// A cursor without a limit uses [`DEFAULT_PAGE_LIMIT`].
+    pub fn from_query(limit: Option<u64>, cursor: Option<&str>) -> Result<Self, ApiError> {
+        if limit == Some(0) || limit.is_some_and(|l| l > MAX_PAGE_LIMIT) {
+            return Err(ApiError::BadRequest(format!(
+                "limit must be between 1 and {}",
+                MAX_PAGE_LIMIT
+            )));
+        }
+
+        let after = cursor.map(Cursor::decode).transpose()?;
+        let limit = limit.or(after.as_ref().map(|_| DEFAULT_PAGE_LIMIT));
+        Ok(Self { limit, after })
+    }
+
+    /// Number of rows to fetch: one extra row tells whether another page exists
+    pub fn fetch_limit(&self) -> Option<u64> {
+        self.limit.map(|l| l + 1)
+    }
+}
+
+/// Condition selecting rows strictly after `(timestamp, id)` in newest-first order
+pub fn keyset_before<C, V>(timestamp_col: C, id_col: C, timestamp: V, id: Uuid) -> Condition
+where
+    C: ColumnTrait,
+    V: Into<Value> + Clone,
+{
+    Condition::any()
+        .add(timestamp_col.lt(timestamp.clone()))
+        .add(
+            Condition::all()
+                .add(timestamp_col.eq(timestamp))
+                .add(id_col.lt(id)),
+        )
+}
+
+/// Condition selecting rows strictly after `(timestamp, id)` in oldest-first order
+pub fn keyset_after<C, V>(timestamp_col: C, id_col: C, timestamp: V, id: Uuid) -> Condition
+where
+    C: ColumnTrait,
+    V: Into<Value> + Clone,
+{
+    Condition::any()
+        .add(timestamp_col.gt(timestamp.clone()))
+        .add(
+            Condition::all()
+                .add(timestamp_col.eq(timestamp))
+                .add(id_col.gt(id)),
+        )
+}
+
+/// Trim the extra row fetched by [`PageRequest::fetch_limit`] and build the next cursor.
+///
+/// Returns the page rows and, when more rows exist, the cursor of the last returned row.
+pub fn finish_page<T>(
+    mut rows: Vec<T>,
+    page: &PageRequest,
+    cursor_of: impl Fn(&T) -> Cursor,
+) -> (Vec<T>, Option<String>) {
+    match page.limit {
+        Some(limit) if rows.len() as u64 > limit => {
+            rows.truncate(limit as usize);
+            let next = rows.last().map(|row| cursor_of(row).encode());
+            (rows, next)
+        }
+        _ => (rows, None),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_cursor_round_trip_keeps_microseconds() {
+        let timestamp = DateTime::parse_from_rfc3339("2024-03-10T12:34:56.123456Z")
+            .unwrap()
+            .with_timezone(&Utc);
+        let cursor = Cursor::new(timestamp, Uuid::new_v4());
+
+        let encoded = cursor.encode();
+        assert!(!encoded.contains('|'));
+        assert_eq!(Cursor::decode(&encoded).unwrap(), cursor);
+
+        assert!(Cursor::decode("not-a-cursor").is_err());
+        assert!(Cursor::decode(&URL_SAFE_NO_PAD.encode("2024-03-10|xyz")).is_err());
+    }
+
+    #[test]
+    fn test_page_request_validation() {
+        let all = PageRequest::from_query(None, None).unwrap();
+        assert_eq!(all.fetch_limit(), None);
+
+        let cursor = Cursor::new(Utc::now(), Uuid::new_v4()).encode();
+        let next = PageRequest::from_query(None, Some(&cursor)).unwrap();
+        assert_eq!(next.limit, Some(DEFAULT_PAGE_LIMIT));
+
+        assert!(PageRequest::from_query(Some(0), None).is_err());
+        assert!(PageRequest::from_query(Some(MAX_PAGE_LIMIT + 1), None).is_err());
+    }
+
+    #[test]
+    fn test_finish_page_emits_cursor_only_when_more_rows_exist() {
+        let now = Utc::now();
+        let rows: Vec<(DateTime<Utc>, Uuid)> = (0..3).map(|_| (now, Uuid::new_v4())).collect();
+        let page = PageRequest { limit: Some(2), after: None };
+
+        let (items, next) = finish_page(rows.clone(), &page, |r| Cursor::new(r.0, r.1));
+        assert_eq!(items.len(), 2);
+        assert_eq!(Cursor::decode(&next.unwrap()).unwrap().id, rows[1].1);
+
+        let (items, next) = finish_page(rows[..2].to_vec(), &page, |r| Cursor::new(r.0, r.1));
+        assert_eq!(items.len(), 2);
+        assert!(next.is_none());
+    }
+}
@@ -0,0 +1,11 @@
+//! Parsing of request parameters shared by handlers.
+
+use chrono::NaiveDate;
+
+use crate::handlers::error::ApiError;
+
+/// Parse a YYYY-MM-DD date, naming `field` in the error
+pub fn parse_date(value: &str, field: &str) -> Result<NaiveDate, ApiError> {
+    NaiveDate::parse_from_str(value.trim(), "%Y-%m-%d")
+        .map_err(|e| ApiError::BadRequest(format!("Invalid {} format: {}. Expected YYYY-MM-DD", field, e)))
+}
@@ -0,0 +1,170 @@
+//! Cached Pendle PT/YT token lists and prices.
+//!
+//! The `pendle_assets` table holds the PT and YT tokens the Pendle API lists per chain, with
+//! maturity and USD price. A wallet sync with the `pendle` DeFi protocol refreshes a chain's
+//! rows when they are older than [`PENDLE_MAX_AGE_SECS`] and reads balances of the listed
+//! tokens; allocation construction values the resulting holdings from the same rows.
+
+use chrono::{NaiveDate, Utc};
+use rust_decimal::Decimal;
+use sea_orm::{
+    sea_query::OnConflict, ColumnTrait, DatabaseConnection, DbErr, EntityTrait, QueryFilter, QueryOrder, Set,
+};
+use std::collections::HashMap;
+use std::error::Error;
+use uuid::Uuid;
+
+use crate::connectors::evm::EvmChain;
+use crate::connectors::pendle::{fetch_pendle_assets, pendle_chain_id, PendleAsset};
+use crate::entities::pendle_assets;
+
+/// A chain's token list and prices are fetched again when older than this
+pub const PENDLE_MAX_AGE_SECS: i64 = 3600;
+
+/// `source` recorded for holdings priced from these rows
+pub const PENDLE_PRICE_SOURCE: &str = "pendle";
+
+/// Rows written per insert statement
+const UPSERT_BATCH_SIZE: usize = 500;
+
+/// Price and maturity of a Pendle holding
+#[derive(Debug, Clone, PartialEq)]
+pub struct PendleQuote {
+    pub price_usd: Option<Decimal>,
+    pub maturity: NaiveDate,
+    /// When the price was fetched
+    pub priced_at: String,
+}
+
+/// Quotes keyed by the holding symbol the EVM connector reports ("<symbol>-<chain>")
+fn quotes_by_holding(rows: Vec<pendle_assets::Model>) -> HashMap<String, PendleQuote> {
+    rows.into_iter()
+        .map(|row| {
+            let quote = PendleQuote {
+                price_usd: row.price_usd,
+                maturity: row.expiry.date_naive(),
+                priced_at: row.price_updated_at.to_rfc3339(),
+            };
+            (format!("{}-{}", row.symbol, row.chain), quote)
+        })
+        .collect()
+}
+
+/// Quotes of all cached Pendle tokens, keyed by holding symbol (e.g. "PT-sUSDE-27MAR2025-ethereum")
+pub async fn load_pendle_quotes(db: &DatabaseConnection) -> Result<HashMap<String, PendleQuote>, DbErr> {
+    Ok(quotes_by_holding(pendle_assets::Entity::find().all(db).await?))
+}
+
+/// Fetch a chain's PT/YT list from the Pendle API when the cached rows are older than
+/// [`PENDLE_MAX_AGE_SECS`], and upsert it
+async fn refresh_chain(db: &DatabaseConnection, chain: &str, chain_id: u64) -> Result<(), Box<dyn Error + Send + Sync>> {
+    let latest = pendle_assets::Entity::find()
+        .filter(pendle_assets::Column::Chain.eq(chain))
+        .order_by_desc(pendle_assets::Column::PriceUpdatedAt)
+        .one(db)
+        .await?;
+    let now = Utc::now();
+    if latest.is_some_and(|row| (now - row.price_updated_at.with_timezone(&Utc)).num_seconds() < PENDLE_MAX_AGE_SECS) {
+        return Ok(());
+    }
+
+    let assets = fetch_pendle_assets(chain_id).await?;
+    tracing::info!("Fetched {} Pendle PT/YT tokens on {}", assets.len(), chain);
+    for batch in assets.chunks(UPSERT_BATCH_SIZE) {
+        let rows = batch.iter().map(|asset| pendle_assets::ActiveModel {
+            id: Set(Uuid::new_v4()),
+            chain: Set(chain.to_string()),
+            address: Set(asset.address.clone()),
+            symbol: Set(asset.symbol.clone()),
+            token_type: Set(asset.token_type.clone()),
+            decimals: Set(i32::from(asset.decimals)),
+            expiry: Set(asset.expiry.into()),
+            price_usd: Set(asset.price_usd),
+            price_updated_at: Set(now.into()),
+            created_at: Set(now.into()),
+            updated_at: Set(now.into()),
+        });
+        pendle_assets::Entity::insert_many(rows)
+            .on_conflict(
+                OnConflict::columns([pendle_assets::Column::Chain, pendle_assets::Column::Address])
+                    .update_columns([
+                        pendle_assets::Column::Symbol,
+                        pendle_assets::Column::TokenType,
+                        pendle_assets::Column::Decimals,
+                        pendle_assets::Column::Expiry,
+                        pendle_assets::Column::PriceUsd,
+                        pendle_assets::Column::PriceUpdatedAt,
+                        pendle_assets::Column::UpdatedAt,
+                    ])
+                    .to_owned(),
+            )
+            .exec_without_returning(db)
+            .await?;
+    }
+    Ok(())
+}
+
+/// PT/YT tokens to check on `chain`, refreshing the chain from the Pendle API first when
+/// stale. Chains without Pendle markets have none.
+///
+/// A failed refresh is logged and the cached rows are used; a DB error yields no tokens.
+pub async fn load_pendle_assets(db: &DatabaseConnection, chain: &EvmChain) -> Vec<PendleAsset> {
+    let Some(chain_id) = pendle_chain_id(chain.name()) else {
+        return Vec::new();
+    };
+    if let Err(e) = refresh_chain(db, chain.name(), chain_id).await {
+        tracing::warn!("Failed to refresh Pendle tokens on {}: {}", chain.name(), e);
+    }
+
+    match pendle_assets::Entity::find()
+        .filter(pendle_assets::Column::Chain.eq(chain.name()))
+        .all(db)
+        .await
+    {
+        Ok(rows) => rows
+            .into_iter()
+            .map(|row| PendleAsset {
+                address: row.address,
+                symbol: row.symbol,
+                token_type: row.token_type,
+                decimals: u8::try_from(row.decimals).unwrap_or(18),
+                expiry: row.expiry.with_timezone(&Utc),
+                price_usd: row.price_usd,
+            })
+            .collect(),
+        Err(e) => {
+            tracing::warn!("Failed to load Pendle tokens on {} from DB: {}", chain.name(), e);
+            Vec::new()
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use chrono::TimeZone;
+
+    #[test]
+    fn test_quotes_keyed_by_holding_symbol() {
+        let at = Utc.with_ymd_and_hms(2025, 3, 27, 0, 0, 0).unwrap().fixed_offset();
+        let row = pendle_assets::Model {
+            id: Uuid::new_v4(),
+            chain: "arbitrum".to_string(),
+            address: "0xaa".to_string(),
+            symbol: "PT-weETH-26JUN2025".to_string(),
+            token_type: "PT".to_string(),
+            decimals: 18,
+            expiry: Utc.with_ymd_and_hms(2025, 6, 26, 0, 0, 0).unwrap().fixed_offset(),
+            price_usd: Some(Decimal::new(3120, 0)),
+            price_updated_at: at,
+            created_at: at,
+            updated_at: at,
+        };
+        let quotes = quotes_by_holding(vec![row]);
+
+        let quote = &quotes["PT-weETH-26JUN2025-arbitrum"];
+        assert_eq!(quote.price_usd, Some(Decimal::new(3120, 0)));
+        assert_eq!(quote.maturity.to_string(), "2025-06-26");
+        assert!(!quotes.contains_key("PT-weETH-26JUN2025-ethereum"));
+    }
+}
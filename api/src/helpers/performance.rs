@@ -0,0 +1,476 @@
+//! Portfolio performance from the snapshot series and external cash flows.
+//!
+//! The time-weighted return (TWR) chains the returns of the sub-periods between consecutive
+//! snapshot dates, so deposits and withdrawals don't count as gains or losses. A sub-period's
+//! net flow is assumed to arrive at its start:
+//!
+//! `r = (V_end - V_start - flow) / (V_start + flow)`
+//!
+//! Cash flows are the deposits and withdrawals of the portfolio's accounts (`transfers`),
+//! valued at the asset's price when they occurred. Withdrawal fees are not flows: they leave
+//! the portfolio as a cost, and lower the return.
+//!
+//! The money-weighted return (XIRR) is the annual rate at which the baseline value and the
+//! flows, discounted to the baseline date, equal the end value. Unlike TWR it weighs each
+//! sub-period by the money invested during it, so it reflects the timing of contributions.
+//! Trades move value between assets inside the portfolio and are not flows.
+//!
+//! Drawdowns are measured on the TWR growth index rather than the raw value, so a
+//! withdrawal is not a drawdown.
+
+use chrono::{Datelike, Duration, NaiveDate, NaiveTime};
+use rust_decimal::prelude::ToPrimitive;
+use rust_decimal::Decimal;
+use sea_orm::{ColumnTrait, DatabaseConnection, DbErr, EntityTrait, QueryFilter, QueryOrder};
+use std::collections::BTreeMap;
+use uuid::Uuid;
+
+use crate::connectors::{TRANSFER_DEPOSIT, TRANSFER_WITHDRAWAL};
+use crate::entities::{snapshots, transfers};
+use crate::helpers::asset_identity::{AssetIdentityNormalizer, NormalizationResult};
+use crate::helpers::price_resolution::price_at;
+
+/// Period a return is computed over
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum PerformancePeriod {
+    Week,
+    Month,
+    YearToDate,
+    All,
+}
+
+impl PerformancePeriod {
+    /// Parse "7d", "30d", "ytd" or "all"
+    pub fn parse(value: &str) -> Option<Self> {
+        match value.trim().to_lowercase().as_str() {
+            "7d" => Some(Self::Week),
+            "30d" => Some(Self::Month),
+            "ytd" => Some(Self::YearToDate),
+            "all" => Some(Self::All),
+            _ => None,
+        }
+    }
+
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            Self::Week => "7d",
+            Self::Month => "30d",
+            Self::YearToDate => "ytd",
+            Self::All => "all",
+        }
+    }
+
+    /// Date of the value the period starts from, for a period ending `today`; `None` for all history.
+    /// Year to date starts from the last day of the previous year.
+    pub fn baseline_date(&self, today: NaiveDate) -> Option<NaiveDate> {
+        match self {
+            Self::Week => Some(today - Duration::days(7)),
+            Self::Month => Some(today - Duration::days(30)),
+            Self::YearToDate => NaiveDate::from_ymd_opt(today.year(), 1, 1).map(|jan1| jan1 - Duration::days(1)),
+            Self::All => None,
+        }
+    }
+}
+
+/// Return of the sub-period ending on one snapshot date
+#[derive(Debug, Clone, PartialEq)]
+pub struct SubPeriodReturn {
+    pub date: NaiveDate,
+    pub value_usd: Decimal,
+    /// Deposits minus withdrawals since the previous snapshot date
+    pub net_flow_usd: Decimal,
+    /// Return of the sub-period as a fraction; `None` when nothing was invested at its start
+    pub return_rate: Option<Decimal>,
+    /// Growth of 1 USD invested at the baseline (1 = unchanged)
+    pub growth: Decimal,
+}
+
+/// Sub-period returns of a value series.
+///
+/// `values` are (date, value) sorted by date, the first being the baseline; each flow counts
+/// towards the first snapshot dated on or after it. Flows before the baseline are ignored.
+pub fn time_weighted_returns(values: &[(NaiveDate, Decimal)], flows: &BTreeMap<NaiveDate, Decimal>) -> Vec<SubPeriodReturn> {
+    let mut returns = Vec::new();
+    let mut growth = Decimal::ONE;
+    for window in values.windows(2) {
+        let ((start_date, start_value), (date, value)) = (window[0], window[1]);
+        let net_flow: Decimal = flows
+            .range(start_date.succ_opt().unwrap_or(start_date)..=date)
+            .map(|(_, flow)| *flow)
+            .sum();
+        let invested = start_value + net_flow;
+        let return_rate = (invested > Decimal::ZERO).then(|| (value - start_value - net_flow) / invested);
+        if let Some(rate) = return_rate {
+            growth *= Decimal::ONE + rate;
+        }
+        returns.push(SubPeriodReturn { date, value_usd: value, net_flow_usd: net_flow, return_rate, growth });
+    }
+    returns
+}
+
+/// Values from the baseline of a period on: the latest value on or before `baseline_date`
+/// and everything after it, or the whole series when there is no earlier value
+pub fn period_values(values: &[(NaiveDate, Decimal)], baseline_date: Option<NaiveDate>) -> &[(NaiveDate, Decimal)] {
+    let start = baseline_date
+        .and_then(|baseline| values.iter().rposition(|(date, _)| *date <= baseline))
+        .unwrap_or(0);
+    &values[start..]
+}
+
+/// Drawdowns of a growth index
+#[derive(Debug, Clone, PartialEq)]
+pub struct Drawdown {
+    /// Largest fall from a peak as a fraction (0 or negative)
+    pub max_drawdown: Decimal,
+    /// Peak and trough of the largest fall; `None` when the index never fell
+    pub peak_date: Option<NaiveDate>,
+    pub trough_date: Option<NaiveDate>,
+    /// First date the index was back at the peak after the largest fall
+    pub recovered_date: Option<NaiveDate>,
+    /// Fall from the highest peak so far to the last value as a fraction (0 or negative)
+    pub current_drawdown: Decimal,
+    /// Date of that peak
+    pub current_peak_date: NaiveDate,
+}
+
+/// Drawdowns of `index` ((date, growth) sorted by date); `None` when it is empty
+pub fn drawdown(index: &[(NaiveDate, Decimal)]) -> Option<Drawdown> {
+    let &(first_date, first_value) = index.first()?;
+    let (mut peak_date, mut peak) = (first_date, first_value);
+    let mut result = Drawdown {
+        max_drawdown: Decimal::ZERO,
+        peak_date: None,
+        trough_date: None,
+        recovered_date: None,
+        current_drawdown: Decimal::ZERO,
+        current_peak_date: first_date,
+    };
+    for &(date, value) in index {
+        if value >= peak {
+            // Back at the peak of the largest fall so far
+            if result.recovered_date.is_none() && result.peak_date == Some(peak_date) {
+                result.recovered_date = Some(date);
+            }
+            peak = value;
+            peak_date = date;
+            continue;
+        }
+        if peak > Decimal::ZERO {
+            let fall = value / peak - Decimal::ONE;
+            if fall < result.max_drawdown {
+                result.max_drawdown = fall;
+                result.peak_date = Some(peak_date);
+                result.trough_date = Some(date);
+                result.recovered_date = None;
+            }
+        }
+    }
+    let &(_, last) = index.last()?;
+    if peak > Decimal::ZERO {
+        result.current_drawdown = last / peak - Decimal::ONE;
+    }
+    result.current_peak_date = peak_date;
+    Some(result)
+}
+
+/// Growth index of sub-period returns: 1 at the baseline, then each return's growth
+pub fn growth_index(baseline_date: NaiveDate, returns: &[SubPeriodReturn]) -> Vec<(NaiveDate, Decimal)> {
+    std::iter::once((baseline_date, Decimal::ONE))
+        .chain(returns.iter().map(|r| (r.date, r.growth)))
+        .collect()
+}
+
+/// Performance of one snapshot date, as stored in `portfolio_performance`
+#[derive(Debug, Clone, PartialEq)]
+pub struct DailyPerformance {
+    pub date: NaiveDate,
+    pub value_usd: Decimal,
+    /// Deposits minus withdrawals since the previous date
+    pub net_flow_usd: Decimal,
+    /// Return since the previous date; `None` on the first date or when nothing was invested
+    pub return_rate: Option<Decimal>,
+    /// Growth since the first date minus one
+    pub cumulative_return: Decimal,
+    /// Fall of the growth from its highest value so far (0 or negative)
+    pub drawdown: Decimal,
+}
+
+/// Daily performance of a whole value series: the first date is the baseline, with no return
+pub fn daily_performance(values: &[(NaiveDate, Decimal)], flows: &BTreeMap<NaiveDate, Decimal>) -> Vec<DailyPerformance> {
+    let Some(&(first_date, first_value)) = values.first() else {
+        return Vec::new();
+    };
+    let mut days = vec![DailyPerformance {
+        date: first_date,
+        value_usd: first_value,
+        net_flow_usd: Decimal::ZERO,
+        return_rate: None,
+        cumulative_return: Decimal::ZERO,
+        drawdown: Decimal::ZERO,
+    }];
+    let mut peak = Decimal::ONE;
+    for r in time_weighted_returns(values, flows) {
+        peak = peak.max(r.growth);
+        days.push(DailyPerformance {
+            date: r.date,
+            value_usd: r.value_usd,
+            net_flow_usd: r.net_flow_usd,
+            return_rate: r.return_rate,
+            cumulative_return: r.growth - Decimal::ONE,
+            drawdown: if peak > Decimal::ZERO { r.growth / peak - Decimal::ONE } else { Decimal::ZERO },
+        });
+    }
+    days
+}
+
+/// Annualized money-weighted return (XIRR) of a value series as a fraction.
+///
+/// The baseline value is invested on the first date, each flow (deposit positive) is invested
+/// on its date and the last value is withdrawn on the last date; flows outside
+/// (first, last] are ignored. `None` when there's nothing to solve (no money in or out, or
+/// the series spans a single day) or no rate in (-100%, 10^6%) fits.
+pub fn money_weighted_return(values: &[(NaiveDate, Decimal)], flows: &BTreeMap<NaiveDate, Decimal>) -> Option<f64> {
+    let (&(start, start_value), &(end, end_value)) = (values.first()?, values.last()?);
+    if end <= start {
+        return None;
+    }
+    // Cash flows from the investor's side: money in is negative, money out positive
+    let mut cash_flows = vec![(0.0, -start_value.to_f64()?)];
+    for (date, flow) in flows.range(start.succ_opt()?..=end) {
+        cash_flows.push(((*date - start).num_days() as f64 / 365.0, -flow.to_f64()?));
+    }
+    cash_flows.push(((end - start).num_days() as f64 / 365.0, end_value.to_f64()?));
+    if !cash_flows.iter().any(|(_, cf)| *cf < 0.0) || !cash_flows.iter().any(|(_, cf)| *cf > 0.0) {
+        return None;
+    }
+
+    // Value at the baseline date of all cash flows at `rate`; falls as the rate rises
+    // when money goes in before it comes out, which bisection relies on
+    let npv = |rate: f64| -> f64 { cash_flows.iter().map(|(years, cf)| cf / (1.0 + rate).powf(*years)).sum() };
+    let (mut low, mut high) = (-0.9999, 1.0);
+    while npv(high) > 0.0 {
+        high *= 2.0;
+        if high > 10_000.0 {
+            return None;
+        }
+    }
+    if npv(low) < 0.0 {
+        return None;
+    }
+    for _ in 0..200 {
+        let mid = (low + high) / 2.0;
+        if npv(mid) > 0.0 {
+            low = mid;
+        } else {
+            high = mid;
+        }
+        if high - low < 1e-10 {
+            break;
+        }
+    }
+    Some((low + high) / 2.0)
+}
+
+/// Portfolio value per snapshot date, oldest first, starting at the baseline: the latest
+/// snapshot on or before `baseline_date`, or the first snapshot when there is none (or no
+/// baseline date). The latest snapshot of a date stands for that date.
+pub async fn load_value_series(
+    db: &DatabaseConnection,
+    portfolio_id: Uuid,
+    baseline_date: Option<NaiveDate>,
+) -> Result<Vec<(NaiveDate, Decimal)>, DbErr> {
+    let baseline = match baseline_date {
+        Some(date) => snapshots::Entity::find()
+            .filter(snapshots::Column::PortfolioId.eq(portfolio_id))
+            .filter(snapshots::Column::SnapshotDate.lte(date))
+            .order_by_desc(snapshots::Column::SnapshotDate)
+            .one(db)
+            .await?
+            .map(|s| s.snapshot_date)
+            .unwrap_or(date),
+        None => NaiveDate::MIN,
+    };
+
+    let rows = snapshots::Entity::find()
+        .filter(snapshots::Column::PortfolioId.eq(portfolio_id))
+        .filter(snapshots::Column::SnapshotDate.gte(baseline))
+        .order_by_asc(snapshots::Column::SnapshotDate)
+        .order_by_asc(snapshots::Column::CreatedAt)
+        .all(db)
+        .await?;
+
+    let mut series: Vec<(NaiveDate, Decimal)> = Vec::new();
+    for row in rows {
+        match series.last_mut() {
+            Some(last) if last.0 == row.snapshot_date => last.1 = row.total_value_usd,
+            _ => series.push((row.snapshot_date, row.total_value_usd)),
+        }
+    }
+    Ok(series)
+}
+
+/// External cash flows of a set of accounts, in USD per UTC date
+#[derive(Debug, Clone, Default)]
+pub struct CashFlows {
+    /// Deposits minus withdrawals per date
+    pub by_date: BTreeMap<NaiveDate, Decimal>,
+    /// Transfers left out because their asset had no price at the time
+    pub unpriced: usize,
+    /// `unpriced` per date
+    pub unpriced_by_date: BTreeMap<NaiveDate, usize>,
+}
+
+/// Deposits (positive) and withdrawals (negative) of `account_ids` dated after `after` up to
+/// and including `until`, valued at the latest stored price at their time
+pub async fn load_cash_flows(
+    db: &DatabaseConnection,
+    account_ids: &[Uuid],
+    after: NaiveDate,
+    until: NaiveDate,
+) -> Result<CashFlows, DbErr> {
+    let mut flows = CashFlows::default();
+    if account_ids.is_empty() || after >= until {
+        return Ok(flows);
+    }
+    let start = (after + Duration::days(1)).and_time(NaiveTime::MIN).and_utc();
+    let end = (until + Duration::days(1)).and_time(NaiveTime::MIN).and_utc();
+    let rows = transfers::Entity::find()
+        .filter(transfers::Column::AccountId.is_in(account_ids.to_vec()))
+        .filter(transfers::Column::OccurredAt.gte(start))
+        .filter(transfers::Column::OccurredAt.lt(end))
+        .order_by_asc(transfers::Column::OccurredAt)
+        .all(db)
+        .await?;
+
+    let normalizer = AssetIdentityNormalizer::new(db.clone());
+    for transfer in rows {
+        let sign = match transfer.direction.as_str() {
+            TRANSFER_DEPOSIT => Decimal::ONE,
+            TRANSFER_WITHDRAWAL => -Decimal::ONE,
+            _ => continue,
+        };
+        let occurred_at = transfer.occurred_at.to_utc();
+        let price = match normalizer.normalize_from_symbol(&transfer.asset).await {
+            NormalizationResult::Mapped(identity) => price_at(db, identity.asset_id, occurred_at).await?,
+            NormalizationResult::Unknown { .. } => None,
+        };
+        match price {
+            Some(price) => {
+                *flows.by_date.entry(occurred_at.date_naive()).or_insert(Decimal::ZERO) += sign * transfer.amount * price;
+            }
+            None => {
+                flows.unpriced += 1;
+                *flows.unpriced_by_date.entry(occurred_at.date_naive()).or_insert(0) += 1;
+            }
+        }
+    }
+    Ok(flows)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn date(s: &str) -> NaiveDate {
+        NaiveDate::parse_from_str(s, "%Y-%m-%d").unwrap()
+    }
+
+    #[test]
+    fn test_time_weighted_returns() {
+        let values = vec![
+            (date("2025-01-01"), Decimal::from(1000)),
+            (date("2025-01-02"), Decimal::from(1100)),
+            // 1000 deposited, then the portfolio fell 10%
+            (date("2025-01-04"), Decimal::from(1890)),
+        ];
+        let flows = BTreeMap::from([
+            (date("2025-01-01"), Decimal::from(500)), // at the baseline, ignored
+            (date("2025-01-03"), Decimal::from(1000)),
+        ]);
+        let returns = time_weighted_returns(&values, &flows);
+
+        assert_eq!(returns.len(), 2);
+        assert_eq!(returns[0].return_rate, Some(Decimal::new(1, 1)));
+        assert_eq!(returns[1].net_flow_usd, Decimal::from(1000));
+        assert_eq!(returns[1].return_rate, Some(Decimal::new(-1, 1)));
+        // 1.1 * 0.9: the deposit is not counted as a gain
+        assert_eq!(returns[1].growth, Decimal::new(99, 2));
+
+        // Nothing invested before the first deposit
+        let returns = time_weighted_returns(&[(date("2025-01-01"), Decimal::ZERO), (date("2025-01-02"), Decimal::ZERO)], &BTreeMap::new());
+        assert_eq!(returns[0].return_rate, None);
+        assert_eq!(returns[0].growth, Decimal::ONE);
+
+        assert_eq!(PerformancePeriod::YearToDate.baseline_date(date("2025-03-10")), Some(date("2024-12-31")));
+        assert_eq!(PerformancePeriod::parse("30D"), Some(PerformancePeriod::Month));
+        assert!(PerformancePeriod::parse("1y").is_none());
+    }
+
+    #[test]
+    fn test_drawdown() {
+        let index = vec![
+            (date("2025-01-01"), Decimal::ONE),
+            (date("2025-01-02"), Decimal::new(12, 1)),
+            (date("2025-01-03"), Decimal::new(9, 1)), // -25% from 1.2
+            (date("2025-01-04"), Decimal::new(12, 1)), // recovered
+            (date("2025-01-05"), Decimal::new(15, 1)),
+            (date("2025-01-06"), Decimal::new(135, 2)), // -10% from 1.5
+        ];
+        let dd = drawdown(&index).unwrap();
+        assert_eq!(dd.max_drawdown, Decimal::new(-25, 2));
+        assert_eq!(dd.peak_date, Some(date("2025-01-02")));
+        assert_eq!(dd.trough_date, Some(date("2025-01-03")));
+        assert_eq!(dd.recovered_date, Some(date("2025-01-04")));
+        assert_eq!(dd.current_drawdown, Decimal::new(-1, 1));
+        assert_eq!(dd.current_peak_date, date("2025-01-05"));
+
+        // Only rises
+        let dd = drawdown(&index[..2]).unwrap();
+        assert_eq!(dd.max_drawdown, Decimal::ZERO);
+        assert_eq!(dd.trough_date, None);
+
+        // The period starts at the latest value on or before its baseline date
+        assert_eq!(period_values(&index, Some(date("2025-01-03"))).len(), 4);
+        assert_eq!(period_values(&index, Some(date("2024-12-01"))).len(), 6);
+    }
+
+    #[test]
+    fn test_daily_performance() {
+        let values = vec![
+            (date("2025-01-01"), Decimal::from(1000)),
+            (date("2025-01-02"), Decimal::from(1100)),
+            (date("2025-01-03"), Decimal::from(1440)), // 500 deposited, then -10%
+        ];
+        let flows = BTreeMap::from([(date("2025-01-03"), Decimal::from(500))]);
+        let days = daily_performance(&values, &flows);
+        assert_eq!(days.len(), 3);
+        assert_eq!(days[0].return_rate, None);
+        assert_eq!(days[0].cumulative_return, Decimal::ZERO);
+        assert_eq!(days[1].return_rate, Some(Decimal::new(1, 1)));
+        assert_eq!(days[2].net_flow_usd, Decimal::from(500));
+        assert_eq!(days[2].return_rate, Some(Decimal::new(-1, 1)));
+        // 1.1 * 0.9 = 0.99 of the start, 10% below the 1.1 peak
+        assert_eq!(days[2].cumulative_return, Decimal::new(-1, 2));
+        assert_eq!(days[2].drawdown, Decimal::new(-1, 1));
+        assert!(daily_performance(&[], &flows).is_empty());
+    }
+
+    #[test]
+    fn test_money_weighted_return() {
+        // 1000 grows 10% over a year
+        let values = vec![(date("2025-01-01"), Decimal::from(1000)), (date("2026-01-01"), Decimal::from(1100))];
+        let rate = money_weighted_return(&values, &BTreeMap::new()).unwrap();
+        assert!((rate - 0.1).abs() < 1e-6);
+
+        // Half the money arrives mid-year after the same 10% year-end gain on everything:
+        // the later deposit was invested for less time, so the rate is higher than 10%
+        let values = vec![(date("2025-01-01"), Decimal::from(1000)), (date("2026-01-01"), Decimal::from(2150))];
+        let flows = BTreeMap::from([(date("2025-07-02"), Decimal::from(1000))]);
+        let rate = money_weighted_return(&values, &flows).unwrap();
+        let npv = -1000.0 - 1000.0 / (1.0 + rate).powf(182.0 / 365.0) + 2150.0 / (1.0 + rate);
+        assert!(rate > 0.1 && npv.abs() < 1e-4);
+
+        // Nothing invested
+        assert!(money_weighted_return(&[(date("2025-01-01"), Decimal::ZERO), (date("2025-02-01"), Decimal::ZERO)], &BTreeMap::new()).is_none());
+    }
+}
@@ -0,0 +1,135 @@
+//! Per-asset price source overrides.
+//!
+//! An `asset_price_overrides` row pins the price source allocation construction uses for an
+//! asset, bypassing source reconciliation and live prices. The source is either one that
+//! writes `asset_prices` rows (e.g. "coingecko") or [`PRICE_SOURCE_DEX`], for which the token
+//! is priced on DEX pairs by chain and contract and the price stored as an `asset_prices` row
+//! with source "dex". DEX prices are refreshed with the scheduled coin fetch and when an
+//! override is saved.
+
+use chrono::{DateTime, Timelike, Utc};
+use reqwest::Client;
+use rust_decimal::Decimal;
+use sea_orm::{sea_query::OnConflict, ActiveValue, ColumnTrait, ConnectionTrait, DbErr, EntityTrait, QueryFilter};
+use std::collections::HashMap;
+use std::error::Error;
+use uuid::Uuid;
+
+use crate::connectors::dexscreener::fetch_dex_token_price;
+use crate::entities::{asset_price_overrides, asset_prices};
+use crate::helpers::price_sanity::{quarantine_outliers, PriceSanityConfig};
+
+/// Source of prices read from DEX pairs by contract address
+pub const PRICE_SOURCE_DEX: &str = "dex";
+
+/// Active overrides keyed by asset ID
+pub async fn load_price_overrides<C: ConnectionTrait>(
+    db: &C,
+) -> Result<HashMap<Uuid, asset_price_overrides::Model>, DbErr> {
+    Ok(asset_price_overrides::Entity::find()
+        .filter(asset_price_overrides::Column::IsActive.eq(true))
+        .all(db)
+        .await?
+        .into_iter()
+        .map(|row| (row.asset_id, row))
+        .collect())
+}
+
+/// Minute bucket a DEX price is stored at, so refreshes within a minute overwrite each other
+fn price_timestamp(now: DateTime<Utc>) -> DateTime<Utc> {
+    now.with_second(0).and_then(|t| t.with_nanosecond(0)).unwrap_or(now)
+}
+
+fn dex_price_row(asset_id: Uuid, price_usd: Decimal, now: DateTime<Utc>) -> asset_prices::ActiveModel {
+    asset_prices::ActiveModel {
+        id: ActiveValue::Set(Uuid::new_v4()),
+        asset_id: ActiveValue::Set(asset_id),
+        timestamp: ActiveValue::Set(price_timestamp(now).into()),
+        price_usd: ActiveValue::Set(price_usd),
+        volume_24h_usd: ActiveValue::Set(None),
+        market_cap_usd: ActiveValue::Set(None),
+        change_percent_24h: ActiveValue::Set(None),
+        source: ActiveValue::Set(PRICE_SOURCE_DEX.to_string()),
+        created_at: ActiveValue::Set(now.into()),
+        rank: ActiveValue::Set(None),
+        circulating_supply: ActiveValue::Set(None),
+        total_supply: ActiveValue::Set(None),
+        max_supply: ActiveValue::Set(None),
+        beta_value: ActiveValue::Set(None),
+        percent_change_1h: ActiveValue::Set(None),
+        percent_change_7d: ActiveValue::Set(None),
+        percent_change_30d: ActiveValue::Set(None),
+        ath_price: ActiveValue::Set(None),
+        ath_date: ActiveValue::Set(None),
+        percent_from_price_ath: ActiveValue::Set(None),
+    }
+}
+
+/// Read the DEX price of a "dex" override and store it; `None` when no pair prices the token
+pub async fn refresh_dex_price<C: ConnectionTrait>(
+    db: &C,
+    client: &Client,
+    price_override: &asset_price_overrides::Model,
+) -> Result<Option<Decimal>, Box<dyn Error + Send + Sync>> {
+    let (Some(chain), Some(contract)) = (&price_override.chain, &price_override.contract_address) else {
+        return Err(format!("Override of asset {} has no chain or contract", price_override.asset_id).into());
+    };
+    let Some(price) = fetch_dex_token_price(client, chain, contract).await? else {
+        return Ok(None);
+    };
+
+    let row = dex_price_row(price_override.asset_id, price.price_usd, Utc::now());
+    let Some(row) = quarantine_outliers(db, vec![row], &PriceSanityConfig::from_env()).await?.pop() else {
+        return Err(format!(
+            "DEX price {} of asset {} was quarantined as an outlier",
+            price.price_usd, price_override.asset_id
+        )
+        .into());
+    };
+    asset_prices::Entity::insert(row)
+        .on_conflict(
+            OnConflict::columns([
+                asset_prices::Column::AssetId,
+                asset_prices::Column::Timestamp,
+                asset_prices::Column::Source,
+            ])
+            .update_column(asset_prices::Column::PriceUsd)
+            .to_owned(),
+        )
+        .exec_without_returning(db)
+        .await?;
+    Ok(Some(price.price_usd))
+}
+
+/// Refresh the DEX prices of every active "dex" override; returns the number stored
+pub async fn refresh_dex_prices<C: ConnectionTrait>(db: &C) -> Result<usize, DbErr> {
+    let client = Client::new();
+    let mut stored = 0;
+    for price_override in load_price_overrides(db).await?.into_values() {
+        if price_override.source != PRICE_SOURCE_DEX {
+            continue;
+        }
+        match refresh_dex_price(db, &client, &price_override).await {
+            Ok(Some(_)) => stored += 1,
+            Ok(None) => tracing::warn!("No DEX pair prices asset {}", price_override.asset_id),
+            Err(e) => tracing::warn!("Failed to refresh DEX price of asset {}: {}", price_override.asset_id, e),
+        }
+    }
+    Ok(stored)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_dex_price_row() {
+        let now = DateTime::parse_from_rfc3339("2025-01-10T12:34:56.789Z").unwrap().with_timezone(&Utc);
+        let row = dex_price_row(Uuid::nil(), Decimal::ONE, now);
+        assert_eq!(
+            row.timestamp.unwrap().to_rfc3339(),
+            "2025-01-10T12:34:00+00:00"
+        );
+        assert_eq!(row.source.unwrap(), PRICE_SOURCE_DEX);
+    }
+}
@@ -0,0 +1,296 @@
+//! Reconciliation of an asset's prices across price sources.
+//!
+//! `asset_prices` may hold rows from several sources (CoinPaprika, CoinGecko, ...). The latest
+//! row of the primary source is used while it is fresh; when it is stale or missing, the
+//! freshest row of another source wins. When every source is stale the newest row is used and
+//! the resolution says so, so a days-old price is never picked over fresher data.
+
+use chrono::{DateTime, Duration, Utc};
+use rust_decimal::Decimal;
+use sea_orm::{ColumnTrait, ConnectionTrait, DbErr, EntityTrait, QueryFilter, QueryOrder, QuerySelect};
+use serde::{Deserialize, Serialize};
+use utoipa::ToSchema;
+use uuid::Uuid;
+
+use crate::connectors::price_provider::PRICE_PROVIDER_COINPAPRIKA;
+use crate::entities::asset_prices;
+
+/// The primary source's latest price is fresh
+pub const RESOLUTION_PRIMARY: &str = "primary";
+/// The primary source was stale or missing and a fresh price from another source was used
+pub const RESOLUTION_FALLBACK: &str = "fallback";
+/// Every source is stale; the newest price was used
+pub const RESOLUTION_STALE: &str = "stale";
+/// The asset is pinned to a source by an `asset_price_overrides` row
+pub const RESOLUTION_OVERRIDE: &str = "override";
+
+/// Default age after which a price is stale (prices are collected daily)
+const DEFAULT_MAX_AGE_HOURS: i64 = 36;
+
+/// Which price source is preferred and how old a price may be
+#[derive(Debug, Clone, PartialEq)]
+pub struct PriceResolutionConfig {
+    pub primary_source: String,
+    pub max_age: Duration,
+    /// Share of a portfolio (0-100) valued with stale prices from which its allocation carries
+    /// a staleness warning
+    pub stale_warning_weight: f64,
+}
+
+impl Default for PriceResolutionConfig {
+    fn default() -> Self {
+        Self {
+            primary_source: PRICE_PROVIDER_COINPAPRIKA.to_string(),
+            max_age: Duration::hours(DEFAULT_MAX_AGE_HOURS),
+            stale_warning_weight: 0.0,
+        }
+    }
+}
+
+impl PriceResolutionConfig {
+    /// Read `PRICE_PRIMARY_SOURCE` (default: `PRICE_PROVIDER`, else coinpaprika),
+    /// `PRICE_MAX_AGE_HOURS` (default: 36) and `PRICE_STALE_WARNING_WEIGHT` (default: 0, i.e.
+    /// warn on any stale price)
+    pub fn from_env() -> Self {
+        let primary_source = std::env::var("PRICE_PRIMARY_SOURCE")
+            .or_else(|_| std::env::var("PRICE_PROVIDER"))
+            .map(|source| source.trim().to_lowercase())
+            .unwrap_or_else(|_| PRICE_PROVIDER_COINPAPRIKA.to_string());
+        let max_age_hours = std::env::var("PRICE_MAX_AGE_HOURS")
+            .ok()
+            .and_then(|v| v.parse::<i64>().ok())
+            .filter(|hours| *hours > 0)
+            .unwrap_or(DEFAULT_MAX_AGE_HOURS);
+        let stale_warning_weight = std::env::var("PRICE_STALE_WARNING_WEIGHT")
+            .ok()
+            .and_then(|v| v.parse::<f64>().ok())
+            .filter(|weight| (0.0..=100.0).contains(weight))
+            .unwrap_or(0.0);
+        Self { primary_source, max_age: Duration::hours(max_age_hours), stale_warning_weight }
+    }
+
+    /// Whether a price from `priced_at` is older than `max_age`
+    pub fn is_stale(&self, priced_at: DateTime<Utc>, now: DateTime<Utc>) -> bool {
+        now.signed_duration_since(priced_at) > self.max_age
+    }
+
+    fn is_fresh(&self, price: &asset_prices::Model, now: DateTime<Utc>) -> bool {
+        !self.is_stale(price.timestamp.into(), now)
+    }
+}
+
+/// Which price row won for an asset, and why
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize, ToSchema)]
+pub struct PriceResolution {
+    /// `asset_prices` row used
+    pub price_id: Uuid,
+    /// Source of the row (e.g. "coinpaprika")
+    pub source: String,
+    /// Time of the row
+    pub priced_at: String,
+    /// "primary", "fallback", "stale" or "override"
+    pub resolution: String,
+    /// Why the primary source was not used
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub reason: Option<String>,
+}
+
+/// Pick the price to use from the latest row of each source.
+///
+/// Returns the winning row with its [`PriceResolution`]; `None` without any row.
+pub fn reconcile_prices(
+    latest_by_source: Vec<asset_prices::Model>,
+    config: &PriceResolutionConfig,
+    now: DateTime<Utc>,
+) -> Option<(asset_prices::Model, PriceResolution)> {
+    let primary = latest_by_source.iter().find(|p| p.source == config.primary_source);
+    let (winner, resolution, reason) = match primary {
+        Some(primary) if config.is_fresh(primary, now) => (primary, RESOLUTION_PRIMARY, None),
+        _ => {
+            let reason = match primary {
+                Some(_) => format!("{} price is stale", config.primary_source),
+                None => format!("{} has no price", config.primary_source),
+            };
+            let newest = latest_by_source.iter().max_by_key(|p| p.timestamp)?;
+            let resolution = if config.is_fresh(newest, now) { RESOLUTION_FALLBACK } else { RESOLUTION_STALE };
+            (newest, resolution, Some(reason))
+        }
+    };
+
+    let resolved = PriceResolution {
+        price_id: winner.id,
+        source: winner.source.clone(),
+        priced_at: winner.timestamp.to_rfc3339(),
+        resolution: resolution.to_string(),
+        reason,
+    };
+    Some((winner.clone(), resolved))
+}
+
+/// Latest price of the source an asset is pinned to, ignoring the other sources.
+///
+/// `None` when the pinned source has no price; the reason notes a stale pinned price.
+pub fn pinned_price(
+    latest_by_source: Vec<asset_prices::Model>,
+    source: &str,
+    config: &PriceResolutionConfig,
+    now: DateTime<Utc>,
+) -> Option<(asset_prices::Model, PriceResolution)> {
+    let pinned = latest_by_source.into_iter().find(|p| p.source == source)?;
+    let reason = if config.is_fresh(&pinned, now) {
+        format!("pinned to {}", source)
+    } else {
+        format!("pinned to {}; price is stale", source)
+    };
+    let resolved = PriceResolution {
+        price_id: pinned.id,
+        source: pinned.source.clone(),
+        priced_at: pinned.timestamp.to_rfc3339(),
+        resolution: RESOLUTION_OVERRIDE.to_string(),
+        reason: Some(reason),
+    };
+    Some((pinned, resolved))
+}
+
+/// Latest `asset_prices` row of each source of an asset
+pub async fn latest_prices_by_source<C: ConnectionTrait>(
+    db: &C,
+    asset_id: Uuid,
+) -> Result<Vec<asset_prices::Model>, DbErr> {
+    let sources: Vec<String> = asset_prices::Entity::find()
+        .select_only()
+        .column(asset_prices::Column::Source)
+        .distinct()
+        .filter(asset_prices::Column::AssetId.eq(asset_id))
+        .into_tuple()
+        .all(db)
+        .await?;
+
+    let mut latest = Vec::with_capacity(sources.len());
+    for source in sources {
+        let row = asset_prices::Entity::find()
+            .filter(asset_prices::Column::AssetId.eq(asset_id))
+            .filter(asset_prices::Column::Source.eq(source))
+            .order_by_desc(asset_prices::Column::Timestamp)
+            .one(db)
+            .await?;
+        latest.extend(row);
+    }
+    Ok(latest)
+}
+
+/// Reconciled current price of an asset; `None` when it has no price at all
+pub async fn resolve_latest_price<C: ConnectionTrait>(
+    db: &C,
+    asset_id: Uuid,
+    config: &PriceResolutionConfig,
+) -> Result<Option<(asset_prices::Model, PriceResolution)>, DbErr> {
+    let latest = latest_prices_by_source(db, asset_id).await?;
+    Ok(reconcile_prices(latest, config, Utc::now()))
+}
+
+/// Current price of an asset from the source it is pinned to; `None` when that source has no price
+pub async fn resolve_pinned_price<C: ConnectionTrait>(
+    db: &C,
+    asset_id: Uuid,
+    source: &str,
+    config: &PriceResolutionConfig,
+) -> Result<Option<(asset_prices::Model, PriceResolution)>, DbErr> {
+    let latest = latest_prices_by_source(db, asset_id).await?;
+    Ok(pinned_price(latest, source, config, Utc::now()))
+}
+
+/// Latest stored price of an asset at or before `at`, from any source
+pub async fn price_at<C: ConnectionTrait>(
+    db: &C,
+    asset_id: Uuid,
+    at: DateTime<Utc>,
+) -> Result<Option<Decimal>, DbErr> {
+    let price = asset_prices::Entity::find()
+        .filter(asset_prices::Column::AssetId.eq(asset_id))
+        .filter(asset_prices::Column::Timestamp.lte(at))
+        .order_by_desc(asset_prices::Column::Timestamp)
+        .one(db)
+        .await?;
+    Ok(price.map(|p| p.price_usd))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn price(source: &str, price_usd: i64, hours_ago: i64, now: DateTime<Utc>) -> asset_prices::Model {
+        asset_prices::Model {
+            id: Uuid::new_v4(),
+            asset_id: Uuid::nil(),
+            timestamp: (now - Duration::hours(hours_ago)).into(),
+            price_usd: Decimal::from(price_usd),
+            volume_24h_usd: None,
+            market_cap_usd: None,
+            change_percent_24h: None,
+            source: source.to_string(),
+            created_at: now.into(),
+            rank: None,
+            circulating_supply: None,
+            total_supply: None,
+            max_supply: None,
+            beta_value: None,
+            percent_change_1h: None,
+            percent_change_7d: None,
+            percent_change_30d: None,
+            ath_price: None,
+            ath_date: None,
+            percent_from_price_ath: None,
+        }
+    }
+
+    #[test]
+    fn test_reconcile_prices() {
+        let now = Utc::now();
+        let config = PriceResolutionConfig::default();
+
+        // Fresh primary wins over a fresher secondary
+        let (winner, resolution) =
+            reconcile_prices(vec![price("coingecko", 101, 1, now), price("coinpaprika", 100, 12, now)], &config, now)
+                .unwrap();
+        assert_eq!(winner.price_usd, Decimal::from(100));
+        assert_eq!(resolution.resolution, RESOLUTION_PRIMARY);
+        assert!(resolution.reason.is_none());
+
+        // A 3-day-old primary falls back to the fresh secondary
+        let (winner, resolution) =
+            reconcile_prices(vec![price("coinpaprika", 90, 72, now), price("coingecko", 101, 2, now)], &config, now)
+                .unwrap();
+        assert_eq!(winner.source, "coingecko");
+        assert_eq!(resolution.resolution, RESOLUTION_FALLBACK);
+        assert_eq!(resolution.reason.as_deref(), Some("coinpaprika price is stale"));
+
+        // Only stale prices: the newest one, flagged
+        let (winner, resolution) =
+            reconcile_prices(vec![price("coingecko", 95, 48, now), price("coinpaprika", 90, 72, now)], &config, now)
+                .unwrap();
+        assert_eq!(winner.source, "coingecko");
+        assert_eq!(resolution.resolution, RESOLUTION_STALE);
+
+        // Missing primary
+        let (_, resolution) = reconcile_prices(vec![price("coingecko", 101, 1, now)], &config, now).unwrap();
+        assert_eq!(resolution.reason.as_deref(), Some("coinpaprika has no price"));
+
+        assert!(reconcile_prices(Vec::new(), &config, now).is_none());
+    }
+
+    #[test]
+    fn test_pinned_price() {
+        let now = Utc::now();
+        let config = PriceResolutionConfig::default();
+        let latest = || vec![price("coinpaprika", 100, 1, now), price("dex", 2, 72, now)];
+
+        // The pinned source wins even when stale and another source is fresh
+        let (winner, resolution) = pinned_price(latest(), "dex", &config, now).unwrap();
+        assert_eq!(winner.price_usd, Decimal::from(2));
+        assert_eq!(resolution.resolution, RESOLUTION_OVERRIDE);
+        assert_eq!(resolution.reason.as_deref(), Some("pinned to dex; price is stale"));
+
+        assert!(pinned_price(latest(), "coingecko", &config, now).is_none());
+    }
+}
@@ -0,0 +1,282 @@
+//! Sanity check of incoming prices.
+//!
+//! Before prices are stored, each one is compared with the previous observation of the asset:
+//! the latest `asset_prices` row of the same source, else of any source. A price more than
+//! `PRICE_OUTLIER_MAX_FACTOR` times higher or lower than that (default 10) is a likely source
+//! glitch; it is quarantined in `rejected_prices` for review instead of being stored, so it
+//! never reaches allocations or snapshots. A genuine jump is accepted once a second observation
+//! of the same source confirms the new level (within 10% of the last pending rejection).
+//! `PRICE_OUTLIER_MAX_FACTOR=0` turns the check off.
+
+use chrono::Utc;
+use rust_decimal::Decimal;
+use sea_orm::{
+    prelude::DateTimeWithTimeZone, ActiveValue, ColumnTrait, ConnectionTrait, DbErr, EntityTrait, QueryFilter,
+    QueryOrder, QuerySelect,
+};
+use std::collections::{HashMap, HashSet};
+use uuid::Uuid;
+
+use crate::entities::{asset_prices, rejected_prices};
+
+/// Rejected price awaiting review
+pub const REJECTION_PENDING: &str = "pending";
+/// Rejected price an admin accepted; it was copied into `asset_prices`
+pub const REJECTION_ACCEPTED: &str = "accepted";
+/// Rejected price an admin confirmed as bad data
+pub const REJECTION_DISMISSED: &str = "dismissed";
+
+/// Default largest plausible factor between two consecutive observations
+const DEFAULT_MAX_FACTOR: i64 = 10;
+
+/// Largest factor between a price and the last rejection it confirms
+fn confirm_tolerance() -> Decimal {
+    Decimal::new(11, 1)
+}
+
+/// How far a price may move between two observations
+#[derive(Debug, Clone, PartialEq)]
+pub struct PriceSanityConfig {
+    /// `None` turns the check off
+    pub max_factor: Option<Decimal>,
+}
+
+impl Default for PriceSanityConfig {
+    fn default() -> Self {
+        Self { max_factor: Some(Decimal::from(DEFAULT_MAX_FACTOR)) }
+    }
+}
+
+impl PriceSanityConfig {
+    /// Read `PRICE_OUTLIER_MAX_FACTOR` (default: 10; 0 disables the check)
+    pub fn from_env() -> Self {
+        match std::env::var("PRICE_OUTLIER_MAX_FACTOR").ok().and_then(|v| v.trim().parse::<Decimal>().ok()) {
+            Some(factor) if factor.is_zero() => Self { max_factor: None },
+            Some(factor) if factor > Decimal::ONE => Self { max_factor: Some(factor) },
+            _ => Self::default(),
+        }
+    }
+}
+
+/// How many times higher or lower `price` is than `reference` (>= 1); `None` for
+/// non-positive prices, which are always implausible
+pub fn deviation_factor(reference: Decimal, price: Decimal) -> Option<Decimal> {
+    if reference <= Decimal::ZERO || price <= Decimal::ZERO {
+        return None;
+    }
+    let factor = if price >= reference { price.checked_div(reference)? } else { reference.checked_div(price)? };
+    Some(factor.round_dp(4))
+}
+
+/// Outcome of checking one price
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum PriceCheck {
+    /// Within range of the previous observation, or nothing to compare with
+    Accepted,
+    /// Out of range, but confirms the last rejected price of the same source
+    Confirmed,
+    /// Out of range; `factor` is `None` for a non-positive price
+    Rejected { factor: Option<Decimal> },
+}
+
+/// Check `price` against the previous observation and the last pending rejection
+pub fn check_price(
+    price: Decimal,
+    reference: Option<Decimal>,
+    last_rejected: Option<Decimal>,
+    max_factor: Decimal,
+) -> PriceCheck {
+    let Some(reference) = reference else {
+        return PriceCheck::Accepted;
+    };
+    let factor = deviation_factor(reference, price);
+    if factor.is_some_and(|f| f <= max_factor) {
+        return PriceCheck::Accepted;
+    }
+    let confirms = last_rejected
+        .and_then(|rejected| deviation_factor(rejected, price))
+        .is_some_and(|f| f <= confirm_tolerance());
+    if confirms {
+        PriceCheck::Confirmed
+    } else {
+        PriceCheck::Rejected { factor }
+    }
+}
+
+fn set_value<T: Clone + Into<sea_orm::Value>>(value: &ActiveValue<T>) -> Option<T> {
+    match value {
+        ActiveValue::Set(v) | ActiveValue::Unchanged(v) => Some(v.clone()),
+        ActiveValue::NotSet => None,
+    }
+}
+
+/// Latest stored price and time per `(asset_id, source)` of the given assets
+async fn latest_observations<C: ConnectionTrait>(
+    db: &C,
+    asset_ids: &[Uuid],
+) -> Result<HashMap<(Uuid, String), (Decimal, DateTimeWithTimeZone)>, DbErr> {
+    let rows: Vec<(Uuid, String, Decimal, DateTimeWithTimeZone)> = asset_prices::Entity::find()
+        .select_only()
+        .column(asset_prices::Column::AssetId)
+        .column(asset_prices::Column::Source)
+        .column(asset_prices::Column::PriceUsd)
+        .column(asset_prices::Column::Timestamp)
+        .distinct_on([asset_prices::Column::AssetId, asset_prices::Column::Source])
+        .filter(asset_prices::Column::AssetId.is_in(asset_ids.iter().copied()))
+        .order_by_asc(asset_prices::Column::AssetId)
+        .order_by_asc(asset_prices::Column::Source)
+        .order_by_desc(asset_prices::Column::Timestamp)
+        .into_tuple()
+        .all(db)
+        .await?;
+    Ok(rows.into_iter().map(|(asset, source, price, at)| ((asset, source), (price, at))).collect())
+}
+
+/// Latest pending rejected price per `(asset_id, source)` of the given assets
+async fn latest_rejections<C: ConnectionTrait>(
+    db: &C,
+    asset_ids: &[Uuid],
+) -> Result<HashMap<(Uuid, String), Decimal>, DbErr> {
+    let rows: Vec<(Uuid, String, Decimal)> = rejected_prices::Entity::find()
+        .select_only()
+        .column(rejected_prices::Column::AssetId)
+        .column(rejected_prices::Column::Source)
+        .column(rejected_prices::Column::PriceUsd)
+        .distinct_on([rejected_prices::Column::AssetId, rejected_prices::Column::Source])
+        .filter(rejected_prices::Column::AssetId.is_in(asset_ids.iter().copied()))
+        .filter(rejected_prices::Column::Status.eq(REJECTION_PENDING))
+        .order_by_asc(rejected_prices::Column::AssetId)
+        .order_by_asc(rejected_prices::Column::Source)
+        .order_by_desc(rejected_prices::Column::Timestamp)
+        .into_tuple()
+        .all(db)
+        .await?;
+    Ok(rows.into_iter().map(|(asset, source, price)| ((asset, source), price)).collect())
+}
+
+/// Drop implausible prices from a batch about to be stored and quarantine them in
+/// `rejected_prices`; returns the prices to store
+pub async fn quarantine_outliers<C: ConnectionTrait>(
+    db: &C,
+    rows: Vec<asset_prices::ActiveModel>,
+    config: &PriceSanityConfig,
+) -> Result<Vec<asset_prices::ActiveModel>, DbErr> {
+    let Some(max_factor) = config.max_factor else {
+        return Ok(rows);
+    };
+    if rows.is_empty() {
+        return Ok(rows);
+    }
+
+    let asset_ids: Vec<Uuid> = rows
+        .iter()
+        .filter_map(|row| set_value(&row.asset_id))
+        .collect::<HashSet<_>>()
+        .into_iter()
+        .collect();
+    let observations = latest_observations(db, &asset_ids).await?;
+    let rejections = latest_rejections(db, &asset_ids).await?;
+
+    // Newest observation of any source, for sources an asset has no price from yet
+    let mut newest_by_asset: HashMap<Uuid, (Decimal, DateTimeWithTimeZone)> = HashMap::new();
+    for ((asset_id, _), observation) in &observations {
+        let newest = newest_by_asset.entry(*asset_id).or_insert(*observation);
+        if observation.1 > newest.1 {
+            *newest = *observation;
+        }
+    }
+
+    let mut accepted = Vec::with_capacity(rows.len());
+    let mut rejected = Vec::new();
+    for row in rows {
+        let (Some(asset_id), Some(source), Some(price), Some(timestamp)) = (
+            set_value(&row.asset_id),
+            set_value(&row.source),
+            set_value(&row.price_usd),
+            set_value(&row.timestamp),
+        ) else {
+            accepted.push(row);
+            continue;
+        };
+        let key = (asset_id, source.clone());
+        let reference = observations.get(&key).or_else(|| newest_by_asset.get(&asset_id)).copied();
+
+        match check_price(price, reference.map(|(p, _)| p), rejections.get(&key).copied(), max_factor) {
+            PriceCheck::Accepted => accepted.push(row),
+            PriceCheck::Confirmed => {
+                tracing::info!("Price {} of asset {} from {} confirms a rejected price; accepting", price, asset_id, source);
+                accepted.push(row);
+            }
+            PriceCheck::Rejected { factor } => {
+                let (reference_price, reference_timestamp) = reference.expect("rejections have a reference");
+                tracing::warn!(
+                    "Rejected price {} of asset {} from {}: previous observation {} ({}x)",
+                    price,
+                    asset_id,
+                    source,
+                    reference_price,
+                    factor.map_or("-".to_string(), |f| f.to_string())
+                );
+                rejected.push(rejected_prices::ActiveModel {
+                    id: ActiveValue::Set(Uuid::new_v4()),
+                    asset_id: ActiveValue::Set(asset_id),
+                    source: ActiveValue::Set(source),
+                    timestamp: ActiveValue::Set(timestamp),
+                    price_usd: ActiveValue::Set(price),
+                    reference_price_usd: ActiveValue::Set(reference_price),
+                    reference_timestamp: ActiveValue::Set(reference_timestamp),
+                    deviation_factor: ActiveValue::Set(factor.unwrap_or(Decimal::ZERO)),
+                    status: ActiveValue::Set(REJECTION_PENDING.to_string()),
+                    reviewed_at: ActiveValue::Set(None),
+                    created_at: ActiveValue::Set(Utc::now().into()),
+                });
+            }
+        }
+    }
+
+    if !rejected.is_empty() {
+        rejected_prices::Entity::insert_many(rejected).exec_without_returning(db).await?;
+    }
+    Ok(accepted)
+}
+
+/// [`quarantine_outliers`] for ingestion jobs: if the check itself fails, the batch is
+/// stored unchecked rather than lost
+pub async fn screen_prices<C: ConnectionTrait>(
+    db: &C,
+    rows: Vec<asset_prices::ActiveModel>,
+    config: &PriceSanityConfig,
+) -> Vec<asset_prices::ActiveModel> {
+    match quarantine_outliers(db, rows.clone(), config).await {
+        Ok(accepted) => accepted,
+        Err(e) => {
+            tracing::error!("Failed to check prices for outliers, storing them unchecked: {}", e);
+            rows
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::str::FromStr;
+
+    #[test]
+    fn test_check_price() {
+        let d = |s: &str| Decimal::from_str(s).unwrap();
+        let max = d("10");
+
+        assert_eq!(deviation_factor(d("2"), d("1")), Some(d("2")));
+        assert_eq!(check_price(d("105"), Some(d("100")), None, max), PriceCheck::Accepted);
+        assert_eq!(check_price(d("5"), None, None, max), PriceCheck::Accepted);
+
+        // A 1000x spike is quarantined, in either direction
+        assert_eq!(check_price(d("100000"), Some(d("100")), None, max), PriceCheck::Rejected { factor: Some(d("1000")) });
+        assert_eq!(check_price(d("0.1"), Some(d("100")), None, max), PriceCheck::Rejected { factor: Some(d("1000")) });
+        assert_eq!(check_price(d("0"), Some(d("100")), None, max), PriceCheck::Rejected { factor: None });
+
+        // A second observation at the new level confirms it
+        assert_eq!(check_price(d("2050"), Some(d("100")), Some(d("2000")), max), PriceCheck::Confirmed);
+        assert!(matches!(check_price(d("5000"), Some(d("100")), Some(d("2000")), max), PriceCheck::Rejected { .. }));
+    }
+}
@@ -0,0 +1,225 @@
+//! Risk metrics of a return series: annualized return and volatility, Sharpe and Sortino
+//! ratios, and value at risk.
+//!
+//! Crypto trades every day, so daily returns annualize over 365 periods. The risk-free rate
+//! is annual (`RISK_FREE_RATE`, a fraction, default 0) and is spread evenly over the periods.
+//!
+//! Value at risk (VaR) is the loss, as a fraction of value, not exceeded with the given
+//! confidence over the horizon. Historical VaR reads it off the observed returns over the
+//! horizon (overlapping windows of compounded daily returns); parametric VaR assumes
+//! normally distributed returns and scales the daily mean and volatility to the horizon.
+
+use std::collections::BTreeMap;
+
+use chrono::NaiveDate;
+use rust_decimal::prelude::ToPrimitive;
+use rust_decimal::Decimal;
+
+/// Daily returns per year
+pub const DAYS_PER_YEAR: f64 = 365.0;
+
+/// Annual risk-free rate from `RISK_FREE_RATE` (e.g. "0.04" for 4%), 0 when unset
+pub fn risk_free_rate_from_env() -> f64 {
+    std::env::var("RISK_FREE_RATE")
+        .ok()
+        .and_then(|v| v.trim().parse::<f64>().ok())
+        .filter(|rate| rate.is_finite())
+        .unwrap_or(0.0)
+}
+
+/// Metrics of one return series
+#[derive(Debug, Clone, PartialEq)]
+pub struct RiskMetrics {
+    /// Returns the metrics were computed from
+    pub observations: usize,
+    /// Mean return times periods per year
+    pub annualized_return: f64,
+    /// Standard deviation of returns times the square root of periods per year
+    pub annualized_volatility: f64,
+    /// Excess return over volatility; `None` without volatility
+    pub sharpe_ratio: Option<f64>,
+    /// Excess return over downside deviation; `None` without returns below the risk-free rate
+    pub sortino_ratio: Option<f64>,
+}
+
+/// Metrics of `returns` (fractions per period); `None` with fewer than two returns
+pub fn risk_metrics(returns: &[f64], periods_per_year: f64, risk_free_rate: f64) -> Option<RiskMetrics> {
+    if returns.len() < 2 || periods_per_year <= 0.0 {
+        return None;
+    }
+    let n = returns.len() as f64;
+    let mean = returns.iter().sum::<f64>() / n;
+    // Sample standard deviation
+    let variance = returns.iter().map(|r| (r - mean).powi(2)).sum::<f64>() / (n - 1.0);
+    let volatility = variance.sqrt() * periods_per_year.sqrt();
+
+    let rf_per_period = risk_free_rate / periods_per_year;
+    let downside = (returns.iter().map(|r| (r - rf_per_period).min(0.0).powi(2)).sum::<f64>() / n).sqrt()
+        * periods_per_year.sqrt();
+
+    let annualized_return = mean * periods_per_year;
+    let excess = annualized_return - risk_free_rate;
+    Some(RiskMetrics {
+        observations: returns.len(),
+        annualized_return,
+        annualized_volatility: volatility,
+        sharpe_ratio: (volatility > 0.0).then(|| excess / volatility),
+        sortino_ratio: (downside > 0.0).then(|| excess / downside),
+    })
+}
+
+/// Returns between consecutive prices; a pair with a non-positive first price is skipped
+pub fn simple_returns(prices: &[Decimal]) -> Vec<f64> {
+    prices
+        .windows(2)
+        .filter_map(|pair| {
+            let (previous, current) = (pair[0].to_f64()?, pair[1].to_f64()?);
+            (previous > 0.0).then(|| current / previous - 1.0)
+        })
+        .collect()
+}
+
+/// Returns between consecutive dated prices, keyed by the later date; a pair with a
+/// non-positive first price is skipped
+pub fn dated_returns(prices: &[(NaiveDate, Decimal)]) -> BTreeMap<NaiveDate, f64> {
+    prices
+        .windows(2)
+        .filter_map(|pair| {
+            let (previous, current) = (pair[0].1.to_f64()?, pair[1].1.to_f64()?);
+            (previous > 0.0).then(|| (pair[1].0, current / previous - 1.0))
+        })
+        .collect()
+}
+
+/// Daily returns of a portfolio holding `assets` (weight, daily returns by date) at fixed
+/// weights, on the dates every asset has a return. Weights are scaled to sum to one.
+pub fn weighted_returns(assets: &[(f64, BTreeMap<NaiveDate, f64>)]) -> Vec<f64> {
+    let total: f64 = assets.iter().map(|(w, _)| w).sum();
+    let Some((_, first)) = assets.first() else {
+        return Vec::new();
+    };
+    if total <= 0.0 {
+        return Vec::new();
+    }
+    first
+        .keys()
+        .filter_map(|date| {
+            assets
+                .iter()
+                .map(|(weight, returns)| returns.get(date).map(|r| weight / total * r))
+                .sum::<Option<f64>>()
+        })
+        .collect()
+}
+
+/// Confidence levels VaR is computed at, with their one-sided normal z-scores
+pub const VAR_CONFIDENCE_LEVELS: [(f64, f64); 2] = [(0.95, 1.644_854), (0.99, 2.326_348)];
+
+/// Horizons in days VaR is computed over
+pub const VAR_HORIZONS: [usize; 2] = [1, 7];
+
+/// VaR of one confidence level and horizon, as fractions of value (positive for a loss)
+#[derive(Debug, Clone, PartialEq)]
+pub struct VarEstimate {
+    pub confidence: f64,
+    pub horizon_days: usize,
+    /// `None` with fewer than two returns over the horizon
+    pub historical: Option<f64>,
+    pub parametric: f64,
+}
+
+/// VaR of daily `returns` at `confidence` (with normal z-score `z`) over `horizon_days`;
+/// `None` with fewer than two returns
+pub fn value_at_risk(returns: &[f64], confidence: f64, z: f64, horizon_days: usize) -> Option<VarEstimate> {
+    if returns.len() < 2 || horizon_days == 0 {
+        return None;
+    }
+    let n = returns.len() as f64;
+    let mean = returns.iter().sum::<f64>() / n;
+    let sd = (returns.iter().map(|r| (r - mean).powi(2)).sum::<f64>() / (n - 1.0)).sqrt();
+    let h = horizon_days as f64;
+    let parametric = (z * sd * h.sqrt() - mean * h).max(0.0);
+
+    let mut horizon_returns: Vec<f64> = returns
+        .windows(horizon_days)
+        .map(|window| window.iter().map(|r| 1.0 + r).product::<f64>() - 1.0)
+        .collect();
+    let historical = (horizon_returns.len() >= 2).then(|| {
+        horizon_returns.sort_by(|a, b| a.total_cmp(b));
+        // The worst ceil((1 - confidence) * n) returns are the tail; VaR is the best of them
+        let tail = ((1.0 - confidence) * horizon_returns.len() as f64 - 1e-9).ceil() as usize;
+        let index = tail.saturating_sub(1).min(horizon_returns.len() - 1);
+        (-horizon_returns[index]).max(0.0)
+    });
+
+    Some(VarEstimate { confidence, horizon_days, historical, parametric })
+}
+
+/// Periods per year of returns between `dates` (sorted), from their average spacing;
+/// daily when there are fewer than two dates
+pub fn periods_per_year(dates: &[NaiveDate]) -> f64 {
+    match (dates.first(), dates.last()) {
+        (Some(first), Some(last)) if dates.len() > 1 && last > first => {
+            let average_days = (*last - *first).num_days() as f64 / (dates.len() - 1) as f64;
+            DAYS_PER_YEAR / average_days
+        }
+        _ => DAYS_PER_YEAR,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_risk_metrics() {
+        let returns = [0.02, -0.01, 0.03, -0.02];
+        let metrics = risk_metrics(&returns, 365.0, 0.0).unwrap();
+        assert_eq!(metrics.observations, 4);
+        assert!((metrics.annualized_return - 0.005 * 365.0).abs() < 1e-9);
+        // Squared deviations from the 0.005 mean sum to 0.0017
+        assert!((metrics.annualized_volatility - (0.0017 / 3.0_f64).sqrt() * 365.0_f64.sqrt()).abs() < 1e-9);
+        let sharpe = metrics.sharpe_ratio.unwrap();
+        let sortino = metrics.sortino_ratio.unwrap();
+        // Only the losses count against Sortino, so it is above Sharpe
+        assert!(sortino > sharpe && sharpe > 0.0);
+
+        // No losses: no downside deviation
+        assert_eq!(risk_metrics(&[0.01, 0.02], 365.0, 0.0).unwrap().sortino_ratio, None);
+        assert!(risk_metrics(&[0.01], 365.0, 0.0).is_none());
+
+        let prices = [Decimal::from(100), Decimal::from(110), Decimal::from(99)];
+        let returns = simple_returns(&prices);
+        assert!((returns[0] - 0.1).abs() < 1e-12 && (returns[1] + 0.1).abs() < 1e-12);
+
+        let weekly = [
+            NaiveDate::from_ymd_opt(2025, 1, 1).unwrap(),
+            NaiveDate::from_ymd_opt(2025, 1, 8).unwrap(),
+            NaiveDate::from_ymd_opt(2025, 1, 15).unwrap(),
+        ];
+        assert!((periods_per_year(&weekly) - 365.0 / 7.0).abs() < 1e-9);
+    }
+
+    #[test]
+    fn test_value_at_risk() {
+        let day = |d: u32| NaiveDate::from_ymd_opt(2025, 1, d).unwrap();
+        let btc = dated_returns(&[(day(1), Decimal::from(100)), (day(2), Decimal::from(90)), (day(3), Decimal::from(99))]);
+        assert!((btc[&day(2)] + 0.1).abs() < 1e-12);
+        let usdt = BTreeMap::from([(day(2), 0.0), (day(3), 0.0)]);
+        // Half in each: half the BTC moves
+        let returns = weighted_returns(&[(50.0, btc), (50.0, usdt)]);
+        assert!((returns[0] + 0.05).abs() < 1e-12 && (returns[1] - 0.05).abs() < 1e-12);
+
+        // 20 days: one -10% day, the others +1%
+        let mut returns = vec![0.01; 19];
+        returns.push(-0.10);
+        let var = value_at_risk(&returns, 0.95, 1.644_854, 1).unwrap();
+        // The 5% worst of 20 returns is the -10% day
+        assert!((var.historical.unwrap() - 0.10).abs() < 1e-12);
+        assert!(var.parametric > 0.0);
+        let weekly = value_at_risk(&returns, 0.95, 1.644_854, 7).unwrap();
+        assert!((weekly.historical.unwrap() - (1.0 - 0.9 * 1.01_f64.powi(6))).abs() < 1e-12);
+        assert!(weekly.parametric > var.parametric);
+        assert!(value_at_risk(&[0.01], 0.95, 1.644_854, 1).is_none());
+    }
+}
@@ -0,0 +1,265 @@
+//! Export of a portfolio's snapshots with their holdings flattened: one row per holding of each
+//! snapshot (a snapshot without holdings still has one row), oldest snapshot first, as CSV or
+//! a JSON array. Snapshots are read and written out a page at a time, so the response streams
+//! however long the history is.
+
+use chrono::NaiveDate;
+use futures::Stream;
+use sea_orm::{ColumnTrait, Condition, DatabaseConnection, EntityTrait, QueryFilter, QueryOrder, QuerySelect};
+use serde::{Deserialize, Serialize};
+use utoipa::ToSchema;
+use uuid::Uuid;
+
+use crate::domain::SnapshotHolding;
+use crate::entities::snapshots;
+
+/// Snapshots read per page
+const EXPORT_PAGE_SIZE: u64 = 200;
+
+type BoxError = Box<dyn std::error::Error + Send + Sync>;
+
+/// Output format of the export
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ExportFormat {
+    Json,
+    Csv,
+}
+
+impl ExportFormat {
+    /// Parse "json" or "csv"
+    pub fn parse(value: &str) -> Option<Self> {
+        match value.trim().to_lowercase().as_str() {
+            "json" => Some(Self::Json),
+            "csv" => Some(Self::Csv),
+            _ => None,
+        }
+    }
+}
+
+/// One holding of one snapshot
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, ToSchema)]
+pub struct SnapshotExportRow {
+    pub snapshot_id: Uuid,
+    pub snapshot_date: String, // YYYY-MM-DD
+    pub snapshot_type: String,
+    pub created_at: String, // RFC 3339
+    pub total_value_usd: String,
+    /// Holding columns are empty for a snapshot without holdings
+    pub asset: Option<String>,
+    pub quantity: Option<String>,
+    pub price_usd: Option<f64>,
+    pub value_usd: Option<f64>,
+    /// Weight in the snapshot (0-100)
+    pub weight: Option<f64>,
+    pub unpriced: Option<bool>,
+    pub borrowed_quantity: Option<String>,
+}
+
+/// Flattened rows of a snapshot, in the order its holdings were stored
+pub fn export_rows(snapshot: &snapshots::Model) -> Vec<SnapshotExportRow> {
+    let row = |holding: Option<SnapshotHolding>| SnapshotExportRow {
+        snapshot_id: snapshot.id,
+        snapshot_date: snapshot.snapshot_date.to_string(),
+        snapshot_type: snapshot.snapshot_type.clone(),
+        created_at: snapshot.created_at.to_rfc3339(),
+        total_value_usd: snapshot.total_value_usd.to_string(),
+        price_usd: holding.as_ref().and_then(|h| h.price_usd),
+        value_usd: holding.as_ref().map(|h| h.value_usd),
+        weight: holding.as_ref().map(|h| h.weight),
+        unpriced: holding.as_ref().map(|h| h.unpriced),
+        quantity: holding.as_ref().map(|h| h.quantity.clone()),
+        borrowed_quantity: holding.as_ref().and_then(|h| h.borrowed_quantity.clone()),
+        asset: holding.map(|h| h.asset),
+    };
+    let holdings: Vec<SnapshotHolding> = serde_json::from_value(snapshot.holdings.clone()).unwrap_or_default();
+    if holdings.is_empty() {
+        return vec![row(None)];
+    }
+    holdings.into_iter().map(|h| row(Some(h))).collect()
+}
+
+/// CSV of `rows`, starting with the header line when `header` is set
+pub fn to_csv(rows: &[SnapshotExportRow], header: bool) -> Result<String, csv::Error> {
+    let mut writer = csv::WriterBuilder::new().has_headers(header).from_writer(Vec::new());
+    for row in rows {
+        writer.serialize(row)?;
+    }
+    if header && rows.is_empty() {
+        // serialize() writes the header with the first row; an empty export still gets one
+        writer.write_record([
+            "snapshot_id", "snapshot_date", "snapshot_type", "created_at", "total_value_usd", "asset", "quantity",
+            "price_usd", "value_usd", "weight", "unpriced", "borrowed_quantity",
+        ])?;
+    }
+    let bytes = writer.into_inner().map_err(|e| e.into_error())?;
+    Ok(String::from_utf8_lossy(&bytes).into_owned())
+}
+
+/// Position of the last snapshot written
+type ExportPosition = (NaiveDate, sea_orm::prelude::DateTimeWithTimeZone, Uuid);
+
+struct ExportState {
+    db: DatabaseConnection,
+    portfolio_id: Uuid,
+    from: Option<NaiveDate>,
+    to: Option<NaiveDate>,
+    format: ExportFormat,
+    after: Option<ExportPosition>,
+    started: bool,
+    wrote_rows: bool,
+    finished: bool,
+}
+
+/// Next page of snapshots after `state.after`, ordered by date, creation time and id
+async fn next_page(state: &ExportState) -> Result<Vec<snapshots::Model>, sea_orm::DbErr> {
+    let mut query = snapshots::Entity::find().filter(snapshots::Column::PortfolioId.eq(state.portfolio_id));
+    if let Some(from) = state.from {
+        query = query.filter(snapshots::Column::SnapshotDate.gte(from));
+    }
+    if let Some(to) = state.to {
+        query = query.filter(snapshots::Column::SnapshotDate.lte(to));
+    }
+    if let Some((date, created_at, id)) = state.after {
+        query = query.filter(
+            Condition::any().add(snapshots::Column::SnapshotDate.gt(date)).add(
+                Condition::all().add(snapshots::Column::SnapshotDate.eq(date)).add(
+                    Condition::any()
+                        .add(snapshots::Column::CreatedAt.gt(created_at))
+                        .add(
+                            Condition::all()
+                                .add(snapshots::Column::CreatedAt.eq(created_at))
+                                .add(snapshots::Column::Id.gt(id)),
+                        ),
+                ),
+            ),
+        );
+    }
+    query
+        .order_by_asc(snapshots::Column::SnapshotDate)
+        .order_by_asc(snapshots::Column::CreatedAt)
+        .order_by_asc(snapshots::Column::Id)
+        .limit(EXPORT_PAGE_SIZE)
+        .all(&state.db)
+        .await
+}
+
+/// Stream the export of a portfolio's snapshots dated `from` to `to` (inclusive, both optional)
+/// as chunks of text in `format`
+pub fn export_stream(
+    db: DatabaseConnection,
+    portfolio_id: Uuid,
+    from: Option<NaiveDate>,
+    to: Option<NaiveDate>,
+    format: ExportFormat,
+) -> impl Stream<Item = Result<String, BoxError>> {
+    let state = ExportState {
+        db,
+        portfolio_id,
+        from,
+        to,
+        format,
+        after: None,
+        started: false,
+        wrote_rows: false,
+        finished: false,
+    };
+    futures::stream::unfold(state, |mut state| async move {
+        if state.finished {
+            return None;
+        }
+        let page = match next_page(&state).await {
+            Ok(page) => page,
+            Err(e) => {
+                state.finished = true;
+                return Some((Err(e.into()), state));
+            }
+        };
+        state.finished = (page.len() as u64) < EXPORT_PAGE_SIZE;
+        state.after = page.last().map(|s| (s.snapshot_date, s.created_at, s.id)).or(state.after);
+        let rows: Vec<SnapshotExportRow> = page.iter().flat_map(export_rows).collect();
+
+        let chunk = match state.format {
+            ExportFormat::Csv => match to_csv(&rows, !state.started) {
+                Ok(chunk) => chunk,
+                Err(e) => {
+                    state.finished = true;
+                    return Some((Err(e.into()), state));
+                }
+            },
+            ExportFormat::Json => {
+                let mut chunk = String::new();
+                if !state.started {
+                    chunk.push('[');
+                }
+                for row in &rows {
+                    if state.wrote_rows {
+                        chunk.push(',');
+                    }
+                    chunk.push_str(&serde_json::to_string(row).unwrap_or_default());
+                    state.wrote_rows = true;
+                }
+                if state.finished {
+                    chunk.push(']');
+                }
+                chunk
+            }
+        };
+        state.started = true;
+        Some((Ok(chunk), state))
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use chrono::Utc;
+    use rust_decimal::Decimal;
+
+    #[test]
+    fn test_export_rows_and_csv() {
+        let holding = |asset: &str, value: f64| SnapshotHolding {
+            asset: asset.to_string(),
+            quantity: "2".to_string(),
+            price_usd: Some(value / 2.0),
+            value_usd: value,
+            weight: value / 10.0,
+            unpriced: false,
+            borrowed_quantity: None,
+        };
+        let mut snapshot = snapshots::Model {
+            id: Uuid::new_v4(),
+            portfolio_id: Uuid::new_v4(),
+            snapshot_date: NaiveDate::from_ymd_opt(2025, 3, 1).unwrap(),
+            snapshot_type: "eod".to_string(),
+            total_value_usd: Decimal::from(1000),
+            holdings: serde_json::json!([holding("BTC", 600.0), holding("ETH", 400.0)]),
+            allocation_id: None,
+            metadata: None,
+            created_at: Utc::now().into(),
+            construction_run_id: None,
+            gross_value_usd: None,
+            debt_usd: None,
+            net_value_usd: None,
+        };
+
+        let rows = export_rows(&snapshot);
+        assert_eq!(rows.len(), 2);
+        assert_eq!(rows[1].asset.as_deref(), Some("ETH"));
+        assert_eq!(rows[1].total_value_usd, "1000");
+
+        let csv = to_csv(&rows, true).unwrap();
+        let lines: Vec<&str> = csv.lines().collect();
+        assert_eq!(lines.len(), 3);
+        assert!(lines[0].starts_with("snapshot_id,snapshot_date,snapshot_type,created_at,total_value_usd,asset"));
+        assert!(lines[1].contains(",2025-03-01,eod,"));
+        assert!(lines[1].ends_with(",BTC,2,300.0,600.0,60.0,false,"));
+        assert!(!to_csv(&rows, false).unwrap().starts_with("snapshot_id"));
+
+        // A snapshot without holdings keeps one row with empty holding columns
+        snapshot.holdings = serde_json::json!([]);
+        let rows = export_rows(&snapshot);
+        assert_eq!(rows.len(), 1);
+        assert!(to_csv(&rows, false).unwrap().trim_end().ends_with(",1000,,,,,,,"));
+        assert_eq!(to_csv(&[], true).unwrap().lines().count(), 1);
+    }
+}
@@ -0,0 +1,346 @@
+//! Tax lot matching of a user's asset ledgers.
+//!
+//! Every acquisition opens a lot in its account; every disposal and withdrawal consumes open
+//! lots of its asset and account in the order of the owner's method, splitting proceeds across
+//! lots by quantity. Quantity beyond the open lots (e.g. bought before the ledger starts) is
+//! disposed without a lot, at zero cost. A transfer between the owner's own accounts moves the
+//! consumed lots: the deposit reopens them in the receiving account with their original cost
+//! and acquisition time.
+
+use chrono::{DateTime, Utc};
+use rust_decimal::Decimal;
+use sha2::{Digest, Sha256};
+use std::collections::HashMap;
+use uuid::Uuid;
+
+use crate::helpers::cost_basis::{LedgerAction, LedgerEntry};
+
+/// Disposal kind: sale or fee, realizing a gain or loss
+pub const DISPOSAL_KIND_DISPOSAL: &str = "disposal";
+/// Disposal kind: withdrawal out of the account, which realizes nothing
+pub const DISPOSAL_KIND_TRANSFER: &str = "transfer";
+
+/// Order in which open lots are consumed
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum LotMethod {
+    /// First in, first out: oldest lot first
+    Fifo,
+    /// Last in, first out: newest lot first
+    Lifo,
+    /// Highest in, first out: highest unit cost first
+    Hifo,
+}
+
+impl LotMethod {
+    pub fn parse(value: &str) -> Option<Self> {
+        match value.trim().to_lowercase().as_str() {
+            "fifo" => Some(Self::Fifo),
+            "lifo" => Some(Self::Lifo),
+            "hifo" => Some(Self::Hifo),
+            _ => None,
+        }
+    }
+
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            Self::Fifo => "fifo",
+            Self::Lifo => "lifo",
+            Self::Hifo => "hifo",
+        }
+    }
+}
+
+/// Ledger entry of one of the owner's accounts
+#[derive(Debug, Clone, PartialEq)]
+pub struct AccountLedgerEntry {
+    pub account_id: Uuid,
+    pub entry: LedgerEntry,
+    /// Shared by a withdrawal and the deposit it became in another of the owner's accounts
+    pub transfer_key: Option<Uuid>,
+}
+
+/// Acquisition of an asset
+#[derive(Debug, Clone, PartialEq)]
+pub struct Lot {
+    /// Derived from the account, asset and acquisition time (see [`lot_id`]), so a rebuild
+    /// keeps the ids of lots whose acquisitions did not change
+    pub id: Uuid,
+    pub account_id: Uuid,
+    pub asset: String,
+    pub acquired_at: DateTime<Utc>,
+    pub quantity: Decimal,
+    pub remaining_quantity: Decimal,
+    /// USD cost of `quantity`
+    pub cost_basis_usd: Decimal,
+}
+
+impl Lot {
+    pub fn unit_cost(&self) -> Decimal {
+        if self.quantity > Decimal::ZERO {
+            self.cost_basis_usd / self.quantity
+        } else {
+            Decimal::ZERO
+        }
+    }
+}
+
+/// Quantity of one lot (or of no lot) consumed by a disposal or withdrawal
+#[derive(Debug, Clone, PartialEq)]
+pub struct LotDisposal {
+    pub account_id: Uuid,
+    pub lot_id: Option<Uuid>,
+    pub asset: String,
+    /// [`DISPOSAL_KIND_DISPOSAL`] or [`DISPOSAL_KIND_TRANSFER`]
+    pub kind: &'static str,
+    pub quantity: Decimal,
+    pub proceeds_usd: Decimal,
+    pub cost_basis_usd: Decimal,
+    pub acquired_at: Option<DateTime<Utc>>,
+    pub disposed_at: DateTime<Utc>,
+}
+
+impl LotDisposal {
+    pub fn gain_usd(&self) -> Decimal {
+        self.proceeds_usd - self.cost_basis_usd
+    }
+}
+
+/// Id of the `ordinal`-th lot (from 0) of `asset` acquired by an account at `acquired_at`
+pub fn lot_id(account_id: Uuid, asset: &str, acquired_at: DateTime<Utc>, ordinal: usize) -> Uuid {
+    let digest = Sha256::new()
+        .chain_update(account_id.as_bytes())
+        .chain_update(asset.as_bytes())
+        .chain_update(acquired_at.to_rfc3339().as_bytes())
+        .chain_update(ordinal.to_be_bytes())
+        .finalize();
+    let mut bytes = [0u8; 16];
+    bytes.copy_from_slice(&digest[..16]);
+    uuid::Builder::from_custom_bytes(bytes).into_uuid()
+}
+
+/// `(acquired_at, quantity, cost)` of a lot taken by a withdrawal; no acquisition time for
+/// quantity beyond the open lots
+type MovedLot = (Option<DateTime<Utc>>, Decimal, Decimal);
+
+/// Open lots of a user's accounts
+#[derive(Default)]
+struct LotBook {
+    lots: Vec<Lot>,
+    ordinals: HashMap<(Uuid, String, DateTime<Utc>), usize>,
+}
+
+impl LotBook {
+    fn open(&mut self, account_id: Uuid, asset: &str, acquired_at: DateTime<Utc>, quantity: Decimal, cost: Decimal) {
+        let ordinal = self.ordinals.entry((account_id, asset.to_string(), acquired_at)).or_default();
+        self.lots.push(Lot {
+            id: lot_id(account_id, asset, acquired_at, *ordinal),
+            account_id,
+            asset: asset.to_string(),
+            acquired_at,
+            quantity,
+            remaining_quantity: quantity,
+            cost_basis_usd: cost,
+        });
+        *ordinal += 1;
+    }
+}
+
+/// Match ledger entries of a user's accounts, oldest first (a paired withdrawal before its
+/// deposit), into lots and the disposals consuming them
+pub fn match_lots(entries: &[AccountLedgerEntry], method: LotMethod) -> (Vec<Lot>, Vec<LotDisposal>) {
+    let mut book = LotBook::default();
+    let mut disposals = Vec::new();
+    // Lots consumed by paired withdrawals, until their deposit
+    let mut in_transit: HashMap<Uuid, Vec<MovedLot>> = HashMap::new();
+    for AccountLedgerEntry { account_id, entry, transfer_key } in entries {
+        let account_id = *account_id;
+        if entry.quantity <= Decimal::ZERO {
+            continue;
+        }
+        let kind = match entry.action {
+            LedgerAction::Acquire => {
+                book.open(account_id, &entry.asset, entry.at, entry.quantity, entry.value_usd);
+                continue;
+            }
+            LedgerAction::TransferIn => {
+                let Some(moved) = transfer_key.and_then(|key| in_transit.remove(&key)) else {
+                    book.open(account_id, &entry.asset, entry.at, entry.quantity, entry.value_usd);
+                    continue;
+                };
+                // Quantities scale to what arrived; the full cost moves with them
+                let sent: Decimal = moved.iter().map(|(_, quantity, _)| *quantity).sum();
+                for (acquired_at, quantity, cost) in moved {
+                    let quantity = quantity * entry.quantity / sent;
+                    match acquired_at {
+                        Some(acquired_at) => book.open(account_id, &entry.asset, acquired_at, quantity, cost),
+                        // Sent without a lot: enters at market value like other deposits
+                        None => book.open(
+                            account_id,
+                            &entry.asset,
+                            entry.at,
+                            quantity,
+                            entry.value_usd * quantity / entry.quantity,
+                        ),
+                    }
+                }
+                continue;
+            }
+            LedgerAction::Dispose => DISPOSAL_KIND_DISPOSAL,
+            LedgerAction::TransferOut => DISPOSAL_KIND_TRANSFER,
+        };
+        let proceeds = if kind == DISPOSAL_KIND_DISPOSAL { entry.value_usd } else { Decimal::ZERO };
+        let lots = &mut book.lots;
+
+        let mut open: Vec<usize> = (0..lots.len())
+            .filter(|&i| {
+                lots[i].account_id == account_id
+                    && lots[i].asset == entry.asset
+                    && lots[i].remaining_quantity > Decimal::ZERO
+            })
+            .collect();
+        // Moved lots keep their acquisition time, so order by it rather than by opening order
+        match method {
+            LotMethod::Fifo => open.sort_by_key(|&i| lots[i].acquired_at),
+            LotMethod::Lifo => open.sort_by(|&a, &b| lots[b].acquired_at.cmp(&lots[a].acquired_at).then(b.cmp(&a))),
+            LotMethod::Hifo => open.sort_by(|&a, &b| lots[b].unit_cost().cmp(&lots[a].unit_cost())),
+        }
+
+        let mut consumed = Vec::new();
+        let mut left = entry.quantity;
+        for i in open {
+            if left.is_zero() {
+                break;
+            }
+            let lot = &mut lots[i];
+            let quantity = left.min(lot.remaining_quantity);
+            let cost = if quantity == lot.quantity { lot.cost_basis_usd } else { lot.unit_cost() * quantity };
+            lot.remaining_quantity -= quantity;
+            left -= quantity;
+            consumed.push((Some(lot.acquired_at), quantity, cost));
+            disposals.push(LotDisposal {
+                account_id,
+                lot_id: Some(lot.id),
+                asset: entry.asset.clone(),
+                kind,
+                quantity,
+                proceeds_usd: proceeds * quantity / entry.quantity,
+                cost_basis_usd: cost,
+                acquired_at: Some(lot.acquired_at),
+                disposed_at: entry.at,
+            });
+        }
+        if left > Decimal::ZERO {
+            consumed.push((None, left, Decimal::ZERO));
+            disposals.push(LotDisposal {
+                account_id,
+                lot_id: None,
+                asset: entry.asset.clone(),
+                kind,
+                quantity: left,
+                proceeds_usd: proceeds * left / entry.quantity,
+                cost_basis_usd: Decimal::ZERO,
+                acquired_at: None,
+                disposed_at: entry.at,
+            });
+        }
+        if let (LedgerAction::TransferOut, Some(key)) = (entry.action, transfer_key) {
+            in_transit.insert(*key, consumed);
+        }
+    }
+    (book.lots, disposals)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use chrono::Duration;
+
+    fn entry(action: LedgerAction, quantity: i64, value_usd: i64, day: i64) -> AccountLedgerEntry {
+        AccountLedgerEntry {
+            account_id: Uuid::nil(),
+            entry: LedgerEntry {
+                at: DateTime::<Utc>::UNIX_EPOCH + Duration::days(day),
+                asset: "BTC".to_string(),
+                action,
+                quantity: Decimal::from(quantity),
+                value_usd: Decimal::from(value_usd),
+                transfer_id: None,
+            },
+            transfer_key: None,
+        }
+    }
+
+    fn gains(method: LotMethod) -> (Vec<Decimal>, Vec<Lot>) {
+        let (lots, disposals) = match_lots(
+            &[
+                entry(LedgerAction::Acquire, 1, 100, 0),
+                entry(LedgerAction::Acquire, 1, 300, 1),
+                entry(LedgerAction::Acquire, 1, 200, 2),
+                entry(LedgerAction::Dispose, 2, 500, 3),
+            ],
+            method,
+        );
+        (disposals.iter().map(|d| d.gain_usd()).collect(), lots)
+    }
+
+    #[test]
+    fn test_match_lots_methods() {
+        // Each unit sold for 250
+        assert_eq!(gains(LotMethod::Fifo).0, vec![Decimal::from(150), Decimal::from(-50)]);
+        assert_eq!(gains(LotMethod::Lifo).0, vec![Decimal::from(50), Decimal::from(-50)]);
+        let (hifo, lots) = gains(LotMethod::Hifo);
+        assert_eq!(hifo, vec![Decimal::from(-50), Decimal::from(50)]);
+        // HIFO left the cheapest lot open
+        assert_eq!(lots[0].remaining_quantity, Decimal::ONE);
+        assert!(lots[1..].iter().all(|lot| lot.remaining_quantity.is_zero()));
+
+        // Withdrawals consume lots without proceeds; quantity beyond the lots has no lot
+        let (_, disposals) = match_lots(
+            &[entry(LedgerAction::Acquire, 1, 100, 0), entry(LedgerAction::TransferOut, 3, 0, 1)],
+            LotMethod::Fifo,
+        );
+        assert_eq!(disposals.len(), 2);
+        assert!(disposals.iter().all(|d| d.kind == DISPOSAL_KIND_TRANSFER && d.proceeds_usd.is_zero()));
+        assert_eq!(disposals[1].lot_id, None);
+        assert_eq!(disposals[1].quantity, Decimal::from(2));
+
+        assert_eq!(LotMethod::parse("HIFO"), Some(LotMethod::Hifo));
+        assert!(LotMethod::parse("average").is_none());
+    }
+
+    #[test]
+    fn test_own_transfer_moves_lots() {
+        let (from, to, key) = (Uuid::new_v4(), Uuid::new_v4(), Uuid::new_v4());
+        let in_account = |account_id, transfer_key, e: AccountLedgerEntry| AccountLedgerEntry {
+            account_id,
+            transfer_key,
+            ..e
+        };
+        let entries = [
+            in_account(from, None, entry(LedgerAction::Acquire, 1, 100, 0)),
+            in_account(from, None, entry(LedgerAction::Acquire, 1, 300, 1)),
+            in_account(from, Some(key), entry(LedgerAction::TransferOut, 2, 0, 10)),
+            in_account(to, Some(key), entry(LedgerAction::TransferIn, 2, 1000, 10)),
+            in_account(to, None, entry(LedgerAction::Dispose, 1, 450, 20)),
+        ];
+        let (lots, disposals) = match_lots(&entries, LotMethod::Fifo);
+
+        // The deposit reopens both lots with their cost and acquisition day, not its market value
+        let moved: Vec<&Lot> = lots.iter().filter(|lot| lot.account_id == to).collect();
+        assert_eq!(moved.len(), 2);
+        assert_eq!(moved[0].cost_basis_usd, Decimal::from(100));
+        assert_eq!(moved[0].acquired_at, entries[0].entry.at);
+        assert_eq!(moved[1].cost_basis_usd, Decimal::from(300));
+
+        // The sale draws on the oldest moved lot
+        let sale = disposals.last().unwrap();
+        assert_eq!((sale.account_id, sale.kind, sale.lot_id), (to, DISPOSAL_KIND_DISPOSAL, Some(moved[0].id)));
+        assert_eq!(sale.gain_usd(), Decimal::from(350));
+        assert_eq!(sale.acquired_at, Some(entries[0].entry.at));
+
+        // Rebuilding the same ledger gives the same lot ids
+        let (again, _) = match_lots(&entries, LotMethod::Fifo);
+        assert_eq!(lots.iter().map(|l| l.id).collect::<Vec<_>>(), again.iter().map(|l| l.id).collect::<Vec<_>>());
+        assert_ne!(lots[0].id, moved[0].id);
+    }
+}
@@ -0,0 +1,164 @@
+//! Rows of the tax report: disposals from `tax_disposals` with their holding period, and their
+//! CSV export in the layout of IRS Form 8949 (description, dates acquired and sold, proceeds,
+//! cost basis, gain or loss), which common tax tools import.
+
+use chrono::{DateTime, Months, Utc};
+use rust_decimal::Decimal;
+use serde::{Deserialize, Serialize};
+use utoipa::ToSchema;
+
+use crate::entities::tax_disposals;
+
+/// Holding period: held one year or less
+pub const TERM_SHORT: &str = "short";
+/// Holding period: held more than one year
+pub const TERM_LONG: &str = "long";
+/// Holding period: acquisition not in the ledger
+pub const TERM_UNKNOWN: &str = "unknown";
+
+/// One disposal of a lot
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, ToSchema)]
+pub struct TaxReportRow {
+    pub asset: String,
+    pub quantity: String,
+    /// YYYY-MM-DD; absent when the acquisition is not in the ledger
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub date_acquired: Option<String>,
+    pub date_sold: String, // YYYY-MM-DD
+    pub proceeds_usd: String,
+    pub cost_basis_usd: String,
+    pub gain_usd: String,
+    /// "short", "long" or "unknown"
+    pub holding_period: String,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub holding_days: Option<i64>,
+    pub account_name: String,
+}
+
+/// "short" or "long" for a lot held from `acquired_at` to `disposed_at`, with the days held.
+/// Long term means disposed after the one-year anniversary of the acquisition date, so leap
+/// years count as one year like any other.
+pub fn holding_period(acquired_at: Option<DateTime<Utc>>, disposed_at: DateTime<Utc>) -> (&'static str, Option<i64>) {
+    match acquired_at {
+        Some(acquired_at) => {
+            let (acquired, disposed) = (acquired_at.date_naive(), disposed_at.date_naive());
+            let days = (disposed - acquired).num_days();
+            let long_term = acquired.checked_add_months(Months::new(12)).is_some_and(|anniversary| disposed > anniversary);
+            (if long_term { TERM_LONG } else { TERM_SHORT }, Some(days))
+        }
+        None => (TERM_UNKNOWN, None),
+    }
+}
+
+fn usd(value: Decimal) -> String {
+    value.round_dp(2).normalize().to_string()
+}
+
+/// Report row of a disposal
+pub fn report_row(disposal: &tax_disposals::Model, account_name: &str) -> TaxReportRow {
+    let acquired_at = disposal.acquired_at.map(|at| at.to_utc());
+    let disposed_at = disposal.disposed_at.to_utc();
+    let (term, days) = holding_period(acquired_at, disposed_at);
+    TaxReportRow {
+        asset: disposal.asset.clone(),
+        quantity: disposal.quantity.normalize().to_string(),
+        date_acquired: acquired_at.map(|at| at.date_naive().to_string()),
+        date_sold: disposed_at.date_naive().to_string(),
+        proceeds_usd: usd(disposal.proceeds_usd),
+        cost_basis_usd: usd(disposal.cost_basis_usd),
+        gain_usd: usd(disposal.proceeds_usd - disposal.cost_basis_usd),
+        holding_period: term.to_string(),
+        holding_days: days,
+        account_name: account_name.to_string(),
+    }
+}
+
+/// CSV of report rows. Dates are MM/DD/YYYY as on Form 8949; an acquisition that is not in
+/// the ledger is "VARIOUS".
+pub fn to_csv(rows: &[TaxReportRow]) -> Result<String, csv::Error> {
+    let us_date = |date: &str| {
+        chrono::NaiveDate::parse_from_str(date, "%Y-%m-%d")
+            .map(|d| d.format("%m/%d/%Y").to_string())
+            .unwrap_or_else(|_| date.to_string())
+    };
+    let mut writer = csv::Writer::from_writer(Vec::new());
+    writer.write_record([
+        "Description",
+        "Asset",
+        "Quantity",
+        "Date Acquired",
+        "Date Sold",
+        "Proceeds",
+        "Cost Basis",
+        "Gain or Loss",
+        "Holding Period",
+        "Holding Days",
+        "Account",
+    ])?;
+    for row in rows {
+        writer.write_record([
+            format!("{} {}", row.quantity, row.asset),
+            row.asset.clone(),
+            row.quantity.clone(),
+            row.date_acquired.as_deref().map(us_date).unwrap_or_else(|| "VARIOUS".to_string()),
+            us_date(&row.date_sold),
+            row.proceeds_usd.clone(),
+            row.cost_basis_usd.clone(),
+            row.gain_usd.clone(),
+            row.holding_period.clone(),
+            row.holding_days.map(|d| d.to_string()).unwrap_or_default(),
+            row.account_name.clone(),
+        ])?;
+    }
+    let bytes = writer.into_inner().map_err(|e| e.into_error())?;
+    Ok(String::from_utf8_lossy(&bytes).into_owned())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use chrono::TimeZone;
+    use uuid::Uuid;
+
+    #[test]
+    fn test_report_row_and_csv() {
+        let disposal = tax_disposals::Model {
+            id: Uuid::new_v4(),
+            account_id: Uuid::new_v4(),
+            lot_id: Some(Uuid::new_v4()),
+            asset: "BTC".to_string(),
+            kind: "disposal".to_string(),
+            quantity: Decimal::new(5, 1),
+            proceeds_usd: Decimal::new(3000050, 2),
+            cost_basis_usd: Decimal::from(20000),
+            acquired_at: Some(Utc.with_ymd_and_hms(2024, 1, 10, 8, 0, 0).unwrap().into()),
+            disposed_at: Utc.with_ymd_and_hms(2025, 3, 1, 8, 0, 0).unwrap().into(),
+            created_at: Utc::now().into(),
+        };
+        let row = report_row(&disposal, "OKX, main");
+        assert_eq!(row.gain_usd, "10000.5");
+        assert_eq!(row.holding_period, TERM_LONG);
+        assert_eq!(row.holding_days, Some(416));
+
+        let unmatched = report_row(&tax_disposals::Model { acquired_at: None, lot_id: None, ..disposal }, "Ledger");
+        assert_eq!(unmatched.holding_period, TERM_UNKNOWN);
+
+        let csv = to_csv(&[row, unmatched]).unwrap();
+        let lines: Vec<&str> = csv.lines().collect();
+        assert_eq!(lines.len(), 3);
+        assert!(lines[0].starts_with("Description,Asset,Quantity,Date Acquired,Date Sold,Proceeds"));
+        assert_eq!(lines[1], "0.5 BTC,BTC,0.5,01/10/2024,03/01/2025,30000.5,20000,10000.5,long,416,\"OKX, main\"");
+        assert!(lines[2].contains(",VARIOUS,03/01/2025,"));
+    }
+
+    #[test]
+    fn test_holding_period_is_calendar_year() {
+        let at = |y, m, d| Utc.with_ymd_and_hms(y, m, d, 12, 0, 0).unwrap();
+
+        // 366 days across the leap day is still the anniversary: short term
+        assert_eq!(holding_period(Some(at(2024, 1, 10)), at(2025, 1, 10)), (TERM_SHORT, Some(366)));
+        assert_eq!(holding_period(Some(at(2024, 1, 10)), at(2025, 1, 11)), (TERM_LONG, Some(367)));
+        assert_eq!(holding_period(Some(at(2025, 1, 10)), at(2026, 1, 11)), (TERM_LONG, Some(366)));
+        assert_eq!(holding_period(None, at(2025, 1, 10)), (TERM_UNKNOWN, None));
+    }
+}
@@ -0,0 +1,137 @@
+//! Portfolio value change annotations (24h / 7d / 30d) computed from the snapshot series.
+//!
+//! Each delta compares a current value against the most recent snapshot taken on or
+//! before the period's target date. Computing this server-side keeps every client on
+//! the same definition of "change over N days".
+
+use chrono::{Duration, NaiveDate};
+use rust_decimal::Decimal;
+use sea_orm::{ColumnTrait, DatabaseConnection, DbErr, EntityTrait, QueryFilter, QueryOrder};
+use serde::{Deserialize, Serialize};
+use utoipa::ToSchema;
+use uuid::Uuid;
+
+use crate::entities::snapshots;
+
+/// Change of portfolio value against one baseline snapshot
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, ToSchema)]
+pub struct ValueDelta {
+    /// Date of the baseline snapshot used (may be earlier than the target date if snapshots are missing)
+    pub baseline_date: String,
+    /// Baseline total value in USD
+    pub baseline_value_usd: String,
+    /// Absolute change in USD (current - baseline)
+    pub change_usd: String,
+    /// Percent change; absent when the baseline value is zero
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub change_percent: Option<String>,
+}
+
+/// Value deltas over the standard periods; a period is absent when no baseline exists
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Default, ToSchema)]
+pub struct ValueDeltas {
+    #[serde(rename = "24h", skip_serializing_if = "Option::is_none")]
+    pub day: Option<ValueDelta>,
+    #[serde(rename = "7d", skip_serializing_if = "Option::is_none")]
+    pub week: Option<ValueDelta>,
+    #[serde(rename = "30d", skip_serializing_if = "Option::is_none")]
+    pub month: Option<ValueDelta>,
+}
+
+/// Compute the delta of `current` against a baseline `(date, value)`
+pub fn value_delta(current: Decimal, baseline: Option<(NaiveDate, Decimal)>) -> Option<ValueDelta> {
+    let (baseline_date, baseline_value) = baseline?;
+    let change = current - baseline_value;
+    let change_percent = (!baseline_value.is_zero())
+        .then(|| (change / baseline_value * Decimal::ONE_HUNDRED).round_dp(2));
+
+    Some(ValueDelta {
+        baseline_date: baseline_date.to_string(),
+        baseline_value_usd: baseline_value.to_string(),
+        change_usd: change.to_string(),
+        change_percent: change_percent.map(|p| p.to_string()),
+    })
+}
+
+/// Latest snapshot `(date, total value)` of a portfolio (by date, then creation time) dated
+/// on or before `date`
+pub async fn load_baseline(
+    db: &DatabaseConnection,
+    portfolio_id: Uuid,
+    date: NaiveDate,
+) -> Result<Option<(NaiveDate, Decimal)>, DbErr> {
+    Ok(snapshots::Entity::find()
+        .filter(snapshots::Column::PortfolioId.eq(portfolio_id))
+        .filter(snapshots::Column::SnapshotDate.lte(date))
+        .order_by_desc(snapshots::Column::SnapshotDate)
+        .order_by_desc(snapshots::Column::CreatedAt)
+        .one(db)
+        .await?
+        .map(|s| (s.snapshot_date, s.total_value_usd)))
+}
+
+/// Load deltas for a portfolio's value `current_value` observed on `as_of`.
+///
+/// Baselines are the latest snapshot (by date, then creation time) dated on or
+/// before `as_of - 1/7/30 days`.
+pub async fn load_value_deltas(
+    db: &DatabaseConnection,
+    portfolio_id: Uuid,
+    as_of: NaiveDate,
+    current_value: Decimal,
+) -> Result<ValueDeltas, DbErr> {
+    let mut baselines = Vec::with_capacity(3);
+    for days in [1, 7, 30] {
+        let baseline = load_baseline(db, portfolio_id, as_of - Duration::days(days)).await?;
+        baselines.push(value_delta(current_value, baseline));
+    }
+
+    let mut baselines = baselines.into_iter();
+    Ok(ValueDeltas {
+        day: baselines.next().flatten(),
+        week: baselines.next().flatten(),
+        month: baselines.next().flatten(),
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::str::FromStr;
+
+    fn date(s: &str) -> NaiveDate {
+        NaiveDate::parse_from_str(s, "%Y-%m-%d").unwrap()
+    }
+
+    #[test]
+    fn test_value_delta_gain_and_loss() {
+        let up = value_delta(Decimal::from(1100), Some((date("2024-03-09"), Decimal::from(1000)))).unwrap();
+        assert_eq!(up.change_usd, "100");
+        assert_eq!(up.change_percent.as_deref(), Some("10.00"));
+        assert_eq!(up.baseline_date, "2024-03-09");
+
+        let down = value_delta(Decimal::from_str("750.5").unwrap(), Some((date("2024-03-03"), Decimal::from(1000)))).unwrap();
+        assert_eq!(down.change_usd, "-249.5");
+        assert_eq!(down.change_percent.as_deref(), Some("-24.95"));
+    }
+
+    #[test]
+    fn test_value_delta_without_baseline_or_zero_baseline() {
+        assert!(value_delta(Decimal::from(10), None).is_none());
+
+        let from_zero = value_delta(Decimal::from(10), Some((date("2024-03-09"), Decimal::ZERO))).unwrap();
+        assert_eq!(from_zero.change_usd, "10");
+        assert!(from_zero.change_percent.is_none());
+    }
+
+    #[test]
+    fn test_deltas_serialize_with_period_keys() {
+        let deltas = ValueDeltas {
+            day: value_delta(Decimal::from(2), Some((date("2024-03-09"), Decimal::ONE))),
+            ..Default::default()
+        };
+        let json = serde_json::to_value(&deltas).unwrap();
+        assert!(json.get("24h").is_some());
+        assert!(json.get("7d").is_none());
+    }
+}
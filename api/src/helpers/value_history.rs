@@ -0,0 +1,209 @@
+//! Portfolio value time series from snapshots, resampled to a granularity.
+//!
+//! The value of a date is its latest snapshot. Dates without a snapshot between two that
+//! have one can be filled with the previous value or by linear interpolation; nothing is
+//! extrapolated before the first or after the last snapshot. A weekly or monthly point is
+//! the last value of the week (starting Monday) or month.
+
+use std::collections::BTreeMap;
+
+use chrono::{Datelike, Duration, NaiveDate};
+use rust_decimal::Decimal;
+use serde::{Deserialize, Serialize};
+use utoipa::ToSchema;
+
+/// Spacing of the points of a value history
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize, ToSchema)]
+#[serde(rename_all = "lowercase")]
+pub enum ValueGranularity {
+    Daily,
+    Weekly,
+    Monthly,
+}
+
+impl ValueGranularity {
+    /// Parse "daily", "weekly" or "monthly"
+    pub fn parse(value: &str) -> Option<Self> {
+        match value.trim().to_lowercase().as_str() {
+            "daily" => Some(Self::Daily),
+            "weekly" => Some(Self::Weekly),
+            "monthly" => Some(Self::Monthly),
+            _ => None,
+        }
+    }
+
+    /// First day of the period containing `date`
+    pub fn period_start(self, date: NaiveDate) -> NaiveDate {
+        match self {
+            Self::Daily => date,
+            Self::Weekly => date - Duration::days(date.weekday().num_days_from_monday() as i64),
+            Self::Monthly => date.with_day(1).unwrap_or(date),
+        }
+    }
+}
+
+/// How dates without a snapshot are filled
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize, ToSchema)]
+#[serde(rename_all = "lowercase")]
+pub enum GapFill {
+    /// Leave them out
+    None,
+    /// Carry the previous snapshot's value forward
+    Previous,
+    /// Interpolate linearly between the surrounding snapshots
+    Linear,
+}
+
+impl GapFill {
+    /// Parse "none", "previous" or "linear"
+    pub fn parse(value: &str) -> Option<Self> {
+        match value.trim().to_lowercase().as_str() {
+            "none" => Some(Self::None),
+            "previous" => Some(Self::Previous),
+            "linear" => Some(Self::Linear),
+            _ => None,
+        }
+    }
+}
+
+/// Value of the portfolio at one point of the history
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, ToSchema)]
+pub struct ValuePoint {
+    /// First day of the point's period (YYYY-MM-DD)
+    pub period_start: String,
+    /// Date the value is from (YYYY-MM-DD)
+    pub date: String,
+    pub value_usd: String,
+    /// Whether the value was filled in rather than taken from a snapshot
+    #[serde(default, skip_serializing_if = "std::ops::Not::not")]
+    pub interpolated: bool,
+}
+
+/// Values per date of `series` (one per date, oldest first) from `from` to `to`, gaps
+/// filled with `fill`, then the last of each `granularity` period
+pub fn value_history(
+    series: &[(NaiveDate, Decimal)],
+    from: Option<NaiveDate>,
+    to: Option<NaiveDate>,
+    granularity: ValueGranularity,
+    fill: GapFill,
+) -> Vec<ValuePoint> {
+    let mut daily: Vec<(NaiveDate, Decimal, bool)> = Vec::new();
+    for pair in series.windows(2) {
+        let ((start, start_value), (end, end_value)) = (pair[0], pair[1]);
+        daily.push((start, start_value, false));
+        let days = (end - start).num_days();
+        for offset in 1..days {
+            let value = match fill {
+                GapFill::None => break,
+                GapFill::Previous => start_value,
+                GapFill::Linear => start_value + (end_value - start_value) * Decimal::from(offset) / Decimal::from(days),
+            };
+            daily.push((start + Duration::days(offset), value, true));
+        }
+    }
+    if let Some((date, value)) = series.last() {
+        daily.push((*date, *value, false));
+    }
+
+    let mut points: Vec<ValuePoint> = Vec::new();
+    for (date, value, interpolated) in daily {
+        if from.is_some_and(|from| date < from) || to.is_some_and(|to| date > to) {
+            continue;
+        }
+        let point = ValuePoint {
+            period_start: granularity.period_start(date).to_string(),
+            date: date.to_string(),
+            value_usd: value.round_dp(2).normalize().to_string(),
+            interpolated,
+        };
+        match points.last_mut() {
+            Some(last) if last.period_start == point.period_start => *last = point,
+            _ => points.push(point),
+        }
+    }
+    points
+}
+
+/// The latest of `items` in each `granularity` period of their date, where `key` gives an
+/// item's date and its order within the date (e.g. creation time and id); oldest period first.
+///
+/// Items sharing a period always resolve to the same one, so repeated queries pick the same
+/// snapshot for a period.
+pub fn latest_per_period<T, K: Ord>(
+    items: Vec<T>,
+    granularity: ValueGranularity,
+    key: impl Fn(&T) -> (NaiveDate, K),
+) -> Vec<T> {
+    let mut latest: BTreeMap<NaiveDate, T> = BTreeMap::new();
+    for item in items {
+        let (date, _) = key(&item);
+        let period = granularity.period_start(date);
+        match latest.get(&period) {
+            Some(current) if key(current) >= key(&item) => {}
+            _ => {
+                latest.insert(period, item);
+            }
+        }
+    }
+    latest.into_values().collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn date(value: &str) -> NaiveDate {
+        NaiveDate::parse_from_str(value, "%Y-%m-%d").unwrap()
+    }
+
+    #[test]
+    fn test_value_history() {
+        let series = [
+            (date("2025-01-01"), Decimal::from(100)),
+            (date("2025-01-05"), Decimal::from(140)),
+            (date("2025-01-06"), Decimal::from(150)),
+        ];
+
+        let raw = value_history(&series, None, None, ValueGranularity::Daily, GapFill::None);
+        assert_eq!(raw.len(), 3);
+
+        let linear = value_history(&series, None, None, ValueGranularity::Daily, GapFill::Linear);
+        assert_eq!(linear.len(), 6);
+        assert_eq!((linear[2].date.as_str(), linear[2].value_usd.as_str()), ("2025-01-03", "120"));
+        assert!(linear[2].interpolated);
+        assert!(!linear[4].interpolated);
+
+        let previous = value_history(&series, Some(date("2025-01-02")), Some(date("2025-01-04")), ValueGranularity::Daily, GapFill::Previous);
+        assert_eq!(previous.len(), 3);
+        assert!(previous.iter().all(|p| p.value_usd == "100"));
+
+        // 2025-01-05 is a Sunday: the first week closes at 140, the next opens on the 6th
+        let weekly = value_history(&series, None, None, ValueGranularity::Weekly, GapFill::None);
+        assert_eq!(weekly.len(), 2);
+        assert_eq!((weekly[0].period_start.as_str(), weekly[0].value_usd.as_str()), ("2024-12-30", "140"));
+        let monthly = value_history(&series, None, None, ValueGranularity::Monthly, GapFill::None);
+        assert_eq!((monthly.len(), monthly[0].date.as_str()), (1, "2025-01-06"));
+    }
+
+    #[test]
+    fn test_latest_per_period() {
+        // (date, creation order); two snapshots on 2025-01-05
+        let items = vec![
+            (date("2025-01-05"), 2),
+            (date("2025-01-01"), 1),
+            (date("2025-01-05"), 1),
+            (date("2025-01-06"), 1),
+            (date("2025-02-03"), 1),
+        ];
+        let weekly = latest_per_period(items.clone(), ValueGranularity::Weekly, |i| *i);
+        assert_eq!(weekly, vec![(date("2025-01-05"), 2), (date("2025-01-06"), 1), (date("2025-02-03"), 1)]);
+
+        let mut reversed = items.clone();
+        reversed.reverse();
+        assert_eq!(latest_per_period(reversed, ValueGranularity::Weekly, |i| *i), weekly);
+
+        let monthly = latest_per_period(items, ValueGranularity::Monthly, |i| *i);
+        assert_eq!(monthly, vec![(date("2025-01-06"), 1), (date("2025-02-03"), 1)]);
+    }
+}
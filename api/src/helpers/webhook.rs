@@ -0,0 +1,132 @@
+//! Checks on user-supplied webhook URLs.
+//!
+//! The server calls these URLs itself, so a URL must not reach into its own network: the host
+//! has to resolve to public addresses only, which rules out loopback, private (RFC 1918),
+//! link-local (including cloud metadata at 169.254.169.254) and other reserved ranges. URLs
+//! are checked when saved and again before each call. The call is pinned to the addresses just
+//! checked, so a DNS change in between cannot redirect it, and redirects are not followed.
+
+use reqwest::{redirect, Client, Url};
+use std::net::{IpAddr, Ipv4Addr, Ipv6Addr, SocketAddr};
+use std::time::Duration;
+
+/// Whether `ip` is a public unicast address
+pub fn is_public_ip(ip: IpAddr) -> bool {
+    match ip {
+        IpAddr::V4(ip) => is_public_ipv4(ip),
+        IpAddr::V6(ip) => match ip.to_ipv4_mapped() {
+            Some(mapped) => is_public_ipv4(mapped),
+            None => is_public_ipv6(ip),
+        },
+    }
+}
+
+fn is_public_ipv4(ip: Ipv4Addr) -> bool {
+    let [a, b, ..] = ip.octets();
+    !(ip.is_unspecified()
+        || ip.is_loopback()
+        || ip.is_private()
+        || ip.is_link_local()
+        || ip.is_broadcast()
+        || ip.is_multicast()
+        || ip.is_documentation()
+        // "This network", carrier-grade NAT, benchmarking and reserved ranges
+        || a == 0
+        || (a == 100 && (64..128).contains(&b))
+        || (a == 198 && (18..20).contains(&b))
+        || a >= 240)
+}
+
+fn is_public_ipv6(ip: Ipv6Addr) -> bool {
+    let first = ip.segments()[0];
+    !(ip.is_unspecified()
+        || ip.is_loopback()
+        || ip.is_multicast()
+        // Unique local (fc00::/7), link-local (fe80::/10) and documentation (2001:db8::/32)
+        || (first & 0xfe00) == 0xfc00
+        || (first & 0xffc0) == 0xfe80
+        || (first == 0x2001 && ip.segments()[1] == 0x0db8))
+}
+
+/// A webhook URL whose host resolved to public addresses only
+#[derive(Debug, Clone)]
+pub struct WebhookTarget {
+    pub url: Url,
+    addrs: Vec<SocketAddr>,
+}
+
+impl WebhookTarget {
+    /// Client calling the checked addresses only, without following redirects
+    pub fn client(&self, timeout: Duration) -> reqwest::Result<Client> {
+        let mut builder = Client::builder().timeout(timeout).redirect(redirect::Policy::none());
+        if let Some(domain) = self.url.domain() {
+            builder = builder.resolve_to_addrs(domain, &self.addrs);
+        }
+        builder.build()
+    }
+}
+
+/// Parse an http(s) webhook URL and resolve its host, rejecting hosts with any non-public
+/// address
+pub async fn resolve_webhook_url(url: &str) -> Result<WebhookTarget, String> {
+    let parsed = Url::parse(url).map_err(|_| format!("webhook_url '{}' is not an http(s) URL", url))?;
+    if !matches!(parsed.scheme(), "http" | "https") {
+        return Err(format!("webhook_url '{}' is not an http(s) URL", url));
+    }
+    let host = parsed.host_str().ok_or_else(|| format!("webhook_url '{}' has no host", url))?;
+    let port = parsed.port_or_known_default().unwrap_or(443);
+
+    let addrs: Vec<SocketAddr> = match host.trim_start_matches('[').trim_end_matches(']').parse::<IpAddr>() {
+        Ok(ip) => vec![SocketAddr::new(ip, port)],
+        Err(_) => tokio::net::lookup_host((host, port))
+            .await
+            .map_err(|e| format!("webhook_url host '{}' does not resolve: {}", host, e))?
+            .collect(),
+    };
+    if addrs.is_empty() {
+        return Err(format!("webhook_url host '{}' does not resolve", host));
+    }
+    if let Some(blocked) = addrs.iter().find(|addr| !is_public_ip(addr.ip())) {
+        return Err(format!(
+            "webhook_url host '{}' resolves to non-public address {}",
+            host,
+            blocked.ip()
+        ));
+    }
+    Ok(WebhookTarget { url: parsed, addrs })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[tokio::test]
+    async fn test_webhook_addresses() {
+        let public = |ip: &str| is_public_ip(ip.parse().unwrap());
+        assert!(public("8.8.8.8"));
+        assert!(public("2606:4700:4700::1111"));
+        for blocked in [
+            "127.0.0.1",
+            "10.1.2.3",
+            "172.16.0.1",
+            "192.168.1.10",
+            "169.254.169.254",
+            "100.64.0.1",
+            "0.0.0.0",
+            "::1",
+            "fd00::1",
+            "fe80::1",
+            "::ffff:127.0.0.1",
+        ] {
+            assert!(!public(blocked), "{} should be blocked", blocked);
+        }
+
+        // IP literals are checked without a lookup
+        assert!(resolve_webhook_url("http://169.254.169.254/latest/meta-data").await.is_err());
+        assert!(resolve_webhook_url("http://[::1]:8080/hook").await.is_err());
+        assert!(resolve_webhook_url("ftp://8.8.8.8/hook").await.is_err());
+        assert!(resolve_webhook_url("http://localhost/hook").await.is_err());
+        let target = resolve_webhook_url("https://8.8.8.8/hook").await.unwrap();
+        assert_eq!(target.url.as_str(), "https://8.8.8.8/hook");
+    }
+}
@@ -0,0 +1,185 @@
+use crate::domain::{AccountHolding, SnapshotHolding, HOLDING_SOURCE_BORROWED};
+use crate::entities::{account_snapshots, accounts};
+use crate::jobs::portfolio_pnl::LedgerPricer;
+use chrono::{NaiveDate, Utc};
+use rust_decimal::prelude::ToPrimitive;
+use rust_decimal::Decimal;
+use sea_orm::sea_query::OnConflict;
+use sea_orm::{ActiveValue, ColumnTrait, DatabaseConnection, EntityTrait, QueryFilter};
+use serde_json::json;
+use std::collections::{BTreeMap, HashMap};
+use std::error::Error;
+use tracing;
+use uuid::Uuid;
+
+/// Snapshot holdings of an account: quantities summed per asset across holding sources
+/// (borrowed quantities are negative, so the total is the net exposure), valued at `prices`
+/// by asset, largest first. Returns the holdings and their total value.
+pub fn account_snapshot_holdings(
+    holdings: &[AccountHolding],
+    prices: &HashMap<String, Decimal>,
+) -> (Vec<SnapshotHolding>, Decimal) {
+    let mut quantities: BTreeMap<String, (Decimal, Decimal)> = BTreeMap::new();
+    for holding in holdings {
+        let entry = quantities.entry(holding.asset.clone()).or_default();
+        entry.0 += holding.quantity_decimal();
+        if holding.source() == HOLDING_SOURCE_BORROWED {
+            entry.1 += holding.quantity_decimal();
+        }
+    }
+    valued_snapshot_holdings(quantities, prices)
+}
+
+/// Snapshot holdings of (quantity, borrowed quantity) by asset, valued at `prices` by asset,
+/// largest first. Assets with neither quantity are left out. Returns the holdings and their
+/// total value.
+pub fn valued_snapshot_holdings(
+    quantities: BTreeMap<String, (Decimal, Decimal)>,
+    prices: &HashMap<String, Decimal>,
+) -> (Vec<SnapshotHolding>, Decimal) {
+    let mut total = Decimal::ZERO;
+    let mut items: Vec<SnapshotHolding> = quantities
+        .into_iter()
+        .filter(|(_, (quantity, borrowed))| !quantity.is_zero() || !borrowed.is_zero())
+        .map(|(asset, (quantity, borrowed))| {
+            let price = prices.get(&asset).copied();
+            let value = price.map(|p| quantity * p).unwrap_or(Decimal::ZERO);
+            total += value;
+            SnapshotHolding {
+                asset,
+                quantity: quantity.normalize().to_string(),
+                price_usd: price.and_then(|p| p.to_f64()),
+                value_usd: value.to_f64().unwrap_or(0.0),
+                weight: 0.0,
+                unpriced: price.is_none(),
+                borrowed_quantity: (!borrowed.is_zero()).then(|| borrowed.normalize().to_string()),
+            }
+        })
+        .collect();
+
+    let total_f64 = total.to_f64().unwrap_or(0.0);
+    for item in items.iter_mut() {
+        item.weight = if total_f64 > 0.0 { item.value_usd / total_f64 * 100.0 } else { 0.0 };
+    }
+    items.sort_by(|a, b| b.value_usd.total_cmp(&a.value_usd));
+    (items, total)
+}
+
+/// Snapshot an account's synced holdings at the latest stored prices, replacing any
+/// snapshot of the account on the same date
+pub async fn create_account_snapshot(
+    db: &DatabaseConnection,
+    account: &accounts::Model,
+    snapshot_date: Option<NaiveDate>,
+) -> Result<account_snapshots::Model, Box<dyn Error + Send + Sync>> {
+    let snapshot_date = snapshot_date.unwrap_or_else(|| Utc::now().date_naive());
+    let holdings: Vec<AccountHolding> = match account.holdings.clone() {
+        Some(json) => serde_json::from_value(json)
+            .map_err(|e| format!("Failed to deserialize holdings of account {}: {}", account.id, e))?,
+        None => Vec::new(),
+    };
+
+    let now = Utc::now();
+    let mut pricer = LedgerPricer::new(db.clone());
+    let mut prices = HashMap::new();
+    for holding in &holdings {
+        if !prices.contains_key(&holding.asset) {
+            if let Some(price) = pricer.price(&holding.asset, now).await? {
+                prices.insert(holding.asset.clone(), price);
+            }
+        }
+    }
+    let (items, total_value_usd) = account_snapshot_holdings(&holdings, &prices);
+
+    let model = account_snapshots::Model {
+        id: Uuid::new_v4(),
+        account_id: account.id,
+        snapshot_date,
+        total_value_usd,
+        holdings: json!(items),
+        created_at: now.into(),
+    };
+    account_snapshots::Entity::insert(account_snapshots::ActiveModel {
+        id: ActiveValue::Set(model.id),
+        account_id: ActiveValue::Set(model.account_id),
+        snapshot_date: ActiveValue::Set(model.snapshot_date),
+        total_value_usd: ActiveValue::Set(model.total_value_usd),
+        holdings: ActiveValue::Set(model.holdings.clone()),
+        created_at: ActiveValue::Set(model.created_at),
+    })
+    .on_conflict(
+        OnConflict::columns([account_snapshots::Column::AccountId, account_snapshots::Column::SnapshotDate])
+            .update_columns([
+                account_snapshots::Column::TotalValueUsd,
+                account_snapshots::Column::Holdings,
+                account_snapshots::Column::CreatedAt,
+            ])
+            .to_owned(),
+    )
+    .exec_without_returning(db)
+    .await?;
+
+    Ok(model)
+}
+
+/// Snapshot every active account; returns the number of accounts snapshotted.
+/// An account that fails is logged and skipped.
+pub async fn create_all_account_snapshots(
+    db: &DatabaseConnection,
+    snapshot_date: Option<NaiveDate>,
+) -> Result<usize, Box<dyn Error + Send + Sync>> {
+    let active_accounts = accounts::Entity::find()
+        .filter(accounts::Column::IsActive.eq(true))
+        .all(db)
+        .await?;
+    let mut created = 0;
+    for account in &active_accounts {
+        match create_account_snapshot(db, account, snapshot_date).await {
+            Ok(_) => created += 1,
+            Err(e) => tracing::warn!("Failed to snapshot account {} ({}): {}", account.name, account.id, e),
+        }
+    }
+    tracing::info!("Account snapshots created for {} of {} accounts", created, active_accounts.len());
+    Ok(created)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn holding(asset: &str, quantity: &str, source: Option<&str>) -> AccountHolding {
+        AccountHolding {
+            asset: asset.to_string(),
+            quantity: quantity.to_string(),
+            available: None,
+            frozen: None,
+            decimals: None,
+            price_usd: None,
+            value_usd: None,
+            holding_source: source.map(str::to_string),
+        }
+    }
+
+    #[test]
+    fn test_account_snapshot_holdings() {
+        let holdings = vec![
+            holding("ETH", "2", None),
+            holding("ETH", "1", Some("earn")),
+            holding("ETH", "-0.5", Some(HOLDING_SOURCE_BORROWED)),
+            holding("USDC", "500", None),
+            holding("NEW", "10", None),
+            holding("DUST", "0", None),
+        ];
+        let prices = HashMap::from([("ETH".to_string(), Decimal::from(2000)), ("USDC".to_string(), Decimal::ONE)]);
+        let (items, total) = account_snapshot_holdings(&holdings, &prices);
+
+        assert_eq!(total, Decimal::from(5500));
+        assert_eq!(items.len(), 3);
+        assert_eq!(items[0].asset, "ETH");
+        assert_eq!(items[0].quantity, "2.5");
+        assert_eq!(items[0].borrowed_quantity.as_deref(), Some("-0.5"));
+        assert!((items[0].weight - 5000.0 / 5500.0 * 100.0).abs() < 1e-9);
+        assert!(items[2].unpriced);
+        assert_eq!(items[2].weight, 0.0);
+    }
+}
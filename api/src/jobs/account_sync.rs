@@ -1,12 +1,21 @@
-use crate::connectors::{okx::OkxConnector, evm::{EvmConnector, EvmChain}, solana::SolanaConnector, ExchangeConnector};
-use crate::entities::{accounts, evm_chains, evm_tokens, solana_tokens};
+use crate::connectors::defi;
+use crate::connectors::registry::{ConnectorContext, ConnectorFactory, ConnectorRegistry};
+use crate::connectors::{merge_balances, Balance, ChainFetch, ChainProgress, ConnectorError, ExchangeConnector};
+use crate::domain::{AccountHolding, AccountSettings};
+use crate::entities::{account_addresses, accounts, sync_runs};
+use crate::jobs::{
+    anomaly_detection, fee_sync, holding_ledger, income_events, nft_sync, position_sync, staking_rewards,
+    trade_sync, transfer_sync,
+};
+use async_trait::async_trait;
 use chrono::Utc;
 use sea_orm::{
     ActiveModelTrait, ActiveValue, ColumnTrait, DatabaseConnection, EntityTrait, QueryFilter,
 };
+use serde::{Deserialize, Serialize};
 use serde_json::json;
-use std::collections::HashMap;
 use std::error::Error;
+use std::sync::Mutex;
 use tracing;
 use uuid::Uuid;
 
@@ -16,30 +25,280 @@ pub struct SyncResult {
     pub account_id: Uuid,
     pub success: bool,
     pub error: Option<String>,
+    /// Typed error kind when a connector reported one (e.g. "rate_limited", "invalid_credentials")
+    pub error_kind: Option<String>,
     pub holdings_count: usize,
 }
 
-/// Decrypt API credentials (placeholder - implement proper encryption/decryption)
-/// 
-/// SECURITY WARNING: This is a placeholder implementation that stores credentials in plain text.
-/// Before production deployment, this MUST be replaced with proper encryption using:
-/// - AWS KMS (Key Management Service)
-/// - HashiCorp Vault
-/// - Google Cloud KMS
-/// - Azure Key Vault
-/// or similar key management solution.
-fn decrypt_credential(encrypted: &str) -> Result<String, Box<dyn Error + Send + Sync>> {
-    // TODO: Implement proper decryption using a key management service
-    // For now, assuming credentials are stored as-is (not recommended for production)
-    Ok(encrypted.to_string())
+/// Fetch the balances of every sub-account, best effort.
+///
+/// Discovery or per-sub-account failures (e.g. a key without sub-account permissions)
+/// are logged and skipped so the master account still syncs.
+async fn fetch_sub_account_balances(connector: &dyn ExchangeConnector, account_id: Uuid) -> Vec<Balance> {
+    let sub_accounts = match connector.discover_sub_accounts().await {
+        Ok(sub_accounts) => sub_accounts,
+        Err(e) => {
+            tracing::warn!("Failed to list sub-accounts for account {}: {}", account_id, e);
+            return Vec::new();
+        }
+    };
+
+    let mut balances = Vec::new();
+    for sub_account in &sub_accounts {
+        match connector.fetch_sub_account_balances(sub_account).await {
+            Ok(sub_balances) => balances.extend(sub_balances),
+            Err(e) => tracing::warn!(
+                "Skipping sub-account {} of account {}: {}",
+                sub_account.id,
+                account_id,
+                e
+            ),
+        }
+    }
+
+    tracing::info!(
+        "Included {} sub-accounts ({} balances) for account {}",
+        sub_accounts.len(),
+        balances.len(),
+        account_id
+    );
+    balances
+}
+
+/// Create a connector for each additional address of a wallet account (`account_addresses`),
+/// from the same factory as the account's primary `wallet_address`
+async fn address_connectors(
+    db: &DatabaseConnection,
+    factory: &dyn ConnectorFactory,
+    account: &accounts::Model,
+) -> Result<Vec<(String, Box<dyn ExchangeConnector>)>, Box<dyn Error + Send + Sync>> {
+    let addresses = account_addresses::Entity::find()
+        .filter(account_addresses::Column::AccountId.eq(account.id))
+        .all(db)
+        .await?;
+
+    let mut connectors = Vec::with_capacity(addresses.len());
+    for row in addresses {
+        let address_account = accounts::Model {
+            wallet_address: Some(row.address.clone()),
+            ..account.clone()
+        };
+        let connector = factory.create(&ConnectorContext { db, account: &address_account }).await?;
+        connectors.push((row.address, connector));
+    }
+    Ok(connectors)
+}
+
+/// Convert balances to the holdings JSON stored on the account.
+///
+/// IMPORTANT: Store ONLY asset symbol and quantity - NO price or valuation fields.
+/// This is a core design principle: account holdings are quantity-only.
+/// Valuation happens separately during portfolio construction using price reference data.
+///
+/// Note: The Balance struct may contain available/frozen fields (for internal use),
+/// but these are intentionally excluded from persisted holdings JSON.
+/// Do NOT add available/frozen/price/value/equity fields to the holdings JSON.
+/// `holding_source` is provenance (spot vs earn), not valuation; it is only written for
+/// non-spot balances so spot holdings keep the original two-field format.
+pub fn holdings_json(balances: &[Balance]) -> Vec<serde_json::Value> {
+    balances
+        .iter()
+        .map(|b| match &b.holding_source {
+            Some(source) => json!({
+                "asset": b.asset,
+                "quantity": b.quantity,
+                "holding_source": source,
+            }),
+            None => json!({
+                "asset": b.asset,
+                "quantity": b.quantity,
+            }),
+        })
+        .collect()
+}
+
+/// Record what changed between the account's stored holdings and freshly read balances:
+/// flag anomalies, append holding ledger rows and record detected income.
+///
+/// Used by sync and by statement imports so both produce the same history. Failures are
+/// logged but never fail the caller.
+pub async fn record_balance_changes(db: &DatabaseConnection, account: &accounts::Model, balances: &[Balance]) {
+    let account_id = account.id;
+
+    // Flag suspicious changes (large drops, emptied wallet) against the previous holdings
+    let previous_holdings: Vec<AccountHolding> = account
+        .holdings
+        .clone()
+        .and_then(|h| serde_json::from_value(h).ok())
+        .unwrap_or_default();
+    let anomalies = anomaly_detection::detect_holding_anomalies(
+        &previous_holdings,
+        balances,
+        anomaly_detection::drop_threshold_from_env(),
+    );
+    if !anomalies.is_empty() {
+        if let Err(e) = anomaly_detection::record_anomalies(
+            db,
+            account.user_id,
+            account_id,
+            &account.name,
+            &anomalies,
+        )
+        .await
+        {
+            tracing::error!("Failed to record holding anomalies for account {}: {}", account_id, e);
+        }
+    }
+
+    // Append quantity changes to the holding ledger, superseding recent rows that this
+    // sync reverts (e.g. a transient wrong RPC balance) instead of appending blindly
+    match holding_ledger::record_sync_transactions(db, account_id, &previous_holdings, balances).await {
+        Ok(summary) if summary.corrected > 0 => tracing::info!(
+            "Holding ledger for account {}: {} appended, {} corrected",
+            account_id,
+            summary.appended,
+            summary.corrected
+        ),
+        Ok(_) => {}
+        Err(e) => tracing::error!("Failed to record holding transactions for account {}: {}", account_id, e),
+    }
+
+    // Staking rewards, interest and airdrops show up as balance growth between syncs
+    match income_events::record_sync_income(db, account, &previous_holdings, balances).await {
+        Ok(count) if count > 0 => tracing::info!("Recorded {} income events for account {}", count, account_id),
+        Ok(_) => {}
+        Err(e) => tracing::error!("Failed to record income events for account {}: {}", account_id, e),
+    }
+}
+
+/// Stages of an account sync, in the order they run
+pub const SYNC_STAGES: &[&str] = &[
+    "connecting",
+    "balances",
+    "defi_positions",
+    "positions",
+    "nfts",
+    "multisig",
+    "trades",
+    "transfers",
+    "fees",
+    "staking_rewards",
+    "saving",
+];
+
+/// Receives the progress of an account sync
+#[async_trait]
+pub trait SyncProgress: Send + Sync {
+    /// Called as the sync enters `stage`, one of [`SYNC_STAGES`]
+    async fn stage(&self, stage: &'static str);
+
+    /// Called as reading a chain of the wallet at `address` starts or finishes, during the
+    /// "balances" stage of multi-chain wallets
+    fn chain(&self, _address: Option<&str>, _progress: &ChainProgress) {}
 }
 
 /// Sync a single account and create a snapshot
 pub async fn sync_account(
     db: &DatabaseConnection,
     account_id: Uuid,
+) -> Result<SyncResult, Box<dyn Error + Send + Sync>> {
+    sync_account_with_progress(db, account_id, None).await
+}
+
+/// Per-chain outcome of a sync's balance fetch, stored in `sync_runs.chains`
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+pub struct SyncChainResult {
+    /// Address read, for wallets listing several addresses
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub address: Option<String>,
+    #[serde(flatten)]
+    pub fetch: ChainFetch,
+}
+
+/// What a sync went through, kept for its `sync_runs` row
+#[derive(Default)]
+struct SyncRunLog {
+    stage: Mutex<Option<&'static str>>,
+    chains: Mutex<Vec<SyncChainResult>>,
+}
+
+impl SyncRunLog {
+    fn set_stage(&self, stage: &'static str) {
+        *self.stage.lock().unwrap_or_else(|e| e.into_inner()) = Some(stage);
+    }
+
+    fn stage(&self) -> Option<&'static str> {
+        *self.stage.lock().unwrap_or_else(|e| e.into_inner())
+    }
+
+    fn chains(&self) -> Vec<SyncChainResult> {
+        self.chains.lock().unwrap_or_else(|e| e.into_inner()).clone()
+    }
+
+    fn record_chain(&self, address: Option<&str>, fetch: ChainFetch) {
+        let result = SyncChainResult { address: address.map(str::to_string), fetch };
+        self.chains.lock().unwrap_or_else(|e| e.into_inner()).push(result);
+    }
+}
+
+/// Pass the progress of a chain to `progress` and keep the chain's outcome for the sync run
+fn report_chain(progress: Option<&dyn SyncProgress>, log: &SyncRunLog, address: Option<&str>, event: ChainProgress) {
+    if let Some(progress) = progress {
+        progress.chain(address, &event);
+    }
+    if let ChainProgress::Finished(fetch) = event {
+        log.record_chain(address, fetch);
+    }
+}
+
+/// Sync a single account, reporting each stage and chain reached to `progress`.
+///
+/// Every attempt is recorded as a `sync_runs` row, whoever started it.
+pub async fn sync_account_with_progress(
+    db: &DatabaseConnection,
+    account_id: Uuid,
+    progress: Option<&dyn SyncProgress>,
+) -> Result<SyncResult, Box<dyn Error + Send + Sync>> {
+    let started_at = Utc::now();
+    let log = SyncRunLog::default();
+    let outcome = run_account_sync(db, account_id, progress, &log).await;
+
+    let (success, holdings_count, error, error_kind) = match &outcome {
+        Ok(result) => (result.success, result.holdings_count, result.error.clone(), result.error_kind.clone()),
+        Err(e) => (false, 0, Some(format!("Sync failed: {}", e)), None),
+    };
+    let chains = log.chains();
+    let run = sync_runs::ActiveModel {
+        id: ActiveValue::Set(Uuid::new_v4()),
+        account_id: ActiveValue::Set(account_id),
+        started_at: ActiveValue::Set(started_at.into()),
+        finished_at: ActiveValue::Set(Utc::now().into()),
+        success: ActiveValue::Set(success),
+        holdings_count: ActiveValue::Set(holdings_count as i32),
+        stage: ActiveValue::Set(log.stage().map(str::to_string)),
+        error: ActiveValue::Set(error),
+        error_kind: ActiveValue::Set(error_kind),
+        chains: ActiveValue::Set((!chains.is_empty()).then(|| json!(chains))),
+    };
+    if let Err(e) = run.insert(db).await {
+        tracing::warn!("Failed to record sync run for account {}: {}", account_id, e);
+    }
+    outcome
+}
+
+async fn run_account_sync(
+    db: &DatabaseConnection,
+    account_id: Uuid,
+    progress: Option<&dyn SyncProgress>,
+    log: &SyncRunLog,
 ) -> Result<SyncResult, Box<dyn Error + Send + Sync>> {
     tracing::info!("Starting sync for account {}", account_id);
+    let report = |stage: &'static str| async move {
+        log.set_stage(stage);
+        if let Some(progress) = progress {
+            progress.stage(stage).await;
+        }
+    };
 
     // Fetch account from database
     let account = accounts::Entity::find_by_id(account_id)
@@ -53,135 +312,65 @@ pub async fn sync_account(
             account_id,
             success: false,
             error: Some("Account is not active".to_string()),
+            error_kind: None,
             holdings_count: 0,
         });
     }
 
-    // Handle different account types
-    let connector: Box<dyn ExchangeConnector> = match account.account_type.as_str() {
-        "exchange" => {
-            // Handle exchange accounts (OKX)
-            let exchange_name = account
-                .exchange_name
-                .as_ref()
-                .ok_or_else(|| "Exchange name not set")?;
-
-            if exchange_name.to_lowercase() != "okx" {
-                return Ok(SyncResult {
-                    account_id,
-                    success: false,
-                    error: Some(format!("Unsupported exchange: {}", exchange_name)),
-                    holdings_count: 0,
-                });
-            }
+    // Look up the connector for the account type and exchange/wallet kind
+    report("connecting").await;
+    let registry = ConnectorRegistry::builtin();
+    let Some(factory) = registry.find(&account.account_type, account.exchange_name.as_deref()) else {
+        let error = if registry.names(&account.account_type).is_empty() {
+            format!("Unsupported account type: {}", account.account_type)
+        } else {
+            format!(
+                "Unsupported {}: {}",
+                account.account_type,
+                account.exchange_name.as_deref().unwrap_or("none")
+            )
+        };
+        return Ok(SyncResult {
+            account_id,
+            success: false,
+            error: Some(error),
+            error_kind: None,
+            holdings_count: 0,
+        });
+    };
 
-            // Get API credentials
-            let api_key = account
-                .api_key_encrypted
-                .as_ref()
-                .ok_or_else(|| "API key not set")?;
-            let api_secret = account
-                .api_secret_encrypted
-                .as_ref()
-                .ok_or_else(|| "API secret not set")?;
-            let passphrase = account
-                .passphrase_encrypted
-                .as_ref()
-                .ok_or_else(|| "Passphrase not set")?;
-
-            // Decrypt credentials
-            let api_key = decrypt_credential(api_key)?;
-            let api_secret = decrypt_credential(api_secret)?;
-            let passphrase = decrypt_credential(passphrase)?;
-
-            // Create OKX connector
-            Box::new(OkxConnector::new(api_key, api_secret, passphrase))
-        }
-        "wallet" => {
-            // Handle wallet accounts (EVM or Solana)
-            let wallet_address = account
-                .wallet_address
-                .as_ref()
-                .ok_or_else(|| "Wallet address not set")?;
-
-            // Check exchange_name to determine wallet type
-            // If exchange_name is "solana", Solana connector would be used (not yet available)
-            // Otherwise, use EVM connector for all other chains
-            match account.exchange_name.as_deref() {
-                Some("solana") => {
-                    // Use SOLANA_RPC_URL env var; fall back to public mainnet endpoint
-                    let rpc_url = std::env::var("SOLANA_RPC_URL")
-                        .unwrap_or_else(|_| "https://api.mainnet-beta.solana.com".to_string());
-                    let db_tokens = load_solana_tokens_from_db(db).await;
-                    Box::new(SolanaConnector::new(
-                        wallet_address.clone(),
-                        rpc_url,
-                        db_tokens,
-                    ))
-                }
-                _ => {
-                    // Load all active EVM chains from DB (carries chain_id, rpc_url, native_symbol)
-                    let all_chains = load_evm_chains_from_db(db).await;
-
-                    // Filter to the account's enabled_chains subset, or use all if unset
-                    let chains = if let Some(enabled_chains_json) = &account.enabled_chains {
-                        match serde_json::from_value::<Vec<String>>(enabled_chains_json.clone()) {
-                            Ok(chain_names) => {
-                                let filtered: Vec<EvmChain> = all_chains
-                                    .iter()
-                                    .filter(|c| chain_names.contains(&c.name().to_string()))
-                                    .cloned()
-                                    .collect();
-                                if filtered.is_empty() {
-                                    tracing::warn!(
-                                        "None of enabled_chains {:?} matched active DB chains; using all",
-                                        chain_names
-                                    );
-                                    all_chains
-                                } else {
-                                    filtered
-                                }
-                            }
-                            Err(e) => {
-                                tracing::warn!("Failed to parse enabled_chains: {}, using all chains", e);
-                                all_chains
-                            }
-                        }
-                    } else {
-                        all_chains
-                    };
-
-                    // Load token list from DB; fall back to built-in list on error
-                    let db_tokens = load_tokens_from_db(db).await;
-
-                    // RPC URLs are already embedded in each EvmChain struct (loaded from DB above).
-                    // No separate rpc_url override map is needed.
-                    match EvmConnector::new_with_tokens(wallet_address.clone(), chains, db_tokens, None) {
-                        Ok(connector) => Box::new(connector),
-                        Err(e) => {
-                            return Ok(SyncResult {
-                                account_id,
-                                success: false,
-                                error: Some(format!("Failed to create EVM connector: {}", e)),
-                                holdings_count: 0,
-                            });
-                        }
-                    }
-                }
-            }
+    let connector = match factory.create(&ConnectorContext { db, account: &account }).await {
+        Ok(connector) => connector,
+        Err(e) => {
+            return Ok(SyncResult {
+                account_id,
+                success: false,
+                error: Some(format!("Failed to create {} connector: {}", factory.name(), e)),
+                error_kind: e.downcast_ref::<ConnectorError>().map(|ce| ce.kind().to_string()),
+                holdings_count: 0,
+            });
         }
-        other => {
+    };
+
+    // Wallet accounts may list more addresses; each is read with its own connector
+    let extra_connectors = match address_connectors(db, factory, &account).await {
+        Ok(connectors) => connectors,
+        Err(e) => {
             return Ok(SyncResult {
                 account_id,
                 success: false,
-                error: Some(format!("Unsupported account type: {}", other)),
+                error: Some(format!("Failed to create {} connector for an additional address: {}", factory.name(), e)),
+                error_kind: e.downcast_ref::<ConnectorError>().map(|ce| ce.kind().to_string()),
                 holdings_count: 0,
             });
         }
     };
 
     // Fetch balances
-    let balances = match connector.fetch_spot_balances().await {
+    report("balances").await;
+    let wallet_address = account.wallet_address.clone();
+    let on_chain = |event| report_chain(progress, log, wallet_address.as_deref(), event);
+    let mut balances = match connector.fetch_spot_balances_by_chain(&on_chain).await {
         Ok(balances) => balances,
         Err(e) => {
             tracing::error!("Failed to fetch balances for account {}: {}", account_id, e);
@@ -189,39 +378,178 @@ pub async fn sync_account(
                 account_id,
                 success: false,
                 error: Some(format!("Failed to fetch balances: {}", e)),
+                error_kind: e.downcast_ref::<ConnectorError>().map(|ce| ce.kind().to_string()),
                 holdings_count: 0,
             });
         }
     };
 
+    // Sum the balances of every address; one failing address fails the sync rather than
+    // storing holdings that silently miss it
+    for (address, extra) in &extra_connectors {
+        let on_chain = |event| report_chain(progress, log, Some(address.as_str()), event);
+        match extra.fetch_spot_balances_by_chain(&on_chain).await {
+            Ok(extra_balances) => balances.extend(extra_balances),
+            Err(e) => {
+                tracing::error!("Failed to fetch balances of {} for account {}: {}", address, account_id, e);
+                return Ok(SyncResult {
+                    account_id,
+                    success: false,
+                    error: Some(format!("Failed to fetch balances of {}: {}", address, e)),
+                    error_kind: e.downcast_ref::<ConnectorError>().map(|ce| ce.kind().to_string()),
+                    holdings_count: 0,
+                });
+            }
+        }
+    }
+    if !extra_connectors.is_empty() {
+        balances = merge_balances(balances);
+    }
+
+    // DeFi adapters (lending, LP, perps, ...) run after the raw balances of every address
+    let addresses: Vec<String> = account
+        .wallet_address
+        .iter()
+        .cloned()
+        .chain(extra_connectors.iter().map(|(address, _)| address.clone()))
+        .collect();
+    report("defi_positions").await;
+    let defi = defi::sync_defi_positions(db, &account, &addresses).await;
+    if !defi.balances.is_empty() {
+        balances.extend(defi.balances);
+        balances = merge_balances(balances);
+    }
+
+    // Consolidate sub-account balances when the account opts in (master account keys only)
+    let settings = AccountSettings::from_json(account.settings.as_ref());
+    if settings.include_sub_accounts && connector.supports_sub_accounts() {
+        balances.extend(fetch_sub_account_balances(connector.as_ref(), account_id).await);
+        balances = merge_balances(balances);
+    }
+
     tracing::info!(
         "Fetched {} balances for account {}",
         balances.len(),
         account_id
     );
 
-    // Convert balances to holdings JSON format
-    // IMPORTANT: Store ONLY asset symbol and quantity - NO price or valuation fields.
-    // This is a core design principle: account holdings are quantity-only.
-    // Valuation happens separately during portfolio construction using price reference data.
-    // 
-    // Note: The Balance struct may contain available/frozen fields (for internal use),
-    // but these are intentionally excluded from persisted holdings JSON.
-    // Do NOT add available/frozen/price/value/equity fields to the holdings JSON.
-    let holdings: Vec<serde_json::Value> = balances
-        .iter()
-        .map(|b| {
-            json!({
-                "asset": b.asset,
-                "quantity": b.quantity,
-            })
-        })
-        .collect();
-
+    let holdings = holdings_json(&balances);
     let holdings_count = holdings.len();
+    record_balance_changes(db, &account, &balances).await;
+
+    // Derivatives positions are synced best-effort: on failure the previously stored
+    // positions are kept and the balance sync still succeeds
+    report("positions").await;
+    let positions = connector.fetch_positions().await.and_then(|mut positions| {
+        if !defi.complete {
+            return Err("a DeFi protocol read failed".into());
+        }
+        positions.extend(defi.positions);
+        Ok(positions)
+    });
+    match positions {
+        Ok(positions) => match position_sync::replace_positions(db, account_id, &positions).await {
+            Ok(count) if count > 0 => tracing::info!("Stored {} open positions for account {}", count, account_id),
+            Ok(_) => {}
+            Err(e) => tracing::error!("Failed to store positions for account {}: {}", account_id, e),
+        },
+        Err(e) => tracing::warn!("Failed to fetch positions for account {}: {}", account_id, e),
+    }
+
+    // NFTs are replaced best-effort too; a failed discovery keeps the previously stored NFTs
+    report("nfts").await;
+    let mut nfts = connector.fetch_nfts().await;
+    for (_, extra) in &extra_connectors {
+        if let Ok(found) = nfts.as_mut() {
+            match extra.fetch_nfts().await {
+                Ok(extra_nfts) => found.extend(extra_nfts),
+                Err(e) => nfts = Err(e),
+            }
+        }
+    }
+    match nfts {
+        Ok(nfts) => match nft_sync::replace_nft_holdings(db, account_id, &nfts).await {
+            Ok(count) if count > 0 => tracing::info!("Stored {} NFTs for account {}", count, account_id),
+            Ok(_) => {}
+            Err(e) => tracing::error!("Failed to store NFTs for account {}: {}", account_id, e),
+        },
+        Err(e) => tracing::warn!("Failed to fetch NFTs for account {}: {}", account_id, e),
+    }
+
+    // Multisig owners/modules are kept for display; a failed lookup keeps the stored configuration
+    report("multisig").await;
+    let multisig = match connector.fetch_multisig().await {
+        Ok(deployments) if !deployments.is_empty() => serde_json::to_value(&deployments).ok(),
+        Ok(_) => None,
+        Err(e) => {
+            tracing::warn!("Failed to fetch multisig configuration for account {}: {}", account_id, e);
+            None
+        }
+    };
+
+    // Trade fills are appended best-effort as well; the next sync resumes from the latest stored trade
+    report("trades").await;
+    match trade_sync::sync_trades(db, account_id, connector.as_ref()).await {
+        Ok(count) if count > 0 => tracing::info!("Stored {} new trades for account {}", count, account_id),
+        Ok(_) => {}
+        Err(e) => tracing::warn!("Failed to sync trades for account {}: {}", account_id, e),
+    }
+
+    // Deposits and withdrawals, kept apart from holdings so cash flows can be separated from performance
+    report("transfers").await;
+    match transfer_sync::sync_transfers(db, account_id, connector.as_ref()).await {
+        Ok(count) if count > 0 => tracing::info!("Stored {} new transfers for account {}", count, account_id),
+        Ok(_) => {}
+        Err(e) => tracing::warn!("Failed to sync transfers for account {}: {}", account_id, e),
+    }
+
+    // Trading, withdrawal and gas fees, captured after the trades and transfers they come from
+    report("fees").await;
+    let fee_connectors: Vec<&dyn ExchangeConnector> =
+        std::iter::once(connector.as_ref()).chain(extra_connectors.iter().map(|(_, c)| c.as_ref())).collect();
+    match fee_sync::sync_fees(db, account_id, &fee_connectors).await {
+        Ok(count) if count > 0 => tracing::info!("Stored {} new fees for account {}", count, account_id),
+        Ok(_) => {}
+        Err(e) => tracing::warn!("Failed to sync fees for account {}: {}", account_id, e),
+    }
+
+    // Staking rewards are recorded as income; the next sync resumes after the latest stored epoch
+    report("staking_rewards").await;
+    match staking_rewards::sync_staking_rewards(db, account_id, connector.as_ref()).await {
+        Ok(count) if count > 0 => tracing::info!("Stored {} new staking rewards for account {}", count, account_id),
+        Ok(_) => {}
+        Err(e) => tracing::warn!("Failed to sync staking rewards for account {}: {}", account_id, e),
+    }
+
+    // A wallet's first transaction date is looked up until found, then kept; it bounds
+    // backfills and is shown as the account age
+    report("saving").await;
+    let first_activity_at = if account.first_activity_at.is_none() {
+        // The earliest over all addresses; kept unset when any lookup fails so a later one retries
+        let mut first_activity = None;
+        for connector in std::iter::once(&connector).chain(extra_connectors.iter().map(|(_, c)| c)) {
+            match connector.fetch_first_activity().await {
+                Ok(first) => first_activity = first_activity.into_iter().chain(first).min(),
+                Err(e) => {
+                    tracing::warn!("Failed to look up first activity for account {}: {}", account_id, e);
+                    first_activity = None;
+                    break;
+                }
+            }
+        }
+        first_activity
+    } else {
+        None
+    };
 
     // Update account's last_synced_at and holdings
     let mut account_update: accounts::ActiveModel = account.into();
+    if let Some(first) = first_activity_at {
+        account_update.first_activity_at = ActiveValue::Set(Some(first.into()));
+    }
+    if let Some(multisig) = multisig {
+        account_update.multisig = ActiveValue::Set(Some(multisig));
+    }
     account_update.last_synced_at = ActiveValue::Set(Some(Utc::now().into()));
     account_update.holdings = ActiveValue::Set(Some(
         serde_json::to_value(&holdings)
@@ -240,173 +568,15 @@ pub async fn sync_account(
         account_id,
         success: true,
         error: None,
+        error_kind: None,
         holdings_count,
     })
 }
 
-/// Sync all active accounts for a user
-pub async fn sync_user_accounts(
-    db: &DatabaseConnection,
-    user_id: Uuid,
-) -> Result<Vec<SyncResult>, Box<dyn Error + Send + Sync>> {
-    tracing::info!("Starting sync for all accounts of user {}", user_id);
-
-    // Fetch all active accounts for the user
-    let accounts = accounts::Entity::find()
-        .filter(accounts::Column::UserId.eq(user_id))
-        .filter(accounts::Column::IsActive.eq(true))
-        .all(db)
-        .await?;
-
-    tracing::info!("Found {} active accounts for user {}", accounts.len(), user_id);
-
-    let mut results = Vec::new();
-
-    for account in accounts {
-        match sync_account(db, account.id).await {
-            Ok(result) => results.push(result),
-            Err(e) => {
-                tracing::error!("Failed to sync account {}: {}", account.id, e);
-                results.push(SyncResult {
-                    account_id: account.id,
-                    success: false,
-                    error: Some(format!("Sync failed: {}", e)),
-                    holdings_count: 0,
-                });
-            }
-        }
-    }
-
-    tracing::info!(
-        "Completed sync for user {}: {} successful, {} failed",
-        user_id,
-        results.iter().filter(|r| r.success).count(),
-        results.iter().filter(|r| !r.success).count()
-    );
-
-    Ok(results)
-}
-
-/// Load active EVM tokens from the database grouped by chain name.
-///
-/// Returns `Some(map)` when the table is reachable and contains rows.
-/// Falls back to `None` on any DB error so the EVM connector uses its built-in token list.
-async fn load_tokens_from_db(
-    db: &DatabaseConnection,
-) -> Option<HashMap<String, Vec<(String, String)>>> {
-    match evm_tokens::Entity::find()
-        .filter(evm_tokens::Column::IsActive.eq(true))
-        .all(db)
-        .await
-    {
-        Ok(rows) if !rows.is_empty() => {
-            let mut map: HashMap<String, Vec<(String, String)>> = HashMap::new();
-            for row in rows {
-                map.entry(row.chain)
-                    .or_default()
-                    .push((row.symbol, row.contract_address));
-            }
-            tracing::info!(
-                "Loaded {} active EVM tokens from DB across {} chains",
-                map.values().map(|v| v.len()).sum::<usize>(),
-                map.len()
-            );
-            Some(map)
-        }
-        Ok(_) => {
-            // Table exists but is empty – fall back to built-in list
-            tracing::warn!("evm_tokens table is empty, falling back to built-in token list");
-            None
-        }
-        Err(e) => {
-            tracing::warn!(
-                "Failed to load EVM tokens from DB: {}, falling back to built-in token list",
-                e
-            );
-            None
-        }
-    }
-}
-
-/// Load all active EVM chains from the database as [`EvmChain`] structs.
-///
-/// Each returned struct carries the chain's `chain_id`, `rpc_url`, and `native_symbol`
-/// directly from the `evm_chains` table, so no separate RPC URL map is needed.
-///
-/// Falls back to a small hardcoded set when the database is unreachable, ensuring
-/// the sync job can still operate in degraded-DB conditions.
-async fn load_evm_chains_from_db(db: &DatabaseConnection) -> Vec<EvmChain> {
-    match evm_chains::Entity::find()
-        .filter(evm_chains::Column::IsActive.eq(true))
-        .all(db)
-        .await
-    {
-        Ok(rows) if !rows.is_empty() => {
-            tracing::info!("Loaded {} active EVM chains from DB", rows.len());
-            rows.into_iter()
-                .map(|r| EvmChain::new(r.chain_id, r.rpc_url, r.native_symbol))
-                .collect()
-        }
-        Ok(_) => {
-            tracing::warn!("evm_chains table is empty, using hardcoded defaults");
-            EvmChain::defaults()
-        }
-        Err(e) => {
-            tracing::warn!("Failed to load EVM chains from DB: {}, using hardcoded defaults", e);
-            EvmChain::defaults()
-        }
-    }
-}
-
-/// Load active Solana SPL tokens from the database.
-///
-/// Returns `Some(vec)` of `(symbol, mint_address)` pairs when the table is reachable
-/// and contains rows. Falls back to `None` on any DB error so the Solana connector
-/// uses its built-in token list.
-async fn load_solana_tokens_from_db(
-    db: &DatabaseConnection,
-) -> Option<Vec<(String, String)>> {
-    match solana_tokens::Entity::find()
-        .filter(solana_tokens::Column::IsActive.eq(true))
-        .all(db)
-        .await
-    {
-        Ok(rows) if !rows.is_empty() => {
-            let pairs: Vec<(String, String)> = rows
-                .into_iter()
-                .map(|row| (row.symbol, row.mint_address))
-                .collect();
-            tracing::info!(
-                "Loaded {} active Solana tokens from DB",
-                pairs.len()
-            );
-            Some(pairs)
-        }
-        Ok(_) => {
-            tracing::warn!("solana_tokens table is empty, falling back to built-in token list");
-            None
-        }
-        Err(e) => {
-            tracing::warn!(
-                "Failed to load Solana tokens from DB: {}, falling back to built-in token list",
-                e
-            );
-            None
-        }
-    }
-}
-
 #[cfg(test)]
 mod tests {
     use super::*;
 
-    #[test]
-    fn test_decrypt_credential() {
-        let encrypted = "test-credential";
-        let decrypted = decrypt_credential(encrypted).unwrap();
-        assert_eq!(decrypted, encrypted);
-    }
-
     #[test]
     fn test_holdings_format_qty_only() {
         // Test that holdings JSON contains only asset and quantity, no price/value
@@ -419,6 +589,7 @@ mod tests {
                 available: "1.2".to_string(),
                 frozen: "0.3".to_string(),
                 decimals: Some(8),
+                holding_source: None,
             },
             Balance {
                 asset: "ETH".to_string(),
@@ -426,19 +597,11 @@ mod tests {
                 available: "8.0".to_string(),
                 frozen: "2.0".to_string(),
                 decimals: Some(18),
+                holding_source: None,
             },
         ];
 
-        // Simulate the holdings conversion logic from sync_account function
-        let holdings: Vec<serde_json::Value> = balances
-            .iter()
-            .map(|b| {
-                json!({
-                    "asset": b.asset,
-                    "quantity": b.quantity,
-                })
-            })
-            .collect();
+        let holdings = holdings_json(&balances);
 
         // Verify each holding has exactly 2 fields: asset and quantity
         for holding in holdings {
@@ -463,4 +626,29 @@ mod tests {
             assert!(!obj.contains_key("equity"), "Holdings must NOT have 'equity' field");
         }
     }
+
+    #[test]
+    fn test_sync_run_log() {
+        let log = SyncRunLog::default();
+        assert_eq!(log.stage(), None);
+        log.set_stage("connecting");
+        log.set_stage("balances");
+        let fetch = |chain: &str, error: Option<&str>| ChainFetch {
+            chain: chain.to_string(),
+            balances: if error.is_some() { 0 } else { 3 },
+            error: error.map(str::to_string),
+            duration_ms: 120,
+        };
+        log.record_chain(None, fetch("ethereum", None));
+        log.record_chain(Some("0xabc"), fetch("bsc", Some("token balances: timeout")));
+        assert_eq!(log.stage(), Some("balances"));
+
+        // Stored flat, one object per chain, without an address when there is none
+        let chains = json!(log.chains());
+        assert_eq!(chains[0], json!({"chain": "ethereum", "balances": 3, "duration_ms": 120}));
+        assert_eq!(chains[1]["address"], "0xabc");
+        assert_eq!(chains[1]["error"], "token balances: timeout");
+        let parsed: Vec<SyncChainResult> = serde_json::from_value(chains).unwrap();
+        assert_eq!(parsed, log.chains());
+    }
 }
@@ -0,0 +1,283 @@
+use crate::connectors::Balance;
+use crate::domain::AccountHolding;
+use crate::entities::{holding_anomalies, notifications, portfolio_accounts};
+use chrono::Utc;
+use rust_decimal::Decimal;
+use sea_orm::{
+    ActiveModelTrait, ActiveValue, ColumnTrait, DatabaseConnection, EntityTrait, PaginatorTrait,
+    QueryFilter,
+};
+use serde_json::json;
+use std::collections::HashMap;
+use std::error::Error;
+use std::str::FromStr;
+use tracing;
+use uuid::Uuid;
+
+/// Default percentage drop (per asset, between two syncs) that is flagged as an anomaly
+const DEFAULT_DROP_THRESHOLD_PERCENT: i64 = 50;
+
+/// Anomaly type: a single asset's quantity dropped by at least the configured threshold
+pub const ANOMALY_BALANCE_DROP: &str = "balance_drop";
+
+/// Anomaly type: the account previously held assets and now holds nothing
+pub const ANOMALY_WALLET_EMPTIED: &str = "wallet_emptied";
+
+/// Notification type raised for detected holding anomalies
+pub const NOTIFICATION_HOLDING_ANOMALY: &str = "holding_anomaly";
+
+/// A suspicious change detected between the previous and the freshly fetched holdings
+#[derive(Debug, Clone, PartialEq)]
+pub struct DetectedAnomaly {
+    pub anomaly_type: &'static str,
+    pub asset: Option<String>,
+    pub previous_quantity: Option<Decimal>,
+    pub current_quantity: Option<Decimal>,
+    pub drop_percent: Option<Decimal>,
+}
+
+/// Read the balance drop threshold from `ANOMALY_DROP_THRESHOLD_PERCENT` (default: 50)
+pub fn drop_threshold_from_env() -> Decimal {
+    std::env::var("ANOMALY_DROP_THRESHOLD_PERCENT")
+        .ok()
+        .and_then(|v| Decimal::from_str(&v).ok())
+        .filter(|v| *v > Decimal::ZERO)
+        .unwrap_or_else(|| Decimal::from(DEFAULT_DROP_THRESHOLD_PERCENT))
+}
+
+/// Compare the previously stored holdings with freshly fetched balances.
+///
+/// Flags either a single `wallet_emptied` anomaly (every previously held asset is gone)
+/// or one `balance_drop` anomaly per asset whose quantity fell by at least
+/// `drop_threshold_percent`. There is no transfer ledger to reconcile against yet,
+/// so every drop above the threshold is treated as unexplained.
+///
+/// Returns an empty list on the first sync (no previous holdings).
+pub fn detect_holding_anomalies(
+    previous: &[AccountHolding],
+    current: &[Balance],
+    drop_threshold_percent: Decimal,
+) -> Vec<DetectedAnomaly> {
+    let previous_totals = sum_by_asset(previous.iter().map(|h| (h.asset.as_str(), h.quantity.as_str())));
+    let current_totals = sum_by_asset(current.iter().map(|b| (b.asset.as_str(), b.quantity.as_str())));
+
+    if previous_totals.is_empty() {
+        return Vec::new();
+    }
+
+    if current_totals.is_empty() {
+        return vec![DetectedAnomaly {
+            anomaly_type: ANOMALY_WALLET_EMPTIED,
+            asset: None,
+            previous_quantity: None,
+            current_quantity: None,
+            drop_percent: Some(Decimal::ONE_HUNDRED),
+        }];
+    }
+
+    let mut anomalies: Vec<DetectedAnomaly> = previous_totals
+        .iter()
+        .filter_map(|(asset, previous_qty)| {
+            let current_qty = current_totals.get(asset).copied().unwrap_or(Decimal::ZERO);
+            if current_qty >= *previous_qty {
+                return None;
+            }
+
+            let drop_percent = ((*previous_qty - current_qty) / *previous_qty * Decimal::ONE_HUNDRED).round_dp(2);
+            if drop_percent < drop_threshold_percent {
+                return None;
+            }
+
+            Some(DetectedAnomaly {
+                anomaly_type: ANOMALY_BALANCE_DROP,
+                asset: Some(asset.clone()),
+                previous_quantity: Some(*previous_qty),
+                current_quantity: Some(current_qty),
+                drop_percent: Some(drop_percent),
+            })
+        })
+        .collect();
+
+    anomalies.sort_by(|a, b| a.asset.cmp(&b.asset));
+    anomalies
+}
+
+/// Sum positive quantities per asset, ignoring unparsable values
+pub(crate) fn sum_by_asset<'a>(items: impl Iterator<Item = (&'a str, &'a str)>) -> HashMap<String, Decimal> {
+    let mut totals: HashMap<String, Decimal> = HashMap::new();
+    for (asset, quantity) in items {
+        let quantity = Decimal::from_str(quantity).unwrap_or(Decimal::ZERO);
+        if quantity > Decimal::ZERO {
+            *totals.entry(asset.to_string()).or_insert(Decimal::ZERO) += quantity;
+        }
+    }
+    totals
+}
+
+/// Persist detected anomalies and raise a single high-priority notification for the user.
+///
+/// Returns the IDs of the inserted `holding_anomalies` rows.
+pub async fn record_anomalies(
+    db: &DatabaseConnection,
+    user_id: Uuid,
+    account_id: Uuid,
+    account_name: &str,
+    anomalies: &[DetectedAnomaly],
+) -> Result<Vec<Uuid>, Box<dyn Error + Send + Sync>> {
+    if anomalies.is_empty() {
+        return Ok(Vec::new());
+    }
+
+    let now = Utc::now();
+    let mut anomaly_ids = Vec::with_capacity(anomalies.len());
+
+    for anomaly in anomalies {
+        let row = holding_anomalies::ActiveModel {
+            id: ActiveValue::Set(Uuid::new_v4()),
+            user_id: ActiveValue::Set(user_id),
+            account_id: ActiveValue::Set(account_id),
+            anomaly_type: ActiveValue::Set(anomaly.anomaly_type.to_string()),
+            asset: ActiveValue::Set(anomaly.asset.clone()),
+            previous_quantity: ActiveValue::Set(anomaly.previous_quantity),
+            current_quantity: ActiveValue::Set(anomaly.current_quantity),
+            details: ActiveValue::Set(Some(json!({
+                "drop_percent": anomaly.drop_percent.map(|d| d.to_string()),
+            }))),
+            detected_at: ActiveValue::Set(now.into()),
+            acknowledged_at: ActiveValue::Set(None),
+        };
+        let inserted = row.insert(db).await?;
+        anomaly_ids.push(inserted.id);
+    }
+
+    let message = if anomalies.iter().any(|a| a.anomaly_type == ANOMALY_WALLET_EMPTIED) {
+        format!("Account '{}' was emptied since the last sync.", account_name)
+    } else {
+        let assets: Vec<&str> = anomalies.iter().filter_map(|a| a.asset.as_deref()).collect();
+        format!(
+            "Large unexplained balance drop on account '{}' for: {}.",
+            account_name,
+            assets.join(", ")
+        )
+    };
+
+    let notification = notifications::ActiveModel {
+        id: ActiveValue::Set(Uuid::new_v4()),
+        user_id: ActiveValue::Set(user_id),
+        notification_type: ActiveValue::Set(NOTIFICATION_HOLDING_ANOMALY.to_string()),
+        priority: ActiveValue::Set("high".to_string()),
+        title: ActiveValue::Set("Suspicious holdings change detected".to_string()),
+        message: ActiveValue::Set(format!(
+            "{} Automatic snapshots for affected portfolios are on hold until you acknowledge.",
+            message
+        )),
+        metadata: ActiveValue::Set(Some(json!({
+            "account_id": account_id,
+            "anomaly_ids": anomaly_ids,
+        }))),
+        read_at: ActiveValue::Set(None),
+        created_at: ActiveValue::Set(now.into()),
+    };
+    notification.insert(db).await?;
+
+    tracing::warn!(
+        "Recorded {} holding anomalies for account {}",
+        anomaly_ids.len(),
+        account_id
+    );
+
+    Ok(anomaly_ids)
+}
+
+/// Check whether automatic snapshots are on hold for a portfolio.
+///
+/// A portfolio is on hold while any of its accounts has an unacknowledged anomaly.
+pub async fn is_portfolio_snapshot_on_hold(
+    db: &DatabaseConnection,
+    portfolio_id: Uuid,
+) -> Result<bool, Box<dyn Error + Send + Sync>> {
+    let account_ids: Vec<Uuid> = portfolio_accounts::Entity::find()
+        .filter(portfolio_accounts::Column::PortfolioId.eq(portfolio_id))
+        .all(db)
+        .await?
+        .into_iter()
+        .map(|pa| pa.account_id)
+        .collect();
+
+    if account_ids.is_empty() {
+        return Ok(false);
+    }
+
+    let open_anomalies = holding_anomalies::Entity::find()
+        .filter(holding_anomalies::Column::AccountId.is_in(account_ids))
+        .filter(holding_anomalies::Column::AcknowledgedAt.is_null())
+        .count(db)
+        .await?;
+
+    Ok(open_anomalies > 0)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn holding(asset: &str, quantity: &str) -> AccountHolding {
+        AccountHolding {
+            asset: asset.to_string(),
+            quantity: quantity.to_string(),
+            available: None,
+            frozen: None,
+            decimals: None,
+            price_usd: None,
+            value_usd: None,
+            holding_source: None,
+        }
+    }
+
+    fn balance(asset: &str, quantity: &str) -> Balance {
+        Balance {
+            asset: asset.to_string(),
+            quantity: quantity.to_string(),
+            available: quantity.to_string(),
+            frozen: "0".to_string(),
+            decimals: None,
+            holding_source: None,
+        }
+    }
+
+    #[test]
+    fn test_first_sync_has_no_anomalies() {
+        let anomalies = detect_holding_anomalies(&[], &[balance("BTC", "1")], Decimal::from(50));
+        assert!(anomalies.is_empty());
+    }
+
+    #[test]
+    fn test_wallet_emptied() {
+        let previous = vec![holding("BTC", "1"), holding("ETH", "10")];
+        let anomalies = detect_holding_anomalies(&previous, &[], Decimal::from(50));
+
+        assert_eq!(anomalies.len(), 1);
+        assert_eq!(anomalies[0].anomaly_type, ANOMALY_WALLET_EMPTIED);
+        assert!(anomalies[0].asset.is_none());
+    }
+
+    #[test]
+    fn test_large_drop_is_flagged_small_drop_is_not() {
+        let previous = vec![holding("BTC", "1"), holding("ETH", "10"), holding("SOL", "5")];
+        let current = vec![balance("BTC", "0.9"), balance("ETH", "2")];
+        let anomalies = detect_holding_anomalies(&previous, &current, Decimal::from(50));
+
+        assert_eq!(anomalies.len(), 2);
+        assert_eq!(anomalies[0].asset.as_deref(), Some("ETH"));
+        assert_eq!(anomalies[0].drop_percent, Some(Decimal::from(80)));
+        assert_eq!(anomalies[1].asset.as_deref(), Some("SOL"));
+        assert_eq!(anomalies[1].current_quantity, Some(Decimal::ZERO));
+    }
+
+    #[test]
+    fn test_increase_is_not_flagged() {
+        let previous = vec![holding("USDT", "100")];
+        let current = vec![balance("USDT", "150")];
+        assert!(detect_holding_anomalies(&previous, &current, Decimal::from(50)).is_empty());
+    }
+}
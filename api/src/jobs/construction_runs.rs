@@ -0,0 +1,178 @@
+use crate::entities::{construction_runs, portfolio_allocations};
+use chrono::{DateTime, Utc};
+use rust_decimal::Decimal;
+use sea_orm::{ActiveModelTrait, ActiveValue, ColumnTrait, ConnectionTrait, DbErr, EntityTrait, QueryFilter};
+use serde::{Deserialize, Serialize};
+use sha2::{Digest, Sha256};
+use std::collections::BTreeMap;
+use utoipa::ToSchema;
+use uuid::Uuid;
+
+/// Trigger: `POST /api/v1/portfolios/{id}/construct`
+pub const TRIGGER_API: &str = "api";
+
+/// Everything an allocation is computed from besides prices.
+///
+/// Two runs with the same digest valued the same quantities under the same settings, so any
+/// difference between their results comes from the prices recorded in their `price_sources`.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize, ToSchema)]
+pub struct ConstructionInputs {
+    /// Accounts linked to the portfolio, sorted
+    pub account_ids: Vec<Uuid>,
+    /// Quantity held per holding symbol across those accounts (after exclusions)
+    #[schema(value_type = Object)]
+    pub quantities: BTreeMap<String, String>,
+    /// Portfolio settings in effect
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub settings: Option<serde_json::Value>,
+}
+
+impl ConstructionInputs {
+    /// Build inputs in a canonical order so equal inputs have equal digests
+    pub fn new(
+        mut account_ids: Vec<Uuid>,
+        quantities: impl IntoIterator<Item = (String, Decimal)>,
+        settings: Option<serde_json::Value>,
+    ) -> Self {
+        account_ids.sort();
+        Self {
+            account_ids,
+            quantities: quantities
+                .into_iter()
+                .map(|(asset, quantity)| (asset, quantity.normalize().to_string()))
+                .collect(),
+            settings,
+        }
+    }
+
+    /// SHA-256 of the inputs' JSON, hex encoded
+    pub fn digest(&self) -> String {
+        let json = serde_json::to_vec(self).unwrap_or_default();
+        Sha256::digest(&json).iter().map(|b| format!("{:02x}", b)).collect()
+    }
+}
+
+/// The price a construction run valued one holding with
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize, ToSchema)]
+pub struct PriceSource {
+    /// Holding symbol (e.g. "ETH-ethereum")
+    pub asset: String,
+    /// Canonical asset, when the symbol could be mapped
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub asset_id: Option<Uuid>,
+    /// `asset_prices` row used; `None` when the holding was unpriced
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub price_id: Option<Uuid>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub price_usd: Option<String>,
+    /// Price provider (e.g. "coinpaprika")
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub source: Option<String>,
+    /// Time of the price row
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub priced_at: Option<String>,
+    /// How the row was picked among the price sources ("primary", "fallback" or "stale")
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub resolution: Option<String>,
+}
+
+/// A finished construction to record
+#[derive(Debug)]
+pub struct NewConstructionRun {
+    pub portfolio_id: Uuid,
+    /// What triggered the run (e.g. [`TRIGGER_API`])
+    pub trigger: &'static str,
+    /// User who triggered it, if any
+    pub triggered_by: Option<Uuid>,
+    pub inputs: ConstructionInputs,
+    pub price_sources: Vec<PriceSource>,
+    pub total_value_usd: Decimal,
+    pub holdings_count: usize,
+    pub started_at: DateTime<Utc>,
+    pub duration_ms: i64,
+}
+
+/// Record a construction run, returning its ID.
+///
+/// Takes any connection so the run can be written in the same transaction as the allocation.
+pub async fn record_construction_run<C: ConnectionTrait>(
+    conn: &C,
+    run: NewConstructionRun,
+) -> Result<Uuid, DbErr> {
+    let id = Uuid::new_v4();
+    let price_sources =
+        serde_json::to_value(&run.price_sources).map_err(|e| DbErr::Custom(e.to_string()))?;
+    let inputs = serde_json::to_value(&run.inputs).map_err(|e| DbErr::Custom(e.to_string()))?;
+
+    construction_runs::ActiveModel {
+        id: ActiveValue::Set(id),
+        portfolio_id: ActiveValue::Set(run.portfolio_id),
+        trigger: ActiveValue::Set(run.trigger.to_string()),
+        triggered_by: ActiveValue::Set(run.triggered_by),
+        inputs_digest: ActiveValue::Set(run.inputs.digest()),
+        inputs: ActiveValue::Set(inputs),
+        price_sources: ActiveValue::Set(price_sources),
+        total_value_usd: ActiveValue::Set(run.total_value_usd),
+        holdings_count: ActiveValue::Set(run.holdings_count as i32),
+        duration_ms: ActiveValue::Set(run.duration_ms),
+        started_at: ActiveValue::Set(run.started_at.into()),
+        created_at: ActiveValue::NotSet,
+    }
+    .insert(conn)
+    .await?;
+
+    tracing::info!(
+        "Recorded construction run {} for portfolio {} ({} ms)",
+        id,
+        run.portfolio_id,
+        run.duration_ms
+    );
+
+    Ok(id)
+}
+
+/// The run that produced a portfolio's current allocation, if any
+pub async fn current_construction_run_id<C: ConnectionTrait>(
+    conn: &C,
+    portfolio_id: Uuid,
+) -> Result<Option<Uuid>, DbErr> {
+    Ok(portfolio_allocations::Entity::find()
+        .filter(portfolio_allocations::Column::PortfolioId.eq(portfolio_id))
+        .one(conn)
+        .await?
+        .and_then(|allocation| allocation.construction_run_id))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    #[test]
+    fn test_inputs_digest_is_canonical() {
+        let (a, b) = (Uuid::new_v4(), Uuid::new_v4());
+        let settings = Some(json!({"excluded_assets": ["USDT"]}));
+
+        let first = ConstructionInputs::new(
+            vec![a, b],
+            [("ETH".to_string(), Decimal::new(150, 2)), ("BTC".to_string(), Decimal::ONE)],
+            settings.clone(),
+        );
+        // Same inputs in another order, with a different decimal scale
+        let second = ConstructionInputs::new(
+            vec![b, a],
+            [("BTC".to_string(), Decimal::new(1000, 3)), ("ETH".to_string(), Decimal::new(15, 1))],
+            settings.clone(),
+        );
+        assert_eq!(first, second);
+        assert_eq!(first.digest(), second.digest());
+        assert_eq!(first.digest().len(), 64);
+
+        let changed = ConstructionInputs::new(
+            vec![a, b],
+            [("ETH".to_string(), Decimal::new(151, 2)), ("BTC".to_string(), Decimal::ONE)],
+            settings,
+        );
+        assert_ne!(first.digest(), changed.digest());
+    }
+}
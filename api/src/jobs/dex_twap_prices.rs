@@ -0,0 +1,163 @@
+use crate::connectors::evm::EvmChain;
+use crate::connectors::price_provider::price_provider_from_env;
+use crate::connectors::uniswap_twap::{fetch_token_decimals, fetch_twap_price, uniswap_v3_deployment};
+use crate::entities::{asset_contracts, asset_prices, assets, evm_chains};
+use crate::helpers::price_sanity::{screen_prices, PriceSanityConfig};
+use chrono::{DateTime, Timelike, Utc};
+use rust_decimal::Decimal;
+use sea_orm::{
+    sea_query::OnConflict, ActiveValue, ColumnTrait, DatabaseConnection, EntityTrait, QueryFilter, QueryOrder,
+};
+use std::collections::HashMap;
+use std::error::Error;
+use tracing;
+use uuid::Uuid;
+
+/// `asset_prices.source` of Uniswap v3 TWAP prices
+pub const PRICE_SOURCE_UNISWAP_V3_TWAP: &str = "uniswap_v3_twap";
+
+/// Default TWAP window in seconds
+const DEFAULT_WINDOW_SECONDS: u32 = 1800;
+
+/// Result of pricing long-tail tokens
+#[derive(Debug, Clone, Default)]
+pub struct DexTwapResult {
+    /// Contracts on a chain with a Uniswap v3 deployment
+    pub contracts_checked: usize,
+    /// Assets a TWAP price was stored for
+    pub prices_stored: usize,
+}
+
+fn window_seconds() -> u32 {
+    std::env::var("DEX_TWAP_WINDOW_SECONDS")
+        .ok()
+        .and_then(|v| v.parse::<u32>().ok())
+        .filter(|seconds| *seconds > 0)
+        .unwrap_or(DEFAULT_WINDOW_SECONDS)
+}
+
+fn twap_price_row(asset_id: Uuid, price_usd: Decimal, now: DateTime<Utc>) -> asset_prices::ActiveModel {
+    let timestamp = now.with_second(0).and_then(|t| t.with_nanosecond(0)).unwrap_or(now);
+    asset_prices::ActiveModel {
+        id: ActiveValue::Set(Uuid::new_v4()),
+        asset_id: ActiveValue::Set(asset_id),
+        timestamp: ActiveValue::Set(timestamp.into()),
+        price_usd: ActiveValue::Set(price_usd),
+        volume_24h_usd: ActiveValue::Set(None),
+        market_cap_usd: ActiveValue::Set(None),
+        change_percent_24h: ActiveValue::Set(None),
+        source: ActiveValue::Set(PRICE_SOURCE_UNISWAP_V3_TWAP.to_string()),
+        created_at: ActiveValue::Set(now.into()),
+        rank: ActiveValue::Set(None),
+        circulating_supply: ActiveValue::Set(None),
+        total_supply: ActiveValue::Set(None),
+        max_supply: ActiveValue::Set(None),
+        beta_value: ActiveValue::Set(None),
+        percent_change_1h: ActiveValue::Set(None),
+        percent_change_7d: ActiveValue::Set(None),
+        percent_change_30d: ActiveValue::Set(None),
+        ath_price: ActiveValue::Set(None),
+        ath_date: ActiveValue::Set(None),
+        percent_from_price_ath: ActiveValue::Set(None),
+    }
+}
+
+/// Price tokens the configured price provider does not list from their Uniswap v3 TWAP.
+///
+/// Every `asset_contracts` entry of an active asset without a provider coin ID, on a chain with
+/// a Uniswap v3 deployment, is priced over `DEX_TWAP_WINDOW_SECONDS` (default 1800) through the
+/// chain's RPC URL in `evm_chains`. An asset with contracts on several chains is priced from
+/// the first chain that has a pool. Prices are stored as `asset_prices` rows with source
+/// "uniswap_v3_twap" and pass the outlier check; allocation picks them up as a fallback source.
+pub async fn collect_dex_twap_prices(db: &DatabaseConnection) -> Result<DexTwapResult, Box<dyn Error + Send + Sync>> {
+    let provider = price_provider_from_env();
+    let window = window_seconds();
+
+    let contracts = asset_contracts::Entity::find()
+        .find_also_related(assets::Entity)
+        .filter(assets::Column::IsActive.eq(true))
+        .filter(provider.id_column().is_null())
+        .order_by_asc(asset_contracts::Column::AssetId)
+        .order_by_desc(asset_contracts::Column::IsVerified)
+        .all(db)
+        .await?;
+
+    // RPC URLs configured in evm_chains, over the built-in defaults
+    let mut rpc_urls: HashMap<String, String> = EvmChain::defaults()
+        .into_iter()
+        .map(|chain| (chain.name().to_string(), chain.rpc_url().to_string()))
+        .collect();
+    rpc_urls.extend(
+        evm_chains::Entity::find()
+            .filter(evm_chains::Column::IsActive.eq(true))
+            .all(db)
+            .await?
+            .into_iter()
+            .map(|chain| (chain.chain_id, chain.rpc_url)),
+    );
+
+    let mut result = DexTwapResult::default();
+    let mut priced: HashMap<Uuid, Decimal> = HashMap::new();
+    for (contract, asset) in contracts {
+        let Some(asset) = asset else { continue };
+        if priced.contains_key(&asset.id) {
+            continue;
+        }
+        let (Some(deployment), Some(rpc_url)) = (uniswap_v3_deployment(&contract.chain), rpc_urls.get(&contract.chain))
+        else {
+            continue;
+        };
+        result.contracts_checked += 1;
+
+        let decimals = match contract.decimals.and_then(|d| u8::try_from(d).ok()) {
+            Some(decimals) => decimals,
+            None => match fetch_token_decimals(rpc_url, &contract.contract_address).await {
+                Ok(decimals) => decimals,
+                Err(e) => {
+                    tracing::warn!("Failed to read decimals of {} on {}: {}", asset.symbol, contract.chain, e);
+                    continue;
+                }
+            },
+        };
+        match fetch_twap_price(rpc_url, &deployment, &contract.contract_address, decimals, window).await {
+            Ok(Some(price)) => {
+                tracing::debug!(
+                    "Priced {} at {} USD from Uniswap v3 pool {} on {}",
+                    asset.symbol,
+                    price.price_usd,
+                    price.pool,
+                    contract.chain
+                );
+                priced.insert(asset.id, price.price_usd);
+            }
+            Ok(None) => tracing::debug!("No Uniswap v3 TWAP for {} on {}", asset.symbol, contract.chain),
+            Err(e) => tracing::warn!("Failed to read the Uniswap v3 TWAP of {} on {}: {}", asset.symbol, contract.chain, e),
+        }
+    }
+
+    let now = Utc::now();
+    let rows: Vec<_> = priced.into_iter().map(|(asset_id, price)| twap_price_row(asset_id, price, now)).collect();
+    let rows = screen_prices(db, rows, &PriceSanityConfig::from_env()).await;
+    result.prices_stored = rows.len();
+    if !rows.is_empty() {
+        asset_prices::Entity::insert_many(rows)
+            .on_conflict(
+                OnConflict::columns([
+                    asset_prices::Column::AssetId,
+                    asset_prices::Column::Timestamp,
+                    asset_prices::Column::Source,
+                ])
+                .update_column(asset_prices::Column::PriceUsd)
+                .to_owned(),
+            )
+            .exec_without_returning(db)
+            .await?;
+    }
+
+    tracing::info!(
+        "Stored {} Uniswap v3 TWAP prices ({} contracts checked)",
+        result.prices_stored,
+        result.contracts_checked
+    );
+    Ok(result)
+}
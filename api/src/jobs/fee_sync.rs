@@ -0,0 +1,228 @@
+use crate::connectors::{ExchangeConnector, GasFee, TRANSFER_WITHDRAWAL};
+use crate::entities::{fees, trades, transfers};
+use crate::jobs::portfolio_pnl::LedgerPricer;
+use crate::jobs::transfer_sync::TRANSFER_LOOKBACK_DAYS;
+use chrono::{DateTime, Duration, Utc};
+use rust_decimal::Decimal;
+use sea_orm::{
+    sea_query::OnConflict, ActiveValue, ColumnTrait, DatabaseConnection, EntityTrait, QueryFilter,
+    QueryOrder,
+};
+use std::error::Error;
+use std::str::FromStr;
+use uuid::Uuid;
+
+/// Fee type: exchange trading fee of a trade fill
+pub const FEE_TYPE_TRADING: &str = "trading";
+
+/// Fee type: network fee charged on an exchange withdrawal
+pub const FEE_TYPE_WITHDRAWAL: &str = "withdrawal";
+
+/// Fee type: gas paid by a wallet transaction
+pub const FEE_TYPE_GAS: &str = "gas";
+
+/// A fee to be stored in `fees`
+#[derive(Debug, Clone, PartialEq)]
+pub struct FeeRecord {
+    pub fee_type: &'static str,
+    pub asset: String,
+    pub amount: Decimal,
+    /// Dedup key: the trade, withdrawal or transaction the fee was paid on
+    pub reference: String,
+    pub occurred_at: DateTime<Utc>,
+}
+
+/// Trading fees of stored trade fills; rebates and fills without a fee currency are left out
+pub fn trade_fees(fills: &[trades::Model]) -> Vec<FeeRecord> {
+    fills
+        .iter()
+        .filter_map(|t| {
+            let amount = t.fee.filter(|fee| *fee > Decimal::ZERO)?;
+            Some(FeeRecord {
+                fee_type: FEE_TYPE_TRADING,
+                asset: t.fee_currency.clone()?,
+                amount,
+                reference: format!("trade:{}", t.trade_id),
+                occurred_at: t.executed_at.with_timezone(&Utc),
+            })
+        })
+        .collect()
+}
+
+/// Network fees of stored withdrawals
+pub fn withdrawal_fees(withdrawals: &[transfers::Model]) -> Vec<FeeRecord> {
+    withdrawals
+        .iter()
+        .filter(|t| t.direction == TRANSFER_WITHDRAWAL)
+        .filter_map(|t| {
+            let amount = t.fee.filter(|fee| *fee > Decimal::ZERO)?;
+            Some(FeeRecord {
+                fee_type: FEE_TYPE_WITHDRAWAL,
+                asset: t.asset.clone(),
+                amount,
+                reference: format!("withdrawal:{}", t.transfer_id),
+                occurred_at: t.occurred_at.with_timezone(&Utc),
+            })
+        })
+        .collect()
+}
+
+/// Gas fees reported by a connector, skipping unparsable or zero amounts
+pub fn gas_fees(paid: &[GasFee]) -> Vec<FeeRecord> {
+    paid.iter()
+        .filter_map(|g| {
+            let amount = Decimal::from_str(&g.amount).ok().filter(|a| *a > Decimal::ZERO)?;
+            Some(FeeRecord {
+                fee_type: FEE_TYPE_GAS,
+                asset: g.asset.clone(),
+                amount,
+                reference: format!("gas:{}:{}", g.chain, g.tx_hash),
+                occurred_at: g.paid_at,
+            })
+        })
+        .collect()
+}
+
+/// Time of the account's latest stored fee of `fee_type`
+async fn latest_fee_at(
+    db: &DatabaseConnection,
+    account_id: Uuid,
+    fee_type: &str,
+) -> Result<Option<DateTime<Utc>>, sea_orm::DbErr> {
+    Ok(fees::Entity::find()
+        .filter(fees::Column::AccountId.eq(account_id))
+        .filter(fees::Column::FeeType.eq(fee_type))
+        .order_by_desc(fees::Column::OccurredAt)
+        .one(db)
+        .await?
+        .map(|f| f.occurred_at.with_timezone(&Utc)))
+}
+
+/// Capture the account's new fees into the `fees` table.
+///
+/// Trading and withdrawal fees are read from the trades and transfers already stored by this
+/// sync; gas is fetched from `connectors` (the account's address connectors). Each kind
+/// resumes at its latest stored fee (withdrawals a lookback earlier), and the unique (account_id, reference) index makes
+/// re-inserting a fee a no-op. Fees are valued at the time they were paid. Returns the
+/// number of new fees.
+pub async fn sync_fees(
+    db: &DatabaseConnection,
+    account_id: Uuid,
+    connectors: &[&dyn ExchangeConnector],
+) -> Result<u64, Box<dyn Error + Send + Sync>> {
+    let mut records = Vec::new();
+
+    let mut fills = trades::Entity::find().filter(trades::Column::AccountId.eq(account_id));
+    if let Some(since) = latest_fee_at(db, account_id, FEE_TYPE_TRADING).await? {
+        fills = fills.filter(trades::Column::ExecutedAt.gte(since));
+    }
+    records.extend(trade_fees(&fills.all(db).await?));
+
+    let mut withdrawals = transfers::Entity::find()
+        .filter(transfers::Column::AccountId.eq(account_id))
+        .filter(transfers::Column::Direction.eq(TRANSFER_WITHDRAWAL));
+    if let Some(since) = latest_fee_at(db, account_id, FEE_TYPE_WITHDRAWAL).await? {
+        // Withdrawals can be stored after newer ones, within the transfer sync's lookback
+        let since = since - Duration::days(TRANSFER_LOOKBACK_DAYS);
+        withdrawals = withdrawals.filter(transfers::Column::OccurredAt.gte(since));
+    }
+    records.extend(withdrawal_fees(&withdrawals.all(db).await?));
+
+    let gas_since = latest_fee_at(db, account_id, FEE_TYPE_GAS).await?;
+    for connector in connectors {
+        records.extend(gas_fees(&connector.fetch_gas_fees(gas_since).await?));
+    }
+
+    if records.is_empty() {
+        return Ok(0);
+    }
+
+    let mut pricer = LedgerPricer::new(db.clone());
+    let mut rows = Vec::with_capacity(records.len());
+    for record in records {
+        let price = pricer.price(&record.asset, record.occurred_at).await?;
+        rows.push(fees::ActiveModel {
+            id: ActiveValue::Set(Uuid::new_v4()),
+            account_id: ActiveValue::Set(account_id),
+            fee_type: ActiveValue::Set(record.fee_type.to_string()),
+            asset: ActiveValue::Set(record.asset),
+            amount: ActiveValue::Set(record.amount),
+            value_usd: ActiveValue::Set(price.map(|p| (p * record.amount).round_dp(8))),
+            reference: ActiveValue::Set(record.reference),
+            occurred_at: ActiveValue::Set(record.occurred_at.into()),
+            created_at: ActiveValue::NotSet,
+        });
+    }
+
+    let inserted = fees::Entity::insert_many(rows)
+        .on_conflict(
+            OnConflict::columns([fees::Column::AccountId, fees::Column::Reference])
+                .do_nothing()
+                .to_owned(),
+        )
+        .exec_without_returning(db)
+        .await?;
+
+    Ok(inserted)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_fee_records() {
+        let now = Utc::now();
+        let fill = trades::Model {
+            id: Uuid::new_v4(),
+            account_id: Uuid::new_v4(),
+            trade_id: "BTC-USDT:1".to_string(),
+            order_id: None,
+            instrument: "BTC-USDT".to_string(),
+            instrument_type: "SPOT".to_string(),
+            base_asset: Some("BTC".to_string()),
+            quote_asset: Some("USDT".to_string()),
+            side: "buy".to_string(),
+            quantity: Decimal::ONE,
+            price: Decimal::from(65000),
+            fee: Some(Decimal::new(65, 0)),
+            fee_currency: Some("USDT".to_string()),
+            executed_at: now.into(),
+            created_at: now.into(),
+        };
+        let rebate = trades::Model { trade_id: "BTC-USDT:2".to_string(), fee: Some(Decimal::new(-1, 1)), ..fill.clone() };
+        let fees = trade_fees(&[fill, rebate]);
+        assert_eq!(fees.len(), 1);
+        assert_eq!(fees[0].reference, "trade:BTC-USDT:1");
+        assert_eq!(fees[0].asset, "USDT");
+
+        let withdrawal = transfers::Model {
+            id: Uuid::new_v4(),
+            account_id: Uuid::new_v4(),
+            transfer_id: "w1".to_string(),
+            direction: TRANSFER_WITHDRAWAL.to_string(),
+            asset: "BTC".to_string(),
+            amount: Decimal::ONE,
+            fee: Some(Decimal::new(2, 4)),
+            network: None,
+            tx_hash: None,
+            occurred_at: now.into(),
+            created_at: now.into(),
+        };
+        let deposit = transfers::Model { direction: "deposit".to_string(), ..withdrawal.clone() };
+        let fees = withdrawal_fees(&[withdrawal, deposit]);
+        assert_eq!(fees.len(), 1);
+        assert_eq!((fees[0].fee_type, fees[0].amount), (FEE_TYPE_WITHDRAWAL, Decimal::new(2, 4)));
+
+        let gas = GasFee {
+            chain: "ethereum".to_string(),
+            tx_hash: "0xabc".to_string(),
+            asset: "ETH-ethereum".to_string(),
+            amount: "0.00042".to_string(),
+            paid_at: now,
+        };
+        let fees = gas_fees(&[gas.clone(), GasFee { amount: "0".to_string(), ..gas }]);
+        assert_eq!(fees.len(), 1);
+        assert_eq!(fees[0].reference, "gas:ethereum:0xabc");
+    }
+}
@@ -1,12 +1,14 @@
-use crate::connectors::coinpaprika::CoinPaprikaConnector;
+use crate::connectors::price_provider::{price_provider_from_env, PriceProvider};
 use crate::entities::{assets, asset_prices};
+use crate::helpers::price_sanity::{screen_prices, PriceSanityConfig};
 use crate::jobs::runner::{JobRunner, JobMetrics};
-use chrono::Utc;
+use chrono::{DateTime, Utc};
 use rust_decimal::Decimal;
 use sea_orm::{
-    ActiveModelTrait, ActiveValue, ColumnTrait, DatabaseConnection, EntityTrait, QueryFilter,
-    sea_query::OnConflict, Insert, Condition,
+    ActiveModelTrait, ActiveValue, ColumnTrait, DatabaseConnection, DbErr, EntityTrait, QueryFilter,
+    QuerySelect, sea_query::{Expr, OnConflict}, Insert, Condition,
 };
+use std::collections::HashSet;
 use std::error::Error;
 use std::str::FromStr;
 use tracing;
@@ -19,10 +21,69 @@ pub struct CollectionResult {
     pub coins_fetched: usize,
     pub assets_created: usize,
     pub assets_updated: usize,
+    /// Assets deactivated because the provider no longer lists them
+    pub assets_delisted: usize,
     pub prices_stored: usize,
     pub error: Option<String>,
 }
 
+/// Most assets one run may delist, as a share (1/N) of the coins fetched; a bigger drop is
+/// more likely a truncated provider response than a wave of delistings
+const MAX_DELISTED_SHARE: usize = 10;
+
+/// Whether delisting `delisted` assets after fetching `fetched` coins looks like real delistings
+fn plausible_delisting(delisted: usize, fetched: usize) -> bool {
+    delisted * MAX_DELISTED_SHARE <= fetched
+}
+
+/// Deactivate active assets with a coin ID of `provider` that is not in `listed_ids`, setting
+/// their `delisted_at`. Returns the number of assets delisted.
+async fn mark_delisted_assets(
+    db: &DatabaseConnection,
+    provider: &dyn PriceProvider,
+    listed_ids: &HashSet<String>,
+    now: DateTime<Utc>,
+) -> Result<usize, DbErr> {
+    let active: Vec<(Uuid, String)> = assets::Entity::find()
+        .select_only()
+        .column(assets::Column::Id)
+        .column(provider.id_column())
+        .filter(assets::Column::IsActive.eq(true))
+        .filter(provider.id_column().is_not_null())
+        .into_tuple()
+        .all(db)
+        .await?;
+    let delisted: Vec<Uuid> = active
+        .into_iter()
+        .filter(|(_, provider_id)| !listed_ids.contains(provider_id))
+        .map(|(id, _)| id)
+        .collect();
+    if delisted.is_empty() {
+        return Ok(0);
+    }
+    if !plausible_delisting(delisted.len(), listed_ids.len()) {
+        tracing::warn!(
+            "{} no longer lists {} active assets out of {} coins fetched; not delisting them",
+            provider.name(),
+            delisted.len(),
+            listed_ids.len()
+        );
+        return Ok(0);
+    }
+
+    for batch in delisted.chunks(500) {
+        assets::Entity::update_many()
+            .col_expr(assets::Column::IsActive, Expr::value(false))
+            .col_expr(assets::Column::DelistedAt, Expr::value(now))
+            .col_expr(assets::Column::UpdatedAt, Expr::value(now))
+            .filter(assets::Column::Id.is_in(batch.to_vec()))
+            .exec(db)
+            .await?;
+    }
+    tracing::info!("Delisted {} assets no longer listed by {}", delisted.len(), provider.name());
+    Ok(delisted.len())
+}
+
 /// Helper function to parse decimal from f64 option
 fn parse_decimal_from_f64(value: Option<f64>) -> Option<Decimal> {
     value.and_then(|v| Decimal::from_str(&v.to_string()).ok())
@@ -61,12 +122,14 @@ pub fn deduplicate_prices(prices: Vec<asset_prices::ActiveModel>) -> Vec<asset_p
     price_map.into_values().collect()
 }
 
-/// Fetch all active coins from CoinPaprika in one request and store in database
+/// Fetch all active coins from the configured price provider and store in database
 /// 
-/// This function uses the CoinPaprika connector to:
-/// 1. Fetch all active coins via /tickers endpoint in one request
+/// This function uses the provider selected by `PRICE_PROVIDER` (CoinPaprika by default) to:
+/// 1. Fetch all listed coins
 /// 2. Upsert asset metadata (creates new or updates existing)
 /// 3. Store comprehensive price data with rank, supply, and market info
+/// 4. Delist active assets the provider no longer returns (`is_active = false`, `delisted_at`);
+///    a relisted coin is reactivated by step 2
 /// 
 /// This replaces the previous approach of separate top_coins_collection, 
 /// price_collection, and contract_addresses_collection jobs.
@@ -78,58 +141,62 @@ pub fn deduplicate_prices(prices: Vec<asset_prices::ActiveModel>) -> Vec<asset_p
 /// CollectionResult with statistics about the operation
 pub async fn fetch_all_coins(
     db: &DatabaseConnection,
+) -> Result<CollectionResult, Box<dyn Error + Send + Sync>> {
+    fetch_all_coins_from(db, price_provider_from_env().as_ref()).await
+}
+
+/// Fetch all coins listed by `provider` and store them, see [`fetch_all_coins`]
+pub async fn fetch_all_coins_from(
+    db: &DatabaseConnection,
+    provider: &dyn PriceProvider,
 ) -> Result<CollectionResult, Box<dyn Error + Send + Sync>> {
     let runner = JobRunner::new("fetch_all_coins".to_string());
 
     let result = runner.execute(|| async {
-        // Create CoinPaprika connector
-        let connector = CoinPaprikaConnector::new();
-
-        // Fetch all coins in one request
-        tracing::info!("Fetching all coins from CoinPaprika");
-        let coins = connector.fetch_all_coins()
+        // Fetch all coins listed by the provider
+        tracing::info!("Fetching all coins from {}", provider.name());
+        let coins = provider.fetch_top(None)
             .await
-            .map_err(|e| format!("Failed to fetch coins from CoinPaprika: {}", e))?;
+            .map_err(|e| format!("Failed to fetch coins from {}: {}", provider.name(), e))?;
 
         let coins_fetched = coins.len();
-        tracing::info!("Successfully fetched {} coins from CoinPaprika", coins_fetched);
+        tracing::info!("Successfully fetched {} coins from {}", coins_fetched, provider.name());
+        let listed_ids: HashSet<String> = coins.iter().map(|coin| coin.provider_id.clone()).collect();
 
         let mut assets_created = 0;
         let mut assets_updated = 0;
         let mut prices_to_store = Vec::new();
+        let sanity = PriceSanityConfig::from_env();
 
-        let source = "coinpaprika";
+        let source = provider.name();
         let current_timestamp = Utc::now();
 
         for coin in coins {
             // Parse price from USD quote
-            let price_usd = match Decimal::from_str(&coin.quotes.usd.price.to_string()) {
+            let price_usd = match Decimal::from_str(&coin.price_usd.to_string()) {
                 Ok(price) => price,
                 Err(e) => {
                     tracing::warn!(
                         "Failed to parse price for {} ({}): {}. Using ZERO.",
-                        coin.symbol, coin.id, e
+                        coin.symbol, coin.provider_id, e
                     );
                     Decimal::ZERO
                 }
             };
-            let market_cap_usd = Decimal::from_str(&coin.quotes.usd.market_cap.to_string()).ok();
-            let volume_24h_usd = parse_decimal_from_f64(coin.quotes.usd.volume_24h);
-            let change_percent_24h = parse_decimal_from_f64(coin.quotes.usd.percent_change_24h);
+            let market_cap_usd = parse_decimal_from_f64(coin.market_cap_usd);
+            let volume_24h_usd = parse_decimal_from_f64(coin.volume_24h_usd);
+            let change_percent_24h = parse_decimal_from_f64(coin.percent_change_24h);
 
-            // Check if asset already exists by coinpaprika_id first (primary key)
-            // Only fall back to symbol+name matching if CoinPaprika ID is not found
-            // This prevents duplicate mappings when multiple coins share the same symbol but different names
-            // Check if asset already exists by coinpaprika_id first (primary key)
-            // Only fall back to symbol+name matching if CoinPaprika ID is not found
+            // Check if asset already exists by the provider's coin ID first (primary key)
+            // Only fall back to symbol+name matching if the provider ID is not found
             // This prevents duplicate mappings when multiple coins share the same symbol but different names
             let existing_asset = assets::Entity::find()
-                .filter(assets::Column::CoinpaprikaId.eq(&coin.id))
+                .filter(provider.id_column().eq(&coin.provider_id))
                 .one(db)
                 .await
                 .map_err(|e| format!("Failed to query assets: {}", e))?;
             
-            // If not found by CoinPaprika ID, try by (symbol AND name) as fallback for legacy data
+            // If not found by provider ID, try by (symbol AND name) as fallback for legacy data
             // Using Condition::all() for clarity and proper operator precedence
             let existing_asset = if existing_asset.is_none() {
                 assets::Entity::find()
@@ -151,8 +218,9 @@ pub async fn fetch_all_coins(
                     let mut asset_update: assets::ActiveModel = existing.into();
                     asset_update.name = ActiveValue::Set(coin.name.clone());
                     asset_update.symbol = ActiveValue::Set(coin.symbol.to_uppercase());
-                    asset_update.coinpaprika_id = ActiveValue::Set(Some(coin.id.clone()));
+                    asset_update.set(provider.id_column(), Some(coin.provider_id.clone()).into());
                     asset_update.is_active = ActiveValue::Set(true);
+                    asset_update.delisted_at = ActiveValue::Set(None);
                     asset_update.updated_at = ActiveValue::Set(current_timestamp.into());
                     
                     let updated = asset_update.update(db).await
@@ -163,20 +231,24 @@ pub async fn fetch_all_coins(
                 }
                 None => {
                     // Create new asset
-                    let new_asset = assets::ActiveModel {
+                    let mut new_asset = assets::ActiveModel {
                         id: ActiveValue::Set(Uuid::new_v4()),
                         symbol: ActiveValue::Set(coin.symbol.to_uppercase()),
                         name: ActiveValue::Set(coin.name.clone()),
                         asset_type: ActiveValue::Set("cryptocurrency".to_string()),
-                        coinpaprika_id: ActiveValue::Set(Some(coin.id.clone())),
+                        coinpaprika_id: ActiveValue::NotSet,
+                        coingecko_id: ActiveValue::NotSet,
                         coinmarketcap_id: ActiveValue::NotSet,
                         logo_url: ActiveValue::NotSet,
                         description: ActiveValue::NotSet,
                         decimals: ActiveValue::NotSet,
                         is_active: ActiveValue::Set(true),
+                        delisted_at: ActiveValue::Set(None),
+                        category: ActiveValue::Set(None),
                         created_at: ActiveValue::Set(current_timestamp.into()),
                         updated_at: ActiveValue::Set(current_timestamp.into()),
                     };
+                    new_asset.set(provider.id_column(), Some(coin.provider_id.clone()).into());
                     
                     let inserted = new_asset.insert(db).await
                         .map_err(|e| format!("Failed to insert asset: {}", e))?;
@@ -186,23 +258,23 @@ pub async fn fetch_all_coins(
                 }
             };
 
-            // Extended fields from CoinPaprika
-            let rank = Some(coin.rank as i32);
-            let circulating_supply = coin.circulating_supply.and_then(|v| Decimal::from_str(&v.to_string()).ok());
-            let total_supply = coin.total_supply.and_then(|v| Decimal::from_str(&v.to_string()).ok());
-            let max_supply = coin.max_supply.and_then(|v| Decimal::from_str(&v.to_string()).ok());
+            // Extended market fields
+            let rank = coin.rank.map(|rank| rank as i32);
+            let circulating_supply = parse_decimal_from_f64(coin.circulating_supply);
+            let total_supply = parse_decimal_from_f64(coin.total_supply);
+            let max_supply = parse_decimal_from_f64(coin.max_supply);
             let beta_value = parse_decimal_from_f64(coin.beta_value);
             
-            let percent_change_1h = parse_decimal_from_f64(coin.quotes.usd.percent_change_1h);
-            let percent_change_7d = parse_decimal_from_f64(coin.quotes.usd.percent_change_7d);
-            let percent_change_30d = parse_decimal_from_f64(coin.quotes.usd.percent_change_30d);
-            let ath_price = parse_decimal_from_f64(coin.quotes.usd.ath_price);
+            let percent_change_1h = parse_decimal_from_f64(coin.percent_change_1h);
+            let percent_change_7d = parse_decimal_from_f64(coin.percent_change_7d);
+            let percent_change_30d = parse_decimal_from_f64(coin.percent_change_30d);
+            let ath_price = parse_decimal_from_f64(coin.ath_price);
             
-            let ath_date = coin.quotes.usd.ath_date
+            let ath_date = coin.ath_date
                 .and_then(|d| chrono::DateTime::parse_from_rfc3339(&d).ok())
                 .map(|dt| dt.with_timezone(&chrono::Utc));
             
-            let percent_from_price_ath = parse_decimal_from_f64(coin.quotes.usd.percent_from_price_ath);
+            let percent_from_price_ath = parse_decimal_from_f64(coin.percent_from_price_ath);
 
             let new_price = asset_prices::ActiveModel {
                 id: ActiveValue::Set(Uuid::new_v4()),
@@ -213,7 +285,7 @@ pub async fn fetch_all_coins(
                 market_cap_usd: ActiveValue::Set(market_cap_usd),
                 change_percent_24h: ActiveValue::Set(change_percent_24h),
                 source: ActiveValue::Set(source.to_string()),
-                // Extended fields from CoinPaprika refactoring
+                // Extended market fields
                 rank: ActiveValue::Set(rank),
                 circulating_supply: ActiveValue::Set(circulating_supply),
                 total_supply: ActiveValue::Set(total_supply),
@@ -233,7 +305,7 @@ pub async fn fetch_all_coins(
             // Batch insert every 500 prices to avoid too large transactions
             if prices_to_store.len() >= 500 {
                 // Deduplicate to prevent "ON CONFLICT DO UPDATE command cannot affect row a second time" error
-                let deduplicated = deduplicate_prices(prices_to_store);
+                let deduplicated = screen_prices(db, deduplicate_prices(prices_to_store), &sanity).await;
                 let count = deduplicated.len();
                 match Insert::many(deduplicated)
                     .on_conflict(
@@ -261,6 +333,7 @@ pub async fn fetch_all_coins(
                         ])
                         .to_owned(),
                     )
+                    .on_empty_do_nothing()
                     .exec(db)
                     .await
                 {
@@ -278,7 +351,7 @@ pub async fn fetch_all_coins(
         // Insert remaining prices
         let prices_stored = if !prices_to_store.is_empty() {
             // Deduplicate to prevent "ON CONFLICT DO UPDATE command cannot affect row a second time" error
-            let deduplicated = deduplicate_prices(prices_to_store);
+            let deduplicated = screen_prices(db, deduplicate_prices(prices_to_store), &sanity).await;
             let count = deduplicated.len();
             match Insert::many(deduplicated)
                 .on_conflict(
@@ -306,6 +379,7 @@ pub async fn fetch_all_coins(
                     ])
                     .to_owned(),
                 )
+                .on_empty_do_nothing()
                 .exec(db)
                 .await
             {
@@ -322,9 +396,13 @@ pub async fn fetch_all_coins(
             0
         };
 
+        let assets_delisted = mark_delisted_assets(db, provider, &listed_ids, current_timestamp)
+            .await
+            .map_err(|e| format!("Failed to delist assets: {}", e))?;
+
         tracing::info!(
-            "Fetch all coins completed: {} coins fetched, {} assets created, {} updated, {} prices stored",
-            coins_fetched, assets_created, assets_updated, prices_stored
+            "Fetch all coins completed: {} coins fetched, {} assets created, {} updated, {} delisted, {} prices stored",
+            coins_fetched, assets_created, assets_updated, assets_delisted, prices_stored
         );
 
         Ok(JobMetrics {
@@ -336,6 +414,7 @@ pub async fn fetch_all_coins(
                 "coins_fetched": coins_fetched,
                 "assets_created": assets_created,
                 "assets_updated": assets_updated,
+                "assets_delisted": assets_delisted,
                 "prices_stored": prices_stored,
             }),
         })
@@ -347,6 +426,7 @@ pub async fn fetch_all_coins(
         coins_fetched: result.metrics.custom["coins_fetched"].as_u64().unwrap_or(0) as usize,
         assets_created: result.metrics.custom["assets_created"].as_u64().unwrap_or(0) as usize,
         assets_updated: result.metrics.custom["assets_updated"].as_u64().unwrap_or(0) as usize,
+        assets_delisted: result.metrics.custom["assets_delisted"].as_u64().unwrap_or(0) as usize,
         prices_stored: result.metrics.custom["prices_stored"].as_u64().unwrap_or(0) as usize,
         error: result.error,
     })
@@ -364,6 +444,7 @@ mod tests {
             coins_fetched: 1000,
             assets_created: 50,
             assets_updated: 950,
+            assets_delisted: 0,
             prices_stored: 1000,
             error: None,
         };
@@ -371,6 +452,15 @@ mod tests {
         assert_eq!(result.coins_fetched, 1000);
     }
 
+    #[test]
+    fn test_plausible_delisting() {
+        assert!(plausible_delisting(5, 2500));
+        assert!(plausible_delisting(250, 2500));
+        // A response missing most coins must not delist them
+        assert!(!plausible_delisting(2000, 500));
+        assert!(!plausible_delisting(1, 0));
+    }
+
     #[test]
     fn test_deduplicate_prices_no_duplicates() {
         // Test with no duplicates - should return all prices unchanged
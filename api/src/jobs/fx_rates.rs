@@ -0,0 +1,71 @@
+use crate::connectors::fx::{
+    fetch_ecb_rates, fetch_exchangerate_host_rates, FxQuotes, FX_SOURCE_ECB, FX_SOURCE_EXCHANGERATE_HOST,
+};
+use crate::entities::fx_rates;
+use chrono::{NaiveDate, Utc};
+use reqwest::Client;
+use sea_orm::{sea_query::OnConflict, ActiveValue, DatabaseConnection, EntityTrait};
+use std::error::Error;
+use tracing;
+use uuid::Uuid;
+
+/// Result of collecting FX rates
+#[derive(Debug, Clone)]
+pub struct FxRatesResult {
+    pub source: &'static str,
+    pub rate_date: NaiveDate,
+    /// Currencies whose rate was stored
+    pub currencies: usize,
+}
+
+/// `fx_rates` rows of one day's quotes
+fn quotes_to_rows(quotes: &FxQuotes) -> Vec<fx_rates::ActiveModel> {
+    quotes
+        .rates
+        .iter()
+        .filter(|(_, rate)| rate.is_sign_positive() && !rate.is_zero())
+        .map(|(currency, rate)| fx_rates::ActiveModel {
+            id: ActiveValue::Set(Uuid::new_v4()),
+            currency: ActiveValue::Set(currency.clone()),
+            rate: ActiveValue::Set(*rate),
+            rate_date: ActiveValue::Set(quotes.date),
+            source: ActiveValue::Set(quotes.source.to_string()),
+            created_at: ActiveValue::Set(Utc::now().into()),
+        })
+        .collect()
+}
+
+/// Fetch today's rates from the source in `FX_RATES_SOURCE` and upsert them into `fx_rates`.
+///
+/// `FX_RATES_SOURCE` is "ecb" (default) or "exchangerate_host", which needs
+/// `EXCHANGERATE_HOST_API_KEY`. Re-running on the same day overwrites that day's rates.
+pub async fn collect_fx_rates(db: &DatabaseConnection) -> Result<FxRatesResult, Box<dyn Error + Send + Sync>> {
+    let source = std::env::var("FX_RATES_SOURCE").unwrap_or_else(|_| FX_SOURCE_ECB.to_string());
+    let client = Client::new();
+
+    let quotes = match source.trim().to_lowercase().as_str() {
+        FX_SOURCE_ECB => fetch_ecb_rates(&client).await?,
+        FX_SOURCE_EXCHANGERATE_HOST => {
+            let api_key = std::env::var("EXCHANGERATE_HOST_API_KEY")
+                .map_err(|_| "EXCHANGERATE_HOST_API_KEY is required for FX_RATES_SOURCE=exchangerate_host")?;
+            fetch_exchangerate_host_rates(&client, &api_key).await?
+        }
+        other => return Err(format!("Unknown FX_RATES_SOURCE '{}'", other).into()),
+    };
+
+    let rows = quotes_to_rows(&quotes);
+    let currencies = rows.len();
+    if !rows.is_empty() {
+        fx_rates::Entity::insert_many(rows)
+            .on_conflict(
+                OnConflict::columns([fx_rates::Column::Currency, fx_rates::Column::RateDate])
+                    .update_columns([fx_rates::Column::Rate, fx_rates::Column::Source])
+                    .to_owned(),
+            )
+            .exec_without_returning(db)
+            .await?;
+    }
+
+    tracing::info!("Stored {} FX rates from {} for {}", currencies, quotes.source, quotes.date);
+    Ok(FxRatesResult { source: quotes.source, rate_date: quotes.date, currencies })
+}
@@ -0,0 +1,257 @@
+use crate::connectors::Balance;
+use crate::domain::AccountHolding;
+use crate::entities::holding_transactions;
+use crate::jobs::anomaly_detection::sum_by_asset;
+use chrono::{DateTime, Duration, Utc};
+use rust_decimal::Decimal;
+use sea_orm::{
+    ActiveModelTrait, ActiveValue, ColumnTrait, DatabaseConnection, EntityTrait, QueryFilter,
+    QueryOrder, TransactionTrait,
+};
+use std::collections::{BTreeSet, HashMap};
+use std::error::Error;
+use tracing;
+use uuid::Uuid;
+
+/// Default window (minutes) in which a reverted change is treated as a correction
+const DEFAULT_CORRECTION_WINDOW_MINUTES: i64 = 60;
+
+/// Transaction type: quantity change observed between two syncs
+pub const TX_SYNC_DELTA: &str = "sync_delta";
+
+/// Transaction type: reversal of a recent `sync_delta` that turned out to be wrong
+pub const TX_CORRECTION: &str = "correction";
+
+/// Transaction type: baseline quantity converted from legacy `accounts.holdings` JSON
+pub const TX_BACKFILL: &str = "backfill";
+
+/// Transaction type: quantity change read from an imported CSV transaction statement
+pub const TX_IMPORT: &str = "import";
+
+/// Most recent effective (not yet corrected) ledger row for an asset
+#[derive(Debug, Clone)]
+pub struct LatestTransaction {
+    pub id: Uuid,
+    pub quantity_before: Decimal,
+    pub recorded_at: DateTime<Utc>,
+}
+
+/// A ledger row to be written for one asset
+#[derive(Debug, Clone, PartialEq)]
+pub struct PlannedEntry {
+    pub asset: String,
+    pub quantity_before: Decimal,
+    pub quantity_after: Decimal,
+    /// ID of the transaction this entry supersedes (entry is then a `correction`)
+    pub corrects: Option<Uuid>,
+}
+
+/// Counts of ledger rows written by a sync
+#[derive(Debug, Default, PartialEq)]
+pub struct LedgerSummary {
+    pub appended: usize,
+    pub corrected: usize,
+}
+
+/// Read the correction window from `HOLDING_CORRECTION_WINDOW_MINUTES` (default: 60)
+pub fn correction_window_from_env() -> Duration {
+    let minutes = std::env::var("HOLDING_CORRECTION_WINDOW_MINUTES")
+        .ok()
+        .and_then(|v| v.parse::<i64>().ok())
+        .filter(|v| *v >= 0)
+        .unwrap_or(DEFAULT_CORRECTION_WINDOW_MINUTES);
+    Duration::minutes(minutes)
+}
+
+/// Decide which ledger rows a sync should write.
+///
+/// One entry is planned per asset whose quantity changed. If the new quantity
+/// exactly restores the `quantity_before` of the asset's latest effective
+/// transaction, and that transaction is younger than `window`, the entry is a
+/// correction of it rather than a new movement. Entries are sorted by asset.
+pub fn plan_ledger_entries(
+    previous: &HashMap<String, Decimal>,
+    current: &HashMap<String, Decimal>,
+    latest: &HashMap<String, LatestTransaction>,
+    now: DateTime<Utc>,
+    window: Duration,
+) -> Vec<PlannedEntry> {
+    let assets: BTreeSet<&String> = previous.keys().chain(current.keys()).collect();
+
+    assets
+        .into_iter()
+        .filter_map(|asset| {
+            let before = previous.get(asset).copied().unwrap_or(Decimal::ZERO);
+            let after = current.get(asset).copied().unwrap_or(Decimal::ZERO);
+            if before == after {
+                return None;
+            }
+
+            let corrects = latest
+                .get(asset)
+                .filter(|tx| now - tx.recorded_at <= window && tx.quantity_before == after)
+                .map(|tx| tx.id);
+
+            Some(PlannedEntry {
+                asset: asset.clone(),
+                quantity_before: before,
+                quantity_after: after,
+                corrects,
+            })
+        })
+        .collect()
+}
+
+/// Write ledger rows for the quantity changes observed by a sync.
+///
+/// Corrections are written atomically: the `correction` row is inserted and the
+/// superseded row's `corrected_by` is set in the same database transaction.
+pub async fn record_sync_transactions(
+    db: &DatabaseConnection,
+    account_id: Uuid,
+    previous: &[AccountHolding],
+    current: &[Balance],
+) -> Result<LedgerSummary, Box<dyn Error + Send + Sync>> {
+    let previous = sum_by_asset(previous.iter().map(|h| (h.asset.as_str(), h.quantity.as_str())));
+    let current = sum_by_asset(current.iter().map(|b| (b.asset.as_str(), b.quantity.as_str())));
+
+    let now = Utc::now();
+    let window = correction_window_from_env();
+
+    // Latest effective sync_delta per asset inside the correction window
+    let mut latest: HashMap<String, LatestTransaction> = HashMap::new();
+    let recent = holding_transactions::Entity::find()
+        .filter(holding_transactions::Column::AccountId.eq(account_id))
+        .filter(holding_transactions::Column::TransactionType.eq(TX_SYNC_DELTA))
+        .filter(holding_transactions::Column::CorrectedBy.is_null())
+        .filter(holding_transactions::Column::RecordedAt.gte(now - window))
+        .order_by_desc(holding_transactions::Column::RecordedAt)
+        .all(db)
+        .await?;
+    for tx in recent {
+        latest.entry(tx.asset.clone()).or_insert(LatestTransaction {
+            id: tx.id,
+            quantity_before: tx.quantity_before,
+            recorded_at: tx.recorded_at.with_timezone(&Utc),
+        });
+    }
+
+    let plan = plan_ledger_entries(&previous, &current, &latest, now, window);
+    if plan.is_empty() {
+        return Ok(LedgerSummary::default());
+    }
+
+    let txn = db.begin().await?;
+    let mut summary = LedgerSummary::default();
+
+    for entry in plan {
+        let id = Uuid::new_v4();
+        let row = holding_transactions::ActiveModel {
+            id: ActiveValue::Set(id),
+            account_id: ActiveValue::Set(account_id),
+            asset: ActiveValue::Set(entry.asset.clone()),
+            transaction_type: ActiveValue::Set(
+                if entry.corrects.is_some() { TX_CORRECTION } else { TX_SYNC_DELTA }.to_string(),
+            ),
+            quantity_before: ActiveValue::Set(entry.quantity_before),
+            quantity_after: ActiveValue::Set(entry.quantity_after),
+            delta: ActiveValue::Set(entry.quantity_after - entry.quantity_before),
+            corrected_by: ActiveValue::Set(None),
+            recorded_at: ActiveValue::Set(now.into()),
+        };
+        row.insert(&txn).await?;
+
+        match entry.corrects {
+            Some(superseded_id) => {
+                let superseded = holding_transactions::ActiveModel {
+                    id: ActiveValue::Unchanged(superseded_id),
+                    corrected_by: ActiveValue::Set(Some(id)),
+                    ..Default::default()
+                };
+                superseded.update(&txn).await?;
+                tracing::info!(
+                    "Account {} {}: transaction {} superseded by correction {}",
+                    account_id,
+                    entry.asset,
+                    superseded_id,
+                    id
+                );
+                summary.corrected += 1;
+            }
+            None => summary.appended += 1,
+        }
+    }
+
+    txn.commit().await?;
+    Ok(summary)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn quantities(items: &[(&str, i64)]) -> HashMap<String, Decimal> {
+        items.iter().map(|(a, q)| (a.to_string(), Decimal::from(*q))).collect()
+    }
+
+    #[test]
+    fn test_unchanged_assets_write_nothing() {
+        let previous = quantities(&[("BTC", 1)]);
+        let plan = plan_ledger_entries(&previous, &previous, &HashMap::new(), Utc::now(), Duration::minutes(60));
+        assert!(plan.is_empty());
+    }
+
+    #[test]
+    fn test_changes_append_new_entries() {
+        let previous = quantities(&[("BTC", 1), ("ETH", 5)]);
+        let current = quantities(&[("BTC", 2), ("USDT", 100)]);
+        let plan = plan_ledger_entries(&previous, &current, &HashMap::new(), Utc::now(), Duration::minutes(60));
+
+        assert_eq!(plan.len(), 3);
+        assert_eq!(plan[0].asset, "BTC");
+        assert_eq!(plan[1].asset, "ETH");
+        assert_eq!(plan[1].quantity_after, Decimal::ZERO);
+        assert_eq!(plan[2].asset, "USDT");
+        assert!(plan.iter().all(|e| e.corrects.is_none()));
+    }
+
+    #[test]
+    fn test_reverted_change_within_window_is_correction() {
+        let now = Utc::now();
+        let bad_tx = Uuid::new_v4();
+        // Previous sync wrongly recorded ETH 5 -> 0; this sync sees 5 again
+        let previous = quantities(&[]);
+        let current = quantities(&[("ETH", 5)]);
+        let latest = HashMap::from([(
+            "ETH".to_string(),
+            LatestTransaction {
+                id: bad_tx,
+                quantity_before: Decimal::from(5),
+                recorded_at: now - Duration::minutes(10),
+            },
+        )]);
+
+        let plan = plan_ledger_entries(&previous, &current, &latest, now, Duration::minutes(60));
+        assert_eq!(plan.len(), 1);
+        assert_eq!(plan[0].corrects, Some(bad_tx));
+    }
+
+    #[test]
+    fn test_reverted_change_outside_window_is_new_movement() {
+        let now = Utc::now();
+        let previous = quantities(&[]);
+        let current = quantities(&[("ETH", 5)]);
+        let latest = HashMap::from([(
+            "ETH".to_string(),
+            LatestTransaction {
+                id: Uuid::new_v4(),
+                quantity_before: Decimal::from(5),
+                recorded_at: now - Duration::hours(3),
+            },
+        )]);
+
+        let plan = plan_ledger_entries(&previous, &current, &latest, now, Duration::minutes(60));
+        assert_eq!(plan.len(), 1);
+        assert!(plan[0].corrects.is_none());
+    }
+}
@@ -0,0 +1,243 @@
+use crate::domain::AccountHolding;
+use crate::entities::{accounts, holding_transactions};
+use crate::jobs::anomaly_detection::sum_by_asset;
+use crate::jobs::holding_ledger::TX_BACKFILL;
+use chrono::{DateTime, Duration, Utc};
+use rust_decimal::Decimal;
+use sea_orm::{
+    ActiveValue, ColumnTrait, DatabaseConnection, EntityTrait, PaginatorTrait, QueryFilter,
+    QueryOrder, TransactionTrait,
+};
+use std::collections::{BTreeMap, HashMap};
+use std::error::Error;
+use tracing;
+use uuid::Uuid;
+
+/// Backfill outcome: baseline rows were written
+pub const BACKFILL_STATUS_BACKFILLED: &str = "backfilled";
+/// Backfill outcome: nothing to do (already backfilled, or no holdings)
+pub const BACKFILL_STATUS_SKIPPED: &str = "skipped";
+/// Backfill outcome: the account's JSON or ledger could not be processed
+pub const BACKFILL_STATUS_FAILED: &str = "failed";
+
+/// Result of backfilling one account
+#[derive(Debug, Clone)]
+pub struct BackfillAccountResult {
+    pub account_id: Uuid,
+    pub account_name: String,
+    /// "backfilled", "skipped" or "failed"
+    pub status: &'static str,
+    /// Baseline rows written (or that would be written in a dry run)
+    pub rows_created: usize,
+    pub message: Option<String>,
+}
+
+/// Compute the baseline quantity per asset for an account.
+///
+/// The baseline is the account's holdings *before* its first ledger row: assets that
+/// appear in the ledger start at the `quantity_before` of their earliest row, all other
+/// assets have not changed since and start at their current JSON quantity. Zero
+/// baselines are dropped; the result is sorted by asset.
+pub fn plan_baseline(
+    current: &HashMap<String, Decimal>,
+    earliest_before: &HashMap<String, Decimal>,
+) -> BTreeMap<String, Decimal> {
+    current
+        .keys()
+        .chain(earliest_before.keys())
+        .filter_map(|asset| {
+            let quantity = earliest_before
+                .get(asset)
+                .or_else(|| current.get(asset))
+                .copied()
+                .unwrap_or(Decimal::ZERO);
+            (quantity > Decimal::ZERO).then(|| (asset.clone(), quantity))
+        })
+        .collect()
+}
+
+/// Keep a backfilled timestamp from predating the wallet's first on-chain activity.
+///
+/// Accounts whose first activity is unknown (exchanges, wallets not yet looked up) are unbounded.
+pub fn bound_by_first_activity(at: DateTime<Utc>, first_activity_at: Option<DateTime<Utc>>) -> DateTime<Utc> {
+    first_activity_at.map_or(at, |first| at.max(first))
+}
+
+/// Backfill one account's legacy holdings JSON into `backfill` ledger rows
+async fn backfill_account(
+    db: &DatabaseConnection,
+    account: &accounts::Model,
+    dry_run: bool,
+) -> Result<BackfillAccountResult, Box<dyn Error + Send + Sync>> {
+    let result = |status, rows_created, message: Option<String>| BackfillAccountResult {
+        account_id: account.id,
+        account_name: account.name.clone(),
+        status,
+        rows_created,
+        message,
+    };
+
+    let already_backfilled = holding_transactions::Entity::find()
+        .filter(holding_transactions::Column::AccountId.eq(account.id))
+        .filter(holding_transactions::Column::TransactionType.eq(TX_BACKFILL))
+        .count(db)
+        .await?;
+    if already_backfilled > 0 {
+        return Ok(result(BACKFILL_STATUS_SKIPPED, 0, Some("Already backfilled".to_string())));
+    }
+
+    let holdings: Vec<AccountHolding> = match account.holdings.clone() {
+        None => Vec::new(),
+        Some(json) => match serde_json::from_value(json) {
+            Ok(holdings) => holdings,
+            Err(e) => {
+                return Ok(result(
+                    BACKFILL_STATUS_FAILED,
+                    0,
+                    Some(format!("Invalid holdings JSON: {}", e)),
+                ))
+            }
+        },
+    };
+    let current = sum_by_asset(holdings.iter().map(|h| (h.asset.as_str(), h.quantity.as_str())));
+
+    // Earliest ledger row per asset (any type) tells what the quantity was before the ledger began
+    let ledger = holding_transactions::Entity::find()
+        .filter(holding_transactions::Column::AccountId.eq(account.id))
+        .order_by_asc(holding_transactions::Column::RecordedAt)
+        .all(db)
+        .await?;
+    let mut earliest_before: HashMap<String, Decimal> = HashMap::new();
+    for tx in &ledger {
+        earliest_before.entry(tx.asset.clone()).or_insert(tx.quantity_before);
+    }
+
+    let baseline = plan_baseline(&current, &earliest_before);
+    if baseline.is_empty() {
+        return Ok(result(BACKFILL_STATUS_SKIPPED, 0, Some("No holdings to backfill".to_string())));
+    }
+
+    // Place the baseline just before the first ledger row, or at the last sync if there is none
+    let recorded_at: DateTime<Utc> = match ledger.first() {
+        Some(first) => first.recorded_at.with_timezone(&Utc) - Duration::milliseconds(1),
+        None => account
+            .last_synced_at
+            .map(|dt| dt.with_timezone(&Utc))
+            .unwrap_or_else(Utc::now),
+    };
+    let recorded_at = bound_by_first_activity(
+        recorded_at,
+        account.first_activity_at.map(|dt| dt.with_timezone(&Utc)),
+    );
+
+    let rows_created = baseline.len();
+    if dry_run {
+        return Ok(result(BACKFILL_STATUS_BACKFILLED, rows_created, Some("Dry run".to_string())));
+    }
+
+    let rows: Vec<holding_transactions::ActiveModel> = baseline
+        .into_iter()
+        .map(|(asset, quantity)| holding_transactions::ActiveModel {
+            id: ActiveValue::Set(Uuid::new_v4()),
+            account_id: ActiveValue::Set(account.id),
+            asset: ActiveValue::Set(asset),
+            transaction_type: ActiveValue::Set(TX_BACKFILL.to_string()),
+            quantity_before: ActiveValue::Set(Decimal::ZERO),
+            quantity_after: ActiveValue::Set(quantity),
+            delta: ActiveValue::Set(quantity),
+            corrected_by: ActiveValue::Set(None),
+            recorded_at: ActiveValue::Set(recorded_at.into()),
+        })
+        .collect();
+
+    let txn = db.begin().await?;
+    holding_transactions::Entity::insert_many(rows).exec(&txn).await?;
+    txn.commit().await?;
+
+    Ok(result(BACKFILL_STATUS_BACKFILLED, rows_created, None))
+}
+
+/// Convert every account's legacy `accounts.holdings` JSON into baseline ledger rows.
+///
+/// Each account is processed independently (one transaction per account) and is
+/// skipped if it already has `backfill` rows, so the job can safely be re-run.
+/// With `dry_run`, nothing is written and the rows that would be created are reported.
+pub async fn backfill_holdings(
+    db: &DatabaseConnection,
+    dry_run: bool,
+) -> Result<Vec<BackfillAccountResult>, Box<dyn Error + Send + Sync>> {
+    let accounts = accounts::Entity::find()
+        .order_by_asc(accounts::Column::CreatedAt)
+        .all(db)
+        .await?;
+
+    tracing::info!("Backfilling holdings ledger for {} accounts (dry_run={})", accounts.len(), dry_run);
+
+    let mut results = Vec::with_capacity(accounts.len());
+    for account in &accounts {
+        let result = match backfill_account(db, account, dry_run).await {
+            Ok(result) => result,
+            Err(e) => {
+                tracing::error!("Failed to backfill holdings for account {}: {}", account.id, e);
+                BackfillAccountResult {
+                    account_id: account.id,
+                    account_name: account.name.clone(),
+                    status: BACKFILL_STATUS_FAILED,
+                    rows_created: 0,
+                    message: Some(e.to_string()),
+                }
+            }
+        };
+        results.push(result);
+    }
+
+    tracing::info!(
+        "Holdings backfill completed: {} backfilled, {} skipped, {} failed",
+        results.iter().filter(|r| r.status == BACKFILL_STATUS_BACKFILLED).count(),
+        results.iter().filter(|r| r.status == BACKFILL_STATUS_SKIPPED).count(),
+        results.iter().filter(|r| r.status == BACKFILL_STATUS_FAILED).count()
+    );
+
+    Ok(results)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn quantities(items: &[(&str, i64)]) -> HashMap<String, Decimal> {
+        items.iter().map(|(a, q)| (a.to_string(), Decimal::from(*q))).collect()
+    }
+
+    #[test]
+    fn test_baseline_without_ledger_uses_current_holdings() {
+        let baseline = plan_baseline(&quantities(&[("ETH", 5), ("BTC", 1)]), &HashMap::new());
+
+        assert_eq!(baseline.len(), 2);
+        assert_eq!(baseline.keys().next().unwrap(), "BTC");
+        assert_eq!(baseline["ETH"], Decimal::from(5));
+    }
+
+    #[test]
+    fn test_baseline_rewinds_assets_changed_by_the_ledger() {
+        // ETH went 3 -> 5 and SOL 10 -> 0 after the ledger started; BTC never changed
+        let current = quantities(&[("BTC", 1), ("ETH", 5), ("NEW", 7)]);
+        let earliest_before = quantities(&[("ETH", 3), ("SOL", 10), ("NEW", 0)]);
+
+        let baseline = plan_baseline(&current, &earliest_before);
+
+        assert_eq!(baseline["BTC"], Decimal::from(1));
+        assert_eq!(baseline["ETH"], Decimal::from(3));
+        assert_eq!(baseline["SOL"], Decimal::from(10));
+        assert!(!baseline.contains_key("NEW"));
+    }
+
+    #[test]
+    fn test_bound_by_first_activity() {
+        let first = Utc::now() - Duration::days(30);
+
+        assert_eq!(bound_by_first_activity(first - Duration::days(1), Some(first)), first);
+        assert_eq!(bound_by_first_activity(first + Duration::days(1), Some(first)), first + Duration::days(1));
+        assert_eq!(bound_by_first_activity(first - Duration::days(1), None), first - Duration::days(1));
+    }
+}
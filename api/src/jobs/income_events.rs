@@ -0,0 +1,289 @@
+use crate::connectors::{Balance, StakingReward};
+use crate::domain::{AccountHolding, HOLDING_SOURCE_EARN, HOLDING_SOURCE_SPOT, HOLDING_SOURCE_STAKED, HOLDING_SOURCE_SUPPLIED};
+use crate::entities::{accounts, income_events, staking_rewards};
+use crate::helpers::asset_identity::{AssetIdentityNormalizer, NormalizationResult};
+use crate::helpers::price_resolution;
+use chrono::{DateTime, Utc};
+use rust_decimal::Decimal;
+use sea_orm::{
+    sea_query::OnConflict, ActiveValue, ColumnTrait, DatabaseConnection, EntityTrait, QueryFilter,
+    QuerySelect,
+};
+use std::collections::{BTreeMap, BTreeSet, HashSet};
+use std::error::Error;
+use std::str::FromStr;
+use uuid::Uuid;
+
+/// Income source: staking rewards (staked balances, per-epoch stake rewards)
+pub const INCOME_SOURCE_STAKING: &str = "staking";
+
+/// Income source: interest on exchange earn products and lending deposits
+pub const INCOME_SOURCE_INTEREST: &str = "interest";
+
+/// Income source: tokens that appeared in a wallet without being bought or swapped for
+pub const INCOME_SOURCE_AIRDROP: &str = "airdrop";
+
+/// Holding sources whose growth is income, with the income source it is recorded as
+const REWARD_SOURCES: &[(&str, &str)] = &[
+    (HOLDING_SOURCE_STAKED, INCOME_SOURCE_STAKING),
+    (HOLDING_SOURCE_EARN, INCOME_SOURCE_INTEREST),
+    (HOLDING_SOURCE_SUPPLIED, INCOME_SOURCE_INTEREST),
+];
+
+/// An income receipt to be stored in `income_events`
+#[derive(Debug, Clone, PartialEq)]
+pub struct IncomeReceipt {
+    pub asset: String,
+    pub quantity: Decimal,
+    pub source: &'static str,
+    pub holding_source: Option<String>,
+    /// Dedup key of explicitly reported income
+    pub reference: Option<String>,
+    pub received_at: DateTime<Utc>,
+}
+
+/// Positive quantities per (asset, holding source)
+fn quantities_by_source<'a>(
+    items: impl Iterator<Item = (&'a str, &'a str, &'a str)>,
+) -> BTreeMap<(String, String), Decimal> {
+    let mut quantities = BTreeMap::new();
+    for (asset, source, quantity) in items {
+        let quantity = Decimal::from_str(quantity).unwrap_or(Decimal::ZERO);
+        if quantity > Decimal::ZERO {
+            *quantities.entry((asset.to_string(), source.to_string())).or_insert(Decimal::ZERO) += quantity;
+        }
+    }
+    quantities
+}
+
+/// Detect income between the stored holdings and freshly read balances.
+///
+/// Growth of a staked, earn or supplied balance is income only as far as the asset's total
+/// grew too, so moving spot into an earn product is not income while an earn payout is.
+/// With `detect_airdrops` (wallets), a spot asset held for the first time is an airdrop when
+/// no other asset shrank in the same sync, i.e. it was not bought with one. Staked growth of
+/// assets in `explicit_staking` is left out; those rewards are recorded per epoch instead.
+/// Nothing is detected on a first sync.
+pub fn detect_income(
+    previous: &[AccountHolding],
+    balances: &[Balance],
+    detect_airdrops: bool,
+    explicit_staking: &HashSet<String>,
+    now: DateTime<Utc>,
+) -> Vec<IncomeReceipt> {
+    let before = quantities_by_source(previous.iter().map(|h| (h.asset.as_str(), h.source(), h.quantity.as_str())));
+    if before.is_empty() {
+        return Vec::new();
+    }
+    let after = quantities_by_source(balances.iter().map(|b| {
+        (b.asset.as_str(), b.holding_source.as_deref().unwrap_or(HOLDING_SOURCE_SPOT), b.quantity.as_str())
+    }));
+
+    let total = |quantities: &BTreeMap<(String, String), Decimal>, asset: &str| -> Decimal {
+        quantities.iter().filter(|((a, _), _)| a == asset).map(|(_, q)| *q).sum()
+    };
+    let assets: BTreeSet<&str> = before.keys().chain(after.keys()).map(|(asset, _)| asset.as_str()).collect();
+    let any_decrease = assets.iter().any(|asset| total(&after, asset) < total(&before, asset));
+
+    let receipt = |asset: &str, quantity: Decimal, source: &'static str, holding_source: &str| IncomeReceipt {
+        asset: asset.to_string(),
+        quantity,
+        source,
+        holding_source: Some(holding_source.to_string()),
+        reference: None,
+        received_at: now,
+    };
+
+    let mut receipts = Vec::new();
+    for asset in assets {
+        let mut growth = total(&after, asset) - total(&before, asset);
+        if growth <= Decimal::ZERO {
+            continue;
+        }
+
+        for (holding_source, income_source) in REWARD_SOURCES {
+            if *holding_source == HOLDING_SOURCE_STAKED && explicit_staking.contains(asset) {
+                continue;
+            }
+            let key = (asset.to_string(), holding_source.to_string());
+            let delta = after.get(&key).copied().unwrap_or_default() - before.get(&key).copied().unwrap_or_default();
+            let income = delta.min(growth);
+            if income > Decimal::ZERO {
+                receipts.push(receipt(asset, income, income_source, holding_source));
+                growth -= income;
+            }
+        }
+
+        let held_before = before.keys().any(|(a, _)| a == asset);
+        if detect_airdrops && !held_before && !any_decrease && growth > Decimal::ZERO {
+            receipts.push(receipt(asset, growth, INCOME_SOURCE_AIRDROP, HOLDING_SOURCE_SPOT));
+        }
+    }
+    receipts
+}
+
+/// Income receipts of per-epoch staking rewards, keyed by stake account and epoch
+pub fn staking_reward_receipts(rewards: &[StakingReward]) -> Vec<IncomeReceipt> {
+    rewards
+        .iter()
+        .filter_map(|r| {
+            let quantity = Decimal::from_str(&r.amount).ok().filter(|q| *q > Decimal::ZERO)?;
+            Some(IncomeReceipt {
+                asset: r.asset.clone(),
+                quantity,
+                source: INCOME_SOURCE_STAKING,
+                holding_source: Some(HOLDING_SOURCE_STAKED.to_string()),
+                reference: Some(format!("staking_reward:{}:{}", r.source_account, r.epoch)),
+                received_at: r.earned_at,
+            })
+        })
+        .collect()
+}
+
+/// Latest price of `asset` at or before `at`
+async fn price_at(
+    db: &DatabaseConnection,
+    normalizer: &AssetIdentityNormalizer,
+    asset: &str,
+    at: DateTime<Utc>,
+) -> Result<Option<Decimal>, Box<dyn Error + Send + Sync>> {
+    let NormalizationResult::Mapped(identity) = normalizer.normalize_from_symbol(asset).await else {
+        return Ok(None);
+    };
+    Ok(price_resolution::price_at(db, identity.asset_id, at).await?)
+}
+
+/// Value `receipts` at their receipt time and append them to `income_events`.
+///
+/// Receipts with a `reference` already stored for the account are skipped. Returns the
+/// number of new events.
+pub async fn record_income_events(
+    db: &DatabaseConnection,
+    account_id: Uuid,
+    receipts: &[IncomeReceipt],
+) -> Result<u64, Box<dyn Error + Send + Sync>> {
+    if receipts.is_empty() {
+        return Ok(0);
+    }
+
+    let normalizer = AssetIdentityNormalizer::new(db.clone());
+    let mut rows = Vec::with_capacity(receipts.len());
+    for receipt in receipts {
+        let price = price_at(db, &normalizer, &receipt.asset, receipt.received_at).await?;
+        rows.push(income_events::ActiveModel {
+            id: ActiveValue::Set(Uuid::new_v4()),
+            account_id: ActiveValue::Set(account_id),
+            asset: ActiveValue::Set(receipt.asset.clone()),
+            quantity: ActiveValue::Set(receipt.quantity),
+            value_usd: ActiveValue::Set(price.map(|p| (p * receipt.quantity).round_dp(8))),
+            source: ActiveValue::Set(receipt.source.to_string()),
+            holding_source: ActiveValue::Set(receipt.holding_source.clone()),
+            reference: ActiveValue::Set(receipt.reference.clone()),
+            received_at: ActiveValue::Set(receipt.received_at.into()),
+            created_at: ActiveValue::NotSet,
+        });
+    }
+
+    let inserted = income_events::Entity::insert_many(rows)
+        .on_conflict(
+            OnConflict::columns([income_events::Column::AccountId, income_events::Column::Reference])
+                .do_nothing()
+                .to_owned(),
+        )
+        .exec_without_returning(db)
+        .await?;
+    Ok(inserted)
+}
+
+/// Detect and store the income between an account's stored holdings and its fresh balances
+pub async fn record_sync_income(
+    db: &DatabaseConnection,
+    account: &accounts::Model,
+    previous: &[AccountHolding],
+    balances: &[Balance],
+) -> Result<u64, Box<dyn Error + Send + Sync>> {
+    let explicit_staking: HashSet<String> = staking_rewards::Entity::find()
+        .select_only()
+        .column(staking_rewards::Column::Asset)
+        .distinct()
+        .filter(staking_rewards::Column::AccountId.eq(account.id))
+        .into_tuple::<String>()
+        .all(db)
+        .await?
+        .into_iter()
+        .collect();
+
+    let receipts = detect_income(previous, balances, account.account_type == "wallet", &explicit_staking, Utc::now());
+    record_income_events(db, account.id, &receipts).await
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn holding(asset: &str, quantity: &str, source: Option<&str>) -> AccountHolding {
+        AccountHolding {
+            asset: asset.to_string(),
+            quantity: quantity.to_string(),
+            available: None,
+            frozen: None,
+            decimals: None,
+            price_usd: None,
+            value_usd: None,
+            holding_source: source.map(str::to_string),
+        }
+    }
+
+    fn balance(asset: &str, quantity: &str, source: Option<&str>) -> Balance {
+        Balance {
+            asset: asset.to_string(),
+            quantity: quantity.to_string(),
+            available: quantity.to_string(),
+            frozen: "0".to_string(),
+            decimals: None,
+            holding_source: source.map(str::to_string),
+        }
+    }
+
+    #[test]
+    fn test_detect_income() {
+        let now = Utc::now();
+        let previous = vec![
+            holding("USDT", "1000", None),
+            holding("USDT", "500", Some("earn")),
+            holding("ETH", "2", None),
+            holding("ETH", "10", Some("staked")),
+            holding("SOL-solana", "100", Some("staked")),
+        ];
+        let balances = vec![
+            // 200 moved into earn, 1.5 paid out as interest
+            balance("USDT", "800", None),
+            balance("USDT", "701.5", Some("earn")),
+            balance("ETH", "2", None),
+            balance("ETH", "10.01", Some("staked")),
+            // Recorded from per-epoch rewards
+            balance("SOL-solana", "100.2", Some("staked")),
+            balance("JUP-solana", "50", None),
+        ];
+        let explicit = HashSet::from(["SOL-solana".to_string()]);
+
+        let income = detect_income(&previous, &balances, true, &explicit, now);
+        let summary: Vec<(&str, String, &str)> =
+            income.iter().map(|r| (r.asset.as_str(), r.quantity.to_string(), r.source)).collect();
+        assert_eq!(
+            summary,
+            vec![
+                ("ETH", "0.01".to_string(), INCOME_SOURCE_STAKING),
+                ("JUP-solana", "50".to_string(), INCOME_SOURCE_AIRDROP),
+                ("USDT", "1.5".to_string(), INCOME_SOURCE_INTEREST),
+            ]
+        );
+
+        // A new token bought with another asset is not an airdrop
+        let swapped = vec![balance("USDT", "900", None), balance("USDT", "500", Some("earn")), balance("ARB", "120", None)];
+        assert!(detect_income(&previous[..2], &swapped, true, &HashSet::new(), now).is_empty());
+
+        // Nothing is income on a first sync
+        assert!(detect_income(&[], &balances, true, &HashSet::new(), now).is_empty());
+    }
+}
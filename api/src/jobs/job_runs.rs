@@ -0,0 +1,115 @@
+use crate::entities::job_runs;
+use chrono::Utc;
+use sea_orm::{ActiveModelTrait, ActiveValue, DatabaseConnection, DbErr, EntityTrait};
+use tracing;
+
+/// Job name: CoinPaprika coin/price collection
+pub const JOB_FETCH_ALL_COINS: &str = "fetch_all_coins";
+
+/// Job name: end-of-day portfolio snapshots
+pub const JOB_EOD_SNAPSHOT: &str = "eod_snapshot";
+
+/// Job name: intraday snapshots of portfolios with an "every_6h" or "hourly" cadence
+pub const JOB_INTRADAY_SNAPSHOT: &str = "intraday_snapshot";
+
+/// Job name: ENS / Unstoppable Domains re-resolution of wallet names
+pub const JOB_NAME_RESOLUTION: &str = "name_resolution";
+
+/// Job name: admin-triggered daily price history backfill of one asset
+pub const JOB_BACKFILL_PRICE_HISTORY: &str = "backfill_price_history";
+
+/// Job name: daily fiat FX rate collection
+pub const JOB_FX_RATES: &str = "fx_rates";
+
+/// Job name: Uniswap v3 TWAP pricing of tokens the price provider does not list
+pub const JOB_DEX_TWAP_PRICES: &str = "dex_twap_prices";
+
+/// Job name: hourly and daily price rollups
+pub const JOB_PRICE_ROLLUP: &str = "price_rollup";
+
+/// Job name: daily cost basis and P&L of every portfolio
+pub const JOB_PORTFOLIO_PNL: &str = "portfolio_pnl";
+
+/// Job name: daily return, cumulative return and drawdown of every portfolio
+pub const JOB_PORTFOLIO_PERFORMANCE: &str = "portfolio_performance";
+
+/// Job name: daily tax lot rebuild of every account
+pub const JOB_TAX_LOTS: &str = "tax_lots";
+
+/// Job name: admin-triggered reconstruction of missing daily portfolio snapshots
+pub const JOB_BACKFILL_SNAPSHOTS: &str = "backfill_snapshots";
+
+/// Job name: admin-triggered rebuild of a range of portfolio snapshots
+pub const JOB_REBUILD_SNAPSHOTS: &str = "rebuild_snapshots";
+
+/// Job name: daily recommendation engine run over every portfolio
+pub const JOB_RECOMMENDATIONS: &str = "recommendations";
+
+/// Run status values stored in `job_runs.last_status`
+pub const RUN_STATUS_RUNNING: &str = "running";
+pub const RUN_STATUS_SUCCEEDED: &str = "succeeded";
+pub const RUN_STATUS_FAILED: &str = "failed";
+
+/// Mark a job run as started, creating the job's row on its first run
+pub async fn record_job_started(db: &DatabaseConnection, job_name: &str) -> Result<(), DbErr> {
+    let now = Utc::now();
+
+    match job_runs::Entity::find_by_id(job_name.to_string()).one(db).await? {
+        Some(existing) => {
+            let mut active: job_runs::ActiveModel = existing.into();
+            active.last_status = ActiveValue::Set(RUN_STATUS_RUNNING.to_string());
+            active.last_started_at = ActiveValue::Set(now.into());
+            active.updated_at = ActiveValue::Set(now.into());
+            active.update(db).await?;
+        }
+        None => {
+            job_runs::ActiveModel {
+                job_name: ActiveValue::Set(job_name.to_string()),
+                last_status: ActiveValue::Set(RUN_STATUS_RUNNING.to_string()),
+                last_started_at: ActiveValue::Set(now.into()),
+                last_finished_at: ActiveValue::Set(None),
+                last_succeeded_at: ActiveValue::Set(None),
+                last_error: ActiveValue::Set(None),
+                updated_at: ActiveValue::Set(now.into()),
+            }
+            .insert(db)
+            .await?;
+        }
+    }
+
+    Ok(())
+}
+
+/// Record the outcome of a job run started with [`record_job_started`].
+///
+/// `error` is `None` for a successful run.
+pub async fn record_job_finished(
+    db: &DatabaseConnection,
+    job_name: &str,
+    error: Option<String>,
+) -> Result<(), DbErr> {
+    let now = Utc::now();
+
+    let Some(existing) = job_runs::Entity::find_by_id(job_name.to_string()).one(db).await? else {
+        tracing::warn!("Job run for '{}' finished without a recorded start", job_name);
+        return Ok(());
+    };
+
+    let mut active: job_runs::ActiveModel = existing.into();
+    active.last_finished_at = ActiveValue::Set(Some(now.into()));
+    active.updated_at = ActiveValue::Set(now.into());
+    match error {
+        None => {
+            active.last_status = ActiveValue::Set(RUN_STATUS_SUCCEEDED.to_string());
+            active.last_succeeded_at = ActiveValue::Set(Some(now.into()));
+            active.last_error = ActiveValue::Set(None);
+        }
+        Some(error) => {
+            active.last_status = ActiveValue::Set(RUN_STATUS_FAILED.to_string());
+            active.last_error = ActiveValue::Set(Some(error));
+        }
+    }
+    active.update(db).await?;
+
+    Ok(())
+}
@@ -1,5 +1,31 @@
+pub mod account_snapshot;
 pub mod account_sync;
+pub mod anomaly_detection;
+pub mod construction_runs;
+pub mod dex_twap_prices;
+pub mod fee_sync;
 pub mod fetch_all_coins;
+pub mod fx_rates;
+pub mod holding_ledger;
+pub mod holdings_backfill;
+pub mod income_events;
+pub mod job_runs;
+pub mod name_resolution;
+pub mod nft_sync;
+pub mod portfolio_pnl;
+pub mod portfolio_performance;
 pub mod portfolio_snapshot;
+pub mod position_sync;
 pub mod price_collection;
+pub mod price_history_backfill;
+pub mod price_rollup;
+pub mod recommendation_delivery;
+pub mod recommendations;
 pub mod runner;
+pub mod snapshot_backfill;
+pub mod staking_rewards;
+pub mod statement_import;
+pub mod sync_queue;
+pub mod tax_lots;
+pub mod trade_sync;
+pub mod transfer_sync;
@@ -0,0 +1,131 @@
+use crate::connectors::registry::{address_family, ACCOUNT_TYPE_WALLET, DEFAULT_WALLET_KIND};
+use crate::entities::accounts;
+use crate::helpers::name_resolution::NameResolver;
+use chrono::Utc;
+use sea_orm::{ActiveModelTrait, ColumnTrait, DatabaseConnection, DbErr, EntityTrait, QueryFilter, Set};
+
+/// Outcome of a name resolution run
+#[derive(Debug, Clone, Default)]
+pub struct NameResolutionResult {
+    /// Accounts whose names were resolved
+    pub accounts_checked: usize,
+    /// Accounts whose `wallet_address` changed because their name now resolves elsewhere
+    pub addresses_updated: usize,
+    /// Accounts where a lookup failed; their stored names and address are kept
+    pub failed: usize,
+}
+
+/// Chain family used to resolve an account's names; Hyperliquid and Safe accounts hold EVM addresses
+pub fn account_chain_family(account: &accounts::Model) -> &str {
+    if account.account_type == ACCOUNT_TYPE_WALLET {
+        address_family(account.exchange_name.as_deref().unwrap_or(DEFAULT_WALLET_KIND))
+    } else {
+        DEFAULT_WALLET_KIND
+    }
+}
+
+/// Whether a re-resolved address differs from the stored one; EVM addresses compare
+/// case-insensitively since checksum casing is not significant
+fn address_changed(chain_family: &str, stored: Option<&str>, resolved: &str) -> bool {
+    match stored {
+        Some(stored) if chain_family == DEFAULT_WALLET_KIND => !stored.eq_ignore_ascii_case(resolved),
+        Some(stored) => stored != resolved,
+        None => true,
+    }
+}
+
+/// Re-resolve an account's `wallet_name` (moving `wallet_address` if the name now points
+/// elsewhere) and reverse-resolve its `display_name`.
+///
+/// Lookups that fail leave the stored values untouched. Returns the updated account and whether
+/// every lookup succeeded.
+pub async fn resolve_account_names(
+    db: &DatabaseConnection,
+    resolver: &NameResolver,
+    account: accounts::Model,
+) -> Result<(accounts::Model, bool), DbErr> {
+    let chain_family = account_chain_family(&account).to_string();
+    let mut wallet_address = account.wallet_address.clone();
+    let mut display_name = account.display_name.clone();
+    let mut ok = true;
+
+    if let Some(name) = account.wallet_name.as_deref() {
+        match resolver.resolve(name, &chain_family).await {
+            Ok(Some(resolved)) if address_changed(&chain_family, wallet_address.as_deref(), &resolved) => {
+                tracing::info!(
+                    "{} now resolves to {} (was {:?}) for account {}",
+                    name,
+                    resolved,
+                    wallet_address,
+                    account.id
+                );
+                wallet_address = Some(resolved);
+            }
+            Ok(Some(_)) => {}
+            Ok(None) => {
+                tracing::warn!("{} no longer resolves for account {}; keeping its address", name, account.id);
+            }
+            Err(e) => {
+                tracing::warn!("Failed to resolve {} for account {}: {}", name, account.id, e);
+                ok = false;
+            }
+        }
+    }
+
+    if let Some(address) = wallet_address.as_deref() {
+        match resolver.reverse(address, &chain_family).await {
+            Ok(name) => display_name = name,
+            Err(e) => {
+                tracing::warn!("Failed to reverse-resolve account {}: {}", account.id, e);
+                ok = false;
+            }
+        }
+    }
+
+    let mut active: accounts::ActiveModel = account.into();
+    active.wallet_address = Set(wallet_address);
+    active.display_name = Set(display_name);
+    if ok {
+        active.names_resolved_at = Set(Some(Utc::now().into()));
+    }
+    Ok((active.update(db).await?, ok))
+}
+
+/// Re-resolve the names of all active accounts that have a wallet address
+pub async fn refresh_account_names(db: &DatabaseConnection) -> Result<NameResolutionResult, DbErr> {
+    let accounts = accounts::Entity::find()
+        .filter(accounts::Column::IsActive.eq(true))
+        .filter(accounts::Column::WalletAddress.is_not_null())
+        .all(db)
+        .await?;
+
+    let resolver = NameResolver::from_db(db).await;
+    let mut result = NameResolutionResult::default();
+    for account in accounts {
+        let previous_address = account.wallet_address.clone();
+        let (updated, ok) = resolve_account_names(db, &resolver, account).await?;
+        result.accounts_checked += 1;
+        if updated.wallet_address != previous_address {
+            result.addresses_updated += 1;
+        }
+        if !ok {
+            result.failed += 1;
+        }
+    }
+
+    Ok(result)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_address_changed() {
+        let address = "0xd8dA6BF26964aF9D7eEd9e03E53415D37aA96045";
+        assert!(!address_changed("evm", Some(&address.to_lowercase()), address));
+        assert!(address_changed("evm", Some("0x0000000000000000000000000000000000000001"), address));
+        assert!(address_changed("solana", Some("abc"), "ABC"));
+        assert!(address_changed("evm", None, address));
+    }
+}
@@ -0,0 +1,105 @@
+use crate::connectors::NftHolding;
+use crate::entities::nft_holdings;
+use chrono::{DateTime, Utc};
+use rust_decimal::Decimal;
+use sea_orm::{
+    ActiveValue, ColumnTrait, DatabaseConnection, EntityTrait, QueryFilter, TransactionTrait,
+};
+use std::collections::HashSet;
+use std::error::Error;
+use std::str::FromStr;
+use uuid::Uuid;
+
+/// Parse a decimal reported by an NFT API, ignoring unparsable values
+fn parse_decimal(value: &str) -> Option<Decimal> {
+    Decimal::from_str(value).or_else(|_| Decimal::from_scientific(value)).ok()
+}
+
+/// Build the rows stored for a sync's NFTs, skipping unparsable quantities and repeated
+/// (chain, contract, token ID) entries
+fn nft_rows(account_id: Uuid, nfts: &[NftHolding], synced_at: DateTime<Utc>) -> Vec<nft_holdings::ActiveModel> {
+    let mut seen = HashSet::new();
+    nfts.iter()
+        .filter_map(|nft| {
+            if !seen.insert((&nft.chain, &nft.contract_address, &nft.token_id)) {
+                return None;
+            }
+            let Some(quantity) = parse_decimal(&nft.quantity) else {
+                tracing::warn!(
+                    "Skipping NFT {}/{} with invalid quantity '{}'",
+                    nft.contract_address,
+                    nft.token_id,
+                    nft.quantity
+                );
+                return None;
+            };
+
+            Some(nft_holdings::ActiveModel {
+                id: ActiveValue::Set(Uuid::new_v4()),
+                account_id: ActiveValue::Set(account_id),
+                chain: ActiveValue::Set(nft.chain.clone()),
+                contract_address: ActiveValue::Set(nft.contract_address.clone()),
+                token_id: ActiveValue::Set(nft.token_id.clone()),
+                token_standard: ActiveValue::Set(nft.token_standard.clone()),
+                quantity: ActiveValue::Set(quantity),
+                collection_name: ActiveValue::Set(nft.collection_name.clone()),
+                name: ActiveValue::Set(nft.name.clone()),
+                image_url: ActiveValue::Set(nft.image_url.clone()),
+                floor_price: ActiveValue::Set(nft.floor_price.as_deref().and_then(parse_decimal)),
+                floor_currency: ActiveValue::Set(nft.floor_currency.clone()),
+                synced_at: ActiveValue::Set(synced_at.into()),
+            })
+        })
+        .collect()
+}
+
+/// Replace an account's stored NFTs with the ones observed by a sync.
+///
+/// NFTs absent from `current` were sold or transferred, so the account's rows are deleted
+/// and re-inserted in one transaction. Returns the number of stored NFTs.
+pub async fn replace_nft_holdings(
+    db: &DatabaseConnection,
+    account_id: Uuid,
+    current: &[NftHolding],
+) -> Result<usize, Box<dyn Error + Send + Sync>> {
+    let rows = nft_rows(account_id, current, Utc::now());
+    let count = rows.len();
+
+    let txn = db.begin().await?;
+    nft_holdings::Entity::delete_many()
+        .filter(nft_holdings::Column::AccountId.eq(account_id))
+        .exec(&txn)
+        .await?;
+    if !rows.is_empty() {
+        nft_holdings::Entity::insert_many(rows).exec(&txn).await?;
+    }
+    txn.commit().await?;
+
+    Ok(count)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_nft_rows_dedupe_and_parse() {
+        let nft = NftHolding {
+            chain: "ethereum".to_string(),
+            contract_address: "0xabc".to_string(),
+            token_id: "1".to_string(),
+            token_standard: Some("ERC721".to_string()),
+            quantity: "1".to_string(),
+            collection_name: None,
+            name: None,
+            image_url: None,
+            floor_price: Some("1.5e-2".to_string()),
+            floor_currency: Some("ETH".to_string()),
+        };
+        let invalid = NftHolding { token_id: "2".to_string(), quantity: "n/a".to_string(), ..nft.clone() };
+
+        let rows = nft_rows(Uuid::new_v4(), &[nft.clone(), nft, invalid], Utc::now());
+        assert_eq!(rows.len(), 1);
+        assert_eq!(rows[0].floor_price, ActiveValue::Set(Some(Decimal::new(15, 3))));
+    }
+}
@@ -0,0 +1,160 @@
+use crate::entities::{portfolio_performance, portfolios};
+use crate::helpers::performance::{daily_performance, load_cash_flows, load_value_series, CashFlows};
+use crate::jobs::portfolio_pnl::portfolio_account_ids;
+use chrono::{NaiveDate, Utc};
+use rust_decimal::Decimal;
+use sea_orm::{
+    ActiveValue, ColumnTrait, DatabaseConnection, DbErr, EntityTrait, QueryFilter, QueryOrder, TransactionTrait,
+};
+use std::collections::BTreeMap;
+use std::error::Error;
+use tracing;
+use uuid::Uuid;
+
+/// Unpriced flows dated after `after` up to and including `until`
+fn unpriced_between(unpriced: &BTreeMap<NaiveDate, usize>, after: Option<NaiveDate>, until: NaiveDate) -> usize {
+    match after {
+        Some(after) if after < until => unpriced.range(after.succ_opt().unwrap_or(after)..=until).map(|(_, n)| n).sum(),
+        Some(_) => 0,
+        None => unpriced.range(..=until).map(|(_, n)| n).sum(),
+    }
+}
+
+/// Recompute a portfolio's daily return, cumulative return, drawdown and value per snapshot
+/// date from its snapshots and its accounts' deposits and withdrawals, and replace its
+/// `portfolio_performance` rows. Returns the number of rows stored.
+pub async fn compute_portfolio_performance(
+    db: &DatabaseConnection,
+    portfolio_id: Uuid,
+) -> Result<usize, Box<dyn Error + Send + Sync>> {
+    let values = load_value_series(db, portfolio_id, None).await?;
+    let flows = match (values.first(), values.last()) {
+        (Some((first, _)), Some((last, _))) => {
+            let account_ids = portfolio_account_ids(db, portfolio_id).await?;
+            load_cash_flows(db, &account_ids, *first, *last).await?
+        }
+        _ => CashFlows::default(),
+    };
+
+    let now = Utc::now();
+    let days = daily_performance(&values, &flows.by_date);
+    let mut previous = None;
+    let models: Vec<portfolio_performance::ActiveModel> = days
+        .iter()
+        .map(|day| {
+            let unpriced = unpriced_between(&flows.unpriced_by_date, previous, day.date);
+            previous = Some(day.date);
+            portfolio_performance::ActiveModel {
+                id: ActiveValue::Set(Uuid::new_v4()),
+                portfolio_id: ActiveValue::Set(portfolio_id),
+                date: ActiveValue::Set(day.date),
+                total_value_usd: ActiveValue::Set(day.value_usd),
+                net_flow_usd: ActiveValue::Set(day.net_flow_usd),
+                daily_return: ActiveValue::Set(day.return_rate),
+                cumulative_return: ActiveValue::Set(day.cumulative_return),
+                drawdown: ActiveValue::Set(day.drawdown),
+                unpriced_flows: ActiveValue::Set(unpriced as i32),
+                computed_at: ActiveValue::Set(now.into()),
+            }
+        })
+        .collect();
+
+    let txn = db.begin().await?;
+    portfolio_performance::Entity::delete_many()
+        .filter(portfolio_performance::Column::PortfolioId.eq(portfolio_id))
+        .exec(&txn)
+        .await?;
+    if !models.is_empty() {
+        portfolio_performance::Entity::insert_many(models).exec_without_returning(&txn).await?;
+    }
+    txn.commit().await?;
+
+    Ok(days.len())
+}
+
+/// Recompute the daily performance of every portfolio; returns the number of portfolios
+/// computed. A portfolio that fails is logged and skipped.
+pub async fn compute_all_portfolio_performance(db: &DatabaseConnection) -> Result<usize, Box<dyn Error + Send + Sync>> {
+    let portfolio_ids: Vec<Uuid> = portfolios::Entity::find().all(db).await?.into_iter().map(|p| p.id).collect();
+    let mut computed = 0;
+    for portfolio_id in portfolio_ids {
+        match compute_portfolio_performance(db, portfolio_id).await {
+            Ok(_) => computed += 1,
+            Err(e) => tracing::warn!("Failed to compute performance of portfolio {}: {}", portfolio_id, e),
+        }
+    }
+    tracing::info!("Portfolio performance computed for {} portfolios", computed);
+    Ok(computed)
+}
+
+/// Stored daily performance of a portfolio, oldest first
+pub async fn load_portfolio_performance(
+    db: &DatabaseConnection,
+    portfolio_id: Uuid,
+) -> Result<Vec<portfolio_performance::Model>, DbErr> {
+    portfolio_performance::Entity::find()
+        .filter(portfolio_performance::Column::PortfolioId.eq(portfolio_id))
+        .order_by_asc(portfolio_performance::Column::Date)
+        .all(db)
+        .await
+}
+
+/// Cash flows as stored with the rows, each dated on its row's snapshot date, when the rows
+/// match `values` date for date; `None` when they are stale (a snapshot was taken, changed or
+/// removed since they were computed) or missing
+pub fn stored_cash_flows(rows: &[portfolio_performance::Model], values: &[(NaiveDate, Decimal)]) -> Option<CashFlows> {
+    if rows.is_empty()
+        || rows.len() != values.len()
+        || rows.iter().zip(values).any(|(row, (date, value))| row.date != *date || row.total_value_usd != *value)
+    {
+        return None;
+    }
+    let mut flows = CashFlows::default();
+    for row in rows {
+        if !row.net_flow_usd.is_zero() {
+            flows.by_date.insert(row.date, row.net_flow_usd);
+        }
+        if row.unpriced_flows > 0 {
+            flows.unpriced += row.unpriced_flows as usize;
+            flows.unpriced_by_date.insert(row.date, row.unpriced_flows as usize);
+        }
+    }
+    Some(flows)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_stored_cash_flows() {
+        let date = |d: u32| NaiveDate::from_ymd_opt(2025, 1, d).unwrap();
+        let row = |d: u32, value: i64, flow: i64, unpriced: i32| portfolio_performance::Model {
+            id: Uuid::new_v4(),
+            portfolio_id: Uuid::nil(),
+            date: date(d),
+            total_value_usd: Decimal::from(value),
+            net_flow_usd: Decimal::from(flow),
+            daily_return: None,
+            cumulative_return: Decimal::ZERO,
+            drawdown: Decimal::ZERO,
+            unpriced_flows: unpriced,
+            computed_at: Utc::now().into(),
+        };
+        let rows = vec![row(1, 1000, 0, 0), row(3, 1600, 500, 1)];
+        let values = vec![(date(1), Decimal::from(1000)), (date(3), Decimal::from(1600))];
+        let flows = stored_cash_flows(&rows, &values).unwrap();
+        assert_eq!(flows.by_date, BTreeMap::from([(date(3), Decimal::from(500))]));
+        assert_eq!(flows.unpriced, 1);
+
+        // A newer snapshot makes the rows stale
+        let mut newer = values.clone();
+        newer.push((date(4), Decimal::from(1700)));
+        assert!(stored_cash_flows(&rows, &newer).is_none());
+        assert!(stored_cash_flows(&[], &[]).is_none());
+
+        let unpriced = BTreeMap::from([(date(2), 2), (date(3), 1)]);
+        assert_eq!(unpriced_between(&unpriced, Some(date(1)), date(3)), 3);
+        assert_eq!(unpriced_between(&unpriced, None, date(2)), 2);
+    }
+}
@@ -0,0 +1,273 @@
+use crate::connectors::{TRANSFER_DEPOSIT, TRANSFER_WITHDRAWAL};
+use crate::entities::{income_events, portfolio_accounts, portfolio_pnl, portfolios, trades, transfers};
+use crate::helpers::asset_identity::{split_chain_suffix, AssetIdentityNormalizer, NormalizationResult};
+use crate::helpers::cost_basis::{average_cost_basis, LedgerAction, LedgerEntry};
+use crate::helpers::price_resolution::price_at;
+use chrono::{DateTime, Utc};
+use rust_decimal::Decimal;
+use sea_orm::{
+    ActiveValue, ColumnTrait, DatabaseConnection, DbErr, EntityTrait, QueryFilter, TransactionTrait,
+};
+use std::collections::HashMap;
+use std::error::Error;
+use tracing;
+use uuid::Uuid;
+
+/// Ledger key of a symbol: chain suffix dropped and uppercased, so "ETH-arbitrum" and "eth" match
+pub fn ledger_asset(symbol: &str) -> String {
+    split_chain_suffix(symbol.trim()).0.to_uppercase()
+}
+
+/// USD prices of ledger symbols at past times, caching symbol lookups
+pub struct LedgerPricer {
+    db: DatabaseConnection,
+    normalizer: AssetIdentityNormalizer,
+    asset_ids: HashMap<String, Option<Uuid>>,
+}
+
+impl LedgerPricer {
+    pub fn new(db: DatabaseConnection) -> Self {
+        Self { normalizer: AssetIdentityNormalizer::new(db.clone()), db, asset_ids: HashMap::new() }
+    }
+
+    /// Latest stored price of `symbol` at or before `at`; `None` for unknown or unpriced assets
+    pub async fn price(&mut self, symbol: &str, at: DateTime<Utc>) -> Result<Option<Decimal>, DbErr> {
+        let key = ledger_asset(symbol);
+        let asset_id = match self.asset_ids.get(&key) {
+            Some(asset_id) => *asset_id,
+            None => {
+                let asset_id = match self.normalizer.normalize_from_symbol(&key).await {
+                    NormalizationResult::Mapped(identity) => Some(identity.asset_id),
+                    NormalizationResult::Unknown { .. } => None,
+                };
+                self.asset_ids.insert(key, asset_id);
+                asset_id
+            }
+        };
+        match asset_id {
+            Some(asset_id) => price_at(&self.db, asset_id, at).await,
+            None => Ok(None),
+        }
+    }
+}
+
+/// Ledger of a set of accounts, oldest first
+#[derive(Debug, Clone, Default)]
+pub struct Ledger {
+    pub entries: Vec<LedgerEntry>,
+    /// Purchases among the entries: the base side of spot buys and deposits, oldest first
+    pub purchases: Vec<LedgerEntry>,
+    /// Entries valued at zero, or trades left out, because no price was known at the time
+    pub unpriced: usize,
+}
+
+fn ledger_entry(at: DateTime<Utc>, asset: &str, action: LedgerAction, quantity: Decimal, value_usd: Decimal) -> LedgerEntry {
+    LedgerEntry { at, asset: ledger_asset(asset), action, quantity, value_usd, transfer_id: None }
+}
+
+/// Build the ledger of `account_ids` from their spot trades, transfers and income.
+///
+/// A trade acquires one side and disposes the other at the same USD value, taken from the
+/// quote's price (or the base's when the quote has none); trades with neither are left out.
+/// Fees paid are disposals with no proceeds, rebates acquisitions at market value. Deposits
+/// and income enter at their value when received.
+pub async fn load_ledger(db: &DatabaseConnection, account_ids: &[Uuid]) -> Result<Ledger, DbErr> {
+    let mut ledger = Ledger::default();
+    if account_ids.is_empty() {
+        return Ok(ledger);
+    }
+    let mut pricer = LedgerPricer::new(db.clone());
+
+    let trade_rows = trades::Entity::find()
+        .filter(trades::Column::AccountId.is_in(account_ids.to_vec()))
+        .filter(trades::Column::InstrumentType.eq("SPOT"))
+        .all(db)
+        .await?;
+    for trade in trade_rows {
+        let (Some(base), Some(quote)) = (trade.base_asset.as_deref(), trade.quote_asset.as_deref()) else {
+            continue;
+        };
+        let at = trade.executed_at.to_utc();
+        let notional = trade.quantity * trade.price;
+        let value_usd = match pricer.price(quote, at).await? {
+            Some(price) => notional * price,
+            None => match pricer.price(base, at).await? {
+                Some(price) => trade.quantity * price,
+                None => {
+                    ledger.unpriced += 1;
+                    continue;
+                }
+            },
+        };
+        let (acquired, acquired_qty, disposed, disposed_qty) = if trade.side == "buy" {
+            (base, trade.quantity, quote, notional)
+        } else {
+            (quote, notional, base, trade.quantity)
+        };
+        let acquisition = ledger_entry(at, acquired, LedgerAction::Acquire, acquired_qty, value_usd);
+        if trade.side == "buy" {
+            ledger.purchases.push(acquisition.clone());
+        }
+        ledger.entries.push(acquisition);
+        ledger.entries.push(ledger_entry(at, disposed, LedgerAction::Dispose, disposed_qty, value_usd));
+
+        if let (Some(fee), Some(currency)) = (trade.fee, trade.fee_currency.as_deref()) {
+            if fee > Decimal::ZERO {
+                ledger.entries.push(ledger_entry(at, currency, LedgerAction::Dispose, fee, Decimal::ZERO));
+            } else if fee < Decimal::ZERO {
+                let value = pricer.price(currency, at).await?.map(|p| -fee * p).unwrap_or_else(|| {
+                    ledger.unpriced += 1;
+                    Decimal::ZERO
+                });
+                ledger.entries.push(ledger_entry(at, currency, LedgerAction::Acquire, -fee, value));
+            }
+        }
+    }
+
+    let transfer_rows = transfers::Entity::find()
+        .filter(transfers::Column::AccountId.is_in(account_ids.to_vec()))
+        .all(db)
+        .await?;
+    for transfer in transfer_rows {
+        let at = transfer.occurred_at.to_utc();
+        match transfer.direction.as_str() {
+            TRANSFER_DEPOSIT => {
+                let value = pricer.price(&transfer.asset, at).await?.map(|p| transfer.amount * p).unwrap_or_else(|| {
+                    ledger.unpriced += 1;
+                    Decimal::ZERO
+                });
+                let deposit = LedgerEntry {
+                    transfer_id: Some(transfer.id),
+                    ..ledger_entry(at, &transfer.asset, LedgerAction::TransferIn, transfer.amount, value)
+                };
+                ledger.purchases.push(deposit.clone());
+                ledger.entries.push(deposit);
+            }
+            TRANSFER_WITHDRAWAL => {
+                ledger.entries.push(LedgerEntry {
+                    transfer_id: Some(transfer.id),
+                    ..ledger_entry(at, &transfer.asset, LedgerAction::TransferOut, transfer.amount, Decimal::ZERO)
+                });
+                if let Some(fee) = transfer.fee.filter(|fee| *fee > Decimal::ZERO) {
+                    ledger.entries.push(ledger_entry(at, &transfer.asset, LedgerAction::Dispose, fee, Decimal::ZERO));
+                }
+            }
+            _ => {}
+        }
+    }
+
+    let income_rows = income_events::Entity::find()
+        .filter(income_events::Column::AccountId.is_in(account_ids.to_vec()))
+        .all(db)
+        .await?;
+    for event in income_rows {
+        if event.value_usd.is_none() {
+            ledger.unpriced += 1;
+        }
+        let value = event.value_usd.unwrap_or(Decimal::ZERO);
+        ledger.entries.push(ledger_entry(event.received_at.to_utc(), &event.asset, LedgerAction::Acquire, event.quantity, value));
+    }
+
+    // Stable, so the two sides of a trade keep their order
+    ledger.entries.sort_by_key(|entry| entry.at);
+    ledger.purchases.sort_by_key(|entry| entry.at);
+    Ok(ledger)
+}
+
+/// Account IDs of a portfolio
+pub async fn portfolio_account_ids(db: &DatabaseConnection, portfolio_id: Uuid) -> Result<Vec<Uuid>, DbErr> {
+    Ok(portfolio_accounts::Entity::find()
+        .filter(portfolio_accounts::Column::PortfolioId.eq(portfolio_id))
+        .all(db)
+        .await?
+        .into_iter()
+        .map(|pa| pa.account_id)
+        .collect())
+}
+
+/// Recompute a portfolio's cost basis and P&L from its accounts' ledger and replace its
+/// `portfolio_pnl` rows. Held quantities are valued at the latest stored price.
+///
+/// Returns the stored rows and the number of unpriced ledger entries.
+pub async fn compute_portfolio_pnl(
+    db: &DatabaseConnection,
+    portfolio_id: Uuid,
+) -> Result<(Vec<portfolio_pnl::Model>, usize), Box<dyn Error + Send + Sync>> {
+    let account_ids = portfolio_account_ids(db, portfolio_id).await?;
+    let ledger = load_ledger(db, &account_ids).await?;
+    let positions = average_cost_basis(&ledger.entries);
+
+    let now = Utc::now();
+    let mut pricer = LedgerPricer::new(db.clone());
+    let mut rows = Vec::with_capacity(positions.len());
+    for (asset, position) in positions {
+        let price = if position.quantity > Decimal::ZERO { pricer.price(&asset, now).await? } else { None };
+        rows.push(portfolio_pnl::Model {
+            id: Uuid::new_v4(),
+            portfolio_id,
+            asset,
+            quantity: position.quantity,
+            cost_basis_usd: position.cost_basis_usd,
+            realized_pnl_usd: position.realized_pnl_usd,
+            price_usd: price,
+            unrealized_pnl_usd: match price {
+                Some(price) => Some(position.unrealized_pnl(price)),
+                None if position.quantity.is_zero() => Some(Decimal::ZERO),
+                None => None,
+            },
+            unmatched_quantity: position.unmatched_quantity,
+            computed_at: now.into(),
+        });
+    }
+
+    let txn = db.begin().await?;
+    portfolio_pnl::Entity::delete_many()
+        .filter(portfolio_pnl::Column::PortfolioId.eq(portfolio_id))
+        .exec(&txn)
+        .await?;
+    if !rows.is_empty() {
+        let models = rows.iter().cloned().map(|row| portfolio_pnl::ActiveModel {
+            id: ActiveValue::Set(row.id),
+            portfolio_id: ActiveValue::Set(row.portfolio_id),
+            asset: ActiveValue::Set(row.asset),
+            quantity: ActiveValue::Set(row.quantity),
+            cost_basis_usd: ActiveValue::Set(row.cost_basis_usd),
+            realized_pnl_usd: ActiveValue::Set(row.realized_pnl_usd),
+            price_usd: ActiveValue::Set(row.price_usd),
+            unrealized_pnl_usd: ActiveValue::Set(row.unrealized_pnl_usd),
+            unmatched_quantity: ActiveValue::Set(row.unmatched_quantity),
+            computed_at: ActiveValue::Set(row.computed_at),
+        });
+        portfolio_pnl::Entity::insert_many(models).exec_without_returning(&txn).await?;
+    }
+    txn.commit().await?;
+
+    Ok((rows, ledger.unpriced))
+}
+
+/// Recompute the P&L of every portfolio; returns the number of portfolios computed.
+/// A portfolio that fails is logged and skipped.
+pub async fn compute_all_portfolio_pnl(db: &DatabaseConnection) -> Result<usize, Box<dyn Error + Send + Sync>> {
+    let portfolio_ids: Vec<Uuid> = portfolios::Entity::find().all(db).await?.into_iter().map(|p| p.id).collect();
+    let mut computed = 0;
+    for portfolio_id in portfolio_ids {
+        match compute_portfolio_pnl(db, portfolio_id).await {
+            Ok(_) => computed += 1,
+            Err(e) => tracing::warn!("Failed to compute P&L of portfolio {}: {}", portfolio_id, e),
+        }
+    }
+    tracing::info!("Portfolio P&L computed for {} portfolios", computed);
+    Ok(computed)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_ledger_asset() {
+        assert_eq!(ledger_asset("ETH-arbitrum"), "ETH");
+        assert_eq!(ledger_asset(" usdt "), "USDT");
+        assert_eq!(ledger_asset("WETH-E"), "WETH-E");
+    }
+}
@@ -1,11 +1,21 @@
-use crate::domain::{AllocationItem, SnapshotHolding, SnapshotMetadata};
-use crate::entities::{portfolio_allocations, portfolios, snapshots};
+use crate::domain::settings::volume_weighted_average;
+use crate::domain::{
+    AllocationItem, DebtMode, DebtSummary, EodPriceMethod, EodValuationSettings, PortfolioSettings, SnapshotHolding,
+    SnapshotMetadata,
+};
+use crate::entities::{asset_prices, portfolio_allocations, portfolios, snapshots};
+use crate::helpers::asset_identity::{AssetIdentityNormalizer, NormalizationResult};
+use crate::jobs::anomaly_detection;
 use chrono::{Utc, NaiveDate};
+use rust_decimal::prelude::{FromPrimitive, ToPrimitive};
+use rust_decimal::Decimal;
 use sea_orm::{
     ActiveModelTrait, ActiveValue, ColumnTrait, DatabaseConnection, EntityTrait, QueryFilter, QueryOrder,
 };
 use serde_json::json;
+use std::collections::HashMap;
 use std::error::Error;
+use std::str::FromStr;
 use tracing;
 use uuid::Uuid;
 
@@ -25,7 +35,8 @@ pub struct SnapshotResult {
 /// This function:
 /// 1. Fetches the latest portfolio allocation
 /// 2. Creates a snapshot from the allocation data
-/// 3. Persists the snapshot to the database
+/// 3. For EOD snapshots, re-prices holdings using the portfolio's EOD valuation settings
+/// 4. Persists the snapshot to the database
 ///
 /// # Arguments
 /// * `db` - Database connection
@@ -90,13 +101,51 @@ pub async fn create_portfolio_snapshot(
             .map_err(|e| format!("Failed to deserialize allocation holdings: {}", e))?;
 
     // Convert AllocationItems to SnapshotHoldings using From trait
-    let holdings: Vec<SnapshotHolding> = allocation_items
+    let mut holdings: Vec<SnapshotHolding> = allocation_items
         .into_iter()
         .map(SnapshotHolding::from)
         .collect();
+    let mut total_value_usd = allocation.total_value_usd;
+    let settings = PortfolioSettings::from_json(portfolio.settings.as_ref());
+
+    // The allocation may predate a change to the excluded assets, so filter again
+    let allocated_count = holdings.len();
+    holdings.retain(|h| !settings.excludes_asset(&h.asset));
+    if holdings.len() < allocated_count {
+        (holdings, total_value_usd) = reweight_holdings(holdings, settings.debt_mode);
+        tracing::info!(
+            "Excluded {} assets from snapshot of portfolio {}",
+            allocated_count - holdings.len(),
+            portfolio_id
+        );
+    }
+
+    // EOD snapshots use the close price selected in the portfolio settings
+    let valuation = settings.eod_valuation;
+    let mut valuation_cutoff = None;
+    let mut price_fallback_assets = Vec::new();
+    if snapshot_type == "eod" && valuation.price_method != EodPriceMethod::Allocation {
+        let (_, cutoff) = valuation.price_window(snapshot_date);
+        let prices = resolve_eod_prices(db, &holdings, &valuation, snapshot_date).await?;
+        price_fallback_assets = holdings
+            .iter()
+            .filter(|h| !prices.contains_key(&h.asset))
+            .map(|h| h.asset.clone())
+            .collect();
+        (holdings, total_value_usd) = reprice_holdings(holdings, &prices, settings.debt_mode);
+        valuation_cutoff = Some(cutoff.to_rfc3339());
+
+        tracing::info!(
+            "Portfolio {} valued with '{}' prices up to {} ({} assets kept allocation price)",
+            portfolio_id,
+            valuation.price_method.as_str(),
+            cutoff,
+            price_fallback_assets.len()
+        );
+    }
 
     let holdings_count = holdings.len();
-    let total_value_usd = allocation.total_value_usd;
+    let debt = DebtSummary::from_values(holdings.iter().map(|h| (h.value_usd, h.debt_usd())));
 
     tracing::info!(
         "Portfolio {} snapshot: {} assets, total value: ${}",
@@ -114,6 +163,10 @@ pub async fn create_portfolio_snapshot(
         allocation_as_of: allocation.as_of.to_rfc3339(),
         snapshot_time: now.to_rfc3339(),
         created_at: now.to_rfc3339(),
+        price_method: (snapshot_type == "eod").then(|| valuation.price_method.as_str().to_string()),
+        utc_offset_minutes: (snapshot_type == "eod").then_some(valuation.utc_offset_minutes),
+        valuation_cutoff,
+        price_fallback_assets,
     };
     
     let snapshot = snapshots::ActiveModel {
@@ -126,6 +179,10 @@ pub async fn create_portfolio_snapshot(
         allocation_id: ActiveValue::Set(Some(allocation.id)),
         metadata: ActiveValue::Set(Some(json!(metadata).into())),
         created_at: ActiveValue::Set(now.into()),
+        construction_run_id: ActiveValue::Set(allocation.construction_run_id),
+        gross_value_usd: ActiveValue::Set(Decimal::from_f64(debt.gross_value_usd)),
+        debt_usd: ActiveValue::Set(Decimal::from_f64(debt.debt_usd)),
+        net_value_usd: ActiveValue::Set(Decimal::from_f64(debt.net_value_usd)),
     };
 
     // Insert snapshot into database
@@ -148,10 +205,106 @@ pub async fn create_portfolio_snapshot(
     })
 }
 
+/// Look up the EOD price of each holding according to the valuation settings.
+///
+/// Returns prices keyed by holding asset symbol. Assets that cannot be mapped to
+/// a known asset, or have no price inside the valuation window, are left out.
+async fn resolve_eod_prices(
+    db: &DatabaseConnection,
+    holdings: &[SnapshotHolding],
+    valuation: &EodValuationSettings,
+    snapshot_date: NaiveDate,
+) -> Result<HashMap<String, Decimal>, Box<dyn Error + Send + Sync>> {
+    let (window_start, cutoff) = valuation.price_window(snapshot_date);
+    let normalizer = AssetIdentityNormalizer::new(db.clone());
+    let mut prices = HashMap::new();
+
+    for holding in holdings {
+        let asset_id = match normalizer.normalize_from_symbol(&holding.asset).await {
+            NormalizationResult::Mapped(identity) => identity.asset_id,
+            NormalizationResult::Unknown { .. } => continue,
+        };
+
+        let price = match valuation.price_method {
+            EodPriceMethod::Allocation => continue,
+            EodPriceMethod::LastBeforeLocalMidnight | EodPriceMethod::UtcMidnight => {
+                asset_prices::Entity::find()
+                    .filter(asset_prices::Column::AssetId.eq(asset_id))
+                    .filter(asset_prices::Column::Timestamp.lt(cutoff))
+                    .order_by_desc(asset_prices::Column::Timestamp)
+                    .one(db)
+                    .await?
+                    .map(|p| p.price_usd)
+            }
+            EodPriceMethod::DailyVwap => {
+                let samples: Vec<(Decimal, Option<Decimal>)> = asset_prices::Entity::find()
+                    .filter(asset_prices::Column::AssetId.eq(asset_id))
+                    .filter(asset_prices::Column::Timestamp.gte(window_start))
+                    .filter(asset_prices::Column::Timestamp.lt(cutoff))
+                    .all(db)
+                    .await?
+                    .into_iter()
+                    .map(|p| (p.price_usd, p.volume_24h_usd))
+                    .collect();
+                volume_weighted_average(&samples)
+            }
+        };
+
+        if let Some(price) = price {
+            prices.insert(holding.asset.clone(), price);
+        }
+    }
+
+    Ok(prices)
+}
+
+/// Re-value holdings with the given prices and recompute weights.
+///
+/// Holdings without an entry in `prices` keep their existing price and value.
+/// Returns the updated holdings and the new total value under `debt_mode`.
+fn reprice_holdings(
+    holdings: Vec<SnapshotHolding>,
+    prices: &HashMap<String, Decimal>,
+    debt_mode: DebtMode,
+) -> (Vec<SnapshotHolding>, Decimal) {
+    let holdings: Vec<SnapshotHolding> = holdings
+        .into_iter()
+        .map(|mut holding| {
+            if let Some(price) = prices.get(&holding.asset) {
+                let quantity = Decimal::from_str(&holding.quantity).unwrap_or(Decimal::ZERO);
+                holding.price_usd = price.to_f64();
+                holding.value_usd = (quantity * price).to_f64().unwrap_or(0.0);
+                holding.unpriced = false;
+            }
+            holding
+        })
+        .collect();
+
+    reweight_holdings(holdings, debt_mode)
+}
+
+/// Recompute holding weights from their values; returns the holdings and the new total.
+///
+/// Under [`DebtMode::Gross`] the total and each holding's weight leave out borrowed amounts.
+fn reweight_holdings(mut holdings: Vec<SnapshotHolding>, debt_mode: DebtMode) -> (Vec<SnapshotHolding>, Decimal) {
+    let total = DebtSummary::from_values(holdings.iter().map(|h| (h.value_usd, h.debt_usd()))).total(debt_mode);
+    for holding in holdings.iter_mut() {
+        let value = match debt_mode {
+            DebtMode::Net => holding.value_usd,
+            DebtMode::Gross => holding.value_usd + holding.debt_usd(),
+        };
+        holding.weight = if total > 0.0 { value / total * 100.0 } else { 0.0 };
+    }
+
+    (holdings, Decimal::from_f64(total).unwrap_or(Decimal::ZERO))
+}
+
 /// Create EOD snapshots for all portfolios
 ///
 /// This function creates EOD snapshots for all portfolios in the system.
 /// It's designed to be run as a scheduled job at the configured cutover time.
+/// Portfolios with an unacknowledged holdings anomaly on any of their accounts
+/// are skipped and reported as failed until the user acknowledges the anomaly.
 ///
 /// # Arguments
 /// * `db` - Database connection
@@ -179,6 +332,35 @@ pub async fn create_all_portfolio_snapshots(
     let mut results = Vec::new();
 
     for portfolio in all_portfolios {
+        // Skip portfolios held by an unacknowledged holdings anomaly
+        match anomaly_detection::is_portfolio_snapshot_on_hold(db, portfolio.id).await {
+            Ok(false) => {}
+            Ok(true) => {
+                tracing::warn!(
+                    "Skipping EOD snapshot for portfolio {} ({}): unacknowledged holdings anomaly",
+                    portfolio.name,
+                    portfolio.id
+                );
+                results.push(SnapshotResult {
+                    portfolio_id: portfolio.id,
+                    snapshot_id: None,
+                    success: false,
+                    error: Some("Snapshot on hold: unacknowledged holdings anomaly".to_string()),
+                    holdings_count: 0,
+                    total_value_usd: "0".to_string(),
+                });
+                continue;
+            }
+            Err(e) => {
+                tracing::error!(
+                    "Failed to check snapshot hold for portfolio {} ({}): {}",
+                    portfolio.name,
+                    portfolio.id,
+                    e
+                );
+            }
+        }
+
         match create_portfolio_snapshot(db, portfolio.id, Some(snapshot_date), "eod").await {
             Ok(result) => {
                 tracing::info!(
@@ -220,3 +402,100 @@ pub async fn create_all_portfolio_snapshots(
 
     Ok(results)
 }
+
+/// Create "hourly" snapshots of the portfolios whose snapshot cadence is due at `hour` (UTC)
+///
+/// Runs every hour; each portfolio's `snapshot_cadence` setting decides whether it is
+/// snapshotted ("every_6h" at hours divisible by 6, "hourly" every hour, "eod" never).
+/// Portfolios on hold for an unacknowledged holdings anomaly are skipped, as for EOD snapshots.
+///
+/// # Returns
+/// Result containing a SnapshotResult for every due portfolio
+pub async fn create_intraday_snapshots(
+    db: &DatabaseConnection,
+    hour: u32,
+) -> Result<Vec<SnapshotResult>, Box<dyn Error + Send + Sync>> {
+    let due: Vec<portfolios::Model> = portfolios::Entity::find()
+        .all(db)
+        .await?
+        .into_iter()
+        .filter(|p| PortfolioSettings::from_json(p.settings.as_ref()).snapshot_cadence.is_due(hour))
+        .collect();
+
+    tracing::info!("Found {} portfolios due for an intraday snapshot at {:02}:00 UTC", due.len(), hour);
+
+    let mut results = Vec::new();
+    for portfolio in due {
+        if anomaly_detection::is_portfolio_snapshot_on_hold(db, portfolio.id).await.unwrap_or(false) {
+            tracing::warn!(
+                "Skipping intraday snapshot for portfolio {} ({}): unacknowledged holdings anomaly",
+                portfolio.name,
+                portfolio.id
+            );
+            continue;
+        }
+
+        match create_portfolio_snapshot(db, portfolio.id, None, "hourly").await {
+            Ok(result) => results.push(result),
+            Err(e) => {
+                tracing::error!(
+                    "Failed to create intraday snapshot for portfolio {} ({}): {}",
+                    portfolio.name,
+                    portfolio.id,
+                    e
+                );
+                results.push(SnapshotResult {
+                    portfolio_id: portfolio.id,
+                    snapshot_id: None,
+                    success: false,
+                    error: Some(format!("Snapshot failed: {}", e)),
+                    holdings_count: 0,
+                    total_value_usd: "0".to_string(),
+                });
+            }
+        }
+    }
+
+    Ok(results)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn holding(asset: &str, quantity: &str, price: Option<f64>) -> SnapshotHolding {
+        let value = price.map(|p| p * quantity.parse::<f64>().unwrap()).unwrap_or(0.0);
+        SnapshotHolding {
+            asset: asset.to_string(),
+            quantity: quantity.to_string(),
+            price_usd: price,
+            value_usd: value,
+            weight: 0.0,
+            unpriced: price.is_none(),
+            borrowed_quantity: None,
+        }
+    }
+
+    #[test]
+    fn test_reprice_holdings_recomputes_values_and_weights() {
+        let holdings = vec![
+            holding("BTC", "1", Some(40000.0)),
+            holding("ETH", "10", Some(2000.0)),
+            holding("NEW", "5", None),
+        ];
+        let prices = HashMap::from([
+            ("BTC".to_string(), Decimal::from(30000)),
+            ("NEW".to_string(), Decimal::from(2000)),
+        ]);
+
+        let (repriced, total) = reprice_holdings(holdings, &prices, DebtMode::Net);
+
+        assert_eq!(total, Decimal::from(60000));
+        assert_eq!(repriced[0].value_usd, 30000.0);
+        assert_eq!(repriced[0].weight, 50.0);
+        // ETH had no EOD price and keeps its allocation value
+        assert_eq!(repriced[1].value_usd, 20000.0);
+        assert!(!repriced[2].unpriced);
+        assert_eq!(repriced[2].price_usd, Some(2000.0));
+    }
+}
@@ -0,0 +1,101 @@
+use crate::connectors::Position;
+use crate::entities::positions;
+use chrono::{DateTime, Utc};
+use rust_decimal::Decimal;
+use sea_orm::{
+    ActiveValue, ColumnTrait, DatabaseConnection, EntityTrait, QueryFilter, TransactionTrait,
+};
+use std::error::Error;
+use std::str::FromStr;
+use uuid::Uuid;
+
+/// Parse an optional decimal string reported by a connector, ignoring unparsable values
+fn parse_decimal(value: Option<&str>) -> Option<Decimal> {
+    value.and_then(|v| Decimal::from_str(v).or_else(|_| Decimal::from_scientific(v)).ok())
+}
+
+/// Build the rows stored for a sync's positions, skipping positions with an unparsable size
+fn position_rows(account_id: Uuid, positions: &[Position], synced_at: DateTime<Utc>) -> Vec<positions::ActiveModel> {
+    positions
+        .iter()
+        .filter_map(|p| {
+            let Some(size) = parse_decimal(Some(&p.size)) else {
+                tracing::warn!("Skipping position {} with invalid size '{}'", p.instrument, p.size);
+                return None;
+            };
+
+            Some(positions::ActiveModel {
+                id: ActiveValue::Set(Uuid::new_v4()),
+                account_id: ActiveValue::Set(account_id),
+                instrument: ActiveValue::Set(p.instrument.clone()),
+                instrument_type: ActiveValue::Set(p.instrument_type.clone()),
+                side: ActiveValue::Set(p.side.clone()),
+                size: ActiveValue::Set(size),
+                entry_price: ActiveValue::Set(parse_decimal(p.entry_price.as_deref())),
+                mark_price: ActiveValue::Set(parse_decimal(p.mark_price.as_deref())),
+                unrealized_pnl: ActiveValue::Set(parse_decimal(p.unrealized_pnl.as_deref())),
+                margin: ActiveValue::Set(parse_decimal(p.margin.as_deref())),
+                margin_currency: ActiveValue::Set(p.margin_currency.clone()),
+                margin_mode: ActiveValue::Set(p.margin_mode.clone()),
+                leverage: ActiveValue::Set(parse_decimal(p.leverage.as_deref())),
+                liquidation_price: ActiveValue::Set(parse_decimal(p.liquidation_price.as_deref())),
+                synced_at: ActiveValue::Set(synced_at.into()),
+            })
+        })
+        .collect()
+}
+
+/// Replace an account's stored positions with the ones observed by a sync.
+///
+/// Positions absent from `current` were closed, so the account's rows are deleted and
+/// re-inserted in one transaction. Returns the number of stored positions.
+pub async fn replace_positions(
+    db: &DatabaseConnection,
+    account_id: Uuid,
+    current: &[Position],
+) -> Result<usize, Box<dyn Error + Send + Sync>> {
+    let rows = position_rows(account_id, current, Utc::now());
+    let count = rows.len();
+
+    let txn = db.begin().await?;
+    positions::Entity::delete_many()
+        .filter(positions::Column::AccountId.eq(account_id))
+        .exec(&txn)
+        .await?;
+    if !rows.is_empty() {
+        positions::Entity::insert_many(rows).exec(&txn).await?;
+    }
+    txn.commit().await?;
+
+    Ok(count)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_position_rows_parse_decimals() {
+        let position = Position {
+            instrument: "BTC-USDT-SWAP".to_string(),
+            instrument_type: "SWAP".to_string(),
+            side: "short".to_string(),
+            size: "2.5".to_string(),
+            entry_price: Some("65000".to_string()),
+            mark_price: None,
+            unrealized_pnl: Some("-12.75".to_string()),
+            margin: Some("not-a-number".to_string()),
+            margin_currency: Some("USDT".to_string()),
+            margin_mode: Some("cross".to_string()),
+            leverage: Some("10".to_string()),
+            liquidation_price: None,
+        };
+        let invalid = Position { size: String::new(), ..position.clone() };
+
+        let rows = position_rows(Uuid::new_v4(), &[position, invalid], Utc::now());
+        assert_eq!(rows.len(), 1);
+        assert_eq!(rows[0].size, ActiveValue::Set(Decimal::from_str("2.5").unwrap()));
+        assert_eq!(rows[0].unrealized_pnl, ActiveValue::Set(Some(Decimal::from_str("-12.75").unwrap())));
+        assert_eq!(rows[0].margin, ActiveValue::Set(None));
+    }
+}
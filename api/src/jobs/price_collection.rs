@@ -1,5 +1,6 @@
-use crate::connectors::coinpaprika::CoinPaprikaConnector;
+use crate::connectors::price_provider::{price_provider_from_env, CoinQuote, PriceProvider};
 use crate::entities::{asset_prices, assets, accounts};
+use crate::helpers::price_sanity::{screen_prices, PriceSanityConfig};
 use crate::jobs::runner::{JobRunner, JobMetrics};
 use chrono::{Timelike, Utc};
 use rust_decimal::Decimal;
@@ -36,7 +37,7 @@ struct Holding {
 /// Collect prices for tracked assets and store in database
 /// 
 /// This function is the unified job that:
-/// 1. Fetches top N coins from CoinPaprika (by market cap)
+/// 1. Fetches top N coins from the `PRICE_PROVIDER` provider (by market cap)
 /// 2. Creates/updates asset records for these coins
 /// 3. Fetches prices for tracked assets (including portfolio holdings)
 /// 4. Stores all price data with rank and market info in asset_prices table
@@ -56,9 +57,10 @@ pub async fn collect_prices(
     let runner = JobRunner::new(format!("price_collection(top_n={})", top_n_limit));
 
     let result = runner.execute(|| async {
-        // Step 1: Fetch top N coins from CoinPaprika to discover/update assets
-        let connector = CoinPaprikaConnector::new();
-        let top_coins = connector.fetch_top_coins(top_n_limit).await
+        // Step 1: Fetch top N coins from the price provider to discover/update assets
+        let provider = price_provider_from_env();
+        let provider = provider.as_ref();
+        let top_coins = provider.fetch_top(Some(top_n_limit)).await
             .map_err(|e| format!("Failed to fetch top coins: {}", e))?;
         
         let mut assets_created = 0;
@@ -66,7 +68,7 @@ pub async fn collect_prices(
         
         // Step 2: Upsert asset records for top coins
         for coin in &top_coins {
-            match upsert_asset(db, coin).await {
+            match upsert_asset(db, provider, coin).await {
                 Ok((created, _asset_id)) => {
                     if created {
                         assets_created += 1;
@@ -86,7 +88,7 @@ pub async fn collect_prices(
         );
         
         // Step 3: Get list of all tracked assets (including portfolio holdings)
-        let tracked_assets = get_tracked_assets(db, top_n_limit).await
+        let tracked_assets = get_tracked_assets(db, provider, top_n_limit).await
             .map_err(|e| format!("Failed to get tracked assets: {}", e))?;
         
         let assets_tracked = tracked_assets.len();
@@ -109,15 +111,15 @@ pub async fn collect_prices(
 
         tracing::info!("Found {} unique assets to collect prices for", assets_tracked);
 
-        // Step 4: Fetch prices from CoinPaprika (reuses top_coins data + fetches additional)
-        let price_data = fetch_prices_for_assets(&connector, &tracked_assets, top_n_limit).await
+        // Step 4: Fetch prices from the provider (reuses top_coins data + fetches additional)
+        let price_data = fetch_prices_for_assets(provider, top_coins, &tracked_assets).await
             .map_err(|e| format!("Failed to fetch prices: {}", e))?;
 
         let prices_collected = price_data.len();
-        tracing::info!("Fetched {} prices from CoinPaprika", prices_collected);
+        tracing::info!("Fetched {} prices from {}", prices_collected, provider.name());
 
         // Step 5: Store prices in database using upserts
-        let prices_stored = store_prices(db, &price_data).await
+        let prices_stored = store_prices(db, provider.name(), &price_data).await
             .map_err(|e| format!("Failed to store prices: {}", e))?;
 
         Ok(JobMetrics {
@@ -147,16 +149,17 @@ pub async fn collect_prices(
     })
 }
 
-/// Upsert an asset record from CoinPaprika data
+/// Upsert an asset record from a provider quote
 /// Returns (created: bool, asset_id: Uuid)
 async fn upsert_asset(
     db: &DatabaseConnection,
-    coin: &crate::connectors::coinpaprika::CoinMarketData,
+    provider: &dyn PriceProvider,
+    coin: &CoinQuote,
 ) -> Result<(bool, Uuid), Box<dyn Error + Send + Sync>> {
     use crate::entities::assets;
     use sea_orm::{ActiveModelTrait, Condition};
     
-    // Check if asset already exists by (symbol AND name) OR coinpaprika_id
+    // Check if asset already exists by (symbol AND name) OR the provider's coin ID
     // The new uniqueness constraint requires both symbol and name to match
     let existing_asset = assets::Entity::find()
         .filter(
@@ -166,7 +169,7 @@ async fn upsert_asset(
                         .add(assets::Column::Symbol.eq(&coin.symbol.to_uppercase()))
                         .add(assets::Column::Name.eq(&coin.name))
                 )
-                .add(assets::Column::CoinpaprikaId.eq(&coin.id))
+                .add(provider.id_column().eq(&coin.provider_id))
         )
         .one(db)
         .await?;
@@ -177,8 +180,9 @@ async fn upsert_asset(
             let mut asset_update: assets::ActiveModel = existing.into();
             asset_update.name = ActiveValue::Set(coin.name.clone());
             asset_update.symbol = ActiveValue::Set(coin.symbol.to_uppercase());
-            asset_update.coinpaprika_id = ActiveValue::Set(Some(coin.id.clone()));
+            asset_update.set(provider.id_column(), Some(coin.provider_id.clone()).into());
             asset_update.is_active = ActiveValue::Set(true);
+            asset_update.delisted_at = ActiveValue::Set(None);
             asset_update.updated_at = ActiveValue::Set(Utc::now().into());
             
             let updated = asset_update.update(db).await?;
@@ -187,20 +191,24 @@ async fn upsert_asset(
         }
         None => {
             // Create new asset
-            let new_asset = assets::ActiveModel {
+            let mut new_asset = assets::ActiveModel {
                 id: ActiveValue::Set(Uuid::new_v4()),
                 symbol: ActiveValue::Set(coin.symbol.to_uppercase()),
                 name: ActiveValue::Set(coin.name.clone()),
                 asset_type: ActiveValue::Set("cryptocurrency".to_string()),
-                coinpaprika_id: ActiveValue::Set(Some(coin.id.clone())),
+                coinpaprika_id: ActiveValue::NotSet,
+                coingecko_id: ActiveValue::NotSet,
                 coinmarketcap_id: ActiveValue::NotSet,
                 logo_url: ActiveValue::NotSet,
                 description: ActiveValue::NotSet,
                 decimals: ActiveValue::NotSet,
                 is_active: ActiveValue::Set(true),
+                delisted_at: ActiveValue::Set(None),
+                category: ActiveValue::Set(None),
                 created_at: ActiveValue::Set(Utc::now().into()),
                 updated_at: ActiveValue::Set(Utc::now().into()),
             };
+            new_asset.set(provider.id_column(), Some(coin.provider_id.clone()).into());
             
             let inserted = new_asset.insert(db).await?;
             tracing::debug!("Created asset: {} ({})", inserted.symbol, inserted.id);
@@ -212,17 +220,18 @@ async fn upsert_asset(
 /// Get list of tracked assets (Top N + portfolio assets)
 async fn get_tracked_assets(
     db: &DatabaseConnection,
+    provider: &dyn PriceProvider,
     top_n_limit: usize,
 ) -> Result<Vec<assets::Model>, Box<dyn Error + Send + Sync>> {
     let mut tracked_asset_ids: HashSet<Uuid> = HashSet::new();
 
-    // Get up to N active assets with CoinPaprika IDs from database
-    // Note: The actual "top N by market cap" is determined by CoinPaprika API in fetch_prices_for_assets().
+    // Get up to N active assets with provider IDs from database
+    // Note: The actual "top N by market cap" is determined by the provider in collect_prices().
     // This query just ensures we have asset records in our DB to match against.
     // The limit here helps reduce unnecessary lookups when we have many assets in the DB.
     let top_assets = assets::Entity::find()
         .filter(assets::Column::IsActive.eq(true))
-        .filter(assets::Column::CoinpaprikaId.is_not_null())
+        .filter(provider.id_column().is_not_null())
         .order_by_asc(assets::Column::Symbol) // Order by symbol for consistent results
         .limit(top_n_limit as u64)
         .all(db)
@@ -268,92 +277,73 @@ async fn get_tracked_assets(
         tracked_asset_ids.len()
     );
 
-    // Fetch full asset models for all tracked asset IDs
+    // Fetch full asset models for all tracked asset IDs; delisted assets are no longer priced
     let tracked_assets = assets::Entity::find()
         .filter(assets::Column::Id.is_in(tracked_asset_ids))
-        .filter(assets::Column::CoinpaprikaId.is_not_null()) // Only assets with CoinPaprika ID
+        .filter(assets::Column::IsActive.eq(true))
+        .filter(provider.id_column().is_not_null()) // Only assets with a provider ID
         .all(db)
         .await?;
 
     Ok(tracked_assets)
 }
 
-/// Fetch prices from CoinPaprika for tracked assets
+/// Fetch prices from the provider for tracked assets
 /// 
 /// This function ensures we get prices for:
-/// 1. Top N coins by market cap (from CoinPaprika's tickers API, sorted by market cap)
+/// 1. Top N coins by market cap (`top_coins`, already fetched from the provider)
 /// 2. All portfolio assets (even if not in top N)
 async fn fetch_prices_for_assets(
-    connector: &CoinPaprikaConnector,
+    provider: &dyn PriceProvider,
+    top_coins: Vec<CoinQuote>,
     tracked_assets: &[assets::Model],
-    top_n_limit: usize,
 ) -> Result<Vec<PriceData>, Box<dyn Error + Send + Sync>> {
-    // Fetch top N coins by market cap from CoinPaprika
-    // CoinPaprika returns these pre-sorted by market cap rank, so this IS the true top N
-    let mut all_coins = connector.fetch_top_coins(top_n_limit).await?;
+    let mut all_coins = top_coins;
     
-    // Build a set of coinpaprika_ids we already have
-    let fetched_ids: HashSet<String> = all_coins.iter().map(|c| c.id.clone()).collect();
+    // Build a set of provider IDs we already have
+    let fetched_ids: HashSet<String> = all_coins.iter().map(|c| c.provider_id.clone()).collect();
     
     // Identify assets not in the top N that we still need prices for
-    let mut missing_coin_ids: Vec<String> = Vec::new();
-    for asset in tracked_assets {
-        if let Some(coinpaprika_id) = &asset.coinpaprika_id {
-            if !fetched_ids.contains(coinpaprika_id) {
-                missing_coin_ids.push(coinpaprika_id.clone());
-            }
-        }
-    }
+    let missing_coin_ids: Vec<String> = tracked_assets
+        .iter()
+        .filter_map(|asset| provider.coin_id(asset))
+        .filter(|id| !fetched_ids.contains(*id))
+        .map(str::to_string)
+        .collect();
     
     // Fetch prices for missing coins (portfolio assets not in top N)
     if !missing_coin_ids.is_empty() {
         tracing::info!(
-            "Fetching prices for {} additional portfolio assets not in top {}",
-            missing_coin_ids.len(),
-            top_n_limit
+            "Fetching prices for {} additional portfolio assets not in the top coins",
+            missing_coin_ids.len()
         );
         
-        let mut additional_coins = connector.fetch_coins_by_ids(&missing_coin_ids).await?;
+        let mut additional_coins = provider.fetch_by_ids(&missing_coin_ids).await?;
         
         // Add them to our all_coins vec
         all_coins.append(&mut additional_coins);
     }
     
-    // Build a map of coinpaprika_id to coin data for quick lookup
-    let coins_map: HashMap<String, &crate::connectors::coinpaprika::CoinMarketData> = 
-        all_coins.iter().map(|c| (c.id.clone(), c)).collect();
+    // Build a map of provider ID to coin data for quick lookup
+    let coins_map: HashMap<&str, &CoinQuote> = 
+        all_coins.iter().map(|c| (c.provider_id.as_str(), c)).collect();
     
-    // Map CoinPaprika coin data to our tracked assets
+    // Map provider coin data to our tracked assets
     let mut price_data = Vec::new();
     
     for asset in tracked_assets {
-        if let Some(coinpaprika_id) = &asset.coinpaprika_id {
+        if let Some(coin_id) = provider.coin_id(asset) {
             // Find matching coin data
-            if let Some(coin) = coins_map.get(coinpaprika_id) {
+            if let Some(coin) = coins_map.get(coin_id) {
                 price_data.push(PriceData {
                     asset_id: asset.id,
-                    price_usd: coin.quotes.usd.price,
-                    volume_24h_usd: coin.quotes.usd.volume_24h,
-                    market_cap_usd: Some(coin.quotes.usd.market_cap),
-                    change_percent_24h: coin.quotes.usd.percent_change_24h,
-                    // New fields from CoinPaprika
-                    rank: Some(coin.rank),
-                    circulating_supply: coin.circulating_supply,
-                    total_supply: coin.total_supply,
-                    max_supply: coin.max_supply,
-                    beta_value: coin.beta_value,
-                    percent_change_1h: coin.quotes.usd.percent_change_1h,
-                    percent_change_7d: coin.quotes.usd.percent_change_7d,
-                    percent_change_30d: coin.quotes.usd.percent_change_30d,
-                    ath_price: coin.quotes.usd.ath_price,
-                    ath_date: coin.quotes.usd.ath_date.clone(),
-                    percent_from_price_ath: coin.quotes.usd.percent_from_price_ath,
+                    quote: (*coin).clone(),
                 });
             } else {
                 tracing::warn!(
                     "No price data found for asset {} ({})",
                     asset.symbol,
-                    coinpaprika_id
+                    coin_id
                 );
             }
         }
@@ -366,37 +356,23 @@ async fn fetch_prices_for_assets(
 #[derive(Debug, Clone)]
 struct PriceData {
     asset_id: Uuid,
-    price_usd: f64,
-    volume_24h_usd: Option<f64>,
-    market_cap_usd: Option<f64>,
-    change_percent_24h: Option<f64>,
-    // New fields from CoinPaprika
-    rank: Option<u32>,
-    circulating_supply: Option<f64>,
-    total_supply: Option<f64>,
-    max_supply: Option<f64>,
-    beta_value: Option<f64>,
-    percent_change_1h: Option<f64>,
-    percent_change_7d: Option<f64>,
-    percent_change_30d: Option<f64>,
-    ath_price: Option<f64>,
-    ath_date: Option<String>,
-    percent_from_price_ath: Option<f64>,
+    quote: CoinQuote,
 }
 
 /// Store prices in the database using ON CONFLICT for idempotency
 /// Uses batched inserts for better performance
 async fn store_prices(
     db: &DatabaseConnection,
+    source: &str,
     price_data: &[PriceData],
 ) -> Result<usize, Box<dyn Error + Send + Sync>> {
     let timestamp = Utc::now();
-    let source = "coinpaprika";
     
     // Prepare all price records in a batch
     let mut price_models = Vec::new();
 
     for data in price_data {
+        let quote = &data.quote;
         // Round timestamp to the nearest minute for consistent time buckets
         let rounded_timestamp = match timestamp
             .date_naive()
@@ -414,12 +390,12 @@ async fn store_prices(
         };
 
         // Convert price data to Decimal
-        let price_usd = match Decimal::from_str(&data.price_usd.to_string()) {
+        let price_usd = match Decimal::from_str(&quote.price_usd.to_string()) {
             Ok(p) => p,
             Err(e) => {
                 tracing::warn!(
                     "Failed to convert price {} to Decimal for asset {}: {}. Skipping.",
-                    data.price_usd,
+                    quote.price_usd,
                     data.asset_id,
                     e
                 );
@@ -427,7 +403,7 @@ async fn store_prices(
             }
         };
         
-        let volume_24h_usd = data.volume_24h_usd
+        let volume_24h_usd = quote.volume_24h_usd
             .and_then(|v| {
                 Decimal::from_str(&v.to_string())
                     .map_err(|e| {
@@ -437,7 +413,7 @@ async fn store_prices(
                     .ok()
             });
         
-        let market_cap_usd = data.market_cap_usd
+        let market_cap_usd = quote.market_cap_usd
             .and_then(|v| {
                 Decimal::from_str(&v.to_string())
                     .map_err(|e| {
@@ -447,7 +423,7 @@ async fn store_prices(
                     .ok()
             });
         
-        let change_percent_24h = data.change_percent_24h
+        let change_percent_24h = quote.percent_change_24h
             .and_then(|v| {
                 Decimal::from_str(&v.to_string())
                     .map_err(|e| {
@@ -458,37 +434,37 @@ async fn store_prices(
             });
         
         // Convert new fields to Decimal
-        let rank = data.rank.map(|rank_value| rank_value as i32);
+        let rank = quote.rank.map(|rank_value| rank_value as i32);
         
-        let circulating_supply = data.circulating_supply
+        let circulating_supply = quote.circulating_supply
             .and_then(|v| Decimal::from_str(&v.to_string()).ok());
         
-        let total_supply = data.total_supply
+        let total_supply = quote.total_supply
             .and_then(|v| Decimal::from_str(&v.to_string()).ok());
         
-        let max_supply = data.max_supply
+        let max_supply = quote.max_supply
             .and_then(|v| Decimal::from_str(&v.to_string()).ok());
         
-        let beta_value = data.beta_value
+        let beta_value = quote.beta_value
             .and_then(|v| Decimal::from_str(&v.to_string()).ok());
         
-        let percent_change_1h = data.percent_change_1h
+        let percent_change_1h = quote.percent_change_1h
             .and_then(|v| Decimal::from_str(&v.to_string()).ok());
         
-        let percent_change_7d = data.percent_change_7d
+        let percent_change_7d = quote.percent_change_7d
             .and_then(|v| Decimal::from_str(&v.to_string()).ok());
         
-        let percent_change_30d = data.percent_change_30d
+        let percent_change_30d = quote.percent_change_30d
             .and_then(|v| Decimal::from_str(&v.to_string()).ok());
         
-        let ath_price = data.ath_price
+        let ath_price = quote.ath_price
             .and_then(|v| Decimal::from_str(&v.to_string()).ok());
         
-        let percent_from_price_ath = data.percent_from_price_ath
+        let percent_from_price_ath = quote.percent_from_price_ath
             .and_then(|v| Decimal::from_str(&v.to_string()).ok());
         
         // Parse ATH date string to DateTimeWithTimeZone
-        let ath_date = data.ath_date.as_ref().and_then(|date_str| {
+        let ath_date = quote.ath_date.as_ref().and_then(|date_str| {
             chrono::DateTime::parse_from_rfc3339(date_str)
                 .ok()
                 .map(|dt| dt.with_timezone(&chrono::Utc).into())
@@ -521,9 +497,12 @@ async fn store_prices(
         price_models.push(new_price);
     }
 
+    // Implausible spikes go to rejected_prices instead
+    let price_models = screen_prices(db, price_models, &PriceSanityConfig::from_env()).await;
     if price_models.is_empty() {
         return Ok(0);
     }
+    let stored = price_models.len();
 
     // Batch insert with ON CONFLICT for idempotency
     // The unique constraint on (asset_id, timestamp, source) ensures idempotency
@@ -558,8 +537,8 @@ async fn store_prices(
         .await
     {
         Ok(_) => {
-            tracing::info!("Batch upserted {} prices", price_data.len());
-            Ok(price_data.len())
+            tracing::info!("Batch upserted {} prices", stored);
+            Ok(stored)
         }
         Err(e) => {
             tracing::error!("Failed to batch upsert prices: {}", e);
@@ -0,0 +1,150 @@
+use crate::connectors::coinpaprika::{CoinPaprikaConnector, OhlcvCandle, OHLCV_MAX_DAYS};
+use crate::connectors::price_provider::PRICE_PROVIDER_COINPAPRIKA;
+use crate::entities::{asset_prices, assets};
+use crate::jobs::fetch_all_coins::deduplicate_prices;
+use chrono::{Duration, NaiveDate, Utc};
+use rust_decimal::Decimal;
+use sea_orm::{sea_query::OnConflict, ActiveValue, DatabaseConnection, EntityTrait};
+use std::error::Error;
+use std::str::FromStr;
+use tracing;
+use uuid::Uuid;
+
+/// Result of backfilling one asset's price history
+#[derive(Debug, Clone)]
+pub struct PriceHistoryBackfillResult {
+    pub asset_id: Uuid,
+    /// Daily candles returned by CoinPaprika
+    pub candles_fetched: usize,
+    /// Price rows inserted or updated
+    pub prices_stored: usize,
+}
+
+/// Split `start..=end` into consecutive windows of at most `max_days` days
+pub fn date_windows(start: NaiveDate, end: NaiveDate, max_days: i64) -> Vec<(NaiveDate, NaiveDate)> {
+    let mut windows = Vec::new();
+    let mut window_start = start;
+    while window_start <= end {
+        let window_end = (window_start + Duration::days(max_days - 1)).min(end);
+        windows.push((window_start, window_end));
+        window_start = window_end + Duration::days(1);
+    }
+    windows
+}
+
+/// `asset_prices` row of a daily candle, priced at its close
+fn candle_to_price(asset_id: Uuid, candle: &OhlcvCandle) -> Option<asset_prices::ActiveModel> {
+    let price_usd = Decimal::from_str(&candle.close.to_string()).ok()?;
+    let to_decimal = |value: Option<f64>| value.and_then(|v| Decimal::from_str(&v.to_string()).ok());
+
+    Some(asset_prices::ActiveModel {
+        id: ActiveValue::Set(Uuid::new_v4()),
+        asset_id: ActiveValue::Set(asset_id),
+        timestamp: ActiveValue::Set(candle.time_close.into()),
+        price_usd: ActiveValue::Set(price_usd),
+        volume_24h_usd: ActiveValue::Set(to_decimal(candle.volume)),
+        market_cap_usd: ActiveValue::Set(to_decimal(candle.market_cap)),
+        change_percent_24h: ActiveValue::Set(None),
+        source: ActiveValue::Set(PRICE_PROVIDER_COINPAPRIKA.to_string()),
+        created_at: ActiveValue::Set(Utc::now().into()),
+        rank: ActiveValue::Set(None),
+        circulating_supply: ActiveValue::Set(None),
+        total_supply: ActiveValue::Set(None),
+        max_supply: ActiveValue::Set(None),
+        beta_value: ActiveValue::Set(None),
+        percent_change_1h: ActiveValue::Set(None),
+        percent_change_7d: ActiveValue::Set(None),
+        percent_change_30d: ActiveValue::Set(None),
+        ath_price: ActiveValue::Set(None),
+        ath_date: ActiveValue::Set(None),
+        percent_from_price_ath: ActiveValue::Set(None),
+    })
+}
+
+/// Backfill an asset's daily prices between `start` and `end` (inclusive) from CoinPaprika's
+/// OHLCV history.
+///
+/// Each candle is stored as one `asset_prices` row at its close time with the close price,
+/// volume and market cap. Rows are upserted on `(asset_id, timestamp, source)`, so the job
+/// can be re-run over overlapping ranges. The asset must have a `coinpaprika_id`.
+///
+/// Candles skip the outlier check of live ingestion (`helpers/price_sanity.rs`): old prices
+/// can legitimately sit far from the latest one.
+pub async fn backfill_price_history(
+    db: &DatabaseConnection,
+    connector: &CoinPaprikaConnector,
+    asset: &assets::Model,
+    start: NaiveDate,
+    end: NaiveDate,
+) -> Result<PriceHistoryBackfillResult, Box<dyn Error + Send + Sync>> {
+    let coin_id = asset
+        .coinpaprika_id
+        .as_deref()
+        .ok_or_else(|| format!("Asset {} has no CoinPaprika ID", asset.symbol))?;
+
+    tracing::info!("Backfilling price history of {} ({}) from {} to {}", asset.symbol, coin_id, start, end);
+
+    let mut result = PriceHistoryBackfillResult { asset_id: asset.id, candles_fetched: 0, prices_stored: 0 };
+    for (window_start, window_end) in date_windows(start, end, OHLCV_MAX_DAYS) {
+        let candles = connector.fetch_ohlcv_historical(coin_id, window_start, window_end).await?;
+        result.candles_fetched += candles.len();
+
+        let prices = deduplicate_prices(candles.iter().filter_map(|c| candle_to_price(asset.id, c)).collect());
+        if prices.is_empty() {
+            continue;
+        }
+        let count = prices.len();
+
+        asset_prices::Entity::insert_many(prices)
+            .on_conflict(
+                OnConflict::columns([
+                    asset_prices::Column::AssetId,
+                    asset_prices::Column::Timestamp,
+                    asset_prices::Column::Source,
+                ])
+                .update_columns([
+                    asset_prices::Column::PriceUsd,
+                    asset_prices::Column::Volume24hUsd,
+                    asset_prices::Column::MarketCapUsd,
+                ])
+                .to_owned(),
+            )
+            .exec_without_returning(db)
+            .await?;
+        result.prices_stored += count;
+    }
+
+    tracing::info!(
+        "Price history backfill of {} completed: {} candles fetched, {} prices stored",
+        asset.symbol,
+        result.candles_fetched,
+        result.prices_stored
+    );
+    Ok(result)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn date(s: &str) -> NaiveDate {
+        NaiveDate::parse_from_str(s, "%Y-%m-%d").unwrap()
+    }
+
+    #[test]
+    fn test_date_windows() {
+        assert_eq!(
+            date_windows(date("2024-01-01"), date("2024-01-10"), 4),
+            vec![
+                (date("2024-01-01"), date("2024-01-04")),
+                (date("2024-01-05"), date("2024-01-08")),
+                (date("2024-01-09"), date("2024-01-10")),
+            ]
+        );
+        assert_eq!(
+            date_windows(date("2024-03-01"), date("2024-03-01"), OHLCV_MAX_DAYS),
+            vec![(date("2024-03-01"), date("2024-03-01"))]
+        );
+        assert!(date_windows(date("2024-03-02"), date("2024-03-01"), OHLCV_MAX_DAYS).is_empty());
+    }
+}
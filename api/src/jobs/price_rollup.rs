@@ -0,0 +1,309 @@
+use crate::entities::{asset_prices, asset_prices_daily, asset_prices_hourly};
+use crate::helpers::candles::{aggregate_candles, Candle, CandleInterval};
+use chrono::{DateTime, Duration, Utc};
+use rust_decimal::Decimal;
+use sea_orm::{
+    prelude::DateTimeWithTimeZone, sea_query::OnConflict, ActiveValue, ColumnTrait, DatabaseConnection, DbErr,
+    EntityTrait, QueryFilter, QueryOrder, QuerySelect,
+};
+use std::collections::{BTreeMap, BTreeSet};
+use std::error::Error;
+use tracing;
+use uuid::Uuid;
+
+/// Rollup rows upserted per statement
+const UPSERT_CHUNK: usize = 1000;
+
+/// Prices of one asset and source, oldest first
+type PriceSeries = Vec<(DateTime<Utc>, Decimal)>;
+
+/// Result of a price rollup run
+#[derive(Debug, Clone, Default)]
+pub struct PriceRollupResult {
+    /// Days (re)aggregated
+    pub days: usize,
+    pub hourly_rows: usize,
+    pub daily_rows: usize,
+}
+
+fn hourly_row(asset_id: Uuid, source: &str, candle: &Candle, now: DateTime<Utc>) -> asset_prices_hourly::ActiveModel {
+    asset_prices_hourly::ActiveModel {
+        id: ActiveValue::Set(Uuid::new_v4()),
+        asset_id: ActiveValue::Set(asset_id),
+        source: ActiveValue::Set(source.to_string()),
+        bucket_start: ActiveValue::Set(candle.open_time.into()),
+        open_usd: ActiveValue::Set(candle.open),
+        high_usd: ActiveValue::Set(candle.high),
+        low_usd: ActiveValue::Set(candle.low),
+        close_usd: ActiveValue::Set(candle.close),
+        ticks: ActiveValue::Set(candle.ticks as i32),
+        created_at: ActiveValue::Set(now.into()),
+        updated_at: ActiveValue::Set(now.into()),
+    }
+}
+
+fn daily_row(asset_id: Uuid, source: &str, candle: &Candle, now: DateTime<Utc>) -> asset_prices_daily::ActiveModel {
+    asset_prices_daily::ActiveModel {
+        id: ActiveValue::Set(Uuid::new_v4()),
+        asset_id: ActiveValue::Set(asset_id),
+        source: ActiveValue::Set(source.to_string()),
+        bucket_start: ActiveValue::Set(candle.open_time.into()),
+        open_usd: ActiveValue::Set(candle.open),
+        high_usd: ActiveValue::Set(candle.high),
+        low_usd: ActiveValue::Set(candle.low),
+        close_usd: ActiveValue::Set(candle.close),
+        ticks: ActiveValue::Set(candle.ticks as i32),
+        created_at: ActiveValue::Set(now.into()),
+        updated_at: ActiveValue::Set(now.into()),
+    }
+}
+
+/// Hourly and daily candles of one asset and source
+#[derive(Debug, Clone, PartialEq)]
+struct SeriesCandles {
+    asset_id: Uuid,
+    source: String,
+    hourly: Vec<Candle>,
+    daily: Vec<Candle>,
+}
+
+/// Group `(asset_id, source, timestamp, price)` rows sorted by time into hourly and daily
+/// candles per asset and source
+fn aggregate_rows(rows: impl IntoIterator<Item = (Uuid, String, DateTime<Utc>, Decimal)>) -> Vec<SeriesCandles> {
+    let mut series: BTreeMap<(Uuid, String), PriceSeries> = BTreeMap::new();
+    for (asset_id, source, timestamp, price) in rows {
+        series.entry((asset_id, source)).or_default().push((timestamp, price));
+    }
+    series
+        .into_iter()
+        .map(|((asset_id, source), prices)| SeriesCandles {
+            asset_id,
+            source,
+            hourly: aggregate_candles(prices.iter().copied(), CandleInterval::Hour),
+            daily: aggregate_candles(prices, CandleInterval::Day),
+        })
+        .collect()
+}
+
+/// Aggregate one UTC day of `asset_prices` into hourly and daily rollups stamped `now`;
+/// returns the number of (hourly, daily) rows written
+async fn rollup_day(
+    db: &DatabaseConnection,
+    day: DateTime<Utc>,
+    now: DateTime<Utc>,
+) -> Result<(usize, usize), Box<dyn Error + Send + Sync>> {
+    let rows: Vec<(Uuid, String, DateTimeWithTimeZone, Decimal)> = asset_prices::Entity::find()
+        .select_only()
+        .column(asset_prices::Column::AssetId)
+        .column(asset_prices::Column::Source)
+        .column(asset_prices::Column::Timestamp)
+        .column(asset_prices::Column::PriceUsd)
+        .filter(asset_prices::Column::Timestamp.gte(day))
+        .filter(asset_prices::Column::Timestamp.lt(day + Duration::days(1)))
+        .order_by_asc(asset_prices::Column::Timestamp)
+        .into_tuple()
+        .all(db)
+        .await?;
+
+    let series = aggregate_rows(
+        rows.into_iter()
+            .map(|(asset_id, source, timestamp, price)| (asset_id, source, timestamp.with_timezone(&Utc), price)),
+    );
+    let mut hourly = Vec::new();
+    let mut daily = Vec::new();
+    for candles in &series {
+        for candle in &candles.hourly {
+            hourly.push(hourly_row(candles.asset_id, &candles.source, candle, now));
+        }
+        for candle in &candles.daily {
+            daily.push(daily_row(candles.asset_id, &candles.source, candle, now));
+        }
+    }
+    let written = (hourly.len(), daily.len());
+
+    while !hourly.is_empty() {
+        let chunk: Vec<_> = hourly.drain(..hourly.len().min(UPSERT_CHUNK)).collect();
+        asset_prices_hourly::Entity::insert_many(chunk)
+            .on_conflict(
+                OnConflict::columns([
+                    asset_prices_hourly::Column::AssetId,
+                    asset_prices_hourly::Column::Source,
+                    asset_prices_hourly::Column::BucketStart,
+                ])
+                .update_columns([
+                    asset_prices_hourly::Column::OpenUsd,
+                    asset_prices_hourly::Column::HighUsd,
+                    asset_prices_hourly::Column::LowUsd,
+                    asset_prices_hourly::Column::CloseUsd,
+                    asset_prices_hourly::Column::Ticks,
+                    asset_prices_hourly::Column::UpdatedAt,
+                ])
+                .to_owned(),
+            )
+            .exec_without_returning(db)
+            .await?;
+    }
+    while !daily.is_empty() {
+        let chunk: Vec<_> = daily.drain(..daily.len().min(UPSERT_CHUNK)).collect();
+        asset_prices_daily::Entity::insert_many(chunk)
+            .on_conflict(
+                OnConflict::columns([
+                    asset_prices_daily::Column::AssetId,
+                    asset_prices_daily::Column::Source,
+                    asset_prices_daily::Column::BucketStart,
+                ])
+                .update_columns([
+                    asset_prices_daily::Column::OpenUsd,
+                    asset_prices_daily::Column::HighUsd,
+                    asset_prices_daily::Column::LowUsd,
+                    asset_prices_daily::Column::CloseUsd,
+                    asset_prices_daily::Column::Ticks,
+                    asset_prices_daily::Column::UpdatedAt,
+                ])
+                .to_owned(),
+            )
+            .exec_without_returning(db)
+            .await?;
+    }
+    Ok(written)
+}
+
+/// Days before `before` that gained `asset_prices` rows after the previous run stamped its
+/// rollups at `last_run`, e.g. from a price history backfill or an approved rejected price
+async fn backfilled_days(
+    db: &DatabaseConnection,
+    last_run: DateTime<Utc>,
+    before: DateTime<Utc>,
+) -> Result<BTreeSet<DateTime<Utc>>, DbErr> {
+    let timestamps: Vec<DateTimeWithTimeZone> = asset_prices::Entity::find()
+        .select_only()
+        .column(asset_prices::Column::Timestamp)
+        .filter(asset_prices::Column::CreatedAt.gte(last_run))
+        .filter(asset_prices::Column::Timestamp.lt(before))
+        .into_tuple()
+        .all(db)
+        .await?;
+    Ok(timestamps
+        .into_iter()
+        .map(|timestamp| CandleInterval::Day.bucket_start(timestamp.with_timezone(&Utc)))
+        .collect())
+}
+
+/// Maintain `asset_prices_hourly` and `asset_prices_daily`.
+///
+/// Resumes at the last rolled-up day, which is re-aggregated since it may have been partial,
+/// and aggregates each UTC day up to today; the first run starts at the oldest stored price.
+/// Earlier days that received prices since the previous run are re-aggregated too, so
+/// backfilled history reaches the rollups. Re-running is idempotent: buckets are upserted on
+/// `(asset_id, source, bucket_start)`.
+///
+/// Raw `asset_prices` rows are kept: valuation, snapshot rebuilds, backtests and risk
+/// metrics still read them.
+pub async fn rollup_prices(db: &DatabaseConnection) -> Result<PriceRollupResult, Box<dyn Error + Send + Sync>> {
+    // Every rollup of this run is stamped with its start, so prices stored while it runs are
+    // picked up by the next one
+    let now = Utc::now();
+    let last_rolled = asset_prices_daily::Entity::find()
+        .order_by_desc(asset_prices_daily::Column::BucketStart)
+        .one(db)
+        .await?;
+    let mut days: BTreeSet<DateTime<Utc>> = BTreeSet::new();
+    let start = match &last_rolled {
+        Some(row) => {
+            let start = row.bucket_start.with_timezone(&Utc);
+            let last_run: Option<DateTimeWithTimeZone> = asset_prices_daily::Entity::find()
+                .select_only()
+                .column_as(asset_prices_daily::Column::UpdatedAt.max(), "last_run")
+                .into_tuple()
+                .one(db)
+                .await?
+                .flatten();
+            if let Some(last_run) = last_run {
+                days.extend(backfilled_days(db, last_run.with_timezone(&Utc), start).await?);
+            }
+            start
+        }
+        None => match asset_prices::Entity::find()
+            .order_by_asc(asset_prices::Column::Timestamp)
+            .one(db)
+            .await?
+        {
+            Some(oldest) => CandleInterval::Day.bucket_start(oldest.timestamp.with_timezone(&Utc)),
+            None => return Ok(PriceRollupResult::default()),
+        },
+    };
+    let mut day = start;
+    while day <= now {
+        days.insert(day);
+        day += Duration::days(1);
+    }
+
+    let mut result = PriceRollupResult::default();
+    for day in days {
+        let (hourly, daily) = rollup_day(db, day, now).await?;
+        result.days += 1;
+        result.hourly_rows += hourly;
+        result.daily_rows += daily;
+    }
+
+    tracing::info!(
+        "Price rollup completed: {} days, {} hourly and {} daily buckets",
+        result.days,
+        result.hourly_rows,
+        result.daily_rows
+    );
+    Ok(result)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_aggregate_rows() {
+        let at = |s: &str| DateTime::parse_from_rfc3339(s).unwrap().with_timezone(&Utc);
+        let btc = Uuid::from_u128(1);
+        let eth = Uuid::from_u128(2);
+        let row = |asset_id: Uuid, source: &str, time: &str, price: i64| {
+            (asset_id, source.to_string(), at(time), Decimal::from(price))
+        };
+        let series = aggregate_rows(vec![
+            row(btc, "coinpaprika", "2025-01-10T00:10:00Z", 100),
+            row(eth, "coinpaprika", "2025-01-10T00:20:00Z", 3000),
+            row(btc, "coinpaprika", "2025-01-10T00:40:00Z", 90),
+            row(btc, "coingecko", "2025-01-10T00:45:00Z", 101),
+            row(btc, "coinpaprika", "2025-01-10T05:00:00Z", 120),
+            row(btc, "coinpaprika", "2025-01-10T23:59:00Z", 110),
+        ]);
+
+        // One series per asset and source
+        let keys: Vec<(Uuid, &str)> = series.iter().map(|s| (s.asset_id, s.source.as_str())).collect();
+        assert_eq!(keys, vec![(btc, "coingecko"), (btc, "coinpaprika"), (eth, "coinpaprika")]);
+
+        let btc_paprika = &series[1];
+        assert_eq!(btc_paprika.hourly.len(), 3);
+        assert_eq!(
+            btc_paprika.hourly[0],
+            Candle {
+                open_time: at("2025-01-10T00:00:00Z"),
+                open: Decimal::from(100),
+                high: Decimal::from(100),
+                low: Decimal::from(90),
+                close: Decimal::from(90),
+                ticks: 2,
+            }
+        );
+        assert_eq!(
+            btc_paprika.daily,
+            vec![Candle {
+                open_time: at("2025-01-10T00:00:00Z"),
+                open: Decimal::from(100),
+                high: Decimal::from(120),
+                low: Decimal::from(90),
+                close: Decimal::from(110),
+                ticks: 4,
+            }]
+        );
+        assert_eq!(series[0].daily[0].ticks, 1);
+    }
+}
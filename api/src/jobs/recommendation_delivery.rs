@@ -0,0 +1,278 @@
+//! Delivery of new recommendations to the channels the portfolio owner configured in
+//! `users.notification_channels`: an in-app notification (on unless turned off) and a webhook
+//! receiving the recommendation as JSON. Webhook hosts must resolve to public addresses (see
+//! `helpers/webhook.rs`); redirects are not followed.
+//!
+//! Every attempt is logged in `notification_deliveries` under a dedup key naming the advice
+//! rather than the row: the portfolio, the recommendation type and what it is about (the
+//! assets traded for rebalancing advice, the guardrail or subject and severity for guardrail
+//! violations and risk alerts). Advice already sent on a channel under the same key within
+//! `RECOMMENDATION_NOTIFY_DEDUP_HOURS` is not sent again, so a daily run repeating yesterday's
+//! advice stays quiet; failed attempts do not count.
+
+use std::collections::BTreeSet;
+use std::error::Error;
+use std::time::Duration as StdDuration;
+
+use chrono::{Duration, Utc};
+use sea_orm::{ActiveModelTrait, ActiveValue, ColumnTrait, DatabaseConnection, DbErr, EntityTrait, PaginatorTrait, QueryFilter};
+use serde_json::json;
+use uuid::Uuid;
+
+use crate::domain::{NotificationChannels, RECOMMENDATION_TYPE_GUARDRAIL, RECOMMENDATION_TYPE_REBALANCE};
+use crate::entities::{notification_deliveries, notifications, portfolios, recommendations, users};
+use crate::helpers::webhook::resolve_webhook_url;
+
+/// Notification type raised for new recommendations
+pub const NOTIFICATION_RECOMMENDATION: &str = "recommendation";
+
+/// `notification_deliveries.channel` of in-app notifications
+pub const CHANNEL_IN_APP: &str = "in_app";
+
+/// `notification_deliveries.channel` of webhook calls
+pub const CHANNEL_WEBHOOK: &str = "webhook";
+
+const DELIVERY_SENT: &str = "sent";
+const DELIVERY_FAILED: &str = "failed";
+
+/// Default dedup window in hours when `RECOMMENDATION_NOTIFY_DEDUP_HOURS` is not set
+const DEFAULT_DEDUP_HOURS: i64 = 168;
+
+/// Time allowed for a webhook to answer
+const WEBHOOK_TIMEOUT: StdDuration = StdDuration::from_secs(10);
+
+/// Read the dedup window from `RECOMMENDATION_NOTIFY_DEDUP_HOURS` (default: 168, a week)
+fn dedup_window_from_env() -> Duration {
+    let hours = std::env::var("RECOMMENDATION_NOTIFY_DEDUP_HOURS")
+        .ok()
+        .and_then(|v| v.trim().parse::<i64>().ok())
+        .filter(|v| *v >= 0)
+        .unwrap_or(DEFAULT_DEDUP_HOURS);
+    Duration::hours(hours)
+}
+
+/// Key identifying the advice of a recommendation, equal for recommendations that say the
+/// same thing
+pub fn dedup_key(recommendation: &recommendations::Model) -> String {
+    let metadata_str = |field: &str| {
+        recommendation.metadata.as_ref().and_then(|m| m.get(field)).and_then(|v| v.as_str()).map(str::to_string)
+    };
+    let subject = if recommendation.recommendation_type == RECOMMENDATION_TYPE_REBALANCE {
+        // The quantities move with prices every day; the direction of each asset does not
+        let orders = recommendation.proposed_orders.as_array().cloned().unwrap_or_default();
+        let sides: BTreeSet<String> = orders
+            .iter()
+            .filter_map(|o| Some(format!("{}:{}", o.get("action")?.as_str()?, o.get("asset")?.as_str()?.to_uppercase())))
+            .collect();
+        sides.into_iter().collect::<Vec<_>>().join(",")
+    } else {
+        let subject = if recommendation.recommendation_type == RECOMMENDATION_TYPE_GUARDRAIL {
+            metadata_str("guardrail")
+        } else {
+            metadata_str("subject")
+        };
+        match (subject, metadata_str("severity")) {
+            (Some(subject), Some(severity)) => format!("{}:{}", subject, severity),
+            (Some(subject), None) => subject,
+            _ => recommendation.id.to_string(),
+        }
+    };
+    format!("{}:{}:{}", recommendation.portfolio_id, recommendation.recommendation_type, subject)
+}
+
+/// Notification title of a recommendation of `portfolio_name`
+fn notification_title(recommendation: &recommendations::Model, portfolio_name: &str) -> String {
+    match recommendation.recommendation_type.as_str() {
+        RECOMMENDATION_TYPE_REBALANCE => format!("Rebalance suggested for {}", portfolio_name),
+        RECOMMENDATION_TYPE_GUARDRAIL => format!("Guardrail breached in {}", portfolio_name),
+        _ => format!("Risk alert for {}", portfolio_name),
+    }
+}
+
+/// Notification priority of a recommendation: "high" for high-severity ones, else "normal"
+pub fn notification_priority(recommendation: &recommendations::Model) -> &'static str {
+    let severity = recommendation.metadata.as_ref().and_then(|m| m.get("severity")).and_then(|s| s.as_str());
+    if severity == Some("high") {
+        "high"
+    } else {
+        "normal"
+    }
+}
+
+/// Outcome of delivering recommendations, counted per recommendation and channel
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct DeliveryOutcome {
+    pub sent: usize,
+    /// Not sent because the same advice was sent on the channel within the dedup window
+    pub deduplicated: usize,
+    pub failed: usize,
+}
+
+/// Deliver new `recommendations` of a portfolio to its owner's channels.
+pub async fn deliver_recommendations(
+    db: &DatabaseConnection,
+    portfolio_id: Uuid,
+    recommendations: &[recommendations::Model],
+) -> Result<DeliveryOutcome, DbErr> {
+    let mut outcome = DeliveryOutcome::default();
+    if recommendations.is_empty() {
+        return Ok(outcome);
+    }
+    let Some(portfolio) = portfolios::Entity::find_by_id(portfolio_id).one(db).await? else {
+        return Ok(outcome);
+    };
+    let Some(user) = users::Entity::find_by_id(portfolio.user_id).one(db).await? else {
+        return Ok(outcome);
+    };
+    let channels = NotificationChannels::from_json(user.notification_channels.as_ref());
+    let mut enabled: Vec<&str> = Vec::new();
+    if channels.in_app {
+        enabled.push(CHANNEL_IN_APP);
+    }
+    if channels.webhook_url.is_some() {
+        enabled.push(CHANNEL_WEBHOOK);
+    }
+    if enabled.is_empty() {
+        return Ok(outcome);
+    }
+
+    let since = Utc::now() - dedup_window_from_env();
+    // Checked again at send time: the host may resolve elsewhere since the URL was saved
+    let webhook = match channels.webhook_url.as_deref() {
+        Some(url) => Some(match resolve_webhook_url(url).await {
+            Ok(target) => target.client(WEBHOOK_TIMEOUT).map(|client| (client, target.url)).map_err(|e| e.to_string()),
+            Err(e) => Err(e),
+        }),
+        None => None,
+    };
+    for recommendation in recommendations {
+        let key = dedup_key(recommendation);
+        let title = notification_title(recommendation, &portfolio.name);
+        for channel in &enabled {
+            let already_sent = notification_deliveries::Entity::find()
+                .filter(notification_deliveries::Column::UserId.eq(user.id))
+                .filter(notification_deliveries::Column::Channel.eq(*channel))
+                .filter(notification_deliveries::Column::DedupKey.eq(key.as_str()))
+                .filter(notification_deliveries::Column::Status.eq(DELIVERY_SENT))
+                .filter(notification_deliveries::Column::CreatedAt.gte(since))
+                .count(db)
+                .await?
+                > 0;
+            if already_sent {
+                outcome.deduplicated += 1;
+                continue;
+            }
+
+            let result: Result<(), Box<dyn Error + Send + Sync>> = match *channel {
+                CHANNEL_IN_APP => notifications::ActiveModel {
+                    id: ActiveValue::Set(Uuid::new_v4()),
+                    user_id: ActiveValue::Set(user.id),
+                    notification_type: ActiveValue::Set(NOTIFICATION_RECOMMENDATION.to_string()),
+                    priority: ActiveValue::Set(notification_priority(recommendation).to_string()),
+                    title: ActiveValue::Set(title.clone()),
+                    message: ActiveValue::Set(recommendation.rationale.clone()),
+                    metadata: ActiveValue::Set(Some(json!({
+                        "portfolio_id": portfolio.id,
+                        "recommendation_id": recommendation.id,
+                        "recommendation_type": recommendation.recommendation_type,
+                    }))),
+                    read_at: ActiveValue::Set(None),
+                    created_at: ActiveValue::Set(Utc::now().into()),
+                }
+                .insert(db)
+                .await
+                .map(|_| ())
+                .map_err(Into::into),
+                _ => {
+                    let payload = json!({
+                        "type": NOTIFICATION_RECOMMENDATION,
+                        "title": title,
+                        "priority": notification_priority(recommendation),
+                        "portfolio_id": portfolio.id,
+                        "portfolio_name": portfolio.name,
+                        "recommendation_id": recommendation.id,
+                        "recommendation_type": recommendation.recommendation_type,
+                        "rationale": recommendation.rationale,
+                        "proposed_orders": recommendation.proposed_orders,
+                        "expected_impact": recommendation.expected_impact.map(|d| d.to_string()),
+                        "metadata": recommendation.metadata,
+                        "created_at": recommendation.created_at.to_rfc3339(),
+                    });
+                    match &webhook {
+                        Some(Ok((client, url))) => match client.post(url.clone()).json(&payload).send().await {
+                            Ok(response) => response.error_for_status().map(|_| ()).map_err(Into::into),
+                            Err(e) => Err(e.into()),
+                        },
+                        Some(Err(e)) => Err(e.clone().into()),
+                        None => Err("No webhook URL configured".into()),
+                    }
+                }
+            };
+
+            let error = result.err().map(|e| e.to_string());
+            if let Some(error) = &error {
+                tracing::warn!("Failed to deliver recommendation {} via {}: {}", recommendation.id, channel, error);
+                outcome.failed += 1;
+            } else {
+                outcome.sent += 1;
+            }
+            notification_deliveries::ActiveModel {
+                id: ActiveValue::Set(Uuid::new_v4()),
+                user_id: ActiveValue::Set(user.id),
+                recommendation_id: ActiveValue::Set(Some(recommendation.id)),
+                channel: ActiveValue::Set(channel.to_string()),
+                dedup_key: ActiveValue::Set(key.clone()),
+                status: ActiveValue::Set(if error.is_some() { DELIVERY_FAILED } else { DELIVERY_SENT }.to_string()),
+                error: ActiveValue::Set(error),
+                created_at: ActiveValue::Set(Utc::now().into()),
+            }
+            .insert(db)
+            .await?;
+        }
+    }
+    Ok(outcome)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn recommendation(recommendation_type: &str, orders: serde_json::Value, metadata: serde_json::Value) -> recommendations::Model {
+        recommendations::Model {
+            id: Uuid::new_v4(),
+            portfolio_id: Uuid::nil(),
+            status: "pending".to_string(),
+            recommendation_type: recommendation_type.to_string(),
+            rationale: "Sell ETH".to_string(),
+            proposed_orders: orders,
+            expected_impact: None,
+            metadata: Some(metadata),
+            created_at: Utc::now().into(),
+            updated_at: Utc::now().into(),
+            executed_at: None,
+            construction_run_id: None,
+        }
+    }
+
+    #[test]
+    fn test_dedup_key() {
+        let order = |action: &str, asset: &str, quantity: &str| {
+            json!({"action": action, "asset": asset, "quantity": quantity, "estimated_price": "1", "estimated_value_usd": quantity})
+        };
+        // Rebalancing advice with other quantities on the same assets is the same advice
+        let today = recommendation("rebalance", json!([order("sell", "ETH", "0.6"), order("buy", "USDC", "2000")]), json!({}));
+        let tomorrow = recommendation("rebalance", json!([order("buy", "USDC", "2100"), order("sell", "ETH", "0.61")]), json!({}));
+        assert_eq!(dedup_key(&today), dedup_key(&tomorrow));
+        assert_eq!(dedup_key(&today), format!("{}:rebalance:buy:USDC,sell:ETH", Uuid::nil()));
+        let reversed = recommendation("rebalance", json!([order("buy", "ETH", "0.6")]), json!({}));
+        assert_ne!(dedup_key(&today), dedup_key(&reversed));
+
+        // Alerts are keyed by subject and severity, so an escalation is sent again
+        let medium = recommendation("depeg_alert", json!([]), json!({"subject": "USDC", "severity": "medium"}));
+        let high = recommendation("depeg_alert", json!([]), json!({"subject": "USDC", "severity": "high"}));
+        assert_ne!(dedup_key(&medium), dedup_key(&high));
+        assert_eq!((notification_priority(&medium), notification_priority(&high)), ("normal", "high"));
+        let guardrail = recommendation("guardrail_violation", json!([]), json!({"guardrail": "max_alt_cap", "severity": "low"}));
+        assert!(dedup_key(&guardrail).ends_with(":guardrail_violation:max_alt_cap:low"));
+    }
+}
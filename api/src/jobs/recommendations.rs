@@ -0,0 +1,547 @@
+//! Recommendation engine: evaluates each portfolio's latest allocation against its target
+//! allocation and guardrails and writes concrete rebalancing advice to `recommendations`.
+//!
+//! A portfolio gets no advice when it has no target allocation, when its allocation is older
+//! than `RECOMMENDATION_MAX_ALLOCATION_AGE_HOURS` (the quantities may no longer be held), or
+//! when its rebalance plan has no trade on a freshly priced asset. New advice supersedes the
+//! engine's pending advice of the same type, so only the latest is awaiting a decision.
+//!
+//! Guardrail violations are recorded when an allocation is constructed: one pending
+//! recommendation per breached guardrail, kept up to date by later constructions and marked
+//! resolved once the allocation is back within the guardrail.
+//!
+//! Risk alerts are evaluated with the rebalancing advice: a drawdown beyond
+//! `RISK_ALERT_DRAWDOWN_PCT`, an asset above `RISK_ALERT_CONCENTRATION_PCT` of the portfolio,
+//! a stablecoin more than `RISK_ALERT_DEPEG_PCT` off its peg, and an account not synced for
+//! `RISK_ALERT_STALE_ACCOUNT_DAYS`. Like guardrail violations, each keeps one pending
+//! recommendation per subject, updated while the condition holds and resolved once it is gone.
+//!
+//! New recommendations are delivered to the owner's notification channels (see
+//! [`crate::jobs::recommendation_delivery`]).
+
+use std::collections::BTreeMap;
+use std::error::Error;
+
+use chrono::{Duration, Utc};
+use rust_decimal::prelude::{FromPrimitive, ToPrimitive};
+use rust_decimal::Decimal;
+use sea_orm::sea_query::Expr;
+use sea_orm::{
+    ActiveModelTrait, ActiveValue, ColumnTrait, DatabaseConnection, DbErr, EntityTrait, QueryFilter, QueryOrder,
+};
+use serde_json::json;
+use uuid::Uuid;
+
+use crate::connectors::registry::ACCOUNT_TYPE_EXCHANGE;
+use crate::domain::{
+    concentration_alerts, depeg_alerts, drawdown_alert, rebalance_plan, rebalance_recommendation, stale_account_alert,
+    target_symbol, target_weights, AccountBalance, AccountHolding, AllocationItem, GuardrailViolation,
+    PortfolioGuardrails, RiskAlert, RiskAlertThresholds, HOLDING_SOURCE_SPOT, RECOMMENDATION_SOURCE_ENGINE,
+    RECOMMENDATION_STATUS_PENDING, RECOMMENDATION_STATUS_RESOLVED, RECOMMENDATION_STATUS_SUPERSEDED,
+    RECOMMENDATION_TYPE_CONCENTRATION, RECOMMENDATION_TYPE_DEPEG, RECOMMENDATION_TYPE_DRAWDOWN,
+    RECOMMENDATION_TYPE_GUARDRAIL, RECOMMENDATION_TYPE_REBALANCE, RECOMMENDATION_TYPE_STALE_ACCOUNT,
+};
+use crate::entities::{
+    accounts, portfolio_accounts, portfolio_allocations, portfolio_performance, portfolios, recommendations,
+};
+use crate::helpers::asset_identity::{AssetIdentityNormalizer, NormalizationResult};
+use crate::helpers::price_resolution::price_at;
+use crate::jobs::construction_runs::current_construction_run_id;
+use crate::jobs::recommendation_delivery::deliver_recommendations;
+
+/// Default minimum trade size in USD when `REBALANCE_MIN_TRADE_USD` is not set
+const DEFAULT_MIN_TRADE_USD: f64 = 10.0;
+
+/// Default age in hours beyond which an allocation is too old to recommend trades from
+const DEFAULT_MAX_ALLOCATION_AGE_HOURS: i64 = 24;
+
+/// Read the default minimum trade size from `REBALANCE_MIN_TRADE_USD` (default: 10)
+pub fn min_trade_usd_from_env() -> f64 {
+    std::env::var("REBALANCE_MIN_TRADE_USD")
+        .ok()
+        .and_then(|v| v.trim().parse::<f64>().ok())
+        .filter(|v| v.is_finite() && *v >= 0.0)
+        .unwrap_or(DEFAULT_MIN_TRADE_USD)
+}
+
+/// Read the allocation age limit from `RECOMMENDATION_MAX_ALLOCATION_AGE_HOURS` (default: 24)
+fn max_allocation_age_from_env() -> Duration {
+    let hours = std::env::var("RECOMMENDATION_MAX_ALLOCATION_AGE_HOURS")
+        .ok()
+        .and_then(|v| v.trim().parse::<i64>().ok())
+        .filter(|v| *v > 0)
+        .unwrap_or(DEFAULT_MAX_ALLOCATION_AGE_HOURS);
+    Duration::hours(hours)
+}
+
+/// Read the risk alert thresholds from `RISK_ALERT_DRAWDOWN_PCT` (default: 20),
+/// `RISK_ALERT_CONCENTRATION_PCT` (default: 50), `RISK_ALERT_DEPEG_PCT` (default: 2) and
+/// `RISK_ALERT_STALE_ACCOUNT_DAYS` (default: 3)
+pub fn risk_alert_thresholds_from_env() -> RiskAlertThresholds {
+    let percent = |name: &str, default: f64| {
+        std::env::var(name)
+            .ok()
+            .and_then(|v| v.trim().parse::<f64>().ok())
+            .filter(|v| v.is_finite() && *v > 0.0)
+            .unwrap_or(default)
+    };
+    let defaults = RiskAlertThresholds::default();
+    RiskAlertThresholds {
+        drawdown_pct: percent("RISK_ALERT_DRAWDOWN_PCT", defaults.drawdown_pct),
+        concentration_pct: percent("RISK_ALERT_CONCENTRATION_PCT", defaults.concentration_pct),
+        depeg_pct: percent("RISK_ALERT_DEPEG_PCT", defaults.depeg_pct),
+        stale_account_days: std::env::var("RISK_ALERT_STALE_ACCOUNT_DAYS")
+            .ok()
+            .and_then(|v| v.trim().parse::<i64>().ok())
+            .filter(|v| *v > 0)
+            .unwrap_or(defaults.stale_account_days),
+    }
+}
+
+/// Spot balances of the portfolio's accounts by target symbol
+pub async fn load_spot_balances(
+    db: &DatabaseConnection,
+    portfolio_id: Uuid,
+) -> Result<BTreeMap<String, Vec<AccountBalance>>, DbErr> {
+    let account_ids: Vec<Uuid> = portfolio_accounts::Entity::find()
+        .filter(portfolio_accounts::Column::PortfolioId.eq(portfolio_id))
+        .all(db)
+        .await?
+        .into_iter()
+        .map(|pa| pa.account_id)
+        .collect();
+    let accounts = accounts::Entity::find()
+        .filter(accounts::Column::Id.is_in(account_ids))
+        .all(db)
+        .await?;
+
+    let mut balances: BTreeMap<String, Vec<AccountBalance>> = BTreeMap::new();
+    for account in accounts {
+        let Some(holdings_json) = account.holdings else { continue };
+        let holdings: Vec<AccountHolding> = match serde_json::from_value(holdings_json) {
+            Ok(h) => h,
+            Err(e) => {
+                tracing::warn!("Failed to deserialize holdings for account {}: {}", account.id, e);
+                continue;
+            }
+        };
+        for holding in holdings.iter().filter(|h| h.source() == HOLDING_SOURCE_SPOT && !h.asset.is_empty()) {
+            let quantity = holding.quantity_decimal();
+            if quantity <= Decimal::ZERO {
+                continue;
+            }
+            let accounts = balances.entry(target_symbol(&holding.asset)).or_default();
+            match accounts.iter_mut().find(|b| b.account_id == account.id) {
+                Some(balance) => balance.quantity += quantity,
+                None => accounts.push(AccountBalance {
+                    account_id: account.id,
+                    account_name: account.name.clone(),
+                    exchange: account.account_type == ACCOUNT_TYPE_EXCHANGE,
+                    quantity,
+                }),
+            }
+        }
+    }
+    Ok(balances)
+}
+
+/// Latest stored prices of the targeted assets that `holdings` do not price
+pub async fn unheld_target_prices(
+    db: &DatabaseConnection,
+    holdings: &[AllocationItem],
+    targets: &BTreeMap<String, f64>,
+) -> Result<BTreeMap<String, f64>, DbErr> {
+    let priced: Vec<String> = holdings
+        .iter()
+        .filter(|h| h.price_usd.is_some_and(|p| p > 0.0))
+        .map(|h| target_symbol(&h.asset))
+        .collect();
+    let normalizer = AssetIdentityNormalizer::new(db.clone());
+    let mut prices = BTreeMap::new();
+    for symbol in targets.keys().filter(|s| !priced.contains(s)) {
+        if let NormalizationResult::Mapped(identity) = normalizer.normalize_from_symbol(symbol).await {
+            if let Some(price) = price_at(db, identity.asset_id, Utc::now()).await?.and_then(|p| p.to_f64()) {
+                prices.insert(symbol.clone(), price);
+            }
+        }
+    }
+    Ok(prices)
+}
+
+/// Whether a recommendation was written by the engine rather than created through the API
+pub fn is_engine_recommendation(recommendation: &recommendations::Model) -> bool {
+    recommendation.metadata.as_ref().and_then(|m| m.get("source")).and_then(|s| s.as_str())
+        == Some(RECOMMENDATION_SOURCE_ENGINE)
+}
+
+/// Outcome of evaluating one portfolio
+#[derive(Debug, Clone)]
+pub struct PortfolioRecommendations {
+    pub portfolio_id: Uuid,
+    /// Recommendations written: the rebalancing advice and new risk alerts
+    pub created: Vec<recommendations::Model>,
+    /// Pending recommendations superseded by the new ones
+    pub superseded: u64,
+    /// Pending risk alerts updated because their condition still holds
+    pub alerts_updated: usize,
+    /// Pending risk alerts resolved because their condition is gone
+    pub alerts_resolved: usize,
+    /// Why no rebalancing advice was written, if none was
+    pub skipped_reason: Option<String>,
+}
+
+/// Evaluate a portfolio and write its recommendations.
+pub async fn generate_portfolio_recommendations(
+    db: &DatabaseConnection,
+    portfolio: &portfolios::Model,
+) -> Result<PortfolioRecommendations, Box<dyn Error + Send + Sync>> {
+    let mut outcome = PortfolioRecommendations {
+        portfolio_id: portfolio.id,
+        created: Vec::new(),
+        superseded: 0,
+        alerts_updated: 0,
+        alerts_resolved: 0,
+        skipped_reason: None,
+    };
+
+    let construction_run_id = current_construction_run_id(db, portfolio.id).await?;
+    let alerts = sync_risk_alerts(db, portfolio, &risk_alert_thresholds_from_env(), construction_run_id).await?;
+    outcome.alerts_updated = alerts.updated;
+    outcome.alerts_resolved = alerts.resolved;
+    outcome.created.extend(alerts.created);
+
+    outcome.skipped_reason = write_rebalance_recommendation(db, portfolio, construction_run_id, &mut outcome).await?;
+
+    // The advice stands even if it cannot be delivered
+    if let Err(e) = deliver_recommendations(db, portfolio.id, &outcome.created).await {
+        tracing::warn!("Failed to deliver recommendations of portfolio {}: {}", portfolio.id, e);
+    }
+    Ok(outcome)
+}
+
+/// Write the rebalancing advice of a portfolio into `outcome`; returns why none was written,
+/// if none was.
+async fn write_rebalance_recommendation(
+    db: &DatabaseConnection,
+    portfolio: &portfolios::Model,
+    construction_run_id: Option<Uuid>,
+    outcome: &mut PortfolioRecommendations,
+) -> Result<Option<String>, Box<dyn Error + Send + Sync>> {
+    let targets = target_weights(portfolio.target_allocation.as_ref());
+    if targets.is_empty() {
+        return Ok(Some("Portfolio has no target_allocation".to_string()));
+    }
+    let Some(allocation) = portfolio_allocations::Entity::find()
+        .filter(portfolio_allocations::Column::PortfolioId.eq(portfolio.id))
+        .one(db)
+        .await?
+    else {
+        return Ok(Some("Portfolio has no allocation yet".to_string()));
+    };
+    let age = Utc::now() - allocation.as_of.with_timezone(&Utc);
+    if age > max_allocation_age_from_env() {
+        return Ok(Some(format!(
+            "Allocation is {} hours old; construct it again for fresh holdings",
+            age.num_hours()
+        )));
+    }
+    let holdings: Vec<AllocationItem> = serde_json::from_value(allocation.holdings)
+        .map_err(|e| format!("Failed to deserialize allocation of portfolio {}: {}", portfolio.id, e))?;
+
+    let guardrails = PortfolioGuardrails::from_json(portfolio.guardrails.as_ref());
+    let prices = unheld_target_prices(db, &holdings, &targets).await?;
+    let balances = load_spot_balances(db, portfolio.id).await?;
+    let min_trade_usd = min_trade_usd_from_env();
+    let plan = rebalance_plan(&holdings, &targets, &guardrails, &prices, &balances, min_trade_usd);
+    let Some(recommendation) = rebalance_recommendation(&holdings, &plan) else {
+        let reason = plan.notes.last().cloned().unwrap_or_else(|| "No trade needed to restore the target allocation".to_string());
+        return Ok(Some(reason));
+    };
+
+    // Only the latest advice awaits a decision
+    let now = Utc::now();
+    let superseded: Vec<Uuid> = recommendations::Entity::find()
+        .filter(recommendations::Column::PortfolioId.eq(portfolio.id))
+        .filter(recommendations::Column::Status.eq(RECOMMENDATION_STATUS_PENDING))
+        .filter(recommendations::Column::RecommendationType.eq(RECOMMENDATION_TYPE_REBALANCE))
+        .all(db)
+        .await?
+        .into_iter()
+        .filter(is_engine_recommendation)
+        .map(|r| r.id)
+        .collect();
+    if !superseded.is_empty() {
+        outcome.superseded = recommendations::Entity::update_many()
+            .col_expr(recommendations::Column::Status, Expr::value(RECOMMENDATION_STATUS_SUPERSEDED))
+            .col_expr(recommendations::Column::UpdatedAt, Expr::value(now))
+            .filter(recommendations::Column::Id.is_in(superseded))
+            .exec(db)
+            .await?
+            .rows_affected;
+    }
+
+    let traded_usd: f64 = recommendation.orders.iter().filter_map(|o| o.estimated_value_usd.parse::<f64>().ok()).sum();
+    let created = recommendations::ActiveModel {
+        id: ActiveValue::Set(Uuid::new_v4()),
+        portfolio_id: ActiveValue::Set(portfolio.id),
+        status: ActiveValue::Set(RECOMMENDATION_STATUS_PENDING.to_string()),
+        recommendation_type: ActiveValue::Set(RECOMMENDATION_TYPE_REBALANCE.to_string()),
+        rationale: ActiveValue::Set(recommendation.rationale.clone()),
+        proposed_orders: ActiveValue::Set(json!(recommendation.orders)),
+        // Drift removed, in percentage points
+        expected_impact: ActiveValue::Set(
+            Decimal::from_f64(recommendation.max_drift_before - recommendation.max_drift_after).map(|d| d.round_dp(2)),
+        ),
+        metadata: ActiveValue::Set(Some(json!({
+            "source": RECOMMENDATION_SOURCE_ENGINE,
+            "summary": recommendation.summary,
+            "targets": plan.targets,
+            "current_allocation": recommendation.current_allocation,
+            "projected_allocation": recommendation.projected_allocation,
+            "drift_before": recommendation.max_drift_before,
+            "drift_after": recommendation.max_drift_after,
+            "traded_usd": (traded_usd * 100.0).round() / 100.0,
+            "stale_assets": recommendation.stale_assets,
+            "skipped_trades": plan.skipped_trades,
+            "notes": plan.notes,
+            "allocation_as_of": allocation.as_of.to_rfc3339(),
+        }))),
+        created_at: ActiveValue::Set(now.into()),
+        updated_at: ActiveValue::Set(now.into()),
+        executed_at: ActiveValue::Set(None),
+        construction_run_id: ActiveValue::Set(construction_run_id),
+    }
+    .insert(db)
+    .await?;
+    tracing::info!("Recommendation for portfolio {} ({}): {}", portfolio.name, portfolio.id, recommendation.summary);
+    outcome.created.push(created);
+    Ok(None)
+}
+
+/// Changes made to a portfolio's pending guardrail or risk alert recommendations
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct RecommendationChanges {
+    pub created: Vec<recommendations::Model>,
+    pub updated: usize,
+    pub resolved: usize,
+}
+
+/// A condition that keeps one pending recommendation per type and `key`
+struct PendingCondition {
+    recommendation_type: &'static str,
+    key: String,
+    rationale: String,
+    proposed_orders: serde_json::Value,
+    expected_impact: Option<Decimal>,
+    metadata: serde_json::Value,
+}
+
+/// Bring the pending recommendations of `recommendation_types` in line with the `conditions`
+/// that hold now: a condition with a pending recommendation of the same type and key (read
+/// from `metadata[key_field]`) updates it, any other creates one, and pending ones whose
+/// condition no longer holds are marked resolved.
+async fn sync_pending_recommendations(
+    db: &DatabaseConnection,
+    portfolio_id: Uuid,
+    recommendation_types: &[&str],
+    key_field: &str,
+    conditions: Vec<PendingCondition>,
+    construction_run_id: Option<Uuid>,
+) -> Result<RecommendationChanges, DbErr> {
+    let mut pending: BTreeMap<(String, String), recommendations::Model> = BTreeMap::new();
+    if !recommendation_types.is_empty() {
+        for recommendation in recommendations::Entity::find()
+            .filter(recommendations::Column::PortfolioId.eq(portfolio_id))
+            .filter(recommendations::Column::Status.eq(RECOMMENDATION_STATUS_PENDING))
+            .filter(recommendations::Column::RecommendationType.is_in(recommendation_types.iter().copied()))
+            .all(db)
+            .await?
+        {
+            let key = recommendation.metadata.as_ref().and_then(|m| m.get(key_field)).and_then(|k| k.as_str());
+            if let Some(key) = key.map(str::to_string) {
+                pending.insert((recommendation.recommendation_type.clone(), key), recommendation);
+            }
+        }
+    }
+
+    let now = Utc::now();
+    let mut outcome = RecommendationChanges::default();
+    for condition in conditions {
+        match pending.remove(&(condition.recommendation_type.to_string(), condition.key)) {
+            Some(existing) => {
+                let mut active: recommendations::ActiveModel = existing.into();
+                active.rationale = ActiveValue::Set(condition.rationale);
+                active.proposed_orders = ActiveValue::Set(condition.proposed_orders);
+                active.expected_impact = ActiveValue::Set(condition.expected_impact);
+                active.metadata = ActiveValue::Set(Some(condition.metadata));
+                active.updated_at = ActiveValue::Set(now.into());
+                active.construction_run_id = ActiveValue::Set(construction_run_id);
+                active.update(db).await?;
+                outcome.updated += 1;
+            }
+            None => {
+                let created = recommendations::ActiveModel {
+                    id: ActiveValue::Set(Uuid::new_v4()),
+                    portfolio_id: ActiveValue::Set(portfolio_id),
+                    status: ActiveValue::Set(RECOMMENDATION_STATUS_PENDING.to_string()),
+                    recommendation_type: ActiveValue::Set(condition.recommendation_type.to_string()),
+                    rationale: ActiveValue::Set(condition.rationale),
+                    proposed_orders: ActiveValue::Set(condition.proposed_orders),
+                    expected_impact: ActiveValue::Set(condition.expected_impact),
+                    metadata: ActiveValue::Set(Some(condition.metadata)),
+                    created_at: ActiveValue::Set(now.into()),
+                    updated_at: ActiveValue::Set(now.into()),
+                    executed_at: ActiveValue::Set(None),
+                    construction_run_id: ActiveValue::Set(construction_run_id),
+                }
+                .insert(db)
+                .await?;
+                outcome.created.push(created);
+            }
+        }
+    }
+
+    let resolved: Vec<Uuid> = pending.into_values().map(|r| r.id).collect();
+    if !resolved.is_empty() {
+        outcome.resolved = recommendations::Entity::update_many()
+            .col_expr(recommendations::Column::Status, Expr::value(RECOMMENDATION_STATUS_RESOLVED))
+            .col_expr(recommendations::Column::UpdatedAt, Expr::value(now))
+            .filter(recommendations::Column::Id.is_in(resolved))
+            .exec(db)
+            .await?
+            .rows_affected as usize;
+    }
+    Ok(outcome)
+}
+
+/// Record the guardrail `violations` of a portfolio's newly constructed allocation as
+/// recommendations: a violation with a pending recommendation for the same guardrail updates
+/// it, any other creates one, and pending ones whose guardrail is no longer breached are
+/// marked resolved.
+pub async fn sync_guardrail_recommendations(
+    db: &DatabaseConnection,
+    portfolio_id: Uuid,
+    violations: &[GuardrailViolation],
+    construction_run_id: Option<Uuid>,
+) -> Result<RecommendationChanges, DbErr> {
+    let conditions = violations
+        .iter()
+        .map(|violation| PendingCondition {
+            recommendation_type: RECOMMENDATION_TYPE_GUARDRAIL,
+            key: violation.guardrail.clone(),
+            rationale: format!("{}. Suggested remediation: {}.", violation.description, violation.remediation),
+            proposed_orders: json!(violation.orders),
+            // Percentage points outside the guardrail
+            expected_impact: Decimal::from_f64((violation.actual - violation.limit).abs()).map(|d| d.round_dp(2)),
+            metadata: json!({
+                "source": RECOMMENDATION_SOURCE_ENGINE,
+                "guardrail": violation.guardrail,
+                "severity": violation.severity,
+                "limit": violation.limit,
+                "actual": violation.actual,
+                "assets": violation.assets,
+                "remediation": violation.remediation,
+            }),
+        })
+        .collect();
+    sync_pending_recommendations(db, portfolio_id, &[RECOMMENDATION_TYPE_GUARDRAIL], "guardrail", conditions, construction_run_id)
+        .await
+}
+
+/// Evaluate the risk alerts of a portfolio and record them as recommendations, one pending
+/// per alert type and subject. Alert types that cannot be evaluated (no performance history,
+/// no recent allocation) keep their pending recommendations as they are.
+pub async fn sync_risk_alerts(
+    db: &DatabaseConnection,
+    portfolio: &portfolios::Model,
+    thresholds: &RiskAlertThresholds,
+    construction_run_id: Option<Uuid>,
+) -> Result<RecommendationChanges, Box<dyn Error + Send + Sync>> {
+    let mut evaluated: Vec<&str> = Vec::new();
+    let mut alerts: Vec<RiskAlert> = Vec::new();
+
+    let latest_performance = portfolio_performance::Entity::find()
+        .filter(portfolio_performance::Column::PortfolioId.eq(portfolio.id))
+        .order_by_desc(portfolio_performance::Column::Date)
+        .one(db)
+        .await?;
+    if let Some(day) = latest_performance {
+        evaluated.push(RECOMMENDATION_TYPE_DRAWDOWN);
+        alerts.extend(drawdown_alert(day.drawdown.to_f64().unwrap_or(0.0), thresholds.drawdown_pct));
+    }
+
+    let allocation = portfolio_allocations::Entity::find()
+        .filter(portfolio_allocations::Column::PortfolioId.eq(portfolio.id))
+        .one(db)
+        .await?
+        .filter(|a| Utc::now() - a.as_of.with_timezone(&Utc) <= max_allocation_age_from_env());
+    if let Some(allocation) = allocation {
+        let holdings: Vec<AllocationItem> = serde_json::from_value(allocation.holdings)
+            .map_err(|e| format!("Failed to deserialize allocation of portfolio {}: {}", portfolio.id, e))?;
+        evaluated.extend([RECOMMENDATION_TYPE_CONCENTRATION, RECOMMENDATION_TYPE_DEPEG]);
+        alerts.extend(concentration_alerts(&holdings, thresholds.concentration_pct));
+        alerts.extend(depeg_alerts(&holdings, thresholds.depeg_pct));
+    }
+
+    let account_ids: Vec<Uuid> = portfolio_accounts::Entity::find()
+        .filter(portfolio_accounts::Column::PortfolioId.eq(portfolio.id))
+        .all(db)
+        .await?
+        .into_iter()
+        .map(|pa| pa.account_id)
+        .collect();
+    let now = Utc::now();
+    evaluated.push(RECOMMENDATION_TYPE_STALE_ACCOUNT);
+    for account in accounts::Entity::find()
+        .filter(accounts::Column::Id.is_in(account_ids))
+        .filter(accounts::Column::IsActive.eq(true))
+        .all(db)
+        .await?
+    {
+        alerts.extend(stale_account_alert(
+            account.id,
+            &account.name,
+            account.last_synced_at.map(|t| t.with_timezone(&Utc)),
+            account.created_at.with_timezone(&Utc),
+            now,
+            thresholds.stale_account_days,
+        ));
+    }
+
+    let conditions = alerts
+        .into_iter()
+        .map(|alert| PendingCondition {
+            recommendation_type: alert.recommendation_type,
+            rationale: format!("{}. Suggested action: {}.", alert.description, alert.remediation),
+            proposed_orders: json!(alert.orders),
+            // Distance beyond the threshold, in the alert's unit
+            expected_impact: Decimal::from_f64((alert.actual - alert.threshold).abs()).map(|d| d.round_dp(2)),
+            metadata: json!({
+                "source": RECOMMENDATION_SOURCE_ENGINE,
+                "subject": alert.subject,
+                "severity": alert.severity,
+                "threshold": alert.threshold,
+                "actual": alert.actual,
+                "assets": alert.assets,
+                "account_id": alert.account_id,
+                "remediation": alert.remediation,
+            }),
+            key: alert.subject,
+        })
+        .collect();
+    Ok(sync_pending_recommendations(db, portfolio.id, &evaluated, "subject", conditions, construction_run_id).await?)
+}
+
+/// Evaluate every portfolio; returns the number of recommendations written. A portfolio that
+/// fails is logged and skipped.
+pub async fn generate_all_recommendations(db: &DatabaseConnection) -> Result<usize, Box<dyn Error + Send + Sync>> {
+    let mut created = 0;
+    for portfolio in portfolios::Entity::find().all(db).await? {
+        match generate_portfolio_recommendations(db, &portfolio).await {
+            Ok(outcome) => created += outcome.created.len(),
+            Err(e) => tracing::warn!("Failed to generate recommendations for portfolio {}: {}", portfolio.id, e),
+        }
+    }
+    tracing::info!("Recommendation engine wrote {} recommendations", created);
+    Ok(created)
+}
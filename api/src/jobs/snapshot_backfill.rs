@@ -0,0 +1,439 @@
+use crate::domain::{AccountHolding, DebtSummary, PortfolioSettings, SnapshotMetadata};
+use crate::entities::{accounts, holding_transactions, portfolios, snapshots};
+use crate::jobs::account_snapshot::valued_snapshot_holdings;
+use crate::jobs::anomaly_detection::sum_by_asset;
+use crate::jobs::portfolio_pnl::{portfolio_account_ids, LedgerPricer};
+use chrono::{DateTime, Duration, NaiveDate, NaiveTime, Utc};
+use rust_decimal::prelude::FromPrimitive;
+use rust_decimal::Decimal;
+use sea_orm::{
+    ActiveModelTrait, ActiveValue, ColumnTrait, Condition, DatabaseConnection, EntityTrait, PaginatorTrait, QueryFilter,
+    QueryOrder, TransactionTrait,
+};
+use serde_json::json;
+use std::collections::{BTreeMap, BTreeSet, HashMap};
+use std::error::Error;
+use tracing;
+use uuid::Uuid;
+
+/// `snapshots.snapshot_type` of snapshots reconstructed by the backfill
+pub const SNAPSHOT_TYPE_BACKFILL: &str = "backfill";
+
+/// Result of backfilling one portfolio
+#[derive(Debug, Clone)]
+pub struct SnapshotBackfillResult {
+    pub portfolio_id: Uuid,
+    pub portfolio_name: String,
+    /// Dates snapshotted (or that would be in a dry run), oldest first
+    pub created_dates: Vec<NaiveDate>,
+    /// Missing dates before the portfolio's first holding ledger row, which cannot be
+    /// reconstructed
+    pub skipped_dates: Vec<NaiveDate>,
+    /// Existing snapshots replaced by a rebuild (always 0 for a backfill)
+    pub replaced: u64,
+    pub error: Option<String>,
+}
+
+/// Dates from `start` to `end` (inclusive) without a snapshot
+pub fn missing_dates(existing: &BTreeSet<NaiveDate>, start: NaiveDate, end: NaiveDate) -> Vec<NaiveDate> {
+    start.iter_days().take_while(|date| *date <= end).filter(|date| !existing.contains(date)).collect()
+}
+
+/// Quantity per asset of one account just before `cutoff`, reconstructed from its holding
+/// ledger `rows` (oldest first) and its `current` quantities.
+///
+/// An asset takes the `quantity_after` of its latest effective row before the cutoff; an
+/// asset whose rows all come later takes the `quantity_before` of the earliest one; assets
+/// without rows have not changed and keep their current quantity. Rows superseded by a
+/// correction are ignored. Zero quantities are dropped.
+pub fn quantities_at(
+    rows: &[holding_transactions::Model],
+    current: &HashMap<String, Decimal>,
+    cutoff: DateTime<Utc>,
+) -> BTreeMap<String, Decimal> {
+    let mut quantities: BTreeMap<String, Decimal> = current.clone().into_iter().collect();
+    let mut seen: BTreeSet<&str> = BTreeSet::new();
+    for row in rows.iter().filter(|r| r.corrected_by.is_none()) {
+        // Rows are oldest first, so every row before the cutoff comes before any after it
+        if row.recorded_at < cutoff {
+            quantities.insert(row.asset.clone(), row.quantity_after);
+            seen.insert(row.asset.as_str());
+        } else if seen.insert(row.asset.as_str()) {
+            quantities.insert(row.asset.clone(), row.quantity_before);
+        }
+    }
+    quantities.retain(|_, quantity| *quantity > Decimal::ZERO);
+    quantities
+}
+
+/// Midnight (UTC) at the end of `date`, as of which a reconstructed snapshot is valued
+fn day_cutoff(date: NaiveDate) -> DateTime<Utc> {
+    (date + Duration::days(1)).and_time(NaiveTime::MIN).and_utc()
+}
+
+/// Split `dates` into those that can be reconstructed from a holding ledger starting at
+/// `history_start` and those that end before it (or all of them without a ledger)
+fn reconstructable_dates(
+    dates: Vec<NaiveDate>,
+    history_start: Option<DateTime<Utc>>,
+) -> (Vec<NaiveDate>, Vec<NaiveDate>) {
+    match history_start {
+        Some(history_start) => dates.into_iter().partition(|date| day_cutoff(*date) > history_start),
+        None => (Vec::new(), dates),
+    }
+}
+
+/// Reconstruct the missing daily snapshots of a portfolio from `start` to `end`.
+///
+/// Each missing date is valued at the end of the day (UTC): quantities come from the holding
+/// ledger of the portfolio's accounts (see [`quantities_at`]) and prices are the latest stored
+/// price at or before midnight. Dates before the first ledger row are skipped. `start`
+/// defaults to the portfolio's earliest snapshot; with no snapshots and no `start` there is
+/// nothing to fill.
+pub async fn backfill_portfolio_snapshots(
+    db: &DatabaseConnection,
+    portfolio: &portfolios::Model,
+    start: Option<NaiveDate>,
+    end: NaiveDate,
+    dry_run: bool,
+) -> Result<SnapshotBackfillResult, Box<dyn Error + Send + Sync>> {
+    let mut result = SnapshotBackfillResult {
+        portfolio_id: portfolio.id,
+        portfolio_name: portfolio.name.clone(),
+        created_dates: Vec::new(),
+        skipped_dates: Vec::new(),
+        replaced: 0,
+        error: None,
+    };
+
+    let existing: BTreeSet<NaiveDate> = snapshots::Entity::find()
+        .filter(snapshots::Column::PortfolioId.eq(portfolio.id))
+        .filter(snapshots::Column::SnapshotDate.lte(end))
+        .all(db)
+        .await?
+        .into_iter()
+        .map(|s| s.snapshot_date)
+        .collect();
+    let Some(start) = start.or_else(|| existing.first().copied()) else {
+        return Ok(result);
+    };
+    let dates = missing_dates(&existing, start, end);
+    reconstruct_dates(db, portfolio, dates, dry_run, false, &mut result).await?;
+
+    tracing::info!(
+        "Snapshot backfill of portfolio {} ({}): {} dates {}, {} before the holding ledger",
+        portfolio.name,
+        portfolio.id,
+        result.created_dates.len(),
+        if dry_run { "to fill" } else { "filled" },
+        result.skipped_dates.len()
+    );
+    Ok(result)
+}
+
+/// Reconstruct a snapshot of `portfolio` for each of `dates`, recording them in `result`.
+/// With `replace`, the existing snapshots of each reconstructed date are deleted in the same
+/// transaction as the new one is inserted; dates before the holding ledger are left untouched.
+async fn reconstruct_dates(
+    db: &DatabaseConnection,
+    portfolio: &portfolios::Model,
+    dates: Vec<NaiveDate>,
+    dry_run: bool,
+    replace: bool,
+    result: &mut SnapshotBackfillResult,
+) -> Result<(), Box<dyn Error + Send + Sync>> {
+    if dates.is_empty() {
+        return Ok(());
+    }
+
+    // Ledger rows and current quantities of each account
+    let account_ids = portfolio_account_ids(db, portfolio.id).await?;
+    let rows = holding_transactions::Entity::find()
+        .filter(holding_transactions::Column::AccountId.is_in(account_ids.clone()))
+        .order_by_asc(holding_transactions::Column::RecordedAt)
+        .all(db)
+        .await?;
+    let (dates, skipped) = reconstructable_dates(dates, rows.first().map(|r| r.recorded_at.with_timezone(&Utc)));
+    result.skipped_dates = skipped;
+    if dates.is_empty() {
+        return Ok(());
+    }
+    let mut rows_by_account: HashMap<Uuid, Vec<holding_transactions::Model>> = HashMap::new();
+    for row in rows {
+        rows_by_account.entry(row.account_id).or_default().push(row);
+    }
+    let mut current_by_account = HashMap::new();
+    for account in accounts::Entity::find().filter(accounts::Column::Id.is_in(account_ids)).all(db).await? {
+        let holdings: Vec<AccountHolding> = match account.holdings {
+            Some(json) => serde_json::from_value(json)
+                .map_err(|e| format!("Failed to deserialize holdings of account {}: {}", account.id, e))?,
+            None => Vec::new(),
+        };
+        let current = sum_by_asset(holdings.iter().map(|h| (h.asset.as_str(), h.quantity.as_str())));
+        current_by_account.insert(account.id, current);
+    }
+
+    let settings = PortfolioSettings::from_json(portfolio.settings.as_ref());
+    let mut pricer = LedgerPricer::new(db.clone());
+    for date in dates {
+        let cutoff = day_cutoff(date);
+        if dry_run {
+            result.created_dates.push(date);
+            continue;
+        }
+
+        let mut quantities: BTreeMap<String, (Decimal, Decimal)> = BTreeMap::new();
+        for (account_id, current) in &current_by_account {
+            let account_rows = rows_by_account.get(account_id).map(Vec::as_slice).unwrap_or_default();
+            for (asset, quantity) in quantities_at(account_rows, current, cutoff) {
+                if !settings.excludes_asset(&asset) {
+                    quantities.entry(asset).or_default().0 += quantity;
+                }
+            }
+        }
+        let mut prices = HashMap::new();
+        for asset in quantities.keys() {
+            if let Some(price) = pricer.price(asset, cutoff).await? {
+                prices.insert(asset.clone(), price);
+            }
+        }
+        let (holdings, total_value_usd) = valued_snapshot_holdings(quantities, &prices);
+        let debt = DebtSummary::from_values(holdings.iter().map(|h| (h.value_usd, h.debt_usd())));
+
+        let now = Utc::now();
+        let metadata = SnapshotMetadata {
+            portfolio_name: portfolio.name.clone(),
+            allocation_as_of: cutoff.to_rfc3339(),
+            snapshot_time: now.to_rfc3339(),
+            created_at: now.to_rfc3339(),
+            price_method: Some(SNAPSHOT_TYPE_BACKFILL.to_string()),
+            utc_offset_minutes: Some(0),
+            valuation_cutoff: Some(cutoff.to_rfc3339()),
+            price_fallback_assets: holdings.iter().filter(|h| h.unpriced).map(|h| h.asset.clone()).collect(),
+        };
+        let txn = db.begin().await?;
+        if replace {
+            result.replaced += snapshots::Entity::delete_many()
+                .filter(snapshots::Column::PortfolioId.eq(portfolio.id))
+                .filter(snapshots::Column::SnapshotDate.eq(date))
+                .exec(&txn)
+                .await?
+                .rows_affected;
+        }
+        snapshots::ActiveModel {
+            id: ActiveValue::Set(Uuid::new_v4()),
+            portfolio_id: ActiveValue::Set(portfolio.id),
+            snapshot_date: ActiveValue::Set(date),
+            snapshot_type: ActiveValue::Set(SNAPSHOT_TYPE_BACKFILL.to_string()),
+            total_value_usd: ActiveValue::Set(total_value_usd),
+            holdings: ActiveValue::Set(json!(holdings)),
+            allocation_id: ActiveValue::Set(None),
+            metadata: ActiveValue::Set(Some(json!(metadata))),
+            created_at: ActiveValue::Set(now.into()),
+            construction_run_id: ActiveValue::Set(None),
+            gross_value_usd: ActiveValue::Set(Decimal::from_f64(debt.gross_value_usd)),
+            debt_usd: ActiveValue::Set(Decimal::from_f64(debt.debt_usd)),
+            net_value_usd: ActiveValue::Set(Decimal::from_f64(debt.net_value_usd)),
+        }
+        .insert(&txn)
+        .await?;
+        txn.commit().await?;
+        result.created_dates.push(date);
+    }
+    Ok(())
+}
+
+/// Filter on the snapshots of a portfolio dated `start` to `end` (inclusive), optionally of one
+/// type only
+fn snapshots_in_range(
+    portfolio_id: Uuid,
+    start: NaiveDate,
+    end: NaiveDate,
+    snapshot_type: Option<&str>,
+) -> Condition {
+    let mut condition = Condition::all()
+        .add(snapshots::Column::PortfolioId.eq(portfolio_id))
+        .add(snapshots::Column::SnapshotDate.gte(start))
+        .add(snapshots::Column::SnapshotDate.lte(end));
+    if let Some(snapshot_type) = snapshot_type {
+        condition = condition.add(snapshots::Column::SnapshotType.eq(snapshot_type));
+    }
+    condition
+}
+
+/// Delete a portfolio's snapshots dated `start` to `end` (inclusive), of every type unless
+/// `snapshot_type` is given. Returns the number of snapshots deleted (or that would be in a
+/// dry run).
+pub async fn delete_snapshot_range(
+    db: &DatabaseConnection,
+    portfolio_id: Uuid,
+    start: NaiveDate,
+    end: NaiveDate,
+    snapshot_type: Option<&str>,
+    dry_run: bool,
+) -> Result<u64, Box<dyn Error + Send + Sync>> {
+    let condition = snapshots_in_range(portfolio_id, start, end, snapshot_type);
+    let deleted = if dry_run {
+        snapshots::Entity::find().filter(condition).count(db).await?
+    } else {
+        snapshots::Entity::delete_many().filter(condition).exec(db).await?.rows_affected
+    };
+    tracing::info!(
+        "Snapshot range {} to {} of portfolio {}: {} snapshots {}",
+        start,
+        end,
+        portfolio_id,
+        deleted,
+        if dry_run { "to delete" } else { "deleted" }
+    );
+    Ok(deleted)
+}
+
+/// Rebuild every day from `start` to `end` (inclusive) of a portfolio from the holding ledger
+/// and stored prices, as the backfill does for missing days, replacing whatever snapshots
+/// those days had (e.g. ones valued by a pricing bug). Days before the holding ledger cannot
+/// be reconstructed: they are reported as skipped and keep their snapshots.
+pub async fn rebuild_snapshot_range(
+    db: &DatabaseConnection,
+    portfolio: &portfolios::Model,
+    start: NaiveDate,
+    end: NaiveDate,
+    dry_run: bool,
+) -> Result<SnapshotBackfillResult, Box<dyn Error + Send + Sync>> {
+    let mut result = SnapshotBackfillResult {
+        portfolio_id: portfolio.id,
+        portfolio_name: portfolio.name.clone(),
+        created_dates: Vec::new(),
+        skipped_dates: Vec::new(),
+        replaced: 0,
+        error: None,
+    };
+    let dates = start.iter_days().take_while(|date| *date <= end).collect();
+    reconstruct_dates(db, portfolio, dates, dry_run, true, &mut result).await?;
+    if dry_run {
+        // Snapshots of the days that would be rebuilt
+        for date in &result.created_dates {
+            result.replaced +=
+                snapshots::Entity::find().filter(snapshots_in_range(portfolio.id, *date, *date, None)).count(db).await?;
+        }
+    }
+
+    tracing::info!(
+        "Snapshot rebuild of portfolio {} ({}) from {} to {}: {} dates {} ({} snapshots replaced), {} before the holding ledger",
+        portfolio.name,
+        portfolio.id,
+        start,
+        end,
+        result.created_dates.len(),
+        if dry_run { "to rebuild" } else { "rebuilt" },
+        result.replaced,
+        result.skipped_dates.len()
+    );
+    Ok(result)
+}
+
+/// Backfill the missing snapshots of one portfolio, or of every portfolio when
+/// `portfolio_id` is `None`. A portfolio that fails is reported with its error.
+pub async fn backfill_snapshots(
+    db: &DatabaseConnection,
+    portfolio_id: Option<Uuid>,
+    start: Option<NaiveDate>,
+    end: NaiveDate,
+    dry_run: bool,
+) -> Result<Vec<SnapshotBackfillResult>, Box<dyn Error + Send + Sync>> {
+    let mut query = portfolios::Entity::find();
+    if let Some(portfolio_id) = portfolio_id {
+        query = query.filter(portfolios::Column::Id.eq(portfolio_id));
+    }
+    let mut results = Vec::new();
+    for portfolio in query.all(db).await? {
+        match backfill_portfolio_snapshots(db, &portfolio, start, end, dry_run).await {
+            Ok(result) => results.push(result),
+            Err(e) => {
+                tracing::error!("Snapshot backfill of portfolio {} ({}) failed: {}", portfolio.name, portfolio.id, e);
+                results.push(SnapshotBackfillResult {
+                    portfolio_id: portfolio.id,
+                    portfolio_name: portfolio.name,
+                    created_dates: Vec::new(),
+                    skipped_dates: Vec::new(),
+                    replaced: 0,
+                    error: Some(e.to_string()),
+                });
+            }
+        }
+    }
+    Ok(results)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use chrono::TimeZone;
+
+    fn row(asset: &str, before: i64, after: i64, day: u32, corrected: bool) -> holding_transactions::Model {
+        holding_transactions::Model {
+            id: Uuid::new_v4(),
+            account_id: Uuid::nil(),
+            asset: asset.to_string(),
+            transaction_type: "sync_delta".to_string(),
+            quantity_before: Decimal::from(before),
+            quantity_after: Decimal::from(after),
+            delta: Decimal::from(after - before),
+            corrected_by: corrected.then(Uuid::new_v4),
+            recorded_at: Utc.with_ymd_and_hms(2025, 3, day, 12, 0, 0).unwrap().into(),
+        }
+    }
+
+    #[test]
+    fn test_quantities_at() {
+        let rows = vec![
+            row("BTC", 0, 2, 1, false),
+            row("ETH", 10, 99, 2, true),
+            row("BTC", 2, 3, 3, false),
+            row("ETH", 10, 4, 4, false),
+        ];
+        let current = HashMap::from([
+            ("BTC".to_string(), Decimal::from(3)),
+            ("ETH".to_string(), Decimal::from(4)),
+            ("SOL".to_string(), Decimal::from(7)),
+        ]);
+        let at = |day| Utc.with_ymd_and_hms(2025, 3, day, 0, 0, 0).unwrap();
+
+        // Before any row: BTC starts at zero, ETH before its first effective row
+        let first = quantities_at(&rows, &current, at(1));
+        assert_eq!(first.get("BTC"), None);
+        assert_eq!(first["ETH"], Decimal::from(10));
+        assert_eq!(first["SOL"], Decimal::from(7));
+
+        // The corrected ETH row on day 2 is ignored
+        let middle = quantities_at(&rows, &current, at(3));
+        assert_eq!(middle["BTC"], Decimal::from(2));
+        assert_eq!(middle["ETH"], Decimal::from(10));
+
+        assert_eq!(quantities_at(&rows, &current, at(5)), current.clone().into_iter().collect());
+
+        let day = |d| NaiveDate::from_ymd_opt(2025, 3, d).unwrap();
+        let existing = BTreeSet::from([day(1), day(3)]);
+        assert_eq!(missing_dates(&existing, day(1), day(4)), vec![day(2), day(4)]);
+        assert!(missing_dates(&existing, day(3), day(1)).is_empty());
+    }
+
+    #[test]
+    fn test_reconstructable_dates() {
+        let day = |d| NaiveDate::from_ymd_opt(2025, 3, d).unwrap();
+        let dates = vec![day(1), day(2), day(3), day(4)];
+
+        // The ledger starts at noon on day 2: day 1 ended before it, day 2 ends after it
+        let history_start = Utc.with_ymd_and_hms(2025, 3, 2, 12, 0, 0).unwrap();
+        assert_eq!(
+            reconstructable_dates(dates.clone(), Some(history_start)),
+            (vec![day(2), day(3), day(4)], vec![day(1)])
+        );
+
+        // A ledger starting exactly at midnight cannot value the day that just ended
+        let midnight = Utc.with_ymd_and_hms(2025, 3, 3, 0, 0, 0).unwrap();
+        assert_eq!(reconstructable_dates(dates.clone(), Some(midnight)), (vec![day(3), day(4)], vec![day(1), day(2)]));
+
+        assert_eq!(reconstructable_dates(dates.clone(), None), (Vec::new(), dates));
+    }
+}
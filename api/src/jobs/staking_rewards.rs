@@ -0,0 +1,77 @@
+use crate::connectors::{ExchangeConnector, StakingReward};
+use crate::entities::staking_rewards;
+use crate::jobs::income_events::{record_income_events, staking_reward_receipts};
+use rust_decimal::Decimal;
+use sea_orm::{
+    sea_query::OnConflict, ActiveValue, ColumnTrait, DatabaseConnection, EntityTrait, QueryFilter,
+    QueryOrder,
+};
+use std::error::Error;
+use std::str::FromStr;
+use uuid::Uuid;
+
+/// Build the rows stored for fetched rewards, skipping rewards with an unparsable amount
+fn reward_rows(account_id: Uuid, rewards: &[StakingReward]) -> Vec<staking_rewards::ActiveModel> {
+    rewards
+        .iter()
+        .filter_map(|r| {
+            let Ok(amount) = Decimal::from_str(&r.amount) else {
+                tracing::warn!("Skipping {} reward for epoch {} with invalid amount", r.source_account, r.epoch);
+                return None;
+            };
+
+            Some(staking_rewards::ActiveModel {
+                id: ActiveValue::Set(Uuid::new_v4()),
+                account_id: ActiveValue::Set(account_id),
+                asset: ActiveValue::Set(r.asset.clone()),
+                source_account: ActiveValue::Set(r.source_account.clone()),
+                epoch: ActiveValue::Set(r.epoch as i64),
+                amount: ActiveValue::Set(amount),
+                post_balance: ActiveValue::Set(r.post_balance.as_deref().and_then(|b| Decimal::from_str(b).ok())),
+                commission: ActiveValue::Set(r.commission.map(i16::from)),
+                earned_at: ActiveValue::Set(r.earned_at.into()),
+                created_at: ActiveValue::NotSet,
+            })
+        })
+        .collect()
+}
+
+/// Fetch the account's staking rewards for epochs after the latest stored one and append them
+/// to the `staking_rewards` table.
+///
+/// The unique (account_id, source_account, epoch) index makes re-inserting known rewards a
+/// no-op. Rewards are also recorded as `income_events`. Returns the number of new rewards.
+pub async fn sync_staking_rewards(
+    db: &DatabaseConnection,
+    account_id: Uuid,
+    connector: &dyn ExchangeConnector,
+) -> Result<u64, Box<dyn Error + Send + Sync>> {
+    let after_epoch: Option<u64> = staking_rewards::Entity::find()
+        .filter(staking_rewards::Column::AccountId.eq(account_id))
+        .order_by_desc(staking_rewards::Column::Epoch)
+        .one(db)
+        .await?
+        .map(|r| r.epoch as u64);
+
+    let fetched = connector.fetch_staking_rewards(after_epoch).await?;
+    let rows = reward_rows(account_id, &fetched);
+    if rows.is_empty() {
+        return Ok(0);
+    }
+
+    let inserted = staking_rewards::Entity::insert_many(rows)
+        .on_conflict(
+            OnConflict::columns([
+                staking_rewards::Column::AccountId,
+                staking_rewards::Column::SourceAccount,
+                staking_rewards::Column::Epoch,
+            ])
+            .do_nothing()
+            .to_owned(),
+        )
+        .exec_without_returning(db)
+        .await?;
+    record_income_events(db, account_id, &staking_reward_receipts(&fetched)).await?;
+
+    Ok(inserted)
+}
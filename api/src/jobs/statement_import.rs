@@ -0,0 +1,266 @@
+use crate::connectors::Balance;
+use crate::domain::AccountHolding;
+use crate::entities::{accounts, holding_transactions};
+use crate::helpers::csv_import::{ImportedBalance, ImportedTransaction};
+use crate::jobs::account_sync::{holdings_json, record_balance_changes};
+use crate::jobs::anomaly_detection::sum_by_asset;
+use crate::jobs::holding_ledger::TX_IMPORT;
+use chrono::{DateTime, Utc};
+use rust_decimal::Decimal;
+use sea_orm::{
+    ActiveModelTrait, ActiveValue, ColumnTrait, DatabaseConnection, EntityTrait, QueryFilter,
+    TransactionTrait,
+};
+use std::collections::{BTreeMap, HashMap, HashSet};
+use std::error::Error;
+use thiserror::Error as ThisError;
+use uuid::Uuid;
+
+/// Outcome of a statement import
+#[derive(Debug, Default, PartialEq)]
+pub struct ImportSummary {
+    /// Holdings stored on the account after the import
+    pub holdings_count: usize,
+    /// Ledger rows written for imported transactions
+    pub transactions_imported: usize,
+    /// Transactions skipped because an identical row was imported before
+    pub transactions_skipped: usize,
+}
+
+/// A statement that cannot be applied to the account (reported to the user as a bad request)
+#[derive(Debug, ThisError)]
+#[error("{0}")]
+pub struct ImportRejected(pub String);
+
+/// A ledger row to be written for one imported transaction
+#[derive(Debug, Clone, PartialEq)]
+pub struct PlannedImport {
+    pub asset: String,
+    pub quantity_before: Decimal,
+    pub quantity_after: Decimal,
+    pub recorded_at: DateTime<Utc>,
+}
+
+/// Result of applying a transaction statement to the current quantities
+#[derive(Debug, PartialEq)]
+pub struct ImportPlan {
+    /// Ledger rows to write, in time order
+    pub entries: Vec<PlannedImport>,
+    /// Transactions skipped as already imported (or zero)
+    pub skipped: usize,
+    /// Quantities per asset after the statement, without zero balances
+    pub quantities: BTreeMap<String, Decimal>,
+}
+
+/// Key identifying an imported transaction, used to skip re-imported rows
+type ImportKey = (String, DateTime<Utc>, Decimal);
+
+/// Apply transactions (in time order) on top of the current quantities.
+///
+/// Transactions already imported (same asset, time and delta) are skipped. Fails when a
+/// transaction would take an asset below zero, which means the statement is incomplete.
+pub fn plan_import_entries(
+    current: &HashMap<String, Decimal>,
+    transactions: &[ImportedTransaction],
+    already_imported: &HashSet<ImportKey>,
+) -> Result<ImportPlan, String> {
+    let mut quantities: BTreeMap<String, Decimal> =
+        current.iter().map(|(asset, quantity)| (asset.clone(), *quantity)).collect();
+    let mut planned = Vec::new();
+    let mut skipped = 0;
+
+    for tx in transactions {
+        if tx.delta.is_zero() || already_imported.contains(&(tx.asset.clone(), tx.occurred_at, tx.delta)) {
+            skipped += 1;
+            continue;
+        }
+
+        let before = quantities.get(&tx.asset).copied().unwrap_or(Decimal::ZERO);
+        let after = before + tx.delta;
+        if after < Decimal::ZERO {
+            return Err(format!(
+                "{} {} at {} would make the {} balance negative; import earlier transactions first",
+                tx.delta,
+                tx.asset,
+                tx.occurred_at.to_rfc3339(),
+                tx.asset
+            ));
+        }
+
+        quantities.insert(tx.asset.clone(), after);
+        planned.push(PlannedImport {
+            asset: tx.asset.clone(),
+            quantity_before: before,
+            quantity_after: after,
+            recorded_at: tx.occurred_at,
+        });
+    }
+
+    quantities.retain(|_, quantity| !quantity.is_zero());
+    Ok(ImportPlan {
+        entries: planned,
+        skipped,
+        quantities,
+    })
+}
+
+/// Store imported quantities as the account's holdings, as a sync would
+async fn store_holdings(
+    db: &DatabaseConnection,
+    account: accounts::Model,
+    balances: &[Balance],
+) -> Result<usize, Box<dyn Error + Send + Sync>> {
+    let holdings = holdings_json(balances);
+    let holdings_count = holdings.len();
+
+    let mut account_update: accounts::ActiveModel = account.into();
+    account_update.last_synced_at = ActiveValue::Set(Some(Utc::now().into()));
+    account_update.holdings = ActiveValue::Set(Some(serde_json::to_value(&holdings)?));
+    account_update.update(db).await?;
+
+    Ok(holdings_count)
+}
+
+/// Imported quantity as a spot balance
+fn spot_balance(asset: String, quantity: Decimal) -> Balance {
+    let quantity = quantity.normalize().to_string();
+    Balance {
+        asset,
+        available: quantity.clone(),
+        quantity,
+        frozen: "0".to_string(),
+        decimals: None,
+        holding_source: None,
+    }
+}
+
+/// Import a balance statement: the statement replaces the account's holdings (assets missing
+/// from it drop to zero) and the changes are flagged and written to the ledger like a sync.
+pub async fn import_balances(
+    db: &DatabaseConnection,
+    account: accounts::Model,
+    balances: Vec<ImportedBalance>,
+) -> Result<ImportSummary, Box<dyn Error + Send + Sync>> {
+    let balances: Vec<Balance> = balances
+        .into_iter()
+        .filter(|b| !b.quantity.is_zero())
+        .map(|b| spot_balance(b.asset, b.quantity))
+        .collect();
+
+    record_balance_changes(db, &account, &balances).await;
+    let holdings_count = store_holdings(db, account, &balances).await?;
+
+    Ok(ImportSummary {
+        holdings_count,
+        ..Default::default()
+    })
+}
+
+/// Import a transaction statement: each transaction becomes an `import` ledger row at its own
+/// time, and the resulting quantities become the account's holdings.
+///
+/// Fails with [`ImportRejected`] when the statement cannot be applied (see [`plan_import_entries`]).
+pub async fn import_transactions(
+    db: &DatabaseConnection,
+    account: accounts::Model,
+    transactions: Vec<ImportedTransaction>,
+) -> Result<ImportSummary, Box<dyn Error + Send + Sync>> {
+    let holdings: Vec<AccountHolding> = account
+        .holdings
+        .clone()
+        .and_then(|h| serde_json::from_value(h).ok())
+        .unwrap_or_default();
+    let current = sum_by_asset(holdings.iter().map(|h| (h.asset.as_str(), h.quantity.as_str())));
+
+    let already_imported: HashSet<ImportKey> = holding_transactions::Entity::find()
+        .filter(holding_transactions::Column::AccountId.eq(account.id))
+        .filter(holding_transactions::Column::TransactionType.eq(TX_IMPORT))
+        .all(db)
+        .await?
+        .into_iter()
+        .map(|tx| (tx.asset, tx.recorded_at.with_timezone(&Utc), tx.delta))
+        .collect();
+
+    let ImportPlan { entries: planned, skipped, quantities } =
+        plan_import_entries(&current, &transactions, &already_imported).map_err(ImportRejected)?;
+
+    let account_id = account.id;
+    let txn = db.begin().await?;
+    for entry in &planned {
+        holding_transactions::ActiveModel {
+            id: ActiveValue::Set(Uuid::new_v4()),
+            account_id: ActiveValue::Set(account_id),
+            asset: ActiveValue::Set(entry.asset.clone()),
+            transaction_type: ActiveValue::Set(TX_IMPORT.to_string()),
+            quantity_before: ActiveValue::Set(entry.quantity_before),
+            quantity_after: ActiveValue::Set(entry.quantity_after),
+            delta: ActiveValue::Set(entry.quantity_after - entry.quantity_before),
+            corrected_by: ActiveValue::Set(None),
+            recorded_at: ActiveValue::Set(entry.recorded_at.into()),
+        }
+        .insert(&txn)
+        .await?;
+    }
+    txn.commit().await?;
+
+    let balances: Vec<Balance> = quantities
+        .into_iter()
+        .map(|(asset, quantity)| spot_balance(asset, quantity))
+        .collect();
+    let holdings_count = store_holdings(db, account, &balances).await?;
+
+    tracing::info!(
+        "Imported {} transactions ({} skipped) for account {}",
+        planned.len(),
+        skipped,
+        account_id
+    );
+
+    Ok(ImportSummary {
+        holdings_count,
+        transactions_imported: planned.len(),
+        transactions_skipped: skipped,
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use chrono::TimeZone;
+
+    fn tx(asset: &str, delta: i64, day: u32) -> ImportedTransaction {
+        ImportedTransaction {
+            asset: asset.to_string(),
+            delta: Decimal::from(delta),
+            occurred_at: Utc.with_ymd_and_hms(2024, 1, day, 0, 0, 0).unwrap(),
+        }
+    }
+
+    #[test]
+    fn test_plan_applies_transactions_and_skips_known_rows() {
+        let current = HashMap::from([("BTC".to_string(), Decimal::ONE)]);
+        let known = tx("BTC", 1, 1);
+        let already_imported = HashSet::from([(known.asset.clone(), known.occurred_at, known.delta)]);
+
+        let plan = plan_import_entries(
+            &current,
+            &[known, tx("USDT", 500, 2), tx("USDT", -500, 3), tx("BTC", 2, 4)],
+            &already_imported,
+        )
+        .unwrap();
+
+        assert_eq!(plan.skipped, 1);
+        assert_eq!(plan.entries.len(), 3);
+        assert_eq!(plan.entries[1].quantity_before, Decimal::from(500));
+        assert_eq!(plan.entries[1].quantity_after, Decimal::ZERO);
+        assert_eq!(plan.entries[2].quantity_after, Decimal::from(3));
+        // USDT went back to zero and is no longer held
+        assert_eq!(plan.quantities, BTreeMap::from([("BTC".to_string(), Decimal::from(3))]));
+    }
+
+    #[test]
+    fn test_plan_rejects_negative_balances() {
+        let result = plan_import_entries(&HashMap::new(), &[tx("ETH", -1, 1)], &HashSet::new());
+        assert!(result.unwrap_err().contains("ETH balance negative"));
+    }
+}
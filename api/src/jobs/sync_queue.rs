@@ -0,0 +1,466 @@
+//! Background queue of account syncs, requested one at a time or for all of a user's accounts.
+//!
+//! Each request becomes a `sync_jobs` row that the client polls: "queued" until one of the
+//! `SYNC_QUEUE_CONCURRENCY` workers (default 4) picks it up, "running" with the stage the sync
+//! has reached, then "succeeded" or "failed". An account with a queued or running job gets
+//! that job back instead of a second sync.
+//!
+//! While a job is queued or running, its progress is also published in memory for
+//! `GET /api/v1/accounts/{account_id}/sync-status/{job_id}/events`: every stage, and for
+//! multi-chain wallets each chain as reading it starts and finishes ("ethereum done, bsc in
+//! progress"), then the outcome.
+//!
+//! Jobs live in the process that queued them; those left queued or running by a restart are
+//! marked failed at startup.
+
+use std::collections::HashMap;
+use std::error::Error;
+use std::sync::{Arc, LazyLock, Mutex, OnceLock};
+
+use async_trait::async_trait;
+use chrono::Utc;
+use sea_orm::sea_query::Expr;
+use sea_orm::{ActiveModelTrait, ActiveValue, ColumnTrait, DatabaseConnection, DbErr, EntityTrait, QueryFilter, SqlErr};
+use serde::Serialize;
+use tokio::sync::{broadcast, Semaphore};
+use uuid::Uuid;
+
+use crate::connectors::ChainProgress;
+use crate::entities::{accounts, sync_jobs};
+use crate::jobs::account_sync::{self, SyncProgress, SyncResult, SYNC_STAGES};
+
+pub const SYNC_JOB_QUEUED: &str = "queued";
+pub const SYNC_JOB_RUNNING: &str = "running";
+pub const SYNC_JOB_SUCCEEDED: &str = "succeeded";
+pub const SYNC_JOB_FAILED: &str = "failed";
+
+/// Default number of syncs run at once when `SYNC_QUEUE_CONCURRENCY` is not set
+const DEFAULT_CONCURRENCY: usize = 4;
+
+/// Workers shared by every queued sync, sized by `SYNC_QUEUE_CONCURRENCY` (default: 4)
+fn workers() -> Arc<Semaphore> {
+    static WORKERS: OnceLock<Arc<Semaphore>> = OnceLock::new();
+    WORKERS
+        .get_or_init(|| {
+            let concurrency = std::env::var("SYNC_QUEUE_CONCURRENCY")
+                .ok()
+                .and_then(|v| v.trim().parse::<usize>().ok())
+                .filter(|v| *v > 0)
+                .unwrap_or(DEFAULT_CONCURRENCY);
+            Arc::new(Semaphore::new(concurrency))
+        })
+        .clone()
+}
+
+/// `ChainStatus::status` of a chain being read
+pub const CHAIN_IN_PROGRESS: &str = "in_progress";
+/// `ChainStatus::status` of a chain that has been read
+pub const CHAIN_DONE: &str = "done";
+
+/// Events buffered per subscriber before the slowest ones skip ahead
+const EVENT_BUFFER: usize = 64;
+
+/// Where reading a chain of a syncing wallet stands
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct ChainStatus {
+    /// Wallet address read
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub address: Option<String>,
+    pub chain: String,
+    /// "in_progress" or "done"
+    pub status: &'static str,
+    /// Balances read; set once done
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub balances: Option<usize>,
+    /// Why some or all of the chain's balances could not be read
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub error: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub duration_ms: Option<u64>,
+}
+
+impl ChainStatus {
+    fn new(address: Option<&str>, progress: &ChainProgress) -> Self {
+        let address = address.map(str::to_string);
+        match progress {
+            ChainProgress::Started { chain } => Self {
+                address,
+                chain: chain.clone(),
+                status: CHAIN_IN_PROGRESS,
+                balances: None,
+                error: None,
+                duration_ms: None,
+            },
+            ChainProgress::Finished(fetch) => Self {
+                address,
+                chain: fetch.chain.clone(),
+                status: CHAIN_DONE,
+                balances: Some(fetch.balances),
+                error: fetch.error.clone(),
+                duration_ms: Some(fetch.duration_ms),
+            },
+        }
+    }
+}
+
+/// Progress of a sync job, streamed to subscribers
+#[derive(Debug, Clone, PartialEq, Serialize)]
+#[serde(tag = "type", rename_all = "snake_case")]
+pub enum SyncEvent {
+    /// Where the job stands, sent first to every subscriber
+    Snapshot {
+        status: String,
+        stage: Option<String>,
+        steps_completed: i32,
+        steps_total: i32,
+        chains: Vec<ChainStatus>,
+    },
+    /// The sync entered a stage
+    Stage { stage: String, steps_completed: i32, steps_total: i32 },
+    /// Reading a chain started or finished
+    Chain(ChainStatus),
+    /// The job succeeded or failed; nothing follows
+    Finished {
+        status: String,
+        holdings_count: Option<i32>,
+        error: Option<String>,
+        error_kind: Option<String>,
+    },
+}
+
+impl SyncEvent {
+    /// Where `job` stands, with the chains its sync has reached so far
+    pub fn snapshot(job: &sync_jobs::Model, chains: Vec<ChainStatus>) -> Self {
+        Self::Snapshot {
+            status: job.status.clone(),
+            stage: job.stage.clone(),
+            steps_completed: job.steps_completed,
+            steps_total: job.steps_total,
+            chains,
+        }
+    }
+
+    /// Outcome of a finished `job`
+    pub fn finished(job: &sync_jobs::Model) -> Self {
+        Self::Finished {
+            status: job.status.clone(),
+            holdings_count: job.holdings_count,
+            error: job.error.clone(),
+            error_kind: job.error_kind.clone(),
+        }
+    }
+
+    /// SSE event name
+    pub fn name(&self) -> &'static str {
+        match self {
+            Self::Snapshot { .. } => "snapshot",
+            Self::Stage { .. } => "stage",
+            Self::Chain(_) => "chain",
+            Self::Finished { .. } => "finished",
+        }
+    }
+}
+
+/// Progress of a queued or running job, kept for subscribers
+struct LiveJob {
+    events: broadcast::Sender<SyncEvent>,
+    /// Latest status per chain, for subscribers joining mid-sync
+    chains: Vec<ChainStatus>,
+}
+
+static LIVE_JOBS: LazyLock<Mutex<HashMap<Uuid, LiveJob>>> = LazyLock::new(|| Mutex::new(HashMap::new()));
+
+fn track_job(job_id: Uuid) {
+    let (events, _) = broadcast::channel(EVENT_BUFFER);
+    let mut jobs = LIVE_JOBS.lock().unwrap_or_else(|e| e.into_inner());
+    jobs.insert(job_id, LiveJob { events, chains: Vec::new() });
+}
+
+/// Publish `event` to the job's subscribers; a finished event stops tracking the job
+fn publish(job_id: Uuid, event: SyncEvent) {
+    let mut jobs = LIVE_JOBS.lock().unwrap_or_else(|e| e.into_inner());
+    let Some(job) = jobs.get_mut(&job_id) else {
+        return;
+    };
+    if let SyncEvent::Chain(status) = &event {
+        match job.chains.iter_mut().find(|c| c.chain == status.chain && c.address == status.address) {
+            Some(current) => *current = status.clone(),
+            None => job.chains.push(status.clone()),
+        }
+    }
+    let finished = matches!(event, SyncEvent::Finished { .. });
+    // Sending fails only when nobody is subscribed
+    let _ = job.events.send(event);
+    if finished {
+        jobs.remove(&job_id);
+    }
+}
+
+/// Stop tracking a job; its subscribers' streams end
+fn forget_job(job_id: Uuid) {
+    LIVE_JOBS.lock().unwrap_or_else(|e| e.into_inner()).remove(&job_id);
+}
+
+/// Subscribe to the progress of a job queued or running in this process, returning the chains
+/// its sync has reached so far; `None` once the job has finished.
+pub fn subscribe(job_id: Uuid) -> Option<(Vec<ChainStatus>, broadcast::Receiver<SyncEvent>)> {
+    let jobs = LIVE_JOBS.lock().unwrap_or_else(|e| e.into_inner());
+    jobs.get(&job_id).map(|job| (job.chains.clone(), job.events.subscribe()))
+}
+
+/// Whether a job with `status` has finished
+pub fn is_finished(status: &str) -> bool {
+    status == SYNC_JOB_SUCCEEDED || status == SYNC_JOB_FAILED
+}
+
+/// Share of the stages done, 0-100; 100 once the job has finished
+pub fn progress_percent(job: &sync_jobs::Model) -> f64 {
+    if is_finished(&job.status) {
+        return 100.0;
+    }
+    if job.steps_total <= 0 {
+        return 0.0;
+    }
+    (f64::from(job.steps_completed) / f64::from(job.steps_total) * 100.0).clamp(0.0, 100.0)
+}
+
+/// Records the stage a job's sync has reached
+struct SyncJobProgress {
+    db: DatabaseConnection,
+    job_id: Uuid,
+}
+
+#[async_trait]
+impl SyncProgress for SyncJobProgress {
+    async fn stage(&self, stage: &'static str) {
+        // Stages before this one are done
+        let completed = SYNC_STAGES.iter().position(|s| *s == stage).unwrap_or(0) as i32;
+        let result = sync_jobs::Entity::update_many()
+            .col_expr(sync_jobs::Column::Stage, Expr::value(stage))
+            .col_expr(sync_jobs::Column::StepsCompleted, Expr::value(completed))
+            .filter(sync_jobs::Column::Id.eq(self.job_id))
+            .exec(&self.db)
+            .await;
+        if let Err(e) = result {
+            tracing::warn!("Failed to record progress of sync job {}: {}", self.job_id, e);
+        }
+        publish(
+            self.job_id,
+            SyncEvent::Stage {
+                stage: stage.to_string(),
+                steps_completed: completed,
+                steps_total: SYNC_STAGES.len() as i32,
+            },
+        );
+    }
+
+    fn chain(&self, address: Option<&str>, progress: &ChainProgress) {
+        publish(self.job_id, SyncEvent::Chain(ChainStatus::new(address, progress)));
+    }
+}
+
+/// Queued or running job of an account
+async fn active_job(db: &DatabaseConnection, account_id: Uuid) -> Result<Option<sync_jobs::Model>, DbErr> {
+    sync_jobs::Entity::find()
+        .filter(sync_jobs::Column::AccountId.eq(account_id))
+        .filter(sync_jobs::Column::Status.is_in([SYNC_JOB_QUEUED, SYNC_JOB_RUNNING]))
+        .one(db)
+        .await
+}
+
+/// Queue a sync of `account` and return its job, or the account's queued or running job if it
+/// has one.
+///
+/// Every account sync goes through here; `idx_sync_jobs_active_account` keeps concurrent
+/// requests from queueing a second job of an account.
+pub async fn enqueue_account_sync(db: &DatabaseConnection, account: &accounts::Model) -> Result<sync_jobs::Model, DbErr> {
+    if let Some(active) = active_job(db, account.id).await? {
+        return Ok(active);
+    }
+
+    let inserted = sync_jobs::ActiveModel {
+        id: ActiveValue::Set(Uuid::new_v4()),
+        account_id: ActiveValue::Set(account.id),
+        user_id: ActiveValue::Set(account.user_id),
+        status: ActiveValue::Set(SYNC_JOB_QUEUED.to_string()),
+        stage: ActiveValue::Set(None),
+        steps_completed: ActiveValue::Set(0),
+        steps_total: ActiveValue::Set(SYNC_STAGES.len() as i32),
+        holdings_count: ActiveValue::Set(None),
+        error: ActiveValue::Set(None),
+        error_kind: ActiveValue::Set(None),
+        created_at: ActiveValue::Set(Utc::now().into()),
+        started_at: ActiveValue::Set(None),
+        finished_at: ActiveValue::Set(None),
+    }
+    .insert(db)
+    .await;
+    let job = match inserted {
+        Ok(job) => job,
+        // A concurrent request queued the account first
+        Err(e) if matches!(e.sql_err(), Some(SqlErr::UniqueConstraintViolation(_))) => {
+            return match active_job(db, account.id).await? {
+                Some(active) => Ok(active),
+                None => Err(e),
+            };
+        }
+        Err(e) => return Err(e),
+    };
+    track_job(job.id);
+
+    // A watcher task monitors the JoinHandle so panics are observable in logs and the job
+    // does not stay "running"
+    let db_bg = db.clone();
+    let job_id = job.id;
+    let handle = tokio::spawn(async move {
+        if let Err(e) = run_sync_job(&db_bg, job_id).await {
+            tracing::error!("Sync job {} failed: {}", job_id, e);
+        }
+    });
+    let db_watch = db.clone();
+    tokio::spawn(async move {
+        if let Err(e) = handle.await {
+            tracing::error!("Sync job {} panicked: {:?}", job_id, e);
+            let _ = finish_job(&db_watch, job_id, Err(format!("Sync panicked: {}", e))).await;
+        }
+        // Normally gone with the finished event; a job that could not be recorded must not
+        // keep its subscribers waiting
+        forget_job(job_id);
+    });
+    Ok(job)
+}
+
+/// Wait for a worker, then run the job's sync and record the outcome
+async fn run_sync_job(db: &DatabaseConnection, job_id: Uuid) -> Result<(), Box<dyn Error + Send + Sync>> {
+    let _permit = workers().acquire_owned().await?;
+    let Some(job) = sync_jobs::Entity::find_by_id(job_id).one(db).await? else {
+        return Ok(());
+    };
+    let account_id = job.account_id;
+    let mut running: sync_jobs::ActiveModel = job.into();
+    running.status = ActiveValue::Set(SYNC_JOB_RUNNING.to_string());
+    running.started_at = ActiveValue::Set(Some(Utc::now().into()));
+    running.update(db).await?;
+
+    let progress = SyncJobProgress { db: db.clone(), job_id };
+    let outcome = account_sync::sync_account_with_progress(db, account_id, Some(&progress))
+        .await
+        .map_err(|e| format!("Sync failed: {}", e));
+    match &outcome {
+        Ok(result) if result.success => {
+            tracing::info!("Sync job {} completed for account {}: {} holdings", job_id, account_id, result.holdings_count)
+        }
+        Ok(result) => tracing::warn!("Sync job {} finished with error for account {}: {:?}", job_id, account_id, result.error),
+        Err(e) => tracing::error!("Sync job {} failed for account {}: {}", job_id, account_id, e),
+    }
+    finish_job(db, job_id, outcome).await?;
+    Ok(())
+}
+
+/// Record the outcome of a job's sync
+async fn finish_job(db: &DatabaseConnection, job_id: Uuid, outcome: Result<SyncResult, String>) -> Result<(), DbErr> {
+    let Some(job) = sync_jobs::Entity::find_by_id(job_id).one(db).await? else {
+        return Ok(());
+    };
+    let mut finished: sync_jobs::ActiveModel = job.into();
+    match outcome {
+        Ok(result) if result.success => {
+            finished.status = ActiveValue::Set(SYNC_JOB_SUCCEEDED.to_string());
+            finished.steps_completed = ActiveValue::Set(SYNC_STAGES.len() as i32);
+            finished.holdings_count = ActiveValue::Set(Some(result.holdings_count as i32));
+        }
+        Ok(result) => {
+            finished.status = ActiveValue::Set(SYNC_JOB_FAILED.to_string());
+            finished.error = ActiveValue::Set(result.error);
+            finished.error_kind = ActiveValue::Set(result.error_kind);
+        }
+        Err(error) => {
+            finished.status = ActiveValue::Set(SYNC_JOB_FAILED.to_string());
+            finished.error = ActiveValue::Set(Some(error));
+        }
+    }
+    finished.finished_at = ActiveValue::Set(Some(Utc::now().into()));
+    let job = finished.update(db).await?;
+    publish(job_id, SyncEvent::finished(&job));
+    Ok(())
+}
+
+/// Mark the jobs a previous process left queued or running as failed; returns how many.
+pub async fn fail_interrupted_sync_jobs(db: &DatabaseConnection) -> Result<u64, DbErr> {
+    let result = sync_jobs::Entity::update_many()
+        .col_expr(sync_jobs::Column::Status, Expr::value(SYNC_JOB_FAILED))
+        .col_expr(sync_jobs::Column::Error, Expr::value("Interrupted by a server restart"))
+        .col_expr(sync_jobs::Column::FinishedAt, Expr::value(Utc::now()))
+        .filter(sync_jobs::Column::Status.is_in([SYNC_JOB_QUEUED, SYNC_JOB_RUNNING]))
+        .exec(db)
+        .await?;
+    Ok(result.rows_affected)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_progress_percent() {
+        let mut job = sync_jobs::Model {
+            id: Uuid::new_v4(),
+            account_id: Uuid::new_v4(),
+            user_id: Uuid::new_v4(),
+            status: SYNC_JOB_QUEUED.to_string(),
+            stage: None,
+            steps_completed: 0,
+            steps_total: 4,
+            holdings_count: None,
+            error: None,
+            error_kind: None,
+            created_at: Utc::now().into(),
+            started_at: None,
+            finished_at: None,
+        };
+        assert_eq!(progress_percent(&job), 0.0);
+        job.status = SYNC_JOB_RUNNING.to_string();
+        job.steps_completed = 1;
+        assert_eq!(progress_percent(&job), 25.0);
+        // A failed sync is finished even if it stopped early
+        job.status = SYNC_JOB_FAILED.to_string();
+        assert_eq!(progress_percent(&job), 100.0);
+        assert!(!is_finished(SYNC_JOB_RUNNING));
+    }
+
+    #[test]
+    fn test_live_job_events() {
+        let job_id = Uuid::new_v4();
+        track_job(job_id);
+        let (chains, mut events) = subscribe(job_id).unwrap();
+        assert!(chains.is_empty());
+
+        let started = |chain: &str| ChainProgress::Started { chain: chain.to_string() };
+        let progress = SyncJobProgress { db: DatabaseConnection::Disconnected, job_id };
+        progress.chain(None, &started("ethereum"));
+        progress.chain(None, &started("bsc"));
+        progress.chain(
+            None,
+            &ChainProgress::Finished(crate::connectors::ChainFetch {
+                chain: "ethereum".to_string(),
+                balances: 4,
+                error: None,
+                duration_ms: 900,
+            }),
+        );
+
+        // A late subscriber sees each chain once, at its latest status
+        let (chains, _) = subscribe(job_id).unwrap();
+        let statuses: Vec<(&str, &str)> = chains.iter().map(|c| (c.chain.as_str(), c.status)).collect();
+        assert_eq!(statuses, vec![("ethereum", CHAIN_DONE), ("bsc", CHAIN_IN_PROGRESS)]);
+        assert_eq!(events.try_recv().unwrap().name(), "chain");
+
+        publish(
+            job_id,
+            SyncEvent::Finished { status: SYNC_JOB_SUCCEEDED.to_string(), holdings_count: Some(4), error: None, error_kind: None },
+        );
+        assert!(subscribe(job_id).is_none());
+        let last = std::iter::from_fn(|| events.try_recv().ok()).last().unwrap();
+        assert_eq!(serde_json::to_value(&last).unwrap()["type"], "finished");
+    }
+}
@@ -0,0 +1,167 @@
+use crate::entities::{accounts, tax_disposals, tax_lots, transfers, users};
+use crate::helpers::cost_basis::LedgerAction;
+use crate::helpers::tax_lots::{match_lots, AccountLedgerEntry, LotMethod};
+use crate::jobs::portfolio_pnl::load_ledger;
+use crate::jobs::transfer_sync::pair_own_transfers;
+use chrono::Utc;
+use sea_orm::{
+    ActiveValue, ColumnTrait, DatabaseConnection, EntityTrait, QueryFilter, TransactionTrait,
+};
+use std::collections::{HashMap, HashSet};
+use std::error::Error;
+use tracing;
+use uuid::Uuid;
+
+/// Rows inserted per statement
+const INSERT_CHUNK: usize = 1000;
+
+/// Result of a tax lot rebuild
+#[derive(Debug, Clone, Default)]
+pub struct TaxLotRebuildResult {
+    pub accounts: usize,
+    pub lots: usize,
+    pub disposals: usize,
+}
+
+/// Rebuild the `tax_lots` and `tax_disposals` of one user's accounts from their ledgers with
+/// `method`, replacing the previous ones; returns the number of (lots, disposals) stored.
+///
+/// The accounts are matched together so that withdrawals paired with deposits into another
+/// of the accounts (see [`pair_own_transfers`]) move their lots instead of realizing them.
+pub async fn rebuild_lots(
+    db: &DatabaseConnection,
+    account_ids: &[Uuid],
+    method: LotMethod,
+) -> Result<(usize, usize), Box<dyn Error + Send + Sync>> {
+    let transfer_rows = transfers::Entity::find()
+        .filter(transfers::Column::AccountId.is_in(account_ids.to_vec()))
+        .all(db)
+        .await?;
+    let pairs = pair_own_transfers(&transfer_rows);
+    let withdrawals: HashSet<Uuid> = pairs.values().copied().collect();
+
+    let mut entries = Vec::new();
+    for account_id in account_ids {
+        for entry in load_ledger(db, &[*account_id]).await?.entries {
+            let transfer_key = entry.transfer_id.and_then(|id| match entry.action {
+                LedgerAction::TransferIn => pairs.get(&id).copied(),
+                LedgerAction::TransferOut => withdrawals.contains(&id).then_some(id),
+                _ => None,
+            });
+            entries.push(AccountLedgerEntry { account_id: *account_id, entry, transfer_key });
+        }
+    }
+    // Stable, so each account keeps its ledger order; paired deposits go after withdrawals
+    entries.sort_by_key(|e| (e.entry.at, e.transfer_key.is_some() && e.entry.action == LedgerAction::TransferIn));
+    let (lots, disposals) = match_lots(&entries, method);
+    let now = Utc::now();
+
+    let lot_rows: Vec<tax_lots::ActiveModel> = lots
+        .iter()
+        .map(|lot| tax_lots::ActiveModel {
+            id: ActiveValue::Set(lot.id),
+            account_id: ActiveValue::Set(lot.account_id),
+            asset: ActiveValue::Set(lot.asset.clone()),
+            acquired_at: ActiveValue::Set(lot.acquired_at.into()),
+            quantity: ActiveValue::Set(lot.quantity),
+            remaining_quantity: ActiveValue::Set(lot.remaining_quantity),
+            cost_basis_usd: ActiveValue::Set(lot.cost_basis_usd),
+            created_at: ActiveValue::Set(now.into()),
+        })
+        .collect();
+    let disposal_rows: Vec<tax_disposals::ActiveModel> = disposals
+        .iter()
+        .map(|disposal| tax_disposals::ActiveModel {
+            id: ActiveValue::Set(Uuid::new_v4()),
+            account_id: ActiveValue::Set(disposal.account_id),
+            lot_id: ActiveValue::Set(disposal.lot_id),
+            asset: ActiveValue::Set(disposal.asset.clone()),
+            kind: ActiveValue::Set(disposal.kind.to_string()),
+            quantity: ActiveValue::Set(disposal.quantity),
+            proceeds_usd: ActiveValue::Set(disposal.proceeds_usd),
+            cost_basis_usd: ActiveValue::Set(disposal.cost_basis_usd),
+            acquired_at: ActiveValue::Set(disposal.acquired_at.map(Into::into)),
+            disposed_at: ActiveValue::Set(disposal.disposed_at.into()),
+            created_at: ActiveValue::Set(now.into()),
+        })
+        .collect();
+    let written = (lot_rows.len(), disposal_rows.len());
+
+    // Disposals go with their lots (cascade); those without a lot are deleted explicitly
+    let txn = db.begin().await?;
+    tax_disposals::Entity::delete_many()
+        .filter(tax_disposals::Column::AccountId.is_in(account_ids.to_vec()))
+        .exec(&txn)
+        .await?;
+    tax_lots::Entity::delete_many()
+        .filter(tax_lots::Column::AccountId.is_in(account_ids.to_vec()))
+        .exec(&txn)
+        .await?;
+    for chunk in lot_rows.chunks(INSERT_CHUNK) {
+        tax_lots::Entity::insert_many(chunk.to_vec()).exec_without_returning(&txn).await?;
+    }
+    for chunk in disposal_rows.chunks(INSERT_CHUNK) {
+        tax_disposals::Entity::insert_many(chunk.to_vec()).exec_without_returning(&txn).await?;
+    }
+    txn.commit().await?;
+
+    Ok(written)
+}
+
+/// Lot method a user configured, FIFO when unset or unrecognized
+pub fn user_lot_method(user: &users::Model) -> LotMethod {
+    LotMethod::parse(&user.tax_lot_method).unwrap_or(LotMethod::Fifo)
+}
+
+/// Rebuild the lots of every account of a user
+pub async fn rebuild_user_lots(
+    db: &DatabaseConnection,
+    user: &users::Model,
+) -> Result<TaxLotRebuildResult, Box<dyn Error + Send + Sync>> {
+    let method = user_lot_method(user);
+    let account_ids: Vec<Uuid> = accounts::Entity::find()
+        .filter(accounts::Column::UserId.eq(user.id))
+        .all(db)
+        .await?
+        .into_iter()
+        .map(|a| a.id)
+        .collect();
+
+    let (lots, disposals) = rebuild_lots(db, &account_ids, method).await?;
+    Ok(TaxLotRebuildResult { accounts: account_ids.len(), lots, disposals })
+}
+
+/// Rebuild the lots of every user's accounts with the user's method. A user whose rebuild
+/// fails is logged and skipped.
+pub async fn rebuild_all_lots(db: &DatabaseConnection) -> Result<TaxLotRebuildResult, Box<dyn Error + Send + Sync>> {
+    let methods: HashMap<Uuid, LotMethod> = users::Entity::find()
+        .all(db)
+        .await?
+        .iter()
+        .map(|user| (user.id, user_lot_method(user)))
+        .collect();
+    let mut accounts_by_user: HashMap<Uuid, Vec<Uuid>> = HashMap::new();
+    for account in accounts::Entity::find().all(db).await? {
+        accounts_by_user.entry(account.user_id).or_default().push(account.id);
+    }
+
+    let mut result = TaxLotRebuildResult::default();
+    for (user_id, account_ids) in accounts_by_user {
+        let method = methods.get(&user_id).copied().unwrap_or(LotMethod::Fifo);
+        match rebuild_lots(db, &account_ids, method).await {
+            Ok((lots, disposals)) => {
+                result.accounts += account_ids.len();
+                result.lots += lots;
+                result.disposals += disposals;
+            }
+            Err(e) => tracing::warn!("Failed to rebuild tax lots of user {}: {}", user_id, e),
+        }
+    }
+    tracing::info!(
+        "Tax lots rebuilt for {} accounts: {} lots, {} disposals",
+        result.accounts,
+        result.lots,
+        result.disposals
+    );
+    Ok(result)
+}
@@ -0,0 +1,110 @@
+use crate::connectors::{ExchangeConnector, Trade};
+use crate::entities::trades;
+use chrono::{DateTime, Utc};
+use rust_decimal::Decimal;
+use sea_orm::{
+    sea_query::OnConflict, ActiveValue, ColumnTrait, DatabaseConnection, EntityTrait, QueryFilter,
+    QueryOrder,
+};
+use std::error::Error;
+use std::str::FromStr;
+use uuid::Uuid;
+
+/// Parse a decimal string reported by a connector, ignoring unparsable values
+fn parse_decimal(value: &str) -> Option<Decimal> {
+    Decimal::from_str(value).or_else(|_| Decimal::from_scientific(value)).ok()
+}
+
+/// Build the rows stored for fetched trades, skipping trades with an unparsable quantity or price
+fn trade_rows(account_id: Uuid, trades: &[Trade]) -> Vec<trades::ActiveModel> {
+    trades
+        .iter()
+        .filter_map(|t| {
+            let (Some(quantity), Some(price)) = (parse_decimal(&t.quantity), parse_decimal(&t.price)) else {
+                tracing::warn!("Skipping trade {} with invalid quantity or price", t.trade_id);
+                return None;
+            };
+
+            Some(trades::ActiveModel {
+                id: ActiveValue::Set(Uuid::new_v4()),
+                account_id: ActiveValue::Set(account_id),
+                trade_id: ActiveValue::Set(t.trade_id.clone()),
+                order_id: ActiveValue::Set(t.order_id.clone()),
+                instrument: ActiveValue::Set(t.instrument.clone()),
+                instrument_type: ActiveValue::Set(t.instrument_type.clone()),
+                base_asset: ActiveValue::Set(t.base_asset.clone()),
+                quote_asset: ActiveValue::Set(t.quote_asset.clone()),
+                side: ActiveValue::Set(t.side.clone()),
+                quantity: ActiveValue::Set(quantity),
+                price: ActiveValue::Set(price),
+                fee: ActiveValue::Set(t.fee.as_deref().and_then(parse_decimal)),
+                fee_currency: ActiveValue::Set(t.fee_currency.clone()),
+                executed_at: ActiveValue::Set(t.executed_at.into()),
+                created_at: ActiveValue::NotSet,
+            })
+        })
+        .collect()
+}
+
+/// Fetch the account's new trades and append them to the `trades` table.
+///
+/// Fetching starts at the latest stored trade, so that trade is fetched again; the unique
+/// (account_id, trade_id) index makes re-inserting it a no-op. Returns the number of new trades.
+pub async fn sync_trades(
+    db: &DatabaseConnection,
+    account_id: Uuid,
+    connector: &dyn ExchangeConnector,
+) -> Result<u64, Box<dyn Error + Send + Sync>> {
+    let since: Option<DateTime<Utc>> = trades::Entity::find()
+        .filter(trades::Column::AccountId.eq(account_id))
+        .order_by_desc(trades::Column::ExecutedAt)
+        .one(db)
+        .await?
+        .map(|t| t.executed_at.with_timezone(&Utc));
+
+    let fetched = connector.fetch_trades(since).await?;
+    let rows = trade_rows(account_id, &fetched);
+    if rows.is_empty() {
+        return Ok(0);
+    }
+
+    let inserted = trades::Entity::insert_many(rows)
+        .on_conflict(
+            OnConflict::columns([trades::Column::AccountId, trades::Column::TradeId])
+                .do_nothing()
+                .to_owned(),
+        )
+        .exec_without_returning(db)
+        .await?;
+
+    Ok(inserted)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_trade_rows_parse_decimals() {
+        let trade = Trade {
+            trade_id: "BTC-USDT:1".to_string(),
+            order_id: None,
+            instrument: "BTC-USDT".to_string(),
+            instrument_type: "SPOT".to_string(),
+            base_asset: Some("BTC".to_string()),
+            quote_asset: Some("USDT".to_string()),
+            side: "buy".to_string(),
+            quantity: "0.5".to_string(),
+            price: "65000".to_string(),
+            fee: Some("-0.1".to_string()),
+            fee_currency: Some("USDT".to_string()),
+            executed_at: Utc::now(),
+        };
+        let invalid = Trade { price: String::new(), ..trade.clone() };
+
+        let rows = trade_rows(Uuid::new_v4(), &[trade, invalid]);
+        assert_eq!(rows.len(), 1);
+        assert_eq!(rows[0].quantity, ActiveValue::Set(Decimal::from_str("0.5").unwrap()));
+        assert_eq!(rows[0].fee, ActiveValue::Set(Some(Decimal::from_str("-0.1").unwrap())));
+    }
+}
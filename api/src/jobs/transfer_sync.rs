@@ -0,0 +1,224 @@
+use crate::connectors::{ExchangeConnector, Transfer, TRANSFER_DEPOSIT, TRANSFER_WITHDRAWAL};
+use crate::entities::transfers;
+use chrono::{DateTime, Duration, Utc};
+use rust_decimal::Decimal;
+use sea_orm::{
+    sea_query::OnConflict, ActiveValue, ColumnTrait, DatabaseConnection, EntityTrait, QueryFilter,
+    QueryOrder,
+};
+use std::collections::{BTreeMap, HashMap};
+use std::error::Error;
+use std::str::FromStr;
+use uuid::Uuid;
+
+/// How far before the latest stored transfer fetching resumes.
+///
+/// Transfers are timestamped when created but only stored once completed, so a slow
+/// withdrawal can complete after a newer transfer was already stored.
+pub const TRANSFER_LOOKBACK_DAYS: i64 = 7;
+
+/// Deposits and withdrawals of one asset
+#[derive(Debug, Clone, PartialEq)]
+pub struct NetContribution {
+    pub asset: String,
+    pub deposited: Decimal,
+    /// Withdrawn amount including withdrawal fees
+    pub withdrawn: Decimal,
+    /// `deposited - withdrawn`
+    pub net: Decimal,
+}
+
+/// Sum transfers per asset into net contributions, sorted by asset.
+///
+/// Withdrawal fees count as withdrawn since they also left the account.
+pub fn net_contributions(transfers: &[transfers::Model]) -> Vec<NetContribution> {
+    let mut by_asset: BTreeMap<&str, (Decimal, Decimal)> = BTreeMap::new();
+    for t in transfers {
+        let entry = by_asset.entry(t.asset.as_str()).or_default();
+        match t.direction.as_str() {
+            TRANSFER_DEPOSIT => entry.0 += t.amount,
+            TRANSFER_WITHDRAWAL => entry.1 += t.amount + t.fee.unwrap_or(Decimal::ZERO),
+            _ => {}
+        }
+    }
+
+    by_asset
+        .into_iter()
+        .map(|(asset, (deposited, withdrawn))| NetContribution {
+            asset: asset.to_string(),
+            deposited,
+            withdrawn,
+            net: deposited - withdrawn,
+        })
+        .collect()
+}
+
+/// How long after a withdrawal the deposit it became may arrive in another account
+pub const TRANSFER_PAIR_WINDOW_HOURS: i64 = 24;
+
+/// Pair withdrawals with the deposits they became in another of the given accounts.
+///
+/// A deposit pairs with a withdrawal of the same asset from another account when it arrives
+/// within [`TRANSFER_PAIR_WINDOW_HOURS`] after it, and has the same tx hash when both carry
+/// one or else the same amount. Withdrawals are paired oldest first, each with the earliest
+/// matching deposit not paired yet. Returns deposit id -> withdrawal id.
+pub fn pair_own_transfers(transfers: &[transfers::Model]) -> HashMap<Uuid, Uuid> {
+    let by_time = |direction: &str| {
+        let mut rows: Vec<&transfers::Model> = transfers.iter().filter(|t| t.direction == direction).collect();
+        rows.sort_by_key(|t| t.occurred_at);
+        rows
+    };
+    let deposits = by_time(TRANSFER_DEPOSIT);
+    let window = Duration::hours(TRANSFER_PAIR_WINDOW_HOURS);
+
+    let mut pairs = HashMap::new();
+    for withdrawal in by_time(TRANSFER_WITHDRAWAL) {
+        let deposit = deposits.iter().find(|deposit| {
+            deposit.account_id != withdrawal.account_id
+                && deposit.asset.eq_ignore_ascii_case(&withdrawal.asset)
+                && deposit.occurred_at >= withdrawal.occurred_at
+                && deposit.occurred_at <= withdrawal.occurred_at + window
+                && match (&deposit.tx_hash, &withdrawal.tx_hash) {
+                    (Some(deposit_hash), Some(withdrawal_hash)) => deposit_hash.eq_ignore_ascii_case(withdrawal_hash),
+                    _ => deposit.amount == withdrawal.amount,
+                }
+                && !pairs.contains_key(&deposit.id)
+        });
+        if let Some(deposit) = deposit {
+            pairs.insert(deposit.id, withdrawal.id);
+        }
+    }
+    pairs
+}
+
+/// Build the rows stored for fetched transfers, skipping transfers with an unparsable amount
+fn transfer_rows(account_id: Uuid, transfers: &[Transfer]) -> Vec<transfers::ActiveModel> {
+    transfers
+        .iter()
+        .filter_map(|t| {
+            let Ok(amount) = Decimal::from_str(&t.amount) else {
+                tracing::warn!("Skipping transfer {} with invalid amount", t.transfer_id);
+                return None;
+            };
+
+            Some(transfers::ActiveModel {
+                id: ActiveValue::Set(Uuid::new_v4()),
+                account_id: ActiveValue::Set(account_id),
+                transfer_id: ActiveValue::Set(t.transfer_id.clone()),
+                direction: ActiveValue::Set(t.direction.clone()),
+                asset: ActiveValue::Set(t.asset.clone()),
+                amount: ActiveValue::Set(amount),
+                fee: ActiveValue::Set(t.fee.as_deref().and_then(|f| Decimal::from_str(f).ok())),
+                network: ActiveValue::Set(t.network.clone()),
+                tx_hash: ActiveValue::Set(t.tx_hash.clone()),
+                occurred_at: ActiveValue::Set(t.occurred_at.into()),
+                created_at: ActiveValue::NotSet,
+            })
+        })
+        .collect()
+}
+
+/// Fetch the account's new deposits and withdrawals and append them to the `transfers` table.
+///
+/// Fetching resumes [`TRANSFER_LOOKBACK_DAYS`] before the latest stored transfer; the unique
+/// (account_id, transfer_id) index makes re-inserting known transfers a no-op.
+/// Returns the number of new transfers.
+pub async fn sync_transfers(
+    db: &DatabaseConnection,
+    account_id: Uuid,
+    connector: &dyn ExchangeConnector,
+) -> Result<u64, Box<dyn Error + Send + Sync>> {
+    let since: Option<DateTime<Utc>> = transfers::Entity::find()
+        .filter(transfers::Column::AccountId.eq(account_id))
+        .order_by_desc(transfers::Column::OccurredAt)
+        .one(db)
+        .await?
+        .map(|t| t.occurred_at.with_timezone(&Utc) - Duration::days(TRANSFER_LOOKBACK_DAYS));
+
+    let fetched = connector.fetch_transfers(since).await?;
+    let rows = transfer_rows(account_id, &fetched);
+    if rows.is_empty() {
+        return Ok(0);
+    }
+
+    let inserted = transfers::Entity::insert_many(rows)
+        .on_conflict(
+            OnConflict::columns([transfers::Column::AccountId, transfers::Column::TransferId])
+                .do_nothing()
+                .to_owned(),
+        )
+        .exec_without_returning(db)
+        .await?;
+
+    Ok(inserted)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn transfer(direction: &str, asset: &str, amount: i64, fee: Option<i64>) -> transfers::Model {
+        transfers::Model {
+            id: Uuid::new_v4(),
+            account_id: Uuid::nil(),
+            transfer_id: Uuid::new_v4().to_string(),
+            direction: direction.to_string(),
+            asset: asset.to_string(),
+            amount: Decimal::from(amount),
+            fee: fee.map(Decimal::from),
+            network: None,
+            tx_hash: None,
+            occurred_at: Utc::now().into(),
+            created_at: Utc::now().into(),
+        }
+    }
+
+    #[test]
+    fn test_net_contributions_count_withdrawal_fees() {
+        let contributions = net_contributions(&[
+            transfer(TRANSFER_DEPOSIT, "USDT", 1000, None),
+            transfer(TRANSFER_WITHDRAWAL, "USDT", 300, Some(1)),
+            transfer(TRANSFER_DEPOSIT, "BTC", 2, None),
+        ]);
+
+        assert_eq!(contributions.len(), 2);
+        assert_eq!(contributions[0].asset, "BTC");
+        assert_eq!(contributions[0].net, Decimal::from(2));
+        assert_eq!(contributions[1].deposited, Decimal::from(1000));
+        assert_eq!(contributions[1].withdrawn, Decimal::from(301));
+        assert_eq!(contributions[1].net, Decimal::from(699));
+    }
+
+    #[test]
+    fn test_pair_own_transfers() {
+        let (a, b) = (Uuid::new_v4(), Uuid::new_v4());
+        let at = |hours| (DateTime::<Utc>::UNIX_EPOCH + Duration::hours(hours)).into();
+        let moved = |direction, account_id, amount, hours, tx_hash: Option<&str>| transfers::Model {
+            account_id,
+            occurred_at: at(hours),
+            tx_hash: tx_hash.map(str::to_string),
+            ..transfer(direction, "BTC", amount, None)
+        };
+
+        let out = moved(TRANSFER_WITHDRAWAL, a, 2, 0, None);
+        let arrived = moved(TRANSFER_DEPOSIT, b, 2, 1, None);
+        // Same account, other amount, or too late: not the same coins
+        let own_deposit = moved(TRANSFER_DEPOSIT, a, 2, 1, None);
+        let other_amount = moved(TRANSFER_DEPOSIT, b, 3, 1, None);
+        let late = moved(TRANSFER_DEPOSIT, b, 2, 30, None);
+        // A matching tx hash pairs even when the network took part of the amount
+        let hashed_out = moved(TRANSFER_WITHDRAWAL, a, 5, 40, Some("0xABC"));
+        let hashed_in = moved(TRANSFER_DEPOSIT, b, 4, 41, Some("0xabc"));
+
+        let pairs = pair_own_transfers(&[
+            late.clone(),
+            own_deposit.clone(),
+            other_amount.clone(),
+            arrived.clone(),
+            out.clone(),
+            hashed_in.clone(),
+            hashed_out.clone(),
+        ]);
+        assert_eq!(pairs, HashMap::from([(arrived.id, out.id), (hashed_in.id, hashed_out.id)]));
+    }
+}
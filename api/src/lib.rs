@@ -11,6 +11,8 @@ pub mod entities;
 pub mod handlers;
 pub mod helpers;
 pub mod jobs;
+pub mod live_prices;
+pub mod maintenance;
 
 // Re-export migration for convenience
 pub use migration;
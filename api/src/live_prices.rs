@@ -0,0 +1,185 @@
+//! Realtime prices of held assets from the Binance public WebSocket stream
+//!
+//! When enabled, a background task subscribes to the mini-ticker stream of every asset held
+//! in any portfolio and keeps the latest price per asset in memory. The map is served by
+//! `GET /api/v1/prices/live`, and portfolio allocation prefers a fresh live price over the
+//! latest collected `asset_prices` row.
+//!
+//! # Configuration
+//!
+//! - `LIVE_PRICES_ENABLED` - run the stream (default: false)
+//! - `LIVE_PRICES_REFRESH_MINUTES` - how often the held asset list is reloaded and the
+//!   stream resubscribed (default: 10)
+//! - `LIVE_PRICES_MAX_AGE_SECONDS` - age after which a live price is no longer used by
+//!   allocation (default: 120)
+//!
+//! The map is held in process memory, so each API instance streams separately.
+
+use chrono::{DateTime, Duration, Utc};
+use rust_decimal::Decimal;
+use sea_orm::{ColumnTrait, DatabaseConnection, DbErr, EntityTrait, QueryFilter, QuerySelect};
+use std::collections::{BTreeSet, HashMap};
+use std::sync::{LazyLock, Mutex, RwLock};
+
+use crate::connectors::binance_stream::{self, StreamTick};
+use crate::domain::AccountHolding;
+use crate::entities::{accounts, portfolio_accounts};
+use crate::helpers::price_sanity::{check_price, PriceCheck, PriceSanityConfig};
+
+/// `source` reported for prices from the stream
+pub const LIVE_PRICE_SOURCE: &str = "binance_stream";
+
+/// Default resubscription interval in minutes
+const DEFAULT_REFRESH_MINUTES: u64 = 10;
+
+/// Default age in seconds after which allocation ignores a live price
+const DEFAULT_MAX_AGE_SECONDS: i64 = 120;
+
+/// Delay before reconnecting after a stream error
+const RECONNECT_DELAY: std::time::Duration = std::time::Duration::from_secs(5);
+
+/// Latest streamed price of an asset
+#[derive(Debug, Clone, PartialEq)]
+pub struct LivePrice {
+    /// Base asset symbol (e.g. "BTC")
+    pub asset: String,
+    pub price_usd: Decimal,
+    pub updated_at: DateTime<Utc>,
+}
+
+static PRICES: LazyLock<RwLock<HashMap<String, LivePrice>>> = LazyLock::new(|| RwLock::new(HashMap::new()));
+
+/// Last dropped tick price per asset, to accept a genuine jump on the next tick
+static REJECTED_TICKS: LazyLock<Mutex<HashMap<String, Decimal>>> = LazyLock::new(|| Mutex::new(HashMap::new()));
+
+static SANITY: LazyLock<PriceSanityConfig> = LazyLock::new(PriceSanityConfig::from_env);
+
+/// Whether `LIVE_PRICES_ENABLED` is set
+pub fn is_enabled() -> bool {
+    std::env::var("LIVE_PRICES_ENABLED")
+        .ok()
+        .and_then(|v| v.parse::<bool>().ok())
+        .unwrap_or(false)
+}
+
+fn max_age() -> Duration {
+    let seconds = std::env::var("LIVE_PRICES_MAX_AGE_SECONDS")
+        .ok()
+        .and_then(|v| v.parse::<i64>().ok())
+        .filter(|seconds| *seconds > 0)
+        .unwrap_or(DEFAULT_MAX_AGE_SECONDS);
+    Duration::seconds(seconds)
+}
+
+/// Drop a tick that deviates implausibly from the previous live price of the asset, unless it
+/// confirms the last dropped tick; dropped ticks are not quarantined, the next one replaces them
+fn record(tick: StreamTick) {
+    let mut prices = PRICES.write().unwrap_or_else(|e| e.into_inner());
+    if let Some(max_factor) = SANITY.max_factor {
+        let previous = prices.get(&tick.asset).map(|p| p.price_usd);
+        let mut rejected = REJECTED_TICKS.lock().unwrap_or_else(|e| e.into_inner());
+        if let PriceCheck::Rejected { .. } =
+            check_price(tick.price_usd, previous, rejected.get(&tick.asset).copied(), max_factor)
+        {
+            tracing::warn!("Dropped live price {} of {}: implausible jump", tick.price_usd, tick.asset);
+            rejected.insert(tick.asset, tick.price_usd);
+            return;
+        }
+        rejected.remove(&tick.asset);
+    }
+    prices.insert(
+        tick.asset.clone(),
+        LivePrice { asset: tick.asset, price_usd: tick.price_usd, updated_at: tick.event_time },
+    );
+}
+
+/// All live prices, sorted by asset
+pub fn all() -> Vec<LivePrice> {
+    let prices = PRICES.read().unwrap_or_else(|e| e.into_inner());
+    let mut all: Vec<LivePrice> = prices.values().cloned().collect();
+    all.sort_by(|a, b| a.asset.cmp(&b.asset));
+    all
+}
+
+/// Live price of `asset` when it is younger than `LIVE_PRICES_MAX_AGE_SECONDS`
+pub fn fresh_price(asset: &str) -> Option<LivePrice> {
+    let prices = PRICES.read().unwrap_or_else(|e| e.into_inner());
+    let price = prices.get(&asset.to_uppercase())?;
+    (Utc::now().signed_duration_since(price.updated_at) <= max_age()).then(|| price.clone())
+}
+
+/// Base symbol of a holding asset (e.g. "ETH-ethereum" → "ETH")
+fn base_symbol(asset: &str) -> Option<String> {
+    let base = asset.split('-').next()?.trim();
+    (!base.is_empty()).then(|| base.to_uppercase())
+}
+
+/// Base symbols of the assets held by accounts linked to any portfolio
+pub async fn held_assets(db: &DatabaseConnection) -> Result<Vec<String>, DbErr> {
+    let account_ids: Vec<uuid::Uuid> = portfolio_accounts::Entity::find()
+        .select_only()
+        .column(portfolio_accounts::Column::AccountId)
+        .distinct()
+        .into_tuple()
+        .all(db)
+        .await?;
+
+    let mut assets = BTreeSet::new();
+    for account in accounts::Entity::find()
+        .filter(accounts::Column::Id.is_in(account_ids))
+        .select_only()
+        .column(accounts::Column::Holdings)
+        .into_tuple::<Option<serde_json::Value>>()
+        .all(db)
+        .await?
+        .into_iter()
+        .flatten()
+    {
+        let holdings: Vec<AccountHolding> = serde_json::from_value(account).unwrap_or_default();
+        assets.extend(holdings.iter().filter_map(|h| base_symbol(&h.asset)));
+    }
+    Ok(assets.into_iter().collect())
+}
+
+/// Stream live prices until the process exits, resubscribing every
+/// `LIVE_PRICES_REFRESH_MINUTES` with the current held assets
+pub async fn run(db: DatabaseConnection) {
+    let refresh_minutes = std::env::var("LIVE_PRICES_REFRESH_MINUTES")
+        .ok()
+        .and_then(|v| v.parse::<u64>().ok())
+        .filter(|minutes| *minutes > 0)
+        .unwrap_or(DEFAULT_REFRESH_MINUTES);
+    let run_for = std::time::Duration::from_secs(refresh_minutes * 60);
+
+    // Both rustls crypto backends are compiled in, so the WebSocket TLS client needs a
+    // process default; an already installed one is kept
+    let _ = rustls::crypto::ring::default_provider().install_default();
+
+    loop {
+        let assets = match held_assets(&db).await {
+            Ok(assets) => assets,
+            Err(e) => {
+                tracing::warn!("Failed to load held assets for live prices: {}", e);
+                tokio::time::sleep(RECONNECT_DELAY).await;
+                continue;
+            }
+        };
+
+        if let Err(e) = binance_stream::stream_prices(&assets, run_for, record).await {
+            tracing::warn!("Live price stream failed, reconnecting: {}", e);
+            tokio::time::sleep(RECONNECT_DELAY).await;
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_base_symbol() {
+        assert_eq!(base_symbol("ETH-ethereum").as_deref(), Some("ETH"));
+        assert_eq!(base_symbol("btc").as_deref(), Some("BTC"));
+        assert!(base_symbol("").is_none());
+    }
+}
@@ -1,9 +1,10 @@
-use axum::{extract::Extension, response::Json, routing::get, Router};
+use axum::{extract::Extension, middleware, response::Json, routing::get, Router};
 use axum_keycloak_auth::{
     decode::KeycloakToken, instance::KeycloakAuthInstance, instance::KeycloakConfig,
     layer::KeycloakAuthLayer, PassthroughMode,
 };
-use crypto_pocket_butler_backend::{db::DbConfig, handlers, jobs};
+use chrono::Timelike;
+use crypto_pocket_butler_backend::{db::DbConfig, handlers, helpers, jobs, live_prices, maintenance};
 use serde::{Deserialize, Serialize};
 use std::{net::SocketAddr, sync::Arc};
 use tokio_cron_scheduler::{JobScheduler, Job};
@@ -62,24 +63,70 @@ struct HealthResponse {
         handlers::portfolios::add_account_to_portfolio,
         handlers::portfolios::remove_account_from_portfolio,
         handlers::portfolios::construct_portfolio_allocation,
+        handlers::portfolios::get_portfolio_allocation_by_category,
+        handlers::portfolios::list_construction_runs,
+        handlers::portfolios::get_construction_run,
         handlers::accounts::list_accounts_handler,
         handlers::accounts::get_account_handler,
         handlers::accounts::create_account_handler,
         handlers::accounts::update_account_handler,
         handlers::accounts::delete_account_handler,
         handlers::accounts::sync_account_handler,
+        handlers::accounts::get_sync_status_handler,
+        handlers::accounts::stream_sync_status_handler,
+        handlers::accounts::list_sync_runs_handler,
         handlers::accounts::sync_all_accounts_handler,
+        handlers::accounts::list_holding_transactions_handler,
+        handlers::accounts::list_positions_handler,
+        handlers::portfolios::list_portfolio_positions,
+        handlers::accounts::list_nfts_handler,
+        handlers::accounts::list_trades_handler,
+        handlers::accounts::list_transfers_handler,
+        handlers::accounts::import_statement_handler,
+        handlers::account_addresses::list_account_addresses_handler,
+        handlers::account_addresses::create_account_address_handler,
+        handlers::account_addresses::update_account_address_handler,
+        handlers::account_addresses::delete_account_address_handler,
+        handlers::account_snapshots::list_account_snapshots_handler,
         handlers::chains::list_supported_chains,
+        handlers::units::list_units_handler,
+        handlers::units::convert_units_handler,
         handlers::snapshots::create_portfolio_snapshot_handler,
         handlers::snapshots::create_all_user_snapshots_handler,
         handlers::snapshots::list_portfolio_snapshots_handler,
         handlers::snapshots::get_latest_portfolio_snapshot_handler,
+        handlers::snapshots::export_portfolio_snapshots_handler,
+        handlers::snapshots::get_latest_snapshots_handler,
+        handlers::income::get_portfolio_income_handler,
+        handlers::performance::get_portfolio_performance_handler,
+        handlers::pnl::get_portfolio_pnl_handler,
+        handlers::tax_lots::list_tax_lots_handler,
+        handlers::reports::get_tax_report_handler,
+        handlers::risk::get_portfolio_risk_handler,
+        handlers::drift::get_portfolio_drift_handler,
+        handlers::rebalance::create_rebalance_plan_handler,
+        handlers::rebalance::get_rebalance_plan_handler,
+        handlers::rebalance::export_rebalance_orders_handler,
+        handlers::backtest::backtest_portfolio_handler,
+        handlers::purchases::get_portfolio_purchases_handler,
+        handlers::fees::get_portfolio_fees_handler,
+        handlers::value_history::get_portfolio_value_history_handler,
+        handlers::attribution::get_portfolio_attribution_handler,
         handlers::recommendations::list_portfolio_recommendations,
         handlers::recommendations::get_recommendation,
         handlers::recommendations::create_recommendation,
-        handlers::recommendations::generate_mock_recommendations,
+        handlers::recommendations::generate_recommendations_handler,
         handlers::migrations::migrate_handler,
+        handlers::anomalies::list_anomalies_handler,
+        handlers::anomalies::acknowledge_anomaly_handler,
+        handlers::notifications::list_notifications_handler,
+        handlers::notifications::mark_notification_read_handler,
         handlers::jobs::fetch_all_coins_handler,
+        handlers::jobs::backfill_holdings_handler,
+        handlers::jobs::backfill_price_history_handler,
+        handlers::jobs::backfill_snapshots_handler,
+        handlers::jobs::delete_snapshots_handler,
+        handlers::jobs::rebuild_snapshots_handler,
         handlers::evm_tokens::list_evm_tokens_handler,
         handlers::evm_tokens::get_evm_token_handler,
         handlers::evm_tokens::create_evm_token_handler,
@@ -92,11 +139,56 @@ struct HealthResponse {
         handlers::evm_chains::create_evm_chain_handler,
         handlers::evm_chains::update_evm_chain_handler,
         handlers::evm_chains::delete_evm_chain_handler,
+        handlers::cosmos_chains::list_cosmos_chains_handler,
+        handlers::cosmos_chains::get_cosmos_chain_handler,
+        handlers::cosmos_chains::create_cosmos_chain_handler,
+        handlers::cosmos_chains::update_cosmos_chain_handler,
+        handlers::cosmos_chains::delete_cosmos_chain_handler,
         handlers::solana_tokens::list_solana_tokens_handler,
         handlers::solana_tokens::get_solana_token_handler,
         handlers::solana_tokens::create_solana_token_handler,
         handlers::solana_tokens::update_solana_token_handler,
         handlers::solana_tokens::delete_solana_token_handler,
+        handlers::spam_tokens::list_spam_tokens_handler,
+        handlers::spam_tokens::get_spam_token_handler,
+        handlers::spam_tokens::create_spam_token_handler,
+        handlers::spam_tokens::update_spam_token_handler,
+        handlers::spam_tokens::delete_spam_token_handler,
+        handlers::curve_pools::list_curve_pools_handler,
+        handlers::curve_pools::get_curve_pool_handler,
+        handlers::curve_pools::create_curve_pool_handler,
+        handlers::curve_pools::update_curve_pool_handler,
+        handlers::curve_pools::delete_curve_pool_handler,
+        handlers::yield_vaults::list_yield_vaults_handler,
+        handlers::yield_vaults::get_yield_vault_handler,
+        handlers::yield_vaults::create_yield_vault_handler,
+        handlers::yield_vaults::update_yield_vault_handler,
+        handlers::yield_vaults::delete_yield_vault_handler,
+        handlers::derivative_assets::list_derivative_assets_handler,
+        handlers::derivative_assets::get_derivative_asset_handler,
+        handlers::derivative_assets::create_derivative_asset_handler,
+        handlers::derivative_assets::update_derivative_asset_handler,
+        handlers::derivative_assets::delete_derivative_asset_handler,
+        handlers::asset_categories::list_asset_categories_handler,
+        handlers::asset_categories::set_asset_category_handler,
+        handlers::asset_price_overrides::list_asset_price_overrides_handler,
+        handlers::asset_price_overrides::get_asset_price_override_handler,
+        handlers::asset_price_overrides::create_asset_price_override_handler,
+        handlers::asset_price_overrides::update_asset_price_override_handler,
+        handlers::asset_price_overrides::delete_asset_price_override_handler,
+        handlers::rejected_prices::list_rejected_prices_handler,
+        handlers::rejected_prices::review_rejected_price_handler,
+        handlers::status::get_status_handler,
+        handlers::maintenance::get_maintenance_handler,
+        handlers::maintenance::update_maintenance_handler,
+        handlers::account_transfers::transfer_account_handler,
+        handlers::asset_prices::list_asset_prices_handler,
+        handlers::asset_prices::get_asset_candles_handler,
+        handlers::prices::get_stored_prices_handler,
+        handlers::prices::get_live_prices_handler,
+        handlers::preferences::get_preferences_handler,
+        handlers::preferences::update_preferences_handler,
+        handlers::holdings::search_holdings_handler,
     ),
     components(
         schemas(
@@ -110,28 +202,165 @@ struct HealthResponse {
             handlers::portfolios::PortfolioAccountResponse,
             handlers::portfolios::AccountInPortfolioResponse,
             handlers::portfolios::AllocationHolding,
+            handlers::portfolios::AssetExposure,
+            handlers::portfolios::CategoryExposure,
+            handlers::portfolios::ChainExposure,
+            handlers::portfolios::VenueExposure,
+            handlers::portfolios::StablecoinExposure,
+            handlers::portfolios::ConcentrationRisk,
+            handlers::portfolios::ConcentrationLevel,
+            handlers::portfolios::GuardrailStatus,
+            handlers::portfolios::GuardrailViolation,
+            handlers::portfolios::Severity,
+            handlers::portfolios::ProposedOrder,
+            handlers::portfolios::CategoryAllocationResponse,
+            handlers::portfolios::MaturityBucket,
+            handlers::portfolios::PriceStalenessWarning,
             handlers::portfolios::ConstructAllocationResponse,
+            handlers::portfolios::ListConstructionRunsQuery,
+            handlers::portfolios::ConstructionRunResponse,
+            handlers::portfolios::ListConstructionRunsResponse,
+            handlers::portfolios::ConstructionRunDetailResponse,
+            jobs::construction_runs::ConstructionInputs,
+            jobs::construction_runs::PriceSource,
             handlers::accounts::CreateAccountRequest,
             handlers::accounts::UpdateAccountRequest,
             handlers::accounts::AccountResponse,
+            handlers::accounts::MultisigDeploymentResponse,
             handlers::accounts::SyncAccountRequest,
             handlers::accounts::SyncResultResponse,
             handlers::accounts::SyncInitiatedResponse,
+            handlers::accounts::SyncJobResponse,
+            handlers::accounts::ListSyncRunsQuery,
+            handlers::accounts::SyncRunChainResponse,
+            handlers::accounts::SyncRunResponse,
+            handlers::accounts::ListSyncRunsResponse,
             handlers::accounts::SyncAllInitiatedResponse,
+            handlers::accounts::HoldingTransactionResponse,
+            handlers::accounts::ListHoldingTransactionsQuery,
+            handlers::accounts::ListHoldingTransactionsResponse,
+            handlers::accounts::PositionResponse,
+            handlers::accounts::ListPositionsResponse,
+            handlers::portfolios::PortfolioPositionResponse,
+            handlers::portfolios::PortfolioPositionsResponse,
+            handlers::accounts::NftHoldingResponse,
+            handlers::accounts::ListNftsResponse,
+            handlers::accounts::ListTradesQuery,
+            handlers::accounts::TradeResponse,
+            handlers::accounts::ListTradesResponse,
+            handlers::accounts::ListTransfersQuery,
+            handlers::accounts::TransferResponse,
+            handlers::accounts::NetContributionResponse,
+            handlers::accounts::ListTransfersResponse,
+            handlers::accounts::ImportStatementRequest,
+            handlers::accounts::ImportStatementResponse,
+            handlers::account_addresses::AccountAddressResponse,
+            handlers::account_addresses::CreateAccountAddressRequest,
+            handlers::account_addresses::UpdateAccountAddressRequest,
+            handlers::account_snapshots::ListAccountSnapshotsQuery,
+            handlers::account_snapshots::AccountSnapshotResponse,
+            handlers::account_snapshots::ListAccountSnapshotsResponse,
+            helpers::csv_import::CsvColumnMapping,
             handlers::chains::ChainInfo,
             handlers::chains::ListChainsResponse,
+            handlers::units::UnitResponse,
+            handlers::units::ListUnitsResponse,
+            handlers::units::ConvertUnitsResponse,
             handlers::snapshots::CreateSnapshotRequest,
             handlers::snapshots::SnapshotResultResponse,
             handlers::snapshots::CreateAllSnapshotsResponse,
             handlers::snapshots::SnapshotResponse,
+            handlers::snapshots::LatestSnapshotResponse,
+            handlers::snapshots::LatestSnapshotSummary,
+            handlers::snapshots::PortfolioLatestSnapshot,
+            handlers::snapshots::LatestSnapshotsResponse,
+            handlers::snapshots::ExportSnapshotsQuery,
+            handlers::snapshots::SnapshotExportRow,
+            helpers::value_deltas::ValueDeltas,
+            helpers::value_deltas::ValueDelta,
+            helpers::price_resolution::PriceResolution,
             handlers::snapshots::ListSnapshotsQuery,
             handlers::snapshots::ListSnapshotsResponse,
+            handlers::income::IncomeReportQuery,
+            handlers::income::IncomeEventResponse,
+            handlers::income::IncomeTotal,
+            handlers::income::IncomeReportResponse,
+            handlers::performance::PerformanceQuery,
+            handlers::performance::PerformancePoint,
+            handlers::performance::DrawdownResponse,
+            handlers::performance::PerformanceResponse,
+            handlers::pnl::PnlQuery,
+            handlers::pnl::AssetPnlResponse,
+            handlers::pnl::PnlResponse,
+            handlers::tax_lots::TaxLotsQuery,
+            handlers::tax_lots::TaxLotResponse,
+            handlers::tax_lots::TaxLotsResponse,
+            handlers::reports::TaxReportQuery,
+            handlers::reports::TaxReportResponse,
+            helpers::tax_report::TaxReportRow,
+            handlers::risk::RiskQuery,
+            handlers::risk::RiskMetricsResponse,
+            handlers::risk::AssetRiskResponse,
+            handlers::risk::RiskResponse,
+            handlers::risk::ValueAtRiskResponse,
+            handlers::risk::VarEstimateResponse,
+            handlers::drift::DriftResponse,
+            handlers::drift::AssetDrift,
+            handlers::rebalance::RebalancePlanRequest,
+            handlers::rebalance::RebalancePlanResponse,
+            handlers::rebalance::PlannedTrade,
+            handlers::rebalance::TradeSide,
+            handlers::rebalance::OrderDraftsQuery,
+            handlers::rebalance::OrderDraftsResponse,
+            handlers::rebalance::OrderDraft,
+            handlers::rebalance::OkxOrderRequest,
+            handlers::rebalance::SkippedOrder,
+            handlers::backtest::BacktestRequest,
+            handlers::backtest::BacktestResponse,
+            handlers::backtest::BacktestPoint,
+            handlers::backtest::CurveStats,
+            handlers::backtest::RebalanceFrequency,
+            handlers::purchases::PurchasesQuery,
+            handlers::purchases::PurchasesResponse,
+            handlers::purchases::AssetPurchasesResponse,
+            handlers::fees::FeeReportQuery,
+            handlers::fees::FeeReportResponse,
+            handlers::fees::FeePeriod,
+            handlers::fees::AssetFees,
+            handlers::fees::PeriodFees,
+            handlers::value_history::ValueHistoryQuery,
+            handlers::value_history::ValueHistoryResponse,
+            handlers::value_history::ValueGranularity,
+            handlers::value_history::GapFill,
+            handlers::value_history::ValuePoint,
+            handlers::attribution::AttributionQuery,
+            handlers::attribution::AttributionResponse,
+            handlers::attribution::AssetAttribution,
             handlers::recommendations::RecommendationResponse,
             handlers::recommendations::ListRecommendationsResponse,
             handlers::recommendations::ListRecommendationsQuery,
             handlers::recommendations::CreateRecommendationRequest,
+            handlers::recommendations::GenerateRecommendationsResponse,
             handlers::migrations::MigrationResponse,
+            handlers::anomalies::AnomalyResponse,
+            handlers::anomalies::ListAnomaliesQuery,
+            handlers::anomalies::ListAnomaliesResponse,
+            handlers::notifications::NotificationResponse,
+            handlers::notifications::ListNotificationsQuery,
+            handlers::notifications::ListNotificationsResponse,
             handlers::jobs::FetchAllCoinsResponse,
+            handlers::jobs::BackfillHoldingsQuery,
+            handlers::jobs::BackfillAccountResponse,
+            handlers::jobs::BackfillHoldingsResponse,
+            handlers::jobs::BackfillPriceHistoryRequest,
+            handlers::jobs::BackfillPriceHistoryResponse,
+            handlers::jobs::BackfillSnapshotsRequest,
+            handlers::jobs::BackfillPortfolioSnapshotsResponse,
+            handlers::jobs::BackfillSnapshotsResponse,
+            handlers::jobs::DeleteSnapshotsRequest,
+            handlers::jobs::DeleteSnapshotsResponse,
+            handlers::jobs::RebuildSnapshotsRequest,
+            handlers::jobs::RebuildSnapshotsResponse,
             handlers::evm_tokens::EvmTokenResponse,
             handlers::evm_tokens::CreateEvmTokenRequest,
             handlers::evm_tokens::UpdateEvmTokenRequest,
@@ -141,9 +370,62 @@ struct HealthResponse {
             handlers::evm_chains::EvmChainResponse,
             handlers::evm_chains::CreateEvmChainRequest,
             handlers::evm_chains::UpdateEvmChainRequest,
+            handlers::cosmos_chains::CosmosChainResponse,
+            handlers::cosmos_chains::CreateCosmosChainRequest,
+            handlers::cosmos_chains::UpdateCosmosChainRequest,
             handlers::solana_tokens::SolanaTokenResponse,
             handlers::solana_tokens::CreateSolanaTokenRequest,
             handlers::solana_tokens::UpdateSolanaTokenRequest,
+            handlers::spam_tokens::SpamTokenResponse,
+            handlers::spam_tokens::CreateSpamTokenRequest,
+            handlers::spam_tokens::UpdateSpamTokenRequest,
+            handlers::curve_pools::CurvePoolResponse,
+            handlers::curve_pools::CreateCurvePoolRequest,
+            handlers::curve_pools::UpdateCurvePoolRequest,
+            handlers::yield_vaults::YieldVaultResponse,
+            handlers::yield_vaults::CreateYieldVaultRequest,
+            handlers::yield_vaults::UpdateYieldVaultRequest,
+            handlers::derivative_assets::DerivativeAssetResponse,
+            handlers::derivative_assets::CreateDerivativeAssetRequest,
+            handlers::derivative_assets::UpdateDerivativeAssetRequest,
+            handlers::asset_categories::ListAssetCategoriesQuery,
+            handlers::asset_categories::AssetCategoryResponse,
+            handlers::asset_categories::SetAssetCategoryRequest,
+            handlers::asset_price_overrides::AssetPriceOverrideResponse,
+            handlers::asset_price_overrides::CreateAssetPriceOverrideRequest,
+            handlers::asset_price_overrides::UpdateAssetPriceOverrideRequest,
+            handlers::rejected_prices::RejectedPriceResponse,
+            handlers::rejected_prices::ListRejectedPricesQuery,
+            handlers::rejected_prices::ListRejectedPricesResponse,
+            handlers::rejected_prices::ReviewRejectedPriceRequest,
+            handlers::status::StatusResponse,
+            handlers::status::JobStatus,
+            handlers::status::PriceDataStatus,
+            handlers::status::DependencyStatus,
+            handlers::maintenance::UpdateMaintenanceRequest,
+            handlers::maintenance::MaintenanceResponse,
+            handlers::account_transfers::TransferAccountRequest,
+            handlers::account_transfers::TransferAccountResponse,
+            handlers::asset_prices::AssetPriceResponse,
+            handlers::asset_prices::ListAssetPricesQuery,
+            handlers::asset_prices::ListAssetPricesResponse,
+            handlers::asset_prices::AssetCandlesQuery,
+            handlers::asset_prices::CandleResponse,
+            handlers::asset_prices::AssetCandlesResponse,
+            handlers::prices::StoredPricesQuery,
+            handlers::prices::StoredPriceResponse,
+            handlers::prices::StoredPricesResponse,
+            handlers::prices::LivePricesQuery,
+            handlers::prices::LivePriceResponse,
+            handlers::prices::LivePricesResponse,
+            handlers::preferences::PreferencesResponse,
+            handlers::preferences::UpdatePreferencesRequest,
+            handlers::preferences::NotificationChannels,
+            helpers::fx::CurrencyInfo,
+            handlers::holdings::SearchHoldingsQuery,
+            handlers::holdings::AssetAccountHolding,
+            handlers::holdings::AssetPortfolioHolding,
+            handlers::holdings::SearchHoldingsResponse,
             handlers::error::ErrorResponse,
         )
     ),
@@ -153,12 +435,31 @@ struct HealthResponse {
         (name = "portfolios", description = "Portfolio management endpoints"),
         (name = "accounts", description = "Account management and sync endpoints"),
         (name = "chains", description = "Supported blockchain chains endpoints"),
+        (name = "units", description = "Conversion between base units and human units (sats, gwei, lamports)"),
         (name = "snapshots", description = "Portfolio snapshot endpoints"),
         (name = "recommendations", description = "Portfolio recommendation endpoints"),
         (name = "migrations", description = "Database migration endpoints"),
+        (name = "anomalies", description = "Suspicious holdings changes detected during sync; unacknowledged anomalies hold automatic snapshots"),
+        (name = "notifications", description = "User notifications"),
+        (name = "holdings", description = "Cross-account holdings lookups"),
+        (name = "assets", description = "Asset reference data and price history"),
+        (name = "prices", description = "Live prices streamed from exchange WebSocket feeds"),
+        (name = "preferences", description = "User preferences such as the base currency"),
+        (name = "reports", description = "Exportable reports such as realized gains for tax filing"),
+        (name = "status", description = "Public system status (job runs, price freshness, dependency health)"),
+        (name = "maintenance", description = "Maintenance mode – pauses scheduled jobs and rejects user writes with 503 (admin only)"),
+        (name = "admin", description = "Administrative operations on user data, recorded in the audit log (admin only)"),
         (name = "evm-tokens", description = "EVM token registry – configurable list of ERC-20 tokens checked during wallet sync"),
         (name = "evm-chains", description = "EVM chain registry – configurable list of EVM chains with RPC URLs"),
+        (name = "cosmos-chains", description = "Cosmos chain registry – configurable list of Cosmos-SDK chains with LCD URLs"),
         (name = "solana-tokens", description = "Solana token registry – configurable list of SPL tokens checked during wallet sync"),
+        (name = "spam-tokens", description = "Spam token blocklist – tokens dropped from wallet holdings during sync"),
+        (name = "curve-pools", description = "Curve pools whose LP positions are resolved into the pool's coins"),
+        (name = "yield-vaults", description = "Yield vaults whose shares are resolved into the vault's underlying token"),
+        (name = "derivative-assets", description = "Wrapped and liquid staking tokens counted towards their underlying asset in allocations"),
+        (name = "asset-categories", description = "Asset categories (L1, DeFi, stablecoin, ...) used to break allocations down by category"),
+        (name = "asset-price-overrides", description = "Per-asset pinned price sources, including DEX prices by contract"),
+        (name = "rejected-prices", description = "Prices quarantined by the ingestion sanity check, for review"),
     ),
     info(
         title = "Crypto Pocket Butler API",
@@ -312,6 +613,18 @@ async fn main() {
         .expect("Failed to connect to database");
     tracing::info!("Database connection pool established");
 
+    // Queued syncs do not survive a restart; their jobs would otherwise stay "running"
+    match jobs::sync_queue::fail_interrupted_sync_jobs(&db).await {
+        Ok(count) if count > 0 => tracing::warn!("Marked {} interrupted sync jobs as failed", count),
+        Ok(_) => {}
+        Err(e) => tracing::error!("Failed to clean up interrupted sync jobs: {}", e),
+    }
+
+    // Maintenance mode starts from MAINTENANCE_MODE and can be toggled via the admin API
+    if maintenance::is_enabled() {
+        tracing::warn!("Starting in maintenance mode: scheduled jobs are paused and user writes return 503");
+    }
+
     // Initialize job scheduler
     tracing::info!("Initializing job scheduler...");
     let scheduler = JobScheduler::new().await.expect("Failed to create job scheduler");
@@ -335,9 +648,22 @@ async fn main() {
         let job = Job::new_async(fetch_all_coins_schedule.as_str(), move |_job_id, _scheduler| {
             let db = db_clone.clone();
             Box::pin(async move {
+                if maintenance::is_enabled() {
+                    tracing::info!("Maintenance mode is on; skipping scheduled fetch all coins job");
+                    return;
+                }
                 tracing::info!("Running scheduled fetch all coins job");
-                match jobs::fetch_all_coins::fetch_all_coins(&db).await {
+                if let Err(e) = jobs::job_runs::record_job_started(&db, jobs::job_runs::JOB_FETCH_ALL_COINS).await {
+                    tracing::warn!("Failed to record fetch all coins job start: {}", e);
+                }
+                let run_error = match jobs::fetch_all_coins::fetch_all_coins(&db).await {
                     Ok(result) => {
+                        // Assets pinned to DEX prices are priced alongside the provider's coins
+                        match helpers::price_overrides::refresh_dex_prices(&db).await {
+                            Ok(stored) if stored > 0 => tracing::info!("Stored {} DEX prices of pinned assets", stored),
+                            Ok(_) => {}
+                            Err(e) => tracing::warn!("Failed to refresh DEX prices of pinned assets: {}", e),
+                        }
                         if result.success {
                             tracing::info!(
                                 "Fetch all coins job completed successfully: {} coins fetched, {} assets created, {} updated, {} prices stored",
@@ -346,16 +672,20 @@ async fn main() {
                                 result.assets_updated,
                                 result.prices_stored
                             );
+                            None
                         } else {
-                            tracing::error!(
-                                "Fetch all coins job failed: {}",
-                                result.error.unwrap_or_else(|| "Unknown error".to_string())
-                            );
+                            let error = result.error.unwrap_or_else(|| "Unknown error".to_string());
+                            tracing::error!("Fetch all coins job failed: {}", error);
+                            Some(error)
                         }
                     }
                     Err(e) => {
                         tracing::error!("Fetch all coins job failed with error: {}", e);
+                        Some(e.to_string())
                     }
+                };
+                if let Err(e) = jobs::job_runs::record_job_finished(&db, jobs::job_runs::JOB_FETCH_ALL_COINS, run_error).await {
+                    tracing::warn!("Failed to record fetch all coins job result: {}", e);
                 }
             })
         })
@@ -386,8 +716,15 @@ async fn main() {
         let job = Job::new_async(eod_snapshot_schedule.as_str(), move |_job_id, _scheduler| {
             let db = db_clone.clone();
             Box::pin(async move {
+                if maintenance::is_enabled() {
+                    tracing::info!("Maintenance mode is on; skipping scheduled EOD snapshot job");
+                    return;
+                }
                 tracing::info!("Running scheduled EOD snapshot job");
-                match jobs::portfolio_snapshot::create_all_portfolio_snapshots(&db, None).await {
+                if let Err(e) = jobs::job_runs::record_job_started(&db, jobs::job_runs::JOB_EOD_SNAPSHOT).await {
+                    tracing::warn!("Failed to record EOD snapshot job start: {}", e);
+                }
+                let run_error = match jobs::portfolio_snapshot::create_all_portfolio_snapshots(&db, None).await {
                     Ok(results) => {
                         let successful = results.iter().filter(|r| r.success).count();
                         let failed = results.iter().filter(|r| !r.success).count();
@@ -409,10 +746,20 @@ async fn main() {
                                 );
                             }
                         }
+                        None
                     }
                     Err(e) => {
                         tracing::error!("EOD snapshot job failed with error: {}", e);
+                        Some(e.to_string())
                     }
+                };
+                // Per-account snapshots alongside the portfolio ones
+                match jobs::account_snapshot::create_all_account_snapshots(&db, None).await {
+                    Ok(created) => tracing::info!("EOD account snapshots completed: {} accounts", created),
+                    Err(e) => tracing::error!("EOD account snapshots failed with error: {}", e),
+                }
+                if let Err(e) = jobs::job_runs::record_job_finished(&db, jobs::job_runs::JOB_EOD_SNAPSHOT, run_error).await {
+                    tracing::warn!("Failed to record EOD snapshot job result: {}", e);
                 }
             })
         })
@@ -424,10 +771,483 @@ async fn main() {
         tracing::info!("EOD snapshot job is disabled");
     }
 
+    // Configure intraday snapshot job; each portfolio's snapshot_cadence decides if it is due
+    let intraday_snapshot_enabled = std::env::var("INTRADAY_SNAPSHOT_ENABLED")
+        .unwrap_or_else(|_| "true".to_string())
+        .parse::<bool>()
+        .unwrap_or(true);
+
+    if intraday_snapshot_enabled {
+        let intraday_snapshot_schedule = std::env::var("INTRADAY_SNAPSHOT_SCHEDULE")
+            .unwrap_or_else(|_| "0 0 * * * *".to_string()); // Default: every hour on the hour
+
+        tracing::info!(
+            "Scheduling intraday snapshot job: schedule='{}'",
+            intraday_snapshot_schedule
+        );
+
+        let db_clone = db.clone();
+        let job = Job::new_async(intraday_snapshot_schedule.as_str(), move |_job_id, _scheduler| {
+            let db = db_clone.clone();
+            Box::pin(async move {
+                if maintenance::is_enabled() {
+                    tracing::info!("Maintenance mode is on; skipping scheduled intraday snapshot job");
+                    return;
+                }
+                tracing::info!("Running scheduled intraday snapshot job");
+                if let Err(e) = jobs::job_runs::record_job_started(&db, jobs::job_runs::JOB_INTRADAY_SNAPSHOT).await {
+                    tracing::warn!("Failed to record intraday snapshot job start: {}", e);
+                }
+                let hour = chrono::Utc::now().hour();
+                let run_error = match jobs::portfolio_snapshot::create_intraday_snapshots(&db, hour).await {
+                    Ok(results) => {
+                        tracing::info!(
+                            "Intraday snapshot job completed: {} portfolios processed, {} successful",
+                            results.len(),
+                            results.iter().filter(|r| r.success).count()
+                        );
+                        None
+                    }
+                    Err(e) => {
+                        tracing::error!("Intraday snapshot job failed with error: {}", e);
+                        Some(e.to_string())
+                    }
+                };
+                if let Err(e) = jobs::job_runs::record_job_finished(&db, jobs::job_runs::JOB_INTRADAY_SNAPSHOT, run_error).await {
+                    tracing::warn!("Failed to record intraday snapshot job result: {}", e);
+                }
+            })
+        })
+        .expect("Failed to create intraday snapshot job");
+
+        scheduler.add(job).await.expect("Failed to add intraday snapshot job to scheduler");
+        tracing::info!("Intraday snapshot job scheduled successfully");
+    } else {
+        tracing::info!("Intraday snapshot job is disabled");
+    }
+
+    // Configure wallet name resolution job
+    let name_resolution_enabled = std::env::var("NAME_RESOLUTION_ENABLED")
+        .unwrap_or_else(|_| "true".to_string())
+        .parse::<bool>()
+        .unwrap_or(true);
+
+    if name_resolution_enabled {
+        let name_resolution_schedule = std::env::var("NAME_RESOLUTION_SCHEDULE")
+            .unwrap_or_else(|_| "0 30 2 * * *".to_string()); // Default: daily at 02:30 UTC
+
+        tracing::info!(
+            "Scheduling name resolution job: schedule='{}'",
+            name_resolution_schedule
+        );
+
+        let db_clone = db.clone();
+        let job = Job::new_async(name_resolution_schedule.as_str(), move |_job_id, _scheduler| {
+            let db = db_clone.clone();
+            Box::pin(async move {
+                if maintenance::is_enabled() {
+                    tracing::info!("Maintenance mode is on; skipping scheduled name resolution job");
+                    return;
+                }
+                tracing::info!("Running scheduled name resolution job");
+                if let Err(e) = jobs::job_runs::record_job_started(&db, jobs::job_runs::JOB_NAME_RESOLUTION).await {
+                    tracing::warn!("Failed to record name resolution job start: {}", e);
+                }
+                let run_error = match jobs::name_resolution::refresh_account_names(&db).await {
+                    Ok(result) => {
+                        tracing::info!(
+                            "Name resolution job completed: {} accounts checked, {} addresses updated, {} failed",
+                            result.accounts_checked,
+                            result.addresses_updated,
+                            result.failed
+                        );
+                        None
+                    }
+                    Err(e) => {
+                        tracing::error!("Name resolution job failed with error: {}", e);
+                        Some(e.to_string())
+                    }
+                };
+                if let Err(e) = jobs::job_runs::record_job_finished(&db, jobs::job_runs::JOB_NAME_RESOLUTION, run_error).await {
+                    tracing::warn!("Failed to record name resolution job result: {}", e);
+                }
+            })
+        })
+        .expect("Failed to create name resolution job");
+
+        scheduler.add(job).await.expect("Failed to add name resolution job to scheduler");
+        tracing::info!("Name resolution job scheduled successfully");
+    } else {
+        tracing::info!("Name resolution job is disabled");
+    }
+
+    // Configure fiat FX rate collection job
+    let fx_rates_enabled = std::env::var("FX_RATES_ENABLED")
+        .unwrap_or_else(|_| "true".to_string())
+        .parse::<bool>()
+        .unwrap_or(true);
+
+    if fx_rates_enabled {
+        let fx_rates_schedule = std::env::var("FX_RATES_SCHEDULE")
+            .unwrap_or_else(|_| "0 0 17 * * *".to_string()); // Default: daily at 17:00 UTC, after the ECB publication
+
+        tracing::info!("Scheduling FX rates job: schedule='{}'", fx_rates_schedule);
+
+        let db_clone = db.clone();
+        let job = Job::new_async(fx_rates_schedule.as_str(), move |_job_id, _scheduler| {
+            let db = db_clone.clone();
+            Box::pin(async move {
+                if maintenance::is_enabled() {
+                    tracing::info!("Maintenance mode is on; skipping scheduled FX rates job");
+                    return;
+                }
+                tracing::info!("Running scheduled FX rates job");
+                if let Err(e) = jobs::job_runs::record_job_started(&db, jobs::job_runs::JOB_FX_RATES).await {
+                    tracing::warn!("Failed to record FX rates job start: {}", e);
+                }
+                let run_error = match jobs::fx_rates::collect_fx_rates(&db).await {
+                    Ok(result) => {
+                        tracing::info!(
+                            "FX rates job completed: {} currencies from {} for {}",
+                            result.currencies,
+                            result.source,
+                            result.rate_date
+                        );
+                        None
+                    }
+                    Err(e) => {
+                        tracing::error!("FX rates job failed with error: {}", e);
+                        Some(e.to_string())
+                    }
+                };
+                if let Err(e) = jobs::job_runs::record_job_finished(&db, jobs::job_runs::JOB_FX_RATES, run_error).await {
+                    tracing::warn!("Failed to record FX rates job result: {}", e);
+                }
+            })
+        })
+        .expect("Failed to create FX rates job");
+
+        scheduler.add(job).await.expect("Failed to add FX rates job to scheduler");
+        tracing::info!("FX rates job scheduled successfully");
+    } else {
+        tracing::info!("FX rates job is disabled");
+    }
+
+    // Configure Uniswap v3 TWAP pricing of long-tail tokens
+    let dex_twap_enabled = std::env::var("DEX_TWAP_ENABLED")
+        .unwrap_or_else(|_| "false".to_string())
+        .parse::<bool>()
+        .unwrap_or(false);
+
+    if dex_twap_enabled {
+        let dex_twap_schedule = std::env::var("DEX_TWAP_SCHEDULE")
+            .unwrap_or_else(|_| "0 30 * * * *".to_string()); // Default: hourly at :30
+
+        tracing::info!("Scheduling DEX TWAP pricing job: schedule='{}'", dex_twap_schedule);
+
+        let db_clone = db.clone();
+        let job = Job::new_async(dex_twap_schedule.as_str(), move |_job_id, _scheduler| {
+            let db = db_clone.clone();
+            Box::pin(async move {
+                if maintenance::is_enabled() {
+                    tracing::info!("Maintenance mode is on; skipping scheduled DEX TWAP pricing job");
+                    return;
+                }
+                tracing::info!("Running scheduled DEX TWAP pricing job");
+                if let Err(e) = jobs::job_runs::record_job_started(&db, jobs::job_runs::JOB_DEX_TWAP_PRICES).await {
+                    tracing::warn!("Failed to record DEX TWAP pricing job start: {}", e);
+                }
+                let run_error = match jobs::dex_twap_prices::collect_dex_twap_prices(&db).await {
+                    Ok(result) => {
+                        tracing::info!(
+                            "DEX TWAP pricing job completed: {} prices stored, {} contracts checked",
+                            result.prices_stored,
+                            result.contracts_checked
+                        );
+                        None
+                    }
+                    Err(e) => {
+                        tracing::error!("DEX TWAP pricing job failed with error: {}", e);
+                        Some(e.to_string())
+                    }
+                };
+                if let Err(e) =
+                    jobs::job_runs::record_job_finished(&db, jobs::job_runs::JOB_DEX_TWAP_PRICES, run_error).await
+                {
+                    tracing::warn!("Failed to record DEX TWAP pricing job result: {}", e);
+                }
+            })
+        })
+        .expect("Failed to create DEX TWAP pricing job");
+
+        scheduler.add(job).await.expect("Failed to add DEX TWAP pricing job to scheduler");
+        tracing::info!("DEX TWAP pricing job scheduled successfully");
+    } else {
+        tracing::info!("DEX TWAP pricing job is disabled");
+    }
+
+    // Configure hourly/daily price rollups
+    let price_rollup_enabled = std::env::var("PRICE_ROLLUP_ENABLED")
+        .unwrap_or_else(|_| "true".to_string())
+        .parse::<bool>()
+        .unwrap_or(true);
+
+    if price_rollup_enabled {
+        let price_rollup_schedule = std::env::var("PRICE_ROLLUP_SCHEDULE")
+            .unwrap_or_else(|_| "0 5 * * * *".to_string()); // Default: hourly at :05
+
+        tracing::info!("Scheduling price rollup job: schedule='{}'", price_rollup_schedule);
+
+        let db_clone = db.clone();
+        let job = Job::new_async(price_rollup_schedule.as_str(), move |_job_id, _scheduler| {
+            let db = db_clone.clone();
+            Box::pin(async move {
+                if maintenance::is_enabled() {
+                    tracing::info!("Maintenance mode is on; skipping scheduled price rollup job");
+                    return;
+                }
+                tracing::info!("Running scheduled price rollup job");
+                if let Err(e) = jobs::job_runs::record_job_started(&db, jobs::job_runs::JOB_PRICE_ROLLUP).await {
+                    tracing::warn!("Failed to record price rollup job start: {}", e);
+                }
+                let run_error = match jobs::price_rollup::rollup_prices(&db).await {
+                    Ok(result) => {
+                        tracing::info!("Price rollup job completed: {} days rolled up", result.days);
+                        None
+                    }
+                    Err(e) => {
+                        tracing::error!("Price rollup job failed with error: {}", e);
+                        Some(e.to_string())
+                    }
+                };
+                if let Err(e) =
+                    jobs::job_runs::record_job_finished(&db, jobs::job_runs::JOB_PRICE_ROLLUP, run_error).await
+                {
+                    tracing::warn!("Failed to record price rollup job result: {}", e);
+                }
+            })
+        })
+        .expect("Failed to create price rollup job");
+
+        scheduler.add(job).await.expect("Failed to add price rollup job to scheduler");
+        tracing::info!("Price rollup job scheduled successfully");
+    } else {
+        tracing::info!("Price rollup job is disabled");
+    }
+
+    // Configure daily tax lot rebuild
+    let tax_lots_enabled = std::env::var("TAX_LOTS_ENABLED")
+        .unwrap_or_else(|_| "true".to_string())
+        .parse::<bool>()
+        .unwrap_or(true);
+
+    if tax_lots_enabled {
+        let tax_lots_schedule = std::env::var("TAX_LOTS_SCHEDULE")
+            .unwrap_or_else(|_| "0 15 0 * * *".to_string()); // Default: daily at 00:15 UTC
+
+        tracing::info!("Scheduling tax lot job: schedule='{}'", tax_lots_schedule);
+
+        let db_clone = db.clone();
+        let job = Job::new_async(tax_lots_schedule.as_str(), move |_job_id, _scheduler| {
+            let db = db_clone.clone();
+            Box::pin(async move {
+                if maintenance::is_enabled() {
+                    tracing::info!("Maintenance mode is on; skipping scheduled tax lot job");
+                    return;
+                }
+                tracing::info!("Running scheduled tax lot job");
+                if let Err(e) = jobs::job_runs::record_job_started(&db, jobs::job_runs::JOB_TAX_LOTS).await {
+                    tracing::warn!("Failed to record tax lot job start: {}", e);
+                }
+                let run_error = match jobs::tax_lots::rebuild_all_lots(&db).await {
+                    Ok(result) => {
+                        tracing::info!(
+                            "Tax lot job completed: {} accounts, {} lots, {} disposals",
+                            result.accounts,
+                            result.lots,
+                            result.disposals
+                        );
+                        None
+                    }
+                    Err(e) => {
+                        tracing::error!("Tax lot job failed with error: {}", e);
+                        Some(e.to_string())
+                    }
+                };
+                if let Err(e) = jobs::job_runs::record_job_finished(&db, jobs::job_runs::JOB_TAX_LOTS, run_error).await {
+                    tracing::warn!("Failed to record tax lot job result: {}", e);
+                }
+            })
+        })
+        .expect("Failed to create tax lot job");
+
+        scheduler.add(job).await.expect("Failed to add tax lot job to scheduler");
+        tracing::info!("Tax lot job scheduled successfully");
+    } else {
+        tracing::info!("Tax lot job is disabled");
+    }
+
+    // Configure daily portfolio P&L computation
+    let portfolio_pnl_enabled = std::env::var("PORTFOLIO_PNL_ENABLED")
+        .unwrap_or_else(|_| "true".to_string())
+        .parse::<bool>()
+        .unwrap_or(true);
+
+    if portfolio_pnl_enabled {
+        let portfolio_pnl_schedule = std::env::var("PORTFOLIO_PNL_SCHEDULE")
+            .unwrap_or_else(|_| "0 30 0 * * *".to_string()); // Default: daily at 00:30 UTC
+
+        tracing::info!("Scheduling portfolio P&L job: schedule='{}'", portfolio_pnl_schedule);
+
+        let db_clone = db.clone();
+        let job = Job::new_async(portfolio_pnl_schedule.as_str(), move |_job_id, _scheduler| {
+            let db = db_clone.clone();
+            Box::pin(async move {
+                if maintenance::is_enabled() {
+                    tracing::info!("Maintenance mode is on; skipping scheduled portfolio P&L job");
+                    return;
+                }
+                tracing::info!("Running scheduled portfolio P&L job");
+                if let Err(e) = jobs::job_runs::record_job_started(&db, jobs::job_runs::JOB_PORTFOLIO_PNL).await {
+                    tracing::warn!("Failed to record portfolio P&L job start: {}", e);
+                }
+                let run_error = match jobs::portfolio_pnl::compute_all_portfolio_pnl(&db).await {
+                    Ok(computed) => {
+                        tracing::info!("Portfolio P&L job completed: {} portfolios", computed);
+                        None
+                    }
+                    Err(e) => {
+                        tracing::error!("Portfolio P&L job failed with error: {}", e);
+                        Some(e.to_string())
+                    }
+                };
+                if let Err(e) =
+                    jobs::job_runs::record_job_finished(&db, jobs::job_runs::JOB_PORTFOLIO_PNL, run_error).await
+                {
+                    tracing::warn!("Failed to record portfolio P&L job result: {}", e);
+                }
+            })
+        })
+        .expect("Failed to create portfolio P&L job");
+
+        scheduler.add(job).await.expect("Failed to add portfolio P&L job to scheduler");
+        tracing::info!("Portfolio P&L job scheduled successfully");
+    } else {
+        tracing::info!("Portfolio P&L job is disabled");
+    }
+
+    // Configure daily portfolio performance computation, after the EOD snapshot
+    let portfolio_performance_enabled = std::env::var("PORTFOLIO_PERFORMANCE_ENABLED")
+        .unwrap_or_else(|_| "true".to_string())
+        .parse::<bool>()
+        .unwrap_or(true);
+
+    if portfolio_performance_enabled {
+        let portfolio_performance_schedule = std::env::var("PORTFOLIO_PERFORMANCE_SCHEDULE")
+            .unwrap_or_else(|_| "0 30 23 * * *".to_string()); // Default: daily at 23:30 UTC
+
+        tracing::info!("Scheduling portfolio performance job: schedule='{}'", portfolio_performance_schedule);
+
+        let db_clone = db.clone();
+        let job = Job::new_async(portfolio_performance_schedule.as_str(), move |_job_id, _scheduler| {
+            let db = db_clone.clone();
+            Box::pin(async move {
+                if maintenance::is_enabled() {
+                    tracing::info!("Maintenance mode is on; skipping scheduled portfolio performance job");
+                    return;
+                }
+                tracing::info!("Running scheduled portfolio performance job");
+                if let Err(e) =
+                    jobs::job_runs::record_job_started(&db, jobs::job_runs::JOB_PORTFOLIO_PERFORMANCE).await
+                {
+                    tracing::warn!("Failed to record portfolio performance job start: {}", e);
+                }
+                let run_error = match jobs::portfolio_performance::compute_all_portfolio_performance(&db).await {
+                    Ok(computed) => {
+                        tracing::info!("Portfolio performance job completed: {} portfolios", computed);
+                        None
+                    }
+                    Err(e) => {
+                        tracing::error!("Portfolio performance job failed with error: {}", e);
+                        Some(e.to_string())
+                    }
+                };
+                if let Err(e) =
+                    jobs::job_runs::record_job_finished(&db, jobs::job_runs::JOB_PORTFOLIO_PERFORMANCE, run_error).await
+                {
+                    tracing::warn!("Failed to record portfolio performance job result: {}", e);
+                }
+            })
+        })
+        .expect("Failed to create portfolio performance job");
+
+        scheduler.add(job).await.expect("Failed to add portfolio performance job to scheduler");
+        tracing::info!("Portfolio performance job scheduled successfully");
+    } else {
+        tracing::info!("Portfolio performance job is disabled");
+    }
+
+    // Configure daily recommendation engine run over every portfolio
+    let recommendations_enabled = std::env::var("RECOMMENDATIONS_ENABLED")
+        .unwrap_or_else(|_| "true".to_string())
+        .parse::<bool>()
+        .unwrap_or(true);
+
+    if recommendations_enabled {
+        let recommendations_schedule = std::env::var("RECOMMENDATIONS_SCHEDULE")
+            .unwrap_or_else(|_| "0 0 1 * * *".to_string()); // Default: daily at 01:00 UTC
+
+        tracing::info!("Scheduling recommendation engine job: schedule='{}'", recommendations_schedule);
+
+        let db_clone = db.clone();
+        let job = Job::new_async(recommendations_schedule.as_str(), move |_job_id, _scheduler| {
+            let db = db_clone.clone();
+            Box::pin(async move {
+                if maintenance::is_enabled() {
+                    tracing::info!("Maintenance mode is on; skipping scheduled recommendation engine job");
+                    return;
+                }
+                tracing::info!("Running scheduled recommendation engine job");
+                if let Err(e) = jobs::job_runs::record_job_started(&db, jobs::job_runs::JOB_RECOMMENDATIONS).await {
+                    tracing::warn!("Failed to record recommendation engine job start: {}", e);
+                }
+                let run_error = match jobs::recommendations::generate_all_recommendations(&db).await {
+                    Ok(created) => {
+                        tracing::info!("Recommendation engine job completed: {} recommendations", created);
+                        None
+                    }
+                    Err(e) => {
+                        tracing::error!("Recommendation engine job failed with error: {}", e);
+                        Some(e.to_string())
+                    }
+                };
+                if let Err(e) =
+                    jobs::job_runs::record_job_finished(&db, jobs::job_runs::JOB_RECOMMENDATIONS, run_error).await
+                {
+                    tracing::warn!("Failed to record recommendation engine job result: {}", e);
+                }
+            })
+        })
+        .expect("Failed to create recommendation engine job");
+
+        scheduler.add(job).await.expect("Failed to add recommendation engine job to scheduler");
+        tracing::info!("Recommendation engine job scheduled successfully");
+    } else {
+        tracing::info!("Recommendation engine job is disabled");
+    }
+
     // Start the scheduler
     scheduler.start().await.expect("Failed to start job scheduler");
     tracing::info!("Job scheduler started");
 
+    // Stream live prices of held assets in the background
+    if live_prices::is_enabled() {
+        tokio::spawn(live_prices::run(db.clone()));
+        tracing::info!("Live price stream started");
+    } else {
+        tracing::info!("Live price stream is disabled");
+    }
+
     // Keycloak configuration from environment variables
     let server_url = std::env::var("KEYCLOAK_SERVER")
         .unwrap_or_else(|_| "https://keycloak.example.com".to_string());
@@ -477,12 +1297,55 @@ async fn main() {
         .merge(handlers::portfolios::create_router())
         // Account sync API routes (protected)
         .merge(handlers::accounts::create_router())
+        // Additional wallet addresses of accounts (protected)
+        .merge(handlers::account_addresses::create_router())
+        // Daily account snapshots (protected)
+        .merge(handlers::account_snapshots::create_router())
         // Snapshot API routes (protected)
         .merge(handlers::snapshots::create_router())
+        .merge(handlers::income::create_router())
+        // Portfolio performance (protected)
+        .merge(handlers::performance::create_router())
+        // Portfolio cost basis and P&L (protected)
+        .merge(handlers::pnl::create_router())
+        // Tax lots of accounts (protected)
+        .merge(handlers::tax_lots::create_router())
+        // Tax report export (protected)
+        .merge(handlers::reports::create_router())
+        // Portfolio risk metrics (protected)
+        .merge(handlers::risk::create_router())
+        // Portfolio drift from target allocation (protected)
+        .merge(handlers::drift::create_router())
+        // Rebalance plans towards the target allocation (protected)
+        .merge(handlers::rebalance::create_router())
+        // Backtests of target allocations (protected)
+        .merge(handlers::backtest::create_router())
+        // Purchases report: invested amount and average entry price (protected)
+        .merge(handlers::purchases::create_router())
+        // Trading, withdrawal and gas fees per period (protected)
+        .merge(handlers::fees::create_router())
+        // Portfolio value time series from snapshots (protected)
+        .merge(handlers::value_history::create_router())
+        // Per-asset return attribution (protected)
+        .merge(handlers::attribution::create_router())
         // Recommendation API routes (protected)
         .merge(handlers::recommendations::create_router())
         // Migration API routes (protected)
         .merge(handlers::migrations::create_router())
+        // Holdings anomaly API routes (protected)
+        .merge(handlers::anomalies::create_router())
+        // Notification API routes (protected)
+        .merge(handlers::notifications::create_router())
+        // Holdings search API routes (protected)
+        .merge(handlers::holdings::create_router())
+        // Asset price history API routes (protected)
+        .merge(handlers::asset_prices::create_router())
+        // Live price API routes (protected)
+        .merge(handlers::prices::create_router())
+        // User preference API routes (protected)
+        .merge(handlers::preferences::create_router())
+        // Reject writes while in maintenance mode (runs after authentication)
+        .layer(middleware::from_fn(maintenance::maintenance_guard))
         .layer(auth_layer);
 
     // Build admin-only routes — require the "administrator" Keycloak realm role
@@ -493,8 +1356,28 @@ async fn main() {
         .merge(handlers::evm_tokens::create_router())
         // EVM chain registry API routes (admin only)
         .merge(handlers::evm_chains::create_router())
+        // Cosmos chain registry API routes (admin only)
+        .merge(handlers::cosmos_chains::create_router())
         // Solana token registry API routes (admin only)
         .merge(handlers::solana_tokens::create_router())
+        // Spam token blocklist API routes (admin only)
+        .merge(handlers::spam_tokens::create_router())
+        // Curve pool registry API routes (admin only)
+        .merge(handlers::curve_pools::create_router())
+        // Yield vault registry API routes (admin only)
+        .merge(handlers::yield_vaults::create_router())
+        // Derivative asset mapping API routes (admin only)
+        .merge(handlers::derivative_assets::create_router())
+        // Asset category tagging API routes (admin only)
+        .merge(handlers::asset_categories::create_router())
+        // Per-asset price source override API routes (admin only)
+        .merge(handlers::asset_price_overrides::create_router())
+        // Quarantined price review API routes (admin only)
+        .merge(handlers::rejected_prices::create_router())
+        // Maintenance mode toggle (admin only)
+        .merge(handlers::maintenance::create_router())
+        // Account ownership transfer (admin only)
+        .merge(handlers::account_transfers::create_router())
         .layer(admin_auth_layer);
 
     // Build application with public and protected routes
@@ -508,6 +1391,10 @@ async fn main() {
         .route("/health", get(health))
         // Chains API routes (public)
         .merge(handlers::chains::create_router())
+        // Unit conversion API routes (public)
+        .merge(handlers::units::create_router())
+        // Status page (public)
+        .merge(handlers::status::create_router())
         // Merge protected routes
         .merge(protected_routes)
         // Merge admin-only routes
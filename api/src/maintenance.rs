@@ -0,0 +1,179 @@
+//! Maintenance mode for safe migrations and backfills on live deployments
+//!
+//! While maintenance mode is on:
+//!
+//! - Mutating requests (anything other than GET/HEAD/OPTIONS) on user routes are
+//!   rejected with `503 Service Unavailable` and a `Retry-After` header
+//! - Read requests keep working
+//! - Scheduled jobs skip their runs
+//!
+//! Admin routes are not guarded so operators can still run migrations, backfills
+//! and turn maintenance mode off again.
+//!
+//! # Configuration
+//!
+//! The initial state is read from the environment at startup and can be changed at
+//! runtime through the admin maintenance endpoint:
+//!
+//! - `MAINTENANCE_MODE` - start in maintenance mode (default: false)
+//! - `MAINTENANCE_RETRY_AFTER_SECONDS` - `Retry-After` value in seconds (default: 300)
+//! - `MAINTENANCE_MESSAGE` - optional message returned with 503 responses
+//!
+//! The state is held in process memory, so each API instance is toggled separately.
+
+use axum::{
+    extract::Request,
+    http::{header, HeaderValue, Method, StatusCode},
+    middleware::Next,
+    response::{IntoResponse, Json, Response},
+};
+use chrono::{DateTime, Utc};
+use std::sync::{LazyLock, RwLock};
+
+use crate::handlers::error::ErrorResponse;
+
+/// Default `Retry-After` value (seconds) for 503 responses
+pub const DEFAULT_RETRY_AFTER_SECONDS: u64 = 300;
+
+/// Mutating endpoints that stay available in maintenance mode
+const EXEMPT_PATHS: [&str; 1] = ["/api/v1/migrations/migrate"];
+
+/// Current maintenance mode settings
+#[derive(Debug, Clone, PartialEq)]
+pub struct MaintenanceState {
+    pub enabled: bool,
+    /// Message returned with 503 responses
+    pub message: Option<String>,
+    /// Value of the `Retry-After` header in seconds
+    pub retry_after_seconds: u64,
+    /// When maintenance mode was last turned on
+    pub since: Option<DateTime<Utc>>,
+}
+
+impl MaintenanceState {
+    /// Load the initial state from environment variables
+    fn from_env() -> Self {
+        let enabled = std::env::var("MAINTENANCE_MODE")
+            .unwrap_or_else(|_| "false".to_string())
+            .parse::<bool>()
+            .unwrap_or(false);
+
+        let retry_after_seconds = std::env::var("MAINTENANCE_RETRY_AFTER_SECONDS")
+            .ok()
+            .and_then(|v| v.parse::<u64>().ok())
+            .unwrap_or(DEFAULT_RETRY_AFTER_SECONDS);
+
+        let message = std::env::var("MAINTENANCE_MESSAGE")
+            .ok()
+            .filter(|m| !m.trim().is_empty());
+
+        Self {
+            enabled,
+            message,
+            retry_after_seconds,
+            since: enabled.then(Utc::now),
+        }
+    }
+}
+
+static STATE: LazyLock<RwLock<MaintenanceState>> =
+    LazyLock::new(|| RwLock::new(MaintenanceState::from_env()));
+
+/// Get a copy of the current maintenance state
+pub fn current() -> MaintenanceState {
+    STATE.read().unwrap_or_else(|e| e.into_inner()).clone()
+}
+
+/// Whether maintenance mode is currently on
+pub fn is_enabled() -> bool {
+    current().enabled
+}
+
+/// Turn maintenance mode on or off.
+///
+/// `retry_after_seconds` keeps the current value when `None`. Returns the new state.
+pub fn set(enabled: bool, message: Option<String>, retry_after_seconds: Option<u64>) -> MaintenanceState {
+    let mut state = STATE.write().unwrap_or_else(|e| e.into_inner());
+
+    if enabled && !state.enabled {
+        state.since = Some(Utc::now());
+    } else if !enabled {
+        state.since = None;
+    }
+    state.enabled = enabled;
+    state.message = message.filter(|m| !m.trim().is_empty());
+    if let Some(seconds) = retry_after_seconds {
+        state.retry_after_seconds = seconds;
+    }
+
+    tracing::warn!("Maintenance mode {}", if enabled { "enabled" } else { "disabled" });
+    state.clone()
+}
+
+/// Whether a request is rejected under `state`
+fn is_blocked(state: &MaintenanceState, method: &Method, path: &str) -> bool {
+    let is_read = matches!(*method, Method::GET | Method::HEAD | Method::OPTIONS);
+    state.enabled && !is_read && !EXEMPT_PATHS.contains(&path)
+}
+
+/// Build the 503 response for a rejected request
+fn unavailable_response(state: &MaintenanceState) -> Response {
+    let error = state
+        .message
+        .clone()
+        .unwrap_or_else(|| "Service is under maintenance; write operations are temporarily disabled".to_string());
+
+    let mut response = (StatusCode::SERVICE_UNAVAILABLE, Json(ErrorResponse { error })).into_response();
+    response
+        .headers_mut()
+        .insert(header::RETRY_AFTER, HeaderValue::from(state.retry_after_seconds));
+    response
+}
+
+/// Middleware rejecting mutating requests while maintenance mode is on
+pub async fn maintenance_guard(request: Request, next: Next) -> Response {
+    let state = current();
+    if is_blocked(&state, request.method(), request.uri().path()) {
+        tracing::debug!(
+            "Rejected {} {} (maintenance mode)",
+            request.method(),
+            request.uri().path()
+        );
+        return unavailable_response(&state);
+    }
+
+    next.run(request).await
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn state(enabled: bool) -> MaintenanceState {
+        MaintenanceState {
+            enabled,
+            message: None,
+            retry_after_seconds: 120,
+            since: None,
+        }
+    }
+
+    #[test]
+    fn test_only_mutating_requests_are_blocked() {
+        let on = state(true);
+        assert!(is_blocked(&on, &Method::POST, "/api/v1/portfolios"));
+        assert!(is_blocked(&on, &Method::DELETE, "/api/v1/accounts/1"));
+        assert!(!is_blocked(&on, &Method::GET, "/api/v1/portfolios"));
+        assert!(!is_blocked(&on, &Method::POST, "/api/v1/migrations/migrate"));
+
+        assert!(!is_blocked(&state(false), &Method::POST, "/api/v1/portfolios"));
+    }
+
+    #[test]
+    fn test_unavailable_response_sets_retry_after() {
+        let response = unavailable_response(&state(true));
+
+        assert_eq!(response.status(), StatusCode::SERVICE_UNAVAILABLE);
+        assert_eq!(response.headers().get(header::RETRY_AFTER).unwrap(), "120");
+    }
+}
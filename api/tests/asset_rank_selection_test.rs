@@ -40,11 +40,14 @@ async fn test_normalize_from_symbol_prefers_better_rank() {
         name: ActiveValue::Set("Ethereum Carbon".to_string()),
         asset_type: ActiveValue::Set("cryptocurrency".to_string()),
         coinpaprika_id: ActiveValue::Set(Some("ethereum-carbon".to_string())),
+        coingecko_id: ActiveValue::NotSet,
         coinmarketcap_id: ActiveValue::NotSet,
         logo_url: ActiveValue::NotSet,
         description: ActiveValue::NotSet,
         decimals: ActiveValue::NotSet,
         is_active: ActiveValue::Set(true),
+        delisted_at: ActiveValue::Set(None),
+        category: ActiveValue::Set(None),
         created_at: ActiveValue::Set(timestamp.into()),
         updated_at: ActiveValue::Set(timestamp.into()),
     };
@@ -86,11 +89,14 @@ async fn test_normalize_from_symbol_prefers_better_rank() {
         name: ActiveValue::Set("Ethereum".to_string()),
         asset_type: ActiveValue::Set("cryptocurrency".to_string()),
         coinpaprika_id: ActiveValue::Set(Some("ethereum".to_string())),
+        coingecko_id: ActiveValue::NotSet,
         coinmarketcap_id: ActiveValue::NotSet,
         logo_url: ActiveValue::NotSet,
         description: ActiveValue::NotSet,
         decimals: ActiveValue::NotSet,
         is_active: ActiveValue::Set(true),
+        delisted_at: ActiveValue::Set(None),
+        category: ActiveValue::Set(None),
         created_at: ActiveValue::Set(timestamp.into()),
         updated_at: ActiveValue::Set(timestamp.into()),
     };
@@ -169,11 +175,14 @@ async fn test_normalize_from_okx_prefers_better_rank() {
         name: ActiveValue::Set("Bitcoin Fake".to_string()),
         asset_type: ActiveValue::Set("cryptocurrency".to_string()),
         coinpaprika_id: ActiveValue::Set(Some("bitcoin-fake".to_string())),
+        coingecko_id: ActiveValue::NotSet,
         coinmarketcap_id: ActiveValue::NotSet,
         logo_url: ActiveValue::NotSet,
         description: ActiveValue::NotSet,
         decimals: ActiveValue::NotSet,
         is_active: ActiveValue::Set(true),
+        delisted_at: ActiveValue::Set(None),
+        category: ActiveValue::Set(None),
         created_at: ActiveValue::Set(timestamp.into()),
         updated_at: ActiveValue::Set(timestamp.into()),
     };
@@ -211,11 +220,14 @@ async fn test_normalize_from_okx_prefers_better_rank() {
         name: ActiveValue::Set("Bitcoin".to_string()),
         asset_type: ActiveValue::Set("cryptocurrency".to_string()),
         coinpaprika_id: ActiveValue::Set(Some("bitcoin".to_string())),
+        coingecko_id: ActiveValue::NotSet,
         coinmarketcap_id: ActiveValue::NotSet,
         logo_url: ActiveValue::NotSet,
         description: ActiveValue::NotSet,
         decimals: ActiveValue::NotSet,
         is_active: ActiveValue::Set(true),
+        delisted_at: ActiveValue::Set(None),
+        category: ActiveValue::Set(None),
         created_at: ActiveValue::Set(timestamp.into()),
         updated_at: ActiveValue::Set(timestamp.into()),
     };
@@ -289,11 +301,14 @@ async fn test_normalize_fallback_without_rank_data() {
         name: ActiveValue::Set("No Rank Coin".to_string()),
         asset_type: ActiveValue::Set("cryptocurrency".to_string()),
         coinpaprika_id: ActiveValue::Set(Some("no-rank-coin".to_string())),
+        coingecko_id: ActiveValue::NotSet,
         coinmarketcap_id: ActiveValue::NotSet,
         logo_url: ActiveValue::NotSet,
         description: ActiveValue::NotSet,
         decimals: ActiveValue::NotSet,
         is_active: ActiveValue::Set(true),
+        delisted_at: ActiveValue::Set(None),
+        category: ActiveValue::Set(None),
         created_at: ActiveValue::Set(timestamp.into()),
         updated_at: ActiveValue::Set(timestamp.into()),
     };
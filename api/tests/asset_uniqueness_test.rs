@@ -38,11 +38,14 @@ async fn test_same_symbol_different_names() {
         name: ActiveValue::Set("Test Coin A".to_string()),
         asset_type: ActiveValue::Set("cryptocurrency".to_string()),
         coinpaprika_id: ActiveValue::Set(Some("test-coin-a".to_string())),
+        coingecko_id: ActiveValue::NotSet,
         coinmarketcap_id: ActiveValue::NotSet,
         logo_url: ActiveValue::NotSet,
         description: ActiveValue::NotSet,
         decimals: ActiveValue::NotSet,
         is_active: ActiveValue::Set(true),
+        delisted_at: ActiveValue::Set(None),
+        category: ActiveValue::Set(None),
         created_at: ActiveValue::Set(timestamp.into()),
         updated_at: ActiveValue::Set(timestamp.into()),
     };
@@ -58,11 +61,14 @@ async fn test_same_symbol_different_names() {
         name: ActiveValue::Set("Test Coin B".to_string()),
         asset_type: ActiveValue::Set("cryptocurrency".to_string()),
         coinpaprika_id: ActiveValue::Set(Some("test-coin-b".to_string())),
+        coingecko_id: ActiveValue::NotSet,
         coinmarketcap_id: ActiveValue::NotSet,
         logo_url: ActiveValue::NotSet,
         description: ActiveValue::NotSet,
         decimals: ActiveValue::NotSet,
         is_active: ActiveValue::Set(true),
+        delisted_at: ActiveValue::Set(None),
+        category: ActiveValue::Set(None),
         created_at: ActiveValue::Set(timestamp.into()),
         updated_at: ActiveValue::Set(timestamp.into()),
     };
@@ -106,11 +112,14 @@ async fn test_same_symbol_and_name_fails() {
         name: ActiveValue::Set("Unique Coin".to_string()),
         asset_type: ActiveValue::Set("cryptocurrency".to_string()),
         coinpaprika_id: ActiveValue::Set(Some("unique-coin-1".to_string())),
+        coingecko_id: ActiveValue::NotSet,
         coinmarketcap_id: ActiveValue::NotSet,
         logo_url: ActiveValue::NotSet,
         description: ActiveValue::NotSet,
         decimals: ActiveValue::NotSet,
         is_active: ActiveValue::Set(true),
+        delisted_at: ActiveValue::Set(None),
+        category: ActiveValue::Set(None),
         created_at: ActiveValue::Set(timestamp.into()),
         updated_at: ActiveValue::Set(timestamp.into()),
     };
@@ -126,11 +135,14 @@ async fn test_same_symbol_and_name_fails() {
         name: ActiveValue::Set("Unique Coin".to_string()),
         asset_type: ActiveValue::Set("cryptocurrency".to_string()),
         coinpaprika_id: ActiveValue::Set(Some("unique-coin-2".to_string())),
+        coingecko_id: ActiveValue::NotSet,
         coinmarketcap_id: ActiveValue::NotSet,
         logo_url: ActiveValue::NotSet,
         description: ActiveValue::NotSet,
         decimals: ActiveValue::NotSet,
         is_active: ActiveValue::Set(true),
+        delisted_at: ActiveValue::Set(None),
+        category: ActiveValue::Set(None),
         created_at: ActiveValue::Set(timestamp.into()),
         updated_at: ActiveValue::Set(timestamp.into()),
     };
@@ -174,11 +186,14 @@ async fn test_normalize_from_symbol_and_name() {
         name: ActiveValue::Set("Bitcoin".to_string()),
         asset_type: ActiveValue::Set("cryptocurrency".to_string()),
         coinpaprika_id: ActiveValue::Set(Some("bitcoin".to_string())),
+        coingecko_id: ActiveValue::NotSet,
         coinmarketcap_id: ActiveValue::NotSet,
         logo_url: ActiveValue::NotSet,
         description: ActiveValue::NotSet,
         decimals: ActiveValue::NotSet,
         is_active: ActiveValue::Set(true),
+        delisted_at: ActiveValue::Set(None),
+        category: ActiveValue::Set(None),
         created_at: ActiveValue::Set(timestamp.into()),
         updated_at: ActiveValue::Set(timestamp.into()),
     };
@@ -191,11 +206,14 @@ async fn test_normalize_from_symbol_and_name() {
         name: ActiveValue::Set("Bitcoin Wrapped".to_string()),
         asset_type: ActiveValue::Set("cryptocurrency".to_string()),
         coinpaprika_id: ActiveValue::Set(Some("bitcoin-wrapped".to_string())),
+        coingecko_id: ActiveValue::NotSet,
         coinmarketcap_id: ActiveValue::NotSet,
         logo_url: ActiveValue::NotSet,
         description: ActiveValue::NotSet,
         decimals: ActiveValue::NotSet,
         is_active: ActiveValue::Set(true),
+        delisted_at: ActiveValue::Set(None),
+        category: ActiveValue::Set(None),
         created_at: ActiveValue::Set(timestamp.into()),
         updated_at: ActiveValue::Set(timestamp.into()),
     };
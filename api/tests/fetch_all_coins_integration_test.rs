@@ -48,11 +48,14 @@ async fn test_batch_insert_with_duplicates() {
         name: ActiveValue::Set("Test Coin".to_string()),
         asset_type: ActiveValue::Set("cryptocurrency".to_string()),
         coinpaprika_id: ActiveValue::Set(Some("test-coin".to_string())),
+        coingecko_id: ActiveValue::NotSet,
         coinmarketcap_id: ActiveValue::NotSet,
         logo_url: ActiveValue::NotSet,
         description: ActiveValue::NotSet,
         decimals: ActiveValue::NotSet,
         is_active: ActiveValue::Set(true),
+        delisted_at: ActiveValue::Set(None),
+        category: ActiveValue::Set(None),
         created_at: ActiveValue::Set(timestamp.into()),
         updated_at: ActiveValue::Set(timestamp.into()),
     };
@@ -161,11 +164,14 @@ async fn test_batch_insert_without_duplicates() {
             name: ActiveValue::Set(format!("{} Coin", symbol)),
             asset_type: ActiveValue::Set("cryptocurrency".to_string()),
             coinpaprika_id: ActiveValue::Set(Some(format!("{}-coin", symbol.to_lowercase()))),
+            coingecko_id: ActiveValue::NotSet,
             coinmarketcap_id: ActiveValue::NotSet,
             logo_url: ActiveValue::NotSet,
             description: ActiveValue::NotSet,
             decimals: ActiveValue::NotSet,
             is_active: ActiveValue::Set(true),
+            delisted_at: ActiveValue::Set(None),
+            category: ActiveValue::Set(None),
             created_at: ActiveValue::Set(timestamp.into()),
             updated_at: ActiveValue::Set(timestamp.into()),
         };